@@ -0,0 +1,44 @@
+//! The fullness of the blocks of a phase of the benchmark, read from `System::BlockWeight`.
+use provotum_client_sdk::{rpc::get_block_weight_at, BlockNumber};
+use serde::Serialize;
+use substrate_subxt::{Client, Error, NodeTemplateRuntime};
+
+/// the fullness of a range of blocks in percent of the maximum block weight
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockFullness {
+    pub first_block: BlockNumber,
+    pub last_block: BlockNumber,
+    pub mean_percent: f64,
+    pub max_percent: f64,
+    /// the blocks which are at least 75% full (AvailableBlockRatio of the runtime)
+    pub full_blocks: u64,
+}
+
+/// reads the weight of every block from `first_block` to `last_block` (inclusive)
+pub async fn get_block_fullness(
+    client: &Client<NodeTemplateRuntime>,
+    first_block: BlockNumber,
+    last_block: BlockNumber,
+    max_block_weight: u64,
+) -> Result<BlockFullness, Error> {
+    let mut percents: Vec<f64> = Vec::new();
+    for block_number in first_block..=last_block {
+        let block_hash = client
+            .block_hash(Some(block_number.into()))
+            .await?
+            .ok_or("failed to fetch block hash!")?;
+        let weight = get_block_weight_at(client, Some(block_hash)).await?;
+        percents.push(weight.total() as f64 * 100.0 / max_block_weight as f64);
+    }
+    if percents.is_empty() {
+        return Ok(BlockFullness::default());
+    }
+    Ok(BlockFullness {
+        first_block,
+        last_block,
+        mean_percent: percents.iter().sum::<f64>() / percents.len() as f64,
+        max_percent: percents.iter().cloned().fold(0.0, f64::max),
+        full_blocks: percents.iter().filter(|percent| **percent >= 75.0).count() as u64,
+    })
+}
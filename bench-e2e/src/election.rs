@@ -0,0 +1,314 @@
+//! Drives a dev node through a single election of a given number of ciphers:
+//! 1. create the vote with a single question, the sealers submit their public key shares
+//! 2. encrypt the ciphers (on all cores) and import them in batches of `import_size`,
+//!    `in_flight` imports are submitted per round with consecutive nonces
+//! 3. start the tallying phase and wait until the offchain workers have shuffled the ciphers
+//! 4. submit the partial decryptions of the sealers and tally the question batch by batch
+//! 5. compare the tally with the imported votes
+use crate::blocks::get_block_fullness;
+use crate::report::{ElectionReport, LatencyStats};
+use crate::sealers::{Sealer, SEALERS};
+use async_std::{future::timeout, task};
+use crypto::{
+    encryption::ElGamal,
+    hash::HashFunction,
+    random::Random,
+    types::{ElGamalParams, PublicKey as ElGamalPK},
+};
+use futures::StreamExt;
+use num_bigint::BigUint;
+use pallet_mixnet::types::{
+    Cipher, NrOfShuffles, TallyStrategy, Topic, TopicId, TopicResult, VoteId, VotePhase,
+};
+use provotum_client_sdk::events::{subscribe_mixnet_events, MixnetEvent};
+use provotum_client_sdk::rpc::{
+    combine_decrypted_shares_batch, combine_pk_shares, finalize_tally, get_block_number,
+    get_cipher_count, get_ciphers, get_protocol_config, get_tally, get_tally_progress,
+    get_vote_public_key, propose_vote, set_vote_phase, submit_import_ciphers,
+};
+use provotum_client_sdk::Signer;
+use rayon::prelude::*;
+use sp_keyring::AccountKeyring;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use substrate_subxt::{system::AccountStoreExt, Client, Error, NodeTemplateRuntime, PairSigner};
+
+/// the source tag of the imported ciphers
+const SOURCE_TAG: &[u8] = b"bench-e2e";
+
+/// the interval between two polls of the imported ciphers (block time of the runtime)
+const POLL_INTERVAL: Duration = Duration::from_secs(6);
+
+pub struct BenchConfig {
+    pub params: ElGamalParams,
+    pub batch_size: u64,
+    pub nr_of_shuffles: NrOfShuffles,
+    pub import_size: usize,
+    pub in_flight: usize,
+    pub max_block_weight: u64,
+    /// the maximum duration of the import and the shuffling of an election
+    pub timeout: Duration,
+}
+
+/// runs a single election, the measurements are added to the report as soon as they are taken
+pub async fn run_election(
+    client: &Client<NodeTemplateRuntime>,
+    config: &BenchConfig,
+    nr_of_ciphers: u64,
+    report: &mut ElectionReport,
+) -> Result<(), Error> {
+    // the dev chain's voting authority creates, fills and tallies the vote
+    let voting_authority: Signer = PairSigner::new(AccountKeyring::Alice.pair());
+
+    // every election uses a new vote
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time went backwards!")
+        .as_millis();
+    let vote_id = format!("bench-{}-{}", nr_of_ciphers, timestamp)
+        .as_bytes()
+        .to_vec();
+    let topic_id = format!("bench-{}-{}-01", nr_of_ciphers, timestamp)
+        .as_bytes()
+        .to_vec();
+    let topic = Topic::new(topic_id.clone(), "Benchmark Question?".as_bytes().to_vec());
+    report.vote_id = String::from_utf8_lossy(&vote_id).to_string();
+    println!("election of {} ciphers: {}", nr_of_ciphers, report.vote_id);
+
+    // 1. create the vote and the public key of the vote -> VotePhase::Voting
+    propose_vote(
+        client,
+        &voting_authority,
+        config.params.clone().into(),
+        vote_id.clone(),
+        vote_id.clone(),
+        vec![topic],
+        config.batch_size,
+        config.nr_of_shuffles,
+        TallyStrategy::Mixnet,
+    )
+    .await?;
+    let hash_function: HashFunction = get_protocol_config(client, vote_id.clone())
+        .await?
+        .map(|config| config.hash_function.into())
+        .unwrap_or_default();
+    let sealers: Vec<Sealer> = SEALERS
+        .iter()
+        .map(|keyring| Sealer::new(*keyring, &config.params))
+        .collect();
+    for sealer in sealers.iter() {
+        sealer
+            .submit_keygen(client, vote_id.clone(), hash_function)
+            .await?;
+    }
+    combine_pk_shares(client, &voting_authority, vote_id.clone()).await?;
+    let pk: ElGamalPK = get_vote_public_key(client, vote_id.clone()).await?.into();
+
+    // 2. encrypt and import the ciphers
+    let first_block = get_block_number(client).await?;
+    let (encryption, import) =
+        import_ciphers(client, config, &vote_id, &topic_id, &pk, nr_of_ciphers).await?;
+    let last_block = get_block_number(client).await?;
+    report.encryption_ms = Some(encryption.as_millis() as u64);
+    report.import_ms = Some(import.as_millis() as u64);
+    report.import_throughput = Some(nr_of_ciphers as f64 / import.as_secs_f64().max(0.001));
+    report.import_blocks =
+        Some(get_block_fullness(client, first_block, last_block, config.max_block_weight).await?);
+    println!("imported {} ciphers in {:?}", nr_of_ciphers, import);
+
+    // 3. start the tallying phase, the offchain workers of the sealers shuffle the ciphers
+    let mut events = subscribe_mixnet_events(client).await?;
+    let first_block = get_block_number(client).await?;
+    set_vote_phase(
+        client,
+        &voting_authority,
+        vote_id.clone(),
+        VotePhase::Tallying,
+    )
+    .await?;
+    let start = Instant::now();
+    let mut latest = start;
+    let mut latencies: Vec<Duration> = Vec::new();
+    let shuffling = async {
+        loop {
+            match events.next().await {
+                Some(Ok(MixnetEvent::ShuffleBatchAccepted {
+                    vote_id: shuffled_vote_id,
+                    topic_id: shuffled_topic_id,
+                    ..
+                })) if shuffled_vote_id == vote_id && shuffled_topic_id == topic_id => {
+                    latencies.push(latest.elapsed());
+                    latest = Instant::now();
+                }
+                Some(Ok(MixnetEvent::ShuffleCompleted {
+                    vote_id: shuffled_vote_id,
+                    topic_id: shuffled_topic_id,
+                    nr_of_shuffles,
+                })) if shuffled_vote_id == vote_id && shuffled_topic_id == topic_id => {
+                    return Ok(nr_of_shuffles)
+                }
+                Some(Ok(_)) => continue,
+                Some(Err(err)) => return Err(err),
+                None => return Err(Error::from("the event subscription has been closed!")),
+            }
+        }
+    };
+    let nr_of_shuffles: Result<NrOfShuffles, Error> = timeout(config.timeout, shuffling)
+        .await
+        .unwrap_or_else(|_| Err("timed out waiting for the shuffling to complete!".into()));
+    // the latencies of an incomplete shuffling are reported as well
+    report.shuffle_batches = Some(LatencyStats::new(&latencies));
+    let nr_of_shuffles = nr_of_shuffles?;
+    report.shuffle_ms = Some(start.elapsed().as_millis() as u64);
+    let last_block = get_block_number(client).await?;
+    report.shuffle_blocks =
+        Some(get_block_fullness(client, first_block, last_block, config.max_block_weight).await?);
+    println!(
+        "shuffled {} ciphers in {:?}",
+        nr_of_ciphers,
+        start.elapsed()
+    );
+
+    // 4. decrypt and tally the question
+    let start = Instant::now();
+    for sealer in sealers.iter() {
+        let ciphers: Vec<Cipher> =
+            get_ciphers(client, vote_id.clone(), topic_id.clone(), nr_of_shuffles).await?;
+        sealer
+            .submit_decryption(
+                client,
+                vote_id.clone(),
+                topic_id.clone(),
+                ciphers,
+                nr_of_shuffles,
+                hash_function,
+            )
+            .await?;
+    }
+    report.decryption_ms = Some(start.elapsed().as_millis() as u64);
+
+    let first_block = get_block_number(client).await?;
+    let start = Instant::now();
+    loop {
+        combine_decrypted_shares_batch(
+            client,
+            &voting_authority,
+            vote_id.clone(),
+            topic_id.clone(),
+            true,
+            nr_of_shuffles,
+        )
+        .await?;
+        match get_tally_progress(client, vote_id.clone(), topic_id.clone()).await? {
+            Some(progress) if !progress.is_complete() => continue,
+            _ => break,
+        }
+    }
+    finalize_tally(client, &voting_authority, vote_id.clone(), topic_id.clone()).await?;
+    report.tally_ms = Some(start.elapsed().as_millis() as u64);
+    let last_block = get_block_number(client).await?;
+    report.tally_blocks =
+        Some(get_block_fullness(client, first_block, last_block, config.max_block_weight).await?);
+    println!("tallied {} ciphers in {:?}", nr_of_ciphers, start.elapsed());
+
+    // 5. every second cipher is a vote for 1, see: import_ciphers
+    let result: TopicResult = get_tally(client, vote_id, topic_id).await?;
+    let expected: TopicResult = vec![
+        (0u64, nr_of_ciphers - nr_of_ciphers / 2),
+        (1u64, nr_of_ciphers / 2),
+    ]
+    .into_iter()
+    .filter(|(_, count)| *count > 0)
+    .map(|(vote, count)| {
+        (
+            BigUint::from(vote).to_bytes_be(),
+            BigUint::from(count).to_bytes_be(),
+        )
+    })
+    .collect();
+    if result != expected {
+        return Err(format!(
+            "the tally doesn't match the imported votes, expected: {:?}, tallied: {:?}",
+            expected, result
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// encrypts and imports the ciphers, returns the duration of the encryption and the import
+async fn import_ciphers(
+    client: &Client<NodeTemplateRuntime>,
+    config: &BenchConfig,
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    pk: &ElGamalPK,
+    nr_of_ciphers: u64,
+) -> Result<(Duration, Duration), Error> {
+    let q = config.params.q();
+
+    // the imports are submitted with consecutive nonces without waiting for their inclusion
+    let mut signer: Signer = PairSigner::new(AccountKeyring::Alice.pair());
+    let account = AccountKeyring::Alice.to_account_id();
+    let mut nonce = client.account(&account, None).await?.nonce;
+    let mut encryption = Duration::default();
+    let mut import = Duration::default();
+    let mut imported: u64 = 0;
+
+    while imported < nr_of_ciphers {
+        // the ciphers of a round are encrypted before its imports are submitted
+        let round = (nr_of_ciphers - imported).min((config.import_size * config.in_flight) as u64);
+        let start = Instant::now();
+        let ciphers: Vec<Cipher> = (imported..imported + round)
+            .into_par_iter()
+            .map(|index| {
+                let r = Random::get_random_less_than(&q);
+                ElGamal::encrypt_encode(&BigUint::from(index % 2), &r, pk).into()
+            })
+            .collect();
+        encryption += start.elapsed();
+
+        let start = Instant::now();
+        for batch in ciphers.chunks(config.import_size) {
+            signer.set_nonce(nonce);
+            submit_import_ciphers(
+                client,
+                &signer,
+                vote_id.clone(),
+                topic_id.clone(),
+                batch.to_vec(),
+                SOURCE_TAG.to_vec(),
+            )
+            .await?;
+            nonce += 1;
+        }
+        imported += round;
+        wait_for_ciphers(client, vote_id, topic_id, imported, config.timeout).await?;
+        import += start.elapsed();
+    }
+    Ok((encryption, import))
+}
+
+/// polls the number of ciphers of the topic until all submitted imports are included
+async fn wait_for_ciphers(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    nr_of_ciphers: u64,
+    max_duration: Duration,
+) -> Result<(), Error> {
+    let start = Instant::now();
+    loop {
+        let count = get_cipher_count(client, vote_id.clone(), topic_id.clone(), 0).await?;
+        if count >= nr_of_ciphers {
+            return Ok(());
+        }
+        if start.elapsed() > max_duration {
+            return Err(format!(
+                "timed out waiting for the imports, {} of {} ciphers have been imported!",
+                count, nr_of_ciphers
+            )
+            .into());
+        }
+        task::sleep(POLL_INTERVAL).await;
+    }
+}
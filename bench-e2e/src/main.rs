@@ -0,0 +1,152 @@
+mod blocks;
+mod election;
+mod report;
+mod sealers;
+
+use async_std::task;
+use clap::Clap;
+use crypto::helper::Helper;
+use election::{run_election, BenchConfig};
+use mixnet_primitives::ErrorCode;
+use report::{ElectionReport, Report};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use substrate_subxt::{ClientBuilder, Error, NodeTemplateRuntime, RuntimeError};
+
+/// The name of the mixnet pallet in the runtime
+const MODULE: &str = "PalletMixnet";
+
+/// The number of ciphers of the elections if none are given
+const DEFAULT_SIZES: [usize; 3] = [10_000, 100_000, 1_000_000];
+
+/// Drives a dev node through elections of increasing size and reports the block fullness,
+/// the shuffle latency of the offchain workers and the duration of the tally
+#[derive(Clap, Debug)]
+#[clap(
+    name = "bench-e2e",
+    version = "1.0",
+    author = "Moritz Eck <moritz.eck@gmail.com>"
+)]
+pub struct Opts {
+    /// The websocket url of the node
+    #[clap(long, default_value = "ws://127.0.0.1:9944")]
+    pub url: String,
+    /// The number of ciphers of the elections (default: 10000, 100000 and 1000000)
+    #[clap(long)]
+    pub sizes: Vec<usize>,
+    /// The bit length of the modulus of the ElGamal keys
+    #[clap(long, default_value = "2048", possible_values = &["512", "1024", "2048", "3072"])]
+    pub key_size: u32,
+    /// The number of ciphers per shuffle batch of the votes
+    #[clap(long, default_value = "100")]
+    pub batch_size: u64,
+    /// The number of shuffles of the votes
+    #[clap(long, default_value = "3")]
+    pub nr_of_shuffles: u8,
+    /// The number of ciphers per import (at most MAX_CIPHERS_PER_IMPORT of the runtime)
+    #[clap(long, default_value = "1000")]
+    pub import_size: usize,
+    /// The number of imports submitted at once (with consecutive nonces)
+    #[clap(long, default_value = "10")]
+    pub in_flight: usize,
+    /// The maximum weight of a block (MaximumBlockWeight of the runtime)
+    #[clap(long, default_value = "4000000000000")]
+    pub max_block_weight: u64,
+    /// The maximum duration of the import and the shuffling of an election in seconds
+    #[clap(long, default_value = "86400")]
+    pub timeout: u64,
+    /// The label of the report, e.g. the release of the node
+    #[clap(long, default_value = "local")]
+    pub label: String,
+    /// The directory the reports are written to (<label>.json and <label>.md)
+    #[clap(long, default_value = "bench-results")]
+    pub output: String,
+}
+
+/// describes the error, errors of the mixnet pallet are described by their message and error code
+fn describe(error: &Error) -> String {
+    match error {
+        Error::Runtime(RuntimeError::Module(module_error)) if module_error.module == MODULE => {
+            match ErrorCode::from_name(&module_error.error) {
+                Some(code) => code.to_string(),
+                None => format!("{:?}", error),
+            }
+        }
+        _ => format!("{:?}", error),
+    }
+}
+
+async fn run(opts: Opts) -> Result<(), Error> {
+    if opts.import_size == 0 || opts.in_flight == 0 {
+        return Err(
+            "the import size and the number of imports in flight must be at least 1!".into(),
+        );
+    }
+    let (params, _, _) = match opts.key_size {
+        512 => Helper::setup_512bit_system(),
+        1024 => Helper::setup_md_system(),
+        3072 => Helper::setup_xl_system(),
+        _ => Helper::setup_lg_system(),
+    };
+    let config = BenchConfig {
+        params,
+        batch_size: opts.batch_size,
+        nr_of_shuffles: opts.nr_of_shuffles,
+        import_size: opts.import_size,
+        in_flight: opts.in_flight,
+        max_block_weight: opts.max_block_weight,
+        timeout: Duration::from_secs(opts.timeout),
+    };
+    let client = ClientBuilder::<NodeTemplateRuntime>::new()
+        .set_url(opts.url.clone())
+        .build()
+        .await?;
+
+    let sizes: Vec<usize> = match opts.sizes.is_empty() {
+        true => DEFAULT_SIZES.to_vec(),
+        false => opts.sizes.clone(),
+    };
+    let mut report = Report {
+        label: opts.label.clone(),
+        url: opts.url.clone(),
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time went backwards!")
+            .as_secs(),
+        key_size: opts.key_size,
+        batch_size: opts.batch_size,
+        nr_of_shuffles: opts.nr_of_shuffles,
+        import_size: opts.import_size,
+        max_block_weight: opts.max_block_weight,
+        elections: Vec::new(),
+    };
+
+    // a failed election is reported and the next one is started
+    for size in sizes.into_iter() {
+        let mut election = ElectionReport {
+            nr_of_ciphers: size as u64,
+            ..Default::default()
+        };
+        if let Err(err) = run_election(&client, &config, size as u64, &mut election).await {
+            println!(
+                "the election of {} ciphers failed: {}",
+                size,
+                describe(&err)
+            );
+            election.error = Some(describe(&err));
+        }
+        report.elections.push(election);
+
+        // the report is written after every election, a long run can be inspected while it runs
+        report.write(&opts.output)?;
+    }
+    println!("{}", report.to_markdown());
+    Ok(())
+}
+
+fn main() {
+    let opts: Opts = Opts::parse();
+    println!("End-to-End Benchmark. Connecting to {}...", opts.url);
+    if let Err(err) = task::block_on(run(opts)) {
+        println!("failed to run the benchmark: {}", describe(&err));
+    }
+}
@@ -0,0 +1,154 @@
+//! The report of a benchmark run, written as JSON (to compare releases) and as markdown.
+use crate::blocks::BlockFullness;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// the latencies of the shuffled batches in milliseconds, i.e., the time between two
+/// accepted batches (the first one is measured from the start of the tallying phase)
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyStats {
+    pub count: u64,
+    pub mean_ms: u64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub max_ms: u64,
+}
+
+impl LatencyStats {
+    pub fn new(latencies: &[Duration]) -> Self {
+        if latencies.is_empty() {
+            return LatencyStats::default();
+        }
+        let mut millis: Vec<u64> = latencies.iter().map(|d| d.as_millis() as u64).collect();
+        millis.sort_unstable();
+        let percentile = |p: usize| millis[(millis.len() - 1) * p / 100];
+        LatencyStats {
+            count: millis.len() as u64,
+            mean_ms: millis.iter().sum::<u64>() / millis.len() as u64,
+            p50_ms: percentile(50),
+            p95_ms: percentile(95),
+            max_ms: millis[millis.len() - 1],
+        }
+    }
+}
+
+/// the measurements of a single election, the measurements of the phases which have not been
+/// reached (see: `error`) are missing
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElectionReport {
+    pub nr_of_ciphers: u64,
+    pub vote_id: String,
+    pub encryption_ms: Option<u64>,
+    pub import_ms: Option<u64>,
+    /// the imported ciphers per second
+    pub import_throughput: Option<f64>,
+    pub import_blocks: Option<BlockFullness>,
+    pub shuffle_ms: Option<u64>,
+    pub shuffle_batches: Option<LatencyStats>,
+    pub shuffle_blocks: Option<BlockFullness>,
+    pub decryption_ms: Option<u64>,
+    pub tally_ms: Option<u64>,
+    pub tally_blocks: Option<BlockFullness>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    /// the release (or commit) the node has been built from, e.g. v1.2.0
+    pub label: String,
+    pub url: String,
+    /// seconds since the unix epoch
+    pub timestamp: u64,
+    pub key_size: u32,
+    pub batch_size: u64,
+    pub nr_of_shuffles: u8,
+    pub import_size: usize,
+    pub max_block_weight: u64,
+    pub elections: Vec<ElectionReport>,
+}
+
+impl Report {
+    /// writes the report to `<dir>/<label>.json` and `<dir>/<label>.md`
+    pub fn write(&self, dir: &str) -> Result<(), String> {
+        fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+        let json = serde_json::to_string_pretty(self).map_err(|error| error.to_string())?;
+        let path = Path::new(dir).join(format!("{}.json", self.label));
+        fs::write(&path, json).map_err(|error| error.to_string())?;
+        let path = Path::new(dir).join(format!("{}.md", self.label));
+        fs::write(&path, self.to_markdown()).map_err(|error| error.to_string())?;
+        Ok(())
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("# Benchmark: {}\n\n", self.label);
+        md.push_str(&format!(
+            "node: `{}`, key size: {} bit, batch size: {}, shuffles: {}, import size: {}\n\n",
+            self.url, self.key_size, self.batch_size, self.nr_of_shuffles, self.import_size
+        ));
+        md.push_str(
+            "| ciphers | encryption | import | ciphers/s | import blocks (mean / max) \
+             | shuffle | batch latency (p50 / p95) | shuffle blocks (mean / max) \
+             | decryption | tally | tally blocks (mean / max) |\n",
+        );
+        md.push_str("|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|\n");
+        for election in self.elections.iter() {
+            md.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+                election.nr_of_ciphers,
+                format_ms(election.encryption_ms),
+                format_ms(election.import_ms),
+                election
+                    .import_throughput
+                    .map(|throughput| format!("{:.1}", throughput))
+                    .unwrap_or_else(|| "-".to_string()),
+                format_blocks(&election.import_blocks),
+                format_ms(election.shuffle_ms),
+                election
+                    .shuffle_batches
+                    .as_ref()
+                    .map(|stats| format!("{} / {} ms", stats.p50_ms, stats.p95_ms))
+                    .unwrap_or_else(|| "-".to_string()),
+                format_blocks(&election.shuffle_blocks),
+                format_ms(election.decryption_ms),
+                format_ms(election.tally_ms),
+                format_blocks(&election.tally_blocks),
+            ));
+        }
+        let errors: Vec<&ElectionReport> = self
+            .elections
+            .iter()
+            .filter(|election| election.error.is_some())
+            .collect();
+        if !errors.is_empty() {
+            md.push_str("\n## Errors\n\n");
+            for election in errors {
+                md.push_str(&format!(
+                    "- {} ciphers (`{}`): {}\n",
+                    election.nr_of_ciphers,
+                    election.vote_id,
+                    election.error.as_deref().unwrap_or_default()
+                ));
+            }
+        }
+        md
+    }
+}
+
+fn format_ms(ms: Option<u64>) -> String {
+    match ms {
+        Some(ms) => format!("{:.1} s", ms as f64 / 1000.0),
+        None => "-".to_string(),
+    }
+}
+
+fn format_blocks(blocks: &Option<BlockFullness>) -> String {
+    match blocks {
+        Some(blocks) => format!("{:.1}% / {:.1}%", blocks.mean_percent, blocks.max_percent),
+        None => "-".to_string(),
+    }
+}
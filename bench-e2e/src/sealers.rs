@@ -0,0 +1,133 @@
+//! The sealers of the dev chain, their private key shares are generated for every election
+//! with the parameters (key size) of the benchmark, see: `provotum-cli sealer keygen`.
+use crypto::{
+    encryption::ElGamal,
+    hash::HashFunction,
+    helper::Helper,
+    proofs::{
+        decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
+        keygen::KeyGenerationProof,
+    },
+    random::Random,
+    secret,
+    types::{Cipher as BigCipher, ElGamalParams, PrivateKey, PublicKey as ElGamalPK},
+};
+use num_bigint::BigUint;
+use pallet_mixnet::types::{
+    Cipher, DecryptedShare, DecryptedShareProof, NrOfShuffles, PublicKeyShare, TopicId, VoteId,
+    Wrapper,
+};
+use provotum_client_sdk::{
+    rpc::{store_public_key_share, submit_partial_decryptions},
+    Signer,
+};
+use sp_keyring::AccountKeyring;
+use substrate_subxt::{Client, Error, NodeTemplateRuntime, PairSigner};
+
+/// the sealers of the dev chain
+pub const SEALERS: [AccountKeyring; 2] = [AccountKeyring::Bob, AccountKeyring::Charlie];
+
+pub struct Sealer {
+    signer: Signer,
+    sealer_id: [u8; 32],
+    sk: PrivateKey,
+    pk: ElGamalPK,
+}
+
+impl Sealer {
+    /// creates a new private key share of the sealer for the given parameters
+    pub fn new(keyring: AccountKeyring, params: &ElGamalParams) -> Self {
+        let mut x = Random::get_random_less_than(&params.q());
+        let (pk, sk) = Helper::generate_key_pair(params, &x);
+        secret::wipe(&mut x);
+        Sealer {
+            signer: PairSigner::new(keyring.pair()),
+            sealer_id: keyring.to_raw_public(),
+            sk,
+            pk,
+        }
+    }
+
+    /// submits the public key share + proof of the sealer
+    pub async fn submit_keygen(
+        &self,
+        client: &Client<NodeTemplateRuntime>,
+        vote_id: VoteId,
+        hash_function: HashFunction,
+    ) -> Result<(), Error> {
+        let params = &self.sk.params;
+        let mut r = Random::get_random_less_than(&params.q());
+        let proof = KeyGenerationProof::generate_with_hash(
+            params,
+            &self.sk.x,
+            &self.pk.h,
+            &r,
+            &self.sealer_id,
+            hash_function,
+        );
+        secret::wipe(&mut r);
+        let pk_share = PublicKeyShare {
+            proof: proof.into(),
+            pk: self.pk.h.to_bytes_be(),
+        };
+        store_public_key_share(client, &self.signer, vote_id, pk_share).await?;
+        Ok(())
+    }
+
+    /// submits the partial decryptions + proof of the shuffled ciphers
+    pub async fn submit_decryption(
+        &self,
+        client: &Client<NodeTemplateRuntime>,
+        vote_id: VoteId,
+        topic_id: TopicId,
+        ciphers: Vec<Cipher>,
+        nr_of_shuffles: NrOfShuffles,
+        hash_function: HashFunction,
+    ) -> Result<(), Error> {
+        let (shares, proof) = self.partial_decrypt(ciphers, hash_function);
+        submit_partial_decryptions(
+            client,
+            &self.signer,
+            vote_id,
+            topic_id,
+            shares,
+            proof,
+            nr_of_shuffles,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// the same computation as the `partial_decrypt` of the sealer-worker
+    fn partial_decrypt(
+        &self,
+        ciphers: Vec<Cipher>,
+        hash_function: HashFunction,
+    ) -> (Vec<DecryptedShare>, DecryptedShareProof) {
+        let params = &self.sk.params;
+        let encryptions: Vec<BigCipher> = Wrapper(ciphers).into();
+
+        // the ciphers are decrypted on all cores
+        let partial_decryptions: Vec<BigUint> =
+            ElGamal::partial_decrypt_many(&encryptions, &self.sk);
+        let shares: Vec<DecryptedShare> = partial_decryptions
+            .iter()
+            .map(|share| share.to_bytes_be())
+            .collect();
+
+        let mut r = Random::get_random_less_than(&params.q());
+        let proof = DecryptionProof::generate_batched_with_hash(
+            params,
+            &self.sk.x,
+            &self.pk.h,
+            &r,
+            &encryptions,
+            &partial_decryptions,
+            &self.sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+            hash_function,
+        );
+        secret::wipe(&mut r);
+        (shares, proof.into())
+    }
+}
@@ -0,0 +1,775 @@
+use codec::Encode;
+use pallet_mixnet::types::{
+    Ballot, Cipher, ClaimSignature, CredentialBlindingProof, DecryptedShare, DecryptedShareProof,
+    EligibilityKey, HashFunction, IdentityProvider, KeySwitchShareProof, LinkedShufflePayload,
+    NrOfShuffles, Nullifier, PublicKey as SubstratePK, PublicKeyShare, PublicParameters,
+    ShufflePayload, SpoiledBallotReveal, TallyStrategy, Title, Topic, TopicId, TopicPacking,
+    TopicResult, VoteId, VoteMetadata, VotePhase, VoteTemplate, VoteTemplateId,
+};
+use substrate_subxt::{sp_core::sr25519, system::System, Call, EventsDecoder, NodeTemplateRuntime};
+
+#[derive(Encode)]
+pub struct ProposeVote {
+    pub vote_id: VoteId,
+    pub title: Title,
+    pub params: PublicParameters,
+    pub topics: Vec<Topic>,
+    pub batch_size: u64,
+    pub nr_of_shuffles: NrOfShuffles,
+    pub tally_strategy: TallyStrategy,
+}
+
+impl Call<NodeTemplateRuntime> for ProposeVote {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "propose_vote";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<Title>("Title");
+        _decoder.register_type_size::<PublicParameters>("PublicParameters");
+        _decoder.register_type_size::<Vec<Topic>>("Vec<Topic>");
+        _decoder.register_type_size::<u64>("batch_size");
+        _decoder.register_type_size::<NrOfShuffles>("NrOfShuffles");
+        _decoder.register_type_size::<TallyStrategy>("TallyStrategy");
+    }
+}
+
+#[derive(Encode)]
+pub struct ApproveVote {
+    pub vote_id: VoteId,
+}
+
+impl Call<NodeTemplateRuntime> for ApproveVote {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "approve_vote";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+    }
+}
+
+#[derive(Encode)]
+pub struct StoreVoteTemplate {
+    pub template_id: VoteTemplateId,
+    pub template: VoteTemplate,
+}
+
+impl Call<NodeTemplateRuntime> for StoreVoteTemplate {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "store_vote_template";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteTemplateId>("VoteTemplateId");
+        _decoder.register_type_size::<VoteTemplate>("VoteTemplate");
+    }
+}
+
+#[derive(Encode)]
+pub struct CreateVoteFromTemplate {
+    pub template_id: VoteTemplateId,
+    pub vote_id: VoteId,
+    pub title: Title,
+}
+
+impl Call<NodeTemplateRuntime> for CreateVoteFromTemplate {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "create_vote_from_template";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteTemplateId>("VoteTemplateId");
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<Title>("Title");
+        _decoder.register_type_size::<PublicParameters>("PublicParameters");
+    }
+}
+
+#[derive(Encode)]
+pub struct StoreQuestion {
+    pub vote_id: VoteId,
+    pub topic: Topic,
+    pub batch_size: u64,
+}
+
+impl Call<NodeTemplateRuntime> for StoreQuestion {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "store_question";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<Topic>("Topic");
+        _decoder.register_type_size::<u64>("batch_size");
+    }
+}
+
+#[derive(Encode)]
+pub struct StorePublicKey {
+    pub vote_id: VoteId,
+    pub pk: SubstratePK,
+}
+
+impl Call<NodeTemplateRuntime> for StorePublicKey {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "store_public_key";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<SubstratePK>("SubstratePK");
+    }
+}
+
+#[derive(Encode)]
+pub struct StorePublicKeyShare {
+    pub vote_id: VoteId,
+    pub pk_share: PublicKeyShare,
+}
+
+impl Call<NodeTemplateRuntime> for StorePublicKeyShare {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "store_public_key_share";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<PublicKeyShare>("PublicKeyShare");
+    }
+}
+
+#[derive(Encode)]
+pub struct CombinePublicKeyShares {
+    pub vote_id: VoteId,
+}
+
+impl Call<NodeTemplateRuntime> for CombinePublicKeyShares {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "combine_public_key_shares";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<SubstratePK>("SubstratePK");
+    }
+}
+
+#[derive(Encode)]
+pub struct SetVotePhase {
+    pub vote_id: VoteId,
+    pub vote_phase: VotePhase,
+}
+
+impl Call<NodeTemplateRuntime> for SetVotePhase {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "set_vote_phase";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<VotePhase>("VotePhase");
+    }
+}
+
+#[derive(Encode)]
+pub struct CancelVote {
+    pub vote_id: VoteId,
+    pub reason: Vec<u8>,
+}
+
+impl Call<NodeTemplateRuntime> for CancelVote {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "cancel_vote";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+    }
+}
+
+#[derive(Encode)]
+pub struct CastBallot {
+    pub vote_id: VoteId,
+    pub ballot: Ballot,
+}
+
+impl Call<NodeTemplateRuntime> for CastBallot {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "cast_ballot";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<Ballot>("Ballot");
+    }
+}
+
+#[derive(Encode)]
+pub struct CastRelayedBallot {
+    pub vote_id: VoteId,
+    pub ballot: Ballot,
+    pub voter: sr25519::Public,
+    pub signature: sr25519::Signature,
+}
+
+impl Call<NodeTemplateRuntime> for CastRelayedBallot {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "cast_relayed_ballot";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<Ballot>("Ballot");
+    }
+}
+
+#[derive(Encode)]
+pub struct CastAnonymousBallot {
+    pub vote_id: VoteId,
+    pub ballot: Ballot,
+    pub key: sr25519::Public,
+    pub signature: Vec<u8>,
+    pub ballot_signature: sr25519::Signature,
+}
+
+impl Call<NodeTemplateRuntime> for CastAnonymousBallot {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "cast_anonymous_ballot";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<Nullifier>("Nullifier");
+        _decoder.register_type_size::<Ballot>("Ballot");
+    }
+}
+
+#[derive(Encode)]
+pub struct ImportCiphers {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+    pub ciphers: Vec<Cipher>,
+    pub source_tag: Vec<u8>,
+}
+
+impl Call<NodeTemplateRuntime> for ImportCiphers {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "import_ciphers";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<TopicId>("TopicId");
+        _decoder.register_type_size::<Vec<u8>>("Vec<u8>");
+        _decoder.register_type_size::<[u8; 32]>("[u8; 32]");
+    }
+}
+
+#[derive(Encode)]
+pub struct SubmitPartialDecryption {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+    pub shares: Vec<DecryptedShare>,
+    pub proof: DecryptedShareProof,
+    pub nr_of_shuffles: NrOfShuffles,
+}
+
+impl Call<NodeTemplateRuntime> for SubmitPartialDecryption {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "submit_decrypted_shares";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<TopicId>("TopicId");
+        _decoder.register_type_size::<Vec<DecryptedShare>>("Vec<DecryptedShare>");
+        _decoder.register_type_size::<DecryptedShareProof>("DecryptedShareProof");
+        _decoder.register_type_size::<NrOfShuffles>("NrOfShuffles");
+    }
+}
+
+#[derive(Encode)]
+pub struct CombineDecryptedShares {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+    pub encoded: bool,
+    pub nr_of_shuffles: NrOfShuffles,
+}
+
+impl Call<NodeTemplateRuntime> for CombineDecryptedShares {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "combine_decrypted_shares";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<TopicId>("TopicId");
+        _decoder.register_type_size::<bool>("bool");
+        _decoder.register_type_size::<NrOfShuffles>("NrOfShuffles");
+        _decoder.register_type_size::<TopicResult>("TopicResult");
+    }
+}
+
+#[derive(Encode)]
+pub struct CombineDecryptedSharesBatch {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+    pub encoded: bool,
+    pub nr_of_shuffles: NrOfShuffles,
+}
+
+impl Call<NodeTemplateRuntime> for CombineDecryptedSharesBatch {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "combine_decrypted_shares_batch";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<TopicId>("TopicId");
+        _decoder.register_type_size::<bool>("bool");
+        _decoder.register_type_size::<NrOfShuffles>("NrOfShuffles");
+        _decoder.register_type_size::<u64>("u64");
+    }
+}
+
+#[derive(Encode)]
+pub struct FinalizeTally {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+}
+
+impl Call<NodeTemplateRuntime> for FinalizeTally {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "finalize_tally";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<TopicId>("TopicId");
+        _decoder.register_type_size::<TopicResult>("TopicResult");
+    }
+}
+
+#[derive(Encode)]
+pub struct AggregateCiphers {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+}
+
+impl Call<NodeTemplateRuntime> for AggregateCiphers {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "aggregate_ciphers";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<TopicId>("TopicId");
+    }
+}
+
+#[derive(Encode)]
+pub struct RotatePublicKey {
+    pub vote_id: VoteId,
+}
+
+impl Call<NodeTemplateRuntime> for RotatePublicKey {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "rotate_public_key";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+    }
+}
+
+#[derive(Encode)]
+pub struct ArchiveVote {
+    pub vote_id: VoteId,
+}
+
+impl Call<NodeTemplateRuntime> for ArchiveVote {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "archive_vote";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<[u8; 32]>("[u8; 32]");
+    }
+}
+
+#[derive(Encode)]
+pub struct SubmitKeySwitchShares {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+    pub shares: Vec<Cipher>,
+    pub proof: KeySwitchShareProof,
+}
+
+impl Call<NodeTemplateRuntime> for SubmitKeySwitchShares {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "submit_key_switch_shares";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<TopicId>("TopicId");
+        _decoder.register_type_size::<Vec<Cipher>>("Vec<Cipher>");
+        _decoder.register_type_size::<KeySwitchShareProof>("KeySwitchShareProof");
+    }
+}
+
+#[derive(Encode)]
+pub struct RegisterCredential {
+    pub vote_id: VoteId,
+    pub voter: <NodeTemplateRuntime as System>::AccountId,
+    pub credential: Cipher,
+}
+
+impl Call<NodeTemplateRuntime> for RegisterCredential {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "register_credential";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<Cipher>("Cipher");
+    }
+}
+
+#[derive(Encode)]
+pub struct SubmitCredentialBlindings {
+    pub vote_id: VoteId,
+    pub blindings: Vec<Cipher>,
+    pub proofs: Vec<CredentialBlindingProof>,
+}
+
+impl Call<NodeTemplateRuntime> for SubmitCredentialBlindings {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "submit_credential_blindings";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<Vec<Cipher>>("Vec<Cipher>");
+        _decoder.register_type_size::<Vec<CredentialBlindingProof>>("Vec<CredentialBlindingProof>");
+    }
+}
+
+#[derive(Encode)]
+pub struct SubmitCredentialDecryptions {
+    pub vote_id: VoteId,
+    pub shares: Vec<DecryptedShare>,
+    pub proof: DecryptedShareProof,
+}
+
+impl Call<NodeTemplateRuntime> for SubmitCredentialDecryptions {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "submit_credential_decryptions";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<Vec<DecryptedShare>>("Vec<DecryptedShare>");
+        _decoder.register_type_size::<DecryptedShareProof>("DecryptedShareProof");
+    }
+}
+
+#[derive(Encode)]
+pub struct SetIdentityProvider {
+    pub vote_id: VoteId,
+    pub provider: Option<IdentityProvider>,
+}
+
+impl Call<NodeTemplateRuntime> for SetIdentityProvider {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "set_identity_provider";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<Option<IdentityProvider>>("Option<IdentityProvider>");
+    }
+}
+
+#[derive(Encode)]
+pub struct SetEligibilityKey {
+    pub vote_id: VoteId,
+    pub key: Option<EligibilityKey>,
+}
+
+impl Call<NodeTemplateRuntime> for SetEligibilityKey {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "set_eligibility_key";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<Option<EligibilityKey>>("Option<EligibilityKey>");
+    }
+}
+
+#[derive(Encode)]
+pub struct RegisterVoterWithClaim {
+    pub vote_id: VoteId,
+    pub voter: <NodeTemplateRuntime as System>::AccountId,
+    pub signature: ClaimSignature,
+}
+
+impl Call<NodeTemplateRuntime> for RegisterVoterWithClaim {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "register_voter_with_claim";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+    }
+}
+
+#[derive(Encode)]
+pub struct RevokeVoter {
+    pub vote_id: VoteId,
+    pub voter: <NodeTemplateRuntime as System>::AccountId,
+}
+
+impl Call<NodeTemplateRuntime> for RevokeVoter {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "revoke_voter";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+    }
+}
+
+#[derive(Encode)]
+pub struct SetMetadata {
+    pub vote_id: VoteId,
+    pub metadata: Option<VoteMetadata>,
+}
+
+impl Call<NodeTemplateRuntime> for SetMetadata {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "set_metadata";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+    }
+}
+
+#[derive(Encode)]
+pub struct SetShuffleBatchSize {
+    pub vote_id: VoteId,
+    pub batch_size: u64,
+}
+
+impl Call<NodeTemplateRuntime> for SetShuffleBatchSize {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "set_shuffle_batch_size";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<u64>("batch_size");
+    }
+}
+
+#[derive(Encode)]
+pub struct SetLinkedShuffle {
+    pub vote_id: VoteId,
+    pub linked: bool,
+}
+
+impl Call<NodeTemplateRuntime> for SetLinkedShuffle {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "set_linked_shuffle";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+    }
+}
+
+#[derive(Encode)]
+pub struct SetGroupCheck {
+    pub vote_id: VoteId,
+    pub enabled: bool,
+}
+
+impl Call<NodeTemplateRuntime> for SetGroupCheck {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "set_group_check";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+    }
+}
+
+#[derive(Encode)]
+pub struct SetTopicPacking {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+    pub packing: Option<TopicPacking>,
+}
+
+impl Call<NodeTemplateRuntime> for SetTopicPacking {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "set_topic_packing";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<TopicId>("TopicId");
+        _decoder.register_type_size::<Option<TopicPacking>>("Option<TopicPacking>");
+    }
+}
+
+#[derive(Encode)]
+pub struct SetDecryptionThreshold {
+    pub vote_id: VoteId,
+    pub threshold: u32,
+}
+
+impl Call<NodeTemplateRuntime> for SetDecryptionThreshold {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "set_decryption_threshold";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+    }
+}
+
+#[derive(Encode)]
+pub struct SetHashFunction {
+    pub vote_id: VoteId,
+    pub hash_function: HashFunction,
+}
+
+impl Call<NodeTemplateRuntime> for SetHashFunction {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "set_hash_function";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<HashFunction>("HashFunction");
+    }
+}
+
+#[derive(Encode)]
+pub struct SetVoterWeight {
+    pub vote_id: VoteId,
+    pub voter: <NodeTemplateRuntime as System>::AccountId,
+    pub weight: Option<u32>,
+}
+
+impl Call<NodeTemplateRuntime> for SetVoterWeight {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "set_voter_weight";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+    }
+}
+
+#[derive(Encode)]
+pub struct WhitelistVote {
+    pub vote_id: VoteId,
+    pub whitelisted: bool,
+}
+
+impl Call<NodeTemplateRuntime> for WhitelistVote {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "whitelist_vote";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+    }
+}
+
+#[derive(Encode)]
+pub struct ClaimBallotDeposit {
+    pub vote_id: VoteId,
+}
+
+impl Call<NodeTemplateRuntime> for ClaimBallotDeposit {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "claim_ballot_deposit";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<u128>("Balance");
+    }
+}
+
+#[derive(Encode)]
+pub struct SpoilBallot {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+}
+
+impl Call<NodeTemplateRuntime> for SpoilBallot {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "spoil_ballot";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<TopicId>("TopicId");
+        _decoder.register_type_size::<Cipher>("Cipher");
+    }
+}
+
+#[derive(Encode)]
+pub struct RevealSpoiledBallot {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+    pub cipher_hash: [u8; 32],
+    pub reveal: SpoiledBallotReveal,
+}
+
+impl Call<NodeTemplateRuntime> for RevealSpoiledBallot {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "reveal_spoiled_ballot";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<TopicId>("TopicId");
+        _decoder.register_type_size::<[u8; 32]>("[u8; 32]");
+    }
+}
+
+#[derive(Encode)]
+pub struct CommitShuffle {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+    pub commitment: [u8; 32],
+}
+
+impl Call<NodeTemplateRuntime> for CommitShuffle {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "commit_shuffle";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<TopicId>("TopicId");
+        _decoder.register_type_size::<[u8; 32]>("[u8; 32]");
+    }
+}
+
+#[derive(Encode)]
+pub struct SubmitShuffledVotesAndProof {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+    pub payload: ShufflePayload,
+}
+
+impl Call<NodeTemplateRuntime> for SubmitShuffledVotesAndProof {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "submit_shuffled_votes_and_proof";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<TopicId>("TopicId");
+        _decoder.register_type_size::<NrOfShuffles>("NrOfShuffles");
+    }
+}
+
+#[derive(Encode)]
+pub struct SubmitLinkedShuffle {
+    pub vote_id: VoteId,
+    pub payload: LinkedShufflePayload,
+}
+
+impl Call<NodeTemplateRuntime> for SubmitLinkedShuffle {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "submit_linked_shuffle";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<TopicId>("TopicId");
+        _decoder.register_type_size::<NrOfShuffles>("NrOfShuffles");
+    }
+}
+
+#[derive(Encode)]
+pub struct CertifyResult {
+    pub vote_id: VoteId,
+    pub result_hash: [u8; 32],
+}
+
+impl Call<NodeTemplateRuntime> for CertifyResult {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "certify_result";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<VoteId>("VoteId");
+        _decoder.register_type_size::<[u8; 32]>("[u8; 32]");
+    }
+}
+
+#[derive(Encode)]
+pub struct AddSealer {
+    pub sealer: <NodeTemplateRuntime as System>::AccountId,
+}
+
+impl Call<NodeTemplateRuntime> for AddSealer {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "add_sealer";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<u32>("u32");
+    }
+}
+
+#[derive(Encode)]
+pub struct RemoveSealer {
+    pub sealer: <NodeTemplateRuntime as System>::AccountId,
+}
+
+impl Call<NodeTemplateRuntime> for RemoveSealer {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "remove_sealer";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {
+        _decoder.register_type_size::<u32>("u32");
+    }
+}
+
+#[derive(Encode)]
+pub struct Heartbeat {}
+
+impl Call<NodeTemplateRuntime> for Heartbeat {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "heartbeat";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {}
+}
+
+#[derive(Encode)]
+pub struct DoNothingWhenItsNotYourTurn {}
+
+impl Call<NodeTemplateRuntime> for DoNothingWhenItsNotYourTurn {
+    const MODULE: &'static str = "PalletMixnet";
+    const FUNCTION: &'static str = "do_nothing_when_its_not_your_turn";
+    fn events_decoder(_decoder: &mut EventsDecoder<NodeTemplateRuntime>) {}
+}
@@ -0,0 +1,703 @@
+use codec::Decode;
+use futures::stream::{self, BoxStream, StreamExt};
+use pallet_mixnet::types::{
+    Ballot, Cipher, EligibilityKey, HashFunction, IdentityProvider, NrOfShuffles, Nullifier,
+    PublicKey as SubstratePK, PublicKeyShare, PublicParameters, Topic, TopicId, TopicPacking,
+    TopicResult, VoteId, VotePhase, VoteTemplateId,
+};
+use substrate_subxt::{
+    sp_core::H256, system::System, Client, Error, EventSubscription, NodeTemplateRuntime, RawEvent,
+};
+
+use super::rpc::{subscribe_events, subscribe_finalized_events};
+use crate::{Balance, BlockNumber};
+
+const MODULE: &str = "PalletMixnet";
+
+type AccountId = <NodeTemplateRuntime as System>::AccountId;
+
+/// The events of the mixnet pallet decoded into their typed representation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MixnetEvent {
+    BallotSubmitted {
+        who: AccountId,
+        vote_id: VoteId,
+        ballot: Ballot,
+    },
+    PublicKeyStored {
+        who: AccountId,
+        vote_id: VoteId,
+        pk: SubstratePK,
+    },
+    VoteCreated {
+        vote_id: VoteId,
+        who: AccountId,
+        params: PublicParameters,
+    },
+    VoteProposed {
+        vote_id: VoteId,
+        who: AccountId,
+    },
+    VoteApproved {
+        vote_id: VoteId,
+        who: AccountId,
+    },
+    VoteTemplateStored {
+        template_id: VoteTemplateId,
+        who: AccountId,
+    },
+    TallyingApproved {
+        vote_id: VoteId,
+        who: AccountId,
+    },
+    VoteTopicQuestionStored {
+        vote_id: VoteId,
+        topic: Topic,
+    },
+    VotePhaseChanged {
+        vote_id: VoteId,
+        previous: VotePhase,
+        phase: VotePhase,
+    },
+    PublicKeyShareSubmitted {
+        share: PublicKeyShare,
+    },
+    PublicKeyCreated {
+        vote_id: VoteId,
+        pk: SubstratePK,
+    },
+    DecryptedShareSubmitted {
+        topic_id: TopicId,
+        who: AccountId,
+    },
+    TopicTallied {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        result: TopicResult,
+    },
+    ShuffleProofSubmitted {
+        topic_id: TopicId,
+        who: AccountId,
+    },
+    ShuffleCommitted {
+        topic_id: TopicId,
+        who: AccountId,
+    },
+    CiphersAggregated {
+        vote_id: VoteId,
+        topic_id: TopicId,
+    },
+    ShuffleCompleted {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        nr_of_shuffles: NrOfShuffles,
+    },
+    ShuffleBatchAccepted {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        iteration: NrOfShuffles,
+        start_position: u64,
+        nr_of_ciphers: u64,
+    },
+    ShuffleIterationCompleted {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        nr_of_shuffles: NrOfShuffles,
+    },
+    DecryptionProgress {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        submitted: u32,
+        required: u32,
+    },
+    PublicKeyRotated {
+        vote_id: VoteId,
+    },
+    KeySwitchSharesSubmitted {
+        topic_id: TopicId,
+        who: AccountId,
+    },
+    CiphersKeySwitched {
+        vote_id: VoteId,
+        topic_id: TopicId,
+    },
+    VoteArchived {
+        vote_id: VoteId,
+        merkle_root: [u8; 32],
+    },
+    CredentialRegistered {
+        vote_id: VoteId,
+        voter: AccountId,
+    },
+    BallotSpoiled {
+        voter: AccountId,
+        vote_id: VoteId,
+        topic_id: TopicId,
+        cipher: Cipher,
+    },
+    SpoiledBallotRevealed {
+        voter: AccountId,
+        topic_id: TopicId,
+        cipher_hash: [u8; 32],
+    },
+    MetadataUpdated {
+        vote_id: VoteId,
+        who: AccountId,
+    },
+    ShuffleBatchSizeUpdated {
+        vote_id: VoteId,
+        batch_size: u64,
+    },
+    LinkedShuffleUpdated {
+        vote_id: VoteId,
+        linked: bool,
+    },
+    GroupCheckUpdated {
+        vote_id: VoteId,
+        enabled: bool,
+    },
+    TopicPackingUpdated {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        packing: Option<TopicPacking>,
+    },
+    DecryptionStalled {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        missing: Vec<AccountId>,
+    },
+    SealerUnresponsive {
+        sealer: AccountId,
+        last_heartbeat: Option<BlockNumber>,
+    },
+    ResultApproved {
+        vote_id: VoteId,
+        who: AccountId,
+        result_hash: [u8; 32],
+    },
+    ResultCertified {
+        vote_id: VoteId,
+        result_hash: [u8; 32],
+    },
+    DecryptionThresholdUpdated {
+        vote_id: VoteId,
+        threshold: u32,
+    },
+    HashFunctionUpdated {
+        vote_id: VoteId,
+        hash_function: HashFunction,
+    },
+    VoteWhitelisted {
+        vote_id: VoteId,
+        whitelisted: bool,
+    },
+    BallotDepositRefunded {
+        voter: AccountId,
+        vote_id: VoteId,
+        deposit: Balance,
+    },
+    VoterWeightUpdated {
+        vote_id: VoteId,
+        voter: AccountId,
+        weight: Option<u32>,
+    },
+    BallotRelayed {
+        relayer: AccountId,
+        voter: AccountId,
+        vote_id: VoteId,
+    },
+    CancellationApproved {
+        vote_id: VoteId,
+        who: AccountId,
+    },
+    VoteCancelled {
+        vote_id: VoteId,
+        reason: Vec<u8>,
+    },
+    ResultDigestCreated {
+        vote_id: VoteId,
+        digest: [u8; 32],
+    },
+    IdentityProviderUpdated {
+        vote_id: VoteId,
+        provider: Option<IdentityProvider>,
+    },
+    VoterRegistered {
+        vote_id: VoteId,
+        voter: AccountId,
+    },
+    VoterRevoked {
+        vote_id: VoteId,
+        voter: AccountId,
+        nr_of_excluded_ciphers: u32,
+    },
+    EligibilityKeyUpdated {
+        vote_id: VoteId,
+        key: Option<EligibilityKey>,
+    },
+    AnonymousBallotSubmitted {
+        vote_id: VoteId,
+        nullifier: Nullifier,
+        ballot: Ballot,
+    },
+    CiphersImported {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        index: u32,
+        source_tag: Vec<u8>,
+        nr_of_ciphers: u64,
+        merkle_root: [u8; 32],
+    },
+    TallyBatchCombined {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        combined: u64,
+        total: u64,
+    },
+    BallotBoxFrozen {
+        vote_id: VoteId,
+        block_hash: H256,
+        digest: [u8; 32],
+    },
+    SealerAdded {
+        sealer: AccountId,
+        epoch: u32,
+    },
+    SealerRemoved {
+        sealer: AccountId,
+        epoch: u32,
+    },
+    CredentialCheckStarted {
+        vote_id: VoteId,
+        nr_of_ballots: u32,
+    },
+    CredentialBlindingsSubmitted {
+        vote_id: VoteId,
+        sealer: AccountId,
+    },
+    CredentialDecryptionsSubmitted {
+        vote_id: VoteId,
+        sealer: AccountId,
+    },
+    CredentialsChecked {
+        vote_id: VoteId,
+        nr_of_discarded: u32,
+    },
+}
+
+impl MixnetEvent {
+    /// Decodes a raw event, None if the event is not a (known) event of the mixnet pallet.
+    pub fn decode(event: &RawEvent) -> Result<Option<Self>, Error> {
+        if event.module != MODULE {
+            return Ok(None);
+        }
+        let data = &mut &event.data[..];
+
+        let event = match event.variant.as_str() {
+            "BallotSubmitted" => {
+                let (who, vote_id, ballot) = <(AccountId, VoteId, Ballot)>::decode(data)?;
+                MixnetEvent::BallotSubmitted {
+                    who,
+                    vote_id,
+                    ballot,
+                }
+            }
+            "PublicKeyStored" => {
+                let (who, vote_id, pk) = <(AccountId, VoteId, SubstratePK)>::decode(data)?;
+                MixnetEvent::PublicKeyStored { who, vote_id, pk }
+            }
+            "VoteCreatedWithPublicParameters" => {
+                let (vote_id, who, params) = <(VoteId, AccountId, PublicParameters)>::decode(data)?;
+                MixnetEvent::VoteCreated {
+                    vote_id,
+                    who,
+                    params,
+                }
+            }
+            "VoteProposed" => {
+                let (vote_id, who) = <(VoteId, AccountId)>::decode(data)?;
+                MixnetEvent::VoteProposed { vote_id, who }
+            }
+            "VoteApproved" => {
+                let (vote_id, who) = <(VoteId, AccountId)>::decode(data)?;
+                MixnetEvent::VoteApproved { vote_id, who }
+            }
+            "VoteTemplateStored" => {
+                let (template_id, who) = <(VoteTemplateId, AccountId)>::decode(data)?;
+                MixnetEvent::VoteTemplateStored { template_id, who }
+            }
+            "TallyingApproved" => {
+                let (vote_id, who) = <(VoteId, AccountId)>::decode(data)?;
+                MixnetEvent::TallyingApproved { vote_id, who }
+            }
+            "VoteTopicQuestionStored" => {
+                let (vote_id, topic) = <(VoteId, Topic)>::decode(data)?;
+                MixnetEvent::VoteTopicQuestionStored { vote_id, topic }
+            }
+            "VotePhaseChanged" => {
+                let (vote_id, previous, phase) = <(VoteId, VotePhase, VotePhase)>::decode(data)?;
+                MixnetEvent::VotePhaseChanged {
+                    vote_id,
+                    previous,
+                    phase,
+                }
+            }
+            "PublicKeyShareSubmitted" => {
+                let share = PublicKeyShare::decode(data)?;
+                MixnetEvent::PublicKeyShareSubmitted { share }
+            }
+            "PublicKeyCreated" => {
+                let (vote_id, pk) = <(VoteId, SubstratePK)>::decode(data)?;
+                MixnetEvent::PublicKeyCreated { vote_id, pk }
+            }
+            "DecryptedShareSubmitted" => {
+                let (topic_id, who) = <(TopicId, AccountId)>::decode(data)?;
+                MixnetEvent::DecryptedShareSubmitted { topic_id, who }
+            }
+            "TopicTallied" => {
+                let (vote_id, topic_id, result) = <(VoteId, TopicId, TopicResult)>::decode(data)?;
+                MixnetEvent::TopicTallied {
+                    vote_id,
+                    topic_id,
+                    result,
+                }
+            }
+            "ShuffleProofSubmitted" => {
+                let (topic_id, who) = <(TopicId, AccountId)>::decode(data)?;
+                MixnetEvent::ShuffleProofSubmitted { topic_id, who }
+            }
+            "ShuffleCommitted" => {
+                let (topic_id, who) = <(TopicId, AccountId)>::decode(data)?;
+                MixnetEvent::ShuffleCommitted { topic_id, who }
+            }
+            "CiphersAggregated" => {
+                let (vote_id, topic_id) = <(VoteId, TopicId)>::decode(data)?;
+                MixnetEvent::CiphersAggregated { vote_id, topic_id }
+            }
+            "ShuffleCompleted" => {
+                let (vote_id, topic_id, nr_of_shuffles) =
+                    <(VoteId, TopicId, NrOfShuffles)>::decode(data)?;
+                MixnetEvent::ShuffleCompleted {
+                    vote_id,
+                    topic_id,
+                    nr_of_shuffles,
+                }
+            }
+            "ShuffleBatchAccepted" => {
+                let (vote_id, topic_id, iteration, start_position, nr_of_ciphers) =
+                    <(VoteId, TopicId, NrOfShuffles, u64, u64)>::decode(data)?;
+                MixnetEvent::ShuffleBatchAccepted {
+                    vote_id,
+                    topic_id,
+                    iteration,
+                    start_position,
+                    nr_of_ciphers,
+                }
+            }
+            "ShuffleIterationCompleted" => {
+                let (vote_id, topic_id, nr_of_shuffles) =
+                    <(VoteId, TopicId, NrOfShuffles)>::decode(data)?;
+                MixnetEvent::ShuffleIterationCompleted {
+                    vote_id,
+                    topic_id,
+                    nr_of_shuffles,
+                }
+            }
+            "DecryptionProgress" => {
+                let (vote_id, topic_id, submitted, required) =
+                    <(VoteId, TopicId, u32, u32)>::decode(data)?;
+                MixnetEvent::DecryptionProgress {
+                    vote_id,
+                    topic_id,
+                    submitted,
+                    required,
+                }
+            }
+            "PublicKeyRotated" => {
+                let vote_id = VoteId::decode(data)?;
+                MixnetEvent::PublicKeyRotated { vote_id }
+            }
+            "KeySwitchSharesSubmitted" => {
+                let (topic_id, who) = <(TopicId, AccountId)>::decode(data)?;
+                MixnetEvent::KeySwitchSharesSubmitted { topic_id, who }
+            }
+            "CiphersKeySwitched" => {
+                let (vote_id, topic_id) = <(VoteId, TopicId)>::decode(data)?;
+                MixnetEvent::CiphersKeySwitched { vote_id, topic_id }
+            }
+            "VoteArchived" => {
+                let (vote_id, merkle_root) = <(VoteId, [u8; 32])>::decode(data)?;
+                MixnetEvent::VoteArchived {
+                    vote_id,
+                    merkle_root,
+                }
+            }
+            "CredentialRegistered" => {
+                let (vote_id, voter) = <(VoteId, AccountId)>::decode(data)?;
+                MixnetEvent::CredentialRegistered { vote_id, voter }
+            }
+            "BallotSpoiled" => {
+                let (voter, vote_id, topic_id, cipher) =
+                    <(AccountId, VoteId, TopicId, Cipher)>::decode(data)?;
+                MixnetEvent::BallotSpoiled {
+                    voter,
+                    vote_id,
+                    topic_id,
+                    cipher,
+                }
+            }
+            "SpoiledBallotRevealed" => {
+                let (voter, topic_id, cipher_hash) =
+                    <(AccountId, TopicId, [u8; 32])>::decode(data)?;
+                MixnetEvent::SpoiledBallotRevealed {
+                    voter,
+                    topic_id,
+                    cipher_hash,
+                }
+            }
+            "MetadataUpdated" => {
+                let (vote_id, who) = <(VoteId, AccountId)>::decode(data)?;
+                MixnetEvent::MetadataUpdated { vote_id, who }
+            }
+            "ShuffleBatchSizeUpdated" => {
+                let (vote_id, batch_size) = <(VoteId, u64)>::decode(data)?;
+                MixnetEvent::ShuffleBatchSizeUpdated {
+                    vote_id,
+                    batch_size,
+                }
+            }
+            "LinkedShuffleUpdated" => {
+                let (vote_id, linked) = <(VoteId, bool)>::decode(data)?;
+                MixnetEvent::LinkedShuffleUpdated { vote_id, linked }
+            }
+            "GroupCheckUpdated" => {
+                let (vote_id, enabled) = <(VoteId, bool)>::decode(data)?;
+                MixnetEvent::GroupCheckUpdated { vote_id, enabled }
+            }
+            "TopicPackingUpdated" => {
+                let (vote_id, topic_id, packing) =
+                    <(VoteId, TopicId, Option<TopicPacking>)>::decode(data)?;
+                MixnetEvent::TopicPackingUpdated {
+                    vote_id,
+                    topic_id,
+                    packing,
+                }
+            }
+            "DecryptionStalled" => {
+                let (vote_id, topic_id, missing) =
+                    <(VoteId, TopicId, Vec<AccountId>)>::decode(data)?;
+                MixnetEvent::DecryptionStalled {
+                    vote_id,
+                    topic_id,
+                    missing,
+                }
+            }
+            "SealerUnresponsive" => {
+                let (sealer, last_heartbeat) = <(AccountId, Option<BlockNumber>)>::decode(data)?;
+                MixnetEvent::SealerUnresponsive {
+                    sealer,
+                    last_heartbeat,
+                }
+            }
+            "ResultApproved" => {
+                let (vote_id, who, result_hash) = <(VoteId, AccountId, [u8; 32])>::decode(data)?;
+                MixnetEvent::ResultApproved {
+                    vote_id,
+                    who,
+                    result_hash,
+                }
+            }
+            "ResultCertified" => {
+                let (vote_id, result_hash) = <(VoteId, [u8; 32])>::decode(data)?;
+                MixnetEvent::ResultCertified {
+                    vote_id,
+                    result_hash,
+                }
+            }
+            "DecryptionThresholdUpdated" => {
+                let (vote_id, threshold) = <(VoteId, u32)>::decode(data)?;
+                MixnetEvent::DecryptionThresholdUpdated { vote_id, threshold }
+            }
+            "HashFunctionUpdated" => {
+                let (vote_id, hash_function) = <(VoteId, HashFunction)>::decode(data)?;
+                MixnetEvent::HashFunctionUpdated {
+                    vote_id,
+                    hash_function,
+                }
+            }
+            "VoteWhitelisted" => {
+                let (vote_id, whitelisted) = <(VoteId, bool)>::decode(data)?;
+                MixnetEvent::VoteWhitelisted {
+                    vote_id,
+                    whitelisted,
+                }
+            }
+            "BallotDepositRefunded" => {
+                let (voter, vote_id, deposit) = <(AccountId, VoteId, Balance)>::decode(data)?;
+                MixnetEvent::BallotDepositRefunded {
+                    voter,
+                    vote_id,
+                    deposit,
+                }
+            }
+            "VoterWeightUpdated" => {
+                let (vote_id, voter, weight) = <(VoteId, AccountId, Option<u32>)>::decode(data)?;
+                MixnetEvent::VoterWeightUpdated {
+                    vote_id,
+                    voter,
+                    weight,
+                }
+            }
+            "BallotRelayed" => {
+                let (relayer, voter, vote_id) = <(AccountId, AccountId, VoteId)>::decode(data)?;
+                MixnetEvent::BallotRelayed {
+                    relayer,
+                    voter,
+                    vote_id,
+                }
+            }
+            "CancellationApproved" => {
+                let (vote_id, who) = <(VoteId, AccountId)>::decode(data)?;
+                MixnetEvent::CancellationApproved { vote_id, who }
+            }
+            "VoteCancelled" => {
+                let (vote_id, reason) = <(VoteId, Vec<u8>)>::decode(data)?;
+                MixnetEvent::VoteCancelled { vote_id, reason }
+            }
+            "ResultDigestCreated" => {
+                let (vote_id, digest) = <(VoteId, [u8; 32])>::decode(data)?;
+                MixnetEvent::ResultDigestCreated { vote_id, digest }
+            }
+            "IdentityProviderUpdated" => {
+                let (vote_id, provider) = <(VoteId, Option<IdentityProvider>)>::decode(data)?;
+                MixnetEvent::IdentityProviderUpdated { vote_id, provider }
+            }
+            "VoterRegistered" => {
+                let (vote_id, voter) = <(VoteId, AccountId)>::decode(data)?;
+                MixnetEvent::VoterRegistered { vote_id, voter }
+            }
+            "VoterRevoked" => {
+                let (vote_id, voter, nr_of_excluded_ciphers) =
+                    <(VoteId, AccountId, u32)>::decode(data)?;
+                MixnetEvent::VoterRevoked {
+                    vote_id,
+                    voter,
+                    nr_of_excluded_ciphers,
+                }
+            }
+            "EligibilityKeyUpdated" => {
+                let (vote_id, key) = <(VoteId, Option<EligibilityKey>)>::decode(data)?;
+                MixnetEvent::EligibilityKeyUpdated { vote_id, key }
+            }
+            "AnonymousBallotSubmitted" => {
+                let (vote_id, nullifier, ballot) = <(VoteId, Nullifier, Ballot)>::decode(data)?;
+                MixnetEvent::AnonymousBallotSubmitted {
+                    vote_id,
+                    nullifier,
+                    ballot,
+                }
+            }
+            "CiphersImported" => {
+                let (vote_id, topic_id, index, source_tag, nr_of_ciphers, merkle_root) =
+                    <(VoteId, TopicId, u32, Vec<u8>, u64, [u8; 32])>::decode(data)?;
+                MixnetEvent::CiphersImported {
+                    vote_id,
+                    topic_id,
+                    index,
+                    source_tag,
+                    nr_of_ciphers,
+                    merkle_root,
+                }
+            }
+            "TallyBatchCombined" => {
+                let (vote_id, topic_id, combined, total) =
+                    <(VoteId, TopicId, u64, u64)>::decode(data)?;
+                MixnetEvent::TallyBatchCombined {
+                    vote_id,
+                    topic_id,
+                    combined,
+                    total,
+                }
+            }
+            "BallotBoxFrozen" => {
+                let (vote_id, block_hash, digest) = <(VoteId, H256, [u8; 32])>::decode(data)?;
+                MixnetEvent::BallotBoxFrozen {
+                    vote_id,
+                    block_hash,
+                    digest,
+                }
+            }
+            "SealerAdded" => {
+                let (sealer, epoch) = <(AccountId, u32)>::decode(data)?;
+                MixnetEvent::SealerAdded { sealer, epoch }
+            }
+            "SealerRemoved" => {
+                let (sealer, epoch) = <(AccountId, u32)>::decode(data)?;
+                MixnetEvent::SealerRemoved { sealer, epoch }
+            }
+            "CredentialCheckStarted" => {
+                let (vote_id, nr_of_ballots) = <(VoteId, u32)>::decode(data)?;
+                MixnetEvent::CredentialCheckStarted {
+                    vote_id,
+                    nr_of_ballots,
+                }
+            }
+            "CredentialBlindingsSubmitted" => {
+                let (vote_id, sealer) = <(VoteId, AccountId)>::decode(data)?;
+                MixnetEvent::CredentialBlindingsSubmitted { vote_id, sealer }
+            }
+            "CredentialDecryptionsSubmitted" => {
+                let (vote_id, sealer) = <(VoteId, AccountId)>::decode(data)?;
+                MixnetEvent::CredentialDecryptionsSubmitted { vote_id, sealer }
+            }
+            "CredentialsChecked" => {
+                let (vote_id, nr_of_discarded) = <(VoteId, u32)>::decode(data)?;
+                MixnetEvent::CredentialsChecked {
+                    vote_id,
+                    nr_of_discarded,
+                }
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+}
+
+/// Subscribes to the events of the mixnet pallet in the best blocks.
+/// The events of other pallets are skipped, all others are decoded into a MixnetEvent.
+/// The events of a block which is reorged out are not revoked, see: `subscribe_finalized_mixnet_events`.
+pub async fn subscribe_mixnet_events(
+    client: &Client<NodeTemplateRuntime>,
+) -> Result<BoxStream<'static, Result<MixnetEvent, Error>>, Error> {
+    let subscription = subscribe_events(client).await?;
+    Ok(decode_mixnet_events(subscription))
+}
+
+/// Subscribes to the events of the mixnet pallet in the finalized blocks (GRANDPA),
+/// e.g., to treat ballots and phase changes as durably recorded only once they are final.
+pub async fn subscribe_finalized_mixnet_events(
+    client: &Client<NodeTemplateRuntime>,
+) -> Result<BoxStream<'static, Result<MixnetEvent, Error>>, Error> {
+    let subscription = subscribe_finalized_events(client).await?;
+    Ok(decode_mixnet_events(subscription))
+}
+
+fn decode_mixnet_events(
+    subscription: EventSubscription<NodeTemplateRuntime>,
+) -> BoxStream<'static, Result<MixnetEvent, Error>> {
+    let events = stream::unfold(subscription, |mut subscription| async move {
+        loop {
+            // the stream ends once the subscription is closed
+            let decoded = match subscription.next().await? {
+                Ok(event) => MixnetEvent::decode(&event),
+                Err(err) => Err(err),
+            };
+            match decoded {
+                Ok(Some(event)) => return Some((Ok(event), subscription)),
+                Ok(None) => continue,
+                Err(err) => return Some((Err(err), subscription)),
+            }
+        }
+    });
+    events.boxed()
+}
@@ -0,0 +1,65 @@
+//! A reader of the offchain index of a node, i.e., of the values the pallet writes using
+//! offchain indexing while importing blocks.
+//!
+//! The node must be started with `--enable-offchain-indexing true`, the index is read using the
+//! RPC `offchain_localStorageGet`, i.e., with `--rpc-methods Unsafe`. Only the blocks imported
+//! with offchain indexing enabled are indexed, e.g. an archive node syncing from genesis.
+use crate::rpc::get_topics;
+use crate::BlockNumber;
+use codec::{Decode, Encode};
+use jsonrpsee::common::{to_value as to_json_value, Params};
+use pallet_mixnet::types::{ShuffleStatus, Topic, TopicId, VoteId, SHUFFLE_STATUS_PREFIX};
+use substrate_subxt::{sp_core::Bytes, Client, Error, NodeTemplateRuntime};
+
+/// the offchain indexing writes to the persistent offchain storage
+const STORAGE_KIND: &str = "PERSISTENT";
+
+/// Returns the key of the shuffle status of the topic in the offchain index.
+pub fn shuffle_status_key(vote_id: &VoteId, topic_id: &TopicId) -> Vec<u8> {
+    let mut key: Vec<u8> = SHUFFLE_STATUS_PREFIX.to_vec();
+    key.extend((vote_id, topic_id).encode());
+    key
+}
+
+async fn get_indexed_value<V: Decode>(
+    client: &Client<NodeTemplateRuntime>,
+    key: Vec<u8>,
+) -> Result<Option<V>, Error> {
+    let params = Params::Array(vec![
+        to_json_value(STORAGE_KIND)?,
+        to_json_value(Bytes(key))?,
+    ]);
+    let value: Option<Bytes> = client
+        .rpc_client()
+        .request("offchain_localStorageGet", params)
+        .await?;
+    match value {
+        Some(bytes) => Ok(Some(V::decode(&mut &bytes[..])?)),
+        None => Ok(None),
+    }
+}
+
+/// Returns the shuffle status of the topic (its shuffle state and the block in which it changed)
+/// from the offchain index, None if the node hasn't indexed it.
+pub async fn get_shuffle_status(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<Option<ShuffleStatus<BlockNumber>>, Error> {
+    get_indexed_value(client, shuffle_status_key(&vote_id, &topic_id)).await
+}
+
+/// Returns the shuffle status of all topics of the vote from the offchain index,
+/// i.e., the mixing progress of the vote.
+pub async fn get_mixing_progress(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Vec<(TopicId, Option<ShuffleStatus<BlockNumber>>)>, Error> {
+    let topics: Vec<Topic> = get_topics(client, vote_id.clone()).await?;
+    let mut progress = Vec::with_capacity(topics.len());
+    for Topic { id: topic_id, .. } in topics {
+        let status = get_shuffle_status(client, vote_id.clone(), topic_id.clone()).await?;
+        progress.push((topic_id, status));
+    }
+    Ok(progress)
+}
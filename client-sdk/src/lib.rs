@@ -0,0 +1,46 @@
+//! A typed client for the mixnet pallet of the Provotum node.
+//!
+//! - `calls`: the extrinsics of the pallet
+//! - `stores`: the storage items of the pallet
+//! - `events`: the events of the pallet decoded into `MixnetEvent`
+//! - `rpc`: an async function for every extrinsic and storage item
+//! - `index`: a reader of the offchain index, e.g. the mixing progress of a vote
+//!
+//! The call wrappers of `rpc` return once their extrinsic is included in a best block, which may
+//! still be reorged out. `rpc::watch_finalized` (and `events::subscribe_finalized_mixnet_events`)
+//! wait for the GRANDPA finalization instead, `rpc::watch_in_block` is the default behaviour.
+//!
+//! ```no_run
+//! use provotum_client_sdk::{rpc, ClientBuilder, NodeTemplateRuntime};
+//!
+//! # async fn run() -> Result<(), provotum_client_sdk::Error> {
+//! let client = ClientBuilder::<NodeTemplateRuntime>::new()
+//!     .set_url("ws://127.0.0.1:9944")
+//!     .build()
+//!     .await?;
+//! let vote_ids = rpc::get_vote_ids(&client).await?;
+//! # Ok(())
+//! # }
+//! ```
+pub mod calls;
+pub mod events;
+pub mod index;
+pub mod rpc;
+pub mod stores;
+
+pub use pallet_mixnet::types;
+pub use substrate_subxt::{Client, ClientBuilder, Error, NodeTemplateRuntime, PairSigner};
+
+use substrate_subxt::system::System;
+
+/// The account id of the node's runtime.
+pub type AccountId = <NodeTemplateRuntime as System>::AccountId;
+
+/// The block number of the node's runtime.
+pub type BlockNumber = u64;
+
+/// The balance of the node's runtime, e.g., the ballot deposit.
+pub type Balance = u128;
+
+/// The signer of the extrinsics, e.g., `PairSigner::new(AccountKeyring::Alice.pair())`.
+pub type Signer = PairSigner<NodeTemplateRuntime, sp_keyring::sr25519::sr25519::Pair>;
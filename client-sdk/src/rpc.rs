@@ -0,0 +1,1898 @@
+use crate::calls::{
+    AddSealer, AggregateCiphers, ApproveVote, ArchiveVote, CancelVote, CastAnonymousBallot,
+    CastBallot, CastRelayedBallot, CertifyResult, ClaimBallotDeposit, CombineDecryptedShares,
+    CombineDecryptedSharesBatch, CombinePublicKeyShares, CommitShuffle, CreateVoteFromTemplate,
+    DoNothingWhenItsNotYourTurn, FinalizeTally, Heartbeat, ImportCiphers, ProposeVote,
+    RegisterCredential, RegisterVoterWithClaim, RemoveSealer, RevealSpoiledBallot, RevokeVoter,
+    RotatePublicKey, SetDecryptionThreshold, SetEligibilityKey, SetGroupCheck, SetHashFunction,
+    SetIdentityProvider, SetLinkedShuffle, SetMetadata, SetShuffleBatchSize, SetTopicPacking,
+    SetVotePhase, SetVoterWeight, SpoilBallot, StorePublicKey, StorePublicKeyShare, StoreQuestion,
+    StoreVoteTemplate, SubmitCredentialBlindings, SubmitCredentialDecryptions,
+    SubmitKeySwitchShares, SubmitLinkedShuffle, SubmitPartialDecryption,
+    SubmitShuffledVotesAndProof, WhitelistVote,
+};
+use crate::stores::*;
+use crate::{AccountId, Balance, BlockNumber};
+use codec::{Decode, Encode};
+use jsonrpsee::common::{to_value as to_json_value, Params};
+use pallet_mixnet::types::{
+    Ballot, BallotBoxFreeze, BallotRate, BallotReceipt, CertifiedResult, ChunkIndex, Cipher,
+    CipherImport, ClaimSignature, CredentialBlindingProof, DecryptedShare, DecryptedShareProof,
+    DecryptionAuditEntry, EligibilityKey, HashFunction, IdentityProvider, KeySwitchAuditEntry,
+    KeySwitchShareProof, LinkedShufflePayload, NrOfShuffles, Nullifier, PartialTally,
+    PendingDecryption, ProofLogEntry, ProtocolConfig, PublicKey as SubstratePK, PublicKeyShare,
+    PublicParameters, ResultApprovals, ResultDigest, ShuffleCommitment, ShufflePayload,
+    ShuffleState, ShuffleTurn, SpoiledBallot, SpoiledBallotReveal, TallyProgress, TallyRound,
+    TallyStrategy, Title, Topic, TopicId, TopicPacking, TopicResult, Vote, VoteArchive, VoteId,
+    VoteMetadata, VotePhase, VoteProposal, VoteResult, VoteTemplate, VoteTemplateId,
+};
+use pallet_mixnet::ErrorCode;
+use sp_keyring::sr25519::sr25519::Pair;
+use substrate_subxt::{
+    sp_core::{sr25519, Bytes, H256},
+    system::System,
+    Call, Client, EventSubscription, EventsDecoder, ExtrinsicSuccess, UncheckedExtrinsic,
+};
+use substrate_subxt::{Error, NodeTemplateRuntime, PairSigner};
+
+pub async fn get_ciphers(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    nr_of_shuffles: NrOfShuffles,
+) -> Result<Vec<Cipher>, Error> {
+    get_ciphers_at(client, vote_id, topic_id, nr_of_shuffles, None).await
+}
+
+/// `get_ciphers` as of the block with hash `at` (default: best block).
+pub async fn get_ciphers_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    nr_of_shuffles: NrOfShuffles,
+    at: Option<H256>,
+) -> Result<Vec<Cipher>, Error> {
+    // the ciphers are stored in chunks, fetch the number of chunks first
+    let store = CipherChunkCountStore {
+        vote_id: vote_id.clone(),
+        topic_id: topic_id.clone(),
+        nr_of_shuffles,
+    };
+    let nr_of_chunks = client.fetch(&store, at).await?.unwrap_or_default();
+
+    let mut ciphers: Vec<Cipher> = Vec::new();
+    for chunk_index in 0..nr_of_chunks {
+        let store = CiphersStore {
+            vote_id: vote_id.clone(),
+            topic_id: topic_id.clone(),
+            nr_of_shuffles,
+            chunk_index,
+        };
+        let chunk = client
+            .fetch(&store, at)
+            .await?
+            .ok_or("failed to fetch ciphers!")?;
+        ciphers.extend(chunk);
+    }
+    Ok(ciphers)
+}
+
+pub async fn get_aggregate_cipher(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<Option<Cipher>, Error> {
+    get_aggregate_cipher_at(client, vote_id, topic_id, None).await
+}
+
+/// `get_aggregate_cipher` as of the block with hash `at` (default: best block).
+pub async fn get_aggregate_cipher_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    at: Option<H256>,
+) -> Result<Option<Cipher>, Error> {
+    let store = AggregateCipherStore { vote_id, topic_id };
+    let aggregate = client.fetch(&store, at).await?;
+    Ok(aggregate)
+}
+
+pub async fn get_ballot_receipt(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    account: <NodeTemplateRuntime as System>::AccountId,
+) -> Result<Option<BallotReceipt<u64, H256>>, Error> {
+    let store = BallotReceiptStore { vote_id, account };
+    let receipt = match client.fetch(&store, None).await? {
+        Some(receipt) => receipt,
+        None => return Ok(None),
+    };
+
+    // the block hash is not stored on chain, resolve it using the block number
+    let block_hash = client
+        .block_hash(Some(receipt.block_number.into()))
+        .await?
+        .ok_or("failed to fetch block hash!")?;
+    Ok(Some(BallotReceipt {
+        block_hash,
+        ..receipt
+    }))
+}
+
+/// Pre-flights the ballot of the voter, i.e., runs all checks of `cast_ballot` in the runtime
+/// (see: `MixnetApi::validate_ballot`) without submitting it. Returns the error the ballot would
+/// be rejected with (e.g. `ErrorCode::VoterNotRegistered`), None if the ballot is valid.
+pub async fn validate_ballot(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    voter: AccountId,
+    ballot: Ballot,
+) -> Result<Option<ErrorCode>, Error> {
+    let params = Params::Array(vec![
+        to_json_value("MixnetApi_validate_ballot")?,
+        to_json_value(Bytes((vote_id, voter, ballot).encode()))?,
+    ]);
+    let response: Bytes = client.rpc_client().request("state_call", params).await?;
+    let validation = Result::<(), u16>::decode(&mut &response[..])?;
+    Ok(validation
+        .err()
+        .map(|code| ErrorCode::from_code(code).unwrap_or(ErrorCode::Unknown)))
+}
+
+pub async fn get_vote_public_key(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<SubstratePK, Error> {
+    get_vote_public_key_at(client, vote_id, None).await
+}
+
+/// `get_vote_public_key` as of the block with hash `at` (default: best block).
+pub async fn get_vote_public_key_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    at: Option<H256>,
+) -> Result<SubstratePK, Error> {
+    let store = PublicKeyStore { vote_id };
+    let pk = client
+        .fetch(&store, at)
+        .await?
+        .ok_or("failed to fetch public key!")?;
+    Ok(pk)
+}
+
+/// Returns the public parameters proposed for new votes, None if not configured in the genesis config.
+pub async fn get_default_public_parameters(
+    client: &Client<NodeTemplateRuntime>,
+) -> Result<Option<PublicParameters>, Error> {
+    let store = DefaultPublicParametersStore {};
+    let params = client.fetch(&store, None).await?.unwrap_or_default();
+    if params.p.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(params))
+}
+
+pub async fn get_vote(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Vote<<NodeTemplateRuntime as System>::AccountId>, Error> {
+    get_vote_at(client, vote_id, None).await
+}
+
+/// `get_vote` as of the block with hash `at` (default: best block).
+pub async fn get_vote_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    at: Option<H256>,
+) -> Result<Vote<<NodeTemplateRuntime as System>::AccountId>, Error> {
+    let store = VoteStore { vote_id };
+    let vote = client
+        .fetch(&store, at)
+        .await?
+        .ok_or("failed to fetch vote!")?;
+    Ok(vote)
+}
+
+/// The parameters of the protocol the vote has been created with, None if the vote doesn't exist.
+pub async fn get_protocol_config(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<ProtocolConfig>, Error> {
+    get_protocol_config_at(client, vote_id, None).await
+}
+
+/// `get_protocol_config` as of the block with hash `at` (default: best block).
+pub async fn get_protocol_config_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    at: Option<H256>,
+) -> Result<Option<ProtocolConfig>, Error> {
+    let store = ProtocolConfigStore { vote_id };
+    let config = client.fetch(&store, at).await?;
+    Ok(config)
+}
+
+pub async fn get_topics(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Vec<Topic>, Error> {
+    get_topics_at(client, vote_id, None).await
+}
+
+/// `get_topics` as of the block with hash `at` (default: best block).
+pub async fn get_topics_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    at: Option<H256>,
+) -> Result<Vec<Topic>, Error> {
+    let store = TopicsStore { vote_id };
+    let topics = client.fetch(&store, at).await?.unwrap_or_default();
+    Ok(topics)
+}
+
+pub async fn get_pending_key_switch(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Vec<TopicId>, Error> {
+    let store = PendingKeySwitchStore { vote_id };
+    let pending = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(pending)
+}
+
+pub async fn get_tally(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<TopicResult, Error> {
+    get_tally_at(client, vote_id, topic_id, None).await
+}
+
+/// `get_tally` as of the block with hash `at` (default: best block).
+pub async fn get_tally_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    at: Option<H256>,
+) -> Result<TopicResult, Error> {
+    let store = TallyStore { vote_id, topic_id };
+    let tally = client
+        .fetch(&store, at)
+        .await?
+        .ok_or("failed to fetch tally!")?;
+    Ok(tally)
+}
+
+/// Returns true if the result of the topic has been published.
+pub async fn is_topic_tallied(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<bool, Error> {
+    let store = TallyStore { vote_id, topic_id };
+    let tally = client.fetch(&store, None).await?;
+    Ok(tally.is_some())
+}
+
+pub async fn get_sealers(
+    client: &Client<NodeTemplateRuntime>,
+) -> Result<Vec<<NodeTemplateRuntime as System>::AccountId>, Error> {
+    get_sealers_at(client, None).await
+}
+
+/// `get_sealers` as of the block with hash `at` (default: best block).
+pub async fn get_sealers_at(
+    client: &Client<NodeTemplateRuntime>,
+    at: Option<H256>,
+) -> Result<Vec<<NodeTemplateRuntime as System>::AccountId>, Error> {
+    let store = SealersStore {};
+    let sealers = client.fetch(&store, at).await?.unwrap_or_default();
+    Ok(sealers)
+}
+
+/// Returns the sealer epoch new votes are created in, i.e. the number of changes of the sealers.
+pub async fn get_sealer_epoch(client: &Client<NodeTemplateRuntime>) -> Result<u32, Error> {
+    let store = SealerEpochStore {};
+    let epoch = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(epoch)
+}
+
+/// Returns the sealers of a vote, i.e. the sealers of the epoch the vote has been created in.
+pub async fn get_vote_sealers(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Vec<<NodeTemplateRuntime as System>::AccountId>, Error> {
+    get_vote_sealers_at(client, vote_id, None).await
+}
+
+/// `get_vote_sealers` as of the block with hash `at` (default: best block).
+pub async fn get_vote_sealers_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    at: Option<H256>,
+) -> Result<Vec<<NodeTemplateRuntime as System>::AccountId>, Error> {
+    let store = VoteSealerEpochStore { vote_id };
+    let epoch = client.fetch(&store, at).await?.unwrap_or_default();
+    let store = SealerSetStore { epoch };
+    let sealers = client.fetch(&store, at).await?.unwrap_or_default();
+    Ok(sealers)
+}
+
+pub async fn get_public_key_share(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    sealer: <NodeTemplateRuntime as System>::AccountId,
+) -> Result<Option<PublicKeyShare>, Error> {
+    get_public_key_share_at(client, vote_id, sealer, None).await
+}
+
+/// `get_public_key_share` as of the block with hash `at` (default: best block).
+pub async fn get_public_key_share_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    sealer: <NodeTemplateRuntime as System>::AccountId,
+    at: Option<H256>,
+) -> Result<Option<PublicKeyShare>, Error> {
+    let store = PublicKeyShareBySealerStore { vote_id, sealer };
+    let share = client.fetch(&store, at).await?;
+    Ok(share)
+}
+
+pub async fn get_shuffle_proofs(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<Vec<ShufflePayload>, Error> {
+    get_shuffle_proofs_at(client, vote_id, topic_id, None).await
+}
+
+/// `get_shuffle_proofs` as of the block with hash `at` (default: best block).
+pub async fn get_shuffle_proofs_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    at: Option<H256>,
+) -> Result<Vec<ShufflePayload>, Error> {
+    let store = ShuffleProofsStore { vote_id, topic_id };
+    let proofs = client.fetch(&store, at).await?.unwrap_or_default();
+    Ok(proofs)
+}
+
+pub async fn get_decryption_audit(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    nr_of_shuffles: NrOfShuffles,
+) -> Result<Vec<DecryptionAuditEntry<<NodeTemplateRuntime as System>::AccountId>>, Error> {
+    get_decryption_audit_at(client, vote_id, topic_id, nr_of_shuffles, None).await
+}
+
+/// `get_decryption_audit` as of the block with hash `at` (default: best block).
+pub async fn get_decryption_audit_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    nr_of_shuffles: NrOfShuffles,
+    at: Option<H256>,
+) -> Result<Vec<DecryptionAuditEntry<<NodeTemplateRuntime as System>::AccountId>>, Error> {
+    let store = DecryptionAuditStore {
+        vote_id,
+        topic_id,
+        nr_of_shuffles,
+    };
+    let audit = client.fetch(&store, at).await?.unwrap_or_default();
+    Ok(audit)
+}
+
+/// returns the transcript hashes of all accepted shuffle and decryption proofs of the vote
+pub async fn get_proof_log(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Vec<ProofLogEntry<<NodeTemplateRuntime as System>::AccountId, BlockNumber>>, Error> {
+    get_proof_log_at(client, vote_id, None).await
+}
+
+/// `get_proof_log` as of the block with hash `at` (default: best block).
+pub async fn get_proof_log_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    at: Option<H256>,
+) -> Result<Vec<ProofLogEntry<<NodeTemplateRuntime as System>::AccountId, BlockNumber>>, Error> {
+    let store = ProofLogStore { vote_id };
+    let log = client.fetch(&store, at).await?.unwrap_or_default();
+    Ok(log)
+}
+
+pub async fn get_decrypted_shares(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    sealer: <NodeTemplateRuntime as System>::AccountId,
+) -> Result<Vec<DecryptedShare>, Error> {
+    get_decrypted_shares_at(client, vote_id, topic_id, sealer, None).await
+}
+
+/// `get_decrypted_shares` as of the block with hash `at` (default: best block).
+pub async fn get_decrypted_shares_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    sealer: <NodeTemplateRuntime as System>::AccountId,
+    at: Option<H256>,
+) -> Result<Vec<DecryptedShare>, Error> {
+    let store = DecryptedSharesStore {
+        vote_id,
+        topic_id,
+        sealer,
+    };
+    let shares = client.fetch(&store, at).await?.unwrap_or_default();
+    Ok(shares)
+}
+
+pub async fn get_storage_version(client: &Client<NodeTemplateRuntime>) -> Result<u32, Error> {
+    let store = StorageVersionStore {};
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_voting_authorities(
+    client: &Client<NodeTemplateRuntime>,
+) -> Result<Vec<AccountId>, Error> {
+    let store = VotingAuthoritiesStore {};
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_approval_threshold(client: &Client<NodeTemplateRuntime>) -> Result<u32, Error> {
+    let store = ApprovalThresholdStore {};
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_count_by_sealer(
+    client: &Client<NodeTemplateRuntime>,
+    sealer: AccountId,
+) -> Result<u32, Error> {
+    let store = CountsBySealerStore { sealer };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_count(client: &Client<NodeTemplateRuntime>) -> Result<u32, Error> {
+    let store = CountsStore {};
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_voters(client: &Client<NodeTemplateRuntime>) -> Result<Vec<AccountId>, Error> {
+    let store = VotersStore {};
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_vote_ids(client: &Client<NodeTemplateRuntime>) -> Result<Vec<VoteId>, Error> {
+    let store = VotesStore {};
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+/// Returns the proposal of a vote, None once the vote has been created.
+pub async fn get_vote_proposal(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<VoteProposal<AccountId>>, Error> {
+    let store = VoteProposalStore { vote_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+pub async fn get_vote_template(
+    client: &Client<NodeTemplateRuntime>,
+    template_id: VoteTemplateId,
+) -> Result<Option<VoteTemplate>, Error> {
+    let store = VoteTemplateStore { template_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+pub async fn get_tallying_approvals(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Vec<AccountId>, Error> {
+    let store = TallyingApprovalsStore { vote_id };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_cancellation_approvals(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Vec<AccountId>, Error> {
+    let store = CancellationApprovalsStore { vote_id };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_metadata(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<VoteMetadata>, Error> {
+    let store = MetadataStore { vote_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns the shuffle batch size set by a voting authority, None if the batch size of the vote's questions is used.
+pub async fn get_shuffle_batch_size(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<u64>, Error> {
+    let store = ShuffleBatchSizeStore { vote_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns true if all topics of the vote are shuffled with the same permutation (linked shuffles).
+pub async fn get_linked_shuffle(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<bool, Error> {
+    get_linked_shuffle_at(client, vote_id, None).await
+}
+
+/// `get_linked_shuffle` as of the block with hash `at` (default: best block).
+pub async fn get_linked_shuffle_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    at: Option<H256>,
+) -> Result<bool, Error> {
+    let store = LinkedShuffleStore { vote_id };
+    let value = client.fetch(&store, at).await?.unwrap_or_default();
+    Ok(value)
+}
+
+/// Returns true if the ciphers of the ballots of the vote must be elements of the group of the vote.
+pub async fn get_group_check(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<bool, Error> {
+    let store = GroupCheckStore { vote_id };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+/// Returns the decryption threshold of a vote with threshold keys, None if all sealers must decrypt.
+pub async fn get_decryption_threshold(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<u32>, Error> {
+    let store = DecryptionThresholdStore { vote_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns the deadline of the topic's decryption, None if the topic isn't waiting for decrypted shares.
+pub async fn get_pending_decryption(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<Option<PendingDecryption<BlockNumber>>, Error> {
+    let store = PendingDecryptionStore { vote_id, topic_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns the votes whose tallying is monitored, i.e., the sealers send heartbeats.
+pub async fn get_monitored_votes(
+    client: &Client<NodeTemplateRuntime>,
+) -> Result<Vec<VoteId>, Error> {
+    let store = MonitoredVotesStore {};
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+/// Returns the block of the sealer's last heartbeat, None if the sealer never sent one.
+pub async fn get_last_heartbeat(
+    client: &Client<NodeTemplateRuntime>,
+    sealer: AccountId,
+) -> Result<Option<BlockNumber>, Error> {
+    let store = LastHeartbeatStore { sealer };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns the block after which the sealer is reported as unresponsive, None if no vote is monitored.
+pub async fn get_heartbeat_deadline(
+    client: &Client<NodeTemplateRuntime>,
+    sealer: AccountId,
+) -> Result<Option<BlockNumber>, Error> {
+    let store = HeartbeatDeadlineStore { sealer };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns the ballot rate of the account, i.e., the number of ballots cast in the current period.
+pub async fn get_ballot_rate(
+    client: &Client<NodeTemplateRuntime>,
+    account: AccountId,
+) -> Result<BallotRate<BlockNumber>, Error> {
+    let store = BallotRateStore { account };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+/// Returns the weight of the voter (weighted voting), None if the voter has the default weight 1.
+pub async fn get_voter_weight(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    voter: AccountId,
+) -> Result<Option<u32>, Error> {
+    let store = VoterWeightStore { vote_id, voter };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns true if the voters of the vote don't reserve a ballot deposit.
+pub async fn get_whitelisted_vote(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<bool, Error> {
+    let store = WhitelistedVoteStore { vote_id };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+/// Returns the ballot deposits reserved by the voter, None if no deposit has to be claimed.
+pub async fn get_ballot_deposit(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    voter: AccountId,
+) -> Result<Option<Balance>, Error> {
+    let store = BallotDepositStore { vote_id, voter };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+pub async fn get_voting_started(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<bool, Error> {
+    let store = VotingStartedStore { vote_id };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_ballot(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    voter: AccountId,
+) -> Result<Option<Ballot>, Error> {
+    let store = BallotStore { vote_id, voter };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns true if a cipher with the hash has been submitted for the topic.
+pub async fn is_cipher_submitted(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    cipher_hash: [u8; 32],
+) -> Result<bool, Error> {
+    let store = CipherHashStore {
+        vote_id,
+        topic_id,
+        cipher_hash,
+    };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_spoiled_ballot(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    cipher_hash: [u8; 32],
+) -> Result<Option<SpoiledBallot<AccountId>>, Error> {
+    let store = SpoiledBallotStore {
+        vote_id,
+        topic_id,
+        cipher_hash,
+    };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+pub async fn get_credential(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    voter: AccountId,
+) -> Result<Option<Cipher>, Error> {
+    let store = CredentialStore { vote_id, voter };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns the voters whose ballots carry a credential, in the order of the credential check.
+pub async fn get_credential_ballots(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Vec<AccountId>, Error> {
+    let store = CredentialBallotsStore { vote_id };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_credential_blindings(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    sealer: AccountId,
+) -> Result<Option<Vec<Cipher>>, Error> {
+    let store = CredentialBlindingsStore { vote_id, sealer };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+pub async fn get_credential_decryptions(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    sealer: AccountId,
+) -> Result<Option<Vec<DecryptedShare>>, Error> {
+    let store = CredentialDecryptionsStore { vote_id, sealer };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns the voters whose ballots have been discarded because of an invalid credential,
+/// None while the credentials haven't been checked.
+pub async fn get_discarded_ballots(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<Vec<AccountId>>, Error> {
+    let store = DiscardedBallotsStore { vote_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns the identity provider of the vote, None if all voters can cast a ballot.
+pub async fn get_identity_provider(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<IdentityProvider>, Error> {
+    let store = IdentityProviderStore { vote_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns true if the voter has been registered by an eligibility claim.
+pub async fn is_registered_voter(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    voter: AccountId,
+) -> Result<bool, Error> {
+    let store = RegisteredVoterStore { vote_id, voter };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+/// Returns the block number in which the voter has been revoked, None if the voter
+/// isn't on the revocation list of the vote.
+pub async fn get_voter_revocation(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    voter: AccountId,
+) -> Result<Option<BlockNumber>, Error> {
+    let store = RevokedVoterStore { vote_id, voter };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns the key which blind-signs the eligibility tokens of the vote, None if the vote
+/// doesn't accept anonymous ballots.
+pub async fn get_eligibility_key(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<EligibilityKey>, Error> {
+    let store = EligibilityKeyStore { vote_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns true if an anonymous ballot has been cast with the eligibility token of the nullifier.
+pub async fn is_nullifier_spent(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    nullifier: Nullifier,
+) -> Result<bool, Error> {
+    let store = SpentNullifierStore { vote_id, nullifier };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_cipher_chunk_count(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    nr_of_shuffles: NrOfShuffles,
+) -> Result<ChunkIndex, Error> {
+    let store = CipherChunkCountStore {
+        vote_id,
+        topic_id,
+        nr_of_shuffles,
+    };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+/// Returns the number of ciphers of the topic after the given number of shuffles,
+/// every shuffle iteration must contain as many ciphers as the previous one.
+pub async fn get_cipher_count(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    nr_of_shuffles: NrOfShuffles,
+) -> Result<u64, Error> {
+    let store = CipherCountStore {
+        vote_id,
+        topic_id,
+        nr_of_shuffles,
+    };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+/// Returns the merkle root of the ciphers after the # of shuffles.
+pub async fn get_cipher_root(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    nr_of_shuffles: NrOfShuffles,
+) -> Result<Option<[u8; 32]>, Error> {
+    let store = CipherRootStore {
+        vote_id,
+        topic_id,
+        nr_of_shuffles,
+    };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+pub async fn get_cipher_peaks(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    nr_of_shuffles: NrOfShuffles,
+) -> Result<Vec<Option<[u8; 32]>>, Error> {
+    let store = CipherPeaksStore {
+        vote_id,
+        topic_id,
+        nr_of_shuffles,
+    };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+/// Returns the number of the current block.
+pub async fn get_block_number(client: &Client<NodeTemplateRuntime>) -> Result<BlockNumber, Error> {
+    get_block_number_at(client, None).await
+}
+
+/// `get_block_number` as of the block with hash `at` (default: best block).
+pub async fn get_block_number_at(
+    client: &Client<NodeTemplateRuntime>,
+    at: Option<H256>,
+) -> Result<BlockNumber, Error> {
+    let store = BlockNumberStore {};
+    let value = client.fetch(&store, at).await?.unwrap_or_default();
+    Ok(value)
+}
+
+/// Returns the weight consumed by the extrinsics of the block with hash `at` (default: best block).
+pub async fn get_block_weight_at(
+    client: &Client<NodeTemplateRuntime>,
+    at: Option<H256>,
+) -> Result<ExtrinsicsWeight, Error> {
+    let store = BlockWeightStore {};
+    let value = client.fetch(&store, at).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_shuffle_commitment(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<Option<ShuffleCommitment<AccountId, BlockNumber>>, Error> {
+    let store = ShuffleCommitmentStore { vote_id, topic_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+pub async fn get_shuffle_turn(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<ShuffleTurn<BlockNumber>, Error> {
+    let store = ShuffleTurnStore { vote_id, topic_id };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_shuffle_state(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<Option<ShuffleState>, Error> {
+    let store = ShuffleStateStore { vote_id, topic_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+pub async fn get_tally_rounds(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<Vec<TallyRound>, Error> {
+    let store = TallyRoundsStore { vote_id, topic_id };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_topic_packing(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<Option<TopicPacking>, Error> {
+    let store = PackedTopicStore { vote_id, topic_id };
+    let packing = client.fetch(&store, None).await?;
+    Ok(packing)
+}
+
+/// the number of votes per answer of the slot of a packed topic, see: `get_topic_packing`.
+pub async fn get_packed_tally(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    slot: u32,
+) -> Result<TopicResult, Error> {
+    let store = PackedTallyStore {
+        vote_id,
+        topic_id,
+        slot,
+    };
+    let tally = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(tally)
+}
+
+pub async fn get_tally_progress(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<Option<TallyProgress>, Error> {
+    let store = TallyProgressStore { vote_id, topic_id };
+    let progress = client.fetch(&store, None).await?;
+    Ok(progress)
+}
+
+pub async fn get_partial_tally(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<PartialTally, Error> {
+    let store = PartialTallyStore { vote_id, topic_id };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_public_key_shares(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Vec<PublicKeyShare>, Error> {
+    let store = PublicKeySharesStore { vote_id };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_previous_public_key_share(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    sealer: AccountId,
+) -> Result<Option<PublicKeyShare>, Error> {
+    let store = PreviousPublicKeyShareBySealerStore { vote_id, sealer };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+pub async fn get_key_switch_shares(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    sealer: AccountId,
+) -> Result<Vec<Cipher>, Error> {
+    let store = KeySwitchSharesStore {
+        vote_id,
+        topic_id,
+        sealer,
+    };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+pub async fn get_key_switch_audit(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<Vec<KeySwitchAuditEntry<AccountId>>, Error> {
+    let store = KeySwitchAuditStore { vote_id, topic_id };
+    let value = client.fetch(&store, None).await?.unwrap_or_default();
+    Ok(value)
+}
+
+/// Returns the results of all topics of the vote whose hash is certified, see: `certify_result`.
+pub async fn get_vote_result(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<VoteResult, Error> {
+    get_vote_result_at(client, vote_id, None).await
+}
+
+/// `get_vote_result` as of the block with hash `at` (default: best block).
+pub async fn get_vote_result_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    at: Option<H256>,
+) -> Result<VoteResult, Error> {
+    let topics: Vec<Topic> = get_topics_at(client, vote_id.clone(), at).await?;
+    let mut results = Vec::with_capacity(topics.len());
+    for topic in topics.into_iter() {
+        let tally = get_tally_at(client, topic.id.clone(), at).await?;
+        results.push((topic.id, tally));
+    }
+    Ok(VoteResult { vote_id, results })
+}
+
+pub async fn get_pending_result_approvals(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<ResultApprovals<AccountId>>, Error> {
+    let store = PendingResultApprovalsStore { vote_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns the certified result of the vote, None until the certification quorum is met.
+pub async fn get_certified_result(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<CertifiedResult<AccountId, BlockNumber>>, Error> {
+    let store = CertifiedResultStore { vote_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns the batches of ciphers imported by the voting authorities for the vote, in the order of their import.
+pub async fn get_cipher_imports(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Vec<CipherImport<AccountId, BlockNumber>>, Error> {
+    let store = CipherImportCountStore {
+        vote_id: vote_id.clone(),
+    };
+    let count = client.fetch(&store, None).await?.unwrap_or_default();
+    let mut imports = Vec::with_capacity(count as usize);
+    for index in 0..count {
+        let store = CipherImportStore {
+            vote_id: vote_id.clone(),
+            index,
+        };
+        if let Some(import) = client.fetch(&store, None).await? {
+            imports.push(import);
+        }
+    }
+    Ok(imports)
+}
+
+/// Returns the digest of the certified result (see: `ResultDigest`), None until the result is certified.
+pub async fn get_result_digest(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<ResultDigest<BlockNumber>>, Error> {
+    let store = ResultDigestStore { vote_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+/// Returns the ciphers of the vote at the end of the voting phase (see: `BallotBoxFreeze`),
+/// None until the tallying phase started. Its digest is the context of the shuffle proofs.
+pub async fn get_ballot_box_freeze(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<BallotBoxFreeze<H256, BlockNumber>>, Error> {
+    get_ballot_box_freeze_at(client, vote_id, None).await
+}
+
+/// `get_ballot_box_freeze` as of the block with hash `at` (default: best block).
+pub async fn get_ballot_box_freeze_at(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    at: Option<H256>,
+) -> Result<Option<BallotBoxFreeze<H256, BlockNumber>>, Error> {
+    let store = BallotBoxFreezeStore { vote_id };
+    let value = client.fetch(&store, at).await?;
+    Ok(value)
+}
+
+pub async fn get_archived_vote(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+) -> Result<Option<VoteArchive>, Error> {
+    let store = ArchivedVoteStore { vote_id };
+    let value = client.fetch(&store, None).await?;
+    Ok(value)
+}
+
+pub async fn propose_vote(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    params: PublicParameters,
+    title: Title,
+    vote_id: VoteId,
+    topics: Vec<Topic>,
+    batch_size: u64,
+    nr_of_shuffles: NrOfShuffles,
+    tally_strategy: TallyStrategy,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = ProposeVote {
+        params,
+        title,
+        vote_id,
+        topics,
+        batch_size,
+        nr_of_shuffles,
+        tally_strategy,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn approve_vote(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = ApproveVote { vote_id };
+    return watch(signer, client, call).await;
+}
+
+/// Stores (or replaces) the template of recurring votes as a voting authority.
+pub async fn store_vote_template(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    template_id: VoteTemplateId,
+    template: VoteTemplate,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = StoreVoteTemplate {
+        template_id,
+        template,
+    };
+    return watch(signer, client, call).await;
+}
+
+/// Proposes a vote with the configuration of the template, a fresh vote_id and title.
+pub async fn create_vote_from_template(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    template_id: VoteTemplateId,
+    vote_id: VoteId,
+    title: Title,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = CreateVoteFromTemplate {
+        template_id,
+        vote_id,
+        title,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn store_question(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic: Topic,
+    batch_size: u64,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = StoreQuestion {
+        vote_id,
+        topic,
+        batch_size,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn submit_ballot(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    ballot: Ballot,
+) -> Result<<NodeTemplateRuntime as System>::Hash, Error> {
+    let call = CastBallot { vote_id, ballot };
+    return submit(signer, client, call).await;
+}
+
+/// Submits a ballot signed by the voter (see: `mixnet_primitives::relay`),
+/// the relayer signs the transaction, the ballot is cast by the voter.
+pub async fn submit_relayed_ballot(
+    client: &Client<NodeTemplateRuntime>,
+    relayer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    ballot: Ballot,
+    voter: sr25519::Public,
+    signature: sr25519::Signature,
+) -> Result<<NodeTemplateRuntime as System>::Hash, Error> {
+    let call = CastRelayedBallot {
+        vote_id,
+        ballot,
+        voter,
+        signature,
+    };
+    return submit(relayer, client, call).await;
+}
+
+/// Submits an anonymous ballot with the eligibility token of the one-time key (see: `mixnet_primitives::tokens`)
+/// as an unsigned transaction, i.e., the ballot isn't linked to an account. The ballot must be signed with the one-time key.
+pub async fn submit_anonymous_ballot(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    ballot: Ballot,
+    key: sr25519::Public,
+    signature: Vec<u8>,
+    ballot_signature: sr25519::Signature,
+) -> Result<<NodeTemplateRuntime as System>::Hash, Error> {
+    let call = client.encode(CastAnonymousBallot {
+        vote_id,
+        ballot,
+        key,
+        signature,
+        ballot_signature,
+    })?;
+    let extrinsic = UncheckedExtrinsic::<NodeTemplateRuntime>::new_unsigned(call);
+    return client.submit_extrinsic(extrinsic).await;
+}
+
+pub async fn store_public_key(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    pk: SubstratePK,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = StorePublicKey { vote_id, pk };
+    return watch(signer, client, call).await;
+}
+
+pub async fn store_public_key_share(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    pk_share: PublicKeyShare,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = StorePublicKeyShare { vote_id, pk_share };
+    return watch(signer, client, call).await;
+}
+
+pub async fn set_vote_phase(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    vote_phase: VotePhase,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SetVotePhase {
+        vote_id,
+        vote_phase,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn cancel_vote(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    reason: Vec<u8>,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = CancelVote { vote_id, reason };
+    return watch(signer, client, call).await;
+}
+
+pub async fn combine_pk_shares(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = CombinePublicKeyShares { vote_id };
+    return watch(signer, client, call).await;
+}
+
+pub async fn combine_decrypted_shares(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    encoded: bool,
+    nr_of_shuffles: NrOfShuffles,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = CombineDecryptedShares {
+        vote_id,
+        topic_id,
+        encoded,
+        nr_of_shuffles,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn combine_decrypted_shares_batch(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    encoded: bool,
+    nr_of_shuffles: NrOfShuffles,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = CombineDecryptedSharesBatch {
+        vote_id,
+        topic_id,
+        encoded,
+        nr_of_shuffles,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn finalize_tally(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = FinalizeTally { vote_id, topic_id };
+    return watch(signer, client, call).await;
+}
+
+pub async fn aggregate_ciphers(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = AggregateCiphers { vote_id, topic_id };
+    return watch(signer, client, call).await;
+}
+
+pub async fn submit_partial_decryptions(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    shares: Vec<DecryptedShare>,
+    proof: DecryptedShareProof,
+    nr_of_shuffles: NrOfShuffles,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SubmitPartialDecryption {
+        vote_id,
+        topic_id,
+        shares,
+        proof,
+        nr_of_shuffles,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn rotate_public_key(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = RotatePublicKey { vote_id };
+    return watch(signer, client, call).await;
+}
+
+/// Approves the result hash of a tallied vote, as a voting authority or a sealer.
+pub async fn certify_result(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    result_hash: [u8; 32],
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = CertifyResult {
+        vote_id,
+        result_hash,
+    };
+    return watch(signer, client, call).await;
+}
+
+/// Adds a sealer to the sealers of new votes, as a voting authority.
+pub async fn add_sealer(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    sealer: <NodeTemplateRuntime as System>::AccountId,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = AddSealer { sealer };
+    return watch(signer, client, call).await;
+}
+
+/// Removes a sealer from the sealers of new votes, as a voting authority.
+pub async fn remove_sealer(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    sealer: <NodeTemplateRuntime as System>::AccountId,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = RemoveSealer { sealer };
+    return watch(signer, client, call).await;
+}
+
+pub async fn archive_vote(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = ArchiveVote { vote_id };
+    return watch(signer, client, call).await;
+}
+
+pub async fn set_metadata(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    metadata: Option<VoteMetadata>,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SetMetadata { vote_id, metadata };
+    return watch(signer, client, call).await;
+}
+
+pub async fn set_shuffle_batch_size(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    batch_size: u64,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SetShuffleBatchSize {
+        vote_id,
+        batch_size,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn set_linked_shuffle(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    linked: bool,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SetLinkedShuffle { vote_id, linked };
+    return watch(signer, client, call).await;
+}
+
+pub async fn set_group_check(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    enabled: bool,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SetGroupCheck { vote_id, enabled };
+    return watch(signer, client, call).await;
+}
+
+pub async fn set_topic_packing(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    packing: Option<TopicPacking>,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SetTopicPacking {
+        vote_id,
+        topic_id,
+        packing,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn set_decryption_threshold(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    threshold: u32,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SetDecryptionThreshold { vote_id, threshold };
+    return watch(signer, client, call).await;
+}
+
+pub async fn set_hash_function(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    hash_function: HashFunction,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SetHashFunction {
+        vote_id,
+        hash_function,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn register_credential(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    voter: <NodeTemplateRuntime as System>::AccountId,
+    credential: Cipher,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = RegisterCredential {
+        vote_id,
+        voter,
+        credential,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn submit_credential_blindings(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    blindings: Vec<Cipher>,
+    proofs: Vec<CredentialBlindingProof>,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SubmitCredentialBlindings {
+        vote_id,
+        blindings,
+        proofs,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn submit_credential_decryptions(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    shares: Vec<DecryptedShare>,
+    proof: DecryptedShareProof,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SubmitCredentialDecryptions {
+        vote_id,
+        shares,
+        proof,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn set_identity_provider(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    provider: Option<IdentityProvider>,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SetIdentityProvider { vote_id, provider };
+    return watch(signer, client, call).await;
+}
+
+pub async fn set_eligibility_key(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    key: Option<EligibilityKey>,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SetEligibilityKey { vote_id, key };
+    return watch(signer, client, call).await;
+}
+
+/// Registers the voter by the eligibility claim signed by the identity provider of the vote,
+/// the signer can be any account (e.g. the voter).
+pub async fn register_voter_with_claim(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    voter: <NodeTemplateRuntime as System>::AccountId,
+    signature: ClaimSignature,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = RegisterVoterWithClaim {
+        vote_id,
+        voter,
+        signature,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn revoke_voter(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    voter: <NodeTemplateRuntime as System>::AccountId,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = RevokeVoter { vote_id, voter };
+    return watch(signer, client, call).await;
+}
+
+pub async fn submit_key_switch_shares(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    shares: Vec<Cipher>,
+    proof: KeySwitchShareProof,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SubmitKeySwitchShares {
+        vote_id,
+        topic_id,
+        shares,
+        proof,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn set_voter_weight(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    voter: <NodeTemplateRuntime as System>::AccountId,
+    weight: Option<u32>,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SetVoterWeight {
+        vote_id,
+        voter,
+        weight,
+    };
+    return watch(signer, client, call).await;
+}
+
+/// Imports a batch of ciphers collected off-chain for the topic of the vote as a voting authority.
+pub async fn import_ciphers(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    ciphers: Vec<Cipher>,
+    source_tag: Vec<u8>,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = ImportCiphers {
+        vote_id,
+        topic_id,
+        ciphers,
+        source_tag,
+    };
+    return watch(signer, client, call).await;
+}
+
+/// Submits a batch of ciphers to import without waiting for its inclusion, e.g. to submit
+/// several batches per block with consecutive nonces (see: `PairSigner::set_nonce`).
+pub async fn submit_import_ciphers(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    ciphers: Vec<Cipher>,
+    source_tag: Vec<u8>,
+) -> Result<<NodeTemplateRuntime as System>::Hash, Error> {
+    let call = ImportCiphers {
+        vote_id,
+        topic_id,
+        ciphers,
+        source_tag,
+    };
+    return submit(signer, client, call).await;
+}
+
+pub async fn whitelist_vote(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    whitelisted: bool,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = WhitelistVote {
+        vote_id,
+        whitelisted,
+    };
+    return watch(signer, client, call).await;
+}
+
+pub async fn claim_ballot_deposit(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = ClaimBallotDeposit { vote_id };
+    return watch(signer, client, call).await;
+}
+
+pub async fn spoil_ballot(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SpoilBallot { vote_id, topic_id };
+    return watch(signer, client, call).await;
+}
+
+pub async fn reveal_spoiled_ballot(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    cipher_hash: [u8; 32],
+    reveal: SpoiledBallotReveal,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = RevealSpoiledBallot {
+        vote_id,
+        topic_id,
+        cipher_hash,
+        reveal,
+    };
+    return watch(signer, client, call).await;
+}
+
+/// Commits to a shuffle, the shuffle is usually committed by the offchain worker of the sealer.
+pub async fn commit_shuffle(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    commitment: [u8; 32],
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = CommitShuffle {
+        vote_id,
+        topic_id,
+        commitment,
+    };
+    return watch(signer, client, call).await;
+}
+
+/// Reveals a committed shuffle, the shuffle is usually revealed by the offchain worker of the sealer.
+pub async fn submit_shuffled_votes_and_proof(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    payload: ShufflePayload,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SubmitShuffledVotesAndProof {
+        vote_id,
+        topic_id,
+        payload,
+    };
+    return watch(signer, client, call).await;
+}
+
+/// Reveals a committed linked shuffle of all topics of the vote, usually revealed by the offchain worker of the sealer.
+pub async fn submit_linked_shuffle(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    vote_id: VoteId,
+    payload: LinkedShufflePayload,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = SubmitLinkedShuffle { vote_id, payload };
+    return watch(signer, client, call).await;
+}
+
+/// Signals that the sealer is alive, usually sent by the offchain worker of the sealer.
+pub async fn heartbeat(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = Heartbeat {};
+    return watch(signer, client, call).await;
+}
+
+pub async fn do_nothing_when_its_not_your_turn(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let call = DoNothingWhenItsNotYourTurn {};
+    return watch(signer, client, call).await;
+}
+
+/// Subscribes to all events of the best blocks, the types of the mixnet events are registered to decode them.
+/// The events of a block which is reorged out are not revoked, see: `subscribe_finalized_events`.
+pub async fn subscribe_events(
+    client: &Client<NodeTemplateRuntime>,
+) -> Result<EventSubscription<NodeTemplateRuntime>, Error> {
+    let subscription = client.subscribe_events().await?;
+    Ok(EventSubscription::<NodeTemplateRuntime>::new(
+        subscription,
+        events_decoder(client),
+    ))
+}
+
+/// Subscribes to all events of the finalized blocks (GRANDPA), i.e., the events are durably recorded.
+pub async fn subscribe_finalized_events(
+    client: &Client<NodeTemplateRuntime>,
+) -> Result<EventSubscription<NodeTemplateRuntime>, Error> {
+    let subscription = client.subscribe_finalized_events().await?;
+    Ok(EventSubscription::<NodeTemplateRuntime>::new(
+        subscription,
+        events_decoder(client),
+    ))
+}
+
+/// The decoder of all events, the types of the mixnet events are registered.
+fn events_decoder(client: &Client<NodeTemplateRuntime>) -> EventsDecoder<NodeTemplateRuntime> {
+    let mut decoder = EventsDecoder::<NodeTemplateRuntime>::new(client.metadata().clone());
+    decoder.register_type_size::<VoteId>("VoteId");
+    decoder.register_type_size::<TopicId>("TopicId");
+    decoder.register_type_size::<Ballot>("Ballot");
+    decoder.register_type_size::<Cipher>("Cipher");
+    decoder.register_type_size::<SubstratePK>("SubstratePK");
+    decoder.register_type_size::<PublicParameters>("PublicParameters");
+    decoder.register_type_size::<Topic>("Topic");
+    decoder.register_type_size::<VotePhase>("VotePhase");
+    decoder.register_type_size::<PublicKeyShare>("PublicKeyShare");
+    decoder.register_type_size::<TopicResult>("TopicResult");
+    decoder.register_type_size::<NrOfShuffles>("NrOfShuffles");
+    decoder.register_type_size::<[u8; 32]>("[u8; 32]");
+    decoder.register_type_size::<BlockNumber>("BlockNumber");
+    decoder
+}
+
+/// Submits the call and returns once the extrinsic is included in a best block.
+/// The block may still be reorged out, see: `watch_finalized`.
+/// The call wrappers of this module (e.g. `cast_ballot`) watch their extrinsic in block.
+pub async fn watch_in_block<C: Call<NodeTemplateRuntime> + Send + Sync>(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    call: C,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    watch(signer, client, call).await
+}
+
+/// Submits the call and returns once the block which includes the extrinsic has been finalized
+/// (GRANDPA), i.e., the ballot or the phase change is durably recorded. Accepts every call of
+/// the `calls` module, e.g. `watch_finalized(&client, &signer, CastBallot { vote_id, ballot })`.
+pub async fn watch_finalized<C: Call<NodeTemplateRuntime> + Send + Sync>(
+    client: &Client<NodeTemplateRuntime>,
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    call: C,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    let success = watch(signer, client, call).await?;
+    wait_for_finality(client, success.block).await?;
+    Ok(success)
+}
+
+/// Waits until the block with the hash has been finalized (GRANDPA). Fails if another block
+/// has been finalized at the same height, i.e., the block has been reorged out.
+pub async fn wait_for_finality(
+    client: &Client<NodeTemplateRuntime>,
+    block_hash: H256,
+) -> Result<(), Error> {
+    let block_number = get_block_number_at(client, Some(block_hash)).await?;
+
+    // subscribe before reading the finalized head, i.e., no finalized block is missed
+    let mut finalized_blocks = client.subscribe_finalized_blocks().await?;
+    loop {
+        let finalized_hash = client.finalized_head().await?;
+        if get_block_number_at(client, Some(finalized_hash)).await? >= block_number {
+            break;
+        }
+        finalized_blocks.next().await;
+    }
+
+    let finalized_hash = client
+        .block_hash(Some(block_number.into()))
+        .await?
+        .ok_or("failed to fetch block hash!")?;
+    if finalized_hash != block_hash {
+        return Err(Error::Other(format!(
+            "block {:?} has been reorged out, block {:?} has been finalized instead!",
+            block_hash, finalized_hash
+        )));
+    }
+    Ok(())
+}
+
+async fn watch<C: Call<NodeTemplateRuntime> + Send + Sync>(
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    client: &Client<NodeTemplateRuntime>,
+    call: C,
+) -> Result<ExtrinsicSuccess<NodeTemplateRuntime>, Error> {
+    return client.watch(call, signer).await;
+}
+
+async fn submit<C: Call<NodeTemplateRuntime> + Send + Sync>(
+    signer: &PairSigner<NodeTemplateRuntime, Pair>,
+    client: &Client<NodeTemplateRuntime>,
+    call: C,
+) -> Result<<NodeTemplateRuntime as System>::Hash, Error> {
+    return client.submit(call, signer).await;
+}
@@ -0,0 +1,312 @@
+//! The storage items of the mixnet pallet.
+//!
+//! Every storage item has a `Store` whose fields are the keys of the item. Storage items
+//! declared with an `Option` value return the inner value, `None` is returned by
+//! `Client::fetch` if the item is not set.
+use crate::{AccountId, Balance, BlockNumber};
+use codec::{Decode, Encode};
+use pallet_mixnet::types::{
+    Ballot, BallotBoxFreeze, BallotRate, BallotReceipt, CertifiedResult, ChunkIndex, Cipher,
+    CipherImport, DecryptedShare, DecryptionAuditEntry, EligibilityKey, IdentityProvider,
+    KeySwitchAuditEntry, NrOfShuffles, Nullifier, PartialTally, PendingDecryption, ProofLogEntry,
+    ProtocolConfig, PublicKey as SubstratePK, PublicKeyShare, PublicParameters, ResultApprovals,
+    ResultDigest, ShuffleCommitment, ShufflePayload, ShuffleState, ShuffleTurn, SpoiledBallot,
+    TallyProgress, TallyRound, Topic, TopicId, TopicPacking, TopicResult, Vote, VoteArchive,
+    VoteId, VoteMetadata, VoteProposal, VoteTemplate, VoteTemplateId,
+};
+use substrate_subxt::{
+    sp_core::{storage::StorageKey, H256},
+    Metadata, MetadataError, NodeTemplateRuntime, Store,
+};
+
+const MODULE: &str = "PalletMixnet";
+
+macro_rules! store {
+    // the struct and the parts of the Store implementation which are the same for all items
+    (@impl $(#[$attr:meta])* $name:ident, $field:literal, $returns:ty, { $($key:ident: $key_type:ty),* }, $self:ident, $item:ident => $storage_key:expr) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Eq, Encode, PartialEq)]
+        pub struct $name {
+            $(pub $key: $key_type,)*
+        }
+
+        impl Store<NodeTemplateRuntime> for $name {
+            /// Module name.
+            const MODULE: &'static str = MODULE;
+            /// Field name.
+            const FIELD: &'static str = $field;
+            /// Return type.
+            type Returns = $returns;
+            /// Returns the key prefix for storage maps
+            fn prefix(metadata: &Metadata) -> Result<StorageKey, MetadataError> {
+                Ok(metadata
+                    .module(Self::MODULE)?
+                    .storage(Self::FIELD)?
+                    .prefix())
+            }
+            /// Returns the `StorageKey`.
+            fn key(&$self, metadata: &Metadata) -> Result<StorageKey, MetadataError> {
+                let $item = metadata.module(Self::MODULE)?.storage(Self::FIELD)?;
+                Ok($storage_key)
+            }
+            /// Returns the default value.
+            fn default(&self, metadata: &Metadata) -> Result<Self::Returns, MetadataError> {
+                metadata
+                    .module(Self::MODULE)?
+                    .storage(Self::FIELD)?
+                    .default()
+            }
+        }
+    };
+    // a storage value
+    ($(#[$attr:meta])* $name:ident => plain $field:literal: $returns:ty) => {
+        store!(@impl $(#[$attr])* $name, $field, $returns, {}, self, storage => storage.plain()?.key());
+    };
+    // a storage map, multiple keys are encoded as a tuple
+    ($(#[$attr:meta])* $name:ident { $($key:ident: $key_type:ty),+ } => map $field:literal: $returns:ty) => {
+        store!(@impl $(#[$attr])* $name, $field, $returns, { $($key: $key_type),+ }, self, storage => storage.map()?.key(&($(&self.$key),+)));
+    };
+    // a storage double map whose first key is a tuple
+    ($(#[$attr:meta])* $name:ident { ($($key1:ident: $key1_type:ty),+), $($key2:ident: $key2_type:ty),+ } => double_map $field:literal: $returns:ty) => {
+        store!(@impl $(#[$attr])* $name, $field, $returns, { $($key1: $key1_type),+, $($key2: $key2_type),+ }, self, storage => storage.double_map()?.key(&($(&self.$key1),+), &($(&self.$key2),+)));
+    };
+    // a storage double map, multiple second keys are encoded as a tuple
+    ($(#[$attr:meta])* $name:ident { $key1:ident: $key1_type:ty, $($key2:ident: $key2_type:ty),+ } => double_map $field:literal: $returns:ty) => {
+        store!(@impl $(#[$attr])* $name, $field, $returns, { $key1: $key1_type, $($key2: $key2_type),+ }, self, storage => storage.double_map()?.key(&self.$key1, &($(&self.$key2),+)));
+    };
+}
+
+store!(
+    /// The version of the storage layout.
+    StorageVersionStore => plain "StorageVersion": u32
+);
+store!(VotingAuthoritiesStore => plain "VotingAuthorities": Vec<AccountId>);
+store!(
+    /// The number of voting authorities which need to approve a vote and its tallying phase.
+    ApprovalThresholdStore => plain "ApprovalThreshold": u32
+);
+store!(
+    /// The sealers of new votes.
+    SealersStore => plain "Sealers": Vec<AccountId>
+);
+store!(
+    /// The number of changes of the sealers, i.e. the epoch new votes are created in.
+    SealerEpochStore => plain "SealerEpoch": u32
+);
+store!(
+    /// The sealers of an epoch.
+    SealerSetStore { epoch: u32 } => map "SealerSets": Vec<AccountId>
+);
+store!(
+    /// The epoch a vote has been created in, i.e. the epoch of its sealers.
+    VoteSealerEpochStore { vote_id: VoteId } => map "VoteSealerEpochs": u32
+);
+store!(
+    /// The public parameters proposed for new votes, empty if not configured.
+    DefaultPublicParametersStore => plain "DefaultPublicParameters": PublicParameters
+);
+store!(CountsBySealerStore { sealer: AccountId } => map "CountsBySealer": u32);
+store!(CountsStore => plain "Counts": u32);
+store!(VotersStore => plain "Voters": Vec<AccountId>);
+store!(
+    /// The ids of all votes.
+    VotesStore => plain "VoteIds": Vec<VoteId>
+);
+store!(VoteProposalStore { vote_id: VoteId } => map "VoteProposals": VoteProposal<AccountId>);
+store!(VoteTemplateStore { template_id: VoteTemplateId } => map "VoteTemplates": VoteTemplate);
+store!(TallyingApprovalsStore { vote_id: VoteId } => map "TallyingApprovals": Vec<AccountId>);
+store!(CancellationApprovalsStore { vote_id: VoteId } => map "CancellationApprovals": Vec<AccountId>);
+store!(VoteStore { vote_id: VoteId } => map "Votes": Vote<AccountId>);
+store!(
+    /// The parameters of the protocol a vote has been created with.
+    ProtocolConfigStore { vote_id: VoteId } => map "ProtocolConfigs": ProtocolConfig
+);
+store!(MetadataStore { vote_id: VoteId } => map "Metadata": VoteMetadata);
+store!(ShuffleBatchSizeStore { vote_id: VoteId } => map "ShuffleBatchSizes": u64);
+store!(LinkedShuffleStore { vote_id: VoteId } => map "LinkedShuffles": bool);
+store!(GroupCheckStore { vote_id: VoteId } => map "GroupChecks": bool);
+store!(DecryptionThresholdStore { vote_id: VoteId } => map "DecryptionThresholds": u32);
+store!(VotingStartedStore { vote_id: VoteId } => map "VotingStarted": bool);
+store!(TopicsStore { vote_id: VoteId } => map "Topics": Vec<Topic>);
+store!(BallotStore { vote_id: VoteId, voter: AccountId } => double_map "Ballots": Ballot);
+store!(CipherHashStore { (vote_id: VoteId, topic_id: TopicId), cipher_hash: [u8; 32] } => double_map "CipherHashes": bool);
+store!(
+    /// The block hash of the receipt is not stored on chain, see: `rpc::get_ballot_receipt`.
+    BallotReceiptStore { vote_id: VoteId, account: AccountId } => double_map "BallotReceipts": BallotReceipt<BlockNumber, H256>
+);
+store!(BallotRateStore { account: AccountId } => map "BallotRates": BallotRate<BlockNumber>);
+store!(WhitelistedVoteStore { vote_id: VoteId } => map "WhitelistedVotes": bool);
+store!(
+    /// The sum of the ballot deposits reserved by the voter, refunded once the voting phase has ended.
+    BallotDepositStore { vote_id: VoteId, voter: AccountId } => double_map "BallotDeposits": Balance
+);
+store!(SpoiledBallotStore { (vote_id: VoteId, topic_id: TopicId), cipher_hash: [u8; 32] } => double_map "SpoiledBallots": SpoiledBallot<AccountId>);
+store!(VoterWeightStore { vote_id: VoteId, voter: AccountId } => double_map "VoterWeights": u32);
+store!(
+    /// The sum of the additional weights (weight - 1) of the weighted ciphers of a topic.
+    AdditionalWeightStore { vote_id: VoteId, topic_id: TopicId } => map "AdditionalWeights": u64
+);
+store!(CredentialStore { vote_id: VoteId, voter: AccountId } => double_map "Credentials": Cipher);
+store!(
+    /// The voters whose ballots carry a credential, in the order of the credential check.
+    CredentialBallotsStore { vote_id: VoteId } => map "CredentialBallots": Vec<AccountId>
+);
+store!(CredentialBlindingsStore { vote_id: VoteId, sealer: AccountId } => double_map "CredentialBlindings": Vec<Cipher>);
+store!(CredentialDecryptionsStore { vote_id: VoteId, sealer: AccountId } => double_map "CredentialDecryptions": Vec<DecryptedShare>);
+store!(
+    /// The voters whose ballots have been discarded because of an invalid credential.
+    DiscardedBallotsStore { vote_id: VoteId } => map "DiscardedBallots": Vec<AccountId>
+);
+store!(
+    /// The identity provider which signs the eligibility claims of the voters of a vote.
+    IdentityProviderStore { vote_id: VoteId } => map "IdentityProviders": IdentityProvider
+);
+store!(RegisteredVoterStore { vote_id: VoteId, voter: AccountId } => double_map "RegisteredVoters": bool);
+store!(
+    /// The block number in which a voter has been revoked, see: `rpc::get_voter_revocation`.
+    RevokedVoterStore { vote_id: VoteId, voter: AccountId } => double_map "RevokedVoters": BlockNumber
+);
+store!(
+    /// The key of the voting authority which blind-signs the eligibility tokens of the voters of a vote.
+    EligibilityKeyStore { vote_id: VoteId } => map "EligibilityKeys": EligibilityKey
+);
+store!(SpentNullifierStore { vote_id: VoteId, nullifier: Nullifier } => double_map "SpentNullifiers": bool);
+store!(
+    /// A batch of ciphers imported by a voting authority, see: `rpc::get_cipher_imports`.
+    CipherImportStore { vote_id: VoteId, index: u32 } => double_map "CipherImports": CipherImport<AccountId, BlockNumber>
+);
+store!(CipherImportCountStore { vote_id: VoteId } => map "CipherImportCount": u32);
+store!(
+    /// A chunk of the ciphers, see: `rpc::get_ciphers`.
+    CiphersStore { (vote_id: VoteId, topic_id: TopicId), nr_of_shuffles: NrOfShuffles, chunk_index: ChunkIndex } => double_map "Ciphers": Vec<Cipher>
+);
+store!(CipherChunkCountStore { (vote_id: VoteId, topic_id: TopicId), nr_of_shuffles: NrOfShuffles } => double_map "CipherChunkCount": ChunkIndex);
+store!(CipherCountStore { (vote_id: VoteId, topic_id: TopicId), nr_of_shuffles: NrOfShuffles } => double_map "CipherCounts": u64);
+store!(CipherRootStore { (vote_id: VoteId, topic_id: TopicId), nr_of_shuffles: NrOfShuffles } => double_map "CipherRoots": [u8; 32]);
+store!(CipherPeaksStore { (vote_id: VoteId, topic_id: TopicId), nr_of_shuffles: NrOfShuffles } => double_map "CipherPeaks": Vec<Option<[u8; 32]>>);
+store!(ShuffleProofsStore { vote_id: VoteId, topic_id: TopicId } => map "ShuffleProofs": Vec<ShufflePayload>);
+store!(ShuffleCommitmentStore { vote_id: VoteId, topic_id: TopicId } => map "ShuffleCommitments": ShuffleCommitment<AccountId, BlockNumber>);
+store!(ShuffleTurnStore { vote_id: VoteId, topic_id: TopicId } => map "ShuffleTurns": ShuffleTurn<BlockNumber>);
+store!(ShuffleStateStore { vote_id: VoteId, topic_id: TopicId } => map "ShuffleStateStore": ShuffleState);
+store!(AggregateCipherStore { vote_id: VoteId, topic_id: TopicId } => map "AggregateCiphers": Cipher);
+store!(TallyStore { vote_id: VoteId, topic_id: TopicId } => map "Tally": TopicResult);
+store!(TallyRoundsStore { vote_id: VoteId, topic_id: TopicId } => map "TallyRounds": Vec<TallyRound>);
+store!(
+    /// The slots of a topic whose answers are packed into a single cipher, see: `ElGamal::pack`.
+    PackedTopicStore { vote_id: VoteId, topic_id: TopicId } => map "PackedTopics": TopicPacking
+);
+store!(PackedTallyStore { (vote_id: VoteId, topic_id: TopicId), slot: u32 } => double_map "PackedTally": TopicResult);
+store!(TallyProgressStore { vote_id: VoteId, topic_id: TopicId } => map "TallyProgresses": TallyProgress);
+store!(PartialTallyStore { vote_id: VoteId, topic_id: TopicId } => map "PartialTallies": PartialTally);
+store!(DecryptedSharesStore { (vote_id: VoteId, topic_id: TopicId), sealer: AccountId } => double_map "DecryptedShares": Vec<DecryptedShare>);
+store!(DecryptionAuditStore { (vote_id: VoteId, topic_id: TopicId), nr_of_shuffles: NrOfShuffles } => double_map "DecryptionAudit": Vec<DecryptionAuditEntry<AccountId>>);
+store!(
+    /// The transcript hashes of all accepted shuffle and decryption proofs of a vote, kept when the vote is archived.
+    ProofLogStore { vote_id: VoteId } => map "ProofLog": Vec<ProofLogEntry<AccountId, BlockNumber>>
+);
+store!(PendingDecryptionStore { vote_id: VoteId, topic_id: TopicId } => map "PendingDecryptions": PendingDecryption<BlockNumber>);
+store!(
+    /// The votes in the tallying phase which haven't been tallied yet, the sealers send heartbeats while there are any.
+    MonitoredVotesStore => plain "MonitoredVotes": Vec<VoteId>
+);
+store!(LastHeartbeatStore { sealer: AccountId } => map "LastHeartbeats": BlockNumber);
+store!(HeartbeatDeadlineStore { sealer: AccountId } => map "HeartbeatDeadlines": BlockNumber);
+store!(PublicKeySharesStore { vote_id: VoteId } => map "PublicKeyShares": Vec<PublicKeyShare>);
+store!(PublicKeyShareBySealerStore { vote_id: VoteId, sealer: AccountId } => map "PublicKeyShareBySealer": PublicKeyShare);
+store!(PublicKeyStore { vote_id: VoteId } => map "PublicKey": SubstratePK);
+store!(PreviousPublicKeyShareBySealerStore { vote_id: VoteId, sealer: AccountId } => map "PreviousPublicKeyShareBySealer": PublicKeyShare);
+store!(PendingKeySwitchStore { vote_id: VoteId } => map "PendingKeySwitch": Vec<TopicId>);
+store!(KeySwitchSharesStore { (vote_id: VoteId, topic_id: TopicId), sealer: AccountId } => double_map "KeySwitchShares": Vec<Cipher>);
+store!(KeySwitchAuditStore { vote_id: VoteId, topic_id: TopicId } => map "KeySwitchAudit": Vec<KeySwitchAuditEntry<AccountId>>);
+store!(PendingResultApprovalsStore { vote_id: VoteId } => map "PendingResultApprovals": ResultApprovals<AccountId>);
+store!(
+    /// The result of the vote approved by a voting authority and sufficient sealers, it is final.
+    CertifiedResultStore { vote_id: VoteId } => map "CertifiedResults": CertifiedResult<AccountId, BlockNumber>
+);
+store!(
+    /// The digest of the certified result which other chains can consume.
+    ResultDigestStore { vote_id: VoteId } => map "ResultDigests": ResultDigest<BlockNumber>
+);
+store!(
+    /// The ciphers of the vote at the end of the voting phase, the context of its shuffle proofs.
+    BallotBoxFreezeStore { vote_id: VoteId } => map "BallotBoxFreezes": BallotBoxFreeze<H256, BlockNumber>
+);
+store!(ArchivedVoteStore { vote_id: VoteId } => map "ArchivedVotes": VoteArchive);
+
+/// The number of the current block, i.e. the `Number` of the system module
+/// (not part of the mixnet pallet, therefore not declared with `store!`).
+#[derive(Clone, Debug, Eq, Encode, PartialEq)]
+pub struct BlockNumberStore {}
+
+impl Store<NodeTemplateRuntime> for BlockNumberStore {
+    /// Module name.
+    const MODULE: &'static str = "System";
+    /// Field name.
+    const FIELD: &'static str = "Number";
+    /// Return type.
+    type Returns = BlockNumber;
+    /// Returns the key prefix for storage maps
+    fn prefix(metadata: &Metadata) -> Result<StorageKey, MetadataError> {
+        Ok(metadata
+            .module(Self::MODULE)?
+            .storage(Self::FIELD)?
+            .prefix())
+    }
+    /// Returns the `StorageKey`.
+    fn key(&self, metadata: &Metadata) -> Result<StorageKey, MetadataError> {
+        let storage = metadata.module(Self::MODULE)?.storage(Self::FIELD)?;
+        Ok(storage.plain()?.key())
+    }
+    /// Returns the default value.
+    fn default(&self, metadata: &Metadata) -> Result<Self::Returns, MetadataError> {
+        metadata
+            .module(Self::MODULE)?
+            .storage(Self::FIELD)?
+            .default()
+    }
+}
+
+/// The weight of the extrinsics of a block per dispatch class (`frame_system::ExtrinsicsWeight`).
+#[derive(Clone, Debug, Default, Decode, Eq, PartialEq)]
+pub struct ExtrinsicsWeight {
+    pub normal: u64,
+    pub operational: u64,
+}
+
+impl ExtrinsicsWeight {
+    pub fn total(&self) -> u64 {
+        self.normal.saturating_add(self.operational)
+    }
+}
+
+/// The weight of the current block, i.e. the `BlockWeight` of the system module.
+#[derive(Clone, Debug, Eq, Encode, PartialEq)]
+pub struct BlockWeightStore {}
+
+impl Store<NodeTemplateRuntime> for BlockWeightStore {
+    /// Module name.
+    const MODULE: &'static str = "System";
+    /// Field name.
+    const FIELD: &'static str = "BlockWeight";
+    /// Return type.
+    type Returns = ExtrinsicsWeight;
+    /// Returns the key prefix for storage maps
+    fn prefix(metadata: &Metadata) -> Result<StorageKey, MetadataError> {
+        Ok(metadata
+            .module(Self::MODULE)?
+            .storage(Self::FIELD)?
+            .prefix())
+    }
+    /// Returns the `StorageKey`.
+    fn key(&self, metadata: &Metadata) -> Result<StorageKey, MetadataError> {
+        let storage = metadata.module(Self::MODULE)?.storage(Self::FIELD)?;
+        Ok(storage.plain()?.key())
+    }
+    /// Returns the default value.
+    fn default(&self, metadata: &Metadata) -> Result<Self::Returns, MetadataError> {
+        metadata
+            .module(Self::MODULE)?
+            .storage(Self::FIELD)?
+            .default()
+    }
+}
@@ -1,3 +1,4 @@
+use crate::output::OutputFormat;
 use clap::Clap;
 
 /// The Provotum CLI to impersonate voters, the voting-authority and sealers
@@ -8,6 +9,9 @@ use clap::Clap;
     author = "Moritz Eck <moritz.eck@gmail.com>"
 )]
 pub struct Opts {
+    /// The output format: text or json (a single JSON object per subcommand, without progress bars)
+    #[clap(long, default_value = "text", possible_values = &["text", "json"])]
+    pub output: OutputFormat,
     #[clap(subcommand)]
     pub subcmd: SubCommand,
 }
@@ -20,11 +24,76 @@ pub enum SubCommand {
     VotingAuthority(VotingAuthority),
     #[clap(name = "sealer")]
     Sealer(Sealer),
+    #[clap(name = "simulate")]
+    Simulate(Simulate),
+    #[clap(name = "export_transcript")]
+    ExportTranscript(ExportTranscript),
+}
+
+/// A subcommand to export the transcript of a vote, it can be audited with the provotum-verifier
+#[derive(Clap, Debug)]
+pub struct ExportTranscript {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The path to write the transcript (JSON) to
+    #[clap(short, long, default_value = "transcript.json")]
+    pub output: String,
+    /// The block to read the transcript at, a block number or a 0x prefixed block hash
+    /// (default: best block). The state of old blocks is only kept by archive nodes.
+    #[clap(long)]
+    pub at: Option<String>,
+}
+
+/// A subcommand to simulate a complete election against a dev node and verify its result
+#[derive(Clap, Debug)]
+pub struct Simulate {
+    /// The number of voters to register
+    #[clap(long, default_value = "5")]
+    pub nr_of_voters: usize,
+    /// The number of random ballots to cast (the voters take turns)
+    #[clap(long, default_value = "10")]
+    pub nr_of_ballots: usize,
+    /// The set of allowed votes
+    #[clap(long)]
+    pub votes: Vec<u32>,
 }
 
 /// A subcommand for controlling the Voter
 #[derive(Clap, Debug)]
 pub struct Voter {
+    /// The voter subcommands
+    #[clap(subcommand)]
+    pub subcmd: VoterSubCommand,
+}
+
+#[derive(Clap, Debug)]
+pub enum VoterSubCommand {
+    #[clap(name = "vote")]
+    CreateVotes(CreateVotes),
+    #[clap(name = "bulk")]
+    BulkVotes(BulkVotes),
+    #[clap(name = "cast")]
+    CastBallot(CastBallot),
+    #[clap(name = "relay")]
+    RelayBallot(RelayBallot),
+    #[clap(name = "verify_encryption")]
+    VerifyEncryption(VerifyEncryption),
+    #[clap(name = "receipt")]
+    Receipt(Receipt),
+    #[clap(name = "fake_credential")]
+    FakeCredential(FakeCredential),
+    #[clap(name = "claim")]
+    ClaimDeposit(ClaimDeposit),
+    #[clap(name = "request_token")]
+    RequestToken(RequestToken),
+    #[clap(name = "cast_anonymous")]
+    CastAnonymousBallot(CastAnonymousBallot),
+}
+
+/// A subcommand for creating random votes
+#[derive(Clap, Debug)]
+pub struct CreateVotes {
     /// The id of the vote
     #[clap(short, long)]
     pub vote: String,
@@ -39,6 +108,156 @@ pub struct Voter {
     pub votes: Vec<u32>,
 }
 
+/// A subcommand to simulate many voters casting their votes according to a distribution
+#[derive(Clap, Debug)]
+pub struct BulkVotes {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The id of the question
+    #[clap(short, long)]
+    pub question: String,
+    /// The number of ballots to cast, one per voter (//0, //1, ...)
+    #[clap(long)]
+    pub nr_of_ballots: usize,
+    /// The set of allowed votes
+    #[clap(long, required = true, min_values = 1)]
+    pub votes: Vec<u32>,
+    /// The distribution of the votes: uniform, a weight per vote (e.g. 60,40) or zipf:<exponent>
+    #[clap(long, default_value = "uniform")]
+    pub distribution: String,
+    /// The number of ballots which are submitted in parallel
+    #[clap(long, default_value = "4")]
+    pub concurrency: usize,
+    /// Wait until the question is tallied and compare the tally with the submitted votes
+    #[clap(long)]
+    pub verify: bool,
+}
+
+/// A subcommand to cast a single vote, the encryption can be challenged before submission
+#[derive(Clap, Debug)]
+pub struct CastBallot {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The id of the question
+    #[clap(short, long)]
+    pub question: String,
+    /// The index of the voter
+    #[clap(long)]
+    pub voter: usize,
+    /// The vote to cast
+    #[clap(long)]
+    pub answer: u32,
+    /// The seed (hex) to derive the randomness from, random values are used if not provided
+    #[clap(long)]
+    pub seed: Option<String>,
+    /// The first attempt of the derivation, must be increased when casting another ballot
+    #[clap(long, default_value = "0")]
+    pub attempt: u32,
+    /// Print the ballot signed by the voter as compact text (QR code) instead of submitting it
+    #[clap(long)]
+    pub compact: bool,
+    /// Wait until the block which includes the ballot has been finalized
+    #[clap(long)]
+    pub finalized: bool,
+    /// The credential (hex) issued by the voting authority or a fake credential,
+    /// required if a credential has been registered for the voter
+    #[clap(long)]
+    pub credential: Option<String>,
+}
+
+/// A subcommand to submit a compact ballot on behalf of the voter which signed it
+#[derive(Clap, Debug)]
+pub struct RelayBallot {
+    /// The compact ballot (base45), as printed by `cast --compact`
+    #[clap(long)]
+    pub ballot: String,
+    /// The index of the relayer which submits the ballot
+    #[clap(long)]
+    pub relayer: usize,
+}
+
+/// A subcommand to re-derive the randomness of a cast vote from the seed and verify its encryption
+#[derive(Clap, Debug)]
+pub struct VerifyEncryption {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The id of the question
+    #[clap(short, long)]
+    pub question: String,
+    /// The index of the voter
+    #[clap(long)]
+    pub voter: usize,
+    /// The vote which was cast
+    #[clap(long)]
+    pub answer: u32,
+    /// The seed (hex) the randomness was derived from
+    #[clap(long)]
+    pub seed: String,
+    /// The attempt of the derivation, as printed when casting the vote
+    #[clap(long, default_value = "0")]
+    pub attempt: u32,
+}
+
+/// A subcommand to fetch the receipt of a voter's ballot
+#[derive(Clap, Debug)]
+pub struct Receipt {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The index of the voter (as used when creating the votes)
+    #[clap(long)]
+    pub voter: usize,
+}
+
+/// A subcommand to claim the ballot deposits of a voter once the voting phase has ended
+#[derive(Clap, Debug)]
+pub struct ClaimDeposit {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The index of the voter (as used when creating the votes)
+    #[clap(long)]
+    pub voter: usize,
+}
+
+/// A subcommand to draw an eligibility token and print its blinded message for the voting authority
+#[derive(Clap, Debug)]
+pub struct RequestToken {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The path to write the secret token (JSON) to
+    #[clap(short, long, default_value = "token.json")]
+    pub output: String,
+}
+
+/// A subcommand to cast an anonymous ballot with an eligibility token blind-signed by the voting authority
+#[derive(Clap, Debug)]
+pub struct CastAnonymousBallot {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The id of the question
+    #[clap(short, long)]
+    pub question: String,
+    /// The vote to cast
+    #[clap(long)]
+    pub answer: u32,
+    /// The path to the secret token, as written by `request_token`
+    #[clap(long, default_value = "token.json")]
+    pub token: String,
+    /// The blind signature (hex) of the voting authority, as printed by `va sign_token`
+    #[clap(long)]
+    pub signature: String,
+}
+
+/// A subcommand to create a fake credential, e.g., to hand it to a coercer
+#[derive(Clap, Debug)]
+pub struct FakeCredential {}
+
 /// A subcommand for controlling the Voting Authority
 #[derive(Clap, Debug)]
 pub struct VotingAuthority {
@@ -53,14 +272,64 @@ pub enum VASubCommand {
     SetupVote(SetupVote),
     #[clap(name = "store_question")]
     StoreQuestion(StoreQuestion),
+    #[clap(name = "approve")]
+    ApproveVote(ApproveVote),
     #[clap(name = "set_phase")]
     SetVotePhase(SetVotePhase),
     #[clap(name = "combine_pk_shares")]
     CombinePublicKeyShares(CombinePublicKeyShares),
+    #[clap(name = "rotate_key")]
+    RotateKey(RotateKey),
+    #[clap(name = "aggregate_question")]
+    AggregateQuestion(AggregateQuestion),
     #[clap(name = "tally_question")]
     TallyQuestion(TallyQuestion),
     #[clap(name = "result")]
     GetResult(GetResult),
+    #[clap(name = "certify")]
+    CertifyResult(CertifyResult),
+    #[clap(name = "archive")]
+    ArchiveVote(ArchiveVote),
+    #[clap(name = "cancel")]
+    CancelVote(CancelVote),
+    #[clap(name = "issue_credential")]
+    IssueCredential(IssueCredential),
+    #[clap(name = "set_metadata")]
+    SetMetadata(SetMetadata),
+    #[clap(name = "set_batch_size")]
+    SetShuffleBatchSize(SetShuffleBatchSize),
+    #[clap(name = "set_threshold")]
+    SetDecryptionThreshold(SetDecryptionThreshold),
+    #[clap(name = "set_hash")]
+    SetHashFunction(SetHashFunction),
+    #[clap(name = "link_shuffles")]
+    SetLinkedShuffle(SetLinkedShuffle),
+    #[clap(name = "check_group")]
+    SetGroupCheck(SetGroupCheck),
+    #[clap(name = "pack_question")]
+    SetTopicPacking(SetTopicPacking),
+    #[clap(name = "whitelist")]
+    WhitelistVote(WhitelistVote),
+    #[clap(name = "set_weight")]
+    SetVoterWeight(SetVoterWeight),
+    #[clap(name = "revoke_voter")]
+    RevokeVoter(RevokeVoter),
+    #[clap(name = "add_sealer")]
+    AddSealer(ChangeSealer),
+    #[clap(name = "remove_sealer")]
+    RemoveSealer(ChangeSealer),
+    #[clap(name = "set_eligibility_key")]
+    SetEligibilityKey(SetEligibilityKey),
+    #[clap(name = "sign_token")]
+    SignToken(SignToken),
+    #[clap(name = "import_ciphers")]
+    ImportCiphers(ImportCiphers),
+    #[clap(name = "save_template")]
+    SaveTemplate(SaveTemplate),
+    #[clap(name = "from_template")]
+    CreateFromTemplate(CreateFromTemplate),
+    #[clap(name = "watch")]
+    Watch(WatchVotes),
 }
 
 /// A subcommand for setting up the vote
@@ -72,6 +341,12 @@ pub struct SetupVote {
     /// The question to store
     #[clap(short, long)]
     pub question: String,
+    /// The number of required shuffle iterations (mixing rounds)
+    #[clap(long, default_value = "3")]
+    pub nr_of_shuffles: u8,
+    /// The tally strategy, homomorphic tallying is only suited for yes/no questions
+    #[clap(long, default_value = "Mixnet", possible_values = &["Mixnet", "Homomorphic"])]
+    pub tally_strategy: String,
 }
 
 /// A subcommand for setting up vote questions
@@ -83,6 +358,18 @@ pub struct StoreQuestion {
     /// The question to store
     #[clap(short, long)]
     pub question: String,
+    /// The candidates of the question, a question without candidates accepts any answer
+    #[clap(long, min_values = 1)]
+    pub candidates: Vec<String>,
+    /// The type of the question, only questions with candidates can be MultiSelect or Ranked
+    #[clap(long, default_value = "SingleChoice", possible_values = &["SingleChoice", "MultiSelect", "Ranked"])]
+    pub question_type: String,
+    /// The number of candidates a voter may select (MultiSelect) or has to rank (Ranked)
+    #[clap(long, default_value = "1")]
+    pub limit: u32,
+    /// The tally method of a Ranked question
+    #[clap(long, default_value = "FirstPreference", possible_values = &["FirstPreference", "Borda", "InstantRunoff"])]
+    pub ranked_tally: String,
 }
 
 /// A subcommand for changing the vote phase
@@ -94,6 +381,20 @@ pub struct SetVotePhase {
     /// The vote phase
     #[clap(short, long, possible_values = &["KeyGeneration", "Voting", "Tallying"])]
     pub phase: String,
+    /// The seed of the voting authority, e.g. //Alice
+    #[clap(long, default_value = "//Alice")]
+    pub authority: String,
+}
+
+/// A subcommand to approve a proposed vote
+#[derive(Clap, Debug)]
+pub struct ApproveVote {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The seed of the voting authority, e.g. //Bob
+    #[clap(long, default_value = "//Alice")]
+    pub authority: String,
 }
 
 /// A subcommand to combine the public key shares
@@ -104,6 +405,299 @@ pub struct CombinePublicKeyShares {
     pub vote: String,
 }
 
+/// A subcommand to rotate the public key of a vote
+#[derive(Clap, Debug)]
+pub struct RotateKey {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+}
+
+/// A subcommand to certify the result of a tallied vote
+#[derive(Clap, Debug)]
+pub struct CertifyResult {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The seed of the voting authority, e.g. //Bob
+    #[clap(long, default_value = "//Alice")]
+    pub authority: String,
+}
+
+/// A subcommand to archive a tallied or cancelled vote
+#[derive(Clap, Debug)]
+pub struct ArchiveVote {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+}
+
+/// A subcommand to approve the cancellation of a vote
+#[derive(Clap, Debug)]
+pub struct CancelVote {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The reason of the cancellation, at most 256 bytes
+    #[clap(short, long)]
+    pub reason: String,
+    /// The seed of the voting authority, e.g. //Bob
+    #[clap(long, default_value = "//Alice")]
+    pub authority: String,
+}
+
+/// A subcommand to set or remove the metadata of a vote (descriptions, translations, candidate bios)
+#[derive(Clap, Debug)]
+pub struct SetMetadata {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The IPFS hash (CID) of the metadata document
+    #[clap(long, conflicts_with = "file")]
+    pub ipfs: Option<String>,
+    /// The path of a JSON metadata document which is stored on-chain
+    #[clap(long)]
+    pub file: Option<String>,
+}
+
+/// A subcommand to set the number of ciphers the sealers shuffle per batch
+#[derive(Clap, Debug)]
+pub struct SetShuffleBatchSize {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The number of ciphers per shuffle batch
+    #[clap(short, long)]
+    pub batch_size: u64,
+}
+
+/// A subcommand to shuffle all topics of a vote with the same permutation (linked shuffles)
+#[derive(Clap, Debug)]
+pub struct SetLinkedShuffle {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// Shuffle the topics of the vote independently again
+    #[clap(long)]
+    pub disable: bool,
+}
+
+/// A subcommand to only accept ballots whose ciphers are elements of the group of the vote
+#[derive(Clap, Debug)]
+pub struct SetGroupCheck {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// Accept ballots without checking the group membership of their ciphers again
+    #[clap(long)]
+    pub disable: bool,
+}
+
+/// A subcommand to pack the answers of several free-form questions into a single cipher
+#[derive(Clap, Debug)]
+pub struct SetTopicPacking {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The id of the question
+    #[clap(short, long)]
+    pub question: String,
+    /// The number of packed answers per cipher, omit it to encrypt a single answer per cipher again
+    #[clap(short, long)]
+    pub slots: Option<u32>,
+    /// The width of a packed answer in bits
+    #[clap(long, default_value = "8")]
+    pub slot_bits: u32,
+}
+
+/// A subcommand to set the number of sealers whose decrypted shares suffice to tally the vote
+#[derive(Clap, Debug)]
+pub struct SetDecryptionThreshold {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The number of sealers required to decrypt
+    #[clap(short, long)]
+    pub threshold: u32,
+}
+
+/// A subcommand to set the hash function of the challenges of the proofs of a vote
+#[derive(Clap, Debug)]
+pub struct SetHashFunction {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The hash function, can only be changed before the sealers submit their public key shares
+    #[clap(long, default_value = "Blake2b", possible_values = &["Blake2b", "Sha256", "Sha3_256"])]
+    pub hash_function: String,
+}
+
+/// A subcommand to exempt the voters of a vote from the ballot deposit
+#[derive(Clap, Debug)]
+pub struct WhitelistVote {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// Remove the vote from the whitelist, i.e., require the ballot deposit again
+    #[clap(long)]
+    pub remove: bool,
+}
+
+/// A subcommand to add or remove a sealer, the change only applies to votes created afterwards
+#[derive(Clap, Debug)]
+pub struct ChangeSealer {
+    /// The seed of the sealer, e.g. //Dave
+    #[clap(short, long)]
+    pub sealer: String,
+    /// The seed of the voting authority, e.g. //Bob
+    #[clap(long, default_value = "//Alice")]
+    pub authority: String,
+}
+
+/// A subcommand to generate and set the key which blind-signs the eligibility tokens of a vote,
+/// the vote only accepts anonymous ballots afterwards
+#[derive(Clap, Debug)]
+pub struct SetEligibilityKey {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The path to write the key pair (JSON) to, it must be kept secret
+    #[clap(short, long, default_value = "eligibility_key.json")]
+    pub output: String,
+    /// The size of the RSA modulus in bits
+    #[clap(long, default_value = "3072")]
+    pub bits: u64,
+    /// Remove the key, i.e., accept the ballots of accounts again
+    #[clap(long)]
+    pub remove: bool,
+}
+
+/// A subcommand to blind-sign the eligibility token of a voter (offline)
+#[derive(Clap, Debug)]
+pub struct SignToken {
+    /// The path to the key pair, as written by `set_eligibility_key`
+    #[clap(short, long, default_value = "eligibility_key.json")]
+    pub key: String,
+    /// The blinded token message (hex), as printed by `voter request_token`
+    #[clap(long)]
+    pub blinded: String,
+}
+
+/// A subcommand to import ciphers collected off-chain (e.g. postal ballots) in the voting phase
+#[derive(Clap, Debug)]
+pub struct ImportCiphers {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The id of the question
+    #[clap(short, long)]
+    pub question: String,
+    /// The path of a JSON array of the ciphers to import
+    #[clap(short, long)]
+    pub file: String,
+    /// The collection system the ciphers stem from, e.g. postal
+    #[clap(short, long)]
+    pub source: String,
+}
+
+/// A subcommand to store the configuration of a vote as the template of recurring votes
+#[derive(Clap, Debug)]
+pub struct SaveTemplate {
+    /// The id of the template
+    #[clap(short, long)]
+    pub template: String,
+    /// The id of the vote whose questions, parameters and tally strategy are stored
+    #[clap(short, long)]
+    pub vote: String,
+}
+
+/// A subcommand to propose a vote with the configuration of a template
+#[derive(Clap, Debug)]
+pub struct CreateFromTemplate {
+    /// The id of the template
+    #[clap(short, long)]
+    pub template: String,
+    /// The id of the new vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The title of the new vote
+    #[clap(long)]
+    pub title: String,
+}
+
+/// A subcommand to monitor all votes which haven't been tallied yet and alert when they stall
+#[derive(Clap, Debug)]
+pub struct WatchVotes {
+    /// The number of blocks without progress after which a vote is stalled
+    #[clap(long, default_value = "20")]
+    pub stall_blocks: u32,
+    /// The interval in which the votes are checked in milliseconds
+    #[clap(long, default_value = "6000")]
+    pub interval: u64,
+    /// The URL the alerts are posted to (JSON)
+    #[clap(long)]
+    pub webhook: Option<String>,
+    /// Changes the phase of a vote once the block is reached, e.g. TestVote:Tallying:1200
+    #[clap(long)]
+    pub advance: Vec<String>,
+    /// Stop at the first alert and exit with code 1
+    #[clap(long)]
+    pub exit_on_alert: bool,
+    /// Only watch the finalized blocks, the phase advances wait for their finalization
+    #[clap(long)]
+    pub finalized: bool,
+    /// The seed of the voting authority which advances the phases, e.g. //Alice
+    #[clap(long, default_value = "//Alice")]
+    pub authority: String,
+}
+
+/// A subcommand to set the weight of a voter (weighted voting, homomorphic tally only)
+#[derive(Clap, Debug)]
+pub struct SetVoterWeight {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The index of the voter (as used when creating the votes)
+    #[clap(long)]
+    pub voter: usize,
+    /// The weight of the voter, the weight is removed if omitted
+    #[clap(short, long)]
+    pub weight: Option<u32>,
+}
+
+/// A subcommand to revoke a voter, the ciphers of its ballot are excluded from the tally
+#[derive(Clap, Debug)]
+pub struct RevokeVoter {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The index of the voter (as used when creating the votes)
+    #[clap(long)]
+    pub voter: usize,
+}
+
+/// A subcommand to issue a credential to a voter and register it on-chain
+#[derive(Clap, Debug)]
+pub struct IssueCredential {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The index of the voter (as used when creating the votes)
+    #[clap(long)]
+    pub voter: usize,
+}
+
+/// A subcommand to homomorphically add all ciphers of a question
+#[derive(Clap, Debug)]
+pub struct AggregateQuestion {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The id of the question
+    #[clap(short, long)]
+    pub question: String,
+}
+
 /// A subcommand to combine the decrypted shares for a question
 #[derive(Clap, Debug)]
 pub struct TallyQuestion {
@@ -113,11 +707,17 @@ pub struct TallyQuestion {
     /// The id of the question
     #[clap(short, long)]
     pub question: String,
+    /// The maximum time (in seconds) to decode the homomorphic tally, cancels the tally if exceeded
+    #[clap(long)]
+    pub timeout: Option<u64>,
 }
 
 /// A subcommand to fetch result for a question
 #[derive(Clap, Debug)]
 pub struct GetResult {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
     /// The id of the question
     #[clap(short, long)]
     pub question: String,
@@ -135,8 +735,22 @@ pub struct Sealer {
 pub enum SealerSubCommand {
     #[clap(name = "keygen")]
     KeyGeneration(KeyGeneration),
+    #[clap(name = "submit_share")]
+    SubmitShare(SubmitShare),
     #[clap(name = "decrypt")]
     PartialDecryption(PartialDecryption),
+    #[clap(name = "switch_key")]
+    SwitchKey(SwitchKey),
+    #[clap(name = "check_credentials")]
+    CheckCredentials(CheckCredentials),
+    #[clap(name = "certify")]
+    ApproveResult(ApproveResult),
+    #[clap(name = "watch")]
+    Watch(Watch),
+    #[clap(name = "export_share")]
+    ExportShare(ExportShare),
+    #[clap(name = "recover_share")]
+    RecoverShare(RecoverShare),
 }
 
 /// A subcommand for controlling the key generation
@@ -151,6 +765,26 @@ pub struct KeyGeneration {
     /// The name of the sealer to use
     #[clap(short, long, required = true, possible_values = &["bob", "charlie"])]
     pub who: String,
+    /// Write the signed public key share to a file instead of submitting it (air-gapped key generation)
+    #[clap(long)]
+    pub offline: bool,
+    /// The file to write the signed public key share to
+    #[clap(short, long, default_value = "key_share.json")]
+    pub output: String,
+    /// The hash function of the vote (offline only, otherwise it is read from the chain)
+    #[clap(long, default_value = "Blake2b", possible_values = &["Blake2b", "Sha256", "Sha3_256"])]
+    pub hash_function: String,
+}
+
+/// A subcommand to submit a public key share created with keygen --offline
+#[derive(Clap, Debug)]
+pub struct SubmitShare {
+    /// The file containing the signed public key share
+    #[clap(short, long)]
+    pub file: String,
+    /// The name of the sealer to use
+    #[clap(short, long, required = true, possible_values = &["bob", "charlie"])]
+    pub who: String,
 }
 
 /// A subcommand for controlling the partial decryption
@@ -169,3 +803,76 @@ pub struct PartialDecryption {
     #[clap(short, long, required = true, possible_values = &["bob", "charlie"])]
     pub who: String,
 }
+
+/// A subcommand to switch the ciphers of a vote to its new public key after a key rotation
+#[derive(Clap, Debug)]
+pub struct SwitchKey {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The private key of the rotated public key as string
+    #[clap(short, long)]
+    pub sk: String,
+    /// The name of the sealer to use
+    #[clap(short, long, required = true, possible_values = &["bob", "charlie"])]
+    pub who: String,
+}
+
+/// A subcommand to blind (first call) or decrypt (second call) the credentials of the ballots
+/// once the tallying phase has started, the ballots with an invalid credential are discarded
+#[derive(Clap, Debug)]
+pub struct CheckCredentials {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The private key as string
+    #[clap(short, long)]
+    pub sk: String,
+    /// The name of the sealer to use
+    #[clap(short, long, required = true, possible_values = &["bob", "charlie"])]
+    pub who: String,
+}
+
+/// A subcommand to certify the result of a tallied vote as a sealer
+#[derive(Clap, Debug)]
+pub struct ApproveResult {
+    /// The id of the vote
+    #[clap(short, long)]
+    pub vote: String,
+    /// The name of the sealer to use
+    #[clap(short, long, required = true, possible_values = &["bob", "charlie"])]
+    pub who: String,
+}
+
+/// A subcommand to automatically perform the key generation and partial decryption
+#[derive(Clap, Debug)]
+pub struct Watch {
+    /// The private key as string
+    #[clap(short, long)]
+    pub sk: String,
+    /// The name of the sealer to use
+    #[clap(short, long, required = true, possible_values = &["bob", "charlie"])]
+    pub who: String,
+}
+
+/// A subcommand to back up the private key share by splitting it into fragments
+#[derive(Clap, Debug)]
+pub struct ExportShare {
+    /// The private key as string
+    #[clap(short, long)]
+    pub sk: String,
+    /// The number of fragments required to recover the private key
+    #[clap(short, long, default_value = "2")]
+    pub threshold: u32,
+    /// The number of fragments to create
+    #[clap(short, long, default_value = "3")]
+    pub fragments: u32,
+}
+
+/// A subcommand to recover the private key share from its fragments
+#[derive(Clap, Debug)]
+pub struct RecoverShare {
+    /// The fragments (index-value) as created by export_share
+    #[clap(short, long, required = true, min_values = 1)]
+    pub fragments: Vec<String>,
+}
@@ -0,0 +1,24 @@
+use mixnet_primitives::ErrorCode;
+use substrate_subxt::{Error, RuntimeError};
+
+/// the name of the mixnet pallet in the runtime
+const MODULE: &str = "PalletMixnet";
+
+/// returns the error code of the error, if it is an error of the mixnet pallet
+pub fn code(error: &Error) -> Option<ErrorCode> {
+    match error {
+        Error::Runtime(RuntimeError::Module(module_error)) if module_error.module == MODULE => {
+            ErrorCode::from_name(&module_error.error)
+        }
+        _ => None,
+    }
+}
+
+/// describes the error, errors of the mixnet pallet are described by their message and error code
+/// e.g., "the vote is not in the required phase (E020 WrongVotePhase)"
+pub fn describe(error: &Error) -> String {
+    match code(error) {
+        Some(code) => code.to_string(),
+        None => format!("{:?}", error),
+    }
+}
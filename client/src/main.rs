@@ -1,115 +1,663 @@
 mod cli;
+mod errors;
+#[macro_use]
+mod output;
 mod voting;
 
 use async_std::task;
 use clap::Clap;
-use cli::cli::{Opts, SealerSubCommand, SubCommand, VASubCommand};
+use cli::cli::{Opts, SealerSubCommand, SubCommand, VASubCommand, VoterSubCommand};
+use std::process;
+use std::time::Duration;
 use voting::{
-    sealer::{decrypt, keygen},
-    va::{change_vote_phase, get_result, setup_question, setup_vote},
+    auditor::export_transcript,
+    sealer::{
+        approve_result, check_credentials, decrypt, export_share, keygen, keygen_offline,
+        recover_share, submit_share, switch_key, watch,
+    },
+    simulate::simulate,
+    va::{approve, change_vote_phase, get_result, setup_question, setup_vote},
+};
+use voting::{
+    va::aggregate_question,
+    va::archive,
+    va::cancel,
+    va::certify,
+    va::change_sealer,
+    va::combine_public_key_shares,
+    va::create_from_template,
+    va::import_question_ciphers,
+    va::issue_credential,
+    va::revoke,
+    va::rotate_key,
+    va::save_template,
+    va::sign_token,
+    va::tally_question,
+    va::update_decryption_threshold,
+    va::update_eligibility_key,
+    va::update_group_check,
+    va::update_hash_function,
+    va::update_linked_shuffle,
+    va::update_metadata,
+    va::update_shuffle_batch_size,
+    va::update_topic_packing,
+    va::update_voter_weight,
+    va::whitelist,
+    voter::{
+        bulk_votes, cast_anonymous_ballot, cast_ballot, claim_deposit, create_votes,
+        fake_credential, get_receipt, relay_ballot, request_token, verify_encryption,
+    },
+    watchdog::{watch_votes, PhaseAdvance, WatchdogConfig},
 };
-use voting::{va::combine_public_key_shares, va::tally_question, voter::create_votes};
 
 fn main() {
     let opts: Opts = Opts::parse();
+    output::init(opts.output);
 
     // You can handle information about subcommands by requesting their matches by name
     // (as below), requesting just the name used, or both at the same time
     match opts.subcmd {
-        SubCommand::Voter(t) => {
-            println!("Voter. Creating votes... {:?}", t);
-            task::block_on(async {
-                let result =
-                    task::spawn(create_votes(t.vote, t.question, t.nr_of_votes, t.votes)).await;
-                match result {
-                    Ok(_) => println!("successfully created {:?} votes.", t.nr_of_votes),
-                    Err(err) => println!("failed to create vote: {:?}", err),
-                }
-            });
-        }
+        SubCommand::Voter(t) => match t.subcmd {
+            VoterSubCommand::CreateVotes(t) => {
+                status!("Voter. Creating votes... {:?}", t);
+                task::block_on(async {
+                    let result =
+                        task::spawn(create_votes(t.vote, t.question, t.nr_of_votes, t.votes)).await;
+                    match result {
+                        Ok(_) => output::success(&format!(
+                            "successfully created {:?} votes.",
+                            t.nr_of_votes
+                        )),
+                        Err(err) => output::failure("failed to create vote", &err),
+                    }
+                });
+            }
+            VoterSubCommand::BulkVotes(t) => {
+                status!("Voter. Casting ballots in bulk... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(bulk_votes(
+                        t.vote,
+                        t.question,
+                        t.nr_of_ballots,
+                        t.votes,
+                        t.distribution,
+                        t.concurrency,
+                        t.verify,
+                    ))
+                    .await;
+                    match result {
+                        Ok(_) => output::success("successfully cast ballots in bulk."),
+                        Err(err) => output::failure("bulk voting failed", &err),
+                    }
+                });
+            }
+            VoterSubCommand::CastBallot(t) => {
+                status!("Voter. Casting ballot... {:?}", t);
+                task::block_on(async {
+                    let compact = t.compact;
+                    let result = task::spawn(cast_ballot(
+                        t.vote,
+                        t.question,
+                        t.voter,
+                        t.answer,
+                        t.seed,
+                        t.attempt,
+                        t.compact,
+                        t.finalized,
+                        t.credential,
+                    ))
+                    .await;
+                    match result {
+                        Ok(_) if compact => output::success("the compact ballot can be relayed."),
+                        Ok(_) => output::success("successfully cast ballot."),
+                        Err(err) => output::failure("ballot rejected", &err),
+                    }
+                });
+            }
+            VoterSubCommand::RelayBallot(t) => {
+                status!("Voter. Relaying ballot... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(relay_ballot(t.ballot, t.relayer)).await;
+                    match result {
+                        Ok(_) => output::success("successfully relayed ballot."),
+                        Err(err) => output::failure("ballot rejected", &err),
+                    }
+                });
+            }
+            VoterSubCommand::VerifyEncryption(t) => {
+                status!("Voter. Verifying encryption... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(verify_encryption(
+                        t.vote, t.question, t.voter, t.answer, t.seed, t.attempt,
+                    ))
+                    .await;
+                    match result {
+                        Ok(_) => output::done(),
+                        Err(err) => output::failure("failed to verify encryption", &err),
+                    }
+                });
+            }
+            VoterSubCommand::Receipt(t) => {
+                status!("Voter. Fetching receipt... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(get_receipt(t.vote, t.voter)).await;
+                    match result {
+                        Ok(_) => output::done(),
+                        Err(err) => output::failure("failed to fetch receipt", &err),
+                    }
+                });
+            }
+            VoterSubCommand::ClaimDeposit(t) => {
+                status!("Voter. Claiming ballot deposit... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(claim_deposit(t.vote, t.voter)).await;
+                    match result {
+                        Ok(_) => output::success("successfully claimed ballot deposit!"),
+                        Err(err) => output::failure("failed to claim ballot deposit", &err),
+                    }
+                });
+            }
+            VoterSubCommand::FakeCredential(t) => {
+                status!("Voter. Creating fake credential... {:?}", t);
+                fake_credential();
+                output::done();
+            }
+            VoterSubCommand::RequestToken(t) => {
+                status!("Voter. Requesting eligibility token... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(request_token(t.vote, t.output)).await;
+                    match result {
+                        Ok(_) => output::success("the blinded token message can be signed."),
+                        Err(err) => output::failure("failed to request token", &err),
+                    }
+                });
+            }
+            VoterSubCommand::CastAnonymousBallot(t) => {
+                status!("Voter. Casting anonymous ballot... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(cast_anonymous_ballot(
+                        t.vote,
+                        t.question,
+                        t.answer,
+                        t.token,
+                        t.signature,
+                    ))
+                    .await;
+                    match result {
+                        Ok(_) => output::success("successfully cast anonymous ballot."),
+                        Err(err) => output::failure("ballot rejected", &err),
+                    }
+                });
+            }
+        },
         SubCommand::VotingAuthority(t) => match t.subcmd {
             VASubCommand::SetupVote(t) => {
-                println!("VA. Creating vote... {:?}", t);
+                status!("VA. Creating vote... {:?}", t);
                 task::block_on(async {
-                    let result = task::spawn(setup_vote(t.vote, t.question)).await;
+                    let result = task::spawn(setup_vote(
+                        t.vote,
+                        t.question,
+                        t.nr_of_shuffles,
+                        t.tally_strategy,
+                    ))
+                    .await;
                     match result {
-                        Ok(_) => println!("successfully created vote!"),
-                        Err(err) => println!("failed to create vote: {:?}", err),
+                        Ok(_) => output::success("successfully created vote!"),
+                        Err(err) => output::failure("failed to create vote", &err),
                     }
                 });
             }
             VASubCommand::StoreQuestion(t) => {
-                println!("VA. Store Question... {:?}", t);
+                status!("VA. Store Question... {:?}", t);
                 task::block_on(async {
-                    let result = task::spawn(setup_question(t.vote, t.question)).await;
+                    let result = task::spawn(setup_question(
+                        t.vote,
+                        t.question,
+                        t.candidates,
+                        t.question_type,
+                        t.limit,
+                        t.ranked_tally,
+                    ))
+                    .await;
                     match result {
-                        Ok(_) => println!("successfully setup question!"),
-                        Err(err) => println!("failed to setup question: {:?}", err),
+                        Ok(_) => output::success("successfully setup question!"),
+                        Err(err) => output::failure("failed to setup question", &err),
+                    }
+                });
+            }
+            VASubCommand::ApproveVote(t) => {
+                status!("VA. Approving Vote... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(approve(t.vote, t.authority)).await;
+                    match result {
+                        Ok(_) => output::success("successfully approved vote!"),
+                        Err(err) => output::failure("failed to approve vote", &err),
                     }
                 });
             }
             VASubCommand::SetVotePhase(t) => {
-                println!("VA. Changing Vote Phase... {:?}", t);
+                status!("VA. Changing Vote Phase... {:?}", t);
                 task::block_on(async {
-                    let result = task::spawn(change_vote_phase(t.vote, t.phase)).await;
+                    let result = task::spawn(change_vote_phase(t.vote, t.phase, t.authority)).await;
                     match result {
-                        Ok(_) => println!("successfully update vote phase!"),
-                        Err(err) => println!("failed to set vote: {:?}", err),
+                        Ok(_) => output::success("successfully update vote phase!"),
+                        Err(err) => output::failure("failed to set vote", &err),
                     }
                 });
             }
             VASubCommand::CombinePublicKeyShares(t) => {
-                println!("VA. Combining Public Key Shares... {:?}", t);
+                status!("VA. Combining Public Key Shares... {:?}", t);
                 task::block_on(async {
                     let result = task::spawn(combine_public_key_shares(t.vote)).await;
                     match result {
-                        Ok(_) => println!("successfully create public key!"),
-                        Err(err) => println!("failed to create public key: {:?}", err),
+                        Ok(_) => output::success("successfully create public key!"),
+                        Err(err) => output::failure("failed to create public key", &err),
+                    }
+                });
+            }
+            VASubCommand::RotateKey(t) => {
+                status!("VA. Rotating Public Key... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(rotate_key(t.vote)).await;
+                    match result {
+                        Ok(_) => output::success("successfully rotated public key!"),
+                        Err(err) => output::failure("failed to rotate public key", &err),
+                    }
+                });
+            }
+            VASubCommand::AggregateQuestion(t) => {
+                status!("VA. Aggregating Question... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(aggregate_question(t.vote, t.question)).await;
+                    match result {
+                        Ok(_) => output::success("successfully aggregated question!"),
+                        Err(err) => output::failure("failed to aggregate question", &err),
                     }
                 });
             }
             VASubCommand::TallyQuestion(t) => {
-                println!("VA. Tallying Question... {:?}", t);
+                status!("VA. Tallying Question... {:?}", t);
                 task::block_on(async {
-                    let result = task::spawn(tally_question(t.vote, t.question)).await;
+                    let result = task::spawn(tally_question(t.vote, t.question, t.timeout)).await;
                     match result {
-                        Ok(_) => println!("successfully tallied question!"),
-                        Err(err) => println!("failed to tally question: {:?}", err),
+                        Ok(_) => output::success("successfully tallied question!"),
+                        Err(err) => output::failure("failed to tally question", &err),
                     }
                 });
             }
             VASubCommand::GetResult(t) => {
-                println!("VA. Get Result... {:?}", t);
+                status!("VA. Get Result... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(get_result(t.vote, t.question)).await;
+                    match result {
+                        Ok(_) => output::done(),
+                        Err(err) => output::failure("failed to fetch result", &err),
+                    }
+                });
+            }
+            VASubCommand::CertifyResult(t) => {
+                status!("VA. Certifying Result... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(certify(t.vote, t.authority)).await;
+                    match result {
+                        Ok(_) => output::success("successfully certified result!"),
+                        Err(err) => output::failure("failed to certify result", &err),
+                    }
+                });
+            }
+            VASubCommand::ArchiveVote(t) => {
+                status!("VA. Archiving Vote... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(archive(t.vote)).await;
+                    match result {
+                        Ok(_) => output::success("successfully archived vote!"),
+                        Err(err) => output::failure("failed to archive vote", &err),
+                    }
+                });
+            }
+            VASubCommand::CancelVote(t) => {
+                status!("VA. Cancelling Vote... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(cancel(t.vote, t.reason, t.authority)).await;
+                    match result {
+                        Ok(_) => {
+                            output::success("successfully approved the cancellation of the vote!")
+                        }
+                        Err(err) => output::failure("failed to cancel vote", &err),
+                    }
+                });
+            }
+            VASubCommand::IssueCredential(t) => {
+                status!("VA. Issuing Credential... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(issue_credential(t.vote, t.voter)).await;
+                    match result {
+                        Ok(_) => output::success("successfully registered credential!"),
+                        Err(err) => output::failure("failed to register credential", &err),
+                    }
+                });
+            }
+            VASubCommand::SetMetadata(t) => {
+                status!("VA. Setting Metadata... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(update_metadata(t.vote, t.ipfs, t.file)).await;
+                    match result {
+                        Ok(_) => output::success("successfully updated metadata!"),
+                        Err(err) => output::failure("failed to update metadata", &err),
+                    }
+                });
+            }
+            VASubCommand::SetShuffleBatchSize(t) => {
+                status!("VA. Setting Shuffle Batch Size... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(update_shuffle_batch_size(t.vote, t.batch_size)).await;
+                    match result {
+                        Ok(_) => output::success("successfully updated shuffle batch size!"),
+                        Err(err) => output::failure("failed to update shuffle batch size", &err),
+                    }
+                });
+            }
+            VASubCommand::SetDecryptionThreshold(t) => {
+                status!("VA. Setting Decryption Threshold... {:?}", t);
+                task::block_on(async {
+                    let result =
+                        task::spawn(update_decryption_threshold(t.vote, t.threshold)).await;
+                    match result {
+                        Ok(_) => output::success("successfully updated decryption threshold!"),
+                        Err(err) => output::failure("failed to update decryption threshold", &err),
+                    }
+                });
+            }
+            VASubCommand::SetHashFunction(t) => {
+                status!("VA. Setting Hash Function... {:?}", t);
                 task::block_on(async {
-                    let result = task::spawn(get_result(t.question)).await;
+                    let result = task::spawn(update_hash_function(t.vote, t.hash_function)).await;
                     match result {
-                        Ok(_) => (),
-                        Err(err) => println!("failed to fetch result: {:?}", err),
+                        Ok(_) => output::success("successfully updated hash function!"),
+                        Err(err) => output::failure("failed to update hash function", &err),
+                    }
+                });
+            }
+            VASubCommand::SetLinkedShuffle(t) => {
+                status!("VA. Setting Linked Shuffles... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(update_linked_shuffle(t.vote, !t.disable)).await;
+                    match result {
+                        Ok(_) => output::success("successfully updated linked shuffles!"),
+                        Err(err) => output::failure("failed to update linked shuffles", &err),
+                    }
+                });
+            }
+            VASubCommand::SetGroupCheck(t) => {
+                status!("VA. Setting Group Check... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(update_group_check(t.vote, !t.disable)).await;
+                    match result {
+                        Ok(_) => output::success("successfully updated group check!"),
+                        Err(err) => output::failure("failed to update group check", &err),
+                    }
+                });
+            }
+            VASubCommand::SetTopicPacking(t) => {
+                status!("VA. Setting Question Packing... {:?}", t);
+                task::block_on(async {
+                    let packing = t.slots.map(|slots| (slots, t.slot_bits));
+                    let result =
+                        task::spawn(update_topic_packing(t.vote, t.question, packing)).await;
+                    match result {
+                        Ok(_) => output::success("successfully updated question packing!"),
+                        Err(err) => output::failure("failed to update question packing", &err),
+                    }
+                });
+            }
+            VASubCommand::AddSealer(t) => {
+                status!("VA. Adding Sealer... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(change_sealer(t.sealer, t.authority, true)).await;
+                    match result {
+                        Ok(_) => output::success("successfully added sealer!"),
+                        Err(err) => output::failure("failed to add sealer", &err),
+                    }
+                });
+            }
+            VASubCommand::RemoveSealer(t) => {
+                status!("VA. Removing Sealer... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(change_sealer(t.sealer, t.authority, false)).await;
+                    match result {
+                        Ok(_) => output::success("successfully removed sealer!"),
+                        Err(err) => output::failure("failed to remove sealer", &err),
+                    }
+                });
+            }
+            VASubCommand::SetVoterWeight(t) => {
+                status!("VA. Setting Voter Weight... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(update_voter_weight(t.vote, t.voter, t.weight)).await;
+                    match result {
+                        Ok(_) => output::success("successfully updated voter weight!"),
+                        Err(err) => output::failure("failed to update voter weight", &err),
+                    }
+                });
+            }
+            VASubCommand::RevokeVoter(t) => {
+                status!("VA. Revoking Voter... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(revoke(t.vote, t.voter)).await;
+                    match result {
+                        Ok(_) => output::success("successfully revoked voter!"),
+                        Err(err) => output::failure("failed to revoke voter", &err),
+                    }
+                });
+            }
+            VASubCommand::SetEligibilityKey(t) => {
+                status!("VA. Setting Eligibility Key... {:?}", t);
+                task::block_on(async {
+                    let result =
+                        task::spawn(update_eligibility_key(t.vote, t.output, t.bits, t.remove))
+                            .await;
+                    match result {
+                        Ok(_) => output::success("successfully updated eligibility key!"),
+                        Err(err) => output::failure("failed to update eligibility key", &err),
+                    }
+                });
+            }
+            VASubCommand::ImportCiphers(t) => {
+                status!("VA. Importing Ciphers... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(import_question_ciphers(
+                        t.vote, t.question, t.file, t.source,
+                    ))
+                    .await;
+                    match result {
+                        Ok(_) => output::success("successfully imported ciphers!"),
+                        Err(err) => output::failure("failed to import ciphers", &err),
+                    }
+                });
+            }
+            VASubCommand::SaveTemplate(t) => {
+                status!("VA. Storing Vote Template... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(save_template(t.template, t.vote)).await;
+                    match result {
+                        Ok(_) => output::success("successfully stored vote template!"),
+                        Err(err) => output::failure("failed to store vote template", &err),
+                    }
+                });
+            }
+            VASubCommand::CreateFromTemplate(t) => {
+                status!("VA. Creating vote from template... {:?}", t);
+                task::block_on(async {
+                    let result =
+                        task::spawn(create_from_template(t.template, t.vote, t.title)).await;
+                    match result {
+                        Ok(_) => output::success("successfully created vote from template!"),
+                        Err(err) => output::failure("failed to create vote from template", &err),
+                    }
+                });
+            }
+            VASubCommand::Watch(t) => {
+                status!("VA. Watching votes... {:?}", t);
+                let advances: Result<Vec<PhaseAdvance>, _> = t
+                    .advance
+                    .iter()
+                    .map(|advance| PhaseAdvance::parse(advance))
+                    .collect();
+                let config = match advances {
+                    Ok(advances) => WatchdogConfig {
+                        stall_blocks: t.stall_blocks,
+                        interval: Duration::from_millis(t.interval),
+                        webhook: t.webhook,
+                        advances,
+                        exit_on_alert: t.exit_on_alert,
+                        finalized: t.finalized,
+                    },
+                    Err(err) => {
+                        output::failure("invalid phase advance", &err);
+                        process::exit(1);
+                    }
+                };
+                task::block_on(async {
+                    let result = task::spawn(watch_votes(config, t.authority)).await;
+                    match result {
+                        Ok(_) => output::success("event subscription closed!"),
+                        Err(err) => {
+                            output::failure("the watchdog stopped", &err);
+                            process::exit(1);
+                        }
+                    }
+                });
+            }
+            VASubCommand::SignToken(t) => {
+                status!("VA. Signing Eligibility Token... {:?}", t);
+                match sign_token(t.key, t.blinded) {
+                    Ok(_) => output::success("successfully signed eligibility token!"),
+                    Err(err) => output::failure("failed to sign eligibility token", &err),
+                }
+            }
+            VASubCommand::WhitelistVote(t) => {
+                status!("VA. Whitelisting Vote... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(whitelist(t.vote, !t.remove)).await;
+                    match result {
+                        Ok(_) => output::success("successfully updated whitelist!"),
+                        Err(err) => output::failure("failed to update whitelist", &err),
                     }
                 });
             }
         },
         SubCommand::Sealer(t) => match t.subcmd {
+            SealerSubCommand::KeyGeneration(t) if t.offline => {
+                status!("Printing sealer - offline key generation... {:?}", t);
+                match keygen_offline(t.vote, t.sk, t.who, t.output, t.hash_function) {
+                    Ok(_) => output::success("successfully created public key share!"),
+                    Err(err) => output::failure("failed to create public key share", &err),
+                }
+            }
             SealerSubCommand::KeyGeneration(t) => {
-                println!("Printing sealer - key generation... {:?}", t);
+                status!("Printing sealer - key generation... {:?}", t);
                 task::block_on(async {
                     let result = task::spawn(keygen(t.vote, t.sk, t.who)).await;
                     match result {
-                        Ok(_) => println!("successfully submitted public key share!"),
-                        Err(err) => println!("failed to submitted public key share: {:?}", err),
+                        Ok(_) => output::success("successfully submitted public key share!"),
+                        Err(err) => output::failure("failed to submitted public key share", &err),
+                    }
+                });
+            }
+            SealerSubCommand::SubmitShare(t) => {
+                status!("Printing sealer - submit public key share... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(submit_share(t.file, t.who)).await;
+                    match result {
+                        Ok(_) => output::success("successfully submitted public key share!"),
+                        Err(err) => output::failure("failed to submit public key share", &err),
                     }
                 });
             }
             SealerSubCommand::PartialDecryption(t) => {
-                println!("Printing sealer - partial decryption... {:?}", t);
+                status!("Printing sealer - partial decryption... {:?}", t);
                 task::block_on(async {
                     let result = task::spawn(decrypt(t.vote, t.question, t.sk, t.who)).await;
                     match result {
-                        Ok(_) => println!("successfully submitted partial decryption!"),
-                        Err(err) => println!("failed to submit partial decryption: {:?}", err),
+                        Ok(_) => output::success("successfully submitted partial decryption!"),
+                        Err(err) => output::failure("failed to submit partial decryption", &err),
+                    }
+                });
+            }
+            SealerSubCommand::SwitchKey(t) => {
+                status!("Printing sealer - key switch... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(switch_key(t.vote, t.sk, t.who)).await;
+                    match result {
+                        Ok(_) => output::success("successfully submitted key switch shares!"),
+                        Err(err) => output::failure("failed to submit key switch shares", &err),
+                    }
+                });
+            }
+            SealerSubCommand::CheckCredentials(t) => {
+                status!("Printing sealer - credential check... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(check_credentials(t.vote, t.sk, t.who)).await;
+                    match result {
+                        Ok(_) => output::success("successfully submitted credential check!"),
+                        Err(err) => output::failure("failed to submit credential check", &err),
+                    }
+                });
+            }
+            SealerSubCommand::ApproveResult(t) => {
+                status!("Printing sealer - certify result... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(approve_result(t.vote, t.who)).await;
+                    match result {
+                        Ok(_) => output::success("successfully certified result!"),
+                        Err(err) => output::failure("failed to certify result", &err),
                     }
                 });
             }
+            SealerSubCommand::Watch(t) => {
+                status!("Printing sealer - watch... {:?}", t);
+                task::block_on(async {
+                    let result = task::spawn(watch(t.sk, t.who)).await;
+                    match result {
+                        Ok(_) => output::success("event subscription closed!"),
+                        Err(err) => output::failure("failed to watch events", &err),
+                    }
+                });
+            }
+            SealerSubCommand::ExportShare(t) => {
+                status!("Printing sealer - export share... {:?}", t);
+                match export_share(t.sk, t.threshold, t.fragments) {
+                    Ok(_) => output::done(),
+                    Err(err) => output::failure("failed to export share", &err),
+                }
+            }
+            SealerSubCommand::RecoverShare(t) => {
+                status!("Printing sealer - recover share... {:?}", t);
+                match recover_share(t.fragments) {
+                    Ok(_) => output::done(),
+                    Err(err) => output::failure("failed to recover share", &err),
+                }
+            }
         },
+        SubCommand::Simulate(t) => {
+            status!("Simulating election... {:?}", t);
+            task::block_on(async {
+                let result = task::spawn(simulate(t.nr_of_voters, t.nr_of_ballots, t.votes)).await;
+                match result {
+                    Ok(_) => output::success("simulation succeeded!"),
+                    Err(err) => output::failure("simulation failed", &err),
+                }
+            });
+        }
+        SubCommand::ExportTranscript(t) => {
+            status!("Exporting transcript... {:?}", t);
+            task::block_on(async {
+                let result = task::spawn(export_transcript(t.vote, t.output, t.at)).await;
+                match result {
+                    Ok(_) => output::success("successfully exported transcript!"),
+                    Err(err) => output::failure("failed to export transcript", &err),
+                }
+            });
+        }
     }
 }
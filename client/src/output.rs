@@ -0,0 +1,202 @@
+//! The output of the subcommands.
+//!
+//! By default, the subcommands print human readable text. With `--output json` the text is
+//! omitted and every subcommand prints a single JSON object once it has finished: whether it
+//! succeeded, its message (or error) and the values it reported, e.g. the hashes and events of
+//! the submitted extrinsics. Progress bars are only drawn in text mode.
+use crate::errors::{code, describe};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use substrate_subxt::{Error, ExtrinsicSuccess, NodeTemplateRuntime};
+
+/// prints the line in text mode, it is omitted in JSON mode
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_json() {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// The output format of the subcommands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!("unknown output format: {}", format)),
+        }
+    }
+}
+
+static JSON: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    /// the values reported by the subcommand, in the order they have been reported
+    static ref VALUES: Mutex<Vec<(String, Value)>> = Mutex::new(Vec::new());
+}
+
+/// sets the output format, must be called before the subcommand is executed
+pub fn init(format: OutputFormat) {
+    JSON.store(format == OutputFormat::Json, Ordering::SeqCst);
+}
+
+pub fn is_json() -> bool {
+    JSON.load(Ordering::SeqCst)
+}
+
+/// records the value in JSON mode, a key which is reported repeatedly becomes a list
+pub fn record(key: &str, value: Value) {
+    if is_json() {
+        let mut values = VALUES.lock().expect("the output is not poisoned!");
+        values.push((key.to_string(), value));
+    }
+}
+
+/// reports the value, e.g. "credential: 0x2a"
+pub fn value<V: Serialize + Display>(key: &str, value: V) {
+    if is_json() {
+        let fallback = Value::String(value.to_string());
+        record(key, serde_json::to_value(&value).unwrap_or(fallback));
+    } else {
+        println!("{}: {}", key, value);
+    }
+}
+
+/// reports the value, its debug representation is printed in text mode
+pub fn debug<V: Serialize + Debug>(key: &str, value: V) {
+    if is_json() {
+        let fallback = Value::String(format!("{:?}", value));
+        record(key, serde_json::to_value(&value).unwrap_or(fallback));
+    } else {
+        println!("{}: {:?}", key, value);
+    }
+}
+
+/// reports the submitted extrinsic: its first event in text mode, the hashes of the
+/// extrinsic and its block and all events (the data hex encoded) in JSON mode
+pub fn extrinsic(key: &str, response: &ExtrinsicSuccess<NodeTemplateRuntime>) {
+    if is_json() {
+        let events: Vec<Value> = response
+            .events
+            .iter()
+            .map(|event| {
+                json!({
+                    "module": event.module,
+                    "variant": event.variant,
+                    "data": format!("0x{}", hex::encode(&event.data)),
+                })
+            })
+            .collect();
+        record(
+            key,
+            json!({
+                "block": format!("{:?}", response.block),
+                "extrinsic": format!("{:?}", response.extrinsic),
+                "events": events,
+            }),
+        );
+    } else if let Some(event) = response.events.first() {
+        println!("{}: {:?}", key, event.variant);
+    }
+}
+
+/// prints the line to stdout in text mode and to stderr in JSON mode,
+/// i.e., interactive prompts never become part of the JSON output
+pub fn prompt(line: &str) {
+    if is_json() {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// creates a progress bar of the given length, it is hidden in JSON mode
+pub fn progress_bar(len: u64, message: &str) -> ProgressBar {
+    if is_json() {
+        return ProgressBar::hidden();
+    }
+    let bar = ProgressBar::new(len);
+    bar.set_style(
+        ProgressStyle::default_bar()
+            .template("{msg} [{bar:40}] {pos}/{len} ({eta})")
+            .progress_chars("=> "),
+    );
+    bar.set_message(message);
+    bar
+}
+
+/// prints the JSON object of the finished subcommand
+fn print_result(success: bool, message: Option<&str>, error: Option<&Error>) {
+    let values: Vec<(String, Value)> = VALUES
+        .lock()
+        .expect("the output is not poisoned!")
+        .drain(..)
+        .collect();
+    let mut result: Map<String, Value> = Map::new();
+    for (key, _) in values.iter() {
+        if result.contains_key(key) {
+            continue;
+        }
+        let mut reported: Vec<Value> = values
+            .iter()
+            .filter(|(other, _)| other == key)
+            .map(|(_, value)| value.clone())
+            .collect();
+        let value = match reported.len() {
+            1 => reported.remove(0),
+            _ => Value::Array(reported),
+        };
+        result.insert(key.clone(), value);
+    }
+
+    let mut output = json!({ "success": success, "result": result });
+    if let Some(message) = message {
+        output["message"] = json!(message);
+    }
+    if let Some(error) = error {
+        output["error"] = json!(describe(error));
+        if let Some(code) = code(error) {
+            output["code"] = json!(code.code());
+        }
+    }
+    println!("{}", output);
+}
+
+/// reports that the subcommand succeeded
+pub fn success(message: &str) {
+    if is_json() {
+        print_result(true, Some(message), None);
+    } else {
+        println!("{}", message);
+    }
+}
+
+/// reports that the subcommand finished, it doesn't print a message in text mode
+pub fn done() {
+    if is_json() {
+        print_result(true, None, None);
+    }
+}
+
+/// reports that the subcommand failed, e.g. "failed to create vote: <error>"
+pub fn failure(message: &str, error: &Error) {
+    if is_json() {
+        print_result(false, Some(message), Some(error));
+    } else {
+        println!("{}: {}", message, describe(error));
+    }
+}
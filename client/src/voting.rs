@@ -1,5 +1,6 @@
+pub mod auditor;
 pub mod sealer;
+pub mod simulate;
 pub mod va;
 pub mod voter;
-
-mod substrate;
+pub mod watchdog;
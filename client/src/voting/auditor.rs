@@ -0,0 +1,265 @@
+use crate::output;
+use codec::Encode;
+use crypto::types::{ElGamalParams, PublicKey as ElGamalPK};
+use num_bigint::BigUint;
+use pallet_mixnet::types::{
+    BallotBoxFreeze, Cipher, NrOfShuffles, ProtocolConfig, ShufflePayload, ShuffleProof,
+    TallyStrategy, TopicId, VoteId, Wrapper,
+};
+use provotum_client_sdk::rpc::{
+    get_aggregate_cipher_at, get_ballot_box_freeze_at, get_block_number_at, get_ciphers_at,
+    get_decrypted_shares_at, get_decryption_audit_at, get_linked_shuffle_at,
+    get_protocol_config_at, get_public_key_share_at, get_shuffle_proofs_at, get_topics_at,
+    get_vote_at, get_vote_public_key_at, get_vote_sealers_at,
+};
+use provotum_client_sdk::BlockNumber;
+use serde_json::json;
+use std::fs;
+use substrate_subxt::{
+    sp_core::H256, system::System, Client, ClientBuilder, Error, NodeTemplateRuntime,
+};
+use verifier::transcript::{
+    from_ciphers, DecryptionRecord, SealerKeyShare, ShuffleProof as TranscriptShuffleProof,
+    ShuffleRecord, TopicTranscript, Transcript, TranscriptBlock, TranscriptCipher,
+    TranscriptFreeze, TranscriptProtocol, TRANSCRIPT_VERSION,
+};
+
+async fn init() -> Result<Client<NodeTemplateRuntime>, Error> {
+    env_logger::init();
+    let url = "ws://127.0.0.1:9944";
+    let client = ClientBuilder::<NodeTemplateRuntime>::new()
+        .set_url(url)
+        .build()
+        .await?;
+    Ok(client)
+}
+
+/// the id of a sealer in the transcript: the hex encoded account id (the id of all its proofs)
+fn sealer_id(sealer: &<NodeTemplateRuntime as System>::AccountId) -> String {
+    hex::encode(sealer.encode())
+}
+
+fn into_transcript_protocol(config: ProtocolConfig) -> TranscriptProtocol {
+    TranscriptProtocol {
+        version: config.version,
+        hash_function: format!("{:?}", config.hash_function),
+        batch_exponent_bits: config.batch_exponent_bits,
+        generator_domain: String::from_utf8_lossy(&config.generator_domain).into_owned(),
+        nr_of_shuffles: config.nr_of_shuffles,
+    }
+}
+
+fn into_transcript_freeze(freeze: BallotBoxFreeze<H256, BlockNumber>) -> TranscriptFreeze {
+    TranscriptFreeze {
+        block_number: freeze.block_number,
+        block_hash: hex::encode(freeze.block_hash.as_bytes()),
+        ciphers_chain: hex::encode(freeze.ciphers_chain),
+        digest: hex::encode(freeze.digest),
+    }
+}
+
+fn into_shuffle_record(payload: ShufflePayload) -> ShuffleRecord {
+    let proof: ShuffleProof = payload.proof.into();
+    let ciphers: Vec<crypto::types::Cipher> = Wrapper(payload.ciphers).into();
+    ShuffleRecord {
+        iteration: payload.iteration,
+        start_position: payload.start_position,
+        batch_size: payload.batch_size,
+        shuffled_ciphers: from_ciphers(&ciphers),
+        proof: TranscriptShuffleProof {
+            challenge: proof.challenge,
+            s1: proof.S.s1,
+            s2: proof.S.s2,
+            s3: proof.S.s3,
+            s4: proof.S.s4,
+            vec_s_hat: proof.S.vec_s_hat,
+            vec_s_tilde: proof.S.vec_s_tilde,
+            permutation_commitments: proof.permutation_commitments,
+            permutation_chain_commitments: proof.permutation_chain_commitments,
+        },
+    }
+}
+
+/// resolves the block to export the transcript at: a block number, a 0x prefixed block hash
+/// or the best block if None. returns the hash of the block.
+async fn resolve_block(
+    client: &Client<NodeTemplateRuntime>,
+    at: Option<String>,
+) -> Result<H256, Error> {
+    let block = match at {
+        None => None,
+        Some(at) if at.starts_with("0x") => {
+            let bytes = hex::decode(&at[2..]).map_err(|error| Error::Other(error.to_string()))?;
+            if bytes.len() != 32 {
+                return Err(Error::Other(format!("invalid block hash: {}", at)));
+            }
+            return Ok(H256::from_slice(&bytes));
+        }
+        Some(at) => {
+            let number: BlockNumber = at
+                .parse()
+                .map_err(|_| Error::Other(format!("invalid block number: {}", at)))?;
+            Some(number.into())
+        }
+    };
+    let hash = client
+        .block_hash(block)
+        .await?
+        .ok_or("failed to fetch block hash!")?;
+    Ok(hash)
+}
+
+async fn get_topic_transcript(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: &VoteId,
+    topic_id: TopicId,
+    nr_of_shuffles: NrOfShuffles,
+    tally_strategy: &TallyStrategy,
+    at: Option<H256>,
+) -> Result<TopicTranscript, Error> {
+    // the ciphers of all performed shuffle iterations
+    let mut ciphers: Vec<Vec<TranscriptCipher>> = Vec::new();
+    for iteration in 0..=nr_of_shuffles {
+        let stored: Vec<Cipher> =
+            get_ciphers_at(client, vote_id.clone(), topic_id.clone(), iteration, at).await?;
+        if iteration > 0 && stored.is_empty() {
+            break;
+        }
+        let stored: Vec<crypto::types::Cipher> = Wrapper(stored).into();
+        ciphers.push(from_ciphers(&stored));
+    }
+
+    let aggregate: Option<TranscriptCipher> = match tally_strategy {
+        TallyStrategy::Homomorphic => {
+            get_aggregate_cipher_at(client, vote_id.clone(), topic_id.clone(), at)
+                .await?
+                .map(|aggregate| TranscriptCipher(aggregate.into()))
+        }
+        TallyStrategy::Mixnet => None,
+    };
+
+    let shuffles: Vec<ShuffleRecord> =
+        get_shuffle_proofs_at(client, vote_id.clone(), topic_id.clone(), at)
+            .await?
+            .into_iter()
+            .map(into_shuffle_record)
+            .collect();
+
+    // the partial decryptions of all sealers (audit trail)
+    let mut decryptions: Vec<DecryptionRecord> = Vec::new();
+    for entry in get_decryption_audit_at(
+        client,
+        vote_id.clone(),
+        topic_id.clone(),
+        nr_of_shuffles,
+        at,
+    )
+    .await?
+    {
+        let shares = get_decrypted_shares_at(
+            client,
+            vote_id.clone(),
+            topic_id.clone(),
+            entry.sealer.clone(),
+            at,
+        )
+        .await?
+        .iter()
+        .map(|share| BigUint::from_bytes_be(share))
+        .collect();
+        decryptions.push(DecryptionRecord {
+            sealer: sealer_id(&entry.sealer),
+            shares,
+            proof: entry.proof.into(),
+        });
+    }
+
+    Ok(TopicTranscript {
+        topic_id: String::from_utf8_lossy(&topic_id).into_owned(),
+        ciphers,
+        shuffles,
+        aggregate,
+        decryptions,
+    })
+}
+
+/// Exports the transcript of a vote, i.e. all public values required to re-verify its proofs.
+/// All values are read at the same block (default: best block), a block number or hash
+/// selects a past block. The transcript can be audited with the provotum-verifier.
+pub async fn export_transcript(
+    vote: String,
+    output: String,
+    at: Option<String>,
+) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+    let block_hash = resolve_block(&client, at).await?;
+    let block_number = get_block_number_at(&client, Some(block_hash)).await?;
+    let at = Some(block_hash);
+
+    let vote_id = vote.as_bytes().to_vec();
+    let vote_state = get_vote_at(&client, vote_id.clone(), at).await?;
+    let params: ElGamalParams = vote_state.params.into();
+    let pk: ElGamalPK = get_vote_public_key_at(&client, vote_id.clone(), at)
+        .await?
+        .into();
+
+    // the public key shares of all sealers of the vote
+    let mut key_shares: Vec<SealerKeyShare> = Vec::new();
+    for sealer in get_vote_sealers_at(&client, vote_id.clone(), at).await? {
+        if let Some(share) =
+            get_public_key_share_at(&client, vote_id.clone(), sealer.clone(), at).await?
+        {
+            key_shares.push(SealerKeyShare {
+                sealer: sealer_id(&sealer),
+                pk: BigUint::from_bytes_be(&share.pk),
+                proof: share.proof.into(),
+            });
+        }
+    }
+
+    let mut topics: Vec<TopicTranscript> = Vec::new();
+    for topic in get_topics_at(&client, vote_id.clone(), at).await? {
+        let topic = get_topic_transcript(
+            &client,
+            &vote_id,
+            topic.id,
+            vote_state.nr_of_shuffles,
+            &vote_state.tally_strategy,
+            at,
+        )
+        .await?;
+        topics.push(topic);
+    }
+
+    let linked_shuffles = get_linked_shuffle_at(&client, vote_id.clone(), at).await?;
+    let protocol = get_protocol_config_at(&client, vote_id.clone(), at).await?;
+    let freeze = get_ballot_box_freeze_at(&client, vote_id.clone(), at).await?;
+    let transcript = Transcript {
+        version: TRANSCRIPT_VERSION,
+        vote_id: vote,
+        params: (&params).into(),
+        public_key: pk.h,
+        nr_of_shuffles: vote_state.nr_of_shuffles,
+        protocol: protocol.map(into_transcript_protocol),
+        linked_shuffles,
+        freeze: freeze.map(into_transcript_freeze),
+        block: Some(TranscriptBlock {
+            number: block_number,
+            hash: hex::encode(block_hash.as_bytes()),
+        }),
+        key_shares,
+        topics,
+    };
+    let json = serde_json::to_string_pretty(&transcript)
+        .map_err(|error| Error::Other(error.to_string()))?;
+    fs::write(&output, json).map_err(|error| Error::Other(error.to_string()))?;
+    status!(
+        "transcript of block #{} written to: {}",
+        block_number,
+        output
+    );
+    output::record("block_number", json!(block_number));
+    output::record("transcript", json!(output));
+    Ok(())
+}
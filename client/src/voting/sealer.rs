@@ -1,18 +1,48 @@
+use crate::output;
+use codec::Encode;
 use crypto::{
     encryption::ElGamal,
+    hash::HashFunction,
     helper::Helper,
-    proofs::{decryption::DecryptionProof, keygen::KeyGenerationProof},
+    proofs::{
+        decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
+        key_switch::KeySwitchProof,
+        keygen::KeyGenerationProof,
+        pet::PlaintextEquivalenceProof,
+    },
     random::Random,
-    types::Cipher as BigCipher,
+    secret,
+    secret_sharing::{SecretSharing, ShareFragment},
+    types::{Cipher as BigCipher, PublicKey as ElGamalPK},
 };
+use futures::StreamExt;
 use hex_literal::hex;
 use num_bigint::BigUint;
-use pallet_mixnet::types::{Cipher, PublicKeyShare, Wrapper};
-use sp_keyring::{sr25519::sr25519::Pair, AccountKeyring};
-use substrate_subxt::{Client, PairSigner};
+use pallet_mixnet::types::{
+    Cipher, HashFunction as VoteHashFunction, NrOfShuffles, PublicKeyShare, TopicId, VoteId,
+    VotePhase, Wrapper,
+};
+use serde::{Deserialize, Serialize};
+use sp_keyring::{
+    sr25519::sr25519::{Pair, Signature},
+    AccountKeyring,
+};
+use std::collections::HashSet;
+use std::fs;
+use std::str::FromStr;
+use substrate_subxt::{sp_core::Pair as _, Client, PairSigner};
 use substrate_subxt::{ClientBuilder, Error, NodeTemplateRuntime};
+use zeroize::Zeroize;
 
-use super::substrate::rpc::{get_ciphers, store_public_key_share, submit_partial_decryptions};
+use provotum_client_sdk::events::{subscribe_mixnet_events, MixnetEvent};
+use provotum_client_sdk::rpc::{
+    certify_result, get_aggregate_cipher, get_ballot, get_ciphers, get_credential,
+    get_credential_ballots, get_credential_blindings, get_credential_decryptions,
+    get_pending_key_switch, get_protocol_config, get_vote_public_key, get_vote_result,
+    get_vote_sealers, store_public_key_share, submit_credential_blindings,
+    submit_credential_decryptions, submit_key_switch_shares, submit_partial_decryptions,
+};
+use provotum_client_sdk::AccountId;
 
 async fn init() -> Result<Client<NodeTemplateRuntime>, Error> {
     env_logger::init();
@@ -39,37 +69,198 @@ fn get_sealer(sealer: String) -> (Pair, [u8; 32]) {
     };
 }
 
+/// returns the hash function of the challenges of the proofs of the vote (see: `ProtocolConfig`)
+async fn get_hash_function(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: &VoteId,
+) -> Result<HashFunction, Error> {
+    let config = get_protocol_config(client, vote_id.clone()).await?;
+    Ok(config
+        .map(|config| config.hash_function.into())
+        .unwrap_or_default())
+}
+
 pub async fn keygen(vote: String, sk_as_string: String, sealer: String) -> Result<(), Error> {
     // init substrate client
     let client = init().await?;
+    let vote_id = vote.as_bytes().to_vec();
+    submit_keygen(&client, vote_id, sk_as_string, sealer).await
+}
 
-    // create private and public key
-    let (params, sk, pk) = Helper::setup_lg_system_with_sk(sk_as_string.as_bytes());
-
+pub async fn submit_keygen(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    mut sk_as_string: String,
+    sealer: String,
+) -> Result<(), Error> {
     // get the sealer and sealer_id
     let (sealer, sealer_id): (Pair, [u8; 32]) = get_sealer(sealer);
 
     // create public key share + proof
-    let r = Random::get_random_less_than(&params.q());
-    let proof = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &r, &sealer_id);
-    let pk_share = PublicKeyShare {
-        proof: proof.clone().into(),
-        pk: pk.h.to_bytes_be(),
-    };
-    let vote_id = vote.as_bytes().to_vec();
+    let hash_function = get_hash_function(client, &vote_id).await?;
+    let pk_share = create_public_key_share(&sk_as_string, &sealer_id, hash_function);
+    sk_as_string.zeroize();
 
     // submit the public key share + proof
     let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(sealer);
     let store_public_key_share_response =
-        store_public_key_share(&client, &signer, vote_id, pk_share).await?;
-    println!(
-        "store_public_key_share_response: {:?}",
-        store_public_key_share_response.events[0].variant
+        store_public_key_share(client, &signer, vote_id, pk_share).await?;
+    output::extrinsic(
+        "store_public_key_share_response",
+        &store_public_key_share_response,
     );
 
     Ok(())
 }
 
+fn create_public_key_share(
+    sk_as_string: &str,
+    sealer_id: &[u8; 32],
+    hash_function: HashFunction,
+) -> PublicKeyShare {
+    // create private and public key
+    let (params, sk, pk) = Helper::setup_lg_system_with_sk(sk_as_string.as_bytes());
+
+    // create public key share + proof, the private key is wiped when it is dropped
+    let mut r = Random::get_random_less_than(&params.q());
+    let proof =
+        KeyGenerationProof::generate_with_hash(&params, &sk.x, &pk.h, &r, sealer_id, hash_function);
+    secret::wipe(&mut r);
+    PublicKeyShare {
+        proof: proof.into(),
+        pk: pk.h.to_bytes_be(),
+    }
+}
+
+/// The public key share of a sealer created on an offline (air-gapped) machine,
+/// signed with the account key of the sealer, see: keygen_offline and submit_share.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedKeyShare {
+    pub vote: String,
+    /// the hex encoded account id of the sealer, i.e., the id of the proof
+    pub sealer: String,
+    /// the hex encoded public key share
+    pub pk: String,
+    pub proof: KeyGenerationProof,
+    /// the hash function of the challenge of the proof, i.e., of the vote
+    #[serde(default)]
+    pub hash_function: HashFunction,
+    /// the hex encoded signature of the sealer over the SCALE encoded (vote id, public key share)
+    pub signature: String,
+}
+
+/// Creates the public key share + proof without connecting to the chain
+/// and writes it, signed by the sealer, to the output file.
+/// The hash function must be the one of the vote, it is checked before the share is submitted.
+pub fn keygen_offline(
+    vote: String,
+    mut sk_as_string: String,
+    sealer: String,
+    output: String,
+    hash_function: String,
+) -> Result<(), Error> {
+    let vote_id: VoteId = vote.as_bytes().to_vec();
+    let (sealer, sealer_id): (Pair, [u8; 32]) = get_sealer(sealer);
+    let hash_function: HashFunction = VoteHashFunction::from_str(&hash_function)
+        .map_err(|_| Error::Other(format!("unknown hash function: {}", hash_function)))?
+        .into();
+    let pk_share = create_public_key_share(&sk_as_string, &sealer_id, hash_function);
+    sk_as_string.zeroize();
+    let signature = sealer.sign(&(&vote_id, &pk_share).encode());
+
+    let signed = SignedKeyShare {
+        vote,
+        sealer: hex::encode(sealer_id),
+        pk: hex::encode(&pk_share.pk),
+        proof: pk_share.proof.into(),
+        hash_function,
+        signature: hex::encode(signature),
+    };
+    let json =
+        serde_json::to_string_pretty(&signed).map_err(|error| Error::Other(error.to_string()))?;
+    fs::write(&output, json).map_err(|error| Error::Other(error.to_string()))?;
+    output::value("signed_public_key_share", &output);
+    Ok(())
+}
+
+/// Verifies the signature and the proof of the signed public key share,
+/// returns the vote id and the public key share to submit.
+fn verify_signed_share(
+    signed: SignedKeyShare,
+    sealer: &Pair,
+    sealer_id: &[u8; 32],
+) -> Result<(VoteId, PublicKeyShare), Error> {
+    if signed.sealer != hex::encode(sealer_id) {
+        return Err(Error::Other(format!(
+            "the public key share was created by another sealer: 0x{}",
+            signed.sealer
+        )));
+    }
+    let pk = hex::decode(&signed.pk).map_err(|error| Error::Other(error.to_string()))?;
+    let signature = hex::decode(&signed.signature)
+        .ok()
+        .filter(|signature| signature.len() == 64)
+        .map(|signature| Signature::from_slice(&signature))
+        .ok_or_else(|| Error::Other("invalid signature encoding".to_string()))?;
+
+    // the public key share must not have been modified since it has been signed
+    let vote_id: VoteId = signed.vote.as_bytes().to_vec();
+    let pk_share = PublicKeyShare {
+        pk,
+        proof: signed.proof.clone().into(),
+    };
+    if !Pair::verify(
+        &signature,
+        &(&vote_id, &pk_share).encode(),
+        &sealer.public(),
+    ) {
+        return Err(Error::Other("invalid signature of the sealer".to_string()));
+    }
+
+    // the proof is verified on-chain as well, reject an invalid share before submitting it
+    let (params, _, _) = Helper::setup_lg_system();
+    let h = BigUint::from_bytes_be(&pk_share.pk);
+    if !KeyGenerationProof::verify_with_hash(
+        &params,
+        &h,
+        &signed.proof,
+        sealer_id,
+        signed.hash_function,
+    ) {
+        return Err(Error::Other("invalid key generation proof".to_string()));
+    }
+    Ok((vote_id, pk_share))
+}
+
+/// Submits a public key share created by keygen_offline.
+pub async fn submit_share(file: String, sealer: String) -> Result<(), Error> {
+    let json = fs::read_to_string(&file).map_err(|error| Error::Other(error.to_string()))?;
+    let signed: SignedKeyShare =
+        serde_json::from_str(&json).map_err(|error| Error::Other(error.to_string()))?;
+    let (sealer, sealer_id): (Pair, [u8; 32]) = get_sealer(sealer);
+    let hash_function = signed.hash_function;
+    let (vote_id, pk_share) = verify_signed_share(signed, &sealer, &sealer_id)?;
+
+    // init substrate client
+    let client = init().await?;
+
+    // the proof would be rejected if it has been created with another hash function than the vote's
+    let vote_hash_function = get_hash_function(&client, &vote_id).await?;
+    if hash_function != vote_hash_function {
+        return Err(Error::Other(format!(
+            "the public key share was created with {:?}, the vote uses {:?}",
+            hash_function, vote_hash_function
+        )));
+    }
+
+    // submit the public key share + proof
+    let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(sealer);
+    let response = store_public_key_share(&client, &signer, vote_id, pk_share).await?;
+    output::extrinsic("store_public_key_share_response", &response);
+    Ok(())
+}
+
 pub async fn decrypt(
     vote: String,
     question: String,
@@ -78,25 +269,47 @@ pub async fn decrypt(
 ) -> Result<(), Error> {
     // init substrate client
     let client = init().await?;
+    let vote_id = vote.as_bytes().to_vec();
+    let topic_id = question.as_bytes().to_vec();
+    let nr_of_shuffles = 3;
+    submit_decryption(
+        &client,
+        vote_id,
+        topic_id,
+        nr_of_shuffles,
+        sk_as_string,
+        sealer,
+    )
+    .await
+}
 
+pub async fn submit_decryption(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    nr_of_shuffles: NrOfShuffles,
+    mut sk_as_string: String,
+    sealer: String,
+) -> Result<(), Error> {
     // create private and public key
     let (params, sk, pk) = Helper::setup_lg_system_with_sk(sk_as_string.as_bytes());
+    sk_as_string.zeroize();
 
     // get the sealer and sealer_id
     let (sealer, sealer_id): (Pair, [u8; 32]) = get_sealer(sealer);
+    let hash_function = get_hash_function(client, &vote_id).await?;
 
     // fetch the encrypted votes from chain
-    let vote_id = vote.as_bytes().to_vec();
-    let topic_id = question.as_bytes().to_vec();
-    let nr_of_shuffles = 3;
-    let encryptions: Vec<Cipher> = get_ciphers(&client, topic_id.clone(), nr_of_shuffles).await?;
+    // if the topic is tallied homomorphically, only the aggregate cipher is decrypted
+    let encryptions: Vec<Cipher> =
+        match get_aggregate_cipher(client, vote_id.clone(), topic_id.clone()).await? {
+            Some(aggregate) => vec![aggregate],
+            None => get_ciphers(client, vote_id.clone(), topic_id.clone(), nr_of_shuffles).await?,
+        };
     let encryptions: Vec<BigCipher> = Wrapper(encryptions).into();
 
-    // get partial decryptions
-    let partial_decryptions = encryptions
-        .iter()
-        .map(|cipher| ElGamal::partial_decrypt_a(cipher, &sk))
-        .collect::<Vec<BigUint>>();
+    // get partial decryptions (on all cores)
+    let partial_decryptions: Vec<BigUint> = ElGamal::partial_decrypt_many(&encryptions, &sk);
 
     // convert the decrypted shares: Vec<BigUint> to Vec<Vec<u8>>
     let shares: Vec<Vec<u8>> = partial_decryptions
@@ -105,21 +318,27 @@ pub async fn decrypt(
         .collect::<Vec<Vec<u8>>>();
 
     // create proof using public and private key share
-    let r = Random::get_random_less_than(&params.q());
-    let proof = DecryptionProof::generate(
+    let mut r = Random::get_random_less_than(&params.q());
+    let proof = DecryptionProof::generate_batched_with_hash(
         &params,
         &sk.x,
         &pk.h.into(),
         &r,
-        encryptions,
-        partial_decryptions,
+        &encryptions,
+        &partial_decryptions,
         &sealer_id,
+        DEFAULT_BATCH_SOUNDNESS,
+        hash_function,
     );
 
+    // the private key and the random of the proof are wiped before the submission
+    secret::wipe(&mut r);
+    drop(sk);
+
     // submit the partial decryption + proof
     let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(sealer);
     let response = submit_partial_decryptions(
-        &client,
+        client,
         &signer,
         vote_id,
         topic_id,
@@ -128,7 +347,371 @@ pub async fn decrypt(
         nr_of_shuffles,
     )
     .await?;
-    println!("response: {:?}", response.events[0].variant);
+    output::extrinsic("response", &response);
 
     Ok(())
 }
+
+/// Checks the credentials of the ballots of the vote, see: submit_credential_check
+pub async fn check_credentials(
+    vote: String,
+    sk_as_string: String,
+    sealer: String,
+) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+    let vote_id = vote.as_bytes().to_vec();
+    submit_credential_check(&client, vote_id, sk_as_string, sealer).await
+}
+
+/// Submits the sealer's part of the credential check of the vote (once the tallying phase has started):
+/// 1. the quotients of the credentials of the ballots and the registered credentials are blinded
+/// 2. once enough sealers have blinded the quotients, the combined blinded quotients are decrypted
+/// the ballots with an invalid credential are discarded once the sealers have decrypted the quotients.
+pub async fn submit_credential_check(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: VoteId,
+    mut sk_as_string: String,
+    sealer: String,
+) -> Result<(), Error> {
+    // create private and public key
+    let (params, sk, pk) = Helper::setup_lg_system_with_sk(sk_as_string.as_bytes());
+    sk_as_string.zeroize();
+    let q = params.q();
+
+    // get the sealer and sealer_id
+    let (sealer, sealer_id): (Pair, [u8; 32]) = get_sealer(sealer);
+    let account: AccountId = sealer.public().into();
+    let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(sealer);
+
+    // 1. blind the quotients of the credentials (in the order of the credential check)
+    if get_credential_blindings(client, vote_id.clone(), account.clone())
+        .await?
+        .is_none()
+    {
+        let mut quotients: Vec<BigCipher> = Vec::new();
+        for voter in get_credential_ballots(client, vote_id.clone()).await? {
+            let cast: BigCipher = get_ballot(client, vote_id.clone(), voter.clone())
+                .await?
+                .and_then(|ballot| ballot.credential)
+                .ok_or("the ballot doesn't carry a credential!")?
+                .into();
+            let registered: BigCipher = get_credential(client, vote_id.clone(), voter)
+                .await?
+                .ok_or("no credential has been registered for the voter!")?
+                .into();
+            quotients.push(PlaintextEquivalenceProof::quotient(
+                &cast,
+                &registered,
+                &params.p,
+            ));
+        }
+
+        // every quotient is blinded and proven using fresh randomness
+        let mut blindings: Vec<Cipher> = Vec::with_capacity(quotients.len());
+        let mut proofs = Vec::with_capacity(quotients.len());
+        for quotient in quotients.iter() {
+            let mut z = Random::get_random_less_than(&q);
+            let mut r = Random::get_random_less_than(&q);
+            let blinded = PlaintextEquivalenceProof::blind(quotient, &z, &params.p);
+            let proof = PlaintextEquivalenceProof::generate(
+                &params, &z, &r, quotient, &blinded, &sealer_id,
+            );
+            secret::wipe(&mut z);
+            secret::wipe(&mut r);
+            blindings.push(blinded.into());
+            proofs.push(proof.into());
+        }
+        let response =
+            submit_credential_blindings(client, &signer, vote_id, blindings, proofs).await?;
+        output::extrinsic("response", &response);
+        return Ok(());
+    }
+    if get_credential_decryptions(client, vote_id.clone(), account)
+        .await?
+        .is_some()
+    {
+        return Err("the sealer has already checked the credentials!".into());
+    }
+
+    // 2. decrypt the combined blinded quotients of the sealers which blinded them
+    let mut blindings: Vec<Vec<BigCipher>> = Vec::new();
+    for blinder in get_vote_sealers(client, vote_id.clone()).await? {
+        if let Some(blinded) = get_credential_blindings(client, vote_id.clone(), blinder).await? {
+            blindings.push(Wrapper(blinded).into());
+        }
+    }
+    let combined: Vec<BigCipher> = (0..blindings[0].len())
+        .map(|index| {
+            let shares: Vec<BigCipher> = blindings
+                .iter()
+                .map(|blinded| blinded[index].clone())
+                .collect();
+            PlaintextEquivalenceProof::combine(&shares, &params.p)
+        })
+        .collect();
+    let partial_decryptions: Vec<BigUint> = ElGamal::partial_decrypt_many(&combined, &sk);
+    let shares: Vec<Vec<u8>> = partial_decryptions
+        .iter()
+        .map(|c| c.to_bytes_be())
+        .collect();
+
+    // create proof using public and private key share
+    let hash_function = get_hash_function(client, &vote_id).await?;
+    let mut r = Random::get_random_less_than(&q);
+    let proof = DecryptionProof::generate_batched_with_hash(
+        &params,
+        &sk.x,
+        &pk.h,
+        &r,
+        &combined,
+        &partial_decryptions,
+        &sealer_id,
+        DEFAULT_BATCH_SOUNDNESS,
+        hash_function,
+    );
+    secret::wipe(&mut r);
+    drop(sk);
+
+    let response =
+        submit_credential_decryptions(client, &signer, vote_id, shares, proof.into()).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Certifies the result of a tallied vote as a sealer.
+pub async fn approve_result(vote: String, sealer: String) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+    let vote_id = vote.as_bytes().to_vec();
+
+    // get the sealer
+    let (sealer, _): (Pair, [u8; 32]) = get_sealer(sealer);
+    let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(sealer);
+
+    // the hash of the results of all questions of the vote
+    let result_hash = get_vote_result(&client, vote_id.clone()).await?.hash();
+    output::value("result_hash", hex::encode(result_hash));
+
+    let response = certify_result(&client, &signer, vote_id, result_hash).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Switches the ciphers of all topics of the vote from the rotated public key to the new one.
+/// Requires the private key share (old_sk) of the rotated public key.
+pub async fn switch_key(
+    vote: String,
+    mut old_sk_as_string: String,
+    sealer: String,
+) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+    let vote_id = vote.as_bytes().to_vec();
+
+    // create private and public key share of the rotated public key
+    let (params, sk, pk) = Helper::setup_lg_system_with_sk(old_sk_as_string.as_bytes());
+    old_sk_as_string.zeroize();
+    let q = params.q();
+
+    // get the sealer and sealer_id
+    let (sealer, sealer_id): (Pair, [u8; 32]) = get_sealer(sealer);
+    let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(sealer);
+
+    // the new public key
+    let new_pk: ElGamalPK = get_vote_public_key(&client, vote_id.clone()).await?.into();
+    let hash_function = get_hash_function(&client, &vote_id).await?;
+
+    let topic_ids = get_pending_key_switch(&client, vote_id.clone()).await?;
+    for topic_id in topic_ids.into_iter() {
+        // fetch the ciphers encrypted under the rotated public key
+        let encryptions: Vec<BigCipher> =
+            Wrapper(get_ciphers(&client, vote_id.clone(), topic_id.clone(), 0).await?).into();
+
+        // compute the key switch shares
+        let mut randoms: Vec<BigUint> = encryptions
+            .iter()
+            .map(|_| Random::get_random_less_than(&q))
+            .collect();
+        let shares: Vec<BigCipher> = encryptions
+            .iter()
+            .zip(randoms.iter())
+            .map(|(cipher, s)| ElGamal::key_switch_share(cipher, &sk, s, &new_pk))
+            .collect();
+
+        // create proof using the private key share of the rotated public key
+        let mut r = Random::get_random_less_than(&q);
+        let mut vec_r: Vec<BigUint> = encryptions
+            .iter()
+            .map(|_| Random::get_random_less_than(&q))
+            .collect();
+        let proof = KeySwitchProof::generate_with_hash(
+            &params,
+            &sk.x,
+            &pk.h,
+            &new_pk.h,
+            &randoms,
+            &r,
+            &vec_r,
+            encryptions,
+            shares.clone(),
+            &sealer_id,
+            hash_function,
+        );
+        secret::wipe_all(&mut randoms);
+        secret::wipe(&mut r);
+        secret::wipe_all(&mut vec_r);
+
+        // submit the key switch shares + proof
+        let response = submit_key_switch_shares(
+            &client,
+            &signer,
+            vote_id.clone(),
+            topic_id,
+            Wrapper(shares).into(),
+            proof.into(),
+        )
+        .await?;
+        output::extrinsic("response", &response);
+    }
+    Ok(())
+}
+
+/// Subscribes to the events of the mixnet pallet and automatically
+/// submits the public key share and the partial decryptions of the sealer.
+pub async fn watch(sk_as_string: String, sealer: String) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+    let mut events = subscribe_mixnet_events(&client).await?;
+
+    // the votes and topics this sealer already submitted its shares for
+    let mut keys_submitted: HashSet<VoteId> = HashSet::new();
+    let mut decryptions_submitted: HashSet<(VoteId, TopicId)> = HashSet::new();
+    status!("watching for events...");
+
+    while let Some(event) = events.next().await {
+        match event? {
+            // new votes start in the key generation phase
+            MixnetEvent::VoteCreated { vote_id, .. } => {
+                if keys_submitted.insert(vote_id.clone()) {
+                    status!("vote created, submitting public key share...");
+                    submit_keygen(&client, vote_id, sk_as_string.clone(), sealer.clone()).await?;
+                }
+            }
+            MixnetEvent::VotePhaseChanged { vote_id, phase, .. } => {
+                if phase == VotePhase::KeyGeneration && keys_submitted.insert(vote_id.clone()) {
+                    status!("key generation started, submitting public key share...");
+                    submit_keygen(&client, vote_id, sk_as_string.clone(), sealer.clone()).await?;
+                }
+            }
+            MixnetEvent::PublicKeyShareSubmitted { share } => {
+                status!("public key share submitted: {:?}", share.pk);
+            }
+            // the ciphers of a topic are ready to be decrypted
+            MixnetEvent::ShuffleCompleted {
+                vote_id,
+                topic_id,
+                nr_of_shuffles,
+            } => {
+                if decryptions_submitted.insert((vote_id.clone(), topic_id.clone())) {
+                    status!("shuffling completed, submitting partial decryptions...");
+                    submit_decryption(
+                        &client,
+                        vote_id,
+                        topic_id,
+                        nr_of_shuffles,
+                        sk_as_string.clone(),
+                        sealer.clone(),
+                    )
+                    .await?;
+                }
+            }
+            MixnetEvent::CiphersAggregated { vote_id, topic_id } => {
+                if decryptions_submitted.insert((vote_id.clone(), topic_id.clone())) {
+                    status!("ciphers aggregated, submitting partial decryptions...");
+                    submit_decryption(
+                        &client,
+                        vote_id,
+                        topic_id,
+                        0,
+                        sk_as_string.clone(),
+                        sealer.clone(),
+                    )
+                    .await?;
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+/// Splits the private key share into fragments (Shamir secret sharing), any `threshold` fragments recover it.
+/// Each fragment is printed as `index-value` (value as hex) and should be handed to a different custodian.
+pub fn export_share(
+    mut sk_as_string: String,
+    threshold: u32,
+    nr_of_fragments: u32,
+) -> Result<(), Error> {
+    let (params, sk, _) = Helper::setup_lg_system_with_sk(sk_as_string.as_bytes());
+    sk_as_string.zeroize();
+    let q = params.q();
+
+    if threshold == 0 {
+        return Err("the threshold must be at least 1!".into());
+    }
+    let mut coefficients: Vec<BigUint> = (1..threshold)
+        .map(|_| Random::get_random_less_than(&q))
+        .collect();
+    let fragments = SecretSharing::split(&sk.x, &coefficients, nr_of_fragments, &q);
+    secret::wipe_all(&mut coefficients);
+    let fragments = fragments.ok_or("the number of fragments must be at least the threshold!")?;
+    for fragment in fragments.iter() {
+        output::value(
+            "fragment",
+            format!("{}-{:x}", fragment.index, fragment.value),
+        );
+    }
+    Ok(())
+}
+
+/// Recovers the private key share from (at least threshold many) fragments created by export_share.
+/// Prints the private key share and the corresponding public key share, to compare it with the one stored on-chain.
+pub fn recover_share(mut fragments_as_strings: Vec<String>) -> Result<(), Error> {
+    let result = recover_fragments(&fragments_as_strings);
+    fragments_as_strings.zeroize();
+    result
+}
+
+fn recover_fragments(fragments_as_strings: &[String]) -> Result<(), Error> {
+    let (params, _, _) = Helper::setup_lg_system();
+    let q = params.q();
+
+    let mut fragments: Vec<ShareFragment> = Vec::new();
+    for fragment in fragments_as_strings.iter() {
+        let parsed = parse_fragment(fragment).ok_or_else(|| {
+            format!(
+                "invalid fragment: {:?}, expected format: index-value",
+                fragment
+            )
+        })?;
+        fragments.push(parsed);
+    }
+
+    let mut sk = SecretSharing::reconstruct(&fragments, &q)
+        .ok_or("the fragments are invalid or contain duplicate indices!")?;
+    let mut sk_as_string = format!("{:x}", sk);
+    let (_, _, pk) = Helper::setup_lg_system_with_sk(sk_as_string.as_bytes());
+    output::value("sk", &sk_as_string);
+    output::value("pk_share", format!("0x{:x}", pk.h));
+    sk_as_string.zeroize();
+    secret::wipe(&mut sk);
+    Ok(())
+}
+
+fn parse_fragment(fragment: &str) -> Option<ShareFragment> {
+    let mut parts = fragment.splitn(2, '-');
+    let index = parts.next()?.parse::<u32>().ok()?;
+    let value = BigUint::parse_bytes(parts.next()?.as_bytes(), 16)?;
+    Some(ShareFragment { index, value })
+}
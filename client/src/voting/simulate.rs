@@ -0,0 +1,260 @@
+use crate::output;
+use crate::voting::sealer::{submit_credential_check, submit_decryption, submit_keygen};
+use codec::Encode;
+use crypto::{
+    encryption::ElGamal,
+    proofs::encryption::EncryptionProof,
+    random::Random,
+    types::{Cipher as BigCipher, PublicKey as ElGamalPK},
+};
+use futures::StreamExt;
+use mixnet_test_utils::{ElectionFixture, KeySize, VoteFixture};
+use num_bigint::BigUint;
+use num_traits::One;
+use pallet_mixnet::types::{Ballot, TallyStrategy, Topic, TopicResult, VotePhase};
+use provotum_client_sdk::events::{subscribe_mixnet_events, MixnetEvent};
+use provotum_client_sdk::rpc::{
+    combine_decrypted_shares, combine_pk_shares, get_tally, get_vote_public_key, propose_vote,
+    register_credential, set_vote_phase, submit_ballot, whitelist_vote,
+};
+use sp_keyring::{sr25519::sr25519::Pair, AccountKeyring};
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use substrate_subxt::{sp_core::Pair as KeyPairGenerator, Client, PairSigner};
+use substrate_subxt::{system::System, ClientBuilder, Error, NodeTemplateRuntime};
+
+/// the number of shuffles of the simulated vote
+const NR_OF_SHUFFLES: u8 = 3;
+
+/// the sealers of the dev chain, the sealers of the election fixture in the same order
+const SEALERS: [&str; 2] = ["bob", "charlie"];
+
+async fn init() -> Result<Client<NodeTemplateRuntime>, Error> {
+    env_logger::init();
+    let url = "ws://127.0.0.1:9944";
+    let client = ClientBuilder::<NodeTemplateRuntime>::new()
+        .set_url(url)
+        .build()
+        .await?;
+    Ok(client)
+}
+
+/// Simulates a complete election against a dev node and verifies the result:
+/// 1. create a vote with a single question, the sealers submit their public key shares
+/// 2. combine the public key shares (starts the voting phase) and register the voters' credentials
+/// 3. cast the ballots of the election fixture (the voters take turns) with their credentials
+/// 4. start the tallying phase, check the credentials and wait until the offchain workers have shuffled the ballots
+/// 5. submit the partial decryptions of the sealers and tally the question
+/// 6. compare the tally with the expected tally of the election fixture
+///
+/// the keys of the sealers and the ballots are the ones of an `ElectionFixture`, i.e., the
+/// i-th ballot votes for the (i mod #votes)-th vote.
+pub async fn simulate(
+    nr_of_voters: usize,
+    nr_of_ballots: usize,
+    votes: Vec<u32>,
+) -> Result<(), Error> {
+    if nr_of_voters == 0 || nr_of_ballots == 0 || votes.is_empty() {
+        return Err("at least one voter, ballot and allowed vote are required!".into());
+    }
+
+    // init substrate client, the voting authority of the dev chain creates and tallies the vote
+    let client = init().await?;
+    let voting_authority =
+        PairSigner::<NodeTemplateRuntime, Pair>::new(AccountKeyring::Alice.pair());
+
+    // the sealers use the 2048bit system, see: keygen
+    let params = KeySize::Large.params();
+    let q = params.q();
+
+    // only quadratic residues can be encrypted without encoding the vote
+    if let Some(vote) = votes
+        .iter()
+        .find(|vote| BigUint::from(**vote).modpow(&q, &params.p) != BigUint::one())
+    {
+        return Err(format!("the vote: {:?} is not a quadratic residue mod p!", vote).into());
+    }
+
+    // every simulation uses a new vote
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time went backwards!")
+        .as_millis();
+    let vote_id = format!("simulation-{}", timestamp).as_bytes().to_vec();
+    let topic_id = format!("simulation-{}-01", timestamp).as_bytes().to_vec();
+    let topic: Topic = Topic::new(topic_id.clone(), "Simulated Question?".as_bytes().to_vec());
+    output::value("vote", String::from_utf8_lossy(&vote_id));
+
+    // the votes are encrypted without encoding, see: the quadratic residues above
+    let messages: Vec<u64> = votes.iter().map(|vote| *vote as u64).collect();
+    let fixture = ElectionFixture::builder()
+        .key_size(KeySize::Large)
+        .sealers(SEALERS.len() as u32)
+        .vote(&vote_id, &[topic_id.as_slice()])
+        .ballots(nr_of_ballots as u32)
+        .messages(&messages)
+        .encoded(false)
+        .build();
+    let vote: &VoteFixture = fixture
+        .vote(&vote_id)
+        .expect("the vote of the fixture exists!");
+
+    // 1. create the vote (requires an approval threshold of 1)
+    //    the sealers use the private key shares of the fixture
+    propose_vote(
+        &client,
+        &voting_authority,
+        params.clone().into(),
+        vote_id.clone(),
+        vote_id.clone(),
+        vec![topic],
+        75,
+        NR_OF_SHUFFLES,
+        TallyStrategy::Mixnet,
+    )
+    .await?;
+    // the simulated voters don't hold any funds to reserve the ballot deposit
+    whitelist_vote(&client, &voting_authority, vote_id.clone(), true).await?;
+    let sealer_keys: Vec<String> = fixture
+        .sealers
+        .iter()
+        .map(|sealer| format!("{:x}", sealer.sk.x))
+        .collect();
+    for (sealer, sk) in SEALERS.iter().zip(sealer_keys.iter()) {
+        submit_keygen(&client, vote_id.clone(), sk.clone(), sealer.to_string()).await?;
+    }
+
+    // 2. create the public key of the vote -> VotePhase::Voting
+    combine_pk_shares(&client, &voting_authority, vote_id.clone()).await?;
+    let pk: ElGamalPK = get_vote_public_key(&client, vote_id.clone()).await?.into();
+    if pk != fixture.public_key {
+        return Err("the public key of the vote doesn't match the one of the fixture!".into());
+    }
+
+    // the voters are derived from their index, see: create_votes
+    let mut voters: Vec<(Pair, BigUint)> = Vec::with_capacity(nr_of_voters);
+    let progress = output::progress_bar(nr_of_voters as u64, "registering voters");
+    for index in 0..nr_of_voters {
+        let voter: Pair = KeyPairGenerator::from_string(&format!("//{}", index), None)?;
+        let credential = Random::get_random_less_than(&q);
+        let r = Random::get_random_less_than(&q);
+        let encrypted = ElGamal::encrypt_encode(&credential, &r, &pk);
+        register_credential(
+            &client,
+            &voting_authority,
+            vote_id.clone(),
+            voter.public().into(),
+            encrypted.into(),
+        )
+        .await?;
+        voters.push((voter, credential));
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+    status!("registered {:?} voters", nr_of_voters);
+
+    // 3. cast the ballots of the fixture
+    let progress = output::progress_bar(nr_of_ballots as u64, "casting ballots");
+    for (index, fixture_ballot) in vote.ballots.iter().enumerate() {
+        let (_, cipher) = fixture_ballot.answers[0].clone();
+        let cipher: BigCipher = cipher.into();
+
+        // bind the cipher to the voter, a copy cannot be submitted by another voter
+        let (voter, credential) = voters[index % nr_of_voters].clone();
+        let account: <NodeTemplateRuntime as System>::AccountId = voter.public().into();
+        let x = Random::get_random_less_than(&q);
+        let r = &fixture_ballot.randoms[0];
+        let proof = EncryptionProof::generate(&params, r, &cipher, &x, &account.encode());
+
+        // every ballot carries a fresh encryption of the voter's credential
+        let r = Random::get_random_less_than(&q);
+        let credential = ElGamal::encrypt_encode(&credential, &r, &pk);
+        let ballot: Ballot = Ballot {
+            answers: vec![(topic_id.clone(), cipher.into())],
+            proofs: vec![proof.into()],
+            validity_proofs: Vec::new(),
+            credential: Some(credential.into()),
+        };
+        let voter = PairSigner::<NodeTemplateRuntime, Pair>::new(voter);
+        submit_ballot(&client, &voter, vote_id.clone(), ballot).await?;
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+    status!("cast {:?} ballots", nr_of_ballots);
+
+    // 4. start the tallying phase, the offchain workers of the sealers shuffle the ballots
+    let mut events = subscribe_mixnet_events(&client).await?;
+    set_vote_phase(
+        &client,
+        &voting_authority,
+        vote_id.clone(),
+        VotePhase::Tallying,
+    )
+    .await?;
+
+    // the ballots are shuffled once the sealers have blinded and decrypted the credentials
+    for round in 0..2 {
+        for (sealer, sk) in SEALERS.iter().zip(sealer_keys.iter()) {
+            submit_credential_check(&client, vote_id.clone(), sk.clone(), sealer.to_string())
+                .await?;
+        }
+        status!("credential check: round {:?} completed", round + 1);
+    }
+    status!("waiting for the shuffling to complete...");
+    let nr_of_shuffles = loop {
+        match events.next().await {
+            Some(Ok(MixnetEvent::ShuffleCompleted {
+                vote_id: shuffled_vote_id,
+                topic_id: shuffled_topic_id,
+                nr_of_shuffles,
+            })) if shuffled_vote_id == vote_id && shuffled_topic_id == topic_id => {
+                break nr_of_shuffles
+            }
+            Some(Ok(_)) => continue,
+            Some(Err(err)) => return Err(err),
+            None => return Err("the event subscription has been closed!".into()),
+        }
+    };
+
+    // 5. decrypt and tally the question
+    for (sealer, sk) in SEALERS.iter().zip(sealer_keys.iter()) {
+        submit_decryption(
+            &client,
+            vote_id.clone(),
+            topic_id.clone(),
+            nr_of_shuffles,
+            sk.clone(),
+            sealer.to_string(),
+        )
+        .await?;
+    }
+    combine_decrypted_shares(
+        &client,
+        &voting_authority,
+        vote_id.clone(),
+        topic_id.clone(),
+        false,
+        nr_of_shuffles,
+    )
+    .await?;
+
+    // 6. verify the result
+    let expected: BTreeMap<u64, u32> = vote.expected_tally(&topic_id);
+    let result: TopicResult = get_tally(&client, vote_id, topic_id).await?;
+    let expected: TopicResult = expected
+        .into_iter()
+        .map(|(vote, count)| {
+            (
+                BigUint::from(vote).to_bytes_be(),
+                BigUint::from(count).to_bytes_be(),
+            )
+        })
+        .collect();
+    if result != expected {
+        status!("expected: {:?}", expected);
+        status!("tallied: {:?}", result);
+        return Err("the tally doesn't match the cast votes!".into());
+    }
+    status!("the tally matches the cast votes: {:?}", result);
+    Ok(())
+}
@@ -1,3 +0,0 @@
-pub mod calls;
-pub mod rpc;
-pub mod stores;
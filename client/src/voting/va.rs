@@ -1,11 +1,34 @@
-use crate::voting::substrate::rpc::{
-    combine_decrypted_shares, combine_pk_shares, create_vote, get_tally, set_vote_phase,
-    store_question,
+use crate::output;
+use async_std::task;
+use crypto::{
+    blind::{BlindSignature, BlindSignaturePrivateKey, BlindSignaturePublicKey},
+    encryption::{DecodeStatus, DecodeTask, ElGamal, DEFAULT_PROGRESS_INTERVAL},
+    helper::Helper,
+    random::Random,
+    types::{Cipher as BigCipher, ElGamalParams, PublicKey as ElGamalPK},
 };
-use crypto::helper::Helper;
-use pallet_mixnet::types::{Topic, VotePhase};
+use num_bigint::BigUint;
+use pallet_mixnet::types::{
+    Cipher, HashFunction, QuestionType, RankedTallyMethod, TallyStrategy, Topic, TopicId,
+    TopicPacking, VoteId, VoteMetadata, VotePhase, VoteTemplate, MAX_CIPHERS_PER_IMPORT, NO_ANSWER,
+};
+use provotum_client_sdk::rpc::{
+    add_sealer, aggregate_ciphers, approve_vote, archive_vote, cancel_vote, certify_result,
+    combine_decrypted_shares, combine_pk_shares, create_vote_from_template, get_aggregate_cipher,
+    get_ciphers, get_decrypted_shares, get_default_public_parameters, get_shuffle_batch_size,
+    get_tally, get_topics, get_vote, get_vote_public_key, get_vote_result, get_vote_sealers,
+    import_ciphers, propose_vote, register_credential, remove_sealer, revoke_voter,
+    rotate_public_key, set_decryption_threshold, set_eligibility_key, set_group_check,
+    set_hash_function, set_linked_shuffle, set_metadata, set_shuffle_batch_size, set_topic_packing,
+    set_vote_phase, set_voter_weight, store_question, store_vote_template, whitelist_vote,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sp_keyring::{sr25519::sr25519::Pair, AccountKeyring};
+use std::fs;
 use std::str::FromStr;
-use substrate_subxt::Client;
+use std::time::{Duration, Instant};
+use substrate_subxt::{sp_core::Pair as KeyPairGenerator, Client, PairSigner};
 use substrate_subxt::{ClientBuilder, Error, NodeTemplateRuntime};
 
 async fn init() -> Result<Client<NodeTemplateRuntime>, Error> {
@@ -18,35 +41,50 @@ async fn init() -> Result<Client<NodeTemplateRuntime>, Error> {
     Ok(client)
 }
 
-pub async fn setup_vote(vote_title: String, topic_question: String) -> Result<(), Error> {
+/// the voting authority which signs the extrinsics of the dev chain
+fn voting_authority() -> PairSigner<NodeTemplateRuntime, Pair> {
+    PairSigner::<NodeTemplateRuntime, Pair>::new(AccountKeyring::Alice.pair())
+}
+
+pub async fn setup_vote(
+    vote_title: String,
+    topic_question: String,
+    nr_of_shuffles: u8,
+    tally_strategy: String,
+) -> Result<(), Error> {
     // init substrate client
     let client = init().await?;
 
-    // create the vote
-    let (params, _, _) = Helper::setup_lg_system();
+    // create the vote, use the default public parameters of the chain if configured
+    let params = match get_default_public_parameters(&client).await? {
+        Some(params) => params,
+        None => Helper::setup_lg_system().0.into(),
+    };
     let vote_id = vote_title.as_bytes().to_vec();
     let vote_title = vote_title.as_bytes().to_vec();
 
     // create the question
     let topic_id = topic_question.as_bytes().to_vec();
     let topic_question = topic_question.as_bytes().to_vec();
-    let topic: Topic = (topic_id.clone(), topic_question);
+    let topic: Topic = Topic::new(topic_id.clone(), topic_question);
     let topics = vec![topic];
+    let tally_strategy = TallyStrategy::from_str(&tally_strategy)
+        .expect("only valid TallyStrategy values should be parsed!");
 
-    // setup the vote
-    let create_vote_response = create_vote(
+    // propose the vote, it is created once sufficient voting authorities approved it
+    let create_vote_response = propose_vote(
         &client,
-        params.into(),
+        &voting_authority(),
+        params,
         vote_title,
         vote_id.clone(),
         topics,
         75,
+        nr_of_shuffles,
+        tally_strategy,
     )
     .await?;
-    println!(
-        "create_vote_response: {:?}",
-        create_vote_response.events[0].variant
-    );
+    output::extrinsic("create_vote_response", &create_vote_response);
     // // DON'T USE THIS IN PRODUCTION ONLY FOR DEV PURPOSES
     // // setup the public key
     // let public_key_response = store_public_key(&client, vote_id.clone(), pk.clone().into()).await?;
@@ -57,7 +95,33 @@ pub async fn setup_vote(vote_title: String, topic_question: String) -> Result<()
     Ok(())
 }
 
-pub async fn setup_question(vote: String, question: String) -> Result<(), Error> {
+/// the question type as given on the command line, the limit is the number of candidates
+/// a voter may select (MultiSelect) or has to rank (Ranked)
+fn parse_question_type(question_type: &str, limit: u32) -> QuestionType {
+    match question_type {
+        "MultiSelect" => QuestionType::MultiSelect { max: limit },
+        "Ranked" => QuestionType::Ranked { candidates: limit },
+        _ => QuestionType::SingleChoice,
+    }
+}
+
+/// the tally method of a ranked question as given on the command line
+fn parse_ranked_tally(ranked_tally: &str) -> RankedTallyMethod {
+    match ranked_tally {
+        "Borda" => RankedTallyMethod::Borda,
+        "InstantRunoff" => RankedTallyMethod::InstantRunoff,
+        _ => RankedTallyMethod::FirstPreference,
+    }
+}
+
+pub async fn setup_question(
+    vote: String,
+    question: String,
+    candidates: Vec<String>,
+    question_type: String,
+    limit: u32,
+    ranked_tally: String,
+) -> Result<(), Error> {
     // init substrate client
     let client = init().await?;
 
@@ -65,15 +129,30 @@ pub async fn setup_question(vote: String, question: String) -> Result<(), Error>
     let vote_id = vote.as_bytes().to_vec();
     let topic_id = question.as_bytes().to_vec();
     let topic_question = question.as_bytes().to_vec();
-    let topic: Topic = (topic_id.clone(), topic_question);
+    let topic: Topic = Topic {
+        candidates: candidates
+            .iter()
+            .map(|candidate| candidate.as_bytes().to_vec())
+            .collect(),
+        question_type: parse_question_type(&question_type, limit),
+        ranked_tally: parse_ranked_tally(&ranked_tally),
+        ..Topic::new(topic_id, topic_question)
+    };
+    if !topic.is_valid() {
+        return Err("the question type doesn't match the candidates or the tally method!".into());
+    }
 
     // store question
-    let response = store_question(&client, vote_id, topic, 75).await?;
-    println!("response: {:?}", response.events[0].variant);
+    let response = store_question(&client, &voting_authority(), vote_id, topic, 75).await?;
+    output::extrinsic("response", &response);
     Ok(())
 }
 
-pub async fn change_vote_phase(vote: String, vote_phase: String) -> Result<(), Error> {
+pub async fn change_vote_phase(
+    vote: String,
+    vote_phase: String,
+    authority: String,
+) -> Result<(), Error> {
     // init substrate client
     let client = init().await?;
 
@@ -81,10 +160,27 @@ pub async fn change_vote_phase(vote: String, vote_phase: String) -> Result<(), E
     let vote_id = vote.as_bytes().to_vec();
     let vote_phase =
         VotePhase::from_str(&vote_phase).expect("only valid VotePhase values should be parsed!");
+    let authority: Pair = KeyPairGenerator::from_string(&authority, None)?;
+    let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(authority);
 
-    // update vote phase to Voting
-    let response = set_vote_phase(&client, vote_id.clone(), vote_phase).await?;
-    println!("response: {:?}", response.events[0].variant);
+    // update vote phase, the tallying phase requires the approval of multiple voting authorities
+    let response = set_vote_phase(&client, &signer, vote_id.clone(), vote_phase).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Approves a proposed vote as one of the voting authorities (m-of-n).
+pub async fn approve(vote: String, authority: String) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+    let authority: Pair = KeyPairGenerator::from_string(&authority, None)?;
+    let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(authority);
+
+    let response = approve_vote(&client, &signer, vote_id).await?;
+    output::extrinsic("response", &response);
     Ok(())
 }
 
@@ -96,40 +192,590 @@ pub async fn combine_public_key_shares(vote: String) -> Result<(), Error> {
     let vote_id = vote.as_bytes().to_vec();
 
     // update vote phase to Voting
-    let response = combine_pk_shares(&client, vote_id.clone()).await?;
-    println!("response: {:?}", response.events[0].variant);
+    let response = combine_pk_shares(&client, &voting_authority(), vote_id.clone()).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+pub async fn rotate_key(vote: String) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+
+    // rotate the public key -> the sealers need to submit new key shares
+    let response = rotate_public_key(&client, &voting_authority(), vote_id).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Generates a fresh credential for a voter and registers it (encrypted under the vote's public key).
+/// The credential is handed to the voter, who can later create fake credentials to satisfy a coercer.
+pub async fn issue_credential(vote: String, voter: usize) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // the voters are derived from their index, see: create_votes
+    let index_string = (voter as u64).to_string();
+    let voter_keypair: Pair = KeyPairGenerator::from_string(&format!("//{}", index_string), None)?;
+    let account = voter_keypair.public().into();
+
+    // generate the credential and encrypt it under the public key of the vote
+    let vote_id = vote.as_bytes().to_vec();
+    let pk: ElGamalPK = get_vote_public_key(&client, vote_id.clone()).await?.into();
+    let q = &pk.params.q();
+    let credential = Random::get_random_less_than(q);
+    let r = Random::get_random_less_than(q);
+    let encrypted = ElGamal::encrypt_encode(&credential, &r, &pk);
+
+    // store the encrypted credential
+    let response = register_credential(
+        &client,
+        &voting_authority(),
+        vote_id,
+        account,
+        encrypted.into(),
+    )
+    .await?;
+    output::extrinsic("response", &response);
+    output::value("credential", format!("0x{:x}", credential));
+    Ok(())
+}
+
+/// The key pair which blind-signs the eligibility tokens of a vote, kept by the voting authority.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EligibilityKeyPair {
+    pub vote: String,
+    pub pk: BlindSignaturePublicKey,
+    pub sk: BlindSignaturePrivateKey,
+}
+
+/// Generates the key pair which blind-signs the eligibility tokens of the vote and sets its
+/// public key, i.e., the vote only accepts anonymous ballots. The key pair is written to the output.
+/// The key is removed if `remove` is set, i.e., the vote accepts the ballots of accounts again.
+pub async fn update_eligibility_key(
+    vote: String,
+    output: String,
+    bits: u64,
+    remove: bool,
+) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+    let key = match remove {
+        true => None,
+        false => {
+            let (pk, sk) = BlindSignature::generate_key_pair(bits);
+            let key_pair = EligibilityKeyPair {
+                vote,
+                pk: pk.clone(),
+                sk,
+            };
+            let json = serde_json::to_string_pretty(&key_pair)
+                .map_err(|error| Error::Other(error.to_string()))?;
+            fs::write(&output, json).map_err(|error| Error::Other(error.to_string()))?;
+            output::value("eligibility_key_pair", &output);
+            Some(pk.into())
+        }
+    };
+
+    let response = set_eligibility_key(&client, &voting_authority(), vote_id, key).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Blind-signs the (hex encoded) blinded token message of a voter, see: `voter request_token`.
+/// The eligibility of the voter must be checked before, the signature can't be linked to the ballot.
+pub fn sign_token(key: String, blinded: String) -> Result<(), Error> {
+    let json = fs::read_to_string(&key).map_err(|error| Error::Other(error.to_string()))?;
+    let key_pair: EligibilityKeyPair =
+        serde_json::from_str(&json).map_err(|error| Error::Other(error.to_string()))?;
+    let blinded = BigUint::parse_bytes(blinded.trim_start_matches("0x").as_bytes(), 16)
+        .ok_or("the blinded token message must be hex encoded!")?;
+    if blinded >= key_pair.pk.n {
+        return Err("the blinded token message must be less than the modulus!".into());
+    }
+
+    let blind_signature = BlindSignature::sign_blinded(&key_pair.sk, &blinded);
+    output::value("vote", &key_pair.vote);
+    output::value("blind_signature", format!("0x{:x}", blind_signature));
+    Ok(())
+}
+
+/// Imports the ciphers of the file (a JSON array) for the question, in batches of `MAX_CIPHERS_PER_IMPORT` ciphers.
+/// Every batch is recorded with the source tag and the merkle root of its ciphers.
+pub async fn import_question_ciphers(
+    vote: String,
+    question: String,
+    file: String,
+    source: String,
+) -> Result<(), Error> {
+    let json = fs::read_to_string(&file).map_err(|error| Error::Other(error.to_string()))?;
+    let ciphers: Vec<BigCipher> =
+        serde_json::from_str(&json).map_err(|error| Error::Other(error.to_string()))?;
+
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+    let topic_id = question.as_bytes().to_vec();
+    let ciphers: Vec<Cipher> = ciphers.into_iter().map(Cipher::from).collect();
+
+    let progress = output::progress_bar(ciphers.len() as u64, "importing ciphers");
+    for batch in ciphers.chunks(MAX_CIPHERS_PER_IMPORT) {
+        let response = import_ciphers(
+            &client,
+            &voting_authority(),
+            vote_id.clone(),
+            topic_id.clone(),
+            batch.to_vec(),
+            source.as_bytes().to_vec(),
+        )
+        .await?;
+        progress.inc(batch.len() as u64);
+        output::extrinsic("response", &response);
+    }
+    progress.finish_and_clear();
+    output::value("imported_ciphers", ciphers.len());
+    Ok(())
+}
+
+/// Stores the questions, public parameters and tally strategy of the vote as template of recurring votes.
+/// The vote_id prefix of the question ids is removed, it is added again for every vote created from the template.
+pub async fn save_template(template: String, vote: String) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let template_id = template.as_bytes().to_vec();
+    let vote_id: VoteId = vote.as_bytes().to_vec();
+    let vote = get_vote(&client, vote_id.clone()).await?;
+    let mut prefix = vote_id.clone();
+    prefix.push(b'-');
+    let topics: Vec<Topic> = get_topics(&client, vote_id.clone())
+        .await?
+        .into_iter()
+        .map(|topic| Topic {
+            id: topic
+                .id
+                .strip_prefix(prefix.as_slice())
+                .map(|id| id.to_vec())
+                .unwrap_or(topic.id),
+            ..topic
+        })
+        .collect();
+    let batch_size = get_shuffle_batch_size(&client, vote_id)
+        .await?
+        .unwrap_or(75);
+    let template = VoteTemplate {
+        params: vote.params,
+        topics,
+        batch_size,
+        nr_of_shuffles: vote.nr_of_shuffles,
+        tally_strategy: vote.tally_strategy,
+    };
+
+    let response = store_vote_template(&client, &voting_authority(), template_id, template).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Proposes a vote with the configuration of the template,
+/// it is created once sufficient voting authorities approved it.
+pub async fn create_from_template(
+    template: String,
+    vote: String,
+    title: String,
+) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let template_id = template.as_bytes().to_vec();
+    let vote_id = vote.as_bytes().to_vec();
+    let title = title.as_bytes().to_vec();
+
+    let response =
+        create_vote_from_template(&client, &voting_authority(), template_id, vote_id, title)
+            .await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Certifies the result of a tallied vote as one of the voting authorities.
+/// The result is final once a voting authority and the runtime's `CertificationQuorum` sealers certified it.
+pub async fn certify(vote: String, authority: String) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+    let authority: Pair = KeyPairGenerator::from_string(&authority, None)?;
+    let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(authority);
+
+    // the hash of the results of all questions of the vote
+    let result_hash = get_vote_result(&client, vote_id.clone()).await?.hash();
+    output::value("result_hash", hex::encode(result_hash));
+
+    let response = certify_result(&client, &signer, vote_id, result_hash).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Adds (or removes) the sealer to (from) the sealers of new votes as a voting authority.
+/// The votes created before keep their sealers, see: `SealerSets`.
+pub async fn change_sealer(sealer: String, authority: String, add: bool) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let sealer: Pair = KeyPairGenerator::from_string(&sealer, None)?;
+    let account = sealer.public().into();
+    let authority: Pair = KeyPairGenerator::from_string(&authority, None)?;
+    let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(authority);
+
+    let response = match add {
+        true => add_sealer(&client, &signer, account).await?,
+        false => remove_sealer(&client, &signer, account).await?,
+    };
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Approves the cancellation of a vote as one of the voting authorities.
+/// The vote is cancelled once sufficient voting authorities approved it (see: `ApprovalThreshold`).
+pub async fn cancel(vote: String, reason: String, authority: String) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+    let reason = reason.as_bytes().to_vec();
+    let authority: Pair = KeyPairGenerator::from_string(&authority, None)?;
+    let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(authority);
+
+    let response = cancel_vote(&client, &signer, vote_id, reason).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+pub async fn archive(vote: String) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+
+    // prune the ciphers and proofs of the tallied vote
+    let response = archive_vote(&client, &voting_authority(), vote_id).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Sets the metadata of a vote to an IPFS hash or the JSON document in the file.
+/// Removes the metadata if neither is provided.
+pub async fn update_metadata(
+    vote: String,
+    ipfs: Option<String>,
+    file: Option<String>,
+) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+    let metadata = match (ipfs, file) {
+        (Some(hash), _) => Some(VoteMetadata::Ipfs(hash.as_bytes().to_vec())),
+        (None, Some(path)) => {
+            let document = std::fs::read(path)?;
+            serde_json::from_slice::<serde_json::Value>(&document)
+                .map_err(|err| Error::Other(format!("the metadata is not valid JSON: {}", err)))?;
+            Some(VoteMetadata::Inline(document))
+        }
+        (None, None) => None,
+    };
+
+    let response = set_metadata(&client, &voting_authority(), vote_id, metadata).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Sets the number of ciphers the sealers shuffle per batch.
+pub async fn update_shuffle_batch_size(vote: String, batch_size: u64) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+
+    let response =
+        set_shuffle_batch_size(&client, &voting_authority(), vote_id, batch_size).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Sets the number of sealers whose decrypted shares suffice to tally the vote.
+pub async fn update_decryption_threshold(vote: String, threshold: u32) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+
+    let response =
+        set_decryption_threshold(&client, &voting_authority(), vote_id, threshold).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Sets the hash function of the challenges of the proofs of the vote,
+/// before the sealers submit their public key shares.
+pub async fn update_hash_function(vote: String, hash_function: String) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+    let hash_function = HashFunction::from_str(&hash_function)
+        .expect("only valid HashFunction values should be parsed!");
+
+    let response = set_hash_function(&client, &voting_authority(), vote_id, hash_function).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Enables (or disables) the linked shuffles of the vote, i.e., all topics are shuffled with the same permutation.
+pub async fn update_linked_shuffle(vote: String, linked: bool) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+
+    let response = set_linked_shuffle(&client, &voting_authority(), vote_id, linked).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Enables (or disables) the group membership check of the ciphers of the vote's ballots.
+pub async fn update_group_check(vote: String, enabled: bool) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+
+    let response = set_group_check(&client, &voting_authority(), vote_id, enabled).await?;
+    output::extrinsic("response", &response);
     Ok(())
 }
 
-pub async fn tally_question(vote: String, question: String) -> Result<(), Error> {
+/// packs (slots, slot_bits) the answers of the question, None unpacks it
+pub async fn update_topic_packing(
+    vote: String,
+    question: String,
+    packing: Option<(u32, u32)>,
+) -> Result<(), Error> {
     // init substrate client
     let client = init().await?;
 
     // create input parameters
     let vote_id = vote.as_bytes().to_vec();
     let topic_id = question.as_bytes().to_vec();
+    let packing = packing.map(|(slots, slot_bits)| TopicPacking { slots, slot_bits });
+
+    let response =
+        set_topic_packing(&client, &voting_authority(), vote_id, topic_id, packing).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+pub async fn update_voter_weight(
+    vote: String,
+    voter: usize,
+    weight: Option<u32>,
+) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // the voters are derived from their index, see: create_votes
+    let index_string = (voter as u64).to_string();
+    let voter_keypair: Pair = KeyPairGenerator::from_string(&format!("//{}", index_string), None)?;
+    let account = voter_keypair.public().into();
+
+    let vote_id = vote.as_bytes().to_vec();
+    let response = set_voter_weight(&client, &voting_authority(), vote_id, account, weight).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Revokes the voter as a voting authority, the ciphers of its last ballot are excluded from the tally.
+/// Voters can only be revoked until the voting phase has ended.
+pub async fn revoke(vote: String, voter: usize) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // the voters are derived from their index, see: create_votes
+    let index_string = (voter as u64).to_string();
+    let voter_keypair: Pair = KeyPairGenerator::from_string(&format!("//{}", index_string), None)?;
+    let account = voter_keypair.public().into();
+
+    let vote_id = vote.as_bytes().to_vec();
+    let response = revoke_voter(&client, &voting_authority(), vote_id, account).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+pub async fn whitelist(vote: String, whitelisted: bool) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+
+    let response = whitelist_vote(&client, &voting_authority(), vote_id, whitelisted).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+pub async fn aggregate_question(vote: String, question: String) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+    let topic_id = question.as_bytes().to_vec();
+
+    // homomorphically add all ciphers of the question
+    let response = aggregate_ciphers(&client, &voting_authority(), vote_id, topic_id).await?;
+    output::extrinsic("response", &response);
+    Ok(())
+}
+
+/// Decodes the decrypted aggregate of a homomorphic vote, i.e. the number of yes votes.
+/// The decoding is performed in steps, after each step the progress is reported and
+/// control is yielded to other tasks. The decoding is cancelled once the timeout is exceeded.
+async fn decode_aggregate(
+    client: &Client<NodeTemplateRuntime>,
+    params: &ElGamalParams,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    timeout: Option<u64>,
+) -> Result<BigUint, Error> {
+    let aggregate: BigCipher = get_aggregate_cipher(client, vote_id.clone(), topic_id.clone())
+        .await?
+        .ok_or("the ciphers of the question have not been aggregated!")?
+        .into();
+
+    // combine the partial decryptions of all sealers of the vote
+    let mut partial_decryptions: Vec<BigUint> = Vec::new();
+    for sealer in get_vote_sealers(client, vote_id.clone()).await? {
+        let shares =
+            get_decrypted_shares(client, vote_id.clone(), topic_id.clone(), sealer).await?;
+        let share = shares
+            .first()
+            .ok_or("not all sealers have decrypted the aggregate!")?;
+        partial_decryptions.push(BigUint::from_bytes_be(share));
+    }
+    let decrypted_a = ElGamal::combine_partial_decrypted_a(partial_decryptions, &params.p);
+    let encoded = ElGamal::partial_decrypt_b(&aggregate.b, &decrypted_a, &params.p);
+
+    // the number of yes votes can't be larger than the number of submitted votes
+    let total = get_ciphers(client, vote_id, topic_id, 0).await?.len() as u64;
+    let deadline = timeout.map(|seconds| Instant::now() + Duration::from_secs(seconds));
+    let mut decode_task = DecodeTask::new(&encoded, &params.g, &params.p, total);
+    let progress_bar = output::progress_bar(decode_task.progress().total_steps, "decoding tally");
+    loop {
+        match decode_task.poll(DEFAULT_PROGRESS_INTERVAL) {
+            DecodeStatus::Decoded(yes) => {
+                progress_bar.finish_and_clear();
+                return Ok(yes);
+            }
+            DecodeStatus::NotFound => {
+                progress_bar.abandon();
+                return Err("the decrypted aggregate is larger than the number of votes!".into());
+            }
+            _ => {
+                let progress = decode_task.progress();
+                progress_bar.set_length(progress.total_steps);
+                progress_bar.set_position(progress.steps);
+                if deadline.map_or(false, |deadline| Instant::now() > deadline) {
+                    progress_bar.abandon();
+                    return Err("decoding the tally has been cancelled: timeout exceeded!".into());
+                }
+                task::yield_now().await;
+            }
+        }
+    }
+}
+
+pub async fn tally_question(
+    vote: String,
+    question: String,
+    timeout: Option<u64>,
+) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
+    let topic_id = question.as_bytes().to_vec();
+
+    // decode the homomorphic tally locally first, the progress is shown while decoding
+    // the tally is only submitted if the decoding succeeds within the timeout
+    let vote_state = get_vote(&client, vote_id.clone()).await?;
+    if vote_state.tally_strategy == TallyStrategy::Homomorphic {
+        let params: ElGamalParams = vote_state.params.into();
+        let yes =
+            decode_aggregate(&client, &params, vote_id.clone(), topic_id.clone(), timeout).await?;
+        output::value("yes_votes", yes.to_string());
+    }
 
     // update vote phase to Voting
-    let response = combine_decrypted_shares(&client, vote_id, topic_id).await?;
-    println!(
-        "response: {:?}, data: {:?}",
-        response.events[0].variant, response.events[0]
-    );
+    let response = combine_decrypted_shares(
+        &client,
+        &voting_authority(),
+        vote_id,
+        topic_id,
+        false,
+        vote_state.nr_of_shuffles,
+    )
+    .await?;
+    output::extrinsic("response", &response);
+    status!("data: {:?}", response.events.first());
     Ok(())
 }
 
-pub async fn get_result(question: String) -> Result<(), Error> {
+pub async fn get_result(vote: String, question: String) -> Result<(), Error> {
     // init substrate client
     let client = init().await?;
 
     // create input parameters
+    let vote_id = vote.as_bytes().to_vec();
     let topic_id = question.as_bytes().to_vec();
 
     // update vote phase to Voting
-    let result = get_tally(&client, topic_id).await?;
-    println!("The result of the question: {:?} is...", question);
+    let result = get_tally(&client, vote_id, topic_id).await?;
+    status!("The result of the question: {:?} is...", question);
+    let no_answer = BigUint::from(NO_ANSWER).to_bytes_be();
     for (vote, count) in result {
-        println!("\tVote: {:?}, Count: {:?}", vote, count);
+        if vote == no_answer {
+            status!("\tNo Answer, Count: {:?}", count);
+        } else {
+            status!("\tVote: {:?}, Count: {:?}", vote, count);
+        }
+        output::record(
+            "result",
+            json!({
+                "vote": BigUint::from_bytes_be(&vote).to_string(),
+                "no_answer": vote == no_answer,
+                "count": BigUint::from_bytes_be(&count).to_string(),
+            }),
+        );
     }
     Ok(())
 }
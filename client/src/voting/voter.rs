@@ -1,17 +1,48 @@
-use crate::voting::substrate::rpc::submit_ballot;
+use crate::errors::describe;
+use crate::output;
+use async_std::io;
+use codec::{Decode, Encode};
 use crypto::{
-    proofs::re_encryption::ReEncryptionProof,
+    blind::{BlindSignature, BlindSignaturePublicKey},
+    derivation::Derivation,
+    encryption::ElGamal,
+    hash::HashFunction,
+    helper::Helper,
+    proofs::{membership::MembershipProof, re_encryption::ReEncryptionProof},
     types::{Cipher, PublicKey},
 };
 use crypto::{random::Random, types::PublicKey as ElGamalPK};
-use pallet_mixnet::types::Ballot;
+use futures::{stream, StreamExt};
+use mixnet_primitives::relay::{relayed_ballot_message, CompactBallot};
+use mixnet_primitives::tokens::{anonymous_ballot_message, eligibility_token_message, nullifier};
+use num_bigint::BigUint;
+use num_traits::One;
+use pallet_mixnet::types::{Ballot, TallyStrategy, TopicResult, ValidityProof, NO_ANSWER};
+use provotum_client_sdk::calls::CastBallot;
+use provotum_client_sdk::events::{subscribe_mixnet_events, MixnetEvent};
+use provotum_client_sdk::rpc::{
+    claim_ballot_deposit, submit_anonymous_ballot, submit_ballot, submit_relayed_ballot,
+    validate_ballot, watch_finalized,
+};
+use rand::distributions::{Distribution, WeightedIndex};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sp_keyring::sr25519::sr25519::Pair;
-use substrate_subxt::{sp_core::Pair as KeyPairGenerator, Client};
-use substrate_subxt::{ClientBuilder, Error, NodeTemplateRuntime, PairSigner};
+use std::collections::BTreeMap;
+use std::fs;
+use substrate_subxt::{
+    sp_core::{sr25519, Pair as KeyPairGenerator},
+    Client,
+};
+use substrate_subxt::{
+    system::System, ClientBuilder, Error, NodeTemplateRuntime, PairSigner, Signer,
+};
 use surf::Body;
 
-use super::substrate::rpc::get_vote_public_key;
+use provotum_client_sdk::rpc::{
+    get_ballot_receipt, get_ciphers, get_eligibility_key, get_tally, get_topics, get_vote,
+    get_vote_public_key, get_voter_weight, is_nullifier_spent,
+};
 
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
 pub struct RequestBody {
@@ -54,11 +85,434 @@ pub async fn create_votes(
     let encryptions = Random::generate_encryptions(&pk, q, nr_of_votes, votes);
 
     // submit some ballots
+    let progress = output::progress_bar(nr_of_votes as u64, "submitting ballots");
     for (index, cipher) in encryptions.into_iter().enumerate() {
-        let index_string = (index as u64).to_string();
-        let voter_keypair = KeyPairGenerator::from_string(&format!("//{}", index_string), None)?;
-        let voter = PairSigner::<NodeTemplateRuntime, Pair>::new(voter_keypair);
+        let hash = submit_random_vote(&client, &pk, &vote_id, &topic_id, index, cipher).await?;
+        progress.inc(1);
+        output::record("ballot_submission_hashes", json!(format!("{:?}", hash)));
+    }
+    progress.finish_and_clear();
+    Ok(())
+}
+
+/// Randomizes the cipher, verifies the re-encryption proof and submits it as the ballot of the voter.
+/// The voters are derived from their index, i.e., //0, //1, ... Returns the hash of the submission.
+async fn submit_random_vote(
+    client: &Client<NodeTemplateRuntime>,
+    pk: &ElGamalPK,
+    vote_id: &[u8],
+    topic_id: &[u8],
+    index: usize,
+    cipher: Cipher,
+) -> Result<<NodeTemplateRuntime as System>::Hash, Error> {
+    let index_string = (index as u64).to_string();
+    let voter_keypair = KeyPairGenerator::from_string(&format!("//{}", index_string), None)?;
+    let voter = PairSigner::<NodeTemplateRuntime, Pair>::new(voter_keypair);
+
+    let body = RequestBody {
+        pk: pk.clone(),
+        cipher: cipher.clone(),
+    };
+    let response: ResponseBody = randomize_cipher(&body)
+        .await
+        .map_err(|error| Error::Other(error.to_string()))?;
+    let proof_is_valid = ReEncryptionProof::verify(pk, &response.proof, &cipher, &response.cipher);
+    if !proof_is_valid {
+        return Err("the re-encryption proof of the randomizer is invalid!".into());
+    }
+    let re_encrypted_cipher = response.cipher;
+
+    // create ballot
+    let ballot: Ballot = Ballot {
+        answers: vec![(topic_id.to_vec(), re_encrypted_cipher.into())],
+        proofs: Vec::new(),
+        validity_proofs: Vec::new(),
+        credential: None,
+    };
 
+    // submit ballot
+    submit_ballot(client, &voter, vote_id.to_vec(), ballot).await
+}
+
+/// The distribution of the votes of a bulk voter simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VoteDistribution {
+    /// every vote is equally likely
+    Uniform,
+    /// the relative weight of every vote, e.g. 60,40
+    Weights(Vec<f64>),
+    /// the k-th vote has the weight 1/k^s (Zipf's law with exponent s)
+    Zipf(f64),
+}
+
+impl VoteDistribution {
+    /// Parses the distribution: "uniform", comma separated weights (e.g. "60,40") or "zipf:<s>".
+    pub fn parse(distribution: &str) -> Result<Self, Error> {
+        let distribution = distribution.trim();
+        if distribution == "uniform" {
+            return Ok(VoteDistribution::Uniform);
+        }
+        if let Some(exponent) = distribution.strip_prefix("zipf:") {
+            let exponent: f64 = exponent
+                .parse()
+                .map_err(|_| "the exponent of the zipf distribution is not a number!")?;
+            if !exponent.is_finite() || exponent <= 0.0 {
+                return Err("the exponent of the zipf distribution must be positive!".into());
+            }
+            return Ok(VoteDistribution::Zipf(exponent));
+        }
+        let weights = distribution
+            .split(',')
+            .map(|weight| weight.trim().parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|_| "the distribution must be uniform, zipf:<s> or a list of weights!")?;
+        Ok(VoteDistribution::Weights(weights))
+    }
+
+    /// Returns the weight of every vote, in the order of the votes.
+    fn weights(&self, nr_of_votes: usize) -> Result<Vec<f64>, Error> {
+        match self {
+            VoteDistribution::Uniform => Ok(vec![1.0; nr_of_votes]),
+            VoteDistribution::Weights(weights) if weights.len() != nr_of_votes => {
+                Err("the distribution must contain a weight for every vote!".into())
+            }
+            VoteDistribution::Weights(weights) => Ok(weights.clone()),
+            VoteDistribution::Zipf(exponent) => Ok((1..=nr_of_votes)
+                .map(|rank| 1.0 / (rank as f64).powf(*exponent))
+                .collect()),
+        }
+    }
+
+    /// Draws the given number of votes (one per voter).
+    fn sample(&self, votes: &[u32], nr_of_ballots: usize) -> Result<Vec<u32>, Error> {
+        let weights = self.weights(votes.len())?;
+        let index =
+            WeightedIndex::new(&weights).map_err(|error| Error::Other(error.to_string()))?;
+        let mut rng = rand::thread_rng();
+        Ok((0..nr_of_ballots)
+            .map(|_| votes[index.sample(&mut rng)])
+            .collect())
+    }
+}
+
+/// Simulates the given number of voters (//0, //1, ...) which cast their votes according to the distribution.
+/// Up to `concurrency` ballots are randomized and submitted in parallel, the votes of failed submissions
+/// are not counted. If `verify` is set, waits until the question has been tallied and compares the
+/// on-chain tally with the generated distribution, i.e., the voters must not have cast any other ballots.
+pub async fn bulk_votes(
+    vote: String,
+    question: String,
+    nr_of_ballots: usize,
+    votes: Vec<u32>,
+    distribution: String,
+    concurrency: usize,
+    verify: bool,
+) -> Result<(), Error> {
+    if nr_of_ballots == 0 || votes.is_empty() || concurrency == 0 {
+        return Err(
+            "at least one ballot, allowed vote and concurrent submission are required!".into(),
+        );
+    }
+    let distribution = VoteDistribution::parse(&distribution)?;
+    let sampled = distribution.sample(&votes, nr_of_ballots)?;
+
+    // init substrate client
+    let client = init().await?;
+
+    let vote_id = vote.as_bytes().to_vec();
+    let topic_id = question.as_bytes().to_vec();
+    let pk: ElGamalPK = get_vote_public_key(&client, vote_id.clone()).await?.into();
+    let q = pk.params.q();
+
+    // only quadratic residues can be encrypted without encoding the vote
+    if let Some(vote) = votes
+        .iter()
+        .find(|vote| BigUint::from(**vote).modpow(&q, &pk.params.p) != BigUint::one())
+    {
+        return Err(format!("the vote: {:?} is not a quadratic residue mod p!", vote).into());
+    }
+
+    // subscribe before casting the ballots, so that the tally cannot be missed
+    let events = if verify {
+        Some(subscribe_mixnet_events(&client).await?)
+    } else {
+        None
+    };
+
+    // randomize and submit the ballots in parallel, keep track of the submitted votes
+    let progress = output::progress_bar(nr_of_ballots as u64, "submitting ballots");
+    let results: Vec<(u32, Result<_, Error>)> = stream::iter(sampled.into_iter().enumerate())
+        .map(|(index, vote)| {
+            let client = &client;
+            let pk = &pk;
+            let vote_id = &vote_id;
+            let topic_id = &topic_id;
+            let q = &q;
+            async move {
+                let r = Random::get_random_less_than(q);
+                let cipher = ElGamal::encrypt(&BigUint::from(vote), &r, pk);
+                let result = submit_random_vote(client, pk, vote_id, topic_id, index, cipher).await;
+                (vote, result)
+            }
+        })
+        .buffer_unordered(concurrency)
+        .inspect(|_| progress.inc(1))
+        .collect()
+        .await;
+    progress.finish_and_clear();
+    let mut expected: BTreeMap<u32, u32> = BTreeMap::new();
+    let mut nr_of_failures = 0usize;
+    for (vote, result) in results.into_iter() {
+        match result {
+            Ok(_) => *expected.entry(vote).or_insert(0) += 1,
+            Err(err) => {
+                nr_of_failures += 1;
+                status!("failed to submit ballot: {}", describe(&err));
+                output::record("failures", json!(describe(&err)));
+            }
+        }
+    }
+    status!(
+        "submitted {:?} of {:?} ballots, distribution: {:?}",
+        nr_of_ballots - nr_of_failures,
+        nr_of_ballots,
+        expected
+    );
+    output::record("submitted", json!(nr_of_ballots - nr_of_failures));
+    output::record("distribution", json!(expected));
+
+    // wait for the tally of the question and compare it with the submitted votes
+    let mut events = match events {
+        Some(events) => events,
+        None => return Ok(()),
+    };
+    status!("waiting for the question to be tallied...");
+    loop {
+        match events.next().await {
+            Some(Ok(MixnetEvent::TopicTallied {
+                vote_id: tallied_vote_id,
+                topic_id: tallied_topic_id,
+                ..
+            })) if tallied_vote_id == vote_id && tallied_topic_id == topic_id => break,
+            Some(Ok(_)) => continue,
+            Some(Err(err)) => return Err(err),
+            None => return Err("the event subscription has been closed!".into()),
+        }
+    }
+    let result: TopicResult = get_tally(&client, vote_id, topic_id).await?;
+    let expected: TopicResult = expected
+        .into_iter()
+        .map(|(vote, count)| {
+            (
+                BigUint::from(vote).to_bytes_be(),
+                BigUint::from(count).to_bytes_be(),
+            )
+        })
+        .collect();
+    if result != expected {
+        status!("expected: {:?}", expected);
+        status!("tallied: {:?}", result);
+        return Err("the tally doesn't match the submitted votes!".into());
+    }
+    status!("the tally matches the submitted votes: {:?}", result);
+    Ok(())
+}
+
+/// Proves that the cipher encrypts one of the valid answers of the question.
+/// The randomizer cannot be used for these ciphers, a re-encryption would invalidate the proof.
+fn prove_valid_answer(
+    pk: &ElGamalPK,
+    valid_answers: Vec<u64>,
+    cipher: &Cipher,
+    r: &BigUint,
+    answer: u64,
+    voter_id: &[u8],
+) -> Result<ValidityProof, Error> {
+    let index = valid_answers
+        .iter()
+        .position(|valid| *valid == answer)
+        .ok_or_else(|| Error::Other("the answer is not valid for the question!".into()))?;
+    let q = pk.params.q();
+    let messages: Vec<BigUint> = valid_answers.into_iter().map(BigUint::from).collect();
+    let simulated: Vec<(BigUint, BigUint)> = (1..messages.len())
+        .map(|_| {
+            (
+                Random::get_random_less_than(&q),
+                Random::get_random_less_than(&q),
+            )
+        })
+        .collect();
+    let w = Random::get_random_less_than(&q);
+    let proof = MembershipProof::generate(
+        &pk.params, pk, cipher, r, &messages, index, &w, &simulated, voter_id,
+    );
+    Ok(proof.into())
+}
+
+/// Decodes the hex encoded (optionally 0x prefixed) seed of the voter.
+fn parse_seed(seed: &str) -> Result<Vec<u8>, Error> {
+    let seed = seed.trim_start_matches("0x");
+    let seed = hex::decode(seed).map_err(|error| Error::Other(error.to_string()))?;
+    if seed.is_empty() {
+        return Err("the seed must not be empty!".into());
+    }
+    Ok(seed)
+}
+
+/// Parses a credential (hex), as printed by `va issue_credential` or `voter fake_credential`.
+fn parse_credential(credential: &str) -> Result<BigUint, Error> {
+    let credential = credential.trim_start_matches("0x");
+    BigUint::parse_bytes(credential.as_bytes(), 16)
+        .ok_or_else(|| "the credential must be a hex number!".into())
+}
+
+/// Returns the randomness to encrypt the answer of the topic.
+/// The randomness is derived from the seed (if any), i.e., the voter can re-derive it later.
+fn get_randomness(
+    seed: Option<&[u8]>,
+    vote_id: &[u8],
+    topic_id: &[u8],
+    attempt: u32,
+    q: &BigUint,
+) -> Result<BigUint, Error> {
+    match seed {
+        Some(seed) => Derivation::derive_randomness(seed, vote_id, topic_id, attempt, q)
+            .ok_or_else(|| "the randomness cannot be derived from the seed!".into()),
+        None => Ok(Random::get_random_less_than(q)),
+    }
+}
+
+/// Returns the weight of the voter (if any) and the value to encrypt for the answer.
+/// A weighted voter answers with 0 (no) or its weight (yes).
+async fn get_answer_value(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: &[u8],
+    voter: &PairSigner<NodeTemplateRuntime, Pair>,
+    answer: u32,
+) -> Result<(Option<u32>, u64), Error> {
+    let weight = get_voter_weight(client, vote_id.to_vec(), voter.account_id().clone()).await?;
+    let value: u64 = match weight {
+        Some(_) if answer > 1 => {
+            return Err("a weighted voter can only answer 0 (no) or 1 (yes)!".into());
+        }
+        Some(weight) => answer as u64 * weight as u64,
+        None => answer as u64,
+    };
+    Ok((weight, value))
+}
+
+/// Casts a single ballot. Implements the Benaloh challenge (cast-as-intended verification):
+/// the voter is shown a commitment to the encryption and can either submit it or challenge it.
+/// On challenge, the randomness is revealed to verify the encryption on an independent device
+/// and the vote is encrypted again using fresh randomness.
+/// All other topics of the vote are padded with an encryption of the no-answer sentinel.
+/// A weighted voter answers with 0 (no) or 1 (yes), the answer is multiplied by its weight.
+/// If a seed is provided, the randomness is derived from the seed starting at the given attempt,
+/// each challenge moves on to the next attempt.
+/// A compact ballot is signed by the voter and printed instead of submitted, see: relay_ballot
+/// With `finalized`, the ballot is only reported as cast once its block has been finalized.
+/// The ballot of a voter with a registered credential carries a fresh encryption of the credential,
+/// a ballot cast with a fake credential is discarded during the tally.
+#[allow(clippy::too_many_arguments)]
+pub async fn cast_ballot(
+    vote: String,
+    question: String,
+    voter: usize,
+    answer: u32,
+    seed: Option<String>,
+    attempt: u32,
+    compact: bool,
+    finalized: bool,
+    credential: Option<String>,
+) -> Result<(), Error> {
+    let seed = seed.as_deref().map(parse_seed).transpose()?;
+    let credential = credential.as_deref().map(parse_credential).transpose()?;
+
+    // init substrate client
+    let client = init().await?;
+
+    let vote_id = vote.as_bytes().to_vec();
+    let topic_id = question.as_bytes().to_vec();
+    let pk: ElGamalPK = get_vote_public_key(&client, vote_id.clone()).await?.into();
+    let q = &pk.params.q();
+
+    // the answers are bound to the voter
+    let index_string = (voter as u64).to_string();
+    let voter_keypair: Pair = KeyPairGenerator::from_string(&format!("//{}", index_string), None)?;
+    let voter = PairSigner::<NodeTemplateRuntime, Pair>::new(voter_keypair.clone());
+    let voter_id = voter.account_id().encode();
+
+    let (weight, value) = get_answer_value(&client, &vote_id, &voter, answer).await?;
+    let message = BigUint::from(value);
+
+    let mut attempt = attempt;
+    let (cipher, r) = loop {
+        // encrypt the vote and commit to the encryption
+        let r = get_randomness(seed.as_deref(), &vote_id, &topic_id, attempt, q)?;
+        let cipher = ElGamal::encrypt_encode(&message, &r, &pk);
+        let commitment: String =
+            Helper::hash_vec_ciphers(vec![cipher.clone()], HashFunction::Blake2b)
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect();
+        output::prompt(&format!("encryption commitment: 0x{}", commitment));
+        output::prompt("[s]ubmit or [c]hallenge the encryption?");
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).await?;
+        if input.trim() != "c" {
+            break (cipher, r);
+        }
+
+        // reveal the randomness, the challenged cipher is never submitted
+        output::prompt(&format!("vote: {:?}", message));
+        output::prompt(&format!("randomness: {:?}", r));
+        output::prompt(&format!("cipher: {:?}", cipher));
+        let is_valid = ElGamal::verify_encryption(&cipher, &message, &r, &pk, true);
+        output::prompt(&format!("encryption verified: {:?}", is_valid));
+        output::prompt("re-encrypting the vote using fresh randomness...");
+        attempt = attempt
+            .checked_add(1)
+            .ok_or("no attempts left to derive the randomness!")?;
+    };
+    if seed.is_some() {
+        output::value("attempt", attempt);
+    }
+
+    // every topic of the vote is answered, so that all ballots have the same shape
+    // the topics which are not answered contain an encryption of the no-answer sentinel
+    let vote = get_vote(&client, vote_id.clone()).await?;
+    let topics = get_topics(&client, vote_id.clone()).await?;
+    if !topics.iter().any(|topic| topic.id == topic_id) {
+        return Err("the question is not part of the vote!".into());
+    }
+    let no_answer: u64 = match vote.tally_strategy {
+        TallyStrategy::Mixnet => NO_ANSWER as u64,
+        TallyStrategy::Homomorphic => 0,
+    };
+
+    // the answers to questions with candidates (and all answers of a weighted voter) are proven to be valid answers,
+    // all other ciphers are randomized and the re-encryption proofs are verified
+    let mut answers = Vec::with_capacity(topics.len());
+    let mut validity_proofs = Vec::with_capacity(topics.len());
+    for topic in topics.iter() {
+        let (value, cipher, r) = if topic.id == topic_id {
+            (value, cipher.clone(), r.clone())
+        } else {
+            let r = get_randomness(seed.as_deref(), &vote_id, &topic.id, attempt, q)?;
+            let cipher = ElGamal::encrypt_encode(&BigUint::from(no_answer), &r, &pk);
+            (no_answer, cipher, r)
+        };
+        let valid_answers = match weight {
+            Some(weight) => vec![0, weight as u64],
+            None => topic.valid_answers(),
+        };
+        if !valid_answers.is_empty() {
+            let proof = prove_valid_answer(&pk, valid_answers, &cipher, &r, value, &voter_id)?;
+            answers.push((topic.id.clone(), cipher.into()));
+            validity_proofs.push(Some(proof));
+            continue;
+        }
         let body = RequestBody {
             pk: pk.clone(),
             cipher: cipher.clone(),
@@ -67,22 +521,351 @@ pub async fn create_votes(
         let proof_is_valid =
             ReEncryptionProof::verify(&pk, &response.proof, &cipher, &response.cipher);
         assert!(proof_is_valid);
-        let re_encrypted_cipher = response.cipher;
-        println!(
-            "randomized ballot + verified proof for voter: {:?}",
-            index_string
-        );
+        answers.push((topic.id.clone(), response.cipher.into()));
+        validity_proofs.push(None);
+    }
+
+    // the credential is encrypted under the public key of the vote using fresh randomness,
+    // i.e., the ballots of a voter can't be linked by their credential
+    let credential = credential.map(|credential| {
+        let r = Random::get_random_less_than(q);
+        ElGamal::encrypt_encode(&credential, &r, &pk).into()
+    });
 
-        // create ballot
-        let ballot: Ballot = Ballot {
-            answers: vec![(topic_id.clone(), re_encrypted_cipher.into())],
+    // create and submit the ballot
+    let ballot: Ballot = Ballot {
+        answers,
+        proofs: Vec::new(),
+        validity_proofs,
+        credential,
+    };
+    if compact {
+        // the voter signs the ballot, any relayer can submit it on behalf of the voter
+        let message = relayed_ballot_message(&vote_id, &ballot.encode());
+        let compact = CompactBallot {
+            vote_id,
+            ballot: ballot.encode(),
+            voter: voter_keypair.public().0.to_vec(),
+            signature: voter_keypair.sign(&message).0.to_vec(),
         };
+        output::value("compact_ballot", compact.to_base45());
+        return Ok(());
+    }
 
-        // submit ballot
-        let ballot_submission_hash =
-            submit_ballot(&client, &voter, vote_id.clone(), ballot).await?;
-        println!("ballot_submission_hash: {:?}", ballot_submission_hash);
+    // pre-flight the ballot, i.e., report why it would be rejected before submitting it
+    let account = voter.account_id().clone();
+    if let Some(error) = validate_ballot(&client, vote_id.clone(), account, ballot.clone()).await? {
+        return Err(format!("the ballot would be rejected: {}", error).into());
     }
+    if finalized {
+        let call = CastBallot { vote_id, ballot };
+        let response = watch_finalized(&client, &voter, call).await?;
+        output::extrinsic("response", &response);
+        return Ok(());
+    }
+    let ballot_submission_hash = submit_ballot(&client, &voter, vote_id, ballot).await?;
+    output::debug("ballot_submission_hash", ballot_submission_hash);
+    Ok(())
+}
+
+/// Decodes a compact ballot (e.g. scanned from a QR code) and submits it on behalf of the voter.
+/// The relayer signs the transaction, the ballot is cast by the voter which signed it.
+pub async fn relay_ballot(ballot: String, relayer: usize) -> Result<(), Error> {
+    let compact = CompactBallot::from_base45(&ballot).map_err(|err| err.to_string())?;
+    let vote_id = compact.vote_id;
+    let ballot = Ballot::decode(&mut &compact.ballot[..])
+        .map_err(|_| "the compact ballot doesn't contain a valid ballot!")?;
+    let mut voter = [0u8; 32];
+    voter.copy_from_slice(&compact.voter);
+    let voter = sr25519::Public::from_raw(voter);
+    let mut signature = [0u8; 64];
+    signature.copy_from_slice(&compact.signature);
+    let signature = sr25519::Signature::from_raw(signature);
+
+    // the ballot is rejected by the chain if it isn't signed by the voter
+    let message = relayed_ballot_message(&vote_id, &compact.ballot);
+    if !Pair::verify(&signature, &message, &voter) {
+        return Err("the compact ballot is not signed by the voter!".into());
+    }
+
+    // init substrate client
+    let client = init().await?;
+
+    // the relayers are derived from their index, like the voters
+    let index_string = (relayer as u64).to_string();
+    let relayer_keypair: Pair =
+        KeyPairGenerator::from_string(&format!("//{}", index_string), None)?;
+    let relayer = PairSigner::<NodeTemplateRuntime, Pair>::new(relayer_keypair);
+    let hash = submit_relayed_ballot(&client, &relayer, vote_id, ballot, voter, signature).await?;
+    output::value("voter", format!("{:?}", voter));
+    output::debug("ballot_submission_hash", hash);
+    Ok(())
+}
+
+/// Re-derives the randomness of the answer from the seed and checks that the resulting encryption
+/// has been submitted for the question. Only the answers to questions with candidates
+/// (and all answers of a weighted voter) can be verified, all other ciphers are randomized.
+pub async fn verify_encryption(
+    vote: String,
+    question: String,
+    voter: usize,
+    answer: u32,
+    seed: String,
+    attempt: u32,
+) -> Result<(), Error> {
+    let seed = parse_seed(&seed)?;
+
+    // init substrate client
+    let client = init().await?;
+
+    let vote_id = vote.as_bytes().to_vec();
+    let topic_id = question.as_bytes().to_vec();
+    let pk: ElGamalPK = get_vote_public_key(&client, vote_id.clone()).await?.into();
+    let q = &pk.params.q();
+
+    // the voters are derived from their index, see: create_votes
+    let index_string = (voter as u64).to_string();
+    let voter_keypair = KeyPairGenerator::from_string(&format!("//{}", index_string), None)?;
+    let voter = PairSigner::<NodeTemplateRuntime, Pair>::new(voter_keypair);
+
+    // re-encrypt the answer using the randomness derived from the seed
+    let (_, value) = get_answer_value(&client, &vote_id, &voter, answer).await?;
+    let message = BigUint::from(value);
+    let r = get_randomness(Some(&seed), &vote_id, &topic_id, attempt, q)?;
+    let cipher = ElGamal::encrypt_encode(&message, &r, &pk);
+    output::value("randomness", format!("0x{:x}", r));
+    output::debug("cipher", &cipher);
+
+    // the submitted ciphers of the question (before the first shuffle)
+    let ciphers = get_ciphers(&client, vote_id.clone(), topic_id, 0).await?;
+    let submitted = ciphers.contains(&cipher.into());
+    if submitted {
+        status!("encryption verified: the cipher has been submitted.");
+    } else {
+        status!("the cipher has not been submitted (ciphers without candidates are randomized).");
+    }
+    output::record("submitted", json!(submitted));
+    Ok(())
+}
+
+/// The secret eligibility token of an anonymous voter and the factor which blinds its message.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EligibilityToken {
+    pub vote: String,
+    /// the hex encoded seed of the one-time key (sr25519), the token is its public key
+    /// and the hash of the token is the nullifier of the ballot
+    pub token: String,
+    /// the hex encoded blinding factor, required to unblind the signature
+    pub r: String,
+}
+
+/// Returns the eligibility key of the vote, fails if the vote doesn't accept anonymous ballots.
+async fn get_blind_signature_key(
+    client: &Client<NodeTemplateRuntime>,
+    vote_id: &[u8],
+) -> Result<BlindSignaturePublicKey, Error> {
+    let key = get_eligibility_key(client, vote_id.to_vec())
+        .await?
+        .ok_or("the vote doesn't accept anonymous ballots!")?;
+    Ok(key.into())
+}
+
+/// Decodes a hex encoded (optionally 0x prefixed) number.
+fn parse_hex_number(value: &str) -> Result<BigUint, Error> {
+    BigUint::parse_bytes(value.trim_start_matches("0x").as_bytes(), 16)
+        .ok_or_else(|| Error::Other(format!("not a hex encoded number: {}", value)))
+}
+
+/// Draws a secret eligibility token for the vote and prints its blinded message,
+/// the voting authority blind-signs it once the voter proved its eligibility (see: `va sign_token`).
+/// The token and the blinding factor are written to the output, they must be kept secret.
+pub async fn request_token(vote: String, output: String) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    let vote_id = vote.as_bytes().to_vec();
+    let pk = get_blind_signature_key(&client, &vote_id).await?;
+
+    // the voting authority only learns the blinded message, not the nullifier
+    let seed: [u8; 32] = rand::random();
+    let key = sr25519::Pair::from_seed(&seed);
+    let message = eligibility_token_message(&vote_id, &nullifier(&key.public().0));
+    let r = BlindSignature::random_blinding_factor(&pk);
+    let blinded =
+        BlindSignature::blind(&pk, &message, &r).ok_or("the blinding factor is not invertible!")?;
+
+    let secret = EligibilityToken {
+        vote,
+        token: hex::encode(seed),
+        r: format!("{:x}", r),
+    };
+    let json =
+        serde_json::to_string_pretty(&secret).map_err(|error| Error::Other(error.to_string()))?;
+    fs::write(&output, json).map_err(|error| Error::Other(error.to_string()))?;
+    output::value("eligibility_token", &output);
+    output::value("blinded_token_message", format!("0x{:x}", blinded));
+    Ok(())
+}
+
+/// Casts an anonymous ballot with the eligibility token, the blind signature of the voting authority
+/// is unblinded and verified before. The ballot is submitted as an unsigned transaction, i.e.,
+/// it isn't linked to an account. The validity proofs are bound to the nullifier of the token,
+/// the ballot is signed with the one-time key of the token.
+/// All other topics of the vote are padded with an encryption of the no-answer sentinel.
+pub async fn cast_anonymous_ballot(
+    vote: String,
+    question: String,
+    answer: u32,
+    token: String,
+    signature: String,
+) -> Result<(), Error> {
+    let json = fs::read_to_string(&token).map_err(|error| Error::Other(error.to_string()))?;
+    let secret: EligibilityToken =
+        serde_json::from_str(&json).map_err(|error| Error::Other(error.to_string()))?;
+    if secret.vote != vote {
+        return Err("the eligibility token was issued for another vote!".into());
+    }
+    let seed = hex::decode(&secret.token).map_err(|error| Error::Other(error.to_string()))?;
+    let key = sr25519::Pair::from_seed_slice(&seed)
+        .map_err(|_| "the eligibility token is not a valid one-time key!")?;
+    let r = parse_hex_number(&secret.r)?;
+    let blind_signature = parse_hex_number(&signature)?;
+
+    // init substrate client
+    let client = init().await?;
+
+    // unblind the signature of the voting authority
+    let vote_id = vote.as_bytes().to_vec();
+    let topic_id = question.as_bytes().to_vec();
+    let eligibility_key = get_blind_signature_key(&client, &vote_id).await?;
+    let nullifier = nullifier(&key.public().0);
+    let message = eligibility_token_message(&vote_id, &nullifier);
+    let signature = BlindSignature::unblind(&eligibility_key, &blind_signature, &r)
+        .ok_or("the blinding factor is not invertible!")?;
+    if !BlindSignature::verify(&eligibility_key, &message, &signature) {
+        return Err("the blind signature is not signed by the voting authority!".into());
+    }
+    if is_nullifier_spent(&client, vote_id.clone(), nullifier).await? {
+        return Err("a ballot has already been cast with the eligibility token!".into());
+    }
+
+    // every topic of the vote is answered, see: cast_ballot
+    let pk: ElGamalPK = get_vote_public_key(&client, vote_id.clone()).await?.into();
+    let q = &pk.params.q();
+    let vote = get_vote(&client, vote_id.clone()).await?;
+    let topics = get_topics(&client, vote_id.clone()).await?;
+    if !topics.iter().any(|topic| topic.id == topic_id) {
+        return Err("the question is not part of the vote!".into());
+    }
+    let no_answer: u64 = match vote.tally_strategy {
+        TallyStrategy::Mixnet => NO_ANSWER as u64,
+        TallyStrategy::Homomorphic => 0,
+    };
+
+    let mut answers = Vec::with_capacity(topics.len());
+    let mut validity_proofs = Vec::with_capacity(topics.len());
+    for topic in topics.iter() {
+        let value = match topic.id == topic_id {
+            true => answer as u64,
+            false => no_answer,
+        };
+        let r = Random::get_random_less_than(q);
+        let cipher = ElGamal::encrypt_encode(&BigUint::from(value), &r, &pk);
+        let valid_answers = topic.valid_answers();
+        if !valid_answers.is_empty() {
+            let proof = prove_valid_answer(&pk, valid_answers, &cipher, &r, value, &nullifier)?;
+            answers.push((topic.id.clone(), cipher.into()));
+            validity_proofs.push(Some(proof));
+            continue;
+        }
+        let body = RequestBody {
+            pk: pk.clone(),
+            cipher: cipher.clone(),
+        };
+        let response: ResponseBody = randomize_cipher(&body).await.unwrap();
+        let proof_is_valid =
+            ReEncryptionProof::verify(&pk, &response.proof, &cipher, &response.cipher);
+        assert!(proof_is_valid);
+        answers.push((topic.id.clone(), response.cipher.into()));
+        validity_proofs.push(None);
+    }
+
+    let ballot: Ballot = Ballot {
+        answers,
+        proofs: Vec::new(),
+        validity_proofs,
+        credential: None,
+    };
+    // bind the ballot to the token, the token can't be used for another ballot
+    let ballot_signature = key.sign(&anonymous_ballot_message(
+        &vote_id,
+        &nullifier,
+        &ballot.encode(),
+    ));
+    let signature = signature.to_bytes_be();
+    let hash = submit_anonymous_ballot(
+        &client,
+        vote_id,
+        ballot,
+        key.public(),
+        signature,
+        ballot_signature,
+    )
+    .await?;
+    output::value("nullifier", format!("0x{}", hex::encode(nullifier)));
+    output::debug("ballot_submission_hash", hash);
+    Ok(())
+}
+
+/// Prints a fake credential which is indistinguishable from a real credential.
+/// A coerced voter can hand it out (or vote with it), ballots cast with a fake credential are not counted.
+pub fn fake_credential() {
+    let (params, _, _) = Helper::setup_lg_system();
+    let credential = Random::get_random_less_than(&params.q());
+    output::value("credential", format!("0x{:x}", credential));
+}
+
+pub async fn get_receipt(vote: String, voter: usize) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // the voters are derived from their index, see: create_votes
+    let index_string = (voter as u64).to_string();
+    let voter_keypair: Pair = KeyPairGenerator::from_string(&format!("//{}", index_string), None)?;
+    let account = voter_keypair.public().into();
+
+    // fetch the receipt of the voter's ballot
+    let vote_id = vote.as_bytes().to_vec();
+    match get_ballot_receipt(&client, vote_id, account).await? {
+        Some(receipt) => {
+            output::debug("block_number", receipt.block_number);
+            output::debug("block_hash", receipt.block_hash);
+            let cipher_hash: String = receipt
+                .cipher_hash
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect();
+            output::value("cipher_hash", format!("0x{}", cipher_hash));
+            output::debug("event_index", receipt.event_index);
+        }
+        None => status!("no ballot found for voter: {:?}", index_string),
+    }
+    Ok(())
+}
+
+pub async fn claim_deposit(vote: String, voter: usize) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+
+    // the voters are derived from their index, see: create_votes
+    let index_string = (voter as u64).to_string();
+    let voter_keypair: Pair = KeyPairGenerator::from_string(&format!("//{}", index_string), None)?;
+    let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(voter_keypair);
+
+    // refund the deposits of all ballots cast for the vote
+    let vote_id = vote.as_bytes().to_vec();
+    let response = claim_ballot_deposit(&client, &signer, vote_id).await?;
+    output::extrinsic("response", &response);
     Ok(())
 }
 
@@ -0,0 +1,543 @@
+use crate::output;
+use async_std::task;
+use futures::stream::{self, StreamExt};
+use pallet_mixnet::types::{ShuffleState, TallyStrategy, Topic, TopicId, VoteId, VotePhase};
+use provotum_client_sdk::calls::SetVotePhase;
+use provotum_client_sdk::events::{
+    subscribe_finalized_mixnet_events, subscribe_mixnet_events, MixnetEvent,
+};
+use provotum_client_sdk::rpc::{
+    get_block_number, get_block_number_at, get_decrypted_shares, get_public_key_share,
+    get_shuffle_state, get_topics, get_vote, get_vote_ids, get_vote_sealers, is_topic_tallied,
+    set_vote_phase, watch_finalized,
+};
+use provotum_client_sdk::{AccountId, BlockNumber};
+use serde_json::{json, Value};
+use sp_keyring::sr25519::sr25519::Pair;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use substrate_subxt::{sp_core::Pair as KeyPairGenerator, Client, PairSigner};
+use substrate_subxt::{ClientBuilder, Error, NodeTemplateRuntime};
+use surf::Body;
+
+async fn init() -> Result<Client<NodeTemplateRuntime>, Error> {
+    env_logger::init();
+    let url = "ws://127.0.0.1:9944";
+    let client = ClientBuilder::<NodeTemplateRuntime>::new()
+        .set_url(url)
+        .build()
+        .await?;
+    Ok(client)
+}
+
+/// A change of the phase of a vote once the chain has reached a block, e.g. "TestVote:Tallying:1200".
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseAdvance {
+    pub vote_id: VoteId,
+    pub phase: VotePhase,
+    pub block: BlockNumber,
+}
+
+impl PhaseAdvance {
+    /// Parses the advance: "<vote>:<phase>:<block>", the vote may contain colons.
+    pub fn parse(advance: &str) -> Result<Self, Error> {
+        let mut parts = advance.trim().rsplitn(3, ':');
+        let block = parts.next().unwrap_or_default();
+        let phase = parts.next().unwrap_or_default();
+        let vote = parts
+            .next()
+            .filter(|vote| !vote.is_empty())
+            .ok_or("the advance must have the format <vote>:<phase>:<block>!")?;
+        let phase = match VotePhase::from_str(phase) {
+            Ok(VotePhase::Cancelled) | Err(_) => {
+                return Err(format!("the phase: {} can't be advanced to!", phase).into())
+            }
+            Ok(phase) => phase,
+        };
+        let block: BlockNumber = block
+            .parse()
+            .map_err(|_| "the block of the advance is not a number!")?;
+        Ok(PhaseAdvance {
+            vote_id: vote.as_bytes().to_vec(),
+            phase,
+            block,
+        })
+    }
+}
+
+/// A vote which didn't make any progress for the configured number of blocks.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Stall {
+    /// the sealers haven't submitted their public key shares
+    MissingKeyShares {
+        vote_id: VoteId,
+        sealers: Vec<AccountId>,
+    },
+    /// the sealers haven't shuffled the ciphers of the topic (None: the shuffling hasn't started)
+    NoShuffleProgress {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        state: Option<ShuffleState>,
+    },
+    /// the sealers haven't submitted their decrypted shares of the topic
+    MissingDecryptedShares {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        sealers: Vec<AccountId>,
+    },
+}
+
+impl Stall {
+    /// the stall as reported to the webhook and in the JSON output
+    fn to_json(&self, block: BlockNumber, since: BlockNumber) -> Value {
+        let (kind, vote_id, topic_id, sealers): (&str, &VoteId, Option<&TopicId>, &[AccountId]) =
+            match self {
+                Stall::MissingKeyShares { vote_id, sealers } => {
+                    ("missing_key_shares", vote_id, None, sealers.as_slice())
+                }
+                Stall::NoShuffleProgress {
+                    vote_id, topic_id, ..
+                } => ("no_shuffle_progress", vote_id, Some(topic_id), &[][..]),
+                Stall::MissingDecryptedShares {
+                    vote_id,
+                    topic_id,
+                    sealers,
+                } => (
+                    "missing_decrypted_shares",
+                    vote_id,
+                    Some(topic_id),
+                    sealers.as_slice(),
+                ),
+            };
+        json!({
+            "kind": kind,
+            "vote": String::from_utf8_lossy(vote_id),
+            "topic": topic_id.map(|topic_id| String::from_utf8_lossy(topic_id).to_string()),
+            "sealers": sealers.iter().map(|sealer| sealer.to_string()).collect::<Vec<String>>(),
+            "block": block,
+            "since": since,
+            "message": self.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Stall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let accounts = |sealers: &[AccountId]| {
+            sealers
+                .iter()
+                .map(|sealer| sealer.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        match self {
+            Stall::MissingKeyShares { vote_id, sealers } => write!(
+                f,
+                "vote {}: missing public key shares of {}",
+                String::from_utf8_lossy(vote_id),
+                accounts(sealers)
+            ),
+            Stall::NoShuffleProgress {
+                vote_id,
+                topic_id,
+                state,
+            } => write!(
+                f,
+                "vote {}: no shuffle progress of question {} ({:?})",
+                String::from_utf8_lossy(vote_id),
+                String::from_utf8_lossy(topic_id),
+                state
+            ),
+            Stall::MissingDecryptedShares {
+                vote_id,
+                topic_id,
+                sealers,
+            } => write!(
+                f,
+                "vote {}: missing decrypted shares of question {} of {}",
+                String::from_utf8_lossy(vote_id),
+                String::from_utf8_lossy(topic_id),
+                accounts(sealers)
+            ),
+        }
+    }
+}
+
+/// The configuration of the watchdog, see: `watch_votes`.
+#[derive(Debug, Clone)]
+pub struct WatchdogConfig {
+    /// the number of blocks without progress after which a vote is stalled
+    pub stall_blocks: BlockNumber,
+    /// the interval in which the chain is checked for stalled votes and phase advances
+    pub interval: Duration,
+    /// the URL the stalls are posted to (JSON)
+    pub webhook: Option<String>,
+    /// the phase changes of the votes
+    pub advances: Vec<PhaseAdvance>,
+    /// stop watching (and fail) on the first stall
+    pub exit_on_alert: bool,
+    /// only the finalized blocks (GRANDPA) count, i.e., the events of the finalized blocks are
+    /// watched and a phase advance is submitted once its block has been finalized
+    pub finalized: bool,
+}
+
+/// The state of a vote which hasn't been tallied yet.
+struct WatchedVote {
+    phase: VotePhase,
+    tally_strategy: TallyStrategy,
+    topics: Vec<TopicId>,
+    /// the block of the last event of the vote
+    progress: BlockNumber,
+    /// whether the current stall has been reported, it is reported again after the next progress
+    alerted: bool,
+}
+
+/// Either an event of the mixnet pallet or the next check of the votes.
+enum Signal {
+    Event(Result<MixnetEvent, Error>),
+    Check,
+}
+
+struct Watchdog {
+    client: Client<NodeTemplateRuntime>,
+    signer: PairSigner<NodeTemplateRuntime, Pair>,
+    config: WatchdogConfig,
+    votes: HashMap<VoteId, WatchedVote>,
+    /// the block of the last check, the events are attributed to it
+    block: BlockNumber,
+}
+
+impl Watchdog {
+    /// starts watching the vote, the votes which have been tallied or cancelled are skipped
+    async fn watch(&mut self, vote_id: VoteId) -> Result<(), Error> {
+        let vote = get_vote(&self.client, vote_id.clone()).await?;
+        if vote.phase == VotePhase::Cancelled {
+            self.votes.remove(&vote_id);
+            return Ok(());
+        }
+        let topics: Vec<TopicId> = get_topics(&self.client, vote_id.clone())
+            .await?
+            .into_iter()
+            .map(|Topic { id, .. }| id)
+            .collect();
+        if vote.phase == VotePhase::Tallying && self.is_tallied(&vote_id, &topics).await? {
+            self.votes.remove(&vote_id);
+            return Ok(());
+        }
+        let watched = WatchedVote {
+            phase: vote.phase,
+            tally_strategy: vote.tally_strategy,
+            topics,
+            progress: self.block,
+            alerted: false,
+        };
+        self.votes.insert(vote_id, watched);
+        Ok(())
+    }
+
+    async fn is_tallied(&self, vote_id: &VoteId, topics: &[TopicId]) -> Result<bool, Error> {
+        for topic_id in topics.iter() {
+            if !is_topic_tallied(&self.client, vote_id.clone(), topic_id.clone()).await? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// the vote of the topic, if it is watched
+    fn vote_of_topic(&self, topic_id: &TopicId) -> Option<VoteId> {
+        self.votes
+            .iter()
+            .find(|(_, vote)| vote.topics.contains(topic_id))
+            .map(|(vote_id, _)| vote_id.clone())
+    }
+
+    fn record_progress(&mut self, vote_id: &VoteId) {
+        let block = self.block;
+        if let Some(vote) = self.votes.get_mut(vote_id) {
+            vote.progress = block;
+            vote.alerted = false;
+        }
+    }
+
+    /// records the progress of the votes the event belongs to
+    async fn handle(&mut self, event: MixnetEvent) -> Result<(), Error> {
+        match event {
+            MixnetEvent::VoteCreated { vote_id, .. }
+            | MixnetEvent::VotePhaseChanged { vote_id, .. }
+            | MixnetEvent::VoteTopicQuestionStored { vote_id, .. }
+            | MixnetEvent::PublicKeyRotated { vote_id }
+            | MixnetEvent::VoteCancelled { vote_id, .. }
+            | MixnetEvent::TopicTallied { vote_id, .. } => self.watch(vote_id).await?,
+            // the share doesn't reveal its vote, it counts for all votes in the key generation phase
+            MixnetEvent::PublicKeyShareSubmitted { .. } => {
+                let vote_ids: Vec<VoteId> = self
+                    .votes
+                    .iter()
+                    .filter(|(_, vote)| vote.phase == VotePhase::KeyGeneration)
+                    .map(|(vote_id, _)| vote_id.clone())
+                    .collect();
+                for vote_id in vote_ids.iter() {
+                    self.record_progress(vote_id);
+                }
+            }
+            MixnetEvent::PublicKeyCreated { vote_id, .. }
+            | MixnetEvent::CiphersAggregated { vote_id, .. }
+            | MixnetEvent::ShuffleCompleted { vote_id, .. }
+            | MixnetEvent::ShuffleBatchAccepted { vote_id, .. }
+            | MixnetEvent::ShuffleIterationCompleted { vote_id, .. }
+            | MixnetEvent::DecryptionProgress { vote_id, .. }
+            | MixnetEvent::CiphersKeySwitched { vote_id, .. }
+            | MixnetEvent::TallyBatchCombined { vote_id, .. } => self.record_progress(&vote_id),
+            MixnetEvent::DecryptedShareSubmitted { topic_id, .. }
+            | MixnetEvent::ShuffleProofSubmitted { topic_id, .. }
+            | MixnetEvent::ShuffleCommitted { topic_id, .. }
+            | MixnetEvent::KeySwitchSharesSubmitted { topic_id, .. } => {
+                if let Some(vote_id) = self.vote_of_topic(&topic_id) {
+                    self.record_progress(&vote_id);
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// changes the phases of the votes whose block has been reached, every advance is submitted once
+    async fn advance(&mut self) {
+        let block = self.block;
+        let (due, pending): (Vec<PhaseAdvance>, Vec<PhaseAdvance>) = self
+            .config
+            .advances
+            .drain(..)
+            .partition(|advance| advance.block <= block);
+        self.config.advances = pending;
+
+        for advance in due.into_iter() {
+            let vote = String::from_utf8_lossy(&advance.vote_id).to_string();
+            status!(
+                "block {}: advancing vote {} to {:?}...",
+                block,
+                vote,
+                advance.phase
+            );
+            let result = match self.config.finalized {
+                true => {
+                    let call = SetVotePhase {
+                        vote_id: advance.vote_id.clone(),
+                        vote_phase: advance.phase.clone(),
+                    };
+                    watch_finalized(&self.client, &self.signer, call).await
+                }
+                false => {
+                    set_vote_phase(
+                        &self.client,
+                        &self.signer,
+                        advance.vote_id.clone(),
+                        advance.phase.clone(),
+                    )
+                    .await
+                }
+            };
+            // e.g. the tallying phase requires the approval of further voting authorities
+            match result {
+                Ok(response) => output::extrinsic("advance", &response),
+                Err(err) => status!("failed to advance vote {}: {:?}", vote, err),
+            }
+        }
+    }
+
+    /// the number of the current block, i.e., of the last finalized block if only those count
+    async fn current_block(&self) -> Result<BlockNumber, Error> {
+        if !self.config.finalized {
+            return get_block_number(&self.client).await;
+        }
+        let finalized_hash = self.client.finalized_head().await?;
+        get_block_number_at(&self.client, Some(finalized_hash)).await
+    }
+
+    /// the reason the vote is stalled, None if nothing is missing
+    async fn diagnose(&self, vote_id: &VoteId, vote: &WatchedVote) -> Result<Option<Stall>, Error> {
+        let sealers = get_vote_sealers(&self.client, vote_id.clone()).await?;
+        match vote.phase {
+            VotePhase::KeyGeneration => {
+                let mut missing: Vec<AccountId> = Vec::new();
+                for sealer in sealers.into_iter() {
+                    let share = get_public_key_share(&self.client, vote_id.clone(), sealer.clone());
+                    if share.await?.is_none() {
+                        missing.push(sealer);
+                    }
+                }
+                if missing.is_empty() {
+                    return Ok(None);
+                }
+                Ok(Some(Stall::MissingKeyShares {
+                    vote_id: vote_id.clone(),
+                    sealers: missing,
+                }))
+            }
+            VotePhase::Tallying => {
+                for topic_id in vote.topics.iter() {
+                    if is_topic_tallied(&self.client, vote_id.clone(), topic_id.clone()).await? {
+                        continue;
+                    }
+
+                    // the ciphers of a homomorphic tally aren't shuffled
+                    if vote.tally_strategy == TallyStrategy::Mixnet {
+                        let state =
+                            get_shuffle_state(&self.client, vote_id.clone(), topic_id.clone())
+                                .await?;
+                        if !state.as_ref().map_or(false, |state| state.done) {
+                            return Ok(Some(Stall::NoShuffleProgress {
+                                vote_id: vote_id.clone(),
+                                topic_id: topic_id.clone(),
+                                state,
+                            }));
+                        }
+                    }
+
+                    let mut missing: Vec<AccountId> = Vec::new();
+                    for sealer in sealers.iter() {
+                        let shares = get_decrypted_shares(
+                            &self.client,
+                            vote_id.clone(),
+                            topic_id.clone(),
+                            sealer.clone(),
+                        );
+                        if shares.await?.is_empty() {
+                            missing.push(sealer.clone());
+                        }
+                    }
+                    if !missing.is_empty() {
+                        return Ok(Some(Stall::MissingDecryptedShares {
+                            vote_id: vote_id.clone(),
+                            topic_id: topic_id.clone(),
+                            sealers: missing,
+                        }));
+                    }
+                }
+                Ok(None)
+            }
+            // the ballots are cast by the voters, a quiet voting phase isn't stalled
+            VotePhase::Voting | VotePhase::Cancelled => Ok(None),
+        }
+    }
+
+    /// reports the votes without progress for `stall_blocks` blocks, every stall is reported once
+    async fn check(&mut self) -> Result<(), Error> {
+        let block = self.block;
+        let stall_blocks = self.config.stall_blocks;
+        let stalled: Vec<VoteId> = self
+            .votes
+            .iter()
+            .filter(|(_, vote)| {
+                !vote.alerted && block.saturating_sub(vote.progress) >= stall_blocks
+            })
+            .map(|(vote_id, _)| vote_id.clone())
+            .collect();
+
+        for vote_id in stalled.into_iter() {
+            let vote = match self.votes.get(&vote_id) {
+                Some(vote) => vote,
+                None => continue,
+            };
+            let since = vote.progress;
+            if let Some(stall) = self.diagnose(&vote_id, vote).await? {
+                self.alert(&stall, since).await?;
+            }
+            if let Some(vote) = self.votes.get_mut(&vote_id) {
+                vote.alerted = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// prints the stall and posts it to the webhook, fails if the watchdog exits on alerts
+    async fn alert(&self, stall: &Stall, since: BlockNumber) -> Result<(), Error> {
+        let alert = stall.to_json(self.block, since);
+        status!(
+            "ALERT block {}: {} (since block {})",
+            self.block,
+            stall,
+            since
+        );
+        output::record("alerts", alert.clone());
+
+        // an unreachable webhook doesn't stop the watchdog
+        if let Some(webhook) = self.config.webhook.as_ref() {
+            let posted = match Body::from_json(&alert) {
+                Ok(body) => surf::post(webhook).body(body).await.map(|_| ()),
+                Err(err) => Err(err),
+            };
+            if let Err(err) = posted {
+                status!("failed to post the alert to {}: {}", webhook, err);
+            }
+        }
+
+        if self.config.exit_on_alert {
+            return Err(stall.to_string().into());
+        }
+        Ok(())
+    }
+}
+
+/// Watches all votes which haven't been tallied yet:
+/// - the events of the mixnet pallet record the progress of every vote (see: `Watchdog::handle`)
+/// - every `interval` the votes without progress for `stall_blocks` blocks are reported as stalled,
+///   i.e., missing public key shares, no shuffle progress or missing decrypted shares
+/// - the phases of the votes are advanced once their block has been reached
+///
+/// With `finalized`, only the finalized blocks count, i.e., a ballot or phase change of a best
+/// block which is reorged out later is never recorded.
+///
+/// The watchdog runs until the event subscription is closed or, with `exit_on_alert`, until the
+/// first stall (the subcommand fails, i.e. exits with code 1).
+pub async fn watch_votes(config: WatchdogConfig, authority: String) -> Result<(), Error> {
+    // init substrate client
+    let client = init().await?;
+    let authority: Pair = KeyPairGenerator::from_string(&authority, None)?;
+    let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(authority);
+
+    // subscribe before reading the votes, i.e., no progress is missed
+    let events = match config.finalized {
+        true => subscribe_finalized_mixnet_events(&client).await?,
+        false => subscribe_mixnet_events(&client).await?,
+    };
+    let events = events.map(Signal::Event);
+    let interval = config.interval;
+    let checks = stream::unfold((), move |_| async move {
+        task::sleep(interval).await;
+        Some((Signal::Check, ()))
+    });
+    let mut signals = stream::select(events, checks.boxed());
+
+    let mut watchdog = Watchdog {
+        client,
+        signer,
+        config,
+        votes: HashMap::new(),
+        block: 0,
+    };
+    watchdog.block = watchdog.current_block().await?;
+    for vote_id in get_vote_ids(&watchdog.client).await?.into_iter() {
+        watchdog.watch(vote_id).await?;
+    }
+    status!(
+        "block {}: watching {} votes...",
+        watchdog.block,
+        watchdog.votes.len()
+    );
+
+    while let Some(signal) = signals.next().await {
+        match signal {
+            Signal::Event(event) => watchdog.handle(event?).await?,
+            Signal::Check => {
+                watchdog.block = watchdog.current_block().await?;
+                watchdog.advance().await;
+                watchdog.check().await?;
+            }
+        }
+    }
+    Ok(())
+}
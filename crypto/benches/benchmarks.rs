@@ -210,7 +210,7 @@ fn bench_proofs(c: &mut Criterion) {
                 let sealer_id = "Bob".as_bytes();
                 let (params, sk, pk) = Helper::setup_lg_system();
                 let r = BigUint::parse_bytes(b"170141183460469231731687303715884", 10).unwrap();
-                (params, sk.x, pk.h, r, sealer_id)
+                (params, sk.x.clone(), pk.h, r, sealer_id)
             },
             |(params, x, h, r, sealer_id)| {
                 KeyGenerationProof::generate(&params, &x, &h, &r, sealer_id)
@@ -442,11 +442,67 @@ fn bench_decryption_encoded_different_votes(c: &mut Criterion) {
     group.finish();
 }
 
+#[cfg(feature = "wasm-opt")]
+fn bench_modpow(c: &mut Criterion) {
+    // benchmark config
+    let mut group = c.benchmark_group("modpow");
+    let (params, _, pk) = Helper::setup_lg_system();
+    let r = BigUint::parse_bytes(b"170141183460469231731687303715884105727", 10).unwrap();
+
+    group.bench_function("num-bigint", |b| b.iter(|| pk.h.modpow(&r, &params.p)));
+
+    group.bench_function("montgomery", |b| {
+        b.iter(|| crypto::montgomery::modpow(&pk.h, &r, &params.p))
+    });
+
+    group.finish();
+}
+
+#[cfg(not(feature = "wasm-opt"))]
+fn bench_modpow(_: &mut Criterion) {}
+
+/// the partial decryptions of a sealer with 1, 2, 4 and 8 threads (rayon), 100k ciphers by default.
+/// the number of ciphers can be set with the environment variable `NR_OF_CIPHERS`.
+#[cfg(feature = "parallel")]
+fn bench_partial_decryption(c: &mut Criterion) {
+    use criterion::{BenchmarkId, Throughput};
+
+    let nr_of_ciphers: usize = std::env::var("NR_OF_CIPHERS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(100_000);
+    let (_, sk, pk) = Helper::setup_lg_system();
+    let (encryptions, _, _, _) = setup_shuffling(nr_of_ciphers, true, pk);
+
+    // benchmark config
+    let mut group = c.benchmark_group("partial decryption");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(nr_of_ciphers as u64));
+
+    for threads in [1usize, 2, 4, 8].iter() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(*threads)
+            .build()
+            .unwrap();
+        group.bench_with_input(
+            BenchmarkId::new(format!("{} ciphers", nr_of_ciphers), threads),
+            threads,
+            |b, _| b.iter(|| pool.install(|| ElGamal::partial_decrypt_many(&encryptions, &sk))),
+        );
+    }
+    group.finish();
+}
+
+#[cfg(not(feature = "parallel"))]
+fn bench_partial_decryption(_: &mut Criterion) {}
+
 criterion_group!(
     benches,
     bench_elgamal,
     bench_proofs,
     bench_shuffle,
-    bench_decryption_encoded_different_votes
+    bench_decryption_encoded_different_votes,
+    bench_modpow,
+    bench_partial_decryption
 );
 criterion_main!(benches);
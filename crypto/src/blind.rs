@@ -0,0 +1,201 @@
+//! RSA blind signatures (Chaum) with a full domain hash (RSA-FDH).
+//!
+//! Used for anonymous eligibility: the voter blinds the message of a token, the authority signs
+//! the blinded message without learning it and the voter unblinds the signature. The signature
+//! verifies under the authority's public key, but the authority can't link it to the signing.
+//!
+//! blind: m' = H(m) * r^e mod n, sign: s' = m'^d mod n, unblind: s = s' * r^-1 mod n = H(m)^d.
+//! The message is hashed to Z_n with expand_message_xmd (RFC 9380, Blake2b) to bits(n) + 128 bits.
+use crate::helper::Helper;
+use crate::types::{div_ceil, ModuloOperations};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// The domain separation tag of the full domain hash.
+pub const BLIND_SIGNATURE_DOMAIN: &str = "provotum/v1/blind-signature";
+
+/// The public exponent of generated keys.
+pub const PUBLIC_EXPONENT: u32 = 65537;
+
+/// The number of additional bits hashed to make the bias of the reduction modulo n negligible.
+const EXTRA_BITS: u64 = 128;
+
+/// The public key of the signer: the modulus n = p * q and the public exponent e.
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BlindSignaturePublicKey {
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
+    pub n: BigUint,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
+    pub e: BigUint,
+}
+
+/// The private key of the signer: the modulus n and the private exponent d = e^-1 mod phi(n).
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BlindSignaturePrivateKey {
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
+    pub n: BigUint,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
+    pub d: BigUint,
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for BlindSignaturePrivateKey {
+    fn zeroize(&mut self) {
+        crate::secret::wipe(&mut self.d);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for BlindSignaturePrivateKey {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+pub struct BlindSignature;
+
+impl BlindSignature {
+    /// Hashes the message to Z_n (full domain hash).
+    pub fn hash_to_message(pk: &BlindSignaturePublicKey, message: &[u8]) -> BigUint {
+        let length = div_ceil((pk.n.bits() + EXTRA_BITS) as usize, 8);
+        let expanded =
+            Helper::expand_message_xmd(message, BLIND_SIGNATURE_DOMAIN.as_bytes(), length);
+        BigUint::from_bytes_be(&expanded) % &pk.n
+    }
+
+    /// Blinds the message with the blinding factor r: m' = H(m) * r^e mod n.
+    ///
+    /// Returns None if r is not invertible modulo n.
+    pub fn blind(pk: &BlindSignaturePublicKey, message: &[u8], r: &BigUint) -> Option<BigUint> {
+        let r = r % &pk.n;
+        r.invmod(&pk.n)?;
+        let hashed = Self::hash_to_message(pk, message);
+        Some(hashed.modmul(&r.modpow(&pk.e, &pk.n), &pk.n))
+    }
+
+    /// Signs the blinded message: s' = m'^d mod n. The signer learns nothing about the message.
+    pub fn sign_blinded(sk: &BlindSignaturePrivateKey, blinded: &BigUint) -> BigUint {
+        blinded.modpow(&sk.d, &sk.n)
+    }
+
+    /// Removes the blinding factor r from the blind signature: s = s' * r^-1 mod n.
+    ///
+    /// Returns None if r is not invertible modulo n.
+    pub fn unblind(
+        pk: &BlindSignaturePublicKey,
+        blind_signature: &BigUint,
+        r: &BigUint,
+    ) -> Option<BigUint> {
+        let r_inverse = (r % &pk.n).invmod(&pk.n)?;
+        Some((blind_signature % &pk.n).modmul(&r_inverse, &pk.n))
+    }
+
+    /// Signs the message directly: s = H(m)^d mod n.
+    pub fn sign(
+        sk: &BlindSignaturePrivateKey,
+        pk: &BlindSignaturePublicKey,
+        message: &[u8],
+    ) -> BigUint {
+        Self::hash_to_message(pk, message).modpow(&sk.d, &sk.n)
+    }
+
+    /// Verifies the (unblinded) signature of the message: s^e = H(m) mod n.
+    pub fn verify(pk: &BlindSignaturePublicKey, message: &[u8], signature: &BigUint) -> bool {
+        if pk.n <= BigUint::one() || pk.e.is_zero() || signature >= &pk.n {
+            return false;
+        }
+        signature.modpow(&pk.e, &pk.n) == Self::hash_to_message(pk, message)
+    }
+
+    /// Generates a key pair with a modulus of (about) `bit_size` bits.
+    #[cfg(any(feature = "std", test))]
+    pub fn generate_key_pair(bit_size: u64) -> (BlindSignaturePublicKey, BlindSignaturePrivateKey) {
+        use crate::random::Random;
+
+        let e = BigUint::from(PUBLIC_EXPONENT);
+        let one = BigUint::one();
+        loop {
+            let p = Random::generate_random_prime(bit_size / 2);
+            let q = Random::generate_random_prime(bit_size - bit_size / 2);
+            if p == q {
+                continue;
+            }
+            let phi = (&p - &one) * (&q - &one);
+            if let Some(d) = e.invmod(&phi) {
+                let n = p * q;
+                let pk = BlindSignaturePublicKey { n: n.clone(), e };
+                return (pk, BlindSignaturePrivateKey { n, d });
+            }
+        }
+    }
+
+    /// Generates a random blinding factor r which is invertible modulo n.
+    #[cfg(any(feature = "std", test))]
+    pub fn random_blinding_factor(pk: &BlindSignaturePublicKey) -> BigUint {
+        use crate::random::Random;
+
+        loop {
+            let r = Random::get_random_less_than(&pk.n);
+            if !r.is_zero() && r.invmod(&pk.n).is_some() {
+                return r;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BlindSignature, BlindSignaturePublicKey};
+    use num_bigint::BigUint;
+    use num_traits::One;
+
+    const MESSAGE: &[u8] = b"provotum/nullifier";
+
+    #[test]
+    fn it_should_verify_an_unblinded_signature() {
+        let (pk, sk) = BlindSignature::generate_key_pair(512);
+        let r = BlindSignature::random_blinding_factor(&pk);
+
+        let blinded = BlindSignature::blind(&pk, MESSAGE, &r).unwrap();
+        assert_ne!(blinded, BlindSignature::hash_to_message(&pk, MESSAGE));
+        let blind_signature = BlindSignature::sign_blinded(&sk, &blinded);
+        let signature = BlindSignature::unblind(&pk, &blind_signature, &r).unwrap();
+
+        assert!(BlindSignature::verify(&pk, MESSAGE, &signature));
+        assert_eq!(signature, BlindSignature::sign(&sk, &pk, MESSAGE));
+    }
+
+    #[test]
+    fn it_should_not_verify_another_message_or_key() {
+        let (pk, sk) = BlindSignature::generate_key_pair(512);
+        let signature = BlindSignature::sign(&sk, &pk, MESSAGE);
+        assert!(!BlindSignature::verify(&pk, b"another message", &signature));
+        assert!(!BlindSignature::verify(
+            &pk,
+            MESSAGE,
+            &(&signature + BigUint::one())
+        ));
+
+        let (other_pk, _) = BlindSignature::generate_key_pair(512);
+        assert!(!BlindSignature::verify(&other_pk, MESSAGE, &signature));
+
+        // signatures must be reduced modulo n
+        assert!(!BlindSignature::verify(&pk, MESSAGE, &(&signature + &pk.n)));
+    }
+
+    #[test]
+    fn it_should_reject_a_blinding_factor_which_is_not_invertible() {
+        let pk = BlindSignaturePublicKey {
+            n: BigUint::from(11u32 * 13u32),
+            e: BigUint::from(7u32),
+        };
+        assert!(BlindSignature::blind(&pk, MESSAGE, &BigUint::from(13u32)).is_none());
+        assert!(BlindSignature::unblind(&pk, &BigUint::one(), &BigUint::from(22u32)).is_none());
+        assert!(BlindSignature::blind(&pk, MESSAGE, &BigUint::from(2u32)).is_some());
+    }
+}
@@ -0,0 +1,92 @@
+//! Deterministic derivation of the encryption randomness from a voter-held seed.
+//!
+//! The randomness r of an answer is derived with HKDF (Blake2b) from the seed, the vote,
+//! the topic and the attempt, i.e., the voter can later re-derive r to verify the own ballot.
+//! A challenged encryption reveals r, the next attempt must therefore use a new index.
+use crate::types::div_ceil;
+use alloc::{vec, vec::Vec};
+use blake2::Blake2b;
+use codec::Encode;
+use hkdf::Hkdf;
+use num_bigint::BigUint;
+
+/// The salt of the HKDF extraction, separates the derivation from other uses of the seed.
+pub const RANDOMNESS_SALT: &[u8] = b"provotum-mixnet/randomness/v1";
+
+/// The number of additional bytes derived to make the bias of the reduction modulo q negligible.
+const EXTRA_BYTES: usize = 16;
+
+pub struct Derivation;
+
+impl Derivation {
+    /// Derives the randomness r in Z_q to encrypt the answer of the topic in the given attempt.
+    /// The vote and topic id are SCALE encoded (length prefixed), i.e., the context is unambiguous.
+    ///
+    /// Returns None if the number of bytes to derive exceeds the HKDF limit (q > 16 KiB).
+    pub fn derive_randomness(
+        seed: &[u8],
+        vote_id: &[u8],
+        topic_id: &[u8],
+        attempt: u32,
+        q: &BigUint,
+    ) -> Option<BigUint> {
+        let info: Vec<u8> = (vote_id, topic_id, attempt).encode();
+        let length = div_ceil(q.bits() as usize, 8) + EXTRA_BYTES;
+        let mut okm = vec![0u8; length];
+
+        let hkdf = Hkdf::<Blake2b>::new(Some(RANDOMNESS_SALT), seed);
+        hkdf.expand(&info, &mut okm).ok()?;
+        Some(BigUint::from_bytes_be(&okm) % q)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{derivation::Derivation, encryption::ElGamal, helper::Helper};
+    use num_bigint::BigUint;
+
+    const SEED: &[u8] = b"a secret seed held by the voter";
+
+    #[test]
+    fn it_should_derive_the_same_randomness_for_the_same_inputs() {
+        let (params, _, _) = Helper::setup_md_system();
+        let q = &params.q();
+
+        let r1 = Derivation::derive_randomness(SEED, b"20201212", b"20201212-01", 0, q).unwrap();
+        let r2 = Derivation::derive_randomness(SEED, b"20201212", b"20201212-01", 0, q).unwrap();
+        assert_eq!(r1, r2);
+        assert!(&r1 < q);
+    }
+
+    #[test]
+    fn it_should_derive_different_randomness_for_each_context() {
+        let (params, _, _) = Helper::setup_md_system();
+        let q = &params.q();
+
+        let r = Derivation::derive_randomness(SEED, b"20201212", b"20201212-01", 0, q).unwrap();
+        let others = vec![
+            Derivation::derive_randomness(b"another seed", b"20201212", b"20201212-01", 0, q),
+            Derivation::derive_randomness(SEED, b"20201213", b"20201212-01", 0, q),
+            Derivation::derive_randomness(SEED, b"20201212", b"20201212-02", 0, q),
+            Derivation::derive_randomness(SEED, b"20201212", b"20201212-01", 1, q),
+            // the ids are length prefixed, moving bytes from one id to the other changes r
+            Derivation::derive_randomness(SEED, b"20201212-", b"20201212-01", 0, q),
+        ];
+        for other in others.into_iter() {
+            assert_ne!(r, other.unwrap());
+        }
+    }
+
+    #[test]
+    fn it_should_re_encrypt_the_same_cipher_from_the_seed() {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let answer = BigUint::from(1u32);
+
+        let r = Derivation::derive_randomness(SEED, b"20201212", b"20201212-01", 0, q).unwrap();
+        let cipher = ElGamal::encrypt_encode(&answer, &r, &pk);
+
+        let r = Derivation::derive_randomness(SEED, b"20201212", b"20201212-01", 0, q).unwrap();
+        assert_eq!(ElGamal::encrypt_encode(&answer, &r, &pk), cipher);
+    }
+}
@@ -1,7 +1,28 @@
-use crate::types::{Cipher, ModuloOperations, PrivateKey, PublicKey};
-use alloc::vec::Vec;
+use crate::types::{
+    multi_modpow, Cipher, ElGamalParams, Group, GroupCipher, ModuloOperations, PrivateKey,
+    PublicKey,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
 use num_bigint::BigUint;
-use num_traits::{One, Zero};
+use num_traits::{One, ToPrimitive, Zero};
+
+/// The default upper bound for the discrete logarithm search used in `decrypt_decode`.
+pub const DEFAULT_DECODE_BOUND: u64 = 1_000_000;
+
+/// The default number of steps between two progress reports of a `DecodeTask`.
+pub const DEFAULT_PROGRESS_INTERVAL: u64 = 10_000;
+
+/// Calculates base^exponent % modulus.
+#[cfg(not(feature = "wasm-opt"))]
+fn modpow(base: &BigUint, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+    base.modpow(exponent, modulus)
+}
+
+/// Calculates base^exponent % modulus in Montgomery form on 32 bit limbs (see: `montgomery`).
+#[cfg(feature = "wasm-opt")]
+fn modpow(base: &BigUint, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+    crate::montgomery::modpow(base, exponent, modulus)
+}
 
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
 pub struct ElGamal;
@@ -21,13 +42,13 @@ impl ElGamal {
         let h = &pk.h;
 
         // a = g^r
-        let a = g.modpow(r, p);
+        let a = modpow(g, r, p);
 
         // encode the message: g^m (exponential elgamal)
         let enc_m = ElGamal::encode_message(m, g, p);
 
         // b = h^r * g^m
-        let h_pow_r = h.modpow(r, p);
+        let h_pow_r = modpow(h, r, p);
         let b = h_pow_r.modmul(&enc_m, p);
 
         Cipher { a, b }
@@ -50,18 +71,45 @@ impl ElGamal {
 
         // perform quadratic residue check: m^q mod p == 1
         // to ensure DDH is given
-        assert!(m.modpow(q, p) == BigUint::one());
+        assert!(modpow(m, q, p) == BigUint::one());
 
         // a = g^r
-        let a = g.modpow(r, p);
+        let a = modpow(g, r, p);
 
         // b = h^r * m
-        let h_pow_r = h.modpow(r, p);
+        let h_pow_r = modpow(h, r, p);
         let b = h_pow_r.modmul(m, p);
 
         Cipher { a, b }
     }
 
+    /// Verifies that a cipher is an encryption of the message `m` using the randomness `r`.
+    /// Used for the Benaloh challenge (cast-as-intended verification): the voter reveals
+    /// the randomness such that an independent device can verify the encryption.
+    /// Important! A cipher whose randomness has been revealed must not be submitted.
+    ///
+    /// ## Arguments
+    ///
+    /// * `cipher`  - The ElGamal Encryption (a: BigUint, b: BigUint)
+    /// * `m`       - The claimed message (BigUint)
+    /// * `r`       - The revealed random number used to encrypt the message
+    /// * `pk`      - The public key used to encrypt the message
+    /// * `encoded` - Whether the message was encrypted using `encrypt_encode`
+    pub fn verify_encryption(
+        cipher: &Cipher,
+        m: &BigUint,
+        r: &BigUint,
+        pk: &PublicKey,
+        encoded: bool,
+    ) -> bool {
+        let expected = if encoded {
+            ElGamal::encrypt_encode(m, r, pk)
+        } else {
+            ElGamal::encrypt(m, r, pk)
+        };
+        *cipher == expected
+    }
+
     /// Returns the plaintext contained in an ElGamal Encryption.
     /// Decrypts the ciphertext and decodes the result.
     /// Important! Requires that the encryption was done using `encrypt_encode`.
@@ -73,6 +121,19 @@ impl ElGamal {
     /// * `cipher` - The ElGamal Encryption (a: BigUint, b: BigUint)
     /// * `sk`     - The private key used to decrypt the vote
     pub fn decrypt_decode(cipher: &Cipher, sk: &PrivateKey) -> BigUint {
+        ElGamal::decrypt_decode_bounded(cipher, sk, DEFAULT_DECODE_BOUND)
+            .expect("message exceeds the decode bound!")
+    }
+
+    /// Same as `decrypt_decode` but with a configurable upper bound for the message.
+    /// Returns None if the message is larger than `bound`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `cipher` - The ElGamal Encryption (a: BigUint, b: BigUint)
+    /// * `sk`     - The private key used to decrypt the vote
+    /// * `bound`  - The largest message that is searched for
+    pub fn decrypt_decode_bounded(cipher: &Cipher, sk: &PrivateKey, bound: u64) -> Option<BigUint> {
         let a = &cipher.a;
         let b = &cipher.b;
 
@@ -89,8 +150,8 @@ impl ElGamal {
         // b = g^m*h^r -> mh = b * s^-1
         let mh = b.modmul(&s_1, p);
 
-        // brute force discrete logarithm
-        ElGamal::decode_message(&mh, g, p)
+        // baby-step giant-step discrete logarithm
+        ElGamal::decode_message_bsgs(&mh, g, p, bound)
     }
 
     /// Returns the plaintext contained in an ElGamal Encryption.
@@ -136,6 +197,32 @@ impl ElGamal {
         a.modpow(x, p)
     }
 
+    /// Computes the partial decryptions of all encryptions using a share sk of the private decryption key,
+    /// i.e., `partial_decrypt_a` for every encryption in the same order.
+    /// With the `parallel` feature the encryptions are decrypted on all cores (rayon).
+    ///
+    /// ## Arguments
+    ///
+    /// * `ciphers` - The ElGamal Encryptions
+    /// * `sk`      - The private key share used to decrypt the votes
+    pub fn partial_decrypt_many(ciphers: &[Cipher], sk: &PrivateKey) -> Vec<BigUint> {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            ciphers
+                .par_iter()
+                .map(|cipher| Self::partial_decrypt_a(cipher, sk))
+                .collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            ciphers
+                .iter()
+                .map(|cipher| Self::partial_decrypt_a(cipher, sk))
+                .collect()
+        }
+    }
+
     /// Similar to GetVotes Algorithm 8.53 (CHVoteSpec 3.2)
     /// Computes the decrypted plaintext vote m by
     /// deducting the combined partial decryptions vec_a (== decrypted_a == a^sk == (g^r)^sk) from
@@ -200,6 +287,45 @@ impl ElGamal {
         combined_decrypted_as
     }
 
+    /// Similar to `combine_partial_decrypted_as` but for threshold keys (Shamir secret sharing),
+    /// i.e., each participant holds the point f(x_i) of the secret polynomial f with f(0) = sk.
+    /// The partial decryptions of any t participants are combined in the exponent:
+    /// a^sk = prod((a^f(x_i))^lambda_i) mod p, see: `SecretSharing::lagrange_coefficients`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `vec_vec_a` - A vector of all participants of a vecor of all partial decryptions of component a: Cipher { a, b }
+    /// * `coefficients` - The Lagrange coefficients of the participants (same order as `vec_vec_a`)
+    /// * `p` - The group modulus p (BigUint)
+    pub fn combine_threshold_partial_decrypted_as(
+        vec_vec_a: Vec<Vec<BigUint>>,
+        coefficients: &[BigUint],
+        p: &BigUint,
+    ) -> Vec<BigUint> {
+        assert!(
+            !vec_vec_a.is_empty(),
+            "there must be at least one participant."
+        );
+        assert!(
+            vec_vec_a.len() == coefficients.len(),
+            "there must be a coefficient per participant."
+        );
+        assert!(!vec_vec_a[0].is_empty(), "there must be at least one vote.");
+        let exponents: Vec<&BigUint> = coefficients.iter().collect();
+
+        // outer loop: all partial decrypted a for all submitted votes -> size = # of votes
+        (0..vec_vec_a[0].len())
+            .map(|i| {
+                // all partial decryptions by all participants -> size = # of participants
+                let bases: Vec<&BigUint> = vec_vec_a
+                    .iter()
+                    .map(|partial_decryptions| &partial_decryptions[i])
+                    .collect();
+                multi_modpow(&bases, &exponents, p)
+            })
+            .collect()
+    }
+
     /// Encodes a plain-text message to be used in an explonential ElGamal scheme
     /// Returns encoded_message = g^m.
     ///
@@ -209,7 +335,7 @@ impl ElGamal {
     /// * `g` - The generator of the cyclic group Z_p (BigUint)
     /// * `p` - The group modulus p (BigUint)
     pub fn encode_message(m: &BigUint, g: &BigUint, p: &BigUint) -> BigUint {
-        g.modpow(m, p)
+        modpow(g, m, p)
     }
 
     /// Decodes an explonential ElGamal scheme encoded message by brute forcing the discrete lograithm.
@@ -232,6 +358,30 @@ impl ElGamal {
         message
     }
 
+    /// Decodes an explonential ElGamal scheme encoded message using the baby-step giant-step algorithm.
+    /// The goal is to find m in [0, bound] such that: encoded_message = g^m.
+    /// Requires O(sqrt(bound)) time and memory instead of O(m) for `decode_message`.
+    /// Returns None if no such m exists.
+    ///
+    /// ## Arguments
+    ///
+    /// * `encoded_message` - The encoded message: g^m (BigUint)
+    /// * `g` - The generator of the cyclic group Z_p (BigUint)
+    /// * `p` - The group modulus p (BigUint)
+    /// * `bound` - The largest message that is searched for
+    pub fn decode_message_bsgs(
+        encoded_message: &BigUint,
+        g: &BigUint,
+        p: &BigUint,
+        bound: u64,
+    ) -> Option<BigUint> {
+        let mut task = DecodeTask::new(encoded_message, g, p, bound);
+        match task.poll(u64::MAX) {
+            DecodeStatus::Decoded(message) => Some(message),
+            _ => None,
+        }
+    }
+
     /// Homomorphically sums two ElGamal encryptions.
     /// Returns an ElGamal encryption.
     ///
@@ -278,6 +428,114 @@ impl ElGamal {
         }
     }
 
+    /// Returns an ElGamal Encryption of a group element in an arbitrary group.
+    /// - (a, b) = (g^r, h^r * m)
+    ///
+    /// ## Arguments
+    ///
+    /// * `group` - The group in which the encryption is done
+    /// * `h`     - The public key (a group element)
+    /// * `m`     - The message (a group element)
+    /// * `r`     - The random number used to encrypt the message
+    pub fn encrypt_in_group<G: Group>(
+        group: &G,
+        h: &G::Element,
+        m: &G::Element,
+        r: &G::Scalar,
+    ) -> GroupCipher<G::Element> {
+        let a = group.exp(&group.generator(), r);
+        let b = group.op(&group.exp(h, r), m);
+        GroupCipher { a, b }
+    }
+
+    /// Returns the group element contained in an ElGamal Encryption in an arbitrary group.
+    /// - m = b * (a^x)^-1
+    ///
+    /// ## Arguments
+    ///
+    /// * `group`  - The group in which the encryption was done
+    /// * `cipher` - The ElGamal Encryption (a, b)
+    /// * `x`      - The private key
+    pub fn decrypt_in_group<G: Group>(
+        group: &G,
+        cipher: &GroupCipher<G::Element>,
+        x: &G::Scalar,
+    ) -> G::Element {
+        let s = group.exp(&cipher.a, x);
+        group.op(&cipher.b, &group.inverse(&s))
+    }
+
+    /// Homomorphically adds two ElGamal encryptions in an arbitrary group.
+    /// - (a, b) = (a1 * a2, b1 * b2)
+    pub fn homomorphic_addition_in_group<G: Group>(
+        group: &G,
+        this: &GroupCipher<G::Element>,
+        other: &GroupCipher<G::Element>,
+    ) -> GroupCipher<G::Element> {
+        GroupCipher {
+            a: group.op(&this.a, &other.a),
+            b: group.op(&this.b, &other.b),
+        }
+    }
+
+    /// Returns an ElGamal re-encryption in an arbitrary group.
+    /// - (a', b') = (a * g^r', b * h^r')
+    pub fn re_encrypt_in_group<G: Group>(
+        group: &G,
+        cipher: &GroupCipher<G::Element>,
+        h: &G::Element,
+        r: &G::Scalar,
+    ) -> GroupCipher<G::Element> {
+        let zero = Self::encrypt_in_group(group, h, &group.identity(), r);
+        Self::homomorphic_addition_in_group(group, cipher, &zero)
+    }
+
+    /// Encodes a message as group element in an arbitrary group (exponential ElGamal): g^m
+    pub fn encode_in_group<G: Group>(group: &G, m: &BigUint) -> G::Element {
+        group.exp(&group.generator(), &group.scalar_from_biguint(m))
+    }
+
+    /// Decodes an encoded message g^m in an arbitrary group by searching the discrete
+    /// logarithm m in [0, bound]. Returns None if no such m exists.
+    pub fn decode_in_group<G: Group>(group: &G, encoded: &G::Element, bound: u64) -> Option<u64> {
+        let g = group.generator();
+        let mut candidate = group.identity();
+        for m in 0..=bound {
+            if candidate == *encoded {
+                return Some(m);
+            }
+            candidate = group.op(&candidate, &g);
+        }
+        None
+    }
+
+    /// Returns a shuffled (permuted & re-encrypted) list of ElGamal encryptions in an
+    /// arbitrary group: the i-th shuffled encryption is the re-encryption of the
+    /// encryption at position permutation\[i\] with the random value at the same position.
+    pub fn shuffle_in_group<G: Group>(
+        group: &G,
+        encryptions: &[GroupCipher<G::Element>],
+        permutation: &[usize],
+        randoms: &[G::Scalar],
+        h: &G::Element,
+    ) -> Vec<GroupCipher<G::Element>> {
+        assert!(
+            encryptions.len() == randoms.len(),
+            "encryptions and randoms need to have the same length!"
+        );
+        assert!(
+            encryptions.len() == permutation.len(),
+            "encryptions and permutation need to have the same length!"
+        );
+        assert!(!encryptions.is_empty(), "vectors cannot be empty!");
+        permutation
+            .iter()
+            .map(|entry| {
+                Self::re_encrypt_in_group(group, &encryptions[*entry], h, &randoms[*entry])
+            })
+            .collect()
+    }
+
     /// Returns an ElGamal re-encryption of a message
     /// - message:      (a, b)   = (g^r, h^r * g^m)
     /// - reencryption: (a', b') = (a * g^r', b * h^r') = (g^(r * r'), h^(r * r') * g^m)
@@ -289,8 +547,8 @@ impl ElGamal {
     /// * `pk`     - The public key used to re-encrypt_encode the vote
     pub fn re_encrypt(cipher: &Cipher, r: &BigUint, pk: &PublicKey) -> Cipher {
         let p = &pk.params.p;
-        let a_ = pk.params.g.modpow(r, p);
-        let b_ = pk.h.modpow(r, p);
+        let a_ = modpow(&pk.params.g, r, p);
+        let b_ = modpow(&pk.h, r, p);
         Cipher {
             a: cipher.a.modmul(&a_, p),
             b: cipher.b.modmul(&b_, p),
@@ -314,6 +572,65 @@ impl ElGamal {
         Self::homomorphic_addition(cipher, &zero, &pk.params.p)
     }
 
+    /// Returns the key switch share of a participant for an ElGamal encryption.
+    /// Used to transform an encryption under the old (combined) public key h = g^x
+    /// into an encryption under a new public key h' without decrypting it.
+    /// - share: (A_i, B_i) = (g^s_i, h'^s_i * a^-x_i)
+    ///
+    /// ## Arguments
+    ///
+    /// * `cipher`   - An ElGamal Encryption { a: BigUint, b: BigUint } under the old public key
+    /// * `sk_share` - The participant's private key share x_i of the old public key
+    /// * `s`        - The random number used by the participant
+    /// * `new_pk`   - The new public key h'
+    pub fn key_switch_share(
+        cipher: &Cipher,
+        sk_share: &PrivateKey,
+        s: &BigUint,
+        new_pk: &PublicKey,
+    ) -> Cipher {
+        let p = &new_pk.params.p;
+        let g = &new_pk.params.g;
+
+        // a^x_i -> the partial decryption of component a
+        let decrypted_a = Self::partial_decrypt_a(cipher, sk_share);
+
+        // A_i = g^s_i, B_i = h'^s_i * a^-x_i
+        let a = g.modpow(s, p);
+        let b = new_pk
+            .h
+            .modpow(s, p)
+            .moddiv(&decrypted_a, p)
+            .expect("cannot compute mod_inverse in mod_div!");
+        Cipher { a, b }
+    }
+
+    /// Combines the key switch shares of all participants of the old public key.
+    /// Returns an encryption of the same message under the new public key.
+    /// - (a', b') = (prod(A_i), b * prod(B_i)) = (g^s, h'^s * g^m) with s = sum(s_i)
+    ///
+    /// ## Arguments
+    ///
+    /// * `cipher` - An ElGamal Encryption { a: BigUint, b: BigUint } under the old public key
+    /// * `shares` - The key switch shares of all participants
+    /// * `p`      - The group modulus p (BigUint)
+    pub fn combine_key_switch_shares(cipher: &Cipher, shares: &[Cipher], p: &BigUint) -> Cipher {
+        assert!(
+            !shares.is_empty(),
+            "there must be at least one participant."
+        );
+        shares.iter().fold(
+            Cipher {
+                a: BigUint::one(),
+                b: cipher.b.clone(),
+            },
+            |combined, share| Cipher {
+                a: combined.a.modmul(&share.a, p),
+                b: combined.b.modmul(&share.b, p),
+            },
+        )
+    }
+
     /// Returns a shuffled (permuted & re-encrypted) list of ElGamal encryptions.
     ///
     /// ## Arguments
@@ -351,17 +668,303 @@ impl ElGamal {
         }
         re_encryptions
     }
+
+    /// Returns the number of answers of `slot_bits` bits each which can be packed into a single message,
+    /// i.e., the largest k such that 2^(k * slot_bits) <= q. Zero if the slot width is not in [1, 64].
+    ///
+    /// ## Arguments
+    ///
+    /// * `slot_bits` - The width of a slot in bits
+    /// * `params`    - The public parameters of the group
+    pub fn packing_capacity(slot_bits: u32, params: &ElGamalParams) -> usize {
+        if slot_bits == 0 || slot_bits > 64 {
+            return 0;
+        }
+        let usable_bits = params.q().bits().saturating_sub(1);
+        (usable_bits / slot_bits as u64) as usize
+    }
+
+    /// Packs several small answers into a single message (ballot packing), i.e., the answers of
+    /// independent questions are shuffled and decrypted as one cipher. Answer i occupies the
+    /// fixed-width bit slot i: x = 1 + sum(a_i * 2^(i * slot_bits)). Since x <= q, either x or p - x
+    /// is a quadratic residue (p = 2q + 1 is a safe prime), the residue is returned. The message
+    /// can be encrypted with `encrypt` and the decrypted message is unpacked with `unpack`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `answers`   - The answers, one per slot (at least one)
+    /// * `slot_bits` - The width of a slot in bits, every answer must be less than 2^slot_bits
+    /// * `params`    - The public parameters of the group
+    pub fn pack(
+        answers: &[u64],
+        slot_bits: u32,
+        params: &ElGamalParams,
+    ) -> Result<BigUint, PackingError> {
+        Self::ensure_valid_packing(answers.len(), slot_bits, params)?;
+
+        // an answer must not overflow into the next slot
+        if slot_bits < 64 && answers.iter().any(|answer| *answer >> slot_bits != 0) {
+            return Err(PackingError::SlotOverflow);
+        }
+        let packed: BigUint = answers
+            .iter()
+            .enumerate()
+            .fold(BigUint::zero(), |packed, (i, answer)| {
+                packed | (BigUint::from(*answer) << (i * slot_bits as usize))
+            });
+        let x = packed + BigUint::one();
+
+        // map x into the group of quadratic residues
+        let p = &params.p;
+        if modpow(&x, &params.q(), p).is_one() {
+            Ok(x)
+        } else {
+            Ok(p - x)
+        }
+    }
+
+    /// Unpacks the answers of a message packed by `pack`, e.g. a decrypted cipher.
+    /// Fails if the message is not a packed message with the given number of slots.
+    ///
+    /// ## Arguments
+    ///
+    /// * `message`     - The packed message (a quadratic residue)
+    /// * `nr_of_slots` - The number of packed answers
+    /// * `slot_bits`   - The width of a slot in bits
+    /// * `params`      - The public parameters of the group
+    pub fn unpack(
+        message: &BigUint,
+        nr_of_slots: usize,
+        slot_bits: u32,
+        params: &ElGamalParams,
+    ) -> Result<Vec<u64>, PackingError> {
+        Self::ensure_valid_packing(nr_of_slots, slot_bits, params)?;
+        let p = &params.p;
+        let q = params.q();
+        if message.is_zero() || message >= p {
+            return Err(PackingError::InvalidMessage);
+        }
+
+        // reverse the mapping into the group: x is in [1, q]
+        let x = if message <= &q {
+            message.clone()
+        } else {
+            p - message
+        };
+        let packed = x - BigUint::one();
+        if packed.bits() > nr_of_slots as u64 * slot_bits as u64 {
+            return Err(PackingError::InvalidMessage);
+        }
+
+        let mask = (BigUint::one() << slot_bits as usize) - BigUint::one();
+        let answers = (0..nr_of_slots)
+            .map(|i| {
+                let slot = (&packed >> (i * slot_bits as usize)) & &mask;
+                // a slot has at most 64 bits
+                slot.to_u64().unwrap_or_default()
+            })
+            .collect();
+        Ok(answers)
+    }
+
+    /// ensures that the slots fit into a single message without wrapping around q
+    fn ensure_valid_packing(
+        nr_of_slots: usize,
+        slot_bits: u32,
+        params: &ElGamalParams,
+    ) -> Result<(), PackingError> {
+        if slot_bits == 0 || slot_bits > 64 {
+            return Err(PackingError::InvalidSlotWidth);
+        }
+        if nr_of_slots == 0 || nr_of_slots > Self::packing_capacity(slot_bits, params) {
+            return Err(PackingError::InvalidSlotCount);
+        }
+        Ok(())
+    }
+}
+
+/// The error of packing answers into a single message, see: `ElGamal::pack`.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum PackingError {
+    /// the width of a slot is not in [1, 64] bits
+    InvalidSlotWidth,
+    /// there are no slots or the slots don't fit into a message, see: `ElGamal::packing_capacity`
+    InvalidSlotCount,
+    /// an answer doesn't fit into its slot
+    SlotOverflow,
+    /// the message is not a packed message with the given slots
+    InvalidMessage,
+}
+
+/// The progress of a `DecodeTask`.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct DecodeProgress {
+    /// the number of performed steps (baby steps and giant steps)
+    pub steps: u64,
+    /// the maximal number of steps of the search
+    pub total_steps: u64,
+}
+
+/// The status of a `DecodeTask`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum DecodeStatus {
+    /// the search has not finished yet
+    Pending,
+    /// the decoded message
+    Decoded(BigUint),
+    /// there exists no message m in [0, bound] such that: encoded_message = g^m
+    NotFound,
+    /// the search was cancelled by the progress callback, it can be resumed
+    Cancelled,
+}
+
+/// An incremental baby-step giant-step decoding of an explonential ElGamal encoded message.
+/// Performs the same search as `ElGamal::decode_message_bsgs` but can be advanced a number of
+/// steps at a time (`poll`) or run with a progress callback which can cancel it (`run`).
+/// A task that has been cancelled or is still pending can be resumed.
+#[derive(Clone, Debug)]
+pub struct DecodeTask {
+    g: BigUint,
+    p: BigUint,
+    bound: u64,
+    /// step size: m = ceil(sqrt(bound + 1)) -> m * m > bound
+    m: u64,
+    baby_steps: BTreeMap<BigUint, u64>,
+    /// the number of performed baby steps j and the next baby step g^j
+    j: u64,
+    value: BigUint,
+    /// the number of performed giant steps i and the next giant step encoded_message * (g^-m)^i
+    i: u64,
+    gamma: BigUint,
+    /// g^-m, available once all baby steps have been performed
+    factor: BigUint,
+    result: Option<Option<BigUint>>,
+}
+
+impl DecodeTask {
+    /// Creates a new decoding task for the message: encoded_message = g^m with m in [0, bound].
+    ///
+    /// ## Arguments
+    ///
+    /// * `encoded_message` - The encoded message: g^m (BigUint)
+    /// * `g` - The generator of the cyclic group Z_p (BigUint)
+    /// * `p` - The group modulus p (BigUint)
+    /// * `bound` - The largest message that is searched for
+    pub fn new(encoded_message: &BigUint, g: &BigUint, p: &BigUint, bound: u64) -> Self {
+        let mut m = 1u64;
+        while m.saturating_mul(m) <= bound {
+            m += 1;
+        }
+        DecodeTask {
+            g: g.clone(),
+            p: p.clone(),
+            bound,
+            m,
+            baby_steps: BTreeMap::new(),
+            j: 0,
+            value: BigUint::one(),
+            i: 0,
+            gamma: encoded_message % p,
+            factor: BigUint::zero(),
+            result: None,
+        }
+    }
+
+    /// Performs a single step of the search: first all baby steps, then the giant steps.
+    fn step(&mut self) {
+        if self.j < self.m {
+            // baby steps: store g^j for j in [0, m)
+            // only the smallest j is kept in case the order of g is smaller than m
+            self.baby_steps.entry(self.value.clone()).or_insert(self.j);
+            self.value = self.value.modmul(&self.g, &self.p);
+            self.j += 1;
+
+            // after the last baby step: value = g^m
+            if self.j == self.m {
+                self.factor = self
+                    .value
+                    .invmod(&self.p)
+                    .expect("cannot compute mod_inverse!");
+            }
+        } else {
+            // giant steps: compute encoded_message * (g^-m)^i for i in [0, m)
+            if let Some(j) = self.baby_steps.get(&self.gamma) {
+                let message = self.i * self.m + j;
+                self.result = Some(if message <= self.bound {
+                    Some(BigUint::from(message))
+                } else {
+                    None
+                });
+                return;
+            }
+            self.gamma = self.gamma.modmul(&self.factor, &self.p);
+            self.i += 1;
+
+            if self.i == self.m {
+                self.result = Some(None);
+            }
+        }
+    }
+
+    /// Advances the search by at most `max_steps` steps.
+    /// Returns `DecodeStatus::Pending` if the search has not finished yet.
+    pub fn poll(&mut self, max_steps: u64) -> DecodeStatus {
+        let mut steps = 0u64;
+        while self.result.is_none() && steps < max_steps {
+            self.step();
+            steps += 1;
+        }
+        self.status()
+    }
+
+    /// Runs the search until it has finished. The callback `on_progress` is called every
+    /// `interval` steps, the search is cancelled if it returns false.
+    pub fn run<F>(&mut self, interval: u64, mut on_progress: F) -> DecodeStatus
+    where
+        F: FnMut(DecodeProgress) -> bool,
+    {
+        loop {
+            let status = self.poll(interval.max(1));
+            if status != DecodeStatus::Pending {
+                return status;
+            }
+            if !on_progress(self.progress()) {
+                return DecodeStatus::Cancelled;
+            }
+        }
+    }
+
+    /// Returns the current status of the search. A pending search is never cancelled.
+    pub fn status(&self) -> DecodeStatus {
+        match &self.result {
+            None => DecodeStatus::Pending,
+            Some(Some(message)) => DecodeStatus::Decoded(message.clone()),
+            Some(None) => DecodeStatus::NotFound,
+        }
+    }
+
+    /// Returns the progress of the search.
+    pub fn progress(&self) -> DecodeProgress {
+        DecodeProgress {
+            steps: self.j + self.i,
+            total_steps: 2 * self.m,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        encryption::ElGamal,
+        encryption::{
+            DecodeProgress, DecodeStatus, DecodeTask, ElGamal, PackingError, DEFAULT_DECODE_BOUND,
+            DEFAULT_PROGRESS_INTERVAL,
+        },
         helper::Helper,
         random::Random,
+        secret_sharing::SecretSharing,
         types::Cipher,
         types::ElGamalParams,
-        types::{ModuloOperations, PublicKey},
+        types::{GroupCipher, ModuloOperations, PublicKey},
     };
     use alloc::vec::Vec;
     use num_bigint::BigUint;
@@ -413,6 +1016,143 @@ mod tests {
         assert_eq!(nine, decoded_message);
     }
 
+    #[test]
+    fn it_should_decode_bsgs() {
+        let (params, _, _) = Helper::setup_sm_system();
+
+        for value in [0u32, 1, 2, 9, 24, 25, 99, 100].iter() {
+            let message = BigUint::from(*value);
+            let encoded_message = ElGamal::encode_message(&message, &params.g, &params.p);
+            let decoded_message =
+                ElGamal::decode_message_bsgs(&encoded_message, &params.g, &params.p, 100);
+            assert_eq!(Some(message), decoded_message);
+        }
+    }
+
+    #[test]
+    fn it_should_not_decode_bsgs_message_larger_than_bound() {
+        let (params, _, _) = Helper::setup_md_system();
+        let message = BigUint::from(101u32);
+        let encoded_message = ElGamal::encode_message(&message, &params.g, &params.p);
+        let decoded_message =
+            ElGamal::decode_message_bsgs(&encoded_message, &params.g, &params.p, 100);
+        assert_eq!(None, decoded_message);
+    }
+
+    #[test]
+    fn it_should_decode_bsgs_large_tally() {
+        let (params, _, _) = Helper::setup_md_system();
+        let message = BigUint::from(54_321u32);
+        let encoded_message = ElGamal::encode_message(&message, &params.g, &params.p);
+        let decoded_message = ElGamal::decode_message_bsgs(
+            &encoded_message,
+            &params.g,
+            &params.p,
+            DEFAULT_DECODE_BOUND,
+        );
+        assert_eq!(Some(message), decoded_message);
+    }
+
+    #[test]
+    fn it_should_decode_incrementally() {
+        let (params, _, _) = Helper::setup_md_system();
+        let message = BigUint::from(4_321u32);
+        let encoded_message = ElGamal::encode_message(&message, &params.g, &params.p);
+        let mut task = DecodeTask::new(&encoded_message, &params.g, &params.p, 10_000);
+
+        // the task is advanced a few steps at a time until it has finished
+        let mut status = task.poll(7);
+        let mut polls = 1;
+        while status == DecodeStatus::Pending {
+            status = task.poll(7);
+            polls += 1;
+        }
+        assert!(polls > 1);
+        assert_eq!(DecodeStatus::Decoded(message), status);
+
+        // polling a finished task doesn't change its result
+        assert_eq!(status, task.poll(1));
+    }
+
+    #[test]
+    fn it_should_not_decode_incrementally_message_larger_than_bound() {
+        let (params, _, _) = Helper::setup_sm_system();
+        let message = BigUint::from(101u32);
+        let encoded_message = ElGamal::encode_message(&message, &params.g, &params.p);
+        let mut task = DecodeTask::new(&encoded_message, &params.g, &params.p, 100);
+        assert_eq!(DecodeStatus::NotFound, task.poll(u64::MAX));
+    }
+
+    #[test]
+    fn it_should_report_decode_progress() {
+        let (params, _, _) = Helper::setup_md_system();
+        let message = BigUint::from(DEFAULT_DECODE_BOUND);
+        let encoded_message = ElGamal::encode_message(&message, &params.g, &params.p);
+        let mut task =
+            DecodeTask::new(&encoded_message, &params.g, &params.p, DEFAULT_DECODE_BOUND);
+
+        let mut reports: Vec<DecodeProgress> = Vec::new();
+        let status = task.run(DEFAULT_PROGRESS_INTERVAL / 10, |progress| {
+            reports.push(progress);
+            true
+        });
+        assert_eq!(DecodeStatus::Decoded(message), status);
+
+        // the progress is reported every interval and increases monotonically
+        assert!(!reports.is_empty());
+        for (index, progress) in reports.iter().enumerate() {
+            assert_eq!(
+                (index as u64 + 1) * DEFAULT_PROGRESS_INTERVAL / 10,
+                progress.steps
+            );
+            assert!(progress.steps <= progress.total_steps);
+        }
+    }
+
+    #[test]
+    fn it_should_resume_cancelled_decoding() {
+        let (params, _, _) = Helper::setup_md_system();
+        let message = BigUint::from(54_321u32);
+        let encoded_message = ElGamal::encode_message(&message, &params.g, &params.p);
+        let mut task = DecodeTask::new(&encoded_message, &params.g, &params.p, 100_000);
+
+        // cancel the search after the first progress report
+        let status = task.run(10, |_| false);
+        assert_eq!(DecodeStatus::Cancelled, status);
+        assert_eq!(10, task.progress().steps);
+
+        // resume the search
+        let status = task.run(10, |_| true);
+        assert_eq!(DecodeStatus::Decoded(message), status);
+    }
+
+    #[test]
+    fn it_should_verify_encryption_with_revealed_randomness() {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let message = BigUint::from(1u32);
+        let r = Random::get_random_less_than(&params.q());
+
+        let cipher = ElGamal::encrypt_encode(&message, &r, &pk);
+        assert!(ElGamal::verify_encryption(&cipher, &message, &r, &pk, true));
+
+        // the cipher doesn't match if the message, the randomness or the encoding differ
+        let other_message = BigUint::from(2u32);
+        assert!(!ElGamal::verify_encryption(
+            &cipher,
+            &other_message,
+            &r,
+            &pk,
+            true
+        ));
+        let other_r = r.clone() + 1u32;
+        assert!(!ElGamal::verify_encryption(
+            &cipher, &message, &other_r, &pk, true
+        ));
+        assert!(!ElGamal::verify_encryption(
+            &cipher, &message, &r, &pk, false
+        ));
+    }
+
     #[test]
     fn it_should_encrypt_encode() {
         let params = ElGamalParams {
@@ -922,6 +1662,106 @@ mod tests {
         assert_eq!(decrypted_five, five, "five does not equal five!");
     }
 
+    #[test]
+    fn it_should_partially_decrypt_many_encryptions_in_order() {
+        let (params, sk, pk) = Helper::setup_md_system();
+        let q = params.q();
+
+        // the messages must be quadratic residues
+        let encryptions: Vec<Cipher> = (1..=20u32)
+            .map(|m| {
+                let r = Random::get_random_less_than(&q);
+                ElGamal::encrypt(&BigUint::from(m * m), &r, &pk)
+            })
+            .collect();
+
+        let partial_decryptions = ElGamal::partial_decrypt_many(&encryptions, &sk);
+        assert_eq!(partial_decryptions.len(), encryptions.len());
+        for (m, (cipher, decrypted_a)) in encryptions
+            .iter()
+            .zip(partial_decryptions.iter())
+            .enumerate()
+        {
+            assert_eq!(decrypted_a, &ElGamal::partial_decrypt_a(cipher, &sk));
+            let plaintext = ElGamal::partial_decrypt_b(&cipher.b, decrypted_a, &params.p);
+            assert_eq!(plaintext, BigUint::from((m + 1) * (m + 1)));
+        }
+        assert!(ElGamal::partial_decrypt_many(&[], &sk).is_empty());
+    }
+
+    #[test]
+    fn it_should_pack_and_unpack_answers() {
+        let (params, sk, pk) = Helper::setup_md_system();
+        let capacity = ElGamal::packing_capacity(8, &params);
+        assert_eq!(capacity, (params.q().bits() as usize - 1) / 8);
+
+        let answers: Vec<u64> = (0..capacity as u64).map(|i| (i * 37) % 256).collect();
+        let message = ElGamal::pack(&answers, 8, &params).unwrap();
+        assert!(message.modpow(&params.q(), &params.p).is_one());
+
+        // the packed message is encrypted and decrypted like any other message
+        let r = Random::get_random_less_than(&params.q());
+        let cipher = ElGamal::encrypt(&message, &r, &pk);
+        let decrypted = ElGamal::decrypt(&cipher, &sk);
+        assert_eq!(
+            ElGamal::unpack(&decrypted, capacity, 8, &params),
+            Ok(answers)
+        );
+
+        // the largest answers of all slots
+        let answers = vec![255u64; capacity];
+        let message = ElGamal::pack(&answers, 8, &params).unwrap();
+        assert_eq!(ElGamal::unpack(&message, capacity, 8, &params), Ok(answers));
+        let answers = vec![u64::MAX];
+        let message = ElGamal::pack(&answers, 64, &params).unwrap();
+        assert_eq!(ElGamal::unpack(&message, 1, 64, &params), Ok(answers));
+    }
+
+    #[test]
+    fn it_should_reject_answers_which_overflow_their_slots() {
+        let (params, _, _) = Helper::setup_sm_system();
+        let capacity = ElGamal::packing_capacity(4, &params);
+        assert!(capacity > 0);
+
+        assert_eq!(
+            ElGamal::pack(&[1, 16], 4, &params),
+            Err(PackingError::SlotOverflow)
+        );
+        assert_eq!(
+            ElGamal::pack(&vec![0; capacity + 1], 4, &params),
+            Err(PackingError::InvalidSlotCount)
+        );
+        assert_eq!(
+            ElGamal::pack(&[], 4, &params),
+            Err(PackingError::InvalidSlotCount)
+        );
+        assert_eq!(
+            ElGamal::pack(&[1], 0, &params),
+            Err(PackingError::InvalidSlotWidth)
+        );
+        assert_eq!(
+            ElGamal::pack(&[1], 65, &params),
+            Err(PackingError::InvalidSlotWidth)
+        );
+        assert_eq!(ElGamal::packing_capacity(65, &params), 0);
+
+        // a message with more slots than expected
+        let message = ElGamal::pack(&[1, 2, 3], 4, &params).unwrap();
+        assert_eq!(
+            ElGamal::unpack(&message, 2, 4, &params),
+            Err(PackingError::InvalidMessage)
+        );
+        assert_eq!(ElGamal::unpack(&message, 3, 4, &params), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            ElGamal::unpack(&BigUint::zero(), 3, 4, &params),
+            Err(PackingError::InvalidMessage)
+        );
+        assert_eq!(
+            ElGamal::unpack(&params.p, 3, 4, &params),
+            Err(PackingError::InvalidMessage)
+        );
+    }
+
     #[test]
     fn it_should_show_that_combined_partial_decryptions_work() {
         // create system parameters
@@ -971,4 +1811,157 @@ mod tests {
         let plaintext = ElGamal::partial_decrypt_b(&encrypted_five.b, &combined_decrypted_a, p);
         assert!(plaintext == five);
     }
+
+    #[test]
+    fn it_should_combine_the_partial_decryptions_of_any_t_sealers() {
+        let (params, sk, pk) = Helper::setup_md_system();
+        let q = &params.q();
+        let p = &params.p;
+
+        // 2-out-of-3 key shares of the secret key
+        let coefficients = vec![Random::get_random_less_than(q)];
+        let fragments = SecretSharing::split(&sk.x, &coefficients, 3, q).unwrap();
+
+        // create two encrypted votes
+        let messages = vec![BigUint::from(5u32), BigUint::from(9u32)];
+        let ciphers: Vec<Cipher> = messages
+            .iter()
+            .map(|m| ElGamal::encrypt(m, &Random::get_random_less_than(q), &pk))
+            .collect();
+
+        for subset in [vec![0usize, 1], vec![2, 0], vec![0, 1, 2]].iter() {
+            // the partial decryptions of the sealers in the subset
+            let partial_decryptions: Vec<Vec<BigUint>> = subset
+                .iter()
+                .map(|i| {
+                    let (_, sk_share) = Helper::generate_key_pair(&params, &fragments[*i].value);
+                    ciphers
+                        .iter()
+                        .map(|cipher| ElGamal::partial_decrypt_a(cipher, &sk_share))
+                        .collect()
+                })
+                .collect();
+            let indices: Vec<u32> = subset.iter().map(|i| fragments[*i].index).collect();
+            let lambdas = SecretSharing::lagrange_coefficients(&indices, q).unwrap();
+
+            let decrypted_as =
+                ElGamal::combine_threshold_partial_decrypted_as(partial_decryptions, &lambdas, p);
+            for ((cipher, decrypted_a), m) in ciphers.iter().zip(decrypted_as).zip(&messages) {
+                assert_eq!(&ElGamal::partial_decrypt_b(&cipher.b, &decrypted_a, p), m);
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_switch_the_key_of_an_encryption() {
+        let (params, _, _) = Helper::setup_md_system();
+        let q = &params.q();
+        let p = &params.p;
+
+        // create bob's and charlie's key shares of the old public key
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &Random::get_random_less_than(q));
+        let (charlie_pk, charlie_sk) =
+            Helper::generate_key_pair(&params, &Random::get_random_less_than(q));
+        let old_pk = PublicKey {
+            h: bob_pk.h.modmul(&charlie_pk.h, p),
+            params: params.clone(),
+        };
+
+        // create the new public key
+        let (new_pk, new_sk) = Helper::generate_key_pair(&params, &Random::get_random_less_than(q));
+
+        // encrypt five using the old public key
+        let five = BigUint::from(5u32);
+        let r = Random::get_random_less_than(q);
+        let encrypted_five = ElGamal::encrypt_encode(&five, &r, &old_pk);
+
+        // each participant creates its key switch share
+        let bob_share = ElGamal::key_switch_share(
+            &encrypted_five,
+            &bob_sk,
+            &Random::get_random_less_than(q),
+            &new_pk,
+        );
+        let charlie_share = ElGamal::key_switch_share(
+            &encrypted_five,
+            &charlie_sk,
+            &Random::get_random_less_than(q),
+            &new_pk,
+        );
+
+        // the combined encryption can be decrypted using the new private key
+        let switched_five =
+            ElGamal::combine_key_switch_shares(&encrypted_five, &[bob_share, charlie_share], p);
+        assert!(switched_five != encrypted_five);
+        assert_eq!(ElGamal::decrypt_decode(&switched_five, &new_sk), five);
+    }
+
+    #[test]
+    fn it_should_encrypt_in_the_integer_group_as_the_biguint_elgamal() {
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let r = BigUint::from(5u32);
+        let m = ElGamal::encode_message(&BigUint::from(3u32), &params.g, &params.p);
+
+        // the group based encryption equals the existing implementation
+        let cipher = ElGamal::encrypt_in_group(&params, &pk.h, &m, &r);
+        let expected: GroupCipher<BigUint> = ElGamal::encrypt(&m, &r, &pk).into();
+        assert_eq!(cipher, expected);
+
+        // re-encryption and decryption
+        let r_ = BigUint::from(11u32);
+        let re_encrypted = ElGamal::re_encrypt_in_group(&params, &cipher, &pk.h, &r_);
+        let expected: GroupCipher<BigUint> = ElGamal::re_encrypt(&expected.into(), &r_, &pk).into();
+        assert_eq!(re_encrypted, expected);
+        assert_eq!(ElGamal::decrypt_in_group(&params, &re_encrypted, &sk.x), m);
+    }
+
+    #[test]
+    fn it_should_add_encryptions_homomorphically_in_the_integer_group() {
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let m1 = ElGamal::encode_message(&BigUint::from(2u32), &params.g, &params.p);
+        let m2 = ElGamal::encode_message(&BigUint::from(5u32), &params.g, &params.p);
+        let c1 = ElGamal::encrypt_in_group(&params, &pk.h, &m1, &BigUint::from(3u32));
+        let c2 = ElGamal::encrypt_in_group(&params, &pk.h, &m2, &BigUint::from(8u32));
+
+        let sum = ElGamal::homomorphic_addition_in_group(&params, &c1, &c2);
+        let decrypted = ElGamal::decrypt_in_group(&params, &sum, &sk.x);
+        let expected = ElGamal::encode_message(&BigUint::from(7u32), &params.g, &params.p);
+        assert_eq!(decrypted, expected);
+    }
+
+    #[test]
+    fn it_should_decode_messages_in_the_integer_group() {
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let m = ElGamal::encode_in_group(&params, &BigUint::from(9u32));
+        assert_eq!(
+            m,
+            ElGamal::encode_message(&BigUint::from(9u32), &params.g, &params.p)
+        );
+        let cipher = ElGamal::encrypt_in_group(&params, &pk.h, &m, &BigUint::from(4u32));
+        let decrypted = ElGamal::decrypt_in_group(&params, &cipher, &sk.x);
+        assert_eq!(ElGamal::decode_in_group(&params, &decrypted, 20), Some(9));
+        assert_eq!(ElGamal::decode_in_group(&params, &decrypted, 8), None);
+    }
+
+    #[cfg(feature = "ristretto")]
+    #[test]
+    fn it_should_encrypt_and_add_in_the_ristretto_group() {
+        use crate::types::{Group, Ristretto};
+
+        let group = Ristretto;
+        let sk = Random::get_random_scalar(&group);
+        let h = group.exp(&group.generator(), &sk);
+        let m1 = ElGamal::encode_in_group(&group, &BigUint::from(2u32));
+        let m2 = ElGamal::encode_in_group(&group, &BigUint::from(5u32));
+        let c1 = ElGamal::encrypt_in_group(&group, &h, &m1, &Random::get_random_scalar(&group));
+        let c2 = ElGamal::encrypt_in_group(&group, &h, &m2, &Random::get_random_scalar(&group));
+
+        // the sum of the encrypted messages is decrypted and decoded
+        let sum = ElGamal::homomorphic_addition_in_group(&group, &c1, &c2);
+        let re_encrypted =
+            ElGamal::re_encrypt_in_group(&group, &sum, &h, &Random::get_random_scalar(&group));
+        assert!(re_encrypted != sum);
+        let decrypted = ElGamal::decrypt_in_group(&group, &re_encrypted, &sk);
+        assert_eq!(ElGamal::decode_in_group(&group, &decrypted, 100), Some(7));
+    }
 }
@@ -0,0 +1,172 @@
+//! The hash functions of the Fiat-Shamir challenges of the proofs.
+//!
+//! The challenges of the key generation, decryption, key switch and shuffle proofs are computed
+//! with the hash function of the vote (see: the `_with_hash` functions of the proofs), i.e.,
+//! prover and verifier must use the same one. The functions without the suffix use Blake2b.
+//! The generators, the batch exponents and the derivation of the randomness always use Blake2b.
+use alloc::vec::Vec;
+use blake2::{Blake2b, Digest};
+use num_bigint::BigUint;
+use sha2::Sha256;
+use sha3::Sha3_256;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// The hash function of the Fiat-Shamir challenges.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum HashFunction {
+    /// Blake2b (512 bits), the default
+    Blake2b,
+    /// SHA-256 (FIPS 180-4)
+    Sha256,
+    /// SHA3-256 (FIPS 202)
+    Sha3_256,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for HashFunction {
+    fn default() -> Self {
+        HashFunction::Blake2b
+    }
+}
+
+impl HashFunction {
+    /// All hash functions.
+    pub const ALL: &'static [HashFunction] = &[
+        HashFunction::Blake2b,
+        HashFunction::Sha256,
+        HashFunction::Sha3_256,
+    ];
+
+    /// Returns the size of the digest in bits.
+    pub fn output_bits(&self) -> usize {
+        match self {
+            HashFunction::Blake2b => 512,
+            HashFunction::Sha256 | HashFunction::Sha3_256 => 256,
+        }
+    }
+
+    /// Returns the name of the hash function, e.g. in the transcript of a vote.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashFunction::Blake2b => "blake2b",
+            HashFunction::Sha256 => "sha256",
+            HashFunction::Sha3_256 => "sha3-256",
+        }
+    }
+
+    /// Returns the hash function with the name.
+    pub fn from_name(name: &str) -> Option<HashFunction> {
+        HashFunction::ALL
+            .iter()
+            .find(|hash| hash.name() == name)
+            .copied()
+    }
+}
+
+/// A hasher of one of the hash functions, the inputs are chained like with `Digest`.
+#[derive(Clone)]
+pub enum Hasher {
+    Blake2b(Blake2b),
+    Sha256(Sha256),
+    Sha3_256(Sha3_256),
+}
+
+impl Hasher {
+    pub fn new(hash: HashFunction) -> Self {
+        match hash {
+            HashFunction::Blake2b => Hasher::Blake2b(Blake2b::new()),
+            HashFunction::Sha256 => Hasher::Sha256(Sha256::new()),
+            HashFunction::Sha3_256 => Hasher::Sha3_256(Sha3_256::new()),
+        }
+    }
+
+    /// Hashes the data.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        match self {
+            Hasher::Blake2b(hasher) => hasher.update(data),
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Sha3_256(hasher) => hasher.update(data),
+        }
+    }
+
+    /// Hashes the data and returns the hasher, i.e., the calls can be chained.
+    pub fn chain(mut self, data: impl AsRef<[u8]>) -> Self {
+        self.update(data);
+        self
+    }
+
+    /// Returns the digest.
+    pub fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Blake2b(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha256(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha3_256(hasher) => hasher.finalize().to_vec(),
+        }
+    }
+
+    /// Returns the digest as big-endian integer.
+    pub fn finalize_to_biguint(self) -> BigUint {
+        BigUint::from_bytes_be(&self.finalize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HashFunction, Hasher};
+    use alloc::vec::Vec;
+
+    fn hex(bytes: &[u8]) -> Vec<u8> {
+        bytes
+            .iter()
+            .flat_map(|byte| format!("{:02x}", byte).into_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn it_should_hash_the_test_vectors() {
+        // the digests of "abc" (FIPS 180-4 and FIPS 202)
+        let digest = Hasher::new(HashFunction::Sha256).chain(b"abc").finalize();
+        assert_eq!(
+            hex(&digest),
+            b"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_vec()
+        );
+        let digest = Hasher::new(HashFunction::Sha3_256).chain(b"abc").finalize();
+        assert_eq!(
+            hex(&digest),
+            b"3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532".to_vec()
+        );
+    }
+
+    #[test]
+    fn it_should_chain_the_inputs() {
+        for hash in HashFunction::ALL.iter() {
+            let chained = Hasher::new(*hash).chain(b"a").chain(b"bc").finalize();
+            let mut hasher = Hasher::new(*hash);
+            hasher.update(b"abc");
+            assert_eq!(chained, hasher.finalize());
+            assert_eq!(chained.len() * 8, hash.output_bits());
+        }
+    }
+
+    #[test]
+    fn it_should_differ_between_the_hash_functions() {
+        let digests: Vec<Vec<u8>> = HashFunction::ALL
+            .iter()
+            .map(|hash| Hasher::new(*hash).chain(b"provotum").finalize())
+            .collect();
+        assert_ne!(digests[0][..32], digests[1][..]);
+        assert_ne!(digests[1], digests[2]);
+    }
+
+    #[test]
+    fn it_should_round_trip_the_names() {
+        for hash in HashFunction::ALL.iter() {
+            assert_eq!(HashFunction::from_name(hash.name()), Some(*hash));
+        }
+        assert_eq!(HashFunction::from_name("md5"), None);
+        assert_eq!(HashFunction::default(), HashFunction::Blake2b);
+    }
+}
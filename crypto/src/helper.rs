@@ -1,9 +1,57 @@
-use crate::types::{BigT, BigY, Cipher, ElGamalParams, PrivateKey, PublicKey};
-use alloc::vec::Vec;
+use crate::hash::{HashFunction, Hasher};
+use crate::types::{
+    div_ceil, BigT, BigY, Cipher, ElGamalParams, Group, GroupCipher, GroupT, GroupY, PrivateKey,
+    PublicKey,
+};
+use alloc::{string::ToString, vec, vec::Vec};
 use blake2::{Blake2b, Digest};
 use num_bigint::BigUint;
 use num_traits::{One, Zero};
 
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// The size (in bits) of the random exponents used for batch verification.
+pub const BATCH_EXPONENT_BITS: usize = 64;
+
+/// The largest size (in bits) of the random exponents, i.e., the size of the hash (Blake2b).
+pub const MAX_BATCH_EXPONENT_BITS: usize = 512;
+
+/// The domain of the generator derivation, the i-th generator is derived with the
+/// domain string "provotum/v1/generator/i" (see: `Helper::derive_generator`).
+pub const GENERATOR_DOMAIN: &str = "provotum/v1/generator/";
+
+/// The number of additional bits of the expanded seed, the bias of the reduction modulo p is at most 2^-128.
+const GENERATOR_EXTRA_BITS: u64 = 128;
+
+/// The input block size of Blake2b in bytes (r_in_bytes of RFC 9380).
+const BLAKE2B_BLOCK_BYTES: usize = 128;
+
+/// The output size of Blake2b in bytes (b_in_bytes of RFC 9380).
+const BLAKE2B_OUTPUT_BYTES: usize = 64;
+
+/// The transcript of the derivation of an independent generator of G_q.
+///
+/// The generator is derived from a nothing-up-my-sleeve seed, i.e., from public inputs only:
+/// nobody knows its discrete logarithm with respect to g, h or any other generator.
+/// Anyone can recompute and verify the generator from the transcript (see: `Helper::verify_generator_derivation`).
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GeneratorDerivation {
+    /// the index i of the generator
+    pub index: usize,
+    /// the domain separation tag: "provotum/v1/generator/i"
+    pub domain: Vec<u8>,
+    /// the id the generators are specific to, e.g. the topic id
+    pub id: Vec<u8>,
+    /// the number of seeds which mapped to 0 or 1 and have been discarded (practically always 0)
+    pub counter: u32,
+    /// the expanded seed reduced modulo p: u
+    pub seed: BigUint,
+    /// the generator: u^2 mod p, i.e., a quadratic residue
+    pub generator: BigUint,
+}
+
 pub struct Helper;
 
 impl Helper {
@@ -137,54 +185,203 @@ impl Helper {
 
     /// GenShuffleProof Algorithm 8.3 (CHVoteSpec 3.1)
     ///
-    /// Computes n independent generators of G_q ∈ Z*_p.
-    /// The algorithm is an adaption of the NIST standard FIPS PUB 186-4 (Appendix A.2.3).
+    /// Computes n independent generators of G_q ∈ Z*_p (see: `Helper::derive_generator`).
     /// Making the generators dependent on election id guarantees that the resulting values are specific to the current election.
     pub fn get_generators(id: &[u8], p: &BigUint, number: usize) -> Vec<BigUint> {
-        let mut vec_h: Vec<BigUint> = Vec::new();
-        let zero = BigUint::zero();
-        let one = BigUint::one();
-        let two = BigUint::from(2u32);
+        Self::get_generator_derivations(id, p, number)
+            .into_iter()
+            .map(|derivation| derivation.generator)
+            .collect()
+    }
 
-        for i in 0..number {
-            // start
-            let mut x = zero.clone();
-            let mut h_i = zero.clone();
+    /// Computes the transcripts of the derivations of n independent generators of G_q ∈ Z*_p.
+    pub fn get_generator_derivations(
+        id: &[u8],
+        p: &BigUint,
+        number: usize,
+    ) -> Vec<GeneratorDerivation> {
+        (0..number)
+            .map(|index| Self::derive_generator(id, p, index))
+            .collect()
+    }
+
+    /// Returns the domain separation tag of the i-th generator: "provotum/v1/generator/i".
+    pub fn generator_domain(index: usize) -> Vec<u8> {
+        let mut domain = GENERATOR_DOMAIN.as_bytes().to_vec();
+        domain.extend_from_slice(index.to_string().as_bytes());
+        domain
+    }
 
-            while h_i == zero || h_i == one {
-                x += one.clone();
+    /// Derives the i-th independent generator of G_q ∈ Z*_p (hash-to-group).
+    ///
+    /// The seed, i.e. the id (length prefixed) and a counter, is expanded to bits(p) + 128 bits
+    /// with expand_message_xmd (RFC 9380, Section 5.3.1) using Blake2b and the domain string
+    /// of the generator. The expanded seed u is reduced modulo p and squared: h_i = u^2 mod p.
+    /// Seeds which map to 0 or 1 are discarded, the counter is incremented.
+    pub fn derive_generator(id: &[u8], p: &BigUint, index: usize) -> GeneratorDerivation {
+        let domain = Self::generator_domain(index);
+        let one = BigUint::one();
+        let mut counter: u32 = 0;
+        loop {
+            let (seed, generator) = Self::map_generator_seed(&domain, id, counter, p);
+            if !generator.is_zero() && generator != one {
+                return GeneratorDerivation {
+                    index,
+                    domain,
+                    id: id.to_vec(),
+                    counter,
+                    seed,
+                    generator,
+                };
+            }
+            counter += 1;
+        }
+    }
 
-                // hash all inputs and transform to a biguint
-                h_i = Self::hash_inputs_to_biguint(id, "ggen", i, x.clone());
-                h_i %= p;
-                h_i = h_i.modpow(&two, p);
+    /// Verifies the transcript of a generator derivation: the generator must be derived from the
+    /// id and the domain of its index, all seeds of a lower counter must have been discarded
+    /// and the generator must be a generator of G_q.
+    pub fn verify_generator_derivation(
+        id: &[u8],
+        p: &BigUint,
+        derivation: &GeneratorDerivation,
+    ) -> bool {
+        if derivation.id != id || derivation.domain != Self::generator_domain(derivation.index) {
+            return false;
+        }
+        // the counter can't be chosen, the first seed which doesn't map to 0 or 1 must be used
+        let one = BigUint::one();
+        for counter in 0..derivation.counter {
+            let (_, generator) = Self::map_generator_seed(&derivation.domain, id, counter, p);
+            if !generator.is_zero() && generator != one {
+                return false;
             }
-            vec_h.push(h_i);
         }
-        vec_h
+        let (seed, generator) =
+            Self::map_generator_seed(&derivation.domain, id, derivation.counter, p);
+        let q = (p - &one) / BigUint::from(2u32);
+        seed == derivation.seed
+            && generator == derivation.generator
+            && Self::is_generator(p, &q, &generator)
     }
 
-    /// Uses the Blak2 hash function and produces a hash of a BigUint. The result is returned as a Vec<u8>.
-    pub fn hash_biguint(input: &BigUint) -> Vec<u8> {
-        let mut hasher = Blake2b::new();
+    /// Verifies that the generators are the first n generators derived from the id,
+    /// e.g. generators which have been cached by a prover. The derivation costs a hash
+    /// and a squaring per generator, i.e., it's cheap compared to a proof.
+    pub fn verify_generators(id: &[u8], p: &BigUint, generators: &[BigUint]) -> bool {
+        generators
+            .iter()
+            .enumerate()
+            .all(|(index, generator)| Self::derive_generator(id, p, index).generator == *generator)
+    }
+
+    /// maps the seed (domain, id and counter) into G_q: returns u and u^2 mod p
+    fn map_generator_seed(
+        domain: &[u8],
+        id: &[u8],
+        counter: u32,
+        p: &BigUint,
+    ) -> (BigUint, BigUint) {
+        let mut message: Vec<u8> = Vec::new();
+        message.extend_from_slice(&(id.len() as u32).to_be_bytes());
+        message.extend_from_slice(id);
+        message.extend_from_slice(&counter.to_be_bytes());
+
+        let length = div_ceil((p.bits() + GENERATOR_EXTRA_BITS) as usize, 8);
+        let expanded = Self::expand_message_xmd(&message, domain, length);
+        let seed = BigUint::from_bytes_be(&expanded) % p;
+        let generator = seed.modpow(&BigUint::from(2u32), p);
+        (seed, generator)
+    }
+
+    /// expand_message_xmd of RFC 9380 (Section 5.3.1) with Blake2b as hash function.
+    /// the domain must be at most 255 bytes and the length at most 255 * 64 bytes.
+    pub(crate) fn expand_message_xmd(message: &[u8], domain: &[u8], length: usize) -> Vec<u8> {
+        let ell = div_ceil(length, BLAKE2B_OUTPUT_BYTES);
+        assert!(
+            ell <= 255 && domain.len() <= 255,
+            "invalid expand_message_xmd input!"
+        );
+
+        let mut domain_prime: Vec<u8> = domain.to_vec();
+        domain_prime.push(domain.len() as u8);
+
+        // b_0 = H(Z_pad || msg || l_i_b_str || I2OSP(0, 1) || DST_prime)
+        let b_0 = Blake2b::new()
+            .chain([0u8; BLAKE2B_BLOCK_BYTES])
+            .chain(message)
+            .chain((length as u16).to_be_bytes())
+            .chain([0u8])
+            .chain(&domain_prime)
+            .finalize();
+
+        // b_1 = H(b_0 || I2OSP(1, 1) || DST_prime), b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime)
+        let mut expanded: Vec<u8> = Vec::with_capacity(ell * BLAKE2B_OUTPUT_BYTES);
+        let mut b_i: Vec<u8> = vec![0u8; BLAKE2B_OUTPUT_BYTES];
+        for i in 1..=ell {
+            let input: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(x, y)| x ^ y).collect();
+            b_i = Blake2b::new()
+                .chain(&input)
+                .chain([i as u8])
+                .chain(&domain_prime)
+                .finalize()
+                .to_vec();
+            expanded.extend_from_slice(&b_i);
+        }
+        expanded.truncate(length);
+        expanded
+    }
+
+    /// Computes n small random exponents for the batch verification of n equations.
+    ///
+    /// The exponents are derived from the challenge of the proof, i.e., after the prover has committed to all values.
+    /// Each exponent has BATCH_EXPONENT_BITS bits: an invalid equation is only accepted with probability 2^-BATCH_EXPONENT_BITS.
+    pub fn get_batch_exponents(id: &[u8], challenge: &BigUint, number: usize) -> Vec<BigUint> {
+        Self::get_batch_exponents_of_size(id, challenge, number, BATCH_EXPONENT_BITS)
+    }
+
+    /// Computes n small random exponents of the given number of bits (1 - 512, the size of the hash).
+    /// The exponents are uniformly distributed in [1, 2^bits - 1]: an invalid equation is only accepted with probability 2^-bits.
+    pub fn get_batch_exponents_of_size(
+        id: &[u8],
+        challenge: &BigUint,
+        number: usize,
+        bits: usize,
+    ) -> Vec<BigUint> {
+        // 2^bits - 1 non-zero exponents, at least one (1) for a single bit
+        let range =
+            ((BigUint::one() << bits.clamp(1, MAX_BATCH_EXPONENT_BITS)) - 1u32).max(BigUint::one());
+        (0..number)
+            .map(|i| {
+                let mut r_i = Self::hash_inputs_to_biguint(id, "batch", i, challenge.clone());
+                r_i %= &range;
+                // the exponent must not be zero, otherwise the equation is not checked at all
+                r_i + 1u32
+            })
+            .collect()
+    }
+
+    /// Uses the hash function and produces a hash of a BigUint. The result is returned as a Vec<u8>.
+    pub fn hash_biguint(input: &BigUint, hash_function: HashFunction) -> Vec<u8> {
+        let mut hasher = Hasher::new(hash_function);
         let data = input.to_bytes_be();
         hasher.update(data);
-        hasher.finalize().to_vec()
+        hasher.finalize()
     }
 
-    /// Uses the Blak2 hash function and produces a hash of a vector of BigUints. The result is returned as a Vec<u8>.
-    pub fn hash_vec_biguints(inputs: Vec<BigUint>) -> Vec<u8> {
-        let mut hash = Blake2b::new();
+    /// Uses the hash function and produces a hash of a vector of BigUints. The result is returned as a Vec<u8>.
+    pub fn hash_vec_biguints(inputs: Vec<BigUint>, hash_function: HashFunction) -> Vec<u8> {
+        let mut hash = Hasher::new(hash_function);
 
         for entry in inputs.iter() {
             hash = hash.chain(entry.to_bytes_be());
         }
-        hash.finalize().to_vec()
+        hash.finalize()
     }
 
-    /// Uses the Blak2 hash function and produces a hash of a vector of BigUints. The result is returned as a Vec<u8>.
-    pub fn hash_vec_ciphers(inputs: Vec<Cipher>) -> Vec<u8> {
-        let mut hash = Blake2b::new();
+    /// Uses the hash function and produces a hash of a vector of BigUints. The result is returned as a Vec<u8>.
+    pub fn hash_vec_ciphers(inputs: Vec<Cipher>, hash_function: HashFunction) -> Vec<u8> {
+        let mut hash = Hasher::new(hash_function);
 
         for item in inputs.iter() {
             // transform both parts of Cipher (a,b) to a byte array
@@ -193,12 +390,46 @@ impl Helper {
             hash = hash.chain(item.b.to_bytes_be());
         }
 
-        hash.finalize().to_vec()
+        hash.finalize()
     }
 
-    /// Uses the Blak2 hash function and produces a hash of a vector of BigUints. The result is returned as a BigUint.
-    pub fn hash_vec_biguints_to_biguint(inputs: Vec<BigUint>) -> BigUint {
-        let mut hash = Blake2b::new();
+    /// Uses the hash function and produces a hash of group elements (see: `Group::element_to_bytes`).
+    /// In the integer group, the hash is equal to the hash of `hash_vec_biguints`.
+    pub fn hash_group_elements<G: Group>(
+        group: &G,
+        inputs: &[G::Element],
+        hash_function: HashFunction,
+    ) -> Vec<u8> {
+        let mut hash = Hasher::new(hash_function);
+
+        for entry in inputs.iter() {
+            hash = hash.chain(group.element_to_bytes(entry));
+        }
+        hash.finalize()
+    }
+
+    /// Uses the hash function and produces a hash of ciphers in an arbitrary group.
+    /// In the integer group, the hash is equal to the hash of `hash_vec_ciphers`.
+    pub fn hash_group_ciphers<G: Group>(
+        group: &G,
+        inputs: &[GroupCipher<G::Element>],
+        hash_function: HashFunction,
+    ) -> Vec<u8> {
+        let mut hash = Hasher::new(hash_function);
+
+        for item in inputs.iter() {
+            hash = hash.chain(group.element_to_bytes(&item.a));
+            hash = hash.chain(group.element_to_bytes(&item.b));
+        }
+        hash.finalize()
+    }
+
+    /// Uses the hash function and produces a hash of a vector of BigUints. The result is returned as a BigUint.
+    pub fn hash_vec_biguints_to_biguint(
+        inputs: Vec<BigUint>,
+        hash_function: HashFunction,
+    ) -> BigUint {
+        let mut hash = Hasher::new(hash_function);
 
         for entry in inputs.iter() {
             hash = hash.chain(entry.to_bytes_be());
@@ -207,9 +438,9 @@ impl Helper {
         BigUint::from_bytes_be(&digest)
     }
 
-    /// Uses the Blak2 hash function and produces a hash of a vector of usize. The result is returned as a BigUint.
-    pub fn hash_vec_usize_to_biguint(inputs: &[usize]) -> BigUint {
-        let mut hash = Blake2b::new();
+    /// Uses the hash function and produces a hash of a vector of usize. The result is returned as a BigUint.
+    pub fn hash_vec_usize_to_biguint(inputs: &[usize], hash_function: HashFunction) -> BigUint {
+        let mut hash = Hasher::new(hash_function);
 
         for entry in inputs.iter() {
             hash = hash.chain(entry.to_be_bytes());
@@ -223,8 +454,9 @@ impl Helper {
         constant: &str,
         h: &BigUint,
         b: &BigUint,
+        hash_function: HashFunction,
     ) -> BigUint {
-        let hasher = Blake2b::new();
+        let hasher = Hasher::new(hash_function);
         let hash = hasher
             .chain(id)
             .chain(constant.as_bytes())
@@ -234,6 +466,26 @@ impl Helper {
         BigUint::from_bytes_be(&hash)
     }
 
+    /// Computes the hash of the inputs of a Schnorr proof (see: `hash_key_gen_proof_inputs`)
+    /// in an arbitrary group.
+    pub fn hash_key_gen_proof_inputs_in_group<G: Group>(
+        group: &G,
+        id: &[u8],
+        constant: &str,
+        h: &G::Element,
+        b: &G::Element,
+        hash_function: HashFunction,
+    ) -> BigUint {
+        let hasher = Hasher::new(hash_function);
+        let hash = hasher
+            .chain(id)
+            .chain(constant.as_bytes())
+            .chain(group.element_to_bytes(h))
+            .chain(group.element_to_bytes(b))
+            .finalize();
+        BigUint::from_bytes_be(&hash)
+    }
+
     pub fn hash_decryption_proof_inputs(
         id: &[u8],
         constant: &str,
@@ -241,20 +493,82 @@ impl Helper {
         vec_e: Vec<Cipher>,
         vec_c: Vec<BigUint>,
         vec_t: Vec<BigUint>,
+        hash_function: HashFunction,
     ) -> BigUint {
-        let hasher = Blake2b::new();
+        let hasher = Hasher::new(hash_function);
         let mut hash = hasher
             .chain(id)
             .chain(constant.as_bytes())
             .chain(h.to_bytes_be());
 
-        let hash_e = Helper::hash_vec_ciphers(vec_e);
+        let hash_e = Helper::hash_vec_ciphers(vec_e, hash_function);
         hash = hash.chain(hash_e);
 
-        let hash_c = Helper::hash_vec_biguints(vec_c);
+        let hash_c = Helper::hash_vec_biguints(vec_c, hash_function);
         hash = hash.chain(hash_c);
 
-        let hash_vec_t = Helper::hash_vec_biguints(vec_t);
+        let hash_vec_t = Helper::hash_vec_biguints(vec_t, hash_function);
+        hash = hash.chain(hash_vec_t);
+
+        // final byte array of all chained hashes + transform back to BigUint
+        let digest = hash.finalize();
+        BigUint::from_bytes_be(&digest)
+    }
+
+    /// Computes the hash of the inputs of a decryption proof (see: `hash_decryption_proof_inputs`)
+    /// in an arbitrary group.
+    #[allow(clippy::too_many_arguments)]
+    pub fn hash_decryption_proof_inputs_in_group<G: Group>(
+        group: &G,
+        id: &[u8],
+        constant: &str,
+        h: &G::Element,
+        vec_e: &[GroupCipher<G::Element>],
+        vec_c: &[G::Element],
+        vec_t: &[G::Element],
+        hash_function: HashFunction,
+    ) -> BigUint {
+        let hasher = Hasher::new(hash_function);
+        let hash = hasher
+            .chain(id)
+            .chain(constant.as_bytes())
+            .chain(group.element_to_bytes(h))
+            .chain(Helper::hash_group_ciphers(group, vec_e, hash_function))
+            .chain(Helper::hash_group_elements(group, vec_c, hash_function))
+            .chain(Helper::hash_group_elements(group, vec_t, hash_function));
+
+        // final byte array of all chained hashes + transform back to BigUint
+        let digest = hash.finalize();
+        BigUint::from_bytes_be(&digest)
+    }
+
+    /// Computes the hash of all inputs.
+    /// Used in the key switch proof
+    #[allow(clippy::too_many_arguments)]
+    pub fn hash_key_switch_proof_inputs(
+        id: &[u8],
+        constant: &str,
+        h: &BigUint,
+        new_h: &BigUint,
+        vec_e: Vec<Cipher>,
+        vec_shares: Vec<Cipher>,
+        vec_t: Vec<BigUint>,
+        hash_function: HashFunction,
+    ) -> BigUint {
+        let hasher = Hasher::new(hash_function);
+        let mut hash = hasher
+            .chain(id)
+            .chain(constant.as_bytes())
+            .chain(h.to_bytes_be())
+            .chain(new_h.to_bytes_be());
+
+        let hash_e = Helper::hash_vec_ciphers(vec_e, hash_function);
+        hash = hash.chain(hash_e);
+
+        let hash_shares = Helper::hash_vec_ciphers(vec_shares, hash_function);
+        hash = hash.chain(hash_shares);
+
+        let hash_vec_t = Helper::hash_vec_biguints(vec_t, hash_function);
         hash = hash.chain(hash_vec_t);
 
         // final byte array of all chained hashes + transform back to BigUint
@@ -262,6 +576,55 @@ impl Helper {
         BigUint::from_bytes_be(&digest)
     }
 
+    /// Computes the hash of all inputs.
+    /// Used in the plaintext equivalence test proof
+    pub fn hash_pet_proof_inputs(
+        id: &[u8],
+        constant: &str,
+        quotient: &Cipher,
+        blinded: &Cipher,
+        t_a: &BigUint,
+        t_b: &BigUint,
+    ) -> BigUint {
+        let hasher = Blake2b::new();
+        let hash = hasher
+            .chain(id)
+            .chain(constant.as_bytes())
+            .chain(quotient.a.to_bytes_be())
+            .chain(quotient.b.to_bytes_be())
+            .chain(blinded.a.to_bytes_be())
+            .chain(blinded.b.to_bytes_be())
+            .chain(t_a.to_bytes_be())
+            .chain(t_b.to_bytes_be())
+            .finalize();
+        BigUint::from_bytes_be(&hash)
+    }
+
+    /// Computes the hash of the inputs of a plaintext equivalence test proof
+    /// (see: `hash_pet_proof_inputs`) in an arbitrary group.
+    pub fn hash_pet_proof_inputs_in_group<G: Group>(
+        group: &G,
+        id: &[u8],
+        constant: &str,
+        quotient: &GroupCipher<G::Element>,
+        blinded: &GroupCipher<G::Element>,
+        t_a: &G::Element,
+        t_b: &G::Element,
+    ) -> BigUint {
+        let hasher = Blake2b::new();
+        let hash = hasher
+            .chain(id)
+            .chain(constant.as_bytes())
+            .chain(group.element_to_bytes(&quotient.a))
+            .chain(group.element_to_bytes(&quotient.b))
+            .chain(group.element_to_bytes(&blinded.a))
+            .chain(group.element_to_bytes(&blinded.b))
+            .chain(group.element_to_bytes(t_a))
+            .chain(group.element_to_bytes(t_b))
+            .finalize();
+        BigUint::from_bytes_be(&hash)
+    }
+
     /// Computes the hash of all inputs.
     /// Used in the multiplicative homomorphic re-encryption proof
     pub fn hash_re_encryption_proof_inputs(
@@ -294,22 +657,24 @@ impl Helper {
         shuffled_encryptions: Vec<Cipher>,
         commitments: Vec<BigUint>,
         pk: &PublicKey,
+        hash_function: HashFunction,
     ) -> BigUint {
         // hash all inputs into a single BigUint
-        let mut hash = Blake2b::new();
+        let mut hash = Hasher::new(hash_function);
 
         // hash public value
-        let hash_encryptions = Helper::hash_vec_ciphers(encryptions);
+        let hash_encryptions = Helper::hash_vec_ciphers(encryptions, hash_function);
         hash = hash.chain(hash_encryptions);
 
-        let hash_shuffled_encryptions = Helper::hash_vec_ciphers(shuffled_encryptions);
+        let hash_shuffled_encryptions =
+            Helper::hash_vec_ciphers(shuffled_encryptions, hash_function);
         hash = hash.chain(hash_shuffled_encryptions);
 
-        let hash_commitments = Helper::hash_vec_biguints(commitments);
+        let hash_commitments = Helper::hash_vec_biguints(commitments, hash_function);
         hash = hash.chain(hash_commitments);
 
         // transform the public key: h (BigUint) to byte array + hash it
-        let hash_pk = Helper::hash_biguint(&pk.h);
+        let hash_pk = Helper::hash_biguint(&pk.h, hash_function);
         hash = hash.chain(hash_pk);
 
         // final byte array of all chained hashes + transform back to BigUint
@@ -322,37 +687,113 @@ impl Helper {
     /// Inputs:
     /// - public_value: Y
     /// - public_commitment: T
-    pub fn hash_challenge_inputs(public_value: BigY, public_commitment: BigT) -> BigUint {
+    pub fn hash_challenge_inputs(
+        public_value: BigY,
+        public_commitment: BigT,
+        hash_function: HashFunction,
+    ) -> BigUint {
         let (e, e_tilde, vec_c, vec_c_hat, public_key) = public_value;
         let (t1, t2, t3, t4_1, t4_2, vec_t_hat) = public_commitment;
 
         // hash all inputs into a single BigUint
-        let mut hash = Blake2b::new();
+        let mut hash = Hasher::new(hash_function);
 
         // hash public value
-        let hash_e = Helper::hash_vec_ciphers(e);
+        let hash_e = Helper::hash_vec_ciphers(e, hash_function);
         hash = hash.chain(hash_e);
 
-        let hash_e_tilde = Helper::hash_vec_ciphers(e_tilde);
+        let hash_e_tilde = Helper::hash_vec_ciphers(e_tilde, hash_function);
         hash = hash.chain(hash_e_tilde);
 
-        let hash_vec_c = Helper::hash_vec_biguints(vec_c);
+        let hash_vec_c = Helper::hash_vec_biguints(vec_c, hash_function);
         hash = hash.chain(hash_vec_c);
 
-        let hash_vec_c_hat = Helper::hash_vec_biguints(vec_c_hat);
+        let hash_vec_c_hat = Helper::hash_vec_biguints(vec_c_hat, hash_function);
         hash = hash.chain(hash_vec_c_hat);
 
-        let hash_pk = Helper::hash_biguint(public_key);
+        let hash_pk = Helper::hash_biguint(public_key, hash_function);
         hash = hash.chain(hash_pk);
 
         // hash public commitments
         let t_values = [t1, t2, t3, t4_1, t4_2];
-        let hash_t_values = Helper::hash_vec_biguints(t_values.to_vec());
+        let hash_t_values = Helper::hash_vec_biguints(t_values.to_vec(), hash_function);
         hash = hash.chain(hash_t_values);
 
-        let hash_vec_t_hat = Helper::hash_vec_biguints(vec_t_hat);
+        let hash_vec_t_hat = Helper::hash_vec_biguints(vec_t_hat, hash_function);
         hash = hash.chain(hash_vec_t_hat);
 
+        // final byte array of all chained hashes + transform back to BigUint
+        let digest = hash.finalize();
+        BigUint::from_bytes_be(&digest)
+    }
+    /// Computes the hash of the inputs of the challenges of a shuffle proof
+    /// (see: `hash_challenges_inputs`) in an arbitrary group.
+    pub fn hash_challenges_inputs_in_group<G: Group>(
+        group: &G,
+        encryptions: &[GroupCipher<G::Element>],
+        shuffled_encryptions: &[GroupCipher<G::Element>],
+        commitments: &[G::Element],
+        pk: &G::Element,
+        hash_function: HashFunction,
+    ) -> BigUint {
+        let hash = Hasher::new(hash_function)
+            .chain(Helper::hash_group_ciphers(
+                group,
+                encryptions,
+                hash_function,
+            ))
+            .chain(Helper::hash_group_ciphers(
+                group,
+                shuffled_encryptions,
+                hash_function,
+            ))
+            .chain(Helper::hash_group_elements(
+                group,
+                commitments,
+                hash_function,
+            ))
+            .chain(Helper::hash_group_elements(
+                group,
+                core::slice::from_ref(pk),
+                hash_function,
+            ));
+
+        // final byte array of all chained hashes + transform back to BigUint
+        let digest = hash.finalize();
+        BigUint::from_bytes_be(&digest)
+    }
+
+    /// Computes the hash of the public value y and the public commitment t of a shuffle proof
+    /// (see: `hash_challenge_inputs`) in an arbitrary group.
+    pub fn hash_challenge_inputs_in_group<G: Group>(
+        group: &G,
+        public_value: GroupY<G::Element>,
+        public_commitment: &GroupT<G::Element>,
+        hash_function: HashFunction,
+    ) -> BigUint {
+        let (e, e_tilde, vec_c, vec_c_hat, public_key) = public_value;
+        let (t1, t2, t3, t4_1, t4_2, vec_t_hat) = public_commitment;
+        let t_values = [
+            t1.clone(),
+            t2.clone(),
+            t3.clone(),
+            t4_1.clone(),
+            t4_2.clone(),
+        ];
+
+        let hash = Hasher::new(hash_function)
+            .chain(Helper::hash_group_ciphers(group, e, hash_function))
+            .chain(Helper::hash_group_ciphers(group, e_tilde, hash_function))
+            .chain(Helper::hash_group_elements(group, vec_c, hash_function))
+            .chain(Helper::hash_group_elements(group, vec_c_hat, hash_function))
+            .chain(Helper::hash_group_elements(
+                group,
+                core::slice::from_ref(public_key),
+                hash_function,
+            ))
+            .chain(Helper::hash_group_elements(group, &t_values, hash_function))
+            .chain(Helper::hash_group_elements(group, vec_t_hat, hash_function));
+
         // final byte array of all chained hashes + transform back to BigUint
         let digest = hash.finalize();
         BigUint::from_bytes_be(&digest)
@@ -361,13 +802,14 @@ impl Helper {
 
 #[cfg(test)]
 mod tests {
-    use super::Helper;
+    use super::{Helper, BATCH_EXPONENT_BITS, GENERATOR_DOMAIN, MAX_BATCH_EXPONENT_BITS};
     use crate::{
+        hash::HashFunction,
         random::Random,
         types::{Cipher, ElGamalParams},
     };
     use num_bigint::BigUint;
-    use num_traits::One;
+    use num_traits::{One, Zero};
 
     #[test]
     fn it_should_create_sm_system() {
@@ -557,11 +999,11 @@ mod tests {
             253, 131, 143, 176, 72, 210, 22, 83,
         ];
         let input = BigUint::one();
-        let hash = Helper::hash_biguint(&input);
+        let hash = Helper::hash_biguint(&input, HashFunction::Blake2b);
         assert_eq!(expected_result, hash);
 
         let input2 = BigUint::from(2u32);
-        let hash2 = Helper::hash_biguint(&input2);
+        let hash2 = Helper::hash_biguint(&input2, HashFunction::Blake2b);
         assert_ne!(hash, hash2);
     }
 
@@ -574,7 +1016,7 @@ mod tests {
             253, 131, 143, 176, 72, 210, 22, 83,
         ];
         let input = [BigUint::one()];
-        let hash = Helper::hash_vec_biguints(input.to_vec());
+        let hash = Helper::hash_vec_biguints(input.to_vec(), HashFunction::Blake2b);
         assert_eq!(expected_result, hash);
     }
 
@@ -590,10 +1032,44 @@ mod tests {
             a: BigUint::from(3u32),
             b: BigUint::from(7u32),
         }];
-        let hash = Helper::hash_vec_ciphers(input.to_vec());
+        let hash = Helper::hash_vec_ciphers(input.to_vec(), HashFunction::Blake2b);
         assert_eq!(expected_result, hash);
     }
 
+    #[test]
+    fn it_should_get_batch_exponents() {
+        let id = "2020-12-12_01".as_bytes();
+        let challenge = BigUint::from(4711u32);
+        let exponents = Helper::get_batch_exponents(&id, &challenge, 10);
+        assert_eq!(exponents.len(), 10);
+        assert!(exponents
+            .iter()
+            .all(|r| !r.is_zero() && r.bits() <= BATCH_EXPONENT_BITS as u64));
+
+        // the exponents are deterministic but depend on the challenge
+        assert_eq!(exponents, Helper::get_batch_exponents(&id, &challenge, 10));
+        let other = Helper::get_batch_exponents(&id, &BigUint::from(4712u32), 10);
+        assert_ne!(exponents, other);
+    }
+
+    #[test]
+    fn it_should_get_batch_exponents_of_any_size() {
+        let id = "2020-12-12_01".as_bytes();
+        let challenge = BigUint::from(4711u32);
+        for bits in [1, 16, 128, MAX_BATCH_EXPONENT_BITS].iter() {
+            let exponents = Helper::get_batch_exponents_of_size(&id, &challenge, 10, *bits);
+            assert!(exponents
+                .iter()
+                .all(|r| !r.is_zero() && r.bits() <= *bits as u64));
+        }
+
+        // the default size is used for the batch verification of the shuffle proof
+        assert_eq!(
+            Helper::get_batch_exponents_of_size(&id, &challenge, 10, BATCH_EXPONENT_BITS),
+            Helper::get_batch_exponents(&id, &challenge, 10)
+        );
+    }
+
     #[test]
     fn it_should_get_generators() {
         let one = BigUint::one();
@@ -609,17 +1085,94 @@ mod tests {
             .all(|gen| Helper::is_generator(&params.p, &params.q(), gen)));
     }
 
+    #[test]
+    fn it_should_derive_generators_from_their_domain() {
+        let id = "2020-12-12_01".as_bytes();
+        let (params, _, _) = Helper::setup_md_system();
+        let p = &params.p;
+
+        let derivations = Helper::get_generator_derivations(&id, p, 3);
+        assert_eq!(derivations[2].domain, b"provotum/v1/generator/2".to_vec());
+        assert!(derivations[2]
+            .domain
+            .starts_with(GENERATOR_DOMAIN.as_bytes()));
+        assert!(derivations
+            .iter()
+            .all(|derivation| Helper::verify_generator_derivation(&id, p, derivation)));
+
+        // the generators are deterministic and specific to the id and the index
+        assert_eq!(Helper::derive_generator(&id, p, 2), derivations[2]);
+        assert_ne!(derivations[0].generator, derivations[1].generator);
+        let other = Helper::derive_generator(b"2020-12-12_02", p, 0);
+        assert_ne!(other.generator, derivations[0].generator);
+        assert!(!Helper::verify_generator_derivation(&id, p, &other));
+    }
+
+    #[test]
+    fn it_should_not_verify_a_modified_generator_derivation() {
+        let id = "2020-12-12_01".as_bytes();
+        let (params, _, _) = Helper::setup_md_system();
+        let p = &params.p;
+        let derivation = Helper::derive_generator(&id, p, 0);
+
+        let mut modified = derivation.clone();
+        modified.generator = params.h.clone();
+        assert!(!Helper::verify_generator_derivation(&id, p, &modified));
+
+        // the counter can't be chosen by the prover
+        let mut modified = derivation.clone();
+        modified.counter += 1;
+        assert!(!Helper::verify_generator_derivation(&id, p, &modified));
+
+        // the generator of another index
+        let mut modified = derivation;
+        modified.index = 1;
+        assert!(!Helper::verify_generator_derivation(&id, p, &modified));
+    }
+
+    #[test]
+    fn it_should_verify_generators() {
+        let id = "2020-12-12_01".as_bytes();
+        let (params, _, _) = Helper::setup_sm_system();
+        let p = &params.p;
+
+        let generators = Helper::get_generators(&id, p, 5);
+        assert!(Helper::verify_generators(&id, p, &generators));
+
+        // the generators must be in the order of their derivation
+        let mut reversed = generators.clone();
+        reversed.reverse();
+        assert!(!Helper::verify_generators(&id, p, &reversed));
+
+        // the generators of another id
+        assert!(!Helper::verify_generators(b"2020-12-12_02", p, &generators));
+    }
+
     #[test]
     fn it_should_hash_vec_biguints_to_biguint() {
         let one = BigUint::one();
-        let hash1 = Helper::hash_vec_biguints_to_biguint([one.clone()].to_vec());
+        let hash1 =
+            Helper::hash_vec_biguints_to_biguint([one.clone()].to_vec(), HashFunction::Blake2b);
 
         let two = BigUint::from(2u32);
-        let hash2 = Helper::hash_vec_biguints_to_biguint([two.clone()].to_vec());
+        let hash2 =
+            Helper::hash_vec_biguints_to_biguint([two.clone()].to_vec(), HashFunction::Blake2b);
         assert_ne!(hash1, hash2);
 
-        let combined = Helper::hash_vec_biguints_to_biguint([one, two].to_vec());
+        let combined =
+            Helper::hash_vec_biguints_to_biguint([one, two].to_vec(), HashFunction::Blake2b);
         assert_ne!(combined, hash1);
         assert_ne!(combined, hash2);
     }
+
+    #[test]
+    fn it_should_hash_with_the_hash_function() {
+        let inputs = [BigUint::one(), BigUint::from(2u32)].to_vec();
+        let blake2b = Helper::hash_vec_biguints_to_biguint(inputs.clone(), HashFunction::Blake2b);
+        let sha256 = Helper::hash_vec_biguints_to_biguint(inputs.clone(), HashFunction::Sha256);
+        let sha3 = Helper::hash_vec_biguints_to_biguint(inputs, HashFunction::Sha3_256);
+        assert_ne!(blake2b, sha256);
+        assert_ne!(sha256, sha3);
+        assert!(sha256.bits() <= 256 && sha3.bits() <= 256);
+    }
 }
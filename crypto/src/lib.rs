@@ -33,3 +33,27 @@ pub mod types;
 #[allow(clippy::many_single_char_names)]
 #[macro_use]
 pub mod proofs;
+
+#[allow(clippy::many_single_char_names)]
+#[macro_use]
+pub mod secret_sharing;
+
+#[allow(clippy::many_single_char_names)]
+#[macro_use]
+pub mod derivation;
+
+pub mod secret;
+
+pub mod hash;
+
+pub mod blind;
+
+#[cfg(feature = "std")]
+pub mod test_vectors;
+
+#[cfg(any(feature = "wasm-opt", test))]
+pub mod montgomery;
+
+// property-based tests, proptest requires std
+#[cfg(all(feature = "std", test))]
+mod proptests;
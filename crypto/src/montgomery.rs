@@ -0,0 +1,307 @@
+//! Modular exponentiation in Montgomery form on 32 bit limbs.
+//!
+//! num-bigint multiplies 64 bit digits, which wasm32 has to emulate (there is no
+//! 64 x 64 -> 128 bit multiplication). This module works on 32 bit limbs with 64 bit
+//! intermediate products, multiplies large operands with Karatsuba and reduces the
+//! products with the Montgomery reduction (REDC), i.e., without any division.
+//! Only enabled with the `wasm-opt` feature, see: `modpow`.
+use crate::types::div_ceil;
+use alloc::{vec, vec::Vec};
+use num_bigint::BigUint;
+use num_traits::One;
+
+/// Operands with fewer limbs are multiplied with the schoolbook method.
+pub const KARATSUBA_THRESHOLD: usize = 32;
+
+/// The number of exponent bits handled per step of `Montgomery::pow`.
+pub const MONTGOMERY_WINDOW: u64 = 4;
+
+/// The Montgomery context of an odd modulus m with R = 2^(32 * n), n = # of limbs of m.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Montgomery {
+    modulus: Vec<u32>,
+    // -m^(-1) mod 2^32
+    m_prime: u32,
+    // R^2 mod m, converts a number into Montgomery form
+    r_squared: Vec<u32>,
+}
+
+impl Montgomery {
+    /// Creates the Montgomery context of the modulus, None if the modulus is even or one.
+    pub fn new(modulus: &BigUint) -> Option<Self> {
+        if !modulus.bit(0) || modulus.is_one() {
+            return None;
+        }
+        let limbs = modulus.to_u32_digits();
+        let n = limbs.len();
+
+        // Newton iteration: every step doubles the number of correct bits of the inverse
+        let m0 = limbs[0];
+        let mut inverse: u32 = 1;
+        for _ in 0..5 {
+            inverse = inverse.wrapping_mul(2u32.wrapping_sub(m0.wrapping_mul(inverse)));
+        }
+
+        let r_squared = (BigUint::one() << (64 * n)) % modulus;
+        Some(Montgomery {
+            m_prime: inverse.wrapping_neg(),
+            r_squared: pad(r_squared.to_u32_digits(), n),
+            modulus: limbs,
+        })
+    }
+
+    /// Calculates base^exponent % modulus (fixed window exponentiation).
+    pub fn pow(&self, base: &BigUint, exponent: &BigUint) -> BigUint {
+        let n = self.modulus.len();
+        let base = pad((base % self.modulus()).to_u32_digits(), n);
+
+        // table[j] = base^j in Montgomery form for j ∈ [0, 2^w)
+        let one = self.to_montgomery(&pad(vec![1], n));
+        let base = self.to_montgomery(&base);
+        let entries = 1usize << MONTGOMERY_WINDOW;
+        let mut table: Vec<Vec<u32>> = Vec::with_capacity(entries);
+        table.push(one.clone());
+        for j in 1..entries {
+            let next = self.mul(&table[j - 1], &base);
+            table.push(next);
+        }
+
+        // process the windows from the most to the least significant one
+        let window = MONTGOMERY_WINDOW;
+        let nr_of_windows = div_ceil(exponent.bits(), window);
+        let mut result = one;
+        for i in (0..nr_of_windows).rev() {
+            for _ in 0..window {
+                result = self.mul(&result, &result);
+            }
+            let offset = i * window;
+            let digit = (0..window).fold(0usize, |digit, k| {
+                digit | ((exponent.bit(offset + k) as usize) << k)
+            });
+            if digit != 0 {
+                result = self.mul(&result, &table[digit]);
+            }
+        }
+        BigUint::new(self.redc(result))
+    }
+
+    fn modulus(&self) -> BigUint {
+        BigUint::new(self.modulus.clone())
+    }
+
+    /// converts a (reduced) number into Montgomery form: x * R mod m
+    fn to_montgomery(&self, x: &[u32]) -> Vec<u32> {
+        self.mul(x, &self.r_squared)
+    }
+
+    /// the Montgomery product: a * b * R^(-1) mod m
+    fn mul(&self, a: &[u32], b: &[u32]) -> Vec<u32> {
+        self.redc(multiply(a, b))
+    }
+
+    /// the Montgomery reduction: t * R^(-1) mod m for t < m * R
+    fn redc(&self, mut t: Vec<u32>) -> Vec<u32> {
+        let m = &self.modulus;
+        let n = m.len();
+        t.resize(2 * n + 1, 0);
+        for i in 0..n {
+            // adding u * m makes the i-th limb zero
+            let u = t[i].wrapping_mul(self.m_prime) as u64;
+            let mut carry: u64 = 0;
+            for j in 0..n {
+                let sum = t[i + j] as u64 + u * m[j] as u64 + carry;
+                t[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            let mut k = i + n;
+            while carry != 0 {
+                let sum = t[k] as u64 + carry;
+                t[k] = sum as u32;
+                carry = sum >> 32;
+                k += 1;
+            }
+        }
+
+        // the result t / R < 2m
+        let mut result = t.split_off(n);
+        if compare(&result, m) != core::cmp::Ordering::Less {
+            sub_assign(&mut result, m);
+        }
+        result.truncate(n);
+        result
+    }
+}
+
+/// Calculates base^exponent % modulus in Montgomery form if the modulus is odd.
+/// Falls back to `BigUint::modpow` otherwise.
+pub fn modpow(base: &BigUint, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+    match Montgomery::new(modulus) {
+        Some(montgomery) => montgomery.pow(base, exponent),
+        None => base.modpow(exponent, modulus),
+    }
+}
+
+/// pads the limbs with zeros to the given length
+fn pad(mut limbs: Vec<u32>, length: usize) -> Vec<u32> {
+    limbs.resize(length.max(limbs.len()), 0);
+    limbs
+}
+
+/// multiplies two numbers, the product has a.len() + b.len() limbs
+fn multiply(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.len() < KARATSUBA_THRESHOLD || b.len() < KARATSUBA_THRESHOLD {
+        return schoolbook(a, b);
+    }
+
+    // a = a1 * B^half + a0, b = b1 * B^half + b0
+    let half = a.len().max(b.len()) / 2;
+    let (a0, a1) = a.split_at(half.min(a.len()));
+    let (b0, b1) = b.split_at(half.min(b.len()));
+
+    // a * b = z2 * B^(2 * half) + z1 * B^half + z0
+    // with z1 = (a0 + a1) * (b0 + b1) - z0 - z2
+    let z0 = multiply(a0, b0);
+    let z2 = multiply(a1, b1);
+    let mut z1 = multiply(&add(a0, a1), &add(b0, b1));
+    sub_assign(&mut z1, &z0);
+    sub_assign(&mut z1, &z2);
+
+    // z1 has up to two more limbs than the halves, the space is truncated once added
+    let mut product = vec![0u32; 2 * (a.len().max(b.len()) + 2)];
+    add_assign_at(&mut product, &z0, 0);
+    add_assign_at(&mut product, &z1, half);
+    add_assign_at(&mut product, &z2, 2 * half);
+    product.truncate(a.len() + b.len());
+    product
+}
+
+fn schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0u32; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry: u64 = 0;
+        for (j, y) in b.iter().enumerate() {
+            let sum = product[i + j] as u64 + *x as u64 * *y as u64 + carry;
+            product[i + j] = sum as u32;
+            carry = sum >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    product
+}
+
+fn add(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = vec![0u32; a.len().max(b.len()) + 1];
+    add_assign_at(&mut sum, a, 0);
+    add_assign_at(&mut sum, b, 0);
+    sum
+}
+
+/// adds x * B^offset to the number, the number must be large enough to hold the sum
+fn add_assign_at(number: &mut [u32], x: &[u32], offset: usize) {
+    let mut carry: u64 = 0;
+    let mut k = offset;
+    for limb in x.iter() {
+        let sum = number[k] as u64 + *limb as u64 + carry;
+        number[k] = sum as u32;
+        carry = sum >> 32;
+        k += 1;
+    }
+    while carry != 0 {
+        let sum = number[k] as u64 + carry;
+        number[k] = sum as u32;
+        carry = sum >> 32;
+        k += 1;
+    }
+}
+
+/// subtracts x from the number, the number must be at least as large as x
+fn sub_assign(number: &mut [u32], x: &[u32]) {
+    let mut borrow: i64 = 0;
+    for (k, limb) in number.iter_mut().enumerate() {
+        let subtrahend = x.get(k).copied().unwrap_or(0);
+        if k >= x.len() && borrow == 0 {
+            break;
+        }
+        let difference = *limb as i64 - subtrahend as i64 - borrow;
+        *limb = difference as u32;
+        borrow = (difference < 0) as i64;
+    }
+}
+
+/// compares two numbers of (possibly) different lengths
+fn compare(a: &[u32], b: &[u32]) -> core::cmp::Ordering {
+    let length = a.len().max(b.len());
+    for k in (0..length).rev() {
+        let x = a.get(k).copied().unwrap_or(0);
+        let y = b.get(k).copied().unwrap_or(0);
+        if x != y {
+            return x.cmp(&y);
+        }
+    }
+    core::cmp::Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{modpow, multiply, Montgomery, KARATSUBA_THRESHOLD};
+    use crate::helper::Helper;
+    use num_bigint::BigUint;
+    use num_traits::{One, Zero};
+
+    #[test]
+    fn it_should_multiply_with_karatsuba() {
+        let a = BigUint::parse_bytes(b"F123456789ABCDEF", 16)
+            .unwrap()
+            .pow(40);
+        let b = BigUint::parse_bytes(b"FEDCBA9876543210", 16)
+            .unwrap()
+            .pow(37);
+        assert!(a.to_u32_digits().len() >= KARATSUBA_THRESHOLD);
+        assert!(b.to_u32_digits().len() >= KARATSUBA_THRESHOLD);
+
+        let product = multiply(&a.to_u32_digits(), &b.to_u32_digits());
+        assert_eq!(BigUint::new(product), &a * &b);
+
+        // operands of different lengths
+        let c = a.pow(4);
+        let product = multiply(&c.to_u32_digits(), &b.to_u32_digits());
+        assert_eq!(BigUint::new(product), &c * &b);
+    }
+
+    #[test]
+    fn it_should_compute_the_same_modpow_as_num_bigint() {
+        let (params, _, pk) = Helper::setup_lg_system();
+        let p = &params.p;
+        let r = BigUint::parse_bytes(b"170141183460469231731687303715884105727", 10).unwrap();
+        assert_eq!(modpow(&params.g, &r, p), params.g.modpow(&r, p));
+        assert_eq!(modpow(&pk.h, &r, p), pk.h.modpow(&r, p));
+
+        // exponents of the size of the group order
+        let q = params.q();
+        let x = &q - BigUint::one();
+        assert_eq!(modpow(&pk.h, &x, p), pk.h.modpow(&x, p));
+    }
+
+    #[test]
+    fn it_should_handle_edge_cases() {
+        let p = BigUint::from(23u32);
+        let base = BigUint::from(5u32);
+        assert_eq!(modpow(&base, &BigUint::zero(), &p), BigUint::one());
+        assert_eq!(
+            modpow(&BigUint::zero(), &BigUint::from(3u32), &p),
+            BigUint::zero()
+        );
+        assert_eq!(
+            modpow(&BigUint::from(28u32), &BigUint::from(2u32), &p),
+            base.pow(2) % &p
+        );
+
+        // even moduli and one are computed by num-bigint
+        assert!(Montgomery::new(&BigUint::from(24u32)).is_none());
+        assert!(Montgomery::new(&BigUint::one()).is_none());
+        assert_eq!(
+            modpow(&base, &BigUint::from(3u32), &BigUint::from(24u32)),
+            BigUint::from(5u32)
+        );
+    }
+}
@@ -1,13 +1,25 @@
 use crate::{
-    helper::Helper,
-    types::{Cipher, ElGamalParams, ModuloOperations},
+    hash::HashFunction,
+    helper::{Helper, BATCH_EXPONENT_BITS},
+    proofs::{GroupProof, ProofFailure},
+    types::{Cipher, ElGamalParams, Group, GroupCipher, ModuloOperations},
 };
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use num_bigint::BigUint;
+use num_traits::One;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// The default soundness (in bits) of the batched decryption proofs.
+pub const DEFAULT_BATCH_SOUNDNESS: usize = BATCH_EXPONENT_BITS;
 
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct DecryptionProof {
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
     pub challenge: BigUint,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
     pub response: BigUint,
 }
 
@@ -23,6 +35,21 @@ impl DecryptionProof {
         vec_e: Vec<Cipher>,
         vec_c: Vec<BigUint>,
         id: &[u8],
+    ) -> DecryptionProof {
+        Self::generate_with_hash(params, sk, pk, r, vec_e, vec_c, id, HashFunction::Blake2b)
+    }
+
+    /// Generates the proof, the challenge is computed with the hash function (see: `generate`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_with_hash(
+        params: &ElGamalParams,
+        sk: &BigUint,
+        pk: &BigUint,
+        r: &BigUint,
+        vec_e: Vec<Cipher>,
+        vec_c: Vec<BigUint>,
+        id: &[u8],
+        hash: HashFunction,
     ) -> DecryptionProof {
         assert!(
             vec_e.len() == vec_c.len(),
@@ -49,7 +76,8 @@ impl DecryptionProof {
 
         // compute challenge
         // hash public values (hash(unique_id, constant, pk, vec_e, vec_c, vec_t) mod q)
-        let mut c = Helper::hash_decryption_proof_inputs(id, "decryption", pk, vec_e, vec_c, vec_t);
+        let mut c =
+            Helper::hash_decryption_proof_inputs(id, "decryption", pk, vec_e, vec_c, vec_t, hash);
         c %= q;
 
         // compute the response: d = r - c * sk mod q
@@ -71,6 +99,19 @@ impl DecryptionProof {
         vec_e: Vec<Cipher>,
         vec_c: Vec<BigUint>,
         id: &[u8],
+    ) -> bool {
+        Self::verify_with_hash(params, pk, proof, vec_e, vec_c, id, HashFunction::Blake2b)
+    }
+
+    /// Verifies the proof, the challenge is recomputed with the hash function (see: `verify`).
+    pub fn verify_with_hash(
+        params: &ElGamalParams,
+        pk: &BigUint,
+        proof: &DecryptionProof,
+        vec_e: Vec<Cipher>,
+        vec_c: Vec<BigUint>,
+        id: &[u8],
+        hash: HashFunction,
     ) -> bool {
         assert!(
             vec_e.len() == vec_c.len(),
@@ -117,22 +158,315 @@ impl DecryptionProof {
             vec_e,
             vec_c,
             recompute_vec_t,
+            hash,
         );
         recomputed_c %= q;
 
         // verify that the challenges are the same
         &recomputed_c == c
     }
+
+    /// Generates the decryption proof in an arbitrary group (see: `generate_with_hash`),
+    /// the partial decryptions are c_i = a_i^sk.
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_in_group<G: Group>(
+        group: &G,
+        sk: &G::Scalar,
+        pk: &G::Element,
+        r: &G::Scalar,
+        vec_e: &[GroupCipher<G::Element>],
+        vec_c: &[G::Element],
+        id: &[u8],
+        hash: HashFunction,
+    ) -> GroupProof<G::Scalar> {
+        assert!(
+            vec_e.len() == vec_c.len(),
+            "encryptions and partial decryptions need to have the same length!"
+        );
+        assert!(!vec_e.is_empty(), "vectors cannot be empty!");
+
+        // the commitments: t_0 = g^r, t_i = a_i^r
+        let mut vec_t: Vec<G::Element> = Vec::with_capacity(vec_e.len() + 1);
+        vec_t.push(group.exp(&group.generator(), r));
+        vec_t.extend(vec_e.iter().map(|e_i| group.exp(&e_i.a, r)));
+
+        // compute challenge
+        // hash public values (hash(unique_id, constant, pk, vec_e, vec_c, vec_t) mod q)
+        let c = Helper::hash_decryption_proof_inputs_in_group(
+            group,
+            id,
+            "decryption",
+            pk,
+            vec_e,
+            vec_c,
+            &vec_t,
+            hash,
+        );
+        let c = group.scalar_from_biguint(&c);
+
+        // compute the response: d = r - c * sk mod q
+        let d = group.scalar_sub(r, &group.scalar_mul(&c, sk));
+
+        GroupProof {
+            challenge: c,
+            response: d,
+        }
+    }
+
+    /// Verifies the decryption proof in an arbitrary group (see: `verify_with_hash`).
+    /// Returns false if the number of encryptions and partial decryptions differ.
+    pub fn verify_in_group<G: Group>(
+        group: &G,
+        pk: &G::Element,
+        proof: &GroupProof<G::Scalar>,
+        vec_e: &[GroupCipher<G::Element>],
+        vec_c: &[G::Element],
+        id: &[u8],
+        hash: HashFunction,
+    ) -> bool {
+        if vec_e.is_empty() || vec_e.len() != vec_c.len() {
+            return false;
+        }
+        let c = &proof.challenge;
+        let d = &proof.response;
+
+        // the recomputed commitments: t_0 = pk^c * g^d, t_i = c_i^c * a_i^d
+        let mut vec_t: Vec<G::Element> = Vec::with_capacity(vec_e.len() + 1);
+        vec_t.push(group.op(&group.exp(pk, c), &group.exp(&group.generator(), d)));
+        for (e_i, c_i) in vec_e.iter().zip(vec_c.iter()) {
+            vec_t.push(group.op(&group.exp(c_i, c), &group.exp(&e_i.a, d)));
+        }
+
+        // recompute the challenge and verify that the challenges are the same
+        let recomputed_c = Helper::hash_decryption_proof_inputs_in_group(
+            group,
+            id,
+            "decryption",
+            pk,
+            vec_e,
+            vec_c,
+            &vec_t,
+            hash,
+        );
+        group.scalar_from_biguint(&recomputed_c) == *c
+    }
+
+    /// Generates a batched decryption proof relative to encryptions e and partial decryptions c.
+    ///
+    /// The equations c_i = a_i ^ sk are combined into a single equation c' = a' ^ sk using a random linear combination:
+    /// a' = prod(a_i ^ z_i) and c' = prod(c_i ^ z_i), where the exponents z_i (of soundness bits) are derived from the hash of all inputs.
+    /// The proof is a NIZKP of the equality of the discrete logarithms log_g(pk) = log_a'(c').
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_batched(
+        params: &ElGamalParams,
+        sk: &BigUint, // private key of public key share
+        pk: &BigUint, // public key of public key share -> not system public key
+        r: &BigUint,
+        vec_e: &[Cipher],
+        vec_c: &[BigUint],
+        id: &[u8],
+        soundness: usize,
+    ) -> DecryptionProof {
+        Self::generate_batched_with_hash(
+            params,
+            sk,
+            pk,
+            r,
+            vec_e,
+            vec_c,
+            id,
+            soundness,
+            HashFunction::Blake2b,
+        )
+    }
+
+    /// Generates the batched proof, the challenge is computed with the hash function
+    /// (see: `generate_batched`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_batched_with_hash(
+        params: &ElGamalParams,
+        sk: &BigUint,
+        pk: &BigUint,
+        r: &BigUint,
+        vec_e: &[Cipher],
+        vec_c: &[BigUint],
+        id: &[u8],
+        soundness: usize,
+        hash: HashFunction,
+    ) -> DecryptionProof {
+        assert!(
+            vec_e.len() == vec_c.len(),
+            "encryptions and partial decryptions need to have the same length!"
+        );
+        assert!(!vec_e.is_empty(), "vectors cannot be empty!");
+
+        // system parameters
+        let g = &params.g;
+        let q = &params.q();
+        let p = &params.p;
+
+        // combine all encryptions and partial decryptions
+        let (seed, a_bar, c_bar) = Self::combine(params, pk, vec_e, vec_c, id, soundness, hash);
+
+        // the commitments
+        let t_0 = g.modpow(r, p);
+        let t_1 = a_bar.modpow(r, p);
+
+        // compute challenge
+        // hash public values (hash(seed, a', c', t_0, t_1) mod q)
+        let mut c = Helper::hash_vec_biguints_to_biguint(vec![seed, a_bar, c_bar, t_0, t_1], hash);
+        c %= q;
+
+        // compute the response: d = r - c * sk mod q
+        let d = r.modsub(&c.modmul(sk, q), q);
+
+        DecryptionProof {
+            challenge: c,
+            response: d,
+        }
+    }
+
+    /// Verifies a batched decryption proof relative to encryptions e and partial decryptions c.
+    ///
+    /// The partial decryptions must be elements of the group of quadratic residues (prime order q), otherwise a share multiplied
+    /// by an element of order 2 (e.g. p - 1) cancels out in the combination. Then, an invalid partial decryption is accepted with
+    /// probability at most 2^-soundness. Requires one full exponentiation per cipher (the group check) and two for the proof
+    /// instead of two per cipher. Returns false if the vectors are empty or have different lengths.
+    pub fn verify_batched(
+        params: &ElGamalParams,
+        pk: &BigUint, // public key of public key share -> not system public key
+        proof: &DecryptionProof,
+        vec_e: &[Cipher],
+        vec_c: &[BigUint],
+        id: &[u8],
+        soundness: usize,
+    ) -> bool {
+        Self::verify_batched_with_hash(
+            params,
+            pk,
+            proof,
+            vec_e,
+            vec_c,
+            id,
+            soundness,
+            HashFunction::Blake2b,
+        )
+    }
+
+    /// Verifies the batched proof, the challenge is recomputed with the hash function
+    /// (see: `verify_batched`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_batched_with_hash(
+        params: &ElGamalParams,
+        pk: &BigUint,
+        proof: &DecryptionProof,
+        vec_e: &[Cipher],
+        vec_c: &[BigUint],
+        id: &[u8],
+        soundness: usize,
+        hash: HashFunction,
+    ) -> bool {
+        Self::check_batched_with_hash(params, pk, proof, vec_e, vec_c, id, soundness, hash).is_ok()
+    }
+
+    /// Verifies the batched proof like `verify_batched_with_hash`, returns the check which failed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn check_batched_with_hash(
+        params: &ElGamalParams,
+        pk: &BigUint,
+        proof: &DecryptionProof,
+        vec_e: &[Cipher],
+        vec_c: &[BigUint],
+        id: &[u8],
+        soundness: usize,
+        hash: HashFunction,
+    ) -> Result<(), ProofFailure> {
+        if vec_e.len() != vec_c.len() || vec_e.is_empty() {
+            return Err(ProofFailure::InputLength);
+        }
+
+        // the random linear combination only binds the shares of the group G_q
+        if !vec_c.iter().all(|c_i| params.is_element(c_i)) {
+            return Err(ProofFailure::Group);
+        }
+
+        // system parameters
+        let g = &params.g;
+        let q = &params.q();
+        let p = &params.p;
+
+        // the proof
+        let c = &proof.challenge;
+        let d = &proof.response;
+
+        // combine all encryptions and partial decryptions
+        let (seed, a_bar, c_bar) = Self::combine(params, pk, vec_e, vec_c, id, soundness, hash);
+
+        // the recomputed commitments
+        // t_0 = pk^c * g^d mod p
+        let t_0 = pk.modpow(c, p).modmul(&g.modpow(d, p), p);
+        // t_1 = c'^c * a'^d mod p
+        let t_1 = c_bar.modpow(c, p).modmul(&a_bar.modpow(d, p), p);
+
+        // recompute the challenge
+        let mut recomputed_c =
+            Helper::hash_vec_biguints_to_biguint(vec![seed, a_bar, c_bar, t_0, t_1], hash);
+        recomputed_c %= q;
+
+        // verify that the challenges are the same
+        if &recomputed_c != c {
+            return Err(ProofFailure::Challenge);
+        }
+        Ok(())
+    }
+
+    /// Computes the seed (hash of all public inputs) and the random linear combinations a' = prod(a_i ^ z_i) and c' = prod(c_i ^ z_i).
+    fn combine(
+        params: &ElGamalParams,
+        pk: &BigUint,
+        vec_e: &[Cipher],
+        vec_c: &[BigUint],
+        id: &[u8],
+        soundness: usize,
+        hash: HashFunction,
+    ) -> (BigUint, BigUint, BigUint) {
+        let p = &params.p;
+
+        // hash public values (hash(unique_id, constant, pk, vec_e, vec_c) mod q)
+        let mut seed = Helper::hash_decryption_proof_inputs(
+            id,
+            "decryption-batch",
+            pk,
+            vec_e.to_vec(),
+            vec_c.to_vec(),
+            Vec::new(),
+            hash,
+        );
+        seed %= params.q();
+
+        let vec_z = Helper::get_batch_exponents_of_size(id, &seed, vec_e.len(), soundness);
+        let mut a_bar = BigUint::one();
+        let mut c_bar = BigUint::one();
+        for ((e_i, c_i), z_i) in vec_e.iter().zip(vec_c.iter()).zip(vec_z.iter()) {
+            a_bar = a_bar.modmul(&e_i.a.modpow(z_i, p), p);
+            c_bar = c_bar.modmul(&c_i.modpow(z_i, p), p);
+        }
+        (seed, a_bar, c_bar)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
         encryption::ElGamal,
+        hash::HashFunction,
         helper::Helper,
-        proofs::decryption::DecryptionProof,
+        proofs::{
+            decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
+            ProofFailure,
+        },
         random::Random,
-        types::{ModuloOperations, PublicKey},
+        types::{GroupCipher, ModuloOperations, PublicKey},
     };
     use alloc::vec::Vec;
     use num_bigint::BigUint;
@@ -172,6 +506,372 @@ mod tests {
         assert!(is_correct);
     }
 
+    #[test]
+    fn it_should_generate_the_same_decryption_proof_in_the_integer_group() {
+        let sealer_id = "Charlie".as_bytes();
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let r = Random::get_random_less_than(q);
+        let hash = HashFunction::Sha3_256;
+
+        let encryptions = Random::generate_random_encryptions(&pk, q, 3);
+        let decryptions = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &sk))
+            .collect::<Vec<BigUint>>();
+        let expected = DecryptionProof::generate_with_hash(
+            &params,
+            &sk.x,
+            &pk.h,
+            &r,
+            encryptions.clone(),
+            decryptions.clone(),
+            sealer_id,
+            hash,
+        );
+
+        let ciphers: Vec<GroupCipher<BigUint>> = encryptions
+            .into_iter()
+            .map(|cipher| cipher.into())
+            .collect();
+        let proof = DecryptionProof::generate_in_group(
+            &params,
+            &sk.x,
+            &pk.h,
+            &r,
+            &ciphers,
+            &decryptions,
+            sealer_id,
+            hash,
+        );
+        assert_eq!(proof.challenge, expected.challenge);
+        assert_eq!(proof.response, expected.response);
+        assert!(DecryptionProof::verify_in_group(
+            &params,
+            &pk.h,
+            &proof,
+            &ciphers,
+            &decryptions,
+            sealer_id,
+            hash
+        ));
+
+        // a missing partial decryption doesn't panic
+        assert!(!DecryptionProof::verify_in_group(
+            &params,
+            &pk.h,
+            &proof,
+            &ciphers,
+            &decryptions[1..],
+            sealer_id,
+            hash
+        ));
+    }
+
+    #[test]
+    fn it_should_verify_batched_decryption_proof() {
+        let sealer_id = "Charlie".as_bytes();
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let r = Random::get_random_less_than(q);
+
+        let encryptions = Random::generate_random_encryptions(&pk, q, 10);
+        let decryptions = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &sk))
+            .collect::<Vec<BigUint>>();
+
+        let proof = DecryptionProof::generate_batched(
+            &params,
+            &sk.x,
+            &pk.h,
+            &r,
+            &encryptions,
+            &decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        );
+        assert!(DecryptionProof::verify_batched(
+            &params,
+            &pk.h,
+            &proof,
+            &encryptions,
+            &decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        ));
+
+        // the proof is bound to the soundness, the sealer and the ciphers
+        assert!(!DecryptionProof::verify_batched(
+            &params,
+            &pk.h,
+            &proof,
+            &encryptions,
+            &decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS + 1,
+        ));
+        assert!(!DecryptionProof::verify_batched(
+            &params,
+            &pk.h,
+            &proof,
+            &encryptions,
+            &decryptions,
+            "Bob".as_bytes(),
+            DEFAULT_BATCH_SOUNDNESS,
+        ));
+        assert!(!DecryptionProof::verify_batched(
+            &params,
+            &pk.h,
+            &proof,
+            &encryptions[1..],
+            &decryptions[1..],
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        ));
+    }
+
+    #[test]
+    fn it_should_verify_batched_decryption_proof_with_the_hash_function() {
+        let sealer_id = "Charlie".as_bytes();
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let r = Random::get_random_less_than(q);
+
+        let encryptions = Random::generate_random_encryptions(&pk, q, 5);
+        let decryptions = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &sk))
+            .collect::<Vec<BigUint>>();
+
+        let proof = DecryptionProof::generate_batched_with_hash(
+            &params,
+            &sk.x,
+            &pk.h,
+            &r,
+            &encryptions,
+            &decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+            HashFunction::Sha256,
+        );
+        assert!(DecryptionProof::verify_batched_with_hash(
+            &params,
+            &pk.h,
+            &proof,
+            &encryptions,
+            &decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+            HashFunction::Sha256,
+        ));
+
+        // the proof is bound to the hash function
+        assert!(!DecryptionProof::verify_batched(
+            &params,
+            &pk.h,
+            &proof,
+            &encryptions,
+            &decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        ));
+    }
+
+    #[test]
+    fn it_should_not_verify_batched_decryption_proof_of_invalid_share() {
+        let sealer_id = "Charlie".as_bytes();
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let r = Random::get_random_less_than(q);
+
+        let encryptions = Random::generate_random_encryptions(&pk, q, 10);
+        let mut decryptions = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &sk))
+            .collect::<Vec<BigUint>>();
+
+        // a single invalid partial decryption (a quadratic residue) invalidates the proof
+        decryptions[7] = decryptions[7].modmul(&params.g, &params.p);
+        let proof = DecryptionProof::generate_batched(
+            &params,
+            &sk.x,
+            &pk.h,
+            &r,
+            &encryptions,
+            &decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        );
+        assert!(!DecryptionProof::verify_batched(
+            &params,
+            &pk.h,
+            &proof,
+            &encryptions,
+            &decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        ));
+
+        // empty vectors and vectors of different lengths are rejected
+        assert!(!DecryptionProof::verify_batched(
+            &params,
+            &pk.h,
+            &proof,
+            &[],
+            &[],
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        ));
+        assert!(!DecryptionProof::verify_batched(
+            &params,
+            &pk.h,
+            &proof,
+            &encryptions,
+            &decryptions[1..],
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        ));
+
+        // the failed checks
+        let hash = HashFunction::Blake2b;
+        assert_eq!(
+            DecryptionProof::check_batched_with_hash(
+                &params,
+                &pk.h,
+                &proof,
+                &encryptions,
+                &decryptions,
+                sealer_id,
+                DEFAULT_BATCH_SOUNDNESS,
+                hash,
+            ),
+            Err(ProofFailure::Challenge)
+        );
+        assert_eq!(
+            DecryptionProof::check_batched_with_hash(
+                &params,
+                &pk.h,
+                &proof,
+                &encryptions,
+                &decryptions[1..],
+                sealer_id,
+                DEFAULT_BATCH_SOUNDNESS,
+                hash,
+            ),
+            Err(ProofFailure::InputLength)
+        );
+    }
+
+    #[test]
+    fn it_should_not_verify_batched_decryption_proof_of_negated_shares() {
+        let sealer_id = "Charlie".as_bytes();
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let p = &params.p;
+        let r = Random::get_random_less_than(q);
+
+        let encryptions = Random::generate_random_encryptions(&pk, q, 10);
+        let mut decryptions = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &sk))
+            .collect::<Vec<BigUint>>();
+
+        // two shares multiplied by the element of order 2 (p - 1) cancel out
+        // in the combination unless the shares are checked to be group elements
+        let minus_one = p - 1u32;
+        decryptions[2] = decryptions[2].modmul(&minus_one, p);
+        decryptions[5] = decryptions[5].modmul(&minus_one, p);
+        let proof = DecryptionProof::generate_batched(
+            &params,
+            &sk.x,
+            &pk.h,
+            &r,
+            &encryptions,
+            &decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        );
+        assert_eq!(
+            DecryptionProof::check_batched_with_hash(
+                &params,
+                &pk.h,
+                &proof,
+                &encryptions,
+                &decryptions,
+                sealer_id,
+                DEFAULT_BATCH_SOUNDNESS,
+                HashFunction::Blake2b,
+            ),
+            Err(ProofFailure::Group)
+        );
+
+        // a share outside of [1, p) is rejected as well
+        decryptions[2] = decryptions[2].modmul(&minus_one, p) + p;
+        decryptions[5] = decryptions[5].modmul(&minus_one, p);
+        assert!(!DecryptionProof::verify_batched(
+            &params,
+            &pk.h,
+            &proof,
+            &encryptions,
+            &decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        ));
+    }
+
+    #[test]
+    fn it_should_not_mix_batched_and_individual_decryption_proofs() {
+        let sealer_id = "Charlie".as_bytes();
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let r = Random::get_random_less_than(q);
+
+        let encryptions = Random::generate_random_encryptions(&pk, q, 3);
+        let decryptions = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &sk))
+            .collect::<Vec<BigUint>>();
+
+        let proof = DecryptionProof::generate(
+            &params,
+            &sk.x,
+            &pk.h,
+            &r,
+            encryptions.clone(),
+            decryptions.clone(),
+            sealer_id,
+        );
+        assert!(!DecryptionProof::verify_batched(
+            &params,
+            &pk.h,
+            &proof,
+            &encryptions,
+            &decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        ));
+
+        let batched_proof = DecryptionProof::generate_batched(
+            &params,
+            &sk.x,
+            &pk.h,
+            &r,
+            &encryptions,
+            &decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        );
+        assert!(!DecryptionProof::verify(
+            &params,
+            &pk.h,
+            &batched_proof,
+            encryptions,
+            decryptions,
+            sealer_id
+        ));
+    }
+
     #[test]
     fn it_should_verify_decryption_proof_multiple_partial_decryptions() {
         // create system parameters
@@ -449,4 +1149,54 @@ mod tests {
         assert!(plaintexts.iter().any(|val| val == &BigUint::one()));
         assert!(plaintexts.iter().any(|val| val == &BigUint::from(2u32)));
     }
+
+    #[cfg(feature = "ristretto")]
+    #[test]
+    fn it_should_verify_decryption_proof_in_the_ristretto_group() {
+        use crate::types::{Group, Ristretto};
+
+        let group = Ristretto;
+        let sealer_id = "Charlie".as_bytes();
+        let hash = HashFunction::Blake2b;
+        let sk = Random::get_random_scalar(&group);
+        let pk = group.exp(&group.generator(), &sk);
+        let ciphers: Vec<GroupCipher<_>> = (0..3u32)
+            .map(|m| {
+                let m = ElGamal::encode_in_group(&group, &BigUint::from(m));
+                ElGamal::encrypt_in_group(&group, &pk, &m, &Random::get_random_scalar(&group))
+            })
+            .collect();
+        let decryptions: Vec<_> = ciphers
+            .iter()
+            .map(|cipher| group.exp(&cipher.a, &sk))
+            .collect();
+
+        let r = Random::get_random_scalar(&group);
+        let proof = DecryptionProof::generate_in_group(
+            &group,
+            &sk,
+            &pk,
+            &r,
+            &ciphers,
+            &decryptions,
+            sealer_id,
+            hash,
+        );
+        assert!(DecryptionProof::verify_in_group(
+            &group,
+            &pk,
+            &proof,
+            &ciphers,
+            &decryptions,
+            sealer_id,
+            hash
+        ));
+
+        // an invalid partial decryption
+        let mut invalid = decryptions.clone();
+        invalid[1] = group.op(&invalid[1], &group.generator());
+        assert!(!DecryptionProof::verify_in_group(
+            &group, &pk, &proof, &ciphers, &invalid, sealer_id, hash
+        ));
+    }
 }
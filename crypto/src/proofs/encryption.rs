@@ -0,0 +1,281 @@
+use crate::{
+    hash::HashFunction,
+    helper::Helper,
+    proofs::GroupProof,
+    types::{Cipher, ElGamalParams, Group, GroupCipher, ModuloOperations},
+};
+use num_bigint::BigUint;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EncryptionProof {
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
+    pub challenge: BigUint,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
+    pub response: BigUint,
+}
+
+impl EncryptionProof {
+    /// Generates a proof of knowledge of the randomness r of an ElGamal encryption (a = g^r) using the Schnorr protocol.
+    ///
+    /// The id (e.g. the voter's account id) is part of the challenge. Therefore, the proof only verifies for the same id
+    /// and a copied (or re-randomized) cipher cannot be submitted by anyone else, since only the creator of the cipher knows r.
+    ///
+    /// Step by Step:
+    /// 1. generate a commitment b = g^x with x a random value from Z_q
+    /// 2. compute challenge c = hash(id, "encryption", a, b)
+    /// 3. compute d = x + c*r
+    pub fn generate(
+        params: &ElGamalParams,
+        r: &BigUint,
+        cipher: &Cipher,
+        x: &BigUint,
+        id: &[u8],
+    ) -> EncryptionProof {
+        // system parameters
+        let g = &params.g;
+        let q = &params.q();
+        let p = &params.p;
+
+        // the commitment
+        let b = &g.modpow(x, p);
+
+        // compute challenge -> hash public values (hash(unique_id, a, b) mod q)
+        let mut c = Helper::hash_key_gen_proof_inputs(
+            id,
+            "encryption",
+            &cipher.a,
+            b,
+            HashFunction::Blake2b,
+        );
+        c %= q;
+
+        // compute the response: d = x + c*r mod q
+        let d = x.modadd(&c.modmul(r, q), q);
+
+        EncryptionProof {
+            challenge: c,
+            response: d,
+        }
+    }
+
+    /// Verifies a proof of knowledge of the randomness r of an ElGamal encryption (a = g^r) using the Schnorr protocol.
+    ///
+    /// Step by Step:
+    /// 1. recompute b = g^d/a^c
+    /// 2. recompute the challenge c
+    /// 3. verify that the challenge is correct
+    /// 4. verify that: g^d == b * a^c
+    pub fn verify(
+        params: &ElGamalParams,
+        cipher: &Cipher,
+        proof: &EncryptionProof,
+        id: &[u8],
+    ) -> bool {
+        // system parameters
+        let g = &params.g;
+        let q = &params.q();
+        let p = &params.p;
+
+        // the first component of the cipher
+        let a = &cipher.a;
+
+        // the proof
+        let c = &proof.challenge;
+        let d = &proof.response;
+
+        // recompute b
+        let g_pow_d = g.modpow(d, p);
+        let a_pow_c = a.modpow(c, p);
+        let b = match g_pow_d.moddiv(&a_pow_c, p) {
+            Some(b) => b,
+            None => return false,
+        };
+
+        // recompute the hash
+        let mut c_ =
+            Helper::hash_key_gen_proof_inputs(id, "encryption", a, &b, HashFunction::Blake2b);
+        c_ %= q;
+
+        // verify that the challenges are the same
+        let v1 = *c == c_;
+
+        // verify that the responses are the same
+        let v2 = g_pow_d == b.modmul(&a_pow_c, p);
+
+        v1 && v2
+    }
+
+    /// Generates the proof of knowledge of the randomness r of an ElGamal encryption
+    /// in an arbitrary group (see: `generate`).
+    pub fn generate_in_group<G: Group>(
+        group: &G,
+        r: &G::Scalar,
+        cipher: &GroupCipher<G::Element>,
+        x: &G::Scalar,
+        id: &[u8],
+    ) -> GroupProof<G::Scalar> {
+        // the commitment
+        let b = group.exp(&group.generator(), x);
+
+        // compute challenge -> hash public values (hash(unique_id, a, b) mod q)
+        let c = Helper::hash_key_gen_proof_inputs_in_group(
+            group,
+            id,
+            "encryption",
+            &cipher.a,
+            &b,
+            HashFunction::Blake2b,
+        );
+        let c = group.scalar_from_biguint(&c);
+
+        // compute the response: d = x + c*r mod q
+        let d = group.scalar_add(x, &group.scalar_mul(&c, r));
+
+        GroupProof {
+            challenge: c,
+            response: d,
+        }
+    }
+
+    /// Verifies the proof of knowledge of the randomness r of an ElGamal encryption
+    /// in an arbitrary group (see: `verify`).
+    pub fn verify_in_group<G: Group>(
+        group: &G,
+        cipher: &GroupCipher<G::Element>,
+        proof: &GroupProof<G::Scalar>,
+        id: &[u8],
+    ) -> bool {
+        let a = &cipher.a;
+        if !group.is_element(a) {
+            return false;
+        }
+
+        // recompute b = g^d / a^c
+        let g_pow_d = group.exp(&group.generator(), &proof.response);
+        let a_pow_c = group.exp(a, &proof.challenge);
+        let b = group.op(&g_pow_d, &group.inverse(&a_pow_c));
+
+        // recompute the hash and verify that the challenges are the same
+        let c_ = Helper::hash_key_gen_proof_inputs_in_group(
+            group,
+            id,
+            "encryption",
+            a,
+            &b,
+            HashFunction::Blake2b,
+        );
+        group.scalar_from_biguint(&c_) == proof.challenge
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        encryption::ElGamal, helper::Helper, proofs::encryption::EncryptionProof, random::Random,
+        types::GroupCipher,
+    };
+    use num_bigint::BigUint;
+
+    #[test]
+    fn it_should_verify_encryption_proof() {
+        let voter_id = "Alice".as_bytes();
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = params.q();
+        let r = Random::get_random_less_than(&q);
+        let x = Random::get_random_less_than(&q);
+        let cipher = ElGamal::encrypt_encode(&BigUint::from(1u32), &r, &pk);
+
+        let proof = EncryptionProof::generate(&params, &r, &cipher, &x, voter_id);
+
+        // verify the proof
+        let is_correct = EncryptionProof::verify(&params, &cipher, &proof, voter_id);
+        assert!(is_correct);
+    }
+
+    #[test]
+    fn it_should_not_verify_encryption_proof_of_another_voter() {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = params.q();
+        let r = Random::get_random_less_than(&q);
+        let x = Random::get_random_less_than(&q);
+        let cipher = ElGamal::encrypt_encode(&BigUint::from(1u32), &r, &pk);
+
+        let proof = EncryptionProof::generate(&params, &r, &cipher, &x, "Alice".as_bytes());
+
+        // the proof is bound to Alice
+        let is_correct = EncryptionProof::verify(&params, &cipher, &proof, "Bob".as_bytes());
+        assert!(!is_correct);
+    }
+
+    #[test]
+    fn it_should_not_verify_encryption_proof_of_re_encrypted_cipher() {
+        let voter_id = "Alice".as_bytes();
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = params.q();
+        let r = Random::get_random_less_than(&q);
+        let x = Random::get_random_less_than(&q);
+        let cipher = ElGamal::encrypt_encode(&BigUint::from(1u32), &r, &pk);
+        let proof = EncryptionProof::generate(&params, &r, &cipher, &x, voter_id);
+
+        // the proof doesn't verify for a re-randomized copy of the cipher
+        let r2 = Random::get_random_less_than(&q);
+        let re_encrypted = ElGamal::re_encrypt(&cipher, &r2, &pk);
+        let is_correct = EncryptionProof::verify(&params, &re_encrypted, &proof, voter_id);
+        assert!(!is_correct);
+    }
+
+    #[test]
+    fn it_should_generate_the_same_encryption_proof_in_the_integer_group() {
+        let voter_id = "Alice".as_bytes();
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = params.q();
+        let r = Random::get_random_less_than(&q);
+        let x = Random::get_random_less_than(&q);
+        let cipher = ElGamal::encrypt_encode(&BigUint::from(1u32), &r, &pk);
+        let expected = EncryptionProof::generate(&params, &r, &cipher, &x, voter_id);
+
+        let cipher: GroupCipher<BigUint> = cipher.into();
+        let proof = EncryptionProof::generate_in_group(&params, &r, &cipher, &x, voter_id);
+        assert_eq!(proof.challenge, expected.challenge);
+        assert_eq!(proof.response, expected.response);
+        assert!(EncryptionProof::verify_in_group(
+            &params, &cipher, &proof, voter_id
+        ));
+        assert!(!EncryptionProof::verify_in_group(
+            &params, &cipher, &proof, b"Bob"
+        ));
+    }
+
+    #[cfg(feature = "ristretto")]
+    #[test]
+    fn it_should_verify_encryption_proof_in_the_ristretto_group() {
+        use crate::types::{Group, Ristretto};
+
+        let group = Ristretto;
+        let voter_id = "Alice".as_bytes();
+        let h = group.exp(&group.generator(), &Random::get_random_scalar(&group));
+        let r = Random::get_random_scalar(&group);
+        let x = Random::get_random_scalar(&group);
+        let m = ElGamal::encode_in_group(&group, &BigUint::from(1u32));
+        let cipher = ElGamal::encrypt_in_group(&group, &h, &m, &r);
+
+        let proof = EncryptionProof::generate_in_group(&group, &r, &cipher, &x, voter_id);
+        assert!(EncryptionProof::verify_in_group(
+            &group, &cipher, &proof, voter_id
+        ));
+
+        // the proof doesn't verify for a re-randomized copy of the cipher
+        let re_encrypted =
+            ElGamal::re_encrypt_in_group(&group, &cipher, &h, &Random::get_random_scalar(&group));
+        assert!(!EncryptionProof::verify_in_group(
+            &group,
+            &re_encrypted,
+            &proof,
+            voter_id
+        ));
+    }
+}
@@ -0,0 +1,408 @@
+use crate::{
+    hash::HashFunction,
+    helper::Helper,
+    types::{Cipher, ElGamalParams, ModuloOperations},
+};
+use alloc::vec::Vec;
+use num_bigint::BigUint;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct KeySwitchProof {
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
+    pub challenge: BigUint,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
+    pub response: BigUint,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex::vec"))]
+    pub responses: Vec<BigUint>,
+}
+
+impl KeySwitchProof {
+    /// Generates a proof that the key switch shares vec_s were computed correctly for the encryptions vec_e.
+    /// This is a NIZKP of knowledge of the private key share x and the random values s_i satisfying
+    /// pk = g^x and (A_i, B_i) = (g^s_i, new_pk^s_i * a_i^-x) for all input encryptions e_i = (a_i, b_i).
+    ///
+    /// Step by Step:
+    /// 1. compute the commitments t_0 = g^r, t_i = (g^r_i, new_pk^r_i * a_i^-r)
+    /// 2. compute the challenge c
+    /// 3. compute the responses d = r + c*x and d_i = r_i + c*s_i
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate(
+        params: &ElGamalParams,
+        sk: &BigUint,        // private key of the public key share
+        pk: &BigUint,        // public key share of the old key -> not system public key
+        new_pk: &BigUint,    // the new system public key
+        randoms: &[BigUint], // the random values s_i used to compute the key switch shares
+        r: &BigUint,
+        vec_r: &[BigUint],
+        vec_e: Vec<Cipher>,
+        vec_s: Vec<Cipher>,
+        id: &[u8],
+    ) -> KeySwitchProof {
+        Self::generate_with_hash(
+            params,
+            sk,
+            pk,
+            new_pk,
+            randoms,
+            r,
+            vec_r,
+            vec_e,
+            vec_s,
+            id,
+            HashFunction::Blake2b,
+        )
+    }
+
+    /// Generates the proof, the challenge is computed with the hash function (see: `generate`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_with_hash(
+        params: &ElGamalParams,
+        sk: &BigUint,
+        pk: &BigUint,
+        new_pk: &BigUint,
+        randoms: &[BigUint],
+        r: &BigUint,
+        vec_r: &[BigUint],
+        vec_e: Vec<Cipher>,
+        vec_s: Vec<Cipher>,
+        id: &[u8],
+        hash: HashFunction,
+    ) -> KeySwitchProof {
+        assert!(
+            vec_e.len() == vec_s.len()
+                && vec_e.len() == randoms.len()
+                && vec_e.len() == vec_r.len(),
+            "encryptions, key switch shares and random values need to have the same length!"
+        );
+        assert!(!vec_e.is_empty(), "vectors cannot be empty!");
+
+        // system parameters
+        let g = &params.g;
+        let q = &params.q();
+        let p = &params.p;
+
+        // the commitments
+        let t_0 = g.modpow(r, p);
+        let mut vec_t: Vec<BigUint> = Vec::with_capacity(2 * vec_e.len() + 1);
+        vec_t.push(t_0);
+
+        for (e_i, r_i) in vec_e.iter().zip(vec_r.iter()) {
+            let t_a = g.modpow(r_i, p);
+            let t_b = new_pk
+                .modpow(r_i, p)
+                .moddiv(&e_i.a.modpow(r, p), p)
+                .expect("cannot compute mod_inverse in mod_div!");
+            vec_t.push(t_a);
+            vec_t.push(t_b);
+        }
+
+        // compute challenge
+        // hash public values (hash(unique_id, constant, pk, new_pk, vec_e, vec_s, vec_t) mod q)
+        let mut c = Helper::hash_key_switch_proof_inputs(
+            id,
+            "key_switch",
+            pk,
+            new_pk,
+            vec_e,
+            vec_s,
+            vec_t,
+            hash,
+        );
+        c %= q;
+
+        // compute the responses: d = r + c * sk mod q, d_i = r_i + c * s_i mod q
+        let d = r.modadd(&c.modmul(sk, q), q);
+        let vec_d = vec_r
+            .iter()
+            .zip(randoms.iter())
+            .map(|(r_i, s_i)| r_i.modadd(&c.modmul(s_i, q), q))
+            .collect::<Vec<BigUint>>();
+
+        KeySwitchProof {
+            challenge: c,
+            response: d,
+            responses: vec_d,
+        }
+    }
+
+    /// Verifies a proof that the key switch shares vec_s were computed correctly for the encryptions vec_e
+    /// using the private key share that belongs to the public key share pk and the new system public key new_pk.
+    ///
+    /// Step by Step:
+    /// 1. recompute the commitments t_0 = g^d / pk^c, t_i = (g^d_i / A_i^c, new_pk^d_i * a_i^-d / B_i^c)
+    /// 2. recompute the challenge c
+    /// 3. verify that the challenge is correct
+    pub fn verify(
+        params: &ElGamalParams,
+        pk: &BigUint,     // public key share of the old key -> not system public key
+        new_pk: &BigUint, // the new system public key
+        proof: &KeySwitchProof,
+        vec_e: Vec<Cipher>,
+        vec_s: Vec<Cipher>,
+        id: &[u8],
+    ) -> bool {
+        Self::verify_with_hash(
+            params,
+            pk,
+            new_pk,
+            proof,
+            vec_e,
+            vec_s,
+            id,
+            HashFunction::Blake2b,
+        )
+    }
+
+    /// Verifies the proof, the challenge is recomputed with the hash function (see: `verify`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_hash(
+        params: &ElGamalParams,
+        pk: &BigUint,
+        new_pk: &BigUint,
+        proof: &KeySwitchProof,
+        vec_e: Vec<Cipher>,
+        vec_s: Vec<Cipher>,
+        id: &[u8],
+        hash: HashFunction,
+    ) -> bool {
+        if vec_e.len() != vec_s.len() || vec_e.len() != proof.responses.len() || vec_e.is_empty() {
+            return false;
+        }
+
+        // system parameters
+        let g = &params.g;
+        let q = &params.q();
+        let p = &params.p;
+
+        // the proof
+        let c = &proof.challenge;
+        let d = &proof.response;
+
+        // the recomputed commitment
+        // t_0 = g^d / pk^c mod p
+        let t_0 = match g.modpow(d, p).moddiv(&pk.modpow(c, p), p) {
+            Some(t_0) => t_0,
+            None => return false,
+        };
+        let mut recomputed_vec_t: Vec<BigUint> = Vec::with_capacity(2 * vec_e.len() + 1);
+        recomputed_vec_t.push(t_0);
+
+        for index in 0..vec_e.len() {
+            let a_i = &vec_e[index].a;
+            let s_i = &vec_s[index];
+            let d_i = &proof.responses[index];
+
+            // recompute t_a = g^d_i / A_i^c mod p
+            let t_a = g.modpow(d_i, p).moddiv(&s_i.a.modpow(c, p), p);
+
+            // recompute t_b = new_pk^d_i / (a_i^d * B_i^c) mod p
+            let divisor = a_i.modpow(d, p).modmul(&s_i.b.modpow(c, p), p);
+            let t_b = new_pk.modpow(d_i, p).moddiv(&divisor, p);
+
+            match (t_a, t_b) {
+                (Some(t_a), Some(t_b)) => {
+                    recomputed_vec_t.push(t_a);
+                    recomputed_vec_t.push(t_b);
+                }
+                _ => return false,
+            }
+        }
+
+        // recompute the challenge
+        // hash public values (hash(unique_id, constant, pk, new_pk, vec_e, vec_s, recomputed_vec_t) mod q)
+        let mut recomputed_c = Helper::hash_key_switch_proof_inputs(
+            id,
+            "key_switch",
+            pk,
+            new_pk,
+            vec_e,
+            vec_s,
+            recomputed_vec_t,
+            hash,
+        );
+        recomputed_c %= q;
+
+        // verify that the challenges are the same
+        &recomputed_c == c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        encryption::ElGamal,
+        helper::Helper,
+        proofs::key_switch::KeySwitchProof,
+        random::Random,
+        types::{Cipher, ModuloOperations, PublicKey},
+    };
+    use alloc::vec::Vec;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn it_should_verify_key_switch_proof() {
+        let sealer_id = "Charlie".as_bytes();
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+
+        // create the new public key
+        let (new_pk, _) = Helper::generate_key_pair(&params, &Random::get_random_less_than(q));
+
+        // get three encryptions under the old public key
+        let encryptions = Random::generate_random_encryptions(&pk, q, 3);
+
+        // compute the key switch shares
+        let randoms = (0..3)
+            .map(|_| Random::get_random_less_than(q))
+            .collect::<Vec<BigUint>>();
+        let shares = encryptions
+            .iter()
+            .zip(randoms.iter())
+            .map(|(cipher, s)| ElGamal::key_switch_share(cipher, &sk, s, &new_pk))
+            .collect::<Vec<Cipher>>();
+
+        // create a proof for the key switch shares
+        let r = Random::get_random_less_than(q);
+        let vec_r = (0..3)
+            .map(|_| Random::get_random_less_than(q))
+            .collect::<Vec<BigUint>>();
+        let proof = KeySwitchProof::generate(
+            &params,
+            &sk.x,
+            &pk.h,
+            &new_pk.h,
+            &randoms,
+            &r,
+            &vec_r,
+            encryptions.clone(),
+            shares.clone(),
+            sealer_id,
+        );
+
+        // verify that the proof is correct
+        let is_correct = KeySwitchProof::verify(
+            &params,
+            &pk.h,
+            &new_pk.h,
+            &proof,
+            encryptions,
+            shares,
+            sealer_id,
+        );
+        assert!(is_correct);
+    }
+
+    #[test]
+    fn it_should_not_verify_key_switch_proof_of_manipulated_shares() {
+        let sealer_id = "Charlie".as_bytes();
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let p = &params.p;
+        let (new_pk, _) = Helper::generate_key_pair(&params, &Random::get_random_less_than(q));
+        let encryptions = Random::generate_random_encryptions(&pk, q, 2);
+
+        let randoms = (0..2)
+            .map(|_| Random::get_random_less_than(q))
+            .collect::<Vec<BigUint>>();
+        let mut shares = encryptions
+            .iter()
+            .zip(randoms.iter())
+            .map(|(cipher, s)| ElGamal::key_switch_share(cipher, &sk, s, &new_pk))
+            .collect::<Vec<Cipher>>();
+
+        let r = Random::get_random_less_than(q);
+        let vec_r = (0..2)
+            .map(|_| Random::get_random_less_than(q))
+            .collect::<Vec<BigUint>>();
+        let proof = KeySwitchProof::generate(
+            &params,
+            &sk.x,
+            &pk.h,
+            &new_pk.h,
+            &randoms,
+            &r,
+            &vec_r,
+            encryptions.clone(),
+            shares.clone(),
+            sealer_id,
+        );
+
+        // manipulate a share -> the plaintext would change from m to m + 1
+        shares[0].b = shares[0].b.modmul(&params.g, p);
+        let is_correct = KeySwitchProof::verify(
+            &params,
+            &pk.h,
+            &new_pk.h,
+            &proof,
+            encryptions,
+            shares,
+            sealer_id,
+        );
+        assert!(!is_correct);
+    }
+
+    #[test]
+    fn it_should_switch_keys_of_multiple_participants() {
+        let (params, _, _) = Helper::setup_sm_system();
+        let q = &params.q();
+        let p = &params.p;
+
+        // create bob's and charlie's key shares of the old public key
+        let bob_id = "Bob".as_bytes();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &Random::get_random_less_than(q));
+        let charlie_id = "Charlie".as_bytes();
+        let (charlie_pk, charlie_sk) =
+            Helper::generate_key_pair(&params, &Random::get_random_less_than(q));
+        let old_pk = PublicKey {
+            h: bob_pk.h.modmul(&charlie_pk.h, p),
+            params: params.clone(),
+        };
+        let (new_pk, new_sk) = Helper::generate_key_pair(&params, &Random::get_random_less_than(q));
+
+        // encrypt three under the old public key
+        let three = BigUint::from(3u32);
+        let encryption = ElGamal::encrypt_encode(&three, &Random::get_random_less_than(q), &old_pk);
+
+        // each participant creates and proves its key switch share
+        let mut shares: Vec<Cipher> = Vec::new();
+        for (id, sk, pk) in [
+            (bob_id, &bob_sk, &bob_pk),
+            (charlie_id, &charlie_sk, &charlie_pk),
+        ]
+        .iter()
+        {
+            let s = Random::get_random_less_than(q);
+            let share = ElGamal::key_switch_share(&encryption, sk, &s, &new_pk);
+            let proof = KeySwitchProof::generate(
+                &params,
+                &sk.x,
+                &pk.h,
+                &new_pk.h,
+                &[s],
+                &Random::get_random_less_than(q),
+                &[Random::get_random_less_than(q)],
+                vec![encryption.clone()],
+                vec![share.clone()],
+                id,
+            );
+            assert!(KeySwitchProof::verify(
+                &params,
+                &pk.h,
+                &new_pk.h,
+                &proof,
+                vec![encryption.clone()],
+                vec![share.clone()],
+                id,
+            ));
+            shares.push(share);
+        }
+
+        // the combined encryption decrypts to three using the new private key
+        let switched = ElGamal::combine_key_switch_shares(&encryption, &shares, p);
+        assert_eq!(ElGamal::decrypt_decode(&switched, &new_sk), three);
+    }
+}
@@ -1,12 +1,20 @@
 use crate::{
+    hash::HashFunction,
     helper::Helper,
-    types::{ElGamalParams, ModuloOperations},
+    proofs::{GroupProof, ProofFailure},
+    types::{ElGamalParams, Group, ModuloOperations},
 };
 use num_bigint::BigUint;
 
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct KeyGenerationProof {
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
     pub challenge: BigUint,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
     pub response: BigUint,
 }
 
@@ -25,6 +33,18 @@ impl KeyGenerationProof {
         pk_share: &BigUint,
         r: &BigUint,
         id: &[u8],
+    ) -> KeyGenerationProof {
+        Self::generate_with_hash(params, sk, pk_share, r, id, HashFunction::Blake2b)
+    }
+
+    /// Generates the proof, the challenge is computed with the hash function (see: `generate`).
+    pub fn generate_with_hash(
+        params: &ElGamalParams,
+        sk: &BigUint,
+        pk_share: &BigUint,
+        r: &BigUint,
+        id: &[u8],
+        hash: HashFunction,
     ) -> KeyGenerationProof {
         // system parameters
         let g = &params.g;
@@ -42,7 +62,7 @@ impl KeyGenerationProof {
         let b = &g.modpow(r, p);
 
         // compute challenge -> hash public values (hash(unique_id, h, b) mod q)
-        let mut c = Helper::hash_key_gen_proof_inputs(id, "keygen", h, b);
+        let mut c = Helper::hash_key_gen_proof_inputs(id, "keygen", h, b, hash);
         c %= q;
 
         // compute the response: d = a + c*sk mod q
@@ -69,6 +89,28 @@ impl KeyGenerationProof {
         proof: &KeyGenerationProof,
         id: &[u8],
     ) -> bool {
+        Self::verify_with_hash(params, pk_share, proof, id, HashFunction::Blake2b)
+    }
+
+    /// Verifies the proof, the challenge is recomputed with the hash function (see: `verify`).
+    pub fn verify_with_hash(
+        params: &ElGamalParams,
+        pk_share: &BigUint,
+        proof: &KeyGenerationProof,
+        id: &[u8],
+        hash: HashFunction,
+    ) -> bool {
+        Self::check_with_hash(params, pk_share, proof, id, hash).is_ok()
+    }
+
+    /// Verifies the proof like `verify_with_hash`, returns the check which failed.
+    pub fn check_with_hash(
+        params: &ElGamalParams,
+        pk_share: &BigUint,
+        proof: &KeyGenerationProof,
+        id: &[u8],
+        hash: HashFunction,
+    ) -> Result<(), ProofFailure> {
         // system parameters
         let g = &params.g;
         let q = &params.q();
@@ -84,27 +126,95 @@ impl KeyGenerationProof {
         // recompute b
         let g_pow_d = g.modpow(d, p);
         let h_pow_c = h.modpow(c, p);
-        let b = g_pow_d
-            .moddiv(&h_pow_c, p)
-            .expect("cannot compute mod_inverse in mod_div!");
+        let b = g_pow_d.moddiv(&h_pow_c, p).ok_or(ProofFailure::PublicKey)?;
 
         // recompute the hash
-        let mut c_ = Helper::hash_key_gen_proof_inputs(id, "keygen", h, &b);
+        let mut c_ = Helper::hash_key_gen_proof_inputs(id, "keygen", h, &b, hash);
         c_ %= q;
 
         // verify that the challenges are the same
-        let v1 = *c == c_;
+        if *c != c_ {
+            return Err(ProofFailure::Challenge);
+        }
 
         // verify that the responses are the same
-        let v2 = g_pow_d == b.modmul(&h_pow_c, p);
+        if g_pow_d != b.modmul(&h_pow_c, p) {
+            return Err(ProofFailure::Response);
+        }
+        Ok(())
+    }
+
+    /// Generates the proof in an arbitrary group (see: `generate_with_hash`).
+    pub fn generate_in_group<G: Group>(
+        group: &G,
+        sk: &G::Scalar,
+        pk_share: &G::Element,
+        r: &G::Scalar,
+        id: &[u8],
+        hash: HashFunction,
+    ) -> GroupProof<G::Scalar> {
+        // the commitment
+        let b = group.exp(&group.generator(), r);
+
+        // compute challenge -> hash public values (hash(unique_id, h, b) mod q)
+        let c = Helper::hash_key_gen_proof_inputs_in_group(group, id, "keygen", pk_share, &b, hash);
+        let c = group.scalar_from_biguint(&c);
 
-        v1 && v2
+        // compute the response: d = r + c*sk mod q
+        let d = group.scalar_add(r, &group.scalar_mul(&c, sk));
+
+        GroupProof {
+            challenge: c,
+            response: d,
+        }
+    }
+
+    /// Verifies the proof in an arbitrary group (see: `verify_with_hash`).
+    pub fn verify_in_group<G: Group>(
+        group: &G,
+        pk_share: &G::Element,
+        proof: &GroupProof<G::Scalar>,
+        id: &[u8],
+        hash: HashFunction,
+    ) -> bool {
+        Self::check_in_group(group, pk_share, proof, id, hash).is_ok()
+    }
+
+    /// Verifies the proof in an arbitrary group like `verify_in_group`, returns the check which failed.
+    pub fn check_in_group<G: Group>(
+        group: &G,
+        pk_share: &G::Element,
+        proof: &GroupProof<G::Scalar>,
+        id: &[u8],
+        hash: HashFunction,
+    ) -> Result<(), ProofFailure> {
+        if !group.is_element(pk_share) {
+            return Err(ProofFailure::PublicKey);
+        }
+
+        // recompute b = g^d / h^c
+        let g_pow_d = group.exp(&group.generator(), &proof.response);
+        let h_pow_c = group.exp(pk_share, &proof.challenge);
+        let b = group.op(&g_pow_d, &group.inverse(&h_pow_c));
+
+        // recompute the hash and verify that the challenges are the same
+        let c_ =
+            Helper::hash_key_gen_proof_inputs_in_group(group, id, "keygen", pk_share, &b, hash);
+        if group.scalar_from_biguint(&c_) != proof.challenge {
+            return Err(ProofFailure::Challenge);
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{helper::Helper, proofs::keygen::KeyGenerationProof, random::Random};
+    use crate::{
+        hash::HashFunction,
+        helper::Helper,
+        proofs::{keygen::KeyGenerationProof, ProofFailure},
+        random::Random,
+    };
     use num_bigint::BigUint;
 
     #[test]
@@ -130,4 +240,116 @@ mod tests {
         let is_correct = KeyGenerationProof::verify(&params, &pk.h, &proof, sealer_id);
         assert!(is_correct);
     }
+
+    #[test]
+    fn it_should_verify_keygen_proof_with_the_same_hash_function() {
+        let sealer_id = "Charlie".as_bytes();
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let r = Random::get_random_less_than(&params.q());
+
+        let proof = KeyGenerationProof::generate_with_hash(
+            &params,
+            &sk.x,
+            &pk.h,
+            &r,
+            sealer_id,
+            HashFunction::Sha256,
+        );
+        assert!(KeyGenerationProof::verify_with_hash(
+            &params,
+            &pk.h,
+            &proof,
+            sealer_id,
+            HashFunction::Sha256
+        ));
+
+        // the challenge of another hash function differs
+        assert!(!KeyGenerationProof::verify(
+            &params, &pk.h, &proof, sealer_id
+        ));
+        assert!(!KeyGenerationProof::verify_with_hash(
+            &params,
+            &pk.h,
+            &proof,
+            sealer_id,
+            HashFunction::Sha3_256
+        ));
+    }
+
+    #[test]
+    fn it_should_return_the_failed_check_of_keygen_proof() {
+        let sealer_id = "Charlie".as_bytes();
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let r = Random::get_random_less_than(&params.q());
+        let hash = HashFunction::Blake2b;
+
+        let proof = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &r, sealer_id);
+        assert_eq!(
+            KeyGenerationProof::check_with_hash(&params, &pk.h, &proof, sealer_id, hash),
+            Ok(())
+        );
+
+        // the proof of another sealer
+        assert_eq!(
+            KeyGenerationProof::check_with_hash(&params, &pk.h, &proof, b"Bob", hash),
+            Err(ProofFailure::Challenge)
+        );
+
+        // a public key share without inverse doesn't panic
+        let zero = BigUint::from(0u32);
+        assert_eq!(
+            KeyGenerationProof::check_with_hash(&params, &zero, &proof, sealer_id, hash),
+            Err(ProofFailure::PublicKey)
+        );
+    }
+
+    #[test]
+    fn it_should_generate_the_same_keygen_proof_in_the_integer_group() {
+        let sealer_id = "Charlie".as_bytes();
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let r = Random::get_random_less_than(&params.q());
+        let hash = HashFunction::Sha256;
+
+        let expected =
+            KeyGenerationProof::generate_with_hash(&params, &sk.x, &pk.h, &r, sealer_id, hash);
+        let proof =
+            KeyGenerationProof::generate_in_group(&params, &sk.x, &pk.h, &r, sealer_id, hash);
+        assert_eq!(proof.challenge, expected.challenge);
+        assert_eq!(proof.response, expected.response);
+        assert!(KeyGenerationProof::verify_in_group(
+            &params, &pk.h, &proof, sealer_id, hash
+        ));
+
+        // the proof of another sealer and a public key share which isn't a group element
+        assert_eq!(
+            KeyGenerationProof::check_in_group(&params, &pk.h, &proof, b"Bob", hash),
+            Err(ProofFailure::Challenge)
+        );
+        assert_eq!(
+            KeyGenerationProof::check_in_group(&params, &params.p, &proof, sealer_id, hash),
+            Err(ProofFailure::PublicKey)
+        );
+    }
+
+    #[cfg(feature = "ristretto")]
+    #[test]
+    fn it_should_verify_keygen_proof_in_the_ristretto_group() {
+        use crate::types::{Group, Ristretto};
+
+        let group = Ristretto;
+        let sealer_id = "Charlie".as_bytes();
+        let hash = HashFunction::Blake2b;
+        let sk = Random::get_random_scalar(&group);
+        let pk = group.exp(&group.generator(), &sk);
+        let r = Random::get_random_scalar(&group);
+
+        let proof = KeyGenerationProof::generate_in_group(&group, &sk, &pk, &r, sealer_id, hash);
+        assert!(KeyGenerationProof::verify_in_group(
+            &group, &pk, &proof, sealer_id, hash
+        ));
+        assert_eq!(
+            KeyGenerationProof::check_in_group(&group, &pk, &proof, b"Bob", hash),
+            Err(ProofFailure::Challenge)
+        );
+    }
 }
@@ -0,0 +1,537 @@
+use crate::{
+    hash::HashFunction,
+    helper::Helper,
+    types::{Cipher, ElGamalParams, Group, GroupCipher, ModuloOperations, PublicKey},
+};
+use alloc::{vec, vec::Vec};
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// A disjunctive Chaum-Pedersen proof (Cramer, Damgård & Schoenmakers) that a cipher is an
+/// encryption of g^m for one message m of a list of valid messages, without revealing which one.
+///
+/// The proof contains one challenge and response per valid message. All but the branch of the
+/// encrypted message are simulated, the challenges of all branches sum up to the challenge c.
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct MembershipProof {
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex::vec"))]
+    pub challenges: Vec<BigUint>,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex::vec"))]
+    pub responses: Vec<BigUint>,
+}
+
+/// The membership proof in an arbitrary group (see: `MembershipProof`).
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct GroupMembershipProof<S> {
+    pub challenges: Vec<S>,
+    pub responses: Vec<S>,
+}
+
+impl MembershipProof {
+    /// Returns the commitments (t_a, t_b) = (g^s / a^c, h^s / (b / g^m)^c) of a branch.
+    /// For the real branch this is equal to (g^w, h^w).
+    fn commitments(
+        params: &ElGamalParams,
+        pk: &PublicKey,
+        cipher: &Cipher,
+        message: &BigUint,
+        challenge: &BigUint,
+        response: &BigUint,
+    ) -> Option<(BigUint, BigUint)> {
+        let g = &params.g;
+        let p = &params.p;
+        let h = &pk.h;
+
+        let g_pow_m = g.modpow(message, p);
+        let b_div_g_pow_m = cipher.b.moddiv(&g_pow_m, p)?;
+        let t_a = g
+            .modpow(response, p)
+            .moddiv(&cipher.a.modpow(challenge, p), p)?;
+        let t_b = h
+            .modpow(response, p)
+            .moddiv(&b_div_g_pow_m.modpow(challenge, p), p)?;
+        Some((t_a, t_b))
+    }
+
+    /// Computes the challenge c = hash(id, "membership", h, cipher, t_a's, t_b's) mod q.
+    fn challenge(
+        pk: &PublicKey,
+        cipher: &Cipher,
+        vec_t_a: Vec<BigUint>,
+        vec_t_b: Vec<BigUint>,
+        id: &[u8],
+    ) -> BigUint {
+        let mut c = Helper::hash_decryption_proof_inputs(
+            id,
+            "membership",
+            &pk.h,
+            vec![cipher.clone()],
+            vec_t_a,
+            vec_t_b,
+            HashFunction::Blake2b,
+        );
+        c %= pk.params.q();
+        c
+    }
+
+    /// Generates a proof that the cipher (a, b) = (g^r, h^r * g^m) encrypts the message m = messages[index].
+    ///
+    /// The id (e.g. the voter's account id) is part of the challenge. Therefore, the proof only verifies for the same id.
+    ///
+    /// Step by Step:
+    /// 1. simulate all other branches i: t_a_i = g^s_i / a^c_i, t_b_i = h^s_i / (b / g^m_i)^c_i
+    ///    with the random challenges and responses (c_i, s_i) of `simulated`
+    /// 2. commit to the real branch: t_a = g^w, t_b = h^w
+    /// 3. compute the challenge c = hash(id, "membership", h, cipher, t_a's, t_b's)
+    /// 4. compute the challenge of the real branch: c_index = c - sum(c_i) mod q
+    /// 5. compute the response of the real branch: s_index = w + c_index * r mod q
+    ///
+    /// ## Arguments
+    ///
+    /// * `params`    - The public parameters
+    /// * `pk`        - The public key used to encrypt the message
+    /// * `cipher`    - The encryption of g^m
+    /// * `r`         - The random number used to encrypt the message
+    /// * `messages`  - The list of valid messages
+    /// * `index`     - The position of the encrypted message in the list of valid messages
+    /// * `w`         - The random value of the commitment of the real branch from Z_q
+    /// * `simulated` - The random (challenge, response) pairs from Z_q of all other branches (messages.len() - 1)
+    /// * `id`        - The context of the proof, e.g. the voter's account id
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate(
+        params: &ElGamalParams,
+        pk: &PublicKey,
+        cipher: &Cipher,
+        r: &BigUint,
+        messages: &[BigUint],
+        index: usize,
+        w: &BigUint,
+        simulated: &[(BigUint, BigUint)],
+        id: &[u8],
+    ) -> MembershipProof {
+        assert!(index < messages.len(), "index out of bounds!");
+        assert!(
+            simulated.len() + 1 == messages.len(),
+            "one simulated branch is required per message except the encrypted one!"
+        );
+        let g = &params.g;
+        let p = &params.p;
+        let q = &params.q();
+        let h = &pk.h;
+
+        let mut challenges: Vec<BigUint> = Vec::with_capacity(messages.len());
+        let mut responses: Vec<BigUint> = Vec::with_capacity(messages.len());
+        let mut vec_t_a: Vec<BigUint> = Vec::with_capacity(messages.len());
+        let mut vec_t_b: Vec<BigUint> = Vec::with_capacity(messages.len());
+        let mut simulated = simulated.iter();
+
+        for (i, message) in messages.iter().enumerate() {
+            if i == index {
+                // the real branch: the challenge and response are computed afterwards
+                challenges.push(BigUint::zero());
+                responses.push(BigUint::zero());
+                vec_t_a.push(g.modpow(w, p));
+                vec_t_b.push(h.modpow(w, p));
+            } else {
+                let (c_i, s_i) = simulated.next().expect("not enough simulated branches!");
+                let (t_a, t_b) =
+                    MembershipProof::commitments(params, pk, cipher, message, c_i, s_i)
+                        .expect("cannot compute mod_inverse in mod_div!");
+                challenges.push(c_i.clone());
+                responses.push(s_i.clone());
+                vec_t_a.push(t_a);
+                vec_t_b.push(t_b);
+            }
+        }
+
+        // the challenges of all branches sum up to the challenge c
+        let c = MembershipProof::challenge(pk, cipher, vec_t_a, vec_t_b, id);
+        let sum_simulated = challenges
+            .iter()
+            .fold(BigUint::zero(), |sum, c_i| sum.modadd(c_i, q));
+        let c_index = c.modsub(&sum_simulated, q);
+        responses[index] = w.modadd(&c_index.modmul(r, q), q);
+        challenges[index] = c_index;
+
+        MembershipProof {
+            challenges,
+            responses,
+        }
+    }
+
+    /// Verifies a proof that the cipher is an encryption of g^m for one of the valid messages.
+    ///
+    /// Step by Step:
+    /// 1. recompute the commitments of all branches: t_a_i = g^s_i / a^c_i, t_b_i = h^s_i / (b / g^m_i)^c_i
+    /// 2. recompute the challenge c
+    /// 3. verify that the challenges of all branches sum up to c
+    pub fn verify(
+        params: &ElGamalParams,
+        pk: &PublicKey,
+        cipher: &Cipher,
+        messages: &[BigUint],
+        proof: &MembershipProof,
+        id: &[u8],
+    ) -> bool {
+        if messages.is_empty()
+            || proof.challenges.len() != messages.len()
+            || proof.responses.len() != messages.len()
+        {
+            return false;
+        }
+        let q = &params.q();
+
+        let mut vec_t_a: Vec<BigUint> = Vec::with_capacity(messages.len());
+        let mut vec_t_b: Vec<BigUint> = Vec::with_capacity(messages.len());
+        let branches = messages
+            .iter()
+            .zip(proof.challenges.iter())
+            .zip(proof.responses.iter());
+        for ((message, c_i), s_i) in branches {
+            match MembershipProof::commitments(params, pk, cipher, message, c_i, s_i) {
+                Some((t_a, t_b)) => {
+                    vec_t_a.push(t_a);
+                    vec_t_b.push(t_b);
+                }
+                None => return false,
+            }
+        }
+
+        let c = MembershipProof::challenge(pk, cipher, vec_t_a, vec_t_b, id);
+        let sum = proof
+            .challenges
+            .iter()
+            .fold(BigUint::zero(), |sum, c_i| sum.modadd(c_i, q));
+        c == sum
+    }
+
+    /// Returns the commitments (t_a, t_b) = (g^s / a^c, h^s / (b / g^m)^c) of a branch
+    /// in an arbitrary group.
+    fn commitments_in_group<G: Group>(
+        group: &G,
+        h: &G::Element,
+        cipher: &GroupCipher<G::Element>,
+        message: &G::Scalar,
+        challenge: &G::Scalar,
+        response: &G::Scalar,
+    ) -> (G::Element, G::Element) {
+        let g = &group.generator();
+        let g_pow_m = group.exp(g, message);
+        let b_div_g_pow_m = group.op(&cipher.b, &group.inverse(&g_pow_m));
+        let t_a = group.op(
+            &group.exp(g, response),
+            &group.inverse(&group.exp(&cipher.a, challenge)),
+        );
+        let t_b = group.op(
+            &group.exp(h, response),
+            &group.inverse(&group.exp(&b_div_g_pow_m, challenge)),
+        );
+        (t_a, t_b)
+    }
+
+    /// Computes the challenge c = hash(id, "membership", h, cipher, t_a's, t_b's) mod q
+    /// in an arbitrary group.
+    fn challenge_in_group<G: Group>(
+        group: &G,
+        h: &G::Element,
+        cipher: &GroupCipher<G::Element>,
+        vec_t_a: &[G::Element],
+        vec_t_b: &[G::Element],
+        id: &[u8],
+    ) -> G::Scalar {
+        let c = Helper::hash_decryption_proof_inputs_in_group(
+            group,
+            id,
+            "membership",
+            h,
+            core::slice::from_ref(cipher),
+            vec_t_a,
+            vec_t_b,
+            HashFunction::Blake2b,
+        );
+        group.scalar_from_biguint(&c)
+    }
+
+    /// Generates the proof that the cipher (a, b) = (g^r, h^r * g^m) encrypts the message
+    /// m = messages\[index\] in an arbitrary group (see: `generate`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_in_group<G: Group>(
+        group: &G,
+        h: &G::Element,
+        cipher: &GroupCipher<G::Element>,
+        r: &G::Scalar,
+        messages: &[G::Scalar],
+        index: usize,
+        w: &G::Scalar,
+        simulated: &[(G::Scalar, G::Scalar)],
+        id: &[u8],
+    ) -> GroupMembershipProof<G::Scalar> {
+        assert!(index < messages.len(), "index out of bounds!");
+        assert!(
+            simulated.len() + 1 == messages.len(),
+            "one simulated branch is required per message except the encrypted one!"
+        );
+        let zero = group.scalar_from_biguint(&BigUint::zero());
+
+        let mut challenges: Vec<G::Scalar> = Vec::with_capacity(messages.len());
+        let mut responses: Vec<G::Scalar> = Vec::with_capacity(messages.len());
+        let mut vec_t_a: Vec<G::Element> = Vec::with_capacity(messages.len());
+        let mut vec_t_b: Vec<G::Element> = Vec::with_capacity(messages.len());
+        let mut simulated = simulated.iter();
+
+        for (i, message) in messages.iter().enumerate() {
+            if i == index {
+                // the real branch: the challenge and response are computed afterwards
+                challenges.push(zero.clone());
+                responses.push(zero.clone());
+                vec_t_a.push(group.exp(&group.generator(), w));
+                vec_t_b.push(group.exp(h, w));
+            } else {
+                let (c_i, s_i) = simulated.next().expect("not enough simulated branches!");
+                let (t_a, t_b) = Self::commitments_in_group(group, h, cipher, message, c_i, s_i);
+                challenges.push(c_i.clone());
+                responses.push(s_i.clone());
+                vec_t_a.push(t_a);
+                vec_t_b.push(t_b);
+            }
+        }
+
+        // the challenges of all branches sum up to the challenge c
+        let c = Self::challenge_in_group(group, h, cipher, &vec_t_a, &vec_t_b, id);
+        let sum_simulated = challenges
+            .iter()
+            .fold(zero, |sum, c_i| group.scalar_add(&sum, c_i));
+        let c_index = group.scalar_sub(&c, &sum_simulated);
+        responses[index] = group.scalar_add(w, &group.scalar_mul(&c_index, r));
+        challenges[index] = c_index;
+
+        GroupMembershipProof {
+            challenges,
+            responses,
+        }
+    }
+
+    /// Verifies the proof that the cipher is an encryption of g^m for one of the valid messages
+    /// in an arbitrary group (see: `verify`). Ciphers which aren't group elements are rejected.
+    pub fn verify_in_group<G: Group>(
+        group: &G,
+        h: &G::Element,
+        cipher: &GroupCipher<G::Element>,
+        messages: &[G::Scalar],
+        proof: &GroupMembershipProof<G::Scalar>,
+        id: &[u8],
+    ) -> bool {
+        if messages.is_empty()
+            || proof.challenges.len() != messages.len()
+            || proof.responses.len() != messages.len()
+        {
+            return false;
+        }
+        if !group.is_element(&cipher.a) || !group.is_element(&cipher.b) {
+            return false;
+        }
+
+        let mut vec_t_a: Vec<G::Element> = Vec::with_capacity(messages.len());
+        let mut vec_t_b: Vec<G::Element> = Vec::with_capacity(messages.len());
+        let branches = messages
+            .iter()
+            .zip(proof.challenges.iter())
+            .zip(proof.responses.iter());
+        for ((message, c_i), s_i) in branches {
+            let (t_a, t_b) = Self::commitments_in_group(group, h, cipher, message, c_i, s_i);
+            vec_t_a.push(t_a);
+            vec_t_b.push(t_b);
+        }
+
+        let c = Self::challenge_in_group(group, h, cipher, &vec_t_a, &vec_t_b, id);
+        let zero = group.scalar_from_biguint(&BigUint::zero());
+        let sum = proof
+            .challenges
+            .iter()
+            .fold(zero, |sum, c_i| group.scalar_add(&sum, c_i));
+        c == sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        encryption::ElGamal,
+        helper::Helper,
+        proofs::membership::MembershipProof,
+        random::Random,
+        types::{ElGamalParams, GroupCipher},
+    };
+    use alloc::vec::Vec;
+    use num_bigint::BigUint;
+
+    fn simulated_branches(params: &ElGamalParams, number: usize) -> Vec<(BigUint, BigUint)> {
+        let q = params.q();
+        (0..number)
+            .map(|_| {
+                (
+                    Random::get_random_less_than(&q),
+                    Random::get_random_less_than(&q),
+                )
+            })
+            .collect()
+    }
+
+    fn to_messages(values: &[u32]) -> Vec<BigUint> {
+        values.iter().map(|value| BigUint::from(*value)).collect()
+    }
+
+    #[test]
+    fn it_should_verify_membership_proof() {
+        let voter_id = "Alice".as_bytes();
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = params.q();
+        let messages = to_messages(&[0, 1, 2, 5]);
+
+        for (index, message) in messages.iter().enumerate() {
+            let r = Random::get_random_less_than(&q);
+            let w = Random::get_random_less_than(&q);
+            let cipher = ElGamal::encrypt_encode(message, &r, &pk);
+            let simulated = simulated_branches(&params, messages.len() - 1);
+            let proof = MembershipProof::generate(
+                &params, &pk, &cipher, &r, &messages, index, &w, &simulated, voter_id,
+            );
+            assert!(MembershipProof::verify(
+                &params, &pk, &cipher, &messages, &proof, voter_id
+            ));
+        }
+    }
+
+    #[test]
+    fn it_should_not_verify_membership_proof_of_invalid_message() {
+        let voter_id = "Alice".as_bytes();
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = params.q();
+        let messages = to_messages(&[0, 1]);
+
+        // the cipher encrypts 2 but the prover claims it is the first message (0)
+        let r = Random::get_random_less_than(&q);
+        let w = Random::get_random_less_than(&q);
+        let cipher = ElGamal::encrypt_encode(&BigUint::from(2u32), &r, &pk);
+        let simulated = simulated_branches(&params, 1);
+        let proof = MembershipProof::generate(
+            &params, &pk, &cipher, &r, &messages, 0, &w, &simulated, voter_id,
+        );
+        assert!(!MembershipProof::verify(
+            &params, &pk, &cipher, &messages, &proof, voter_id
+        ));
+    }
+
+    #[test]
+    fn it_should_not_verify_membership_proof_of_another_voter() {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = params.q();
+        let messages = to_messages(&[0, 1]);
+
+        let r = Random::get_random_less_than(&q);
+        let w = Random::get_random_less_than(&q);
+        let cipher = ElGamal::encrypt_encode(&BigUint::from(1u32), &r, &pk);
+        let simulated = simulated_branches(&params, 1);
+        let proof = MembershipProof::generate(
+            &params,
+            &pk,
+            &cipher,
+            &r,
+            &messages,
+            1,
+            &w,
+            &simulated,
+            "Alice".as_bytes(),
+        );
+
+        // the proof is bound to Alice and to the list of valid messages
+        assert!(!MembershipProof::verify(
+            &params,
+            &pk,
+            &cipher,
+            &messages,
+            &proof,
+            "Bob".as_bytes()
+        ));
+        let other_messages = to_messages(&[1, 2]);
+        assert!(!MembershipProof::verify(
+            &params,
+            &pk,
+            &cipher,
+            &other_messages,
+            &proof,
+            "Alice".as_bytes()
+        ));
+    }
+
+    #[test]
+    fn it_should_generate_the_same_membership_proof_in_the_integer_group() {
+        let voter_id = "Alice".as_bytes();
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = params.q();
+        let messages = to_messages(&[0, 1, 2]);
+        let r = Random::get_random_less_than(&q);
+        let w = Random::get_random_less_than(&q);
+        let cipher = ElGamal::encrypt_encode(&messages[2], &r, &pk);
+        let simulated = simulated_branches(&params, messages.len() - 1);
+        let expected = MembershipProof::generate(
+            &params, &pk, &cipher, &r, &messages, 2, &w, &simulated, voter_id,
+        );
+
+        let cipher: GroupCipher<BigUint> = cipher.into();
+        let proof = MembershipProof::generate_in_group(
+            &params, &pk.h, &cipher, &r, &messages, 2, &w, &simulated, voter_id,
+        );
+        assert_eq!(proof.challenges, expected.challenges);
+        assert_eq!(proof.responses, expected.responses);
+        assert!(MembershipProof::verify_in_group(
+            &params, &pk.h, &cipher, &messages, &proof, voter_id
+        ));
+        assert!(!MembershipProof::verify_in_group(
+            &params, &pk.h, &cipher, &messages, &proof, b"Bob"
+        ));
+    }
+
+    #[cfg(feature = "ristretto")]
+    #[test]
+    fn it_should_verify_membership_proof_in_the_ristretto_group() {
+        use crate::types::{Group, Ristretto};
+
+        let group = Ristretto;
+        let voter_id = "Alice".as_bytes();
+        let h = group.exp(&group.generator(), &Random::get_random_scalar(&group));
+        let messages: Vec<_> = to_messages(&[0, 1])
+            .iter()
+            .map(|m| group.scalar_from_biguint(m))
+            .collect();
+        let simulated = vec![(
+            Random::get_random_scalar(&group),
+            Random::get_random_scalar(&group),
+        )];
+
+        let r = Random::get_random_scalar(&group);
+        let w = Random::get_random_scalar(&group);
+        let m = group.exp(&group.generator(), &messages[1]);
+        let cipher = ElGamal::encrypt_in_group(&group, &h, &m, &r);
+        let proof = MembershipProof::generate_in_group(
+            &group, &h, &cipher, &r, &messages, 1, &w, &simulated, voter_id,
+        );
+        assert!(MembershipProof::verify_in_group(
+            &group, &h, &cipher, &messages, &proof, voter_id
+        ));
+
+        // the cipher of a message which isn't valid
+        let invalid = ElGamal::encode_in_group(&group, &BigUint::from(2u32));
+        let cipher = ElGamal::encrypt_in_group(&group, &h, &invalid, &r);
+        let proof = MembershipProof::generate_in_group(
+            &group, &h, &cipher, &r, &messages, 1, &w, &simulated, voter_id,
+        );
+        assert!(!MembershipProof::verify_in_group(
+            &group, &h, &cipher, &messages, &proof, voter_id
+        ));
+    }
+}
@@ -12,3 +12,48 @@ pub mod decryption;
 #[allow(clippy::many_single_char_names)]
 #[macro_use]
 pub mod re_encryption;
+
+#[allow(clippy::many_single_char_names)]
+#[macro_use]
+pub mod encryption;
+
+#[allow(clippy::many_single_char_names)]
+#[macro_use]
+pub mod key_switch;
+
+#[allow(clippy::many_single_char_names)]
+#[macro_use]
+pub mod pet;
+
+#[allow(clippy::many_single_char_names)]
+#[macro_use]
+pub mod membership;
+
+#[allow(clippy::many_single_char_names)]
+#[macro_use]
+pub mod wire;
+
+/// The check of a proof which doesn't hold (see: the `check_with_hash` functions of the proofs),
+/// e.g. to show public observers which equation of an invalid proof failed.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub enum ProofFailure {
+    /// the number of encryptions and partial decryptions differ, or there are none
+    InputLength,
+    /// the public key (share) has no inverse mod p, i.e. the commitment can't be recomputed
+    PublicKey,
+    /// a partial decryption isn't an element of the group, e.g. it has a component of order 2
+    Group,
+    /// the recomputed challenge differs from the challenge of the proof
+    Challenge,
+    /// the response doesn't satisfy the verification equation with the recomputed commitment
+    Response,
+}
+
+/// A proof of knowledge (challenge, response) in an arbitrary group (see: `Group`), e.g. of the
+/// `*_in_group` key generation, encryption, decryption and plaintext equivalence test proofs.
+/// In the integer group, the values are equal to the ones of the corresponding BigUint proof.
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct GroupProof<S> {
+    pub challenge: S,
+    pub response: S,
+}
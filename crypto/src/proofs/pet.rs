@@ -0,0 +1,484 @@
+use crate::{
+    helper::Helper,
+    proofs::GroupProof,
+    types::{Cipher, ElGamalParams, Group, GroupCipher, ModuloOperations},
+};
+use alloc::vec::Vec;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// A distributed plaintext equivalence test (PET) of two ciphers e_1 and e_2 (Jakobsson & Juels).
+///
+/// Every party blinds the quotient e_1 / e_2 with a secret random exponent z_i and proves
+/// that both components of the quotient were raised to the same exponent.
+/// The combined blinded quotient is decrypted jointly: it decrypts to 1 if e_1 and e_2 encrypt the same plaintext,
+/// otherwise, it decrypts to a random value which reveals nothing about the plaintexts.
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PlaintextEquivalenceProof {
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
+    pub challenge: BigUint,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
+    pub response: BigUint,
+}
+
+impl PlaintextEquivalenceProof {
+    /// Returns the quotient of two ciphers: e_1 / e_2 = (a_1 / a_2, b_1 / b_2).
+    /// The quotient is an encryption of 1 if both ciphers encrypt the same plaintext.
+    pub fn quotient(e_1: &Cipher, e_2: &Cipher, p: &BigUint) -> Cipher {
+        Cipher {
+            a: e_1
+                .a
+                .moddiv(&e_2.a, p)
+                .expect("cannot compute mod_inverse in mod_div!"),
+            b: e_1
+                .b
+                .moddiv(&e_2.b, p)
+                .expect("cannot compute mod_inverse in mod_div!"),
+        }
+    }
+
+    /// Blinds the quotient with the secret random exponent z: (a^z, b^z).
+    pub fn blind(quotient: &Cipher, z: &BigUint, p: &BigUint) -> Cipher {
+        Cipher {
+            a: quotient.a.modpow(z, p),
+            b: quotient.b.modpow(z, p),
+        }
+    }
+
+    /// Combines the blinded quotients of all parties by multiplying them component-wise.
+    pub fn combine(blinded_shares: &[Cipher], p: &BigUint) -> Cipher {
+        blinded_shares.iter().fold(
+            Cipher {
+                a: BigUint::one(),
+                b: BigUint::one(),
+            },
+            |product, share| Cipher {
+                a: product.a.modmul(&share.a, p),
+                b: product.b.modmul(&share.b, p),
+            },
+        )
+    }
+
+    /// Returns true if the decrypted combined blinded quotient shows that
+    /// both ciphers encrypt the same plaintext.
+    pub fn is_equivalent(plaintext: &BigUint) -> bool {
+        plaintext.is_one()
+    }
+
+    /// Generates a proof that the blinded quotient was computed correctly,
+    /// i.e., a NIZKP of knowledge of z satisfying blinded = (a^z, b^z) for quotient = (a, b).
+    ///
+    /// Step by Step:
+    /// 1. compute the commitments t_a = a^r, t_b = b^r
+    /// 2. compute the challenge c
+    /// 3. compute the response s = r + c*z
+    pub fn generate(
+        params: &ElGamalParams,
+        z: &BigUint,
+        r: &BigUint,
+        quotient: &Cipher,
+        blinded: &Cipher,
+        id: &[u8],
+    ) -> PlaintextEquivalenceProof {
+        // system parameters
+        let q = &params.q();
+        let p = &params.p;
+
+        // the commitments
+        let t_a = quotient.a.modpow(r, p);
+        let t_b = quotient.b.modpow(r, p);
+
+        // compute challenge
+        // hash public values (hash(unique_id, constant, quotient, blinded, t_a, t_b) mod q)
+        let mut c = Helper::hash_pet_proof_inputs(id, "pet", quotient, blinded, &t_a, &t_b);
+        c %= q;
+
+        // compute the response: s = r + c * z mod q
+        let s = r.modadd(&c.modmul(z, q), q);
+
+        PlaintextEquivalenceProof {
+            challenge: c,
+            response: s,
+        }
+    }
+
+    /// Verifies a proof that the blinded quotient was computed correctly.
+    /// A blinded quotient of (1, 1), i.e., z = 0, is rejected since it would make any two ciphers equivalent.
+    ///
+    /// Step by Step:
+    /// 1. recompute the commitments t_a = a^s / blinded.a^c, t_b = b^s / blinded.b^c
+    /// 2. recompute the challenge c
+    /// 3. verify that the challenge is correct
+    pub fn verify(
+        params: &ElGamalParams,
+        proof: &PlaintextEquivalenceProof,
+        quotient: &Cipher,
+        blinded: &Cipher,
+        id: &[u8],
+    ) -> bool {
+        if blinded.a.is_one() && blinded.b.is_one() {
+            return false;
+        }
+        if blinded.a.is_zero() || blinded.b.is_zero() {
+            return false;
+        }
+
+        // system parameters
+        let q = &params.q();
+        let p = &params.p;
+
+        // the proof
+        let c = &proof.challenge;
+        let s = &proof.response;
+
+        // the recomputed commitments
+        let t_a = quotient.a.modpow(s, p).moddiv(&blinded.a.modpow(c, p), p);
+        let t_b = quotient.b.modpow(s, p).moddiv(&blinded.b.modpow(c, p), p);
+        let (t_a, t_b) = match (t_a, t_b) {
+            (Some(t_a), Some(t_b)) => (t_a, t_b),
+            _ => return false,
+        };
+
+        // recompute the challenge
+        // hash public values (hash(unique_id, constant, quotient, blinded, t_a, t_b) mod q)
+        let mut recomputed_c =
+            Helper::hash_pet_proof_inputs(id, "pet", quotient, blinded, &t_a, &t_b);
+        recomputed_c %= q;
+
+        // verify that the challenges are the same
+        &recomputed_c == c
+    }
+
+    /// Returns the quotient of two ciphers in an arbitrary group: e_1 / e_2 = (a_1 / a_2, b_1 / b_2).
+    /// Both ciphers must consist of group elements (see: `Group::is_element`).
+    pub fn quotient_in_group<G: Group>(
+        group: &G,
+        e_1: &GroupCipher<G::Element>,
+        e_2: &GroupCipher<G::Element>,
+    ) -> GroupCipher<G::Element> {
+        GroupCipher {
+            a: group.op(&e_1.a, &group.inverse(&e_2.a)),
+            b: group.op(&e_1.b, &group.inverse(&e_2.b)),
+        }
+    }
+
+    /// Blinds the quotient with the secret random exponent z in an arbitrary group: (a^z, b^z).
+    pub fn blind_in_group<G: Group>(
+        group: &G,
+        quotient: &GroupCipher<G::Element>,
+        z: &G::Scalar,
+    ) -> GroupCipher<G::Element> {
+        GroupCipher {
+            a: group.exp(&quotient.a, z),
+            b: group.exp(&quotient.b, z),
+        }
+    }
+
+    /// Generates the proof that the blinded quotient was computed correctly
+    /// in an arbitrary group (see: `generate`).
+    pub fn generate_in_group<G: Group>(
+        group: &G,
+        z: &G::Scalar,
+        r: &G::Scalar,
+        quotient: &GroupCipher<G::Element>,
+        blinded: &GroupCipher<G::Element>,
+        id: &[u8],
+    ) -> GroupProof<G::Scalar> {
+        // the commitments
+        let t_a = group.exp(&quotient.a, r);
+        let t_b = group.exp(&quotient.b, r);
+
+        // compute challenge
+        // hash public values (hash(unique_id, constant, quotient, blinded, t_a, t_b) mod q)
+        let c =
+            Helper::hash_pet_proof_inputs_in_group(group, id, "pet", quotient, blinded, &t_a, &t_b);
+        let c = group.scalar_from_biguint(&c);
+
+        // compute the response: s = r + c * z mod q
+        let s = group.scalar_add(r, &group.scalar_mul(&c, z));
+
+        GroupProof {
+            challenge: c,
+            response: s,
+        }
+    }
+
+    /// Verifies the proof that the blinded quotient was computed correctly
+    /// in an arbitrary group (see: `verify`).
+    /// A blinded quotient of (1, 1) and values which aren't group elements are rejected.
+    pub fn verify_in_group<G: Group>(
+        group: &G,
+        proof: &GroupProof<G::Scalar>,
+        quotient: &GroupCipher<G::Element>,
+        blinded: &GroupCipher<G::Element>,
+        id: &[u8],
+    ) -> bool {
+        let identity = group.identity();
+        if blinded.a == identity && blinded.b == identity {
+            return false;
+        }
+        let is_element = [&quotient.a, &quotient.b, &blinded.a, &blinded.b]
+            .iter()
+            .all(|value| group.is_element(value));
+        if !is_element {
+            return false;
+        }
+        let c = &proof.challenge;
+        let s = &proof.response;
+
+        // the recomputed commitments: t_a = a^s / blinded.a^c, t_b = b^s / blinded.b^c
+        let t_a = group.op(
+            &group.exp(&quotient.a, s),
+            &group.inverse(&group.exp(&blinded.a, c)),
+        );
+        let t_b = group.op(
+            &group.exp(&quotient.b, s),
+            &group.inverse(&group.exp(&blinded.b, c)),
+        );
+
+        // recompute the challenge and verify that the challenges are the same
+        let recomputed_c =
+            Helper::hash_pet_proof_inputs_in_group(group, id, "pet", quotient, blinded, &t_a, &t_b);
+        group.scalar_from_biguint(&recomputed_c) == *c
+    }
+
+    /// Returns the indices of the decrypted combined blinded quotients which show plaintext equivalence.
+    /// Used during the tally to keep only the ballots cast with a valid credential.
+    pub fn equivalent_indices(plaintexts: &[BigUint]) -> Vec<usize> {
+        plaintexts
+            .iter()
+            .enumerate()
+            .filter(|(_, plaintext)| Self::is_equivalent(plaintext))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        encryption::ElGamal,
+        helper::Helper,
+        proofs::pet::PlaintextEquivalenceProof,
+        random::Random,
+        types::{Cipher, GroupCipher, ModuloOperations},
+    };
+    use alloc::vec::Vec;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn it_should_detect_equivalent_plaintexts() {
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let p = &params.p;
+
+        // two encryptions of the same credential
+        let credential = BigUint::from(4711u32);
+        let e_1 = ElGamal::encrypt_encode(&credential, &Random::get_random_less_than(q), &pk);
+        let e_2 = ElGamal::encrypt_encode(&credential, &Random::get_random_less_than(q), &pk);
+
+        let quotient = PlaintextEquivalenceProof::quotient(&e_1, &e_2, p);
+        let z = Random::get_random_less_than(q);
+        let blinded = PlaintextEquivalenceProof::blind(&quotient, &z, p);
+
+        let plaintext = ElGamal::decrypt(&blinded, &sk);
+        assert!(PlaintextEquivalenceProof::is_equivalent(&plaintext));
+    }
+
+    #[test]
+    fn it_should_reject_different_plaintexts() {
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let p = &params.p;
+
+        // the real and a fake credential
+        let real = BigUint::from(4711u32);
+        let fake = BigUint::from(1337u32);
+        let e_1 = ElGamal::encrypt_encode(&real, &Random::get_random_less_than(q), &pk);
+        let e_2 = ElGamal::encrypt_encode(&fake, &Random::get_random_less_than(q), &pk);
+
+        let quotient = PlaintextEquivalenceProof::quotient(&e_1, &e_2, p);
+        let z = Random::get_random_less_than(q);
+        let blinded = PlaintextEquivalenceProof::blind(&quotient, &z, p);
+
+        let plaintext = ElGamal::decrypt(&blinded, &sk);
+        assert!(!PlaintextEquivalenceProof::is_equivalent(&plaintext));
+    }
+
+    #[test]
+    fn it_should_verify_pet_proofs_of_multiple_parties() {
+        let (params, _, _) = Helper::setup_sm_system();
+        let q = &params.q();
+        let p = &params.p;
+
+        // the system public key is the product of the public key shares of both sealers
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &Random::get_random_less_than(q));
+        let (charlie_pk, charlie_sk) =
+            Helper::generate_key_pair(&params, &Random::get_random_less_than(q));
+        let mut pk = bob_pk.clone();
+        pk.h = bob_pk.h.modmul(&charlie_pk.h, p);
+
+        let credential = BigUint::from(42u32);
+        let e_1 = ElGamal::encrypt_encode(&credential, &Random::get_random_less_than(q), &pk);
+        let e_2 = ElGamal::encrypt_encode(&credential, &Random::get_random_less_than(q), &pk);
+        let quotient = PlaintextEquivalenceProof::quotient(&e_1, &e_2, p);
+
+        // every sealer blinds the quotient and proves it
+        let mut shares: Vec<Cipher> = Vec::new();
+        for id in ["Bob".as_bytes(), "Charlie".as_bytes()].iter() {
+            let z = Random::get_random_less_than(q);
+            let r = Random::get_random_less_than(q);
+            let blinded = PlaintextEquivalenceProof::blind(&quotient, &z, p);
+            let proof =
+                PlaintextEquivalenceProof::generate(&params, &z, &r, &quotient, &blinded, id);
+            assert!(PlaintextEquivalenceProof::verify(
+                &params, &proof, &quotient, &blinded, id
+            ));
+            shares.push(blinded);
+        }
+
+        // decrypt the combined blinded quotient jointly
+        let combined = PlaintextEquivalenceProof::combine(&shares, p);
+        let decrypted_a = ElGamal::combine_partial_decrypted_a(
+            vec![
+                ElGamal::partial_decrypt_a(&combined, &bob_sk),
+                ElGamal::partial_decrypt_a(&combined, &charlie_sk),
+            ],
+            p,
+        );
+        let plaintext = ElGamal::partial_decrypt_b(&combined.b, &decrypted_a, p);
+        assert!(PlaintextEquivalenceProof::is_equivalent(&plaintext));
+        assert_eq!(
+            PlaintextEquivalenceProof::equivalent_indices(&[BigUint::from(7u32), plaintext]),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn it_should_reject_invalid_pet_proofs() {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let p = &params.p;
+        let id = "Bob".as_bytes();
+
+        let e_1 =
+            ElGamal::encrypt_encode(&BigUint::from(1u32), &Random::get_random_less_than(q), &pk);
+        let e_2 =
+            ElGamal::encrypt_encode(&BigUint::from(2u32), &Random::get_random_less_than(q), &pk);
+        let quotient = PlaintextEquivalenceProof::quotient(&e_1, &e_2, p);
+
+        // the blinded quotient was computed with a different exponent than the one proven
+        let z = Random::get_random_less_than(q);
+        let r = Random::get_random_less_than(q);
+        let blinded = PlaintextEquivalenceProof::blind(&quotient, &z, p);
+        let other = z.modadd(&BigUint::from(1u32), q);
+        let proof =
+            PlaintextEquivalenceProof::generate(&params, &other, &r, &quotient, &blinded, id);
+        assert!(!PlaintextEquivalenceProof::verify(
+            &params, &proof, &quotient, &blinded, id
+        ));
+
+        // a blinding exponent of zero would make any two ciphers equivalent
+        let zero = BigUint::from(0u32);
+        let blinded = PlaintextEquivalenceProof::blind(&quotient, &zero, p);
+        let proof =
+            PlaintextEquivalenceProof::generate(&params, &zero, &r, &quotient, &blinded, id);
+        assert!(!PlaintextEquivalenceProof::verify(
+            &params, &proof, &quotient, &blinded, id
+        ));
+    }
+
+    #[test]
+    fn it_should_generate_the_same_pet_proof_in_the_integer_group() {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let p = &params.p;
+        let id = "Bob".as_bytes();
+
+        let credential = BigUint::from(42u32);
+        let e_1 = ElGamal::encrypt_encode(&credential, &Random::get_random_less_than(q), &pk);
+        let e_2 = ElGamal::encrypt_encode(&credential, &Random::get_random_less_than(q), &pk);
+        let z = Random::get_random_less_than(q);
+        let r = Random::get_random_less_than(q);
+        let quotient = PlaintextEquivalenceProof::quotient(&e_1, &e_2, p);
+        let blinded = PlaintextEquivalenceProof::blind(&quotient, &z, p);
+        let expected =
+            PlaintextEquivalenceProof::generate(&params, &z, &r, &quotient, &blinded, id);
+
+        let (e_1, e_2): (GroupCipher<BigUint>, GroupCipher<BigUint>) = (e_1.into(), e_2.into());
+        let group_quotient = PlaintextEquivalenceProof::quotient_in_group(&params, &e_1, &e_2);
+        let group_blinded = PlaintextEquivalenceProof::blind_in_group(&params, &group_quotient, &z);
+        assert_eq!(group_quotient, quotient.into());
+        assert_eq!(group_blinded, blinded.into());
+
+        let proof = PlaintextEquivalenceProof::generate_in_group(
+            &params,
+            &z,
+            &r,
+            &group_quotient,
+            &group_blinded,
+            id,
+        );
+        assert_eq!(proof.challenge, expected.challenge);
+        assert_eq!(proof.response, expected.response);
+        assert!(PlaintextEquivalenceProof::verify_in_group(
+            &params,
+            &proof,
+            &group_quotient,
+            &group_blinded,
+            id
+        ));
+
+        // a blinded quotient which isn't a group element is rejected
+        let invalid = GroupCipher {
+            a: BigUint::from(0u32),
+            b: group_blinded.b.clone(),
+        };
+        assert!(!PlaintextEquivalenceProof::verify_in_group(
+            &params,
+            &proof,
+            &group_quotient,
+            &invalid,
+            id
+        ));
+    }
+
+    #[cfg(feature = "ristretto")]
+    #[test]
+    fn it_should_verify_pet_proof_in_the_ristretto_group() {
+        use crate::types::{Group, Ristretto};
+
+        let group = Ristretto;
+        let id = "Bob".as_bytes();
+        let sk = Random::get_random_scalar(&group);
+        let h = group.exp(&group.generator(), &sk);
+        let encrypt = |m: u32| {
+            let m = ElGamal::encode_in_group(&group, &BigUint::from(m));
+            ElGamal::encrypt_in_group(&group, &h, &m, &Random::get_random_scalar(&group))
+        };
+
+        // the same credential is detected, another one isn't
+        for (other, is_equivalent) in [(42u32, true), (43u32, false)].iter() {
+            let quotient = PlaintextEquivalenceProof::quotient_in_group(
+                &group,
+                &encrypt(42),
+                &encrypt(*other),
+            );
+            let z = Random::get_random_scalar(&group);
+            let r = Random::get_random_scalar(&group);
+            let blinded = PlaintextEquivalenceProof::blind_in_group(&group, &quotient, &z);
+            let proof = PlaintextEquivalenceProof::generate_in_group(
+                &group, &z, &r, &quotient, &blinded, id,
+            );
+            assert!(PlaintextEquivalenceProof::verify_in_group(
+                &group, &proof, &quotient, &blinded, id
+            ));
+            let plaintext = ElGamal::decrypt_in_group(&group, &blinded, &sk);
+            assert_eq!(plaintext == group.identity(), *is_equivalent);
+        }
+    }
+}
@@ -12,11 +12,20 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ReEncryptionProof {
+    #[cfg_attr(
+        feature = "std",
+        serde(with = "crate::proofs::wire::serde_hex::cipher")
+    )]
     pub c_one_prime: Cipher,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
     pub challenge: BigUint,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
     pub h1: BigUint,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
     pub h2: BigUint,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
     pub s2: BigUint,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
     pub t2: BigUint,
 }
 
@@ -1,6 +1,13 @@
-use crate::types::{BigT, BigY, ElGamalParams, ModuloOperations};
+#[cfg(any(feature = "std", test))]
+use crate::random::Random;
+use crate::types::{
+    multi_modpow, BigT, BigY, ElGamalParams, FixedBaseTable, Group, GroupCipher, GroupShuffleProof,
+    GroupT, GroupY, ModuloOperations, ShuffleProofValues,
+};
 use crate::{
+    hash::HashFunction,
     helper::Helper,
+    secret,
     types::{Cipher, PermutationCommitment, PublicKey},
 };
 use alloc::{vec, vec::Vec};
@@ -23,6 +30,26 @@ impl ShuffleProof {
         permutation: &[usize],
         randoms: Vec<BigUint>,
         generators: Vec<BigUint>,
+    ) -> PermutationCommitment {
+        let g_table = params.g.precompute(&params.p, params.q().bits());
+        Self::generate_permutation_commitment_with_table(
+            params,
+            permutation,
+            randoms,
+            generators,
+            &g_table,
+        )
+    }
+
+    /// Generates the commitment to the permutation with a precomputed table of the
+    /// generator g (see: `generate_permutation_commitment`), e.g. if a prover
+    /// generates several proofs with the same parameters.
+    pub fn generate_permutation_commitment_with_table(
+        params: &ElGamalParams,
+        permutation: &[usize],
+        randoms: Vec<BigUint>,
+        generators: Vec<BigUint>,
+        g_table: &FixedBaseTable,
     ) -> PermutationCommitment {
         assert!(
             permutation.len() == randoms.len(),
@@ -35,7 +62,6 @@ impl ShuffleProof {
         assert!(!permutation.is_empty(), "vectors cannot be empty!");
 
         let p = &params.p;
-        let g = &params.g;
         let one = BigUint::one();
         let too_large = p.clone() + one;
 
@@ -53,7 +79,7 @@ impl ShuffleProof {
 
             // create commitment
             // g_pow_r_j_i = g^(r_j_i) mod p
-            let g_pow_r_j_i = g.modpow(r_j_i, p);
+            let g_pow_r_j_i = g_table.pow(r_j_i);
 
             // c_j_i = (g^(r_j_i) * h_i) mod p
             let c_j_i = g_pow_r_j_i.modmul(h_i, p);
@@ -84,6 +110,21 @@ impl ShuffleProof {
         challenges: Vec<BigUint>,
         randoms: Vec<BigUint>,
         params: &ElGamalParams,
+    ) -> PermutationCommitment {
+        let q = &params.q();
+        let g_table = params.g.precompute(&params.p, q.bits());
+        let h_table = params.h.precompute(&params.p, q.bits());
+        Self::generate_commitment_chain_with_tables(challenges, randoms, params, &g_table, &h_table)
+    }
+
+    /// Generates the commitment chain with precomputed tables of the
+    /// generators g and h (see: `generate_commitment_chain`).
+    pub fn generate_commitment_chain_with_tables(
+        challenges: Vec<BigUint>,
+        mut randoms: Vec<BigUint>,
+        params: &ElGamalParams,
+        g_table: &FixedBaseTable,
+        h_table: &FixedBaseTable,
     ) -> PermutationCommitment {
         assert!(
             challenges.len() == randoms.len(),
@@ -93,8 +134,6 @@ impl ShuffleProof {
 
         let p = &params.p;
         let q = &params.q();
-        let g = &params.g;
-        let h = &params.h;
 
         let mut commitment_values = Vec::new();
         let mut commitment_randoms = Vec::new();
@@ -123,13 +162,18 @@ impl ShuffleProof {
 
             // compute the commitment value: c_i = g^r_i * h^u_i mod p
             // g is the first and h the second public generator: g ∈ G_q, h ∈ G_q
-            let g_pow_r_i = g.modpow(&r_i, p);
-            let h_pow_u_i = h.modpow(&u_i, p);
+            let g_pow_r_i = g_table.pow(&r_i);
+            let h_pow_u_i = h_table.pow(&u_i);
             c_i = g_pow_r_i * h_pow_u_i;
             c_i %= p;
             commitment_values.push(c_i);
         }
         assert!(commitment_values.len() == commitment_randoms.len());
+
+        // the randoms are returned with the commitments, the running values reveal them
+        secret::wipe_all(&mut randoms);
+        secret::wipe(&mut r_i);
+        secret::wipe(&mut u_i);
         PermutationCommitment {
             commitments: commitment_values,
             randoms: commitment_randoms,
@@ -151,6 +195,18 @@ impl ShuffleProof {
         vec_e_tilde: Vec<Cipher>,
         vec_c: Vec<BigUint>,
         pk: &PublicKey,
+    ) -> Vec<BigUint> {
+        Self::get_challenges_with_hash(n, vec_e, vec_e_tilde, vec_c, pk, HashFunction::Blake2b)
+    }
+
+    /// Computes the n challenges with the hash function (see: `get_challenges`).
+    pub fn get_challenges_with_hash(
+        n: usize,
+        vec_e: Vec<Cipher>,
+        vec_e_tilde: Vec<Cipher>,
+        vec_c: Vec<BigUint>,
+        pk: &PublicKey,
+        hash: HashFunction,
     ) -> Vec<BigUint> {
         assert!(n > 0, "at least one challenge must be generated!");
         assert!(
@@ -166,13 +222,13 @@ impl ShuffleProof {
         let mut challenges: Vec<BigUint> = Vec::new();
 
         // hash all inputs into a single BigUint
-        let h = Helper::hash_challenges_inputs(vec_e, vec_e_tilde, vec_c, pk);
+        let h = Helper::hash_challenges_inputs(vec_e, vec_e_tilde, vec_c, pk, hash);
 
         for i in 0..n {
-            let i_ = Helper::hash_vec_usize_to_biguint(&[i].to_vec());
-            let mut c_i = Helper::hash_vec_biguints_to_biguint([h.clone(), i_].to_vec());
+            let i_ = Helper::hash_vec_usize_to_biguint(&[i].to_vec(), hash);
+            let mut c_i = Helper::hash_vec_biguints_to_biguint([h.clone(), i_].to_vec(), hash);
 
-            // The minimal privacy σ defines the amount of computational work for a polynomially bounded adversary to break the privacy of the votes to be greater or equal to c * 2^σ for some constant value c > 0. This is equivalent to brute-force searching a key of length σ bits. 
+            // The minimal privacy σ defines the amount of computational work for a polynomially bounded adversary to break the privacy of the votes to be greater or equal to c * 2^σ for some constant value c > 0. This is equivalent to brute-force searching a key of length σ bits.
             // Recommended values today are σ = 112, σ = 128, or higher.
             // The minimal integrity τ defines the amount of computational work for breaking the integrity of a vote in the same way as σ for breaking the privacy of the vote. In other words, the actual choice of τ determines the risk that an adversary succeeds in manipulating an election. Recommendations for τ are similar to the above-mentioned values for σ, but since manipulating an election is only possible during the election period or during tallying, a less conservative value may be chosen.
             // hash(h,i_) mod 2^τ
@@ -190,15 +246,979 @@ impl ShuffleProof {
     /// - public value: ((encryptions, shuffled_encryptions, permutation_commitments, chain_commitments, public_key)
     /// - public commitment: (t1, t2, t3, (t4_1, t4_2), (t_hat_0, ..., t_hat_(size-1)))
     pub fn get_challenge(public_value: BigY, public_commitment: BigT, q: &BigUint) -> BigUint {
-        let value = Helper::hash_challenge_inputs(public_value, public_commitment);
+        let value =
+            Helper::hash_challenge_inputs(public_value, public_commitment, HashFunction::Blake2b);
         value % q
     }
+
+    /// Computes the challenge for a public value y and a public commitment t bound to a context,
+    /// e.g. the digest of the ballot box at the end of the voting phase. The proof is then only
+    /// valid for the shuffled ciphers of the given context.
+    ///
+    /// An empty context results in the challenge of `get_challenge` (proofs without a context).
+    pub fn get_challenge_in_context(
+        public_value: BigY,
+        public_commitment: BigT,
+        context: &[u8],
+        q: &BigUint,
+    ) -> BigUint {
+        Self::get_challenge_in_context_with_hash(
+            public_value,
+            public_commitment,
+            context,
+            q,
+            HashFunction::Blake2b,
+        )
+    }
+
+    /// Computes the challenge bound to the context with the hash function
+    /// (see: `get_challenge_in_context`).
+    pub fn get_challenge_in_context_with_hash(
+        public_value: BigY,
+        public_commitment: BigT,
+        context: &[u8],
+        q: &BigUint,
+        hash: HashFunction,
+    ) -> BigUint {
+        let value = Helper::hash_challenge_inputs(public_value, public_commitment, hash);
+        if context.is_empty() {
+            return value % q;
+        }
+        // the leading byte preserves the leading zeros of the context
+        let context = BigUint::from_bytes_be(&[&[1u8], context].concat());
+        Helper::hash_vec_biguints_to_biguint(vec![value, context], hash) % q
+    }
+
+    /// CheckShuffleProof Algorithm 8.51 (CHVoteSpec 3.1)
+    ///
+    /// Checks the correctness of a shuffle proof generated by Algorithm 8.47.
+    /// The public values are the ElGamal encryptions e and e~ and the public encryption key pk.
+    /// The independent generators are derived from the id (e.g. the topic id).
+    ///
+    /// All commitments t are recomputed from the proof, i.e. the commitments of the prover are not required.
+    /// Returns false instead of panicking if the proof doesn't match the size of the shuffle.
+    pub fn verify(
+        id: &[u8],
+        proof: &ShuffleProofValues,
+        encryptions: Vec<Cipher>,
+        shuffled_encryptions: Vec<Cipher>,
+        pk: &PublicKey,
+    ) -> bool {
+        Self::verify_in_context(id, &[], proof, encryptions, shuffled_encryptions, pk)
+    }
+
+    /// Checks the correctness of a shuffle proof whose challenge is bound to the context,
+    /// see: `get_challenge_in_context`.
+    pub fn verify_in_context(
+        id: &[u8],
+        context: &[u8],
+        proof: &ShuffleProofValues,
+        encryptions: Vec<Cipher>,
+        shuffled_encryptions: Vec<Cipher>,
+        pk: &PublicKey,
+    ) -> bool {
+        Self::verify_in_context_with_hash(
+            id,
+            context,
+            proof,
+            encryptions,
+            shuffled_encryptions,
+            pk,
+            HashFunction::Blake2b,
+        )
+    }
+
+    /// Checks the correctness of a shuffle proof whose challenges are computed
+    /// with the hash function (see: `verify_in_context`).
+    pub fn verify_in_context_with_hash(
+        id: &[u8],
+        context: &[u8],
+        proof: &ShuffleProofValues,
+        encryptions: Vec<Cipher>,
+        shuffled_encryptions: Vec<Cipher>,
+        pk: &PublicKey,
+        hash: HashFunction,
+    ) -> bool {
+        let e = encryptions;
+        let e_tilde = shuffled_encryptions;
+        let challenge = &proof.challenge;
+        let vec_c = &proof.permutation_commitments;
+        let vec_c_hat = &proof.permutation_chain_commitments;
+        let vec_s_hat = &proof.vec_s_hat;
+        let vec_s_tilde = &proof.vec_s_tilde;
+
+        // the size of the shuffle (# of encrypted votes)
+        let size = e.len();
+        let is_size_valid = size > 0
+            && e_tilde.len() == size
+            && vec_c.len() == size
+            && vec_c_hat.len() == size
+            && vec_s_hat.len() == size
+            && vec_s_tilde.len() == size;
+        if !is_size_valid {
+            return false;
+        }
+
+        let params = &pk.params;
+        let g = &params.g;
+        let h = &params.h;
+        let p = &params.p;
+        let q = &params.q();
+
+        // get {size} independent generators: vec_h
+        let vec_h = Helper::get_generators(id, p, size);
+
+        // get {size} challenges
+        // vec_u = get_challenges(size, hash(e, e_tilde, vec_c, pk))
+        let vec_u = Self::get_challenges_with_hash(
+            size,
+            e.clone(),
+            e_tilde.clone(),
+            vec_c.clone(),
+            pk,
+            hash,
+        );
+
+        // get c_flat = Π(c_i) / Π(vec_h_i) mod p
+        let prod_vec_c = vec_c
+            .iter()
+            .fold(BigUint::one(), |prod, c| prod.modmul(c, p));
+        let prod_h = vec_h
+            .iter()
+            .fold(BigUint::one(), |prod, gen| prod.modmul(gen, p));
+        let c_flat = match prod_vec_c.moddiv(&prod_h, p) {
+            Some(c_flat) => c_flat,
+            None => return false,
+        };
+
+        // get u = Π(vec_u_i) mod q
+        let u = vec_u
+            .iter()
+            .fold(BigUint::one(), |product, u| product.modmul(u, q));
+
+        // get c_hat = c_hat_n / h^u mod p
+        let c_hat = match vec_c_hat[size - 1].moddiv(&h.modpow(&u, p), p) {
+            Some(c_hat) => c_hat,
+            None => return false,
+        };
+
+        // get c_tilde = Π(c_i^u_i) mod p
+        let c_tilde = product_of_powers(vec_c, &vec_u, p);
+
+        // get a_tilde = Π(a_i^u_i) mod p and b_tilde = Π(b_i^u_i) mod p
+        let vec_a: Vec<BigUint> = e.iter().map(|cipher| cipher.a.clone()).collect();
+        let vec_b: Vec<BigUint> = e.iter().map(|cipher| cipher.b.clone()).collect();
+        let a_tilde = product_of_powers(&vec_a, &vec_u, p);
+        let b_tilde = product_of_powers(&vec_b, &vec_u, p);
+
+        // t1 = c_flat^challenge * g^s1 mod p
+        let t1 = c_flat
+            .modpow(challenge, p)
+            .modmul(&g.modpow(&proof.s1, p), p);
+
+        // t2 = c_hat^challenge * g^s2 mod p
+        let t2 = c_hat
+            .modpow(challenge, p)
+            .modmul(&g.modpow(&proof.s2, p), p);
+
+        // t3 = c_tilde^challenge * g^s3 * Π(h_i^s_tilde_i) mod p
+        let t3 = c_tilde
+            .modpow(challenge, p)
+            .modmul(&g.modpow(&proof.s3, p), p)
+            .modmul(&product_of_powers(&vec_h, vec_s_tilde, p), p);
+
+        // since our encryption contains (a,b) with a = g^r (and not a = pk^r as in the spec), pk and g are swapped
+        // t4_1 = a_tilde^challenge * g^-s4 * Π(a_tilde_i^s_tilde_i) mod p
+        // t4_2 = b_tilde^challenge * pk^-s4 * Π(b_tilde_i^s_tilde_i) mod p
+        let g_pow_minus_s4 = match g.modpow(&proof.s4, p).invmod(p) {
+            Some(value) => value,
+            None => return false,
+        };
+        let pk_pow_minus_s4 = match pk.h.modpow(&proof.s4, p).invmod(p) {
+            Some(value) => value,
+            None => return false,
+        };
+        let vec_a_tilde: Vec<BigUint> = e_tilde.iter().map(|cipher| cipher.a.clone()).collect();
+        let vec_b_tilde: Vec<BigUint> = e_tilde.iter().map(|cipher| cipher.b.clone()).collect();
+        let t4_1 = a_tilde
+            .modpow(challenge, p)
+            .modmul(&g_pow_minus_s4, p)
+            .modmul(&product_of_powers(&vec_a_tilde, vec_s_tilde, p), p);
+        let t4_2 = b_tilde
+            .modpow(challenge, p)
+            .modmul(&pk_pow_minus_s4, p)
+            .modmul(&product_of_powers(&vec_b_tilde, vec_s_tilde, p), p);
+
+        // t_hat_i = c_hat_i^challenge * g^s_hat_i * c_hat_(i-1)^s_tilde_i mod p with c_hat_(-1) = h
+        let mut vec_t_hat: Vec<BigUint> = Vec::with_capacity(size);
+        for i in 0..size {
+            let c_hat_i_minus_1 = if i == 0 { h } else { &vec_c_hat[i - 1] };
+            let t_hat_i = vec_c_hat[i]
+                .modpow(challenge, p)
+                .modmul(&g.modpow(&vec_s_hat[i], p), p)
+                .modmul(&c_hat_i_minus_1.modpow(&vec_s_tilde[i], p), p);
+            vec_t_hat.push(t_hat_i);
+        }
+
+        // recompute the challenge from (y, t)
+        // public value y = (e, e_tilde, vec_c, vec_c_hat, pk)
+        // public commitment t = (t1, t2, t3, (t4_1, t4_2), (t_hat_0, ..., t_hat_(size-1)))
+        let public_value: BigY = (e, e_tilde, vec_c.clone(), vec_c_hat.clone(), &pk.h);
+        let public_commitment: BigT = (t1, t2, t3, t4_1, t4_2, vec_t_hat);
+        let recomputed_challenge = Self::get_challenge_in_context_with_hash(
+            public_value,
+            public_commitment,
+            context,
+            q,
+            hash,
+        );
+
+        recomputed_challenge == *challenge
+    }
+}
+
+/// The fixed-base tables of the generators g and h, computed once per prover run
+/// and shared by the permutation commitment, the commitment chain and the t values
+/// of all proofs generated in the run.
+pub struct ShuffleProverTables {
+    pub g: FixedBaseTable,
+    pub h: FixedBaseTable,
+}
+
+impl ShuffleProverTables {
+    pub fn new(params: &ElGamalParams) -> Self {
+        let bits = params.q().bits();
+        ShuffleProverTables {
+            g: params.g.precompute(&params.p, bits),
+            h: params.h.precompute(&params.p, bits),
+        }
+    }
+}
+
+/// The random values (all < q) of a shuffle proof, except the randoms of the permutation
+/// commitment which the proofs of a linked shuffle share. None of the values depends on
+/// the challenges, i.e. the caller draws them before the proof is generated, e.g. from
+/// the randomness of the offchain worker.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ShuffleProofRandoms {
+    /// the randoms of the commitment chain
+    pub vec_r_hat: Vec<BigUint>,
+    pub vec_w_hat: Vec<BigUint>,
+    pub vec_w_tilde: Vec<BigUint>,
+    pub w1: BigUint,
+    pub w2: BigUint,
+    pub w3: BigUint,
+    pub w4: BigUint,
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for ShuffleProofRandoms {
+    fn zeroize(&mut self) {
+        secret::wipe_all(&mut self.vec_r_hat);
+        secret::wipe_all(&mut self.vec_w_hat);
+        secret::wipe_all(&mut self.vec_w_tilde);
+        secret::wipe(&mut self.w1);
+        secret::wipe(&mut self.w2);
+        secret::wipe(&mut self.w3);
+        secret::wipe(&mut self.w4);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for ShuffleProofRandoms {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl ShuffleProofRandoms {
+    /// draws the random values of a proof of a shuffle of {size} encryptions
+    pub fn random(q: &BigUint, size: usize) -> Self {
+        let randoms = |n: usize| -> Vec<BigUint> {
+            (0..n).map(|_| Random::get_random_less_than(q)).collect()
+        };
+        ShuffleProofRandoms {
+            vec_r_hat: randoms(size),
+            vec_w_hat: randoms(size),
+            vec_w_tilde: randoms(size),
+            w1: Random::get_random_less_than(q),
+            w2: Random::get_random_less_than(q),
+            w3: Random::get_random_less_than(q),
+            w4: Random::get_random_less_than(q),
+        }
+    }
+}
+
+impl ShuffleProof {
+    /// GenShuffleProof Algorithm 8.47 (CHVoteSpec 3.1)
+    ///
+    /// Generates a shuffle proof relative to encryptions e and e~, which
+    /// is equivalent to proving knowledge of a permutation and randomizations.
+    /// The independent generators are derived from the id (e.g. the topic id),
+    /// all random values are drawn from the randomness of the operating system.
+    ///
+    /// The challenge is bound to the context (see: `get_challenge_in_context`).
+    ///
+    /// Returns the proof and the public commitment t (required for batch verification).
+    #[cfg(any(feature = "std", test))]
+    pub fn generate(
+        id: &[u8],
+        context: &[u8],
+        encryptions: Vec<Cipher>,
+        shuffled_encryptions: Vec<Cipher>,
+        re_encryption_randoms: Vec<BigUint>,
+        permutation: &[usize],
+        pk: &PublicKey,
+    ) -> Option<(ShuffleProofValues, BigT)> {
+        let params = &pk.params;
+        let q = &params.q();
+        let size = permutation.len();
+        let tables = ShuffleProverTables::new(params);
+        let vec_h = Helper::get_generators(id, &params.p, size);
+
+        // commit to the given permutation: (vec_c, vec_r)
+        let commitment_randoms: Vec<BigUint> =
+            (0..size).map(|_| Random::get_random_less_than(q)).collect();
+        let permutation_commitment = Self::generate_permutation_commitment_with_table(
+            params,
+            permutation,
+            commitment_randoms,
+            vec_h.clone(),
+            &tables.g,
+        );
+        Self::prove(
+            encryptions,
+            shuffled_encryptions,
+            re_encryption_randoms,
+            permutation,
+            &vec_h,
+            permutation_commitment,
+            pk,
+            &tables,
+            ShuffleProofRandoms::random(q, size),
+            context,
+        )
+    }
+
+    /// Proves the shuffle of the encryptions e to the shuffled encryptions e~ relative to
+    /// the commitment to the permutation with the independent generators vec_h.
+    /// The algorithm implements Wikström’s proof of a shuffle
+    /// except for the fact that the offline and online phases are merged.
+    /// The challenge is bound to the context (see: `get_challenge_in_context`).
+    ///
+    /// Returns the proof and the public commitment t (required for batch verification)
+    /// or None if g^w4 or the public key have no inverse mod p.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove(
+        encryptions: Vec<Cipher>,
+        shuffled_encryptions: Vec<Cipher>,
+        re_encryption_randoms: Vec<BigUint>,
+        permutation: &[usize],
+        vec_h: &[BigUint],
+        permutation_commitment: PermutationCommitment,
+        pk: &PublicKey,
+        tables: &ShuffleProverTables,
+        randoms: ShuffleProofRandoms,
+        context: &[u8],
+    ) -> Option<(ShuffleProofValues, BigT)> {
+        Self::prove_with_hash(
+            encryptions,
+            shuffled_encryptions,
+            re_encryption_randoms,
+            permutation,
+            vec_h,
+            permutation_commitment,
+            pk,
+            tables,
+            randoms,
+            context,
+            HashFunction::Blake2b,
+        )
+    }
+
+    /// Proves the shuffle, the challenges are computed with the hash function (see: `prove`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_with_hash(
+        encryptions: Vec<Cipher>,
+        shuffled_encryptions: Vec<Cipher>,
+        re_encryption_randoms: Vec<BigUint>,
+        permutation: &[usize],
+        vec_h: &[BigUint],
+        permutation_commitment: PermutationCommitment,
+        pk: &PublicKey,
+        tables: &ShuffleProverTables,
+        randoms: ShuffleProofRandoms,
+        context: &[u8],
+        hash: HashFunction,
+    ) -> Option<(ShuffleProofValues, BigT)> {
+        // input checks
+        assert!(
+            encryptions.len() == shuffled_encryptions.len(),
+            "encryptions and shuffled_encryptions need to have the same length!"
+        );
+        assert!(
+            encryptions.len() == re_encryption_randoms.len(),
+            "encryptions and re_encryption_randoms need to have the same length!"
+        );
+        assert!(
+            encryptions.len() == permutation.len(),
+            "encryptions and permutation need to have the same length!"
+        );
+        assert!(
+            randoms.vec_r_hat.len() == permutation.len()
+                && randoms.vec_w_hat.len() == permutation.len()
+                && randoms.vec_w_tilde.len() == permutation.len(),
+            "permutation and randoms need to have the same length!"
+        );
+        assert!(!encryptions.is_empty(), "vectors cannot be empty!");
+
+        // the size of the shuffle (# of encrypted votes)
+        let size = encryptions.len();
+        let params = &pk.params;
+        let p = &params.p;
+        let q = &params.q();
+        let e = encryptions;
+        let e_tilde = shuffled_encryptions;
+        let mut vec_r_tilde = re_encryption_randoms;
+        let vec_c = permutation_commitment.commitments;
+        let mut vec_r = permutation_commitment.randoms;
+        // the randoms are borrowed, they are wiped when they are dropped
+        let ShuffleProofRandoms {
+            vec_r_hat,
+            vec_w_hat,
+            vec_w_tilde,
+            w1,
+            w2,
+            w3,
+            w4,
+        } = &randoms;
+
+        // get {size} challenges
+        // vec_u = get_challenges(size, hash(e, e_tilde, vec_c, pk))
+        let vec_u = Self::get_challenges_with_hash(
+            size,
+            e.clone(),
+            e_tilde.clone(),
+            vec_c.clone(),
+            pk,
+            hash,
+        );
+
+        // permute the challenges -> same order as randoms + permuation
+        let mut u_tilde: Vec<BigUint> = permutation.iter().map(|j_i| vec_u[*j_i].clone()).collect();
+
+        // (vector_c_hat, vector_r_hat) = GenCommitmentChain(vector_u_tilde)
+        // vector_u_tilde = challenges, re-ordered according to the permutation
+        let mut chain = Self::generate_commitment_chain_with_tables(
+            u_tilde.clone(),
+            vec_r_hat.clone(),
+            params,
+            &tables.g,
+            &tables.h,
+        );
+        let vec_c_hat = core::mem::take(&mut chain.commitments);
+        secret::wipe_all(&mut chain.randoms);
+
+        // part 1: generate the t_hat values
+        let mut r_i = BigUint::zero();
+        let mut u_i = BigUint::one();
+        let mut vec_t_hat: Vec<BigUint> = Vec::with_capacity(size);
+        for i in 0..size {
+            let w_hat_i = &vec_w_hat[i];
+            let w_tilde_i = &vec_w_tilde[i];
+            let u_tilde_i = &u_tilde[i];
+
+            // r_i_dash = w_hat_i + w_tilde_i * r_(i-1) mod q
+            let r_i_dash = w_hat_i.modadd(&w_tilde_i.modmul(&r_i, q), q);
+
+            // r_i = r_hat_i + u_tilde_i * r_(i-1) mod q
+            r_i = vec_r_hat[i].modadd(&u_tilde_i.modmul(&r_i, q), q);
+
+            // u_i_dash = w_tilde_i * u_(i-1) mod q
+            let u_i_dash = w_tilde_i.modmul(&u_i, q);
+
+            // u_i = u_tilde_i * u_(i-1) mod q
+            u_i = u_tilde_i.modmul(&u_i, q);
+
+            // t_hat_i = g^r_i_dash * h^u_i_dash mod p
+            let t_hat_i = tables.g.pow(&r_i_dash).modmul(&tables.h.pow(&u_i_dash), p);
+            vec_t_hat.push(t_hat_i);
+        }
+
+        // part 2: generate t1, t2, t3, t4_1 & t4_2
+        let t1 = tables.g.pow(w1);
+        let t2 = tables.g.pow(w2);
+
+        // t3 = g^w3 * Π(h_i^w_tilde_i) % p
+        let t3 = tables
+            .g
+            .pow(w3)
+            .modmul(&product_of_powers(vec_h, vec_w_tilde, p), p);
+
+        // since our encryption contains (a,b) with a = g^r (and not a = pk^r as in the spec), pk and g are swapped
+        // t4_1 = g^-w4 * Π(a_tilde_i^w_tilde_i) mod p
+        // t4_2 = pk^-w4 * Π(b_tilde_i^w_tilde_i) mod p
+        // for an explanation see: Verifiable Re-Encryption Mixnets (Haenni, Locher, Koenig, Dubuis) page 9
+        let inv_g_pow_w4 = tables.g.pow(w4).invmod(p)?;
+        let inv_pk_pow_w4 = pk.h.invmod(p)?.modpow(w4, p);
+        let vec_a_tilde: Vec<BigUint> = e_tilde.iter().map(|cipher| cipher.a.clone()).collect();
+        let vec_b_tilde: Vec<BigUint> = e_tilde.iter().map(|cipher| cipher.b.clone()).collect();
+        let t4_1 = inv_g_pow_w4.modmul(&product_of_powers(&vec_a_tilde, vec_w_tilde, p), p);
+        let t4_2 = inv_pk_pow_w4.modmul(&product_of_powers(&vec_b_tilde, vec_w_tilde, p), p);
+
+        // generate challenge from (y, t)
+        // public value y = (e, e_tilde, vec_c, vec_c_hat, pk)
+        // public commitment t = (t1, t2, t3, (t4_1, t4_2), (t_hat_0, ..., t_hat_(size-1)))
+        let public_value: BigY = (e, e_tilde, vec_c.clone(), vec_c_hat.clone(), &pk.h);
+        let public_commitment: BigT = (t1, t2, t3, t4_1, t4_2, vec_t_hat);
+        let challenge = Self::get_challenge_in_context_with_hash(
+            public_value,
+            public_commitment.clone(),
+            context,
+            q,
+            hash,
+        );
+
+        // s1 = w1 - challenge * r_flat mod q, with r_flat = Σ(r_i) mod q
+        let mut r_flat = vec_r
+            .iter()
+            .fold(BigUint::zero(), |sum, r| sum.modadd(r, q));
+        let s1 = w1.modsub(&challenge.modmul(&r_flat, q), q);
+
+        // v_(n-1) = 1, v_(i-1) = u_tilde_i * v_i mod q
+        let mut v: Vec<BigUint> = vec![BigUint::one(); size];
+        for i in (0..(size - 1)).rev() {
+            v[i] = u_tilde[i + 1].modmul(&v[i + 1], q);
+        }
+
+        // s2 = w2 - challenge * r_hat mod q, with r_hat = Σ(r_hat_i * v_i) mod q
+        let mut r_hat = sum_of_products(vec_r_hat, &v, q);
+        let s2 = w2.modsub(&challenge.modmul(&r_hat, q), q);
+
+        // s3 = w3 - challenge * r mod q, with r = Σ(r_i * u_i) mod q
+        let mut r = sum_of_products(&vec_r, &vec_u, q);
+        let s3 = w3.modsub(&challenge.modmul(&r, q), q);
+
+        // s4 = w4 - challenge * r_tilde mod q, with r_tilde = Σ(r_tilde_i * u_i) mod q
+        let mut r_tilde = sum_of_products(&vec_r_tilde, &vec_u, q);
+        let s4 = w4.modsub(&challenge.modmul(&r_tilde, q), q);
+
+        // s_hat_i = w_hat_i - challenge * r_hat_i mod q
+        // s_tilde_i = w_tilde_i - challenge * u_tilde_i mod q
+        let vec_s_hat: Vec<BigUint> = vec_w_hat
+            .iter()
+            .zip(vec_r_hat.iter())
+            .map(|(w_hat_i, r_hat_i)| w_hat_i.modsub(&challenge.modmul(r_hat_i, q), q))
+            .collect();
+        let vec_s_tilde: Vec<BigUint> = vec_w_tilde
+            .iter()
+            .zip(u_tilde.iter())
+            .map(|(w_tilde_i, u_tilde_i)| w_tilde_i.modsub(&challenge.modmul(u_tilde_i, q), q))
+            .collect();
+
+        // wipe the secrets which reveal the permutation or the randoms
+        secret::wipe_all(&mut vec_r);
+        secret::wipe_all(&mut vec_r_tilde);
+        secret::wipe_all(&mut u_tilde);
+        secret::wipe_all(&mut v);
+        secret::wipe(&mut r_i);
+        secret::wipe(&mut u_i);
+        secret::wipe(&mut r_flat);
+        secret::wipe(&mut r_hat);
+        secret::wipe(&mut r);
+        secret::wipe(&mut r_tilde);
+
+        let proof = ShuffleProofValues {
+            challenge,
+            s1,
+            s2,
+            s3,
+            s4,
+            vec_s_hat,
+            vec_s_tilde,
+            permutation_commitments: vec_c,
+            permutation_chain_commitments: vec_c_hat,
+        };
+        Some((proof, public_commitment))
+    }
+}
+
+/// The shuffle proof in an arbitrary group and its public commitment t (see: `prove_in_group`)
+pub type GroupShuffle<G> = (
+    GroupShuffleProof<<G as Group>::Element, <G as Group>::Scalar>,
+    GroupT<<G as Group>::Element>,
+);
+
+/// The shuffle proof in an arbitrary group (see: `Group`), e.g. in the Ristretto group.
+/// In the integer group, the proof is equal to the BigUint proof for the same randoms.
+impl ShuffleProof {
+    /// Computes the n challenges in an arbitrary group (see: `get_challenges_with_hash`).
+    pub fn get_challenges_in_group<G: Group>(
+        group: &G,
+        n: usize,
+        vec_e: &[GroupCipher<G::Element>],
+        vec_e_tilde: &[GroupCipher<G::Element>],
+        vec_c: &[G::Element],
+        pk: &G::Element,
+        hash: HashFunction,
+    ) -> Vec<G::Scalar> {
+        assert!(n > 0, "at least one challenge must be generated!");
+        let h = Helper::hash_challenges_inputs_in_group(group, vec_e, vec_e_tilde, vec_c, pk, hash);
+        (0..n)
+            .map(|i| {
+                let i_ = Helper::hash_vec_usize_to_biguint(&[i], hash);
+                let c_i = Helper::hash_vec_biguints_to_biguint(vec![h.clone(), i_], hash);
+                group.scalar_from_biguint(&c_i)
+            })
+            .collect()
+    }
+
+    /// Computes the challenge bound to the context in an arbitrary group
+    /// (see: `get_challenge_in_context_with_hash`).
+    pub fn get_challenge_in_group<G: Group>(
+        group: &G,
+        public_value: GroupY<G::Element>,
+        public_commitment: &GroupT<G::Element>,
+        context: &[u8],
+        hash: HashFunction,
+    ) -> G::Scalar {
+        let value =
+            Helper::hash_challenge_inputs_in_group(group, public_value, public_commitment, hash);
+        if context.is_empty() {
+            return group.scalar_from_biguint(&value);
+        }
+        // the leading byte preserves the leading zeros of the context
+        let context = BigUint::from_bytes_be(&[&[1u8], context].concat());
+        group.scalar_from_biguint(&Helper::hash_vec_biguints_to_biguint(
+            vec![value, context],
+            hash,
+        ))
+    }
+
+    /// Proves the shuffle of the encryptions e to the shuffled encryptions e~ in an arbitrary group
+    /// (see: `prove_with_hash`), e.g. shuffled by `ElGamal::shuffle_in_group`. The independent
+    /// generators are derived from the id (see: `Group::derive_generator`).
+    ///
+    /// The randoms of the proof, i.e. the randoms of the permutation commitment and the randoms
+    /// (see: `ShuffleProofRandoms`), are drawn from Z_q like for the BigUint proof.
+    ///
+    /// Returns the proof and the public commitment t or None if the public key isn't a group element.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_in_group<G: Group>(
+        group: &G,
+        id: &[u8],
+        context: &[u8],
+        encryptions: &[GroupCipher<G::Element>],
+        shuffled_encryptions: &[GroupCipher<G::Element>],
+        re_encryption_randoms: &[G::Scalar],
+        permutation: &[usize],
+        pk: &G::Element,
+        commitment_randoms: &[BigUint],
+        randoms: &ShuffleProofRandoms,
+        hash: HashFunction,
+    ) -> Option<GroupShuffle<G>> {
+        // input checks
+        let size = encryptions.len();
+        assert!(
+            shuffled_encryptions.len() == size,
+            "encryptions and shuffled_encryptions need to have the same length!"
+        );
+        assert!(
+            re_encryption_randoms.len() == size,
+            "encryptions and re_encryption_randoms need to have the same length!"
+        );
+        assert!(
+            permutation.len() == size,
+            "encryptions and permutation need to have the same length!"
+        );
+        assert!(
+            commitment_randoms.len() == size
+                && randoms.vec_r_hat.len() == size
+                && randoms.vec_w_hat.len() == size
+                && randoms.vec_w_tilde.len() == size,
+            "permutation and randoms need to have the same length!"
+        );
+        assert!(size > 0, "vectors cannot be empty!");
+        if !group.is_element(pk) {
+            return None;
+        }
+
+        let e = encryptions;
+        let e_tilde = shuffled_encryptions;
+        let g = &group.generator();
+        let h = &group.second_generator();
+        let vec_h: Vec<G::Element> = (0..size).map(|i| group.derive_generator(id, i)).collect();
+        let to_scalars = |values: &[BigUint]| -> Vec<G::Scalar> {
+            values
+                .iter()
+                .map(|value| group.scalar_from_biguint(value))
+                .collect()
+        };
+        let vec_r = to_scalars(commitment_randoms);
+        let vec_r_hat = to_scalars(&randoms.vec_r_hat);
+        let vec_w_hat = to_scalars(&randoms.vec_w_hat);
+        let vec_w_tilde = to_scalars(&randoms.vec_w_tilde);
+        let w1 = &group.scalar_from_biguint(&randoms.w1);
+        let w2 = &group.scalar_from_biguint(&randoms.w2);
+        let w3 = &group.scalar_from_biguint(&randoms.w3);
+        let w4 = &group.scalar_from_biguint(&randoms.w4);
+        let zero = group.scalar_from_biguint(&BigUint::zero());
+        let one = group.scalar_from_biguint(&BigUint::one());
+
+        // commit to the permutation: c_j_i = g^r_j_i * h_i with j_i = permutation[i]
+        let mut vec_c: Vec<Option<G::Element>> = vec![None; size];
+        for (h_i, j_i) in vec_h.iter().zip(permutation.iter()) {
+            let c_j_i = group.op(&group.exp(g, &vec_r[*j_i]), h_i);
+            assert!(
+                vec_c[*j_i].replace(c_j_i).is_none(),
+                "permutation must not contain an index twice!"
+            );
+        }
+        let vec_c: Vec<G::Element> = vec_c.into_iter().flatten().collect();
+
+        // get {size} challenges and permute them: u_tilde_i = u_j_i
+        let vec_u = Self::get_challenges_in_group(group, size, e, e_tilde, &vec_c, pk, hash);
+        let u_tilde: Vec<G::Scalar> = permutation.iter().map(|j_i| vec_u[*j_i].clone()).collect();
+
+        // the commitment chain c_hat_i = g^r_i * h^u_i and the t_hat values
+        // r_i = r_hat_i + u_tilde_i * r_(i-1), u_i = u_tilde_i * u_(i-1) with r_(-1) = 0, u_(-1) = 1
+        let mut r_i = zero.clone();
+        let mut u_i = one.clone();
+        let mut vec_c_hat: Vec<G::Element> = Vec::with_capacity(size);
+        let mut vec_t_hat: Vec<G::Element> = Vec::with_capacity(size);
+        for i in 0..size {
+            // r_i_dash = w_hat_i + w_tilde_i * r_(i-1), u_i_dash = w_tilde_i * u_(i-1)
+            let r_i_dash =
+                group.scalar_add(&vec_w_hat[i], &group.scalar_mul(&vec_w_tilde[i], &r_i));
+            let u_i_dash = group.scalar_mul(&vec_w_tilde[i], &u_i);
+            r_i = group.scalar_add(&vec_r_hat[i], &group.scalar_mul(&u_tilde[i], &r_i));
+            u_i = group.scalar_mul(&u_tilde[i], &u_i);
+            vec_c_hat.push(group.multi_exp(&[g.clone(), h.clone()], &[r_i.clone(), u_i.clone()]));
+            vec_t_hat.push(group.multi_exp(&[g.clone(), h.clone()], &[r_i_dash, u_i_dash]));
+        }
+
+        // t1 = g^w1, t2 = g^w2, t3 = g^w3 * Π(h_i^w_tilde_i)
+        let t1 = group.exp(g, w1);
+        let t2 = group.exp(g, w2);
+        let t3 = group.op(&group.exp(g, w3), &group.multi_exp(&vec_h, &vec_w_tilde));
+
+        // t4_1 = g^-w4 * Π(a_tilde_i^w_tilde_i), t4_2 = pk^-w4 * Π(b_tilde_i^w_tilde_i)
+        let vec_a_tilde: Vec<G::Element> = e_tilde.iter().map(|cipher| cipher.a.clone()).collect();
+        let vec_b_tilde: Vec<G::Element> = e_tilde.iter().map(|cipher| cipher.b.clone()).collect();
+        let t4_1 = group.op(
+            &group.inverse(&group.exp(g, w4)),
+            &group.multi_exp(&vec_a_tilde, &vec_w_tilde),
+        );
+        let t4_2 = group.op(
+            &group.inverse(&group.exp(pk, w4)),
+            &group.multi_exp(&vec_b_tilde, &vec_w_tilde),
+        );
+
+        // generate challenge from (y, t)
+        let public_value: GroupY<G::Element> = (e, e_tilde, &vec_c, &vec_c_hat, pk);
+        let public_commitment: GroupT<G::Element> = (t1, t2, t3, t4_1, t4_2, vec_t_hat);
+        let challenge =
+            Self::get_challenge_in_group(group, public_value, &public_commitment, context, hash);
+        let minus_challenge_times =
+            |w: &G::Scalar, x: &G::Scalar| group.scalar_sub(w, &group.scalar_mul(&challenge, x));
+
+        // s1 = w1 - challenge * r_flat, with r_flat = Σ(r_i)
+        let r_flat = vec_r
+            .iter()
+            .fold(zero.clone(), |sum, r| group.scalar_add(&sum, r));
+        let s1 = minus_challenge_times(w1, &r_flat);
+
+        // v_(n-1) = 1, v_(i-1) = u_tilde_i * v_i
+        let mut v: Vec<G::Scalar> = vec![one; size];
+        for i in (0..(size - 1)).rev() {
+            v[i] = group.scalar_mul(&u_tilde[i + 1], &v[i + 1]);
+        }
+
+        // s2 = w2 - challenge * Σ(r_hat_i * v_i), s3 = w3 - challenge * Σ(r_i * u_i)
+        // s4 = w4 - challenge * Σ(r_tilde_i * u_i)
+        let s2 = minus_challenge_times(w2, &scalar_sum_of_products(group, &vec_r_hat, &v));
+        let s3 = minus_challenge_times(w3, &scalar_sum_of_products(group, &vec_r, &vec_u));
+        let r_tilde = scalar_sum_of_products(group, re_encryption_randoms, &vec_u);
+        let s4 = minus_challenge_times(w4, &r_tilde);
+
+        // s_hat_i = w_hat_i - challenge * r_hat_i, s_tilde_i = w_tilde_i - challenge * u_tilde_i
+        let vec_s_hat: Vec<G::Scalar> = vec_w_hat
+            .iter()
+            .zip(vec_r_hat.iter())
+            .map(|(w_hat_i, r_hat_i)| minus_challenge_times(w_hat_i, r_hat_i))
+            .collect();
+        let vec_s_tilde: Vec<G::Scalar> = vec_w_tilde
+            .iter()
+            .zip(u_tilde.iter())
+            .map(|(w_tilde_i, u_tilde_i)| minus_challenge_times(w_tilde_i, u_tilde_i))
+            .collect();
+
+        let proof = GroupShuffleProof {
+            challenge,
+            s1,
+            s2,
+            s3,
+            s4,
+            vec_s_hat,
+            vec_s_tilde,
+            permutation_commitments: vec_c,
+            permutation_chain_commitments: vec_c_hat,
+        };
+        Some((proof, public_commitment))
+    }
+
+    /// Checks the correctness of a shuffle proof in an arbitrary group
+    /// (see: `verify_in_context_with_hash`). Returns false instead of panicking if the proof
+    /// doesn't match the size of the shuffle or if the public key isn't a group element.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_in_group<G: Group>(
+        group: &G,
+        id: &[u8],
+        context: &[u8],
+        proof: &GroupShuffleProof<G::Element, G::Scalar>,
+        encryptions: &[GroupCipher<G::Element>],
+        shuffled_encryptions: &[GroupCipher<G::Element>],
+        pk: &G::Element,
+        hash: HashFunction,
+    ) -> bool {
+        let e = encryptions;
+        let e_tilde = shuffled_encryptions;
+        let challenge = &proof.challenge;
+        let vec_c = &proof.permutation_commitments;
+        let vec_c_hat = &proof.permutation_chain_commitments;
+        let vec_s_hat = &proof.vec_s_hat;
+        let vec_s_tilde = &proof.vec_s_tilde;
+
+        // the size of the shuffle (# of encrypted votes)
+        let size = e.len();
+        let is_size_valid = size > 0
+            && e_tilde.len() == size
+            && vec_c.len() == size
+            && vec_c_hat.len() == size
+            && vec_s_hat.len() == size
+            && vec_s_tilde.len() == size;
+        // the public key is inverted, all other inverted values are computed by the verifier
+        if !is_size_valid || !group.is_element(pk) {
+            return false;
+        }
+
+        let g = &group.generator();
+        let h = &group.second_generator();
+        let vec_h: Vec<G::Element> = (0..size).map(|i| group.derive_generator(id, i)).collect();
+        let vec_u = Self::get_challenges_in_group(group, size, e, e_tilde, vec_c, pk, hash);
+        let one = group.scalar_from_biguint(&BigUint::one());
+
+        // c_flat = Π(c_i) / Π(h_i), u = Π(u_i), c_hat = c_hat_n / h^u
+        let prod_vec_c = vec_c
+            .iter()
+            .fold(group.identity(), |prod, c| group.op(&prod, c));
+        let prod_h = vec_h
+            .iter()
+            .fold(group.identity(), |prod, h_i| group.op(&prod, h_i));
+        let c_flat = group.op(&prod_vec_c, &group.inverse(&prod_h));
+        let u = vec_u
+            .iter()
+            .fold(one, |product, u_i| group.scalar_mul(&product, u_i));
+        let c_hat = group.op(&vec_c_hat[size - 1], &group.inverse(&group.exp(h, &u)));
+
+        // c_tilde = Π(c_i^u_i), a_tilde = Π(a_i^u_i), b_tilde = Π(b_i^u_i)
+        let c_tilde = group.multi_exp(vec_c, &vec_u);
+        let vec_a: Vec<G::Element> = e.iter().map(|cipher| cipher.a.clone()).collect();
+        let vec_b: Vec<G::Element> = e.iter().map(|cipher| cipher.b.clone()).collect();
+        let a_tilde = group.multi_exp(&vec_a, &vec_u);
+        let b_tilde = group.multi_exp(&vec_b, &vec_u);
+
+        // t1 = c_flat^challenge * g^s1, t2 = c_hat^challenge * g^s2
+        let t1 = group.multi_exp(&[c_flat, g.clone()], &[challenge.clone(), proof.s1.clone()]);
+        let t2 = group.multi_exp(&[c_hat, g.clone()], &[challenge.clone(), proof.s2.clone()]);
+
+        // t3 = c_tilde^challenge * g^s3 * Π(h_i^s_tilde_i)
+        let t3 = group.op(
+            &group.multi_exp(
+                &[c_tilde, g.clone()],
+                &[challenge.clone(), proof.s3.clone()],
+            ),
+            &group.multi_exp(&vec_h, vec_s_tilde),
+        );
+
+        // t4_1 = a_tilde^challenge * g^-s4 * Π(a_tilde_i^s_tilde_i)
+        // t4_2 = b_tilde^challenge * pk^-s4 * Π(b_tilde_i^s_tilde_i)
+        let vec_a_tilde: Vec<G::Element> = e_tilde.iter().map(|cipher| cipher.a.clone()).collect();
+        let vec_b_tilde: Vec<G::Element> = e_tilde.iter().map(|cipher| cipher.b.clone()).collect();
+        let t4_1 = group.op(
+            &group.op(
+                &group.exp(&a_tilde, challenge),
+                &group.inverse(&group.exp(g, &proof.s4)),
+            ),
+            &group.multi_exp(&vec_a_tilde, vec_s_tilde),
+        );
+        let t4_2 = group.op(
+            &group.op(
+                &group.exp(&b_tilde, challenge),
+                &group.inverse(&group.exp(pk, &proof.s4)),
+            ),
+            &group.multi_exp(&vec_b_tilde, vec_s_tilde),
+        );
+
+        // t_hat_i = c_hat_i^challenge * g^s_hat_i * c_hat_(i-1)^s_tilde_i with c_hat_(-1) = h
+        let vec_t_hat: Vec<G::Element> = (0..size)
+            .map(|i| {
+                let c_hat_i_minus_1 = if i == 0 { h } else { &vec_c_hat[i - 1] };
+                group.multi_exp(
+                    &[vec_c_hat[i].clone(), g.clone(), c_hat_i_minus_1.clone()],
+                    &[
+                        challenge.clone(),
+                        vec_s_hat[i].clone(),
+                        vec_s_tilde[i].clone(),
+                    ],
+                )
+            })
+            .collect();
+
+        // recompute the challenge from (y, t)
+        let public_value: GroupY<G::Element> = (e, e_tilde, vec_c, vec_c_hat, pk);
+        let public_commitment: GroupT<G::Element> = (t1, t2, t3, t4_1, t4_2, vec_t_hat);
+        let recomputed_challenge =
+            Self::get_challenge_in_group(group, public_value, &public_commitment, context, hash);
+        recomputed_challenge == *challenge
+    }
+}
+
+/// computes Σ(a_i * b_i) mod q in an arbitrary group
+fn scalar_sum_of_products<G: Group>(group: &G, a: &[G::Scalar], b: &[G::Scalar]) -> G::Scalar {
+    let zero = group.scalar_from_biguint(&BigUint::zero());
+    a.iter().zip(b.iter()).fold(zero, |sum, (a_i, b_i)| {
+        group.scalar_add(&sum, &group.scalar_mul(a_i, b_i))
+    })
+}
+
+/// computes Σ(a_i * b_i) mod q
+fn sum_of_products(a: &[BigUint], b: &[BigUint], q: &BigUint) -> BigUint {
+    a.iter()
+        .zip(b.iter())
+        .fold(BigUint::zero(), |sum, (a_i, b_i)| {
+            sum.modadd(&a_i.modmul(b_i, q), q)
+        })
+}
+
+/// computes Π(bases_i^exponents_i) mod p
+fn product_of_powers(bases: &[BigUint], exponents: &[BigUint], p: &BigUint) -> BigUint {
+    let bases: Vec<&BigUint> = bases.iter().collect();
+    let exponents: Vec<&BigUint> = exponents.iter().collect();
+    multi_modpow(&bases, &exponents, p)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ShuffleProof;
-    use crate::{helper::Helper, random::Random, types::Cipher};
+    use super::{ShuffleProof, ShuffleProofRandoms, ShuffleProverTables};
+    use crate::{
+        encryption::ElGamal,
+        hash::HashFunction,
+        helper::Helper,
+        random::Random,
+        types::{Cipher, GroupCipher, ModuloOperations, ShuffleProofValues},
+    };
     use alloc::{vec, vec::Vec};
     use num_bigint::BigUint;
     use num_traits::{One, Zero};
@@ -546,4 +1566,479 @@ mod tests {
             commitent_chain.randoms.len()
         );
     }
+
+    #[test]
+    fn it_should_generate_the_same_commitments_with_precomputed_tables() {
+        let (params, _, _) = Helper::setup_md_system();
+        let p = &params.p;
+        let q = &params.q();
+        let vote_id = "2020-12-12_01".as_bytes();
+        let size = 3usize;
+
+        let permutation = Random::generate_permutation(&size);
+        let randoms: Vec<BigUint> = (0..size).map(|_| Random::get_random_less_than(q)).collect();
+        let challenges: Vec<BigUint> = (0..size).map(|_| Random::get_random_less_than(q)).collect();
+        let generators = Helper::get_generators(&vote_id, p, size);
+
+        // the tables are shared between both commitments
+        let g_table = params.g.precompute(p, q.bits());
+        let h_table = params.h.precompute(p, q.bits());
+
+        assert_eq!(
+            ShuffleProof::generate_permutation_commitment_with_table(
+                &params,
+                &permutation,
+                randoms.clone(),
+                generators.clone(),
+                &g_table,
+            ),
+            ShuffleProof::generate_permutation_commitment(
+                &params,
+                &permutation,
+                randoms.clone(),
+                generators,
+            )
+        );
+        assert_eq!(
+            ShuffleProof::generate_commitment_chain_with_tables(
+                challenges.clone(),
+                randoms.clone(),
+                &params,
+                &g_table,
+                &h_table,
+            ),
+            ShuffleProof::generate_commitment_chain(challenges, randoms, &params)
+        );
+    }
+
+    #[test]
+    fn it_should_not_verify_shuffle_proof_of_wrong_size() {
+        let (_, _, pk) = Helper::setup_sm_system();
+        let encryptions = vec![Cipher {
+            a: pk.params.g.clone(),
+            b: pk.h.clone(),
+        }];
+        let proof = ShuffleProofValues {
+            challenge: BigUint::one(),
+            s1: BigUint::one(),
+            s2: BigUint::one(),
+            s3: BigUint::one(),
+            s4: BigUint::one(),
+            vec_s_hat: vec![BigUint::one()],
+            vec_s_tilde: vec![BigUint::one()],
+            permutation_commitments: vec![BigUint::one()],
+            permutation_chain_commitments: Vec::new(),
+        };
+
+        let is_valid =
+            ShuffleProof::verify(b"Topic", &proof, encryptions.clone(), encryptions, &pk);
+        assert!(!is_valid);
+    }
+
+    #[test]
+    fn it_should_generate_a_verifiable_shuffle_proof() {
+        let (_, _, pk) = Helper::setup_sm_system();
+        let q = &pk.params.q();
+        let size = 4usize;
+        let encryptions = Random::generate_random_encryptions(&pk, q, size);
+        let permutation = Random::generate_permutation(&size);
+        let randoms: Vec<BigUint> = (0..size).map(|_| Random::get_random_less_than(q)).collect();
+        let shuffled: Vec<Cipher> = ElGamal::shuffle(&encryptions, &permutation, &randoms, &pk)
+            .into_iter()
+            .map(|item| item.0)
+            .collect();
+
+        let (proof, _) = ShuffleProof::generate(
+            b"Topic",
+            &[],
+            encryptions.clone(),
+            shuffled.clone(),
+            randoms,
+            &permutation,
+            &pk,
+        )
+        .unwrap();
+        assert!(ShuffleProof::verify(
+            b"Topic",
+            &proof,
+            encryptions.clone(),
+            shuffled.clone(),
+            &pk
+        ));
+
+        // the generators are derived from the id
+        assert!(!ShuffleProof::verify(
+            b"Another Topic",
+            &proof,
+            encryptions,
+            shuffled,
+            &pk
+        ));
+    }
+
+    #[test]
+    fn it_should_prove_linked_shuffles_with_a_shared_permutation_commitment() {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let size = 3usize;
+        let permutation = Random::generate_permutation(&size);
+        let vec_h = Helper::get_generators(b"Vote", &params.p, size);
+        let tables = ShuffleProverTables::new(&params);
+        let commitment_randoms: Vec<BigUint> =
+            (0..size).map(|_| Random::get_random_less_than(q)).collect();
+        let permutation_commitment = ShuffleProof::generate_permutation_commitment_with_table(
+            &params,
+            &permutation,
+            commitment_randoms,
+            vec_h.clone(),
+            &tables.g,
+        );
+
+        for _ in 0..2 {
+            let encryptions = Random::generate_random_encryptions(&pk, q, size);
+            let randoms: Vec<BigUint> =
+                (0..size).map(|_| Random::get_random_less_than(q)).collect();
+            let shuffled: Vec<Cipher> = ElGamal::shuffle(&encryptions, &permutation, &randoms, &pk)
+                .into_iter()
+                .map(|item| item.0)
+                .collect();
+            let (proof, _) = ShuffleProof::prove(
+                encryptions.clone(),
+                shuffled.clone(),
+                randoms,
+                &permutation,
+                &vec_h,
+                permutation_commitment.clone(),
+                &pk,
+                &tables,
+                ShuffleProofRandoms::random(q, size),
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                proof.permutation_commitments,
+                permutation_commitment.commitments
+            );
+            assert!(ShuffleProof::verify(
+                b"Vote",
+                &proof,
+                encryptions,
+                shuffled,
+                &pk
+            ));
+        }
+    }
+
+    #[test]
+    fn it_should_bind_the_shuffle_proof_to_the_context() {
+        let (_, _, pk) = Helper::setup_sm_system();
+        let q = &pk.params.q();
+        let size = 3usize;
+        let encryptions = Random::generate_random_encryptions(&pk, q, size);
+        let permutation = Random::generate_permutation(&size);
+        let randoms: Vec<BigUint> = (0..size).map(|_| Random::get_random_less_than(q)).collect();
+        let shuffled: Vec<Cipher> = ElGamal::shuffle(&encryptions, &permutation, &randoms, &pk)
+            .into_iter()
+            .map(|item| item.0)
+            .collect();
+
+        // a context starting with a zero byte
+        let context = [0u8, 1, 2, 3];
+        let (proof, _) = ShuffleProof::generate(
+            b"Topic",
+            &context,
+            encryptions.clone(),
+            shuffled.clone(),
+            randoms,
+            &permutation,
+            &pk,
+        )
+        .unwrap();
+        assert!(ShuffleProof::verify_in_context(
+            b"Topic",
+            &context,
+            &proof,
+            encryptions.clone(),
+            shuffled.clone(),
+            &pk
+        ));
+
+        // the proof is only valid in its context
+        for other in [&[][..], &[1u8, 2, 3][..], &[0u8, 1, 2, 4][..]].iter() {
+            assert!(!ShuffleProof::verify_in_context(
+                b"Topic",
+                other,
+                &proof,
+                encryptions.clone(),
+                shuffled.clone(),
+                &pk
+            ));
+        }
+    }
+
+    #[test]
+    fn it_should_verify_the_shuffle_proof_with_the_hash_function_of_the_prover() {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let size = 3usize;
+        let encryptions = Random::generate_random_encryptions(&pk, q, size);
+        let permutation = Random::generate_permutation(&size);
+        let randoms: Vec<BigUint> = (0..size).map(|_| Random::get_random_less_than(q)).collect();
+        let shuffled: Vec<Cipher> = ElGamal::shuffle(&encryptions, &permutation, &randoms, &pk)
+            .into_iter()
+            .map(|item| item.0)
+            .collect();
+        let vec_h = Helper::get_generators(b"Topic", &params.p, size);
+        let tables = ShuffleProverTables::new(&params);
+        let commitment_randoms: Vec<BigUint> =
+            (0..size).map(|_| Random::get_random_less_than(q)).collect();
+        let permutation_commitment = ShuffleProof::generate_permutation_commitment_with_table(
+            &params,
+            &permutation,
+            commitment_randoms,
+            vec_h.clone(),
+            &tables.g,
+        );
+
+        let context = [1u8, 2, 3];
+        let (proof, _) = ShuffleProof::prove_with_hash(
+            encryptions.clone(),
+            shuffled.clone(),
+            randoms,
+            &permutation,
+            &vec_h,
+            permutation_commitment,
+            &pk,
+            &tables,
+            ShuffleProofRandoms::random(q, size),
+            &context,
+            HashFunction::Sha3_256,
+        )
+        .unwrap();
+        assert!(ShuffleProof::verify_in_context_with_hash(
+            b"Topic",
+            &context,
+            &proof,
+            encryptions.clone(),
+            shuffled.clone(),
+            &pk,
+            HashFunction::Sha3_256
+        ));
+
+        // the challenges of the other hash functions differ
+        for hash in [HashFunction::Blake2b, HashFunction::Sha256].iter() {
+            assert!(!ShuffleProof::verify_in_context_with_hash(
+                b"Topic",
+                &context,
+                &proof,
+                encryptions.clone(),
+                shuffled.clone(),
+                &pk,
+                *hash
+            ));
+        }
+    }
+
+    #[test]
+    fn it_should_generate_the_same_shuffle_proof_in_the_integer_group() {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let size = 4usize;
+        let context = b"ballot box digest";
+        let hash = HashFunction::Sha256;
+        let encryptions = Random::generate_random_encryptions(&pk, q, size);
+        let permutation = Random::generate_permutation(&size);
+        let randoms: Vec<BigUint> = (0..size).map(|_| Random::get_random_less_than(q)).collect();
+        let shuffled: Vec<Cipher> = ElGamal::shuffle(&encryptions, &permutation, &randoms, &pk)
+            .into_iter()
+            .map(|item| item.0)
+            .collect();
+        let commitment_randoms: Vec<BigUint> =
+            (0..size).map(|_| Random::get_random_less_than(q)).collect();
+        let proof_randoms = ShuffleProofRandoms::random(q, size);
+
+        let vec_h = Helper::get_generators(b"Topic", &params.p, size);
+        let permutation_commitment = ShuffleProof::generate_permutation_commitment(
+            &params,
+            &permutation,
+            commitment_randoms.clone(),
+            vec_h.clone(),
+        );
+        let (expected, expected_t) = ShuffleProof::prove_with_hash(
+            encryptions.clone(),
+            shuffled.clone(),
+            randoms.clone(),
+            &permutation,
+            &vec_h,
+            permutation_commitment,
+            &pk,
+            &ShuffleProverTables::new(&params),
+            proof_randoms.clone(),
+            context,
+            hash,
+        )
+        .unwrap();
+
+        // the group based shuffle and proof equal the existing implementation
+        let ciphers: Vec<GroupCipher<BigUint>> = encryptions
+            .into_iter()
+            .map(|cipher| cipher.into())
+            .collect();
+        let group_shuffled =
+            ElGamal::shuffle_in_group(&params, &ciphers, &permutation, &randoms, &pk.h);
+        let shuffled: Vec<GroupCipher<BigUint>> =
+            shuffled.into_iter().map(|cipher| cipher.into()).collect();
+        assert_eq!(group_shuffled, shuffled);
+
+        let (proof, t) = ShuffleProof::prove_in_group(
+            &params,
+            b"Topic",
+            context,
+            &ciphers,
+            &shuffled,
+            &randoms,
+            &permutation,
+            &pk.h,
+            &commitment_randoms,
+            &proof_randoms,
+            hash,
+        )
+        .unwrap();
+        assert_eq!(t, expected_t);
+        assert_eq!(proof.challenge, expected.challenge);
+        assert_eq!(proof.s1, expected.s1);
+        assert_eq!(proof.s2, expected.s2);
+        assert_eq!(proof.s3, expected.s3);
+        assert_eq!(proof.s4, expected.s4);
+        assert_eq!(proof.vec_s_hat, expected.vec_s_hat);
+        assert_eq!(proof.vec_s_tilde, expected.vec_s_tilde);
+        assert_eq!(
+            proof.permutation_commitments,
+            expected.permutation_commitments
+        );
+        assert_eq!(
+            proof.permutation_chain_commitments,
+            expected.permutation_chain_commitments
+        );
+
+        assert!(ShuffleProof::verify_in_group(
+            &params, b"Topic", context, &proof, &ciphers, &shuffled, &pk.h, hash
+        ));
+        assert!(!ShuffleProof::verify_in_group(
+            &params,
+            b"Topic",
+            &[],
+            &proof,
+            &ciphers,
+            &shuffled,
+            &pk.h,
+            hash
+        ));
+        assert!(!ShuffleProof::verify_in_group(
+            &params,
+            b"Topic",
+            context,
+            &proof,
+            &ciphers,
+            &shuffled[1..],
+            &pk.h,
+            hash
+        ));
+    }
+
+    #[cfg(feature = "ristretto")]
+    #[test]
+    fn it_should_generate_a_verifiable_shuffle_proof_in_the_ristretto_group() {
+        use crate::types::{Group, Ristretto};
+
+        let group = Ristretto;
+        let q = &group.order();
+        let size = 10usize;
+        let hash = HashFunction::Blake2b;
+        let sk = Random::get_random_scalar(&group);
+        let pk = group.exp(&group.generator(), &sk);
+        let encryptions: Vec<GroupCipher<_>> = (0..size)
+            .map(|m| {
+                let m = ElGamal::encode_in_group(&group, &BigUint::from(m));
+                ElGamal::encrypt_in_group(&group, &pk, &m, &Random::get_random_scalar(&group))
+            })
+            .collect();
+        let permutation = Random::generate_permutation(&size);
+        let randoms: Vec<_> = (0..size)
+            .map(|_| Random::get_random_scalar(&group))
+            .collect();
+        let shuffled = ElGamal::shuffle_in_group(&group, &encryptions, &permutation, &randoms, &pk);
+        let commitment_randoms: Vec<BigUint> =
+            (0..size).map(|_| Random::get_random_less_than(q)).collect();
+
+        let (proof, _) = ShuffleProof::prove_in_group(
+            &group,
+            b"Topic",
+            b"context",
+            &encryptions,
+            &shuffled,
+            &randoms,
+            &permutation,
+            &pk,
+            &commitment_randoms,
+            &ShuffleProofRandoms::random(q, size),
+            hash,
+        )
+        .unwrap();
+        assert!(ShuffleProof::verify_in_group(
+            &group,
+            b"Topic",
+            b"context",
+            &proof,
+            &encryptions,
+            &shuffled,
+            &pk,
+            hash
+        ));
+
+        // the shuffled ciphers decrypt to the permuted messages
+        for (cipher, j_i) in shuffled.iter().zip(permutation.iter()) {
+            let decrypted = ElGamal::decrypt_in_group(&group, cipher, &sk);
+            assert_eq!(
+                ElGamal::decode_in_group(&group, &decrypted, size as u64),
+                Some(*j_i as u64)
+            );
+        }
+
+        // another id, a replaced cipher or swapped ciphers aren't accepted
+        assert!(!ShuffleProof::verify_in_group(
+            &group,
+            b"Another Topic",
+            b"context",
+            &proof,
+            &encryptions,
+            &shuffled,
+            &pk,
+            hash
+        ));
+        let mut replaced = shuffled.clone();
+        replaced[0] = ElGamal::re_encrypt_in_group(&group, &replaced[0], &pk, &randoms[0]);
+        assert!(!ShuffleProof::verify_in_group(
+            &group,
+            b"Topic",
+            b"context",
+            &proof,
+            &encryptions,
+            &replaced,
+            &pk,
+            hash
+        ));
+        let mut swapped = shuffled;
+        swapped.swap(0, 1);
+        assert!(!ShuffleProof::verify_in_group(
+            &group,
+            b"Topic",
+            b"context",
+            &proof,
+            &encryptions,
+            &swapped,
+            &pk,
+            hash
+        ));
+    }
 }
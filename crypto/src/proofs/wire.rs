@@ -0,0 +1,590 @@
+//! The canonical wire format of all zero-knowledge proofs.
+//!
+//! Every proof is prefixed by a header containing the version of the wire format
+//! and the kind of the proof. A big integer is always represented by its minimal
+//! big-endian bytes (zero is represented as a single zero byte).
+//!
+//! SCALE: header (version: u8, kind: u8) followed by the proof fields in declaration order.
+//! Each big integer is encoded as a byte vector (compact length + bytes),
+//! a vector of big integers as compact length followed by its big integers.
+//!
+//! serde: `{ "version": 1, "kind": "KeyGeneration", "proof": { .. } }`.
+//! Each big integer is serialized as a lower-case hex string of its bytes.
+use crate::{
+    proofs::{
+        decryption::DecryptionProof, key_switch::KeySwitchProof, keygen::KeyGenerationProof,
+        re_encryption::ReEncryptionProof,
+    },
+    types::Cipher,
+};
+use alloc::vec::Vec;
+use codec::{Compact, Decode, Encode, Error as CodecError, Input, Output};
+use num_bigint::BigUint;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// The current version of the wire format.
+pub const WIRE_FORMAT_VERSION: u8 = 1;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ProofKind {
+    KeyGeneration,
+    Decryption,
+    ReEncryption,
+    KeySwitch,
+}
+
+impl ProofKind {
+    fn to_byte(self) -> u8 {
+        match self {
+            ProofKind::KeyGeneration => 0,
+            ProofKind::Decryption => 1,
+            ProofKind::ReEncryption => 2,
+            ProofKind::KeySwitch => 3,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<ProofKind> {
+        match byte {
+            0 => Some(ProofKind::KeyGeneration),
+            1 => Some(ProofKind::Decryption),
+            2 => Some(ProofKind::ReEncryption),
+            3 => Some(ProofKind::KeySwitch),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum WireError {
+    /// the proof was created using an unknown version of the wire format
+    UnsupportedVersion(u8),
+    /// the proof is of another kind than expected
+    UnexpectedKind(ProofKind),
+    /// the bytes are not a canonical encoding of the proof
+    Malformed,
+}
+
+/// The header which precedes every proof on the wire.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ProofHeader {
+    pub version: u8,
+    pub kind: ProofKind,
+}
+
+impl ProofHeader {
+    pub fn new(kind: ProofKind) -> Self {
+        ProofHeader {
+            version: WIRE_FORMAT_VERSION,
+            kind,
+        }
+    }
+
+    /// Checks that the header belongs to a proof of the expected kind
+    /// using the current version of the wire format.
+    pub fn check(&self, kind: ProofKind) -> Result<(), WireError> {
+        if self.version != WIRE_FORMAT_VERSION {
+            return Err(WireError::UnsupportedVersion(self.version));
+        }
+        if self.kind != kind {
+            return Err(WireError::UnexpectedKind(self.kind));
+        }
+        Ok(())
+    }
+}
+
+impl Encode for ProofHeader {
+    fn encode_to<T: Output>(&self, dest: &mut T) {
+        dest.push_byte(self.version);
+        dest.push_byte(self.kind.to_byte());
+    }
+}
+
+impl Decode for ProofHeader {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, CodecError> {
+        let version = input.read_byte()?;
+        let kind = ProofKind::from_byte(input.read_byte()?).ok_or("unknown proof kind")?;
+        Ok(ProofHeader { version, kind })
+    }
+}
+
+/// A proof which can be transmitted using the canonical wire format.
+pub trait WireProof: Encode + Decode {
+    const KIND: ProofKind;
+
+    /// Returns the SCALE encoded proof including its header.
+    fn to_wire(&self) -> Vec<u8> {
+        let mut bytes = ProofHeader::new(Self::KIND).encode();
+        self.encode_to(&mut bytes);
+        bytes
+    }
+
+    /// Decodes a SCALE encoded proof including its header.
+    /// Fails if the header doesn't match or the bytes are not fully consumed.
+    fn from_wire(mut bytes: &[u8]) -> Result<Self, WireError> {
+        let header = ProofHeader::decode(&mut bytes).map_err(|_| WireError::Malformed)?;
+        header.check(Self::KIND)?;
+        let proof = Self::decode(&mut bytes).map_err(|_| WireError::Malformed)?;
+        if !bytes.is_empty() {
+            return Err(WireError::Malformed);
+        }
+        Ok(proof)
+    }
+}
+
+/// A proof together with its header, used for the serde serialization.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VersionedProof<P> {
+    pub version: u8,
+    pub kind: ProofKind,
+    pub proof: P,
+}
+
+impl<P: WireProof> VersionedProof<P> {
+    pub fn new(proof: P) -> Self {
+        VersionedProof {
+            version: WIRE_FORMAT_VERSION,
+            kind: P::KIND,
+            proof,
+        }
+    }
+
+    /// Returns the proof if the header matches the proof.
+    pub fn into_proof(self) -> Result<P, WireError> {
+        ProofHeader {
+            version: self.version,
+            kind: self.kind,
+        }
+        .check(P::KIND)?;
+        Ok(self.proof)
+    }
+}
+
+fn encode_biguint<T: Output>(value: &BigUint, dest: &mut T) {
+    value.to_bytes_be().encode_to(dest);
+}
+
+fn decode_biguint<I: Input>(input: &mut I) -> Result<BigUint, CodecError> {
+    let bytes: Vec<u8> = Vec::decode(input)?;
+    // only the minimal big-endian representation is accepted
+    if bytes.is_empty() || (bytes.len() > 1 && bytes[0] == 0) {
+        return Err("non-canonical big integer".into());
+    }
+    Ok(BigUint::from_bytes_be(&bytes))
+}
+
+fn encode_biguints<T: Output>(values: &[BigUint], dest: &mut T) {
+    Compact(values.len() as u32).encode_to(dest);
+    for value in values.iter() {
+        encode_biguint(value, dest);
+    }
+}
+
+fn decode_biguints<I: Input>(input: &mut I) -> Result<Vec<BigUint>, CodecError> {
+    let length = <Compact<u32>>::decode(input)?.0;
+    let mut values: Vec<BigUint> = Vec::new();
+    for _ in 0..length {
+        values.push(decode_biguint(input)?);
+    }
+    Ok(values)
+}
+
+impl Encode for KeyGenerationProof {
+    fn encode_to<T: Output>(&self, dest: &mut T) {
+        encode_biguint(&self.challenge, dest);
+        encode_biguint(&self.response, dest);
+    }
+}
+
+impl Decode for KeyGenerationProof {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, CodecError> {
+        Ok(KeyGenerationProof {
+            challenge: decode_biguint(input)?,
+            response: decode_biguint(input)?,
+        })
+    }
+}
+
+impl WireProof for KeyGenerationProof {
+    const KIND: ProofKind = ProofKind::KeyGeneration;
+}
+
+impl Encode for DecryptionProof {
+    fn encode_to<T: Output>(&self, dest: &mut T) {
+        encode_biguint(&self.challenge, dest);
+        encode_biguint(&self.response, dest);
+    }
+}
+
+impl Decode for DecryptionProof {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, CodecError> {
+        Ok(DecryptionProof {
+            challenge: decode_biguint(input)?,
+            response: decode_biguint(input)?,
+        })
+    }
+}
+
+impl WireProof for DecryptionProof {
+    const KIND: ProofKind = ProofKind::Decryption;
+}
+
+impl Encode for ReEncryptionProof {
+    fn encode_to<T: Output>(&self, dest: &mut T) {
+        encode_biguint(&self.c_one_prime.a, dest);
+        encode_biguint(&self.c_one_prime.b, dest);
+        encode_biguint(&self.challenge, dest);
+        encode_biguint(&self.h1, dest);
+        encode_biguint(&self.h2, dest);
+        encode_biguint(&self.s2, dest);
+        encode_biguint(&self.t2, dest);
+    }
+}
+
+impl Decode for ReEncryptionProof {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, CodecError> {
+        Ok(ReEncryptionProof {
+            c_one_prime: Cipher {
+                a: decode_biguint(input)?,
+                b: decode_biguint(input)?,
+            },
+            challenge: decode_biguint(input)?,
+            h1: decode_biguint(input)?,
+            h2: decode_biguint(input)?,
+            s2: decode_biguint(input)?,
+            t2: decode_biguint(input)?,
+        })
+    }
+}
+
+impl WireProof for ReEncryptionProof {
+    const KIND: ProofKind = ProofKind::ReEncryption;
+}
+
+impl Encode for KeySwitchProof {
+    fn encode_to<T: Output>(&self, dest: &mut T) {
+        encode_biguint(&self.challenge, dest);
+        encode_biguint(&self.response, dest);
+        encode_biguints(&self.responses, dest);
+    }
+}
+
+impl Decode for KeySwitchProof {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, CodecError> {
+        Ok(KeySwitchProof {
+            challenge: decode_biguint(input)?,
+            response: decode_biguint(input)?,
+            responses: decode_biguints(input)?,
+        })
+    }
+}
+
+impl WireProof for KeySwitchProof {
+    const KIND: ProofKind = ProofKind::KeySwitch;
+}
+
+/// serde helpers to serialize big integers as hex strings of their minimal big-endian bytes.
+#[cfg(feature = "std")]
+pub mod serde_hex {
+    use num_bigint::BigUint;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::{string::String, vec::Vec};
+
+    fn to_hex(value: &BigUint) -> String {
+        value
+            .to_bytes_be()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    fn from_hex(hex: &str) -> Option<BigUint> {
+        // only lower-case, minimal representations are accepted
+        let is_lower_hex = hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'));
+        if hex.is_empty() || hex.len() & 1 == 1 || !is_lower_hex {
+            return None;
+        }
+        if hex.len() > 2 && hex.starts_with("00") {
+            return None;
+        }
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        Some(BigUint::from_bytes_be(&bytes))
+    }
+
+    pub fn serialize<S: Serializer>(value: &BigUint, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigUint, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        from_hex(&hex).ok_or_else(|| D::Error::custom("invalid big integer hex string"))
+    }
+
+    /// serializes a vector of big integers as a vector of hex strings
+    pub mod vec {
+        use num_bigint::BigUint;
+        use serde::{de::Error, ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+        use std::{string::String, vec::Vec};
+
+        pub fn serialize<S: Serializer>(
+            values: &[BigUint],
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(values.len()))?;
+            for value in values.iter() {
+                seq.serialize_element(&super::to_hex(value))?;
+            }
+            seq.end()
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Vec<BigUint>, D::Error> {
+            let hexes = Vec::<String>::deserialize(deserializer)?;
+            hexes
+                .iter()
+                .map(|hex| super::from_hex(hex))
+                .collect::<Option<Vec<BigUint>>>()
+                .ok_or_else(|| D::Error::custom("invalid big integer hex string"))
+        }
+    }
+
+    /// serializes a cipher as { "a": hex, "b": hex }
+    pub mod cipher {
+        use crate::types::Cipher;
+        use num_bigint::BigUint;
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        #[derive(Serialize, Deserialize)]
+        struct HexCipher {
+            #[serde(with = "super")]
+            a: BigUint,
+            #[serde(with = "super")]
+            b: BigUint,
+        }
+
+        pub fn serialize<S: Serializer>(cipher: &Cipher, serializer: S) -> Result<S::Ok, S::Error> {
+            HexCipher {
+                a: cipher.a.clone(),
+                b: cipher.b.clone(),
+            }
+            .serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Cipher, D::Error> {
+            let cipher = HexCipher::deserialize(deserializer)?;
+            Ok(Cipher {
+                a: cipher.a,
+                b: cipher.b,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProofKind, VersionedProof, WireError, WireProof, WIRE_FORMAT_VERSION};
+    use crate::{
+        encryption::ElGamal,
+        helper::Helper,
+        proofs::{
+            decryption::DecryptionProof, key_switch::KeySwitchProof, keygen::KeyGenerationProof,
+            re_encryption::ReEncryptionProof,
+        },
+        random::Random,
+        types::Cipher,
+    };
+    use alloc::vec::Vec;
+    use num_bigint::BigUint;
+    use num_traits::{One, Zero};
+
+    fn small_keygen_proof() -> KeyGenerationProof {
+        KeyGenerationProof {
+            challenge: BigUint::from(258u32),
+            response: BigUint::zero(),
+        }
+    }
+
+    fn random_re_encryption_proof() -> ReEncryptionProof {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let r1 = Random::get_random_less_than(q);
+        let c_one = ElGamal::encrypt(&BigUint::one(), &r1, &pk);
+        ReEncryptionProof::generate(
+            &r1,
+            &Random::get_random_less_than(q),
+            &Random::get_random_less_than(q),
+            &Random::get_random_less_than(q),
+            &c_one,
+            &pk,
+        )
+    }
+
+    fn random_key_switch_proof() -> KeySwitchProof {
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let (new_pk, _) = Helper::generate_key_pair(&params, &Random::get_random_less_than(q));
+        let encryptions = Random::generate_random_encryptions(&pk, q, 2);
+        let randoms = (0..2)
+            .map(|_| Random::get_random_less_than(q))
+            .collect::<Vec<BigUint>>();
+        let shares = encryptions
+            .iter()
+            .zip(randoms.iter())
+            .map(|(cipher, s)| ElGamal::key_switch_share(cipher, &sk, s, &new_pk))
+            .collect::<Vec<Cipher>>();
+        let vec_r = (0..2)
+            .map(|_| Random::get_random_less_than(q))
+            .collect::<Vec<BigUint>>();
+        KeySwitchProof::generate(
+            &params,
+            &sk.x,
+            &pk.h,
+            &new_pk.h,
+            &randoms,
+            &Random::get_random_less_than(q),
+            &vec_r,
+            encryptions,
+            shares,
+            "Bob".as_bytes(),
+        )
+    }
+
+    #[test]
+    fn it_should_encode_the_header_and_minimal_big_endian_bytes() {
+        let bytes = small_keygen_proof().to_wire();
+
+        // version, kind, challenge: [len: 2, 0x01, 0x02], response: [len: 1, 0x00]
+        let expected: Vec<u8> = vec![WIRE_FORMAT_VERSION, 0, 8, 1, 2, 4, 0];
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn it_should_round_trip_all_proof_kinds() {
+        let keygen = small_keygen_proof();
+        assert_eq!(KeyGenerationProof::from_wire(&keygen.to_wire()), Ok(keygen));
+
+        let decryption = DecryptionProof {
+            challenge: BigUint::from(123_456_789u32),
+            response: BigUint::from(987_654_321u32),
+        };
+        assert_eq!(
+            DecryptionProof::from_wire(&decryption.to_wire()),
+            Ok(decryption)
+        );
+
+        let re_encryption = random_re_encryption_proof();
+        assert_eq!(
+            ReEncryptionProof::from_wire(&re_encryption.to_wire()),
+            Ok(re_encryption)
+        );
+
+        let key_switch = random_key_switch_proof();
+        assert_eq!(
+            KeySwitchProof::from_wire(&key_switch.to_wire()),
+            Ok(key_switch)
+        );
+    }
+
+    #[test]
+    fn it_should_reject_an_invalid_header() {
+        let mut bytes = small_keygen_proof().to_wire();
+
+        // a proof of another kind
+        assert_eq!(
+            DecryptionProof::from_wire(&bytes),
+            Err(WireError::UnexpectedKind(ProofKind::KeyGeneration))
+        );
+
+        // an unknown version
+        bytes[0] = WIRE_FORMAT_VERSION + 1;
+        assert_eq!(
+            KeyGenerationProof::from_wire(&bytes),
+            Err(WireError::UnsupportedVersion(WIRE_FORMAT_VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn it_should_reject_non_canonical_encodings() {
+        // trailing bytes
+        let mut bytes = small_keygen_proof().to_wire();
+        bytes.push(0);
+        assert_eq!(
+            KeyGenerationProof::from_wire(&bytes),
+            Err(WireError::Malformed)
+        );
+
+        // leading zero byte: challenge = [0x00, 0x01, 0x02]
+        let bytes: Vec<u8> = vec![WIRE_FORMAT_VERSION, 0, 12, 0, 1, 2, 4, 0];
+        assert_eq!(
+            KeyGenerationProof::from_wire(&bytes),
+            Err(WireError::Malformed)
+        );
+
+        // truncated proof
+        let bytes = random_key_switch_proof().to_wire();
+        assert_eq!(
+            KeySwitchProof::from_wire(&bytes[..bytes.len() - 1]),
+            Err(WireError::Malformed)
+        );
+    }
+
+    #[test]
+    fn it_should_check_the_header_of_a_versioned_proof() {
+        let proof = VersionedProof::new(small_keygen_proof());
+        assert_eq!(proof.kind, ProofKind::KeyGeneration);
+        assert_eq!(proof.clone().into_proof(), Ok(small_keygen_proof()));
+
+        let outdated = VersionedProof {
+            version: 0,
+            ..proof
+        };
+        assert_eq!(outdated.into_proof(), Err(WireError::UnsupportedVersion(0)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn it_should_serialize_a_versioned_proof_as_json() {
+        let json = serde_json::to_string(&VersionedProof::new(small_keygen_proof())).unwrap();
+        assert_eq!(
+            json,
+            r#"{"version":1,"kind":"KeyGeneration","proof":{"challenge":"0102","response":"00"}}"#
+        );
+        let decoded: VersionedProof<KeyGenerationProof> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.into_proof(), Ok(small_keygen_proof()));
+
+        // upper-case and non-minimal hex strings are rejected
+        let upper_case = json.replace("0102", "0A02");
+        assert!(serde_json::from_str::<VersionedProof<KeyGenerationProof>>(&upper_case).is_err());
+        let leading_zero = json.replace("0102", "000102");
+        assert!(serde_json::from_str::<VersionedProof<KeyGenerationProof>>(&leading_zero).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn it_should_round_trip_all_proof_kinds_as_json() {
+        let re_encryption = VersionedProof::new(random_re_encryption_proof());
+        let json = serde_json::to_string(&re_encryption).unwrap();
+        let decoded: VersionedProof<ReEncryptionProof> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, re_encryption);
+
+        let key_switch = VersionedProof::new(random_key_switch_proof());
+        let json = serde_json::to_string(&key_switch).unwrap();
+        let decoded: VersionedProof<KeySwitchProof> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, key_switch);
+
+        let decryption = VersionedProof::new(DecryptionProof {
+            challenge: BigUint::from(42u32),
+            response: BigUint::from(4242u32),
+        });
+        let json = serde_json::to_string(&decryption).unwrap();
+        let decoded: VersionedProof<DecryptionProof> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, decryption);
+    }
+}
@@ -0,0 +1,307 @@
+//! Property-based tests of the crypto primitives.
+//!
+//! The parameters are one of the predefined systems, all other values (the private key,
+//! messages, randomness and permutations) are generated randomly by proptest.
+use crate::{
+    encryption::ElGamal,
+    helper::Helper,
+    proofs::{
+        decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
+        keygen::KeyGenerationProof,
+        re_encryption::ReEncryptionProof,
+        wire::{VersionedProof, WireProof},
+    },
+    types::{Cipher, ElGamalParams, PrivateKey, PublicKey},
+};
+use alloc::vec::Vec;
+use num_bigint::BigUint;
+use num_traits::One;
+use proptest::prelude::*;
+
+/// the largest message which is encrypted (encoded messages are decoded by brute force)
+const MAX_MESSAGE: u32 = 1000;
+
+/// the (id of the) prover which is bound to all proofs
+const PROVER_ID: &[u8] = b"Bob";
+
+/// an ElGamal system with a randomly generated key pair
+fn system() -> impl Strategy<Value = (ElGamalParams, PrivateKey, PublicKey)> {
+    (0..3usize, random()).prop_map(|(index, x)| {
+        let (params, _, _) = match index {
+            0 => Helper::setup_sm_system(),
+            1 => Helper::setup_256bit_system(),
+            _ => Helper::setup_512bit_system(),
+        };
+        // the private key must be in [1, q)
+        let q = params.q();
+        let x = x % (&q - BigUint::one()) + BigUint::one();
+        let (pk, sk) = Helper::generate_key_pair(&params, &x);
+        (params, sk, pk)
+    })
+}
+
+/// a random value which is reduced modulo q by the test (at most 512 bits)
+fn random() -> impl Strategy<Value = BigUint> {
+    prop::collection::vec(any::<u8>(), 64).prop_map(|bytes| BigUint::from_bytes_be(&bytes))
+}
+
+/// maps the message into the group of quadratic residues, i.e., m^2 mod p
+fn group_element(m: &BigUint, params: &ElGamalParams) -> BigUint {
+    m.modpow(&BigUint::from(2u32), &params.p)
+}
+
+/// messages together with a random value and a position (permutation) for each message
+fn batch() -> impl Strategy<Value = (Vec<u32>, Vec<BigUint>, Vec<usize>)> {
+    (1..8usize).prop_flat_map(|size| {
+        (
+            prop::collection::vec(0..MAX_MESSAGE, size),
+            prop::collection::vec(random(), size),
+            Just((0..size).collect::<Vec<usize>>()).prop_shuffle(),
+        )
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn decrypt_inverts_encrypt((_, sk, pk) in system(), m in 1..MAX_MESSAGE, r in random()) {
+        let r = r % pk.params.q();
+        let m = BigUint::from(m);
+
+        let encoded = ElGamal::encrypt_encode(&m, &r, &pk);
+        prop_assert_eq!(ElGamal::decrypt_decode(&encoded, &sk), m.clone());
+
+        // a message which is not encoded must be an element of the group
+        let m = group_element(&m, &pk.params);
+        let cipher = ElGamal::encrypt(&m, &r, &pk);
+        prop_assert_eq!(ElGamal::decrypt(&cipher, &sk), m);
+    }
+
+    #[test]
+    fn re_encrypt_preserves_the_message(
+        (_, sk, pk) in system(),
+        m in 0..MAX_MESSAGE,
+        r in random(),
+        r_prime in random(),
+    ) {
+        let q = pk.params.q();
+        let m = BigUint::from(m);
+        let cipher = ElGamal::encrypt_encode(&m, &(r % &q), &pk);
+
+        let re_encrypted = ElGamal::re_encrypt_via_addition(&cipher, &(r_prime % &q), &pk);
+        prop_assert_eq!(ElGamal::decrypt_decode(&re_encrypted, &sk), m);
+    }
+
+    #[test]
+    fn homomorphic_addition_adds_the_messages(
+        (_, sk, pk) in system(),
+        m1 in 0..MAX_MESSAGE,
+        m2 in 0..MAX_MESSAGE,
+        r1 in random(),
+        r2 in random(),
+    ) {
+        let q = pk.params.q();
+        let c1 = ElGamal::encrypt_encode(&BigUint::from(m1), &(r1 % &q), &pk);
+        let c2 = ElGamal::encrypt_encode(&BigUint::from(m2), &(r2 % &q), &pk);
+
+        let sum = ElGamal::homomorphic_addition(&c1, &c2, &pk.params.p);
+        prop_assert_eq!(ElGamal::decrypt_decode(&sum, &sk), BigUint::from(m1 + m2));
+    }
+
+    #[test]
+    fn shuffle_preserves_the_multiset_of_plaintexts(
+        (_, sk, pk) in system(),
+        (messages, randoms, permutation) in batch(),
+    ) {
+        let q = pk.params.q();
+        let randoms: Vec<BigUint> = randoms.into_iter().map(|r| r % &q).collect();
+        let encryptions: Vec<Cipher> = messages
+            .iter()
+            .zip(randoms.iter())
+            .map(|(m, r)| ElGamal::encrypt_encode(&BigUint::from(*m), r, &pk))
+            .collect();
+
+        let shuffled = ElGamal::shuffle(&encryptions, &permutation, &randoms, &pk);
+        prop_assert_eq!(shuffled.len(), messages.len());
+
+        // the cipher at position i is a re-encryption of the cipher at position permutation[i]
+        for (i, (cipher, _, position)) in shuffled.iter().enumerate() {
+            prop_assert_eq!(*position, permutation[i]);
+            prop_assert_eq!(
+                ElGamal::decrypt_decode(cipher, &sk),
+                BigUint::from(messages[*position])
+            );
+        }
+
+        let mut plaintexts: Vec<BigUint> = shuffled
+            .iter()
+            .map(|(cipher, _, _)| ElGamal::decrypt_decode(cipher, &sk))
+            .collect();
+        let mut expected: Vec<BigUint> = messages.into_iter().map(BigUint::from).collect();
+        plaintexts.sort();
+        expected.sort();
+        prop_assert_eq!(plaintexts, expected);
+    }
+
+    #[test]
+    fn keygen_proof_verifies((params, sk, pk) in system(), r in random()) {
+        let r = r % params.q();
+        let proof = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &r, PROVER_ID);
+        prop_assert!(KeyGenerationProof::verify(&params, &pk.h, &proof, PROVER_ID));
+
+        // the proof is bound to the prover
+        prop_assert!(!KeyGenerationProof::verify(&params, &pk.h, &proof, b"Charlie"));
+    }
+
+    #[test]
+    fn decryption_proof_verifies(
+        (params, sk, pk) in system(),
+        (messages, randoms, _) in batch(),
+        r in random(),
+    ) {
+        let q = params.q();
+        let encryptions: Vec<Cipher> = messages
+            .iter()
+            .zip(randoms.into_iter())
+            .map(|(m, r)| ElGamal::encrypt_encode(&BigUint::from(*m), &(r % &q), &pk))
+            .collect();
+        let decryptions: Vec<BigUint> = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &sk))
+            .collect();
+
+        let proof = DecryptionProof::generate(
+            &params,
+            &sk.x,
+            &pk.h,
+            &(r % &q),
+            encryptions.clone(),
+            decryptions.clone(),
+            PROVER_ID,
+        );
+        prop_assert!(DecryptionProof::verify(
+            &params,
+            &pk.h,
+            &proof,
+            encryptions,
+            decryptions,
+            PROVER_ID
+        ));
+    }
+
+    #[test]
+    fn batched_decryption_proof_verifies(
+        (params, sk, pk) in system(),
+        (messages, randoms, _) in batch(),
+        r in random(),
+    ) {
+        let q = params.q();
+        let encryptions: Vec<Cipher> = messages
+            .iter()
+            .zip(randoms.into_iter())
+            .map(|(m, r)| ElGamal::encrypt_encode(&BigUint::from(*m), &(r % &q), &pk))
+            .collect();
+        let decryptions: Vec<BigUint> = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &sk))
+            .collect();
+
+        let proof = DecryptionProof::generate_batched(
+            &params,
+            &sk.x,
+            &pk.h,
+            &(r % &q),
+            &encryptions,
+            &decryptions,
+            PROVER_ID,
+            DEFAULT_BATCH_SOUNDNESS,
+        );
+        prop_assert!(DecryptionProof::verify_batched(
+            &params,
+            &pk.h,
+            &proof,
+            &encryptions,
+            &decryptions,
+            PROVER_ID,
+            DEFAULT_BATCH_SOUNDNESS,
+        ));
+    }
+
+    #[test]
+    fn re_encryption_proof_verifies(
+        (params, _, pk) in system(),
+        m in 1..MAX_MESSAGE,
+        randoms in prop::collection::vec(random(), 5),
+    ) {
+        let q = params.q();
+        let randoms: Vec<BigUint> = randoms.into_iter().map(|r| r % &q).collect();
+        let m = group_element(&BigUint::from(m), &params);
+        let cipher = ElGamal::encrypt(&m, &randoms[0], &pk);
+        let re_encrypted = ElGamal::re_encrypt(&cipher, &randoms[1], &pk);
+
+        // the encryption of one with the re-encryption random r1
+        let c_one = ElGamal::encrypt(&BigUint::one(), &randoms[1], &pk);
+        let proof = ReEncryptionProof::generate(
+            &randoms[1],
+            &randoms[2],
+            &randoms[3],
+            &randoms[4],
+            &c_one,
+            &pk,
+        );
+        prop_assert!(ReEncryptionProof::verify(&pk, &proof, &cipher, &re_encrypted));
+    }
+
+    #[test]
+    fn challenge_is_stable_across_scale_round_trips((params, sk, pk) in system(), r in random()) {
+        let r = r % params.q();
+        let proof = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &r, PROVER_ID);
+
+        let decoded = KeyGenerationProof::from_wire(&proof.to_wire()).unwrap();
+        prop_assert_eq!(&decoded.challenge, &proof.challenge);
+        prop_assert_eq!(&decoded, &proof);
+        prop_assert!(KeyGenerationProof::verify(&params, &pk.h, &decoded, PROVER_ID));
+    }
+
+    #[test]
+    fn challenge_is_stable_across_serde_round_trips(
+        (params, sk, pk) in system(),
+        (messages, randoms, _) in batch(),
+        r in random(),
+    ) {
+        let q = params.q();
+        let encryptions: Vec<Cipher> = messages
+            .iter()
+            .zip(randoms.into_iter())
+            .map(|(m, r)| ElGamal::encrypt_encode(&BigUint::from(*m), &(r % &q), &pk))
+            .collect();
+        let decryptions: Vec<BigUint> = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &sk))
+            .collect();
+        let proof = DecryptionProof::generate(
+            &params,
+            &sk.x,
+            &pk.h,
+            &(r % &q),
+            encryptions.clone(),
+            decryptions.clone(),
+            PROVER_ID,
+        );
+
+        let json = serde_json::to_string(&VersionedProof::new(proof.clone())).unwrap();
+        let decoded: VersionedProof<DecryptionProof> = serde_json::from_str(&json).unwrap();
+        let decoded = decoded.into_proof().unwrap();
+        prop_assert_eq!(&decoded.challenge, &proof.challenge);
+        prop_assert_eq!(&decoded, &proof);
+        prop_assert!(DecryptionProof::verify(
+            &params,
+            &pk.h,
+            &decoded,
+            encryptions,
+            decryptions,
+            PROVER_ID
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     encryption::ElGamal,
-    types::{Cipher, PublicKey},
+    types::{Cipher, Group, PublicKey},
 };
 use alloc::vec::Vec;
 use core::ops::{AddAssign, Sub};
@@ -164,6 +164,11 @@ impl Random {
         rng.gen_biguint(bit_size) % number
     }
 
+    /// Returns a random exponent in Z_q of the group, e.g. to encrypt in the group.
+    pub fn get_random_scalar<G: Group>(group: &G) -> G::Scalar {
+        group.scalar_from_biguint(&Self::get_random_less_than(&group.order()))
+    }
+
     /// Generates a random prime
     ///
     /// Arguments
@@ -0,0 +1,109 @@
+//! Zeroization of secret values, e.g. private keys, re-encryption randoms and permutations.
+//!
+//! With the `zeroize` feature (enabled by default) the secret-bearing types
+//! (`PrivateKey`, `ShuffleProofRandoms` and `ShareFragment`) overwrite their values with zeros
+//! when they are dropped and the provers wipe their intermediate secrets before returning.
+//! Without the feature all functions of this module do nothing.
+//!
+//! `num-bigint` has no support for zeroization and doesn't expose its digits. A `BigUint` is
+//! wiped by a bitwise AND with a mask of zeros which covers all of its digits, the AND is
+//! computed in place, i.e. the digits are overwritten before the `BigUint` is normalized to
+//! zero. This is best effort: copies made by earlier reallocations can't be reached.
+use alloc::vec::Vec;
+use num_bigint::BigUint;
+
+#[cfg(feature = "zeroize")]
+use num_traits::One;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// overwrites all digits of the value with zeros, the value is zero afterwards
+#[cfg(feature = "zeroize")]
+pub fn wipe(value: &mut BigUint) {
+    // the mask is a 1 followed by zeros, it has at least as many (64 bit) digits as the value
+    let mask = BigUint::one() << (value.bits() + 64);
+    *value &= &mask;
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+#[cfg(not(feature = "zeroize"))]
+pub fn wipe(_value: &mut BigUint) {}
+
+/// wipes all values and clears the vector
+#[cfg(feature = "zeroize")]
+pub fn wipe_all(values: &mut Vec<BigUint>) {
+    values.iter_mut().for_each(wipe);
+    values.clear();
+}
+
+#[cfg(not(feature = "zeroize"))]
+pub fn wipe_all(_values: &mut Vec<BigUint>) {}
+
+/// overwrites the permutation (including its spare capacity) with zeros and clears it
+#[cfg(feature = "zeroize")]
+pub fn wipe_permutation(permutation: &mut Vec<usize>) {
+    permutation.zeroize();
+}
+
+#[cfg(not(feature = "zeroize"))]
+pub fn wipe_permutation(_permutation: &mut Vec<usize>) {}
+
+#[cfg(test)]
+#[cfg(feature = "zeroize")]
+mod tests {
+    use super::{wipe, wipe_all, wipe_permutation};
+    use crate::helper::Helper;
+    use crate::proofs::shuffle::ShuffleProofRandoms;
+    use alloc::vec::Vec;
+    use num_bigint::BigUint;
+    use num_traits::{One, Zero};
+    use zeroize::Zeroize;
+
+    #[test]
+    fn it_should_wipe_a_biguint() {
+        let mut value =
+            BigUint::parse_bytes(b"B7E151628AED2A6ABF7158809CF4F3C762E7160F", 16).unwrap();
+        wipe(&mut value);
+        assert_eq!(value, BigUint::zero());
+
+        let mut value = BigUint::one() << 64;
+        wipe(&mut value);
+        assert_eq!(value, BigUint::zero());
+
+        let mut value = BigUint::zero();
+        wipe(&mut value);
+        assert_eq!(value, BigUint::zero());
+    }
+
+    #[test]
+    fn it_should_wipe_all_values_and_the_permutation() {
+        let mut values: Vec<BigUint> = vec![BigUint::from(7u32), BigUint::one() << 300];
+        wipe_all(&mut values);
+        assert!(values.is_empty());
+
+        let mut permutation: Vec<usize> = vec![2, 0, 1];
+        wipe_permutation(&mut permutation);
+        assert!(permutation.is_empty());
+    }
+
+    #[test]
+    fn it_should_zeroize_the_private_key() {
+        let (params, mut sk, _) = Helper::setup_sm_system();
+        assert!(sk.x > BigUint::zero());
+        sk.zeroize();
+        assert_eq!(sk.x, BigUint::zero());
+        // the public parameters are kept
+        assert_eq!(sk.params, params);
+    }
+
+    #[test]
+    fn it_should_zeroize_the_shuffle_proof_randoms() {
+        let q = BigUint::from(11u32);
+        let mut randoms = ShuffleProofRandoms::random(&q, 3);
+        randoms.zeroize();
+        assert!(randoms.vec_r_hat.is_empty());
+        assert!(randoms.vec_w_hat.is_empty());
+        assert!(randoms.vec_w_tilde.is_empty());
+        assert_eq!(randoms.w4, BigUint::zero());
+    }
+}
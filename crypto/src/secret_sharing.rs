@@ -0,0 +1,225 @@
+//! Shamir secret sharing (t-out-of-n) over Z_q.
+//!
+//! Used to back up the private key share of a sealer: the key share is split into n fragments,
+//! any t of them reconstruct it, while t - 1 fragments reveal nothing about it.
+use crate::types::ModuloOperations;
+use alloc::vec::Vec;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// A single fragment of a shared secret: the point (index, f(index)) of the secret polynomial f.
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ShareFragment {
+    pub index: u32,
+    #[cfg_attr(feature = "std", serde(with = "crate::proofs::wire::serde_hex"))]
+    pub value: BigUint,
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for ShareFragment {
+    fn zeroize(&mut self) {
+        crate::secret::wipe(&mut self.value);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for ShareFragment {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+pub struct SecretSharing;
+
+impl SecretSharing {
+    /// Splits the secret into `nr_of_fragments` fragments.
+    /// The threshold is t = coefficients.len() + 1, i.e., the secret polynomial is
+    /// f(x) = secret + c_1 * x + ... + c_{t-1} * x^{t-1} mod q.
+    /// The coefficients must be chosen uniformly at random from Z_q.
+    ///
+    /// Returns None if fewer fragments than the threshold are requested or the indices exceed Z_q.
+    pub fn split(
+        secret: &BigUint,
+        coefficients: &[BigUint],
+        nr_of_fragments: u32,
+        q: &BigUint,
+    ) -> Option<Vec<ShareFragment>> {
+        let threshold = coefficients.len() + 1;
+        if (nr_of_fragments as usize) < threshold
+            || secret >= q
+            || &BigUint::from(nr_of_fragments) >= q
+        {
+            return None;
+        }
+
+        // the fragments are evaluated at x = 1..n, f(0) is the secret
+        let fragments = (1..=nr_of_fragments)
+            .map(|index| ShareFragment {
+                index,
+                value: Self::evaluate(secret, coefficients, &BigUint::from(index), q),
+            })
+            .collect();
+        Some(fragments)
+    }
+
+    /// Reconstructs the secret f(0) from the fragments using Lagrange interpolation.
+    /// All fragments are used, at least t fragments are required to get the correct secret.
+    ///
+    /// Returns None if there are no fragments or an index is either zero, not in Z_q or used more than once.
+    pub fn reconstruct(fragments: &[ShareFragment], q: &BigUint) -> Option<BigUint> {
+        let indices: Vec<u32> = fragments.iter().map(|fragment| fragment.index).collect();
+        let coefficients = Self::lagrange_coefficients(&indices, q)?;
+
+        // f(0) = sum(y_i * lambda_i) mod q
+        let secret = fragments
+            .iter()
+            .zip(coefficients.iter())
+            .fold(BigUint::zero(), |secret, (fragment, lambda)| {
+                secret.modadd(&fragment.value.modmul(lambda, q), q)
+            });
+        Some(secret)
+    }
+
+    /// Returns the Lagrange coefficients lambda_i = prod_{j != i}(x_j / (x_j - x_i)) mod q
+    /// which interpolate f(0) from the points at the indices x_i.
+    /// Allows to interpolate in the exponent, e.g., to combine the partial decryptions a^f(x_i)
+    /// of any t sealers into a^f(0) = prod((a^f(x_i))^lambda_i) mod p.
+    ///
+    /// Returns None if there are no indices or an index is either zero, not in Z_q or used more than once.
+    pub fn lagrange_coefficients(indices: &[u32], q: &BigUint) -> Option<Vec<BigUint>> {
+        if indices.is_empty() {
+            return None;
+        }
+        for (i, index) in indices.iter().enumerate() {
+            if *index == 0 || &BigUint::from(*index) >= q || indices[..i].contains(index) {
+                return None;
+            }
+        }
+
+        let mut coefficients: Vec<BigUint> = Vec::with_capacity(indices.len());
+        for index in indices.iter() {
+            let x_i = BigUint::from(*index);
+            let mut numerator = BigUint::one();
+            let mut denominator = BigUint::one();
+            for other in indices.iter().filter(|other| *other != index) {
+                let x_j = BigUint::from(*other);
+                numerator = numerator.modmul(&x_j, q);
+                denominator = denominator.modmul(&x_j.modsub(&x_i, q), q);
+            }
+            coefficients.push(numerator.moddiv(&denominator, q)?);
+        }
+        Some(coefficients)
+    }
+
+    /// Evaluates the secret polynomial at x using Horner's method.
+    fn evaluate(secret: &BigUint, coefficients: &[BigUint], x: &BigUint, q: &BigUint) -> BigUint {
+        coefficients
+            .iter()
+            .rev()
+            .fold(BigUint::zero(), |acc, coefficient| {
+                acc.modmul(x, q).modadd(coefficient, q)
+            })
+            .modmul(x, q)
+            .modadd(secret, q)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helper::Helper, random::Random, secret_sharing::SecretSharing, types::ModuloOperations,
+    };
+    use alloc::vec::Vec;
+    use num_bigint::BigUint;
+    use num_traits::One;
+
+    #[test]
+    fn it_should_reconstruct_the_secret_from_any_t_fragments() {
+        let (params, sk, _) = Helper::setup_sm_system();
+        let q = &params.q();
+
+        // 2-out-of-3
+        let coefficients = vec![Random::get_random_less_than(q)];
+        let fragments = SecretSharing::split(&sk.x, &coefficients, 3, q).unwrap();
+        assert_eq!(fragments.len(), 3);
+
+        for (i, j) in [(0, 1), (0, 2), (1, 2), (2, 0)].iter() {
+            let subset = vec![fragments[*i].clone(), fragments[*j].clone()];
+            let secret = SecretSharing::reconstruct(&subset, q).unwrap();
+            assert_eq!(secret, sk.x);
+        }
+
+        // all fragments
+        let secret = SecretSharing::reconstruct(&fragments, q).unwrap();
+        assert_eq!(secret, sk.x);
+    }
+
+    #[test]
+    fn it_should_not_reconstruct_the_secret_from_less_than_t_fragments() {
+        let (params, sk, _) = Helper::setup_md_system();
+        let q = &params.q();
+
+        // 3-out-of-5
+        let coefficients: Vec<BigUint> = (0..2).map(|_| Random::get_random_less_than(q)).collect();
+        let fragments = SecretSharing::split(&sk.x, &coefficients, 5, q).unwrap();
+
+        let secret = SecretSharing::reconstruct(&fragments[1..4], q).unwrap();
+        assert_eq!(secret, sk.x);
+
+        let secret = SecretSharing::reconstruct(&fragments[3..], q).unwrap();
+        assert_ne!(secret, sk.x);
+    }
+
+    #[test]
+    fn it_should_reject_invalid_inputs() {
+        let q = &BigUint::from(1019u32);
+        let secret = BigUint::from(42u32);
+        let coefficients = vec![BigUint::from(7u32), BigUint::from(3u32)];
+
+        // fewer fragments than the threshold
+        assert_eq!(SecretSharing::split(&secret, &coefficients, 2, q), None);
+
+        // the secret must be an element of Z_q
+        assert_eq!(SecretSharing::split(q, &coefficients, 3, q), None);
+
+        // f(x) = 42 + 7x + 3x^2 mod 1019
+        let fragments = SecretSharing::split(&secret, &coefficients, 3, q).unwrap();
+        let values: Vec<u32> = vec![52, 68, 90];
+        for (fragment, value) in fragments.iter().zip(values) {
+            assert_eq!(fragment.value, BigUint::from(value));
+        }
+
+        // duplicate fragments and empty inputs
+        let duplicates = vec![fragments[0].clone(), fragments[0].clone()];
+        assert_eq!(SecretSharing::reconstruct(&duplicates, q), None);
+        assert_eq!(SecretSharing::reconstruct(&[], q), None);
+        assert_eq!(SecretSharing::lagrange_coefficients(&[1, 0], q), None);
+        assert_eq!(SecretSharing::lagrange_coefficients(&[1, 2, 2], q), None);
+    }
+
+    #[test]
+    fn it_should_interpolate_the_public_key_in_the_exponent() {
+        let (params, sk, pk) = Helper::setup_md_system();
+        let q = &params.q();
+
+        // 2-out-of-3, the public key shares are g^f(i)
+        let coefficients = vec![Random::get_random_less_than(q)];
+        let fragments = SecretSharing::split(&sk.x, &coefficients, 3, q).unwrap();
+        let subset = vec![fragments[2].clone(), fragments[0].clone()];
+        let indices: Vec<u32> = subset.iter().map(|fragment| fragment.index).collect();
+        let lambdas = SecretSharing::lagrange_coefficients(&indices, q).unwrap();
+
+        let h = subset
+            .iter()
+            .zip(lambdas.iter())
+            .fold(BigUint::one(), |h, (fragment, lambda)| {
+                let share = params.g.modpow(&fragment.value, &params.p);
+                h.modmul(&share.modpow(lambda, &params.p), &params.p)
+            });
+        assert_eq!(h, pk.h);
+    }
+}
@@ -0,0 +1,554 @@
+//! Deterministic test vectors of the zero-knowledge proofs.
+//!
+//! A test vector contains all inputs of a proof, including the random values,
+//! and the values the proof is expected to produce. The vectors are (de)serialized
+//! with serde, big integers as lower-case hex strings (see: `proofs::wire::serde_hex`),
+//! such that other implementations (e.g. of the CHVote spec) can consume them as JSON fixtures.
+//!
+//! The fixtures of this crate are stored in `test-vectors/proofs.json`.
+use crate::{
+    encryption::ElGamal,
+    helper::Helper,
+    proofs::{decryption::DecryptionProof, keygen::KeyGenerationProof, shuffle::ShuffleProof},
+    types::{BigT, BigY, Cipher, ElGamalParams, PublicKey},
+};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::{string::String, vec::Vec};
+
+/// The current version of the test vector format.
+pub const TEST_VECTOR_VERSION: u8 = 1;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum TestVectorError {
+    /// The recomputed value differs from the expected value. [name of the value]
+    Mismatch(&'static str),
+    /// The expected proof is rejected by the verifier. [name of the proof]
+    InvalidProof(&'static str),
+    UnsupportedVersion(u8),
+}
+
+/// The group parameters of a test vector.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct VectorParams {
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub p: BigUint,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub g: BigUint,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub h: BigUint,
+}
+
+impl From<&ElGamalParams> for VectorParams {
+    fn from(params: &ElGamalParams) -> Self {
+        VectorParams {
+            p: params.p.clone(),
+            g: params.g.clone(),
+            h: params.h.clone(),
+        }
+    }
+}
+
+impl From<&VectorParams> for ElGamalParams {
+    fn from(params: &VectorParams) -> Self {
+        ElGamalParams {
+            p: params.p.clone(),
+            g: params.g.clone(),
+            h: params.h.clone(),
+        }
+    }
+}
+
+fn ensure(condition: bool, error: TestVectorError) -> Result<(), TestVectorError> {
+    if condition {
+        Ok(())
+    } else {
+        Err(error)
+    }
+}
+
+/// KeyGenerationProof (Algorithm 8.7) of the key share: pk_share = g^sk
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct KeyGenerationVector {
+    pub id: String,
+    pub params: VectorParams,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub sk: BigUint,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub pk_share: BigUint,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub r: BigUint,
+    pub expected: KeyGenerationProof,
+}
+
+impl KeyGenerationVector {
+    pub fn generate(id: &str, params: &ElGamalParams, sk: &BigUint, r: &BigUint) -> Self {
+        let pk_share = params.g.modpow(sk, &params.p);
+        let expected = KeyGenerationProof::generate(params, sk, &pk_share, r, id.as_bytes());
+        KeyGenerationVector {
+            id: id.into(),
+            params: params.into(),
+            sk: sk.clone(),
+            pk_share,
+            r: r.clone(),
+            expected,
+        }
+    }
+
+    /// recomputes the proof from the inputs and verifies the expected proof
+    pub fn check(&self) -> Result<(), TestVectorError> {
+        let params: ElGamalParams = (&self.params).into();
+        let id = self.id.as_bytes();
+        let proof = KeyGenerationProof::generate(&params, &self.sk, &self.pk_share, &self.r, id);
+        ensure(
+            proof.challenge == self.expected.challenge,
+            TestVectorError::Mismatch("keygen challenge"),
+        )?;
+        ensure(
+            proof.response == self.expected.response,
+            TestVectorError::Mismatch("keygen response"),
+        )?;
+        ensure(
+            KeyGenerationProof::verify(&params, &self.pk_share, &self.expected, id),
+            TestVectorError::InvalidProof("keygen"),
+        )
+    }
+}
+
+/// DecryptionProof (Algorithm 8.50) of the partial decryptions: c_i = a_i^sk
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct DecryptionVector {
+    pub id: String,
+    pub params: VectorParams,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub sk: BigUint,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub pk_share: BigUint,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub r: BigUint,
+    pub encryptions: Vec<VectorCipher>,
+    #[serde(with = "crate::proofs::wire::serde_hex::vec")]
+    pub partial_decryptions: Vec<BigUint>,
+    pub expected: DecryptionProof,
+}
+
+/// A cipher of a test vector: { "a": hex, "b": hex }
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct VectorCipher(#[serde(with = "crate::proofs::wire::serde_hex::cipher")] pub Cipher);
+
+fn into_ciphers(ciphers: &[VectorCipher]) -> Vec<Cipher> {
+    ciphers.iter().map(|cipher| cipher.0.clone()).collect()
+}
+
+fn from_ciphers(ciphers: &[Cipher]) -> Vec<VectorCipher> {
+    ciphers.iter().cloned().map(VectorCipher).collect()
+}
+
+impl DecryptionVector {
+    pub fn generate(
+        id: &str,
+        params: &ElGamalParams,
+        sk: &BigUint,
+        r: &BigUint,
+        encryptions: &[Cipher],
+    ) -> Self {
+        let p = &params.p;
+        let pk_share = params.g.modpow(sk, p);
+        let partial_decryptions: Vec<BigUint> = encryptions
+            .iter()
+            .map(|cipher| cipher.a.modpow(sk, p))
+            .collect();
+        let expected = DecryptionProof::generate(
+            params,
+            sk,
+            &pk_share,
+            r,
+            encryptions.to_vec(),
+            partial_decryptions.clone(),
+            id.as_bytes(),
+        );
+        DecryptionVector {
+            id: id.into(),
+            params: params.into(),
+            sk: sk.clone(),
+            pk_share,
+            r: r.clone(),
+            encryptions: from_ciphers(encryptions),
+            partial_decryptions,
+            expected,
+        }
+    }
+
+    /// recomputes the partial decryptions and the proof and verifies the expected proof
+    pub fn check(&self) -> Result<(), TestVectorError> {
+        let params: ElGamalParams = (&self.params).into();
+        let id = self.id.as_bytes();
+        let encryptions = into_ciphers(&self.encryptions);
+        let partial_decryptions: Vec<BigUint> = encryptions
+            .iter()
+            .map(|cipher| cipher.a.modpow(&self.sk, &params.p))
+            .collect();
+        ensure(
+            partial_decryptions == self.partial_decryptions,
+            TestVectorError::Mismatch("partial decryptions"),
+        )?;
+        let proof = DecryptionProof::generate(
+            &params,
+            &self.sk,
+            &self.pk_share,
+            &self.r,
+            encryptions.clone(),
+            partial_decryptions.clone(),
+            id,
+        );
+        ensure(
+            proof.challenge == self.expected.challenge,
+            TestVectorError::Mismatch("decryption challenge"),
+        )?;
+        ensure(
+            proof.response == self.expected.response,
+            TestVectorError::Mismatch("decryption response"),
+        )?;
+        ensure(
+            DecryptionProof::verify(
+                &params,
+                &self.pk_share,
+                &self.expected,
+                encryptions,
+                partial_decryptions,
+                id,
+            ),
+            TestVectorError::InvalidProof("decryption"),
+        )
+    }
+}
+
+/// The public commitment t of the shuffle proof (Algorithm 8.47)
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct VectorCommitment {
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub t1: BigUint,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub t2: BigUint,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub t3: BigUint,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub t4_1: BigUint,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub t4_2: BigUint,
+    #[serde(with = "crate::proofs::wire::serde_hex::vec")]
+    pub vec_t_hat: Vec<BigUint>,
+}
+
+impl From<&VectorCommitment> for BigT {
+    fn from(t: &VectorCommitment) -> Self {
+        (
+            t.t1.clone(),
+            t.t2.clone(),
+            t.t3.clone(),
+            t.t4_1.clone(),
+            t.t4_2.clone(),
+            t.vec_t_hat.clone(),
+        )
+    }
+}
+
+/// The values of the shuffle proof (Algorithm 8.47) which are computed by this crate:
+/// the shuffle, the permutation commitment, the challenges and the commitment chain.
+/// The public commitment t is an input, the proof challenge is derived from (y, t).
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct ShuffleVector {
+    pub id: String,
+    pub params: VectorParams,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub pk: BigUint,
+    pub encryptions: Vec<VectorCipher>,
+    pub permutation: Vec<usize>,
+    #[serde(with = "crate::proofs::wire::serde_hex::vec")]
+    pub re_encryption_randoms: Vec<BigUint>,
+    #[serde(with = "crate::proofs::wire::serde_hex::vec")]
+    pub commitment_randoms: Vec<BigUint>,
+    #[serde(with = "crate::proofs::wire::serde_hex::vec")]
+    pub chain_randoms: Vec<BigUint>,
+    pub public_commitment: VectorCommitment,
+    pub expected_shuffled_encryptions: Vec<VectorCipher>,
+    #[serde(with = "crate::proofs::wire::serde_hex::vec")]
+    pub expected_permutation_commitments: Vec<BigUint>,
+    #[serde(with = "crate::proofs::wire::serde_hex::vec")]
+    pub expected_challenges: Vec<BigUint>,
+    #[serde(with = "crate::proofs::wire::serde_hex::vec")]
+    pub expected_chain_commitments: Vec<BigUint>,
+    #[serde(with = "crate::proofs::wire::serde_hex")]
+    pub expected_challenge: BigUint,
+}
+
+/// the values a shuffle vector is expected to produce
+struct ShuffleValues {
+    shuffled_encryptions: Vec<Cipher>,
+    permutation_commitments: Vec<BigUint>,
+    challenges: Vec<BigUint>,
+    chain_commitments: Vec<BigUint>,
+    challenge: BigUint,
+}
+
+impl ShuffleVector {
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate(
+        id: &str,
+        pk: &PublicKey,
+        encryptions: &[Cipher],
+        permutation: &[usize],
+        re_encryption_randoms: &[BigUint],
+        commitment_randoms: &[BigUint],
+        chain_randoms: &[BigUint],
+        public_commitment: VectorCommitment,
+    ) -> Self {
+        let mut vector = ShuffleVector {
+            id: id.into(),
+            params: (&pk.params).into(),
+            pk: pk.h.clone(),
+            encryptions: from_ciphers(encryptions),
+            permutation: permutation.to_vec(),
+            re_encryption_randoms: re_encryption_randoms.to_vec(),
+            commitment_randoms: commitment_randoms.to_vec(),
+            chain_randoms: chain_randoms.to_vec(),
+            public_commitment,
+            expected_shuffled_encryptions: Vec::new(),
+            expected_permutation_commitments: Vec::new(),
+            expected_challenges: Vec::new(),
+            expected_chain_commitments: Vec::new(),
+            expected_challenge: BigUint::from(0u32),
+        };
+        let values = vector.compute();
+        vector.expected_shuffled_encryptions = from_ciphers(&values.shuffled_encryptions);
+        vector.expected_permutation_commitments = values.permutation_commitments;
+        vector.expected_challenges = values.challenges;
+        vector.expected_chain_commitments = values.chain_commitments;
+        vector.expected_challenge = values.challenge;
+        vector
+    }
+
+    fn compute(&self) -> ShuffleValues {
+        let params: ElGamalParams = (&self.params).into();
+        let pk = PublicKey {
+            params: params.clone(),
+            h: self.pk.clone(),
+        };
+        let q = &params.q();
+        let size = self.encryptions.len();
+        let e = into_ciphers(&self.encryptions);
+
+        // the shuffle: e_tilde
+        let e_tilde: Vec<Cipher> =
+            ElGamal::shuffle(&e, &self.permutation, &self.re_encryption_randoms, &pk)
+                .into_iter()
+                .map(|(cipher, _, _)| cipher)
+                .collect();
+
+        // the permutation commitment: vec_c
+        let vec_h = Helper::get_generators(self.id.as_bytes(), &params.p, size);
+        let vec_c = ShuffleProof::generate_permutation_commitment(
+            &params,
+            &self.permutation,
+            self.commitment_randoms.clone(),
+            vec_h,
+        )
+        .commitments;
+
+        // the challenges: vec_u, permuted: u_tilde
+        let vec_u =
+            ShuffleProof::get_challenges(size, e.clone(), e_tilde.clone(), vec_c.clone(), &pk);
+        let u_tilde: Vec<BigUint> = self
+            .permutation
+            .iter()
+            .map(|j_i| vec_u[*j_i].clone())
+            .collect();
+
+        // the commitment chain: vec_c_hat
+        let vec_c_hat =
+            ShuffleProof::generate_commitment_chain(u_tilde, self.chain_randoms.clone(), &params)
+                .commitments;
+
+        // the challenge of the proof: hash(y, t)
+        let public_value: BigY = (e, e_tilde.clone(), vec_c.clone(), vec_c_hat.clone(), &pk.h);
+        let challenge =
+            ShuffleProof::get_challenge(public_value, (&self.public_commitment).into(), q);
+
+        ShuffleValues {
+            shuffled_encryptions: e_tilde,
+            permutation_commitments: vec_c,
+            challenges: vec_u,
+            chain_commitments: vec_c_hat,
+            challenge,
+        }
+    }
+
+    /// recomputes all values from the inputs and compares them to the expected values
+    pub fn check(&self) -> Result<(), TestVectorError> {
+        let values = self.compute();
+        ensure(
+            values.shuffled_encryptions == into_ciphers(&self.expected_shuffled_encryptions),
+            TestVectorError::Mismatch("shuffled encryptions"),
+        )?;
+        ensure(
+            values.permutation_commitments == self.expected_permutation_commitments,
+            TestVectorError::Mismatch("permutation commitments"),
+        )?;
+        ensure(
+            values.challenges == self.expected_challenges,
+            TestVectorError::Mismatch("shuffle challenges"),
+        )?;
+        ensure(
+            values.chain_commitments == self.expected_chain_commitments,
+            TestVectorError::Mismatch("chain commitments"),
+        )?;
+        ensure(
+            values.challenge == self.expected_challenge,
+            TestVectorError::Mismatch("shuffle challenge"),
+        )
+    }
+}
+
+/// A fixture file containing test vectors of all proofs.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct TestVectors {
+    pub version: u8,
+    pub keygen: Vec<KeyGenerationVector>,
+    pub decryption: Vec<DecryptionVector>,
+    pub shuffle: Vec<ShuffleVector>,
+}
+
+impl TestVectors {
+    /// checks all test vectors, returns the first failure
+    pub fn check(&self) -> Result<(), TestVectorError> {
+        ensure(
+            self.version == TEST_VECTOR_VERSION,
+            TestVectorError::UnsupportedVersion(self.version),
+        )?;
+        self.keygen
+            .iter()
+            .try_for_each(KeyGenerationVector::check)?;
+        self.decryption
+            .iter()
+            .try_for_each(DecryptionVector::check)?;
+        self.shuffle.iter().try_for_each(ShuffleVector::check)
+    }
+
+    /// generates the test vectors of `test-vectors/proofs.json`.
+    /// all inputs are fixed, hence, the result is deterministic.
+    pub fn generate() -> Self {
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let (tiny_params, tiny_sk, _) = Helper::setup_tiny_system();
+        let encryptions: Vec<Cipher> = [1u32, 2, 3]
+            .iter()
+            .zip([5u32, 7, 11].iter())
+            .map(|(m, r)| ElGamal::encrypt_encode(&BigUint::from(*m), &BigUint::from(*r), &pk))
+            .collect();
+        let numbers = |values: &[u32]| -> Vec<BigUint> {
+            values.iter().map(|value| BigUint::from(*value)).collect()
+        };
+
+        TestVectors {
+            version: TEST_VECTOR_VERSION,
+            keygen: vec![
+                KeyGenerationVector::generate(
+                    "Bob",
+                    &tiny_params,
+                    &tiny_sk.x,
+                    &BigUint::from(11u32),
+                ),
+                KeyGenerationVector::generate("Charlie", &params, &sk.x, &BigUint::from(1337u32)),
+            ],
+            decryption: vec![DecryptionVector::generate(
+                "Bob",
+                &params,
+                &sk.x,
+                &BigUint::from(4242u32),
+                &encryptions,
+            )],
+            shuffle: vec![ShuffleVector::generate(
+                "20201212-01",
+                &pk,
+                &encryptions,
+                &[2, 0, 1],
+                &numbers(&[3, 13, 17]),
+                &numbers(&[19, 23, 29]),
+                &numbers(&[31, 37, 41]),
+                VectorCommitment {
+                    t1: BigUint::from(43u32),
+                    t2: BigUint::from(47u32),
+                    t3: BigUint::from(53u32),
+                    t4_1: BigUint::from(59u32),
+                    t4_2: BigUint::from(61u32),
+                    vec_t_hat: numbers(&[67, 71, 73]),
+                },
+            )],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TestVectorError, TestVectors};
+    use num_bigint::BigUint;
+
+    const FIXTURE: &str = include_str!("../test-vectors/proofs.json");
+
+    fn fixture() -> TestVectors {
+        serde_json::from_str(FIXTURE).unwrap()
+    }
+
+    #[test]
+    fn it_should_verify_the_fixture() {
+        assert_eq!(fixture().check(), Ok(()));
+    }
+
+    #[test]
+    fn it_should_generate_the_fixture_deterministically() {
+        assert_eq!(TestVectors::generate(), fixture());
+        assert_eq!(TestVectors::generate(), TestVectors::generate());
+    }
+
+    #[test]
+    fn it_should_round_trip_the_test_vectors_as_json() {
+        let vectors = TestVectors::generate();
+        let json = serde_json::to_string_pretty(&vectors).unwrap();
+        let decoded: TestVectors = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, vectors);
+    }
+
+    #[test]
+    fn it_should_detect_modified_test_vectors() {
+        let mut vectors = fixture();
+        vectors.keygen[0].expected.response += 1u32;
+        assert_eq!(
+            vectors.check(),
+            Err(TestVectorError::Mismatch("keygen response"))
+        );
+
+        let mut vectors = fixture();
+        vectors.decryption[0].partial_decryptions.swap(0, 1);
+        assert_eq!(
+            vectors.check(),
+            Err(TestVectorError::Mismatch("partial decryptions"))
+        );
+
+        let mut vectors = fixture();
+        vectors.shuffle[0].public_commitment.t1 = BigUint::from(42u32);
+        assert_eq!(
+            vectors.check(),
+            Err(TestVectorError::Mismatch("shuffle challenge"))
+        );
+
+        let mut vectors = fixture();
+        vectors.shuffle[0].expected_challenges.reverse();
+        assert_eq!(
+            vectors.check(),
+            Err(TestVectorError::Mismatch("shuffle challenges"))
+        );
+
+        let mut vectors = fixture();
+        vectors.version = 0;
+        assert_eq!(vectors.check(), Err(TestVectorError::UnsupportedVersion(0)));
+    }
+}
@@ -1,7 +1,17 @@
+use crate::helper::Helper;
 use alloc::vec::Vec;
+use core::fmt::Debug;
 use core::ops::{Add, Div, Mul, Sub};
 use num_bigint::{BigInt, BigUint};
-use num_traits::{One, Zero};
+use num_traits::{One, PrimInt, Zero};
+
+#[cfg(feature = "ristretto")]
+use curve25519_dalek::{
+    constants::{BASEPOINT_ORDER, RISTRETTO_BASEPOINT_POINT},
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar as RistrettoScalar,
+    traits::{Identity, MultiscalarMul},
+};
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -27,6 +37,302 @@ impl ElGamalParams {
     }
 }
 
+/// A cyclic group of prime order q in which ElGamal is instantiated.
+/// The group operation is written multiplicatively, i.e. `op(a, b) = a * b`
+/// and `exp(a, x) = a^x`.
+///
+/// Implemented by the quadratic residues modulo a safe prime p (`ElGamalParams`)
+/// and by the Ristretto group of curve25519 (`Ristretto`, feature: ristretto).
+/// The `*_in_group` encryptions and proofs work with any implementation.
+pub trait Group {
+    /// An element of the group, e.g. a quadratic residue modulo p or a curve point.
+    type Element: Clone + PartialEq + Debug;
+
+    /// An exponent in Zq.
+    type Scalar: Clone + PartialEq + Debug;
+
+    /// Returns the order q of the group.
+    fn order(&self) -> BigUint;
+
+    /// Returns the generator g of the group.
+    fn generator(&self) -> Self::Element;
+
+    /// Returns a second generator h of the group whose discrete logarithm
+    /// with respect to g is unknown (used by the shuffle proof).
+    fn second_generator(&self) -> Self::Element;
+
+    /// Derives the i-th independent generator of the group from the id,
+    /// nobody knows its discrete logarithm (see: `Helper::derive_generator`).
+    fn derive_generator(&self, id: &[u8], index: usize) -> Self::Element;
+
+    /// Returns the neutral element of the group.
+    fn identity(&self) -> Self::Element;
+
+    /// Applies the group operation: a * b
+    fn op(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    /// Applies the group operation x times to the base: base^x
+    fn exp(&self, base: &Self::Element, x: &Self::Scalar) -> Self::Element;
+
+    /// Returns the inverse element: a^-1
+    fn inverse(&self, a: &Self::Element) -> Self::Element;
+
+    /// Returns true if the element is a member of the group.
+    fn is_element(&self, a: &Self::Element) -> bool;
+
+    /// Returns the canonical encoding of the element, e.g. to hash it.
+    fn element_to_bytes(&self, a: &Self::Element) -> Vec<u8>;
+
+    /// Decodes an element, returns None if the bytes don't encode a member of the group.
+    fn element_from_bytes(&self, bytes: &[u8]) -> Option<Self::Element>;
+
+    /// Returns the integer x mod q as exponent, e.g. a message or a hash.
+    fn scalar_from_biguint(&self, x: &BigUint) -> Self::Scalar;
+
+    /// Calculates x + y mod q
+    fn scalar_add(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar;
+
+    /// Calculates x - y mod q
+    fn scalar_sub(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar;
+
+    /// Calculates x * y mod q
+    fn scalar_mul(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar;
+
+    /// Calculates the product of multiple exponentiations: Π(bases_i^exponents_i)
+    fn multi_exp(&self, bases: &[Self::Element], exponents: &[Self::Scalar]) -> Self::Element {
+        assert!(
+            bases.len() == exponents.len(),
+            "bases and exponents need to have the same length!"
+        );
+        bases
+            .iter()
+            .zip(exponents.iter())
+            .fold(self.identity(), |product, (base, exponent)| {
+                self.op(&product, &self.exp(base, exponent))
+            })
+    }
+}
+
+/// The group of quadratic residues modulo the safe prime p = 2q + 1.
+impl Group for ElGamalParams {
+    type Element = BigUint;
+    type Scalar = BigUint;
+
+    fn order(&self) -> BigUint {
+        self.q()
+    }
+
+    fn generator(&self) -> BigUint {
+        self.g.clone()
+    }
+
+    fn second_generator(&self) -> BigUint {
+        self.h.clone()
+    }
+
+    fn derive_generator(&self, id: &[u8], index: usize) -> BigUint {
+        Helper::derive_generator(id, &self.p, index).generator
+    }
+
+    fn identity(&self) -> BigUint {
+        BigUint::one()
+    }
+
+    fn op(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a.modmul(b, &self.p)
+    }
+
+    fn exp(&self, base: &BigUint, x: &BigUint) -> BigUint {
+        base.modpow(x, &self.p)
+    }
+
+    fn inverse(&self, a: &BigUint) -> BigUint {
+        a.invmod(&self.p).expect("cannot compute mod_inverse!")
+    }
+
+    fn is_element(&self, a: &BigUint) -> bool {
+        !a.is_zero() && a < &self.p && a.modpow(&self.q(), &self.p).is_one()
+    }
+
+    fn element_to_bytes(&self, a: &BigUint) -> Vec<u8> {
+        a.to_bytes_be()
+    }
+
+    fn element_from_bytes(&self, bytes: &[u8]) -> Option<BigUint> {
+        let a = BigUint::from_bytes_be(bytes);
+        if self.is_element(&a) {
+            Some(a)
+        } else {
+            None
+        }
+    }
+
+    fn scalar_from_biguint(&self, x: &BigUint) -> BigUint {
+        x % self.q()
+    }
+
+    fn scalar_add(&self, x: &BigUint, y: &BigUint) -> BigUint {
+        x.modadd(y, &self.q())
+    }
+
+    fn scalar_sub(&self, x: &BigUint, y: &BigUint) -> BigUint {
+        x.modsub(y, &self.q())
+    }
+
+    fn scalar_mul(&self, x: &BigUint, y: &BigUint) -> BigUint {
+        x.modmul(y, &self.q())
+    }
+
+    fn multi_exp(&self, bases: &[BigUint], exponents: &[BigUint]) -> BigUint {
+        let bases: Vec<&BigUint> = bases.iter().collect();
+        let exponents: Vec<&BigUint> = exponents.iter().collect();
+        multi_modpow(&bases, &exponents, &self.p)
+    }
+}
+
+/// The domain separation tag of the second generator h of the Ristretto group.
+#[cfg(feature = "ristretto")]
+const RISTRETTO_H_DOMAIN: &[u8] = b"provotum/v1/ristretto/h";
+
+/// The Ristretto group of prime order l = 2^252 + 27742317777372353535851937790883648493
+/// built from curve25519 (see: <https://ristretto.group>).
+///
+/// An element is encoded in 32 bytes, an exponentiation is a scalar multiplication.
+/// Compared to the quadratic residues modulo a 2048 bit prime, both the ciphers
+/// and the exponentiations of the shuffle proofs are much cheaper.
+/// The independent generators are hashed to the curve (Elligator), nobody knows their discrete logarithm.
+/// Only the `*_in_group` functions of this crate support the group, the pallet and the client don't.
+#[cfg(feature = "ristretto")]
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub struct Ristretto;
+
+#[cfg(feature = "ristretto")]
+impl Ristretto {
+    /// maps the message to a curve point: the message is expanded to 64 bytes with
+    /// expand_message_xmd using the domain, the bytes are mapped with Elligator twice.
+    fn hash_to_element(message: &[u8], domain: &[u8]) -> RistrettoPoint {
+        let expanded = Helper::expand_message_xmd(message, domain, 64);
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(&expanded);
+        RistrettoPoint::from_uniform_bytes(&bytes)
+    }
+}
+
+#[cfg(feature = "ristretto")]
+impl Group for Ristretto {
+    type Element = RistrettoPoint;
+    type Scalar = RistrettoScalar;
+
+    fn order(&self) -> BigUint {
+        BigUint::from_bytes_le(&BASEPOINT_ORDER.to_bytes())
+    }
+
+    fn generator(&self) -> RistrettoPoint {
+        RISTRETTO_BASEPOINT_POINT
+    }
+
+    fn second_generator(&self) -> RistrettoPoint {
+        Self::hash_to_element(&[], RISTRETTO_H_DOMAIN)
+    }
+
+    fn derive_generator(&self, id: &[u8], index: usize) -> RistrettoPoint {
+        // the same seed as the generators of the integer group (counter: 0), the
+        // Elligator map never needs to discard a seed
+        let mut message: Vec<u8> = Vec::new();
+        message.extend_from_slice(&(id.len() as u32).to_be_bytes());
+        message.extend_from_slice(id);
+        message.extend_from_slice(&0u32.to_be_bytes());
+        Self::hash_to_element(&message, &Helper::generator_domain(index))
+    }
+
+    fn identity(&self) -> RistrettoPoint {
+        RistrettoPoint::identity()
+    }
+
+    fn op(&self, a: &RistrettoPoint, b: &RistrettoPoint) -> RistrettoPoint {
+        a + b
+    }
+
+    fn exp(&self, base: &RistrettoPoint, x: &RistrettoScalar) -> RistrettoPoint {
+        base * x
+    }
+
+    fn inverse(&self, a: &RistrettoPoint) -> RistrettoPoint {
+        -a
+    }
+
+    fn is_element(&self, _: &RistrettoPoint) -> bool {
+        // a decoded point is always a member of the group (see: `element_from_bytes`)
+        true
+    }
+
+    fn element_to_bytes(&self, a: &RistrettoPoint) -> Vec<u8> {
+        a.compress().to_bytes().to_vec()
+    }
+
+    fn element_from_bytes(&self, bytes: &[u8]) -> Option<RistrettoPoint> {
+        if bytes.len() != 32 {
+            return None;
+        }
+        CompressedRistretto::from_slice(bytes).decompress()
+    }
+
+    fn scalar_from_biguint(&self, x: &BigUint) -> RistrettoScalar {
+        let mut bytes = [0u8; 32];
+        let reduced = (x % self.order()).to_bytes_le();
+        bytes[..reduced.len()].copy_from_slice(&reduced);
+        RistrettoScalar::from_bytes_mod_order(bytes)
+    }
+
+    fn scalar_add(&self, x: &RistrettoScalar, y: &RistrettoScalar) -> RistrettoScalar {
+        x + y
+    }
+
+    fn scalar_sub(&self, x: &RistrettoScalar, y: &RistrettoScalar) -> RistrettoScalar {
+        x - y
+    }
+
+    fn scalar_mul(&self, x: &RistrettoScalar, y: &RistrettoScalar) -> RistrettoScalar {
+        x * y
+    }
+
+    fn multi_exp(&self, bases: &[RistrettoPoint], exponents: &[RistrettoScalar]) -> RistrettoPoint {
+        assert!(
+            bases.len() == exponents.len(),
+            "bases and exponents need to have the same length!"
+        );
+        RistrettoPoint::multiscalar_mul(exponents, bases)
+    }
+}
+
+/// An ElGamal encryption in an arbitrary group (see: `Group`).
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+pub struct GroupCipher<E> {
+    // a = g^r
+    pub a: E,
+
+    // b = h^r * m
+    pub b: E,
+}
+
+impl From<Cipher> for GroupCipher<BigUint> {
+    fn from(cipher: Cipher) -> Self {
+        GroupCipher {
+            a: cipher.a,
+            b: cipher.b,
+        }
+    }
+}
+
+impl From<GroupCipher<BigUint>> for Cipher {
+    fn from(cipher: GroupCipher<BigUint>) -> Self {
+        Cipher {
+            a: cipher.a,
+            b: cipher.b,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct PublicKey {
@@ -73,6 +379,21 @@ pub struct PrivateKey {
     pub x: BigUint,
 }
 
+// zeroize 1.2 has no `ZeroizeOnDrop`, the private key is wiped when it is dropped
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for PrivateKey {
+    fn zeroize(&mut self) {
+        crate::secret::wipe(&mut self.x);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
 #[derive(Eq, PartialEq, Clone, Debug, Hash)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Cipher {
@@ -112,6 +433,54 @@ pub type BigT = (
     Vec<BigUint>, // vec_t_hat
 );
 
+/// Algorithm 8.47: The shuffle proof without the public commitment t,
+/// which the verifier recomputes from the challenge and the responses (see: `ShuffleProof::verify`).
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct ShuffleProofValues {
+    pub challenge: BigUint,
+    pub s1: BigUint,
+    pub s2: BigUint,
+    pub s3: BigUint,
+    pub s4: BigUint,
+    pub vec_s_hat: Vec<BigUint>,
+    pub vec_s_tilde: Vec<BigUint>,
+    pub permutation_commitments: Vec<BigUint>,
+    pub permutation_chain_commitments: Vec<BigUint>,
+}
+
+/// The public value Y of a shuffle proof in an arbitrary group (see: `BigY`)
+pub type GroupY<'a, E> = (
+    &'a [GroupCipher<E>], // e
+    &'a [GroupCipher<E>], // e_tilde
+    &'a [E],              // vec_c
+    &'a [E],              // vec_c_hat
+    &'a E,                // public key
+);
+
+/// The public commitment t of a shuffle proof in an arbitrary group (see: `BigT`)
+pub type GroupT<E> = (
+    E,      // t1
+    E,      // t2
+    E,      // t3
+    E,      // t4_1
+    E,      // t4_2
+    Vec<E>, // vec_t_hat
+);
+
+/// The shuffle proof in an arbitrary group (see: `ShuffleProofValues`).
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct GroupShuffleProof<E, S> {
+    pub challenge: S,
+    pub s1: S,
+    pub s2: S,
+    pub s3: S,
+    pub s4: S,
+    pub vec_s_hat: Vec<S>,
+    pub vec_s_tilde: Vec<S>,
+    pub permutation_commitments: Vec<E>,
+    pub permutation_chain_commitments: Vec<E>,
+}
+
 pub trait ModuloOperations {
     /// Calculates the modular multiplicative of a BigUint: result = self * rhs % modulus.
     fn modmul(&self, rhs: &Self, modulus: &Self) -> Self;
@@ -128,6 +497,10 @@ pub trait ModuloOperations {
     /// Calculates the modular multiplicative inverse x of an integer a such that ax ≡ 1 (mod m).
     /// Alternative formulation: a^-1 (mod m)
     fn invmod(&self, modulus: &Self) -> Option<BigUint>;
+
+    /// Precomputes a table for the fixed-base exponentiation: result = self^x % modulus
+    /// for exponents x of at most `exponent_bits` bits (see: `FixedBaseTable`).
+    fn precompute(&self, modulus: &Self, exponent_bits: u64) -> FixedBaseTable;
     // fn extended_gcd(a: &BigUint, b: &BigUint) -> (BigUint, BigUint, BigUint);
 }
 
@@ -193,6 +566,153 @@ impl ModuloOperations for BigUint {
             result.to_biguint()
         }
     }
+
+    fn precompute(&self, modulus: &Self, exponent_bits: u64) -> FixedBaseTable {
+        FixedBaseTable::new(self, modulus, exponent_bits, FIXED_BASE_WINDOW)
+    }
+}
+
+/// The number of exponent bits handled per table lookup of a `FixedBaseTable`.
+pub const FIXED_BASE_WINDOW: u64 = 4;
+
+/// A precomputed table for the repeated exponentiation of the same base (windowed method).
+/// The exponent is split into windows of w bits, each window requires a single
+/// modular multiplication and no squarings: base^x = Π(table[i][x_i]) % modulus.
+///
+/// Note: num-bigint already performs `modpow` in Montgomery form for odd moduli,
+/// the speed up stems from the saved squarings. Worthwhile if the base is used
+/// for more than a handful of exponentiations (e.g. g and h in the shuffle proof).
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct FixedBaseTable {
+    base: BigUint,
+    modulus: BigUint,
+    window: u64,
+    exponent_bits: u64,
+
+    // table[i][j - 1] = base^(j * 2^(w * i)) % modulus for j ∈ [1, 2^w)
+    table: Vec<Vec<BigUint>>,
+}
+
+impl FixedBaseTable {
+    pub fn new(base: &BigUint, modulus: &BigUint, exponent_bits: u64, window: u64) -> Self {
+        assert!(
+            !modulus.is_zero(),
+            "attempt to calculate with zero modulus!"
+        );
+        assert!(window > 0 && window < 16, "window must be in [1, 16)!");
+        let nr_of_windows = div_ceil(exponent_bits, window);
+        let entries = (1usize << window) - 1;
+
+        let mut table: Vec<Vec<BigUint>> = Vec::with_capacity(nr_of_windows as usize);
+        // base of the current window: base^(2^(w * i))
+        let mut window_base = base % modulus;
+        for _ in 0..nr_of_windows {
+            let mut row: Vec<BigUint> = Vec::with_capacity(entries);
+            let mut value = window_base.clone();
+            for _ in 0..entries {
+                row.push(value.clone());
+                value = value.modmul(&window_base, modulus);
+            }
+            // value = window_base^(2^w) = base^(2^(w * (i + 1)))
+            window_base = value;
+            table.push(row);
+        }
+
+        FixedBaseTable {
+            base: base.clone(),
+            modulus: modulus.clone(),
+            window,
+            exponent_bits,
+            table,
+        }
+    }
+
+    /// Calculates base^exponent % modulus using the precomputed table.
+    /// Falls back to `modpow` if the exponent exceeds the size of the table.
+    pub fn pow(&self, exponent: &BigUint) -> BigUint {
+        if exponent.bits() > self.exponent_bits {
+            return self.base.modpow(exponent, &self.modulus);
+        }
+        let mut result = BigUint::one() % &self.modulus;
+        for (i, row) in self.table.iter().enumerate() {
+            let offset = i as u64 * self.window;
+            let digit = (0..self.window).fold(0usize, |digit, k| {
+                digit | ((exponent.bit(offset + k) as usize) << k)
+            });
+            if digit != 0 {
+                result = result.modmul(&row[digit - 1], &self.modulus);
+            }
+        }
+        result
+    }
+}
+
+/// The number of exponent bits handled per step of `multi_modpow`.
+pub const MULTI_EXP_WINDOW: u64 = 4;
+
+/// Calculates the product of multiple exponentiations: Π(bases_i^exponents_i) % modulus.
+///
+/// Simultaneous (Straus) multi-exponentiation with fixed windows of w bits:
+/// all bases share the same squarings, each base only requires a table of 2^w - 1 powers
+/// and a single modular multiplication per non-zero window of its exponent.
+/// Worthwhile if many bases are combined, e.g. when verifying a shuffle proof in batch.
+pub fn multi_modpow(bases: &[&BigUint], exponents: &[&BigUint], modulus: &BigUint) -> BigUint {
+    assert!(
+        !modulus.is_zero(),
+        "attempt to calculate with zero modulus!"
+    );
+    assert!(
+        bases.len() == exponents.len(),
+        "bases and exponents need to have the same length!"
+    );
+    let window = MULTI_EXP_WINDOW;
+    let entries = (1usize << window) - 1;
+
+    // tables[i][j - 1] = bases_i^j % modulus for j ∈ [1, 2^w)
+    let tables: Vec<Vec<BigUint>> = bases
+        .iter()
+        .map(|base| {
+            let base = *base % modulus;
+            let mut row: Vec<BigUint> = Vec::with_capacity(entries);
+            let mut value = base.clone();
+            for _ in 0..entries {
+                row.push(value.clone());
+                value = value.modmul(&base, modulus);
+            }
+            row
+        })
+        .collect();
+
+    let max_bits = exponents.iter().map(|x| x.bits()).max().unwrap_or(0);
+    let nr_of_windows = div_ceil(max_bits, window);
+
+    // process the windows from the most to the least significant one
+    let mut result = BigUint::one() % modulus;
+    for i in (0..nr_of_windows).rev() {
+        for _ in 0..window {
+            result = result.modmul(&result, modulus);
+        }
+        let offset = i * window;
+        for (exponent, table) in exponents.iter().zip(tables.iter()) {
+            let digit = (0..window).fold(0usize, |digit, k| {
+                digit | ((exponent.bit(offset + k) as usize) << k)
+            });
+            if digit != 0 {
+                result = result.modmul(&table[digit - 1], modulus);
+            }
+        }
+    }
+    result
+}
+
+/// Returns x / d rounded up.
+/// `usize::div_ceil` is not available on the toolchain of the runtime (see: node/Dockerfile).
+pub(crate) fn div_ceil<T: PrimInt>(x: T, d: T) -> T {
+    if x % d == T::zero() {
+        x / d
+    } else {
+        x / d + T::one()
+    }
 }
 
 fn extended_gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
@@ -209,11 +729,14 @@ fn extended_gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
 mod tests {
     use crate::{
         helper::Helper,
-        types::{ElGamalParams, ModuloOperations, PrivateKey, PublicKey},
+        types::{
+            div_ceil, multi_modpow, ElGamalParams, FixedBaseTable, Group, ModuloOperations,
+            PrivateKey, PublicKey,
+        },
     };
     use alloc::vec::Vec;
     use num_bigint::BigUint;
-    use num_traits::Zero;
+    use num_traits::{One, Zero};
 
     #[test]
     fn check_that_q_is_correctly_computed() {
@@ -489,4 +1012,186 @@ mod tests {
         // should panic since modulus is zero
         six.invmod(&zero);
     }
+
+    #[test]
+    fn it_should_satisfy_the_group_laws_for_quadratic_residues() {
+        let (params, _, _) = Helper::setup_sm_system();
+        let g = params.generator();
+        let q = params.order();
+        let x = BigUint::from(7u32);
+        let y = BigUint::from(12u32);
+
+        // g^x * g^y = g^(x + y)
+        let g_x = params.exp(&g, &x);
+        let g_y = params.exp(&g, &y);
+        assert_eq!(params.op(&g_x, &g_y), params.exp(&g, &x.modadd(&y, &q)));
+
+        // g^x * (g^x)^-1 = 1
+        assert_eq!(params.op(&g_x, &params.inverse(&g_x)), params.identity());
+
+        // g^q = 1
+        assert_eq!(params.exp(&g, &q), params.identity());
+
+        // the generator is a member of the group, zero and p are not
+        assert!(params.is_element(&g));
+        assert!(params.is_element(&g_x));
+        assert!(!params.is_element(&BigUint::zero()));
+        assert!(!params.is_element(&params.p));
+    }
+
+    #[test]
+    fn it_should_encode_and_derive_elements_of_the_integer_group() {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let bytes = params.element_to_bytes(&pk.h);
+        assert_eq!(params.element_from_bytes(&bytes), Some(pk.h.clone()));
+        assert_eq!(params.element_from_bytes(&params.p.to_bytes_be()), None);
+
+        // the generators of the shuffle proof
+        assert_eq!(params.second_generator(), params.h);
+        assert_eq!(
+            params.derive_generator(b"Topic", 3),
+            Helper::get_generators(b"Topic", &params.p, 4)[3]
+        );
+
+        // the multi exponentiation equals the product of the exponentiations
+        let bases = vec![params.g.clone(), pk.h.clone()];
+        let exponents = vec![
+            BigUint::from(5u32),
+            params.scalar_from_biguint(&(params.q() + 3u32)),
+        ];
+        let expected = params.op(
+            &params.exp(&params.g, &BigUint::from(5u32)),
+            &params.exp(&pk.h, &BigUint::from(3u32)),
+        );
+        assert_eq!(params.multi_exp(&bases, &exponents), expected);
+    }
+
+    #[cfg(feature = "ristretto")]
+    #[test]
+    fn it_should_satisfy_the_group_laws_for_ristretto() {
+        use crate::types::Ristretto;
+
+        let group = Ristretto;
+        let g = group.generator();
+        let q = group.order();
+        let x = group.scalar_from_biguint(&BigUint::from(7u32));
+        let y = group.scalar_from_biguint(&BigUint::from(12u32));
+
+        // g^x * g^y = g^(x + y), g^x * (g^x)^-1 = 1 and g^q = 1
+        let g_x = group.exp(&g, &x);
+        let g_y = group.exp(&g, &y);
+        assert_eq!(
+            group.op(&g_x, &g_y),
+            group.exp(&g, &group.scalar_add(&x, &y))
+        );
+        assert_eq!(group.op(&g_x, &group.inverse(&g_x)), group.identity());
+        assert_eq!(
+            group.exp(&g, &group.scalar_from_biguint(&q)),
+            group.identity()
+        );
+        assert_eq!(
+            q,
+            (BigUint::one() << 252)
+                + BigUint::parse_bytes(b"27742317777372353535851937790883648493", 10).unwrap()
+        );
+
+        // the elements are encoded in 32 bytes, invalid encodings are rejected
+        let bytes = group.element_to_bytes(&g_x);
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(group.element_from_bytes(&bytes), Some(g_x));
+        assert_eq!(group.element_from_bytes(&[0xffu8; 32]), None);
+        assert_eq!(group.element_from_bytes(&bytes[1..]), None);
+
+        // the generators are distinct and deterministic
+        let h = group.second_generator();
+        let h_0 = group.derive_generator(b"Topic", 0);
+        assert!(h != g && h_0 != g && h_0 != h);
+        assert_eq!(group.derive_generator(b"Topic", 0), h_0);
+        assert!(group.derive_generator(b"Topic", 1) != h_0);
+        assert!(group.derive_generator(b"Another Topic", 0) != h_0);
+
+        // the multi exponentiation equals the product of the exponentiations
+        let expected = group.op(&group.exp(&g, &x), &group.exp(&h, &y));
+        assert_eq!(group.multi_exp(&[g, h], &[x, y]), expected);
+    }
+
+    #[test]
+    fn it_should_compute_fixed_base_exponentiations() {
+        let (params, _, pk) = Helper::setup_md_system();
+        let p = &params.p;
+        let q = params.q();
+        let table = params.g.precompute(p, q.bits());
+
+        let exponents = vec![
+            BigUint::zero(),
+            BigUint::one(),
+            BigUint::from(15u32),
+            BigUint::from(16u32),
+            BigUint::from(123_456_789u64),
+            q.clone() - 1u32,
+        ];
+        for x in exponents.iter() {
+            assert_eq!(table.pow(x), params.g.modpow(x, p));
+        }
+
+        // exponents exceeding the table fall back to modpow
+        let large = p.clone() * 3u32;
+        assert_eq!(table.pow(&large), params.g.modpow(&large, p));
+
+        // all window sizes yield the same results
+        for window in 1..9 {
+            let table = FixedBaseTable::new(&pk.h, p, q.bits(), window);
+            assert_eq!(
+                table.pow(&(q.clone() - 2u32)),
+                pk.h.modpow(&(q.clone() - 2u32), p)
+            );
+        }
+    }
+
+    #[test]
+    fn it_should_compute_multi_exponentiations() {
+        let (params, _, pk) = Helper::setup_md_system();
+        let p = &params.p;
+        let q = params.q();
+
+        let bases = vec![params.g.clone(), pk.h.clone(), params.h.clone()];
+        let exponents = vec![
+            q.clone() - 1u32,
+            BigUint::from(17u32),
+            // exponents of different sizes and larger than q
+            q.clone() * 5u32 + 3u32,
+        ];
+        let expected = bases
+            .iter()
+            .zip(exponents.iter())
+            .fold(BigUint::one(), |product, (base, exponent)| {
+                product.modmul(&base.modpow(exponent, p), p)
+            });
+        let result = multi_modpow(
+            &bases.iter().collect::<Vec<&BigUint>>(),
+            &exponents.iter().collect::<Vec<&BigUint>>(),
+            p,
+        );
+        assert_eq!(result, expected);
+
+        // zero exponents and no bases at all yield the identity
+        let zero = BigUint::zero();
+        assert_eq!(multi_modpow(&[&params.g], &[&zero], p), BigUint::one());
+        assert_eq!(multi_modpow(&[], &[], p), BigUint::one());
+    }
+
+    #[test]
+    #[should_panic(expected = "bases and exponents need to have the same length!")]
+    fn it_should_panic_multi_exponentiation_different_sizes() {
+        let (params, _, _) = Helper::setup_tiny_system();
+        multi_modpow(&[&params.g], &[], &params.p);
+    }
+
+    #[test]
+    fn it_should_divide_and_round_up() {
+        assert_eq!(div_ceil(0u64, 4), 0);
+        assert_eq!(div_ceil(8u64, 4), 2);
+        assert_eq!(div_ceil(9u64, 4), 3);
+        assert_eq!(div_ceil(2049usize, 8), 257);
+    }
 }
@@ -137,6 +137,73 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
     ))
 }
 
+/// The seeds of the sealers, the well-known development accounts are used first.
+fn sealer_seeds(nr_of_sealers: usize) -> Vec<String> {
+    let well_known = ["Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+    (0..nr_of_sealers)
+        .map(|index| match well_known.get(index) {
+            Some(seed) => seed.to_string(),
+            None => format!("Sealer{}", index + 1),
+        })
+        .collect()
+}
+
+/// A local chain with Alice as voting authority and {nr_of_sealers} sealers.
+/// Every sealer is also a block authority (Aura + Grandpa) and the first sealer is the sudo account.
+pub fn sealers_config(nr_of_sealers: usize) -> Result<ChainSpec, String> {
+    let wasm_binary =
+        WASM_BINARY.ok_or_else(|| "Development wasm binary not available".to_string())?;
+    if nr_of_sealers == 0 {
+        return Err("at least one sealer is required".to_string());
+    }
+    let seeds = sealer_seeds(nr_of_sealers);
+
+    Ok(ChainSpec::from_genesis(
+        // Name
+        &format!("Provotum ({} Sealers)", nr_of_sealers),
+        // ID
+        &format!("provotum_{}_sealers", nr_of_sealers),
+        ChainType::Local,
+        move || {
+            let sealers: Vec<AccountId> = seeds
+                .iter()
+                .map(|seed| get_account_id_from_seed::<sr25519::Public>(seed))
+                .collect();
+            let voting_authority = get_account_id_from_seed::<sr25519::Public>("Alice");
+            let mut endowed_accounts = vec![voting_authority.clone()];
+            endowed_accounts.extend(sealers.clone());
+
+            testnet_genesis(
+                wasm_binary,
+                // Initial PoA authorities
+                seeds
+                    .iter()
+                    .map(|seed| authority_keys_from_seed(seed))
+                    .collect(),
+                // Sudo account
+                sealers[0].clone(),
+                // Pre-funded accounts
+                endowed_accounts,
+                true,
+                // Voting Authority
+                vec![voting_authority],
+                // Sealers
+                sealers,
+            )
+        },
+        // Bootnodes
+        vec![],
+        // Telemetry
+        None,
+        // Protocol ID
+        None,
+        // Properties
+        None,
+        // Extensions
+        None,
+    ))
+}
+
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
     wasm_binary: &[u8],
@@ -174,9 +241,14 @@ fn testnet_genesis(
             // Assign network admin rights.
             key: root_key,
         }),
-        pallet_mixnet: Some(PalletMixnetConfig {
-            voting_authorities,
-            sealers,
-        }),
+        pallet_mixnet: Some(
+            voting_authorities
+                .into_iter()
+                .fold(PalletMixnetConfig::default(), |config, authority| {
+                    config.with_authority(authority)
+                })
+                .with_sealers(sealers)
+                .with_default_params(),
+        ),
     }
 }
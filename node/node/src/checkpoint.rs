@@ -0,0 +1,83 @@
+//! Writes the signed checkpoints of the election state to a directory and restores them.
+//!
+//! The offchain worker of a sealer periodically takes a signed checkpoint of the storage of
+//! the mixnet pallet and keeps the latest one in the offchain local storage
+//! (see: `pallet_mixnet::types::CHECKPOINT_KEY`). Whenever a block has been imported, the node
+//! writes a new checkpoint to `<checkpoint-path>/checkpoint-<block number>.cbor`.
+//!
+//! A checkpoint can be restored into the genesis storage of a chain specification
+//! (see: `restore-checkpoint`) to rebuild an aborted chain in a test environment.
+
+use codec::Decode;
+use futures::StreamExt;
+use mixnet_primitives::checkpoint::SignedCheckpoint;
+use provotum_runtime::{
+    opaque::Block,
+    pallet_mixnet::types::{CHECKPOINT_KEY, STORAGE_MODULE_PREFIX},
+    AccountId, Signature,
+};
+use sc_client_api::BlockchainEvents;
+use sc_service::ChainSpec;
+use sp_core::{
+    hashing::twox_128,
+    offchain::{OffchainStorage, STORAGE_PREFIX},
+    storage::Storage,
+};
+use sp_runtime::traits::Verify;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Writes the signed checkpoint to the directory, returns the path of the file.
+fn write_checkpoint(path: &Path, encoded: &[u8]) -> Result<PathBuf, String> {
+    let checkpoint = SignedCheckpoint::from_cbor(encoded)
+        .and_then(|signed| signed.decode_checkpoint())
+        .map_err(|err| err.to_string())?;
+    fs::create_dir_all(path).map_err(|err| err.to_string())?;
+    let file = path.join(format!("checkpoint-{}.cbor", checkpoint.block_number));
+    fs::write(&file, encoded).map_err(|err| err.to_string())?;
+    Ok(file)
+}
+
+/// Writes every new checkpoint of the offchain worker to the directory whenever a block has been imported.
+pub async fn run<C, S>(client: Arc<C>, storage: S, path: PathBuf)
+where
+    C: BlockchainEvents<Block>,
+    S: OffchainStorage,
+{
+    let mut imported = client.import_notification_stream();
+    // the last checkpoint written to the directory
+    let mut last: Option<Vec<u8>> = None;
+    while imported.next().await.is_some() {
+        let encoded = match storage.get(STORAGE_PREFIX, CHECKPOINT_KEY) {
+            Some(encoded) if last.as_ref() != Some(&encoded) => encoded,
+            _ => continue,
+        };
+        match write_checkpoint(&path, &encoded) {
+            Ok(file) => log::info!("mixnet checkpoint written to: {:?}", file),
+            Err(err) => log::warn!("failed to write the mixnet checkpoint: {}", err),
+        }
+        last = Some(encoded);
+    }
+}
+
+/// Verifies the signature of the checkpoint and returns the genesis storage of the chain
+/// specification in which the storage of the mixnet pallet is replaced by the checkpoint.
+/// The storage of the other pallets (e.g. the reserved ballot deposits) is not restored.
+pub fn restore(spec: &dyn ChainSpec, encoded: &[u8]) -> Result<Storage, String> {
+    let signed = SignedCheckpoint::from_cbor(encoded).map_err(|err| err.to_string())?;
+    let signer = AccountId::decode(&mut &signed.signer[..])
+        .map_err(|_| "the signer of the checkpoint is not an account")?;
+    let signature = Signature::decode(&mut &signed.signature[..])
+        .map_err(|_| "the signature of the checkpoint is malformed")?;
+    if !signature.verify(&signed.message()[..], &signer) {
+        return Err("the signature of the checkpoint is invalid".into());
+    }
+    let checkpoint = signed.decode_checkpoint().map_err(|err| err.to_string())?;
+
+    let mut storage = spec.as_storage_builder().build_storage()?;
+    let prefix = twox_128(STORAGE_MODULE_PREFIX);
+    storage.top.retain(|key, _| !key.starts_with(&prefix));
+    storage.top.extend(checkpoint.entries.into_iter());
+    Ok(storage)
+}
@@ -1,4 +1,5 @@
 use sc_cli::RunCmd;
+use std::path::PathBuf;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -8,6 +9,10 @@ pub struct Cli {
 
     #[structopt(flatten)]
     pub run: RunCmd,
+
+    /// The directory the signed checkpoints of the election state taken by the offchain worker are written to.
+    #[structopt(long, parse(from_os_str))]
+    pub checkpoint_path: Option<PathBuf>,
 }
 
 #[derive(Debug, StructOpt)]
@@ -33,7 +38,37 @@ pub enum Subcommand {
     /// Revert the chain to a previous state.
     Revert(sc_cli::RevertCmd),
 
+    /// Generate a ready-to-run chain specification for a number of sealers.
+    #[structopt(name = "chain-spec")]
+    ChainSpec(ChainSpecCmd),
+
+    /// Restore a signed checkpoint of the election state into the genesis of a chain specification.
+    #[structopt(name = "restore-checkpoint")]
+    RestoreCheckpoint(RestoreCheckpointCmd),
+
     /// The custom benchmark subcommmand benchmarking runtime pallets.
     #[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
     Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 }
+
+#[derive(Debug, StructOpt)]
+pub struct ChainSpecCmd {
+    /// The number of sealers, each sealer is also a block authority.
+    #[structopt(long, default_value = "2")]
+    pub sealers: usize,
+
+    /// Force raw genesis storage output.
+    #[structopt(long)]
+    pub raw: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct RestoreCheckpointCmd {
+    /// The signed checkpoint (CBOR) written by a sealer, see: --checkpoint-path.
+    #[structopt(long, parse(from_os_str))]
+    pub checkpoint: PathBuf,
+
+    /// The chain specification of the restored chain: dev, local or the path to a JSON file.
+    #[structopt(long, default_value = "dev")]
+    pub chain: String,
+}
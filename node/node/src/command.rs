@@ -16,7 +16,7 @@
 // limitations under the License.
 
 use crate::cli::{Cli, Subcommand};
-use crate::{chain_spec, service};
+use crate::{chain_spec, checkpoint, service};
 use provotum_runtime::Block;
 use sc_cli::{ChainSpec, Role, RuntimeVersion, SubstrateCli};
 use sc_service::PartialComponents;
@@ -70,6 +70,23 @@ pub fn run() -> sc_cli::Result<()> {
             let runner = cli.create_runner(cmd)?;
             runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
         }
+        Some(Subcommand::ChainSpec(cmd)) => {
+            let spec =
+                chain_spec::sealers_config(cmd.sealers).map_err(sc_cli::Error::Input)?;
+            let json = sc_service::chain_ops::build_spec(&spec, cmd.raw)?;
+            println!("{}", json);
+            Ok(())
+        }
+        Some(Subcommand::RestoreCheckpoint(cmd)) => {
+            let mut spec = cli.load_spec(&cmd.chain).map_err(sc_cli::Error::Input)?;
+            let encoded = std::fs::read(&cmd.checkpoint)?;
+            let storage =
+                checkpoint::restore(&*spec, &encoded).map_err(sc_cli::Error::Input)?;
+            spec.set_storage(storage);
+            let json = sc_service::chain_ops::build_spec(&*spec, true)?;
+            println!("{}", json);
+            Ok(())
+        }
         Some(Subcommand::CheckBlock(cmd)) => {
             let runner = cli.create_runner(cmd)?;
             runner.async_run(|config| {
@@ -147,7 +164,7 @@ pub fn run() -> sc_cli::Result<()> {
             let runner = cli.create_runner(&cli.run)?;
             runner.run_node_until_exit(|config| match config.role {
                 Role::Light => service::new_light(config),
-                _ => service::new_full(config),
+                _ => service::new_full(config, cli.checkpoint_path.clone()),
             })
         }
     }
@@ -2,10 +2,12 @@
 #![warn(missing_docs)]
 
 mod chain_spec;
+mod checkpoint;
 #[macro_use]
 mod service;
 mod cli;
 mod command;
+mod metrics;
 mod rpc;
 
 fn main() -> sc_cli::Result<()> {
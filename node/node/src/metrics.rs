@@ -0,0 +1,184 @@
+//! Exposes the metrics of the mixnet offchain worker through the node's Prometheus registry.
+//!
+//! The offchain worker records its metrics in the offchain local storage
+//! (see: `pallet_mixnet::types::OffchainMetrics`). The node reads them whenever a block
+//! has been imported and updates the Prometheus metrics accordingly.
+
+use codec::Decode;
+use futures::StreamExt;
+use provotum_runtime::{
+    opaque::Block,
+    pallet_mixnet::types::{
+        OffchainHistogram, OffchainMetrics, OFFCHAIN_METRICS_BUCKETS_MS,
+        OFFCHAIN_METRICS_KEY,
+    },
+};
+use sc_client_api::BlockchainEvents;
+use sp_core::offchain::{OffchainStorage, STORAGE_PREFIX};
+use std::iter::{once, repeat};
+use std::sync::Arc;
+use substrate_prometheus_endpoint::{
+    register, Counter, Gauge, GaugeVec, Opts, PrometheusError, Registry, U64,
+};
+
+/// A duration histogram of the offchain worker. The histogram is recorded by the runtime,
+/// therefore it is exported as gauges named and labelled like a Prometheus histogram
+/// (`_bucket` with the cumulative count per `le`, `_sum` and `_count`).
+struct HistogramMetrics {
+    buckets: GaugeVec<U64>,
+    sum: Gauge<U64>,
+    count: Gauge<U64>,
+}
+
+impl HistogramMetrics {
+    fn register(
+        name: &str,
+        help: &str,
+        registry: &Registry,
+    ) -> Result<Self, PrometheusError> {
+        Ok(HistogramMetrics {
+            buckets: register(
+                GaugeVec::new(Opts::new(format!("{}_bucket", name), help), &["le"])?,
+                registry,
+            )?,
+            sum: register(Gauge::new(format!("{}_sum", name), help)?, registry)?,
+            count: register(Gauge::new(format!("{}_count", name), help)?, registry)?,
+        })
+    }
+
+    fn update(&self, histogram: &OffchainHistogram) {
+        let bounds = OFFCHAIN_METRICS_BUCKETS_MS
+            .iter()
+            .map(|bound| bound.to_string())
+            .chain(once("+Inf".to_string()));
+        let counts = histogram.buckets.iter().chain(repeat(&0));
+
+        let mut cumulative: u64 = 0;
+        for (bound, count) in bounds.zip(counts) {
+            cumulative = cumulative.saturating_add(*count);
+            self.buckets.with_label_values(&[&bound]).set(cumulative);
+        }
+        self.sum.set(histogram.sum);
+        self.count.set(histogram.count);
+    }
+}
+
+/// The Prometheus metrics of the mixnet offchain worker.
+pub struct OffchainWorkerMetrics {
+    ciphers_shuffled: Counter<U64>,
+    shuffles_committed: Counter<U64>,
+    shuffles_revealed: Counter<U64>,
+    submissions_failed: Counter<U64>,
+    jobs_dead_lettered: Counter<U64>,
+    shuffle_ms: HistogramMetrics,
+    proof_generation_ms: HistogramMetrics,
+    // the last metrics read from the offchain storage, the counters are increased by the difference
+    last: OffchainMetrics,
+}
+
+impl OffchainWorkerMetrics {
+    /// Registers the metrics in the registry.
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(OffchainWorkerMetrics {
+            ciphers_shuffled: register(
+                Counter::new(
+                    "mixnet_ocw_ciphers_shuffled_total",
+                    "Number of ciphers shuffled by the offchain worker",
+                )?,
+                registry,
+            )?,
+            shuffles_committed: register(
+                Counter::new(
+                    "mixnet_ocw_shuffles_committed_total",
+                    "Number of shuffle commitments submitted by the offchain worker",
+                )?,
+                registry,
+            )?,
+            shuffles_revealed: register(
+                Counter::new(
+                    "mixnet_ocw_shuffles_revealed_total",
+                    "Number of shuffles revealed by the offchain worker",
+                )?,
+                registry,
+            )?,
+            submissions_failed: register(
+                Counter::new(
+                    "mixnet_ocw_submissions_failed_total",
+                    "Number of signed transactions the offchain worker failed to submit",
+                )?,
+                registry,
+            )?,
+            jobs_dead_lettered: register(
+                Counter::new(
+                    "mixnet_ocw_jobs_dead_lettered_total",
+                    "Number of offchain worker jobs moved to the dead-letter log",
+                )?,
+                registry,
+            )?,
+            shuffle_ms: HistogramMetrics::register(
+                "mixnet_ocw_shuffle_duration_ms",
+                "Time to shuffle a batch of ciphers in milliseconds",
+                registry,
+            )?,
+            proof_generation_ms: HistogramMetrics::register(
+                "mixnet_ocw_proof_generation_duration_ms",
+                "Time to generate the shuffle proof of a batch in milliseconds",
+                registry,
+            )?,
+            last: OffchainMetrics::default(),
+        })
+    }
+
+    fn update(&mut self, current: OffchainMetrics) {
+        let last = &self.last;
+        let increase = |counter: &Counter<U64>, last: u64, current: u64| {
+            counter.inc_by(current.saturating_sub(last));
+        };
+        increase(
+            &self.ciphers_shuffled,
+            last.ciphers_shuffled,
+            current.ciphers_shuffled,
+        );
+        increase(
+            &self.shuffles_committed,
+            last.shuffles_committed,
+            current.shuffles_committed,
+        );
+        increase(
+            &self.shuffles_revealed,
+            last.shuffles_revealed,
+            current.shuffles_revealed,
+        );
+        increase(
+            &self.submissions_failed,
+            last.submissions_failed,
+            current.submissions_failed,
+        );
+        increase(
+            &self.jobs_dead_lettered,
+            last.jobs_dead_lettered,
+            current.jobs_dead_lettered,
+        );
+        self.shuffle_ms.update(&current.shuffle_ms);
+        self.proof_generation_ms
+            .update(&current.proof_generation_ms);
+        self.last = current;
+    }
+}
+
+/// Updates the metrics from the offchain storage whenever a block has been imported.
+pub async fn run<C, S>(client: Arc<C>, storage: S, mut metrics: OffchainWorkerMetrics)
+where
+    C: BlockchainEvents<Block>,
+    S: OffchainStorage,
+{
+    let mut imported = client.import_notification_stream();
+    while imported.next().await.is_some() {
+        let recorded = storage
+            .get(STORAGE_PREFIX, OFFCHAIN_METRICS_KEY)
+            .and_then(|encoded| OffchainMetrics::decode(&mut &encoded[..]).ok());
+        if let Some(recorded) = recorded {
+            metrics.update(recorded);
+        }
+    }
+}
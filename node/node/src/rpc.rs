@@ -34,9 +34,14 @@ where
     C: Send + Sync + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: pallet_mixnet_rpc::MixnetRuntimeApi<Block, AccountId>,
     C::Api: BlockBuilder<Block>,
     P: TransactionPool + 'static,
 {
+    use pallet_mixnet_rpc::{
+        provotum::{Provotum, ProvotumApi},
+        Mixnet, MixnetApi,
+    };
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
@@ -54,13 +59,16 @@ where
     )));
 
     io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(
-        client,
+        client.clone(),
     )));
 
-    // Extend this RPC with a custom API by using the following syntax.
-    // `YourRpcStruct` should have a reference to a client, which is needed
-    // to call into the runtime.
-    // `io.extend_with(YourRpcTrait::to_delegate(YourRpcStruct::new(ReferenceToClient, ...)));`
+    // custom rpc: query the election state of the mixnet pallet
+    io.extend_with(MixnetApi::to_delegate(Mixnet::new(client.clone())));
+
+    // custom rpc: versioned, hex encoded election data for web clients
+    io.extend_with(ProvotumApi::to_delegate(Provotum::<_, _, AccountId>::new(
+        client,
+    )));
 
     io
 }
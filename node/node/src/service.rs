@@ -1,7 +1,7 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
 use provotum_runtime::{self, opaque::Block, pallet_mixnet, RuntimeApi};
-use sc_client_api::{ExecutorProvider, RemoteBackend};
+use sc_client_api::{Backend, ExecutorProvider, RemoteBackend};
 use sc_executor::native_executor_instance;
 pub use sc_executor::NativeExecutor;
 use sc_finality_grandpa::{
@@ -10,6 +10,7 @@ use sc_finality_grandpa::{
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
 use sp_inherents::InherentDataProviders;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -119,7 +120,10 @@ pub fn new_partial(
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(
+    config: Configuration,
+    checkpoint_path: Option<PathBuf>,
+) -> Result<TaskManager, ServiceError> {
     let sc_service::PartialComponents {
         client,
         backend,
@@ -163,6 +167,32 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
     let name = config.network.node_name.clone();
     let enable_grandpa = !config.disable_grandpa;
     let prometheus_registry = config.prometheus_registry().cloned();
+
+    // export the metrics the offchain worker records in the offchain storage
+    if let (true, Some(registry), Some(storage)) = (
+        config.offchain_worker.enabled,
+        prometheus_registry.as_ref(),
+        backend.offchain_storage(),
+    ) {
+        let metrics = crate::metrics::OffchainWorkerMetrics::register(registry)?;
+        task_manager.spawn_handle().spawn(
+            "mixnet-ocw-metrics",
+            crate::metrics::run(client.clone(), storage, metrics),
+        );
+    }
+
+    // write the checkpoints the offchain worker takes of the election state
+    if let (true, Some(path), Some(storage)) = (
+        config.offchain_worker.enabled,
+        checkpoint_path,
+        backend.offchain_storage(),
+    ) {
+        task_manager.spawn_handle().spawn(
+            "mixnet-ocw-checkpoints",
+            crate::checkpoint::run(client.clone(), storage, path),
+        );
+    }
+
     let telemetry_connection_sinks = sc_service::TelemetryConnectionSinks::default();
 
     let rpc_extensions_builder = {
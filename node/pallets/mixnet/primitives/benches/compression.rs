@@ -0,0 +1,51 @@
+use codec::Encode;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use crypto::{encryption::ElGamal, helper::Helper};
+use mixnet_primitives::{compression::CompressedCiphers, wire::Cipher};
+use num_bigint::BigUint;
+
+/// the encrypted votes (0 or 1) of the 2048bit system
+fn setup_ciphers(nr_of_ciphers: u32) -> Vec<Cipher> {
+    let (_, _, pk) = Helper::setup_lg_system();
+    (0..nr_of_ciphers)
+        .map(|vote| {
+            let r = BigUint::from(vote + 1234u32).pow(100);
+            ElGamal::encrypt_encode(&BigUint::from(vote % 2), &r, &pk).into()
+        })
+        .collect()
+}
+
+fn bench_compression(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compression");
+    for nr_of_ciphers in [10u32, 100, 1000].iter() {
+        let ciphers = setup_ciphers(*nr_of_ciphers);
+        let compressed = CompressedCiphers::compress(&ciphers);
+
+        // the storage savings compared to the SCALE encoding of the ciphers
+        let scale_size = ciphers.encode().len();
+        let compressed_size = compressed.encode().len();
+        println!(
+            "{} ciphers: SCALE {} bytes, compressed {} bytes, saved {} bytes ({:.2}%)",
+            nr_of_ciphers,
+            scale_size,
+            compressed_size,
+            scale_size as i64 - compressed_size as i64,
+            (scale_size as f64 - compressed_size as f64) * 100f64 / scale_size as f64
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("compress", nr_of_ciphers),
+            &ciphers,
+            |b, ciphers| b.iter(|| CompressedCiphers::compress(ciphers)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("decompress", nr_of_ciphers),
+            &compressed,
+            |b, compressed| b.iter(|| compressed.decompress().unwrap()),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_compression);
+criterion_main!(benches);
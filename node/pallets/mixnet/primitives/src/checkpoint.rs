@@ -0,0 +1,286 @@
+//! Signed checkpoints of the election state for disaster recovery.
+//!
+//! The offchain worker of a sealer periodically takes a snapshot of the storage of the mixnet
+//! pallet (votes, ciphers, proofs, ...) as raw key/value pairs, encodes it as a CBOR array
+//! [version, block number, parent hash, [[key, value], ...]] and signs it with the node's
+//! authority key. The signed checkpoint is the CBOR array [checkpoint, signer, signature].
+//! A checkpoint can be restored into the genesis storage of a new (test) chain.
+//!
+//! The encoder is part of the runtime (no_std), the decoder requires `std`.
+use alloc::vec::Vec;
+use codec::Encode;
+
+/// The context of the signature of a checkpoint, separates it from other signatures of the sealer.
+pub const CHECKPOINT_CONTEXT: &[u8] = b"provotum-mixnet/checkpoint/v1";
+
+/// The version of the checkpoint encoding, the first element of the CBOR array.
+pub const CHECKPOINT_VERSION: u8 = 1;
+
+/// the major types of CBOR data items used by the checkpoints
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_ARRAY: u8 = 4;
+
+/// appends the head of a CBOR data item, i.e. the major type and its argument
+/// (the value of an unsigned integer or the length of a byte string or an array)
+/// in the shortest encoding.
+fn push_head(output: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    if argument < 24 {
+        output.push(major | argument as u8);
+    } else if argument <= u8::MAX as u64 {
+        output.push(major | 24);
+        output.push(argument as u8);
+    } else if argument <= u16::MAX as u64 {
+        output.push(major | 25);
+        output.extend_from_slice(&(argument as u16).to_be_bytes());
+    } else if argument <= u32::MAX as u64 {
+        output.push(major | 26);
+        output.extend_from_slice(&(argument as u32).to_be_bytes());
+    } else {
+        output.push(major | 27);
+        output.extend_from_slice(&argument.to_be_bytes());
+    }
+}
+
+fn push_bytes(output: &mut Vec<u8>, bytes: &[u8]) {
+    push_head(output, MAJOR_BYTES, bytes.len() as u64);
+    output.extend_from_slice(bytes);
+}
+
+/// A snapshot of the storage of the mixnet pallet at a block.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Checkpoint {
+    pub block_number: u64,
+    /// the SCALE encoded hash of the parent block, the hash of the block itself is unknown to the runtime
+    pub parent_hash: Vec<u8>,
+    /// the raw storage entries (key, value) of the pallet, ordered by key
+    pub entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl Checkpoint {
+    /// Returns the CBOR array [version, block number, parent hash, [[key, value], ...]].
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut output: Vec<u8> = Vec::new();
+        push_head(&mut output, MAJOR_ARRAY, 4);
+        push_head(&mut output, MAJOR_UNSIGNED, CHECKPOINT_VERSION as u64);
+        push_head(&mut output, MAJOR_UNSIGNED, self.block_number);
+        push_bytes(&mut output, &self.parent_hash);
+        push_head(&mut output, MAJOR_ARRAY, self.entries.len() as u64);
+        for (key, value) in self.entries.iter() {
+            push_head(&mut output, MAJOR_ARRAY, 2);
+            push_bytes(&mut output, key);
+            push_bytes(&mut output, value);
+        }
+        output
+    }
+}
+
+/// A CBOR encoded checkpoint signed by a sealer.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SignedCheckpoint {
+    /// the CBOR encoded checkpoint, see: `Checkpoint::to_cbor`
+    pub checkpoint: Vec<u8>,
+    /// the SCALE encoded account id of the sealer
+    pub signer: Vec<u8>,
+    /// the SCALE encoded signature of the checkpoint message, see: `checkpoint_message`
+    pub signature: Vec<u8>,
+}
+
+impl SignedCheckpoint {
+    /// Returns the CBOR array [checkpoint, signer, signature] of byte strings.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut output: Vec<u8> = Vec::new();
+        push_head(&mut output, MAJOR_ARRAY, 3);
+        push_bytes(&mut output, &self.checkpoint);
+        push_bytes(&mut output, &self.signer);
+        push_bytes(&mut output, &self.signature);
+        output
+    }
+
+    /// Returns the message the sealer has signed.
+    pub fn message(&self) -> Vec<u8> {
+        checkpoint_message(&self.checkpoint)
+    }
+}
+
+/// Returns the message the sealer signs to certify the (CBOR encoded) checkpoint.
+pub fn checkpoint_message(encoded_checkpoint: &[u8]) -> Vec<u8> {
+    (CHECKPOINT_CONTEXT, encoded_checkpoint).encode()
+}
+
+#[cfg(feature = "std")]
+pub use decode::CheckpointError;
+
+#[cfg(feature = "std")]
+mod decode {
+    use super::{Checkpoint, SignedCheckpoint, CHECKPOINT_VERSION};
+    use serde::Deserialize;
+    use std::fmt;
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    pub enum CheckpointError {
+        /// the bytes are not a valid CBOR array of a (signed) checkpoint
+        Cbor,
+        /// the checkpoint has been encoded with an unknown version
+        UnsupportedVersion(u8),
+    }
+
+    impl fmt::Display for CheckpointError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                CheckpointError::Cbor => write!(f, "the checkpoint is not valid CBOR"),
+                CheckpointError::UnsupportedVersion(version) => {
+                    write!(f, "the checkpoint version {} is not supported", version)
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for CheckpointError {}
+
+    #[derive(Deserialize)]
+    struct Entry(
+        #[serde(with = "serde_bytes")] Vec<u8>,
+        #[serde(with = "serde_bytes")] Vec<u8>,
+    );
+
+    #[derive(Deserialize)]
+    struct CheckpointEnvelope(
+        u8,
+        u64,
+        #[serde(with = "serde_bytes")] Vec<u8>,
+        Vec<Entry>,
+    );
+
+    #[derive(Deserialize)]
+    struct SignedEnvelope(
+        #[serde(with = "serde_bytes")] Vec<u8>,
+        #[serde(with = "serde_bytes")] Vec<u8>,
+        #[serde(with = "serde_bytes")] Vec<u8>,
+    );
+
+    impl Checkpoint {
+        /// Decodes a CBOR encoded checkpoint and checks its version.
+        pub fn from_cbor(bytes: &[u8]) -> Result<Checkpoint, CheckpointError> {
+            let CheckpointEnvelope(version, block_number, parent_hash, entries) =
+                serde_cbor::from_slice(bytes).map_err(|_| CheckpointError::Cbor)?;
+            if version != CHECKPOINT_VERSION {
+                return Err(CheckpointError::UnsupportedVersion(version));
+            }
+            Ok(Checkpoint {
+                block_number,
+                parent_hash,
+                entries: entries
+                    .into_iter()
+                    .map(|Entry(key, value)| (key, value))
+                    .collect(),
+            })
+        }
+    }
+
+    impl SignedCheckpoint {
+        /// Decodes a CBOR encoded signed checkpoint, the signature is not verified.
+        pub fn from_cbor(bytes: &[u8]) -> Result<SignedCheckpoint, CheckpointError> {
+            let SignedEnvelope(checkpoint, signer, signature) =
+                serde_cbor::from_slice(bytes).map_err(|_| CheckpointError::Cbor)?;
+            Ok(SignedCheckpoint {
+                checkpoint,
+                signer,
+                signature,
+            })
+        }
+
+        /// Decodes the signed checkpoint.
+        pub fn decode_checkpoint(&self) -> Result<Checkpoint, CheckpointError> {
+            Checkpoint::from_cbor(&self.checkpoint)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn checkpoint() -> Checkpoint {
+        Checkpoint {
+            block_number: 1_000,
+            parent_hash: vec![3u8; 32],
+            entries: vec![
+                (vec![1u8; 32], vec![]),
+                (vec![1u8; 48], vec![7u8; 300]),
+                (vec![2u8; 64], vec![9u8; 70_000]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let checkpoint = checkpoint();
+        let signed = SignedCheckpoint {
+            checkpoint: checkpoint.to_cbor(),
+            signer: vec![7u8; 32],
+            signature: vec![9u8; 65],
+        };
+        let decoded = SignedCheckpoint::from_cbor(&signed.to_cbor()).unwrap();
+        assert_eq!(decoded, signed);
+        assert_eq!(decoded.decode_checkpoint(), Ok(checkpoint));
+    }
+
+    #[test]
+    fn test_checkpoint_encoding_is_canonical() {
+        // the heads use the shortest encoding of their argument, the same as serde_cbor
+        let checkpoint = checkpoint();
+        let entries: Vec<(serde_bytes::ByteBuf, serde_bytes::ByteBuf)> = checkpoint
+            .entries
+            .iter()
+            .map(|(key, value)| {
+                (
+                    serde_bytes::ByteBuf::from(key.clone()),
+                    serde_bytes::ByteBuf::from(value.clone()),
+                )
+            })
+            .collect();
+        let expected = serde_cbor::to_vec(&(
+            CHECKPOINT_VERSION,
+            checkpoint.block_number,
+            serde_bytes::ByteBuf::from(checkpoint.parent_hash.clone()),
+            entries,
+        ))
+        .unwrap();
+        assert_eq!(checkpoint.to_cbor(), expected);
+
+        let mut output = Vec::new();
+        for argument in [23u64, 24, 255, 256, 65_536, 1 << 32].iter() {
+            push_head(&mut output, MAJOR_UNSIGNED, *argument);
+        }
+        assert_eq!(
+            output,
+            serde_cbor::to_vec(&(23u64, 24u64, 255u64, 256u64, 65_536u64, 1u64 << 32))
+                .unwrap()[1..]
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_rejects_malformed_input() {
+        assert_eq!(
+            SignedCheckpoint::from_cbor(&[1, 2, 3]),
+            Err(CheckpointError::Cbor)
+        );
+
+        let mut encoded = checkpoint().to_cbor();
+        encoded[1] = 2;
+        assert_eq!(
+            Checkpoint::from_cbor(&encoded),
+            Err(CheckpointError::UnsupportedVersion(2))
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_message_binds_the_checkpoint() {
+        let message = checkpoint_message(&[1, 2, 3]);
+        assert!(message.starts_with(&CHECKPOINT_CONTEXT.encode()));
+        assert_ne!(message, checkpoint_message(&[1, 2, 4]));
+    }
+}
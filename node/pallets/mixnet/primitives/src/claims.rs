@@ -0,0 +1,30 @@
+//! Eligibility claims of an external identity provider.
+//!
+//! An identity provider (e.g. a government eID service) checks the eligibility of a voter
+//! off-chain and signs a claim which binds the voter's account to the vote (sr25519 or ed25519).
+//! Anyone can submit the claim (see: `register_voter_with_claim`), the pallet verifies the
+//! signature with the identity provider's public key configured for the vote.
+use alloc::vec::Vec;
+use codec::Encode;
+
+/// The context of the signature of an eligibility claim, separates it from other signatures of the identity provider.
+pub const ELIGIBILITY_CLAIM_CONTEXT: &[u8] = b"provotum-mixnet/eligibility-claim/v1";
+
+/// Returns the message the identity provider signs to claim that the voter (SCALE encoded account id)
+/// is eligible to vote.
+pub fn eligibility_claim_message(vote_id: &[u8], encoded_voter: &[u8]) -> Vec<u8> {
+    (ELIGIBILITY_CLAIM_CONTEXT, vote_id, encoded_voter).encode()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eligibility_claim_message_binds_the_vote_and_the_voter() {
+        let message = eligibility_claim_message(b"20201212", &[7u8; 32]);
+        assert!(message.starts_with(&ELIGIBILITY_CLAIM_CONTEXT.encode()));
+        assert_ne!(message, eligibility_claim_message(b"20201213", &[7u8; 32]));
+        assert_ne!(message, eligibility_claim_message(b"20201212", &[8u8; 32]));
+    }
+}
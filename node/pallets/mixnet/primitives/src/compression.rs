@@ -0,0 +1,307 @@
+//! A compressed encoding of a batch of ciphers.
+//!
+//! The SCALE encoding of a `Vec<Cipher>` prefixes both components of every cipher with their
+//! length (2 bytes for a 2048bit modulus). The components are group elements, i.e. they have
+//! (almost always) the byte length of the modulus. The compressed encoding therefore stores
+//! this width once per batch and every component as the number of its leading zero bytes
+//! (a single byte) followed by its big-endian bytes without leading zeros (zero is `[0]`):
+//!
+//! [version, width (varint), count (varint), [zeros (varint), bytes]...]
+//!
+//! The varints are unsigned LEB128. The decoding only accepts the canonical encoding
+//! (minimal varints, no leading zeros, no trailing bytes) and returns the ciphers as
+//! `From<BigCipher>` creates them (minimal big-endian bytes), i.e. hashes over the decompressed
+//! ciphers (merkle roots, ballot box freeze, proof contexts) equal the hashes over the originals.
+//!
+//! The components are uniformly distributed group elements, therefore a general purpose
+//! compressor (e.g. zstd) can't shrink them any further.
+use crate::wire::{Cipher, Wrapper};
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use core::{convert::TryFrom, iter};
+use crypto::types::Cipher as BigCipher;
+
+/// The version of the compressed encoding, the first byte of a compressed batch.
+pub const COMPRESSION_VERSION: u8 = 1;
+
+/// A batch of ciphers in the compressed encoding.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct CompressedCiphers(pub Vec<u8>);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CompressionError {
+    /// the batch has been compressed with an unknown version
+    UnsupportedVersion(u8),
+    /// the bytes end within a varint or a component
+    UnexpectedEnd,
+    /// the bytes are not the canonical encoding of a batch
+    NonCanonical,
+}
+
+/// appends the value as unsigned LEB128
+fn push_varint(output: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        output.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+/// the components of the ciphers in the order of the encoding
+fn components(ciphers: &[Cipher]) -> impl Iterator<Item = &Vec<u8>> {
+    ciphers
+        .iter()
+        .flat_map(|cipher| iter::once(&cipher.a).chain(iter::once(&cipher.b)))
+}
+
+/// the big-endian bytes without leading zeros, the same as `BigUint::to_bytes_be`
+fn strip_leading_zeros(bytes: &[u8]) -> &[u8] {
+    match bytes.iter().position(|byte| *byte != 0) {
+        Some(start) => &bytes[start..],
+        None => &[0u8],
+    }
+}
+
+/// reads the bytes of a compressed batch, see: `CompressedCiphers::decompress`
+struct Reader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn read_byte(&mut self) -> Result<u8, CompressionError> {
+        let (byte, rest) = self
+            .input
+            .split_first()
+            .ok_or(CompressionError::UnexpectedEnd)?;
+        self.input = rest;
+        Ok(*byte)
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], CompressionError> {
+        if self.input.len() < length {
+            return Err(CompressionError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.input.split_at(length);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    /// reads a minimal unsigned LEB128 varint
+    fn read_varint(&mut self) -> Result<u64, CompressionError> {
+        let mut value: u64 = 0;
+        for index in 0..10 {
+            let byte = self.read_byte()?;
+            let bits = (byte & 0x7f) as u64;
+            if index == 9 && bits > 1 {
+                return Err(CompressionError::NonCanonical);
+            }
+            value |= bits << (7 * index);
+            if byte & 0x80 == 0 {
+                // a trailing zero byte is not minimal
+                if index > 0 && byte == 0 {
+                    return Err(CompressionError::NonCanonical);
+                }
+                return Ok(value);
+            }
+        }
+        Err(CompressionError::NonCanonical)
+    }
+
+    /// reads a component of the width: the number of leading zeros and the remaining bytes
+    fn read_component(&mut self, width: usize) -> Result<Vec<u8>, CompressionError> {
+        let zeros = self.read_varint()?;
+        if zeros > width as u64 {
+            return Err(CompressionError::NonCanonical);
+        }
+        let bytes = self.read_bytes(width - zeros as usize)?;
+        match bytes {
+            [] => Err(CompressionError::NonCanonical),
+            [0, _, ..] => Err(CompressionError::NonCanonical),
+            _ => Ok(bytes.to_vec()),
+        }
+    }
+}
+
+impl CompressedCiphers {
+    /// Compresses the ciphers, leading zeros of their components are stripped.
+    pub fn compress(ciphers: &[Cipher]) -> CompressedCiphers {
+        let width = components(ciphers)
+            .map(|component| strip_leading_zeros(component).len())
+            .max()
+            .unwrap_or(0);
+
+        let mut output: Vec<u8> = Vec::new();
+        output.push(COMPRESSION_VERSION);
+        push_varint(&mut output, width as u64);
+        push_varint(&mut output, ciphers.len() as u64);
+        for component in components(ciphers) {
+            let bytes = strip_leading_zeros(component);
+            push_varint(&mut output, (width - bytes.len()) as u64);
+            output.extend_from_slice(bytes);
+        }
+        CompressedCiphers(output)
+    }
+
+    /// Decompresses the ciphers, only the canonical encoding is accepted.
+    pub fn decompress(&self) -> Result<Vec<Cipher>, CompressionError> {
+        let mut reader = Reader { input: &self.0 };
+        let version = reader.read_byte()?;
+        if version != COMPRESSION_VERSION {
+            return Err(CompressionError::UnsupportedVersion(version));
+        }
+        let width = reader.read_varint()? as usize;
+        let count = reader.read_varint()?;
+
+        // every cipher requires at least four bytes, bounds the allocation
+        if count > (reader.input.len() / 4) as u64 {
+            return Err(CompressionError::UnexpectedEnd);
+        }
+        let mut ciphers: Vec<Cipher> = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let a = reader.read_component(width)?;
+            let b = reader.read_component(width)?;
+            ciphers.push(Cipher { a, b });
+        }
+
+        // the width must be the length of the largest component
+        let largest = components(&ciphers)
+            .map(|component| component.len())
+            .max()
+            .unwrap_or(0);
+        if largest != width || !reader.input.is_empty() {
+            return Err(CompressionError::NonCanonical);
+        }
+        Ok(ciphers)
+    }
+}
+
+impl From<Wrapper<Cipher>> for CompressedCiphers {
+    fn from(source: Wrapper<Cipher>) -> Self {
+        CompressedCiphers::compress(&source.0)
+    }
+}
+
+impl From<Wrapper<BigCipher>> for CompressedCiphers {
+    fn from(source: Wrapper<BigCipher>) -> Self {
+        let ciphers: Vec<Cipher> = source.into();
+        CompressedCiphers::compress(&ciphers)
+    }
+}
+
+impl TryFrom<CompressedCiphers> for Vec<Cipher> {
+    type Error = CompressionError;
+
+    fn try_from(source: CompressedCiphers) -> Result<Self, Self::Error> {
+        source.decompress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto::{encryption::ElGamal, helper::Helper};
+    use num_bigint::BigUint;
+
+    /// the encrypted votes (0 or 1) of the 2048bit system
+    fn setup_ciphers(nr_of_ciphers: u32) -> Vec<Cipher> {
+        let (_, _, pk) = Helper::setup_lg_system();
+        (0..nr_of_ciphers)
+            .map(|vote| {
+                let r = BigUint::from(vote + 1234u32).pow(100);
+                ElGamal::encrypt_encode(&BigUint::from(vote % 2), &r, &pk).into()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compression_round_trip() {
+        let ciphers = setup_ciphers(5);
+        let compressed: CompressedCiphers = Wrapper(ciphers.clone()).into();
+        assert_eq!(compressed.decompress(), Ok(ciphers));
+        assert_eq!(
+            CompressedCiphers::compress(&[]).decompress(),
+            Ok(Vec::new())
+        );
+
+        // a zero component is a single zero byte
+        let zero = vec![Cipher {
+            a: vec![0u8],
+            b: vec![7u8],
+        }];
+        assert_eq!(
+            CompressedCiphers::compress(&zero).decompress(),
+            Ok(zero.clone())
+        );
+        let empty = vec![Cipher {
+            a: Vec::new(),
+            b: vec![7u8],
+        }];
+        assert_eq!(CompressedCiphers::compress(&empty).decompress(), Ok(zero));
+    }
+
+    #[test]
+    fn test_compression_is_smaller_than_scale() {
+        let ciphers = setup_ciphers(10);
+        let compressed = CompressedCiphers::compress(&ciphers);
+        // the length prefixes of both components (2 bytes each) are replaced by a single byte,
+        // the header (version, width and count) is three bytes longer than the SCALE length prefix
+        assert_eq!(
+            compressed.0.len() + 2 * ciphers.len(),
+            ciphers.encode().len() + 3
+        );
+    }
+
+    #[test]
+    fn test_compression_strips_leading_zeros() {
+        let ciphers = setup_ciphers(2);
+        let padded: Vec<Cipher> = ciphers
+            .iter()
+            .map(|cipher| Cipher {
+                a: [&[0u8, 0u8][..], &cipher.a[..]].concat(),
+                b: cipher.b.clone(),
+            })
+            .collect();
+        let compressed = CompressedCiphers::compress(&padded);
+        assert_eq!(compressed, CompressedCiphers::compress(&ciphers));
+        assert_eq!(compressed.decompress(), Ok(ciphers));
+    }
+
+    #[test]
+    fn test_compression_rejects_non_canonical_input() {
+        let compressed = CompressedCiphers::compress(&[Cipher {
+            a: vec![1u8, 2u8],
+            b: vec![3u8],
+        }]);
+        assert_eq!(compressed.0, vec![1, 2, 1, 0, 1, 2, 1, 3]);
+
+        let decompress = |bytes: &[u8]| CompressedCiphers(bytes.to_vec()).decompress();
+        assert_eq!(
+            decompress(&[2, 2, 1, 0, 1, 2, 1, 3]),
+            Err(CompressionError::UnsupportedVersion(2))
+        );
+        assert_eq!(
+            decompress(&[1, 2, 1, 0, 1, 2, 1]),
+            Err(CompressionError::UnexpectedEnd)
+        );
+        // trailing bytes
+        assert_eq!(
+            decompress(&[1, 2, 1, 0, 1, 2, 1, 3, 0]),
+            Err(CompressionError::NonCanonical)
+        );
+        // a leading zero
+        assert_eq!(
+            decompress(&[1, 2, 1, 0, 0, 2, 1, 3]),
+            Err(CompressionError::NonCanonical)
+        );
+        // the width is larger than the largest component
+        assert_eq!(
+            decompress(&[1, 3, 1, 1, 1, 2, 2, 3]),
+            Err(CompressionError::NonCanonical)
+        );
+        // a non-minimal varint
+        assert_eq!(
+            decompress(&[1, 0x82, 0, 1, 0, 1, 2, 1, 3]),
+            Err(CompressionError::NonCanonical)
+        );
+    }
+}
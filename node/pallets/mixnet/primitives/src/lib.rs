@@ -0,0 +1,252 @@
+//! Primitives shared between the mixnet pallet and its clients.
+//!
+//! The pallet's errors reach the clients as `DispatchError::Module { index, error }`,
+//! where `error` is the position of the variant in the pallet's `Error` enum. The position
+//! changes whenever an error is added, therefore every error has a stable `ErrorCode`
+//! with a human-readable message which the clients can print instead.
+//!
+//! The wire types (see: `wire`) are shared as well, i.e., the pallet and the clients
+//! encode and convert ciphers, keys and proofs the same way.
+//! Ballots relayed on behalf of a voter are signed and (with `std`) encoded compactly for
+//! QR codes, see: `relay`. The eligibility claims of an identity provider are signed
+//! the same way by the pallet and the identity provider, see: `claims`.
+//! The signed checkpoints of the election state are encoded as CBOR, see: `checkpoint`.
+//! Batches of ciphers can be compressed for storage, see: `compression`.
+//! The messages of the blind-signed eligibility tokens of anonymous ballots, see: `tokens`.
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+pub mod checkpoint;
+pub mod claims;
+pub mod compression;
+pub mod relay;
+pub mod tokens;
+pub mod wire;
+
+use core::fmt;
+
+macro_rules! error_codes {
+    ($($code:literal => $name:ident: $message:literal,)*) => {
+        /// The stable code of every error of the mixnet pallet.
+        /// Codes are never reused or renumbered, new errors get the next free code.
+        #[repr(u16)]
+        #[derive(Clone, Copy, Eq, PartialEq, Debug)]
+        pub enum ErrorCode {
+            /// An error which is unknown to this version of the primitives.
+            Unknown = 0,
+            $($name = $code,)*
+        }
+
+        impl ErrorCode {
+            /// All known error codes in ascending order (without `Unknown`).
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$name,)*];
+
+            /// Returns the name of the error, i.e., the name of the pallet's error variant.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    ErrorCode::Unknown => "Unknown",
+                    $(ErrorCode::$name => stringify!($name),)*
+                }
+            }
+
+            /// Returns the human-readable message of the error.
+            pub fn message(&self) -> &'static str {
+                match self {
+                    ErrorCode::Unknown => "unknown error",
+                    $(ErrorCode::$name => $message,)*
+                }
+            }
+
+            /// Returns the error code of the pallet's error variant with the name.
+            pub fn from_name(name: &str) -> Option<ErrorCode> {
+                match name {
+                    $(stringify!($name) => Some(ErrorCode::$name),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    1 => UnknownOffchainMux: "unknown offchain worker function",
+    2 => ParseError: "a value cannot be parsed into a big integer",
+    3 => NotAVotingAuthority: "the sender is not a voting authority",
+    4 => IsVotingAuthority: "the sender is a voting authority",
+    5 => NotASealer: "the sender is not a sealer",
+    6 => NoLocalAcctForSigning: "the offchain worker has no local account to sign transactions",
+    7 => OffchainSignedTxError: "the offchain worker failed to submit a signed transaction",
+    8 => RandomnessGenerationError: "failed to generate randomness",
+    9 => RandomnessUpperBoundZeroError: "the upper bound of the randomness is zero",
+    10 => RandomRangeError: "failed to generate a random number in the range",
+    11 => PermutationSizeZeroError: "the size of the permutation is zero",
+    12 => PublicKeyNotExistsError: "the public key of the vote doesn't exist yet",
+    13 => PublicKeyShareNotExistsError: "the public key share doesn't exist",
+    14 => PublicKeyShareProofError: "the proof of the public key share doesn't verify",
+    15 => NotEnoughPublicKeyShares: "not enough public key shares have been submitted",
+    16 => InvModError: "the modular inverse doesn't exist",
+    17 => DivModError: "the modular division failed",
+    18 => VoteDoesNotExist: "the vote doesn't exist",
+    19 => VoteAlreadyExists: "a vote with this id already exists",
+    20 => WrongVotePhase: "the vote is not in the required phase",
+    21 => DecryptedShareProofError: "the proof of the decrypted shares doesn't verify",
+    22 => NotEnoughDecryptedShares: "not all sealers have submitted their decrypted shares yet",
+    23 => TopicHasAlreadyBeenTallied: "the question has already been tallied",
+    24 => ShuffleProofVerifcationFailed: "the shuffle proof doesn't verify",
+    25 => ShuffleAlreadyPerformed: "the ciphers have already been shuffled",
+    26 => ShuffleCiphersSizeZeroError: "there are no ciphers to shuffle",
+    27 => NrOfShufflesDoesNotExist: "there are no ciphers for the number of shuffles",
+    28 => CouldNotComputeShuffleStartPosition: "the start position of the shuffle batch cannot be computed",
+    29 => ShuffleStateIncorrect: "the shuffle doesn't match the current shuffle state",
+    30 => ShuffleAlreadyCompleted: "the question has already been shuffled completely",
+    31 => NrOfShufflesInvalid: "the vote requires at least one shuffle",
+    32 => WrongTallyStrategy: "the operation doesn't match the tally strategy of the vote",
+    33 => CiphersAlreadyAggregated: "the ciphers of the question have already been aggregated",
+    34 => CiphersNotAggregated: "the ciphers of the question have not been aggregated yet",
+    35 => HomomorphicTallyInvalid: "the decrypted aggregate exceeds the number of votes",
+    36 => DecodeBoundExceeded: "a decrypted vote cannot be decoded within the decode bound",
+    37 => KeyRotationPending: "the ciphers have not been switched to the rotated public key yet",
+    38 => KeySwitchNotPending: "the ciphers of the question don't need to be switched",
+    39 => KeySwitchSharesAlreadySubmitted: "the key switch shares have already been submitted",
+    40 => KeySwitchShareProofError: "the key switch proof doesn't verify",
+    41 => VoteNotTallied: "not all questions of the vote have been tallied yet",
+    42 => VoteAlreadyArchived: "the vote has already been archived",
+    43 => ShuffleNotCommitted: "the sealer has not committed to a shuffle",
+    44 => ShuffleAlreadyCommitted: "another sealer's shuffle commitment is still pending",
+    45 => ShuffleRevealTooEarly: "the shuffle cannot be revealed in the block of its commitment",
+    46 => ShuffleCommitmentMismatch: "the revealed shuffle doesn't match the commitment",
+    47 => CredentialAlreadyRegistered: "a credential has already been registered for the voter",
+    48 => ResultsPostError: "failed to post the results to the results endpoint",
+    49 => VoteAlreadyStarted: "the voting phase of the vote has already started",
+    50 => VoteProposalDoesNotExist: "the vote has not been proposed",
+    51 => AlreadyApproved: "the voting authority has already approved the vote",
+    52 => DuplicateCipher: "the ballot contains a cipher which has already been submitted",
+    53 => BallotProofError: "the proofs of the ballot don't verify for the voter",
+    54 => SpoiledCipherNotFound: "the voter has no cipher for the question which can be spoiled",
+    55 => BallotAlreadySpoiled: "the cipher has already been spoiled",
+    56 => SpoiledBallotNotFound: "the voter has no spoiled cipher with the hash",
+    57 => SpoiledBallotAlreadyRevealed: "the spoiled cipher has already been revealed",
+    58 => InvalidSpoiledBallotReveal: "the revealed message and randomness don't match the spoiled cipher",
+    59 => InvalidQuestionType: "the question type doesn't match the candidates or the tally strategy",
+    60 => BallotValidityProofError: "the answer is not proven to be valid for the question",
+    61 => NotYourShuffleTurn: "it is not the sealer's turn to shuffle the question",
+    62 => MetadataTooLarge: "the metadata of the vote exceeds the maximum size",
+    63 => InvalidShuffleBatchSize: "the shuffle batch size is out of the allowed bounds",
+    64 => InvalidDecryptionThreshold: "the decryption threshold must be between one and the number of sealers",
+    65 => KeyRotationNotSupported: "the public key of a vote with threshold keys can't be rotated",
+    66 => BallotRateLimitExceeded: "the account submitted too many ballots, try again later",
+    67 => InsufficientBallotDeposit: "the free balance doesn't cover the ballot deposit",
+    68 => NoBallotDeposit: "no ballot deposit has been reserved for the vote",
+    69 => InvalidVoterWeight: "the voter weight must be between 1 and the maximum voter weight",
+    70 => IncompleteLinkedBallot: "a ballot of a vote with linked shuffles must answer every question exactly once and can't be spoiled partially",
+    71 => LinkedShuffleMismatch: "the shuffles of the questions don't use the same batch or the same permutation",
+    72 => WrongShuffleMode: "the shuffle doesn't match whether the questions of the vote are shuffled linked",
+    73 => NotACertifier: "only voting authorities and sealers can certify the result of a vote",
+    74 => ResultHashMismatch: "the result hash doesn't match the results of the vote",
+    75 => ResultAlreadyCertified: "the result of the vote has already been certified",
+    76 => ResultAlreadyApproved: "the account has already approved the result of the vote",
+    77 => RelayedBallotSignatureError: "the relayed ballot is not signed by the voter",
+    78 => CancellationReasonTooLong: "the reason of the cancellation is too long",
+    79 => VoteCancelled: "the vote has been cancelled",
+    80 => NoIdentityProvider: "the vote has no identity provider",
+    81 => InvalidEligibilityClaim: "the eligibility claim is not signed by the identity provider of the vote",
+    82 => VoterAlreadyRegistered: "the voter is already registered for the vote",
+    83 => VoterNotRegistered: "the voter is not registered for the vote",
+    84 => CipherCountMismatch: "the number of shuffled ciphers doesn't match the number of input ciphers",
+    85 => TallyParametersMismatch: "the batch doesn't match the parameters of the tally in progress",
+    86 => NoCiphersLeftToTally: "all batches of the topic have been combined, the tally can be finalized",
+    87 => TallyIncomplete: "not all batches of the topic have been combined yet",
+    88 => UnsupportedProtocol: "the vote has been created with a protocol which is not supported by this release",
+    89 => VoteIdTooLong: "the vote id exceeds the maximum length",
+    90 => TitleTooLong: "the title of the vote exceeds the maximum length",
+    91 => TopicIdTooLong: "the topic id exceeds the maximum length",
+    92 => QuestionTooLong: "the question or one of its candidates exceeds the maximum length",
+    93 => CipherMalformed: "the cipher is empty or larger than the modulus of the vote",
+    94 => SealerAlreadyAdded: "the account is already a sealer",
+    95 => SealerNotFound: "the account is not a sealer",
+    96 => TooFewSealers: "the last sealer can't be removed",
+    97 => KeyGenerationStarted: "the protocol of the vote can't be changed once a public key share has been submitted",
+    98 => NoEligibilityKey: "the vote doesn't accept anonymous ballots",
+    99 => InvalidEligibilityToken: "the eligibility token is not signed by the voting authority of the vote",
+    100 => NullifierAlreadySpent: "a ballot has already been cast with the eligibility token",
+    101 => AnonymousVote: "the vote only accepts anonymous ballots",
+    102 => TopicNotInVote: "the question is not part of the vote",
+    103 => CipherImportTooLarge: "the batch of imported ciphers is empty or too large",
+    104 => SourceTagTooLong: "the source tag of the imported ciphers is too long",
+    105 => InvalidPrivateKeyShare: "the private key share of the offchain worker is missing or invalid",
+    106 => VoteTemplateDoesNotExist: "the vote template does not exist",
+    107 => VoteTemplateTooLarge: "the vote template has no topics or too many topics",
+    108 => InvalidPhaseTransition: "the vote can't change from its current phase to the requested phase",
+    109 => InvalidTopicPacking: "the answers of the question can't be packed, or the slots don't fit into a cipher",
+    110 => VoterRevoked: "the voter has been revoked and can't cast a ballot or be registered",
+    111 => VoterAlreadyRevoked: "the voter has already been revoked",
+    112 => LateVoterRevocation: "the voter can't be revoked once the voting phase has ended",
+    113 => CipherNotInGroup: "a component of the cipher is not a quadratic residue modulo p",
+    114 => CredentialMissing: "the voter has a registered credential, the ballot must carry its encrypted credential",
+    115 => CredentialNotRegistered: "the ballot carries a credential but no credential has been registered for the voter",
+    116 => BallotAlreadyCast: "the voter has already cast a ballot",
+    117 => CredentialCheckPending: "the credentials of the ballots haven't been checked yet",
+    118 => CredentialCheckNotPending: "the credentials of the ballots of the vote aren't being checked",
+    119 => CredentialBlindingsIncomplete: "not all sealers have blinded the credential quotients yet",
+    120 => CredentialCheckAlreadySubmitted: "the sealer has already submitted its part of the credential check",
+    121 => CredentialBlindingProofError: "the blinded credential quotients or their proofs are invalid",
+    122 => CredentialDecryptionProofError: "the partial decryptions of the blinded credential quotients are invalid",
+    123 => AnonymousBallotSignatureError: "the anonymous ballot is not signed with the one-time key of the eligibility token",
+}
+
+impl ErrorCode {
+    /// Returns the numeric code of the error.
+    pub fn code(&self) -> u16 {
+        *self as u16
+    }
+
+    /// Returns the error with the numeric code.
+    pub fn from_code(code: u16) -> Option<ErrorCode> {
+        ErrorCode::ALL
+            .iter()
+            .find(|error| error.code() == code)
+            .copied()
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} (E{:03} {})",
+            self.message(),
+            self.code(),
+            self.name()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ErrorCode;
+
+    #[test]
+    fn test_error_codes_are_unique_and_ascending() {
+        let codes: Vec<u16> = ErrorCode::ALL.iter().map(|error| error.code()).collect();
+        assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(!codes.contains(&ErrorCode::Unknown.code()));
+    }
+
+    #[test]
+    fn test_error_code_round_trip() {
+        for error in ErrorCode::ALL.iter() {
+            assert_eq!(ErrorCode::from_code(error.code()), Some(*error));
+            assert_eq!(ErrorCode::from_name(error.name()), Some(*error));
+        }
+        assert_eq!(ErrorCode::from_code(0), None);
+        assert_eq!(ErrorCode::from_name("NotAnError"), None);
+    }
+
+    #[test]
+    fn test_error_code_display() {
+        assert_eq!(
+            ErrorCode::WrongVotePhase.to_string(),
+            "the vote is not in the required phase (E020 WrongVotePhase)"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Ballots relayed on behalf of a voter, e.g. ballots created on an offline device and
+//! transferred to a relayer by a QR code (paper channel).
+//!
+//! The voter signs the vote id and the SCALE encoded ballot (sr25519). A relayer submits the
+//! ballot together with the voter's public key and signature (see: `cast_relayed_ballot`),
+//! it can neither change the ballot nor submit it in the name of another voter.
+use alloc::vec::Vec;
+use codec::Encode;
+
+/// The context of the signature of a relayed ballot, separates it from other signatures of the voter.
+pub const RELAYED_BALLOT_CONTEXT: &[u8] = b"provotum-mixnet/relayed-ballot/v1";
+
+/// Returns the message the voter signs to relay the (SCALE encoded) ballot for the vote.
+pub fn relayed_ballot_message(vote_id: &[u8], encoded_ballot: &[u8]) -> Vec<u8> {
+    let mut message = (RELAYED_BALLOT_CONTEXT, vote_id).encode();
+    message.extend_from_slice(encoded_ballot);
+    message
+}
+
+#[cfg(feature = "std")]
+pub use compact::{CompactBallot, CompactBallotError, COMPACT_BALLOT_VERSION};
+
+#[cfg(feature = "std")]
+mod compact {
+    use serde::{Deserialize, Serialize};
+    use std::fmt;
+
+    /// The version of the compact encoding, the first element of the CBOR array.
+    pub const COMPACT_BALLOT_VERSION: u8 = 1;
+
+    /// The length of an sr25519 public key.
+    const PUBLIC_KEY_LENGTH: usize = 32;
+
+    /// The length of an sr25519 signature.
+    const SIGNATURE_LENGTH: usize = 64;
+
+    /// A ballot signed by the voter in its compact encoding, i.e., a CBOR array
+    /// [version, vote id, ballot, voter, signature] of byte strings encoded with base45
+    /// (the alphanumeric mode of QR codes).
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    pub struct CompactBallot {
+        pub vote_id: Vec<u8>,
+        /// the SCALE encoded ballot, including its proofs and validity proofs
+        pub ballot: Vec<u8>,
+        /// the sr25519 public key of the voter
+        pub voter: Vec<u8>,
+        /// the sr25519 signature of the relayed ballot message, see: `relayed_ballot_message`
+        pub signature: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    pub enum CompactBallotError {
+        /// the text is not valid base45
+        Base45,
+        /// the decoded bytes are not a valid CBOR array of the compact ballot
+        Cbor,
+        /// the ballot has been encoded with an unknown version
+        UnsupportedVersion(u8),
+        /// the voter is not an sr25519 public key
+        InvalidVoter,
+        /// the signature is not an sr25519 signature
+        InvalidSignature,
+    }
+
+    impl fmt::Display for CompactBallotError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                CompactBallotError::Base45 => {
+                    write!(f, "the compact ballot is not valid base45")
+                }
+                CompactBallotError::Cbor => {
+                    write!(f, "the compact ballot is not valid CBOR")
+                }
+                CompactBallotError::UnsupportedVersion(version) => {
+                    write!(f, "the compact ballot version {} is not supported", version)
+                }
+                CompactBallotError::InvalidVoter => {
+                    write!(f, "the voter of the compact ballot is not a public key")
+                }
+                CompactBallotError::InvalidSignature => {
+                    write!(f, "the signature of the compact ballot is malformed")
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for CompactBallotError {}
+
+    /// the CBOR representation, an array of the version and byte strings
+    #[derive(Serialize, Deserialize)]
+    struct Envelope(
+        u8,
+        #[serde(with = "serde_bytes")] Vec<u8>,
+        #[serde(with = "serde_bytes")] Vec<u8>,
+        #[serde(with = "serde_bytes")] Vec<u8>,
+        #[serde(with = "serde_bytes")] Vec<u8>,
+    );
+
+    impl CompactBallot {
+        /// Returns the base45 encoded CBOR array of the ballot.
+        pub fn to_base45(&self) -> String {
+            let envelope = Envelope(
+                COMPACT_BALLOT_VERSION,
+                self.vote_id.clone(),
+                self.ballot.clone(),
+                self.voter.clone(),
+                self.signature.clone(),
+            );
+            let bytes =
+                serde_cbor::to_vec(&envelope).expect("byte strings are serializable");
+            base45::encode(bytes)
+        }
+
+        /// Decodes a base45 encoded ballot. Checks the version and the lengths of the voter
+        /// and the signature, the ballot itself is decoded and verified by the pallet.
+        pub fn from_base45(text: &str) -> Result<CompactBallot, CompactBallotError> {
+            let bytes =
+                base45::decode(text.trim()).map_err(|_| CompactBallotError::Base45)?;
+            let Envelope(version, vote_id, ballot, voter, signature) =
+                serde_cbor::from_slice(&bytes).map_err(|_| CompactBallotError::Cbor)?;
+            if version != COMPACT_BALLOT_VERSION {
+                return Err(CompactBallotError::UnsupportedVersion(version));
+            }
+            if voter.len() != PUBLIC_KEY_LENGTH {
+                return Err(CompactBallotError::InvalidVoter);
+            }
+            if signature.len() != SIGNATURE_LENGTH {
+                return Err(CompactBallotError::InvalidSignature);
+            }
+            Ok(CompactBallot {
+                vote_id,
+                ballot,
+                voter,
+                signature,
+            })
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn compact_ballot() -> CompactBallot {
+        CompactBallot {
+            vote_id: b"20201212".to_vec(),
+            ballot: vec![4, 1, 2, 3, 255, 0],
+            voter: vec![7u8; 32],
+            signature: vec![9u8; 64],
+        }
+    }
+
+    #[test]
+    fn test_compact_ballot_round_trip() {
+        let ballot = compact_ballot();
+        let text = ballot.to_base45();
+
+        // base45 only uses the alphanumeric characters of QR codes
+        assert!(text
+            .chars()
+            .all(|c| "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:".contains(c)));
+        assert_eq!(CompactBallot::from_base45(&text), Ok(ballot));
+    }
+
+    #[test]
+    fn test_compact_ballot_rejects_malformed_input() {
+        assert_eq!(
+            CompactBallot::from_base45("not base45!"),
+            Err(CompactBallotError::Base45)
+        );
+        assert_eq!(
+            CompactBallot::from_base45(&base45::encode([1, 2, 3])),
+            Err(CompactBallotError::Cbor)
+        );
+
+        let mut ballot = compact_ballot();
+        ballot.signature.pop();
+        assert_eq!(
+            CompactBallot::from_base45(&ballot.to_base45()),
+            Err(CompactBallotError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_relayed_ballot_message_binds_the_vote() {
+        let message = relayed_ballot_message(b"20201212", &[1, 2, 3]);
+        assert!(message.ends_with(&[1, 2, 3]));
+        assert_ne!(message, relayed_ballot_message(b"20201213", &[1, 2, 3]));
+        // the vote id is length prefixed, moving bytes between the vote id and the ballot changes the message
+        assert_ne!(
+            message,
+            relayed_ballot_message(b"2020121", &[b'2', 1, 2, 3])
+        );
+    }
+}
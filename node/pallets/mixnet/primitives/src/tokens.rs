@@ -0,0 +1,115 @@
+//! Blind-signed eligibility tokens of anonymous ballots.
+//!
+//! The voter draws a one-time key pair (sr25519), the token is its public key and the nullifier
+//! a hash of the token. The voting authority checks the eligibility of the voter off-chain and
+//! blind-signs the token message of the nullifier (RSA-FDH, see: `crypto::blind`), i.e., without
+//! learning the nullifier. The voter signs the ballot with the one-time key and casts it with the
+//! token, the unblinded signature and the ballot signature (see: `cast_anonymous_ballot`).
+//! The pallet verifies both signatures and rejects spent nullifiers, i.e., whoever observes the
+//! token and its signature in the transaction pool can't cast another ballot with them.
+//! The ballot is neither linked to the voter's account nor to the signing.
+use alloc::vec::Vec;
+use codec::Encode;
+use crypto::hash::{HashFunction, Hasher};
+
+/// The context of the nullifier, separates it from other hashes of the token.
+pub const NULLIFIER_CONTEXT: &[u8] = b"provotum-mixnet/nullifier/v1";
+
+/// The context of the token message, separates it from other messages signed with the eligibility key.
+pub const ELIGIBILITY_TOKEN_CONTEXT: &[u8] = b"provotum-mixnet/eligibility-token/v1";
+
+/// The context of the signature of an anonymous ballot, separates it from other signatures of the one-time key.
+pub const ANONYMOUS_BALLOT_CONTEXT: &[u8] = b"provotum-mixnet/anonymous-ballot/v1";
+
+/// The nullifier of a token, spent once a ballot has been cast with it.
+pub type Nullifier = [u8; 32];
+
+/// Returns the nullifier of the token (the one-time public key): SHA-256(context || token).
+pub fn nullifier(token: &[u8]) -> Nullifier {
+    let digest = Hasher::new(HashFunction::Sha256)
+        .chain(NULLIFIER_CONTEXT)
+        .chain(token)
+        .finalize();
+    let mut nullifier = [0u8; 32];
+    nullifier.copy_from_slice(&digest);
+    nullifier
+}
+
+/// Returns the message the voting authority blind-signs to issue the token of the nullifier for the vote.
+pub fn eligibility_token_message(vote_id: &[u8], nullifier: &Nullifier) -> Vec<u8> {
+    (ELIGIBILITY_TOKEN_CONTEXT, vote_id, nullifier).encode()
+}
+
+/// Returns the message the voter signs with the one-time key of the nullifier to cast the
+/// (SCALE encoded) anonymous ballot for the vote.
+pub fn anonymous_ballot_message(
+    vote_id: &[u8],
+    nullifier: &Nullifier,
+    encoded_ballot: &[u8],
+) -> Vec<u8> {
+    let mut message = (ANONYMOUS_BALLOT_CONTEXT, vote_id, nullifier).encode();
+    message.extend_from_slice(encoded_ballot);
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wire::EligibilityKey;
+    use crypto::blind::{
+        BlindSignature, BlindSignaturePrivateKey, BlindSignaturePublicKey,
+    };
+    use num_bigint::BigUint;
+
+    #[test]
+    fn test_nullifier_is_bound_to_the_token() {
+        assert_eq!(nullifier(b"token"), nullifier(b"token"));
+        assert_ne!(nullifier(b"token"), nullifier(b"another token"));
+    }
+
+    #[test]
+    fn test_eligibility_token_message_binds_the_vote_and_the_nullifier() {
+        let message = eligibility_token_message(b"20201212", &[7u8; 32]);
+        assert!(message.starts_with(&ELIGIBILITY_TOKEN_CONTEXT.encode()));
+        assert_ne!(message, eligibility_token_message(b"20201213", &[7u8; 32]));
+        assert_ne!(message, eligibility_token_message(b"20201212", &[8u8; 32]));
+    }
+
+    #[test]
+    fn test_anonymous_ballot_message_binds_the_nullifier_and_the_ballot() {
+        let message = anonymous_ballot_message(b"20201212", &[7u8; 32], b"ballot");
+        assert!(message.starts_with(&ANONYMOUS_BALLOT_CONTEXT.encode()));
+        assert!(message.ends_with(b"ballot"));
+        assert_ne!(
+            message,
+            anonymous_ballot_message(b"20201212", &[8u8; 32], b"ballot")
+        );
+        assert_ne!(
+            message,
+            anonymous_ballot_message(b"20201212", &[7u8; 32], b"another ballot")
+        );
+    }
+
+    #[test]
+    fn test_blind_signed_token_verifies_with_the_eligibility_key() {
+        // textbook RSA: n = 61 * 53, e = 17, d = e^-1 mod 3120
+        let pk = BlindSignaturePublicKey {
+            n: BigUint::from(3233u32),
+            e: BigUint::from(17u32),
+        };
+        let sk = BlindSignaturePrivateKey {
+            n: BigUint::from(3233u32),
+            d: BigUint::from(2753u32),
+        };
+        let key: EligibilityKey = pk.clone().into();
+        let message = eligibility_token_message(b"20201212", &nullifier(b"token"));
+
+        let r = BigUint::from(7u32);
+        let blinded = BlindSignature::blind(&pk, &message, &r).unwrap();
+        let blind_signature = BlindSignature::sign_blinded(&sk, &blinded);
+        let signature = BlindSignature::unblind(&pk, &blind_signature, &r).unwrap();
+
+        let pk = BlindSignaturePublicKey::from(key);
+        assert!(BlindSignature::verify(&pk, &message, &signature));
+    }
+}
@@ -0,0 +1,572 @@
+//! The wire types of the mixnet pallet, i.e., the SCALE encodable representations of the
+//! types of the crypto crate (big integers as big-endian bytes), and their conversions.
+//! Shared by the pallet (re-exported from its `types` module) and its clients.
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use crypto::blind::BlindSignaturePublicKey;
+use crypto::proofs::{
+    decryption::DecryptionProof, encryption::EncryptionProof, key_switch::KeySwitchProof,
+    keygen::KeyGenerationProof, membership::MembershipProof,
+    pet::PlaintextEquivalenceProof,
+};
+use crypto::types::{
+    BigT, Cipher as BigCipher, ElGamalParams, PublicKey as ElGamalPK, ShuffleProofValues,
+};
+use num_bigint::BigUint;
+use num_traits::One;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// the BigCipher from the crypto crate.
+/// different types which the blockchain can handle.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct Cipher {
+    pub a: Vec<u8>,
+    pub b: Vec<u8>,
+}
+
+impl From<BigCipher> for Cipher {
+    fn from(source: BigCipher) -> Self {
+        Cipher {
+            a: source.a.to_bytes_be(),
+            b: source.b.to_bytes_be(),
+        }
+    }
+}
+
+impl From<Cipher> for BigCipher {
+    fn from(source: Cipher) -> Self {
+        BigCipher {
+            a: BigUint::from_bytes_be(&source.a),
+            b: BigUint::from_bytes_be(&source.b),
+        }
+    }
+}
+
+/// required to perform into() conversion for trait Vec
+/// for Vec<Cipher> is not allowed, since trait Vec is not defined here
+pub struct Wrapper<T>(pub Vec<T>);
+
+impl From<Wrapper<Cipher>> for Vec<BigCipher> {
+    fn from(source: Wrapper<Cipher>) -> Self {
+        source
+            .0
+            .into_iter()
+            .map(|v| v.into())
+            .collect::<Vec<BigCipher>>()
+    }
+}
+
+impl From<Wrapper<BigCipher>> for Vec<Cipher> {
+    fn from(source: Wrapper<BigCipher>) -> Self {
+        source
+            .0
+            .into_iter()
+            .map(|v| v.into())
+            .collect::<Vec<Cipher>>()
+    }
+}
+
+/// the PublicKey from the crypto crate.
+/// different types which the blockchain can handle.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct PublicKey {
+    pub params: PublicParameters,
+    pub h: Vec<u8>,
+}
+
+impl From<ElGamalPK> for PublicKey {
+    fn from(source: ElGamalPK) -> Self {
+        PublicKey {
+            params: source.params.into(),
+            h: source.h.to_bytes_be(),
+        }
+    }
+}
+
+impl From<PublicKey> for ElGamalPK {
+    fn from(source: PublicKey) -> Self {
+        ElGamalPK {
+            params: source.params.into(),
+            h: BigUint::from_bytes_be(&source.h),
+        }
+    }
+}
+
+/// the ElGamalParams from the crypto crate.
+/// different types which the blockchain can handle.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PublicParameters {
+    #[cfg_attr(feature = "std", serde(with = "impl_serde::serialize"))]
+    pub p: Vec<u8>,
+    // 1. public generator g
+    #[cfg_attr(feature = "std", serde(with = "impl_serde::serialize"))]
+    pub g: Vec<u8>,
+    // 2. public generator h
+    #[cfg_attr(feature = "std", serde(with = "impl_serde::serialize"))]
+    pub h: Vec<u8>,
+}
+
+/// defines the function q = (p - 1) / 2 with return type BigUint.
+/// implemented for PublicParameters (i.e. ElGamalParams from the crypto crate)
+pub trait QAsBigUint {
+    fn q(&self) -> BigUint;
+}
+
+impl QAsBigUint for PublicParameters {
+    fn q(&self) -> BigUint {
+        let p: BigUint = BigUint::from_bytes_be(&self.p);
+        let q: BigUint = (p - BigUint::one()) / BigUint::from(2u32);
+        q
+    }
+}
+
+/// defines the function q = (p - 1) / 2 with return type Vec<u8>
+/// implemented for PublicParameters (i.e. ElGamalParams from the crypto crate)
+pub trait QAsVecU8 {
+    fn q(&self) -> Vec<u8>;
+}
+
+impl QAsVecU8 for PublicParameters {
+    fn q(&self) -> Vec<u8> {
+        let p: BigUint = BigUint::from_bytes_be(&self.p);
+        let q: BigUint = (p - BigUint::one()) / BigUint::from(2u32);
+        q.to_bytes_be()
+    }
+}
+
+impl From<ElGamalParams> for PublicParameters {
+    fn from(source: ElGamalParams) -> Self {
+        PublicParameters {
+            p: source.p.to_bytes_be(),
+            g: source.g.to_bytes_be(),
+            h: source.h.to_bytes_be(),
+        }
+    }
+}
+
+impl From<PublicParameters> for ElGamalParams {
+    fn from(source: PublicParameters) -> Self {
+        ElGamalParams {
+            p: BigUint::from_bytes_be(&source.p),
+            g: BigUint::from_bytes_be(&source.g),
+            h: BigUint::from_bytes_be(&source.h),
+        }
+    }
+}
+
+/// Algorithm 8.47: The s value of the ShuffleProof
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct BigS {
+    pub s1: BigUint,               // s1
+    pub s2: BigUint,               // s2
+    pub s3: BigUint,               // s3
+    pub s4: BigUint,               // s4
+    pub vec_s_hat: Vec<BigUint>,   // vec_s_hat
+    pub vec_s_tilde: Vec<BigUint>, // vec_s_tilde
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct BigSAsBytes {
+    pub s1: Vec<u8>,               // s1
+    pub s2: Vec<u8>,               // s2
+    pub s3: Vec<u8>,               // s3
+    pub s4: Vec<u8>,               // s4
+    pub vec_s_hat: Vec<Vec<u8>>,   // vec_s_hat
+    pub vec_s_tilde: Vec<Vec<u8>>, // vec_s_tilde
+}
+
+impl From<BigSAsBytes> for BigS {
+    fn from(source: BigSAsBytes) -> Self {
+        BigS {
+            s1: BigUint::from_bytes_be(&source.s1),
+            s2: BigUint::from_bytes_be(&source.s2),
+            s3: BigUint::from_bytes_be(&source.s3),
+            s4: BigUint::from_bytes_be(&source.s4),
+            vec_s_hat: source
+                .vec_s_hat
+                .iter()
+                .map(|v| BigUint::from_bytes_be(v))
+                .collect::<Vec<BigUint>>(),
+            vec_s_tilde: source
+                .vec_s_tilde
+                .iter()
+                .map(|v| BigUint::from_bytes_be(v))
+                .collect::<Vec<BigUint>>(),
+        }
+    }
+}
+
+impl From<BigS> for BigSAsBytes {
+    fn from(source: BigS) -> Self {
+        BigSAsBytes {
+            s1: source.s1.to_bytes_be(),
+            s2: source.s2.to_bytes_be(),
+            s3: source.s3.to_bytes_be(),
+            s4: source.s4.to_bytes_be(),
+            vec_s_hat: source
+                .vec_s_hat
+                .into_iter()
+                .map(|v| v.to_bytes_be())
+                .collect::<Vec<Vec<u8>>>(),
+            vec_s_tilde: source
+                .vec_s_tilde
+                .into_iter()
+                .map(|v| v.to_bytes_be())
+                .collect::<Vec<Vec<u8>>>(),
+        }
+    }
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct BigTAsBytes {
+    pub t1: Vec<u8>,             // t1
+    pub t2: Vec<u8>,             // t2
+    pub t3: Vec<u8>,             // t3
+    pub t4_1: Vec<u8>,           // t4_1
+    pub t4_2: Vec<u8>,           // t4_2
+    pub vec_t_hat: Vec<Vec<u8>>, // vec_t_hat
+}
+
+impl From<BigTAsBytes> for BigT {
+    fn from(source: BigTAsBytes) -> Self {
+        (
+            BigUint::from_bytes_be(&source.t1),
+            BigUint::from_bytes_be(&source.t2),
+            BigUint::from_bytes_be(&source.t3),
+            BigUint::from_bytes_be(&source.t4_1),
+            BigUint::from_bytes_be(&source.t4_2),
+            source
+                .vec_t_hat
+                .iter()
+                .map(|v| BigUint::from_bytes_be(v))
+                .collect::<Vec<BigUint>>(),
+        )
+    }
+}
+
+impl From<BigT> for BigTAsBytes {
+    fn from(source: BigT) -> Self {
+        let (t1, t2, t3, t4_1, t4_2, vec_t_hat) = source;
+        BigTAsBytes {
+            t1: t1.to_bytes_be(),
+            t2: t2.to_bytes_be(),
+            t3: t3.to_bytes_be(),
+            t4_1: t4_1.to_bytes_be(),
+            t4_2: t4_2.to_bytes_be(),
+            vec_t_hat: vec_t_hat
+                .into_iter()
+                .map(|v| v.to_bytes_be())
+                .collect::<Vec<Vec<u8>>>(),
+        }
+    }
+}
+
+/// Algorithm 8.47: The ShuffleProof
+#[allow(non_snake_case)]
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct ShuffleProof {
+    pub challenge: BigUint,                          // challenge
+    pub S: BigS,                                     // S
+    pub permutation_commitments: Vec<BigUint>,       // permutation_commitments
+    pub permutation_chain_commitments: Vec<BigUint>, // permutation_chain_commitments
+    pub commitments: BigT, // public commitment t (required for batch verification)
+}
+
+#[allow(non_snake_case)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct ShuffleProofAsBytes {
+    pub challenge: Vec<u8>,                          // challenge
+    pub S: BigSAsBytes,                              // S
+    pub permutation_commitments: Vec<Vec<u8>>,       // permutation_commitments
+    pub permutation_chain_commitments: Vec<Vec<u8>>, // permutation_chain_commitments
+    pub commitments: BigTAsBytes, // public commitment t (required for batch verification)
+}
+
+impl From<ShuffleProofAsBytes> for ShuffleProof {
+    fn from(source: ShuffleProofAsBytes) -> Self {
+        ShuffleProof {
+            challenge: BigUint::from_bytes_be(&source.challenge),
+            S: source.S.into(),
+            permutation_commitments: source
+                .permutation_commitments
+                .iter()
+                .map(|v| BigUint::from_bytes_be(v))
+                .collect::<Vec<BigUint>>(),
+            permutation_chain_commitments: source
+                .permutation_chain_commitments
+                .iter()
+                .map(|v| BigUint::from_bytes_be(v))
+                .collect::<Vec<BigUint>>(),
+            commitments: source.commitments.into(),
+        }
+    }
+}
+
+/// the proof and the public commitment t generated by the prover of the crypto crate
+impl From<(ShuffleProofValues, BigT)> for ShuffleProof {
+    fn from((proof, commitments): (ShuffleProofValues, BigT)) -> Self {
+        ShuffleProof {
+            challenge: proof.challenge,
+            S: BigS {
+                s1: proof.s1,
+                s2: proof.s2,
+                s3: proof.s3,
+                s4: proof.s4,
+                vec_s_hat: proof.vec_s_hat,
+                vec_s_tilde: proof.vec_s_tilde,
+            },
+            permutation_commitments: proof.permutation_commitments,
+            permutation_chain_commitments: proof.permutation_chain_commitments,
+            commitments,
+        }
+    }
+}
+
+impl From<ShuffleProof> for ShuffleProofAsBytes {
+    fn from(source: ShuffleProof) -> Self {
+        ShuffleProofAsBytes {
+            challenge: source.challenge.to_bytes_be(),
+            S: source.S.into(),
+            permutation_commitments: source
+                .permutation_commitments
+                .into_iter()
+                .map(|v| v.to_bytes_be())
+                .collect::<Vec<Vec<u8>>>(),
+            permutation_chain_commitments: source
+                .permutation_chain_commitments
+                .into_iter()
+                .map(|v| v.to_bytes_be())
+                .collect::<Vec<Vec<u8>>>(),
+            commitments: source.commitments.into(),
+        }
+    }
+}
+
+/// the proof of knowledge of the randomness of a cipher, bound to the voter's account id.
+/// prevents that a voter submits a (re-randomized) copy of another voter's cipher.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct BallotProof {
+    pub challenge: Vec<u8>,
+    pub response: Vec<u8>,
+}
+
+impl From<EncryptionProof> for BallotProof {
+    fn from(source: EncryptionProof) -> Self {
+        BallotProof {
+            challenge: source.challenge.to_bytes_be(),
+            response: source.response.to_bytes_be(),
+        }
+    }
+}
+
+impl From<BallotProof> for EncryptionProof {
+    fn from(source: BallotProof) -> Self {
+        EncryptionProof {
+            challenge: BigUint::from_bytes_be(&source.challenge),
+            response: BigUint::from_bytes_be(&source.response),
+        }
+    }
+}
+
+/// the proof that an answer is one of the valid answers of its question (see: `Topic::valid_answers`),
+/// bound to the voter's account id.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct ValidityProof {
+    pub challenges: Vec<Vec<u8>>,
+    pub responses: Vec<Vec<u8>>,
+}
+
+impl From<MembershipProof> for ValidityProof {
+    fn from(source: MembershipProof) -> Self {
+        ValidityProof {
+            challenges: source.challenges.iter().map(|c| c.to_bytes_be()).collect(),
+            responses: source.responses.iter().map(|s| s.to_bytes_be()).collect(),
+        }
+    }
+}
+
+impl From<ValidityProof> for MembershipProof {
+    fn from(source: ValidityProof) -> Self {
+        MembershipProof {
+            challenges: source
+                .challenges
+                .iter()
+                .map(|c| BigUint::from_bytes_be(c))
+                .collect(),
+            responses: source
+                .responses
+                .iter()
+                .map(|s| BigUint::from_bytes_be(s))
+                .collect(),
+        }
+    }
+}
+
+// the public key generation proof submitted by the sealer -> this prooves knowledge of a secret key that belongs to the submitted public key
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct PublicKeyShareProof {
+    pub challenge: Vec<u8>,
+    pub response: Vec<u8>,
+}
+
+impl From<KeyGenerationProof> for PublicKeyShareProof {
+    fn from(source: KeyGenerationProof) -> Self {
+        PublicKeyShareProof {
+            challenge: source.challenge.to_bytes_be(),
+            response: source.response.to_bytes_be(),
+        }
+    }
+}
+
+impl From<PublicKeyShareProof> for KeyGenerationProof {
+    fn from(source: PublicKeyShareProof) -> Self {
+        KeyGenerationProof {
+            challenge: BigUint::from_bytes_be(&source.challenge),
+            response: BigUint::from_bytes_be(&source.response),
+        }
+    }
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct DecryptedShareProof {
+    pub challenge: Vec<u8>,
+    pub response: Vec<u8>,
+}
+
+impl From<DecryptionProof> for DecryptedShareProof {
+    fn from(source: DecryptionProof) -> Self {
+        DecryptedShareProof {
+            challenge: source.challenge.to_bytes_be(),
+            response: source.response.to_bytes_be(),
+        }
+    }
+}
+
+impl From<DecryptedShareProof> for DecryptionProof {
+    fn from(source: DecryptedShareProof) -> Self {
+        DecryptionProof {
+            challenge: BigUint::from_bytes_be(&source.challenge),
+            response: BigUint::from_bytes_be(&source.response),
+        }
+    }
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct CredentialBlindingProof {
+    pub challenge: Vec<u8>,
+    pub response: Vec<u8>,
+}
+
+impl From<PlaintextEquivalenceProof> for CredentialBlindingProof {
+    fn from(source: PlaintextEquivalenceProof) -> Self {
+        CredentialBlindingProof {
+            challenge: source.challenge.to_bytes_be(),
+            response: source.response.to_bytes_be(),
+        }
+    }
+}
+
+impl From<CredentialBlindingProof> for PlaintextEquivalenceProof {
+    fn from(source: CredentialBlindingProof) -> Self {
+        PlaintextEquivalenceProof {
+            challenge: BigUint::from_bytes_be(&source.challenge),
+            response: BigUint::from_bytes_be(&source.response),
+        }
+    }
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct KeySwitchShareProof {
+    pub challenge: Vec<u8>,
+    pub response: Vec<u8>,
+    pub responses: Vec<Vec<u8>>,
+}
+
+impl From<KeySwitchProof> for KeySwitchShareProof {
+    fn from(source: KeySwitchProof) -> Self {
+        KeySwitchShareProof {
+            challenge: source.challenge.to_bytes_be(),
+            response: source.response.to_bytes_be(),
+            responses: source
+                .responses
+                .into_iter()
+                .map(|v| v.to_bytes_be())
+                .collect::<Vec<Vec<u8>>>(),
+        }
+    }
+}
+
+impl From<KeySwitchShareProof> for KeySwitchProof {
+    fn from(source: KeySwitchShareProof) -> Self {
+        KeySwitchProof {
+            challenge: BigUint::from_bytes_be(&source.challenge),
+            response: BigUint::from_bytes_be(&source.response),
+            responses: source
+                .responses
+                .iter()
+                .map(|v| BigUint::from_bytes_be(v))
+                .collect::<Vec<BigUint>>(),
+        }
+    }
+}
+
+/// the public key of the voting authority which blind-signs the eligibility tokens of a vote,
+/// i.e., the RSA modulus n and the public exponent e (see: `tokens`).
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct EligibilityKey {
+    pub n: Vec<u8>,
+    pub e: Vec<u8>,
+}
+
+impl From<BlindSignaturePublicKey> for EligibilityKey {
+    fn from(source: BlindSignaturePublicKey) -> Self {
+        EligibilityKey {
+            n: source.n.to_bytes_be(),
+            e: source.e.to_bytes_be(),
+        }
+    }
+}
+
+impl From<EligibilityKey> for BlindSignaturePublicKey {
+    fn from(source: EligibilityKey) -> Self {
+        BlindSignaturePublicKey {
+            n: BigUint::from_bytes_be(&source.n),
+            e: BigUint::from_bytes_be(&source.e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto::{encryption::ElGamal, helper::Helper};
+
+    #[test]
+    fn test_cipher_round_trip() {
+        let (_, _, pk) = Helper::setup_sm_system();
+        let big_cipher =
+            ElGamal::encrypt_encode(&BigUint::from(3u32), &BigUint::from(7u32), &pk);
+
+        let cipher: Cipher = big_cipher.clone().into();
+        let decoded = Cipher::decode(&mut &cipher.encode()[..]).unwrap();
+        assert_eq!(decoded, cipher);
+        assert_eq!(BigCipher::from(decoded), big_cipher);
+
+        let ciphers: Vec<Cipher> = Wrapper(vec![big_cipher.clone()]).into();
+        let big_ciphers: Vec<BigCipher> = Wrapper(ciphers).into();
+        assert_eq!(big_ciphers, vec![big_cipher]);
+    }
+
+    #[test]
+    fn test_public_key_round_trip() {
+        let (params, _, pk) = Helper::setup_md_system();
+
+        let public_key: PublicKey = pk.clone().into();
+        assert_eq!(ElGamalPK::from(public_key.clone()), pk);
+        assert_eq!(QAsBigUint::q(&public_key.params), params.q());
+        assert_eq!(QAsVecU8::q(&public_key.params), params.q().to_bytes_be());
+    }
+}
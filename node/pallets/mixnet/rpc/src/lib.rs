@@ -0,0 +1,378 @@
+//! RPC interface for the mixnet pallet.
+//! Exposes the election state (vote phase, results and shuffle progress)
+//! with typed responses, so clients don't have to decode raw storage.
+//! The cipher proofs can be verified by light clients, see: `provotum-wasm`.
+//! Web clients use the versioned interface, see: `provotum`.
+
+use codec::{Codec, Decode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use num_bigint::BigUint;
+use pallet_mixnet::types::{
+    Ballot, BallotReceipt as SubstrateBallotReceipt, CipherMerkleProof, NrOfShuffles,
+    ShuffleState, TopicResult, VotePhase as SubstrateVotePhase,
+};
+use pallet_mixnet::ErrorCode as MixnetErrorCode;
+pub use pallet_mixnet_runtime_api::MixnetApi as MixnetRuntimeApi;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, UniqueSaturatedInto},
+};
+use std::{collections::BTreeMap, fmt::Debug, marker::PhantomData, sync::Arc};
+
+pub mod provotum;
+
+/// error code returned if the runtime api call fails
+const RUNTIME_ERROR: i64 = 1;
+
+/// error code returned if the requested block is unknown to the node
+const UNKNOWN_BLOCK: i64 = 2;
+
+/// error code returned if the ballot can't be decoded
+const INVALID_BALLOT: i64 = 3;
+
+/// The phase of a vote.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum VotePhase {
+    KeyGeneration,
+    Voting,
+    Tallying,
+    Cancelled,
+}
+
+impl From<SubstrateVotePhase> for VotePhase {
+    fn from(phase: SubstrateVotePhase) -> Self {
+        match phase {
+            SubstrateVotePhase::KeyGeneration => VotePhase::KeyGeneration,
+            SubstrateVotePhase::Voting => VotePhase::Voting,
+            SubstrateVotePhase::Tallying => VotePhase::Tallying,
+            SubstrateVotePhase::Cancelled => VotePhase::Cancelled,
+        }
+    }
+}
+
+/// The progress of the shuffle operations of a topic.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ShuffleProgress {
+    pub iteration: u8,
+    pub start_position: u64,
+    pub batch_size: u64,
+    pub done: bool,
+}
+
+impl From<ShuffleState> for ShuffleProgress {
+    fn from(state: ShuffleState) -> Self {
+        ShuffleProgress {
+            iteration: state.iteration,
+            start_position: state.start_position,
+            batch_size: state.batch_size,
+            done: state.done,
+        }
+    }
+}
+
+/// The receipt of a ballot, allows a voter to check that the ballot was recorded.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BallotReceipt<BlockHash> {
+    pub block_number: u64,
+    pub block_hash: BlockHash,
+    /// hex encoded blake2 hash of the ciphers of the ballot
+    pub cipher_hash: String,
+    pub event_index: u32,
+}
+
+impl<BlockNumber, BlockHash> From<SubstrateBallotReceipt<BlockNumber, BlockHash>>
+    for BallotReceipt<BlockHash>
+where
+    BlockNumber: UniqueSaturatedInto<u64>,
+{
+    fn from(receipt: SubstrateBallotReceipt<BlockNumber, BlockHash>) -> Self {
+        BallotReceipt {
+            block_number: receipt.block_number.unique_saturated_into(),
+            block_hash: receipt.block_hash,
+            cipher_hash: to_hex(&receipt.cipher_hash),
+            event_index: receipt.event_index,
+        }
+    }
+}
+
+/// The merkle inclusion proof of a cipher, verifies against the root in the `CipherRoots` storage.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CipherProof {
+    /// hex encoded blake2 hash of the cipher
+    pub leaf: String,
+    pub index: u64,
+    pub nr_of_leaves: u64,
+    /// hex encoded sibling hashes from the leaf up to the root
+    pub path: Vec<String>,
+}
+
+impl From<CipherMerkleProof> for CipherProof {
+    fn from(proof: CipherMerkleProof) -> Self {
+        CipherProof {
+            leaf: to_hex(&proof.leaf),
+            index: proof.index,
+            nr_of_leaves: proof.nr_of_leaves,
+            path: proof.path.iter().map(|node| to_hex(node)).collect(),
+        }
+    }
+}
+
+/// The error a ballot would be rejected with, see: `mixnet_primitives::ErrorCode`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct BallotError {
+    pub code: u16,
+    pub name: String,
+    pub message: String,
+}
+
+impl From<u16> for BallotError {
+    fn from(code: u16) -> Self {
+        let error = MixnetErrorCode::from_code(code).unwrap_or(MixnetErrorCode::Unknown);
+        BallotError {
+            code,
+            name: error.name().into(),
+            message: error.message().into(),
+        }
+    }
+}
+
+/// The result of a topic. Maps each voting option to its count (both as decimal strings).
+pub type ElectionResult = BTreeMap<String, String>;
+
+#[rpc]
+pub trait MixnetApi<BlockHash, AccountId> {
+    /// Returns the current phase of the vote.
+    #[rpc(name = "mixnet_getVotePhase")]
+    fn get_vote_phase(
+        &self,
+        vote_id: String,
+        at: Option<BlockHash>,
+    ) -> Result<Option<VotePhase>>;
+
+    /// Returns the result of the topic of the vote, if it has already been tallied.
+    #[rpc(name = "mixnet_getResult")]
+    fn get_result(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        at: Option<BlockHash>,
+    ) -> Result<Option<ElectionResult>>;
+
+    /// Returns the shuffle progress of the topic.
+    #[rpc(name = "mixnet_getShuffleProgress")]
+    fn get_shuffle_progress(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        at: Option<BlockHash>,
+    ) -> Result<Option<ShuffleProgress>>;
+
+    /// Returns the receipt of the voter's ballot.
+    #[rpc(name = "mixnet_getBallotReceipt")]
+    fn get_ballot_receipt(
+        &self,
+        vote_id: String,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BallotReceipt<BlockHash>>>;
+
+    /// Returns the merkle inclusion proof of the cipher at position index
+    /// for the topic of the vote and shuffle iteration.
+    #[rpc(name = "mixnet_getCipherProof")]
+    fn get_cipher_proof(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        nr_of_shuffles: NrOfShuffles,
+        index: u64,
+        at: Option<BlockHash>,
+    ) -> Result<Option<CipherProof>>;
+
+    /// Runs all checks of `cast_ballot` for the SCALE encoded ballot of the voter
+    /// without submitting it. Returns the error the ballot would be rejected with,
+    /// null if the ballot is valid.
+    #[rpc(name = "mixnet_validateBallot")]
+    fn validate_ballot(
+        &self,
+        vote_id: String,
+        account: AccountId,
+        ballot: Bytes,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BallotError>>;
+}
+
+/// A struct that implements the `MixnetApi`.
+pub struct Mixnet<C, B> {
+    client: Arc<C>,
+    _marker: PhantomData<B>,
+}
+
+impl<C, B> Mixnet<C, B> {
+    /// Create new `Mixnet` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Mixnet {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+fn runtime_error<E: Debug>(error: E) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(RUNTIME_ERROR),
+        message: "Unable to query the mixnet runtime api.".into(),
+        data: Some(format!("{:?}", error).into()),
+    }
+}
+
+fn unknown_block<E: Debug>(block: E) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(UNKNOWN_BLOCK),
+        message: "The block is unknown to the node.".into(),
+        data: Some(format!("{:?}", block).into()),
+    }
+}
+
+fn invalid_ballot<E: Debug>(error: E) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(INVALID_BALLOT),
+        message: "The ballot can't be decoded.".into(),
+        data: Some(format!("{:?}", error).into()),
+    }
+}
+
+/// encodes the bytes as 0x prefixed hex string
+fn to_hex(bytes: &[u8]) -> String {
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("0x{}", hex)
+}
+
+fn to_election_result(result: TopicResult) -> ElectionResult {
+    result
+        .iter()
+        .map(|(option, count)| {
+            (
+                BigUint::from_bytes_be(option).to_string(),
+                BigUint::from_bytes_be(count).to_string(),
+            )
+        })
+        .collect()
+}
+
+impl<C, Block, AccountId> MixnetApi<<Block as BlockT>::Hash, AccountId>
+    for Mixnet<C, Block>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<Block>,
+    C: HeaderBackend<Block>,
+    C::Api: MixnetRuntimeApi<Block, AccountId>,
+{
+    fn get_vote_phase(
+        &self,
+        vote_id: String,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<VotePhase>> {
+        let api = self.client.runtime_api();
+        // use the best block if no block hash is provided
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let phase = api
+            .get_vote_phase(&at, vote_id.into_bytes())
+            .map_err(runtime_error)?;
+        Ok(phase.map(Into::into))
+    }
+
+    fn get_result(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<ElectionResult>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let result = api
+            .get_result(&at, vote_id.into_bytes(), topic_id.into_bytes())
+            .map_err(runtime_error)?;
+        Ok(result.map(to_election_result))
+    }
+
+    fn get_shuffle_progress(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<ShuffleProgress>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let state = api
+            .get_shuffle_progress(&at, vote_id.into_bytes(), topic_id.into_bytes())
+            .map_err(runtime_error)?;
+        Ok(state.map(Into::into))
+    }
+
+    fn get_ballot_receipt(
+        &self,
+        vote_id: String,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<BallotReceipt<<Block as BlockT>::Hash>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let receipt = api
+            .get_ballot_receipt(&at, vote_id.into_bytes(), account)
+            .map_err(runtime_error)?;
+        Ok(receipt.map(Into::into))
+    }
+    fn get_cipher_proof(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        nr_of_shuffles: NrOfShuffles,
+        index: u64,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<CipherProof>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let proof = api
+            .get_cipher_proof(
+                &at,
+                vote_id.into_bytes(),
+                topic_id.into_bytes(),
+                nr_of_shuffles,
+                index,
+            )
+            .map_err(runtime_error)?;
+        Ok(proof.map(Into::into))
+    }
+
+    fn validate_ballot(
+        &self,
+        vote_id: String,
+        account: AccountId,
+        ballot: Bytes,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<BallotError>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let ballot = Ballot::decode(&mut &ballot[..]).map_err(invalid_ballot)?;
+
+        let validation = api
+            .validate_ballot(&at, vote_id.into_bytes(), account, ballot)
+            .map_err(runtime_error)?;
+        Ok(validation.err().map(Into::into))
+    }
+}
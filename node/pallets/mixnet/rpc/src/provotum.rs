@@ -0,0 +1,481 @@
+//! Versioned RPC interface for web clients.
+//! All big integers, hashes and account ids are returned as 0x prefixed hex strings
+//! and all texts as (lossy) UTF-8 strings, clients don't need to know SCALE.
+//! Every response carries the version of the interface, a breaking change increments it.
+
+use crate::{runtime_error, to_hex, unknown_block, MixnetRuntimeApi, VotePhase};
+use codec::{Codec, Encode};
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use num_bigint::BigUint;
+use pallet_mixnet::types::{
+    Cipher, CipherPage as SubstrateCipherPage, NrOfShuffles,
+    QuestionType as SubstrateQuestionType,
+    RankedTallyMethod as SubstrateRankedTallyMethod,
+    TallyStrategy as SubstrateTallyStrategy, Topic, TopicResult, VoteDetails,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, UniqueSaturatedInto},
+};
+use std::{marker::PhantomData, sync::Arc};
+
+/// the version of the interface, included in every response
+pub const RPC_VERSION: u32 = 2;
+
+/// the number of ciphers returned if the client doesn't specify a limit
+pub const DEFAULT_PAGE_SIZE: u64 = 100;
+
+/// the maximal number of ciphers returned per request, larger limits are capped
+pub const MAX_PAGE_SIZE: u64 = 1000;
+
+/// A response of the versioned interface.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Versioned<T> {
+    pub version: u32,
+    pub data: T,
+}
+
+impl<T> Versioned<T> {
+    fn new(data: T) -> Self {
+        Versioned {
+            version: RPC_VERSION,
+            data,
+        }
+    }
+}
+
+/// A block of the chain, either by its hash or by its number (of the best chain).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(untagged)]
+pub enum BlockRef<BlockHash> {
+    Number(u64),
+    Hash(BlockHash),
+}
+
+/// The response of a historical query: the data as of the block it has been read at.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AtBlock<BlockHash, T> {
+    pub block_number: u64,
+    pub block_hash: BlockHash,
+    pub data: T,
+}
+
+/// An ElGamal cipher (a, b).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct HexCipher {
+    pub a: String,
+    pub b: String,
+}
+
+impl From<&Cipher> for HexCipher {
+    fn from(cipher: &Cipher) -> Self {
+        HexCipher {
+            a: to_hex_biguint(&cipher.a),
+            b: to_hex_biguint(&cipher.b),
+        }
+    }
+}
+
+/// A page of the ciphers of a topic and shuffle iteration.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CipherPage {
+    /// the number of ciphers of the topic and shuffle iteration
+    pub total: u64,
+    pub offset: u64,
+    /// the limit applied, at most `MAX_PAGE_SIZE`
+    pub limit: u64,
+    pub ciphers: Vec<HexCipher>,
+}
+
+/// The tally strategy of a vote.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum TallyStrategy {
+    Mixnet,
+    Homomorphic,
+}
+
+impl From<SubstrateTallyStrategy> for TallyStrategy {
+    fn from(strategy: SubstrateTallyStrategy) -> Self {
+        match strategy {
+            SubstrateTallyStrategy::Mixnet => TallyStrategy::Mixnet,
+            SubstrateTallyStrategy::Homomorphic => TallyStrategy::Homomorphic,
+        }
+    }
+}
+
+/// The type of a question.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum QuestionType {
+    SingleChoice,
+    MultiSelect { max: u32 },
+    Ranked { candidates: u32 },
+}
+
+impl From<SubstrateQuestionType> for QuestionType {
+    fn from(question_type: SubstrateQuestionType) -> Self {
+        match question_type {
+            SubstrateQuestionType::SingleChoice => QuestionType::SingleChoice,
+            SubstrateQuestionType::MultiSelect { max } => {
+                QuestionType::MultiSelect { max }
+            }
+            SubstrateQuestionType::Ranked { candidates } => {
+                QuestionType::Ranked { candidates }
+            }
+        }
+    }
+}
+
+/// The tally method of a ranked question.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum RankedTallyMethod {
+    FirstPreference,
+    Borda,
+    InstantRunoff,
+}
+
+impl From<SubstrateRankedTallyMethod> for RankedTallyMethod {
+    fn from(method: SubstrateRankedTallyMethod) -> Self {
+        match method {
+            SubstrateRankedTallyMethod::FirstPreference => {
+                RankedTallyMethod::FirstPreference
+            }
+            SubstrateRankedTallyMethod::Borda => RankedTallyMethod::Borda,
+            SubstrateRankedTallyMethod::InstantRunoff => RankedTallyMethod::InstantRunoff,
+        }
+    }
+}
+
+/// A question of a vote.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Question {
+    pub id: String,
+    pub question: String,
+    pub question_type: QuestionType,
+    pub candidates: Vec<String>,
+    pub ranked_tally: RankedTallyMethod,
+}
+
+impl From<Topic> for Question {
+    fn from(topic: Topic) -> Self {
+        Question {
+            id: to_text(&topic.id),
+            question: to_text(&topic.question),
+            question_type: topic.question_type.into(),
+            candidates: topic.candidates.iter().map(|name| to_text(name)).collect(),
+            ranked_tally: topic.ranked_tally.into(),
+        }
+    }
+}
+
+/// The public parameters (p, g, h) of a vote.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Parameters {
+    pub p: String,
+    pub g: String,
+    pub h: String,
+}
+
+/// A vote with its questions and its public key.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Vote {
+    pub id: String,
+    pub title: String,
+    /// the SCALE encoded account id of the voting authority (hex)
+    pub voting_authority: String,
+    pub phase: VotePhase,
+    pub tally_strategy: TallyStrategy,
+    pub nr_of_shuffles: NrOfShuffles,
+    pub params: Parameters,
+    /// the public key (value h) of the vote, None until the key shares have been combined
+    pub public_key: Option<String>,
+    pub questions: Vec<Question>,
+}
+
+/// The count of a voting option.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ResultEntry {
+    pub option: String,
+    pub count: String,
+}
+
+#[rpc]
+pub trait ProvotumApi<BlockHash> {
+    /// Returns a page of the ciphers of the topic of the vote and shuffle iteration.
+    /// The page starts at offset (default: 0) and contains at most limit ciphers
+    /// (default: `DEFAULT_PAGE_SIZE`, at most: `MAX_PAGE_SIZE`).
+    #[rpc(name = "provotum_getCiphers")]
+    fn get_ciphers(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        nr_of_shuffles: NrOfShuffles,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        at: Option<BlockHash>,
+    ) -> Result<Versioned<CipherPage>>;
+
+    /// Returns the vote with its questions and its public key.
+    #[rpc(name = "provotum_getVote")]
+    fn get_vote(
+        &self,
+        vote_id: String,
+        at: Option<BlockHash>,
+    ) -> Result<Versioned<Option<Vote>>>;
+
+    /// Returns the counts of all voting options of the topic of the vote, if it has already been tallied.
+    #[rpc(name = "provotum_getResult")]
+    fn get_result(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        at: Option<BlockHash>,
+    ) -> Result<Versioned<Option<Vec<ResultEntry>>>>;
+
+    /// Returns a page of the ciphers of the topic of the vote and shuffle iteration as of the block,
+    /// i.e. the ciphers a shuffle proof submitted in the next block has been verified against.
+    /// The block is referenced by its hash or number (default: best block).
+    /// Blocks whose state has been pruned can only be queried on an archive node.
+    #[rpc(name = "provotum_getCiphersAt")]
+    fn get_ciphers_at(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        nr_of_shuffles: NrOfShuffles,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        at: Option<BlockRef<BlockHash>>,
+    ) -> Result<Versioned<AtBlock<BlockHash, CipherPage>>>;
+
+    /// Returns the counts of all voting options of the topic of the vote as of the block,
+    /// referenced by its hash or number (default: best block).
+    #[rpc(name = "provotum_getResultAt")]
+    fn get_result_at(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        at: Option<BlockRef<BlockHash>>,
+    ) -> Result<Versioned<AtBlock<BlockHash, Option<Vec<ResultEntry>>>>>;
+}
+
+/// A struct that implements the `ProvotumApi`.
+pub struct Provotum<C, B, AccountId> {
+    client: Arc<C>,
+    _marker: PhantomData<(B, AccountId)>,
+}
+
+impl<C, B, AccountId> Provotum<C, B, AccountId> {
+    /// Create new `Provotum` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Provotum {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, AccountId> Provotum<C, Block, AccountId>
+where
+    Block: BlockT,
+    C: HeaderBackend<Block>,
+{
+    /// returns the number and the hash of the referenced block (default: best block)
+    fn resolve_block(
+        &self,
+        at: Option<BlockRef<<Block as BlockT>::Hash>>,
+    ) -> Result<(u64, <Block as BlockT>::Hash)> {
+        match at {
+            None => {
+                let info = self.client.info();
+                Ok((info.best_number.unique_saturated_into(), info.best_hash))
+            }
+            Some(BlockRef::Number(number)) => {
+                let hash = self
+                    .client
+                    .hash(number.unique_saturated_into())
+                    .map_err(runtime_error)?
+                    .ok_or_else(|| unknown_block(number))?;
+                Ok((number, hash))
+            }
+            Some(BlockRef::Hash(hash)) => {
+                let number = self
+                    .client
+                    .number(hash)
+                    .map_err(runtime_error)?
+                    .ok_or_else(|| unknown_block(hash))?;
+                Ok((number.unique_saturated_into(), hash))
+            }
+        }
+    }
+}
+
+/// encodes a big-endian big integer as 0x prefixed hex string (minimal bytes)
+fn to_hex_biguint(bytes: &[u8]) -> String {
+    to_hex(&BigUint::from_bytes_be(bytes).to_bytes_be())
+}
+
+fn to_text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+fn to_cipher_page(page: SubstrateCipherPage, offset: u64, limit: u64) -> CipherPage {
+    CipherPage {
+        total: page.total,
+        offset,
+        limit,
+        ciphers: page.ciphers.iter().map(Into::into).collect(),
+    }
+}
+
+fn to_vote<AccountId: Encode>(vote_id: String, details: VoteDetails<AccountId>) -> Vote {
+    let VoteDetails {
+        vote,
+        topics,
+        public_key,
+    } = details;
+    Vote {
+        id: vote_id,
+        title: to_text(&vote.title),
+        voting_authority: to_hex(&vote.voting_authority.encode()),
+        phase: vote.phase.into(),
+        tally_strategy: vote.tally_strategy.into(),
+        nr_of_shuffles: vote.nr_of_shuffles,
+        params: Parameters {
+            p: to_hex_biguint(&vote.params.p),
+            g: to_hex_biguint(&vote.params.g),
+            h: to_hex_biguint(&vote.params.h),
+        },
+        public_key: public_key.map(|pk| to_hex_biguint(&pk.h)),
+        questions: topics.into_iter().map(Into::into).collect(),
+    }
+}
+
+fn to_result_entries(result: TopicResult) -> Vec<ResultEntry> {
+    result
+        .iter()
+        .map(|(option, count)| ResultEntry {
+            option: to_hex_biguint(option),
+            count: to_hex_biguint(count),
+        })
+        .collect()
+}
+
+impl<C, Block, AccountId> ProvotumApi<<Block as BlockT>::Hash>
+    for Provotum<C, Block, AccountId>
+where
+    Block: BlockT,
+    AccountId: Codec + Send + Sync + 'static,
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<Block>,
+    C: HeaderBackend<Block>,
+    C::Api: MixnetRuntimeApi<Block, AccountId>,
+{
+    fn get_ciphers(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        nr_of_shuffles: NrOfShuffles,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Versioned<CipherPage>> {
+        let api = self.client.runtime_api();
+        // use the best block if no block hash is provided
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let offset = offset.unwrap_or(0);
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+
+        let page = api
+            .get_ciphers(
+                &at,
+                vote_id.into_bytes(),
+                topic_id.into_bytes(),
+                nr_of_shuffles,
+                offset,
+                limit,
+            )
+            .map_err(runtime_error)?;
+        Ok(Versioned::new(to_cipher_page(page, offset, limit)))
+    }
+
+    fn get_vote(
+        &self,
+        vote_id: String,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Versioned<Option<Vote>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let details = api
+            .get_vote(&at, vote_id.clone().into_bytes())
+            .map_err(runtime_error)?;
+        Ok(Versioned::new(
+            details.map(|details| to_vote(vote_id, details)),
+        ))
+    }
+
+    fn get_result(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Versioned<Option<Vec<ResultEntry>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let result = api
+            .get_result(&at, vote_id.into_bytes(), topic_id.into_bytes())
+            .map_err(runtime_error)?;
+        Ok(Versioned::new(result.map(to_result_entries)))
+    }
+
+    fn get_ciphers_at(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        nr_of_shuffles: NrOfShuffles,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        at: Option<BlockRef<<Block as BlockT>::Hash>>,
+    ) -> Result<Versioned<AtBlock<<Block as BlockT>::Hash, CipherPage>>> {
+        let (block_number, block_hash) = self.resolve_block(at)?;
+        let Versioned { data, .. } = self.get_ciphers(
+            vote_id,
+            topic_id,
+            nr_of_shuffles,
+            offset,
+            limit,
+            Some(block_hash),
+        )?;
+        Ok(Versioned::new(AtBlock {
+            block_number,
+            block_hash,
+            data,
+        }))
+    }
+
+    fn get_result_at(
+        &self,
+        vote_id: String,
+        topic_id: String,
+        at: Option<BlockRef<<Block as BlockT>::Hash>>,
+    ) -> Result<Versioned<AtBlock<<Block as BlockT>::Hash, Option<Vec<ResultEntry>>>>>
+    {
+        let (block_number, block_hash) = self.resolve_block(at)?;
+        let Versioned { data, .. } =
+            self.get_result(vote_id, topic_id, Some(block_hash))?;
+        Ok(Versioned::new(AtBlock {
+            block_number,
+            block_hash,
+            data,
+        }))
+    }
+}
@@ -0,0 +1,92 @@
+//! Runtime API definition for the mixnet pallet.
+//! Allows clients to query the election state and to retrieve the stored ciphers.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_mixnet::types::{
+    Ballot, BallotReceipt, ChunkIndex, Cipher, CipherMerkleProof, CipherPage,
+    NrOfShuffles, ShuffleState, TopicId, TopicProgress, TopicResult, VoteDetails, VoteId,
+    VotePhase,
+};
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait MixnetApi<AccountId> where AccountId: Codec {
+        /// Returns the number of cipher chunks for a topic of a vote and shuffle iteration.
+        fn get_cipher_chunk_count(
+            vote_id: VoteId,
+            topic_id: TopicId,
+            nr_of_shuffles: NrOfShuffles,
+        ) -> ChunkIndex;
+
+        /// Returns a single chunk of ciphers for a topic of a vote and shuffle iteration.
+        fn get_cipher_chunk(
+            vote_id: VoteId,
+            topic_id: TopicId,
+            nr_of_shuffles: NrOfShuffles,
+            chunk_index: ChunkIndex,
+        ) -> Vec<Cipher>;
+
+        /// Returns the ciphers in the range [offset, offset + limit) for a topic of a vote
+        /// and shuffle iteration together with the total number of ciphers.
+        fn get_ciphers(
+            vote_id: VoteId,
+            topic_id: TopicId,
+            nr_of_shuffles: NrOfShuffles,
+            offset: u64,
+            limit: u64,
+        ) -> CipherPage;
+
+        /// Returns the merkle inclusion proof of the cipher at position index
+        /// for a topic and shuffle iteration, None if the cipher doesn't exist.
+        /// The proof verifies against the merkle root in the `CipherRoots` storage.
+        fn get_cipher_proof(
+            vote_id: VoteId,
+            topic_id: TopicId,
+            nr_of_shuffles: NrOfShuffles,
+            index: u64,
+        ) -> Option<CipherMerkleProof>;
+
+        /// Returns the current phase of a vote, None if the vote doesn't exist.
+        fn get_vote_phase(vote_id: VoteId) -> Option<VotePhase>;
+
+        /// Returns the vote with its questions and its public key, None if the vote doesn't exist.
+        fn get_vote(vote_id: VoteId) -> Option<VoteDetails<AccountId>>;
+
+        /// Returns the result of a topic of a vote, None if the topic hasn't been tallied yet.
+        fn get_result(vote_id: VoteId, topic_id: TopicId) -> Option<TopicResult>;
+
+        /// Returns the shuffle state of a topic, None if the vote or topic doesn't exist.
+        fn get_shuffle_progress(
+            vote_id: VoteId,
+            topic_id: TopicId,
+        ) -> Option<ShuffleState>;
+
+        /// Returns the mixing and decryption progress of all topics of a vote,
+        /// i.e., the size of the anonymity set, the shuffle iterations and the decrypted shares.
+        fn get_progress(vote_id: VoteId) -> Vec<TopicProgress>;
+
+        /// Verifies the audit trail of all partial decryptions of a topic.
+        fn verify_full_decryption_chain(
+            vote_id: VoteId,
+            topic_id: TopicId,
+            nr_of_shuffles: NrOfShuffles,
+        ) -> bool;
+
+        /// Returns the receipt of the voter's ballot, None if the voter hasn't voted.
+        fn get_ballot_receipt(
+            vote_id: VoteId,
+            account: AccountId,
+        ) -> Option<BallotReceipt<NumberFor<Block>, <Block as BlockT>::Hash>>;
+
+        /// Runs all checks of `cast_ballot` for the ballot of the voter without storing anything,
+        /// i.e., clients can pre-flight a ballot before they submit it. Returns the stable code
+        /// of the error the ballot would be rejected with, see: `mixnet_primitives::ErrorCode`.
+        fn validate_ballot(
+            vote_id: VoteId,
+            account: AccountId,
+            ballot: Ballot,
+        ) -> Result<(), u16>;
+    }
+}
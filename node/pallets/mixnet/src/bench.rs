@@ -1,141 +1,187 @@
 #![cfg(feature = "runtime-benchmarks")]
 
+use crate::helpers::assertions::ensure_ciphers_in_group;
 use crate::types::{
-    Ballot, Cipher, PublicKey as SubstratePK, PublicKeyShare, PublicParameters,
-    ShuffleProof as Proof, Topic, TopicId, Vote, VoteId, VotePhase, Wrapper,
+    Ballot, Cipher, ProtocolConfig, PublicKeyShare, ShuffleProof as Proof, TallyStrategy,
+    Topic, TopicId, Vote, VoteId, VotePhase, Wrapper,
 };
-use crate::{Ballots, Module, Trait};
+use crate::{BalanceOf, Ballots, Module, Trait};
 use alloc::vec::Vec;
-use codec::Decode;
 use crypto::{
     encryption::ElGamal,
-    helper::Helper,
-    proofs::{decryption::DecryptionProof, keygen::KeyGenerationProof},
+    proofs::decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
     types::Cipher as BigCipher,
-    types::{ElGamalParams, ModuloOperations, PrivateKey, PublicKey as ElGamalPK},
+    types::PublicKey as ElGamalPK,
 };
 use frame_benchmarking::{benchmarks, whitelisted_caller};
-use frame_support::{ensure, storage::StorageDoubleMap, traits::Box};
+use frame_support::{
+    ensure,
+    storage::StorageDoubleMap,
+    traits::{Box, Currency},
+};
 use frame_system::RawOrigin;
-use hex_literal::hex;
+use mixnet_test_utils::{
+    accounts::account, BallotFixture, ElectionFixture, KeySize, SealerFixture,
+};
 use num_bigint::BigUint;
-use num_traits::One;
+use sp_runtime::traits::Bounded;
 use sp_std::vec;
 
 use crate::Module as PalletMixnet;
 
 const NR_OF_SHUFFLES: u8 = 0;
 
-fn get_voting_authority<T: Trait>() -> RawOrigin<T::AccountId> {
-    // use Alice as VotingAuthority
-    let account_id: [u8; 32] =
-        hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d").into();
-
-    let account = T::AccountId::decode(&mut &account_id[..]).unwrap();
-    RawOrigin::Signed(account.into())
+/// the vote and the topic of the benchmarks
+const VOTE_ID: &[u8] = b"20201212";
+const TOPIC_ID: &[u8] = b"20201212-01";
+
+/// the election of the benchmarks: a 2048bit key, Alice as voting authority, Bob and Charlie
+/// as sealers and a vote with a single topic and `nr_of_ballots` pre-cast ballots.
+/// the messages of ballots which aren't encoded are squares, i.e., quadratic residues.
+fn election_fixture(nr_of_ballots: usize, encoded: bool) -> ElectionFixture {
+    let builder = ElectionFixture::builder()
+        .key_size(KeySize::Large)
+        .vote(VOTE_ID, &[TOPIC_ID])
+        .ballots(nr_of_ballots as u32)
+        .encoded(encoded);
+    match encoded {
+        true => builder.build(),
+        false => builder.messages(&[1, 4, 9]).build(),
+    }
 }
 
-fn get_sealer_bob<T: Trait>() -> (RawOrigin<T::AccountId>, [u8; 32]) {
-    let account_id: [u8; 32] =
-        hex!("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48").into();
+fn get_voting_authority<T: Trait>(fixture: &ElectionFixture) -> RawOrigin<T::AccountId> {
+    // use Alice as VotingAuthority
+    RawOrigin::Signed(account(&fixture.voting_authorities[0]))
+}
 
-    let account = T::AccountId::decode(&mut &account_id[..]).unwrap();
-    (RawOrigin::Signed(account.into()), account_id)
+fn get_sealer<T: Trait>(sealer: &SealerFixture) -> RawOrigin<T::AccountId> {
+    RawOrigin::Signed(account(&sealer.account))
 }
 
-fn get_sealer_charlie<T: Trait>() -> (RawOrigin<T::AccountId>, [u8; 32]) {
-    let account_id: [u8; 32] =
-        hex!("90b5ab205c6974c9ea841be688864633dc9ca8a357843eeacf2314649965fe22").into();
+/// the pre-cast ballot of the fixture
+fn get_ballot(ballot: &BallotFixture) -> Ballot {
+    Ballot {
+        answers: ballot.answers.clone(),
+        proofs: Vec::new(),
+        validity_proofs: Vec::new(),
+        credential: None,
+    }
+}
 
-    let account = T::AccountId::decode(&mut &account_id[..]).unwrap();
-    (RawOrigin::Signed(account.into()), account_id)
+/// the ciphers of the pre-cast ballots of the fixture's vote
+fn get_ciphers(fixture: &ElectionFixture) -> Vec<Cipher> {
+    fixture.votes[0]
+        .ballots
+        .iter()
+        .flat_map(|ballot| ballot.answers.iter().map(|(_, cipher)| cipher.clone()))
+        .collect()
 }
 
-fn setup_public_key<T: Trait>(
-    vote_id: VoteId,
-    pk: SubstratePK,
-) -> Result<(), &'static str> {
-    // use Alice as VotingAuthority
-    let who = get_voting_authority::<T>();
+fn setup_public_key<T: Trait>(fixture: &ElectionFixture) -> Result<(), &'static str> {
+    let who = get_voting_authority::<T>(fixture);
 
-    // store created public key and public parameters
-    let _setup_result = PalletMixnet::<T>::store_public_key(who.into(), vote_id, pk)?;
+    // store the public key of the fixture
+    PalletMixnet::<T>::store_public_key(
+        who.into(),
+        VOTE_ID.to_vec(),
+        fixture.system_public_key(),
+    )?;
     Ok(())
 }
 
 fn setup_vote<T: Trait>(
-    params: PublicParameters,
+    fixture: &ElectionFixture,
+) -> Result<(Vec<u8>, Vec<u8>), &'static str> {
+    let (vote_id, topic_id) = setup_vote_in_key_generation::<T>(fixture)?;
+    set_vote_phase::<T>(fixture, vote_id.clone(), VotePhase::Voting)?;
+    Ok((vote_id, topic_id))
+}
+
+/// creates the fixture's vote which remains in the key generation phase
+fn setup_vote_in_key_generation<T: Trait>(
+    fixture: &ElectionFixture,
 ) -> Result<(Vec<u8>, Vec<u8>), &'static str> {
     // use Alice as VotingAuthority
-    let who = get_voting_authority::<T>();
+    let who = get_voting_authority::<T>(fixture);
 
     // create the vote
-    let vote_id = "20201212".as_bytes().to_vec();
-    let vote_title = "Popular Vote of 12.12.2020".as_bytes().to_vec();
-
-    let topic_id = "20201212-01".as_bytes().to_vec();
+    let vote = &fixture.votes[0];
+    let topic_id = vote.topic_ids[0].clone();
     let topic_question = "Moritz for President?".as_bytes().to_vec();
-    let topic: Topic = (topic_id.clone(), topic_question);
+    let topic: Topic = Topic::new(topic_id.clone(), topic_question);
     let topics = vec![topic];
 
-    PalletMixnet::<T>::create_vote(
+    PalletMixnet::<T>::propose_vote(
         who.into(),
-        vote_id.clone(),
-        vote_title,
-        params,
+        vote.vote_id.clone(),
+        vote.title.clone(),
+        fixture.public_parameters(),
         topics,
         30,
+        3,
+        TallyStrategy::Mixnet,
     )?;
-    set_vote_phase::<T>(vote_id.clone(), VotePhase::Voting)?;
 
-    Ok((vote_id, topic_id))
+    Ok((vote.vote_id.clone(), topic_id))
 }
 
 fn set_vote_phase<T: Trait>(
+    fixture: &ElectionFixture,
     vote_id: VoteId,
     vote_phase: VotePhase,
 ) -> Result<(), &'static str> {
-    let voting_authority = get_voting_authority::<T>();
+    let voting_authority = get_voting_authority::<T>(fixture);
     PalletMixnet::<T>::set_vote_phase(voting_authority.into(), vote_id, vote_phase)?;
     Ok(())
 }
 
-fn generate_random_encryptions_encoded<T: Trait>(
-    pk: &ElGamalPK,
-    q: &BigUint,
-    number: usize,
-) -> Result<Vec<Cipher>, &'static str> {
-    let mut encryptions: Vec<Cipher> = Vec::new();
-
-    for i in 0..number {
-        let nr = BigUint::from(i);
-        let r = PalletMixnet::<T>::get_random_biguint_less_than(q)?;
-        let enc = ElGamal::encrypt_encode(&nr, &r, pk);
-        encryptions.push(enc.into());
+/// casts the pre-cast ballots of the fixture's vote, all of them signed by the whitelisted caller
+fn cast_ballots<T: Trait>(fixture: &ElectionFixture) -> Result<(), &'static str> {
+    let account: T::AccountId = whitelisted_caller();
+    let voter = RawOrigin::Signed(account);
+    let vote = &fixture.votes[0];
+    for ballot in vote.ballots.iter() {
+        PalletMixnet::<T>::cast_ballot(
+            voter.clone().into(),
+            vote.vote_id.clone(),
+            get_ballot(ballot),
+        )?;
     }
-    Ok(encryptions)
+    Ok(())
 }
 
-fn generate_random_encryptions<T: Trait>(
-    pk: &ElGamalPK,
-    q: &BigUint,
-    number: usize,
-) -> Result<Vec<Cipher>, &'static str> {
-    let mut encryptions: Vec<Cipher> = Vec::new();
-    let mut i: u32 = 0;
-    let one = BigUint::one();
-    let p = &pk.params.p;
-
-    while encryptions.len() != number {
-        let nr = BigUint::from(i);
-        if nr.modpow(q, p) == one {
-            let r = PalletMixnet::<T>::get_random_biguint_less_than(q)?;
-            let enc = ElGamal::encrypt(&nr, &r, pk);
-            encryptions.push(enc.into());
-        }
-        i += 1u32;
+/// creates a vote in the voting phase and a ballot with one answer, the voter can reserve the ballot deposit.
+/// the vote checks the group membership of the ciphers if `group_check` is set
+fn setup_ballot<T: Trait>(
+    group_check: bool,
+) -> Result<(VoteId, T::AccountId, Ballot), &'static str> {
+    // setup
+    let fixture = election_fixture(1, true);
+    let (vote_id, _) = setup_vote_in_key_generation::<T>(&fixture)?;
+    if group_check {
+        let who = get_voting_authority::<T>(&fixture);
+        PalletMixnet::<T>::set_group_check(who.into(), vote_id.clone(), true)?;
     }
-    Ok(encryptions)
+    set_vote_phase::<T>(&fixture, vote_id.clone(), VotePhase::Voting)?;
+
+    // create the voter (i.e. the transaction signer)
+    let account: T::AccountId = whitelisted_caller();
+
+    // the voter must be able to reserve the ballot deposit
+    T::Currency::make_free_balance_be(&account, BalanceOf::<T>::max_value());
+
+    let ballot: Ballot = get_ballot(&fixture.votes[0].ballots[0]);
+    Ok((vote_id, account, ballot))
+}
+
+/// creates a vote and `size` ciphers encrypted under its public key
+fn setup_group_check<T: Trait>(
+    size: usize,
+) -> Result<(VoteId, Vec<Cipher>), &'static str> {
+    let fixture = election_fixture(size, true);
+    let (vote_id, _) = setup_vote::<T>(&fixture)?;
+    Ok((vote_id, get_ciphers(&fixture)))
 }
 
 fn setup_shuffle<T: Trait>(
@@ -143,42 +189,21 @@ fn setup_shuffle<T: Trait>(
     encoded: bool,
 ) -> Result<(Vec<u8>, ElGamalPK, Vec<BigCipher>), &'static str> {
     // setup
-    let (params, _, pk) = Helper::setup_lg_system();
-    let (vote_id, topic_id) = setup_vote::<T>(params.into())?;
-    setup_public_key::<T>(vote_id.clone(), pk.clone().into())?;
-
-    // create messages and random values
-    let q = pk.params.q();
-
-    // create the voter (i.e. the transaction signer)
-    let account: T::AccountId = whitelisted_caller();
-    let voter = RawOrigin::Signed(account.into());
-
-    // generate random encryptions
-    let ciphers: Vec<Cipher>;
-    if encoded {
-        ciphers = generate_random_encryptions_encoded::<T>(&pk, &q, size)?;
-    } else {
-        ciphers = generate_random_encryptions::<T>(&pk, &q, size)?;
-    }
+    let fixture = election_fixture(size, encoded);
 
     // ensure the vote phase is Voting -> otherwise Ballots cannot be submitted
-    set_vote_phase::<T>(vote_id.clone(), VotePhase::Voting)?;
-
-    for cipher in ciphers.iter() {
-        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher.clone())];
-        let ballot: Ballot = Ballot { answers };
-        PalletMixnet::<T>::cast_ballot(voter.clone().into(), vote_id.clone(), ballot)?;
-    }
+    let (_, topic_id) = setup_vote::<T>(&fixture)?;
+    setup_public_key::<T>(&fixture)?;
+    cast_ballots::<T>(&fixture)?;
 
     // type conversion
-    let encryptions: Vec<BigCipher> = Wrapper(ciphers).into();
+    let encryptions: Vec<BigCipher> = Wrapper(get_ciphers(&fixture)).into();
     ensure!(
         encryptions.len() == size,
         "# of votes on chain is not correct"
     );
 
-    Ok((topic_id, pk, encryptions))
+    Ok((topic_id, fixture.public_key.clone(), encryptions))
 }
 
 fn setup_shuffle_proof<T: Trait>(
@@ -207,83 +232,45 @@ fn setup_shuffle_proof<T: Trait>(
     Ok((topic_id, e, e_hat, r, permutation, pk))
 }
 
+/// the public key share of the sealer and the proof of knowledge of its private key share
+fn get_public_key_share(sealer: &SealerFixture) -> PublicKeyShare {
+    PublicKeyShare {
+        proof: sealer.key_generation_proof().into(),
+        pk: sealer.pk.h.to_bytes_be(),
+    }
+}
+
 fn setup_sealer<T: Trait>(
-    params: &ElGamalParams,
-    sk: &PrivateKey,
-    pk: &ElGamalPK,
-    who: RawOrigin<T::AccountId>,
+    sealer: &SealerFixture,
     vote_id: &VoteId,
-    sealer_id: &[u8],
-) -> Result<(PublicKeyShare, KeyGenerationProof), &'static str> {
-    // create public key share + proof
-    let q = &pk.params.q();
-    let r = PalletMixnet::<T>::get_random_biguint_less_than(q)?;
-    let proof = KeyGenerationProof::generate(params, &sk.x, &pk.h, &r, sealer_id);
-    let pk_share = PublicKeyShare {
-        proof: proof.clone().into(),
-        pk: pk.h.to_bytes_be(),
-    };
-
+) -> Result<(), &'static str> {
     // submit the public key share
     PalletMixnet::<T>::store_public_key_share(
-        who.into(),
+        get_sealer::<T>(sealer).into(),
         vote_id.clone(),
-        pk_share.clone().into(),
+        get_public_key_share(sealer),
     )?;
-    Ok((pk_share, proof))
+    Ok(())
 }
 
+/// creates the vote of the fixture, the sealers (Bob and Charlie) submit their key shares,
+/// the ballots are cast and the tallying phase is started
 fn setup_vote_with_distributed_keys<T: Trait>(
     size: usize,
     encoded: bool,
-) -> Result<
-    (
-        Vec<u8>,
-        Vec<u8>,
-        ElGamalPK,  // system public key
-        ElGamalPK,  // bob's public key share
-        PrivateKey, // bob's private key
-        ElGamalPK,  // charlie's public key share
-        PrivateKey, // charlie's private key share
-    ),
-    &'static str,
-> {
-    // setup the vote and generate intial system parameters
-    let (params, _, _) = Helper::setup_lg_system();
-    let q = &params.q();
-    let (vote_id, topic_id) = setup_vote::<T>(params.clone().into())?;
+) -> Result<(Vec<u8>, Vec<u8>, ElectionFixture), &'static str> {
+    let fixture = election_fixture(size, encoded);
+    let (vote_id, topic_id) = setup_vote_in_key_generation::<T>(&fixture)?;
 
     // distributed key generation setup
-    // Use 1. Sealer: Bob
-    let (bob, bob_sealer_id) = get_sealer_bob::<T>();
-    let bob_sk_x = PalletMixnet::<T>::get_random_biguint_less_than(q)?;
-    let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
-    let (_, _) = setup_sealer::<T>(
-        &params,
-        &bob_sk,
-        &bob_pk,
-        bob.clone(),
-        &vote_id,
-        &bob_sealer_id,
-    )?;
-
-    // Use 2. Sealer: Charlie
-    let (charlie, charlie_sealer_id) = get_sealer_charlie::<T>();
-    let charlie_sk_x = PalletMixnet::<T>::get_random_biguint_less_than(q)?;
-    let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
-    let (_, _) = setup_sealer::<T>(
-        &params,
-        &charlie_sk,
-        &charlie_pk,
-        charlie,
-        &vote_id,
-        &charlie_sealer_id,
-    )?;
+    for sealer in fixture.sealers.iter() {
+        setup_sealer::<T>(sealer, &vote_id)?;
+    }
 
-    // combine the public key shares
-    let voting_authority = get_voting_authority::<T>();
+    // combine the public key shares -> VotePhase::Voting
+    let voting_authority = get_voting_authority::<T>(&fixture);
     PalletMixnet::<T>::combine_public_key_shares(
-        voting_authority.clone().into(),
+        voting_authority.into(),
         vote_id.clone(),
     )?;
 
@@ -291,51 +278,32 @@ fn setup_vote_with_distributed_keys<T: Trait>(
     let system_pk: ElGamalPK = PalletMixnet::<T>::public_key(vote_id.clone())
         .unwrap()
         .into();
-    let computed_system_pk: BigUint = bob_pk.h.modmul(&charlie_pk.h, &bob_pk.params.p);
     ensure!(
-        system_pk.h == computed_system_pk,
+        system_pk == fixture.public_key,
         "public keys are not the same!"
     );
 
-    // create the voter (i.e. the transaction signer)
-    let account: T::AccountId = whitelisted_caller();
-    let voter = RawOrigin::Signed(account.into());
-
-    // generate random encryptions
-    let ciphers: Vec<Cipher>;
-    if encoded {
-        ciphers = generate_random_encryptions_encoded::<T>(&system_pk, q, size)?;
-    } else {
-        ciphers = generate_random_encryptions::<T>(&system_pk, q, size)?;
-    }
-
-    set_vote_phase::<T>(vote_id.clone(), VotePhase::Voting)?;
-
-    for cipher in ciphers {
-        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher)];
-        let ballot: Ballot = Ballot { answers };
-        PalletMixnet::<T>::cast_ballot(voter.clone().into(), vote_id.clone(), ballot)?;
-    }
-
-    set_vote_phase::<T>(vote_id.clone(), VotePhase::Tallying)?;
+    cast_ballots::<T>(&fixture)?;
+    set_vote_phase::<T>(&fixture, vote_id.clone(), VotePhase::Tallying)?;
 
-    Ok((
-        topic_id, vote_id, system_pk, bob_pk, bob_sk, charlie_pk, charlie_sk,
-    ))
+    Ok((topic_id, vote_id, fixture))
 }
 
 fn create_decrypted_shares_and_proof<T: Trait>(
+    vote_id: &VoteId,
     topic_id: &TopicId,
-    params: &ElGamalParams,
-    sealer_pk: &ElGamalPK,
-    sealer_sk: &PrivateKey,
-    sealer_id: [u8; 32],
+    sealer: &SealerFixture,
 ) -> Result<(DecryptionProof, Vec<Vec<u8>>), &'static str> {
+    let params = &sealer.pk.params;
     let q = &params.q();
 
     // fetch the encrypted votes from chain
-    let encryptions: Vec<BigCipher> =
-        Wrapper(PalletMixnet::<T>::ciphers(topic_id, NR_OF_SHUFFLES)).into();
+    let encryptions: Vec<BigCipher> = Wrapper(PalletMixnet::<T>::ciphers(
+        vote_id,
+        topic_id,
+        NR_OF_SHUFFLES,
+    ))
+    .into();
     ensure!(
         encryptions.len() > 0,
         "the number of encryptions is too low"
@@ -344,7 +312,7 @@ fn create_decrypted_shares_and_proof<T: Trait>(
     // get sealer's partial decryptions
     let partial_decrytpions = encryptions
         .iter()
-        .map(|cipher| ElGamal::partial_decrypt_a(cipher, sealer_sk))
+        .map(|cipher| ElGamal::partial_decrypt_a(cipher, &sealer.sk))
         .collect::<Vec<BigUint>>();
 
     // convert the decrypted shares: Vec<BigUint> to Vec<Vec<u8>>
@@ -355,14 +323,15 @@ fn create_decrypted_shares_and_proof<T: Trait>(
 
     // create sealer's proof using sealer's public and private key share
     let r = PalletMixnet::<T>::get_random_biguint_less_than(q)?;
-    let decryption_proof = DecryptionProof::generate(
+    let decryption_proof = DecryptionProof::generate_batched(
         params,
-        &sealer_sk.x,
-        &sealer_pk.h.clone().into(),
+        &sealer.sk.x,
+        &sealer.pk.h,
         &r,
-        encryptions,
-        partial_decrytpions,
-        &sealer_id,
+        &encryptions,
+        &partial_decrytpions,
+        &sealer.account,
+        DEFAULT_BATCH_SOUNDNESS,
     );
     Ok((decryption_proof, decrypted_shares))
 }
@@ -370,64 +339,35 @@ fn create_decrypted_shares_and_proof<T: Trait>(
 fn submit_decrypted_shares_and_proofs<T: Trait>(
     size: usize,
     encoded: bool,
-) -> Result<(TopicId, VoteId), &'static str> {
+) -> Result<(TopicId, VoteId, ElectionFixture), &'static str> {
     // setup system with distributed keys
-    let (topic_id, vote_id, _, bob_pk, bob_sk, charlie_pk, charlie_sk) =
+    let (topic_id, vote_id, fixture) =
         setup_vote_with_distributed_keys::<T>(size, encoded)?;
 
-    // use bob
-    let (bob, bob_id) = get_sealer_bob::<T>();
-
-    // create bob's decrypted shares + proof using bob's public and private key share
-    let (bob_proof, bob_shares) = create_decrypted_shares_and_proof::<T>(
-        &topic_id,
-        &bob_pk.params,
-        &bob_pk,
-        &bob_sk,
-        bob_id,
-    )?;
-
-    // submit bob's proof + shares
-    PalletMixnet::<T>::submit_decrypted_shares(
-        bob.into(),
-        vote_id.clone(),
-        topic_id.clone(),
-        bob_shares,
-        bob_proof.into(),
-        NR_OF_SHUFFLES,
-    )?;
-
-    // use charlie
-    let (charlie, charlie_id) = get_sealer_charlie::<T>();
-
-    // create charlie's decrypted shares + proof using charlie's public and private key share
-    let (charlie_proof, charlie_shares) = create_decrypted_shares_and_proof::<T>(
-        &topic_id,
-        &charlie_pk.params,
-        &charlie_pk,
-        &charlie_sk,
-        charlie_id,
-    )?;
-
-    // submit charlie's proof + shares
-    PalletMixnet::<T>::submit_decrypted_shares(
-        charlie.into(),
-        vote_id.clone(),
-        topic_id.clone(),
-        charlie_shares,
-        charlie_proof.into(),
-        NR_OF_SHUFFLES,
-    )?;
-    Ok((topic_id, vote_id))
+    // every sealer (Bob and Charlie) submits its decrypted shares + proof
+    for sealer in fixture.sealers.iter() {
+        let (proof, shares) =
+            create_decrypted_shares_and_proof::<T>(&vote_id, &topic_id, sealer)?;
+        PalletMixnet::<T>::submit_decrypted_shares(
+            get_sealer::<T>(sealer).into(),
+            vote_id.clone(),
+            topic_id.clone(),
+            shares,
+            proof.into(),
+            NR_OF_SHUFFLES,
+        )?;
+    }
+    Ok((topic_id, vote_id, fixture))
 }
 
 benchmarks! {
     _{ }
 
     store_public_key {
-        let (_, _, pk) = Helper::setup_lg_system();
-        let who = get_voting_authority::<T>();
-        let vote_id = "20201212".as_bytes().to_vec();
+        let fixture = election_fixture(0, true);
+        let pk = fixture.public_key.clone();
+        let who = get_voting_authority::<T>(&fixture);
+        let vote_id = VOTE_ID.to_vec();
     }: {
         // store created public key and public parameters
         let _result = PalletMixnet::<T>::store_public_key(who.into(), vote_id.clone(), pk.clone().into());
@@ -439,118 +379,79 @@ benchmarks! {
     }
 
     store_public_key_share {
-        let (params, sk, pk) = Helper::setup_lg_system();
-        let (bob, bob_id) = get_sealer_bob::<T>();
-        let (vote_id, _) = setup_vote::<T>(params.clone().into())?;
+        let fixture = election_fixture(0, true);
+        let bob = get_sealer::<T>(&fixture.sealers[0]);
+        let (vote_id, _) = setup_vote::<T>(&fixture)?;
 
         // create public key share + proof
-        let q = &params.clone().q();
-        let random = PalletMixnet::<T>::get_random_biguint_less_than(q)?;
-        let proof = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &random, &bob_id);
-        let pk_share = PublicKeyShare {
-            proof: proof.clone().into(),
-            pk: pk.h.to_bytes_be(),
-        };
+        let pk_share = get_public_key_share(&fixture.sealers[0]);
     }: {
         // store created public key and public parameters
         let _result = PalletMixnet::<T>::store_public_key_share(bob.into(), vote_id.clone(), pk_share.clone().into());
     }
 
     combine_public_key_shares {
-        let (params, sk, pk) = Helper::setup_lg_system();
-        let voting_authority = get_voting_authority::<T>();
-        let (vote_id, _) = setup_vote::<T>(params.clone().into())?;
-        let q = &params.clone().q();
-
-        // create public key share + proof for bob
-        let (bob, bob_id) = get_sealer_bob::<T>();
-        let random = PalletMixnet::<T>::get_random_biguint_less_than(q)?;
-        let proof_bob = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &random, &bob_id);
-        let pk_share_bob = PublicKeyShare {
-            proof: proof_bob.clone().into(),
-            pk: pk.h.to_bytes_be(),
-        };
-        // store created public key and public parameters
-        let result_ = PalletMixnet::<T>::store_public_key_share(bob.into(), vote_id.clone(), pk_share_bob.clone().into());
-
-        // create public key share + proof for charlie
-        let (charlie, charlie_id) = get_sealer_charlie::<T>();
-        let random = PalletMixnet::<T>::get_random_biguint_less_than(q)?;
-        let proof_charlie = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &random, &charlie_id);
-        let pk_share_charlie = PublicKeyShare {
-            proof: proof_charlie.clone().into(),
-            pk: pk.h.to_bytes_be(),
-        };
-        // store created public key and public parameters
-        let result_ = PalletMixnet::<T>::store_public_key_share(charlie.into(), vote_id.clone(), pk_share_charlie.clone().into());
+        let fixture = election_fixture(0, true);
+        let voting_authority = get_voting_authority::<T>(&fixture);
+        let (vote_id, _) = setup_vote_in_key_generation::<T>(&fixture)?;
+
+        // store the public key shares + proofs of bob and charlie
+        for sealer in fixture.sealers.iter() {
+            setup_sealer::<T>(sealer, &vote_id)?;
+        }
     }: {
         // combine the public key shares
         let _result = PalletMixnet::<T>::combine_public_key_shares(voting_authority.into(), vote_id.clone())?;
     }
 
-    create_vote {
+    propose_vote {
         // use Alice as VotingAuthority
-        let who = get_voting_authority::<T>();
+        let fixture = election_fixture(0, true);
+        let who = get_voting_authority::<T>(&fixture);
 
         // create the vote
-        let vote_id = "20201212".as_bytes().to_vec();
-        let vote_title = "Popular Vote of 12.12.2020".as_bytes().to_vec();
+        let vote_id = VOTE_ID.to_vec();
+        let vote_title = fixture.votes[0].title.clone();
 
-        let topic_id = "20201212-01".as_bytes().to_vec();
+        let topic_id = TOPIC_ID.to_vec();
         let topic_question = "Moritz for President?".as_bytes().to_vec();
-        let topic: Topic = (topic_id.clone(), topic_question);
+        let topic: Topic = Topic::new(topic_id.clone(), topic_question);
         let topics = vec![topic];
 
         // store created public key
-        let (params, _, pk) = Helper::setup_lg_system();
-        PalletMixnet::<T>::store_public_key(who.clone().into(), vote_id.clone(), pk.into())?;
+        let params = fixture.public_parameters();
+        setup_public_key::<T>(&fixture)?;
 
     }: {
-        let _result = PalletMixnet::<T>::create_vote(who.into(), vote_id.clone(), vote_title.clone(), params.into(), topics, 30)?;
+        let _result = PalletMixnet::<T>::propose_vote(who.into(), vote_id.clone(), vote_title.clone(), params, topics, 30, 3, TallyStrategy::Mixnet)?;
     } verify {
         let vote: Vote<T::AccountId> = PalletMixnet::<T>::votes(vote_id);
         ensure!(vote_title == vote.title, "title are not the same!");
     }
 
     store_question {
-        let (params, _, pk) = Helper::setup_lg_system();
-        let (vote_id, topic_id) = setup_vote::<T>(params.into())?;
+        let fixture = election_fixture(0, true);
+        let (vote_id, topic_id) = setup_vote_in_key_generation::<T>(&fixture)?;
 
         // use Alice as VotingAuthority
-        let who = get_voting_authority::<T>();
+        let who = get_voting_authority::<T>(&fixture);
 
         // create another topic
         let topic_id_2 = "20201212-02".as_bytes().to_vec();
         let topic_question = "Moritz for King?".as_bytes().to_vec();
-        let topic: Topic = (topic_id_2.clone(), topic_question.clone());
+        let topic: Topic = Topic::new(topic_id_2.clone(), topic_question.clone());
     }: {
         let _result = PalletMixnet::<T>::store_question(who.into(), vote_id.clone(), topic, 30);
     } verify {
         let topic_: Vec<Topic> = PalletMixnet::<T>::topics(vote_id);
-        ensure!(topic_id == topic_[0].0, "topic ids are not the same!");
-        ensure!(topic_id_2 == topic_[1].0, "topic ids are not the same!");
-        ensure!(topic_question == topic_[1].1, "topic questions are not the same!");
+        ensure!(topic_id == topic_[0].id, "topic ids are not the same!");
+        ensure!(topic_id_2 == topic_[1].id, "topic ids are not the same!");
+        ensure!(topic_question == topic_[1].question, "topic questions are not the same!");
     }
 
     cast_ballot {
-        // setup
-        let (params, _, pk) = Helper::setup_lg_system();
-        let (vote_id, topic_id) = setup_vote::<T>(params.into())?;
-
-        // create messages and random values
-        let q = &pk.params.q();
-        let message = BigUint::one();
-        let random = PalletMixnet::<T>::get_random_biguint_less_than(q)?;
-
-        // create the voter (i.e. the transaction signer)
-        let account: T::AccountId = whitelisted_caller();
-        let voter = RawOrigin::Signed(account.clone().into());
-
-        // transform the ballot into a from that the blockchain can handle
-        // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
-        let cipher: Cipher = ElGamal::encrypt_encode(&message, &random, &pk).into();
-        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id, cipher)];
-        let ballot: Ballot = Ballot { answers };
+        let (vote_id, account, ballot) = setup_ballot::<T>(false)?;
+        let voter = RawOrigin::Signed(account.clone());
     }: {
         let _result = PalletMixnet::<T>::cast_ballot(voter.clone().into(), vote_id.clone(), ballot.clone())?;
     } verify {
@@ -558,25 +459,38 @@ benchmarks! {
         ensure!(ballot == ballot_, "ballots are not the same!");
     }
 
+    cast_ballot_with_group_check {
+        let (vote_id, account, ballot) = setup_ballot::<T>(true)?;
+        let voter = RawOrigin::Signed(account.clone());
+    }: {
+        let _result = PalletMixnet::<T>::cast_ballot(voter.clone().into(), vote_id.clone(), ballot.clone())?;
+    } verify {
+        let ballot_: Ballot = Ballots::<T>::get(vote_id, account);
+        ensure!(ballot == ballot_, "ballots are not the same!");
+    }
+
+    check_ciphers_in_group_10 {
+        let (vote_id, ciphers) = setup_group_check::<T>(10)?;
+    }: {
+        ensure_ciphers_in_group::<T>(&vote_id, ciphers.iter())?;
+    }
+
+    check_ciphers_in_group_100 {
+        let (vote_id, ciphers) = setup_group_check::<T>(100)?;
+    }: {
+        ensure_ciphers_in_group::<T>(&vote_id, ciphers.iter())?;
+    }
+
     verify_public_key_share_proof {
         // setup
-        let (params, sk, pk) = Helper::setup_lg_system();
-        let q = params.q();
-        let (vote_id, topic_id) = setup_vote::<T>(params.clone().into())?;
+        let fixture = election_fixture(0, true);
+        let (vote_id, topic_id) = setup_vote::<T>(&fixture)?;
 
         // create the sealer
-        let sealer_id: [u8; 32] =
-        hex!("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48").into();
-        let sealer_account_id = T::AccountId::decode(&mut &sealer_id[..]).unwrap();
-        let sealer = RawOrigin::Signed(sealer_account_id.into());
+        let sealer = get_sealer::<T>(&fixture.sealers[0]);
 
         // create public key share + proof
-        let r = PalletMixnet::<T>::get_random_biguint_less_than(&q)?;
-        let proof = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &r, &sealer_id);
-        let pk_share = PublicKeyShare {
-            proof: proof.clone().into(),
-            pk: pk.h.to_bytes_be(),
-        };
+        let pk_share = get_public_key_share(&fixture.sealers[0]);
 
     }: {
         PalletMixnet::<T>::store_public_key_share(sealer.into(), vote_id, pk_share.clone())?;
@@ -706,7 +620,7 @@ benchmarks! {
         let (topic_id, e, e_hat, r, permutation, pk) = setup_shuffle_proof::<T>(3, false)?;
         let proof: Proof = PalletMixnet::<T>::generate_shuffle_proof(&topic_id, e.clone(), e_hat.clone(), r, &permutation, &pk)?;
     }: {
-        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk)?;
+        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk, &ProtocolConfig::new(1))?;
         ensure!(success, "proof did not verify!");
     }
 
@@ -714,7 +628,7 @@ benchmarks! {
         let (topic_id, e, e_hat, r, permutation, pk) = setup_shuffle_proof::<T>(10, false)?;
         let proof: Proof = PalletMixnet::<T>::generate_shuffle_proof(&topic_id, e.clone(), e_hat.clone(), r, &permutation, &pk)?;
     }: {
-        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk)?;
+        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk, &ProtocolConfig::new(1))?;
         ensure!(success, "proof did not verify!");
     }
 
@@ -722,7 +636,7 @@ benchmarks! {
         let (topic_id, e, e_hat, r, permutation, pk) = setup_shuffle_proof::<T>(30, false)?;
         let proof: Proof = PalletMixnet::<T>::generate_shuffle_proof(&topic_id, e.clone(), e_hat.clone(), r, &permutation, &pk)?;
     }: {
-        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk)?;
+        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk, &ProtocolConfig::new(1))?;
         ensure!(success, "proof did not verify!");
     }
 
@@ -730,7 +644,7 @@ benchmarks! {
         let (topic_id, e, e_hat, r, permutation, pk) = setup_shuffle_proof::<T>(100, false)?;
         let proof: Proof = PalletMixnet::<T>::generate_shuffle_proof(&topic_id, e.clone(), e_hat.clone(), r, &permutation, &pk)?;
     }: {
-        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk)?;
+        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk, &ProtocolConfig::new(1))?;
         ensure!(success, "proof did not verify!");
     }
 
@@ -738,7 +652,7 @@ benchmarks! {
         let (topic_id, e, e_hat, r, permutation, pk) = setup_shuffle_proof::<T>(1000, false)?;
         let proof: Proof = PalletMixnet::<T>::generate_shuffle_proof(&topic_id, e.clone(), e_hat.clone(), r, &permutation, &pk)?;
     }: {
-        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk)?;
+        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk, &ProtocolConfig::new(1))?;
         ensure!(success, "proof did not verify!");
     }
 
@@ -746,7 +660,7 @@ benchmarks! {
         let (topic_id, e, e_hat, r, permutation, pk) = setup_shuffle_proof::<T>(3, true)?;
         let proof: Proof = PalletMixnet::<T>::generate_shuffle_proof(&topic_id, e.clone(), e_hat.clone(), r, &permutation, &pk)?;
     }: {
-        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk)?;
+        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk, &ProtocolConfig::new(1))?;
         ensure!(success, "proof did not verify!");
     }
 
@@ -754,7 +668,7 @@ benchmarks! {
         let (topic_id, e, e_hat, r, permutation, pk) = setup_shuffle_proof::<T>(10, true)?;
         let proof: Proof = PalletMixnet::<T>::generate_shuffle_proof(&topic_id, e.clone(), e_hat.clone(), r, &permutation, &pk)?;
     }: {
-        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk)?;
+        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk, &ProtocolConfig::new(1))?;
         ensure!(success, "proof did not verify!");
     }
 
@@ -762,7 +676,7 @@ benchmarks! {
         let (topic_id, e, e_hat, r, permutation, pk) = setup_shuffle_proof::<T>(30, true)?;
         let proof: Proof = PalletMixnet::<T>::generate_shuffle_proof(&topic_id, e.clone(), e_hat.clone(), r, &permutation, &pk)?;
     }: {
-        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk)?;
+        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk, &ProtocolConfig::new(1))?;
         ensure!(success, "proof did not verify!");
     }
 
@@ -770,7 +684,7 @@ benchmarks! {
         let (topic_id, e, e_hat, r, permutation, pk) = setup_shuffle_proof::<T>(100, true)?;
         let proof: Proof = PalletMixnet::<T>::generate_shuffle_proof(&topic_id, e.clone(), e_hat.clone(), r, &permutation, &pk)?;
     }: {
-        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk)?;
+        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk, &ProtocolConfig::new(1))?;
         ensure!(success, "proof did not verify!");
     }
 
@@ -778,19 +692,19 @@ benchmarks! {
         let (topic_id, e, e_hat, r, permutation, pk) = setup_shuffle_proof::<T>(1000, true)?;
         let proof: Proof = PalletMixnet::<T>::generate_shuffle_proof(&topic_id, e.clone(), e_hat.clone(), r, &permutation, &pk)?;
     }: {
-        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk)?;
+        let success = PalletMixnet::<T>::verify_shuffle_proof(&topic_id, proof, e, e_hat, &pk, &ProtocolConfig::new(1))?;
         ensure!(success, "proof did not verify!");
     }
 
     verify_submit_decrypted_shares_100 {
         // setup system with distributed keys
-        let (topic_id, vote_id, system_pk, bob_pk, bob_sk, charlie_pk, charlie_sk) = setup_vote_with_distributed_keys::<T>(100, false)?;
+        let (topic_id, vote_id, fixture) = setup_vote_with_distributed_keys::<T>(100, false)?;
 
         // use bob
-        let (bob, bob_id) = get_sealer_bob::<T>();
+        let bob = get_sealer::<T>(&fixture.sealers[0]);
 
         // create bob's decrypted shares + proof using bob's public and private key share
-        let (bob_proof, bob_shares) = create_decrypted_shares_and_proof::<T>(&topic_id, &bob_pk.params, &bob_pk, &bob_sk, bob_id)?;
+        let (bob_proof, bob_shares) = create_decrypted_shares_and_proof::<T>(&vote_id, &topic_id, &fixture.sealers[0])?;
     }: {
         let _success = PalletMixnet::<T>::submit_decrypted_shares(
             bob.into(),
@@ -803,13 +717,13 @@ benchmarks! {
 
     verify_submit_decrypted_shares_1000 {
         // setup system with distributed keys
-        let (topic_id, vote_id, system_pk, bob_pk, bob_sk, charlie_pk, charlie_sk) = setup_vote_with_distributed_keys::<T>(1000, false)?;
+        let (topic_id, vote_id, fixture) = setup_vote_with_distributed_keys::<T>(1000, false)?;
 
         // use bob
-        let (bob, bob_id) = get_sealer_bob::<T>();
+        let bob = get_sealer::<T>(&fixture.sealers[0]);
 
         // create bob's decrypted shares + proof using bob's public and private key share
-        let (bob_proof, bob_shares) = create_decrypted_shares_and_proof::<T>(&topic_id, &bob_pk.params, &bob_pk, &bob_sk, bob_id)?;
+        let (bob_proof, bob_shares) = create_decrypted_shares_and_proof::<T>(&vote_id, &topic_id, &fixture.sealers[0])?;
     }: {
         let _success = PalletMixnet::<T>::submit_decrypted_shares(
             bob.into(),
@@ -822,13 +736,13 @@ benchmarks! {
 
     verify_submit_decrypted_shares_10000 {
         // setup system with distributed keys
-        let (topic_id, vote_id, system_pk, bob_pk, bob_sk, charlie_pk, charlie_sk) = setup_vote_with_distributed_keys::<T>(10000, false)?;
+        let (topic_id, vote_id, fixture) = setup_vote_with_distributed_keys::<T>(10000, false)?;
 
         // use bob
-        let (bob, bob_id) = get_sealer_bob::<T>();
+        let bob = get_sealer::<T>(&fixture.sealers[0]);
 
         // create bob's decrypted shares + proof using bob's public and private key share
-        let (bob_proof, bob_shares) = create_decrypted_shares_and_proof::<T>(&topic_id, &bob_pk.params, &bob_pk, &bob_sk, bob_id)?;
+        let (bob_proof, bob_shares) = create_decrypted_shares_and_proof::<T>(&vote_id, &topic_id, &fixture.sealers[0])?;
     }: {
         let _success = PalletMixnet::<T>::submit_decrypted_shares(
             bob.into(),
@@ -841,13 +755,13 @@ benchmarks! {
 
     verify_submit_decrypted_shares_100_encoded {
         // setup system with distributed keys
-        let (topic_id, vote_id, system_pk, bob_pk, bob_sk, charlie_pk, charlie_sk) = setup_vote_with_distributed_keys::<T>(100, true)?;
+        let (topic_id, vote_id, fixture) = setup_vote_with_distributed_keys::<T>(100, true)?;
 
         // use bob
-        let (bob, bob_id) = get_sealer_bob::<T>();
+        let bob = get_sealer::<T>(&fixture.sealers[0]);
 
         // create bob's decrypted shares + proof using bob's public and private key share
-        let (bob_proof, bob_shares) = create_decrypted_shares_and_proof::<T>(&topic_id, &bob_pk.params, &bob_pk, &bob_sk, bob_id)?;
+        let (bob_proof, bob_shares) = create_decrypted_shares_and_proof::<T>(&vote_id, &topic_id, &fixture.sealers[0])?;
     }: {
         let _success = PalletMixnet::<T>::submit_decrypted_shares(
             bob.into(),
@@ -860,13 +774,13 @@ benchmarks! {
 
     verify_submit_decrypted_shares_1000_encoded {
         // setup system with distributed keys
-        let (topic_id, vote_id, system_pk, bob_pk, bob_sk, charlie_pk, charlie_sk) = setup_vote_with_distributed_keys::<T>(1000, true)?;
+        let (topic_id, vote_id, fixture) = setup_vote_with_distributed_keys::<T>(1000, true)?;
 
         // use bob
-        let (bob, bob_id) = get_sealer_bob::<T>();
+        let bob = get_sealer::<T>(&fixture.sealers[0]);
 
         // create bob's decrypted shares + proof using bob's public and private key share
-        let (bob_proof, bob_shares) = create_decrypted_shares_and_proof::<T>(&topic_id, &bob_pk.params, &bob_pk, &bob_sk, bob_id)?;
+        let (bob_proof, bob_shares) = create_decrypted_shares_and_proof::<T>(&vote_id, &topic_id, &fixture.sealers[0])?;
     }: {
         let _success = PalletMixnet::<T>::submit_decrypted_shares(
             bob.into(),
@@ -879,10 +793,10 @@ benchmarks! {
 
     combine_decrypted_shares_100 {
         // setup everything including keys, votes, decrypted shares
-        let (topic_id, vote_id) = submit_decrypted_shares_and_proofs::<T>(100, false)?;
+        let (topic_id, vote_id, fixture) = submit_decrypted_shares_and_proofs::<T>(100, false)?;
 
         // use Alice as VotingAuthority to combine the votes
-        let who = get_voting_authority::<T>();
+        let who = get_voting_authority::<T>(&fixture);
     }: {
         let _success = PalletMixnet::<T>::combine_decrypted_shares(
             who.into(),
@@ -894,10 +808,10 @@ benchmarks! {
 
     combine_decrypted_shares_1000 {
         // setup everything including keys, votes, decrypted shares
-        let (topic_id, vote_id) = submit_decrypted_shares_and_proofs::<T>(1000, false)?;
+        let (topic_id, vote_id, fixture) = submit_decrypted_shares_and_proofs::<T>(1000, false)?;
 
         // use Alice as VotingAuthority to combine the votes
-        let who = get_voting_authority::<T>();
+        let who = get_voting_authority::<T>(&fixture);
     }: {
         let _success = PalletMixnet::<T>::combine_decrypted_shares(
             who.into(),
@@ -909,10 +823,10 @@ benchmarks! {
 
     combine_decrypted_shares_10000 {
         // setup everything including keys, votes, decrypted shares
-        let (topic_id, vote_id) = submit_decrypted_shares_and_proofs::<T>(10000, false)?;
+        let (topic_id, vote_id, fixture) = submit_decrypted_shares_and_proofs::<T>(10000, false)?;
 
         // use Alice as VotingAuthority to combine the votes
-        let who = get_voting_authority::<T>();
+        let who = get_voting_authority::<T>(&fixture);
     }: {
         let _success = PalletMixnet::<T>::combine_decrypted_shares(
             who.into(),
@@ -924,10 +838,10 @@ benchmarks! {
 
     combine_decrypted_shares_100_encoded {
         // setup everything including keys, votes, decrypted shares
-        let (topic_id, vote_id) = submit_decrypted_shares_and_proofs::<T>(100, true)?;
+        let (topic_id, vote_id, fixture) = submit_decrypted_shares_and_proofs::<T>(100, true)?;
 
         // use Alice as VotingAuthority to combine the votes
-        let who = get_voting_authority::<T>();
+        let who = get_voting_authority::<T>(&fixture);
     }: {
         let _success = PalletMixnet::<T>::combine_decrypted_shares(
             who.into(),
@@ -939,10 +853,10 @@ benchmarks! {
 
     combine_decrypted_shares_1000_encoded {
         // setup everything including keys, votes, decrypted shares
-        let (topic_id, vote_id) = submit_decrypted_shares_and_proofs::<T>(1000, true)?;
+        let (topic_id, vote_id, fixture) = submit_decrypted_shares_and_proofs::<T>(1000, true)?;
 
         // use Alice as VotingAuthority to combine the votes
-        let who = get_voting_authority::<T>();
+        let who = get_voting_authority::<T>(&fixture);
     }: {
         let _success = PalletMixnet::<T>::combine_decrypted_shares(
             who.into(),
@@ -967,11 +881,20 @@ mod tests {
             assert_ok!(test_benchmark_store_public_key_share::<TestRuntime>());
             assert_ok!(test_benchmark_combine_public_key_shares::<TestRuntime>());
             assert_ok!(test_benchmark_store_question::<TestRuntime>());
-            assert_ok!(test_benchmark_create_vote::<TestRuntime>());
+            assert_ok!(test_benchmark_propose_vote::<TestRuntime>());
             assert_ok!(test_benchmark_cast_ballot::<TestRuntime>());
         });
     }
 
+    #[test]
+    fn test_benchmarks_group_check() {
+        let (mut t, _, _) = ExternalityBuilder::build();
+        t.execute_with(|| {
+            assert_ok!(test_benchmark_cast_ballot_with_group_check::<TestRuntime>());
+            assert_ok!(test_benchmark_check_ciphers_in_group_10::<TestRuntime>());
+        });
+    }
+
     #[test]
     fn test_benchmarks_shuffle_ciphers() {
         let (mut t, _, _) = ExternalityBuilder::build();
@@ -0,0 +1,132 @@
+use crate::helpers::{
+    ciphers::{get_all_ciphers, get_cipher_count, get_ciphers_in_range},
+    params::get_public_params,
+};
+use crate::types::{
+    Cipher, NrOfShuffles, PublicParameters, TallyStrategy, TopicId, VoteId, Wrapper,
+};
+use crate::{AggregateCiphers, Error, Trait, Votes};
+use crypto::encryption::ElGamal;
+use crypto::types::Cipher as BigCipher;
+use frame_support::{ensure, storage::StorageMap};
+use num_bigint::BigUint;
+use sp_std::{vec, vec::Vec};
+
+/// the ciphers are aggregated as submitted by the voters (never shuffled)
+const INITIAL_NUMBER_OF_SHUFFLES: NrOfShuffles = 0;
+
+pub fn get_tally_strategy<T: Trait>(vote_id: &VoteId) -> TallyStrategy {
+    Votes::<T>::get(vote_id).tally_strategy
+}
+
+/// homomorphically adds all ciphers of the topic: E(m1) * E(m2) = E(m1 + m2)
+/// and stores the resulting aggregate cipher.
+pub fn aggregate_topic_ciphers<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+) -> Result<Cipher, Error<T>> {
+    ensure!(
+        get_tally_strategy::<T>(vote_id) == TallyStrategy::Homomorphic,
+        Error::<T>::WrongTallyStrategy
+    );
+    ensure!(
+        !AggregateCiphers::contains_key((vote_id, topic_id)),
+        Error::<T>::CiphersAlreadyAggregated
+    );
+
+    // get the public parameters
+    let params: PublicParameters = get_public_params::<T>(vote_id)?;
+    let big_p: BigUint = BigUint::from_bytes_be(&params.p);
+
+    // get all encrypted votes (ciphers) for the topic with id: topic_id
+    let ciphers: Vec<Cipher> =
+        get_all_ciphers::<T>(vote_id, topic_id, INITIAL_NUMBER_OF_SHUFFLES);
+    ensure!(!ciphers.is_empty(), Error::<T>::NrOfShufflesDoesNotExist);
+
+    // type conversion: Vec<Cipher> (Vec<Vec<u8>>) to Vec<BigCipher> (Vec<BigUint>)
+    let big_ciphers: Vec<BigCipher> = Wrapper(ciphers).into();
+
+    // add all ciphers
+    let mut iterator = big_ciphers.into_iter();
+    let first: BigCipher = iterator
+        .next()
+        .expect("there is at least one cipher to aggregate!");
+    let aggregate: Cipher = iterator
+        .fold(first, |sum, cipher| {
+            ElGamal::homomorphic_addition(&sum, &cipher, &big_p)
+        })
+        .into();
+
+    // store the aggregate cipher
+    AggregateCiphers::insert((vote_id, topic_id), aggregate.clone());
+    Ok(aggregate)
+}
+
+/// returns the ciphers which need to be decrypted to tally the topic.
+/// - mixnet: all ciphers after nr_of_shuffles shuffles
+/// - homomorphic: only the aggregate cipher
+pub fn get_ciphers_to_decrypt<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    nr_of_shuffles: NrOfShuffles,
+) -> Result<Vec<Cipher>, Error<T>> {
+    match get_tally_strategy::<T>(vote_id) {
+        TallyStrategy::Mixnet => {
+            Ok(get_all_ciphers::<T>(vote_id, topic_id, nr_of_shuffles))
+        }
+        TallyStrategy::Homomorphic => {
+            let aggregate: Cipher = AggregateCiphers::get((vote_id, topic_id))
+                .ok_or(Error::<T>::CiphersNotAggregated)?;
+            Ok(vec![aggregate])
+        }
+    }
+}
+
+/// returns the number of ciphers which need to be decrypted to tally the topic
+/// (see: `get_ciphers_to_decrypt`), no cipher is read from storage.
+pub fn get_nr_of_ciphers_to_decrypt<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    nr_of_shuffles: NrOfShuffles,
+) -> Result<u64, Error<T>> {
+    match get_tally_strategy::<T>(vote_id) {
+        TallyStrategy::Mixnet => {
+            Ok(get_cipher_count::<T>(vote_id, topic_id, nr_of_shuffles))
+        }
+        TallyStrategy::Homomorphic => {
+            ensure!(
+                AggregateCiphers::contains_key((vote_id, topic_id)),
+                Error::<T>::CiphersNotAggregated
+            );
+            Ok(1)
+        }
+    }
+}
+
+/// returns the ciphers in the range [start_position, start_position + batch_size)
+/// of the ciphers which need to be decrypted to tally the topic.
+pub fn get_ciphers_to_decrypt_in_range<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    nr_of_shuffles: NrOfShuffles,
+    start_position: u64,
+    batch_size: u64,
+) -> Result<Vec<Cipher>, Error<T>> {
+    match get_tally_strategy::<T>(vote_id) {
+        TallyStrategy::Mixnet => Ok(get_ciphers_in_range::<T>(
+            vote_id,
+            topic_id,
+            nr_of_shuffles,
+            start_position,
+            batch_size,
+        )),
+        TallyStrategy::Homomorphic => {
+            let ciphers = get_ciphers_to_decrypt::<T>(vote_id, topic_id, nr_of_shuffles)?;
+            Ok(ciphers
+                .into_iter()
+                .skip(start_position as usize)
+                .take(batch_size as usize)
+                .collect())
+        }
+    }
+}
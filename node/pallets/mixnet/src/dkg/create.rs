@@ -1,3 +1,4 @@
+use super::threshold::{combine_threshold_public_key_shares, get_decryption_threshold};
 use crate::{
     helpers::params::get_public_params,
     helpers::phase::set_phase,
@@ -14,12 +15,12 @@ use num_bigint::BigUint;
 use num_traits::One;
 
 /// all functions related to key generation and decrypted share operations
-pub fn combine_shares<T: Trait>(
-    who: T::AccountId,
+///
+/// multiplies the public key shares of all sealers: h = g^(x_1 + ... + x_n)
+fn combine_all_shares<T: Trait>(
     vote_id: &VoteId,
-) -> Result<SubstratePK, Error<T>> {
-    // get the public parameters
-    let params: PublicParameters = get_public_params::<T>(&vote_id)?;
+    params: PublicParameters,
+) -> Result<ElGamalPK, Error<T>> {
     let shares: Vec<PublicKeyShare> = PublicKeyShares::get(&vote_id);
 
     // check that there are at least two shares
@@ -38,9 +39,25 @@ pub fn combine_shares<T: Trait>(
         h: BigUint::one(),
         params: params.into(),
     };
+    Ok(base.combine_public_keys_bigunits(&pk_shares_biguint))
+}
+
+/// combines the public key shares into the public key of the vote and starts the voting phase
+pub fn combine_shares<T: Trait>(
+    who: T::AccountId,
+    vote_id: &VoteId,
+) -> Result<SubstratePK, Error<T>> {
+    // get the public parameters
+    let params: PublicParameters = get_public_params::<T>(&vote_id)?;
 
-    // combine the shares into a single key
-    let new_pk: ElGamalPK = base.combine_public_keys_bigunits(&pk_shares_biguint);
+    // combine the shares into a single key, threshold keys are interpolated in the exponent
+    let new_pk: ElGamalPK = match get_decryption_threshold::<T>(vote_id) {
+        Some(threshold) => ElGamalPK {
+            h: combine_threshold_public_key_shares::<T>(vote_id, threshold)?,
+            params: params.into(),
+        },
+        None => combine_all_shares::<T>(vote_id, params)?,
+    };
     let pk: SubstratePK = new_pk.into();
     PublicKey::insert(vote_id.to_owned(), pk.clone());
     debug::info!("public_key successfully generated!");
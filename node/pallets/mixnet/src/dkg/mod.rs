@@ -1,3 +1,6 @@
+pub mod aggregate;
 pub mod create;
+pub mod rotate;
 pub mod tally;
+pub mod threshold;
 pub mod verify;
@@ -0,0 +1,203 @@
+use crate::helpers::{
+    assertions::{ensure_no_key_switch_pending, ensure_vote_phase},
+    ciphers::{get_all_ciphers, get_cipher_count, replace_ciphers},
+    params::{get_hash_function, get_public_key, get_public_params},
+    phase::set_phase,
+    sealers::get_vote_sealers,
+};
+use crate::types::{
+    Cipher, KeySwitchAuditEntry, KeySwitchShareProof, PublicKeyShare, PublicParameters,
+    TopicId, VoteId, VotePhase, Wrapper,
+};
+use crate::{
+    Credentials, DecryptionThresholds, Error, KeySwitchAudit, KeySwitchShares,
+    PendingKeySwitch, PreviousPublicKeyShareBySealer, PublicKey, PublicKeyShareBySealer,
+    PublicKeyShares, Topics, Trait,
+};
+use codec::Encode;
+use crypto::encryption::ElGamal;
+use crypto::proofs::key_switch::KeySwitchProof;
+use crypto::types::{Cipher as BigCipher, PublicKey as ElGamalPK};
+use frame_support::{
+    debug, ensure,
+    storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageMap},
+};
+use num_bigint::BigUint;
+use sp_std::vec::Vec;
+
+// the ciphers submitted by the voters, i.e., before any shuffle
+const INITIAL_NUMBER_OF_SHUFFLES: u8 = 0;
+
+/// all functions related to the rotation of a vote's public key
+///
+/// rotating the public key starts a new key generation round. the ciphers stored so
+/// far remain encrypted under the old key until every sealer of the old key has
+/// submitted its key switch shares (and proof) for all topics of the vote.
+pub fn rotate_key<T: Trait>(
+    who: &T::AccountId,
+    vote_id: &VoteId,
+) -> Result<(), Error<T>> {
+    // only a public key of a vote in the voting phase can be rotated
+    ensure_vote_phase::<T>(vote_id, VotePhase::Voting)?;
+    ensure_no_key_switch_pending::<T>(vote_id)?;
+    get_public_key::<T>(vote_id)?;
+
+    // the key switch shares are combined additively, i.e., threshold keys can't be switched
+    ensure!(
+        !DecryptionThresholds::contains_key(vote_id),
+        Error::<T>::KeyRotationNotSupported
+    );
+    // the registered credentials are encrypted under the old key and aren't switched
+    ensure!(
+        Credentials::<T>::iter_prefix_values(vote_id)
+            .next()
+            .is_none(),
+        Error::<T>::KeyRotationNotSupported
+    );
+
+    // keep the public key shares of the old key to verify the key switch proofs
+    for sealer in get_vote_sealers::<T>(vote_id).iter() {
+        PreviousPublicKeyShareBySealer::<T>::remove((vote_id, sealer));
+        if let Some(share) = PublicKeyShareBySealer::<T>::take((vote_id, sealer)) {
+            PreviousPublicKeyShareBySealer::<T>::insert((vote_id, sealer), share);
+        }
+    }
+    PublicKeyShares::remove(vote_id);
+    PublicKey::remove(vote_id);
+
+    // all topics with ciphers need to be switched to the new key
+    let pending: Vec<TopicId> = Topics::get(vote_id)
+        .into_iter()
+        .map(|topic| topic.id)
+        .filter(|topic_id| {
+            get_cipher_count::<T>(vote_id, topic_id, INITIAL_NUMBER_OF_SHUFFLES) > 0
+        })
+        .collect();
+    PendingKeySwitch::insert(vote_id, pending);
+
+    // start a new key generation round
+    set_phase::<T>(who, vote_id, VotePhase::KeyGeneration)?;
+    debug::info!("public key of vote: {:?} rotated", vote_id);
+    Ok(())
+}
+
+pub fn verify_proof_and_store_key_switch_shares<T: Trait>(
+    who: T::AccountId,
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    shares: Vec<Cipher>,
+    proof: KeySwitchShareProof,
+) -> Result<(), Error<T>> {
+    ensure!(
+        PendingKeySwitch::get(vote_id).contains(topic_id),
+        Error::<T>::KeySwitchNotPending
+    );
+    ensure!(
+        !KeySwitchShares::<T>::contains_key((vote_id, topic_id), &who),
+        Error::<T>::KeySwitchSharesAlreadySubmitted
+    );
+
+    // get the public parameters, the new public key
+    // and the sealer's public key share of the old key
+    let sealer_id: &[u8] = &who.encode();
+    let params: PublicParameters = get_public_params::<T>(vote_id)?;
+    let new_pk: ElGamalPK = get_public_key::<T>(vote_id)?.into();
+    let sealer_pk_share: PublicKeyShare =
+        PreviousPublicKeyShareBySealer::<T>::get((vote_id, &who))
+            .ok_or(Error::<T>::PublicKeyShareNotExistsError)?;
+    let sealer_pk: BigUint = BigUint::from_bytes_be(&sealer_pk_share.pk);
+
+    // the ciphers encrypted under the old key
+    let ciphers: Vec<Cipher> =
+        get_all_ciphers::<T>(vote_id, topic_id, INITIAL_NUMBER_OF_SHUFFLES);
+    let big_ciphers: Vec<BigCipher> = Wrapper(ciphers).into();
+    let big_shares: Vec<BigCipher> = Wrapper(shares.clone()).into();
+
+    // verify the proof using the sealer's public key share of the old key
+    let is_valid: bool = KeySwitchProof::verify_with_hash(
+        &params.into(),
+        &sealer_pk,
+        &new_pk.h,
+        &proof.clone().into(),
+        big_ciphers,
+        big_shares,
+        sealer_id,
+        get_hash_function::<T>(vote_id),
+    );
+    ensure!(is_valid, Error::<T>::KeySwitchShareProofError);
+
+    // add the submission to the audit trail
+    let entry = KeySwitchAuditEntry {
+        sealer: who.clone(),
+        shares_hash: sp_io::hashing::blake2_256(&shares.encode()),
+        proof,
+    };
+    let mut audit: Vec<KeySwitchAuditEntry<T::AccountId>> =
+        KeySwitchAudit::<T>::get((vote_id, topic_id));
+    audit.push(entry);
+    KeySwitchAudit::<T>::insert((vote_id, topic_id), audit);
+
+    // store the key switch shares until all sealers have submitted theirs
+    KeySwitchShares::<T>::insert((vote_id, topic_id), &who, shares);
+    Ok(())
+}
+
+/// combines the key switch shares of all sealers of the old key and
+/// replaces the ciphers of the topic with the ciphers encrypted under the new key.
+/// returns false if not all sealers have submitted their key switch shares yet.
+pub fn combine_key_switch_shares<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+) -> Result<bool, Error<T>> {
+    // the sealers who contributed to the old key
+    let sealers: Vec<T::AccountId> = get_vote_sealers::<T>(vote_id)
+        .into_iter()
+        .filter(|sealer| {
+            PreviousPublicKeyShareBySealer::<T>::contains_key((vote_id, sealer))
+        })
+        .collect();
+    let all_submitted = sealers
+        .iter()
+        .all(|sealer| KeySwitchShares::<T>::contains_key((vote_id, topic_id), sealer));
+    if !all_submitted {
+        return Ok(false);
+    }
+
+    let params: PublicParameters = get_public_params::<T>(vote_id)?;
+    let p = BigUint::from_bytes_be(&params.p);
+    let ciphers: Vec<BigCipher> = Wrapper(get_all_ciphers::<T>(
+        vote_id,
+        topic_id,
+        INITIAL_NUMBER_OF_SHUFFLES,
+    ))
+    .into();
+    let shares: Vec<Vec<BigCipher>> = sealers
+        .iter()
+        .map(|sealer| {
+            Wrapper(KeySwitchShares::<T>::get((vote_id, topic_id), sealer)).into()
+        })
+        .collect();
+
+    // combine the shares of all sealers for each cipher
+    let switched: Vec<BigCipher> = ciphers
+        .iter()
+        .enumerate()
+        .map(|(index, cipher)| {
+            let cipher_shares: Vec<BigCipher> =
+                shares.iter().map(|s| s[index].clone()).collect();
+            ElGamal::combine_key_switch_shares(cipher, &cipher_shares, &p)
+        })
+        .collect();
+    replace_ciphers::<T>(
+        vote_id,
+        topic_id,
+        INITIAL_NUMBER_OF_SHUFFLES,
+        Wrapper(switched).into(),
+    );
+
+    // the topic is encrypted under the new key
+    KeySwitchShares::<T>::remove_prefix((vote_id, topic_id));
+    PendingKeySwitch::mutate(vote_id, |pending| pending.retain(|id| id != topic_id));
+    debug::info!("ciphers of topic: {:?} switched to the new key", topic_id);
+    Ok(true)
+}
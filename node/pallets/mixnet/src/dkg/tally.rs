@@ -1,50 +1,136 @@
+use super::aggregate::{
+    get_ciphers_to_decrypt_in_range, get_nr_of_ciphers_to_decrypt, get_tally_strategy,
+};
+use super::threshold::{
+    combine_threshold_credential_decryptions, combine_threshold_decrypted_shares,
+    get_decryption_threshold,
+};
 use crate::types::{
-    Cipher, DecryptedShare, NrOfShuffles, PublicParameters, TopicId, TopicResult, VoteId,
-    Wrapper,
+    Cipher, DecryptedShare, NrOfShuffles, PartialTally, PublicParameters, QuestionType,
+    RankedTallyMethod, TallyProgress, TallyRound, TallyStrategy, Topic, TopicId,
+    TopicResult, VoteId, Wrapper, NO_ANSWER,
 };
 use crate::{
-    helpers::params::get_public_params, Ciphers, DecryptedShares, Error, Sealers, Tally,
-    Trait,
+    helpers::{
+        ballot::exclude_ballot_ciphers,
+        credentials::get_combined_credential_blindings,
+        packing::{count_packed_answers, get_topic_packing},
+        params::get_public_params,
+        sealers::get_vote_sealers,
+        voter_weights::get_topic_weight,
+    },
+    tally::{borda, instant_runoff},
+    CredentialBallots, CredentialDecryptions, DecryptedShares, DiscardedBallots, Error,
+    PartialTallies, ShuffleBatchSizes, ShuffleStateStore, Tally, TallyProgresses,
+    TallyRounds, Topics, Trait,
 };
-use crypto::encryption::ElGamal;
+use crypto::encryption::{ElGamal, DEFAULT_DECODE_BOUND};
+use crypto::proofs::pet::PlaintextEquivalenceProof;
 use crypto::types::Cipher as BigCipher;
 use frame_support::{
     ensure,
-    storage::{StorageDoubleMap, StorageMap, StorageValue},
+    storage::{StorageDoubleMap, StorageMap},
+    traits::Get,
 };
 use num_bigint::BigUint;
-use num_traits::One;
-use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+use num_traits::{One, ToPrimitive, Zero};
+use sp_std::{collections::btree_map::BTreeMap, vec, vec::Vec};
 
-pub fn combine_shares_and_tally_topic<T: Trait>(
-    vote_id: &VoteId,
-    topic_id: &TopicId,
-    encoded: bool,
-    nr_of_shuffles: &NrOfShuffles,
-) -> Result<TopicResult, Error<T>> {
-    // get the public parameters and the system public key
-    let params: PublicParameters = get_public_params::<T>(vote_id)?;
-    let big_p: BigUint = BigUint::from_bytes_be(&params.p);
-    let big_g: BigUint = BigUint::from_bytes_be(&params.g);
+/// returns the topic of the vote, free-form if the topic doesn't exist
+fn get_topic(vote_id: &VoteId, topic_id: &TopicId) -> Topic {
+    Topics::get(vote_id)
+        .into_iter()
+        .find(|topic| topic.id == *topic_id)
+        .unwrap_or_else(|| Topic::new(topic_id.clone(), Vec::new()))
+}
 
-    // get all encrypted votes (ciphers)
-    // for the topic with id: topic_id and the # of shuffles (nr_of_shuffles)
-    let ciphers: Vec<Cipher> = Ciphers::get(topic_id, nr_of_shuffles);
+/// returns the voting options an answer counts for:
+/// - SingleChoice: the answer itself (the index of the candidate)
+/// - MultiSelect: the index of every selected candidate
+/// - Ranked: the index of the first preference
+/// NO_ANSWER and answers which aren't valid for the question count as they are.
+fn count_answer(topic: &Topic, answer: BigUint) -> Vec<BigUint> {
+    let message = match answer.to_u64() {
+        Some(message)
+            if topic.requires_validity_proof() && message != NO_ANSWER as u64 =>
+        {
+            message
+        }
+        _ => return vec![answer],
+    };
+    match topic.question_type {
+        QuestionType::SingleChoice => vec![answer],
+        QuestionType::MultiSelect { .. } => (0..topic.candidates.len() as u64)
+            .filter(|index| message & (1 << index) != 0)
+            .map(BigUint::from)
+            .collect(),
+        QuestionType::Ranked { .. } => match topic.decode_ranking(message) {
+            Some(ranking) => vec![BigUint::from(ranking[0])],
+            None => vec![answer],
+        },
+    }
+}
 
-    // type conversion: Vec<Cipher> (Vec<Vec<u8>>) to Vec<BigCipher> (Vec<BigUint>)
-    let big_ciphers: Vec<BigCipher> = Wrapper(ciphers).into();
+/// tallies the rankings of a ranked question by Borda count or instant-runoff,
+/// every answer is given together with the number of ciphers which decrypted to it.
+/// NO_ANSWER and answers which aren't valid rankings count as they are.
+/// returns the count per voting option and the rounds of an instant-runoff tally (empty otherwise).
+fn tally_rankings(
+    topic: &Topic,
+    answers: Vec<(BigUint, u64)>,
+) -> (BTreeMap<BigUint, BigUint>, Vec<TallyRound>) {
+    let mut big_results: BTreeMap<BigUint, BigUint> = BTreeMap::new();
+    let mut rankings: Vec<(Vec<u32>, u64)> = Vec::with_capacity(answers.len());
+    for (answer, count) in answers.into_iter() {
+        match answer.to_u64().and_then(|m| topic.decode_ranking(m)) {
+            Some(ranking) => rankings.push((ranking, count)),
+            None => *big_results.entry(answer).or_default() += BigUint::from(count),
+        }
+    }
 
+    let nr_of_candidates = topic.candidates.len() as u32;
+    let (counts, rounds) = match topic.ranked_tally {
+        RankedTallyMethod::InstantRunoff => {
+            let rounds = instant_runoff(nr_of_candidates, &rankings);
+            let counts = rounds
+                .last()
+                .map(|round| round.counts.clone())
+                .unwrap_or_default();
+            (counts, rounds)
+        }
+        _ => (borda(nr_of_candidates, &rankings), Vec::new()),
+    };
+    for (candidate, count) in counts.into_iter() {
+        big_results.insert(BigUint::from(candidate), BigUint::from(count));
+    }
+    (big_results, rounds)
+}
+
+/// combines the partial decryptions of the ciphers in the range [start, end) of all sealers
+/// of the vote, each sealer must have submitted its decrypted shares
+fn combine_decrypted_shares_of_all_sealers<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    big_p: &BigUint,
+    start: usize,
+    end: usize,
+) -> Result<Vec<BigUint>, Error<T>> {
     // retrieve the decrypted shares of all sealers
-    let sealers: Vec<T::AccountId> = Sealers::<T>::get();
+    let sealers: Vec<T::AccountId> = get_vote_sealers::<T>(vote_id);
     let mut partial_decryptions: Vec<Vec<BigUint>> = Vec::with_capacity(sealers.len());
 
     for sealer in sealers.iter() {
         // get the partial decryptions of each sealer
-        let shares: Vec<DecryptedShare> =
-            DecryptedShares::<T>::get::<&TopicId, &T::AccountId>(topic_id, &sealer);
+        let shares: Vec<DecryptedShare> = DecryptedShares::<T>::get::<
+            (&VoteId, &TopicId),
+            &T::AccountId,
+        >((vote_id, topic_id), &sealer);
 
         // make sure that each sealer has submitted his decrypted shares
         ensure!(!shares.is_empty(), Error::<T>::NotEnoughDecryptedShares);
+        let shares = shares
+            .get(start..end)
+            .ok_or(Error::<T>::NotEnoughDecryptedShares)?;
 
         // type conversion: DecryptedShare (Vec<u8>) to BigUint
         let big_shares: Vec<BigUint> = shares
@@ -55,8 +141,100 @@ pub fn combine_shares_and_tally_topic<T: Trait>(
     }
 
     // combine all partial decryptions by all sealers
-    let combined_partial_decryptions =
-        ElGamal::combine_partial_decrypted_as(partial_decryptions, &big_p);
+    Ok(ElGamal::combine_partial_decrypted_as(
+        partial_decryptions,
+        big_p,
+    ))
+}
+
+/// returns the number of ciphers which are combined and decoded per batch of the tally,
+/// i.e., the shuffle batch size of the vote
+fn get_tally_batch_size<T: Trait>(vote_id: &VoteId, topic_id: &TopicId) -> u64 {
+    ShuffleBatchSizes::get(vote_id)
+        .or_else(|| {
+            ShuffleStateStore::get((vote_id, topic_id)).map(|state| state.batch_size)
+        })
+        .unwrap_or_else(T::MaxShuffleBatchSize::get)
+}
+
+/// returns the progress of the tally of the topic, starts a new tally if none is in progress.
+/// all batches of a topic must be combined with the same parameters.
+fn get_or_start_tally<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    encoded: bool,
+    nr_of_shuffles: NrOfShuffles,
+) -> Result<TallyProgress, Error<T>> {
+    // check that topic has not been tallied yet
+    ensure!(
+        !Tally::contains_key((vote_id, topic_id)),
+        Error::<T>::TopicHasAlreadyBeenTallied
+    );
+    match TallyProgresses::get((vote_id, topic_id)) {
+        Some(progress) => {
+            ensure!(
+                progress.encoded == encoded && progress.nr_of_shuffles == nr_of_shuffles,
+                Error::<T>::TallyParametersMismatch
+            );
+            Ok(progress)
+        }
+        None => Ok(TallyProgress {
+            encoded,
+            nr_of_shuffles,
+            position: 0,
+            total: get_nr_of_ciphers_to_decrypt::<T>(vote_id, topic_id, nr_of_shuffles)?,
+        }),
+    }
+}
+
+/// combines the decrypted shares of the next (up to) `batch_size` ciphers of the topic,
+/// decodes the plaintexts and adds them to the partial tally of the topic.
+fn combine_batch<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    progress: &mut TallyProgress,
+    batch_size: u64,
+) -> Result<(), Error<T>> {
+    let start = progress.position;
+    let end = start.saturating_add(batch_size).min(progress.total);
+
+    // get the public parameters and the system public key
+    let params: PublicParameters = get_public_params::<T>(vote_id)?;
+    let big_p: BigUint = BigUint::from_bytes_be(&params.p);
+    let big_g: BigUint = BigUint::from_bytes_be(&params.g);
+
+    // get the encrypted votes (ciphers) of the batch to decrypt
+    // for the topic with id: topic_id and the # of shuffles (nr_of_shuffles)
+    let ciphers: Vec<Cipher> = get_ciphers_to_decrypt_in_range::<T>(
+        vote_id,
+        topic_id,
+        progress.nr_of_shuffles,
+        start,
+        end - start,
+    )?;
+    let homomorphic = get_tally_strategy::<T>(vote_id) == TallyStrategy::Homomorphic;
+
+    // the answers to questions with candidates are always encoded,
+    // packed answers never (they are unpacked once all batches have been combined)
+    let topic = get_topic(vote_id, topic_id);
+    let packed = get_topic_packing(vote_id, topic_id).is_some();
+    let encoded = !packed && (progress.encoded || topic.requires_validity_proof());
+
+    // type conversion: Vec<Cipher> (Vec<Vec<u8>>) to Vec<BigCipher> (Vec<BigUint>)
+    let big_ciphers: Vec<BigCipher> = Wrapper(ciphers).into();
+
+    // combine the partial decryptions of all sealers
+    // or of any `threshold` sealers (threshold keys)
+    let (start, end) = (start as usize, end as usize);
+    let combined_partial_decryptions: Vec<BigUint> =
+        match get_decryption_threshold::<T>(vote_id) {
+            Some(threshold) => combine_threshold_decrypted_shares::<T>(
+                vote_id, topic_id, threshold, start, end,
+            )?,
+            None => combine_decrypted_shares_of_all_sealers::<T>(
+                vote_id, topic_id, &big_p, start, end,
+            )?,
+        };
 
     // retrieve the plaintext votes
     // by combining the decrypted components a with their decrypted components b
@@ -67,27 +245,128 @@ pub fn combine_shares_and_tally_topic<T: Trait>(
         })
         .collect::<Vec<BigUint>>();
 
-    // if the votes were encoded, we need to decoded them (brute force dlog)
-    if encoded {
-        plaintexts = plaintexts
-            .iter()
-            .map(|encoded| ElGamal::decode_message(encoded, &big_g, &big_p))
-            .collect::<Vec<BigUint>>();
+    // if the votes were encoded, we need to decoded them (baby-step giant-step dlog)
+    // the homomorphic aggregate can only be computed on encoded votes
+    // and can never be larger than the total weight of the submitted votes
+    if encoded || homomorphic {
+        let bound = if homomorphic {
+            get_topic_weight::<T>(vote_id, topic_id)
+        } else {
+            DEFAULT_DECODE_BOUND
+        };
+        let bound_error = || {
+            if homomorphic {
+                Error::<T>::HomomorphicTallyInvalid
+            } else {
+                Error::<T>::DecodeBoundExceeded
+            }
+        };
+        let mut decoded: Vec<BigUint> = Vec::with_capacity(plaintexts.len());
+        for encoded in plaintexts.iter() {
+            let plaintext = ElGamal::decode_message_bsgs(encoded, &big_g, &big_p, bound)
+                .ok_or_else(bound_error)?;
+            decoded.push(plaintext);
+        }
+        plaintexts = decoded;
     }
 
-    // get the tally for the vote with topic id: topic_id
-    let tally: Option<TopicResult> = Tally::get::<&TopicId>(topic_id);
+    // count the number of ciphers per plaintext
+    PartialTallies::mutate((vote_id, topic_id), |partial: &mut PartialTally| {
+        for plaintext in plaintexts.iter() {
+            *partial.entry(plaintext.to_bytes_be()).or_default() += 1;
+        }
+    });
+    progress.position = end as u64;
+    Ok(())
+}
+
+/// combines the decrypted shares of the next batch of ciphers of the topic (the shuffle batch size)
+/// and adds the decoded plaintexts to the partial tally. the tally of the topic is started with
+/// the first batch. returns the progress of the tally.
+pub fn combine_shares_of_next_batch<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    encoded: bool,
+    nr_of_shuffles: &NrOfShuffles,
+) -> Result<TallyProgress, Error<T>> {
+    let mut progress =
+        get_or_start_tally::<T>(vote_id, topic_id, encoded, *nr_of_shuffles)?;
+    ensure!(!progress.is_complete(), Error::<T>::NoCiphersLeftToTally);
+
+    let batch_size = get_tally_batch_size::<T>(vote_id, topic_id);
+    combine_batch::<T>(vote_id, topic_id, &mut progress, batch_size)?;
+    TallyProgresses::insert((vote_id, topic_id), progress.clone());
+    Ok(progress)
+}
 
+/// counts the votes per voting option of the partial tally of the topic, once all batches
+/// have been combined, and stores the result. no cipher is decrypted.
+pub fn finalize_topic_tally<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+) -> Result<TopicResult, Error<T>> {
     // check that topic has not been tallied yet
-    ensure!(tally.is_none(), Error::<T>::TopicHasAlreadyBeenTallied);
+    ensure!(
+        !Tally::contains_key((vote_id, topic_id)),
+        Error::<T>::TopicHasAlreadyBeenTallied
+    );
+    let progress: TallyProgress =
+        TallyProgresses::get((vote_id, topic_id)).ok_or(Error::<T>::TallyIncomplete)?;
+    ensure!(progress.is_complete(), Error::<T>::TallyIncomplete);
+
+    // the plaintexts and the number of ciphers which decrypted to them
+    let partial: Vec<(BigUint, u64)> = PartialTallies::get((vote_id, topic_id))
+        .into_iter()
+        .map(|(plaintext, count)| (BigUint::from_bytes_be(&plaintext), count))
+        .collect();
+    let homomorphic = get_tally_strategy::<T>(vote_id) == TallyStrategy::Homomorphic;
+    let topic = get_topic(vote_id, topic_id);
 
     // count the number of votes per voting option
     // store result as a map -> key: voting option, value: count
-    let one = BigUint::one();
     let mut big_results: BTreeMap<BigUint, BigUint> = BTreeMap::new();
-    plaintexts
-        .into_iter()
-        .for_each(|item| *big_results.entry(item).or_default() += &one);
+    if homomorphic {
+        // the decrypted aggregate is the (weighted) number of yes votes (1)
+        // all remaining votes are no votes (0)
+        // the total weight of the submitted votes,
+        // i.e., the number of submitted votes if none of the voters is weighted
+        let total = BigUint::from(get_topic_weight::<T>(vote_id, topic_id));
+        let yes: BigUint = partial
+            .into_iter()
+            .map(|(value, count)| value * BigUint::from(count))
+            .sum();
+        ensure!(yes <= total, Error::<T>::HomomorphicTallyInvalid);
+        let no: BigUint = total - &yes;
+        if !no.is_zero() {
+            big_results.insert(BigUint::zero(), no);
+        }
+        if !yes.is_zero() {
+            big_results.insert(BigUint::one(), yes);
+        }
+    } else if let Some(packing) = get_topic_packing(vote_id, topic_id) {
+        // the answers of the packed questions are stored per slot (see: PackedTally),
+        // the result of the topic contains the messages which aren't packed answers (e.g. invalid messages)
+        big_results = count_packed_answers::<T>(vote_id, topic_id, &packing, partial)?;
+    } else if topic.requires_validity_proof()
+        && topic.ranked_tally != RankedTallyMethod::FirstPreference
+    {
+        // the rounds of an instant-runoff tally are stored for transparency
+        let (counts, rounds) = tally_rankings(&topic, partial);
+        if !rounds.is_empty() {
+            TallyRounds::insert::<(&VoteId, &TopicId), Vec<TallyRound>>(
+                (vote_id, topic_id),
+                rounds,
+            );
+        }
+        big_results = counts;
+    } else {
+        for (answer, count) in partial.into_iter() {
+            let count = BigUint::from(count);
+            for item in count_answer(&topic, answer).into_iter() {
+                *big_results.entry(item).or_default() += &count;
+            }
+        }
+    }
 
     // type conversion: BTreeMap<BigUint, BigUint> to BTreeMap<Vec<u8>, Vec<u8>>
     // to be able to store the results on chain
@@ -96,7 +375,86 @@ pub fn combine_shares_and_tally_topic<T: Trait>(
         results.insert(key.to_bytes_be(), value.to_bytes_be());
     }
 
-    // store the results on chain
-    Tally::insert::<&TopicId, TopicResult>(topic_id, results.clone());
+    // store the results on chain, the partial tally isn't needed anymore
+    Tally::insert::<(&VoteId, &TopicId), TopicResult>(
+        (vote_id, topic_id),
+        results.clone(),
+    );
+    TallyProgresses::remove((vote_id, topic_id));
+    PartialTallies::remove((vote_id, topic_id));
     Ok(results)
 }
+
+/// combines the decrypted shares of all remaining ciphers of the topic at once and finalizes the tally
+pub fn combine_shares_and_tally_topic<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    encoded: bool,
+    nr_of_shuffles: &NrOfShuffles,
+) -> Result<TopicResult, Error<T>> {
+    let mut progress =
+        get_or_start_tally::<T>(vote_id, topic_id, encoded, *nr_of_shuffles)?;
+    combine_batch::<T>(vote_id, topic_id, &mut progress, u64::max_value())?;
+    TallyProgresses::insert((vote_id, topic_id), progress);
+    finalize_topic_tally::<T>(vote_id, topic_id)
+}
+
+/// discards the ballots cast with an invalid credential before their ciphers are shuffled or
+/// aggregated: the combined blinded credential quotients (see: get_combined_credential_blindings)
+/// are decrypted with the partial decryptions of all sealers (or of any `threshold` sealers).
+/// a quotient which doesn't decrypt to 1 shows that the credential of the ballot isn't plaintext
+/// equivalent to the registered one (e.g. a fake credential handed to a coercer), the ciphers of
+/// the ballot are excluded (see: exclude_ballot_ciphers).
+/// returns the number of discarded ballots, None if not all partial decryptions have been submitted yet.
+pub fn discard_invalid_credential_ballots<T: Trait>(
+    vote_id: &VoteId,
+) -> Result<Option<u32>, Error<T>> {
+    let combined: Vec<BigCipher> = get_combined_credential_blindings::<T>(vote_id)?;
+    let params: PublicParameters = get_public_params::<T>(vote_id)?;
+    let big_p: BigUint = BigUint::from_bytes_be(&params.p);
+
+    // combine the partial decryptions of all sealers
+    // or of any `threshold` sealers (threshold keys)
+    let sealers: Vec<T::AccountId> = get_vote_sealers::<T>(vote_id);
+    let decrypted_as: Vec<BigUint> = match get_decryption_threshold::<T>(vote_id) {
+        Some(threshold) => {
+            let submitted = sealers
+                .iter()
+                .filter(|sealer| {
+                    CredentialDecryptions::<T>::contains_key(vote_id, sealer)
+                })
+                .count();
+            if submitted < threshold as usize {
+                return Ok(None);
+            }
+            combine_threshold_credential_decryptions::<T>(vote_id, threshold)?
+        }
+        None => {
+            let mut partial_decryptions: Vec<Vec<BigUint>> =
+                Vec::with_capacity(sealers.len());
+            for sealer in sealers.iter() {
+                match CredentialDecryptions::<T>::get(vote_id, sealer) {
+                    Some(shares) => partial_decryptions
+                        .push(shares.iter().map(|s| BigUint::from_bytes_be(s)).collect()),
+                    None => return Ok(None),
+                }
+            }
+            ElGamal::combine_partial_decrypted_as(partial_decryptions, &big_p)
+        }
+    };
+
+    // a plaintext of 1 shows that the ballot has been cast with the registered credential
+    let voters: Vec<T::AccountId> = CredentialBallots::<T>::get(vote_id);
+    let mut discarded: Vec<T::AccountId> = Vec::new();
+    let quotients = combined.iter().zip(decrypted_as.iter());
+    for (voter, (quotient, decrypted_a)) in voters.into_iter().zip(quotients) {
+        let plaintext = ElGamal::partial_decrypt_b(&quotient.b, decrypted_a, &big_p);
+        if !PlaintextEquivalenceProof::is_equivalent(&plaintext) {
+            exclude_ballot_ciphers::<T>(&voter, vote_id);
+            discarded.push(voter);
+        }
+    }
+    let nr_of_discarded = discarded.len() as u32;
+    DiscardedBallots::<T>::insert(vote_id, discarded);
+    Ok(Some(nr_of_discarded))
+}
@@ -0,0 +1,153 @@
+use crate::helpers::{params::get_public_params, sealers::get_vote_sealers};
+use crate::types::{PublicKeyShare, TopicId, VoteId};
+use crate::{
+    CredentialDecryptions, DecryptedShares, DecryptionThresholds, Error,
+    PublicKeyShareBySealer, Trait,
+};
+use crypto::encryption::ElGamal;
+use crypto::secret_sharing::SecretSharing;
+use crypto::types::{multi_modpow, ElGamalParams};
+use frame_support::{
+    ensure,
+    storage::{StorageDoubleMap, StorageMap},
+};
+use num_bigint::BigUint;
+use sp_std::vec::Vec;
+
+/// all functions related to threshold keys
+///
+/// with threshold keys, the private key share of the i-th sealer of the vote (starting at 1)
+/// is the point f(i) of a secret polynomial f of degree t - 1 with f(0) = sk, i.e., the i-th
+/// fragment of the secret key (Shamir secret sharing). the public key g^f(0) and the
+/// decryptions a^f(0) are interpolated in the exponent from the shares of any t sealers.
+///
+/// returns the decryption threshold of the vote, None if all sealers are required
+pub fn get_decryption_threshold<T: Trait>(vote_id: &VoteId) -> Option<u32> {
+    DecryptionThresholds::get(vote_id)
+}
+
+/// returns the sealers of the vote for which the predicate holds together with their index,
+/// i.e., the x-coordinate of their key share
+fn get_indexed_sealers<T: Trait>(
+    vote_id: &VoteId,
+    predicate: impl Fn(&T::AccountId) -> bool,
+) -> Vec<(u32, T::AccountId)> {
+    get_vote_sealers::<T>(vote_id)
+        .into_iter()
+        .enumerate()
+        .map(|(position, sealer)| (position as u32 + 1, sealer))
+        .filter(|(_, sealer)| predicate(sealer))
+        .collect()
+}
+
+/// returns the Lagrange coefficients of the key shares of the sealers and the group modulus p
+fn get_lagrange_coefficients<T: Trait>(
+    vote_id: &VoteId,
+    sealers: &[(u32, T::AccountId)],
+) -> Result<(Vec<BigUint>, BigUint), Error<T>> {
+    let params: ElGamalParams = get_public_params::<T>(vote_id)?.into();
+    let indices: Vec<u32> = sealers.iter().map(|(index, _)| *index).collect();
+    let coefficients = SecretSharing::lagrange_coefficients(&indices, &params.q())
+        .ok_or(Error::<T>::InvModError)?;
+    Ok((coefficients, params.p))
+}
+
+/// interpolates the public key h = g^f(0) from the public key shares g^f(i) of all sealers
+/// which submitted their public key share, requires the shares of at least `threshold` sealers.
+pub fn combine_threshold_public_key_shares<T: Trait>(
+    vote_id: &VoteId,
+    threshold: u32,
+) -> Result<BigUint, Error<T>> {
+    let sealers: Vec<(u32, T::AccountId)> = get_indexed_sealers::<T>(vote_id, |sealer| {
+        PublicKeyShareBySealer::<T>::contains_key((vote_id, sealer))
+    });
+    ensure!(
+        sealers.len() >= threshold as usize,
+        Error::<T>::NotEnoughPublicKeyShares
+    );
+
+    let shares: Vec<BigUint> = sealers
+        .iter()
+        .filter_map(|(_, sealer)| PublicKeyShareBySealer::<T>::get((vote_id, sealer)))
+        .map(|share: PublicKeyShare| BigUint::from_bytes_be(&share.pk))
+        .collect();
+    let (coefficients, p) = get_lagrange_coefficients::<T>(vote_id, &sealers)?;
+    let bases: Vec<&BigUint> = shares.iter().collect();
+    let exponents: Vec<&BigUint> = coefficients.iter().collect();
+    Ok(multi_modpow(&bases, &exponents, &p))
+}
+
+/// interpolates the decrypted components a^f(0) of the ciphers in the range [start, end)
+/// from the decrypted shares a^f(i) of the first `threshold` sealers which submitted
+/// their decrypted shares for the topic.
+pub fn combine_threshold_decrypted_shares<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    threshold: u32,
+    start: usize,
+    end: usize,
+) -> Result<Vec<BigUint>, Error<T>> {
+    let sealers: Vec<(u32, T::AccountId)> = get_indexed_sealers::<T>(vote_id, |sealer| {
+        !DecryptedShares::<T>::get((vote_id, topic_id), sealer).is_empty()
+    })
+    .into_iter()
+    .take(threshold as usize)
+    .collect();
+    ensure!(
+        sealers.len() == threshold as usize,
+        Error::<T>::NotEnoughDecryptedShares
+    );
+
+    // type conversion: DecryptedShare (Vec<u8>) to BigUint
+    let mut partial_decryptions: Vec<Vec<BigUint>> = Vec::with_capacity(sealers.len());
+    for (_, sealer) in sealers.iter() {
+        let shares = DecryptedShares::<T>::get((vote_id, topic_id), sealer);
+        let shares = shares
+            .get(start..end)
+            .ok_or(Error::<T>::NotEnoughDecryptedShares)?;
+        partial_decryptions
+            .push(shares.iter().map(|s| BigUint::from_bytes_be(s)).collect());
+    }
+    let (coefficients, p) = get_lagrange_coefficients::<T>(vote_id, &sealers)?;
+    Ok(ElGamal::combine_threshold_partial_decrypted_as(
+        partial_decryptions,
+        &coefficients,
+        &p,
+    ))
+}
+
+/// interpolates the decrypted components a^f(0) of the combined blinded credential quotients
+/// from the partial decryptions of the first `threshold` sealers which submitted theirs.
+pub fn combine_threshold_credential_decryptions<T: Trait>(
+    vote_id: &VoteId,
+    threshold: u32,
+) -> Result<Vec<BigUint>, Error<T>> {
+    let sealers: Vec<(u32, T::AccountId)> = get_indexed_sealers::<T>(vote_id, |sealer| {
+        CredentialDecryptions::<T>::contains_key(vote_id, sealer)
+    })
+    .into_iter()
+    .take(threshold as usize)
+    .collect();
+    ensure!(
+        sealers.len() == threshold as usize,
+        Error::<T>::NotEnoughDecryptedShares
+    );
+
+    // type conversion: DecryptedShare (Vec<u8>) to BigUint
+    let partial_decryptions: Vec<Vec<BigUint>> = sealers
+        .iter()
+        .map(|(_, sealer)| {
+            CredentialDecryptions::<T>::get(vote_id, sealer)
+                .unwrap_or_default()
+                .iter()
+                .map(|s| BigUint::from_bytes_be(s))
+                .collect()
+        })
+        .collect();
+    let (coefficients, p) = get_lagrange_coefficients::<T>(vote_id, &sealers)?;
+    Ok(ElGamal::combine_threshold_partial_decrypted_as(
+        partial_decryptions,
+        &coefficients,
+        &p,
+    ))
+}
@@ -1,13 +1,24 @@
-use crate::helpers::params::get_public_params;
+use super::aggregate::get_ciphers_to_decrypt;
+use crate::helpers::{
+    params::{get_hash_function, get_public_params},
+    progress::get_required_share_count,
+    sealers::get_vote_sealers,
+    transcripts::{hash_transcript, log_proof},
+};
 use crate::types::{
-    Cipher, DecryptedShare, DecryptedShareProof, NrOfShuffles, PublicKeyShare,
-    PublicKeyShareProof, PublicParameters, TopicId, VoteId, Wrapper,
+    Cipher, DecryptedShare, DecryptedShareProof, DecryptionAuditEntry, NrOfShuffles,
+    ProofKind, PublicKeyShare, PublicKeyShareProof, PublicParameters, TopicId, VoteId,
+    Wrapper,
 };
 use crate::{
-    Ciphers, DecryptedShares, Error, PublicKeyShareBySealer, PublicKeyShares, Trait,
+    DecryptedShares, DecryptionAudit, Error, Module, PublicKeyShareBySealer,
+    PublicKeyShares, Trait,
 };
 use codec::Encode;
-use crypto::proofs::{decryption::DecryptionProof, keygen::KeyGenerationProof};
+use crypto::proofs::{
+    decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
+    keygen::KeyGenerationProof,
+};
 use crypto::types::Cipher as BigCipher;
 use frame_support::{
     debug, ensure,
@@ -32,12 +43,17 @@ pub fn verify_proof_and_store_keygen_share<T: Trait>(
     // get the public parameters
     let params: PublicParameters = get_public_params::<T>(&vote_id)?;
 
-    // verify the public key share proof
+    // verify the public key share proof, the challenge uses the hash function of the vote
     let sealer_id = who.encode();
     let proof: PublicKeyShareProof = pk_share.proof.clone();
     let pk: BigUint = BigUint::from_bytes_be(&pk_share.pk);
-    let proof_valid =
-        KeyGenerationProof::verify(&params.into(), &pk, &proof.into(), &sealer_id);
+    let proof_valid = KeyGenerationProof::verify_with_hash(
+        &params.into(),
+        &pk,
+        &proof.into(),
+        &sealer_id,
+        get_hash_function::<T>(vote_id),
+    );
     ensure!(proof_valid, Error::<T>::PublicKeyShareProofError);
 
     // store the public key share
@@ -63,9 +79,19 @@ pub fn verify_proof_and_store_decrypted_share<T: Trait>(
     let sealer_pk_share: PublicKeyShare = get_public_keyshare::<T>(vote_id, &who)?;
     let sealer_pk: BigUint = BigUint::from_bytes_be(&sealer_pk_share.pk);
 
-    // get all encrypted votes (ciphers)
+    // get the encrypted votes (ciphers) to decrypt
     // for the topic with id: topic_id and the # of shuffles (nr_of_shuffles)
-    let ciphers: Vec<Cipher> = Ciphers::get(topic_id, nr_of_shuffles);
+    let ciphers: Vec<Cipher> =
+        get_ciphers_to_decrypt::<T>(vote_id, topic_id, *nr_of_shuffles)?;
+
+    // the transcript: the decrypted ciphers, the decrypted shares and the proof
+    let transcript_hash = hash_transcript(
+        vote_id,
+        topic_id,
+        *nr_of_shuffles,
+        &ciphers,
+        &(&shares, &proof),
+    );
 
     // type conversion: Vec<Cipher> (Vec<Vec<u8>>) to Vec<BigCipher> (Vec<BigUint>)
     let big_ciphers: Vec<BigCipher> = Wrapper(ciphers).into();
@@ -76,20 +102,45 @@ pub fn verify_proof_and_store_decrypted_share<T: Trait>(
         .map(|s| BigUint::from_bytes_be(s))
         .collect::<Vec<BigUint>>();
 
-    // verify the proof using the sealer's public key share
-    let is_valid: bool = DecryptionProof::verify(
+    // verify the (batched) proof using the sealer's public key share
+    let is_valid: bool = DecryptionProof::verify_batched_with_hash(
         &params.into(),
         &sealer_pk,
-        &proof.into(),
-        big_ciphers,
-        decrypted_shares,
+        &proof.clone().into(),
+        &big_ciphers,
+        &decrypted_shares,
         sealer_id,
+        DEFAULT_BATCH_SOUNDNESS,
+        get_hash_function::<T>(vote_id),
     );
     ensure!(is_valid, Error::<T>::DecryptedShareProofError);
 
+    // add the submission to the audit trail
+    let entry = DecryptionAuditEntry {
+        sealer: who.clone(),
+        shares_hash: hash_decrypted_shares(&shares),
+        proof,
+    };
+    let mut audit: Vec<DecryptionAuditEntry<T::AccountId>> =
+        DecryptionAudit::<T>::get((vote_id, topic_id), nr_of_shuffles);
+    if !audit.contains(&entry) {
+        audit.push(entry);
+        DecryptionAudit::<T>::insert((vote_id, topic_id), nr_of_shuffles, audit);
+        log_proof::<T>(
+            vote_id,
+            topic_id,
+            ProofKind::Decryption,
+            *nr_of_shuffles,
+            &who,
+            transcript_hash,
+        );
+    }
+
     // store the decrypted shares
-    let mut stored: Vec<DecryptedShare> =
-        DecryptedShares::<T>::get::<&TopicId, &T::AccountId>(topic_id, &who);
+    let mut stored: Vec<DecryptedShare> = DecryptedShares::<T>::get::<
+        (&VoteId, &TopicId),
+        &T::AccountId,
+    >((vote_id, topic_id), &who);
 
     // check if the share has been already submitted. if not, store it.
     for share in shares.iter() {
@@ -99,6 +150,88 @@ pub fn verify_proof_and_store_decrypted_share<T: Trait>(
     }
 
     // store the decrypted shares per topic and sealer
-    DecryptedShares::<T>::insert(topic_id, &who, stored);
+    DecryptedShares::<T>::insert((vote_id, topic_id), &who, stored);
     Ok(())
 }
+
+fn hash_decrypted_shares(shares: &[DecryptedShare]) -> [u8; 32] {
+    sp_io::hashing::blake2_256(&shares.encode())
+}
+
+/// verifies the audit trail of all partial decryptions of a topic.
+/// checks that every sealer (or the decryption threshold of the vote with threshold keys)
+/// has submitted its partial decryptions, that the stored
+/// shares match the hashes in the audit trail and that all proofs are valid.
+pub fn verify_full_decryption_chain<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    nr_of_shuffles: &NrOfShuffles,
+) -> Result<bool, Error<T>> {
+    let params: PublicParameters = get_public_params::<T>(vote_id)?;
+    let hash_function = get_hash_function::<T>(vote_id);
+    let audit: Vec<DecryptionAuditEntry<T::AccountId>> =
+        DecryptionAudit::<T>::get((vote_id, topic_id), nr_of_shuffles);
+
+    // every sealer (or the threshold of the vote) must have submitted its partial decryptions
+    let sealers: Vec<T::AccountId> = get_vote_sealers::<T>(vote_id);
+    let submitted = sealers
+        .iter()
+        .filter(|sealer| audit.iter().any(|entry| &entry.sealer == *sealer))
+        .count() as u32;
+    if submitted < get_required_share_count::<T>(vote_id) {
+        return Ok(false);
+    }
+
+    // get the encrypted votes (ciphers) to decrypt
+    // for the topic with id: topic_id and the # of shuffles (nr_of_shuffles)
+    let ciphers: Vec<Cipher> =
+        get_ciphers_to_decrypt::<T>(vote_id, topic_id, *nr_of_shuffles)?;
+    let big_ciphers: Vec<BigCipher> = Wrapper(ciphers).into();
+
+    for entry in audit.into_iter() {
+        // the stored shares must match the audited shares
+        let shares: Vec<DecryptedShare> = DecryptedShares::<T>::get::<
+            (&VoteId, &TopicId),
+            &T::AccountId,
+        >((vote_id, topic_id), &entry.sealer);
+        if hash_decrypted_shares(&shares) != entry.shares_hash {
+            return Ok(false);
+        }
+
+        // re-verify the proof using the sealer's public key share
+        let sealer_pk_share: PublicKeyShare =
+            get_public_keyshare::<T>(vote_id, &entry.sealer)?;
+        let sealer_pk: BigUint = BigUint::from_bytes_be(&sealer_pk_share.pk);
+        let decrypted_shares: Vec<BigUint> = shares
+            .iter()
+            .map(|s| BigUint::from_bytes_be(s))
+            .collect::<Vec<BigUint>>();
+        let is_valid: bool = DecryptionProof::verify_batched_with_hash(
+            &params.clone().into(),
+            &sealer_pk,
+            &entry.proof.into(),
+            &big_ciphers,
+            &decrypted_shares,
+            &entry.sealer.encode(),
+            DEFAULT_BATCH_SOUNDNESS,
+            hash_function,
+        );
+        if !is_valid {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+impl<T: Trait> Module<T> {
+    /// verifies the decryption audit trail of a topic.
+    /// returns false if the chain is incomplete, invalid or the vote doesn't exist.
+    pub fn verify_full_decryption_chain(
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+        nr_of_shuffles: &NrOfShuffles,
+    ) -> bool {
+        verify_full_decryption_chain::<T>(vote_id, topic_id, nr_of_shuffles)
+            .unwrap_or(false)
+    }
+}
@@ -0,0 +1,146 @@
+use super::{
+    ciphers::get_all_ciphers,
+    indexing::remove_shuffle_state,
+    merkle::{hash_leaf, merkle_root},
+    sealers::get_vote_sealers,
+};
+use crate::types::{Topic, TopicId, Vote, VoteArchive, VoteId, VotePhase};
+use crate::{
+    AdditionalWeights, AggregateCiphers, ArchivedVotes, BallotReceipts, Ballots,
+    CancellationApprovals, CipherChunkCount, CipherCounts, CipherHashes, CipherPeaks,
+    CipherRoots, Ciphers, CredentialBallots, CredentialBlindings, CredentialDecryptions,
+    Credentials, DecryptedShares, DecryptionAudit, DecryptionThresholds, EligibilityKeys,
+    Error, GroupChecks, IdentityProviders, KeySwitchAudit, KeySwitchShares,
+    LinkedShuffles, PartialTallies, PendingDecryptions, PendingKeySwitch,
+    PendingResultApprovals, PreviousPublicKeyShareBySealer, PublicKeyShareBySealer,
+    PublicKeyShares, RegisteredVoters, RevokedVoters, ShuffleBatchSizes,
+    ShuffleCommitments, ShuffleProofs, SpentNullifiers, Tally, TallyProgresses,
+    TallyingApprovals, Topics, Trait, VoterWeights, Votes, WhitelistedVotes,
+};
+use frame_support::{
+    ensure,
+    storage::{StorageDoubleMap, StorageMap},
+};
+use sp_std::vec::Vec;
+
+/// all functions related to the archival of finished votes
+///
+/// returns the hashes of all ciphers and proofs of the vote in a deterministic order.
+/// per topic: the ciphers of every shuffle iteration, the aggregate cipher,
+/// the shuffle proofs, the decryption audit trail and the key switch audit trail.
+pub fn get_archive_leaves<T: Trait>(vote_id: &VoteId) -> Vec<[u8; 32]> {
+    let vote: Vote<T::AccountId> = Votes::<T>::get(vote_id);
+    let topics: Vec<Topic> = Topics::get(vote_id);
+    let mut leaves: Vec<[u8; 32]> = Vec::new();
+
+    for Topic { id: topic_id, .. } in topics.iter() {
+        for iteration in 0..=vote.nr_of_shuffles {
+            let ciphers = get_all_ciphers::<T>(vote_id, topic_id, iteration);
+            leaves.extend(ciphers.iter().map(hash_leaf));
+        }
+        if let Some(aggregate) = AggregateCiphers::get((vote_id, topic_id)) {
+            leaves.push(hash_leaf(&aggregate));
+        }
+        let shuffle_proofs = ShuffleProofs::get((vote_id, topic_id));
+        leaves.extend(shuffle_proofs.iter().map(hash_leaf));
+        for iteration in 0..=vote.nr_of_shuffles {
+            let audit_trail = DecryptionAudit::<T>::get((vote_id, topic_id), iteration);
+            leaves.extend(audit_trail.iter().map(hash_leaf));
+        }
+        let key_switch_trail = KeySwitchAudit::<T>::get((vote_id, topic_id));
+        leaves.extend(key_switch_trail.iter().map(hash_leaf));
+    }
+    leaves
+}
+
+/// removes the ballots, ciphers, proofs and key shares of the vote.
+/// the vote, its topics, public key, (certified) results, cipher imports, proof log
+/// and unclaimed ballot deposits are kept.
+fn clear_vote_storage<T: Trait>(vote_id: &VoteId, topics: &[Topic]) {
+    Ballots::<T>::remove_prefix(vote_id);
+    BallotReceipts::<T>::remove_prefix(vote_id);
+    Credentials::<T>::remove_prefix(vote_id);
+    CredentialBallots::<T>::remove(vote_id);
+    CredentialBlindings::<T>::remove_prefix(vote_id);
+    CredentialDecryptions::<T>::remove_prefix(vote_id);
+    RegisteredVoters::<T>::remove_prefix(vote_id);
+    RevokedVoters::<T>::remove_prefix(vote_id);
+    SpentNullifiers::remove_prefix(vote_id);
+
+    for Topic { id: topic_id, .. } in topics.iter() {
+        clear_topic_storage::<T>(vote_id, topic_id);
+    }
+
+    // the vote keeps its sealer epoch, i.e., its sealers can still be audited
+    for sealer in get_vote_sealers::<T>(vote_id).iter() {
+        PublicKeyShareBySealer::<T>::remove((vote_id, sealer));
+        PreviousPublicKeyShareBySealer::<T>::remove((vote_id, sealer));
+    }
+    PublicKeyShares::remove(vote_id);
+    PendingKeySwitch::remove(vote_id);
+    ShuffleBatchSizes::remove(vote_id);
+    LinkedShuffles::remove(vote_id);
+    GroupChecks::remove(vote_id);
+    DecryptionThresholds::remove(vote_id);
+    WhitelistedVotes::remove(vote_id);
+    IdentityProviders::remove(vote_id);
+    EligibilityKeys::remove(vote_id);
+    VoterWeights::<T>::remove_prefix(vote_id);
+    PendingResultApprovals::<T>::remove(vote_id);
+    TallyingApprovals::<T>::remove(vote_id);
+    CancellationApprovals::<T>::remove(vote_id);
+}
+
+fn clear_topic_storage<T: Trait>(vote_id: &VoteId, topic_id: &TopicId) {
+    Ciphers::remove_prefix((vote_id, topic_id));
+    CipherChunkCount::remove_prefix((vote_id, topic_id));
+    CipherCounts::remove_prefix((vote_id, topic_id));
+    CipherRoots::remove_prefix((vote_id, topic_id));
+    CipherPeaks::remove_prefix((vote_id, topic_id));
+    CipherHashes::remove_prefix((vote_id, topic_id));
+    AggregateCiphers::remove((vote_id, topic_id));
+    AdditionalWeights::remove((vote_id, topic_id));
+    ShuffleProofs::remove((vote_id, topic_id));
+    remove_shuffle_state(vote_id, topic_id);
+    ShuffleCommitments::<T>::remove((vote_id, topic_id));
+    DecryptedShares::<T>::remove_prefix((vote_id, topic_id));
+    DecryptionAudit::<T>::remove_prefix((vote_id, topic_id));
+    PendingDecryptions::<T>::remove((vote_id, topic_id));
+    TallyProgresses::remove((vote_id, topic_id));
+    PartialTallies::remove((vote_id, topic_id));
+    KeySwitchShares::<T>::remove_prefix((vote_id, topic_id));
+    KeySwitchAudit::<T>::remove((vote_id, topic_id));
+}
+
+/// replaces the ciphers and proofs of a tallied (or cancelled) vote by their merkle root.
+pub fn archive_ciphers_and_proofs<T: Trait>(
+    vote_id: &VoteId,
+) -> Result<VoteArchive, Error<T>> {
+    ensure!(
+        !ArchivedVotes::contains_key(vote_id),
+        Error::<T>::VoteAlreadyArchived
+    );
+
+    // the results of all topics must be published, unless the vote has been cancelled
+    let topics: Vec<Topic> = Topics::get(vote_id);
+    let cancelled = Votes::<T>::get(vote_id).phase == VotePhase::Cancelled;
+    ensure!(
+        cancelled
+            || topics
+                .iter()
+                .all(|Topic { id: topic_id, .. }| Tally::contains_key((
+                    vote_id, topic_id
+                ))),
+        Error::<T>::VoteNotTallied
+    );
+
+    // commit to the ciphers and proofs before pruning them
+    let leaves = get_archive_leaves::<T>(vote_id);
+    let archive = VoteArchive {
+        nr_of_leaves: leaves.len() as u64,
+        merkle_root: merkle_root(leaves),
+    };
+    clear_vote_storage::<T>(vote_id, &topics);
+    ArchivedVotes::insert(vote_id, archive.clone());
+    Ok(archive)
+}
@@ -1,8 +1,21 @@
+use super::{
+    params::get_public_params,
+    sealers::{get_vote_sealers, is_sealer, is_vote_sealer},
+};
 use crate::{
-    types::{VoteId, VotePhase},
-    Error, Module, Trait, Votes,
+    types::{
+        Cipher, TallyStrategy, Title, Topic, VoteId, VoteMetadata, VotePhase,
+        MAX_CANCELLATION_REASON_LENGTH,
+    },
+    ArchivedVotes, Error, GroupChecks, Module, PendingKeySwitch, Trait, Votes,
+    VotingStarted,
+};
+use crypto::types::{Cipher as BigCipher, ElGamalParams, Group};
+use frame_support::{
+    debug, ensure,
+    storage::{StorageMap, StorageValue},
+    traits::Get,
 };
-use frame_support::{debug, ensure, storage::StorageMap};
 
 pub fn ensure_voting_authority<T: Trait>(
     account_id: &T::AccountId,
@@ -30,9 +43,9 @@ pub fn ensure_not_a_voting_authority<T: Trait>(
     }
 }
 
+/// the account must be a sealer of new votes or of a vote which is being tallied
 pub fn ensure_sealer<T: Trait>(account_id: &T::AccountId) -> Result<(), Error<T>> {
-    let sealers = Module::<T>::sealers();
-    match sealers.contains(account_id) {
+    match is_sealer::<T>(account_id) {
         true => Ok(()),
         false => {
             debug::info!("Requester: {:?} is not a sealer!", account_id);
@@ -41,6 +54,24 @@ pub fn ensure_sealer<T: Trait>(account_id: &T::AccountId) -> Result<(), Error<T>
     }
 }
 
+/// the account must be a sealer of the vote, i.e., of the epoch the vote has been created in
+pub fn ensure_vote_sealer<T: Trait>(
+    account_id: &T::AccountId,
+    vote_id: &VoteId,
+) -> Result<(), Error<T>> {
+    match is_vote_sealer::<T>(account_id, vote_id) {
+        true => Ok(()),
+        false => {
+            debug::info!(
+                "Requester: {:?} is not a sealer of vote: {:?}!",
+                account_id,
+                vote_id
+            );
+            Err(Error::<T>::NotASealer)
+        }
+    }
+}
+
 pub fn ensure_vote_exists<T: Trait>(vote_id: &VoteId) -> Result<(), Error<T>> {
     // check that the vote_id exists
     ensure!(
@@ -68,3 +99,186 @@ pub fn ensure_vote_phase<T: Trait>(
     ensure!(vote.phase == phase, Error::<T>::WrongVotePhase);
     Ok(())
 }
+
+pub fn ensure_valid_phase_transition<T: Trait>(
+    vote_id: &VoteId,
+    phase: &VotePhase,
+) -> Result<(), Error<T>> {
+    // e.g. a vote which is being tallied can't return to the voting phase
+    ensure!(
+        Votes::<T>::get(vote_id).phase.can_transition_to(phase),
+        Error::<T>::InvalidPhaseTransition
+    );
+    Ok(())
+}
+
+pub fn ensure_no_key_switch_pending<T: Trait>(vote_id: &VoteId) -> Result<(), Error<T>> {
+    // the ciphers of all topics must be encrypted under the current public key
+    ensure!(
+        PendingKeySwitch::get(vote_id).is_empty(),
+        Error::<T>::KeyRotationPending
+    );
+    Ok(())
+}
+
+pub fn ensure_vote_not_archived<T: Trait>(vote_id: &VoteId) -> Result<(), Error<T>> {
+    // the ciphers and proofs of an archived vote have been pruned
+    ensure!(
+        !ArchivedVotes::contains_key(vote_id),
+        Error::<T>::VoteAlreadyArchived
+    );
+    Ok(())
+}
+
+pub fn ensure_vote_ended<T: Trait>(vote_id: &VoteId) -> Result<(), Error<T>> {
+    // the voting phase is over once the vote is being tallied or has been cancelled
+    let phase = Votes::<T>::get(vote_id).phase;
+    ensure!(
+        phase == VotePhase::Tallying || phase == VotePhase::Cancelled,
+        Error::<T>::WrongVotePhase
+    );
+    Ok(())
+}
+
+pub fn ensure_vote_not_cancelled<T: Trait>(vote_id: &VoteId) -> Result<(), Error<T>> {
+    // a cancelled vote can only be archived and its ballot deposits refunded
+    ensure!(
+        Votes::<T>::get(vote_id).phase != VotePhase::Cancelled,
+        Error::<T>::VoteCancelled
+    );
+    Ok(())
+}
+
+pub fn ensure_valid_cancellation_reason<T: Trait>(reason: &[u8]) -> Result<(), Error<T>> {
+    ensure!(
+        reason.len() <= MAX_CANCELLATION_REASON_LENGTH,
+        Error::<T>::CancellationReasonTooLong
+    );
+    Ok(())
+}
+
+pub fn ensure_vote_not_started<T: Trait>(vote_id: &VoteId) -> Result<(), Error<T>> {
+    // the vote is frozen once the voting phase has started, even if its public key is rotated later
+    ensure!(!VotingStarted::get(vote_id), Error::<T>::VoteAlreadyStarted);
+    Ok(())
+}
+
+pub fn ensure_valid_vote_id<T: Trait>(vote_id: &VoteId) -> Result<(), Error<T>> {
+    ensure!(
+        vote_id.len() <= T::MaxVoteIdLength::get() as usize,
+        Error::<T>::VoteIdTooLong
+    );
+    Ok(())
+}
+
+pub fn ensure_valid_title<T: Trait>(title: &Title) -> Result<(), Error<T>> {
+    ensure!(
+        title.len() <= T::MaxTitleLength::get() as usize,
+        Error::<T>::TitleTooLong
+    );
+    Ok(())
+}
+
+pub fn ensure_valid_topic<T: Trait>(
+    topic: &Topic,
+    tally_strategy: &TallyStrategy,
+) -> Result<(), Error<T>> {
+    ensure!(
+        topic.id.len() <= T::MaxTopicIdLength::get() as usize,
+        Error::<T>::TopicIdTooLong
+    );
+    let max_question_length = T::MaxQuestionLength::get() as usize;
+    ensure!(
+        topic.question.len() <= max_question_length
+            && topic
+                .candidates
+                .iter()
+                .all(|candidate| candidate.len() <= max_question_length),
+        Error::<T>::QuestionTooLong
+    );
+    ensure!(topic.is_valid(), Error::<T>::InvalidQuestionType);
+    // the homomorphic tally only sums up the answers, i.e., only yes/no questions are supported
+    ensure!(
+        *tally_strategy != TallyStrategy::Homomorphic || !topic.requires_validity_proof(),
+        Error::<T>::InvalidQuestionType
+    );
+    Ok(())
+}
+
+/// the components of the ciphers are group elements, i.e. (big-endian) at most as long as the modulus
+/// of the vote's public parameters. rejects oversized ciphers before any proof is verified.
+pub fn ensure_valid_ciphers<'a, T: Trait>(
+    vote_id: &VoteId,
+    ciphers: impl IntoIterator<Item = &'a Cipher>,
+) -> Result<(), Error<T>> {
+    let modulus_length = Votes::<T>::get(vote_id).params.p.len();
+    let is_valid = |value: &[u8]| !value.is_empty() && value.len() <= modulus_length;
+    for cipher in ciphers.into_iter() {
+        ensure!(
+            is_valid(&cipher.a) && is_valid(&cipher.b),
+            Error::<T>::CipherMalformed
+        );
+    }
+    Ok(())
+}
+
+/// ensures that both components of the ciphers are elements of the group of the vote,
+/// i.e., quadratic residues modulo p: a^q mod p == 1 and b^q mod p == 1
+pub fn ensure_ciphers_in_group<'a, T: Trait>(
+    vote_id: &VoteId,
+    ciphers: impl IntoIterator<Item = &'a Cipher>,
+) -> Result<(), Error<T>> {
+    let params: ElGamalParams = get_public_params::<T>(vote_id)?.into();
+    for cipher in ciphers.into_iter() {
+        let cipher: BigCipher = cipher.clone().into();
+        ensure!(
+            params.is_element(&cipher.a) && params.is_element(&cipher.b),
+            Error::<T>::CipherNotInGroup
+        );
+    }
+    Ok(())
+}
+
+/// ensures that the ciphers of a ballot are elements of the group of the vote if the
+/// voting authority enabled the group check of the vote, see: GroupChecks
+pub fn ensure_group_check<'a, T: Trait>(
+    vote_id: &VoteId,
+    ciphers: impl IntoIterator<Item = &'a Cipher>,
+) -> Result<(), Error<T>> {
+    if !GroupChecks::get(vote_id) {
+        return Ok(());
+    }
+    ensure_ciphers_in_group::<T>(vote_id, ciphers)
+}
+
+pub fn ensure_valid_metadata<T: Trait>(metadata: &VoteMetadata) -> Result<(), Error<T>> {
+    ensure!(
+        metadata.size() <= T::MaxMetadataSize::get() as usize,
+        Error::<T>::MetadataTooLarge
+    );
+    Ok(())
+}
+
+pub fn ensure_valid_shuffle_batch_size<T: Trait>(
+    batch_size: u64,
+) -> Result<(), Error<T>> {
+    ensure!(
+        batch_size >= T::MinShuffleBatchSize::get()
+            && batch_size <= T::MaxShuffleBatchSize::get(),
+        Error::<T>::InvalidShuffleBatchSize
+    );
+    Ok(())
+}
+
+/// a threshold requires the shares of at least one and at most all sealers of the vote
+pub fn ensure_valid_decryption_threshold<T: Trait>(
+    vote_id: &VoteId,
+    threshold: u32,
+) -> Result<(), Error<T>> {
+    let nr_of_sealers = get_vote_sealers::<T>(vote_id).len() as u32;
+    ensure!(
+        threshold >= 1 && threshold <= nr_of_sealers,
+        Error::<T>::InvalidDecryptionThreshold
+    );
+    Ok(())
+}
@@ -1,25 +1,329 @@
-use crate::types::{Ballot, Cipher, VoteId};
-use crate::{Ballots, Ciphers, Trait};
-use frame_support::storage::StorageDoubleMap;
-use sp_std::vec::Vec;
+use super::{
+    ciphers::{append_ciphers, remove_cipher},
+    params::{get_public_key, get_public_params},
+    voter_weights::{add_cipher_weight, remove_cipher_weight},
+};
+use crate::types::{
+    Ballot, BallotReceipt, Cipher, SpoiledBallot, SpoiledBallotReveal, Topic, TopicId,
+    VoteId,
+};
+use crate::{
+    BallotReceipts, Ballots, CipherHashes, Error, Module, SpoiledBallots, Topics, Trait,
+    VoterWeights,
+};
+use codec::{Decode, Encode};
+use crypto::encryption::ElGamal;
+use crypto::proofs::{encryption::EncryptionProof, membership::MembershipProof};
+use crypto::types::{Cipher as BigCipher, ElGamalParams, PublicKey as ElGamalPK};
+use frame_support::{
+    ensure,
+    storage::{StorageDoubleMap, StorageMap},
+};
+use mixnet_primitives::relay::relayed_ballot_message;
+use num_bigint::BigUint;
+use sp_core::sr25519;
+use sp_std::{vec, vec::Vec};
 
 const INITIAL_NUMBER_OF_SHUFFLES: u8 = 0;
 
+fn hash_cipher(cipher: &Cipher) -> [u8; 32] {
+    sp_io::hashing::blake2_256(&cipher.encode())
+}
+
+/// ensures that none of the ciphers of the ballot has been submitted before,
+/// neither by another voter nor within the ballot itself.
+pub fn ensure_unique_ciphers<T: Trait>(
+    vote_id: &VoteId,
+    ballot: &Ballot,
+) -> Result<(), Error<T>> {
+    let mut hashes: Vec<[u8; 32]> = Vec::with_capacity(ballot.answers.len());
+    for (topic_id, cipher) in ballot.answers.iter() {
+        let hash = hash_cipher(cipher);
+        ensure!(
+            !CipherHashes::contains_key((vote_id, topic_id), hash)
+                && !hashes.contains(&hash),
+            Error::<T>::DuplicateCipher
+        );
+        hashes.push(hash);
+    }
+    Ok(())
+}
+
+/// verifies that the relayed ballot is signed by the voter and returns the voter's account,
+/// i.e., the account of the voter's sr25519 public key.
+pub fn verify_relayed_ballot<T: Trait>(
+    vote_id: &VoteId,
+    ballot: &Ballot,
+    voter: &sr25519::Public,
+    signature: &sr25519::Signature,
+) -> Result<T::AccountId, Error<T>> {
+    let message = relayed_ballot_message(vote_id, &ballot.encode());
+    ensure!(
+        sp_io::crypto::sr25519_verify(signature, &message, voter),
+        Error::<T>::RelayedBallotSignatureError
+    );
+    T::AccountId::decode(&mut &voter.0[..])
+        .map_err(|_| Error::<T>::RelayedBallotSignatureError)
+}
+
+/// verifies the proofs of knowledge of the randomness of the ciphers,
+/// the voter's account id is the context of the proofs.
+/// ballots without proofs are accepted, e.g., ballots re-randomized by the randomizer.
+pub fn verify_ballot_proofs<T: Trait>(
+    from: &T::AccountId,
+    vote_id: &VoteId,
+    ballot: &Ballot,
+) -> Result<(), Error<T>> {
+    verify_ballot_proofs_in_context::<T>(&from.encode(), vote_id, ballot)
+}
+
+/// verifies the proofs of knowledge of the randomness of the ciphers in the context of the voter,
+/// i.e., the encoded account id or the nullifier of an anonymous ballot.
+pub fn verify_ballot_proofs_in_context<T: Trait>(
+    voter_id: &[u8],
+    vote_id: &VoteId,
+    ballot: &Ballot,
+) -> Result<(), Error<T>> {
+    if ballot.proofs.is_empty() {
+        return Ok(());
+    }
+    ensure!(
+        ballot.proofs.len() == ballot.answers.len(),
+        Error::<T>::BallotProofError
+    );
+
+    let params: ElGamalParams = get_public_params::<T>(vote_id)?.into();
+    for ((_, cipher), proof) in ballot.answers.iter().zip(ballot.proofs.iter()) {
+        let cipher: BigCipher = cipher.clone().into();
+        let proof: EncryptionProof = proof.clone().into();
+        ensure!(
+            EncryptionProof::verify(&params, &cipher, &proof, voter_id),
+            Error::<T>::BallotProofError
+        );
+    }
+    Ok(())
+}
+
+/// returns the valid messages of an answer to the topic, i.e., the messages of its validity proof.
+/// a weighted voter answers with 0 (no) or its weight (yes), see: voter_weights.
+fn valid_messages(topic: &Topic, weight: Option<u32>) -> Vec<u64> {
+    match weight {
+        Some(weight) => vec![0, weight as u64],
+        None => topic.valid_answers(),
+    }
+}
+
+/// verifies that the answers of all questions with candidates are valid answers,
+/// i.e., every such answer requires a validity proof (bound to the voter's account id).
+/// all answers of a weighted voter require a validity proof for its registered weight.
+/// answers of free-form questions don't need a validity proof.
+pub fn verify_ballot_validity_proofs<T: Trait>(
+    from: &T::AccountId,
+    vote_id: &VoteId,
+    ballot: &Ballot,
+) -> Result<(), Error<T>> {
+    let weight: Option<u32> = VoterWeights::<T>::get(vote_id, from);
+    verify_ballot_validity_proofs_in_context::<T>(&from.encode(), weight, vote_id, ballot)
+}
+
+/// verifies the validity proofs in the context of the voter (see: verify_ballot_proofs_in_context)
+/// for the weight of the voter, anonymous voters have no weight.
+pub fn verify_ballot_validity_proofs_in_context<T: Trait>(
+    voter_id: &[u8],
+    weight: Option<u32>,
+    vote_id: &VoteId,
+    ballot: &Ballot,
+) -> Result<(), Error<T>> {
+    let topics: Vec<Topic> = Topics::get(vote_id);
+    let requires_proof =
+        |topic: &Topic| weight.is_some() || topic.requires_validity_proof();
+    let find_topic =
+        |topic_id: &TopicId| topics.iter().find(|topic| topic.id == *topic_id);
+    if ballot.validity_proofs.is_empty() {
+        ensure!(
+            !ballot
+                .answers
+                .iter()
+                .filter_map(|(topic_id, _)| find_topic(topic_id))
+                .any(requires_proof),
+            Error::<T>::BallotValidityProofError
+        );
+        return Ok(());
+    }
+    ensure!(
+        ballot.validity_proofs.len() == ballot.answers.len(),
+        Error::<T>::BallotValidityProofError
+    );
+
+    let pk: ElGamalPK = get_public_key::<T>(vote_id)?.into();
+    let params = &pk.params;
+    let answers = ballot.answers.iter().zip(ballot.validity_proofs.iter());
+    for ((topic_id, cipher), proof) in answers {
+        let topic = match find_topic(topic_id) {
+            Some(topic) if requires_proof(topic) => topic,
+            // free-form questions (or unknown topics) are not restricted
+            _ => continue,
+        };
+        let proof: MembershipProof = proof
+            .clone()
+            .ok_or(Error::<T>::BallotValidityProofError)?
+            .into();
+        let messages: Vec<BigUint> = valid_messages(topic, weight)
+            .into_iter()
+            .map(BigUint::from)
+            .collect();
+        let cipher: BigCipher = cipher.clone().into();
+        ensure!(
+            MembershipProof::verify(params, &pk, &cipher, &messages, &proof, voter_id),
+            Error::<T>::BallotValidityProofError
+        );
+    }
+    Ok(())
+}
+
 pub fn store_ballot<T: Trait>(from: &T::AccountId, vote_id: &VoteId, ballot: Ballot) {
-    // TODO: perform ballot duplication check
     // TODO: perform voter double vote cast check
 
     // store the encrypted ballot
     Ballots::<T>::insert(vote_id, from, ballot.clone());
 
+    let weight: Option<u32> = VoterWeights::<T>::get(vote_id, from);
+    store_ballot_ciphers::<T>(vote_id, ballot, weight);
+}
+
+/// stores the ciphers of an anonymous ballot, the ballot itself isn't linked to a voter.
+pub fn store_anonymous_ballot<T: Trait>(vote_id: &VoteId, ballot: Ballot) {
+    store_ballot_ciphers::<T>(vote_id, ballot, None);
+}
+
+fn store_ballot_ciphers<T: Trait>(vote_id: &VoteId, ballot: Ballot, weight: Option<u32>) {
     for (topic_id, cipher) in ballot.answers {
+        // the weight of the voter's answer is counted by the homomorphic tally
+        add_cipher_weight(vote_id, &topic_id, weight);
+
+        // remember the cipher, it cannot be submitted again
+        CipherHashes::insert((vote_id, &topic_id), hash_cipher(&cipher), true);
+
         // store the encrypted cipher with the respective topic_id
         // # of shuffles is always 0 -> since the voter has just submitted the vote
-        let mut ciphers: Vec<Cipher> =
-            Ciphers::get(&topic_id, INITIAL_NUMBER_OF_SHUFFLES);
-        ciphers.push(cipher);
+        append_ciphers::<T>(vote_id, &topic_id, INITIAL_NUMBER_OF_SHUFFLES, vec![cipher]);
+    }
+}
+
+/// excludes the ciphers of the voter's last ballot from the ciphers to shuffle (e.g. of a revoked voter)
+/// and returns the number of excluded ciphers. the ballot itself is kept for the audit.
+/// spoiled ciphers have already been excluded and aren't counted.
+pub fn exclude_ballot_ciphers<T: Trait>(from: &T::AccountId, vote_id: &VoteId) -> u32 {
+    let ballot: Ballot = Ballots::<T>::get(vote_id, from);
+    let weight: Option<u32> = VoterWeights::<T>::get(vote_id, from);
+    let mut nr_of_excluded_ciphers: u32 = 0;
+    for (topic_id, cipher) in ballot.answers.iter() {
+        if remove_cipher::<T>(vote_id, topic_id, INITIAL_NUMBER_OF_SHUFFLES, cipher) {
+            remove_cipher_weight(vote_id, topic_id, weight);
+            nr_of_excluded_ciphers += 1;
+        }
+    }
+    nr_of_excluded_ciphers
+}
+
+/// stores the receipt of the ballot.
+/// must be called before the BallotSubmitted event is deposited.
+pub fn store_ballot_receipt<T: Trait>(
+    from: &T::AccountId,
+    vote_id: &VoteId,
+    ballot: &Ballot,
+) {
+    let ciphers: Vec<&Cipher> = ballot.answers.iter().map(|(_, cipher)| cipher).collect();
+    let receipt = BallotReceipt {
+        block_number: <frame_system::Module<T>>::block_number(),
+        block_hash: T::Hash::default(),
+        cipher_hash: sp_io::hashing::blake2_256(&ciphers.encode()),
+        // the index of the next event, i.e., the BallotSubmitted event
+        event_index: <frame_system::Module<T>>::event_count(),
+    };
+    BallotReceipts::<T>::insert(vote_id, from, receipt);
+}
+
+/// spoils the cipher of the voter's last ballot for the topic (audit ballot).
+/// the cipher is removed from the ciphers to shuffle and can't be submitted again.
+/// ciphers which have been switched to a rotated public key can't be spoiled anymore.
+pub fn spoil_ballot_cipher<T: Trait>(
+    from: &T::AccountId,
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+) -> Result<Cipher, Error<T>> {
+    let ballot: Ballot = Ballots::<T>::get(vote_id, from);
+    let cipher: Cipher = ballot
+        .answers
+        .into_iter()
+        .find(|(id, _)| id == topic_id)
+        .map(|(_, cipher)| cipher)
+        .ok_or(Error::<T>::SpoiledCipherNotFound)?;
+    let hash = hash_cipher(&cipher);
+    ensure!(
+        !SpoiledBallots::<T>::contains_key((vote_id, topic_id), hash),
+        Error::<T>::BallotAlreadySpoiled
+    );
+    let public_key = get_public_key::<T>(vote_id)?;
+
+    // exclude the cipher from the tally
+    ensure!(
+        remove_cipher::<T>(vote_id, topic_id, INITIAL_NUMBER_OF_SHUFFLES, &cipher),
+        Error::<T>::SpoiledCipherNotFound
+    );
+    remove_cipher_weight(vote_id, topic_id, VoterWeights::<T>::get(vote_id, from));
+    let spoiled = SpoiledBallot {
+        voter: from.clone(),
+        cipher: cipher.clone(),
+        public_key,
+        reveal: None,
+    };
+    SpoiledBallots::<T>::insert((vote_id, topic_id), hash, spoiled);
+    Ok(cipher)
+}
+
+/// verifies that the revealed message and randomness match the spoiled cipher
+/// and publishes them. only the voter of the spoiled cipher can reveal them.
+pub fn verify_and_store_spoiled_ballot_reveal<T: Trait>(
+    from: &T::AccountId,
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    cipher_hash: [u8; 32],
+    reveal: SpoiledBallotReveal,
+) -> Result<(), Error<T>> {
+    let mut spoiled: SpoiledBallot<T::AccountId> =
+        SpoiledBallots::<T>::get((vote_id, topic_id), cipher_hash)
+            .ok_or(Error::<T>::SpoiledBallotNotFound)?;
+    ensure!(spoiled.voter == *from, Error::<T>::SpoiledBallotNotFound);
+    ensure!(
+        spoiled.reveal.is_none(),
+        Error::<T>::SpoiledBallotAlreadyRevealed
+    );
+
+    let pk: ElGamalPK = spoiled.public_key.clone().into();
+    let cipher: BigCipher = spoiled.cipher.clone().into();
+    let message = BigUint::from_bytes_be(&reveal.message);
+    let randomness = BigUint::from_bytes_be(&reveal.randomness);
+    ensure!(
+        ElGamal::verify_encryption(&cipher, &message, &randomness, &pk, reveal.encoded),
+        Error::<T>::InvalidSpoiledBallotReveal
+    );
+
+    spoiled.reveal = Some(reveal);
+    SpoiledBallots::<T>::insert((vote_id, topic_id), cipher_hash, spoiled);
+    Ok(())
+}
 
-        // store the ciphers
-        Ciphers::insert(&topic_id, INITIAL_NUMBER_OF_SHUFFLES, ciphers);
+impl<T: Trait> Module<T> {
+    /// returns the receipt of the voter's ballot, None if the voter hasn't voted.
+    pub fn ballot_receipt(
+        vote_id: &VoteId,
+        voter: &T::AccountId,
+    ) -> Option<BallotReceipt<T::BlockNumber, T::Hash>> {
+        BallotReceipts::<T>::get(vote_id, voter).map(|mut receipt| {
+            receipt.block_hash =
+                <frame_system::Module<T>>::block_hash(receipt.block_number);
+            receipt
+        })
     }
 }
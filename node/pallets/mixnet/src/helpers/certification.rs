@@ -0,0 +1,159 @@
+use super::{
+    merkle::{hash_leaf, merkle_root},
+    sealers::get_vote_sealers,
+};
+use crate::types::{
+    CertifiedResult, ResultApprovals, ResultDigest, Topic, Vote, VoteId, VoteResult,
+};
+use crate::{
+    CertifiedResults, CipherRoots, DecryptionAudit, Error, Module,
+    PendingResultApprovals, ResultDigests, ShuffleProofs, Tally, Topics, Trait, Votes,
+    VotingAuthorities,
+};
+use frame_support::{
+    ensure,
+    storage::{StorageDoubleMap, StorageMap, StorageValue},
+    traits::Get,
+};
+use sp_std::vec::Vec;
+
+/// all functions related to the certification of the result of a vote
+///
+/// returns the results of all topics of the vote, fails if a topic hasn't been tallied yet
+pub fn get_vote_result<T: Trait>(vote_id: &VoteId) -> Result<VoteResult, Error<T>> {
+    let topics: Vec<Topic> = Topics::get(vote_id);
+    let mut results = Vec::with_capacity(topics.len());
+    for Topic { id: topic_id, .. } in topics.into_iter() {
+        let result =
+            Tally::get((&vote_id, &topic_id)).ok_or(Error::<T>::VoteNotTallied)?;
+        results.push((topic_id, result));
+    }
+    Ok(VoteResult {
+        vote_id: vote_id.clone(),
+        results,
+    })
+}
+
+/// returns the number of sealers which must approve the result,
+/// i.e., the `CertificationQuorum` capped at the number of sealers of the vote
+pub fn get_required_sealer_approvals<T: Trait>(vote_id: &VoteId) -> usize {
+    let nr_of_sealers = get_vote_sealers::<T>(vote_id).len();
+    (T::CertificationQuorum::get() as usize).min(nr_of_sealers)
+}
+
+/// records the approval of the result hash by a voting authority or a sealer.
+/// returns the certified result once a voting authority and sufficient sealers approved it.
+pub fn approve_result<T: Trait>(
+    who: &T::AccountId,
+    vote_id: &VoteId,
+    result_hash: [u8; 32],
+) -> Result<Option<CertifiedResult<T::AccountId, T::BlockNumber>>, Error<T>> {
+    let authorities: Vec<T::AccountId> = VotingAuthorities::<T>::get();
+    let sealers: Vec<T::AccountId> = get_vote_sealers::<T>(vote_id);
+    ensure!(
+        authorities.contains(who) || sealers.contains(who),
+        Error::<T>::NotACertifier
+    );
+    ensure!(
+        !CertifiedResults::<T>::contains_key(vote_id),
+        Error::<T>::ResultAlreadyCertified
+    );
+
+    // the result hash must match the results of all topics
+    let vote_result: VoteResult = get_vote_result::<T>(vote_id)?;
+    ensure!(
+        vote_result.hash() == result_hash,
+        Error::<T>::ResultHashMismatch
+    );
+
+    // the approvals of a previous result hash don't count
+    let mut pending: ResultApprovals<T::AccountId> =
+        match PendingResultApprovals::<T>::get(vote_id) {
+            Some(pending) if pending.result_hash == result_hash => pending,
+            _ => ResultApprovals {
+                result_hash,
+                approvals: Vec::new(),
+            },
+        };
+    ensure!(
+        !pending.approvals.contains(who),
+        Error::<T>::ResultAlreadyApproved
+    );
+    pending.approvals.push(who.clone());
+
+    let nr_of_authorities = pending
+        .approvals
+        .iter()
+        .filter(|account| authorities.contains(account))
+        .count();
+    let nr_of_sealers = pending
+        .approvals
+        .iter()
+        .filter(|account| sealers.contains(account))
+        .count();
+    if nr_of_authorities == 0
+        || nr_of_sealers < get_required_sealer_approvals::<T>(vote_id)
+    {
+        PendingResultApprovals::<T>::insert(vote_id, pending);
+        return Ok(None);
+    }
+
+    let certified = CertifiedResult {
+        result_hash,
+        approvals: pending.approvals,
+        block_number: <frame_system::Module<T>>::block_number(),
+    };
+    PendingResultApprovals::<T>::remove(vote_id);
+    CertifiedResults::<T>::insert(vote_id, certified.clone());
+    Ok(Some(certified))
+}
+
+/// returns the merkle root of the commitments to the ciphers and proofs of the vote, per topic:
+/// the cipher root of every shuffle iteration (zero if there are no ciphers), the hash of
+/// the shuffle proofs and the hash of the decryption audit trail of every shuffle iteration.
+/// unlike the archive (see: archive::get_archive_leaves) the ciphers aren't read,
+/// their roots are maintained whenever ciphers are stored.
+pub fn get_proofs_root<T: Trait>(vote_id: &VoteId) -> [u8; 32] {
+    let vote: Vote<T::AccountId> = Votes::<T>::get(vote_id);
+    let topics: Vec<Topic> = Topics::get(vote_id);
+    let mut leaves: Vec<[u8; 32]> = Vec::new();
+
+    for Topic { id: topic_id, .. } in topics.iter() {
+        for iteration in 0..=vote.nr_of_shuffles {
+            leaves.push(
+                CipherRoots::get((vote_id, topic_id), iteration).unwrap_or_default(),
+            );
+        }
+        leaves.push(hash_leaf(&ShuffleProofs::get((vote_id, topic_id))));
+        for iteration in 0..=vote.nr_of_shuffles {
+            leaves.push(hash_leaf(&DecryptionAudit::<T>::get(
+                (vote_id, topic_id),
+                iteration,
+            )));
+        }
+    }
+    merkle_root(leaves)
+}
+
+/// stores the digest of the certified result, which other chains can consume (see: ResultDigest)
+pub fn store_result_digest<T: Trait>(
+    vote_id: &VoteId,
+    certified: &CertifiedResult<T::AccountId, T::BlockNumber>,
+) -> Result<ResultDigest<T::BlockNumber>, Error<T>> {
+    let vote_result: VoteResult = get_vote_result::<T>(vote_id)?;
+    let digest = ResultDigest::new(
+        vote_result.results_chain(),
+        get_proofs_root::<T>(vote_id),
+        certified.block_number,
+    );
+    ResultDigests::<T>::insert(vote_id, digest.clone());
+    Ok(digest)
+}
+
+impl<T: Trait> Module<T> {
+    /// returns the results of all topics of the vote whose hash is certified,
+    /// None if a topic hasn't been tallied yet
+    pub fn vote_result(vote_id: &VoteId) -> Option<VoteResult> {
+        get_vote_result::<T>(vote_id).ok()
+    }
+}
@@ -0,0 +1,281 @@
+use super::array::get_slice;
+use super::merkle::{append_leaf, hash_leaf, merkle_path, root_from_peaks};
+use crate::types::{
+    ChunkIndex, Cipher, CipherMerkleProof, CipherPage, NrOfShuffles, TopicId, VoteId,
+};
+use crate::{
+    CipherChunkCount, CipherCounts, CipherPeaks, CipherRoots, Ciphers, Module, Trait,
+};
+use frame_support::{storage::StorageDoubleMap, traits::Get};
+use sp_std::vec::Vec;
+
+/// all functions related to the chunked cipher storage
+///
+/// the ciphers of a topic and shuffle iteration are stored in chunks of at most
+/// `T::CipherChunkSize` ciphers. the chunks, their count, the number of ciphers and the merkle tree
+/// are keyed by the vote and the topic, i.e., topic ids only need to be unique within a vote.
+/// the chunk size must not be changed while ciphers are stored.
+pub fn get_chunk_size<T: Trait>() -> u64 {
+    // a chunk size of zero would make it impossible to store any cipher
+    let chunk_size: u32 = T::CipherChunkSize::get();
+    if chunk_size == 0 {
+        1
+    } else {
+        chunk_size as u64
+    }
+}
+
+/// returns the number of chunks stored for the topic and shuffle iteration
+pub fn get_chunk_count<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    iteration: NrOfShuffles,
+) -> ChunkIndex {
+    CipherChunkCount::get((vote_id, topic_id), iteration)
+}
+
+/// returns a single chunk of ciphers, empty if the chunk doesn't exist
+pub fn get_cipher_chunk<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    iteration: NrOfShuffles,
+    chunk_index: ChunkIndex,
+) -> Vec<Cipher> {
+    Ciphers::get((vote_id, topic_id), (iteration, chunk_index))
+}
+
+/// returns the total number of ciphers stored for the topic and shuffle iteration.
+/// the count is maintained on every insert, i.e., no chunk is read.
+pub fn get_cipher_count<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    iteration: NrOfShuffles,
+) -> u64 {
+    CipherCounts::get((vote_id, topic_id), iteration)
+}
+
+/// returns all ciphers stored for the topic and shuffle iteration (all chunks)
+pub fn get_all_ciphers<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    iteration: NrOfShuffles,
+) -> Vec<Cipher> {
+    let nr_of_chunks = get_chunk_count::<T>(vote_id, topic_id, iteration);
+    let mut ciphers: Vec<Cipher> = Vec::new();
+    for chunk_index in 0..nr_of_chunks {
+        ciphers.extend(get_cipher_chunk::<T>(
+            vote_id,
+            topic_id,
+            iteration,
+            chunk_index,
+        ));
+    }
+    ciphers
+}
+
+/// returns the ciphers in the range [start_position, start_position + batch_size).
+/// only the chunks overlapping with the range are read from storage.
+pub fn get_ciphers_in_range<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    iteration: NrOfShuffles,
+    start_position: u64,
+    batch_size: u64,
+) -> Vec<Cipher> {
+    let nr_of_ciphers = get_cipher_count::<T>(vote_id, topic_id, iteration);
+    if batch_size == 0 || start_position >= nr_of_ciphers {
+        return Vec::new();
+    }
+    let chunk_size = get_chunk_size::<T>();
+
+    // compute the chunks which overlap with the requested range
+    let end_position = (start_position + batch_size).min(nr_of_ciphers);
+    let first_chunk = (start_position / chunk_size) as ChunkIndex;
+    let last_chunk = ((end_position - 1) / chunk_size) as ChunkIndex;
+
+    let mut window: Vec<Cipher> = Vec::new();
+    for chunk_index in first_chunk..=last_chunk {
+        window.extend(get_cipher_chunk::<T>(
+            vote_id,
+            topic_id,
+            iteration,
+            chunk_index,
+        ));
+    }
+
+    // the start position relative to the first chunk read
+    let offset = start_position - first_chunk as u64 * chunk_size;
+    get_slice::<T, Cipher>(window, offset, end_position - start_position)
+}
+
+/// appends the ciphers to the merkle tree of the topic and shuffle iteration
+/// and stores the new merkle root.
+fn commit_ciphers<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    iteration: NrOfShuffles,
+    mut peaks: Vec<Option<[u8; 32]>>,
+    ciphers: &[Cipher],
+) {
+    for cipher in ciphers.iter() {
+        append_leaf(&mut peaks, hash_leaf(cipher));
+    }
+    CipherRoots::insert((vote_id, topic_id), iteration, root_from_peaks(&peaks));
+    CipherPeaks::insert((vote_id, topic_id), iteration, peaks);
+}
+
+/// appends the ciphers to the chunked storage of the topic and shuffle iteration.
+/// fills up the last chunk first before creating new chunks.
+pub fn append_ciphers<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    iteration: NrOfShuffles,
+    ciphers: Vec<Cipher>,
+) {
+    if ciphers.is_empty() {
+        return;
+    }
+    // update the merkle root and the number of the ciphers
+    let peaks = CipherPeaks::get((vote_id, topic_id), iteration);
+    commit_ciphers::<T>(vote_id, topic_id, iteration, peaks, &ciphers);
+    CipherCounts::mutate((vote_id, topic_id), iteration, |count| {
+        *count = count.saturating_add(ciphers.len() as u64)
+    });
+
+    let chunk_size = get_chunk_size::<T>() as usize;
+    let mut nr_of_chunks = get_chunk_count::<T>(vote_id, topic_id, iteration);
+
+    // continue with the last chunk if there is still space left
+    let (mut chunk_index, mut chunk) = if nr_of_chunks == 0 {
+        (0, Vec::new())
+    } else {
+        let last_chunk_index = nr_of_chunks - 1;
+        (
+            last_chunk_index,
+            get_cipher_chunk::<T>(vote_id, topic_id, iteration, last_chunk_index),
+        )
+    };
+
+    for cipher in ciphers.into_iter() {
+        if chunk.len() >= chunk_size {
+            Ciphers::insert((vote_id, topic_id), (iteration, chunk_index), &chunk);
+            chunk_index += 1;
+            chunk = Vec::new();
+        }
+        chunk.push(cipher);
+    }
+    Ciphers::insert((vote_id, topic_id), (iteration, chunk_index), chunk);
+
+    // update the number of chunks
+    if chunk_index + 1 > nr_of_chunks {
+        nr_of_chunks = chunk_index + 1;
+        CipherChunkCount::insert((vote_id, topic_id), iteration, nr_of_chunks);
+    }
+}
+
+/// replaces the ciphers of the topic and shuffle iteration.
+/// the number of ciphers must not change, the chunks are overwritten in place.
+pub fn replace_ciphers<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    iteration: NrOfShuffles,
+    ciphers: Vec<Cipher>,
+) {
+    let chunk_size = get_chunk_size::<T>() as usize;
+    // all leaves change, the merkle tree is rebuilt from scratch
+    commit_ciphers::<T>(vote_id, topic_id, iteration, Vec::new(), &ciphers);
+    for (chunk_index, chunk) in ciphers.chunks(chunk_size).enumerate() {
+        Ciphers::insert(
+            (vote_id, topic_id),
+            (iteration, chunk_index as ChunkIndex),
+            chunk.to_vec(),
+        );
+    }
+}
+
+/// removes the cipher from the ciphers of the topic and shuffle iteration.
+/// the chunks and the merkle tree are rebuilt from scratch.
+/// returns false if the cipher isn't stored.
+pub fn remove_cipher<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    iteration: NrOfShuffles,
+    cipher: &Cipher,
+) -> bool {
+    let mut ciphers = get_all_ciphers::<T>(vote_id, topic_id, iteration);
+    let position = match ciphers.iter().position(|stored| stored == cipher) {
+        Some(position) => position,
+        None => return false,
+    };
+    ciphers.remove(position);
+
+    let nr_of_chunks = get_chunk_count::<T>(vote_id, topic_id, iteration);
+    for chunk_index in 0..nr_of_chunks {
+        Ciphers::remove((vote_id, topic_id), (iteration, chunk_index));
+    }
+    CipherChunkCount::remove((vote_id, topic_id), iteration);
+    CipherCounts::remove((vote_id, topic_id), iteration);
+    CipherRoots::remove((vote_id, topic_id), iteration);
+    CipherPeaks::remove((vote_id, topic_id), iteration);
+    append_ciphers::<T>(vote_id, topic_id, iteration, ciphers);
+    true
+}
+
+impl<T: Trait> Module<T> {
+    /// returns all ciphers for the topic and shuffle iteration.
+    /// large votes should iterate over the chunks instead, see: `cipher_chunk`
+    pub fn ciphers(
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+        iteration: NrOfShuffles,
+    ) -> Vec<Cipher> {
+        get_all_ciphers::<T>(vote_id, topic_id, iteration)
+    }
+
+    /// returns a single chunk of ciphers for the topic and shuffle iteration
+    pub fn cipher_chunk(
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+        iteration: NrOfShuffles,
+        chunk_index: ChunkIndex,
+    ) -> Vec<Cipher> {
+        get_cipher_chunk::<T>(vote_id, topic_id, iteration, chunk_index)
+    }
+
+    /// returns the ciphers in the range [offset, offset + limit) for the topic and shuffle
+    /// iteration together with the total number of ciphers (pagination)
+    pub fn cipher_page(
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+        iteration: NrOfShuffles,
+        offset: u64,
+        limit: u64,
+    ) -> CipherPage {
+        CipherPage {
+            total: get_cipher_count::<T>(vote_id, topic_id, iteration),
+            ciphers: get_ciphers_in_range::<T>(
+                vote_id, topic_id, iteration, offset, limit,
+            ),
+        }
+    }
+
+    /// returns the merkle inclusion proof of the cipher at position index
+    /// for the topic and shuffle iteration, None if the cipher doesn't exist.
+    /// the proof verifies against the stored cipher root, see: `cipher_root`
+    pub fn cipher_proof(
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+        iteration: NrOfShuffles,
+        index: u64,
+    ) -> Option<CipherMerkleProof> {
+        let ciphers = get_all_ciphers::<T>(vote_id, topic_id, iteration);
+        let leaves: Vec<[u8; 32]> = ciphers.iter().map(hash_leaf).collect();
+        let leaf: [u8; 32] = *leaves.get(index as usize)?;
+        Some(CipherMerkleProof {
+            leaf,
+            index,
+            nr_of_leaves: leaves.len() as u64,
+            path: merkle_path(leaves, index as usize),
+        })
+    }
+}
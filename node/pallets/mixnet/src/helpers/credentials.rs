@@ -0,0 +1,291 @@
+use super::assertions::ensure_ciphers_in_group;
+use super::params::{get_hash_function, get_public_key, get_public_params};
+use super::progress::get_required_share_count;
+use super::sealers::get_vote_sealers;
+use crate::types::{
+    Ballot, Cipher, CredentialBlindingProof, DecryptedShare, DecryptedShareProof,
+    PublicKeyShare, VoteId, VotePhase, Wrapper,
+};
+use crate::{
+    Ballots, CredentialBallots, CredentialBlindings, CredentialDecryptions, Credentials,
+    DiscardedBallots, Error, Module, PublicKeyShareBySealer, RawEvent, Trait, Votes,
+};
+use codec::Encode;
+use crypto::proofs::{
+    decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
+    pet::PlaintextEquivalenceProof,
+};
+use crypto::types::{Cipher as BigCipher, ElGamalParams};
+use frame_support::{
+    debug, ensure,
+    storage::{StorageDoubleMap, StorageMap},
+};
+use num_bigint::BigUint;
+use sp_std::vec::Vec;
+
+/// all functions related to the credentials of the voters (coercion resistance)
+///
+/// stores the encrypted credential of a voter.
+/// the credential is encrypted under the public key of the vote,
+/// therefore, it can only be registered once the public key exists and before the tally starts.
+/// the ballots of the voter carry its credential, i.e., it can't be registered once the voter has voted.
+pub fn store_credential<T: Trait>(
+    vote_id: &VoteId,
+    voter: &T::AccountId,
+    credential: Cipher,
+) -> Result<(), Error<T>> {
+    get_public_key::<T>(vote_id)?;
+    let phase = Votes::<T>::get(vote_id).phase;
+    ensure!(
+        phase != VotePhase::Tallying && phase != VotePhase::Cancelled,
+        Error::<T>::WrongVotePhase
+    );
+    ensure!(
+        !Credentials::<T>::contains_key(vote_id, voter),
+        Error::<T>::CredentialAlreadyRegistered
+    );
+    ensure!(
+        !Ballots::<T>::contains_key(vote_id, voter),
+        Error::<T>::BallotAlreadyCast
+    );
+    // the plaintext equivalence test is only sound for group elements
+    ensure_ciphers_in_group::<T>(vote_id, core::iter::once(&credential))?;
+    Credentials::<T>::insert(vote_id, voter, credential);
+    Ok(())
+}
+
+/// ensures that the ballot carries an encrypted credential if, and only if,
+/// a credential has been registered for the voter. the credential must be a group element.
+pub fn ensure_ballot_credential<T: Trait>(
+    vote_id: &VoteId,
+    voter: &T::AccountId,
+    ballot: &Ballot,
+) -> Result<(), Error<T>> {
+    let registered = Credentials::<T>::contains_key(vote_id, voter);
+    match &ballot.credential {
+        Some(credential) => {
+            ensure!(registered, Error::<T>::CredentialNotRegistered);
+            ensure_ciphers_in_group::<T>(vote_id, core::iter::once(credential))
+        }
+        None => {
+            ensure!(!registered, Error::<T>::CredentialMissing);
+            Ok(())
+        }
+    }
+}
+
+/// remembers the voter of a ballot with a credential, its ballot is checked once the tallying
+/// phase starts. must be called before the ballot is stored: the voter is added with its first
+/// ballot, all ballots of the voter carry a credential (see: ensure_ballot_credential).
+pub fn store_credential_ballot<T: Trait>(
+    vote_id: &VoteId,
+    voter: &T::AccountId,
+    ballot: &Ballot,
+) {
+    if ballot.credential.is_some() && !Ballots::<T>::contains_key(vote_id, voter) {
+        CredentialBallots::<T>::append(vote_id, voter);
+    }
+}
+
+/// returns true while the credentials of the ballots of the vote are checked, i.e., once the
+/// tallying phase has started and until the ballots with an invalid credential have been discarded
+pub fn is_credential_check_pending<T: Trait>(vote_id: &VoteId) -> bool {
+    Votes::<T>::get(vote_id).phase == VotePhase::Tallying
+        && CredentialBallots::<T>::contains_key(vote_id)
+        && !DiscardedBallots::<T>::contains_key(vote_id)
+}
+
+pub fn ensure_credential_check_pending<T: Trait>(
+    vote_id: &VoteId,
+) -> Result<(), Error<T>> {
+    ensure!(
+        is_credential_check_pending::<T>(vote_id),
+        Error::<T>::CredentialCheckNotPending
+    );
+    Ok(())
+}
+
+/// the ciphers are only shuffled or aggregated once the ballots with an invalid credential
+/// have been discarded
+pub fn ensure_no_credential_check_pending<T: Trait>(
+    vote_id: &VoteId,
+) -> Result<(), Error<T>> {
+    ensure!(
+        !is_credential_check_pending::<T>(vote_id),
+        Error::<T>::CredentialCheckPending
+    );
+    Ok(())
+}
+
+/// starts the check of the credentials once the tallying phase starts. the blindings and
+/// decryptions of an earlier check (e.g. before the vote returned to the voting phase) are dropped,
+/// the ballots cast since then need to be blinded as well.
+/// returns false if there is nothing to check, i.e., the ballot box can be frozen right away.
+pub fn start_credential_check<T: Trait>(vote_id: &VoteId) -> bool {
+    if !is_credential_check_pending::<T>(vote_id) {
+        return false;
+    }
+    CredentialBlindings::<T>::remove_prefix(vote_id);
+    CredentialDecryptions::<T>::remove_prefix(vote_id);
+    let nr_of_ballots = CredentialBallots::<T>::get(vote_id).len() as u32;
+    debug::info!(
+        "checking the credentials of {:?} ballots of vote: {:?}",
+        nr_of_ballots,
+        vote_id
+    );
+    Module::<T>::deposit_event(RawEvent::CredentialCheckStarted(
+        vote_id.clone(),
+        nr_of_ballots,
+    ));
+    true
+}
+
+/// returns the quotients of the credentials of the ballots and the credentials registered for
+/// their voters (in the order of CredentialBallots). a quotient is an encryption of 1 if, and
+/// only if, the ballot has been cast with the registered credential.
+pub fn get_credential_quotients<T: Trait>(
+    vote_id: &VoteId,
+) -> Result<Vec<BigCipher>, Error<T>> {
+    let params: ElGamalParams = get_public_params::<T>(vote_id)?.into();
+    let mut quotients: Vec<BigCipher> = Vec::new();
+    for voter in CredentialBallots::<T>::get(vote_id).iter() {
+        let cast: BigCipher = Ballots::<T>::get(vote_id, voter)
+            .credential
+            .ok_or(Error::<T>::CredentialMissing)?
+            .into();
+        let registered: BigCipher = Credentials::<T>::get(vote_id, voter)
+            .ok_or(Error::<T>::CredentialNotRegistered)?
+            .into();
+        quotients.push(PlaintextEquivalenceProof::quotient(
+            &cast,
+            &registered,
+            &params.p,
+        ));
+    }
+    Ok(quotients)
+}
+
+/// returns the sealers of the vote which have blinded the credential quotients
+fn get_blinding_sealers<T: Trait>(vote_id: &VoteId) -> Vec<T::AccountId> {
+    get_vote_sealers::<T>(vote_id)
+        .into_iter()
+        .filter(|sealer| CredentialBlindings::<T>::contains_key(vote_id, sealer))
+        .collect()
+}
+
+/// verifies the blinded credential quotients of the sealer (one per ballot with a credential)
+/// and their proofs of correct blinding, and stores them. the quotients are blinded by as many
+/// sealers as are required to decrypt (see: get_required_share_count), later blindings are rejected.
+pub fn verify_and_store_credential_blindings<T: Trait>(
+    who: &T::AccountId,
+    vote_id: &VoteId,
+    blindings: Vec<Cipher>,
+    proofs: Vec<CredentialBlindingProof>,
+) -> Result<(), Error<T>> {
+    ensure!(
+        !CredentialBlindings::<T>::contains_key(vote_id, who)
+            && get_blinding_sealers::<T>(vote_id).len()
+                < get_required_share_count::<T>(vote_id) as usize,
+        Error::<T>::CredentialCheckAlreadySubmitted
+    );
+
+    // one blinded quotient and proof per ballot with a credential
+    let quotients: Vec<BigCipher> = get_credential_quotients::<T>(vote_id)?;
+    ensure!(
+        blindings.len() == quotients.len() && proofs.len() == quotients.len(),
+        Error::<T>::CredentialBlindingProofError
+    );
+
+    // verify the proofs of correct blinding, bound to the sealer
+    let sealer_id: &[u8] = &who.encode();
+    let params: ElGamalParams = get_public_params::<T>(vote_id)?.into();
+    let submitted = blindings.iter().zip(proofs.into_iter());
+    for (quotient, (blinded, proof)) in quotients.iter().zip(submitted) {
+        let blinded: BigCipher = blinded.clone().into();
+        ensure!(
+            PlaintextEquivalenceProof::verify(
+                &params,
+                &proof.into(),
+                quotient,
+                &blinded,
+                sealer_id
+            ),
+            Error::<T>::CredentialBlindingProofError
+        );
+    }
+    CredentialBlindings::<T>::insert(vote_id, who, blindings);
+    Ok(())
+}
+
+/// returns the combined blinded credential quotients, i.e., the product of the blinded quotients
+/// of all sealers which blinded them. requires the blindings of as many sealers as are required to decrypt.
+pub fn get_combined_credential_blindings<T: Trait>(
+    vote_id: &VoteId,
+) -> Result<Vec<BigCipher>, Error<T>> {
+    let sealers: Vec<T::AccountId> = get_blinding_sealers::<T>(vote_id);
+    ensure!(
+        !sealers.is_empty()
+            && sealers.len() >= get_required_share_count::<T>(vote_id) as usize,
+        Error::<T>::CredentialBlindingsIncomplete
+    );
+    let params: ElGamalParams = get_public_params::<T>(vote_id)?.into();
+    let blindings: Vec<Vec<BigCipher>> = sealers
+        .iter()
+        .map(|sealer| {
+            Wrapper(CredentialBlindings::<T>::get(vote_id, sealer).unwrap_or_default())
+                .into()
+        })
+        .collect();
+    let nr_of_quotients = blindings[0].len();
+    Ok((0..nr_of_quotients)
+        .map(|index| {
+            let shares: Vec<BigCipher> = blindings
+                .iter()
+                .map(|blinded| blinded[index].clone())
+                .collect();
+            PlaintextEquivalenceProof::combine(&shares, &params.p)
+        })
+        .collect())
+}
+
+/// verifies the partial decryptions of the combined blinded credential quotients of the sealer
+/// and their (batched) decryption proof using the sealer's public key share, and stores them.
+pub fn verify_and_store_credential_decryptions<T: Trait>(
+    who: &T::AccountId,
+    vote_id: &VoteId,
+    shares: Vec<DecryptedShare>,
+    proof: DecryptedShareProof,
+) -> Result<(), Error<T>> {
+    ensure!(
+        !CredentialDecryptions::<T>::contains_key(vote_id, who),
+        Error::<T>::CredentialCheckAlreadySubmitted
+    );
+    let combined: Vec<BigCipher> = get_combined_credential_blindings::<T>(vote_id)?;
+    ensure!(
+        shares.len() == combined.len(),
+        Error::<T>::CredentialDecryptionProofError
+    );
+
+    // verify the proof using the sealer's public key share
+    let sealer_id: &[u8] = &who.encode();
+    let params: ElGamalParams = get_public_params::<T>(vote_id)?.into();
+    let sealer_pk_share: PublicKeyShare =
+        PublicKeyShareBySealer::<T>::get((vote_id, who))
+            .ok_or(Error::<T>::PublicKeyShareNotExistsError)?;
+    let sealer_pk: BigUint = BigUint::from_bytes_be(&sealer_pk_share.pk);
+    let decrypted_shares: Vec<BigUint> =
+        shares.iter().map(|s| BigUint::from_bytes_be(s)).collect();
+    let is_valid: bool = DecryptionProof::verify_batched_with_hash(
+        &params,
+        &sealer_pk,
+        &proof.into(),
+        &combined,
+        &decrypted_shares,
+        sealer_id,
+        DEFAULT_BATCH_SOUNDNESS,
+        get_hash_function::<T>(vote_id),
+    );
+    ensure!(is_valid, Error::<T>::CredentialDecryptionProofError);
+    CredentialDecryptions::<T>::insert(vote_id, who, shares);
+    Ok(())
+}
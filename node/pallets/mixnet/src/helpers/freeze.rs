@@ -0,0 +1,54 @@
+use crate::types::{BallotBoxFreeze, Topic, TopicId, VoteId};
+use crate::{
+    BallotBoxFreezes, CipherCounts, CipherRoots, Module, RawEvent, Topics, Trait,
+};
+use frame_support::{
+    debug,
+    storage::{StorageDoubleMap, StorageMap},
+};
+use sp_std::vec::Vec;
+
+/// all functions related to the ballot box freeze
+///
+/// records the ciphers of the vote once the tallying phase starts for the first time:
+/// the hash of the last block of the voting phase and the hash chain over the number
+/// and the merkle root of the submitted ciphers of every topic (see: BallotBoxFreeze).
+/// freezing an already frozen ballot box is a no-op, i.e., the freeze and its event are
+/// only recorded once even if the tallying phase is entered again (see: force_phase).
+pub fn freeze_ballot_box<T: Trait>(vote_id: &VoteId) {
+    if BallotBoxFreezes::<T>::contains_key(vote_id) {
+        return;
+    }
+    let topics: Vec<(TopicId, u64, [u8; 32])> = Topics::get(vote_id)
+        .into_iter()
+        .map(|Topic { id: topic_id, .. }| {
+            let nr_of_ciphers = CipherCounts::get((&vote_id, &topic_id), 0);
+            let root = CipherRoots::get((&vote_id, &topic_id), 0).unwrap_or_default();
+            (topic_id, nr_of_ciphers, root)
+        })
+        .collect();
+    let freeze = BallotBoxFreeze::new(
+        <frame_system::Module<T>>::parent_hash(),
+        <frame_system::Module<T>>::block_number(),
+        BallotBoxFreeze::<T::Hash, T::BlockNumber>::ciphers_chain(vote_id, &topics),
+    );
+    BallotBoxFreezes::<T>::insert(vote_id, freeze.clone());
+    debug::info!(
+        "ballot box frozen: {:?}, digest: {:?}",
+        vote_id,
+        freeze.digest
+    );
+    Module::<T>::deposit_event(RawEvent::BallotBoxFrozen(
+        vote_id.clone(),
+        freeze.block_hash,
+        freeze.digest,
+    ));
+}
+
+/// returns the context of the challenges of the shuffle proofs of the vote: the digest
+/// of its ballot box freeze. votes tallied before the freeze was recorded have no context.
+pub fn get_shuffle_context<T: Trait>(vote_id: &VoteId) -> Vec<u8> {
+    BallotBoxFreezes::<T>::get(vote_id)
+        .map(|freeze| freeze.digest.to_vec())
+        .unwrap_or_default()
+}
@@ -0,0 +1,140 @@
+use super::assertions::ensure_vote_does_not_exist;
+use super::indexing::set_shuffle_state;
+use super::sealers::snapshot_vote_sealers;
+use crate::types::{
+    ProtocolConfig, ShuffleState, TallyStrategy, Topic, VoteId, VoteProposal,
+};
+use crate::{
+    ApprovalThreshold, CancellationApprovals, Error, ProtocolConfigs, TallyingApprovals,
+    Topics, Trait, VoteIds, VoteProposals, Votes,
+};
+use frame_support::{
+    debug, ensure,
+    storage::{StorageMap, StorageValue},
+};
+use sp_std::vec::Vec;
+
+/// all functions related to the approval of votes and phase transitions by multiple voting authorities (m-of-n)
+///
+/// returns the number of approvals required, a threshold of 0 is treated as a single approval.
+pub fn get_required_approvals<T: Trait>() -> usize {
+    ApprovalThreshold::get().max(1) as usize
+}
+
+/// stores the proposal of a vote, the proposer's approval is recorded with the proposal.
+/// returns true if the proposal has sufficient approvals and the vote has been created.
+pub fn propose<T: Trait>(
+    vote_id: &VoteId,
+    proposal: VoteProposal<T::AccountId>,
+) -> Result<bool, Error<T>> {
+    ensure_vote_does_not_exist::<T>(vote_id)?;
+    ensure!(
+        !VoteProposals::<T>::contains_key(vote_id),
+        Error::<T>::VoteAlreadyExists
+    );
+    activate_if_approved::<T>(vote_id, proposal)
+}
+
+/// records the approval of a voting authority for a proposed vote.
+/// returns true if the proposal has sufficient approvals and the vote has been created.
+pub fn approve<T: Trait>(who: &T::AccountId, vote_id: &VoteId) -> Result<bool, Error<T>> {
+    let mut proposal =
+        VoteProposals::<T>::get(vote_id).ok_or(Error::<T>::VoteProposalDoesNotExist)?;
+    ensure!(
+        !proposal.approvals.contains(who),
+        Error::<T>::AlreadyApproved
+    );
+    proposal.approvals.push(who.clone());
+    activate_if_approved::<T>(vote_id, proposal)
+}
+
+/// records the approval of a voting authority to start the tallying phase.
+/// returns true if sufficient voting authorities approved, the approvals are reset in that case.
+pub fn approve_tallying<T: Trait>(
+    who: &T::AccountId,
+    vote_id: &VoteId,
+) -> Result<bool, Error<T>> {
+    let mut approvals: Vec<T::AccountId> = TallyingApprovals::<T>::get(vote_id);
+    ensure!(!approvals.contains(who), Error::<T>::AlreadyApproved);
+    approvals.push(who.clone());
+
+    if approvals.len() >= get_required_approvals::<T>() {
+        TallyingApprovals::<T>::remove(vote_id);
+        Ok(true)
+    } else {
+        TallyingApprovals::<T>::insert(vote_id, approvals);
+        Ok(false)
+    }
+}
+
+/// records the approval of a voting authority to cancel the vote.
+/// returns true if sufficient voting authorities approved, the approvals are reset in that case.
+pub fn approve_cancellation<T: Trait>(
+    who: &T::AccountId,
+    vote_id: &VoteId,
+) -> Result<bool, Error<T>> {
+    let mut approvals: Vec<T::AccountId> = CancellationApprovals::<T>::get(vote_id);
+    ensure!(!approvals.contains(who), Error::<T>::AlreadyApproved);
+    approvals.push(who.clone());
+
+    if approvals.len() >= get_required_approvals::<T>() {
+        CancellationApprovals::<T>::remove(vote_id);
+        Ok(true)
+    } else {
+        CancellationApprovals::<T>::insert(vote_id, approvals);
+        Ok(false)
+    }
+}
+
+fn activate_if_approved<T: Trait>(
+    vote_id: &VoteId,
+    proposal: VoteProposal<T::AccountId>,
+) -> Result<bool, Error<T>> {
+    if proposal.approvals.len() < get_required_approvals::<T>() {
+        VoteProposals::<T>::insert(vote_id, proposal);
+        return Ok(false);
+    }
+    VoteProposals::<T>::remove(vote_id);
+    create_vote::<T>(vote_id, proposal)?;
+    Ok(true)
+}
+
+/// creates the vote of an approved proposal
+fn create_vote<T: Trait>(
+    vote_id: &VoteId,
+    proposal: VoteProposal<T::AccountId>,
+) -> Result<(), Error<T>> {
+    // store the vote_id, vote + topic information
+    let mut vote_ids: Vec<VoteId> = VoteIds::get();
+    ensure_vote_does_not_exist::<T>(vote_id)?;
+
+    vote_ids.push(vote_id.clone());
+    VoteIds::put(vote_ids);
+
+    // create an empty shuffle state for each topic
+    // topics which are tallied homomorphically are never shuffled
+    let skip_shuffling = proposal.vote.tally_strategy == TallyStrategy::Homomorphic;
+    for Topic { id: topic_id, .. } in proposal.topics.iter() {
+        set_shuffle_state::<T>(
+            vote_id,
+            topic_id,
+            ShuffleState {
+                iteration: 0,
+                start_position: 0,
+                batch_size: proposal.batch_size,
+                done: skip_shuffling,
+            },
+        );
+    }
+    // the vote is verified with the parameters of the protocol it has been created with
+    let config = ProtocolConfig::new(proposal.vote.nr_of_shuffles);
+    ProtocolConfigs::insert(vote_id, config);
+    // the vote keeps the sealers of its creation, see: SealerSets
+    snapshot_vote_sealers::<T>(vote_id);
+    Votes::<T>::insert(vote_id, proposal.vote);
+
+    // store all topics topic_id, question, type and candidates
+    Topics::insert(vote_id, proposal.topics);
+    debug::info!("created vote: {:?}", vote_id);
+    Ok(())
+}
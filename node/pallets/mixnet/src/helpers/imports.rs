@@ -0,0 +1,108 @@
+use super::{
+    assertions::ensure_valid_ciphers,
+    ciphers::append_ciphers,
+    merkle::{hash_leaf, merkle_root},
+};
+use crate::shuffle::linked::is_linked_shuffle;
+use crate::types::{
+    Cipher, CipherImport, NrOfShuffles, TopicId, VoteId, MAX_CIPHERS_PER_IMPORT,
+    MAX_SOURCE_TAG_LENGTH,
+};
+use crate::{
+    CipherCounts, CipherHashes, CipherImportCount, CipherImports, Error, Topics, Trait,
+};
+use frame_support::{
+    ensure,
+    storage::{StorageDoubleMap, StorageMap},
+};
+use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+const INITIAL_NUMBER_OF_SHUFFLES: NrOfShuffles = 0;
+
+/// all functions related to the import of ciphers collected off-chain
+///
+/// a batch of imported ciphers is appended to the submitted ciphers of the topic like the
+/// ciphers of ballots. the import records the position of the batch, its source and the merkle
+/// root of its ciphers, i.e., the imported ciphers stay distinguishable from the ballots.
+///
+/// ensures that the batch can be imported for the topic of the vote: the batch is neither empty
+/// nor too large, the topic is a question of the vote and the vote isn't shuffled linked,
+/// i.e., all topics of a ballot would have to be imported together.
+fn ensure_valid_import<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    ciphers: &[Cipher],
+    source_tag: &[u8],
+) -> Result<(), Error<T>> {
+    ensure!(
+        !ciphers.is_empty() && ciphers.len() <= MAX_CIPHERS_PER_IMPORT,
+        Error::<T>::CipherImportTooLarge
+    );
+    ensure!(
+        source_tag.len() <= MAX_SOURCE_TAG_LENGTH,
+        Error::<T>::SourceTagTooLong
+    );
+    ensure!(
+        Topics::get(vote_id)
+            .iter()
+            .any(|topic| topic.id == *topic_id),
+        Error::<T>::TopicNotInVote
+    );
+    ensure!(
+        !is_linked_shuffle(vote_id),
+        Error::<T>::IncompleteLinkedBallot
+    );
+    ensure_valid_ciphers::<T>(vote_id, ciphers.iter())
+}
+
+/// imports the batch of ciphers for the topic of the vote and records the import.
+/// every cipher can only be submitted once, neither by a ballot nor by another import.
+/// returns the index of the import and the import.
+pub fn store_imported_ciphers<T: Trait>(
+    importer: &T::AccountId,
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    ciphers: Vec<Cipher>,
+    source_tag: Vec<u8>,
+) -> Result<(u32, CipherImport<T::AccountId, T::BlockNumber>), Error<T>> {
+    ensure_valid_import::<T>(vote_id, topic_id, &ciphers, &source_tag)?;
+
+    let hashes: Vec<[u8; 32]> = ciphers.iter().map(hash_leaf).collect();
+    let mut unique: BTreeSet<&[u8; 32]> = BTreeSet::new();
+    for hash in hashes.iter() {
+        ensure!(
+            !CipherHashes::contains_key((vote_id, topic_id), hash) && unique.insert(hash),
+            Error::<T>::DuplicateCipher
+        );
+    }
+
+    // the imported ciphers are appended to the submitted ciphers of the topic
+    let start = CipherCounts::get((vote_id, topic_id), INITIAL_NUMBER_OF_SHUFFLES);
+    for hash in hashes.iter() {
+        CipherHashes::insert((vote_id, topic_id), hash, true);
+    }
+    let import = CipherImport {
+        topic_id: topic_id.clone(),
+        source_tag,
+        importer: importer.clone(),
+        block_number: <frame_system::Module<T>>::block_number(),
+        start,
+        nr_of_ciphers: ciphers.len() as u64,
+        merkle_root: merkle_root(hashes),
+    };
+    append_ciphers::<T>(vote_id, topic_id, INITIAL_NUMBER_OF_SHUFFLES, ciphers);
+
+    let index = CipherImportCount::get(vote_id);
+    CipherImports::<T>::insert(vote_id, index, import.clone());
+    CipherImportCount::insert(vote_id, index.saturating_add(1));
+    Ok((index, import))
+}
+
+/// returns all imports of the vote in the order they have been imported
+pub fn get_cipher_imports<T: Trait>(
+    vote_id: &VoteId,
+) -> Vec<CipherImport<T::AccountId, T::BlockNumber>> {
+    (0..CipherImportCount::get(vote_id))
+        .filter_map(|index| CipherImports::<T>::get(vote_id, index))
+        .collect()
+}
@@ -0,0 +1,40 @@
+use crate::types::{ShuffleState, ShuffleStatus, TopicId, VoteId, SHUFFLE_STATUS_PREFIX};
+use crate::{ShuffleStateStore, Trait};
+use codec::Encode;
+use frame_support::storage::StorageMap;
+use sp_std::vec::Vec;
+
+/// all functions related to the offchain indexing of the mixing progress
+///
+/// returns the key of the shuffle status of the topic in the offchain index.
+/// the value is the SCALE encoded `ShuffleStatus`, e.g. read using the RPC `offchain_localStorageGet`.
+pub fn get_shuffle_status_key(vote_id: &VoteId, topic_id: &TopicId) -> Vec<u8> {
+    let mut key: Vec<u8> = SHUFFLE_STATUS_PREFIX.to_vec();
+    key.extend((vote_id, topic_id).encode());
+    key
+}
+
+/// stores the shuffle state of the topic and writes its status to the offchain index,
+/// i.e., the nodes with offchain indexing enabled can serve the mixing progress without
+/// querying the state. the index is written by all nodes importing the block.
+pub fn set_shuffle_state<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    state: ShuffleState,
+) {
+    let status = ShuffleStatus {
+        state: state.clone(),
+        updated_at: <frame_system::Module<T>>::block_number(),
+    };
+    sp_io::offchain_index::set(
+        &get_shuffle_status_key(vote_id, topic_id),
+        &status.encode(),
+    );
+    ShuffleStateStore::insert((vote_id, topic_id), state);
+}
+
+/// removes the shuffle state of the topic and its status from the offchain index
+pub fn remove_shuffle_state(vote_id: &VoteId, topic_id: &TopicId) {
+    sp_io::offchain_index::clear(&get_shuffle_status_key(vote_id, topic_id));
+    ShuffleStateStore::remove((vote_id, topic_id));
+}
@@ -0,0 +1,90 @@
+use crate::types::{BallotRate, VoteId};
+use crate::{BalanceOf, BallotDeposits, BallotRates, Error, Trait, WhitelistedVotes};
+use frame_support::{
+    ensure,
+    storage::{StorageDoubleMap, StorageMap},
+    traits::{Get, ReservableCurrency},
+};
+use sp_runtime::traits::{Saturating, Zero};
+
+/// all functions related to the spam protection of ballot submissions
+///
+/// returns the ballot rate of the account including the next ballot.
+/// an account can submit `MaxBallotsPerPeriod` ballots per `BallotRatePeriod` blocks,
+/// the period starts with the first ballot after the previous period has ended.
+/// the ballot rate is only stored once the ballot has been accepted, see: store_ballot_rate
+pub fn ensure_ballot_rate_limit<T: Trait>(
+    who: &T::AccountId,
+) -> Result<BallotRate<T::BlockNumber>, Error<T>> {
+    let now = <frame_system::Module<T>>::block_number();
+    let rate = BallotRates::<T>::get(who);
+    let period_end = rate.period_start.saturating_add(T::BallotRatePeriod::get());
+    if rate.count == 0 || now >= period_end {
+        return Ok(BallotRate {
+            period_start: now,
+            count: 1,
+        });
+    }
+    ensure!(
+        rate.count < T::MaxBallotsPerPeriod::get(),
+        Error::<T>::BallotRateLimitExceeded
+    );
+    Ok(BallotRate {
+        period_start: rate.period_start,
+        count: rate.count + 1,
+    })
+}
+
+pub fn store_ballot_rate<T: Trait>(who: &T::AccountId, rate: BallotRate<T::BlockNumber>) {
+    BallotRates::<T>::insert(who, rate);
+}
+
+/// reserves the ballot deposit of the voter, unless the vote is whitelisted.
+/// the deposits of all ballots of the voter are refunded together, see: refund_ballot_deposit
+pub fn reserve_ballot_deposit<T: Trait>(
+    who: &T::AccountId,
+    vote_id: &VoteId,
+) -> Result<(), Error<T>> {
+    let deposit: BalanceOf<T> = T::BallotDeposit::get();
+    if deposit.is_zero() || WhitelistedVotes::get(vote_id) {
+        return Ok(());
+    }
+    T::Currency::reserve(who, deposit)
+        .map_err(|_| Error::<T>::InsufficientBallotDeposit)?;
+    BallotDeposits::<T>::mutate(vote_id, who, |reserved| {
+        *reserved = reserved.saturating_add(deposit)
+    });
+    Ok(())
+}
+
+/// ensures that the voter can reserve the ballot deposit without reserving it,
+/// see: reserve_ballot_deposit
+pub fn ensure_ballot_deposit<T: Trait>(
+    who: &T::AccountId,
+    vote_id: &VoteId,
+) -> Result<(), Error<T>> {
+    let deposit: BalanceOf<T> = T::BallotDeposit::get();
+    if deposit.is_zero() || WhitelistedVotes::get(vote_id) {
+        return Ok(());
+    }
+    ensure!(
+        T::Currency::can_reserve(who, deposit),
+        Error::<T>::InsufficientBallotDeposit
+    );
+    Ok(())
+}
+
+/// unreserves all ballot deposits of the voter for the vote, returns the refunded amount
+pub fn refund_ballot_deposit<T: Trait>(
+    who: &T::AccountId,
+    vote_id: &VoteId,
+) -> Result<BalanceOf<T>, Error<T>> {
+    ensure!(
+        BallotDeposits::<T>::contains_key(vote_id, who),
+        Error::<T>::NoBallotDeposit
+    );
+    let deposit: BalanceOf<T> = BallotDeposits::<T>::take(vote_id, who);
+    // the deposit can't be slashed, i.e., the whole deposit is unreserved
+    T::Currency::unreserve(who, deposit);
+    Ok(deposit)
+}
@@ -0,0 +1,109 @@
+use super::sealers::{get_vote_sealers, is_vote_sealer};
+use crate::types::{Topic, VoteId};
+use crate::{HeartbeatDeadlines, LastHeartbeats, MonitoredVotes, Tally, Topics, Trait};
+use frame_support::{
+    storage::{IterableStorageMap, StorageMap, StorageValue},
+    traits::Get,
+    weights::Weight,
+};
+use sp_runtime::traits::Saturating;
+use sp_std::vec::Vec;
+
+/// all functions related to the liveness of the sealers while votes are being tallied
+///
+/// returns the number of blocks a sealer has to send its next heartbeat,
+/// i.e., a single missed heartbeat is tolerated
+fn get_heartbeat_window<T: Trait>() -> T::BlockNumber {
+    T::HeartbeatInterval::get().saturating_add(T::HeartbeatInterval::get())
+}
+
+/// returns true if the sealers must send heartbeats, i.e., a vote is being tallied
+pub fn is_liveness_monitored() -> bool {
+    !MonitoredVotes::get().is_empty()
+}
+
+/// returns true if all topics of the vote have been tallied
+pub fn is_vote_tallied<T: Trait>(vote_id: &VoteId) -> bool {
+    let topics: Vec<Topic> = Topics::get(vote_id);
+    topics
+        .iter()
+        .all(|Topic { id: topic_id, .. }| Tally::contains_key((vote_id, topic_id)))
+}
+
+/// starts to monitor the liveness of the sealers while the vote is being tallied.
+/// the sealers which aren't monitored yet have to send a heartbeat within the next window.
+pub fn start_liveness_monitoring<T: Trait>(vote_id: &VoteId) {
+    let mut votes: Vec<VoteId> = MonitoredVotes::get();
+    if votes.contains(vote_id) {
+        return;
+    }
+    votes.push(vote_id.clone());
+    MonitoredVotes::put(votes);
+
+    let deadline = <frame_system::Module<T>>::block_number()
+        .saturating_add(get_heartbeat_window::<T>());
+    for sealer in get_vote_sealers::<T>(vote_id).iter() {
+        if !HeartbeatDeadlines::<T>::contains_key(sealer) {
+            HeartbeatDeadlines::<T>::insert(sealer, deadline);
+        }
+    }
+}
+
+/// stops to monitor the liveness of the sealers for the vote, i.e., the vote has been tallied.
+/// the deadlines of the sealers are removed once none of their votes is being tallied anymore.
+pub fn stop_liveness_monitoring<T: Trait>(vote_id: &VoteId) {
+    let mut votes: Vec<VoteId> = MonitoredVotes::get();
+    if !votes.contains(vote_id) {
+        return;
+    }
+    votes.retain(|monitored| monitored != vote_id);
+    MonitoredVotes::put(&votes);
+
+    for sealer in get_vote_sealers::<T>(vote_id).iter() {
+        if !votes
+            .iter()
+            .any(|monitored| is_vote_sealer::<T>(sealer, monitored))
+        {
+            HeartbeatDeadlines::<T>::remove(sealer);
+        }
+    }
+}
+
+/// records the heartbeat of the sealer and moves its deadline to the end of the next window
+pub fn record_heartbeat<T: Trait>(sealer: &T::AccountId) {
+    let block_number = <frame_system::Module<T>>::block_number();
+    LastHeartbeats::<T>::insert(sealer, block_number);
+    if is_liveness_monitored() {
+        let deadline = block_number.saturating_add(get_heartbeat_window::<T>());
+        HeartbeatDeadlines::<T>::insert(sealer, deadline);
+    }
+}
+
+/// returns the sealers which missed their heartbeat window together with their last heartbeat.
+/// an unresponsive sealer is reported again after another window, until it sends a heartbeat.
+pub fn take_unresponsive_sealers<T: Trait>(
+    block_number: T::BlockNumber,
+) -> (Vec<(T::AccountId, Option<T::BlockNumber>)>, Weight) {
+    let deadlines: Vec<(T::AccountId, T::BlockNumber)> =
+        HeartbeatDeadlines::<T>::iter().collect();
+    let nr_of_deadlines = deadlines.len() as Weight;
+
+    let missed = deadlines
+        .into_iter()
+        .filter(|(_, deadline)| block_number > *deadline);
+    let mut reports = Vec::new();
+    for (sealer, _) in missed {
+        let next = block_number.saturating_add(get_heartbeat_window::<T>());
+        HeartbeatDeadlines::<T>::insert(&sealer, next);
+        let last_heartbeat = LastHeartbeats::<T>::get(&sealer);
+        reports.push((sealer, last_heartbeat));
+    }
+
+    // the deadlines and the last heartbeat of each unresponsive sealer
+    let nr_of_unresponsive = reports.len() as Weight;
+    let db = T::DbWeight::get();
+    let weight = db
+        .reads(nr_of_deadlines + nr_of_unresponsive)
+        .saturating_add(db.writes(nr_of_unresponsive));
+    (reports, weight)
+}
@@ -0,0 +1,133 @@
+use crate::types::CipherMerkleProof;
+use codec::Encode;
+use sp_io::hashing::blake2_256;
+use sp_std::vec::Vec;
+
+/// all functions related to merkle tree commitments
+///
+/// the tree is a binary blake2_256 merkle tree. a node without a sibling
+/// is promoted to the next level unchanged. the root of an empty tree is zero.
+pub fn hash_leaf<E: Encode>(item: &E) -> [u8; 32] {
+    blake2_256(&item.encode())
+}
+
+/// hashes two sibling nodes into their parent node
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut input: Vec<u8> = Vec::with_capacity(64);
+    input.extend_from_slice(left);
+    input.extend_from_slice(right);
+    blake2_256(&input)
+}
+
+/// computes the next level of the tree
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_node(left, right),
+            [single] => *single,
+            _ => unreachable!("chunks(2) returns either one or two nodes"),
+        })
+        .collect()
+}
+
+/// computes the merkle root of the leaves
+pub fn merkle_root(leaves: Vec<[u8; 32]>) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+    let mut level = leaves;
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// returns the sibling hashes from the leaf at position index up to the root.
+/// levels on which the node is promoted don't contribute a sibling.
+pub fn merkle_path(leaves: Vec<[u8; 32]>, index: usize) -> Vec<[u8; 32]> {
+    let mut path: Vec<[u8; 32]> = Vec::new();
+    let mut level = leaves;
+    let mut index = index;
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        if sibling < level.len() {
+            path.push(level[sibling]);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    path
+}
+
+/// recomputes the root from the leaf and its path and compares it to the expected root
+pub fn verify_merkle_path(
+    root: &[u8; 32],
+    leaf: &[u8; 32],
+    index: u64,
+    nr_of_leaves: u64,
+    path: &[[u8; 32]],
+) -> bool {
+    if index >= nr_of_leaves {
+        return false;
+    }
+    let mut siblings = path.iter();
+    let mut hash = *leaf;
+    let mut index = index;
+    let mut width = nr_of_leaves;
+    while width > 1 {
+        let is_right_node = index % 2 == 1;
+        if is_right_node || index + 1 < width {
+            let sibling = match siblings.next() {
+                Some(sibling) => sibling,
+                None => return false,
+            };
+            hash = if is_right_node {
+                hash_node(sibling, &hash)
+            } else {
+                hash_node(&hash, sibling)
+            };
+        }
+        index /= 2;
+        width = (width + 1) / 2;
+    }
+    siblings.next().is_none() && &hash == root
+}
+
+/// appends a leaf to the peaks of an append-only tree.
+/// the peaks are the roots of the perfect subtrees, indexed by their height.
+/// requires only log(n) hashes instead of recomputing the whole tree.
+pub fn append_leaf(peaks: &mut Vec<Option<[u8; 32]>>, leaf: [u8; 32]) {
+    let mut carry = leaf;
+    let mut height = 0;
+    while let Some(Some(peak)) = peaks.get(height) {
+        carry = hash_node(peak, &carry);
+        peaks[height] = None;
+        height += 1;
+    }
+    if height == peaks.len() {
+        peaks.push(Some(carry));
+    } else {
+        peaks[height] = Some(carry);
+    }
+}
+
+/// computes the merkle root from the peaks, equal to `merkle_root` of all appended leaves.
+/// the peaks are folded from the smallest to the largest subtree.
+pub fn root_from_peaks(peaks: &[Option<[u8; 32]>]) -> [u8; 32] {
+    peaks
+        .iter()
+        .flatten()
+        .fold(None, |root: Option<[u8; 32]>, peak| match root {
+            None => Some(*peak),
+            Some(root) => Some(hash_node(peak, &root)),
+        })
+        .unwrap_or([0u8; 32])
+}
+
+impl CipherMerkleProof {
+    /// verifies that the cipher is included in the cipher list committed to by the root
+    pub fn verify(&self, root: &[u8; 32]) -> bool {
+        verify_merkle_path(root, &self.leaf, self.index, self.nr_of_leaves, &self.path)
+    }
+}
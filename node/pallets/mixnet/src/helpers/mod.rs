@@ -1,7 +1,27 @@
+pub mod archive;
 pub mod array;
 pub mod assertions;
 pub mod ballot;
+pub mod certification;
+pub mod ciphers;
+pub mod credentials;
+pub mod freeze;
+pub mod governance;
+pub mod imports;
+pub mod indexing;
+pub mod limits;
+pub mod liveness;
 pub mod math;
+pub mod merkle;
+pub mod packing;
 pub mod params;
 pub mod phase;
+pub mod progress;
 pub mod random;
+pub mod registration;
+pub mod sealers;
+pub mod templates;
+pub mod tokens;
+pub mod transcripts;
+pub mod unsigned;
+pub mod voter_weights;
@@ -0,0 +1,105 @@
+use super::{assertions::ensure_vote_not_started, params::get_public_params};
+use crate::dkg::aggregate::get_tally_strategy;
+use crate::types::{
+    TallyStrategy, Topic, TopicId, TopicPacking, TopicResult, VoteId, MAX_PACKED_SLOTS,
+};
+use crate::{Error, PackedTally, PackedTopics, Topics, Trait};
+use crypto::{encryption::ElGamal, types::ElGamalParams};
+use frame_support::{
+    ensure,
+    storage::{StorageDoubleMap, StorageMap},
+};
+use num_bigint::BigUint;
+use sp_std::{collections::btree_map::BTreeMap, vec, vec::Vec};
+
+/// all functions related to the packing of several questions into a single cipher (ballot packing)
+///
+/// returns the packing of the topic, None if its answers are not packed
+pub fn get_topic_packing(vote_id: &VoteId, topic_id: &TopicId) -> Option<TopicPacking> {
+    PackedTopics::get((vote_id, topic_id))
+}
+
+/// ensures that the answers of the topic can be packed: the vote is shuffled, the topic is a
+/// free-form question of the vote (the answers of packed questions can't be proven to be valid)
+/// and the slots fit into a single message of the vote's group, see: `ElGamal::packing_capacity`.
+fn ensure_valid_packing<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    packing: &TopicPacking,
+) -> Result<(), Error<T>> {
+    ensure!(
+        get_tally_strategy::<T>(vote_id) == TallyStrategy::Mixnet,
+        Error::<T>::WrongTallyStrategy
+    );
+    let topic: Topic = Topics::get(vote_id)
+        .into_iter()
+        .find(|topic| topic.id == *topic_id)
+        .ok_or(Error::<T>::TopicNotInVote)?;
+    ensure!(
+        !topic.requires_validity_proof(),
+        Error::<T>::InvalidTopicPacking
+    );
+
+    let params: ElGamalParams = get_public_params::<T>(vote_id)?.into();
+    let capacity = ElGamal::packing_capacity(packing.slot_bits, &params);
+    ensure!(
+        packing.slots > 0
+            && packing.slots <= MAX_PACKED_SLOTS
+            && packing.slots as usize <= capacity,
+        Error::<T>::InvalidTopicPacking
+    );
+    Ok(())
+}
+
+/// packs (or unpacks, None) the answers of the topic before the voting phase starts
+pub fn store_topic_packing<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    packing: Option<TopicPacking>,
+) -> Result<(), Error<T>> {
+    ensure_vote_not_started::<T>(vote_id)?;
+    match packing {
+        Some(packing) => {
+            ensure_valid_packing::<T>(vote_id, topic_id, &packing)?;
+            PackedTopics::insert((vote_id, topic_id), packing);
+        }
+        None => PackedTopics::remove((vote_id, topic_id)),
+    }
+    Ok(())
+}
+
+/// unpacks the decrypted messages of the topic and stores the number of votes per answer and slot
+/// (see: `PackedTally`). the messages which can't be unpacked (e.g. invalid messages) count as they are,
+/// they are returned as the result of the topic.
+pub fn count_packed_answers<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    packing: &TopicPacking,
+    partial: Vec<(BigUint, u64)>,
+) -> Result<BTreeMap<BigUint, BigUint>, Error<T>> {
+    let params: ElGamalParams = get_public_params::<T>(vote_id)?.into();
+    let nr_of_slots = packing.slots as usize;
+
+    let mut slots: Vec<BTreeMap<BigUint, BigUint>> = vec![BTreeMap::new(); nr_of_slots];
+    let mut unpacked: BTreeMap<BigUint, BigUint> = BTreeMap::new();
+    for (message, count) in partial.into_iter() {
+        let count = BigUint::from(count);
+        match ElGamal::unpack(&message, nr_of_slots, packing.slot_bits, &params) {
+            Ok(answers) => {
+                for (slot, answer) in answers.into_iter().enumerate() {
+                    *slots[slot].entry(BigUint::from(answer)).or_default() += &count;
+                }
+            }
+            Err(_) => *unpacked.entry(message).or_default() += &count,
+        }
+    }
+
+    for (slot, counts) in slots.into_iter().enumerate() {
+        let result: TopicResult = counts
+            .iter()
+            .map(|(answer, count)| (answer.to_bytes_be(), count.to_bytes_be()))
+            .collect();
+        PackedTally::insert((vote_id, topic_id), slot as u32, result);
+    }
+    Ok(unpacked)
+}
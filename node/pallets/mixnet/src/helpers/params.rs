@@ -1,9 +1,12 @@
 use super::assertions::ensure_vote_exists;
 use crate::{
-    types::{PublicKey as SubstratePK, PublicParameters, Vote, VoteId},
-    Error, PublicKey, Trait, Votes,
+    types::{
+        HashFunction, ProtocolConfig, PublicKey as SubstratePK, PublicParameters, Vote,
+        VoteId,
+    },
+    Error, ProtocolConfigs, PublicKey, PublicKeyShares, Trait, Votes,
 };
-use frame_support::storage::StorageMap;
+use frame_support::{ensure, storage::StorageMap};
 
 /// all functions related to key generation and decrypted share operations
 pub fn get_public_params<T: Trait>(
@@ -19,3 +22,39 @@ pub fn get_public_params<T: Trait>(
 pub fn get_public_key<T: Trait>(vote_id: &VoteId) -> Result<SubstratePK, Error<T>> {
     PublicKey::get(vote_id).ok_or(Error::<T>::PublicKeyNotExistsError)
 }
+
+/// returns the parameters of the protocol the vote has been created with,
+/// fails if the proofs of the vote cannot be generated or verified by this release
+pub fn get_protocol_config<T: Trait>(
+    vote_id: &VoteId,
+) -> Result<ProtocolConfig, Error<T>> {
+    ProtocolConfigs::get(vote_id)
+        .filter(|config| config.is_supported())
+        .ok_or(Error::<T>::UnsupportedProtocol)
+}
+
+/// returns the hash function of the challenges of the proofs of the vote,
+/// Blake2b if the vote has no protocol
+pub fn get_hash_function<T: Trait>(vote_id: &VoteId) -> crypto::hash::HashFunction {
+    ProtocolConfigs::get(vote_id)
+        .map(|config| config.hash_function)
+        .unwrap_or_default()
+        .into()
+}
+
+/// records the hash function in the protocol of the vote.
+/// the proofs of the public key shares are bound to it, i.e., it can't be changed once
+/// the first share has been submitted.
+pub fn store_hash_function<T: Trait>(
+    vote_id: &VoteId,
+    hash_function: HashFunction,
+) -> Result<(), Error<T>> {
+    ensure!(
+        PublicKeyShares::get(vote_id).is_empty(),
+        Error::<T>::KeyGenerationStarted
+    );
+    let mut config: ProtocolConfig = get_protocol_config::<T>(vote_id)?;
+    config.hash_function = hash_function;
+    ProtocolConfigs::insert(vote_id, config);
+    Ok(())
+}
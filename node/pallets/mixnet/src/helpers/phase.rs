@@ -1,23 +1,142 @@
-use super::assertions::{ensure_vote_exists, ensure_voting_authority};
-use crate::types::{Vote, VoteId, VotePhase};
-use crate::{Error, Trait, Votes};
+use super::assertions::{
+    ensure_no_key_switch_pending, ensure_valid_phase_transition, ensure_vote_exists,
+    ensure_vote_not_cancelled, ensure_vote_not_started, ensure_voting_authority,
+};
+use super::credentials::start_credential_check;
+use super::freeze::freeze_ballot_box;
+use super::indexing::set_shuffle_state;
+use super::liveness::{start_liveness_monitoring, stop_liveness_monitoring};
+use super::progress::stop_decryption;
+use crate::shuffle::turn::start_shuffle_turn;
+use crate::types::{Topic, Vote, VoteDetails, VoteId, VotePhase};
+use crate::{
+    Error, Module, PublicKey, ShuffleBatchSizes, ShuffleStateStore, ShuffleTurns,
+    TallyingApprovals, Topics, Trait, Votes, VotingStarted,
+};
 use frame_support::{debug, storage::StorageMap};
 
-/// all functions related to key generation and decrypted share operations
+/// changes the phase of the vote, the transition must be valid (see: `VotePhase::can_transition_to`).
+/// returns the previous phase of the vote.
 pub fn set_phase<T: Trait>(
     who: &T::AccountId,
     vote_id: &VoteId,
     phase: VotePhase,
-) -> Result<(), Error<T>> {
+) -> Result<VotePhase, Error<T>> {
     // only the voting_authority should be able to store the key
     ensure_voting_authority::<T>(who)?;
     // pase can only be changed if the vote exists
     ensure_vote_exists(vote_id)?;
+    // a cancelled vote never leaves its phase
+    ensure_vote_not_cancelled(vote_id)?;
+    ensure_valid_phase_transition::<T>(vote_id, &phase)?;
+    Ok(enter_phase::<T>(vote_id, phase))
+}
 
+/// changes the phase of the vote regardless of the transition table, e.g. to repair a vote
+/// stuck in a phase. returns the previous phase of the vote.
+///
+/// only the transition table and the tallying approvals are bypassed, the guards protecting
+/// the ciphers still apply: the tallying phase requires the ciphers to be encrypted under the
+/// current key and a started vote only returns to the key generation by rotating its key.
+pub fn force_phase<T: Trait>(
+    vote_id: &VoteId,
+    phase: VotePhase,
+) -> Result<VotePhase, Error<T>> {
+    ensure_vote_exists(vote_id)?;
+    // a cancelled vote never leaves its phase
+    ensure_vote_not_cancelled(vote_id)?;
+    if phase == VotePhase::Tallying {
+        ensure_no_key_switch_pending::<T>(vote_id)?;
+    }
+    if phase == VotePhase::KeyGeneration {
+        ensure_vote_not_started::<T>(vote_id)?;
+    }
+    Ok(enter_phase::<T>(vote_id, phase))
+}
+
+fn enter_phase<T: Trait>(vote_id: &VoteId, phase: VotePhase) -> VotePhase {
     // set the new phase
     let mut vote: Vote<T::AccountId> = Votes::<T>::get(&vote_id);
+    let previous: VotePhase = vote.phase;
     vote.phase = phase.clone();
     Votes::<T>::insert(&vote_id, &vote);
+
+    // remember that the vote has started, the key rotation returns to the key generation phase
+    if phase != VotePhase::KeyGeneration {
+        VotingStarted::insert(&vote_id, true);
+    }
+
+    // the ballots with an invalid credential are discarded before the ballot box is frozen,
+    // the shuffles start once the credentials have been checked (see: submit_credential_decryptions)
+    if phase == VotePhase::Tallying {
+        if !start_credential_check::<T>(vote_id) {
+            start_tallying::<T>(vote_id);
+        }
+
+        // the sealers send heartbeats until the vote has been tallied
+        start_liveness_monitoring::<T>(vote_id);
+    }
     debug::info!("vote phase updated! new phase: {:?}", phase);
-    Ok(())
+    previous
+}
+
+/// freezes the ballot box and the first sealer starts to shuffle,
+/// the first batch is shuffled with the batch size set by the voting authority (if any)
+pub fn start_tallying<T: Trait>(vote_id: &VoteId) {
+    // the shuffle proofs are bound to the ciphers present at the end of the voting phase,
+    // re-entering the tallying phase (see: force_phase) keeps the first freeze
+    freeze_ballot_box::<T>(vote_id);
+
+    let batch_size: Option<u64> = ShuffleBatchSizes::get(vote_id);
+    for Topic { id: topic_id, .. } in Topics::get(vote_id).iter() {
+        if !ShuffleTurns::<T>::contains_key((vote_id, topic_id)) {
+            start_shuffle_turn::<T>(vote_id, topic_id, 0);
+            if let Some(batch_size) = batch_size {
+                if let Some(mut state) = ShuffleStateStore::get((vote_id, topic_id)) {
+                    state.batch_size = batch_size;
+                    set_shuffle_state::<T>(vote_id, topic_id, state);
+                }
+            }
+        }
+    }
+}
+
+/// cancels the vote: neither ballots nor shuffles, decrypted shares and the tally are accepted anymore.
+/// the pending decryptions and the liveness monitoring of the vote are stopped, the ballot deposits
+/// can be claimed and the vote can be archived afterwards.
+pub fn cancel<T: Trait>(vote_id: &VoteId) {
+    let mut vote: Vote<T::AccountId> = Votes::<T>::get(vote_id);
+    vote.phase = VotePhase::Cancelled;
+    Votes::<T>::insert(vote_id, &vote);
+
+    for Topic { id: topic_id, .. } in Topics::get(vote_id).iter() {
+        stop_decryption::<T>(vote_id, topic_id);
+    }
+    TallyingApprovals::<T>::remove(vote_id);
+    stop_liveness_monitoring::<T>(vote_id);
+    debug::info!("vote cancelled: {:?}", vote_id);
+}
+
+impl<T: Trait> Module<T> {
+    /// returns the current phase of the vote, None if the vote doesn't exist
+    pub fn vote_phase(vote_id: &VoteId) -> Option<VotePhase> {
+        if Votes::<T>::contains_key(vote_id) {
+            Some(Votes::<T>::get(vote_id).phase)
+        } else {
+            None
+        }
+    }
+
+    /// returns the vote together with its questions and its public key,
+    /// None if the vote doesn't exist
+    pub fn vote_details(vote_id: &VoteId) -> Option<VoteDetails<T::AccountId>> {
+        if !Votes::<T>::contains_key(vote_id) {
+            return None;
+        }
+        Some(VoteDetails {
+            vote: Votes::<T>::get(vote_id),
+            topics: Topics::get(vote_id),
+            public_key: PublicKey::get(vote_id),
+        })
+    }
 }
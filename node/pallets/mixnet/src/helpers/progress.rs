@@ -0,0 +1,129 @@
+use super::{ciphers::get_cipher_count, sealers::get_vote_sealers};
+use crate::types::{PendingDecryption, Topic, TopicId, TopicProgress, VoteId};
+use crate::{
+    DecryptedShares, DecryptionThresholds, Module, PendingDecryptions, Sealers,
+    ShuffleStateStore, Tally, Topics, Trait, Votes,
+};
+use frame_support::{
+    storage::{IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue},
+    traits::Get,
+    weights::Weight,
+};
+use sp_runtime::traits::Saturating;
+use sp_std::vec::Vec;
+
+/// all functions related to the mixing and decryption progress of a vote
+///
+/// returns the number of sealers which submitted their decrypted shares for the topic
+pub fn get_decrypted_share_count<T: Trait>(vote_id: &VoteId, topic_id: &TopicId) -> u32 {
+    get_vote_sealers::<T>(vote_id)
+        .iter()
+        .filter(|sealer| {
+            !DecryptedShares::<T>::get((vote_id, topic_id), sealer).is_empty()
+        })
+        .count() as u32
+}
+
+/// returns the number of sealers which must submit their decrypted shares,
+/// i.e., the decryption threshold of the vote (threshold keys) or all sealers of the vote
+pub fn get_required_share_count<T: Trait>(vote_id: &VoteId) -> u32 {
+    DecryptionThresholds::get(vote_id)
+        .unwrap_or_else(|| get_vote_sealers::<T>(vote_id).len() as u32)
+}
+
+/// returns the sealers of the vote which haven't submitted their decrypted shares for the topic
+pub fn get_missing_sealers<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+) -> Vec<T::AccountId> {
+    get_vote_sealers::<T>(vote_id)
+        .into_iter()
+        .filter(|sealer| {
+            DecryptedShares::<T>::get((vote_id, topic_id), sealer).is_empty()
+        })
+        .collect()
+}
+
+/// starts to track the decryption of the topic. the decryption is reported as stalled
+/// if not sufficient sealers submitted their decrypted shares within `DecryptionTimeout` blocks.
+pub fn start_decryption<T: Trait>(vote_id: &VoteId, topic_id: &TopicId) {
+    let deadline = <frame_system::Module<T>>::block_number()
+        .saturating_add(T::DecryptionTimeout::get());
+    let pending = PendingDecryption {
+        vote_id: vote_id.clone(),
+        deadline,
+    };
+    PendingDecryptions::<T>::insert((vote_id, topic_id), pending);
+}
+
+/// stops to track the decryption of the topic, i.e., the topic can be tallied
+pub fn stop_decryption<T: Trait>(vote_id: &VoteId, topic_id: &TopicId) {
+    PendingDecryptions::<T>::remove((vote_id, topic_id));
+}
+
+/// returns the topics whose decryption timed out together with the sealers which haven't
+/// submitted their decrypted shares. a stalled decryption is reported again after another
+/// `DecryptionTimeout` blocks, until sufficient decrypted shares have been submitted.
+pub fn take_stalled_decryptions<T: Trait>(
+    block_number: T::BlockNumber,
+) -> (Vec<(VoteId, TopicId, Vec<T::AccountId>)>, Weight) {
+    let pending: Vec<((VoteId, TopicId), PendingDecryption<T::BlockNumber>)> =
+        PendingDecryptions::<T>::iter().collect();
+    let nr_of_pending = pending.len() as Weight;
+    // estimated with the sealers of new votes
+    let nr_of_sealers = Sealers::<T>::decode_len().unwrap_or_default() as Weight;
+
+    let stalled = pending
+        .into_iter()
+        .filter(|(_, pending)| block_number > pending.deadline);
+    let mut reports = Vec::new();
+    for ((vote_id, topic_id), pending) in stalled {
+        let missing: Vec<T::AccountId> = get_missing_sealers::<T>(&vote_id, &topic_id);
+        let next = PendingDecryption {
+            vote_id: pending.vote_id,
+            deadline: block_number.saturating_add(T::DecryptionTimeout::get()),
+        };
+        PendingDecryptions::<T>::insert((&vote_id, &topic_id), next);
+        reports.push((vote_id, topic_id, missing));
+    }
+
+    // the pending decryptions, the sealers and their shares for each stalled topic
+    let nr_of_stalled = reports.len() as Weight;
+    let db = T::DbWeight::get();
+    let weight = db
+        .reads(nr_of_pending + 1)
+        .saturating_add(db.reads(nr_of_stalled * (nr_of_sealers + 1)))
+        .saturating_add(db.writes(nr_of_stalled));
+    (reports, weight)
+}
+
+fn get_topic_progress<T: Trait>(vote_id: &VoteId, topic_id: &TopicId) -> TopicProgress {
+    let nr_of_shuffles = Votes::<T>::get(vote_id).nr_of_shuffles;
+    let shuffle_state = ShuffleStateStore::get((vote_id, topic_id)).unwrap_or_default();
+    TopicProgress {
+        topic_id: topic_id.clone(),
+        nr_of_ciphers: get_cipher_count::<T>(vote_id, topic_id, 0),
+        nr_of_shuffles,
+        shuffle_iteration: shuffle_state.iteration,
+        shuffle_start_position: shuffle_state.start_position,
+        shuffle_done: shuffle_state.done,
+        decrypted_shares: get_decrypted_share_count::<T>(vote_id, topic_id),
+        required_shares: get_required_share_count::<T>(vote_id),
+        tallied: Tally::contains_key((vote_id, topic_id)),
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// returns the mixing and decryption progress of all topics of the vote,
+    /// empty if the vote doesn't exist
+    pub fn progress(vote_id: &VoteId) -> Vec<TopicProgress> {
+        if !Votes::<T>::contains_key(vote_id) {
+            return Vec::new();
+        }
+        let topics: Vec<Topic> = Topics::get(vote_id);
+        topics
+            .iter()
+            .map(|Topic { id: topic_id, .. }| get_topic_progress::<T>(vote_id, topic_id))
+            .collect()
+    }
+}
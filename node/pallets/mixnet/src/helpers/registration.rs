@@ -0,0 +1,105 @@
+use super::{assertions::ensure_vote_not_started, ballot::exclude_ballot_ciphers};
+use crate::types::{ClaimSignature, IdentityProvider, VoteId, VotePhase};
+use crate::{Error, IdentityProviders, RegisteredVoters, RevokedVoters, Trait, Votes};
+use codec::Encode;
+use frame_support::{
+    ensure,
+    storage::{StorageDoubleMap, StorageMap},
+};
+use mixnet_primitives::claims::eligibility_claim_message;
+
+/// all functions related to the registration of voters by the eligibility claims of an identity provider
+///
+/// sets the identity provider of the vote (None removes it).
+/// the voters of a vote with an identity provider must be registered to cast a ballot,
+/// therefore, the identity provider can only be changed before the voting phase starts.
+pub fn store_identity_provider<T: Trait>(
+    vote_id: &VoteId,
+    provider: Option<IdentityProvider>,
+) -> Result<(), Error<T>> {
+    ensure_vote_not_started::<T>(vote_id)?;
+    match provider {
+        Some(provider) => IdentityProviders::insert(vote_id, provider),
+        None => IdentityProviders::remove(vote_id),
+    }
+    Ok(())
+}
+
+/// registers the voter once the signature of the eligibility claim has been verified,
+/// the claim is signed by the identity provider of the vote (see: mixnet_primitives::claims).
+pub fn register_voter<T: Trait>(
+    vote_id: &VoteId,
+    voter: &T::AccountId,
+    signature: &ClaimSignature,
+) -> Result<(), Error<T>> {
+    let provider: IdentityProvider =
+        IdentityProviders::get(vote_id).ok_or(Error::<T>::NoIdentityProvider)?;
+    let phase = Votes::<T>::get(vote_id).phase;
+    ensure!(
+        phase != VotePhase::Tallying && phase != VotePhase::Cancelled,
+        Error::<T>::WrongVotePhase
+    );
+    ensure!(
+        !RegisteredVoters::<T>::get(vote_id, voter),
+        Error::<T>::VoterAlreadyRegistered
+    );
+    ensure_not_revoked::<T>(vote_id, voter)?;
+
+    let message = eligibility_claim_message(vote_id, &voter.encode());
+    ensure!(
+        provider.verify(&message, signature),
+        Error::<T>::InvalidEligibilityClaim
+    );
+    RegisteredVoters::<T>::insert(vote_id, voter, true);
+    Ok(())
+}
+
+/// the voters of a vote with an identity provider must be registered,
+/// all voters can cast a ballot for votes without identity provider.
+pub fn ensure_registered_voter<T: Trait>(
+    vote_id: &VoteId,
+    voter: &T::AccountId,
+) -> Result<(), Error<T>> {
+    if IdentityProviders::contains_key(vote_id) {
+        ensure!(
+            RegisteredVoters::<T>::get(vote_id, voter),
+            Error::<T>::VoterNotRegistered
+        );
+    }
+    Ok(())
+}
+
+/// revokes the voter and adds it to the revocation list of the vote, the voter is unregistered
+/// and the ciphers of its last ballot are excluded. returns the number of excluded ciphers.
+/// the ballot box is frozen once the voting phase has ended, later revocations are rejected.
+pub fn revoke_voter<T: Trait>(
+    vote_id: &VoteId,
+    voter: &T::AccountId,
+) -> Result<u32, Error<T>> {
+    let phase = Votes::<T>::get(vote_id).phase;
+    ensure!(
+        phase == VotePhase::KeyGeneration || phase == VotePhase::Voting,
+        Error::<T>::LateVoterRevocation
+    );
+    ensure!(
+        !RevokedVoters::<T>::contains_key(vote_id, voter),
+        Error::<T>::VoterAlreadyRevoked
+    );
+
+    let block_number = <frame_system::Module<T>>::block_number();
+    RevokedVoters::<T>::insert(vote_id, voter, block_number);
+    RegisteredVoters::<T>::remove(vote_id, voter);
+    Ok(exclude_ballot_ciphers::<T>(voter, vote_id))
+}
+
+/// ensures that the voter isn't on the revocation list of the vote.
+pub fn ensure_not_revoked<T: Trait>(
+    vote_id: &VoteId,
+    voter: &T::AccountId,
+) -> Result<(), Error<T>> {
+    ensure!(
+        !RevokedVoters::<T>::contains_key(vote_id, voter),
+        Error::<T>::VoterRevoked
+    );
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::types::VoteId;
+use crate::{
+    Error, MonitoredVotes, SealerEpoch, SealerSets, Sealers, Trait, VoteSealerEpochs,
+};
+use frame_support::{
+    ensure,
+    storage::{StorageMap, StorageValue},
+};
+use sp_std::vec::Vec;
+
+/// all functions related to the sealers of the votes
+///
+/// the sealers are tracked in epochs: every change of `Sealers` starts a new epoch and
+/// records its sealers in `SealerSets`. a vote is bound to the epoch it has been created in,
+/// i.e., the sealers which generate its key, shuffle and decrypt it never change while it is
+/// in flight. votes created before the epochs were introduced belong to the epoch 0.
+///
+/// returns the sealers of the vote, in the order of their turns (and key shares)
+pub fn get_vote_sealers<T: Trait>(vote_id: &VoteId) -> Vec<T::AccountId> {
+    SealerSets::<T>::get(VoteSealerEpochs::get(vote_id))
+}
+
+/// returns true if the account is a sealer of the vote
+pub fn is_vote_sealer<T: Trait>(who: &T::AccountId, vote_id: &VoteId) -> bool {
+    get_vote_sealers::<T>(vote_id).contains(who)
+}
+
+/// returns true if the account is a sealer of new votes or of a vote which is being tallied
+pub fn is_sealer<T: Trait>(who: &T::AccountId) -> bool {
+    Sealers::<T>::get().contains(who)
+        || MonitoredVotes::get()
+            .iter()
+            .any(|vote_id| is_vote_sealer::<T>(who, vote_id))
+}
+
+/// binds the vote to the current epoch, i.e., to the current sealers
+pub fn snapshot_vote_sealers<T: Trait>(vote_id: &VoteId) {
+    VoteSealerEpochs::insert(vote_id, SealerEpoch::get());
+}
+
+/// starts a new epoch with the sealers, returns the epoch
+fn start_sealer_epoch<T: Trait>(sealers: Vec<T::AccountId>) -> u32 {
+    let epoch = SealerEpoch::get().saturating_add(1);
+    SealerSets::<T>::insert(epoch, &sealers);
+    Sealers::<T>::put(sealers);
+    SealerEpoch::put(epoch);
+    epoch
+}
+
+/// adds the sealer to the sealers of new votes, returns the new epoch
+pub fn add_sealer<T: Trait>(sealer: &T::AccountId) -> Result<u32, Error<T>> {
+    let mut sealers: Vec<T::AccountId> = Sealers::<T>::get();
+    ensure!(!sealers.contains(sealer), Error::<T>::SealerAlreadyAdded);
+    sealers.push(sealer.clone());
+    Ok(start_sealer_epoch::<T>(sealers))
+}
+
+/// removes the sealer from the sealers of new votes, returns the new epoch.
+/// the votes created before keep the sealer.
+pub fn remove_sealer<T: Trait>(sealer: &T::AccountId) -> Result<u32, Error<T>> {
+    let mut sealers: Vec<T::AccountId> = Sealers::<T>::get();
+    ensure!(sealers.contains(sealer), Error::<T>::SealerNotFound);
+    ensure!(sealers.len() > 1, Error::<T>::TooFewSealers);
+    sealers.retain(|existing| existing != sealer);
+    Ok(start_sealer_epoch::<T>(sealers))
+}
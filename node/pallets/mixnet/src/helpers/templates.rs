@@ -0,0 +1,90 @@
+use super::assertions::{
+    ensure_valid_shuffle_batch_size, ensure_valid_topic, ensure_valid_vote_id,
+};
+use crate::types::{
+    Title, Topic, TopicId, Vote, VoteId, VotePhase, VoteProposal, VoteTemplate,
+    VoteTemplateId, MAX_TOPICS_PER_TEMPLATE,
+};
+use crate::{Error, Trait, VoteTemplates};
+use frame_support::{ensure, storage::StorageMap};
+use sp_std::{vec, vec::Vec};
+
+/// the separator of the vote_id and the id of a template topic, e.g. 20201212-01
+const TOPIC_ID_SEPARATOR: u8 = b'-';
+
+/// all functions related to the templates of recurring votes
+///
+/// returns the id of the topic of a vote created from a template, i.e.,
+/// the id of the template topic prefixed with the vote_id.
+/// the topics of the votes created from the same template remain distinct.
+pub fn get_template_topic_id(vote_id: &VoteId, topic_id: &TopicId) -> TopicId {
+    let mut id: TopicId = vote_id.clone();
+    id.push(TOPIC_ID_SEPARATOR);
+    id.extend(topic_id);
+    id
+}
+
+/// ensures that the template is a valid configuration of a vote.
+/// the ids of its topics are checked once a vote is created, since they are prefixed with the vote_id.
+fn ensure_valid_template<T: Trait>(template: &VoteTemplate) -> Result<(), Error<T>> {
+    ensure!(
+        !template.topics.is_empty() && template.topics.len() <= MAX_TOPICS_PER_TEMPLATE,
+        Error::<T>::VoteTemplateTooLarge
+    );
+    ensure!(template.nr_of_shuffles > 0, Error::<T>::NrOfShufflesInvalid);
+    ensure_valid_shuffle_batch_size::<T>(template.batch_size)?;
+    for topic in template.topics.iter() {
+        ensure_valid_topic::<T>(topic, &template.tally_strategy)?;
+    }
+    Ok(())
+}
+
+/// stores (or replaces) the template, the votes created from it before are not affected
+pub fn store_template<T: Trait>(
+    template_id: &VoteTemplateId,
+    template: VoteTemplate,
+) -> Result<(), Error<T>> {
+    // template ids are limited like vote ids
+    ensure_valid_vote_id::<T>(template_id)?;
+    ensure_valid_template::<T>(&template)?;
+    VoteTemplates::insert(template_id, template);
+    Ok(())
+}
+
+/// returns the proposal of a vote cloned from the template with a fresh vote_id and phase,
+/// the proposer's approval is recorded with the proposal (see: governance::propose).
+pub fn get_template_proposal<T: Trait>(
+    who: &T::AccountId,
+    template_id: &VoteTemplateId,
+    vote_id: &VoteId,
+    title: Title,
+) -> Result<VoteProposal<T::AccountId>, Error<T>> {
+    let template: VoteTemplate =
+        VoteTemplates::get(template_id).ok_or(Error::<T>::VoteTemplateDoesNotExist)?;
+
+    let topics: Vec<Topic> = template
+        .topics
+        .into_iter()
+        .map(|topic| Topic {
+            id: get_template_topic_id(vote_id, &topic.id),
+            ..topic
+        })
+        .collect();
+    for topic in topics.iter() {
+        ensure_valid_topic::<T>(topic, &template.tally_strategy)?;
+    }
+
+    Ok(VoteProposal {
+        vote: Vote {
+            voting_authority: who.clone(),
+            title,
+            phase: VotePhase::default(),
+            params: template.params,
+            nr_of_shuffles: template.nr_of_shuffles,
+            tally_strategy: template.tally_strategy,
+        },
+        topics,
+        batch_size: template.batch_size,
+        approvals: vec![who.clone()],
+    })
+}
@@ -0,0 +1,90 @@
+use super::assertions::ensure_vote_not_started;
+use crate::types::{Ballot, EligibilityKey, Nullifier, VoteId};
+use crate::{EligibilityKeys, Error, SpentNullifiers, Trait};
+use codec::Encode;
+use crypto::blind::{BlindSignature, BlindSignaturePublicKey};
+use frame_support::{
+    ensure,
+    storage::{StorageDoubleMap, StorageMap},
+};
+use mixnet_primitives::tokens::{
+    anonymous_ballot_message, eligibility_token_message, nullifier,
+};
+use num_bigint::BigUint;
+use sp_core::sr25519;
+
+/// all functions related to the blind-signed eligibility tokens of anonymous ballots
+///
+/// sets the eligibility key of the vote (None removes it).
+/// a vote with an eligibility key only accepts anonymous ballots,
+/// therefore, the key can only be changed before the voting phase starts.
+pub fn store_eligibility_key<T: Trait>(
+    vote_id: &VoteId,
+    key: Option<EligibilityKey>,
+) -> Result<(), Error<T>> {
+    ensure_vote_not_started::<T>(vote_id)?;
+    match key {
+        Some(key) => EligibilityKeys::insert(vote_id, key),
+        None => EligibilityKeys::remove(vote_id),
+    }
+    Ok(())
+}
+
+/// the ballots of a vote with an eligibility key must be cast anonymously,
+/// i.e., with an eligibility token instead of an account.
+pub fn ensure_not_anonymous_vote<T: Trait>(vote_id: &VoteId) -> Result<(), Error<T>> {
+    ensure!(
+        !EligibilityKeys::contains_key(vote_id),
+        Error::<T>::AnonymousVote
+    );
+    Ok(())
+}
+
+/// verifies that the token of the nullifier is signed with the eligibility key of the vote
+/// (see: mixnet_primitives::tokens) and that the nullifier hasn't been spent yet.
+pub fn verify_eligibility_token<T: Trait>(
+    vote_id: &VoteId,
+    nullifier: &Nullifier,
+    signature: &[u8],
+) -> Result<(), Error<T>> {
+    let key: EligibilityKey =
+        EligibilityKeys::get(vote_id).ok_or(Error::<T>::NoEligibilityKey)?;
+    ensure!(
+        !SpentNullifiers::contains_key(vote_id, nullifier),
+        Error::<T>::NullifierAlreadySpent
+    );
+
+    let pk: BlindSignaturePublicKey = key.into();
+    let message = eligibility_token_message(vote_id, nullifier);
+    let signature = BigUint::from_bytes_be(signature);
+    ensure!(
+        BlindSignature::verify(&pk, &message, &signature),
+        Error::<T>::InvalidEligibilityToken
+    );
+    Ok(())
+}
+
+/// verifies the anonymous ballot, i.e., its signature by the one-time key of the token and the
+/// eligibility token of the key's nullifier (see: verify_eligibility_token), and returns the nullifier.
+/// the ballot is bound to the one-time key, a copied token and signature can't cast another ballot.
+pub fn verify_anonymous_ballot<T: Trait>(
+    vote_id: &VoteId,
+    ballot: &Ballot,
+    key: &sr25519::Public,
+    signature: &[u8],
+    ballot_signature: &sr25519::Signature,
+) -> Result<Nullifier, Error<T>> {
+    let nullifier: Nullifier = nullifier(&key.0);
+    let message = anonymous_ballot_message(vote_id, &nullifier, &ballot.encode());
+    ensure!(
+        sp_io::crypto::sr25519_verify(ballot_signature, &message, key),
+        Error::<T>::AnonymousBallotSignatureError
+    );
+    verify_eligibility_token::<T>(vote_id, &nullifier, signature)?;
+    Ok(nullifier)
+}
+
+/// spends the nullifier, the token can't be used for another ballot of the vote.
+pub fn spend_nullifier<T: Trait>(vote_id: &VoteId, nullifier: &Nullifier) {
+    SpentNullifiers::insert(vote_id, nullifier, true);
+}
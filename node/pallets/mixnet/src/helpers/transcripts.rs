@@ -0,0 +1,47 @@
+use super::merkle::hash_leaf;
+use crate::types::{NrOfShuffles, ProofKind, ProofLogEntry, TopicId, VoteId};
+use crate::{ProofLog, Trait};
+use codec::Encode;
+use frame_support::storage::StorageMap;
+
+/// all functions related to the proof log
+///
+/// returns the transcript hash of a proof, i.e., the blake2 hash of
+/// (vote_id, topic_id, iteration, hash(inputs), hash(proof)).
+/// the inputs are the ciphers the proof refers to, the proof includes its outputs
+/// (i.e., the shuffled ciphers or the decrypted shares).
+pub fn hash_transcript<I: Encode, P: Encode>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    iteration: NrOfShuffles,
+    inputs: &I,
+    proof: &P,
+) -> [u8; 32] {
+    hash_leaf(&(
+        vote_id,
+        topic_id,
+        iteration,
+        hash_leaf(inputs),
+        hash_leaf(proof),
+    ))
+}
+
+/// appends the transcript hash of an accepted proof to the proof log of the vote
+pub fn log_proof<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    kind: ProofKind,
+    iteration: NrOfShuffles,
+    submitter: &T::AccountId,
+    transcript_hash: [u8; 32],
+) {
+    let entry = ProofLogEntry {
+        kind,
+        topic_id: topic_id.clone(),
+        iteration,
+        submitter: submitter.clone(),
+        block_number: <frame_system::Module<T>>::block_number(),
+        transcript_hash,
+    };
+    ProofLog::<T>::append(vote_id, entry);
+}
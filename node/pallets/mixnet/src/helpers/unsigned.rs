@@ -0,0 +1,153 @@
+use super::assertions::{ensure_sealer, ensure_vote_phase, ensure_vote_sealer};
+use super::liveness::is_liveness_monitored;
+use super::tokens::verify_anonymous_ballot;
+use crate::shuffle::commitment::hash_shuffle_payload;
+use crate::types::{
+    Ballot, DecryptedSharesSubmission, HeartbeatPayload, Nullifier, ShuffleSubmission,
+    VoteId, VotePhase,
+};
+use crate::{Error, LastHeartbeats, ShuffleCommitments, Trait};
+use frame_support::{storage::StorageMap, traits::Get};
+use frame_system::offchain::SignedPayload;
+use sp_core::sr25519;
+use sp_runtime::{
+    traits::{IdentifyAccount, Saturating, UniqueSaturatedInto},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError,
+        ValidTransaction,
+    },
+};
+
+/// all functions related to the validation of the unsigned transactions of the sealers
+/// and of the anonymous ballots.
+/// the payload of an unsigned transaction is signed by the sealer, i.e., the sealer doesn't pay
+/// a transaction fee but the transaction is still bound to its account.
+/// the checks are cheap, the proofs are verified once the transaction is dispatched.
+///
+/// returns the account of the sealer of the vote which signed the payload.
+pub fn ensure_signed_by_sealer<T: Trait, P: SignedPayload<T>>(
+    payload: &P,
+    signature: &T::Signature,
+    vote_id: &VoteId,
+) -> Result<T::AccountId, TransactionValidityError> {
+    if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+        return Err(InvalidTransaction::BadProof.into());
+    }
+    let who: T::AccountId = payload.public().into_account();
+    ensure_vote_sealer::<T>(&who, vote_id).map_err(|_| InvalidTransaction::BadProof)?;
+    Ok(who)
+}
+
+/// validates the reveal of a shuffle, only the sealer which committed to the payload can reveal it.
+/// the transaction is valid until the turn of the sealer has passed.
+pub fn validate_shuffle_submission<T: Trait>(
+    submission: &ShuffleSubmission<T::Public>,
+    signature: &T::Signature,
+) -> TransactionValidity {
+    let who =
+        ensure_signed_by_sealer::<T, _>(submission, signature, &submission.vote_id)?;
+    let key = (&submission.vote_id, &submission.topic_id);
+    match ShuffleCommitments::<T>::get(key) {
+        Some(pending)
+            if pending.sealer == who
+                && pending.commitment == hash_shuffle_payload(&submission.payload) => {}
+        // the shuffle has been revealed or the commitment has been replaced
+        _ => return Err(InvalidTransaction::Stale.into()),
+    }
+
+    ValidTransaction::with_tag_prefix("MixnetShuffle")
+        .priority(T::UnsignedPriority::get())
+        .and_provides((
+            &submission.vote_id,
+            &submission.topic_id,
+            submission.payload.iteration,
+            submission.payload.start_position,
+        ))
+        .longevity(T::ShuffleTurnTimeout::get().unique_saturated_into())
+        .propagate(true)
+        .build()
+}
+
+/// validates the decrypted shares of a sealer, a sealer submits its shares
+/// of a topic once per number of shuffles.
+/// the transaction is valid until the decryption of the topic is reported as stalled.
+pub fn validate_decrypted_shares_submission<T: Trait>(
+    submission: &DecryptedSharesSubmission<T::Public>,
+    signature: &T::Signature,
+) -> TransactionValidity {
+    let who =
+        ensure_signed_by_sealer::<T, _>(submission, signature, &submission.vote_id)?;
+    ensure_vote_phase::<T>(&submission.vote_id, VotePhase::Tallying)
+        .map_err(|_| InvalidTransaction::Stale)?;
+
+    ValidTransaction::with_tag_prefix("MixnetDecryptedShares")
+        .priority(T::UnsignedPriority::get())
+        .and_provides((&submission.topic_id, who, submission.nr_of_shuffles))
+        .longevity(T::DecryptionTimeout::get().unique_saturated_into())
+        .propagate(true)
+        .build()
+}
+
+/// validates the heartbeat of a sealer. a sealer sends at most one heartbeat per `HeartbeatInterval`
+/// and only while a vote is being tallied, i.e., the free transactions can't flood the pool.
+/// the transaction is valid until the next heartbeat is due.
+pub fn validate_heartbeat<T: Trait>(
+    heartbeat: &HeartbeatPayload<T::Public, T::BlockNumber>,
+    signature: &T::Signature,
+) -> TransactionValidity {
+    if !SignedPayload::<T>::verify::<T::AuthorityId>(heartbeat, signature.clone()) {
+        return Err(InvalidTransaction::BadProof.into());
+    }
+    let who: T::AccountId = heartbeat.public.clone().into_account();
+    ensure_sealer::<T>(&who).map_err(|_| InvalidTransaction::BadProof)?;
+    if !is_liveness_monitored() {
+        return Err(InvalidTransaction::Stale.into());
+    }
+
+    // the heartbeat can't be sent ahead of time, nor before the interval has passed
+    let current_block = <frame_system::Module<T>>::block_number();
+    if heartbeat.block_number > current_block {
+        return Err(InvalidTransaction::Future.into());
+    }
+    if let Some(last_heartbeat) = LastHeartbeats::<T>::get(&who) {
+        if heartbeat.block_number
+            < last_heartbeat.saturating_add(T::HeartbeatInterval::get())
+        {
+            return Err(InvalidTransaction::Stale.into());
+        }
+    }
+
+    ValidTransaction::with_tag_prefix("MixnetHeartbeat")
+        .priority(T::UnsignedPriority::get())
+        .and_provides(who)
+        .longevity(T::HeartbeatInterval::get().unique_saturated_into())
+        .propagate(true)
+        .build()
+}
+
+/// validates an anonymous ballot, it must be signed with the one-time key of its eligibility token,
+/// the token must be signed with the eligibility key of the vote and its nullifier must not have been
+/// spent (see: verify_anonymous_ballot). the nullifier is provided by the transaction, i.e., only one
+/// ballot per token enters the transaction pool. the proofs of the ballot are verified once the
+/// transaction is dispatched.
+pub fn validate_anonymous_ballot<T: Trait>(
+    vote_id: &VoteId,
+    ballot: &Ballot,
+    key: &sr25519::Public,
+    signature: &[u8],
+    ballot_signature: &sr25519::Signature,
+) -> TransactionValidity {
+    ensure_vote_phase::<T>(vote_id, VotePhase::Voting)
+        .map_err(|_| InvalidTransaction::Stale)?;
+    let nullifier: Nullifier =
+        verify_anonymous_ballot::<T>(vote_id, ballot, key, signature, ballot_signature)
+            .map_err(|error| match error {
+            Error::<T>::NullifierAlreadySpent => InvalidTransaction::Stale,
+            _ => InvalidTransaction::BadProof,
+        })?;
+
+    ValidTransaction::with_tag_prefix("MixnetAnonymousBallot")
+        .and_provides((vote_id, nullifier))
+        .propagate(true)
+        .build()
+}
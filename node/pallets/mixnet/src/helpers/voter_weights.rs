@@ -0,0 +1,68 @@
+use super::assertions::ensure_vote_not_started;
+use super::ciphers::get_cipher_count;
+use crate::dkg::aggregate::get_tally_strategy;
+use crate::types::{NrOfShuffles, TallyStrategy, TopicId, VoteId};
+use crate::{AdditionalWeights, Error, Trait, VoterWeights};
+use frame_support::{
+    ensure,
+    storage::{StorageDoubleMap, StorageMap},
+    traits::Get,
+};
+
+/// the weights apply to the ciphers cast by the voters (never shuffled)
+const INITIAL_NUMBER_OF_SHUFFLES: NrOfShuffles = 0;
+
+/// all functions related to weighted voting (e.g., shareholder votes)
+///
+/// stores the weight of a voter, None removes the weight (i.e., the voter has weight 1).
+/// a weighted voter answers with 0 (no) or its weight (yes), therefore,
+/// weights are only supported by the homomorphic tally and can't change once the voting has started.
+pub fn store_voter_weight<T: Trait>(
+    vote_id: &VoteId,
+    voter: &T::AccountId,
+    weight: Option<u32>,
+) -> Result<(), Error<T>> {
+    ensure!(
+        get_tally_strategy::<T>(vote_id) == TallyStrategy::Homomorphic,
+        Error::<T>::WrongTallyStrategy
+    );
+    ensure_vote_not_started::<T>(vote_id)?;
+    match weight {
+        Some(weight) => {
+            ensure!(
+                weight > 0 && weight <= T::MaxVoterWeight::get(),
+                Error::<T>::InvalidVoterWeight
+            );
+            VoterWeights::<T>::insert(vote_id, voter, weight);
+        }
+        None => VoterWeights::<T>::remove(vote_id, voter),
+    }
+    Ok(())
+}
+
+/// adds the additional weight (weight - 1) of a weighted voter's cipher to the topic
+pub fn add_cipher_weight(vote_id: &VoteId, topic_id: &TopicId, weight: Option<u32>) {
+    if let Some(weight) = weight {
+        let additional = weight.saturating_sub(1) as u64;
+        AdditionalWeights::mutate((vote_id, topic_id), |sum| {
+            *sum = sum.saturating_add(additional)
+        });
+    }
+}
+
+/// removes the additional weight of a weighted voter's cipher from the topic, e.g., if it is spoiled
+pub fn remove_cipher_weight(vote_id: &VoteId, topic_id: &TopicId, weight: Option<u32>) {
+    if let Some(weight) = weight {
+        let additional = weight.saturating_sub(1) as u64;
+        AdditionalWeights::mutate((vote_id, topic_id), |sum| {
+            *sum = sum.saturating_sub(additional)
+        });
+    }
+}
+
+/// returns the total weight of the ciphers cast for the topic,
+/// i.e., the number of ciphers plus the additional weights of the weighted ciphers
+pub fn get_topic_weight<T: Trait>(vote_id: &VoteId, topic_id: &TopicId) -> u64 {
+    get_cipher_count::<T>(vote_id, topic_id, INITIAL_NUMBER_OF_SHUFFLES)
+        .saturating_add(AdditionalWeights::get((vote_id, topic_id)))
+}
@@ -16,8 +16,14 @@ mod dkg;
 #[allow(clippy::many_single_char_names)]
 pub mod types;
 
+mod migrations;
+
+mod tally;
+
 mod bench;
 
+pub mod weights;
+
 #[cfg(test)]
 mod mock;
 
@@ -28,34 +34,130 @@ mod tests;
 pub mod keys;
 
 use crate::dkg::{
+    aggregate::aggregate_topic_ciphers,
     create::combine_shares,
-    tally::combine_shares_and_tally_topic,
+    rotate::{
+        combine_key_switch_shares, rotate_key, verify_proof_and_store_key_switch_shares,
+    },
+    tally::{
+        combine_shares_and_tally_topic, combine_shares_of_next_batch,
+        discard_invalid_credential_ballots, finalize_topic_tally,
+    },
     verify::{
         verify_proof_and_store_decrypted_share, verify_proof_and_store_keygen_share,
     },
 };
 use crate::helpers::{
+    archive::archive_ciphers_and_proofs,
     assertions::{
-        ensure_not_a_voting_authority, ensure_sealer, ensure_vote_does_not_exist,
-        ensure_vote_exists, ensure_vote_phase, ensure_voting_authority,
+        ensure_group_check, ensure_no_key_switch_pending, ensure_not_a_voting_authority,
+        ensure_sealer, ensure_valid_cancellation_reason, ensure_valid_ciphers,
+        ensure_valid_decryption_threshold, ensure_valid_metadata,
+        ensure_valid_phase_transition, ensure_valid_shuffle_batch_size,
+        ensure_valid_title, ensure_valid_topic, ensure_valid_vote_id, ensure_vote_ended,
+        ensure_vote_exists, ensure_vote_not_archived, ensure_vote_not_cancelled,
+        ensure_vote_not_started, ensure_vote_phase, ensure_vote_sealer,
+        ensure_voting_authority,
+    },
+    ballot::{
+        ensure_unique_ciphers, spoil_ballot_cipher, store_anonymous_ballot, store_ballot,
+        store_ballot_receipt, verify_and_store_spoiled_ballot_reveal,
+        verify_ballot_proofs, verify_ballot_proofs_in_context,
+        verify_ballot_validity_proofs, verify_ballot_validity_proofs_in_context,
+        verify_relayed_ballot,
+    },
+    certification::{approve_result, store_result_digest},
+    credentials::{
+        ensure_ballot_credential, ensure_credential_check_pending,
+        ensure_no_credential_check_pending, store_credential, store_credential_ballot,
+        verify_and_store_credential_blindings, verify_and_store_credential_decryptions,
+    },
+    governance::{approve, approve_cancellation, approve_tallying, propose},
+    imports::store_imported_ciphers,
+    indexing::set_shuffle_state,
+    limits::{
+        ensure_ballot_deposit, ensure_ballot_rate_limit, refund_ballot_deposit,
+        reserve_ballot_deposit, store_ballot_rate,
+    },
+    liveness::{
+        is_vote_tallied, record_heartbeat, stop_liveness_monitoring,
+        take_unresponsive_sealers,
+    },
+    packing::store_topic_packing,
+    params::store_hash_function,
+    phase::{cancel, force_phase, set_phase, start_tallying},
+    progress::{
+        get_decrypted_share_count, get_required_share_count, start_decryption,
+        stop_decryption, take_stalled_decryptions,
+    },
+    registration::{
+        ensure_not_revoked, ensure_registered_voter, register_voter, revoke_voter,
+        store_identity_provider,
+    },
+    sealers::{add_sealer, is_sealer, remove_sealer},
+    templates::{get_template_proposal, store_template},
+    tokens::{
+        ensure_not_anonymous_vote, spend_nullifier, store_eligibility_key,
+        verify_anonymous_ballot,
+    },
+    unsigned::{
+        validate_anonymous_ballot, validate_decrypted_shares_submission,
+        validate_heartbeat, validate_shuffle_submission,
+    },
+    voter_weights::store_voter_weight,
+};
+use crate::shuffle::{
+    commitment::{
+        remove_shuffle_commitment, store_shuffle_commitment, verify_shuffle_reveal,
+    },
+    linked::{
+        ensure_linked_ballot, ensure_linked_batch_size, ensure_shuffle_mode,
+        ensure_shuffle_topic, ensure_spoilable_answer, get_lead_topic,
+        store_linked_shuffle,
     },
-    ballot::store_ballot,
-    phase::set_phase,
+    turn::{ensure_shuffle_turn, start_shuffle_turn},
 };
 use crate::types::{
-    Ballot, Cipher, DecryptedShare, DecryptedShareProof, NrOfShuffles,
-    PublicKey as SubstratePK, PublicKeyShare, PublicParameters, ShufflePayload,
-    ShuffleState, Title, Topic, TopicId, TopicResult, Vote, VoteId, VotePhase,
+    Ballot, BallotBoxFreeze, BallotRate, BallotReceipt, CertifiedResult, ChunkIndex,
+    Cipher, CipherImport, ClaimSignature, CredentialBlindingProof, DecryptedShare,
+    DecryptedShareProof, DecryptedSharesSubmission, DecryptionAuditEntry, EligibilityKey,
+    HashFunction, HeartbeatPayload, IdentityProvider, KeySwitchAuditEntry,
+    KeySwitchShareProof, LinkedShufflePayload, NrOfShuffles, Nullifier, PartialTally,
+    PendingDecryption, ProofLogEntry, ProtocolConfig, PublicKey as SubstratePK,
+    PublicKeyShare, PublicParameters, ResultApprovals, ResultDigest, ShuffleCommitment,
+    ShufflePayload, ShuffleState, ShuffleSubmission, ShuffleTurn, SpoiledBallot,
+    SpoiledBallotReveal, TallyProgress, TallyRound, TallyStrategy, Title, Topic, TopicId,
+    TopicPacking, TopicResult, Vote, VoteArchive, VoteId, VoteMetadata, VotePhase,
+    VoteProposal, VoteTemplate, VoteTemplateId, MAX_TOPICS_PER_TEMPLATE,
 };
+use crate::weights::{FeePolicy, WeightInfo};
 use frame_support::{
-    debug, decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult,
-    storage::StorageMap, storage::StorageValue, traits::Get, weights::Pays,
+    debug, decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchResult, DispatchResultWithPostInfo},
+    ensure,
+    storage::StorageMap,
+    storage::StorageValue,
+    traits::{Currency, Get, ReservableCurrency},
+    weights::{Pays, PostDispatchInfo, Weight},
 };
 use frame_system::{
-    ensure_signed,
+    ensure_none, ensure_root, ensure_signed,
     offchain::{AppCrypto, CreateSignedTransaction},
 };
-use sp_std::{prelude::*, str, vec::Vec};
+use sp_core::sr25519;
+use sp_runtime::{
+    traits::IdentifyAccount,
+    transaction_validity::{
+        InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+    },
+};
+use sp_std::{prelude::*, str, vec, vec::Vec};
+
+pub use mixnet_primitives::ErrorCode;
+
+/// The balance of the currency in which the ballot deposits are reserved.
+pub type BalanceOf<T> =
+    <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
 /// This is the pallet's configuration trait
 pub trait Trait:
@@ -71,13 +173,110 @@ pub trait Trait:
     // Wait period between automated fetches. Set to 0 disable this feature.
     //   Then you need to manucally kickoff pricefetch
     type BlockDuration: Get<Self::BlockNumber>;
+
+    /// The maximum number of ciphers stored in a single storage entry.
+    /// Must not be changed while ciphers are stored.
+    type CipherChunkSize: Get<u32>;
+
+    /// The number of blocks a sealer has to commit to and reveal its shuffle.
+    /// Afterwards, the turn passes to the next sealer which can replace the commitment.
+    type ShuffleTurnTimeout: Get<Self::BlockNumber>;
+
+    /// The number of blocks the sealers have to submit their decrypted shares of a topic
+    /// once its ciphers are ready to be decrypted. Afterwards, the decryption is reported as stalled.
+    type DecryptionTimeout: Get<Self::BlockNumber>;
+
+    /// The number of blocks between two heartbeats of a sealer while a vote is being tallied.
+    /// A sealer which hasn't sent a heartbeat for two intervals is reported as unresponsive.
+    type HeartbeatInterval: Get<Self::BlockNumber>;
+
+    /// The number of blocks between two signed checkpoints of the election state taken by
+    /// the offchain worker of a sealer (disaster recovery). Zero disables the checkpoints.
+    type CheckpointInterval: Get<Self::BlockNumber>;
+
+    /// The number of sealers which must certify the result of a vote in addition to a voting authority.
+    /// Capped at the number of sealers.
+    type CertificationQuorum: Get<u32>;
+
+    /// The maximum number of bytes of the metadata of a vote (IPFS hash or inline document).
+    type MaxMetadataSize: Get<u32>;
+
+    /// The maximum number of bytes of a vote id.
+    type MaxVoteIdLength: Get<u32>;
+
+    /// The maximum number of bytes of the title of a vote.
+    type MaxTitleLength: Get<u32>;
+
+    /// The maximum number of bytes of a topic id.
+    type MaxTopicIdLength: Get<u32>;
+
+    /// The maximum number of bytes of a question and of each of its candidates.
+    type MaxQuestionLength: Get<u32>;
+
+    /// The smallest number of ciphers a voting authority can configure to be shuffled per batch.
+    type MinShuffleBatchSize: Get<u64>;
+
+    /// The largest number of ciphers a voting authority can configure to be shuffled per batch.
+    /// Bounds the weight of the shuffle proof verification of a single batch.
+    type MaxShuffleBatchSize: Get<u64>;
+
+    /// The time (in milliseconds) the offchain worker aims to spend shuffling and proving a batch.
+    /// Smaller batches than configured are shuffled if the measured costs exceed the budget.
+    type ShuffleTimeBudget: Get<u64>;
+
+    /// The priority of the unsigned transactions of the sealers,
+    /// i.e., of the revealed shuffles and the decrypted shares.
+    type UnsignedPriority: Get<TransactionPriority>;
+
+    /// The currency in which the ballot deposits are reserved.
+    type Currency: ReservableCurrency<Self::AccountId>;
+
+    /// The deposit reserved per ballot of a vote which isn't whitelisted (zero disables the deposit).
+    /// Refunded to the voter once the voting phase has ended.
+    type BallotDeposit: Get<BalanceOf<Self>>;
+
+    /// The maximum number of ballots an account can submit per `BallotRatePeriod` blocks.
+    type MaxBallotsPerPeriod: Get<u32>;
+
+    /// The number of blocks of a ballot rate limit period.
+    type BallotRatePeriod: Get<Self::BlockNumber>;
+
+    /// The largest weight a voting authority can assign to a voter (weighted voting).
+    /// Bounds the decoding of the homomorphic tally, which is bounded by the total weight.
+    type MaxVoterWeight: Get<u32>;
+
+    /// Weight information for the extrinsics of this pallet.
+    type WeightInfo: WeightInfo;
+
+    /// Decides which extrinsics of this pallet are charged a transaction fee.
+    type FeePolicy: FeePolicy;
 }
 
 decl_storage! {
     trait Store for Module<T: Trait> as OffchainModule {
+        /// The version of the storage layout, see: migrations
+        StorageVersion get(fn storage_version) build(|_: &GenesisConfig<T>| migrations::STORAGE_VERSION): u32;
+
         pub VotingAuthorities get(fn voting_authorities) config(): Vec<T::AccountId>;
+
+        /// The number of voting authorities which need to approve a vote and the start of its tallying phase (at least 1).
+        pub ApprovalThreshold get(fn approval_threshold) config(): u32;
+
+        /// The sealers of new votes, see: SealerSets
         pub Sealers get(fn sealers) config(): Vec<T::AccountId>;
 
+        /// The number of changes of the sealers, i.e. the epoch new votes are created in
+        SealerEpoch get(fn sealer_epoch): u32;
+
+        /// Maps an epoch to its sealers, the sealers of the genesis config are the epoch 0
+        SealerSets get(fn sealer_set) build(|config: &GenesisConfig<T>| vec![(0u32, config.sealers.clone())]): map hasher(twox_64_concat) u32 => Vec<T::AccountId>;
+
+        /// Maps a vote to the epoch it has been created in, its sealers never change while it is in flight
+        VoteSealerEpochs get(fn vote_sealer_epoch): map hasher(blake2_128_concat) VoteId => u32;
+
+        /// The public parameters proposed for new votes, empty if not configured in the genesis config.
+        pub DefaultPublicParameters get(fn default_public_parameters) config(): PublicParameters;
+
         CountsBySealer get(fn count_by_sealer): map hasher(blake2_128_concat) T::AccountId => u32;
         Counts get(fn count): u32;
 
@@ -87,29 +286,179 @@ decl_storage! {
         /// Set of all voteIds
         VoteIds get(fn vote_ids): Vec<VoteId>;
 
+        /// Maps a proposed vote to its proposal, removed once the vote has been created
+        VoteProposals get(fn vote_proposal): map hasher(blake2_128_concat) VoteId => Option<VoteProposal<T::AccountId>>;
+
+        /// Maps a template to the reusable configuration of recurring votes, see: create_vote_from_template
+        VoteTemplates get(fn vote_template): map hasher(blake2_128_concat) VoteTemplateId => Option<VoteTemplate>;
+
+        /// Maps a vote to the voting authorities which approved the start of the tallying phase
+        TallyingApprovals get(fn tallying_approvals): map hasher(blake2_128_concat) VoteId => Vec<T::AccountId>;
+
+        /// Maps a vote to the voting authorities which approved its cancellation
+        CancellationApprovals get(fn cancellation_approvals): map hasher(blake2_128_concat) VoteId => Vec<T::AccountId>;
+
         /// Maps a vote (i.e. the voteId) to a due date
         Votes get(fn votes): map hasher(blake2_128_concat) VoteId => Vote<T::AccountId>;
 
+        /// Maps a vote to the parameters of the protocol it has been created with
+        ProtocolConfigs get(fn protocol_config): map hasher(blake2_128_concat) VoteId => Option<ProtocolConfig>;
+
+        /// Maps a vote to its metadata (descriptions, translations, candidate bios), see: VoteMetadata
+        Metadata get(fn metadata): map hasher(blake2_128_concat) VoteId => Option<VoteMetadata>;
+
+        /// Maps a vote to the shuffle batch size set by a voting authority, applied from the next batch on
+        ShuffleBatchSizes get(fn shuffle_batch_size): map hasher(blake2_128_concat) VoteId => Option<u64>;
+
+        /// The votes whose topics are shuffled together with the same permutation, i.e., the answers of a ballot stay linked.
+        LinkedShuffles get(fn linked_shuffle): map hasher(blake2_128_concat) VoteId => bool;
+
+        /// The votes whose ballots are only accepted if both components of all ciphers are elements of the group, i.e., a^q mod p == 1 and b^q mod p == 1.
+        GroupChecks get(fn group_check): map hasher(blake2_128_concat) VoteId => bool;
+
+        /// Maps a vote with threshold keys to the number of sealers whose decrypted shares suffice to tally it, all sealers are required otherwise
+        DecryptionThresholds get(fn decryption_threshold): map hasher(blake2_128_concat) VoteId => Option<u32>;
+
+        /// True once a vote has entered the voting phase, its topics can't be changed anymore
+        VotingStarted get(fn voting_started): map hasher(blake2_128_concat) VoteId => bool;
+
         /// Maps a voteId to a topic (topicId, question)
         Topics get(fn topics): map hasher(blake2_128_concat) VoteId => Vec<Topic>;
 
         /// Maps an voter and a vote to a ballot. Used to verify if a voter has already voted.
         Ballots get(fn ballots): double_map hasher(blake2_128_concat) VoteId, hasher(blake2_128_concat) T::AccountId => Ballot;
 
-        /// Maps a topicId (question) to a list of Ciphers and how many times each Cipher has been shuffled
-        Ciphers get(fn ciphers): double_map hasher(blake2_128_concat) TopicId, hasher(blake2_128_concat) NrOfShuffles => Vec<Cipher>;
+        /// Maps a vote and a topic to the hashes of all ciphers submitted by the voters (replay protection).
+        CipherHashes: double_map hasher(blake2_128_concat) (VoteId, TopicId), hasher(identity) [u8; 32] => bool;
+
+        /// Maps a vote and a voter to the receipt of the last submitted ballot.
+        BallotReceipts: double_map hasher(blake2_128_concat) VoteId, hasher(blake2_128_concat) T::AccountId => Option<BallotReceipt<T::BlockNumber, T::Hash>>;
+
+        /// Maps an account to the number of ballots it submitted in the current rate limit period (spam protection).
+        BallotRates get(fn ballot_rate): map hasher(blake2_128_concat) T::AccountId => BallotRate<T::BlockNumber>;
+
+        /// The votes whose voters don't reserve a deposit per ballot, e.g. votes with known voters.
+        WhitelistedVotes get(fn whitelisted_vote): map hasher(blake2_128_concat) VoteId => bool;
+
+        /// Maps a vote and a voter to the deposit reserved for its ballots, refunded once the voting phase has ended.
+        BallotDeposits get(fn ballot_deposit): double_map hasher(blake2_128_concat) VoteId, hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+
+        /// Maps a vote and a topic and the hash of a spoiled cipher to the spoiled ballot (audit ballot), excluded from the tally.
+        SpoiledBallots get(fn spoiled_ballot): double_map hasher(blake2_128_concat) (VoteId, TopicId), hasher(identity) [u8; 32] => Option<SpoiledBallot<T::AccountId>>;
+
+        /// Maps a vote and a voter to the weight of the voter's answers (weighted voting), voters without a weight have weight 1.
+        VoterWeights get(fn voter_weight): double_map hasher(blake2_128_concat) VoteId, hasher(blake2_128_concat) T::AccountId => Option<u32>;
+
+        /// Maps a voteId and topicId (question) to the sum of the additional weights (weight - 1) of the ciphers of weighted voters.
+        /// The total weight of a topic is the number of its ciphers plus its additional weight.
+        AdditionalWeights get(fn additional_weight): map hasher(blake2_128_concat) (VoteId, TopicId) => u64;
+
+        /// Maps a vote and a voter to the encrypted credential of the voter (coercion resistance).
+        Credentials get(fn credential): double_map hasher(blake2_128_concat) VoteId, hasher(blake2_128_concat) T::AccountId => Option<Cipher>;
+
+        /// Maps a vote to the voters whose ballots carry an encrypted credential, in the order of the credential check.
+        CredentialBallots get(fn credential_ballots): map hasher(blake2_128_concat) VoteId => Vec<T::AccountId>;
+
+        /// Maps a vote and a sealer to its blinded credential quotients (one per voter of CredentialBallots).
+        CredentialBlindings get(fn credential_blindings): double_map hasher(blake2_128_concat) VoteId, hasher(blake2_128_concat) T::AccountId => Option<Vec<Cipher>>;
+
+        /// Maps a vote and a sealer to its partial decryptions of the combined blinded credential quotients.
+        CredentialDecryptions get(fn credential_decryptions): double_map hasher(blake2_128_concat) VoteId, hasher(blake2_128_concat) T::AccountId => Option<Vec<DecryptedShare>>;
+
+        /// Maps a vote to the voters whose ballots have been discarded because of an invalid credential, set once the credentials have been checked.
+        DiscardedBallots get(fn discarded_ballots): map hasher(blake2_128_concat) VoteId => Option<Vec<T::AccountId>>;
+
+        /// Maps a vote to the identity provider which signs the eligibility claims of its voters.
+        IdentityProviders get(fn identity_provider): map hasher(blake2_128_concat) VoteId => Option<IdentityProvider>;
+
+        /// Maps a vote and a voter to true if the voter has been registered by an eligibility claim.
+        RegisteredVoters get(fn registered_voter): double_map hasher(blake2_128_concat) VoteId, hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Maps a vote and a revoked voter to the block number of the revocation (revocation list), see: revoke_voter.
+        /// A revoked voter can't cast a ballot anymore, the ciphers of its ballot are excluded before the ballot box is frozen.
+        RevokedVoters get(fn revoked_voter): double_map hasher(blake2_128_concat) VoteId, hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+
+        /// Maps a vote to the key of the voting authority which blind-signs the eligibility tokens of its voters.
+        /// A vote with an eligibility key only accepts anonymous ballots, see: cast_anonymous_ballot.
+        EligibilityKeys get(fn eligibility_key): map hasher(blake2_128_concat) VoteId => Option<EligibilityKey>;
+
+        /// Maps a vote and a nullifier to true once an anonymous ballot has been cast with the eligibility token of the nullifier.
+        SpentNullifiers get(fn spent_nullifier): double_map hasher(blake2_128_concat) VoteId, hasher(identity) Nullifier => bool;
+
+        /// Maps a vote and the index of an import to the batch of ciphers imported by a voting authority, see: import_ciphers.
+        CipherImports get(fn cipher_import): double_map hasher(blake2_128_concat) VoteId, hasher(twox_64_concat) u32 => Option<CipherImport<T::AccountId, T::BlockNumber>>;
+
+        /// Maps a vote to the number of its imports, i.e. the index of the next import.
+        CipherImportCount get(fn cipher_import_count): map hasher(blake2_128_concat) VoteId => u32;
+
+        /// Maps a voteId and topicId (question), how many times each Cipher has been shuffled and a chunk index to a chunk of Ciphers
+        Ciphers: double_map hasher(blake2_128_concat) (VoteId, TopicId), hasher(blake2_128_concat) (NrOfShuffles, ChunkIndex) => Vec<Cipher>;
+
+        /// Maps a voteId and topicId (question) and how many times each Cipher has been shuffled to the number of cipher chunks
+        CipherChunkCount get(fn cipher_chunk_count): double_map hasher(blake2_128_concat) (VoteId, TopicId), hasher(blake2_128_concat) NrOfShuffles => ChunkIndex;
+
+        /// Maps a voteId and topicId (question) and how many times each Cipher has been shuffled to the number of Ciphers
+        CipherCounts get(fn cipher_count): double_map hasher(blake2_128_concat) (VoteId, TopicId), hasher(blake2_128_concat) NrOfShuffles => u64;
+
+        /// Maps a voteId and topicId (question) and how many times each Cipher has been shuffled to the merkle root of all Ciphers
+        CipherRoots get(fn cipher_root): double_map hasher(blake2_128_concat) (VoteId, TopicId), hasher(blake2_128_concat) NrOfShuffles => Option<[u8; 32]>;
+
+        /// The peaks of the merkle tree of the Ciphers, allows to update the merkle root without reading all Ciphers
+        CipherPeaks: double_map hasher(blake2_128_concat) (VoteId, TopicId), hasher(blake2_128_concat) NrOfShuffles => Vec<Option<[u8; 32]>>;
 
         /// Maps a voteId and topicId to a list of shuffle proofs (iteration, ciphers, proof)
         ShuffleProofs: map hasher(blake2_128_concat) (VoteId, TopicId) => Vec<ShufflePayload>;
 
+        /// Maps a voteId and topicId to the pending shuffle commitment of a sealer
+        ShuffleCommitments get(fn shuffle_commitment): map hasher(blake2_128_concat) (VoteId, TopicId) => Option<ShuffleCommitment<T::AccountId, T::BlockNumber>>;
+
+        /// Maps a voteId and topicId to the turn of the sealer which shuffles the next batch
+        ShuffleTurns get(fn shuffle_turn): map hasher(blake2_128_concat) (VoteId, TopicId) => ShuffleTurn<T::BlockNumber>;
+
         /// Maps a voteId and topicid to a shuffle status
-        ShuffleStateStore: map hasher(blake2_128_concat) (VoteId, TopicId) => Option<ShuffleState>;
+        ShuffleStateStore get(fn shuffle_state): map hasher(blake2_128_concat) (VoteId, TopicId) => Option<ShuffleState>;
+
+        /// Maps a vote and a topic to the homomorphic sum of all its ciphers (only for TallyStrategy::Homomorphic)
+        AggregateCiphers get(fn aggregate_cipher): map hasher(blake2_128_concat) (VoteId, TopicId) => Option<Cipher>;
+
+        /// Maps a vote and a topic to a map of results. [(vote_id, topic_id) -> {message/vote: count}]
+        Tally get(fn tally): map hasher(blake2_128_concat) (VoteId, TopicId) => Option<TopicResult>;
+
+        /// Maps a vote and a topic to the rounds of its instant-runoff tally (only for ranked questions tallied by RankedTallyMethod::InstantRunoff)
+        TallyRounds get(fn tally_rounds): map hasher(blake2_128_concat) (VoteId, TopicId) => Vec<TallyRound>;
+
+        /// Maps a vote and a topic to the packing of the answers of its questions into a single cipher (ballot packing), see: TopicPacking
+        PackedTopics get(fn packed_topic): map hasher(blake2_128_concat) (VoteId, TopicId) => Option<TopicPacking>;
+
+        /// Maps a vote and a packed topic and a slot (the index of the packed question) to the results of the question. [(vote_id, topic_id), slot -> {answer: count}]
+        PackedTally get(fn packed_tally): double_map hasher(blake2_128_concat) (VoteId, TopicId), hasher(twox_64_concat) u32 => TopicResult;
+
+        /// Maps a vote and a topic to the progress of its tally while the decrypted shares are combined batch by batch
+        TallyProgresses get(fn tally_progress): map hasher(blake2_128_concat) (VoteId, TopicId) => Option<TallyProgress>;
+
+        /// Maps a vote and a topic to the number of ciphers per decoded plaintext of the batches combined so far
+        PartialTallies get(fn partial_tally): map hasher(blake2_128_concat) (VoteId, TopicId) => PartialTally;
+
+        /// Maps a vote and a topic and a sealer to a vector of decrypted shares.
+        DecryptedShares get(fn decrypted_shares): double_map hasher(blake2_128_concat) (VoteId, TopicId), hasher(blake2_128_concat) T::AccountId  => Vec<Vec<u8>>;
+
+        /// Maps a vote and a topic and the # of shuffles to the audit trail of all submitted partial decryptions (sealer, shares hash, proof).
+        DecryptionAudit get(fn decryption_audit): double_map hasher(blake2_128_concat) (VoteId, TopicId), hasher(blake2_128_concat) NrOfShuffles => Vec<DecryptionAuditEntry<T::AccountId>>;
+
+        /// Maps a vote to the append-only log of the transcript hashes of all accepted shuffle and decryption proofs, kept when the vote is archived
+        ProofLog get(fn proof_log): map hasher(blake2_128_concat) VoteId => Vec<ProofLogEntry<T::AccountId, T::BlockNumber>>;
 
-        /// Maps a topic to a map of results. [topic_id -> {message/vote: count}]
-        Tally get(fn tally): map hasher(blake2_128_concat) TopicId => Option<TopicResult>;
+        /// Maps a vote and a topic whose ciphers are ready to be decrypted to the deadline of the decryption, removed once sufficient sealers decrypted them
+        PendingDecryptions get(fn pending_decryption): map hasher(blake2_128_concat) (VoteId, TopicId) => Option<PendingDecryption<T::BlockNumber>>;
 
-        /// Maps a sealer and a topic to a vector of decrypted shares.
-        DecryptedShares get(fn decrypted_shares): double_map hasher(blake2_128_concat) TopicId, hasher(blake2_128_concat) T::AccountId  => Vec<Vec<u8>>;
+        /// The votes in the tallying phase which haven't been tallied yet, the sealers send heartbeats while there are any
+        MonitoredVotes get(fn monitored_votes): Vec<VoteId>;
+
+        /// Maps a sealer to the block of its last heartbeat
+        LastHeartbeats get(fn last_heartbeat): map hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+
+        /// Maps a sealer to the block after which it is reported as unresponsive, only set while votes are monitored
+        HeartbeatDeadlines get(fn heartbeat_deadline): map hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
 
         /// Stores the public key of a sealer together with its Schnorr proof.
         PublicKeyShares get(fn key_shares): map hasher(blake2_128_concat) VoteId => Vec<PublicKeyShare>;
@@ -119,6 +468,61 @@ decl_storage! {
 
         /// Maps a vote to a public key (the vote's/system's public key) used to encrypt ballots.
         PublicKey get(fn public_key): map hasher(blake2_128_concat) VoteId => Option<SubstratePK>;
+
+        /// Stores the public key share of a sealer of the vote's previous (rotated) public key
+        PreviousPublicKeyShareBySealer get(fn previous_key_share_by_sealer): map hasher(blake2_128_concat) (VoteId, T::AccountId) => Option<PublicKeyShare>;
+
+        /// Maps a vote to the topics whose ciphers are still encrypted under the previous public key
+        PendingKeySwitch get(fn pending_key_switch): map hasher(blake2_128_concat) VoteId => Vec<TopicId>;
+
+        /// Maps a vote and a topic and a sealer to the key switch shares of the ciphers. Removed once the ciphers are switched.
+        KeySwitchShares: double_map hasher(blake2_128_concat) (VoteId, TopicId), hasher(blake2_128_concat) T::AccountId => Vec<Cipher>;
+
+        /// Maps a vote and a topic to the audit trail of all submitted key switch shares (sealer, shares hash, proof).
+        KeySwitchAudit get(fn key_switch_audit): map hasher(blake2_128_concat) (VoteId, TopicId) => Vec<KeySwitchAuditEntry<T::AccountId>>;
+
+        /// Maps a tallied vote to the approvals of its result hash, removed once the result has been certified
+        PendingResultApprovals get(fn pending_result_approvals): map hasher(blake2_128_concat) VoteId => Option<ResultApprovals<T::AccountId>>;
+
+        /// Maps a vote to its certified result, see: CertificationQuorum
+        CertifiedResults get(fn certified_result): map hasher(blake2_128_concat) VoteId => Option<CertifiedResult<T::AccountId, T::BlockNumber>>;
+
+        /// Maps a vote with a certified result to the digest of the result, see: ResultDigest
+        ResultDigests get(fn result_digest): map hasher(blake2_128_concat) VoteId => Option<ResultDigest<T::BlockNumber>>;
+
+        /// Maps a vote to its ciphers at the end of the voting phase, the context of all its shuffle proofs, see: BallotBoxFreeze
+        BallotBoxFreezes get(fn ballot_box_freeze): map hasher(blake2_128_concat) VoteId => Option<BallotBoxFreeze<T::Hash, T::BlockNumber>>;
+
+        /// Maps an archived vote to the merkle root of its pruned ciphers and proofs.
+        ArchivedVotes get(fn archived_vote): map hasher(blake2_128_concat) VoteId => Option<VoteArchive>;
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Trait> GenesisConfig<T> {
+    /// Adds a voting authority, i.e., an account which can create and manage votes.
+    pub fn with_authority(mut self, authority: T::AccountId) -> Self {
+        self.voting_authorities.push(authority);
+        self
+    }
+
+    /// Adds the sealers which generate the public key, shuffle and decrypt the votes.
+    pub fn with_sealers(mut self, sealers: Vec<T::AccountId>) -> Self {
+        self.sealers.extend(sealers);
+        self
+    }
+
+    /// Sets the number of voting authorities which need to approve a vote (m-of-n).
+    pub fn with_approval_threshold(mut self, threshold: u32) -> Self {
+        self.approval_threshold = threshold;
+        self
+    }
+
+    /// Proposes the default public parameters (2048 bit group) for new votes.
+    pub fn with_default_params(mut self) -> Self {
+        let (params, _, _) = crypto::helper::Helper::setup_lg_system();
+        self.default_public_parameters = params.into();
+        self
     }
 }
 
@@ -127,6 +531,9 @@ decl_event!(
     pub enum Event<T>
     where
         AccountId = <T as frame_system::Trait>::AccountId,
+        Balance = BalanceOf<T>,
+        BlockNumber = <T as frame_system::Trait>::BlockNumber,
+        Hash = <T as frame_system::Trait>::Hash,
     {
         /// ballot submission event -> [from/who, ballot]
         BallotSubmitted(AccountId, VoteId, Ballot),
@@ -137,11 +544,23 @@ decl_event!(
         /// A voting authority set the vote's public parameters. [vote, who, params]
         VoteCreatedWithPublicParameters(VoteId, AccountId, PublicParameters),
 
-        /// A voting authority set the question of a topic of a vote [vote, (topic_id, question)]
+        /// A voting authority proposed a vote, it is created once approved. [vote_id, who]
+        VoteProposed(VoteId, AccountId),
+
+        /// A voting authority approved a proposed vote. [vote_id, who]
+        VoteApproved(VoteId, AccountId),
+
+        /// A voting authority stored the template of recurring votes. [template_id, who]
+        VoteTemplateStored(VoteTemplateId, AccountId),
+
+        /// A voting authority approved the start of the tallying phase. [vote_id, who]
+        TallyingApproved(VoteId, AccountId),
+
+        /// A voting authority set the question of a topic of a vote [vote, topic]
         VoteTopicQuestionStored(VoteId, Topic),
 
-        /// A voting authority changed the vote phase [vote_id, newPhase]
-        VotePhaseChanged(VoteId, VotePhase),
+        /// The vote phase has been changed [vote_id, oldPhase, newPhase]
+        VotePhaseChanged(VoteId, VotePhase, VotePhase),
 
         /// A public key share was submitted. [public key with its proof]
         PublicKeyShareSubmitted(PublicKeyShare),
@@ -157,6 +576,141 @@ decl_event!(
 
         /// A decrypted share was submitted for a vote. [paritial decryptions with its proof]
         ShuffleProofSubmitted(TopicId, AccountId),
+
+        /// A sealer committed to a shuffle of the ciphers of a topic. [topic_id, who]
+        ShuffleCommitted(TopicId, AccountId),
+
+        /// The ciphers of a topic have been added homomorphically. [vote_id, topic_id]
+        CiphersAggregated(VoteId, TopicId),
+
+        /// All shuffle iterations of a topic are completed. [vote_id, topic_id, nr_of_shuffles]
+        ShuffleCompleted(VoteId, TopicId, NrOfShuffles),
+
+        /// A batch of shuffled ciphers has been verified and stored. [vote_id, topic_id, iteration, start_position, nr_of_ciphers]
+        ShuffleBatchAccepted(VoteId, TopicId, NrOfShuffles, u64, u64),
+
+        /// All ciphers of a topic have been shuffled once more. [vote_id, topic_id, nr_of_completed_shuffles]
+        ShuffleIterationCompleted(VoteId, TopicId, NrOfShuffles),
+
+        /// The number of sealers which decrypted the ciphers of a topic changed. [vote_id, topic_id, submitted, required]
+        DecryptionProgress(VoteId, TopicId, u32, u32),
+
+        /// The public key of a vote has been rotated, a new key generation round started. [vote_id]
+        PublicKeyRotated(VoteId),
+
+        /// A sealer submitted its key switch shares for a topic. [topic_id, who]
+        KeySwitchSharesSubmitted(TopicId, AccountId),
+
+        /// The ciphers of a topic have been switched to the new public key. [vote_id, topic_id]
+        CiphersKeySwitched(VoteId, TopicId),
+
+        /// The ciphers and proofs of a vote have been pruned and replaced by their merkle root. [vote_id, merkle_root]
+        VoteArchived(VoteId, [u8; 32]),
+
+        /// A voting authority registered the encrypted credential of a voter. [vote_id, voter]
+        CredentialRegistered(VoteId, AccountId),
+
+        /// A voter spoiled its cipher of a topic, it is excluded from the tally. [voter, vote_id, topic_id, cipher]
+        BallotSpoiled(AccountId, VoteId, TopicId, Cipher),
+
+        /// A voter revealed the message and randomness of a spoiled cipher. [voter, topic_id, cipher_hash]
+        SpoiledBallotRevealed(AccountId, TopicId, [u8; 32]),
+
+        /// A voting authority set or removed the metadata of a vote. [vote_id, who]
+        MetadataUpdated(VoteId, AccountId),
+
+        /// A voting authority changed the number of ciphers shuffled per batch. [vote_id, batch_size]
+        ShuffleBatchSizeUpdated(VoteId, u64),
+
+        /// Not sufficient sealers decrypted the ciphers of a topic within `DecryptionTimeout` blocks. [vote_id, topic_id, missing_sealers]
+        DecryptionStalled(VoteId, TopicId, Vec<AccountId>),
+
+        /// A voting authority set the number of sealers whose decrypted shares suffice to tally the vote. [vote_id, threshold]
+        DecryptionThresholdUpdated(VoteId, u32),
+
+        /// A voting authority set the hash function of the challenges of the proofs of a vote. [vote_id, hash_function]
+        HashFunctionUpdated(VoteId, HashFunction),
+
+        /// A voting authority exempted the voters of a vote from the ballot deposit (or revoked the exemption). [vote_id, whitelisted]
+        VoteWhitelisted(VoteId, bool),
+
+        /// The ballot deposits of a voter have been refunded. [voter, vote_id, amount]
+        BallotDepositRefunded(AccountId, VoteId, Balance),
+
+        /// A voting authority set (or removed) the weight of a voter. [vote_id, voter, weight]
+        VoterWeightUpdated(VoteId, AccountId, Option<u32>),
+
+        /// A voting authority enabled (or disabled) shuffling all topics of a vote with the same permutation. [vote_id, linked]
+        LinkedShuffleUpdated(VoteId, bool),
+
+        /// A voting authority enabled (or disabled) the group membership check of the ciphers of a vote's ballots. [vote_id, enabled]
+        GroupCheckUpdated(VoteId, bool),
+
+        /// A voting authority packed (or unpacked) the answers of a topic. [vote_id, topic_id, packing]
+        TopicPackingUpdated(VoteId, TopicId, Option<TopicPacking>),
+
+        /// A sealer hasn't sent a heartbeat within two `HeartbeatInterval`s while a vote is being tallied. [sealer, last_heartbeat]
+        SealerUnresponsive(AccountId, Option<BlockNumber>),
+
+        /// A voting authority or a sealer approved the result hash of a vote. [vote_id, who, result_hash]
+        ResultApproved(VoteId, AccountId, [u8; 32]),
+
+        /// A voting authority and `CertificationQuorum` sealers approved the result hash of a vote, the result is final. [vote_id, result_hash]
+        ResultCertified(VoteId, [u8; 32]),
+
+        /// A relayer cast a ballot signed by the voter, see: cast_relayed_ballot. [relayer, voter, vote_id]
+        BallotRelayed(AccountId, AccountId, VoteId),
+
+        /// A voting authority approved the cancellation of a vote. [vote_id, who]
+        CancellationApproved(VoteId, AccountId),
+
+        /// A vote has been cancelled, neither ballots nor the tally are accepted anymore. [vote_id, reason]
+        VoteCancelled(VoteId, Vec<u8>),
+
+        /// The digest of a certified result has been stored, other chains can consume the result. [vote_id, digest]
+        ResultDigestCreated(VoteId, [u8; 32]),
+
+        /// A voting authority set the identity provider of a vote (None: removed). [vote_id, identity_provider]
+        IdentityProviderUpdated(VoteId, Option<IdentityProvider>),
+
+        /// A voter has been registered by the eligibility claim of the identity provider. [vote_id, voter]
+        VoterRegistered(VoteId, AccountId),
+
+        /// A voting authority revoked a voter, the ciphers of its ballot have been excluded from the tally. [vote_id, voter, nr_of_excluded_ciphers]
+        VoterRevoked(VoteId, AccountId, u32),
+
+        /// A voting authority set the key which blind-signs the eligibility tokens of a vote (None: removed). [vote_id, eligibility_key]
+        EligibilityKeyUpdated(VoteId, Option<EligibilityKey>),
+
+        /// An anonymous ballot has been cast with the eligibility token of the nullifier. [vote_id, nullifier, ballot]
+        AnonymousBallotSubmitted(VoteId, Nullifier, Ballot),
+
+        /// A voting authority imported a batch of ciphers collected off-chain. [vote_id, topic_id, import_index, source_tag, nr_of_ciphers, merkle_root]
+        CiphersImported(VoteId, TopicId, u32, Vec<u8>, u64, [u8; 32]),
+
+        /// The decrypted shares of a batch of ciphers have been combined and decoded. [vote_id, topic_id, combined, total]
+        TallyBatchCombined(VoteId, TopicId, u64, u64),
+
+        /// The ciphers of a vote have been frozen at the end of the voting phase, the shuffle proofs are bound to the digest. [vote_id, block_hash, digest]
+        BallotBoxFrozen(VoteId, Hash, [u8; 32]),
+
+        /// A voting authority added a sealer, it seals the votes created from the new epoch on. [sealer, epoch]
+        SealerAdded(AccountId, u32),
+
+        /// A voting authority removed a sealer, it still seals the votes created before the new epoch. [sealer, epoch]
+        SealerRemoved(AccountId, u32),
+
+        /// The tallying phase started, the credentials of the ballots are checked before the ballot box is frozen. [vote_id, nr_of_ballots]
+        CredentialCheckStarted(VoteId, u32),
+
+        /// A sealer submitted its blinded credential quotients. [vote_id, sealer]
+        CredentialBlindingsSubmitted(VoteId, AccountId),
+
+        /// A sealer submitted its partial decryptions of the blinded credential quotients. [vote_id, sealer]
+        CredentialDecryptionsSubmitted(VoteId, AccountId),
+
+        /// The credentials have been checked, the ballots with an invalid credential have been discarded. [vote_id, nr_of_discarded_ballots]
+        CredentialsChecked(VoteId, u32),
     }
 );
 
@@ -248,220 +802,1086 @@ decl_error! {
         ShuffleStateIncorrect,
 
         /// Error returned when shuffle is submitted for (vote_id, topic_id) which is already completed
-        ShuffleAlreadyCompleted
-    }
-}
+        ShuffleAlreadyCompleted,
 
-decl_module! {
-    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
-        // Errors must be initialized if they are used by the pallet.
-        type Error = Error<T>;
+        /// Error returned when a vote is created with zero required shuffle iterations
+        NrOfShufflesInvalid,
 
-        // Events must be initialized if they are used by the pallet.
-        fn deposit_event() = default;
+        /// Error returned when an operation doesn't match the vote's tally strategy
+        WrongTallyStrategy,
 
-        /// Set a vote phase.
-        #[weight = (10_000, Pays::No)]
-        fn set_vote_phase(origin, vote_id: VoteId, phase: VotePhase) -> DispatchResult {
-            // only the voting_authority should be able to store the key
-            let who: T::AccountId = ensure_signed(origin)?;
-            ensure_voting_authority::<T>(&who)?;
+        /// Error returned when the ciphers of a topic have already been aggregated
+        CiphersAlreadyAggregated,
 
-            // check that the vote_id exists
-            ensure_vote_exists::<T>(&vote_id)?;
+        /// Error returned when the ciphers of a topic have not been aggregated yet
+        CiphersNotAggregated,
 
-            // set the new phase
-            let mut vote: Vote<T::AccountId> = Votes::<T>::get(&vote_id);
-            vote.phase = phase.clone();
-            Votes::<T>::insert(&vote_id, &vote);
-            set_phase::<T>(&who, &vote_id, phase.clone())?;
+        /// Error returned when the decrypted aggregate exceeds the number of votes
+        HomomorphicTallyInvalid,
 
-            // notify that the vote phase has been changed
-            debug::info!("updated vote phase: {:?}, {:?}", vote_id, phase);
-            Self::deposit_event(RawEvent::VotePhaseChanged(vote_id, phase));
-            Ok(())
-        }
+        /// Error returned when a decrypted vote cannot be decoded within the decode bound
+        DecodeBoundExceeded,
 
-        /// DEV ONLY
-        /// NEEDS TO BE DISABLE IN PRODUCTION
-        #[weight = (10000, Pays::No)]
-        pub fn store_public_key(origin, vote_id: VoteId, pk: SubstratePK) -> DispatchResult {
-            // only the voting_authority should be able to store the key
-            let who: T::AccountId = ensure_signed(origin)?;
-            ensure_voting_authority::<T>(&who)?;
+        /// Error returned when the ciphers of a vote are not yet switched to the rotated public key
+        KeyRotationPending,
 
-            // store the public key
-            PublicKey::insert(vote_id.clone(), pk.clone());
+        /// Error returned when key switch shares are submitted for a topic which doesn't need to be switched
+        KeySwitchNotPending,
 
-            // notify that the public key has been stored
-            debug::info!("stored public key for vote: {:?}", vote_id);
-            Self::deposit_event(RawEvent::PublicKeyStored(who, vote_id, pk));
-            Ok(())
-        }
+        /// Error returned when a sealer has already submitted its key switch shares for a topic
+        KeySwitchSharesAlreadySubmitted,
 
-        /// Store a public key and its proof.
-        /// Can only be called from a sealer.
-        #[weight = (10_000, Pays::No)]
-        fn store_public_key_share(origin, vote_id: VoteId, pk_share: PublicKeyShare) -> DispatchResult {
-            // only sealers can store their public key shares
-            let who: T::AccountId = ensure_signed(origin)?;
-            ensure_not_a_voting_authority::<T>(&who)?;
-            ensure_sealer::<T>(&who)?;
+        /// Error returned when the key switch proof doesn't verify
+        KeySwitchShareProofError,
 
-            // verify key generatin proof
-            // and store public key share
-            verify_proof_and_store_keygen_share::<T>(who.clone(), &vote_id, pk_share.clone())?;
+        /// Error returned when a vote is archived before all its topics have been tallied
+        VoteNotTallied,
 
-            debug::info!("stored public key share for vote: {:?} (by sealer: {:?})", vote_id, who.clone());
-            Self::deposit_event(RawEvent::PublicKeyShareSubmitted(pk_share));
-            Ok(())
-        }
+        /// Error returned when a vote has already been archived
+        VoteAlreadyArchived,
 
-        /// Combine public key shares into a single public key.
-        #[weight = (10_000, Pays::No)]
-        fn combine_public_key_shares(origin, vote_id: VoteId) -> DispatchResult {
-            // only the voting_authority should be able to combine the public key shares
-            let who: T::AccountId = ensure_signed(origin)?;
-            ensure_voting_authority::<T>(&who)?;
-            ensure_vote_exists::<T>(&vote_id)?;
+        /// Error returned when a shuffle is revealed without a prior commitment of the sealer
+        ShuffleNotCommitted,
 
-            // create the system's public key
-            let pk: SubstratePK = combine_shares::<T>(who, &vote_id)?;
+        /// Error returned when another sealer's shuffle commitment is still pending
+        ShuffleAlreadyCommitted,
 
-            debug::info!("combined public key shares for vote: {:?}", vote_id);
-            Self::deposit_event(RawEvent::PublicKeyCreated(vote_id, pk));
-            Ok(())
-        }
+        /// Error returned when a shuffle is revealed in the same block as its commitment
+        ShuffleRevealTooEarly,
 
-        /// Create a vote and store public crypto parameters.
-        /// Can only be called from a voting authority.
-        #[weight = (10000, Pays::No)]
-        fn create_vote(origin, vote_id: VoteId, title: Title, params: PublicParameters, topics: Vec<Topic>, batch_size: u64) -> DispatchResult {
-            let who: T::AccountId = ensure_signed(origin)?;
-            ensure_voting_authority::<T>(&who)?;
+        /// Error returned when the revealed shuffle doesn't match the commitment
+        ShuffleCommitmentMismatch,
 
-            // create new vote
-            let vote = Vote::<T::AccountId> {
-                voting_authority: who.clone(),
-                title,
-                phase: VotePhase::default(),
-                params: params.clone()
-            };
+        /// Error returned when a sealer commits to or reveals a shuffle when it's not its turn
+        NotYourShuffleTurn,
 
-            // store the vote_id, vote + topic information
-            let mut vote_ids: Vec<VoteId> = VoteIds::get();
-            ensure_vote_does_not_exist::<T>(&vote_id)?;
+        /// Error returned when a credential has already been registered for the voter
+        CredentialAlreadyRegistered,
 
-            vote_ids.push(vote_id.clone());
-            VoteIds::put(vote_ids);
-            Votes::<T>::insert(&vote_id, vote);
+        /// Error returned when posting the results to the results endpoint fails
+        ResultsPostError,
 
-            // create an empty shuffle state for each topic
-            for topic in topics.iter() {
-                let (topic_id, _) = topic;
-                ShuffleStateStore::insert((&vote_id, &topic_id), ShuffleState {
-                    iteration: 0,
-                    start_position: 0,
-                    batch_size,
-                    done: false
-                });
-            }
+        /// Error returned when a vote is changed after its voting phase has started
+        VoteAlreadyStarted,
 
-            // store all topics (topic_id, question)
-            Topics::insert(&vote_id, topics);
+        /// Error returned when approving a vote which hasn't been proposed
+        VoteProposalDoesNotExist,
 
-            // log success + emit event
-            debug::info!("created vote: {:?}", vote_id);
-            Self::deposit_event(RawEvent::VoteCreatedWithPublicParameters(vote_id, who, params));
-            Ok(())
-        }
+        /// Error returned when a voting authority approves the same proposal twice
+        AlreadyApproved,
 
-        /// Add a question to the vote.
-        /// Can only be called from a voting authority.
-        #[weight = (10000, Pays::No)]
-        fn store_question(origin, vote_id: VoteId, topic: Topic, batch_size: u64) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-            ensure_voting_authority::<T>(&who)?;
-            ensure_vote_exists::<T>(&vote_id)?;
+        /// Error returned when a ballot contains a cipher which has already been submitted
+        DuplicateCipher,
 
-            let topic_id = &topic.0;
-            let mut topics: Vec<Topic> = Topics::get(&vote_id);
-            topics.push(topic.clone());
+        /// Error returned when the proofs of a ballot don't verify for the voter
+        BallotProofError,
 
-            // create an empty shuffle state for the topic
-            ShuffleStateStore::insert((&vote_id, topic_id), ShuffleState {
-                iteration: 0,
-                start_position: 0,
-                batch_size,
-                done: false,
-            });
+        /// Error returned when the voter has no stored cipher for the topic which can be spoiled
+        SpoiledCipherNotFound,
 
-            // store the topic
-            Topics::insert(&vote_id, topics);
+        /// Error returned when spoiling a cipher which has already been spoiled
+        BallotAlreadySpoiled,
 
-            debug::info!("added question: {:?} to vote: {:?}", topic, vote_id);
-            Self::deposit_event(RawEvent::VoteTopicQuestionStored(vote_id, topic));
-            Ok(())
-        }
+        /// Error returned when the voter has no spoiled cipher with the given hash
+        SpoiledBallotNotFound,
 
-        #[weight = (10000, Pays::No)]
-        pub fn cast_ballot(origin, vote_id: VoteId, ballot: Ballot) -> DispatchResult {
-          let who = ensure_signed(origin)?;
-          ensure_vote_exists::<T>(&vote_id)?;
-          ensure_vote_phase::<T>(&vote_id, VotePhase::Voting)?;
+        /// Error returned when the message and randomness of a spoiled cipher have already been revealed
+        SpoiledBallotAlreadyRevealed,
 
-          // TODO: ensure that it is a legit voter -> in some other project where identity management is considered
+        /// Error returned when the revealed message and randomness don't match the spoiled cipher
+        InvalidSpoiledBallotReveal,
 
-          // store the ballot
-          store_ballot::<T>(&who, &vote_id, ballot.clone());
+        /// Error returned when a question has too many candidates or a type which doesn't match its candidates or the tally strategy
+        InvalidQuestionType,
 
-          // notify that the ballot has been submitted and stored
-          debug::info!("stored ballot for vote_id: {:?}", vote_id);
-          Self::deposit_event(RawEvent::BallotSubmitted(who, vote_id, ballot));
-          Ok(())
-        }
+        /// Error returned when an answer to a question with candidates isn't proven to be a valid answer
+        BallotValidityProofError,
 
-        /// Test function to check signer.
-        #[weight = (10_000, Pays::No)]
-        fn submit_shuffled_votes_and_proof(origin, vote_id: VoteId, topic_id: TopicId, payload: ShufflePayload) -> DispatchResult {
+        /// Error returned when the metadata of a vote exceeds `MaxMetadataSize`
+        MetadataTooLarge,
+
+        /// Error returned when the shuffle batch size is not within `MinShuffleBatchSize` and `MaxShuffleBatchSize`
+        InvalidShuffleBatchSize,
+
+        /// Error returned when the decryption threshold is zero or larger than the number of sealers
+        InvalidDecryptionThreshold,
+
+        /// Error returned when rotating the public key of a vote with threshold keys
+        KeyRotationNotSupported,
+
+        /// Error returned when an account submitted more than `MaxBallotsPerPeriod` ballots in the current period
+        BallotRateLimitExceeded,
+
+        /// Error returned when the free balance of a voter doesn't cover the ballot deposit
+        InsufficientBallotDeposit,
+
+        /// Error returned when claiming the ballot deposit of a vote without a reserved deposit
+        NoBallotDeposit,
+
+        /// Error returned when the weight of a voter is zero or larger than `MaxVoterWeight`
+        InvalidVoterWeight,
+
+        /// Error returned when a ballot of a vote with linked shuffles doesn't answer every topic exactly once or is spoiled partially
+        IncompleteLinkedBallot,
+
+        /// Error returned when the shuffles of a linked shuffle don't cover all topics, their batches or their permutation commitments differ
+        LinkedShuffleMismatch,
+
+        /// Error returned when a topic of a vote with linked shuffles is shuffled on its own, or the topics of another vote are shuffled linked
+        WrongShuffleMode,
+
+        /// Error returned when an account which is neither a voting authority nor a sealer certifies a result
+        NotACertifier,
+
+        /// Error returned when the certified result hash doesn't match the results of the vote
+        ResultHashMismatch,
+
+        /// Error returned when approving the result of a vote which has already been certified
+        ResultAlreadyCertified,
+
+        /// Error returned when an account approves the result of a vote twice
+        ResultAlreadyApproved,
+
+        /// Error returned when a relayed ballot is not signed by the voter
+        RelayedBallotSignatureError,
+
+        /// Error returned when the reason of a cancellation exceeds MAX_CANCELLATION_REASON_LENGTH
+        CancellationReasonTooLong,
+
+        /// Error returned when a cancelled vote is modified or cancelled again
+        VoteCancelled,
+
+        /// Error returned when a voter is registered for a vote without identity provider
+        NoIdentityProvider,
+
+        /// Error returned when the eligibility claim is not signed by the identity provider of the vote
+        InvalidEligibilityClaim,
+
+        /// Error returned when a voter is registered twice for the same vote
+        VoterAlreadyRegistered,
+
+        /// Error returned when a voter which isn't registered casts a ballot for a vote with an identity provider
+        VoterNotRegistered,
+
+        /// Error returned when a shuffle drops or injects ciphers, i.e., the number of shuffled ciphers doesn't match the batch
+        CipherCountMismatch,
+
+        /// Error returned when a batch of the tally is combined with other parameters than the previous batches
+        TallyParametersMismatch,
+
+        /// Error returned when the decrypted shares of all batches of the topic have already been combined
+        NoCiphersLeftToTally,
+
+        /// Error returned when the tally is finalized before the decrypted shares of all batches have been combined
+        TallyIncomplete,
+
+        /// Error returned when the vote has been created with a protocol which is not supported by this release
+        UnsupportedProtocol,
+
+        /// Error returned when a vote id is longer than `MaxVoteIdLength`
+        VoteIdTooLong,
+
+        /// Error returned when the title of a vote is longer than `MaxTitleLength`
+        TitleTooLong,
+
+        /// Error returned when a topic id is longer than `MaxTopicIdLength`
+        TopicIdTooLong,
+
+        /// Error returned when a question or one of its candidates is longer than `MaxQuestionLength`
+        QuestionTooLong,
+
+        /// Error returned when a component of a cipher is empty or longer than the modulus of the vote's public parameters
+        CipherMalformed,
+
+        /// Error returned when the account to add is already a sealer
+        SealerAlreadyAdded,
+
+        /// Error returned when the account to remove is not a sealer
+        SealerNotFound,
+
+        /// Error returned when the last sealer would be removed
+        TooFewSealers,
+
+        /// Error returned when the protocol of a vote is changed after the first public key share has been submitted
+        KeyGenerationStarted,
+
+        /// Error returned when an anonymous ballot is cast for a vote without eligibility key
+        NoEligibilityKey,
+
+        /// Error returned when the eligibility token is not signed with the eligibility key of the vote
+        InvalidEligibilityToken,
+
+        /// Error returned when a second anonymous ballot is cast with the same eligibility token
+        NullifierAlreadySpent,
+
+        /// Error returned when an anonymous ballot is not signed with the one-time key of its eligibility token
+        AnonymousBallotSignatureError,
+
+        /// Error returned when a ballot of a vote with an eligibility key is cast by an account
+        AnonymousVote,
+
+        /// Error returned when the topic is not a question of the vote
+        TopicNotInVote,
+
+        /// Error returned when a batch of imported ciphers is empty or has more than `MAX_CIPHERS_PER_IMPORT` ciphers
+        CipherImportTooLarge,
+
+        /// Error returned when the source tag of imported ciphers is longer than `MAX_SOURCE_TAG_LENGTH`
+        SourceTagTooLong,
+
+        /// Error returned when the private key share of the offchain worker is not configured
+        /// or doesn't match the public key share of the sealer
+        InvalidPrivateKeyShare,
+
+        /// Error returned when a vote is created from a template which doesn't exist
+        VoteTemplateDoesNotExist,
+
+        /// Error returned when a template has no topics or more than `MAX_TOPICS_PER_TEMPLATE` topics
+        VoteTemplateTooLarge,
+
+        /// Error returned when the vote can't change from its current phase to the requested phase,
+        /// see: `VotePhase::can_transition_to`
+        InvalidPhaseTransition,
+
+        /// Error returned when the answers of a question with candidates are packed
+        /// or the slots don't fit into a single cipher, see: TopicPacking
+        InvalidTopicPacking,
+
+        /// Error returned when a revoked voter casts a ballot or is registered again
+        VoterRevoked,
+
+        /// Error returned when a voter is revoked a second time
+        VoterAlreadyRevoked,
+
+        /// Error returned when a voter is revoked after the voting phase,
+        /// i.e., once the ballot box has been frozen
+        LateVoterRevocation,
+
+        /// Error returned when a component of a cipher is not an element of the group of the vote,
+        /// i.e., not a quadratic residue modulo p
+        CipherNotInGroup,
+
+        /// Error returned when a voter with a registered credential casts a ballot without a credential
+        CredentialMissing,
+
+        /// Error returned when a ballot carries a credential but no credential has been registered for the voter
+        CredentialNotRegistered,
+
+        /// Error returned when a credential is registered for a voter who has already cast a ballot
+        BallotAlreadyCast,
+
+        /// Error returned when the ciphers are aggregated while the credentials are checked
+        CredentialCheckPending,
+
+        /// Error returned when blindings or decryptions are submitted while no credential check is pending
+        CredentialCheckNotPending,
+
+        /// Error returned when the credential quotients are decrypted before enough sealers blinded them
+        CredentialBlindingsIncomplete,
+
+        /// Error returned when a sealer submits its blindings or decryptions a second time,
+        /// or the blindings once enough sealers blinded the credential quotients
+        CredentialCheckAlreadySubmitted,
+
+        /// Error returned when the number of blinded credential quotients or a proof of correct blinding is invalid
+        CredentialBlindingProofError,
+
+        /// Error returned when the number of decrypted credential quotients or the decryption proof is invalid
+        CredentialDecryptionProofError
+    }
+}
+
+impl<T: Trait> From<Error<T>> for ErrorCode {
+    /// maps the error to its stable code, see: `mixnet_primitives::ErrorCode`
+    fn from(error: Error<T>) -> Self {
+        let name: &'static str = error.into();
+        ErrorCode::from_name(name).unwrap_or(ErrorCode::Unknown)
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        // Errors must be initialized if they are used by the pallet.
+        type Error = Error<T>;
+
+        // Events must be initialized if they are used by the pallet.
+        fn deposit_event() = default;
+
+        /// Migrates the storage to the current layout, see: migrations
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate::<T>()
+        }
+
+        /// Reports the topics whose decryption stalled, see: DecryptionTimeout
+        /// and the sealers which missed their heartbeats, see: HeartbeatInterval
+        fn on_initialize(block_number: T::BlockNumber) -> Weight {
+            let (stalled, decryption_weight) = take_stalled_decryptions::<T>(block_number);
+            for (vote_id, topic_id, missing) in stalled.into_iter() {
+                debug::warn!("decryption of vote: {:?}, topic: {:?} stalled, missing sealers: {:?}", vote_id, topic_id, missing);
+                Self::deposit_event(RawEvent::DecryptionStalled(vote_id, topic_id, missing));
+            }
+
+            let (unresponsive, liveness_weight) = take_unresponsive_sealers::<T>(block_number);
+            for (sealer, last_heartbeat) in unresponsive.into_iter() {
+                debug::warn!("sealer: {:?} is unresponsive, last heartbeat: {:?}", sealer, last_heartbeat);
+                Self::deposit_event(RawEvent::SealerUnresponsive(sealer, last_heartbeat));
+            }
+            decryption_weight.saturating_add(liveness_weight)
+        }
+
+        /// Set a vote phase.
+        /// The vote can only change to the phases allowed by `VotePhase::can_transition_to`.
+        #[weight = (T::WeightInfo::set_vote_phase(), T::FeePolicy::set_vote_phase())]
+        fn set_vote_phase(origin, vote_id: VoteId, phase: VotePhase) -> DispatchResult {
+            // only the voting_authority should be able to store the key
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+
+            // check that the vote_id exists
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_not_archived::<T>(&vote_id)?;
+            ensure_vote_not_cancelled::<T>(&vote_id)?;
+
+            // a vote is only cancelled by its voting authorities, see: cancel_vote
+            ensure!(phase != VotePhase::Cancelled, Error::<T>::WrongVotePhase);
+
+            // the ciphers can only be tallied once they are encrypted under the current key
+            if phase == VotePhase::Tallying {
+                ensure_no_key_switch_pending::<T>(&vote_id)?;
+            }
+
+            // a started vote only returns to the key generation by rotating its public key
+            if phase == VotePhase::KeyGeneration {
+                ensure_vote_not_started::<T>(&vote_id)?;
+            }
+            ensure_valid_phase_transition::<T>(&vote_id, &phase)?;
+
+            // the tallying phase starts once sufficient voting authorities approved it
+            if phase == VotePhase::Tallying {
+                let approved = approve_tallying::<T>(&who, &vote_id)?;
+                Self::deposit_event(RawEvent::TallyingApproved(vote_id.clone(), who.clone()));
+                if !approved {
+                    debug::info!("approved tallying of vote: {:?}, by: {:?}", vote_id, who);
+                    return Ok(());
+                }
+            }
+
+            // set the new phase
+            let previous: VotePhase = set_phase::<T>(&who, &vote_id, phase.clone())?;
+
+            // notify that the vote phase has been changed
+            debug::info!("updated vote phase: {:?}, {:?} -> {:?}", vote_id, previous, phase);
+            Self::deposit_event(RawEvent::VotePhaseChanged(vote_id, previous, phase));
+            Ok(())
+        }
+
+        /// Force the phase of a vote, e.g. to repair a vote stuck in a phase.
+        /// Neither the transitions (see: `VotePhase::can_transition_to`) nor the approvals
+        /// of the tallying phase are checked, a vote is still only cancelled using `cancel_vote`.
+        /// Can only be called from the root origin (i.e., sudo).
+        #[weight = (T::WeightInfo::set_vote_phase(), T::FeePolicy::set_vote_phase())]
+        fn force_vote_phase(origin, vote_id: VoteId, phase: VotePhase) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_not_archived::<T>(&vote_id)?;
+            ensure!(phase != VotePhase::Cancelled, Error::<T>::WrongVotePhase);
+
+            let previous: VotePhase = force_phase::<T>(&vote_id, phase.clone())?;
+            TallyingApprovals::<T>::remove(&vote_id);
+
+            debug::warn!("forced vote phase: {:?}, {:?} -> {:?}", vote_id, previous, phase);
+            Self::deposit_event(RawEvent::VotePhaseChanged(vote_id, previous, phase));
+            Ok(())
+        }
+
+        /// Cancel a vote which has not been archived yet, e.g. to abort a flawed election.
+        /// The vote is cancelled once sufficient voting authorities approved it (see: ApprovalThreshold),
+        /// neither ballots nor the tally are accepted afterwards. The ballot deposits can be claimed
+        /// and the vote can be archived to clean up its storage.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::cancel_vote(), T::FeePolicy::cancel_vote())]
+        fn cancel_vote(origin, vote_id: VoteId, reason: Vec<u8>) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_not_archived::<T>(&vote_id)?;
+            ensure_vote_not_cancelled::<T>(&vote_id)?;
+            ensure_valid_cancellation_reason::<T>(&reason)?;
+
+            let approved = approve_cancellation::<T>(&who, &vote_id)?;
+            Self::deposit_event(RawEvent::CancellationApproved(vote_id.clone(), who.clone()));
+            if !approved {
+                debug::info!("approved cancellation of vote: {:?}, by: {:?}", vote_id, who);
+                return Ok(());
+            }
+
+            cancel::<T>(&vote_id);
+            Self::deposit_event(RawEvent::VoteCancelled(vote_id, reason));
+            Ok(())
+        }
+
+        /// DEV ONLY
+        /// NEEDS TO BE DISABLE IN PRODUCTION
+        #[weight = (T::WeightInfo::store_public_key(), T::FeePolicy::store_public_key())]
+        pub fn store_public_key(origin, vote_id: VoteId, pk: SubstratePK) -> DispatchResult {
+            // only the voting_authority should be able to store the key
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+
+            // store the public key
+            PublicKey::insert(vote_id.clone(), pk.clone());
+
+            // notify that the public key has been stored
+            debug::info!("stored public key for vote: {:?}", vote_id);
+            Self::deposit_event(RawEvent::PublicKeyStored(who, vote_id, pk));
+            Ok(())
+        }
+
+        /// Store a public key and its proof.
+        /// Can only be called from a sealer.
+        #[weight = (T::WeightInfo::store_public_key_share(), T::FeePolicy::store_public_key_share())]
+        fn store_public_key_share(origin, vote_id: VoteId, pk_share: PublicKeyShare) -> DispatchResult {
+            // only sealers can store their public key shares
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_not_a_voting_authority::<T>(&who)?;
+            ensure_vote_sealer::<T>(&who, &vote_id)?;
+
+            // verify key generatin proof
+            // and store public key share
+            verify_proof_and_store_keygen_share::<T>(who.clone(), &vote_id, pk_share.clone())?;
+
+            debug::info!("stored public key share for vote: {:?} (by sealer: {:?})", vote_id, who.clone());
+            Self::deposit_event(RawEvent::PublicKeyShareSubmitted(pk_share));
+            Ok(())
+        }
+
+        /// Combine public key shares into a single public key.
+        #[weight = (T::WeightInfo::combine_public_key_shares(), T::FeePolicy::combine_public_key_shares())]
+        fn combine_public_key_shares(origin, vote_id: VoteId) -> DispatchResult {
+            // only the voting_authority should be able to combine the public key shares
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+
+            // create the system's public key
+            let pk: SubstratePK = combine_shares::<T>(who, &vote_id)?;
+
+            debug::info!("combined public key shares for vote: {:?}", vote_id);
+            Self::deposit_event(RawEvent::PublicKeyCreated(vote_id, pk));
+            Ok(())
+        }
+
+        /// Propose a vote and its public crypto parameters.
+        /// The vote is created once it has been approved by ApprovalThreshold voting authorities,
+        /// the proposal counts as the first approval.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::propose_vote(topics.len() as u32), T::FeePolicy::propose_vote())]
+        fn propose_vote(origin, vote_id: VoteId, title: Title, params: PublicParameters, topics: Vec<Topic>, batch_size: u64, nr_of_shuffles: NrOfShuffles, tally_strategy: TallyStrategy) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_valid_vote_id::<T>(&vote_id)?;
+            ensure_valid_title::<T>(&title)?;
+            ensure!(nr_of_shuffles > 0, Error::<T>::NrOfShufflesInvalid);
+            ensure_valid_shuffle_batch_size::<T>(batch_size)?;
+            for topic in topics.iter() {
+                ensure_valid_topic::<T>(topic, &tally_strategy)?;
+            }
+
+            // create new vote proposal
+            let proposal = VoteProposal::<T::AccountId> {
+                vote: Vote::<T::AccountId> {
+                    voting_authority: who.clone(),
+                    title,
+                    phase: VotePhase::default(),
+                    params: params.clone(),
+                    nr_of_shuffles,
+                    tally_strategy
+                },
+                topics,
+                batch_size,
+                approvals: vec![who.clone()],
+            };
+            let created = propose::<T>(&vote_id, proposal)?;
+
+            // log success + emit event
+            debug::info!("proposed vote: {:?}", vote_id);
+            Self::deposit_event(RawEvent::VoteProposed(vote_id.clone(), who.clone()));
+            if created {
+                Self::deposit_event(RawEvent::VoteCreatedWithPublicParameters(vote_id, who, params));
+            }
+            Ok(())
+        }
+
+        /// Approve a proposed vote, the vote is created once sufficient voting authorities approved it.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::approve_vote(), T::FeePolicy::approve_vote())]
+        fn approve_vote(origin, vote_id: VoteId) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+
+            let created = approve::<T>(&who, &vote_id)?;
+            debug::info!("approved vote: {:?}, by: {:?}", vote_id, who);
+            Self::deposit_event(RawEvent::VoteApproved(vote_id.clone(), who));
+            if created {
+                let vote: Vote<T::AccountId> = Votes::<T>::get(&vote_id);
+                Self::deposit_event(RawEvent::VoteCreatedWithPublicParameters(vote_id, vote.voting_authority, vote.params));
+            }
+            Ok(())
+        }
+
+        /// Store the template of recurring votes, i.e., their topics, public parameters and tally strategy.
+        /// An existing template is replaced, the votes created from it before are not affected.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::store_vote_template(template.topics.len() as u32), T::FeePolicy::store_vote_template())]
+        fn store_vote_template(origin, template_id: VoteTemplateId, template: VoteTemplate) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            store_template::<T>(&template_id, template)?;
+
+            debug::info!("stored vote template: {:?}", template_id);
+            Self::deposit_event(RawEvent::VoteTemplateStored(template_id, who));
+            Ok(())
+        }
+
+        /// Propose a vote cloned from a template with a fresh vote_id and title.
+        /// The ids of the topics are prefixed with the vote_id, e.g. `<vote_id>-<topic_id>`.
+        /// The vote is created once it has been approved like a vote proposed with `propose_vote`.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::create_vote_from_template(MAX_TOPICS_PER_TEMPLATE as u32), T::FeePolicy::create_vote_from_template())]
+        fn create_vote_from_template(origin, template_id: VoteTemplateId, vote_id: VoteId, title: Title) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_valid_vote_id::<T>(&vote_id)?;
+            ensure_valid_title::<T>(&title)?;
+
+            let proposal = get_template_proposal::<T>(&who, &template_id, &vote_id, title)?;
+            let params = proposal.vote.params.clone();
+            let created = propose::<T>(&vote_id, proposal)?;
+
+            debug::info!("proposed vote: {:?} from template: {:?}", vote_id, template_id);
+            Self::deposit_event(RawEvent::VoteProposed(vote_id.clone(), who.clone()));
+            if created {
+                Self::deposit_event(RawEvent::VoteCreatedWithPublicParameters(vote_id, who, params));
+            }
+            Ok(())
+        }
+
+        /// Add a question to the vote.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::store_question(), T::FeePolicy::store_question())]
+        fn store_question(origin, vote_id: VoteId, topic: Topic, batch_size: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+
+            // a new topic would invalidate the ballots which have already been cast
+            ensure_vote_not_started::<T>(&vote_id)?;
+
+            let vote: Vote<T::AccountId> = Votes::<T>::get(&vote_id);
+            ensure_valid_topic::<T>(&topic, &vote.tally_strategy)?;
+            ensure_valid_shuffle_batch_size::<T>(batch_size)?;
+            ensure_linked_batch_size::<T>(&vote_id, batch_size)?;
+
+            let topic_id = &topic.id;
+            let mut topics: Vec<Topic> = Topics::get(&vote_id);
+            topics.push(topic.clone());
+
+            // create an empty shuffle state for the topic
+            // topics which are tallied homomorphically are never shuffled
+            set_shuffle_state::<T>(&vote_id, topic_id, ShuffleState {
+                iteration: 0,
+                start_position: 0,
+                batch_size,
+                done: vote.tally_strategy == TallyStrategy::Homomorphic,
+            });
+
+            // store the topic
+            Topics::insert(&vote_id, topics);
+
+            debug::info!("added question: {:?} to vote: {:?}", topic, vote_id);
+            Self::deposit_event(RawEvent::VoteTopicQuestionStored(vote_id, topic));
+            Ok(())
+        }
+
+        /// Set the metadata of a vote, None removes it.
+        /// Can only be called from a voting authority until the vote is archived.
+        #[weight = (T::WeightInfo::set_metadata(), T::FeePolicy::set_metadata())]
+        fn set_metadata(origin, vote_id: VoteId, metadata: Option<VoteMetadata>) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_not_archived::<T>(&vote_id)?;
+
+            match metadata {
+                Some(metadata) => {
+                    ensure_valid_metadata::<T>(&metadata)?;
+                    Metadata::insert(&vote_id, metadata);
+                }
+                None => Metadata::remove(&vote_id),
+            }
+
+            debug::info!("updated metadata of vote: {:?}, by: {:?}", vote_id, who);
+            Self::deposit_event(RawEvent::MetadataUpdated(vote_id, who));
+            Ok(())
+        }
+
+        /// Set the number of ciphers shuffled per batch of all questions of the vote.
+        /// A larger batch size requires fewer shuffles, a smaller one less weight per block.
+        /// Questions which are being shuffled already switch to the batch size from their next batch on.
+        /// Can only be called from a voting authority until the vote is archived.
+        #[weight = (T::WeightInfo::set_shuffle_batch_size(), T::FeePolicy::set_shuffle_batch_size())]
+        fn set_shuffle_batch_size(origin, vote_id: VoteId, batch_size: u64) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_not_archived::<T>(&vote_id)?;
+            ensure_valid_shuffle_batch_size::<T>(batch_size)?;
+
+            ShuffleBatchSizes::insert(&vote_id, batch_size);
+
+            debug::info!("updated shuffle batch size of vote: {:?} to: {:?}", vote_id, batch_size);
+            Self::deposit_event(RawEvent::ShuffleBatchSizeUpdated(vote_id, batch_size));
+            Ok(())
+        }
+
+        /// Set the number of sealers whose decrypted shares suffice to tally the vote (threshold keys).
+        /// The private key share of the i-th sealer must be the i-th fragment of the vote's secret key,
+        /// the public key is interpolated from the public key shares of the sealers.
+        /// Can only be called from a voting authority before the voting phase starts.
+        #[weight = (T::WeightInfo::set_decryption_threshold(), T::FeePolicy::set_decryption_threshold())]
+        fn set_decryption_threshold(origin, vote_id: VoteId, threshold: u32) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_phase::<T>(&vote_id, VotePhase::KeyGeneration)?;
+            ensure_vote_not_started::<T>(&vote_id)?;
+            ensure_valid_decryption_threshold::<T>(&vote_id, threshold)?;
+
+            DecryptionThresholds::insert(&vote_id, threshold);
+
+            debug::info!("updated decryption threshold of vote: {:?} to: {:?}", vote_id, threshold);
+            Self::deposit_event(RawEvent::DecryptionThresholdUpdated(vote_id, threshold));
+            Ok(())
+        }
+
+        /// Set the hash function of the Fiat-Shamir challenges of the key generation, decryption, key switch and shuffle proofs of a vote.
+        /// The sealers generate and the pallet and the verifier check the proofs with the hash function recorded in the protocol of the vote.
+        /// Can only be called from a voting authority before the first public key share has been submitted.
+        #[weight = (T::WeightInfo::set_hash_function(), T::FeePolicy::set_hash_function())]
+        fn set_hash_function(origin, vote_id: VoteId, hash_function: HashFunction) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_phase::<T>(&vote_id, VotePhase::KeyGeneration)?;
+
+            store_hash_function::<T>(&vote_id, hash_function)?;
+            debug::info!("updated hash function of vote: {:?} to: {:?}", vote_id, hash_function);
+            Self::deposit_event(RawEvent::HashFunctionUpdated(vote_id, hash_function));
+            Ok(())
+        }
+
+        /// Register the encrypted credential of a voter. Only ballots cast with a credential
+        /// which is plaintext equivalent to the registered one are counted, all others
+        /// (e.g., cast with a fake credential handed to a coercer) are discarded during the tally.
+        /// Can only be called from a voting authority before the voter casts its first ballot and before the tally starts.
+        #[weight = (T::WeightInfo::register_credential(), T::FeePolicy::register_credential())]
+        fn register_credential(origin, vote_id: VoteId, voter: T::AccountId, credential: Cipher) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_valid_ciphers::<T>(&vote_id, core::iter::once(&credential))?;
+
+            store_credential::<T>(&vote_id, &voter, credential)?;
+            debug::info!("registered credential for vote_id: {:?}, voter: {:?}", vote_id, voter);
+            Self::deposit_event(RawEvent::CredentialRegistered(vote_id, voter));
+            Ok(())
+        }
+
+        /// Submit the blinded credential quotients of a sealer (one per ballot with a credential, see: CredentialBallots)
+        /// and their proofs of correct blinding. The credentials are checked once the tallying phase starts, before the ballot box is frozen.
+        /// Can only be called from a sealer of the vote while the credentials are checked.
+        #[weight = (T::WeightInfo::submit_credential_blindings(blindings.len() as u32), T::FeePolicy::submit_credential_blindings())]
+        fn submit_credential_blindings(origin, vote_id: VoteId, blindings: Vec<Cipher>, proofs: Vec<CredentialBlindingProof>) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_vote_sealer::<T>(&who, &vote_id)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_credential_check_pending::<T>(&vote_id)?;
+            ensure_valid_ciphers::<T>(&vote_id, blindings.iter())?;
+
+            verify_and_store_credential_blindings::<T>(&who, &vote_id, blindings, proofs)?;
+            debug::info!("stored credential blindings of sealer: {:?} for vote: {:?}", who, vote_id);
+            Self::deposit_event(RawEvent::CredentialBlindingsSubmitted(vote_id, who));
+            Ok(())
+        }
+
+        /// Submit the partial decryptions of the combined blinded credential quotients of a sealer and their decryption proof.
+        /// Once all sealers (or the threshold of sealers) have submitted their partial decryptions, the ballots with an
+        /// invalid credential are discarded, the ballot box is frozen and the shuffles start.
+        /// Can only be called from a sealer of the vote while the credentials are checked.
+        #[weight = (T::WeightInfo::submit_credential_decryptions(shares.len() as u32), T::FeePolicy::submit_credential_decryptions())]
+        fn submit_credential_decryptions(origin, vote_id: VoteId, shares: Vec<DecryptedShare>, proof: DecryptedShareProof) -> DispatchResult {
             let who: T::AccountId = ensure_signed(origin)?;
-            ensure_sealer::<T>(&who)?;
+            ensure_vote_sealer::<T>(&who, &vote_id)?;
             ensure_vote_exists::<T>(&vote_id)?;
+            ensure_credential_check_pending::<T>(&vote_id)?;
 
-            // TODO: discuss if shuffling should be allowed earlier
-            ensure_vote_phase::<T>(&vote_id, VotePhase::Tallying)?;
+            verify_and_store_credential_decryptions::<T>(&who, &vote_id, shares, proof)?;
+            debug::info!("stored credential decryptions of sealer: {:?} for vote: {:?}", who, vote_id);
+            Self::deposit_event(RawEvent::CredentialDecryptionsSubmitted(vote_id.clone(), who));
+
+            // discard the ballots with an invalid credential before the ciphers are shuffled or aggregated
+            if let Some(nr_of_discarded) = discard_invalid_credential_ballots::<T>(&vote_id)? {
+                debug::info!("discarded {:?} ballots with an invalid credential of vote: {:?}", nr_of_discarded, vote_id);
+                Self::deposit_event(RawEvent::CredentialsChecked(vote_id.clone(), nr_of_discarded));
+                start_tallying::<T>(&vote_id);
+            }
+            Ok(())
+        }
 
-            Self::verify_proof_store_shuffled_ciphers(&vote_id, &topic_id, payload)?;
+        /// Set the identity provider of a vote which signs the eligibility claims of its voters, None removes it.
+        /// Only the voters registered by a claim (see: register_voter_with_claim) can cast a ballot for a vote with an identity provider.
+        /// Can only be called from a voting authority before the voting phase starts.
+        #[weight = (T::WeightInfo::set_identity_provider(), T::FeePolicy::set_identity_provider())]
+        fn set_identity_provider(origin, vote_id: VoteId, provider: Option<IdentityProvider>) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
 
-            // notify that the decrypted share has been:
-            // submitted, the proof verified and stored
-            debug::info!("verified shuffle proof for vote_id: {:?}, topic_id: {:?}", vote_id, topic_id);
-            Self::deposit_event(RawEvent::ShuffleProofSubmitted(topic_id, who));
+            store_identity_provider::<T>(&vote_id, provider.clone())?;
+            debug::info!("identity provider of vote: {:?}: {:?}", vote_id, provider);
+            Self::deposit_event(RawEvent::IdentityProviderUpdated(vote_id, provider));
             Ok(())
         }
 
-        /// Store a decrypted shares.
-        #[weight = (10_000, Pays::No)]
-        fn submit_decrypted_shares(origin, vote_id: VoteId, topic_id: TopicId, shares: Vec<DecryptedShare>, proof: DecryptedShareProof, nr_of_shuffles: NrOfShuffles) -> DispatchResult {
-            // only sealers should be able to store their decrypted shares
+        /// Set the key of the voting authority which blind-signs the eligibility tokens of the voters of a vote, None removes it.
+        /// A vote with an eligibility key only accepts anonymous ballots (see: cast_anonymous_ballot), the voting authority
+        /// checks the eligibility of the voters off-chain. Can only be called from a voting authority before the voting phase starts.
+        #[weight = (T::WeightInfo::set_eligibility_key(), T::FeePolicy::set_eligibility_key())]
+        fn set_eligibility_key(origin, vote_id: VoteId, key: Option<EligibilityKey>) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+
+            store_eligibility_key::<T>(&vote_id, key.clone())?;
+            debug::info!("eligibility key of vote: {:?}: {:?}", vote_id, key.is_some());
+            Self::deposit_event(RawEvent::EligibilityKeyUpdated(vote_id, key));
+            Ok(())
+        }
+
+        /// Register a voter by the eligibility claim of the identity provider of the vote, i.e., the signature
+        /// of the vote id and the voter's account id (see: mixnet_primitives::claims).
+        /// Can be called by anyone (e.g. the voter) before the tally starts, the claim can't be used for another voter.
+        #[weight = (T::WeightInfo::register_voter_with_claim(), T::FeePolicy::register_voter_with_claim())]
+        fn register_voter_with_claim(origin, vote_id: VoteId, voter: T::AccountId, signature: ClaimSignature) -> DispatchResult {
+            ensure_signed(origin)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+
+            register_voter::<T>(&vote_id, &voter, &signature)?;
+            debug::info!("registered voter: {:?} for vote_id: {:?}", voter, vote_id);
+            Self::deposit_event(RawEvent::VoterRegistered(vote_id, voter));
+            Ok(())
+        }
+
+        /// Revoke a voter, e.g. a voter which has been registered erroneously, and add it to the revocation list.
+        /// A revoked voter can't cast a ballot anymore, the ciphers of its last ballot are excluded from the tally.
+        /// Can only be called from a voting authority before the tally starts, later revocations are rejected.
+        #[weight = (T::WeightInfo::revoke_voter(), T::FeePolicy::revoke_voter())]
+        fn revoke_voter(origin, vote_id: VoteId, voter: T::AccountId) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_no_key_switch_pending::<T>(&vote_id)?;
+
+            let nr_of_excluded_ciphers = revoke_voter::<T>(&vote_id, &voter)?;
+            debug::info!("revoked voter: {:?} of vote_id: {:?}, excluded ciphers: {:?}", voter, vote_id, nr_of_excluded_ciphers);
+            Self::deposit_event(RawEvent::VoterRevoked(vote_id, voter, nr_of_excluded_ciphers));
+            Ok(())
+        }
+
+        /// Set the weight of a voter (weighted voting, e.g. shareholder votes), None removes the weight.
+        /// A weighted voter answers with 0 (no) or its weight (yes) and must prove that its answers are one of them.
+        /// Can only be called from a voting authority before the voting phase starts, only for votes with the homomorphic tally.
+        #[weight = (T::WeightInfo::set_voter_weight(), T::FeePolicy::set_voter_weight())]
+        fn set_voter_weight(origin, vote_id: VoteId, voter: T::AccountId, weight: Option<u32>) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+
+            store_voter_weight::<T>(&vote_id, &voter, weight)?;
+            debug::info!("set weight of voter: {:?}, vote_id: {:?}: {:?}", voter, vote_id, weight);
+            Self::deposit_event(RawEvent::VoterWeightUpdated(vote_id, voter, weight));
+            Ok(())
+        }
+
+        /// Shuffle all topics of a vote with the same permutation (linked shuffles), i.e., the answers
+        /// of a ballot can't be correlated across the independently shuffled topics. Every ballot has to answer all topics.
+        /// Can only be called from a voting authority before the voting phase starts, only for votes which are shuffled.
+        #[weight = (T::WeightInfo::set_linked_shuffle(), T::FeePolicy::set_linked_shuffle())]
+        fn set_linked_shuffle(origin, vote_id: VoteId, linked: bool) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+
+            store_linked_shuffle::<T>(&vote_id, linked)?;
+            debug::info!("linked shuffles of vote: {:?}: {:?}", vote_id, linked);
+            Self::deposit_event(RawEvent::LinkedShuffleUpdated(vote_id, linked));
+            Ok(())
+        }
+
+        /// Check that both components of the ciphers of all ballots cast for a vote are elements of the group of the vote,
+        /// i.e., the ciphers can't break the proofs of the shuffles and decryptions later. Costs two modular exponentiations per cipher.
+        /// Can only be called from a voting authority before the voting phase starts.
+        #[weight = (T::WeightInfo::set_group_check(), T::FeePolicy::set_group_check())]
+        fn set_group_check(origin, vote_id: VoteId, enabled: bool) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_not_started::<T>(&vote_id)?;
+
+            if enabled {
+                GroupChecks::insert(&vote_id, true);
+            } else {
+                GroupChecks::remove(&vote_id);
+            }
+
+            debug::info!("group check of vote: {:?}: {:?}", vote_id, enabled);
+            Self::deposit_event(RawEvent::GroupCheckUpdated(vote_id, enabled));
+            Ok(())
+        }
+
+        /// Pack the answers of several independent free-form questions into the cipher of a topic (ballot packing),
+        /// i.e., they are shuffled and decrypted once and unpacked during the tally (see: PackedTally). None unpacks the topic.
+        /// Can only be called from a voting authority before the voting phase starts, only for votes which are shuffled.
+        #[weight = (T::WeightInfo::set_topic_packing(), T::FeePolicy::set_topic_packing())]
+        fn set_topic_packing(origin, vote_id: VoteId, topic_id: TopicId, packing: Option<TopicPacking>) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+
+            store_topic_packing::<T>(&vote_id, &topic_id, packing.clone())?;
+            debug::info!("packing of topic: {:?} of vote: {:?}: {:?}", topic_id, vote_id, packing);
+            Self::deposit_event(RawEvent::TopicPackingUpdated(vote_id, topic_id, packing));
+            Ok(())
+        }
+
+        /// Cast a ballot. Every cipher can only be submitted once.
+        /// The proofs of the ballot (if any) bind its ciphers to the voter.
+        /// The answers to questions with candidates must be proven to be valid answers.
+        #[weight = (T::WeightInfo::cast_ballot(ballot.answers.len() as u32).saturating_add(T::WeightInfo::check_ciphers_in_group(ballot.nr_of_ciphers() as u32)), T::FeePolicy::cast_ballot())]
+        pub fn cast_ballot(origin, vote_id: VoteId, ballot: Ballot) -> DispatchResult {
+          let who = ensure_signed(origin)?;
+          Self::cast_ballot_of(who, vote_id, ballot)
+        }
+
+        /// Cast a ballot on behalf of a voter, e.g. a ballot created offline and scanned from a QR code.
+        /// The voter signs the vote id and the ballot (sr25519), see: mixnet_primitives::relay.
+        /// The ballot is cast by the voter, the relayer can neither change it nor cast it for another voter.
+        #[weight = (T::WeightInfo::cast_relayed_ballot(ballot.answers.len() as u32).saturating_add(T::WeightInfo::check_ciphers_in_group(ballot.nr_of_ciphers() as u32)), T::FeePolicy::cast_relayed_ballot())]
+        pub fn cast_relayed_ballot(origin, vote_id: VoteId, ballot: Ballot, voter: sr25519::Public, signature: sr25519::Signature) -> DispatchResult {
+          let relayer = ensure_signed(origin)?;
+          let who: T::AccountId = verify_relayed_ballot::<T>(&vote_id, &ballot, &voter, &signature)?;
+          Self::cast_ballot_of(who.clone(), vote_id.clone(), ballot)?;
+          Self::deposit_event(RawEvent::BallotRelayed(relayer, who, vote_id));
+          Ok(())
+        }
+
+        /// Cast an anonymous ballot as an unsigned transaction, i.e., the ballot isn't linked to an account.
+        /// The token is a one-time public key (sr25519), its nullifier is the hash of the key. The ballot must be signed
+        /// with the one-time key and the signature of the eligibility token of the nullifier must verify with the eligibility
+        /// key of the vote (see: mixnet_primitives::tokens), every nullifier can only be spent once. The proofs of the ballot are bound to the nullifier.
+        #[weight = (T::WeightInfo::cast_anonymous_ballot(ballot.answers.len() as u32).saturating_add(T::WeightInfo::check_ciphers_in_group(ballot.nr_of_ciphers() as u32)), Pays::No)]
+        pub fn cast_anonymous_ballot(origin, vote_id: VoteId, ballot: Ballot, key: sr25519::Public, signature: Vec<u8>, ballot_signature: sr25519::Signature) -> DispatchResult {
+            ensure_none(origin)?;
+            Self::cast_anonymous_ballot_of(vote_id, ballot, key, signature, ballot_signature)
+        }
+
+        /// Import a batch of ciphers collected off-chain (e.g. postal ballots) for a topic of a vote.
+        /// The ciphers are appended to the submitted ciphers of the topic, every cipher can only be submitted once.
+        /// The import records the source tag, the position and the merkle root of the batch (see: CipherImports).
+        /// Can only be called from a voting authority in the voting phase, not for votes with linked shuffles.
+        #[weight = (T::WeightInfo::import_ciphers(ciphers.len() as u32), T::FeePolicy::import_ciphers())]
+        fn import_ciphers(origin, vote_id: VoteId, topic_id: TopicId, ciphers: Vec<Cipher>, source_tag: Vec<u8>) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_phase::<T>(&vote_id, VotePhase::Voting)?;
+            ensure_no_key_switch_pending::<T>(&vote_id)?;
+
+            let (index, import) = store_imported_ciphers::<T>(&who, &vote_id, &topic_id, ciphers, source_tag)?;
+            debug::info!("imported {:?} ciphers for vote_id: {:?}, topic_id: {:?}", import.nr_of_ciphers, vote_id, topic_id);
+            Self::deposit_event(RawEvent::CiphersImported(
+                vote_id,
+                topic_id,
+                index,
+                import.source_tag,
+                import.nr_of_ciphers,
+                import.merkle_root,
+            ));
+            Ok(())
+        }
+
+        /// Spoil the cipher of the voter's last ballot for a topic (audit ballot).
+        /// The cipher is excluded from the tally, the voter may cast a new ballot.
+        #[weight = (T::WeightInfo::spoil_ballot(), T::FeePolicy::spoil_ballot())]
+        pub fn spoil_ballot(origin, vote_id: VoteId, topic_id: TopicId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_phase::<T>(&vote_id, VotePhase::Voting)?;
+            ensure_no_key_switch_pending::<T>(&vote_id)?;
+            ensure_spoilable_answer::<T>(&vote_id)?;
+
+            let cipher: Cipher = spoil_ballot_cipher::<T>(&who, &vote_id, &topic_id)?;
+            debug::info!("spoiled cipher of vote_id: {:?}, topic_id: {:?}", vote_id, topic_id);
+            Self::deposit_event(RawEvent::BallotSpoiled(who, vote_id, topic_id, cipher));
+            Ok(())
+        }
+
+        /// Reveal the message and randomness of a spoiled cipher such that anyone can verify its encryption.
+        #[weight = (T::WeightInfo::reveal_spoiled_ballot(), T::FeePolicy::reveal_spoiled_ballot())]
+        pub fn reveal_spoiled_ballot(origin, vote_id: VoteId, topic_id: TopicId, cipher_hash: [u8; 32], reveal: SpoiledBallotReveal) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+
+            verify_and_store_spoiled_ballot_reveal::<T>(&who, &vote_id, &topic_id, cipher_hash, reveal)?;
+            debug::info!("revealed spoiled cipher of vote_id: {:?}, topic_id: {:?}", vote_id, topic_id);
+            Self::deposit_event(RawEvent::SpoiledBallotRevealed(who, topic_id, cipher_hash));
+            Ok(())
+        }
+
+        /// Exempt the voters of a vote from the ballot deposit, e.g. if the voters are known.
+        /// Can only be called from a voting authority, applies to the ballots cast afterwards.
+        #[weight = (T::WeightInfo::whitelist_vote(), T::FeePolicy::whitelist_vote())]
+        fn whitelist_vote(origin, vote_id: VoteId, whitelisted: bool) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+
+            if whitelisted {
+                WhitelistedVotes::insert(&vote_id, true);
+            } else {
+                WhitelistedVotes::remove(&vote_id);
+            }
+
+            debug::info!("whitelisted vote: {:?}: {:?}", vote_id, whitelisted);
+            Self::deposit_event(RawEvent::VoteWhitelisted(vote_id, whitelisted));
+            Ok(())
+        }
+
+        /// Refund the ballot deposits of the voter once the voting phase has ended
+        /// or the vote has been cancelled.
+        #[weight = (T::WeightInfo::claim_ballot_deposit(), T::FeePolicy::claim_ballot_deposit())]
+        fn claim_ballot_deposit(origin, vote_id: VoteId) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_ended::<T>(&vote_id)?;
+
+            let deposit: BalanceOf<T> = refund_ballot_deposit::<T>(&who, &vote_id)?;
+            debug::info!("refunded ballot deposit of vote: {:?}", vote_id);
+            Self::deposit_event(RawEvent::BallotDepositRefunded(who, vote_id, deposit));
+            Ok(())
+        }
+
+        /// Commit to the hash of a shuffle payload, which is revealed in a later block
+        /// via `submit_shuffled_votes_and_proof`.
+        #[weight = (T::WeightInfo::commit_shuffle(), T::FeePolicy::commit_shuffle())]
+        fn commit_shuffle(origin, vote_id: VoteId, topic_id: TopicId, commitment: [u8; 32]) -> DispatchResult {
             let who: T::AccountId = ensure_signed(origin)?;
+            ensure_vote_sealer::<T>(&who, &vote_id)?;
             ensure_vote_exists::<T>(&vote_id)?;
             ensure_vote_phase::<T>(&vote_id, VotePhase::Tallying)?;
-            ensure_sealer::<T>(&who)?;
+            ensure_shuffle_topic::<T>(&vote_id, &topic_id)?;
+            let turn = ensure_shuffle_turn::<T>(&who, &vote_id, &topic_id)?;
+
+            // the sealer has a full turn to reveal its shuffle
+            store_shuffle_commitment::<T>(&who, &vote_id, &topic_id, commitment)?;
+            start_shuffle_turn::<T>(&vote_id, &topic_id, turn.sealer_index);
+
+            // a shuffle transaction counts as a heartbeat of the sealer
+            record_heartbeat::<T>(&who);
+            debug::info!("shuffle committed for vote_id: {:?}, topic_id: {:?}", vote_id, topic_id);
+            Self::deposit_event(RawEvent::ShuffleCommitted(topic_id, who));
+            Ok(())
+        }
+
+        /// Reveal the shuffle committed to in an earlier block,
+        /// i.e., store the shuffled ciphers once the proof has been verified.
+        #[weight = (T::WeightInfo::submit_shuffled_votes_and_proof(payload.ciphers.len() as u32), T::FeePolicy::submit_shuffled_votes_and_proof())]
+        fn submit_shuffled_votes_and_proof(origin, vote_id: VoteId, topic_id: TopicId, payload: ShufflePayload) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            Self::submit_shuffle_of(who, vote_id, topic_id, payload)
+        }
 
-            // verify the decrypted share proof
-            // and store the decrypted shares if proof verification is successfull
-            verify_proof_and_store_decrypted_share::<T>(who.clone(), &vote_id, &topic_id, shares, proof.clone(), &nr_of_shuffles)?;
+        /// Reveal the shuffle committed to in an earlier block as an unsigned transaction,
+        /// i.e., the sealer doesn't need any funds to pay the transaction fee.
+        /// The submission must be signed by the sealer which committed to the shuffle (see: `validate_unsigned`).
+        #[weight = (T::WeightInfo::submit_shuffled_votes_and_proof(submission.payload.ciphers.len() as u32), Pays::No)]
+        fn submit_shuffled_votes_and_proof_unsigned(origin, submission: ShuffleSubmission<T::Public>, _signature: T::Signature) -> DispatchResult {
+            ensure_none(origin)?;
+            // the signature has been verified when the transaction has been validated
+            let who: T::AccountId = submission.public.into_account();
+            Self::submit_shuffle_of(who, submission.vote_id, submission.topic_id, submission.payload)
+        }
 
-            // notify that the decrypted share has been:
-            // submitted, the proof verified and stored
-            debug::info!("stored decrypted share for vote: {:?} and topic: {:?}, by sealer: {:?}", vote_id, topic_id, who.clone());
-            Self::deposit_event(RawEvent::DecryptedShareSubmitted(topic_id, who));
+        /// Reveal the linked shuffle committed to (for the first topic of the vote) in an earlier block,
+        /// i.e., store the shuffled ciphers of all topics once the proofs have been verified
+        /// and all topics have been shuffled with the same permutation.
+        #[weight = (T::WeightInfo::submit_linked_shuffle(payload.first().map(|(_, shuffle)| shuffle.ciphers.len() as u32).unwrap_or(0), payload.len() as u32), T::FeePolicy::submit_linked_shuffle())]
+        fn submit_linked_shuffle(origin, vote_id: VoteId, payload: LinkedShufflePayload) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_vote_sealer::<T>(&who, &vote_id)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_phase::<T>(&vote_id, VotePhase::Tallying)?;
+            ensure_shuffle_mode::<T>(&vote_id, true)?;
+            ensure_valid_ciphers::<T>(&vote_id, payload.iter().flat_map(|(_, shuffle)| shuffle.ciphers.iter()))?;
+
+            // the turns and the commitment of the lead topic apply to all topics
+            let lead_topic_id: TopicId = get_lead_topic::<T>(&vote_id)?;
+            let turn = ensure_shuffle_turn::<T>(&who, &vote_id, &lead_topic_id)?;
+            verify_shuffle_reveal::<T, _>(&who, &vote_id, &lead_topic_id, &payload)?;
+            let topic_ids: Vec<TopicId> = payload.iter().map(|(topic_id, _)| topic_id.clone()).collect();
+            Self::verify_linked_proofs_store_shuffled_ciphers(&who, &vote_id, payload)?;
+            remove_shuffle_commitment::<T>(&vote_id, &lead_topic_id);
+
+            // the next batch is shuffled by the next sealer
+            start_shuffle_turn::<T>(&vote_id, &lead_topic_id, turn.sealer_index + 1);
+            record_heartbeat::<T>(&who);
+
+            debug::info!("verified linked shuffle proofs for vote_id: {:?}", vote_id);
+            for topic_id in topic_ids.into_iter() {
+                Self::deposit_event(RawEvent::ShuffleProofSubmitted(topic_id.clone(), who.clone()));
+
+                // notify the sealers that the ciphers are ready to be decrypted
+                if let Some(state) = ShuffleStateStore::get((&vote_id, &topic_id)) {
+                    if state.done {
+                        start_decryption::<T>(&vote_id, &topic_id);
+                        Self::deposit_event(RawEvent::ShuffleCompleted(vote_id.clone(), topic_id, state.iteration));
+                    }
+                }
+            }
             Ok(())
         }
 
+        /// Store a decrypted shares.
+        #[weight = (T::WeightInfo::submit_decrypted_shares(shares.len() as u32), T::FeePolicy::submit_decrypted_shares())]
+        fn submit_decrypted_shares(origin, vote_id: VoteId, topic_id: TopicId, shares: Vec<DecryptedShare>, proof: DecryptedShareProof, nr_of_shuffles: NrOfShuffles) -> DispatchResult {
+            // only sealers should be able to store their decrypted shares
+            let who: T::AccountId = ensure_signed(origin)?;
+            Self::submit_decrypted_shares_of(who, vote_id, topic_id, shares, proof, nr_of_shuffles)
+        }
+
+        /// Store the decrypted shares of a sealer submitted as an unsigned transaction,
+        /// i.e., the sealer doesn't need any funds to pay the transaction fee.
+        /// The submission must be signed by the sealer (see: `validate_unsigned`).
+        #[weight = (T::WeightInfo::submit_decrypted_shares(submission.shares.len() as u32), Pays::No)]
+        fn submit_decrypted_shares_unsigned(origin, submission: DecryptedSharesSubmission<T::Public>, _signature: T::Signature) -> DispatchResult {
+            ensure_none(origin)?;
+            // the signature has been verified when the transaction has been validated
+            let who: T::AccountId = submission.public.into_account();
+            Self::submit_decrypted_shares_of(who, submission.vote_id, submission.topic_id, submission.shares, submission.proof, submission.nr_of_shuffles)
+        }
+
         /// Combine decrypted shares into a final plain text tally.
-        #[weight = (10_000, Pays::No)]
+        #[weight = (T::WeightInfo::combine_decrypted_shares(), T::FeePolicy::combine_decrypted_shares())]
         fn combine_decrypted_shares(origin, vote_id: VoteId, topic_id: TopicId, encoded: bool, nr_of_shuffles: NrOfShuffles) -> DispatchResult {
             // only the voting_authority should be able to create the final tally
             let who: T::AccountId = ensure_signed(origin)?;
@@ -472,6 +1892,10 @@ decl_module! {
             // combine the decrypted shares
             // tally the topic
             let result: TopicResult = combine_shares_and_tally_topic::<T>(&vote_id, &topic_id, encoded, &nr_of_shuffles)?;
+            stop_decryption::<T>(&vote_id, &topic_id);
+            if is_vote_tallied::<T>(&vote_id) {
+                stop_liveness_monitoring::<T>(&vote_id);
+            }
 
             // notify that the decrypted shares have been combined
             // and that the result has been tallied!
@@ -480,12 +1904,214 @@ decl_module! {
             Ok(())
         }
 
+        /// Combine the decrypted shares of the next batch of ciphers of a topic (the shuffle batch size)
+        /// and add the decoded votes to the partial tally of the topic, see: `finalize_tally`.
+        /// All batches of a topic must be combined with the same parameters.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::combine_decrypted_shares_batch(T::MaxShuffleBatchSize::get() as u32), T::FeePolicy::combine_decrypted_shares_batch())]
+        fn combine_decrypted_shares_batch(origin, vote_id: VoteId, topic_id: TopicId, encoded: bool, nr_of_shuffles: NrOfShuffles) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_phase::<T>(&vote_id, VotePhase::Tallying)?;
+            ensure_voting_authority::<T>(&who)?;
+
+            let progress: TallyProgress = combine_shares_of_next_batch::<T>(&vote_id, &topic_id, encoded, &nr_of_shuffles)?;
+
+            debug::info!("combined {:?} of {:?} ciphers of vote: {:?} and topic: {:?}", progress.position, progress.total, vote_id, topic_id);
+            Self::deposit_event(RawEvent::TallyBatchCombined(vote_id, topic_id, progress.position, progress.total));
+            Ok(())
+        }
+
+        /// Count the votes of a topic once the decrypted shares of all its batches have been combined
+        /// (see: `combine_decrypted_shares_batch`) and store the final tally.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::finalize_tally(), T::FeePolicy::finalize_tally())]
+        fn finalize_tally(origin, vote_id: VoteId, topic_id: TopicId) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_phase::<T>(&vote_id, VotePhase::Tallying)?;
+            ensure_voting_authority::<T>(&who)?;
+
+            let result: TopicResult = finalize_topic_tally::<T>(&vote_id, &topic_id)?;
+            stop_decryption::<T>(&vote_id, &topic_id);
+            if is_vote_tallied::<T>(&vote_id) {
+                stop_liveness_monitoring::<T>(&vote_id);
+            }
+
+            debug::info!("result for vote: {:?} and topic: {:?} is: {:?}", vote_id, topic_id, result);
+            Self::deposit_event(RawEvent::TopicTallied(vote_id, topic_id, result));
+            Ok(())
+        }
+
+        /// Homomorphically add all ciphers of a topic into a single aggregate cipher.
+        /// Only for votes using the homomorphic tally strategy.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::aggregate_ciphers(), T::FeePolicy::aggregate_ciphers())]
+        fn aggregate_ciphers(origin, vote_id: VoteId, topic_id: TopicId) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_phase::<T>(&vote_id, VotePhase::Tallying)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_no_credential_check_pending::<T>(&vote_id)?;
+
+            // add all ciphers of the topic and store the aggregate cipher
+            aggregate_topic_ciphers::<T>(&vote_id, &topic_id)?;
+            start_decryption::<T>(&vote_id, &topic_id);
+
+            debug::info!("aggregated ciphers for vote: {:?} and topic: {:?}", vote_id, topic_id);
+            Self::deposit_event(RawEvent::CiphersAggregated(vote_id, topic_id));
+            Ok(())
+        }
+
+        /// Rotate the public key of a vote and start a new key generation round.
+        /// The stored ciphers need to be switched to the new key by the sealers.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::rotate_public_key(), T::FeePolicy::rotate_public_key())]
+        fn rotate_public_key(origin, vote_id: VoteId) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+
+            // keep the old key shares and reset the public key
+            rotate_key::<T>(&who, &vote_id)?;
+
+            debug::info!("rotated public key of vote: {:?}", vote_id);
+            Self::deposit_event(RawEvent::PublicKeyRotated(vote_id));
+            Ok(())
+        }
+
+        /// Store the key switch shares of the ciphers of a topic and their proof.
+        /// Switches the ciphers to the new key once all sealers have submitted their shares.
+        /// Can only be called from a sealer.
+        #[weight = (T::WeightInfo::submit_key_switch_shares(shares.len() as u32), T::FeePolicy::submit_key_switch_shares())]
+        fn submit_key_switch_shares(origin, vote_id: VoteId, topic_id: TopicId, shares: Vec<Cipher>, proof: KeySwitchShareProof) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_vote_sealer::<T>(&who, &vote_id)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_phase::<T>(&vote_id, VotePhase::Voting)?;
+            ensure_valid_ciphers::<T>(&vote_id, shares.iter())?;
+
+            // verify the key switch proof and store the shares
+            verify_proof_and_store_key_switch_shares::<T>(who.clone(), &vote_id, &topic_id, shares, proof)?;
+            Self::deposit_event(RawEvent::KeySwitchSharesSubmitted(topic_id.clone(), who));
+
+            // switch the ciphers if all sealers have submitted their shares
+            if combine_key_switch_shares::<T>(&vote_id, &topic_id)? {
+                debug::info!("switched ciphers of vote: {:?} and topic: {:?}", vote_id, topic_id);
+                Self::deposit_event(RawEvent::CiphersKeySwitched(vote_id, topic_id));
+            }
+            Ok(())
+        }
+
+        /// Certify the result of a tallied vote, i.e., approve the hash of the results of all its topics (see: VoteResult).
+        /// The result is certified once a voting authority and `CertificationQuorum` sealers approved it.
+        /// Can only be called from a voting authority or a sealer.
+        #[weight = (T::WeightInfo::certify_result(), T::FeePolicy::certify_result())]
+        fn certify_result(origin, vote_id: VoteId, result_hash: [u8; 32]) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_phase::<T>(&vote_id, VotePhase::Tallying)?;
+
+            // record the approval and certify the result once the quorum is met
+            let certified = approve_result::<T>(&who, &vote_id, result_hash)?;
+            Self::deposit_event(RawEvent::ResultApproved(vote_id.clone(), who, result_hash));
+            if let Some(certified) = certified {
+                debug::info!("certified result of vote: {:?}, approvals: {:?}", vote_id, certified.approvals);
+                Self::deposit_event(RawEvent::ResultCertified(vote_id.clone(), certified.result_hash));
+
+                // the digest allows other chains to consume the certified result
+                let digest = store_result_digest::<T>(&vote_id, &certified)?;
+                Self::deposit_event(RawEvent::ResultDigestCreated(vote_id, digest.digest));
+            }
+            Ok(())
+        }
+
+        /// Add a sealer to the sealers of new votes, starts a new sealer epoch (see: SealerSets).
+        /// The votes created before keep their sealers.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::add_sealer(), T::FeePolicy::add_sealer())]
+        fn add_sealer(origin, sealer: T::AccountId) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+
+            let epoch = add_sealer::<T>(&sealer)?;
+            debug::info!("added sealer: {:?}, epoch: {:?}", sealer, epoch);
+            Self::deposit_event(RawEvent::SealerAdded(sealer, epoch));
+            Ok(())
+        }
+
+        /// Remove a sealer from the sealers of new votes, starts a new sealer epoch (see: SealerSets).
+        /// The votes created before keep their sealers, i.e., the sealer still has to tally them.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::remove_sealer(), T::FeePolicy::remove_sealer())]
+        fn remove_sealer(origin, sealer: T::AccountId) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+
+            let epoch = remove_sealer::<T>(&sealer)?;
+            debug::info!("removed sealer: {:?}, epoch: {:?}", sealer, epoch);
+            Self::deposit_event(RawEvent::SealerRemoved(sealer, epoch));
+            Ok(())
+        }
+
+        /// Archive a tallied or cancelled vote. Replaces the ciphers and proofs of the vote by their
+        /// merkle root and removes them from storage. The transaction fee is refunded on success.
+        /// Can only be called from a voting authority.
+        #[weight = (T::WeightInfo::archive_vote(), T::FeePolicy::archive_vote())]
+        fn archive_vote(origin, vote_id: VoteId) -> DispatchResultWithPostInfo {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_voting_authority::<T>(&who)?;
+            ensure_vote_exists::<T>(&vote_id)?;
+            ensure_vote_ended::<T>(&vote_id)?;
+
+            // commit to the ciphers and proofs and prune them
+            let archive: VoteArchive = archive_ciphers_and_proofs::<T>(&vote_id)?;
+
+            debug::info!("archived vote: {:?}, merkle root: {:?}", vote_id, archive.merkle_root);
+            Self::deposit_event(RawEvent::VoteArchived(vote_id, archive.merkle_root));
+            Ok(PostDispatchInfo { actual_weight: None, pays_fee: Pays::No })
+        }
+
+        /// Signal that the sealer is alive, sent by the offchain workers of the sealers
+        /// every `HeartbeatInterval` blocks while a vote is being tallied.
+        /// Can only be called from a sealer.
+        #[weight = (T::WeightInfo::heartbeat(), T::FeePolicy::heartbeat())]
+        fn heartbeat(origin) -> DispatchResult {
+            let who: T::AccountId = ensure_signed(origin)?;
+            ensure_sealer::<T>(&who)?;
+
+            record_heartbeat::<T>(&who);
+            debug::info!("heartbeat of sealer: {:?}", who);
+            Ok(())
+        }
+
+        /// Store the heartbeat of a sealer submitted as an unsigned transaction,
+        /// i.e., the sealer doesn't need any funds to pay the transaction fee.
+        /// The heartbeat must be signed by the sealer and is rate limited to one per
+        /// `HeartbeatInterval` (see: `validate_unsigned`).
+        #[weight = (T::WeightInfo::heartbeat(), Pays::No)]
+        fn heartbeat_unsigned(origin, heartbeat: HeartbeatPayload<T::Public, T::BlockNumber>, _signature: T::Signature) -> DispatchResult {
+            ensure_none(origin)?;
+            // the signature has been verified when the transaction has been validated
+            let who: T::AccountId = heartbeat.public.into_account();
+            ensure_sealer::<T>(&who)?;
+
+            record_heartbeat::<T>(&who);
+            debug::info!("heartbeat of sealer: {:?}", who);
+            Ok(())
+        }
+
         /// Empty function that does nothing but needs to be called by an offchain worker
         /// when it's not the offchain worker's turn to shuffle the votes.
-        #[weight = (10_000, Pays::No)]
+        #[weight = (T::WeightInfo::do_nothing_when_its_not_your_turn(), T::FeePolicy::do_nothing_when_its_not_your_turn())]
         fn do_nothing_when_its_not_your_turn(origin) -> DispatchResult {
             let who: T::AccountId = ensure_signed(origin)?;
             debug::info!("offchain fn call when not shuffling, who: {:?}", who);
+
+            // the offchain worker doesn't send a heartbeat in the same block
+            if is_sealer::<T>(&who) {
+                record_heartbeat::<T>(&who);
+            }
             Ok(())
         }
 
@@ -498,11 +2124,304 @@ decl_module! {
                 Err(err) => debug::error!("error while shuffling in offchain worker: {:?}", err),
              }
 
+            let offchain_heartbeat_result = Self::offchain_heartbeat(block_number);
+            match offchain_heartbeat_result {
+                Ok(_) => (),
+                Err(err) => debug::error!("error while sending the heartbeat in offchain worker: {:?}", err),
+            }
+
+            let offchain_results_result = Self::offchain_post_results();
+            match offchain_results_result {
+                Ok(_) => (),
+                Err(err) => debug::error!("error while posting the results in offchain worker: {:?}", err),
+            }
+
+            let offchain_checkpoint_result = Self::offchain_checkpoint(block_number);
+            match offchain_checkpoint_result {
+                Ok(_) => (),
+                Err(err) => debug::error!("error while taking a checkpoint in offchain worker: {:?}", err),
+            }
+
             debug::info!("off-chain worker: done...");
         }
     }
 }
 
+impl<T: Trait> Module<T> {
+    /// casts the ballot of the voter, see: cast_ballot
+    fn cast_ballot_of(
+        who: T::AccountId,
+        vote_id: VoteId,
+        ballot: Ballot,
+    ) -> DispatchResult {
+        let rate: BallotRate<T::BlockNumber> =
+            Self::ensure_valid_ballot(&who, &vote_id, &ballot)?;
+
+        // reserve the ballot deposit, refunded once the voting phase has ended
+        reserve_ballot_deposit::<T>(&who, &vote_id)?;
+        store_ballot_rate::<T>(&who, rate);
+
+        // store the ballot and its receipt, the credential of the ballot is checked during the tally
+        store_credential_ballot::<T>(&vote_id, &who, &ballot);
+        store_ballot::<T>(&who, &vote_id, ballot.clone());
+        store_ballot_receipt::<T>(&who, &vote_id, &ballot);
+
+        // notify that the ballot has been submitted and stored
+        debug::info!("stored ballot for vote_id: {:?}", vote_id);
+        Self::deposit_event(RawEvent::BallotSubmitted(who, vote_id, ballot));
+        Ok(())
+    }
+
+    /// runs all checks of the ballot of the voter without storing anything,
+    /// returns the ballot rate of the voter including the ballot, see: cast_ballot_of
+    fn ensure_valid_ballot(
+        who: &T::AccountId,
+        vote_id: &VoteId,
+        ballot: &Ballot,
+    ) -> Result<BallotRate<T::BlockNumber>, Error<T>> {
+        ensure_vote_exists::<T>(vote_id)?;
+        ensure_vote_phase::<T>(vote_id, VotePhase::Voting)?;
+        ensure_no_key_switch_pending::<T>(vote_id)?;
+
+        // the ballots of a vote with an eligibility key are cast anonymously
+        ensure_not_anonymous_vote::<T>(vote_id)?;
+
+        // the voters of a vote with an identity provider must be registered
+        ensure_registered_voter::<T>(vote_id, who)?;
+        ensure_not_revoked::<T>(vote_id, who)?;
+
+        // limit the number of ballots per account (spam protection)
+        let rate: BallotRate<T::BlockNumber> = ensure_ballot_rate_limit::<T>(who)?;
+
+        // the ballot of a vote with linked shuffles answers all topics
+        ensure_linked_ballot::<T>(vote_id, ballot)?;
+
+        // reject oversized ciphers, replayed ciphers and ciphers which are bound to another voter
+        ensure_valid_ciphers::<T>(
+            vote_id,
+            ballot
+                .answers
+                .iter()
+                .map(|(_, cipher)| cipher)
+                .chain(ballot.credential.iter()),
+        )?;
+        ensure_group_check::<T>(
+            vote_id,
+            ballot.answers.iter().map(|(_, cipher)| cipher),
+        )?;
+        ensure_unique_ciphers::<T>(vote_id, ballot)?;
+
+        // the ballot carries the encrypted credential of a voter with a registered credential
+        ensure_ballot_credential::<T>(vote_id, who, ballot)?;
+        verify_ballot_proofs::<T>(who, vote_id, ballot)?;
+
+        // answers to questions with candidates must be valid answers
+        verify_ballot_validity_proofs::<T>(who, vote_id, ballot)?;
+        Ok(rate)
+    }
+
+    /// pre-flights the ballot of the voter (runtime api), i.e., runs all checks of cast_ballot
+    /// without storing anything (including the group check of the vote, see: GroupChecks).
+    /// additionally, the voter must be able to reserve the ballot deposit. returns the stable
+    /// code of the error the ballot would be rejected with, see: ErrorCode
+    pub fn validate_ballot(
+        who: &T::AccountId,
+        vote_id: &VoteId,
+        ballot: &Ballot,
+    ) -> Result<(), u16> {
+        Self::ensure_valid_ballot(who, vote_id, ballot)
+            .and_then(|_| ensure_ballot_deposit::<T>(who, vote_id))
+            .map_err(|error| ErrorCode::from(error).code())
+    }
+
+    /// casts the anonymous ballot signed with the one-time key of the token, see: cast_anonymous_ballot
+    fn cast_anonymous_ballot_of(
+        vote_id: VoteId,
+        ballot: Ballot,
+        key: sr25519::Public,
+        signature: Vec<u8>,
+        ballot_signature: sr25519::Signature,
+    ) -> DispatchResult {
+        ensure_vote_exists::<T>(&vote_id)?;
+        ensure_vote_phase::<T>(&vote_id, VotePhase::Voting)?;
+        ensure_no_key_switch_pending::<T>(&vote_id)?;
+
+        // the voting authority checked the eligibility of the voter when it signed the token,
+        // the ballot is bound to the token by the signature of the one-time key
+        let nullifier: Nullifier = verify_anonymous_ballot::<T>(
+            &vote_id,
+            &ballot,
+            &key,
+            &signature,
+            &ballot_signature,
+        )?;
+
+        // the ballot of a vote with linked shuffles answers all topics
+        ensure_linked_ballot::<T>(&vote_id, &ballot)?;
+
+        // the credentials are registered for accounts, an anonymous ballot can't carry one
+        ensure!(
+            ballot.credential.is_none(),
+            Error::<T>::CredentialNotRegistered
+        );
+
+        // reject oversized and replayed ciphers, the proofs are bound to the nullifier instead of an account
+        ensure_valid_ciphers::<T>(
+            &vote_id,
+            ballot.answers.iter().map(|(_, cipher)| cipher),
+        )?;
+        ensure_group_check::<T>(
+            &vote_id,
+            ballot.answers.iter().map(|(_, cipher)| cipher),
+        )?;
+        ensure_unique_ciphers::<T>(&vote_id, &ballot)?;
+        verify_ballot_proofs_in_context::<T>(&nullifier, &vote_id, &ballot)?;
+        verify_ballot_validity_proofs_in_context::<T>(
+            &nullifier, None, &vote_id, &ballot,
+        )?;
+
+        // the token can't be used for another ballot
+        spend_nullifier::<T>(&vote_id, &nullifier);
+        store_anonymous_ballot::<T>(&vote_id, ballot.clone());
+
+        debug::info!("stored anonymous ballot for vote_id: {:?}", vote_id);
+        Self::deposit_event(RawEvent::AnonymousBallotSubmitted(
+            vote_id, nullifier, ballot,
+        ));
+        Ok(())
+    }
+
+    /// reveals the shuffle of the sealer, see: submit_shuffled_votes_and_proof
+    fn submit_shuffle_of(
+        who: T::AccountId,
+        vote_id: VoteId,
+        topic_id: TopicId,
+        payload: ShufflePayload,
+    ) -> DispatchResult {
+        ensure_vote_sealer::<T>(&who, &vote_id)?;
+        ensure_vote_exists::<T>(&vote_id)?;
+
+        // TODO: discuss if shuffling should be allowed earlier
+        ensure_vote_phase::<T>(&vote_id, VotePhase::Tallying)?;
+        ensure_shuffle_mode::<T>(&vote_id, false)?;
+        ensure_valid_ciphers::<T>(&vote_id, payload.ciphers.iter())?;
+
+        let turn = ensure_shuffle_turn::<T>(&who, &vote_id, &topic_id)?;
+        verify_shuffle_reveal::<T, _>(&who, &vote_id, &topic_id, &payload)?;
+        Self::verify_proof_store_shuffled_ciphers(&who, &vote_id, &topic_id, payload)?;
+        remove_shuffle_commitment::<T>(&vote_id, &topic_id);
+
+        // the next batch is shuffled by the next sealer
+        start_shuffle_turn::<T>(&vote_id, &topic_id, turn.sealer_index + 1);
+        record_heartbeat::<T>(&who);
+
+        // notify that the decrypted share has been:
+        // submitted, the proof verified and stored
+        debug::info!(
+            "verified shuffle proof for vote_id: {:?}, topic_id: {:?}",
+            vote_id,
+            topic_id
+        );
+        Self::deposit_event(RawEvent::ShuffleProofSubmitted(topic_id.clone(), who));
+
+        // notify the sealers that the ciphers are ready to be decrypted
+        if let Some(state) = ShuffleStateStore::get((&vote_id, &topic_id)) {
+            if state.done {
+                start_decryption::<T>(&vote_id, &topic_id);
+                Self::deposit_event(RawEvent::ShuffleCompleted(
+                    vote_id,
+                    topic_id,
+                    state.iteration,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// stores the decrypted shares of the sealer, see: submit_decrypted_shares
+    fn submit_decrypted_shares_of(
+        who: T::AccountId,
+        vote_id: VoteId,
+        topic_id: TopicId,
+        shares: Vec<DecryptedShare>,
+        proof: DecryptedShareProof,
+        nr_of_shuffles: NrOfShuffles,
+    ) -> DispatchResult {
+        ensure_vote_exists::<T>(&vote_id)?;
+        ensure_vote_phase::<T>(&vote_id, VotePhase::Tallying)?;
+        ensure_vote_sealer::<T>(&who, &vote_id)?;
+
+        // verify the decrypted share proof
+        // and store the decrypted shares if proof verification is successfull
+        verify_proof_and_store_decrypted_share::<T>(
+            who.clone(),
+            &vote_id,
+            &topic_id,
+            shares,
+            proof.clone(),
+            &nr_of_shuffles,
+        )?;
+
+        // notify that the decrypted share has been:
+        // submitted, the proof verified and stored
+        debug::info!(
+            "stored decrypted share for vote: {:?} and topic: {:?}, by sealer: {:?}",
+            vote_id,
+            topic_id,
+            who.clone()
+        );
+        Self::deposit_event(RawEvent::DecryptedShareSubmitted(topic_id.clone(), who));
+
+        // notify about the overall decryption progress of the topic
+        let submitted = get_decrypted_share_count::<T>(&vote_id, &topic_id);
+        let required = get_required_share_count::<T>(&vote_id);
+        if submitted >= required {
+            stop_decryption::<T>(&vote_id, &topic_id);
+        }
+        Self::deposit_event(RawEvent::DecryptionProgress(
+            vote_id, topic_id, submitted, required,
+        ));
+        Ok(())
+    }
+}
+
+impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
+    type Call = Call<T>;
+
+    /// only the unsigned shuffles, decrypted shares and heartbeats signed by a sealer
+    /// and the anonymous ballots with an unspent eligibility token are valid, see: helpers::unsigned
+    fn validate_unsigned(
+        _source: TransactionSource,
+        call: &Self::Call,
+    ) -> TransactionValidity {
+        match call {
+            Call::submit_shuffled_votes_and_proof_unsigned(submission, signature) => {
+                validate_shuffle_submission::<T>(submission, signature)
+            }
+            Call::submit_decrypted_shares_unsigned(submission, signature) => {
+                validate_decrypted_shares_submission::<T>(submission, signature)
+            }
+            Call::heartbeat_unsigned(heartbeat, signature) => {
+                validate_heartbeat::<T>(heartbeat, signature)
+            }
+            Call::cast_anonymous_ballot(
+                vote_id,
+                ballot,
+                key,
+                signature,
+                ballot_signature,
+            ) => validate_anonymous_ballot::<T>(
+                vote_id,
+                ballot,
+                key,
+                signature,
+                ballot_signature,
+            ),
+            _ => InvalidTransaction::Call.into(),
+        }
+    }
+}
+
 impl<T: Trait> sp_runtime::offchain::storage_lock::BlockNumberProvider for Module<T> {
     type BlockNumber = T::BlockNumber;
     fn current_block_number() -> Self::BlockNumber {
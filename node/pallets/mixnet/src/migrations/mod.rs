@@ -0,0 +1,98 @@
+use crate::{StorageVersion, Trait};
+use codec::{Decode, Encode};
+use frame_support::{
+    debug,
+    storage::{
+        migration::{put_storage_value, StorageIterator},
+        StorageValue,
+    },
+    traits::Get,
+    weights::Weight,
+};
+use sp_std::vec::Vec;
+
+pub mod v1;
+pub mod v2;
+pub mod v3;
+pub mod v4;
+pub mod v5;
+pub mod v6;
+pub mod v7;
+pub mod v8;
+
+/// the version of the storage layout, increase it for every change which requires a migration
+/// and add the migration to `migrate`. chains created from a genesis config start at this version.
+pub const STORAGE_VERSION: u32 = 8;
+
+/// the storage prefix of the pallet, see: decl_storage
+const MODULE: &[u8] = b"OffchainModule";
+
+/// runs all migrations between the stored version and STORAGE_VERSION (in order).
+/// chains which have never been migrated are at version 0, i.e., the initial release.
+pub fn migrate<T: Trait>() -> Weight {
+    let version: u32 = StorageVersion::get();
+    let mut weight: Weight = T::DbWeight::get().reads(1);
+    if version >= STORAGE_VERSION {
+        return weight;
+    }
+
+    if version < 1 {
+        debug::info!("migrating the mixnet storage to version 1");
+        weight = weight.saturating_add(v1::migrate::<T>());
+    }
+
+    if version < 2 {
+        debug::info!("migrating the mixnet storage to version 2");
+        weight = weight.saturating_add(v2::migrate::<T>());
+    }
+
+    if version < 3 {
+        debug::info!("migrating the mixnet storage to version 3");
+        weight = weight.saturating_add(v3::migrate::<T>());
+    }
+
+    if version < 4 {
+        debug::info!("migrating the mixnet storage to version 4");
+        weight = weight.saturating_add(v4::migrate::<T>());
+    }
+
+    if version < 5 {
+        debug::info!("migrating the mixnet storage to version 5");
+        weight = weight.saturating_add(v5::migrate::<T>());
+    }
+
+    if version < 6 {
+        debug::info!("migrating the mixnet storage to version 6");
+        weight = weight.saturating_add(v6::migrate::<T>());
+    }
+
+    if version < 7 {
+        debug::info!("migrating the mixnet storage to version 7");
+        weight = weight.saturating_add(v7::migrate::<T>());
+    }
+
+    if version < 8 {
+        debug::info!("migrating the mixnet storage to version 8");
+        weight = weight.saturating_add(v8::migrate::<T>());
+    }
+
+    StorageVersion::put(STORAGE_VERSION);
+    weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// re-encodes all values of a storage item (map or double_map) from the old to the new layout.
+/// the keys are kept, f receives the key (without the prefix of the storage item) and the old value.
+/// returns the number of migrated values.
+fn translate_values<O: Decode, V: Encode, F: FnMut(&[u8], O) -> V>(
+    item: &[u8],
+    mut f: F,
+) -> Weight {
+    // collect the values first, the iterator must not see the written values
+    let values: Vec<(Vec<u8>, O)> = StorageIterator::<O>::new(MODULE, item).collect();
+    let count = values.len() as Weight;
+    for (key, old) in values.into_iter() {
+        let new = f(&key, old);
+        put_storage_value(MODULE, item, &key, new);
+    }
+    count
+}
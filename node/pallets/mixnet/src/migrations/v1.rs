@@ -0,0 +1,123 @@
+use super::{
+    translate_values,
+    v2::{BallotV1, TopicV1},
+    MODULE,
+};
+use crate::helpers::ciphers::append_ciphers;
+use crate::types::{
+    Cipher, NrOfShuffles, PublicParameters, TallyStrategy, Title, TopicId, Vote, VoteId,
+    VotePhase,
+};
+use crate::{Trait, VotingStarted};
+use codec::{Decode, Encode};
+use frame_support::{
+    storage::{migration::StorageIterator, StorageMap},
+    traits::Get,
+    weights::Weight,
+};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// the number of shuffles of all votes created before it was configurable per vote
+const NR_OF_SHUFFLES: NrOfShuffles = 3;
+
+/// the length of the blake2_128 hash which prefixes every blake2_128_concat key
+const HASH_LENGTH: usize = 16;
+
+/// the vote before the number of shuffles and the tally strategy were configurable
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct VoteV0<AccountId> {
+    pub voting_authority: AccountId,
+    pub title: Title,
+    pub phase: VotePhase,
+    pub params: PublicParameters,
+}
+
+/// the ballot before it could contain the proofs of its ciphers
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct BallotV0 {
+    pub answers: Vec<(TopicId, Cipher)>,
+}
+
+/// decodes the key of a map with a blake2_128_concat hasher
+fn decode_map_key<K: Decode>(key: &[u8]) -> Option<K> {
+    K::decode(&mut key.get(HASH_LENGTH..)?).ok()
+}
+
+/// decodes the keys of a double_map with two blake2_128_concat hashers
+fn decode_double_map_key<K1: Decode, K2: Decode>(key: &[u8]) -> Option<(K1, K2)> {
+    let input = &mut key.get(HASH_LENGTH..)?;
+    let key1 = K1::decode(input).ok()?;
+    let input = &mut input.get(HASH_LENGTH..)?;
+    let key2 = K2::decode(input).ok()?;
+    Some((key1, key2))
+}
+
+/// migrates the storage of the initial release:
+/// 1. the votes get the default number of shuffles and the mixnet tally strategy
+/// 2. the ballots get an empty list of proofs
+/// 3. the ciphers are moved into the chunked cipher storage of their vote (incl. merkle roots)
+/// 4. votes which already left the key generation are marked as started
+pub fn migrate<T: Trait>() -> Weight {
+    let db = T::DbWeight::get();
+    let mut weight: Weight = 0;
+
+    let mut started: Vec<VoteId> = Vec::new();
+    let nr_of_votes = translate_values(b"Votes", |key, old: VoteV0<T::AccountId>| {
+        if old.phase != VotePhase::KeyGeneration {
+            if let Some(vote_id) = decode_map_key::<VoteId>(key) {
+                started.push(vote_id);
+            }
+        }
+        Vote {
+            voting_authority: old.voting_authority,
+            title: old.title,
+            phase: old.phase,
+            params: old.params,
+            nr_of_shuffles: NR_OF_SHUFFLES,
+            tally_strategy: TallyStrategy::Mixnet,
+        }
+    });
+    weight = weight.saturating_add(db.reads_writes(nr_of_votes, nr_of_votes));
+
+    for vote_id in started.iter() {
+        VotingStarted::insert(vote_id, true);
+    }
+    weight = weight.saturating_add(db.writes(started.len() as Weight));
+
+    let nr_of_ballots = translate_values(b"Ballots", |_, old: BallotV0| BallotV1 {
+        answers: old.answers,
+        proofs: Vec::new(),
+    });
+    weight = weight.saturating_add(db.reads_writes(nr_of_ballots, nr_of_ballots));
+
+    // the ciphers are stored with the vote of their topic
+    let mut topic_votes: BTreeMap<TopicId, VoteId> = BTreeMap::new();
+    for (key, topics) in StorageIterator::<Vec<TopicV1>>::new(MODULE, b"Topics") {
+        if let Some(vote_id) = decode_map_key::<VoteId>(&key) {
+            for (topic_id, _) in topics.into_iter() {
+                topic_votes.insert(topic_id, vote_id.clone());
+            }
+        }
+        weight = weight.saturating_add(db.reads(1));
+    }
+
+    // the old ciphers share the prefix with the chunks, drain them before the chunks are written
+    let ciphers: Vec<(Vec<u8>, Vec<Cipher>)> =
+        StorageIterator::<Vec<Cipher>>::new(MODULE, b"Ciphers")
+            .drain()
+            .collect();
+    weight = weight.saturating_add(
+        db.reads_writes(ciphers.len() as Weight, ciphers.len() as Weight),
+    );
+    for (key, ciphers) in ciphers.into_iter() {
+        let decoded = decode_double_map_key::<TopicId, NrOfShuffles>(&key);
+        if let Some((topic_id, iteration)) = decoded {
+            if let Some(vote_id) = topic_votes.get(&topic_id) {
+                // chunks, chunk count, count, merkle root and peaks
+                weight = weight.saturating_add(db.reads_writes(5, 5));
+                append_ciphers::<T>(vote_id, &topic_id, iteration, ciphers);
+            }
+        }
+    }
+    weight
+}
@@ -0,0 +1,72 @@
+use super::{
+    translate_values,
+    v3::{TopicV2, VoteProposalV2},
+    v8::BallotV2,
+};
+use crate::types::{BallotProof, Cipher, QuestionType, TopicId, TopicQuestion, Vote};
+use crate::Trait;
+use codec::{Decode, Encode};
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::vec::Vec;
+
+/// the topic before questions could have candidates: (topic_id, question)
+pub type TopicV1 = (TopicId, TopicQuestion);
+
+/// the ballot before it could contain the validity proofs of its answers
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct BallotV1 {
+    pub answers: Vec<(TopicId, Cipher)>,
+    pub proofs: Vec<BallotProof>,
+}
+
+/// the vote proposal before questions could have candidates
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct VoteProposalV1<AccountId> {
+    pub vote: Vote<AccountId>,
+    pub topics: Vec<TopicV1>,
+    pub batch_size: u64,
+    pub approvals: Vec<AccountId>,
+}
+
+fn into_topics(topics: Vec<TopicV1>) -> Vec<TopicV2> {
+    topics
+        .into_iter()
+        .map(|(id, question)| TopicV2 {
+            id,
+            question,
+            question_type: QuestionType::SingleChoice,
+            candidates: Vec::new(),
+        })
+        .collect()
+}
+
+/// migrates the storage to the question types:
+/// 1. all existing topics become free-form single choice questions
+/// 2. the topics of the pending vote proposals as well
+/// 3. the ballots get an empty list of validity proofs
+pub fn migrate<T: Trait>() -> Weight {
+    let db = T::DbWeight::get();
+    let mut weight: Weight = 0;
+
+    let nr_of_topics =
+        translate_values(b"Topics", |_, old: Vec<TopicV1>| into_topics(old));
+    weight = weight.saturating_add(db.reads_writes(nr_of_topics, nr_of_topics));
+
+    let nr_of_proposals =
+        translate_values(b"VoteProposals", |_, old: VoteProposalV1<T::AccountId>| {
+            VoteProposalV2 {
+                vote: old.vote,
+                topics: into_topics(old.topics),
+                batch_size: old.batch_size,
+                approvals: old.approvals,
+            }
+        });
+    weight = weight.saturating_add(db.reads_writes(nr_of_proposals, nr_of_proposals));
+
+    let nr_of_ballots = translate_values(b"Ballots", |_, old: BallotV1| BallotV2 {
+        answers: old.answers,
+        proofs: old.proofs,
+        validity_proofs: Vec::new(),
+    });
+    weight.saturating_add(db.reads_writes(nr_of_ballots, nr_of_ballots))
+}
@@ -0,0 +1,63 @@
+use super::translate_values;
+use crate::types::{
+    Candidate, QuestionType, RankedTallyMethod, Topic, TopicId, TopicQuestion, Vote,
+    VoteProposal,
+};
+use crate::Trait;
+use codec::{Decode, Encode};
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::vec::Vec;
+
+/// the topic before ranked questions could be tallied by another method than first preference
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct TopicV2 {
+    pub id: TopicId,
+    pub question: TopicQuestion,
+    pub question_type: QuestionType,
+    pub candidates: Vec<Candidate>,
+}
+
+/// the vote proposal before ranked questions could be tallied by another method than first preference
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct VoteProposalV2<AccountId> {
+    pub vote: Vote<AccountId>,
+    pub topics: Vec<TopicV2>,
+    pub batch_size: u64,
+    pub approvals: Vec<AccountId>,
+}
+
+fn into_topics(topics: Vec<TopicV2>) -> Vec<Topic> {
+    topics
+        .into_iter()
+        .map(|old| Topic {
+            id: old.id,
+            question: old.question,
+            question_type: old.question_type,
+            candidates: old.candidates,
+            ranked_tally: RankedTallyMethod::FirstPreference,
+        })
+        .collect()
+}
+
+/// migrates the storage to the ranked tally methods:
+/// 1. all existing topics are tallied by first preference
+/// 2. the topics of the pending vote proposals as well
+pub fn migrate<T: Trait>() -> Weight {
+    let db = T::DbWeight::get();
+    let mut weight: Weight = 0;
+
+    let nr_of_topics =
+        translate_values(b"Topics", |_, old: Vec<TopicV2>| into_topics(old));
+    weight = weight.saturating_add(db.reads_writes(nr_of_topics, nr_of_topics));
+
+    let nr_of_proposals =
+        translate_values(b"VoteProposals", |_, old: VoteProposalV2<T::AccountId>| {
+            VoteProposal {
+                vote: old.vote,
+                topics: into_topics(old.topics),
+                batch_size: old.batch_size,
+                approvals: old.approvals,
+            }
+        });
+    weight.saturating_add(db.reads_writes(nr_of_proposals, nr_of_proposals))
+}
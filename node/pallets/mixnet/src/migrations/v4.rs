@@ -0,0 +1,73 @@
+use super::{
+    v7::{get_legacy_chunk, legacy_double_map_key},
+    MODULE,
+};
+use crate::helpers::ciphers::get_chunk_size;
+use crate::types::{ChunkIndex, NrOfShuffles, Topic, TopicId, VoteId};
+use crate::{Topics, Trait};
+use frame_support::{
+    storage::{
+        migration::{get_storage_value, put_storage_value},
+        IterableStorageMap,
+    },
+    traits::Get,
+    weights::Weight,
+};
+use sp_std::vec::Vec;
+
+/// counts the ciphers stored in the chunks of the topic and shuffle iteration
+/// (before the ciphers were keyed by the vote).
+/// only reads the last chunk, all others are full by construction.
+fn count_chunked_ciphers<T: Trait>(
+    topic_id: &TopicId,
+    iteration: NrOfShuffles,
+    nr_of_chunks: ChunkIndex,
+) -> u64 {
+    if nr_of_chunks == 0 {
+        return 0;
+    }
+    let last_chunk_index = nr_of_chunks - 1;
+    let last_chunk = get_legacy_chunk(topic_id, iteration, last_chunk_index);
+    last_chunk_index as u64 * get_chunk_size::<T>() + last_chunk.len() as u64
+}
+
+/// migrates the storage to the stored number of ciphers:
+/// the ciphers of all topics and shuffle iterations are counted once.
+/// the chunk counts and the counts are keyed by the topic only (see: v7).
+/// the ciphers moved into the chunked storage by the migration to version 1 are counted already.
+pub fn migrate<T: Trait>() -> Weight {
+    let db = T::DbWeight::get();
+    let topics: Vec<(VoteId, Vec<Topic>)> = Topics::iter().collect();
+    let mut weight: Weight = db.reads(topics.len() as Weight);
+
+    for (_, topics) in topics.iter() {
+        for topic in topics.iter() {
+            // the shuffle iterations are stored consecutively, starting with the ciphers of the voters
+            let mut iteration: NrOfShuffles = 0;
+            loop {
+                let key = legacy_double_map_key(&topic.id, &iteration);
+                let nr_of_chunks = match get_storage_value::<ChunkIndex>(
+                    MODULE,
+                    b"CipherChunkCount",
+                    &key,
+                ) {
+                    Some(nr_of_chunks) => nr_of_chunks,
+                    None => break,
+                };
+                if get_storage_value::<u64>(MODULE, b"CipherCounts", &key).is_none() {
+                    let count =
+                        count_chunked_ciphers::<T>(&topic.id, iteration, nr_of_chunks);
+                    put_storage_value(MODULE, b"CipherCounts", &key, count);
+                    weight = weight.saturating_add(db.reads_writes(1, 1));
+                }
+                weight = weight.saturating_add(db.reads(2));
+                if iteration == NrOfShuffles::max_value() {
+                    break;
+                }
+                iteration += 1;
+            }
+            weight = weight.saturating_add(db.reads(1));
+        }
+    }
+    weight
+}
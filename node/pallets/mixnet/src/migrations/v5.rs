@@ -0,0 +1,25 @@
+use crate::types::{ProtocolConfig, Vote, VoteId};
+use crate::{ProtocolConfigs, Trait, Votes};
+use frame_support::{
+    storage::{IterableStorageMap, StorageMap},
+    traits::Get,
+    weights::Weight,
+};
+use sp_std::vec::Vec;
+
+/// migrates the storage to the protocol parameters stored with every vote:
+/// all existing votes have been created with the first version of the protocol
+pub fn migrate<T: Trait>() -> Weight {
+    let db = T::DbWeight::get();
+    let votes: Vec<(VoteId, Vote<T::AccountId>)> = Votes::<T>::iter().collect();
+    let mut weight: Weight = db.reads(votes.len() as Weight);
+
+    for (vote_id, vote) in votes.into_iter() {
+        if !ProtocolConfigs::contains_key(&vote_id) {
+            ProtocolConfigs::insert(&vote_id, ProtocolConfig::new(vote.nr_of_shuffles));
+            weight = weight.saturating_add(db.writes(1));
+        }
+        weight = weight.saturating_add(db.reads(1));
+    }
+    weight
+}
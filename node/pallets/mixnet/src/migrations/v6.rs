@@ -0,0 +1,19 @@
+use crate::{SealerSets, Sealers, Trait};
+use frame_support::{
+    storage::{StorageMap, StorageValue},
+    traits::Get,
+    weights::Weight,
+};
+use sp_std::vec::Vec;
+
+/// migrates the storage to the sealer epochs: all existing votes belong to the
+/// epoch 0 (the default of `VoteSealerEpochs`), i.e., to the current sealers
+pub fn migrate<T: Trait>() -> Weight {
+    let db = T::DbWeight::get();
+    if SealerSets::<T>::contains_key(0) {
+        return db.reads(1);
+    }
+    let sealers: Vec<T::AccountId> = Sealers::<T>::get();
+    SealerSets::<T>::insert(0, sealers);
+    db.reads_writes(2, 1)
+}
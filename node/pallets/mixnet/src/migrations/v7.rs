@@ -0,0 +1,132 @@
+use super::MODULE;
+use crate::types::{
+    ChunkIndex, Cipher, DecryptionAuditEntry, KeySwitchAuditEntry, NrOfShuffles,
+    PartialTally, PendingDecryption, SpoiledBallot, TallyProgress, TallyRound, Topic,
+    TopicId, TopicPacking, TopicResult, VoteId,
+};
+use crate::{Topics, Trait};
+use codec::{Decode, Encode};
+use frame_support::{
+    storage::{
+        migration::{get_storage_value, put_storage_value, StorageIterator},
+        IterableStorageMap,
+    },
+    traits::Get,
+    weights::Weight,
+    Blake2_128Concat, StorageHasher,
+};
+use sp_io::hashing::blake2_128;
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// the length of the blake2_128 hash which prefixes every blake2_128_concat key
+const HASH_LENGTH: usize = 16;
+
+/// returns the key of a double map entry before it was keyed by the vote,
+/// i.e., (topic_id, key2) without the prefix of the storage item
+pub fn legacy_double_map_key<K2: Encode>(topic_id: &TopicId, key2: &K2) -> Vec<u8> {
+    let mut key = Blake2_128Concat::hash(&topic_id.encode());
+    key.extend(Blake2_128Concat::hash(&key2.encode()));
+    key
+}
+
+/// returns a chunk of ciphers stored before the ciphers were keyed by the vote,
+/// empty if the chunk doesn't exist
+pub fn get_legacy_chunk(
+    topic_id: &TopicId,
+    iteration: NrOfShuffles,
+    chunk_index: ChunkIndex,
+) -> Vec<Cipher> {
+    let key = legacy_double_map_key(topic_id, &(iteration, chunk_index));
+    get_storage_value::<Vec<Cipher>>(MODULE, b"Ciphers", &key).unwrap_or_default()
+}
+
+/// decodes the topic id of a key before it was keyed by the vote, i.e.,
+/// blake2_128(topic_id) ++ topic_id ++ the (hashed) second key of a double map.
+/// returns None for a key which is already keyed by the vote and the topic:
+/// its hash doesn't match the vote id which is decoded as topic id.
+fn decode_legacy_key(key: &[u8]) -> Option<(TopicId, &[u8])> {
+    let hash = key.get(..HASH_LENGTH)?;
+    let input = &mut key.get(HASH_LENGTH..)?;
+    let topic_id = TopicId::decode(input).ok()?;
+    if blake2_128(&topic_id.encode())[..] != *hash {
+        return None;
+    }
+    Some((topic_id, *input))
+}
+
+/// moves all values of the storage item from the key topic_id to the key (vote_id, topic_id).
+/// the values which are already keyed by the vote and the values of unknown topics are kept.
+/// returns the number of values read.
+fn move_to_vote<V: Decode + Encode>(
+    item: &[u8],
+    topic_votes: &BTreeMap<TopicId, VoteId>,
+) -> Weight {
+    // drain the values first, the legacy and the new keys share the prefix of the item
+    let values: Vec<(Vec<u8>, V)> =
+        StorageIterator::<V>::new(MODULE, item).drain().collect();
+    let count = values.len() as Weight;
+    for (key, value) in values.into_iter() {
+        let new_key = decode_legacy_key(&key)
+            .and_then(|(topic_id, rest)| {
+                let vote_id = topic_votes.get(&topic_id)?;
+                let mut new_key = Blake2_128Concat::hash(&(vote_id, &topic_id).encode());
+                new_key.extend_from_slice(rest);
+                Some(new_key)
+            })
+            .unwrap_or(key);
+        put_storage_value(MODULE, item, &new_key, value);
+    }
+    count
+}
+
+/// migrates the storage to the per topic storage keyed by the vote and the topic:
+/// the ciphers, their chunk counts, counts, merkle roots and hashes, the spoiled ballots,
+/// the weights, the aggregates, the tallies, the decrypted shares, the pending decryptions,
+/// the key switch shares and their audit trails are moved from the key topic_id
+/// to the key (vote_id, topic_id), i.e., topic ids only need to be unique within a vote.
+/// the values are kept, a topic id used by several votes is assigned to the first vote.
+pub fn migrate<T: Trait>() -> Weight {
+    let db = T::DbWeight::get();
+    let topics: Vec<(VoteId, Vec<Topic>)> = Topics::iter().collect();
+    let weight: Weight = db.reads(topics.len() as Weight);
+
+    let mut topic_votes: BTreeMap<TopicId, VoteId> = BTreeMap::new();
+    for (vote_id, topics) in topics.into_iter() {
+        for topic in topics.into_iter() {
+            topic_votes
+                .entry(topic.id)
+                .or_insert_with(|| vote_id.clone());
+        }
+    }
+
+    let votes = &topic_votes;
+    let nr_of_values = move_to_vote::<Vec<Cipher>>(b"Ciphers", votes)
+        + move_to_vote::<ChunkIndex>(b"CipherChunkCount", votes)
+        + move_to_vote::<u64>(b"CipherCounts", votes)
+        + move_to_vote::<[u8; 32]>(b"CipherRoots", votes)
+        + move_to_vote::<Vec<Option<[u8; 32]>>>(b"CipherPeaks", votes)
+        + move_to_vote::<bool>(b"CipherHashes", votes)
+        + move_to_vote::<SpoiledBallot<T::AccountId>>(b"SpoiledBallots", votes)
+        + move_to_vote::<u64>(b"AdditionalWeights", votes)
+        + move_to_vote::<Cipher>(b"AggregateCiphers", votes)
+        + move_to_vote::<TopicResult>(b"Tally", votes)
+        + move_to_vote::<Vec<TallyRound>>(b"TallyRounds", votes)
+        + move_to_vote::<TopicPacking>(b"PackedTopics", votes)
+        + move_to_vote::<TopicResult>(b"PackedTally", votes)
+        + move_to_vote::<TallyProgress>(b"TallyProgresses", votes)
+        + move_to_vote::<PartialTally>(b"PartialTallies", votes)
+        + move_to_vote::<Vec<Vec<u8>>>(b"DecryptedShares", votes)
+        + move_to_vote::<Vec<DecryptionAuditEntry<T::AccountId>>>(
+            b"DecryptionAudit",
+            votes,
+        )
+        + move_to_vote::<PendingDecryption<T::BlockNumber>>(b"PendingDecryptions", votes)
+        + move_to_vote::<Vec<Cipher>>(b"KeySwitchShares", votes)
+        + move_to_vote::<Vec<KeySwitchAuditEntry<T::AccountId>>>(
+            b"KeySwitchAudit",
+            votes,
+        );
+
+    // every value is read, removed and written once
+    weight.saturating_add(db.reads_writes(nr_of_values, 2 * nr_of_values))
+}
@@ -0,0 +1,27 @@
+use super::translate_values;
+use crate::types::{Ballot, BallotProof, Cipher, TopicId, ValidityProof};
+use crate::Trait;
+use codec::{Decode, Encode};
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::vec::Vec;
+
+/// the ballot before it could carry the encrypted credential of the voter
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct BallotV2 {
+    pub answers: Vec<(TopicId, Cipher)>,
+    pub proofs: Vec<BallotProof>,
+    pub validity_proofs: Vec<Option<ValidityProof>>,
+}
+
+/// migrates the storage to the ballots with credentials:
+/// the existing ballots carry no credential, i.e., they are counted without a credential check
+pub fn migrate<T: Trait>() -> Weight {
+    let db = T::DbWeight::get();
+    let nr_of_ballots = translate_values(b"Ballots", |_, old: BallotV2| Ballot {
+        answers: old.answers,
+        proofs: old.proofs,
+        validity_proofs: old.validity_proofs,
+        credential: None,
+    });
+    db.reads_writes(nr_of_ballots, nr_of_ballots)
+}
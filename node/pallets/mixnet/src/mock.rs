@@ -1,11 +1,10 @@
 use crate as pallet_mixnet;
 use crate::Call;
 use codec::alloc::sync::Arc;
-use codec::Decode;
 use frame_support::{
-    dispatch::Weight, impl_outer_event, impl_outer_origin, parameter_types,
+    dispatch::Weight, impl_outer_event, impl_outer_origin, parameter_types, traits::Get,
 };
-use hex_literal::hex;
+use mixnet_test_utils::{accounts::account, ElectionFixture};
 use pallet_timestamp;
 use parking_lot::RwLock;
 use sp_core::{
@@ -24,6 +23,7 @@ use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup, Verify},
     Perbill,
 };
+use std::cell::RefCell;
 
 impl_outer_origin! {
     pub enum Origin for TestRuntime {}
@@ -33,6 +33,7 @@ impl_outer_event! {
     pub enum TestEvent for TestRuntime {
         // events of crate: pallet_mixnet
         frame_system<T>,
+        pallet_balances<T>,
         pallet_mixnet<T>,
     }
 }
@@ -70,7 +71,7 @@ impl frame_system::Trait for TestRuntime {
     type AvailableBlockRatio = AvailableBlockRatio;
     type Version = ();
     type PalletInfo = ();
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<u64>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
@@ -88,6 +89,22 @@ impl pallet_timestamp::Trait for TestRuntime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Trait for TestRuntime {
+    type MaxLocks = ();
+    type Balance = u64;
+    type Event = TestEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+pub type Balances = pallet_balances::Module<TestRuntime>;
+
 // --- mocking offchain-worker trait
 
 pub type TestExtrinsic = TestXt<Call<TestRuntime>, ()>;
@@ -130,6 +147,51 @@ pub type System = frame_system::Module<TestRuntime>;
 // Mock Implementation of pallet_mixnet
 parameter_types! {
     pub const TestBlockDuration: u64 = 1;
+    pub const TestCipherChunkSize: u32 = 4;
+    pub const TestShuffleTurnTimeout: u64 = 3;
+    pub const TestDecryptionTimeout: u64 = 5;
+    pub const TestHeartbeatInterval: u64 = 2;
+    pub const TestCheckpointInterval: u64 = 5;
+    pub const TestCertificationQuorum: u32 = 1;
+    pub const TestMaxMetadataSize: u32 = 64;
+    pub const TestMaxVoteIdLength: u32 = 32;
+    pub const TestMaxTitleLength: u32 = 64;
+    pub const TestMaxTopicIdLength: u32 = 32;
+    pub const TestMaxQuestionLength: u32 = 128;
+    pub const TestMinShuffleBatchSize: u64 = 1;
+    pub const TestMaxShuffleBatchSize: u64 = 10;
+    pub const TestShuffleTimeBudget: u64 = 2000;
+    pub const TestUnsignedPriority: u64 = 1 << 20;
+    pub const TestBallotRatePeriod: u64 = 5;
+    pub const TestMaxVoterWeight: u32 = 100;
+}
+
+// the spam protection is disabled by default, such that the tests can cast any number of ballots
+thread_local! {
+    static BALLOT_DEPOSIT: RefCell<u64> = RefCell::new(0);
+    static MAX_BALLOTS_PER_PERIOD: RefCell<u32> = RefCell::new(u32::MAX);
+}
+
+pub struct TestBallotDeposit;
+impl Get<u64> for TestBallotDeposit {
+    fn get() -> u64 {
+        BALLOT_DEPOSIT.with(|deposit| *deposit.borrow())
+    }
+}
+
+pub struct TestMaxBallotsPerPeriod;
+impl Get<u32> for TestMaxBallotsPerPeriod {
+    fn get() -> u32 {
+        MAX_BALLOTS_PER_PERIOD.with(|max| *max.borrow())
+    }
+}
+
+pub fn set_ballot_deposit(deposit: u64) {
+    BALLOT_DEPOSIT.with(|value| *value.borrow_mut() = deposit);
+}
+
+pub fn set_max_ballots_per_period(max: u32) {
+    MAX_BALLOTS_PER_PERIOD.with(|value| *value.borrow_mut() = max);
 }
 
 impl pallet_mixnet::Trait for TestRuntime {
@@ -137,6 +199,28 @@ impl pallet_mixnet::Trait for TestRuntime {
     type Event = TestEvent;
     type AuthorityId = pallet_mixnet::keys::TestAuthId;
     type BlockDuration = TestBlockDuration;
+    type CipherChunkSize = TestCipherChunkSize;
+    type ShuffleTurnTimeout = TestShuffleTurnTimeout;
+    type DecryptionTimeout = TestDecryptionTimeout;
+    type HeartbeatInterval = TestHeartbeatInterval;
+    type CheckpointInterval = TestCheckpointInterval;
+    type CertificationQuorum = TestCertificationQuorum;
+    type MaxMetadataSize = TestMaxMetadataSize;
+    type MaxVoteIdLength = TestMaxVoteIdLength;
+    type MaxTitleLength = TestMaxTitleLength;
+    type MaxTopicIdLength = TestMaxTopicIdLength;
+    type MaxQuestionLength = TestMaxQuestionLength;
+    type MinShuffleBatchSize = TestMinShuffleBatchSize;
+    type MaxShuffleBatchSize = TestMaxShuffleBatchSize;
+    type ShuffleTimeBudget = TestShuffleTimeBudget;
+    type UnsignedPriority = TestUnsignedPriority;
+    type Currency = Balances;
+    type BallotDeposit = TestBallotDeposit;
+    type MaxBallotsPerPeriod = TestMaxBallotsPerPeriod;
+    type BallotRatePeriod = TestBallotRatePeriod;
+    type MaxVoterWeight = TestMaxVoterWeight;
+    type WeightInfo = ();
+    type FeePolicy = ();
 }
 
 pub type OffchainModule = pallet_mixnet::Module<TestRuntime>;
@@ -144,64 +228,45 @@ pub type OffchainModule = pallet_mixnet::Module<TestRuntime>;
 pub struct ExternalityBuilder;
 
 impl ExternalityBuilder {
-    fn initialize_test_authorities() -> (
+    /// the voting authorities and sealers of the fixture in the genesis
+    fn initialize_test_authorities(
+        fixture: &ElectionFixture,
+    ) -> (
         Vec<<TestRuntime as frame_system::Trait>::AccountId>,
         Vec<<TestRuntime as frame_system::Trait>::AccountId>,
     ) {
-        // use Alice as VotingAuthority
-        let alice_account_id: [u8; 32] =
-            hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")
-                .into();
-
-        let voting_authority: <TestRuntime as frame_system::Trait>::AccountId =
-            <TestRuntime as frame_system::Trait>::AccountId::decode(
-                &mut &alice_account_id[..],
-            )
-            .unwrap();
-
-        // Use Bob, Charlie, Dave as Sealers
-        let bob_account_id: [u8; 32] =
-            hex!("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48")
-                .into();
-
-        let sealer1: <TestRuntime as frame_system::Trait>::AccountId =
-            <TestRuntime as frame_system::Trait>::AccountId::decode(
-                &mut &bob_account_id[..],
-            )
-            .unwrap();
-
-        let charlie_account_id: [u8; 32] =
-            hex!("90b5ab205c6974c9ea841be688864633dc9ca8a357843eeacf2314649965fe22")
-                .into();
-
-        let sealer2: <TestRuntime as frame_system::Trait>::AccountId =
-            <TestRuntime as frame_system::Trait>::AccountId::decode(
-                &mut &charlie_account_id[..],
-            )
-            .unwrap();
-
-        // let dave_account_id: [u8; 32] =
-        //     hex!("90b5ab205c6974c9ea841be688864633dc9ca8a357843eebbf2314649965fe22")
-        //         .into();
-
-        // let sealer3: <TestRuntime as frame_system::Trait>::AccountId =
-        //     <TestRuntime as frame_system::Trait>::AccountId::decode(&mut &dave_account_id[..])
-        //         .unwrap();
-
-        let voting_authorities = vec![voting_authority];
-        // let sealers = vec![sealer1, sealer2, sealer3];
-        let sealers = vec![sealer1, sealer2];
+        let voting_authorities = fixture.voting_authorities.iter().map(account).collect();
+        let sealers = fixture
+            .sealers
+            .iter()
+            .map(|sealer| account(&sealer.account))
+            .collect();
         (voting_authorities, sealers)
     }
 
+    /// Alice as voting authority, Bob and Charlie as sealers
     pub fn build() -> (
         TestExternalities,
         Arc<RwLock<PoolState>>,
         Arc<RwLock<OffchainState>>,
+    ) {
+        Self::with_fixture(&ElectionFixture::builder().build())
+    }
+
+    pub fn with_fixture(
+        fixture: &ElectionFixture,
+    ) -> (
+        TestExternalities,
+        Arc<RwLock<PoolState>>,
+        Arc<RwLock<OffchainState>>,
     ) {
         const PHRASE: &str =
             "expire stage crawl shell boss any story swamp skull yellow bamboo copy";
 
+        // the thread locals outlive a test if the tests share a thread
+        set_ballot_deposit(0);
+        set_max_ballots_per_period(u32::MAX);
+
         let (offchain, offchain_state) = testing::TestOffchainExt::new();
         let (pool, pool_state) = testing::TestTransactionPoolExt::new();
         let keystore = KeyStore::new();
@@ -217,14 +282,17 @@ impl ExternalityBuilder {
             .build_storage::<TestRuntime>()
             .unwrap();
 
-        let (voting_authorities, sealers) = Self::initialize_test_authorities();
+        let (voting_authorities, sealers) = Self::initialize_test_authorities(fixture);
 
-        super::GenesisConfig::<TestRuntime> {
-            voting_authorities,
-            sealers,
-        }
-        .assimilate_storage(&mut storage)
-        .unwrap();
+        voting_authorities
+            .into_iter()
+            .fold(
+                super::GenesisConfig::<TestRuntime>::default(),
+                |config, authority| config.with_authority(authority),
+            )
+            .with_sealers(sealers)
+            .assimilate_storage(&mut storage)
+            .unwrap();
 
         let mut t = TestExternalities::from(storage);
         t.register_extension(OffchainExt::new(offchain));
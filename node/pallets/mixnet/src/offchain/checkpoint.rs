@@ -0,0 +1,83 @@
+use crate::types::{CHECKPOINT_KEY, STORAGE_MODULE_PREFIX};
+use crate::{Error, Module, Trait, VoteIds};
+use codec::Encode;
+use core::convert::TryInto;
+use frame_support::{debug, storage::StorageValue, traits::Get};
+use frame_system::offchain::{SignMessage, Signer};
+use mixnet_primitives::checkpoint::{checkpoint_message, Checkpoint, SignedCheckpoint};
+use sp_runtime::offchain::StorageKind;
+use sp_std::vec::Vec;
+
+/// returns the raw storage entries (key, value) of the pallet, ordered by key
+pub fn get_storage_entries() -> Vec<(Vec<u8>, Vec<u8>)> {
+    let prefix = sp_io::hashing::twox_128(STORAGE_MODULE_PREFIX).to_vec();
+    let mut entries: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
+    let mut key = prefix.clone();
+    while let Some(next) = sp_io::storage::next_key(&key) {
+        if !next.starts_with(&prefix) {
+            break;
+        }
+        if let Some(value) = sp_io::storage::get(&next) {
+            entries.push((next.clone(), value));
+        }
+        key = next;
+    }
+    entries
+}
+
+/// returns the latest signed checkpoint (CBOR encoded) of this node, if any
+pub fn get_latest_checkpoint() -> Option<Vec<u8>> {
+    sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, CHECKPOINT_KEY)
+}
+
+impl<T: Trait> Module<T> {
+    /// takes a signed checkpoint of the election state every `CheckpointInterval` blocks
+    /// as long as there are votes. only the latest checkpoint is kept in the offchain storage.
+    pub fn offchain_checkpoint(block_number: T::BlockNumber) -> Result<(), Error<T>> {
+        // only the sealers (validators) hold an authority key
+        if !sp_io::offchain::is_validator() {
+            return Ok(());
+        }
+
+        let number: u64 = block_number.try_into().unwrap_or(0u64) as u64;
+        let interval: u64 =
+            T::CheckpointInterval::get().try_into().unwrap_or(0u64) as u64;
+        if interval == 0 || number % interval != 0 || VoteIds::get().is_empty() {
+            return Ok(());
+        }
+
+        let signed = Self::create_checkpoint(number)?;
+        sp_io::offchain::local_storage_set(
+            StorageKind::PERSISTENT,
+            CHECKPOINT_KEY,
+            &signed.to_cbor(),
+        );
+        debug::info!(
+            "checkpoint taken in block: {:?}, size: {:?} bytes",
+            number,
+            signed.checkpoint.len()
+        );
+        Ok(())
+    }
+
+    /// takes a snapshot of the storage of the pallet (votes, ciphers, proofs, ...)
+    /// and signs it with the node's authority key.
+    pub fn create_checkpoint(block_number: u64) -> Result<SignedCheckpoint, Error<T>> {
+        let checkpoint = Checkpoint {
+            block_number,
+            parent_hash: <frame_system::Module<T>>::parent_hash().encode(),
+            entries: get_storage_entries(),
+        }
+        .to_cbor();
+
+        let signer = Signer::<T, T::AuthorityId>::any_account();
+        let (account, signature) = signer
+            .sign_message(&checkpoint_message(&checkpoint))
+            .ok_or(Error::<T>::NoLocalAcctForSigning)?;
+        Ok(SignedCheckpoint {
+            checkpoint,
+            signer: account.id.encode(),
+            signature: signature.encode(),
+        })
+    }
+}
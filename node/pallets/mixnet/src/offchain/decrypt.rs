@@ -0,0 +1,274 @@
+use super::{heartbeat, jobs, jobs::OffchainJob, metrics};
+use crate::dkg::aggregate::{get_ciphers_to_decrypt, get_nr_of_ciphers_to_decrypt};
+use crate::helpers::{
+    params::{get_hash_function, get_public_params},
+    sealers::is_vote_sealer,
+};
+use crate::types::{
+    Cipher, DecryptedShare, DecryptedShareProof, DecryptedSharesSubmission, NrOfShuffles,
+    Topic, TopicId, VoteId, VotePhase, Wrapper,
+};
+use crate::{
+    ArchivedVotes, Call, DecryptedShares, Error, Module, PendingDecryptions,
+    PublicKeyShareBySealer, Topics, Trait, Votes,
+};
+use codec::Encode;
+use core::convert::TryInto;
+use crypto::{
+    encryption::ElGamal,
+    proofs::decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
+    secret,
+    types::{Cipher as BigCipher, ElGamalParams, PrivateKey},
+};
+use frame_support::{
+    debug,
+    storage::{StorageDoubleMap, StorageMap},
+};
+use frame_system::offchain::{Account, SendUnsignedTransaction, SignMessage, Signer};
+use num_bigint::BigUint;
+use sp_runtime::offchain::StorageKind;
+use sp_std::vec::Vec;
+
+/// offchain local storage key prefix of the private key shares of the sealer, followed by the vote id.
+/// the value is the hex encoded (utf-8) private key share the sealer generated its public key share with,
+/// e.g. set using the RPC `offchain_localStorageSet`. the private key share is removed once the sealer
+/// decrypted all topics of the vote or the vote has been cancelled or archived (see: README).
+pub const SEALER_SK_PREFIX: &[u8] = b"provotum::mixnet::ocw::sealer_sk::";
+
+fn sealer_sk_key(vote_id: &VoteId) -> Vec<u8> {
+    let mut key: Vec<u8> = SEALER_SK_PREFIX.to_vec();
+    key.extend(vote_id);
+    key
+}
+
+/// true if the private key share of the vote is configured
+fn has_sealer_sk(vote_id: &VoteId) -> bool {
+    sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &sealer_sk_key(vote_id))
+        .map(|sk| !sk.is_empty())
+        .unwrap_or(false)
+}
+
+/// removes the private key share of the vote.
+/// a value of the offchain local storage can't be removed, it is overwritten by an empty one.
+fn clear_sealer_sk(vote_id: &VoteId) {
+    sp_io::offchain::local_storage_set(
+        StorageKind::PERSISTENT,
+        &sealer_sk_key(vote_id),
+        &[],
+    );
+}
+
+/// returns the configured private key share of the vote, if any
+fn get_sealer_sk(vote_id: &VoteId) -> Option<BigUint> {
+    let mut sk = sp_io::offchain::local_storage_get(
+        StorageKind::PERSISTENT,
+        &sealer_sk_key(vote_id),
+    )?;
+    let x = BigUint::parse_bytes(&sk, 16);
+    sk.iter_mut().for_each(|byte| *byte = 0);
+    x
+}
+
+impl<T: Trait> Module<T> {
+    /// returns the account the offchain worker signs with,
+    /// the same account signs the unsigned transactions of `Signer::any_account`
+    pub(super) fn get_local_account() -> Result<Account<T>, Error<T>> {
+        let signer = Signer::<T, T::AuthorityId>::any_account();
+        signer
+            .sign_message(&[])
+            .map(|(account, _)| account)
+            .ok_or(Error::<T>::NoLocalAcctForSigning)
+    }
+
+    /// removes the private key share of the vote once it is not needed anymore, i.e., once the
+    /// local sealer submitted the decrypted shares of all topics of the vote or once the vote
+    /// has been cancelled or archived. a key which is needed again must be configured again.
+    pub(super) fn clear_obsolete_sealer_sk(
+        vote_id: &VoteId,
+        sealer: Option<&T::AccountId>,
+    ) {
+        if !has_sealer_sk(vote_id) {
+            return;
+        }
+        let obsolete = Votes::<T>::get(vote_id).phase == VotePhase::Cancelled
+            || ArchivedVotes::contains_key(vote_id)
+            || sealer
+                .map(|sealer| {
+                    let topics: Vec<Topic> = Topics::get(vote_id);
+                    !topics.is_empty()
+                        && topics.iter().all(|Topic { id: topic_id, .. }| {
+                            !DecryptedShares::<T>::get((vote_id, topic_id), sealer)
+                                .is_empty()
+                        })
+                })
+                .unwrap_or(false);
+        if obsolete {
+            clear_sealer_sk(vote_id);
+            debug::info!("private key share removed, vote_id: {:?}", vote_id);
+        }
+    }
+
+    /// derives the decryption jobs of the local sealer: the topics of the vote whose
+    /// shuffles are done, i.e., which await their decryption (see: `PendingDecryptions`)
+    /// and which the sealer hasn't decrypted yet.
+    /// no jobs are derived if the private key share of the vote is not configured.
+    pub(super) fn get_decryption_jobs(
+        vote_id: &VoteId,
+        topics: &[Topic],
+        nr_of_shuffles: NrOfShuffles,
+        sealer: &T::AccountId,
+    ) -> Vec<OffchainJob> {
+        if !is_vote_sealer::<T>(sealer, vote_id) || !has_sealer_sk(vote_id) {
+            return Vec::new();
+        }
+
+        topics
+            .iter()
+            .filter(|Topic { id: topic_id, .. }| {
+                PendingDecryptions::<T>::contains_key((vote_id, topic_id))
+                    && DecryptedShares::<T>::get((vote_id, topic_id), sealer).is_empty()
+                    && get_nr_of_ciphers_to_decrypt::<T>(
+                        vote_id,
+                        topic_id,
+                        nr_of_shuffles,
+                    )
+                    .map(|nr_of_ciphers| nr_of_ciphers > 0)
+                    .unwrap_or(false)
+            })
+            .map(|Topic { id: topic_id, .. }| OffchainJob::Decrypt {
+                vote_id: vote_id.clone(),
+                topic_id: topic_id.clone(),
+                nr_of_shuffles,
+            })
+            .collect()
+    }
+
+    /// decrypts the ciphers of the topic with the private key share of the local sealer and
+    /// submits the decrypted shares and the proof as an unsigned transaction signed by the sealer,
+    /// i.e., the sealer doesn't pay for the verification of the decryption proof
+    pub(super) fn execute_decryption_job(
+        block_number: T::BlockNumber,
+        job: &OffchainJob,
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+        nr_of_shuffles: NrOfShuffles,
+    ) -> Result<(), Error<T>> {
+        let number: u64 = block_number.try_into().unwrap_or(0u64) as u64;
+        let account = Self::get_local_account()?;
+
+        let (shares, proof) = match Self::offchain_decrypt_and_proof(
+            vote_id,
+            topic_id,
+            nr_of_shuffles,
+            &account.id,
+        ) {
+            Ok(decryption) => decryption,
+            Err(error) => {
+                jobs::fail_job(job, number);
+                return Err(error);
+            }
+        };
+
+        debug::info!("submitting decrypted shares (unsigned)");
+        let signer = Signer::<T, T::AuthorityId>::any_account();
+        let transaction_response = signer.send_unsigned_transaction(
+            |acct| DecryptedSharesSubmission {
+                vote_id: vote_id.to_vec(),
+                topic_id: topic_id.to_vec(),
+                shares: shares.clone(),
+                proof: proof.clone(),
+                nr_of_shuffles,
+                public: acct.public.clone(),
+            },
+            |submission, signature| {
+                Call::submit_decrypted_shares_unsigned(submission, signature)
+            },
+        );
+
+        // the job is completed once the decrypted shares have been submitted
+        match transaction_response {
+            Some((_, Ok(_))) => {
+                heartbeat::record_sent(number);
+                jobs::complete_job(job);
+                debug::info!(
+                    "ciphers decrypted in offchain worker -> vote_id: {:?}, topic_id: {:?}",
+                    vote_id,
+                    topic_id
+                );
+                Ok(())
+            }
+            Some((acc, res)) => {
+                debug::error!(
+                    "failure in offchain tx, acc: {:?}, res: {:?}",
+                    acc.id,
+                    res
+                );
+                metrics::record_failed_submission();
+                jobs::fail_job(job, number);
+                Ok(())
+            }
+            None => {
+                // the case of `None`: no account is available for sending
+                debug::error!("No local account available");
+                Err(<Error<T>>::NoLocalAcctForSigning)
+            }
+        }
+    }
+
+    /// computes the partial decryptions of the ciphers of the topic and the (batched) proof
+    /// of their correctness, the same computation as `provotum-cli sealer decrypt`.
+    /// the private key share must match the public key share the sealer submitted for the vote.
+    pub fn offchain_decrypt_and_proof(
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+        nr_of_shuffles: NrOfShuffles,
+        sealer: &T::AccountId,
+    ) -> Result<(Vec<DecryptedShare>, DecryptedShareProof), Error<T>> {
+        let params: ElGamalParams = get_public_params::<T>(vote_id)?.into();
+        let x: BigUint =
+            get_sealer_sk(vote_id).ok_or(Error::<T>::InvalidPrivateKeyShare)?;
+        let sk = PrivateKey {
+            params: params.clone(),
+            x,
+        };
+
+        // a wrong private key share would only produce invalid proofs
+        let pk_share = PublicKeyShareBySealer::<T>::get((vote_id, sealer))
+            .ok_or(Error::<T>::PublicKeyShareNotExistsError)?;
+        let h: BigUint = params.g.modpow(&sk.x, &params.p);
+        if h != BigUint::from_bytes_be(&pk_share.pk) {
+            debug::error!("private key share doesn't match, vote_id: {:?}", vote_id);
+            return Err(Error::<T>::InvalidPrivateKeyShare);
+        }
+
+        // get the encrypted votes (ciphers) to decrypt
+        let ciphers: Vec<Cipher> =
+            get_ciphers_to_decrypt::<T>(vote_id, topic_id, nr_of_shuffles)?;
+        let encryptions: Vec<BigCipher> = Wrapper(ciphers).into();
+
+        let partial_decryptions: Vec<BigUint> =
+            ElGamal::partial_decrypt_many(&encryptions, &sk);
+        let shares: Vec<DecryptedShare> = partial_decryptions
+            .iter()
+            .map(|share| share.to_bytes_be())
+            .collect();
+
+        let mut r = Self::get_random_biguint_less_than(&params.q())?;
+        let proof = DecryptionProof::generate_batched_with_hash(
+            &params,
+            &sk.x,
+            &h,
+            &r,
+            &encryptions,
+            &partial_decryptions,
+            &sealer.encode(),
+            DEFAULT_BATCH_SOUNDNESS,
+            get_hash_function::<T>(vote_id),
+        );
+
+        // the private key is wiped when it is dropped (see: PrivateKey), the random of the proof
+        // is wiped as well: together with the response of the proof it reveals the private key
+        secret::wipe(&mut r);
+        Ok((shares, proof.into()))
+    }
+}
@@ -0,0 +1,47 @@
+use codec::Encode;
+use crypto::helper::{Helper, GENERATOR_DOMAIN};
+use num_bigint::BigUint;
+use sp_io::hashing::blake2_256;
+use sp_runtime::offchain::storage::StorageValueRef;
+use sp_std::vec::Vec;
+
+/// offchain local storage key prefix of the independent generators of the shuffle proofs
+const GENERATORS_PREFIX: &[u8] = b"provotum::mixnet::ocw::generators::";
+
+/// the generators are cached per id (topicId or voteId for linked shuffles), group (q) and
+/// derivation (the domain of the generators, see: `Helper::derive_generator`).
+/// the generator h_i only depends on the id, the group and i, i.e. the generators of a batch
+/// of size n are the first n generators of any larger batch: the cache keeps the largest batch.
+fn generators_key(id: &[u8], q: &BigUint) -> Vec<u8> {
+    let mut key: Vec<u8> = GENERATORS_PREFIX.to_vec();
+    key.extend(&blake2_256(
+        &(id, q.to_bytes_be(), GENERATOR_DOMAIN.as_bytes()).encode(),
+    ));
+    key
+}
+
+/// returns the {size} independent generators of the shuffle proof (see: `Helper::get_generators`).
+/// the generators are computed once per id and group and are kept in the offchain local storage,
+/// all further batches of a large election reuse them. the cached generators are verified
+/// before they are used, a proof with other generators wouldn't verify.
+pub fn get_generators(id: &[u8], p: &BigUint, q: &BigUint, size: usize) -> Vec<BigUint> {
+    let key = generators_key(id, q);
+    let storage = StorageValueRef::persistent(&key);
+    let cached: Vec<Vec<u8>> =
+        storage.get::<Vec<Vec<u8>>>().flatten().unwrap_or_default();
+    if cached.len() >= size {
+        let vec_h: Vec<BigUint> = cached
+            .iter()
+            .take(size)
+            .map(|h_i| BigUint::from_bytes_be(h_i))
+            .collect();
+        if Helper::verify_generators(id, p, &vec_h) {
+            return vec_h;
+        }
+    }
+
+    let vec_h = Helper::get_generators(id, p, size);
+    let encoded: Vec<Vec<u8>> = vec_h.iter().map(|h_i| h_i.to_bytes_be()).collect();
+    storage.set(&encoded);
+    vec_h
+}
@@ -0,0 +1,86 @@
+use super::metrics;
+use crate::helpers::liveness::is_liveness_monitored;
+use crate::types::HeartbeatPayload;
+use crate::{Call, Error, Module, Trait};
+use core::convert::TryInto;
+use frame_support::{debug, traits::Get};
+use frame_system::offchain::{SendUnsignedTransaction, Signer};
+use sp_runtime::offchain::storage::StorageValueRef;
+
+/// offchain local storage key of the block in which this node last sent a transaction
+const LAST_SENT_KEY: &[u8] = b"provotum::mixnet::ocw::last_sent";
+
+/// returns the block in which this node last sent a transaction, if any
+fn get_last_sent() -> Option<u64> {
+    let storage = StorageValueRef::persistent(LAST_SENT_KEY);
+    storage.get::<u64>().flatten()
+}
+
+/// remembers that this node sent a transaction in the block. shuffle transactions
+/// count as heartbeats, i.e., no heartbeat is sent while the node is shuffling.
+pub fn record_sent(block_number: u64) {
+    let storage = StorageValueRef::persistent(LAST_SENT_KEY);
+    storage.set(&block_number);
+}
+
+/// returns true if the node hasn't sent a transaction for `interval` blocks
+fn is_heartbeat_due(block_number: u64, interval: u64) -> bool {
+    match get_last_sent() {
+        Some(last_sent) => block_number >= last_sent.saturating_add(interval),
+        None => true,
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// sends a heartbeat every `HeartbeatInterval` blocks while a vote is being tallied.
+    /// a single transaction is sent per block, the heartbeat is skipped (i.e., delayed)
+    /// if the node already sent a shuffle transaction in the block.
+    /// the heartbeat is an unsigned transaction signed by the sealer, i.e., it's free of charge.
+    pub fn offchain_heartbeat(block_number: T::BlockNumber) -> Result<(), Error<T>> {
+        // only the sealers (validators) send heartbeats
+        if !sp_io::offchain::is_validator() {
+            return Ok(());
+        }
+
+        if !is_liveness_monitored() {
+            return Ok(());
+        }
+
+        let number: u64 = block_number.try_into().unwrap_or(0u64) as u64;
+        let interval: u64 = T::HeartbeatInterval::get().try_into().unwrap_or(0u64) as u64;
+        if !is_heartbeat_due(number, interval) {
+            return Ok(());
+        }
+
+        debug::info!("sending heartbeat in block: {:?}", number);
+        let signer = Signer::<T, T::AuthorityId>::any_account();
+        let transaction_response = signer.send_unsigned_transaction(
+            |acct| HeartbeatPayload {
+                block_number,
+                public: acct.public.clone(),
+            },
+            |heartbeat, signature| Call::heartbeat_unsigned(heartbeat, signature),
+        );
+
+        match transaction_response {
+            Some((_, Ok(_))) => {
+                record_sent(number);
+                Ok(())
+            }
+            Some((acc, res)) => {
+                debug::error!(
+                    "failure in offchain tx, acc: {:?}, res: {:?}",
+                    acc.id,
+                    res
+                );
+                metrics::record_failed_submission();
+                Err(<Error<T>>::OffchainSignedTxError)
+            }
+            None => {
+                // the case of `None`: no account is available for sending
+                debug::error!("No local account available");
+                Err(<Error<T>>::NoLocalAcctForSigning)
+            }
+        }
+    }
+}
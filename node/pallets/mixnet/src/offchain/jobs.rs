@@ -0,0 +1,226 @@
+use super::metrics;
+use crate::types::{NrOfShuffles, TopicId, VoteId};
+use codec::{Decode, Encode};
+use frame_support::debug;
+use sp_runtime::offchain::storage::StorageValueRef;
+use sp_std::vec::Vec;
+
+/// offchain local storage key of the job queue
+const JOB_QUEUE_KEY: &[u8] = b"provotum::mixnet::ocw::job_queue";
+
+/// offchain local storage key of the dead-letter log
+const DEAD_LETTER_KEY: &[u8] = b"provotum::mixnet::ocw::dead_letter";
+
+/// the number of failed attempts after which a job is moved to the dead-letter log
+pub const MAX_ATTEMPTS: u32 = 5;
+
+/// the maximum number of blocks a job is delayed after a failed attempt
+const MAX_BACKOFF: u64 = 64;
+
+/// the work the offchain worker has to perform.
+/// topics are only decrypted if the private key share of the vote is configured (see: `decrypt`).
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub enum OffchainJob {
+    /// shuffle the batch of ciphers starting at `start_position`
+    Shuffle {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        iteration: NrOfShuffles,
+        start_position: u64,
+    },
+    /// decrypt the ciphers of a topic whose shuffles are done and submit the decrypted shares
+    Decrypt {
+        vote_id: VoteId,
+        topic_id: TopicId,
+        nr_of_shuffles: NrOfShuffles,
+    },
+}
+
+impl OffchainJob {
+    /// two jobs target the same work if they are of the same kind
+    /// and operate on the same vote and topic
+    fn same_target(&self, other: &OffchainJob) -> bool {
+        match (self, other) {
+            (
+                OffchainJob::Shuffle {
+                    vote_id: v1,
+                    topic_id: t1,
+                    ..
+                },
+                OffchainJob::Shuffle {
+                    vote_id: v2,
+                    topic_id: t2,
+                    ..
+                },
+            ) => v1 == v2 && t1 == t2,
+            (
+                OffchainJob::Decrypt {
+                    vote_id: v1,
+                    topic_id: t1,
+                    ..
+                },
+                OffchainJob::Decrypt {
+                    vote_id: v2,
+                    topic_id: t2,
+                    ..
+                },
+            ) => v1 == v2 && t1 == t2,
+            _ => false,
+        }
+    }
+}
+
+/// a job in the queue including its retry state
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct QueuedJob {
+    pub job: OffchainJob,
+    /// the number of failed attempts
+    pub attempts: u32,
+    /// the first block at which the job may be executed (again)
+    pub next_attempt: u64,
+}
+
+/// a job which failed `MAX_ATTEMPTS` times
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct DeadLetter {
+    pub job: OffchainJob,
+    pub attempts: u32,
+    /// the block of the last failed attempt
+    pub failed_at: u64,
+}
+
+/// returns all queued jobs
+pub fn get_job_queue() -> Vec<QueuedJob> {
+    let storage = StorageValueRef::persistent(JOB_QUEUE_KEY);
+    storage
+        .get::<Vec<QueuedJob>>()
+        .flatten()
+        .unwrap_or_default()
+}
+
+fn set_job_queue(queue: &[QueuedJob]) {
+    let storage = StorageValueRef::persistent(JOB_QUEUE_KEY);
+    storage.set(&queue);
+}
+
+/// returns all jobs which have been given up on
+pub fn get_dead_letters() -> Vec<DeadLetter> {
+    let storage = StorageValueRef::persistent(DEAD_LETTER_KEY);
+    storage
+        .get::<Vec<DeadLetter>>()
+        .flatten()
+        .unwrap_or_default()
+}
+
+fn push_dead_letter(letter: DeadLetter) {
+    let storage = StorageValueRef::persistent(DEAD_LETTER_KEY);
+    let mut letters = get_dead_letters();
+    letters.push(letter);
+    storage.set(&letters);
+}
+
+fn is_dead_letter(job: &OffchainJob) -> bool {
+    get_dead_letters().iter().any(|letter| &letter.job == job)
+}
+
+/// the number of blocks to wait after `attempts` failed attempts: 2^attempts
+fn backoff(attempts: u32) -> u64 {
+    2u64.checked_pow(attempts)
+        .unwrap_or(MAX_BACKOFF)
+        .min(MAX_BACKOFF)
+}
+
+/// synchronizes the queue with the jobs derived from the on-chain state.
+/// the retry state of jobs which are still required is kept, jobs which are no longer
+/// required (e.g. the shuffle state has progressed) are dropped and
+/// jobs in the dead-letter log are not queued again.
+pub fn sync_jobs(jobs: Vec<OffchainJob>) {
+    let queue = get_job_queue();
+    let mut updated: Vec<QueuedJob> = Vec::with_capacity(jobs.len());
+
+    for job in jobs.into_iter() {
+        if updated.iter().any(|queued| queued.job.same_target(&job)) {
+            continue;
+        }
+        match queue.iter().find(|queued| queued.job == job) {
+            Some(queued) => updated.push(queued.clone()),
+            None if is_dead_letter(&job) => continue,
+            None => updated.push(QueuedJob {
+                job,
+                attempts: 0,
+                next_attempt: 0,
+            }),
+        }
+    }
+    set_job_queue(&updated);
+}
+
+/// returns all jobs which are due at the block
+pub fn get_due_jobs(block_number: u64) -> Vec<OffchainJob> {
+    get_job_queue()
+        .into_iter()
+        .filter(|queued| queued.next_attempt <= block_number)
+        .map(|queued| queued.job)
+        .collect()
+}
+
+/// selects the job to execute at the block.
+/// the job only changes every nr_of_sealers blocks, i.e., every sealer gets the chance
+/// to take its turn (see: `shuffle::turn`). this way the shuffles of concurrent votes
+/// and topics are interleaved and each sealer works on all of them.
+pub fn select_job(
+    mut jobs: Vec<OffchainJob>,
+    block_number: u64,
+    nr_of_sealers: u64,
+) -> Option<OffchainJob> {
+    if jobs.is_empty() {
+        return None;
+    }
+    let round = block_number / nr_of_sealers.max(1);
+    let index = (round % jobs.len() as u64) as usize;
+    Some(jobs.swap_remove(index))
+}
+
+/// removes a successfully executed job from the queue
+pub fn complete_job(job: &OffchainJob) {
+    let mut queue = get_job_queue();
+    queue.retain(|queued| &queued.job != job);
+    set_job_queue(&queue);
+}
+
+/// records a failed attempt. the job is either retried after an exponential backoff
+/// or, after `MAX_ATTEMPTS` failed attempts, moved to the dead-letter log.
+pub fn fail_job(job: &OffchainJob, block_number: u64) {
+    let mut queue = get_job_queue();
+    let position = match queue.iter().position(|queued| &queued.job == job) {
+        Some(position) => position,
+        None => return,
+    };
+
+    let attempts = queue[position].attempts + 1;
+    if attempts >= MAX_ATTEMPTS {
+        let queued = queue.remove(position);
+        debug::error!(
+            "offchain job failed {:?} times, moved to dead-letter log: {:?}",
+            attempts,
+            queued.job
+        );
+        push_dead_letter(DeadLetter {
+            job: queued.job,
+            attempts,
+            failed_at: block_number,
+        });
+        metrics::record_dead_letter();
+    } else {
+        let next_attempt = block_number + backoff(attempts);
+        debug::warn!(
+            "offchain job failed (attempt: {:?}), retry at block: {:?}, job: {:?}",
+            attempts,
+            next_attempt,
+            job
+        );
+        queue[position].attempts = attempts;
+        queue[position].next_attempt = next_attempt;
+    }
+    set_job_queue(&queue);
+}
@@ -0,0 +1,56 @@
+use crate::types::{OffchainMetrics, OFFCHAIN_METRICS_KEY};
+use sp_runtime::offchain::storage::StorageValueRef;
+
+/// returns the metrics recorded by the offchain worker
+pub fn get_metrics() -> OffchainMetrics {
+    let storage = StorageValueRef::persistent(OFFCHAIN_METRICS_KEY);
+    storage
+        .get::<OffchainMetrics>()
+        .flatten()
+        .unwrap_or_default()
+}
+
+fn update_metrics(update: impl FnOnce(&mut OffchainMetrics)) {
+    let storage = StorageValueRef::persistent(OFFCHAIN_METRICS_KEY);
+    let mut metrics = get_metrics();
+    update(&mut metrics);
+    storage.set(&metrics);
+}
+
+/// the current time of the offchain worker in milliseconds
+pub fn now_ms() -> u64 {
+    sp_io::offchain::timestamp().unix_millis()
+}
+
+/// records the shuffle of a batch of ciphers and the generation of its proof
+pub fn record_shuffle(nr_of_ciphers: u64, shuffle_ms: u64, proof_generation_ms: u64) {
+    update_metrics(|metrics| {
+        metrics.ciphers_shuffled = metrics.ciphers_shuffled.saturating_add(nr_of_ciphers);
+        metrics.shuffle_ms.observe(shuffle_ms);
+        metrics.proof_generation_ms.observe(proof_generation_ms);
+    });
+}
+
+pub fn record_commitment() {
+    update_metrics(|metrics| {
+        metrics.shuffles_committed = metrics.shuffles_committed.saturating_add(1);
+    });
+}
+
+pub fn record_reveal() {
+    update_metrics(|metrics| {
+        metrics.shuffles_revealed = metrics.shuffles_revealed.saturating_add(1);
+    });
+}
+
+pub fn record_failed_submission() {
+    update_metrics(|metrics| {
+        metrics.submissions_failed = metrics.submissions_failed.saturating_add(1);
+    });
+}
+
+pub fn record_dead_letter() {
+    update_metrics(|metrics| {
+        metrics.jobs_dead_lettered = metrics.jobs_dead_lettered.saturating_add(1);
+    });
+}
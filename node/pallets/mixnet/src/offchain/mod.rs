@@ -1,30 +1,66 @@
+pub mod checkpoint;
+pub mod decrypt;
+pub mod generators;
+mod heartbeat;
+pub mod jobs;
+pub mod metrics;
+pub mod results;
+mod reveal;
 mod send;
+pub mod sizing;
 
 use crate::{
-    helpers::{array::get_slice, assertions::ensure_vote_exists, params::get_public_key},
+    helpers::{
+        assertions::ensure_vote_exists,
+        ciphers::get_ciphers_in_range,
+        credentials::is_credential_check_pending,
+        freeze::get_shuffle_context,
+        params::{get_hash_function, get_protocol_config, get_public_key},
+    },
+    shuffle::{
+        commitment::{hash_shuffle_payload, is_commitment_expired},
+        linked::is_linked_shuffle,
+        turn::get_shuffle_sealer,
+    },
     types::{
-        Ballot, Cipher, PublicKey as SubstratePK, ShufflePayload, ShuffleProof,
-        ShuffleState, Topic, TopicId, Vote, VoteId, VotePhase, Wrapper,
+        Ballot, Cipher, LinkedShufflePayload, NrOfShuffles, PublicKey as SubstratePK,
+        ShuffleCommitment, ShufflePayload, ShuffleProof, ShuffleState, ShuffleSubmission,
+        Topic, TopicId, Vote, VoteId, VotePhase, Wrapper,
     },
 };
 use crate::{
-    Call, Ciphers, Error, Module, Sealers, ShuffleStateStore, Topics, Trait, VoteIds,
-    Votes,
+    Call, Error, Module, Sealers, ShuffleCommitments, ShuffleStateStore, Topics, Trait,
+    VoteIds, Votes,
 };
-use core::convert::TryInto;
+use core::{cell::Cell, convert::TryInto};
 use crypto::{
-    encryption::ElGamal, types::Cipher as BigCipher, types::PublicKey as ElGamalPK,
+    encryption::ElGamal, secret, types::Cipher as BigCipher,
+    types::PublicKey as ElGamalPK,
 };
 use frame_support::{
     debug,
-    storage::{StorageDoubleMap, StorageMap, StorageValue},
+    storage::{StorageMap, StorageValue},
     traits::Get,
 };
-use frame_system::offchain::{Account, SendSignedTransaction, Signer};
+use frame_system::offchain::{
+    Account, SendSignedTransaction, SendUnsignedTransaction, Signer,
+};
+use jobs::OffchainJob;
 use num_bigint::BigUint;
 use send::send_signed;
 use sp_std::{vec, vec::Vec};
 
+/// the step of the commit-reveal protocol a sealer performs for a shuffle job
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ShuffleAction {
+    /// shuffle the ciphers and commit to the hash of the payload
+    Commit,
+    /// submit the payload committed to in an earlier block
+    Reveal,
+    /// it is neither the sealer's turn nor does it have a pending reveal
+    Wait,
+}
+
 impl<T: Trait> Module<T> {
     pub fn offchain_signed_tx(
         block_number: T::BlockNumber,
@@ -53,7 +89,12 @@ impl<T: Trait> Module<T> {
         // encrypt the current block number
         let cipher: Cipher = ElGamal::encrypt_encode(&number_as_biguint, &r, &pk).into();
         let answers: Vec<(TopicId, Cipher)> = vec![(topic_id, cipher)];
-        let ballot: Ballot = Ballot { answers };
+        let ballot: Ballot = Ballot {
+            answers,
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
 
         return send_signed::<T>(
             signer,
@@ -61,6 +102,8 @@ impl<T: Trait> Module<T> {
         );
     }
 
+    /// shuffles the ciphers of the votes being tallied and decrypts the topics whose
+    /// shuffles are done, a single job is executed per block
     pub fn offchain_shuffling(block_number: T::BlockNumber) -> Result<(), Error<T>> {
         // if the offchain worker is not a validator, we don't shuffle the votes
         if !sp_io::offchain::is_validator() {
@@ -79,26 +122,52 @@ impl<T: Trait> Module<T> {
         let vote_ids: Vec<VoteId> = VoteIds::get();
         debug::info!("vote_ids: {:?}", vote_ids);
 
+        // the topics are only decrypted by the sealers with a local account
+        let local_account: Option<T::AccountId> =
+            Self::get_local_account().ok().map(|account| account.id);
+
+        // derive the jobs from the on-chain state
+        let mut derived_jobs: Vec<OffchainJob> = Vec::new();
         for vote_id in vote_ids.iter() {
             // check vote state -> TALLYING
             let vote: Vote<T::AccountId> = Votes::<T>::get(&vote_id);
             let state: VotePhase = vote.phase;
+            let nr_of_shuffles = vote.nr_of_shuffles;
+
+            // the private key share of the local sealer is only kept while it is needed
+            Self::clear_obsolete_sealer_sk(vote_id, local_account.as_ref());
 
             // early return if the vote is not in
             if state != VotePhase::Tallying {
                 continue;
             }
 
+            // the shuffles start once the credentials of the ballots have been checked
+            if is_credential_check_pending::<T>(vote_id) {
+                debug::info!("credential check pending, skipping vote_id: {:?}", vote_id);
+                continue;
+            }
+
+            // the proofs would be rejected if the protocol of the vote is not supported
+            if get_protocol_config::<T>(vote_id).is_err() {
+                debug::warn!("unsupported protocol, skipping vote_id: {:?}", vote_id);
+                continue;
+            }
+
             debug::info!("vote_id: {:?}, state: VotePhase::Tallying", vote_id);
 
             // get all topics
             let topics: Vec<Topic> = Topics::get(vote_id);
 
-            // get public key
-            let pk: SubstratePK = get_public_key::<T>(&vote_id)?;
-            let pk: ElGamalPK = pk.into();
+            // the topics of a vote with linked shuffles are shuffled in a single job,
+            // the job of the first topic (lead topic)
+            let nr_of_jobs = if is_linked_shuffle(vote_id) {
+                1
+            } else {
+                topics.len()
+            };
 
-            for (topic_id, _) in topics.iter() {
+            for Topic { id: topic_id, .. } in topics.iter().take(nr_of_jobs) {
                 // get shuffle state
                 let shuffle_state: ShuffleState = ShuffleStateStore::get((
                     vote_id, topic_id,
@@ -107,55 +176,355 @@ impl<T: Trait> Module<T> {
                 debug::info!("shuffle_state: {:?}", shuffle_state);
 
                 // if the shuffling has been completed -> skip to next topic
-                if shuffle_state.done {
+                if shuffle_state.done || shuffle_state.iteration >= nr_of_shuffles {
                     continue;
                 }
 
-                // check who's turn it is
-                let sealers: Vec<T::AccountId> = Sealers::<T>::get();
-                let current_sealer = Self::get_current_sealer(block_number, sealers);
+                derived_jobs.push(OffchainJob::Shuffle {
+                    vote_id: vote_id.clone(),
+                    topic_id: topic_id.clone(),
+                    iteration: shuffle_state.iteration,
+                    start_position: shuffle_state.start_position,
+                });
+            }
 
-                // get the signer for the transaction
-                let signer = Signer::<T, T::AuthorityId>::any_account();
+            // the topics whose shuffles are done are decrypted by the local sealer
+            if let Some(sealer) = &local_account {
+                derived_jobs.extend(Self::get_decryption_jobs(
+                    vote_id,
+                    &topics,
+                    nr_of_shuffles,
+                    sealer,
+                ));
+            }
+        }
 
-                // if it's the current_sealer's turn, then shuffle + submit ciphers + proof
-                // else, submit empty transaction
-                let transaction_response = signer.send_signed_transaction(|_acct| {
-                    let local_address = &_acct.id;
+        // persist the jobs, keeps the retry state of jobs which previously failed
+        jobs::sync_jobs(derived_jobs);
+
+        // only execute the jobs whose backoff period has passed.
+        // a single job is executed per block, otherwise the signed transactions of
+        // concurrent votes would be sent with the same account nonce.
+        let number: u64 = block_number.try_into().unwrap_or(0u64) as u64;
+        // the sealers of new votes, only used to pace the job rotation
+        let nr_of_sealers = Sealers::<T>::get().len() as u64;
+        let due_jobs = jobs::get_due_jobs(number);
+
+        // a pending reveal takes precedence, otherwise the commitment could expire
+        // before the job is selected again
+        let pending = due_jobs.iter().find(|job| match job {
+            OffchainJob::Shuffle {
+                vote_id, topic_id, ..
+            } => Self::has_pending_reveal(vote_id, topic_id),
+            OffchainJob::Decrypt { .. } => false,
+        });
+        if let Some(job) = pending {
+            return Self::execute_job(block_number, job);
+        }
+
+        match jobs::select_job(due_jobs, number, nr_of_sealers) {
+            Some(job) => Self::execute_job(block_number, &job),
+            None => Ok(()),
+        }
+    }
 
-                    if current_sealer.eq(local_address) {
-                        debug::info!("my turn!");
-                        // shuffle ciphers + create proof
-                        let payload_response = Self::offchain_shuffle_and_proof(
-                            &topic_id,
-                            shuffle_state.iteration,
-                            &pk,
-                            shuffle_state.start_position,
-                            shuffle_state.batch_size,
-                        );
-                        let payload: ShufflePayload = payload_response.unwrap();
-                        Call::submit_shuffled_votes_and_proof(
-                            vote_id.to_vec(),
-                            topic_id.to_vec(),
-                            payload,
+    /// executes a single job of the offchain worker job queue
+    fn execute_job(
+        block_number: T::BlockNumber,
+        job: &OffchainJob,
+    ) -> Result<(), Error<T>> {
+        match job {
+            OffchainJob::Shuffle {
+                vote_id,
+                topic_id,
+                iteration,
+                start_position,
+            } => Self::execute_shuffle_job(
+                block_number,
+                job,
+                vote_id,
+                topic_id,
+                iteration,
+                start_position,
+            ),
+            OffchainJob::Decrypt {
+                vote_id,
+                topic_id,
+                nr_of_shuffles,
+            } => Self::execute_decryption_job(
+                block_number,
+                job,
+                vote_id,
+                topic_id,
+                *nr_of_shuffles,
+            ),
+        }
+    }
+
+    /// shuffles (and commits to) or reveals the next batch of ciphers of the topic
+    fn execute_shuffle_job(
+        block_number: T::BlockNumber,
+        job: &OffchainJob,
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+        iteration: &NrOfShuffles,
+        start_position: &u64,
+    ) -> Result<(), Error<T>> {
+        // get public key
+        let pk: SubstratePK = get_public_key::<T>(&vote_id)?;
+        let pk: ElGamalPK = pk.into();
+
+        // get the batch size of the shuffle configured on chain
+        let shuffle_state: ShuffleState = ShuffleStateStore::get((vote_id, topic_id))
+            .expect("shuffle state should exist for all existing votes & topics!");
+
+        // the batch is sized to the measured shuffle costs,
+        // at most the batch size configured on chain is shuffled
+        let linked = is_linked_shuffle(vote_id);
+        let nr_of_topics = if linked {
+            Topics::get(vote_id).len() as u64
+        } else {
+            1
+        };
+        let batch_size = sizing::get_batch_size(
+            pk.params.p.bits(),
+            nr_of_topics,
+            T::ShuffleTimeBudget::get(),
+            T::MinShuffleBatchSize::get(),
+            shuffle_state.batch_size,
+        );
+
+        // check who's turn it is, the turns are stored on chain
+        let current_sealer =
+            match get_shuffle_sealer::<T>(vote_id, topic_id, block_number) {
+                Some(sealer) => sealer,
+                None => return Ok(()),
+            };
+        debug::info!("current turn: sealer {:?}", current_sealer);
+
+        // get the signer for the transaction
+        let signer = Signer::<T, T::AuthorityId>::any_account();
+
+        // the shuffle is committed to first and revealed in a later block.
+        // if it's the current_sealer's turn, then shuffle + commit to ciphers + proof,
+        // if the sealer committed earlier, then submit ciphers + proof
+        // else, submit empty transaction
+        let commitment = ShuffleCommitments::<T>::get((vote_id, topic_id));
+        let performed: Cell<ShuffleAction> = Cell::new(ShuffleAction::Wait);
+        let transaction_response =
+            if Self::can_reveal_unsigned(block_number, &commitment, vote_id, topic_id) {
+                // the shuffle is revealed as an unsigned transaction signed by the sealer,
+                // i.e., the sealer doesn't pay for the verification of the shuffle proof
+                debug::info!("revealing committed shuffle (unsigned)");
+                performed.set(ShuffleAction::Reveal);
+                let payload: ShufflePayload =
+                    reveal::get_pending_reveal(vote_id, topic_id)
+                        .expect("pending reveal has been checked");
+                signer.send_unsigned_transaction(
+                    |acct| ShuffleSubmission {
+                        vote_id: vote_id.to_vec(),
+                        topic_id: topic_id.to_vec(),
+                        payload: payload.clone(),
+                        public: acct.public.clone(),
+                    },
+                    |submission, signature| {
+                        Call::submit_shuffled_votes_and_proof_unsigned(
+                            submission, signature,
                         )
-                    // do nothing in case that it is not this sealer's turn
-                    } else {
-                        debug::info!("not my turn!");
-                        Call::do_nothing_when_its_not_your_turn()
+                    },
+                )
+            } else {
+                signer.send_signed_transaction(|_acct| {
+                    let local_address = &_acct.id;
+                    let action = Self::get_shuffle_action(
+                        block_number,
+                        local_address,
+                        &current_sealer,
+                        &commitment,
+                        vote_id,
+                        topic_id,
+                    );
+                    performed.set(action);
+
+                    match action {
+                        ShuffleAction::Commit if linked => {
+                            debug::info!("my turn! committing to linked shuffle");
+                            // shuffle the ciphers of all topics with the same permutation + create proofs
+                            let topic_ids: Vec<TopicId> = Topics::get(vote_id)
+                                .into_iter()
+                                .map(|topic| topic.id)
+                                .collect();
+                            let payload_response =
+                                Self::offchain_linked_shuffle_and_proof(
+                                    &vote_id,
+                                    &topic_ids,
+                                    *iteration,
+                                    &pk,
+                                    *start_position,
+                                    batch_size,
+                                );
+                            let payload: LinkedShufflePayload = payload_response.unwrap();
+                            reveal::store_pending_reveal(vote_id, topic_id, &payload);
+                            Call::commit_shuffle(
+                                vote_id.to_vec(),
+                                topic_id.to_vec(),
+                                hash_shuffle_payload(&payload),
+                            )
+                        }
+                        ShuffleAction::Commit => {
+                            debug::info!("my turn! committing to shuffle");
+                            // shuffle ciphers + create proof
+                            let payload_response = Self::offchain_shuffle_and_proof(
+                                &vote_id,
+                                &topic_id,
+                                *iteration,
+                                &pk,
+                                *start_position,
+                                batch_size,
+                            );
+                            let payload: ShufflePayload = payload_response.unwrap();
+                            reveal::store_pending_reveal(vote_id, topic_id, &payload);
+                            Call::commit_shuffle(
+                                vote_id.to_vec(),
+                                topic_id.to_vec(),
+                                hash_shuffle_payload(&payload),
+                            )
+                        }
+                        ShuffleAction::Reveal if linked => {
+                            debug::info!("revealing committed linked shuffle");
+                            let payload: LinkedShufflePayload =
+                                reveal::get_pending_reveal(vote_id, topic_id)
+                                    .expect("pending reveal has been checked");
+                            Call::submit_linked_shuffle(vote_id.to_vec(), payload)
+                        }
+                        ShuffleAction::Reveal => {
+                            debug::info!("revealing committed shuffle");
+                            let payload: ShufflePayload =
+                                reveal::get_pending_reveal(vote_id, topic_id)
+                                    .expect("pending reveal has been checked");
+                            Call::submit_shuffled_votes_and_proof(
+                                vote_id.to_vec(),
+                                topic_id.to_vec(),
+                                payload,
+                            )
+                        }
+                        // do nothing in case that it is not this sealer's turn
+                        ShuffleAction::Wait => {
+                            debug::info!("not my turn!");
+                            Call::do_nothing_when_its_not_your_turn()
+                        }
                     }
-                });
-                Self::handle_transaction_response(
-                    &vote_id,
-                    &current_sealer,
-                    transaction_response,
-                )?;
+                })
+            };
+
+        // update the job queue, only the sealer which commits or reveals executes the job.
+        // the job is completed once the shuffle has been revealed.
+        let number: u64 = block_number.try_into().unwrap_or(0u64) as u64;
+        if let Some((_, res)) = &transaction_response {
+            if res.is_err() {
+                metrics::record_failed_submission();
+            } else {
+                heartbeat::record_sent(number);
             }
+            match (performed.get(), res) {
+                (ShuffleAction::Wait, _) => (),
+                (ShuffleAction::Commit, Ok(_)) => metrics::record_commitment(),
+                (ShuffleAction::Reveal, Ok(_)) => {
+                    metrics::record_reveal();
+                    reveal::clear_pending_reveal(vote_id, topic_id);
+                    jobs::complete_job(job);
+                }
+                (_, Err(_)) => jobs::fail_job(job, number),
+            }
+        }
+        Self::handle_transaction_response(&vote_id, &current_sealer, transaction_response)
+    }
+
+    /// true if this node committed to a shuffle (without linked shuffles) in an earlier block,
+    /// such a shuffle is revealed as an unsigned transaction (see: `validate_unsigned`).
+    /// linked shuffles are revealed as signed transactions.
+    fn can_reveal_unsigned(
+        block_number: T::BlockNumber,
+        commitment: &Option<ShuffleCommitment<T::AccountId, T::BlockNumber>>,
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+    ) -> bool {
+        match commitment {
+            Some(pending) => {
+                !is_linked_shuffle(vote_id)
+                    && block_number > pending.block_number
+                    && Self::has_pending_reveal(vote_id, topic_id)
+            }
+            None => false,
+        }
+    }
+
+    /// true if this node stored the payload of the pending on-chain commitment
+    fn has_pending_reveal(vote_id: &VoteId, topic_id: &TopicId) -> bool {
+        match (
+            ShuffleCommitments::<T>::get((vote_id, topic_id)),
+            Self::get_pending_reveal_hash(vote_id, topic_id),
+        ) {
+            (Some(pending), Some(hash)) => hash == pending.commitment,
+            _ => false,
+        }
+    }
+
+    /// the hash of the payload this node committed to, if any
+    fn get_pending_reveal_hash(vote_id: &VoteId, topic_id: &TopicId) -> Option<[u8; 32]> {
+        if is_linked_shuffle(vote_id) {
+            reveal::get_pending_reveal::<LinkedShufflePayload>(vote_id, topic_id)
+                .map(|payload| hash_shuffle_payload(&payload))
+        } else {
+            reveal::get_pending_reveal::<ShufflePayload>(vote_id, topic_id)
+                .map(|payload| hash_shuffle_payload(&payload))
+        }
+    }
+
+    /// derives the step of the commit-reveal protocol the local sealer performs
+    fn get_shuffle_action(
+        block_number: T::BlockNumber,
+        local_address: &T::AccountId,
+        current_sealer: &T::AccountId,
+        commitment: &Option<ShuffleCommitment<T::AccountId, T::BlockNumber>>,
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+    ) -> ShuffleAction {
+        let is_current_sealer = current_sealer.eq(local_address);
+        match commitment {
+            // no pending commitment -> the current sealer commits
+            None if is_current_sealer => ShuffleAction::Commit,
+            None => ShuffleAction::Wait,
+            Some(pending) if pending.sealer.eq(local_address) => {
+                match Self::get_pending_reveal_hash(vote_id, topic_id) {
+                    // the commitment can only be revealed in a later block
+                    Some(hash) if hash == pending.commitment => {
+                        if block_number > pending.block_number {
+                            ShuffleAction::Reveal
+                        } else {
+                            ShuffleAction::Wait
+                        }
+                    }
+                    // the committed payload has been lost -> commit again
+                    _ if is_current_sealer => ShuffleAction::Commit,
+                    _ => ShuffleAction::Wait,
+                }
+            }
+            // another sealer didn't reveal in time -> take over
+            Some(pending)
+                if is_current_sealer
+                    && is_commitment_expired::<T>(pending, block_number) =>
+            {
+                ShuffleAction::Commit
+            }
+            Some(_) => ShuffleAction::Wait,
         }
-        Ok(())
     }
 
+    /// shuffles the batch of ciphers of the topic and generates the shuffle proof,
+    /// the challenge of the proof is bound to the ballot box freeze of the vote
     pub fn offchain_shuffle_and_proof(
+        vote_id: &VoteId,
         topic_id: &TopicId,
         iteration: u8,
         pk: &ElGamalPK,
@@ -165,32 +534,58 @@ impl<T: Trait> Module<T> {
         // get all encrypted votes (ciphers)
         // for the topic with id: topic_id and the # of shuffles (iteration)
         debug::info!("topic_id: {:?}", topic_id);
-        let ciphers: Vec<Cipher> = Ciphers::get(&topic_id, iteration);
+        // only the ciphers of the computed range are retrieved
+        let ciphers: Vec<Cipher> = get_ciphers_in_range::<T>(
+            vote_id,
+            &topic_id,
+            iteration,
+            start_position,
+            batch_size,
+        );
 
         // type conversion: Cipher (Vec<u8>) to BigCipher (BigUint)
-        let encryptions: Vec<BigCipher> = Wrapper(ciphers).into();
-
-        // retrieve the ciphers for the computed range
-        let slice =
-            get_slice::<T, BigCipher>(encryptions.clone(), start_position, batch_size);
+        let slice: Vec<BigCipher> = Wrapper(ciphers).into();
 
         // for each topic_id & vote_id
         // shuffle the votes
-        let (shuffled_slice, re_encryption_randoms, permutation): (
+        let nr_of_ciphers = slice.len() as u64;
+        let started = metrics::now_ms();
+        let (shuffled_slice, re_encryption_randoms, mut permutation): (
             Vec<BigCipher>,
             Vec<BigUint>,
             Vec<usize>,
         ) = Self::shuffle_ciphers(&pk, slice.to_vec())?;
+        let shuffled = metrics::now_ms();
 
-        // generate the shuffle proof
-        let proof: ShuffleProof = Self::generate_shuffle_proof(
+        // generate the shuffle proof, the generators are reused across the batches
+        let vec_h = generators::get_generators(
             &topic_id,
+            &pk.params.p,
+            &pk.params.q(),
+            slice.len(),
+        );
+        let proof: ShuffleProof = Self::generate_shuffle_proof_with_generators(
             slice,
             shuffled_slice.clone(),
             re_encryption_randoms,
             &permutation,
+            vec_h,
             &pk,
+            &get_shuffle_context::<T>(vote_id),
+            get_hash_function::<T>(vote_id),
         )?;
+        secret::wipe_permutation(&mut permutation);
+        let proven = metrics::now_ms();
+        metrics::record_shuffle(
+            nr_of_ciphers,
+            shuffled.saturating_sub(started),
+            proven.saturating_sub(shuffled),
+        );
+        sizing::record_batch(
+            pk.params.p.bits(),
+            nr_of_ciphers,
+            proven.saturating_sub(started),
+        );
 
         // create transaction payload
         let payload = ShufflePayload {
@@ -203,19 +598,94 @@ impl<T: Trait> Module<T> {
         Ok(payload)
     }
 
-    /// retrieves the current sealer, depends on the block number
-    fn get_current_sealer(
-        block_number: T::BlockNumber,
-        sealers: Vec<T::AccountId>,
-    ) -> T::AccountId {
-        let n: T::BlockNumber = (sealers.len() as u32).into();
-        let index = block_number % n;
-        let index_as_u64 = TryInto::<u64>::try_into(index)
-            .ok()
-            .expect("BockNumber to u64 type conversion failed!");
-        let sealer: T::AccountId = sealers[index_as_u64 as usize].clone();
-        debug::info!("current turn: sealer {:?} (index: {:?})", sealer, index);
-        sealer
+    /// shuffles the same batch of all topics of a vote with linked shuffles
+    /// with the same permutation and generates the proofs with a shared permutation commitment
+    pub fn offchain_linked_shuffle_and_proof(
+        vote_id: &VoteId,
+        topic_ids: &[TopicId],
+        iteration: u8,
+        pk: &ElGamalPK,
+        start_position: u64,
+        batch_size: u64,
+    ) -> Result<LinkedShufflePayload, Error<T>> {
+        // only the ciphers of the computed range are retrieved
+        let slices: Vec<Vec<BigCipher>> = topic_ids
+            .iter()
+            .map(|topic_id| {
+                let ciphers: Vec<Cipher> = get_ciphers_in_range::<T>(
+                    vote_id,
+                    topic_id,
+                    iteration,
+                    start_position,
+                    batch_size,
+                );
+                Wrapper(ciphers).into()
+            })
+            .collect();
+
+        // shuffle the ciphers of all topics with the same permutation
+        let nr_of_ciphers: u64 = slices.iter().map(|slice| slice.len() as u64).sum();
+        let started = metrics::now_ms();
+        let (mut shuffles, mut permutation) =
+            Self::shuffle_linked_ciphers(&pk, slices.clone())?;
+        let shuffled = metrics::now_ms();
+
+        // generate the shuffle proofs, all proofs share the permutation commitment
+        let proof_inputs: Vec<(Vec<BigCipher>, Vec<BigCipher>, Vec<BigUint>)> = slices
+            .into_iter()
+            .zip(shuffles.iter())
+            .map(|(slice, (shuffled_slice, randoms))| {
+                (slice, shuffled_slice.clone(), randoms.clone())
+            })
+            .collect();
+        let vec_h = generators::get_generators(
+            vote_id,
+            &pk.params.p,
+            &pk.params.q(),
+            permutation.len(),
+        );
+        let proofs: Vec<ShuffleProof> =
+            Self::generate_linked_shuffle_proofs_with_generators(
+                proof_inputs,
+                &permutation,
+                vec_h,
+                &pk,
+                &get_shuffle_context::<T>(vote_id),
+                get_hash_function::<T>(vote_id),
+            )?;
+        secret::wipe_permutation(&mut permutation);
+        shuffles
+            .iter_mut()
+            .for_each(|(_, randoms)| secret::wipe_all(randoms));
+        let proven = metrics::now_ms();
+        metrics::record_shuffle(
+            nr_of_ciphers,
+            shuffled.saturating_sub(started),
+            proven.saturating_sub(shuffled),
+        );
+        sizing::record_batch(
+            pk.params.p.bits(),
+            nr_of_ciphers,
+            proven.saturating_sub(started),
+        );
+
+        // create transaction payload
+        let payload: LinkedShufflePayload = topic_ids
+            .iter()
+            .cloned()
+            .zip(shuffles.into_iter().zip(proofs.into_iter()))
+            .map(|(topic_id, ((shuffled_slice, _), proof))| {
+                let shuffle = ShufflePayload {
+                    ciphers: Wrapper(shuffled_slice).into(),
+                    proof: proof.into(),
+                    iteration,
+                    start_position,
+                    batch_size,
+                };
+                (topic_id, shuffle)
+            })
+            .collect();
+        Ok(payload)
     }
 
     fn handle_transaction_response(
@@ -0,0 +1,168 @@
+use crate::types::{Topic, TopicId, TopicResult, VoteId};
+use crate::{Error, Module, Tally, Topics, Trait, VoteIds};
+use alloc::{format, string::String};
+use codec::Encode;
+use core::str;
+use frame_support::{
+    debug,
+    storage::{StorageMap, StorageValue},
+};
+use frame_system::offchain::{SignMessage, Signer};
+use num_bigint::BigUint;
+use sp_core::{hexdisplay::HexDisplay, offchain::Duration};
+use sp_runtime::offchain::{http, storage::StorageValueRef, StorageKind};
+use sp_std::{vec, vec::Vec};
+
+/// offchain local storage key of the URL the results are posted to.
+/// the value is the raw (utf-8) URL, e.g. set using the RPC `offchain_localStorageSet`.
+pub const RESULTS_URL_KEY: &[u8] = b"provotum::mixnet::ocw::results_url";
+
+/// offchain local storage key of the topics whose results have already been posted
+const POSTED_RESULTS_KEY: &[u8] = b"provotum::mixnet::ocw::posted_results";
+
+/// the time after which a request to the results endpoint is aborted (in ms)
+const HTTP_TIMEOUT: u64 = 5_000;
+
+/// returns the configured URL of the results endpoint, if any
+pub fn get_results_url() -> Option<String> {
+    let url =
+        sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, RESULTS_URL_KEY)?;
+    match String::from_utf8(url) {
+        Ok(url) if !url.is_empty() => Some(url),
+        _ => None,
+    }
+}
+
+/// returns the (vote, topic) pairs whose results have already been posted
+pub fn get_posted_results() -> Vec<(VoteId, TopicId)> {
+    let storage = StorageValueRef::persistent(POSTED_RESULTS_KEY);
+    storage
+        .get::<Vec<(VoteId, TopicId)>>()
+        .flatten()
+        .unwrap_or_default()
+}
+
+fn set_posted_results(posted: &[(VoteId, TopicId)]) {
+    let storage = StorageValueRef::persistent(POSTED_RESULTS_KEY);
+    storage.set(&posted);
+}
+
+/// encodes an identifier as JSON string, identifiers which are not valid utf-8 are hex encoded
+fn json_string(bytes: &[u8]) -> String {
+    match str::from_utf8(bytes) {
+        Ok(value) => {
+            let mut escaped = String::from("\"");
+            for c in value.chars() {
+                match c {
+                    '"' => escaped.push_str("\\\""),
+                    '\\' => escaped.push_str("\\\\"),
+                    c if (c as u32) < 0x20 => {
+                        escaped.push_str(&format!("\\u{:04x}", c as u32))
+                    }
+                    c => escaped.push(c),
+                }
+            }
+            escaped.push('"');
+            escaped
+        }
+        Err(_) => format!("\"0x{}\"", HexDisplay::from(&bytes)),
+    }
+}
+
+/// serializes the result of a topic to JSON:
+/// {"vote_id":"..","topic_id":"..","result":[{"vote":"1","count":"3"},..]}
+/// votes and counts are decimal strings since they can exceed the range of JSON numbers.
+pub fn results_json(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    result: &TopicResult,
+) -> Vec<u8> {
+    let entries: Vec<String> = result
+        .iter()
+        .map(|(vote, count)| {
+            format!(
+                "{{\"vote\":\"{}\",\"count\":\"{}\"}}",
+                BigUint::from_bytes_be(vote),
+                BigUint::from_bytes_be(count)
+            )
+        })
+        .collect();
+    format!(
+        "{{\"vote_id\":{},\"topic_id\":{},\"result\":[{}]}}",
+        json_string(vote_id),
+        json_string(topic_id),
+        entries.join(",")
+    )
+    .into_bytes()
+}
+
+impl<T: Trait> Module<T> {
+    /// posts the result of every tallied topic to the configured results endpoint (once per topic).
+    /// nothing is posted if no URL is configured in the offchain local storage.
+    pub fn offchain_post_results() -> Result<(), Error<T>> {
+        let url = match get_results_url() {
+            Some(url) => url,
+            None => return Ok(()),
+        };
+
+        let mut posted = get_posted_results();
+        for vote_id in VoteIds::get().iter() {
+            for Topic { id: topic_id, .. } in Topics::get(vote_id).iter() {
+                let key = (vote_id.clone(), topic_id.clone());
+                if posted.contains(&key) {
+                    continue;
+                }
+                let result: TopicResult = match Tally::get(&key) {
+                    Some(result) => result,
+                    None => continue,
+                };
+
+                let payload = results_json(vote_id, topic_id, &result);
+                Self::post_signed_payload(&url, payload)?;
+                debug::info!(
+                    "posted results: vote_id: {:?}, topic_id: {:?}",
+                    vote_id,
+                    topic_id
+                );
+
+                posted.push(key);
+                set_posted_results(&posted);
+            }
+        }
+        Ok(())
+    }
+
+    /// POSTs the payload to the URL. the payload is signed by the node's authority key,
+    /// the signer (account id) and the signature are SCALE encoded and sent hex encoded as headers.
+    fn post_signed_payload(url: &str, payload: Vec<u8>) -> Result<(), Error<T>> {
+        let signer = Signer::<T, T::AuthorityId>::any_account();
+        let (account, signature) = signer
+            .sign_message(&payload)
+            .ok_or(Error::<T>::NoLocalAcctForSigning)?;
+        let signer_hex = format!("0x{}", HexDisplay::from(&account.id.encode()));
+        let signature_hex = format!("0x{}", HexDisplay::from(&signature.encode()));
+
+        let deadline =
+            sp_io::offchain::timestamp().add(Duration::from_millis(HTTP_TIMEOUT));
+        let pending = http::Request::post(url, vec![payload])
+            .add_header("Content-Type", "application/json")
+            .add_header("X-Provotum-Signer", &signer_hex)
+            .add_header("X-Provotum-Signature", &signature_hex)
+            .deadline(deadline)
+            .send()
+            .map_err(|_| Error::<T>::ResultsPostError)?;
+        let response = pending
+            .try_wait(deadline)
+            .map_err(|_| Error::<T>::ResultsPostError)?
+            .map_err(|_| Error::<T>::ResultsPostError)?;
+
+        if response.code < 200 || response.code >= 300 {
+            debug::error!(
+                "results endpoint responded with status: {:?}",
+                response.code
+            );
+            return Err(Error::<T>::ResultsPostError);
+        }
+        Ok(())
+    }
+}
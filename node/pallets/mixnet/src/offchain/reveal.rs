@@ -0,0 +1,39 @@
+use crate::types::{ShufflePayload, TopicId, VoteId};
+use codec::{Decode, Encode};
+use sp_runtime::offchain::storage::StorageValueRef;
+use sp_std::vec::Vec;
+
+/// offchain local storage key prefix of the shuffle payloads which await their reveal
+const SHUFFLE_REVEAL_PREFIX: &[u8] = b"provotum::mixnet::ocw::shuffle_reveal::";
+
+fn shuffle_reveal_key(vote_id: &VoteId, topic_id: &TopicId) -> Vec<u8> {
+    let mut key: Vec<u8> = SHUFFLE_REVEAL_PREFIX.to_vec();
+    key.extend((vote_id, topic_id).encode());
+    key
+}
+
+/// returns the shuffle payload this node committed to, if any.
+/// the payload of a vote with linked shuffles is a LinkedShufflePayload.
+pub fn get_pending_reveal<P: Decode>(vote_id: &VoteId, topic_id: &TopicId) -> Option<P> {
+    let key = shuffle_reveal_key(vote_id, topic_id);
+    let storage = StorageValueRef::persistent(&key);
+    storage.get::<Option<P>>().flatten().flatten()
+}
+
+/// keeps the shuffle payload until it can be revealed in a later block
+pub fn store_pending_reveal<P: Encode>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    payload: &P,
+) {
+    let key = shuffle_reveal_key(vote_id, topic_id);
+    let storage = StorageValueRef::persistent(&key);
+    storage.set(&Some(payload));
+}
+
+/// removes the shuffle payload once it has been revealed
+pub fn clear_pending_reveal(vote_id: &VoteId, topic_id: &TopicId) {
+    let key = shuffle_reveal_key(vote_id, topic_id);
+    let storage = StorageValueRef::persistent(&key);
+    storage.set(&None::<ShufflePayload>);
+}
@@ -1,3 +1,4 @@
+use super::metrics;
 use crate::{Call, Error, Trait};
 use frame_support::debug;
 use frame_system::offchain::{SendSignedTransaction, Signer};
@@ -16,6 +17,7 @@ pub fn send_signed<T: Trait>(
     if let Some((acc, res)) = result {
         if res.is_err() {
             debug::error!("failure: offchain_signed_tx: tx sent: {:?}", acc.id);
+            metrics::record_failed_submission();
             return Err(<Error<T>>::OffchainSignedTxError);
         }
         // Transaction is sent successfully
@@ -0,0 +1,79 @@
+use codec::{Decode, Encode};
+use sp_runtime::offchain::storage::StorageValueRef;
+use sp_std::vec::Vec;
+
+/// offchain local storage key of the measured shuffle costs
+const CIPHER_COSTS_KEY: &[u8] = b"provotum::mixnet::ocw::cipher_costs";
+
+/// the weight (in percent) of the latest batch in the moving average of the cost per cipher
+const SMOOTHING_PERCENT: u64 = 25;
+
+/// the measured cost of shuffling a cipher and proving the shuffle, per key size
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct CipherCost {
+    /// the number of bits of the modulus p
+    pub key_bits: u64,
+    /// the moving average of the time (in microseconds) spent per cipher
+    pub micros_per_cipher: u64,
+}
+
+/// returns the measured costs of all key sizes
+pub fn get_cipher_costs() -> Vec<CipherCost> {
+    let storage = StorageValueRef::persistent(CIPHER_COSTS_KEY);
+    storage
+        .get::<Vec<CipherCost>>()
+        .flatten()
+        .unwrap_or_default()
+}
+
+fn set_cipher_costs(costs: &[CipherCost]) {
+    let storage = StorageValueRef::persistent(CIPHER_COSTS_KEY);
+    storage.set(&costs);
+}
+
+/// records the time spent to shuffle a batch of ciphers and to generate its proof
+pub fn record_batch(key_bits: u64, nr_of_ciphers: u64, elapsed_ms: u64) {
+    if nr_of_ciphers == 0 {
+        return;
+    }
+    let sample = elapsed_ms.saturating_mul(1000) / nr_of_ciphers;
+    let mut costs = get_cipher_costs();
+    match costs.iter_mut().find(|cost| cost.key_bits == key_bits) {
+        Some(cost) => {
+            let previous = cost
+                .micros_per_cipher
+                .saturating_mul(100 - SMOOTHING_PERCENT);
+            let latest = sample.saturating_mul(SMOOTHING_PERCENT);
+            cost.micros_per_cipher = previous.saturating_add(latest) / 100;
+        }
+        None => costs.push(CipherCost {
+            key_bits,
+            micros_per_cipher: sample,
+        }),
+    }
+    set_cipher_costs(&costs);
+}
+
+/// returns the number of ciphers to shuffle in the next batch, such that shuffling them
+/// (of all `nr_of_topics` topics for linked shuffles) and generating the proofs takes at most
+/// `budget_ms`. the batch size is clamped to [min, max], where max is the batch size configured
+/// on chain. max is used as long as no batch has been measured for the key size.
+pub fn get_batch_size(
+    key_bits: u64,
+    nr_of_topics: u64,
+    budget_ms: u64,
+    min: u64,
+    max: u64,
+) -> u64 {
+    let micros_per_cipher = get_cipher_costs()
+        .iter()
+        .find(|cost| cost.key_bits == key_bits)
+        .map(|cost| cost.micros_per_cipher)
+        .unwrap_or_default();
+    if micros_per_cipher == 0 {
+        return max;
+    }
+    let micros_per_position = micros_per_cipher.saturating_mul(nr_of_topics.max(1));
+    let affordable = budget_ms.saturating_mul(1000) / micros_per_position;
+    affordable.max(min).min(max)
+}
@@ -0,0 +1,79 @@
+use crate::types::{ShuffleCommitment, TopicId, VoteId};
+use crate::{Error, ShuffleCommitments, Trait};
+use codec::Encode;
+use frame_support::{ensure, storage::StorageMap, traits::Get};
+use sp_runtime::traits::Saturating;
+
+/// all functions related to the commit-reveal protocol of the shuffles
+///
+/// a sealer first commits to the hash of its shuffle payload and reveals the payload
+/// in a later block. this way, no sealer can adapt its shuffle to another sealer's
+/// shuffle which is submitted in the same block window.
+/// the payload is either a ShufflePayload or, for votes with linked shuffles,
+/// a LinkedShufflePayload committed to for the lead topic (see: linked).
+pub fn hash_shuffle_payload<P: Encode>(payload: &P) -> [u8; 32] {
+    sp_io::hashing::blake2_256(&payload.encode())
+}
+
+/// returns true if the commitment has not been revealed within `ShuffleTurnTimeout` blocks,
+/// i.e., the turn of its sealer has passed (see: turn)
+pub fn is_commitment_expired<T: Trait>(
+    commitment: &ShuffleCommitment<T::AccountId, T::BlockNumber>,
+    block_number: T::BlockNumber,
+) -> bool {
+    let expires_at = commitment
+        .block_number
+        .saturating_add(T::ShuffleTurnTimeout::get());
+    block_number > expires_at
+}
+
+/// stores the commitment of the sealer. a sealer can replace its own commitment,
+/// the commitment of another sealer only once it is expired.
+pub fn store_shuffle_commitment<T: Trait>(
+    who: &T::AccountId,
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    commitment: [u8; 32],
+) -> Result<(), Error<T>> {
+    let block_number = <frame_system::Module<T>>::block_number();
+    if let Some(pending) = ShuffleCommitments::<T>::get((vote_id, topic_id)) {
+        ensure!(
+            &pending.sealer == who || is_commitment_expired::<T>(&pending, block_number),
+            Error::<T>::ShuffleAlreadyCommitted
+        );
+    }
+
+    let commitment = ShuffleCommitment {
+        sealer: who.clone(),
+        commitment,
+        block_number,
+    };
+    ShuffleCommitments::<T>::insert((vote_id, topic_id), commitment);
+    Ok(())
+}
+
+/// checks that the payload matches the commitment the sealer submitted in an earlier block
+pub fn verify_shuffle_reveal<T: Trait, P: Encode>(
+    who: &T::AccountId,
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    payload: &P,
+) -> Result<(), Error<T>> {
+    let pending = ShuffleCommitments::<T>::get((vote_id, topic_id))
+        .ok_or(Error::<T>::ShuffleNotCommitted)?;
+    ensure!(&pending.sealer == who, Error::<T>::ShuffleNotCommitted);
+    ensure!(
+        <frame_system::Module<T>>::block_number() > pending.block_number,
+        Error::<T>::ShuffleRevealTooEarly
+    );
+    ensure!(
+        hash_shuffle_payload(payload) == pending.commitment,
+        Error::<T>::ShuffleCommitmentMismatch
+    );
+    Ok(())
+}
+
+/// removes the commitment once the shuffle has been revealed and verified
+pub fn remove_shuffle_commitment<T: Trait>(vote_id: &VoteId, topic_id: &TopicId) {
+    ShuffleCommitments::<T>::remove((vote_id, topic_id));
+}
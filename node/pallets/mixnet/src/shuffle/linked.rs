@@ -0,0 +1,139 @@
+use crate::dkg::aggregate::get_tally_strategy;
+use crate::helpers::assertions::ensure_vote_not_started;
+use crate::types::{Ballot, TallyStrategy, Topic, TopicId, VoteId};
+use crate::{Error, LinkedShuffles, ShuffleStateStore, Topics, Trait};
+use frame_support::{ensure, storage::StorageMap};
+use sp_std::vec::Vec;
+
+/// all functions related to linked shuffles
+///
+/// the topics of a vote with linked shuffles are shuffled together: the same batch of all
+/// topics is shuffled with the same permutation (shared permutation commitment) and the
+/// re-encryption randoms of each topic. otherwise, the answers of a ballot could be correlated
+/// across the independently shuffled topics. the turns and the commitment of a linked shuffle
+/// are the ones of the first topic of the vote (lead topic).
+pub fn is_linked_shuffle(vote_id: &VoteId) -> bool {
+    LinkedShuffles::get(vote_id)
+}
+
+/// returns the batch sizes of the current shuffle batch of all topics of the vote
+fn get_batch_sizes(vote_id: &VoteId) -> Vec<u64> {
+    Topics::get(vote_id)
+        .iter()
+        .filter_map(|topic| ShuffleStateStore::get((vote_id, &topic.id)))
+        .map(|state| state.batch_size)
+        .collect()
+}
+
+/// enables (or disables) the linked shuffles of the vote before the voting phase starts.
+/// only votes which are shuffled can be linked, all their topics must use the same batch size.
+pub fn store_linked_shuffle<T: Trait>(
+    vote_id: &VoteId,
+    linked: bool,
+) -> Result<(), Error<T>> {
+    ensure!(
+        get_tally_strategy::<T>(vote_id) != TallyStrategy::Homomorphic,
+        Error::<T>::WrongTallyStrategy
+    );
+    ensure_vote_not_started::<T>(vote_id)?;
+    if linked {
+        let batch_sizes = get_batch_sizes(vote_id);
+        ensure!(
+            batch_sizes.windows(2).all(|sizes| sizes[0] == sizes[1]),
+            Error::<T>::LinkedShuffleMismatch
+        );
+        LinkedShuffles::insert(vote_id, true);
+    } else {
+        LinkedShuffles::remove(vote_id);
+    }
+    Ok(())
+}
+
+/// ensures that a new topic of a vote with linked shuffles uses the batch size of the other topics
+pub fn ensure_linked_batch_size<T: Trait>(
+    vote_id: &VoteId,
+    batch_size: u64,
+) -> Result<(), Error<T>> {
+    if !is_linked_shuffle(vote_id) {
+        return Ok(());
+    }
+    ensure!(
+        get_batch_sizes(vote_id)
+            .iter()
+            .all(|size| *size == batch_size),
+        Error::<T>::LinkedShuffleMismatch
+    );
+    Ok(())
+}
+
+/// ensures that the topics of the vote are (or aren't) shuffled linked
+pub fn ensure_shuffle_mode<T: Trait>(
+    vote_id: &VoteId,
+    linked: bool,
+) -> Result<(), Error<T>> {
+    ensure!(
+        is_linked_shuffle(vote_id) == linked,
+        Error::<T>::WrongShuffleMode
+    );
+    Ok(())
+}
+
+/// returns the topic whose turns and commitment are used by the linked shuffle
+pub fn get_lead_topic<T: Trait>(vote_id: &VoteId) -> Result<TopicId, Error<T>> {
+    let topics: Vec<Topic> = Topics::get(vote_id);
+    topics
+        .into_iter()
+        .next()
+        .map(|topic| topic.id)
+        .ok_or(Error::<T>::LinkedShuffleMismatch)
+}
+
+/// ensures that a shuffle of a vote with linked shuffles is committed to for the lead topic,
+/// the other topics of such a vote can't be shuffled on their own
+pub fn ensure_shuffle_topic<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+) -> Result<(), Error<T>> {
+    if is_linked_shuffle(vote_id) {
+        ensure!(
+            get_lead_topic::<T>(vote_id)? == *topic_id,
+            Error::<T>::WrongShuffleMode
+        );
+    }
+    Ok(())
+}
+
+/// a ballot of a vote with linked shuffles answers every topic exactly once,
+/// i.e., the ciphers at the same position of all topics belong to the same ballot
+pub fn ensure_linked_ballot<T: Trait>(
+    vote_id: &VoteId,
+    ballot: &Ballot,
+) -> Result<(), Error<T>> {
+    if !is_linked_shuffle(vote_id) {
+        return Ok(());
+    }
+    let topics: Vec<Topic> = Topics::get(vote_id);
+    let answers = |topic: &Topic| {
+        ballot
+            .answers
+            .iter()
+            .filter(|(topic_id, _)| *topic_id == topic.id)
+            .count()
+    };
+    ensure!(
+        ballot.answers.len() == topics.len()
+            && topics.iter().all(|topic| answers(topic) == 1),
+        Error::<T>::IncompleteLinkedBallot
+    );
+    Ok(())
+}
+
+/// spoiling a single answer would move the later ciphers of its topic to other positions,
+/// therefore, the answers of a vote with linked shuffles can't be spoiled
+pub fn ensure_spoilable_answer<T: Trait>(vote_id: &VoteId) -> Result<(), Error<T>> {
+    ensure!(
+        !is_linked_shuffle(vote_id),
+        Error::<T>::IncompleteLinkedBallot
+    );
+    Ok(())
+}
@@ -1,43 +1,129 @@
+pub mod commitment;
+pub mod linked;
 pub mod prover;
 pub mod shuffle;
+pub mod turn;
 pub mod verifier;
 
 use crate::{
-    helpers::{array::get_slice, params::get_public_key},
+    helpers::{
+        ciphers::{append_ciphers, get_cipher_count, get_ciphers_in_range},
+        freeze::get_shuffle_context,
+        indexing::set_shuffle_state,
+        params::{get_protocol_config, get_public_key},
+        transcripts::{hash_transcript, log_proof},
+    },
     types::{
-        Cipher, NrOfShuffles, PublicKey as SubstratePK, ShufflePayload, ShuffleProof,
-        ShuffleState, TopicId, VoteId, Wrapper,
+        Cipher, LinkedShufflePayload, NrOfShuffles, ProofKind, PublicKey as SubstratePK,
+        ShufflePayload, ShuffleProof, ShuffleState, Topic, TopicId, VoteId, Wrapper,
     },
 };
-use crate::{Ciphers, Error, Module, ShuffleProofs, ShuffleStateStore, Trait};
+use crate::{
+    Error, Module, RawEvent, ShuffleBatchSizes, ShuffleProofs, ShuffleStateStore, Topics,
+    Trait, Votes,
+};
 use alloc::vec::Vec;
 use crypto::types::{Cipher as BigCipher, PublicKey as ElGamalPK};
-use frame_support::{
-    ensure,
-    storage::{StorageDoubleMap, StorageMap},
-};
+use frame_support::{debug, ensure, storage::StorageMap};
 
 impl<T: Trait> Module<T> {
-    const NR_OF_SHUFFLES: u8 = 3;
-
     pub fn verify_proof_store_shuffled_ciphers(
+        who: &T::AccountId,
         vote_id: &VoteId,
         topic_id: &TopicId,
         payload: ShufflePayload,
     ) -> Result<(), Error<T>> {
+        let (total_ciphers, transcript_hash) =
+            Self::verify_shuffled_ciphers(vote_id, topic_id, topic_id, &payload)?;
+        log_proof::<T>(
+            vote_id,
+            topic_id,
+            ProofKind::Shuffle,
+            payload.iteration,
+            who,
+            transcript_hash,
+        );
+        Self::store_shuffled_ciphers(vote_id, topic_id, payload, total_ciphers);
+        Ok(())
+    }
+
+    /// verifies the shuffles of the same batch of all topics of a vote with linked shuffles
+    /// and stores them. the shuffles must be in the order of the topics and use the same
+    /// permutation commitments, i.e., all topics are shuffled with the same permutation.
+    /// the generators of the permutation commitments are derived from the vote id.
+    pub fn verify_linked_proofs_store_shuffled_ciphers(
+        who: &T::AccountId,
+        vote_id: &VoteId,
+        payload: LinkedShufflePayload,
+    ) -> Result<(), Error<T>> {
+        let topics: Vec<Topic> = Topics::get(vote_id);
+        let (_, lead) = payload.first().ok_or(Error::<T>::LinkedShuffleMismatch)?;
+        let is_complete = payload.len() == topics.len()
+            && topics
+                .iter()
+                .zip(payload.iter())
+                .all(|(topic, (topic_id, _))| topic.id == *topic_id);
+        let is_same_permutation = payload.iter().all(|(_, shuffle)| {
+            shuffle.iteration == lead.iteration
+                && shuffle.start_position == lead.start_position
+                && shuffle.batch_size == lead.batch_size
+                && shuffle.proof.permutation_commitments
+                    == lead.proof.permutation_commitments
+        });
+        ensure!(
+            is_complete && is_same_permutation,
+            Error::<T>::LinkedShuffleMismatch
+        );
+
+        // verify the shuffles of all topics before any of them is stored
+        let mut totals: Vec<(usize, [u8; 32])> = Vec::with_capacity(payload.len());
+        for (topic_id, shuffle) in payload.iter() {
+            totals.push(Self::verify_shuffled_ciphers(
+                vote_id, topic_id, vote_id, shuffle,
+            )?);
+        }
+        ensure!(
+            totals.iter().all(|(total, _)| *total == totals[0].0),
+            Error::<T>::LinkedShuffleMismatch
+        );
+        for ((topic_id, shuffle), (total_ciphers, transcript_hash)) in
+            payload.into_iter().zip(totals)
+        {
+            log_proof::<T>(
+                vote_id,
+                &topic_id,
+                ProofKind::Shuffle,
+                shuffle.iteration,
+                who,
+                transcript_hash,
+            );
+            Self::store_shuffled_ciphers(vote_id, &topic_id, shuffle, total_ciphers);
+        }
+        Ok(())
+    }
+
+    /// verifies the shuffle of the current batch of the topic, returns the number of ciphers
+    /// of the topic in the current iteration and the transcript hash of the shuffle.
+    /// the generators of the permutation commitments are derived from the generator_id
+    /// (the topic id, or the vote id for linked shuffles).
+    fn verify_shuffled_ciphers(
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+        generator_id: &Vec<u8>,
+        payload: &ShufflePayload,
+    ) -> Result<(usize, [u8; 32]), Error<T>> {
         let proof: ShuffleProof = payload.proof.clone().into();
         let shuffled_ciphers: Vec<Cipher> = payload.ciphers.clone();
         let iteration: NrOfShuffles = payload.iteration;
         let start_position: u64 = payload.start_position;
         let batch_size: u64 = payload.batch_size;
 
-        // get all encrypted votes (ciphers)
+        // get the number of encrypted votes (ciphers)
         // for the topic with id: topic_id and the # of shuffles already performed (iteration)
-        let ciphers: Vec<Cipher> = Ciphers::get(topic_id, iteration);
-        let total_ciphers = ciphers.len();
+        let total_ciphers = get_cipher_count::<T>(vote_id, topic_id, iteration) as usize;
 
         // check if there are any ciphers for the given nr_of_shuffles
-        if ciphers.is_empty() {
+        if total_ciphers == 0 {
             return Err(Error::<T>::NrOfShufflesDoesNotExist);
         }
 
@@ -51,12 +137,13 @@ impl<T: Trait> Module<T> {
 
         // check prerequisites
         // - start_position must match
-        // - batch_size must match
+        // - batch_size must be <= the configured batch_size (the offchain worker sizes the batch to its shuffle costs)
         // - # of ciphers must be <= batch_size (actually, mostly ==, but edge case when last batch is smaller than batch_size)
         if shuffle_state.iteration != iteration
             || shuffle_state.start_position != start_position
-            || shuffle_state.batch_size != batch_size
-            || shuffled_ciphers.len() > shuffle_state.batch_size as usize
+            || batch_size == 0
+            || batch_size > shuffle_state.batch_size
+            || shuffled_ciphers.len() > batch_size as usize
         {
             return Err(Error::<T>::ShuffleStateIncorrect);
         }
@@ -65,34 +152,79 @@ impl<T: Trait> Module<T> {
         // State: The votes exist and have not been shuffled yet!
         //
 
-        // get the public key for the vote
+        // get the public key and the protocol parameters of the vote
         let pk: SubstratePK = get_public_key::<T>(vote_id)?;
         let pk: ElGamalPK = pk.into();
+        let config = get_protocol_config::<T>(vote_id)?;
 
-        // type conversion: Vec<Cipher> (Vec<Vec<u8>>) to Vec<BigCipher> (Vec<BigUint>)
-        let big_ciphers: Vec<BigCipher> = Wrapper(ciphers).into();
-        let big_shuffled_ciphers: Vec<BigCipher> =
-            Wrapper(shuffled_ciphers.clone()).into();
+        // get the required range of ciphers, only the affected chunks are read
+        let ciphers: Vec<Cipher> = get_ciphers_in_range::<T>(
+            vote_id,
+            topic_id,
+            iteration,
+            start_position,
+            batch_size,
+        );
+
+        // the batch must be shuffled into as many ciphers and the ciphers of the next iteration
+        // must not contain more ciphers than shuffled so far, i.e., no cipher is dropped or injected
+        let nr_of_shuffled_ciphers =
+            get_cipher_count::<T>(vote_id, topic_id, iteration + 1);
+        if shuffled_ciphers.len() != ciphers.len()
+            || nr_of_shuffled_ciphers != start_position
+        {
+            debug::error!(
+                "cipher count mismatch, topic_id: {:?}, iteration: {:?}, input: {:?}, output: {:?}, already shuffled: {:?} (expected: {:?})",
+                topic_id,
+                iteration,
+                ciphers.len(),
+                shuffled_ciphers.len(),
+                nr_of_shuffled_ciphers,
+                start_position
+            );
+            return Err(Error::<T>::CipherCountMismatch);
+        }
 
-        // get the required range of ciphers
-        let slice: Vec<BigCipher> =
-            get_slice::<T, BigCipher>(big_ciphers, start_position, batch_size);
+        // the transcript: the shuffled batch and the payload (shuffled ciphers + proof)
+        let transcript_hash =
+            hash_transcript(vote_id, topic_id, iteration, &ciphers, payload);
 
-        // verify the shuffle proof
-        let is_proof_valid = Self::verify_shuffle_proof(
-            &topic_id,
+        // type conversion: Vec<Cipher> (Vec<Vec<u8>>) to Vec<BigCipher> (Vec<BigUint>)
+        let slice: Vec<BigCipher> = Wrapper(ciphers).into();
+        let big_shuffled_ciphers: Vec<BigCipher> = Wrapper(shuffled_ciphers).into();
+
+        // verify the shuffle proof, its challenge is bound to the ballot box freeze of the vote
+        let context = get_shuffle_context::<T>(vote_id);
+        let is_proof_valid = Self::verify_shuffle_proof_in_context(
+            generator_id,
+            &context,
             proof,
             slice,
             big_shuffled_ciphers,
             &pk,
+            &config,
         )?;
         ensure!(is_proof_valid, Error::<T>::ShuffleProofVerifcationFailed);
+        Ok((total_ciphers, transcript_hash))
+    }
+
+    /// stores the verified shuffle of the current batch of the topic and its proof,
+    /// and moves the shuffle state of the topic to the next batch
+    fn store_shuffled_ciphers(
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+        payload: ShufflePayload,
+        total_ciphers: usize,
+    ) {
+        let shuffled_ciphers: Vec<Cipher> = payload.ciphers.clone();
+        let iteration: NrOfShuffles = payload.iteration;
+        let start_position: u64 = payload.start_position;
+        let batch_size: u64 = payload.batch_size;
+        let nr_of_shuffled_ciphers = shuffled_ciphers.len() as u64;
 
         // store the shuffle ciphers with the new increased shuffle iteration
         let next_iteration = iteration + 1;
-        let mut already_shuffled: Vec<Cipher> = Ciphers::get(topic_id, next_iteration);
-        already_shuffled.extend(shuffled_ciphers.iter().cloned());
-        Ciphers::insert(&topic_id, next_iteration, already_shuffled);
+        append_ciphers::<T>(vote_id, topic_id, next_iteration, shuffled_ciphers);
 
         // store the shuffle proof payload for verification (audit trail)
         let mut shuffle_proofs: Vec<ShufflePayload> =
@@ -101,23 +233,51 @@ impl<T: Trait> Module<T> {
         ShuffleProofs::insert((&vote_id, &topic_id), shuffle_proofs);
 
         // compute the new shuffle state
+        // the number of required shuffle iterations is configured per vote
+        // the next batch is shuffled with the batch size set by the voting authority (if any),
+        // otherwise the configured batch size is kept (the shuffled batch may have been smaller)
+        let nr_of_shuffles: NrOfShuffles = Votes::<T>::get(vote_id).nr_of_shuffles;
+        let configured_batch_size: u64 = ShuffleStateStore::get((vote_id, topic_id))
+            .map(|state| state.batch_size)
+            .unwrap_or(batch_size);
+        let next_batch_size: u64 =
+            ShuffleBatchSizes::get(vote_id).unwrap_or(configured_batch_size);
         let new_state: ShuffleState = Self::compute_next_shuffle_state(
             start_position,
             batch_size,
+            next_batch_size,
             total_ciphers,
             iteration,
+            nr_of_shuffles,
         );
 
+        // notify about the mixing progress
+        Self::deposit_event(RawEvent::ShuffleBatchAccepted(
+            vote_id.clone(),
+            topic_id.clone(),
+            iteration,
+            start_position,
+            nr_of_shuffled_ciphers,
+        ));
+        if new_state.iteration > iteration {
+            Self::deposit_event(RawEvent::ShuffleIterationCompleted(
+                vote_id.clone(),
+                topic_id.clone(),
+                new_state.iteration,
+            ));
+        }
+
         // update the shuffle state
-        ShuffleStateStore::insert((vote_id, topic_id), new_state);
-        Ok(())
+        set_shuffle_state::<T>(vote_id, topic_id, new_state);
     }
 
     fn compute_next_shuffle_state(
         start_position: u64,
         batch_size: u64,
+        next_batch_size: u64,
         nr_ciphers: usize,
         iteration: u8,
+        nr_of_shuffles: NrOfShuffles,
     ) -> ShuffleState {
         let next_iteration = iteration + 1;
 
@@ -139,7 +299,7 @@ impl<T: Trait> Module<T> {
         };
 
         // check if shuffling is completed
-        let done = if new_iteration >= Self::NR_OF_SHUFFLES {
+        let done = if new_iteration >= nr_of_shuffles {
             true
         } else {
             false
@@ -148,7 +308,7 @@ impl<T: Trait> Module<T> {
         ShuffleState {
             iteration: new_iteration,
             start_position: new_start_position,
-            batch_size,
+            batch_size: next_batch_size,
             done,
         }
     }
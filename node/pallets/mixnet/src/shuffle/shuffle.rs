@@ -32,4 +32,36 @@ impl<T: Trait> Module<T> {
         // return the shuffled ciphers, randoms, permutation as result
         Ok((shuffled_ciphers, randoms, permutation))
     }
+
+    /// shuffles the ciphers of several topics with the same permutation (linked shuffle),
+    /// the ciphers of each topic are re-encrypted with their own random values.
+    /// returns the shuffled ciphers and the random values per topic and the permutation.
+    pub fn shuffle_linked_ciphers(
+        pk: &ElGamalPK,
+        ciphers: Vec<Vec<BigCipher>>,
+    ) -> Result<(Vec<(Vec<BigCipher>, Vec<BigUint>)>, Vec<usize>), Error<T>> {
+        let q = pk.params.q();
+        let size = ciphers.first().map(|topic| topic.len()).unwrap_or(0);
+
+        // check that there are ballots to shuffle and that all topics have the same size
+        if size == 0 {
+            return Err(Error::<T>::ShuffleCiphersSizeZeroError);
+        }
+        if ciphers.iter().any(|topic| topic.len() != size) {
+            return Err(Error::<T>::LinkedShuffleMismatch);
+        }
+
+        // all topics are shuffled with the same permutation
+        let permutation: Vec<usize> = Self::generate_permutation(size)?;
+
+        let mut shuffles: Vec<(Vec<BigCipher>, Vec<BigUint>)> = Vec::new();
+        for topic in ciphers.iter() {
+            let randoms: Vec<BigUint> = Self::get_random_biguints_less_than(&q, size)?;
+            let shuffle = ElGamal::shuffle(topic, &permutation, &randoms, &pk);
+            let shuffled_ciphers: Vec<BigCipher> =
+                shuffle.into_iter().map(|item| item.0).collect();
+            shuffles.push((shuffled_ciphers, randoms));
+        }
+        Ok((shuffles, permutation))
+    }
 }
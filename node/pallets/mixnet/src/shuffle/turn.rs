@@ -0,0 +1,79 @@
+use crate::helpers::sealers::get_vote_sealers;
+use crate::types::{ShuffleTurn, TopicId, VoteId};
+use crate::{Error, ShuffleTurns, Trait};
+use core::convert::TryInto;
+use frame_support::{ensure, storage::StorageMap, traits::Get};
+use sp_runtime::traits::{One, Saturating, Zero};
+use sp_std::vec::Vec;
+
+/// all functions related to the turns of the sealers to shuffle a topic
+///
+/// the sealers shuffle the batches of a topic in the order of the sealers of the vote. the turn only
+/// passes to the next sealer once the expected sealer revealed its shuffle, or, if the
+/// sealer didn't commit or reveal within `ShuffleTurnTimeout` blocks, once it timed out.
+/// therefore, every sealer shuffles exactly once per round of turns, regardless of the
+/// blocks it produces.
+pub fn get_shuffle_turn<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    block_number: T::BlockNumber,
+) -> ShuffleTurn<T::BlockNumber> {
+    let turn = ShuffleTurns::<T>::get((vote_id, topic_id));
+    let nr_of_sealers = get_vote_sealers::<T>(vote_id).len() as u64;
+
+    // the number of turns which timed out since the turn started
+    let period = T::ShuffleTurnTimeout::get().saturating_add(One::one());
+    let timeouts = block_number.saturating_sub(turn.since) / period;
+    if timeouts.is_zero() || nr_of_sealers == 0 {
+        return turn;
+    }
+
+    let skipped = TryInto::<u64>::try_into(timeouts).unwrap_or(u64::MAX) % nr_of_sealers;
+    let sealer_index = (turn.sealer_index as u64 + skipped) % nr_of_sealers;
+    ShuffleTurn {
+        sealer_index: sealer_index as u32,
+        since: turn.since.saturating_add(timeouts.saturating_mul(period)),
+    }
+}
+
+/// returns the sealer whose turn it is to shuffle the topic at the block
+pub fn get_shuffle_sealer<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    block_number: T::BlockNumber,
+) -> Option<T::AccountId> {
+    let turn = get_shuffle_turn::<T>(vote_id, topic_id, block_number);
+    get_vote_sealers::<T>(vote_id)
+        .get(turn.sealer_index as usize)
+        .cloned()
+}
+
+/// ensures that it is the sealer's turn to shuffle the topic, returns the turn
+pub fn ensure_shuffle_turn<T: Trait>(
+    who: &T::AccountId,
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+) -> Result<ShuffleTurn<T::BlockNumber>, Error<T>> {
+    let block_number = <frame_system::Module<T>>::block_number();
+    let turn = get_shuffle_turn::<T>(vote_id, topic_id, block_number);
+    let sealers: Vec<T::AccountId> = get_vote_sealers::<T>(vote_id);
+    ensure!(
+        sealers.get(turn.sealer_index as usize) == Some(who),
+        Error::<T>::NotYourShuffleTurn
+    );
+    Ok(turn)
+}
+
+/// starts the turn of the sealer (index) in the current block
+pub fn start_shuffle_turn<T: Trait>(
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    sealer_index: u32,
+) {
+    let nr_of_sealers = get_vote_sealers::<T>(vote_id).len().max(1) as u32;
+    let turn = ShuffleTurn {
+        sealer_index: sealer_index % nr_of_sealers,
+        since: <frame_system::Module<T>>::block_number(),
+    };
+    ShuffleTurns::<T>::insert((vote_id, topic_id), turn);
+}
@@ -1,13 +1,18 @@
-use crate::{types::BigS, types::ShuffleProof as Proof, Error, Module, Trait};
+use crate::{
+    types::{BigS, ProtocolConfig, ShuffleProof as Proof},
+    Error, Module, Trait,
+};
 use crypto::{
+    hash::HashFunction,
     helper::Helper,
     proofs::shuffle::ShuffleProof,
     types::{
-        BigT, BigY, Cipher as BigCipher, ElGamalParams, ModuloOperations, PublicKey,
+        multi_modpow, BigT, BigY, Cipher as BigCipher, FixedBaseTable, ModuloOperations,
+        PublicKey,
     },
 };
 use num_bigint::BigUint;
-use num_traits::One;
+use num_traits::{One, Zero};
 use sp_std::{vec, vec::Vec};
 
 /// all functions related to zero-knowledge proofs in the offchain worker
@@ -17,12 +22,40 @@ impl<T: Trait> Module<T> {
     /// Checks the correctness of a shuffle proof generated by Algorithm 8.47.
     /// The public values are the ElGamal encryptions e and e~ and
     /// the public encryption key pk.
+    ///
+    /// By default, the commitments t4 and t_hat submitted with the proof are verified
+    /// in batch (small random exponents + multi-exponentiation) instead of recomputing them one by one.
+    /// The feature `exact-shuffle-verification` falls back to recomputing all commitments.
+    /// The size of the random exponents is a parameter of the protocol of the vote.
     pub fn verify_shuffle_proof(
         id: &Vec<u8>, // topicId (vote question)
         proof: Proof,
         encryptions: Vec<BigCipher>,
         shuffled_encryptions: Vec<BigCipher>,
         pk: &PublicKey,
+        config: &ProtocolConfig,
+    ) -> Result<bool, Error<T>> {
+        Self::verify_shuffle_proof_in_context(
+            id,
+            &[],
+            proof,
+            encryptions,
+            shuffled_encryptions,
+            pk,
+            config,
+        )
+    }
+
+    /// Checks the correctness of a shuffle proof whose challenge is bound to the context,
+    /// i.e. the digest of the ballot box freeze of the vote (see: helpers::freeze).
+    pub fn verify_shuffle_proof_in_context(
+        id: &Vec<u8>, // topicId (vote question)
+        context: &[u8],
+        proof: Proof,
+        encryptions: Vec<BigCipher>,
+        shuffled_encryptions: Vec<BigCipher>,
+        pk: &PublicKey,
+        config: &ProtocolConfig,
     ) -> Result<bool, Error<T>> {
         let e = encryptions;
         let e_tilde = shuffled_encryptions;
@@ -36,6 +69,7 @@ impl<T: Trait> Module<T> {
         let s4: BigUint = s.s4;
         let vec_s_hat: Vec<BigUint> = s.vec_s_hat;
         let vec_s_tilde: Vec<BigUint> = s.vec_s_tilde;
+        let commitments: BigT = proof.commitments;
 
         // input checks
         assert!(
@@ -67,17 +101,22 @@ impl<T: Trait> Module<T> {
         let p = &params.p;
         let q = &params.q();
 
+        // g is used for size + 4 exponentiations
+        let g_table = params.g.precompute(p, q.bits());
+
         // get {size} independent generators: vec_h
         let vec_h = Helper::get_generators(id, p, size);
 
-        // get {size} challenges
+        // get {size} challenges, using the hash function of the vote
         // vec_u = get_challenges(size, hash(e, e_tilde, vec_c, pk))
-        let vec_u = ShuffleProof::get_challenges(
+        let hash_function: HashFunction = config.hash_function.into();
+        let vec_u = ShuffleProof::get_challenges_with_hash(
             size,
             e.clone(),
             e_tilde.clone(),
             vec_c.clone(),
             pk,
+            hash_function,
         );
 
         // get c_hat_0
@@ -119,42 +158,98 @@ impl<T: Trait> Module<T> {
         let a_tilde = Self::zip_vectors_multiply_a_pow_b(&vec_a, &vec_u, p);
         let b_tilde = Self::zip_vectors_multiply_a_pow_b(&vec_b, &vec_u, p);
 
-        // generate vec_t_hat values
-        let vec_t_hat = Self::get_vec_t_hat_verifier(
-            &c_hat_0,
-            &challenge,
-            &vec_c_hat,
-            &vec_s_hat,
-            &vec_s_tilde,
-            size,
-            params,
-        );
-
-        let (t1, t2, t3, (t4_1, t4_2)) = Self::get_t_values_verifier(
+        let (t1, t2, t3) = Self::get_t_values_verifier(
             &c_flat,
             &c_hat,
             &c_tilde,
             &challenge,
-            &a_tilde,
-            &b_tilde,
-            &e_tilde,
             &vec_h,
             &vec_s_tilde,
             &s1,
             &s2,
             &s3,
-            &s4,
-            size,
-            pk,
-        )?;
+            p,
+            &g_table,
+        );
+
+        let (t4_1, t4_2, vec_t_hat) = if cfg!(feature = "exact-shuffle-verification") {
+            // recompute the commitments t4 and vec_t_hat
+            let (t4_1, t4_2) = Self::get_t4_values_verifier(
+                &challenge,
+                &a_tilde,
+                &b_tilde,
+                &e_tilde,
+                &vec_s_tilde,
+                &s4,
+                pk,
+                &g_table,
+            )?;
+            let vec_t_hat = Self::get_vec_t_hat_verifier(
+                &c_hat_0,
+                &challenge,
+                &vec_c_hat,
+                &vec_s_hat,
+                &vec_s_tilde,
+                size,
+                p,
+                &g_table,
+            );
+            (t4_1, t4_2, vec_t_hat)
+        } else {
+            // verify the commitments t4 and vec_t_hat of the prover in batch
+            // the random exponents are derived from the challenge -> after the prover committed to t
+            let (_, _, _, t4_1, t4_2, vec_t_hat) = commitments;
+            if vec_t_hat.len() != size {
+                return Ok(false);
+            }
+            let vec_r = Helper::get_batch_exponents_of_size(
+                id,
+                &challenge,
+                size + 2,
+                config.batch_exponent_bits as usize,
+            );
+            let is_t4_valid = Self::verify_t4_values_batched(
+                &challenge,
+                &a_tilde,
+                &b_tilde,
+                &e_tilde,
+                &vec_s_tilde,
+                &s4,
+                (&t4_1, &t4_2),
+                (&vec_r[size], &vec_r[size + 1]),
+                pk,
+                &g_table,
+            );
+            let is_t_hat_valid = Self::verify_vec_t_hat_batched(
+                &c_hat_0,
+                &challenge,
+                &vec_c_hat,
+                &vec_s_hat,
+                &vec_s_tilde,
+                &vec_t_hat,
+                &vec_r[..size],
+                p,
+                q,
+                &g_table,
+            );
+            if !is_t4_valid || !is_t_hat_valid {
+                return Ok(false);
+            }
+            (t4_1, t4_2, vec_t_hat)
+        };
 
         // generate challenge from (y, t)
         // public value y = ((e, e_tilde, vec_c, vec_c_hat, public_key) -> public_key = component h of pk
         // public commitment t = (t1, t2, t3, (t4_1, t4_2), (t_hat_0, ..., t_hat_(size-1)))
         let public_value: BigY = (e, e_tilde, vec_c, vec_c_hat, &pk.h);
         let public_commitment: BigT = (t1, t2, t3, t4_1, t4_2, vec_t_hat);
-        let recomputed_challenge =
-            ShuffleProof::get_challenge(public_value, public_commitment, q);
+        let recomputed_challenge = ShuffleProof::get_challenge_in_context_with_hash(
+            public_value,
+            public_commitment,
+            context,
+            q,
+            hash_function,
+        );
 
         let is_proof_valid = recomputed_challenge == challenge;
         Ok(is_proof_valid)
@@ -165,38 +260,46 @@ impl<T: Trait> Module<T> {
         c_hat: &BigUint,
         c_tilde: &BigUint,
         challenge: &BigUint,
-        a_tilde: &BigUint,
-        b_tilde: &BigUint,
-        e_tilde: &Vec<BigCipher>,
         vec_h: &Vec<BigUint>,
         vec_s_tilde: &Vec<BigUint>,
         s1: &BigUint,
         s2: &BigUint,
         s3: &BigUint,
-        s4: &BigUint,
-        size: usize,
-        public_key: &PublicKey,
-    ) -> Result<(BigUint, BigUint, BigUint, (BigUint, BigUint)), Error<T>> {
-        let g = &public_key.params.g;
-        let p = &public_key.params.p;
-        let pk = &public_key.h;
-
+        p: &BigUint,
+        g_table: &FixedBaseTable,
+    ) -> (BigUint, BigUint, BigUint) {
         // get t1 = c_flat^challenge * g^s1 mod p
-        let t1 = c_flat.modpow(challenge, p).modmul(&g.modpow(s1, p), p);
+        let t1 = c_flat.modpow(challenge, p).modmul(&g_table.pow(s1), p);
 
         // get t2 = c_hat^challenge * g^s2 mod p
-        let g_pow_s2 = g.modpow(s2, p);
+        let g_pow_s2 = g_table.pow(s2);
         let c_hat_pow_challenge = c_hat.modpow(challenge, p);
         let t2 = c_hat_pow_challenge.modmul(&g_pow_s2, p);
 
         // get t3 = c_tilde^challenge * g^s3 * Π(h_i^s_tilde_i) mod p
         let prod_h_s_tilde = Self::zip_vectors_multiply_a_pow_b(&vec_h, &vec_s_tilde, p);
-        let g_pow_s3 = g.modpow(s3, p);
+        let g_pow_s3 = g_table.pow(s3);
         let c_tilde_pow_challenge = c_tilde.modpow(challenge, p);
         let t3 = c_tilde_pow_challenge
             .modmul(&g_pow_s3, p)
             .modmul(&prod_h_s_tilde, p);
 
+        (t1, t2, t3)
+    }
+
+    fn get_t4_values_verifier(
+        challenge: &BigUint,
+        a_tilde: &BigUint,
+        b_tilde: &BigUint,
+        e_tilde: &Vec<BigCipher>,
+        vec_s_tilde: &Vec<BigUint>,
+        s4: &BigUint,
+        public_key: &PublicKey,
+        g_table: &FixedBaseTable,
+    ) -> Result<(BigUint, BigUint), Error<T>> {
+        let p = &public_key.params.p;
+        let pk = &public_key.h;
+        let size = e_tilde.len();
         // we need to swap pk and g
         // since our encryption conatins (a,b) with a = g^r
         // and not as in the spec a = pk^r
@@ -205,7 +308,7 @@ impl<T: Trait> Module<T> {
 
         // g^-s4 = (g^-1)^s4 = (g^s4)^-1 = invmod(g^s4)
         // for an explanation see: Verifiable Re-Encryption Mixnets (Haenni, Locher, Koenig, Dubuis) page 9
-        let mut g_pow_minus_s4 = g.modpow(&s4, p);
+        let mut g_pow_minus_s4 = g_table.pow(&s4);
         g_pow_minus_s4 = g_pow_minus_s4.invmod(p).ok_or(Error::InvModError)?;
 
         // compute prod_a = Π(vec_a_tilde_i^s_tilde_i)
@@ -248,7 +351,53 @@ impl<T: Trait> Module<T> {
         t4_2 = t4_2.modmul(&pk_pow_minus_s4, p);
         t4_2 = t4_2.modmul(&prod_b, p);
 
-        Ok((t1, t2, t3, (t4_1, t4_2)))
+        Ok((t4_1, t4_2))
+    }
+
+    /// Verifies the commitments t4_1 and t4_2 in a single equation.
+    ///
+    /// Both equations (see: get_t4_values_verifier) are raised to the random exponents r_1, r_2 and multiplied:
+    /// t4_1^r_1 * t4_2^r_2 * g^(r_1 * s4) * pk^(r_2 * s4) =
+    /// a_tilde^(r_1 * challenge) * b_tilde^(r_2 * challenge) * Π(vec_a_tilde_i^(r_1 * s_tilde_i) * vec_b_tilde_i^(r_2 * s_tilde_i)) mod p
+    fn verify_t4_values_batched(
+        challenge: &BigUint,
+        a_tilde: &BigUint,
+        b_tilde: &BigUint,
+        e_tilde: &Vec<BigCipher>,
+        vec_s_tilde: &Vec<BigUint>,
+        s4: &BigUint,
+        t4: (&BigUint, &BigUint),
+        r: (&BigUint, &BigUint),
+        public_key: &PublicKey,
+        g_table: &FixedBaseTable,
+    ) -> bool {
+        let p = &public_key.params.p;
+        let q = &public_key.params.q();
+        let pk = &public_key.h;
+        let (t4_1, t4_2) = t4;
+        let (r_1, r_2) = r;
+
+        // left side: t4_1^r_1 * t4_2^r_2 * pk^(r_2 * s4) * g^(r_1 * s4)
+        let r_2_s4 = r_2 * s4;
+        let g_exponent = (r_1 * s4) % q;
+        let left = multi_modpow(&[t4_1, t4_2, pk], &[r_1, r_2, &r_2_s4], p)
+            .modmul(&g_table.pow(&g_exponent), p);
+
+        // right side: all bases and their combined exponents
+        let r_1_challenge = r_1 * challenge;
+        let r_2_challenge = r_2 * challenge;
+        let mut bases: Vec<&BigUint> = vec![a_tilde, b_tilde];
+        let mut exponents: Vec<BigUint> = vec![r_1_challenge, r_2_challenge];
+        for (cipher, s_tilde_i) in e_tilde.iter().zip(vec_s_tilde.iter()) {
+            bases.push(&cipher.a);
+            exponents.push(r_1 * s_tilde_i);
+            bases.push(&cipher.b);
+            exponents.push(r_2 * s_tilde_i);
+        }
+        let exponents: Vec<&BigUint> = exponents.iter().collect();
+        let right = multi_modpow(&bases, &exponents, p);
+
+        Self::is_batch_equal(&left, &right, p)
     }
 
     fn get_vec_t_hat_verifier(
@@ -258,11 +407,9 @@ impl<T: Trait> Module<T> {
         vec_s_hat: &Vec<BigUint>,
         vec_s_tilde: &Vec<BigUint>,
         size: usize,
-        params: &ElGamalParams,
+        p: &BigUint,
+        g_table: &FixedBaseTable,
     ) -> Vec<BigUint> {
-        let g = &params.g;
-        let p = &params.p;
-
         // create an extended vec_c_hat
         // extended = [c_hat_0, ...c_hat];
         let mut vec_c_hat_extended = vec![c_hat_0];
@@ -281,7 +428,7 @@ impl<T: Trait> Module<T> {
 
             // g ^ s_hat_i
             let s_hat_i = &vec_s_hat[i];
-            let g_pow_s_hat_i = g.modpow(&s_hat_i, p);
+            let g_pow_s_hat_i = g_table.pow(&s_hat_i);
 
             // c_hat_(i-1) ^ s_tilde_i
             let s_tilde_i = &vec_s_tilde[i];
@@ -302,4 +449,69 @@ impl<T: Trait> Module<T> {
         );
         vec_t_hat
     }
+
+    /// Verifies all commitments vec_t_hat in a single equation.
+    ///
+    /// Each equation t_hat_i = c_hat_i^challenge * g^s_hat_i * c_hat_(i-1)^s_tilde_i (see: get_vec_t_hat_verifier)
+    /// is raised to the random exponent r_i and all are multiplied. Since c_hat_i appears in two equations,
+    /// the exponents are combined: e_i = r_(i-1) * challenge + r_i * s_tilde_i.
+    /// Π(t_hat_i^r_i) = g^(Σ(r_i * s_hat_i)) * Π(c_hat_i^e_i) mod p
+    fn verify_vec_t_hat_batched(
+        c_hat_0: &BigUint,
+        challenge: &BigUint,
+        vec_c_hat: &Vec<BigUint>,
+        vec_s_hat: &Vec<BigUint>,
+        vec_s_tilde: &Vec<BigUint>,
+        vec_t_hat: &Vec<BigUint>,
+        vec_r: &[BigUint],
+        p: &BigUint,
+        q: &BigUint,
+        g_table: &FixedBaseTable,
+    ) -> bool {
+        let size = vec_t_hat.len();
+
+        // left side: Π(t_hat_i^r_i)
+        let vec_t_hat_refs: Vec<&BigUint> = vec_t_hat.iter().collect();
+        let vec_r_refs: Vec<&BigUint> = vec_r.iter().collect();
+        let left = multi_modpow(&vec_t_hat_refs, &vec_r_refs, p);
+
+        // g^(Σ(r_i * s_hat_i))
+        let g_exponent = vec_r
+            .iter()
+            .zip(vec_s_hat.iter())
+            .fold(BigUint::zero(), |sum, (r_i, s_hat_i)| {
+                (sum + r_i * s_hat_i) % q
+            });
+
+        // create an extended vec_c_hat
+        // extended = [c_hat_0, ...c_hat];
+        let mut vec_c_hat_extended = vec![c_hat_0];
+        vec_c_hat_extended.extend(vec_c_hat);
+
+        // the combined exponent of c_hat_i: r_(i-1) * challenge + r_i * s_tilde_i
+        let exponents: Vec<BigUint> = (0..=size)
+            .map(|i| {
+                let mut exponent = BigUint::zero();
+                if i > 0 {
+                    exponent += &vec_r[i - 1] * challenge;
+                }
+                if i < size {
+                    exponent += &vec_r[i] * &vec_s_tilde[i];
+                }
+                exponent
+            })
+            .collect();
+        let exponents: Vec<&BigUint> = exponents.iter().collect();
+        let right = multi_modpow(&vec_c_hat_extended, &exponents, p)
+            .modmul(&g_table.pow(&g_exponent), p);
+
+        Self::is_batch_equal(&left, &right, p)
+    }
+
+    /// Compares both sides of a batched equation.
+    /// The commitments of the prover are not necessarily elements of G_q, e.g. -t_hat_i.
+    /// Therefore, both sides are squared which removes the order 2 component (p = 2q + 1).
+    fn is_batch_equal(left: &BigUint, right: &BigUint, p: &BigUint) -> bool {
+        left.modmul(left, p) == right.modmul(right, p)
+    }
 }
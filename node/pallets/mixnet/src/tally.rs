@@ -0,0 +1,73 @@
+use crate::types::TallyRound;
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// counts the points of every candidate (index) of the decrypted rankings,
+/// every ranking is given together with the number of ballots it has been decrypted from.
+/// the candidate at rank i gets (nr_of_candidates - 1 - i) points, unranked candidates get none.
+pub fn borda(nr_of_candidates: u32, rankings: &[(Vec<u32>, u64)]) -> BTreeMap<u32, u64> {
+    let mut points: BTreeMap<u32, u64> = (0..nr_of_candidates)
+        .map(|candidate| (candidate, 0))
+        .collect();
+    for (ranking, count) in rankings.iter() {
+        for (rank, candidate) in ranking.iter().enumerate() {
+            let score = nr_of_candidates.saturating_sub(rank as u32 + 1) as u64;
+            let entry = points.entry(*candidate).or_default();
+            *entry = entry.saturating_add(score.saturating_mul(*count));
+        }
+    }
+    points
+}
+
+/// counts the highest ranked remaining candidate of every ranking (weighted by its count).
+/// rankings without a remaining candidate are exhausted and not counted.
+fn count_round(remaining: &[u32], rankings: &[(Vec<u32>, u64)]) -> BTreeMap<u32, u64> {
+    let mut counts: BTreeMap<u32, u64> =
+        remaining.iter().map(|candidate| (*candidate, 0)).collect();
+    for (ranking, count) in rankings.iter() {
+        if let Some(candidate) = ranking.iter().find(|c| remaining.contains(c)) {
+            let entry = counts.entry(*candidate).or_default();
+            *entry = entry.saturating_add(*count);
+        }
+    }
+    counts
+}
+
+/// tallies the decrypted rankings by instant-runoff and returns all rounds, the last round is the final result.
+/// every round the candidate with the fewest votes is eliminated until a candidate has a majority
+/// of the counted (not exhausted) rankings or a single candidate remains.
+/// ties are eliminated in reverse candidate order (the candidate with the highest index first).
+/// every ranking is given together with the number of ballots it has been decrypted from.
+pub fn instant_runoff(
+    nr_of_candidates: u32,
+    rankings: &[(Vec<u32>, u64)],
+) -> Vec<TallyRound> {
+    let mut remaining: Vec<u32> = (0..nr_of_candidates).collect();
+    let mut rounds: Vec<TallyRound> = Vec::with_capacity(nr_of_candidates as usize);
+
+    while !remaining.is_empty() {
+        let counts = count_round(&remaining, rankings);
+        let counted: u128 = counts.values().map(|count| *count as u128).sum();
+        let has_majority = counts.values().any(|count| *count as u128 * 2 > counted);
+        if has_majority || remaining.len() == 1 {
+            rounds.push(TallyRound {
+                counts,
+                eliminated: None,
+            });
+            break;
+        }
+
+        // the candidate with the fewest votes, the last one in case of a tie
+        let eliminated = counts
+            .iter()
+            .rev()
+            .min_by_key(|(_, count)| **count)
+            .map(|(candidate, _)| *candidate)
+            .expect("at least two candidates remain");
+        remaining.retain(|candidate| *candidate != eliminated);
+        rounds.push(TallyRound {
+            counts,
+            eliminated: Some(eliminated),
+        });
+    }
+    rounds
+}
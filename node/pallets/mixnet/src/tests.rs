@@ -1,35 +1,86 @@
+use crate::migrations::{
+    v1::{BallotV0, VoteV0},
+    v2::{BallotV1, TopicV1, VoteProposalV1},
+    v3::{TopicV2, VoteProposalV2},
+    v8::BallotV2,
+    STORAGE_VERSION,
+};
 use crate::mock::*;
+use crate::offchain::{
+    decrypt, generators,
+    jobs::{self, OffchainJob},
+    metrics, results, sizing,
+};
+use crate::tally::{borda, instant_runoff};
 use crate::types::{
-    Ballot, Cipher, PublicKey as SubstratePK, PublicParameters, ShufflePayload,
-    ShuffleProof as Proof, VotePhase, Wrapper,
+    Ballot, BallotBoxFreeze, Cipher, ClaimSignature, DecryptedSharesSubmission,
+    EligibilityKey, HashFunction, HeartbeatPayload, IdentityProvider,
+    LinkedShufflePayload, OffchainHistogram, OffchainMetrics, ProofKind, ProtocolConfig,
+    PublicKey as SubstratePK, PublicParameters, QuestionType, RankedTallyMethod,
+    ShufflePayload, ShuffleProof as Proof, ShuffleSubmission, SpoiledBallotReveal,
+    TallyRound, TallyStrategy, TopicPacking, ValidityProof, VoteMetadata, VotePhase,
+    VoteTemplate, Wrapper, MAX_CANCELLATION_REASON_LENGTH, MAX_CANDIDATES,
+    MAX_SOURCE_TAG_LENGTH, NO_ANSWER, PROTOCOL_VERSION, STORAGE_MODULE_PREFIX,
 };
 use crate::*;
-use codec::Decode;
+use codec::{Decode, Encode};
 use crypto::{
+    blind::{
+        BlindSignature, BlindSignaturePrivateKey, BlindSignaturePublicKey,
+        PUBLIC_EXPONENT,
+    },
     encryption::ElGamal,
     helper::Helper,
-    proofs::{decryption::DecryptionProof, keygen::KeyGenerationProof},
+    proofs::{
+        decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
+        encryption::EncryptionProof,
+        key_switch::KeySwitchProof,
+        keygen::KeyGenerationProof,
+        membership::MembershipProof,
+        pet::PlaintextEquivalenceProof,
+    },
     types::{
         Cipher as BigCipher, ElGamalParams, ModuloOperations, PrivateKey,
         PublicKey as ElGamalPK,
     },
 };
-use frame_support::{assert_err, assert_ok};
+use frame_support::{
+    assert_err, assert_ok,
+    storage::{
+        migration::{put_storage_value, StorageIterator},
+        StorageDoubleMap,
+    },
+    traits::{Currency, Get, OnInitialize, OnRuntimeUpgrade, ReservableCurrency},
+    unsigned::ValidateUnsigned,
+    Blake2_128Concat, StorageHasher,
+};
+use frame_system::offchain::SignedPayload;
 use hex_literal::hex;
+use mixnet_primitives::{
+    checkpoint::SignedCheckpoint,
+    claims::eligibility_claim_message,
+    relay::{relayed_ballot_message, CompactBallot},
+    tokens::{anonymous_ballot_message, eligibility_token_message, nullifier, Nullifier},
+};
+use mixnet_test_utils::{
+    accounts::{account, ALICE, BOB, CHARLIE, DAVE},
+    ElectionFixture, KeySize,
+};
 use num_bigint::BigUint;
-use num_traits::Zero;
+use num_traits::{One, Zero};
+use sp_core::{sr25519, Pair};
+use sp_runtime::{
+    offchain::StorageKind,
+    transaction_validity::{InvalidTransaction, TransactionSource},
+    DispatchError,
+};
 use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
 const NR_OF_SHUFFLES: u8 = 0;
 
 fn get_voting_authority() -> Origin {
     // use Alice as VotingAuthority
-    let account_id: [u8; 32] =
-        hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d").into();
-    let account =
-        <TestRuntime as frame_system::Trait>::AccountId::decode(&mut &account_id[..])
-            .unwrap();
-    Origin::signed(account)
+    Origin::signed(account(&ALICE))
 }
 
 fn get_sealer_bob() -> (
@@ -37,13 +88,8 @@ fn get_sealer_bob() -> (
     <TestRuntime as frame_system::Trait>::AccountId,
     [u8; 32],
 ) {
-    let account_id: [u8; 32] =
-        hex!("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48").into();
-
-    let sealer: <TestRuntime as frame_system::Trait>::AccountId =
-        <TestRuntime as frame_system::Trait>::AccountId::decode(&mut &account_id[..])
-            .unwrap();
-    (Origin::signed(sealer), sealer, account_id)
+    let sealer: <TestRuntime as frame_system::Trait>::AccountId = account(&BOB);
+    (Origin::signed(sealer), sealer, BOB)
 }
 
 fn get_sealer_charlie() -> (
@@ -51,13 +97,8 @@ fn get_sealer_charlie() -> (
     <TestRuntime as frame_system::Trait>::AccountId,
     [u8; 32],
 ) {
-    let account_id: [u8; 32] =
-        hex!("90b5ab205c6974c9ea841be688864633dc9ca8a357843eeacf2314649965fe22").into();
-
-    let sealer: <TestRuntime as frame_system::Trait>::AccountId =
-        <TestRuntime as frame_system::Trait>::AccountId::decode(&mut &account_id[..])
-            .unwrap();
-    (Origin::signed(sealer), sealer, account_id)
+    let sealer: <TestRuntime as frame_system::Trait>::AccountId = account(&CHARLIE);
+    (Origin::signed(sealer), sealer, CHARLIE)
 }
 
 fn setup_sealer(
@@ -95,6 +136,23 @@ fn setup_public_key(vote_id: VoteId, pk: SubstratePK) {
 }
 
 fn setup_vote(params: PublicParameters) -> (Vec<u8>, Vec<u8>) {
+    setup_vote_with_strategy(params, TallyStrategy::Mixnet)
+}
+
+fn setup_vote_with_strategy(
+    params: PublicParameters,
+    tally_strategy: TallyStrategy,
+) -> (Vec<u8>, Vec<u8>) {
+    let (vote_id, topic_id) = setup_vote_in_key_generation(params, tally_strategy);
+    set_vote_phase(vote_id.clone(), VotePhase::Voting);
+    (vote_id, topic_id)
+}
+
+/// creates a vote which remains in the key generation phase
+fn setup_vote_in_key_generation(
+    params: PublicParameters,
+    tally_strategy: TallyStrategy,
+) -> (Vec<u8>, Vec<u8>) {
     // use Alice as VotingAuthority
     let who = get_voting_authority();
 
@@ -104,13 +162,20 @@ fn setup_vote(params: PublicParameters) -> (Vec<u8>, Vec<u8>) {
 
     let topic_id = "20201212-01".as_bytes().to_vec();
     let topic_question = "Moritz for President?".as_bytes().to_vec();
-    let topic: Topic = (topic_id.clone(), topic_question);
+    let topic: Topic = Topic::new(topic_id.clone(), topic_question);
     let topics = vec![topic];
 
-    let vote_created =
-        OffchainModule::create_vote(who, vote_id.clone(), vote_title, params, topics, 2);
+    let vote_created = OffchainModule::propose_vote(
+        who,
+        vote_id.clone(),
+        vote_title,
+        params,
+        topics,
+        2,
+        3,
+        tally_strategy,
+    );
     assert_ok!(vote_created);
-    set_vote_phase(vote_id.clone(), VotePhase::Voting);
     (vote_id, topic_id)
 }
 
@@ -123,6 +188,77 @@ fn set_vote_phase(vote_id: VoteId, vote_phase: VotePhase) {
     ));
 }
 
+/// creates a question with the given number of candidates
+fn setup_question(
+    topic_id: &str,
+    question_type: QuestionType,
+    nr_of_candidates: u32,
+) -> Topic {
+    Topic {
+        id: topic_id.as_bytes().to_vec(),
+        question: "Who for President?".as_bytes().to_vec(),
+        question_type,
+        candidates: (0..nr_of_candidates)
+            .map(|index| format!("Candidate {}", index).into_bytes())
+            .collect(),
+        ranked_tally: RankedTallyMethod::FirstPreference,
+    }
+}
+
+/// encrypts the answer and proves that it is one of the valid answers of the topic.
+/// the proof of an invalid answer claims that it is the first valid answer.
+fn encrypt_valid_answer(
+    pk: &ElGamalPK,
+    topic: &Topic,
+    answer: u64,
+    r: u32,
+    voter_id: &[u8],
+) -> (Cipher, Option<ValidityProof>) {
+    encrypt_one_of(pk, topic.valid_answers(), answer, r, voter_id)
+}
+
+/// encrypts the answer of a weighted voter (0 or weight) and proves it
+fn encrypt_weighted_answer(
+    pk: &ElGamalPK,
+    weight: u64,
+    answer: u64,
+    r: u32,
+    voter_id: &[u8],
+) -> (Cipher, Option<ValidityProof>) {
+    encrypt_one_of(pk, vec![0, weight], answer, r, voter_id)
+}
+
+fn encrypt_one_of(
+    pk: &ElGamalPK,
+    valid_answers: Vec<u64>,
+    answer: u64,
+    r: u32,
+    voter_id: &[u8],
+) -> (Cipher, Option<ValidityProof>) {
+    let index = valid_answers
+        .iter()
+        .position(|valid| *valid == answer)
+        .unwrap_or(0);
+    let messages: Vec<BigUint> = valid_answers.into_iter().map(BigUint::from).collect();
+    let simulated: Vec<(BigUint, BigUint)> = (1..messages.len() as u32)
+        .map(|i| (BigUint::from(i + 3), BigUint::from(i + 5)))
+        .collect();
+    let r = BigUint::from(r);
+    let cipher = ElGamal::encrypt_encode(&BigUint::from(answer), &r, pk);
+    let proof = MembershipProof::generate(
+        &pk.params,
+        pk,
+        &cipher,
+        &r,
+        &messages,
+        index,
+        &BigUint::from(13u32),
+        &simulated,
+        voter_id,
+    );
+    (cipher.into(), Some(proof.into()))
+}
+
 fn setup_ciphers(vote_id: &VoteId, topic_id: &TopicId, pk: &ElGamalPK, encoded: bool) {
     let messages = vec![
         BigUint::from(1u32),
@@ -159,7 +295,12 @@ fn setup_ciphers(vote_id: &VoteId, topic_id: &TopicId, pk: &ElGamalPK, encoded:
             cipher = ElGamal::encrypt(&messages[index], &random, pk).into();
         }
         let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher)];
-        let ballot: Ballot = Ballot { answers };
+        let ballot: Ballot = Ballot {
+            answers,
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
 
         assert_ok!(OffchainModule::cast_ballot(
             voter.clone(),
@@ -169,6 +310,24 @@ fn setup_ciphers(vote_id: &VoteId, topic_id: &TopicId, pk: &ElGamalPK, encoded:
     }
 }
 
+/// commits to the shuffle payload and reveals it in the next block
+fn commit_and_reveal_shuffle(
+    sealer: Origin,
+    vote_id: VoteId,
+    topic_id: TopicId,
+    payload: ShufflePayload,
+) -> DispatchResult {
+    let commitment = shuffle::commitment::hash_shuffle_payload(&payload);
+    OffchainModule::commit_shuffle(
+        sealer.clone(),
+        vote_id.clone(),
+        topic_id.clone(),
+        commitment,
+    )?;
+    System::set_block_number(System::block_number() + 1);
+    OffchainModule::submit_shuffled_votes_and_proof(sealer, vote_id, topic_id, payload)
+}
+
 fn shuffle_proof_test(
     vote_id: Vec<u8>,
     topic_id: Vec<u8>,
@@ -181,7 +340,7 @@ fn shuffle_proof_test(
 
     // get the encrypted votes
     let big_ciphers_from_chain: Vec<BigCipher> =
-        Wrapper(OffchainModule::ciphers(&topic_id, NR_OF_SHUFFLES)).into();
+        Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES)).into();
     assert!(big_ciphers_from_chain.len() > 0);
 
     // shuffle the votes
@@ -211,6 +370,7 @@ fn shuffle_proof_test(
         big_ciphers_from_chain,
         shuffled_ciphers,
         &pk,
+        &ProtocolConfig::new(3),
     );
     let is_proof_valid = verification.unwrap();
     is_proof_valid
@@ -226,6 +386,37 @@ fn test_setup_public_key_work() {
     });
 }
 
+#[test]
+fn test_genesis_config_builder() {
+    let (_, bob, _) = get_sealer_bob();
+    let (_, charlie, _) = get_sealer_charlie();
+    let alice: <TestRuntime as frame_system::Trait>::AccountId =
+        <TestRuntime as frame_system::Trait>::AccountId::decode(
+            &mut &hex!(
+                "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+            )[..],
+        )
+        .unwrap();
+
+    let mut storage = frame_system::GenesisConfig::default()
+        .build_storage::<TestRuntime>()
+        .unwrap();
+    GenesisConfig::<TestRuntime>::default()
+        .with_authority(alice)
+        .with_sealers(vec![bob, charlie])
+        .with_default_params()
+        .assimilate_storage(&mut storage)
+        .unwrap();
+
+    sp_io::TestExternalities::from(storage).execute_with(|| {
+        assert_eq!(OffchainModule::voting_authorities(), vec![alice]);
+        assert_eq!(OffchainModule::sealers(), vec![bob, charlie]);
+        let (params, _, _) = Helper::setup_lg_system();
+        let expected: PublicParameters = params.into();
+        assert_eq!(OffchainModule::default_public_parameters(), expected);
+    });
+}
+
 #[test]
 fn test_setup_vote_works() {
     let (mut t, _, _) = ExternalityBuilder::build();
@@ -305,7 +496,7 @@ fn test_fetch_public_key_does_not_exist() {
 }
 
 #[test]
-fn test_create_vote_not_a_voting_authority() {
+fn test_propose_vote_not_a_voting_authority() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
         // create the submitter (i.e. the default voter)
@@ -320,24 +511,157 @@ fn test_create_vote_not_a_voting_authority() {
 
         let topic_id = "20201212-01".as_bytes().to_vec();
         let topic_question = "Moritz for President?".as_bytes().to_vec();
-        let topic: Topic = (topic_id, topic_question);
+        let topic: Topic = Topic::new(topic_id, topic_question);
         let topics = vec![topic];
 
         assert_err!(
-            OffchainModule::create_vote(
+            OffchainModule::propose_vote(
                 who,
                 vote_id,
                 vote_title,
                 params.into(),
                 topics,
-                2
+                2,
+                3,
+                TallyStrategy::Mixnet
             ),
             Error::<TestRuntime>::NotAVotingAuthority
         )
     });
 }
+
+#[test]
+fn test_propose_vote_invalid_nr_of_shuffles() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // use Alice as VotingAuthority
+        let who = get_voting_authority();
+
+        // create the vote
+        let (params, _, _) = Helper::setup_sm_system();
+        let vote_id = "20201212".as_bytes().to_vec();
+        let vote_title = "Popular Vote of 12.12.2020".as_bytes().to_vec();
+
+        let topic_id = "20201212-01".as_bytes().to_vec();
+        let topic_question = "Moritz for President?".as_bytes().to_vec();
+        let topic: Topic = Topic::new(topic_id, topic_question);
+        let topics = vec![topic];
+
+        // at least one shuffle iteration is required
+        assert_err!(
+            OffchainModule::propose_vote(
+                who,
+                vote_id,
+                vote_title,
+                params.into(),
+                topics,
+                2,
+                0,
+                TallyStrategy::Mixnet
+            ),
+            Error::<TestRuntime>::NrOfShufflesInvalid
+        )
+    });
+}
+
+#[test]
+fn test_propose_vote_rejects_oversized_inputs() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, _) = Helper::setup_sm_system();
+        let vote_id = "20201212".as_bytes().to_vec();
+        let vote_title = "Popular Vote of 12.12.2020".as_bytes().to_vec();
+        let topic = Topic::new(
+            "20201212-01".as_bytes().to_vec(),
+            "Moritz for President?".as_bytes().to_vec(),
+        );
+        let propose = |vote_id: Vec<u8>, title: Vec<u8>, topic: Topic| {
+            OffchainModule::propose_vote(
+                get_voting_authority(),
+                vote_id,
+                title,
+                params.clone().into(),
+                vec![topic],
+                2,
+                3,
+                TallyStrategy::Mixnet,
+            )
+        };
+        let too_long = |max_length: u32| vec![b'x'; max_length as usize + 1];
+
+        assert_err!(
+            propose(
+                too_long(TestMaxVoteIdLength::get()),
+                vote_title.clone(),
+                topic.clone()
+            ),
+            Error::<TestRuntime>::VoteIdTooLong
+        );
+        assert_err!(
+            propose(
+                vote_id.clone(),
+                too_long(TestMaxTitleLength::get()),
+                topic.clone()
+            ),
+            Error::<TestRuntime>::TitleTooLong
+        );
+        let mut long_topic = topic.clone();
+        long_topic.id = too_long(TestMaxTopicIdLength::get());
+        assert_err!(
+            propose(vote_id.clone(), vote_title.clone(), long_topic),
+            Error::<TestRuntime>::TopicIdTooLong
+        );
+        let mut long_question = topic.clone();
+        long_question.question = too_long(TestMaxQuestionLength::get());
+        assert_err!(
+            propose(vote_id.clone(), vote_title.clone(), long_question),
+            Error::<TestRuntime>::QuestionTooLong
+        );
+        let mut long_candidate =
+            setup_question("20201212-01", QuestionType::SingleChoice, 2);
+        long_candidate.candidates[1] = too_long(TestMaxQuestionLength::get());
+        assert_err!(
+            propose(vote_id.clone(), vote_title.clone(), long_candidate),
+            Error::<TestRuntime>::QuestionTooLong
+        );
+        assert!(!Votes::<TestRuntime>::contains_key(&vote_id));
+
+        // inputs of the maximum length are accepted
+        assert_ok!(propose(
+            vec![b'x'; TestMaxVoteIdLength::get() as usize],
+            vec![b'x'; TestMaxTitleLength::get() as usize],
+            topic
+        ));
+
+        // questions added later are bounded as well
+        let vote_id = vec![b'x'; TestMaxVoteIdLength::get() as usize];
+        let question = Topic::new(
+            "20201212-02".as_bytes().to_vec(),
+            too_long(TestMaxQuestionLength::get()),
+        );
+        assert_err!(
+            OffchainModule::store_question(
+                get_voting_authority(),
+                vote_id.clone(),
+                question,
+                2
+            ),
+            Error::<TestRuntime>::QuestionTooLong
+        );
+        let question = Topic::new(
+            "20201212-02".as_bytes().to_vec(),
+            "Moritz for Vice President?".as_bytes().to_vec(),
+        );
+        assert_ok!(OffchainModule::store_question(
+            get_voting_authority(),
+            vote_id,
+            question,
+            2
+        ));
+    });
+}
 #[test]
-fn test_create_vote_works() {
+fn test_propose_vote_works() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
         // create the submitter (i.e. the voting_authority)
@@ -351,18 +675,220 @@ fn test_create_vote_works() {
 
         let topic_id = "20201212-01".as_bytes().to_vec();
         let topic_question = "Moritz for President?".as_bytes().to_vec();
-        let topic: Topic = (topic_id, topic_question);
+        let topic: Topic = Topic::new(topic_id, topic_question);
         let topics = vec![topic];
 
-        let vote_created = OffchainModule::create_vote(
+        let vote_created = OffchainModule::propose_vote(
             who,
-            vote_id,
+            vote_id.clone(),
             vote_title,
             params.into(),
             topics,
             2,
+            1,
+            TallyStrategy::Mixnet,
         );
         assert_ok!(vote_created);
+        assert_eq!(OffchainModule::votes(vote_id).nr_of_shuffles, 1);
+    });
+}
+
+#[test]
+fn test_create_vote_from_template() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let who = get_voting_authority();
+        let (params, _, _) = Helper::setup_sm_system();
+        let template_id = "board".as_bytes().to_vec();
+        let topic = Topic::new(
+            "01".as_bytes().to_vec(),
+            "Approve the annual accounts?".as_bytes().to_vec(),
+        );
+        let template = VoteTemplate {
+            params: params.into(),
+            topics: vec![topic.clone()],
+            batch_size: 2,
+            nr_of_shuffles: 3,
+            tally_strategy: TallyStrategy::Mixnet,
+        };
+
+        // only a voting authority can store a template
+        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        assert_err!(
+            OffchainModule::store_vote_template(
+                Origin::signed(account),
+                template_id.clone(),
+                template.clone()
+            ),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+
+        // a template without topics is rejected
+        let empty = VoteTemplate {
+            topics: Vec::new(),
+            ..template.clone()
+        };
+        assert_err!(
+            OffchainModule::store_vote_template(who.clone(), template_id.clone(), empty),
+            Error::<TestRuntime>::VoteTemplateTooLarge
+        );
+
+        // the template must exist
+        let vote_id = "20210101".as_bytes().to_vec();
+        let title = "Board Vote of 01.01.2021".as_bytes().to_vec();
+        assert_err!(
+            OffchainModule::create_vote_from_template(
+                who.clone(),
+                template_id.clone(),
+                vote_id.clone(),
+                title.clone()
+            ),
+            Error::<TestRuntime>::VoteTemplateDoesNotExist
+        );
+
+        assert_ok!(OffchainModule::store_vote_template(
+            who.clone(),
+            template_id.clone(),
+            template.clone()
+        ));
+        assert_eq!(
+            OffchainModule::vote_template(&template_id),
+            Some(template.clone())
+        );
+
+        // the vote is cloned from the template with its own id, title and phase
+        assert_ok!(OffchainModule::create_vote_from_template(
+            who.clone(),
+            template_id.clone(),
+            vote_id.clone(),
+            title.clone()
+        ));
+        let vote = OffchainModule::votes(&vote_id);
+        assert_eq!(vote.title, title);
+        assert_eq!(vote.phase, VotePhase::default());
+        assert_eq!(vote.params, template.params);
+        assert_eq!(vote.nr_of_shuffles, 3);
+        let topic_id = "20210101-01".as_bytes().to_vec();
+        assert_eq!(
+            OffchainModule::topics(&vote_id),
+            vec![Topic {
+                id: topic_id.clone(),
+                ..topic.clone()
+            }]
+        );
+        assert!(OffchainModule::shuffle_state((&vote_id, &topic_id)).is_some());
+
+        // the topics of the votes created from the same template are distinct
+        let next_vote_id = "20210201".as_bytes().to_vec();
+        assert_ok!(OffchainModule::create_vote_from_template(
+            who.clone(),
+            template_id.clone(),
+            next_vote_id.clone(),
+            "Board Vote of 01.02.2021".as_bytes().to_vec()
+        ));
+        assert_eq!(
+            OffchainModule::topics(&next_vote_id)[0].id,
+            "20210201-01".as_bytes().to_vec()
+        );
+
+        // a vote can't be created twice
+        assert_err!(
+            OffchainModule::create_vote_from_template(who, template_id, vote_id, title),
+            Error::<TestRuntime>::VoteAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn test_propose_vote_requires_approvals() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // use Alice and Bob as VotingAuthorities, both need to approve
+        let alice = get_voting_authority();
+        let (bob, bob_id, _) = get_sealer_bob();
+        VotingAuthorities::<TestRuntime>::mutate(|authorities| authorities.push(bob_id));
+        ApprovalThreshold::put(2);
+
+        // propose the vote
+        let (params, _, _) = Helper::setup_sm_system();
+        let vote_id = "20201212".as_bytes().to_vec();
+        let vote_title = "Popular Vote of 12.12.2020".as_bytes().to_vec();
+
+        let topic_id = "20201212-01".as_bytes().to_vec();
+        let topic_question = "Moritz for President?".as_bytes().to_vec();
+        let topic: Topic = Topic::new(topic_id, topic_question);
+        let topics = vec![topic];
+
+        assert_ok!(OffchainModule::propose_vote(
+            alice.clone(),
+            vote_id.clone(),
+            vote_title,
+            params.into(),
+            topics,
+            2,
+            3,
+            TallyStrategy::Mixnet,
+        ));
+
+        // the vote is only created once approved by Bob
+        assert!(!Votes::<TestRuntime>::contains_key(&vote_id));
+        assert!(OffchainModule::vote_proposal(&vote_id).is_some());
+        assert_err!(
+            OffchainModule::approve_vote(alice, vote_id.clone()),
+            Error::<TestRuntime>::AlreadyApproved
+        );
+
+        // NOT a voting authority
+        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        assert_err!(
+            OffchainModule::approve_vote(Origin::signed(account), vote_id.clone()),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+
+        assert_ok!(OffchainModule::approve_vote(bob.clone(), vote_id.clone()));
+        assert!(Votes::<TestRuntime>::contains_key(&vote_id));
+        assert!(OffchainModule::vote_proposal(&vote_id).is_none());
+        assert_eq!(OffchainModule::vote_ids(), vec![vote_id.clone()]);
+
+        // the proposal has been consumed
+        assert_err!(
+            OffchainModule::approve_vote(bob, vote_id),
+            Error::<TestRuntime>::VoteProposalDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn test_set_vote_phase_tallying_requires_approvals() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, _) = setup_vote(params.into());
+
+        // use Alice and Bob as VotingAuthorities, both need to approve
+        let alice = get_voting_authority();
+        let (bob, bob_id, _) = get_sealer_bob();
+        VotingAuthorities::<TestRuntime>::mutate(|authorities| authorities.push(bob_id));
+        ApprovalThreshold::put(2);
+
+        assert_ok!(OffchainModule::set_vote_phase(
+            alice.clone(),
+            vote_id.clone(),
+            VotePhase::Tallying
+        ));
+        assert_eq!(OffchainModule::votes(&vote_id).phase, VotePhase::Voting);
+        assert_err!(
+            OffchainModule::set_vote_phase(alice, vote_id.clone(), VotePhase::Tallying),
+            Error::<TestRuntime>::AlreadyApproved
+        );
+
+        assert_ok!(OffchainModule::set_vote_phase(
+            bob,
+            vote_id.clone(),
+            VotePhase::Tallying
+        ));
+        assert_eq!(OffchainModule::votes(&vote_id).phase, VotePhase::Tallying);
+        assert!(OffchainModule::tallying_approvals(&vote_id).is_empty());
     });
 }
 
@@ -380,7 +906,7 @@ fn test_store_question_not_a_voting_authority() {
         // Create A New Topic
         let new_topic_id = "20201212-02".as_bytes().to_vec();
         let topic_question = "Moritz for King?".as_bytes().to_vec();
-        let topic: Topic = (new_topic_id.clone(), topic_question);
+        let topic: Topic = Topic::new(new_topic_id.clone(), topic_question);
 
         // Try to store the Topic (Question)
         assert_err!(
@@ -404,7 +930,7 @@ fn test_store_question_no_vote_exists() {
         // Create A New Topic
         let new_topic_id = "20201212-02".as_bytes().to_vec();
         let topic_question = "Moritz for King?".as_bytes().to_vec();
-        let topic: Topic = (new_topic_id.clone(), topic_question);
+        let topic: Topic = Topic::new(new_topic_id.clone(), topic_question);
 
         // Try to store the Topic (Question)
         assert_err!(
@@ -424,12 +950,13 @@ fn test_store_question_works() {
 
         // Setup Vote & Store initial Topic
         let (params, _, _) = Helper::setup_sm_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
+        let (vote_id, topic_id) =
+            setup_vote_in_key_generation(params.into(), TallyStrategy::Mixnet);
 
         // Create A New Topic
         let new_topic_id = "20201212-02".as_bytes().to_vec();
         let topic_question = "Moritz for King?".as_bytes().to_vec();
-        let topic: Topic = (new_topic_id.clone(), topic_question);
+        let topic: Topic = Topic::new(new_topic_id.clone(), topic_question);
 
         // Store the Topic (Question)
         let question_stored =
@@ -438,110 +965,323 @@ fn test_store_question_works() {
 
         let topics = OffchainModule::topics(vote_id);
         assert_eq!(topics.len(), 2usize);
-        assert_eq!(topics[0].0, topic_id);
-        assert_eq!(topics[1].0, new_topic_id);
+        assert_eq!(topics[0].id, topic_id);
+        assert_eq!(topics[1].id, new_topic_id);
     });
 }
 
 #[test]
-fn test_cast_ballot_no_vote_exists() {
+fn test_store_question_fails_after_voting_started() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // use the default voter
-        let acct: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
-
-        // create not existing topic_id and vote_id
-        let topic_id = "Topic Doesn't Exist".as_bytes().to_vec();
-        let vote_id = "Vote Doesn't Exist".as_bytes().to_vec();
+        let who = get_voting_authority();
+        let topic: Topic = Topic::new(
+            "20201212-02".as_bytes().to_vec(),
+            "Moritz for King?".as_bytes().to_vec(),
+        );
 
-        // create fake cipher & ballot
-        let cipher = Cipher {
-            a: "1".as_bytes().to_vec(),
-            b: "2".as_bytes().to_vec(),
-        };
-        let answers = vec![(topic_id, cipher)];
-        let ballot: Ballot = Ballot { answers };
+        // Setup Vote -> VotePhase::Voting
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, _) = setup_vote(params.into());
         assert_err!(
-            OffchainModule::cast_ballot(
-                Origin::signed(acct),
+            OffchainModule::store_question(
+                who.clone(),
                 vote_id.clone(),
-                ballot.clone()
+                topic.clone(),
+                2
             ),
-            Error::<TestRuntime>::VoteDoesNotExist
+            Error::<TestRuntime>::VoteAlreadyStarted
         );
+
+        // the topics are still frozen in the tallying phase
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert_err!(
+            OffchainModule::store_question(who, vote_id.clone(), topic, 2),
+            Error::<TestRuntime>::VoteAlreadyStarted
+        );
+        assert_eq!(OffchainModule::topics(vote_id).len(), 1usize);
     });
 }
 
 #[test]
-fn test_cast_ballot_works_encoded() {
+fn test_store_question_with_candidates() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Setup Public Key
-        let (params, _, pk) = Helper::setup_sm_system();
-        let q = &params.q();
+        let who = get_voting_authority();
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, _) =
+            setup_vote_in_key_generation(params.clone().into(), TallyStrategy::Mixnet);
+
+        // more selections than candidates, too many candidates, a ranking without candidates
+        let invalid = vec![
+            setup_question("20201212-02", QuestionType::MultiSelect { max: 4 }, 3),
+            setup_question(
+                "20201212-02",
+                QuestionType::SingleChoice,
+                MAX_CANDIDATES + 1,
+            ),
+            setup_question("20201212-02", QuestionType::Ranked { candidates: 1 }, 0),
+        ];
+        for topic in invalid.into_iter() {
+            assert_err!(
+                OffchainModule::store_question(who.clone(), vote_id.clone(), topic, 2),
+                Error::<TestRuntime>::InvalidQuestionType
+            );
+        }
 
-        // Setup Vote
-        let (vote_id, topic_id) = setup_vote(params.into());
-        setup_public_key(vote_id.clone(), pk.clone().into());
+        let ranked =
+            setup_question("20201212-02", QuestionType::Ranked { candidates: 2 }, 3);
+        assert_ok!(OffchainModule::store_question(
+            who.clone(),
+            vote_id.clone(),
+            ranked.clone(),
+            2
+        ));
+        assert_eq!(OffchainModule::topics(vote_id)[1], ranked);
 
-        // Create the voter
-        let acct: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        // the homomorphic tally only supports yes/no questions
+        assert_err!(
+            OffchainModule::propose_vote(
+                who,
+                "20201213".as_bytes().to_vec(),
+                "Popular Vote of 13.12.2020".as_bytes().to_vec(),
+                params.into(),
+                vec![ranked],
+                2,
+                3,
+                TallyStrategy::Homomorphic,
+            ),
+            Error::<TestRuntime>::InvalidQuestionType
+        );
+    });
+}
 
-        // submit the value 32
-        let num: u64 = 32;
-        let big: BigUint = BigUint::from(num);
-        let r = OffchainModule::get_random_biguint_less_than(q).unwrap();
+#[test]
+fn test_question_type_valid_answers() {
+    let single_choice = setup_question("topic-01", QuestionType::SingleChoice, 3);
+    assert_eq!(
+        single_choice.valid_answers(),
+        vec![0, 1, 2, NO_ANSWER as u64]
+    );
 
-        // use additive homomorphic encoding for message i.e. g^m
-        let cipher: Cipher = ElGamal::encrypt_encode(&big, &r, &pk).into();
-        let answers = vec![(topic_id.clone(), cipher.clone())];
-        let ballot: Ballot = Ballot { answers };
+    // 1 empty + 3 single + 3 double selections
+    let multi_select =
+        setup_question("topic-02", QuestionType::MultiSelect { max: 2 }, 3);
+    assert_eq!(multi_select.nr_of_valid_answers(), Some(7));
+    assert_eq!(multi_select.valid_answers().len(), 8);
+    assert!(multi_select
+        .valid_answers()
+        .contains(&Topic::encode_selection(&[0, 2])));
+    assert!(!multi_select
+        .valid_answers()
+        .contains(&Topic::encode_selection(&[0, 1, 2])));
+
+    // 3 * 2 rankings
+    let ranked = setup_question("topic-03", QuestionType::Ranked { candidates: 2 }, 3);
+    assert_eq!(ranked.nr_of_valid_answers(), Some(6));
+    assert_eq!(ranked.valid_answers().len(), 7);
+    let message = ranked.encode_ranking(&[2, 0]);
+    assert!(ranked.valid_answers().contains(&message));
+    assert_eq!(ranked.decode_ranking(message), Some(vec![2, 0]));
+
+    // the same candidate can't be ranked twice
+    assert_eq!(ranked.decode_ranking(ranked.encode_ranking(&[1, 1])), None);
+
+    // 8 * 7 * 6 * 5 rankings require too large proofs
+    assert!(
+        !setup_question("topic-04", QuestionType::Ranked { candidates: 4 }, 8).is_valid()
+    );
 
-        // Test
-        // call cast_ballot
-        assert_ok!(OffchainModule::cast_ballot(
-            Origin::signed(acct),
-            vote_id.clone(),
-            ballot.clone()
-        ));
-        let ballot_from_chain = OffchainModule::ballots(vote_id.clone(), acct);
-        // A encrypted ballot is inserted to Ballots vec
-        assert_eq!(ballot_from_chain, ballot.clone());
+    // free-form questions accept any answer
+    let free_form = Topic::new(
+        "topic-05".as_bytes().to_vec(),
+        "Question 5?".as_bytes().to_vec(),
+    );
+    assert!(free_form.is_valid());
+    assert!(!free_form.requires_validity_proof());
+    assert!(free_form.valid_answers().is_empty());
+
+    // only ranked questions can be tallied by Borda count or instant-runoff
+    let borda = Topic {
+        ranked_tally: RankedTallyMethod::Borda,
+        ..ranked
+    };
+    assert!(borda.is_valid());
+    let borda = Topic {
+        ranked_tally: RankedTallyMethod::Borda,
+        ..single_choice
+    };
+    assert!(!borda.is_valid());
+}
 
-        // Cipher is inserted into Ciphers
-        assert_eq!(
-            OffchainModule::ciphers(topic_id.clone(), NR_OF_SHUFFLES),
-            vec![cipher.clone()]
-        );
+#[test]
+fn test_borda_count() {
+    let rankings = vec![
+        (vec![0, 1, 2], 1),
+        (vec![1, 0, 2], 1),
+        (vec![1, 2, 0], 1),
+        (vec![2, 1], 1),
+    ];
+    let points = borda(3, &rankings);
+
+    // the first rank gets 2 points, the second 1 and the last none
+    let expected: BTreeMap<u32, u64> = vec![(0, 3), (1, 6), (2, 3)].into_iter().collect();
+    assert_eq!(points, expected);
+
+    // a ranking counts as often as it has been decrypted
+    let points = borda(3, &[(vec![0, 1, 2], 1_000_000), (vec![2, 1], 3)]);
+    let expected: BTreeMap<u32, u64> = vec![(0, 2_000_000), (1, 1_000_003), (2, 6)]
+        .into_iter()
+        .collect();
+    assert_eq!(points, expected);
+
+    // candidates without any rankings get no points
+    let points = borda(2, &[]);
+    let expected: BTreeMap<u32, u64> = vec![(0, 0), (1, 0)].into_iter().collect();
+    assert_eq!(points, expected);
+}
 
-        // An event is emitted
-        assert!(System::events().iter().any(|er| er.event
-            == TestEvent::pallet_mixnet(RawEvent::BallotSubmitted(
-                acct,
-                vote_id.clone(),
-                ballot.clone()
-            ))));
+#[test]
+fn test_instant_runoff() {
+    let rankings = vec![
+        (vec![0, 1, 2], 1),
+        (vec![0, 2, 1], 1),
+        (vec![1, 0, 2], 1),
+        (vec![2, 1, 0], 1),
+        (vec![2, 0, 1], 1),
+    ];
+    let rounds = instant_runoff(3, &rankings);
+
+    // no majority in the first round, candidate 1 has the fewest votes
+    // its vote is transferred to the next preference (candidate 0)
+    let expected = vec![
+        TallyRound {
+            counts: vec![(0, 2), (1, 1), (2, 2)].into_iter().collect(),
+            eliminated: Some(1),
+        },
+        TallyRound {
+            counts: vec![(0, 3), (2, 2)].into_iter().collect(),
+            eliminated: None,
+        },
+    ];
+    assert_eq!(rounds, expected);
+
+    // a majority in the first round ends the tally
+    let rounds = instant_runoff(3, &rankings[..3]);
+    assert_eq!(rounds.len(), 1);
+    assert_eq!(rounds[0].eliminated, None);
+
+    // the rankings are weighted by the number of ballots they have been decrypted from
+    let weighted = vec![
+        (vec![0, 1, 2], 2_000_000),
+        (vec![1, 0, 2], 1_000_000),
+        (vec![2, 1, 0], 2_000_000),
+    ];
+    let rounds = instant_runoff(3, &weighted);
+    let expected = vec![
+        TallyRound {
+            counts: vec![(0, 2_000_000), (1, 1_000_000), (2, 2_000_000)]
+                .into_iter()
+                .collect(),
+            eliminated: Some(1),
+        },
+        TallyRound {
+            counts: vec![(0, 3_000_000), (2, 2_000_000)].into_iter().collect(),
+            eliminated: None,
+        },
+    ];
+    assert_eq!(rounds, expected);
+}
 
-        // Insert another ballot
-        let ballot2 = ballot.clone();
-        assert_ok!(OffchainModule::cast_ballot(
-            Origin::signed(acct),
-            vote_id.clone(),
-            ballot.clone()
-        ));
-        // A encrypted ballot is inserted to Ballots vec
-        assert_eq!(OffchainModule::ballots(vote_id, acct), ballot2.clone());
+#[test]
+fn test_instant_runoff_ties_and_exhausted_rankings() {
+    // partial rankings are exhausted once all their candidates are eliminated
+    let rankings = vec![(vec![0], 1), (vec![1], 1), (vec![2, 1], 1), (vec![3, 2], 1)];
+    let rounds = instant_runoff(4, &rankings);
+
+    // ties are eliminated in reverse candidate order: first 3, then 1 (2 has 2 votes)
+    let eliminated: Vec<Option<u32>> =
+        rounds.iter().map(|round| round.eliminated).collect();
+    assert_eq!(eliminated, vec![Some(3), Some(1), None]);
+
+    // the exhausted ranking of candidate 1 isn't counted in the last round
+    let last = rounds.last().unwrap();
+    let expected: BTreeMap<u32, u64> = vec![(0, 1), (2, 2)].into_iter().collect();
+    assert_eq!(last.counts, expected);
+}
 
-        // Cipher is inserted into Ciphers
-        assert_eq!(
-            OffchainModule::ciphers(topic_id.clone(), NR_OF_SHUFFLES),
-            vec![cipher.clone(), cipher]
+#[test]
+fn test_cast_ballot_no_vote_exists() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // use the default voter
+        let acct: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+
+        // create not existing topic_id and vote_id
+        let topic_id = "Topic Doesn't Exist".as_bytes().to_vec();
+        let vote_id = "Vote Doesn't Exist".as_bytes().to_vec();
+
+        // create fake cipher & ballot
+        let cipher = Cipher {
+            a: "1".as_bytes().to_vec(),
+            b: "2".as_bytes().to_vec(),
+        };
+        let answers = vec![(topic_id, cipher)];
+        let ballot: Ballot = Ballot {
+            answers,
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_err!(
+            OffchainModule::cast_ballot(
+                Origin::signed(acct),
+                vote_id.clone(),
+                ballot.clone()
+            ),
+            Error::<TestRuntime>::VoteDoesNotExist
         );
     });
 }
 
 #[test]
-fn test_cast_ballot_works() {
+fn test_cast_ballot_rejects_malformed_ciphers() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.clone().into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        let acct: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        let cast = |cipher: Cipher| {
+            let ballot = Ballot {
+                answers: vec![(topic_id.clone(), cipher)],
+                proofs: Vec::new(),
+                validity_proofs: Vec::new(),
+                credential: None,
+            };
+            OffchainModule::cast_ballot(Origin::signed(acct), vote_id.clone(), ballot)
+        };
+        let cipher: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(1u32), &BigUint::from(7u32), &pk)
+                .into();
+
+        // a component is empty
+        let mut empty = cipher.clone();
+        empty.a = Vec::new();
+        assert_err!(cast(empty), Error::<TestRuntime>::CipherMalformed);
+
+        // a component is longer than the modulus
+        let mut oversized = cipher.clone();
+        oversized.b = vec![1u8; params.p.to_bytes_be().len() + 1];
+        assert_err!(cast(oversized), Error::<TestRuntime>::CipherMalformed);
+        assert!(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES).is_empty());
+
+        assert_ok!(cast(cipher));
+    });
+}
+
+#[test]
+fn test_cast_ballot_works_encoded() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
         // Setup Public Key
@@ -559,9 +1299,16 @@ fn test_cast_ballot_works() {
         let num: u64 = 32;
         let big: BigUint = BigUint::from(num);
         let r = OffchainModule::get_random_biguint_less_than(q).unwrap();
-        let cipher: Cipher = ElGamal::encrypt(&big, &r, &pk).into();
+
+        // use additive homomorphic encoding for message i.e. g^m
+        let cipher: Cipher = ElGamal::encrypt_encode(&big, &r, &pk).into();
         let answers = vec![(topic_id.clone(), cipher.clone())];
-        let ballot: Ballot = Ballot { answers };
+        let ballot: Ballot = Ballot {
+            answers,
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
 
         // Test
         // call cast_ballot
@@ -576,7 +1323,7 @@ fn test_cast_ballot_works() {
 
         // Cipher is inserted into Ciphers
         assert_eq!(
-            OffchainModule::ciphers(topic_id.clone(), NR_OF_SHUFFLES),
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES),
             vec![cipher.clone()]
         );
 
@@ -588,1427 +1335,7242 @@ fn test_cast_ballot_works() {
                 ballot.clone()
             ))));
 
-        // Insert another ballot
-        let ballot2 = ballot.clone();
+        // The same cipher cannot be submitted again
+        assert_err!(
+            OffchainModule::cast_ballot(Origin::signed(acct), vote_id.clone(), ballot),
+            Error::<TestRuntime>::DuplicateCipher
+        );
+
+        // Insert another ballot (same vote, different randomness)
+        let r2 = (r + 1u32) % q;
+        let cipher2: Cipher = ElGamal::encrypt_encode(&big, &r2, &pk).into();
+        let ballot2 = Ballot {
+            answers: vec![(topic_id.clone(), cipher2.clone())],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
         assert_ok!(OffchainModule::cast_ballot(
             Origin::signed(acct),
             vote_id.clone(),
-            ballot.clone()
+            ballot2.clone()
         ));
         // A encrypted ballot is inserted to Ballots vec
-        assert_eq!(OffchainModule::ballots(vote_id, acct), ballot2.clone());
+        assert_eq!(OffchainModule::ballots(&vote_id, acct), ballot2.clone());
 
         // Cipher is inserted into Ciphers
         assert_eq!(
-            OffchainModule::ciphers(topic_id.clone(), NR_OF_SHUFFLES),
-            vec![cipher.clone(), cipher]
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES),
+            vec![cipher, cipher2]
         );
     });
 }
 
 #[test]
-fn test_offchain_signed_tx_encoded() {
-    let (mut t, pool_state, _) = ExternalityBuilder::build();
-
+fn test_ballot_padding() {
+    let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Setup
-        let (params, _, pk) = Helper::setup_sm_system();
+        let (params, sk, pk) = Helper::setup_sm_system();
         let q = &params.q();
+        let topics: Vec<Topic> = vec![
+            Topic::new(
+                "topic-01".as_bytes().to_vec(),
+                "Question 1?".as_bytes().to_vec(),
+            ),
+            Topic::new(
+                "topic-02".as_bytes().to_vec(),
+                "Question 2?".as_bytes().to_vec(),
+            ),
+            Topic::new(
+                "topic-03".as_bytes().to_vec(),
+                "Question 3?".as_bytes().to_vec(),
+            ),
+        ];
 
-        // Setup Vote
-        let (vote_id, topic_id) = setup_vote(params.into());
-        setup_public_key(vote_id.clone(), pk.clone().into());
-
-        let num: u64 = 32;
-        let big: BigUint = BigUint::from(num);
+        // the voter only answers the second topic
         let r = OffchainModule::get_random_biguint_less_than(q).unwrap();
-
-        // use additive homomorphic encoding for message i.e. g^m
-        let cipher: Cipher = ElGamal::encrypt_encode(&big, &r, &pk).into();
-        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher)];
-        let ballot: Ballot = Ballot { answers };
-
-        // Test
-        OffchainModule::offchain_signed_tx(num, vote_id.clone(), topic_id).unwrap();
-
-        // Verify
-        let tx = pool_state.write().transactions.pop().unwrap();
-        assert!(pool_state.read().transactions.is_empty());
-        let tx = TestExtrinsic::decode(&mut &*tx).unwrap();
-        assert_eq!(tx.signature.unwrap().0, 0);
-        assert_eq!(tx.call, Call::cast_ballot(vote_id, ballot.clone()));
+        let cipher: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(1u32), &r, &pk).into();
+        let ballot = Ballot {
+            answers: vec![(topics[1].id.clone(), cipher.clone())],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert!(!ballot.has_uniform_shape(&topics));
+
+        // all other topics are answered with an encryption of the sentinel
+        let no_answer = BigUint::from(NO_ANSWER);
+        let padded = ballot.pad(&topics, |_| {
+            let r = OffchainModule::get_random_biguint_less_than(q).unwrap();
+            ElGamal::encrypt_encode(&no_answer, &r, &pk).into()
+        });
+        assert!(padded.has_uniform_shape(&topics));
+        assert_eq!(padded.answers[1], (topics[1].id.clone(), cipher));
+
+        // the sentinel can be decoded like any other answer
+        for (_, cipher) in [&padded.answers[0], &padded.answers[2]].iter() {
+            let big_cipher: BigCipher = cipher.clone().into();
+            let decoded =
+                ElGamal::decrypt_decode_bounded(&big_cipher, &sk, NO_ANSWER as u64);
+            assert_eq!(decoded, Some(no_answer.clone()));
+        }
     });
 }
 
 #[test]
-fn test_get_random_bytes() {
+fn test_register_credential() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let size: usize = 32;
-        let random = OffchainModule::get_random_bytes(size).unwrap();
-        assert_eq!(random.len(), size);
-    });
-}
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+        let (vote_id, _) = setup_vote(params.into());
+        let voter: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
 
-#[test]
-fn test_get_random_number_less_than() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let upper_bound: BigUint =
-            BigUint::parse_bytes(b"10981023801283012983912312", 10).unwrap();
-        let random = OffchainModule::get_random_biguint_less_than(&upper_bound).unwrap();
-        assert!(random < upper_bound);
+        // the credential is encrypted under the public key of the vote
+        let credential = BigUint::from(4711u32);
+        let r = OffchainModule::get_random_biguint_less_than(q).unwrap();
+        let cipher: Cipher = ElGamal::encrypt_encode(&credential, &r, &pk).into();
+
+        // only the voting authority can register credentials
+        let (bob, _, _) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::register_credential(
+                bob,
+                vote_id.clone(),
+                voter,
+                cipher.clone()
+            ),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+
+        // the public key of the vote must exist
+        assert_err!(
+            OffchainModule::register_credential(
+                get_voting_authority(),
+                vote_id.clone(),
+                voter,
+                cipher.clone()
+            ),
+            Error::<TestRuntime>::PublicKeyNotExistsError
+        );
+
+        setup_public_key(vote_id.clone(), pk.into());
+        assert_ok!(OffchainModule::register_credential(
+            get_voting_authority(),
+            vote_id.clone(),
+            voter,
+            cipher.clone()
+        ));
+        assert_eq!(
+            OffchainModule::credential(&vote_id, &voter),
+            Some(cipher.clone())
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::CredentialRegistered(
+                vote_id.clone(),
+                voter
+            ))));
+
+        // the credential cannot be replaced
+        assert_err!(
+            OffchainModule::register_credential(
+                get_voting_authority(),
+                vote_id.clone(),
+                voter,
+                cipher.clone()
+            ),
+            Error::<TestRuntime>::CredentialAlreadyRegistered
+        );
+
+        // no credentials can be registered once the tally started
+        let other: <TestRuntime as frame_system::Trait>::AccountId =
+            get_sealer_charlie().1;
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert_err!(
+            OffchainModule::register_credential(
+                get_voting_authority(),
+                vote_id,
+                other,
+                cipher
+            ),
+            Error::<TestRuntime>::WrongVotePhase
+        );
     });
 }
 
 #[test]
-fn test_get_random_number_less_than_should_panic_number_is_zero() {
+fn test_cast_ballot_with_credential() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let upper_bound: BigUint = BigUint::parse_bytes(b"0", 10).unwrap();
-        OffchainModule::get_random_biguint_less_than(&upper_bound).expect_err(
-            "The returned value should be: '<Error<T>>::RandomnessUpperBoundZeroError'",
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        let (bob, bob_id, _) = get_sealer_bob();
+        let (charlie, charlie_id, _) = get_sealer_charlie();
+
+        // Bob's credential is registered
+        let credential: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(4711u32), &BigUint::from(3u32), &pk)
+                .into();
+        assert_ok!(OffchainModule::register_credential(
+            get_voting_authority(),
+            vote_id.clone(),
+            bob_id,
+            credential
+        ));
+
+        // Bob's ballots must carry a credential, Charlie's ballots must not
+        assert_err!(
+            OffchainModule::cast_ballot(
+                bob.clone(),
+                vote_id.clone(),
+                create_ballot(&topic_id, &pk, 7)
+            ),
+            Error::<TestRuntime>::CredentialMissing
+        );
+        let cast_credential: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(4711u32), &BigUint::from(5u32), &pk)
+                .into();
+        let ballot = Ballot {
+            credential: Some(cast_credential),
+            ..create_ballot(&topic_id, &pk, 11)
+        };
+        assert_err!(
+            OffchainModule::cast_ballot(charlie.clone(), vote_id.clone(), ballot.clone()),
+            Error::<TestRuntime>::CredentialNotRegistered
+        );
+        assert_ok!(OffchainModule::cast_ballot(bob, vote_id.clone(), ballot));
+        assert_eq!(OffchainModule::credential_ballots(&vote_id), vec![bob_id]);
+
+        // the credential must be registered before the voter votes
+        assert_ok!(OffchainModule::cast_ballot(
+            charlie,
+            vote_id.clone(),
+            create_ballot(&topic_id, &pk, 13)
+        ));
+        let late_credential: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(42u32), &BigUint::from(17u32), &pk)
+                .into();
+        assert_err!(
+            OffchainModule::register_credential(
+                get_voting_authority(),
+                vote_id,
+                charlie_id,
+                late_credential
+            ),
+            Error::<TestRuntime>::BallotAlreadyCast
         );
     });
 }
 
-#[test]
-fn test_get_random_numbers_less_than() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let upper_bound: BigUint =
-            BigUint::parse_bytes(b"10981023801283012983912312", 10).unwrap();
-        let randoms: Vec<BigUint> =
-            OffchainModule::get_random_biguints_less_than(&upper_bound, 10).unwrap();
-        assert_eq!(randoms.len(), 10);
-        let zero = BigUint::zero();
-        for random in randoms.iter() {
-            assert!(random < &upper_bound);
-            assert!(random > &zero);
-        }
-    });
+/// blinds the credential quotients of the vote with z and proves the blindings
+fn blind_credential_quotients(
+    vote_id: &VoteId,
+    params: &ElGamalParams,
+    z: &BigUint,
+    sealer_id: &[u8],
+) -> (Vec<Cipher>, Vec<CredentialBlindingProof>) {
+    let quotients: Vec<BigCipher> =
+        helpers::credentials::get_credential_quotients::<TestRuntime>(vote_id).unwrap();
+    quotients
+        .iter()
+        .enumerate()
+        .map(|(index, quotient)| {
+            // every proof requires fresh randomness, otherwise z can be computed
+            let r = BigUint::parse_bytes(b"1234123123", 10).unwrap() + index as u32;
+            let blinded = PlaintextEquivalenceProof::blind(quotient, z, &params.p);
+            let proof = PlaintextEquivalenceProof::generate(
+                params, z, &r, quotient, &blinded, sealer_id,
+            );
+            (blinded.into(), proof.into())
+        })
+        .unzip()
+}
+
+/// partially decrypts the combined blinded credential quotients of the vote with the private key share
+fn decrypt_credential_blindings(
+    vote_id: &VoteId,
+    params: &ElGamalParams,
+    sk: &PrivateKey,
+    pk: &ElGamalPK,
+    sealer_id: &[u8],
+) -> (Vec<DecryptedShare>, DecryptedShareProof) {
+    let combined: Vec<BigCipher> =
+        helpers::credentials::get_combined_credential_blindings::<TestRuntime>(vote_id)
+            .unwrap();
+    let decryptions: Vec<BigUint> = combined
+        .iter()
+        .map(|cipher| ElGamal::partial_decrypt_a(cipher, sk))
+        .collect();
+    let r = BigUint::parse_bytes(b"80981238129912392", 10).unwrap();
+    let proof = DecryptionProof::generate_batched(
+        params,
+        &sk.x,
+        &pk.h,
+        &r,
+        &combined,
+        &decryptions,
+        sealer_id,
+        DEFAULT_BATCH_SOUNDNESS,
+    );
+    let shares = decryptions.iter().map(|d| d.to_bytes_be()).collect();
+    (shares, proof.into())
 }
 
 #[test]
-fn test_get_random_numbers_less_than_should_panic_number_is_zero() {
+fn test_fake_credential_ballot_is_discarded() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let upper_bound: BigUint =
-            BigUint::parse_bytes(b"10981023801283012983912312", 10).unwrap();
-        OffchainModule::get_random_biguints_less_than(&upper_bound, 0).expect_err(
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.clone().into());
+
+        // Bob and Charlie are the sealers of the vote
+        let (bob, bob_id, bob_sealer_id) = get_sealer_bob();
+        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
+        setup_sealer(
+            &params,
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
+        let (charlie, charlie_id, charlie_sealer_id) = get_sealer_charlie();
+        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
+        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
+        setup_sealer(
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            charlie.clone(),
+            &vote_id,
+            &charlie_sealer_id,
+        );
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            get_voting_authority(),
+            vote_id.clone()
+        ));
+        let pk: ElGamalPK = OffchainModule::public_key(vote_id.clone()).unwrap().into();
+
+        // the credentials of Bob and Charlie are registered
+        let encrypt = |credential: u32, r: u32| -> Cipher {
+            ElGamal::encrypt_encode(&BigUint::from(credential), &BigUint::from(r), &pk)
+                .into()
+        };
+        for (voter, credential) in [(bob_id, 4711u32), (charlie_id, 1291u32)].iter() {
+            assert_ok!(OffchainModule::register_credential(
+                get_voting_authority(),
+                vote_id.clone(),
+                *voter,
+                encrypt(*credential, 3)
+            ));
+        }
+
+        // Bob votes with his credential, Charlie with a fake credential (e.g. coerced)
+        let counted = Ballot {
+            credential: Some(encrypt(4711, 5)),
+            ..create_ballot(&topic_id, &pk, 7)
+        };
+        let discarded = Ballot {
+            credential: Some(encrypt(1292, 11)),
+            ..create_ballot(&topic_id, &pk, 13)
+        };
+        assert_ok!(OffchainModule::cast_ballot(
+            bob.clone(),
+            vote_id.clone(),
+            counted.clone()
+        ));
+        assert_ok!(OffchainModule::cast_ballot(
+            charlie.clone(),
+            vote_id.clone(),
+            discarded
+        ));
+
+        // the ballot box isn't frozen and nothing is shuffled or aggregated before the check
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::CredentialCheckStarted(
+                vote_id.clone(),
+                2
+            ))));
+        assert_eq!(OffchainModule::ballot_box_freeze(&vote_id), None);
+        assert!(OffchainModule::shuffle_state((&vote_id, &topic_id)).is_none());
+        assert_err!(
+            OffchainModule::aggregate_ciphers(
+                get_voting_authority(),
+                vote_id.clone(),
+                topic_id.clone()
+            ),
+            Error::<TestRuntime>::CredentialCheckPending
+        );
+
+        // 1. round: the sealers blind the credential quotients
+        let z = BigUint::parse_bytes(b"1701411834604692317", 10).unwrap();
+        let (blindings, mut proofs) =
+            blind_credential_quotients(&vote_id, &params, &z, &bob_sealer_id);
+        proofs.swap(0, 1);
+        assert_err!(
+            OffchainModule::submit_credential_blindings(
+                bob.clone(),
+                vote_id.clone(),
+                blindings.clone(),
+                proofs.clone()
+            ),
+            Error::<TestRuntime>::CredentialBlindingProofError
+        );
+        proofs.swap(0, 1);
+        assert_ok!(OffchainModule::submit_credential_blindings(
+            bob.clone(),
+            vote_id.clone(),
+            blindings.clone(),
+            proofs.clone()
+        ));
+        assert_err!(
+            OffchainModule::submit_credential_blindings(
+                bob.clone(),
+                vote_id.clone(),
+                blindings,
+                proofs
+            ),
+            Error::<TestRuntime>::CredentialCheckAlreadySubmitted
+        );
+        let (bob_shares, bob_proof) = (Vec::new(), DecryptedShareProof::default());
+        assert_err!(
+            OffchainModule::submit_credential_decryptions(
+                bob.clone(),
+                vote_id.clone(),
+                bob_shares,
+                bob_proof
+            ),
+            Error::<TestRuntime>::CredentialBlindingsIncomplete
+        );
+        let z = BigUint::parse_bytes(b"8232344587897", 10).unwrap();
+        let (blindings, proofs) =
+            blind_credential_quotients(&vote_id, &params, &z, &charlie_sealer_id);
+        assert_ok!(OffchainModule::submit_credential_blindings(
+            charlie.clone(),
+            vote_id.clone(),
+            blindings,
+            proofs
+        ));
+
+        // 2. round: the sealers decrypt the combined blinded quotients
+        let (bob_shares, bob_proof) = decrypt_credential_blindings(
+            &vote_id,
+            &params,
+            &bob_sk,
+            &bob_pk,
+            &bob_sealer_id,
+        );
+        assert_ok!(OffchainModule::submit_credential_decryptions(
+            bob,
+            vote_id.clone(),
+            bob_shares,
+            bob_proof
+        ));
+        assert_eq!(OffchainModule::discarded_ballots(&vote_id), None);
+        let (charlie_shares, charlie_proof) = decrypt_credential_blindings(
+            &vote_id,
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            &charlie_sealer_id,
+        );
+        assert_ok!(OffchainModule::submit_credential_decryptions(
+            charlie.clone(),
+            vote_id.clone(),
+            charlie_shares,
+            charlie_proof
+        ));
+
+        // Charlie's ballot is discarded, only Bob's cipher is frozen and shuffled
+        assert_eq!(
+            OffchainModule::discarded_ballots(&vote_id),
+            Some(vec![charlie_id])
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::CredentialsChecked(
+                vote_id.clone(),
+                1
+            ))));
+        assert_eq!(
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES),
+            vec![counted.answers[0].1.clone()]
+        );
+        assert!(OffchainModule::ballot_box_freeze(&vote_id).is_some());
+        assert!(OffchainModule::shuffle_state((&vote_id, &topic_id)).is_some());
+
+        // the credentials have been checked
+        assert_err!(
+            OffchainModule::submit_credential_blindings(
+                charlie,
+                vote_id,
+                Vec::new(),
+                Vec::new()
+            ),
+            Error::<TestRuntime>::CredentialCheckNotPending
+        );
+    });
+}
+
+#[test]
+fn test_register_voter_with_claim() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) =
+            setup_vote_in_key_generation(params.into(), TallyStrategy::Mixnet);
+        let identity_provider =
+            sr25519::Pair::from_string("//IdentityProvider", None).unwrap();
+        let voter = sr25519::Pair::from_string("//Dave", None).unwrap().public();
+        let message = eligibility_claim_message(&vote_id, &voter.encode());
+        let claim = ClaimSignature::Sr25519(identity_provider.sign(&message));
+
+        // voters can't be registered without an identity provider
+        let (bob, _, _) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::register_voter_with_claim(
+                bob.clone(),
+                vote_id.clone(),
+                voter,
+                claim.clone()
+            ),
+            Error::<TestRuntime>::NoIdentityProvider
+        );
+
+        // only the voting authority can set the identity provider
+        let provider = IdentityProvider::Sr25519(identity_provider.public());
+        assert_err!(
+            OffchainModule::set_identity_provider(
+                bob.clone(),
+                vote_id.clone(),
+                Some(provider.clone())
+            ),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+        assert_ok!(OffchainModule::set_identity_provider(
+            get_voting_authority(),
+            vote_id.clone(),
+            Some(provider.clone())
+        ));
+        assert_eq!(
+            OffchainModule::identity_provider(&vote_id),
+            Some(provider.clone())
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::IdentityProviderUpdated(
+                vote_id.clone(),
+                Some(provider.clone())
+            ))));
+
+        // the claim is bound to the voter
+        let (_, other_voter, _) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::register_voter_with_claim(
+                bob.clone(),
+                vote_id.clone(),
+                other_voter,
+                claim.clone()
+            ),
+            Error::<TestRuntime>::InvalidEligibilityClaim
+        );
+
+        // anyone can register the voter with the claim
+        assert_ok!(OffchainModule::register_voter_with_claim(
+            bob.clone(),
+            vote_id.clone(),
+            voter,
+            claim.clone()
+        ));
+        assert!(OffchainModule::registered_voter(&vote_id, &voter));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::VoterRegistered(
+                vote_id.clone(),
+                voter
+            ))));
+        assert_err!(
+            OffchainModule::register_voter_with_claim(
+                bob.clone(),
+                vote_id.clone(),
+                voter,
+                claim
+            ),
+            Error::<TestRuntime>::VoterAlreadyRegistered
+        );
+
+        // only registered voters can cast a ballot
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+        assert_err!(
+            OffchainModule::cast_ballot(
+                Origin::signed(other_voter),
+                vote_id.clone(),
+                create_ballot(&topic_id, &pk, 11)
+            ),
+            Error::<TestRuntime>::VoterNotRegistered
+        );
+        assert_ok!(OffchainModule::cast_ballot(
+            Origin::signed(voter),
+            vote_id.clone(),
+            create_ballot(&topic_id, &pk, 12)
+        ));
+
+        // the identity provider can't be changed once the voting phase has started
+        assert_err!(
+            OffchainModule::set_identity_provider(get_voting_authority(), vote_id, None),
+            Error::<TestRuntime>::VoteAlreadyStarted
+        );
+    });
+}
+
+/// a 512 bit RSA key of the voting authority which blind-signs the eligibility tokens
+fn get_eligibility_keys() -> (BlindSignaturePublicKey, BlindSignaturePrivateKey) {
+    let n = BigUint::from_bytes_be(&hex!("9de94734025dbd37e809454470f35f713b889137aab53ed4bc6e7591630ba24fab75f1ba331e387e5a5ad2e935c58c7e6d2cfbf07e0d678d7fb8630ad23980b5"));
+    let d = BigUint::from_bytes_be(&hex!("682f2c0a35717150fc67796af176b0c6bd779a0ff73203954a093aca4465bd9cf769966bf7e1924fdb025c7118814dec19bf1f47bdcb6b405dd6e2336bbb1e81"));
+    let pk = BlindSignaturePublicKey {
+        n: n.clone(),
+        e: BigUint::from(PUBLIC_EXPONENT),
+    };
+    (pk, BlindSignaturePrivateKey { n, d })
+}
+
+/// the voter draws a one-time key (the token) and blinds the message of its nullifier, the voting
+/// authority signs it and the voter unblinds the signature. returns the one-time key, the nullifier
+/// and the signature.
+fn issue_eligibility_token(
+    vote_id: &VoteId,
+    seed: &str,
+) -> (sr25519::Pair, Nullifier, Vec<u8>) {
+    let (pk, sk) = get_eligibility_keys();
+    let key = sr25519::Pair::from_string(seed, None).unwrap();
+    let nullifier = nullifier(&key.public().0);
+    let message = eligibility_token_message(vote_id, &nullifier);
+    let r = BigUint::from(1_234_567u32);
+    let blinded = BlindSignature::blind(&pk, &message, &r).unwrap();
+    let blind_signature = BlindSignature::sign_blinded(&sk, &blinded);
+    let signature = BlindSignature::unblind(&pk, &blind_signature, &r).unwrap();
+    (key, nullifier, signature.to_bytes_be())
+}
+
+/// the voter signs the anonymous ballot with the one-time key of its token
+fn sign_anonymous_ballot(
+    vote_id: &VoteId,
+    key: &sr25519::Pair,
+    ballot: &Ballot,
+) -> sr25519::Signature {
+    let nullifier = nullifier(&key.public().0);
+    key.sign(&anonymous_ballot_message(
+        vote_id,
+        &nullifier,
+        &ballot.encode(),
+    ))
+}
+
+#[test]
+fn test_cast_anonymous_ballot() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) =
+            setup_vote_in_key_generation(params.clone().into(), TallyStrategy::Mixnet);
+        let (eligibility_pk, _) = get_eligibility_keys();
+        let key: EligibilityKey = eligibility_pk.into();
+        let (token, nullifier, signature) =
+            issue_eligibility_token(&vote_id, "//TokenOfDave");
+
+        // the proof of the ballot is bound to the nullifier
+        let r = BigUint::from(7u32);
+        let big_cipher = ElGamal::encrypt_encode(&BigUint::from(1u32), &r, &pk);
+        let proof = EncryptionProof::generate(
+            &params,
+            &r,
+            &big_cipher,
+            &BigUint::from(11u32),
+            &nullifier,
+        );
+        let ballot = Ballot {
+            answers: vec![(topic_id.clone(), big_cipher.into())],
+            proofs: vec![proof.into()],
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        let ballot_signature = sign_anonymous_ballot(&vote_id, &token, &ballot);
+        let call = Call::cast_anonymous_ballot(
+            vote_id.clone(),
+            ballot.clone(),
+            token.public(),
+            signature.clone(),
+            ballot_signature.clone(),
+        );
+        let validate = |call: &Call<TestRuntime>| {
+            OffchainModule::validate_unsigned(TransactionSource::External, call)
+        };
+
+        // only the voting authority can set the eligibility key
+        let (bob, _, _) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::set_eligibility_key(bob, vote_id.clone(), Some(key.clone())),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+        assert_ok!(OffchainModule::set_eligibility_key(
+            get_voting_authority(),
+            vote_id.clone(),
+            Some(key.clone())
+        ));
+        assert_eq!(OffchainModule::eligibility_key(&vote_id), Some(key.clone()));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::EligibilityKeyUpdated(
+                vote_id.clone(),
+                Some(key.clone())
+            ))));
+
+        // anonymous ballots are only valid in the voting phase
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        assert_eq!(validate(&call), InvalidTransaction::Stale.into());
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+        assert!(validate(&call).is_ok());
+
+        // the ballots of the vote can't be cast by an account
+        let voter = sr25519::Pair::from_string("//Dave", None).unwrap().public();
+        assert_err!(
+            OffchainModule::cast_ballot(
+                Origin::signed(voter),
+                vote_id.clone(),
+                create_ballot(&topic_id, &pk, 12)
+            ),
+            Error::<TestRuntime>::AnonymousVote
+        );
+
+        // the signature of the token is bound to the nullifier of the one-time key
+        let (other_token, _, _) = issue_eligibility_token(&vote_id, "//TokenOfEve");
+        let other_ballot = create_ballot(&topic_id, &pk, 13);
+        let other_ballot_signature =
+            sign_anonymous_ballot(&vote_id, &other_token, &other_ballot);
+        let forged = Call::cast_anonymous_ballot(
+            vote_id.clone(),
+            other_ballot.clone(),
+            other_token.public(),
+            signature.clone(),
+            other_ballot_signature.clone(),
+        );
+        assert_eq!(validate(&forged), InvalidTransaction::BadProof.into());
+        assert_err!(
+            OffchainModule::cast_anonymous_ballot(
+                Origin::none(),
+                vote_id.clone(),
+                other_ballot.clone(),
+                other_token.public(),
+                signature.clone(),
+                other_ballot_signature
+            ),
+            Error::<TestRuntime>::InvalidEligibilityToken
+        );
+
+        // the ballot is bound to the one-time key, i.e., whoever copies the token and its
+        // signature from the transaction pool can't cast another ballot with them
+        let front_run = Call::cast_anonymous_ballot(
+            vote_id.clone(),
+            other_ballot.clone(),
+            token.public(),
+            signature.clone(),
+            ballot_signature.clone(),
+        );
+        assert_eq!(validate(&front_run), InvalidTransaction::BadProof.into());
+        assert_err!(
+            OffchainModule::cast_anonymous_ballot(
+                Origin::none(),
+                vote_id.clone(),
+                other_ballot,
+                token.public(),
+                signature.clone(),
+                ballot_signature.clone()
+            ),
+            Error::<TestRuntime>::AnonymousBallotSignatureError
+        );
+        assert!(!OffchainModule::spent_nullifier(&vote_id, nullifier));
+
+        // anonymous ballots must be submitted without origin
+        assert_err!(
+            OffchainModule::cast_anonymous_ballot(
+                Origin::signed(voter),
+                vote_id.clone(),
+                ballot.clone(),
+                token.public(),
+                signature.clone(),
+                ballot_signature.clone()
+            ),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(OffchainModule::cast_anonymous_ballot(
+            Origin::none(),
+            vote_id.clone(),
+            ballot.clone(),
+            token.public(),
+            signature.clone(),
+            ballot_signature
+        ));
+        assert!(OffchainModule::spent_nullifier(&vote_id, nullifier));
+        assert_eq!(
+            OffchainModule::cipher_count((&vote_id, &topic_id), NR_OF_SHUFFLES),
+            1
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::AnonymousBallotSubmitted(
+                vote_id.clone(),
+                nullifier,
+                ballot.clone()
+            ))));
+
+        // the nullifier can only be spent once
+        assert_eq!(validate(&call), InvalidTransaction::Stale.into());
+        let another_ballot = create_ballot(&topic_id, &pk, 14);
+        assert_err!(
+            OffchainModule::cast_anonymous_ballot(
+                Origin::none(),
+                vote_id.clone(),
+                another_ballot.clone(),
+                token.public(),
+                signature,
+                sign_anonymous_ballot(&vote_id, &token, &another_ballot)
+            ),
+            Error::<TestRuntime>::NullifierAlreadySpent
+        );
+
+        // the eligibility key can't be changed once the voting phase has started
+        assert_err!(
+            OffchainModule::set_eligibility_key(get_voting_authority(), vote_id, None),
+            Error::<TestRuntime>::VoteAlreadyStarted
+        );
+    });
+}
+
+#[test]
+fn test_import_ciphers() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        let source_tag = b"postal".to_vec();
+
+        // a voter casts a ballot before the postal ballots are imported
+        let voter = sr25519::Pair::from_string("//Dave", None).unwrap().public();
+        let ballot = create_ballot(&topic_id, &pk, 12);
+        assert_ok!(OffchainModule::cast_ballot(
+            Origin::signed(voter),
+            vote_id.clone(),
+            ballot.clone()
+        ));
+
+        let ciphers: Vec<Cipher> = vec![
+            create_ballot(&topic_id, &pk, 13).answers[0].1.clone(),
+            create_ballot(&topic_id, &pk, 14).answers[0].1.clone(),
+        ];
+
+        // only the voting authority can import ciphers
+        let (bob, _, _) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::import_ciphers(
+                bob,
+                vote_id.clone(),
+                topic_id.clone(),
+                ciphers.clone(),
+                source_tag.clone()
+            ),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+
+        // the batch is checked before any cipher is imported
+        let import = |topic_id: &TopicId, ciphers: Vec<Cipher>, source_tag: Vec<u8>| {
+            OffchainModule::import_ciphers(
+                get_voting_authority(),
+                vote_id.clone(),
+                topic_id.clone(),
+                ciphers,
+                source_tag,
+            )
+        };
+        assert_err!(
+            import(&b"unknown".to_vec(), ciphers.clone(), source_tag.clone()),
+            Error::<TestRuntime>::TopicNotInVote
+        );
+        assert_err!(
+            import(&topic_id, Vec::new(), source_tag.clone()),
+            Error::<TestRuntime>::CipherImportTooLarge
+        );
+        assert_err!(
+            import(
+                &topic_id,
+                ciphers.clone(),
+                vec![0u8; MAX_SOURCE_TAG_LENGTH + 1]
+            ),
+            Error::<TestRuntime>::SourceTagTooLong
+        );
+        assert_err!(
+            import(
+                &topic_id,
+                vec![ballot.answers[0].1.clone()],
+                source_tag.clone()
+            ),
+            Error::<TestRuntime>::DuplicateCipher
+        );
+        assert_err!(
+            import(
+                &topic_id,
+                vec![ciphers[0].clone(), ciphers[0].clone()],
+                source_tag.clone()
+            ),
+            Error::<TestRuntime>::DuplicateCipher
+        );
+        assert_eq!(
+            OffchainModule::cipher_count((&vote_id, &topic_id), NR_OF_SHUFFLES),
+            1
+        );
+
+        // the imported ciphers follow the ciphers of the ballots
+        assert_ok!(import(&topic_id, ciphers.clone(), source_tag.clone()));
+        assert_eq!(
+            OffchainModule::cipher_count((&vote_id, &topic_id), NR_OF_SHUFFLES),
+            3
+        );
+        let all_ciphers = helpers::ciphers::get_all_ciphers::<TestRuntime>(
+            &vote_id,
+            &topic_id,
+            NR_OF_SHUFFLES,
+        );
+        assert_eq!(all_ciphers[1..], ciphers[..]);
+
+        // the import commits to the ciphers of the batch
+        let merkle_root = helpers::merkle::merkle_root(
+            ciphers.iter().map(helpers::merkle::hash_leaf).collect(),
+        );
+        let stored = OffchainModule::cipher_import(&vote_id, 0).unwrap();
+        assert_eq!(stored.topic_id, topic_id);
+        assert_eq!(stored.source_tag, source_tag);
+        assert_eq!(stored.start, 1);
+        assert_eq!(stored.nr_of_ciphers, 2);
+        assert_eq!(stored.merkle_root, merkle_root);
+        assert_eq!(OffchainModule::cipher_import_count(&vote_id), 1);
+        assert_eq!(
+            helpers::imports::get_cipher_imports::<TestRuntime>(&vote_id),
+            vec![stored]
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::CiphersImported(
+                vote_id.clone(),
+                topic_id.clone(),
+                0,
+                source_tag.clone(),
+                2,
+                merkle_root
+            ))));
+
+        // a cipher can't be imported twice
+        assert_err!(
+            import(&topic_id, ciphers.clone(), b"scanner".to_vec()),
+            Error::<TestRuntime>::DuplicateCipher
+        );
+
+        // ciphers can only be imported in the voting phase
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        let late = vec![create_ballot(&topic_id, &pk, 15).answers[0].1.clone()];
+        assert_err!(
+            import(&topic_id, late, source_tag),
+            Error::<TestRuntime>::WrongVotePhase
+        );
+    });
+}
+
+#[test]
+fn test_cast_ballot_works() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Setup Public Key
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+
+        // Setup Vote
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // Create the voter
+        let acct: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+
+        // submit the value 32
+        let num: u64 = 32;
+        let big: BigUint = BigUint::from(num);
+        let r = OffchainModule::get_random_biguint_less_than(q).unwrap();
+        let cipher: Cipher = ElGamal::encrypt(&big, &r, &pk).into();
+        let answers = vec![(topic_id.clone(), cipher.clone())];
+        let ballot: Ballot = Ballot {
+            answers,
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+
+        // Test
+        // call cast_ballot
+        assert_ok!(OffchainModule::cast_ballot(
+            Origin::signed(acct),
+            vote_id.clone(),
+            ballot.clone()
+        ));
+        let ballot_from_chain = OffchainModule::ballots(vote_id.clone(), acct);
+        // A encrypted ballot is inserted to Ballots vec
+        assert_eq!(ballot_from_chain, ballot.clone());
+
+        // Cipher is inserted into Ciphers
+        assert_eq!(
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES),
+            vec![cipher.clone()]
+        );
+
+        // An event is emitted
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::BallotSubmitted(
+                acct,
+                vote_id.clone(),
+                ballot.clone()
+            ))));
+
+        // A receipt is stored which points to the event
+        let receipt = OffchainModule::ballot_receipt(&vote_id, &acct).unwrap();
+        assert_eq!(receipt.block_number, System::block_number());
+        assert_eq!(
+            receipt.cipher_hash,
+            sp_io::hashing::blake2_256(&vec![&cipher].encode())
+        );
+        let event = &System::events()[receipt.event_index as usize];
+        assert_eq!(
+            event.event,
+            TestEvent::pallet_mixnet(RawEvent::BallotSubmitted(
+                acct,
+                vote_id.clone(),
+                ballot.clone()
+            ))
+        );
+
+        // The same cipher cannot be submitted again
+        assert_err!(
+            OffchainModule::cast_ballot(Origin::signed(acct), vote_id.clone(), ballot),
+            Error::<TestRuntime>::DuplicateCipher
+        );
+
+        // Insert another ballot (same vote, different randomness)
+        let r2 = (r + 1u32) % q;
+        let cipher2: Cipher = ElGamal::encrypt(&big, &r2, &pk).into();
+        let ballot2 = Ballot {
+            answers: vec![(topic_id.clone(), cipher2.clone())],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_ok!(OffchainModule::cast_ballot(
+            Origin::signed(acct),
+            vote_id.clone(),
+            ballot2.clone()
+        ));
+        // A encrypted ballot is inserted to Ballots vec
+        assert_eq!(OffchainModule::ballots(&vote_id, acct), ballot2.clone());
+
+        // Cipher is inserted into Ciphers
+        assert_eq!(
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES),
+            vec![cipher, cipher2]
+        );
+    });
+}
+
+#[test]
+fn test_cast_ballot_rejects_copied_cipher() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // Alice submits a ballot
+        let alice = get_voting_authority();
+        let r = BigUint::from(7u32);
+        let cipher: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(1u32), &r, &pk).into();
+        let ballot = Ballot {
+            answers: vec![(topic_id.clone(), cipher.clone())],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_ok!(OffchainModule::cast_ballot(
+            alice,
+            vote_id.clone(),
+            ballot.clone()
+        ));
+
+        // Bob copies Alice's cipher
+        let (bob, _, _) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::cast_ballot(bob.clone(), vote_id.clone(), ballot),
+            Error::<TestRuntime>::DuplicateCipher
+        );
+
+        // a ballot may not contain the same cipher twice
+        let other: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(1u32), &(r + 1u32), &pk).into();
+        let ballot = Ballot {
+            answers: vec![(topic_id.clone(), other.clone()), (topic_id.clone(), other)],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_err!(
+            OffchainModule::cast_ballot(bob, vote_id.clone(), ballot),
+            Error::<TestRuntime>::DuplicateCipher
+        );
+        assert_eq!(
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES),
+            vec![cipher]
+        );
+    });
+}
+
+/// creates a ballot which answers the topic with the encryption of 32
+fn create_ballot(topic_id: &TopicId, pk: &ElGamalPK, random: u32) -> Ballot {
+    let cipher: Cipher =
+        ElGamal::encrypt(&BigUint::from(32u32), &BigUint::from(random), pk).into();
+    Ballot {
+        answers: vec![(topic_id.clone(), cipher)],
+        proofs: Vec::new(),
+        validity_proofs: Vec::new(),
+        credential: None,
+    }
+}
+
+#[test]
+fn test_cast_relayed_ballot() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // the voter signs the ballot offline and encodes it for a QR code
+        let voter = sr25519::Pair::from_string("//Dave", None).unwrap();
+        let ballot = create_ballot(&topic_id, &pk, 11);
+        let message = relayed_ballot_message(&vote_id, &ballot.encode());
+        let compact = CompactBallot {
+            vote_id: vote_id.clone(),
+            ballot: ballot.encode(),
+            voter: voter.public().0.to_vec(),
+            signature: voter.sign(&message).0.to_vec(),
+        }
+        .to_base45();
+
+        // the relayer scans and decodes the ballot
+        let scanned = CompactBallot::from_base45(&compact).unwrap();
+        let relayed = Ballot::decode(&mut &scanned.ballot[..]).unwrap();
+        let mut public = [0u8; 32];
+        public.copy_from_slice(&scanned.voter);
+        let public = sr25519::Public::from_raw(public);
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(&scanned.signature);
+        let signature = sr25519::Signature::from_raw(signature);
+        assert_eq!(relayed, ballot);
+
+        // the relayer can neither change the ballot nor relay it for another voter
+        let (relayer, relayer_account, bob_id) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::cast_relayed_ballot(
+                relayer.clone(),
+                scanned.vote_id.clone(),
+                create_ballot(&topic_id, &pk, 12),
+                public.clone(),
+                signature.clone()
+            ),
+            Error::<TestRuntime>::RelayedBallotSignatureError
+        );
+        assert_err!(
+            OffchainModule::cast_relayed_ballot(
+                relayer.clone(),
+                scanned.vote_id.clone(),
+                relayed.clone(),
+                sr25519::Public::from_raw(bob_id),
+                signature.clone()
+            ),
+            Error::<TestRuntime>::RelayedBallotSignatureError
+        );
+
+        // the ballot is cast by the voter
+        assert_ok!(OffchainModule::cast_relayed_ballot(
+            relayer.clone(),
+            scanned.vote_id.clone(),
+            relayed.clone(),
+            public.clone(),
+            signature.clone()
+        ));
+        let voter_account = <TestRuntime as frame_system::Trait>::AccountId::decode(
+            &mut &scanned.voter[..],
+        )
+        .unwrap();
+        assert_eq!(OffchainModule::ballots(&vote_id, voter_account), ballot);
+        assert!(System::events().iter().any(|record| record.event
+            == TestEvent::pallet_mixnet(RawEvent::BallotRelayed(
+                relayer_account,
+                voter_account,
+                vote_id.clone()
+            ))));
+
+        // a relayed ballot can't be replayed
+        assert_err!(
+            OffchainModule::cast_relayed_ballot(
+                relayer,
+                scanned.vote_id,
+                relayed,
+                public,
+                signature
+            ),
+            Error::<TestRuntime>::DuplicateCipher
+        );
+    });
+}
+
+#[test]
+fn test_cast_ballot_rate_limit() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        set_max_ballots_per_period(2);
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        let voter = Origin::signed(Default::default());
+        System::set_block_number(1);
+
+        // the voter can cast two ballots per period
+        for random in [1u32, 2].iter() {
+            assert_ok!(OffchainModule::cast_ballot(
+                voter.clone(),
+                vote_id.clone(),
+                create_ballot(&topic_id, &pk, *random)
+            ));
+        }
+        assert_err!(
+            OffchainModule::cast_ballot(
+                voter.clone(),
+                vote_id.clone(),
+                create_ballot(&topic_id, &pk, 3)
+            ),
+            Error::<TestRuntime>::BallotRateLimitExceeded
+        );
+        assert_eq!(
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES).len(),
+            2
+        );
+
+        // the rate limit applies per account
+        let (bob, _, _) = get_sealer_bob();
+        assert_ok!(OffchainModule::cast_ballot(
+            bob,
+            vote_id.clone(),
+            create_ballot(&topic_id, &pk, 4)
+        ));
+
+        // a new period starts once the current one has ended
+        System::set_block_number(TestBallotRatePeriod::get());
+        assert_err!(
+            OffchainModule::cast_ballot(
+                voter.clone(),
+                vote_id.clone(),
+                create_ballot(&topic_id, &pk, 5)
+            ),
+            Error::<TestRuntime>::BallotRateLimitExceeded
+        );
+        System::set_block_number(1 + TestBallotRatePeriod::get());
+        assert_ok!(OffchainModule::cast_ballot(
+            voter,
+            vote_id,
+            create_ballot(&topic_id, &pk, 6)
+        ));
+        let rate = OffchainModule::ballot_rate(
+            <TestRuntime as frame_system::Trait>::AccountId::default(),
+        );
+        assert_eq!(rate.period_start, 1 + TestBallotRatePeriod::get());
+        assert_eq!(rate.count, 1);
+    });
+}
+
+#[test]
+fn test_cast_ballot_reserves_deposit() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        set_ballot_deposit(10);
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        let acct: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        Balances::make_free_balance_be(&acct, 25);
+
+        // the deposit is reserved per ballot
+        for random in [1u32, 2].iter() {
+            assert_ok!(OffchainModule::cast_ballot(
+                Origin::signed(acct),
+                vote_id.clone(),
+                create_ballot(&topic_id, &pk, *random)
+            ));
+        }
+        assert_eq!(Balances::reserved_balance(&acct), 20);
+        assert_eq!(Balances::free_balance(&acct), 5);
+        assert_eq!(OffchainModule::ballot_deposit(&vote_id, &acct), 20);
+
+        // the ballot is rejected if the free balance doesn't cover the deposit
+        assert_err!(
+            OffchainModule::cast_ballot(
+                Origin::signed(acct),
+                vote_id.clone(),
+                create_ballot(&topic_id, &pk, 3)
+            ),
+            Error::<TestRuntime>::InsufficientBallotDeposit
+        );
+        let (bob, _, _) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::cast_ballot(
+                bob.clone(),
+                vote_id.clone(),
+                create_ballot(&topic_id, &pk, 4)
+            ),
+            Error::<TestRuntime>::InsufficientBallotDeposit
+        );
+        assert_eq!(
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES).len(),
+            2
+        );
+
+        // the voters of a whitelisted vote don't reserve a deposit
+        assert_err!(
+            OffchainModule::whitelist_vote(bob.clone(), vote_id.clone(), true),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+        assert_ok!(OffchainModule::whitelist_vote(
+            get_voting_authority(),
+            vote_id.clone(),
+            true
+        ));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::VoteWhitelisted(
+                vote_id.clone(),
+                true
+            ))));
+        assert_ok!(OffchainModule::cast_ballot(
+            bob,
+            vote_id.clone(),
+            create_ballot(&topic_id, &pk, 4)
+        ));
+
+        // the deposit is refunded once the voting phase has ended
+        assert_err!(
+            OffchainModule::claim_ballot_deposit(Origin::signed(acct), vote_id.clone()),
+            Error::<TestRuntime>::WrongVotePhase
+        );
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert_ok!(OffchainModule::claim_ballot_deposit(
+            Origin::signed(acct),
+            vote_id.clone()
+        ));
+        assert_eq!(Balances::reserved_balance(&acct), 0);
+        assert_eq!(Balances::free_balance(&acct), 25);
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::BallotDepositRefunded(
+                acct,
+                vote_id.clone(),
+                20
+            ))));
+        assert_err!(
+            OffchainModule::claim_ballot_deposit(Origin::signed(acct), vote_id),
+            Error::<TestRuntime>::NoBallotDeposit
+        );
+    });
+}
+
+#[test]
+fn test_cast_ballot_verifies_proofs() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.clone().into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // Bob proves the knowledge of the randomness of his cipher
+        let (bob, bob_id, _) = get_sealer_bob();
+        let r = BigUint::from(7u32);
+        let x = BigUint::from(11u32);
+        let big_cipher = ElGamal::encrypt_encode(&BigUint::from(1u32), &r, &pk);
+        let proof =
+            EncryptionProof::generate(&params, &r, &big_cipher, &x, &bob_id.encode());
+        let ballot = Ballot {
+            answers: vec![(topic_id.clone(), big_cipher.clone().into())],
+            proofs: vec![proof.into()],
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+
+        // the proof is bound to Bob, it doesn't verify for Charlie
+        let (charlie, _, _) = get_sealer_charlie();
+        assert_err!(
+            OffchainModule::cast_ballot(charlie, vote_id.clone(), ballot.clone()),
+            Error::<TestRuntime>::BallotProofError
+        );
+
+        // one proof per answer is required
+        let incomplete = Ballot {
+            answers: vec![
+                (topic_id.clone(), big_cipher.clone().into()),
+                (
+                    topic_id.clone(),
+                    ElGamal::encrypt_encode(&BigUint::from(1u32), &x, &pk).into(),
+                ),
+            ],
+            proofs: ballot.proofs.clone(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_err!(
+            OffchainModule::cast_ballot(bob.clone(), vote_id.clone(), incomplete),
+            Error::<TestRuntime>::BallotProofError
+        );
+
+        assert_ok!(OffchainModule::cast_ballot(
+            bob,
+            vote_id.clone(),
+            ballot.clone()
+        ));
+        assert_eq!(OffchainModule::ballots(vote_id, bob_id), ballot);
+    });
+}
+
+#[test]
+fn test_spoil_ballot_excludes_cipher_from_tally() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // Bob and Charlie submit a ballot
+        let (bob, bob_id, _) = get_sealer_bob();
+        let (charlie, _, _) = get_sealer_charlie();
+        let spoiled: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(1u32), &BigUint::from(7u32), &pk)
+                .into();
+        let counted: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(1u32), &BigUint::from(11u32), &pk)
+                .into();
+        let ballot = Ballot {
+            answers: vec![(topic_id.clone(), spoiled.clone())],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_ok!(OffchainModule::cast_ballot(
+            bob.clone(),
+            vote_id.clone(),
+            ballot.clone()
+        ));
+        assert_ok!(OffchainModule::cast_ballot(
+            charlie,
+            vote_id.clone(),
+            Ballot {
+                answers: vec![(topic_id.clone(), counted.clone())],
+                proofs: Vec::new(),
+                validity_proofs: Vec::new(),
+                credential: None,
+            }
+        ));
+
+        // Bob spoils his cipher, it is removed from the ciphers to shuffle
+        assert_ok!(OffchainModule::spoil_ballot(
+            bob.clone(),
+            vote_id.clone(),
+            topic_id.clone()
+        ));
+        assert_eq!(
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES),
+            vec![counted]
+        );
+        let hash = sp_io::hashing::blake2_256(&spoiled.encode());
+        let audit_ballot =
+            OffchainModule::spoiled_ballot((&vote_id, &topic_id), hash).unwrap();
+        assert_eq!(audit_ballot.voter, bob_id);
+        assert_eq!(audit_ballot.cipher, spoiled);
+        assert_eq!(audit_ballot.reveal, None);
+
+        // the cipher can neither be spoiled nor submitted again
+        assert_err!(
+            OffchainModule::spoil_ballot(bob.clone(), vote_id.clone(), topic_id.clone()),
+            Error::<TestRuntime>::BallotAlreadySpoiled
+        );
+        assert_err!(
+            OffchainModule::cast_ballot(bob, vote_id.clone(), ballot),
+            Error::<TestRuntime>::DuplicateCipher
+        );
+
+        // Alice hasn't voted, she has nothing to spoil
+        assert_err!(
+            OffchainModule::spoil_ballot(get_voting_authority(), vote_id, topic_id),
+            Error::<TestRuntime>::SpoiledCipherNotFound
+        );
+    });
+}
+
+#[test]
+fn test_revoke_voter_excludes_ballot_from_tally() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // Bob and Charlie submit a ballot
+        let (bob, bob_id, _) = get_sealer_bob();
+        let (charlie, charlie_id, _) = get_sealer_charlie();
+        let revoked = create_ballot(&topic_id, &pk, 7);
+        let counted = create_ballot(&topic_id, &pk, 11);
+        assert_ok!(OffchainModule::cast_ballot(
+            bob.clone(),
+            vote_id.clone(),
+            revoked.clone()
+        ));
+        assert_ok!(OffchainModule::cast_ballot(
+            charlie,
+            vote_id.clone(),
+            counted.clone()
+        ));
+
+        // only a voting authority can revoke a voter
+        assert_err!(
+            OffchainModule::revoke_voter(bob.clone(), vote_id.clone(), charlie_id),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+
+        // Bob is revoked, his cipher is removed from the ciphers to shuffle
+        assert_ok!(OffchainModule::revoke_voter(
+            get_voting_authority(),
+            vote_id.clone(),
+            bob_id
+        ));
+        assert_eq!(
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES),
+            vec![counted.answers[0].1.clone()]
+        );
+        assert_eq!(
+            OffchainModule::revoked_voter(&vote_id, &bob_id),
+            Some(System::block_number())
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::VoterRevoked(
+                vote_id.clone(),
+                bob_id,
+                1
+            ))));
+
+        // Bob can neither be revoked again nor cast another ballot
+        assert_err!(
+            OffchainModule::revoke_voter(get_voting_authority(), vote_id.clone(), bob_id),
+            Error::<TestRuntime>::VoterAlreadyRevoked
+        );
+        assert_err!(
+            OffchainModule::cast_ballot(
+                bob,
+                vote_id.clone(),
+                create_ballot(&topic_id, &pk, 13)
+            ),
+            Error::<TestRuntime>::VoterRevoked
+        );
+
+        // voters can be revoked before they have voted
+        let dave: <TestRuntime as frame_system::Trait>::AccountId = account(&DAVE);
+        assert_ok!(OffchainModule::revoke_voter(
+            get_voting_authority(),
+            vote_id.clone(),
+            dave
+        ));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::VoterRevoked(
+                vote_id.clone(),
+                dave,
+                0
+            ))));
+
+        // the ballot box is frozen once the voting phase has ended
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert_err!(
+            OffchainModule::revoke_voter(get_voting_authority(), vote_id, charlie_id),
+            Error::<TestRuntime>::LateVoterRevocation
+        );
+    });
+}
+
+#[test]
+fn test_validate_ballot_without_storing_it() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        let (bob, bob_id, _) = get_sealer_bob();
+        let (_, charlie_id, _) = get_sealer_charlie();
+
+        // a valid ballot passes all checks but isn't stored
+        let ballot = create_ballot(&topic_id, &pk, 7);
+        assert_eq!(
+            OffchainModule::validate_ballot(&bob_id, &vote_id, &ballot),
+            Ok(())
+        );
+        assert!(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES).is_empty());
+        assert_eq!(OffchainModule::ballot_receipt(&vote_id, &bob_id), None);
+
+        // the ballot is rejected once it has been cast
+        assert_ok!(OffchainModule::cast_ballot(
+            bob,
+            vote_id.clone(),
+            ballot.clone()
+        ));
+        assert_eq!(
+            OffchainModule::validate_ballot(&charlie_id, &vote_id, &ballot),
+            Err(ErrorCode::DuplicateCipher.code())
+        );
+
+        // the vote doesn't check the group membership, cast_ballot would accept the cipher
+        // even though p - 1 is not a quadratic residue modulo the safe prime p
+        let non_residue = (&pk.params.p - BigUint::one()).to_bytes_be();
+        let mut unchecked = create_ballot(&topic_id, &pk, 11);
+        unchecked.answers[0].1.b = non_residue;
+        assert_eq!(
+            OffchainModule::validate_ballot(&charlie_id, &vote_id, &unchecked),
+            Ok(())
+        );
+
+        // revoked voters and unknown votes are rejected
+        assert_ok!(OffchainModule::revoke_voter(
+            get_voting_authority(),
+            vote_id.clone(),
+            charlie_id
+        ));
+        assert_eq!(
+            OffchainModule::validate_ballot(
+                &charlie_id,
+                &vote_id,
+                &create_ballot(&topic_id, &pk, 13)
+            ),
+            Err(ErrorCode::VoterRevoked.code())
+        );
+        assert_eq!(
+            OffchainModule::validate_ballot(&bob_id, &b"unknown".to_vec(), &ballot),
+            Err(ErrorCode::VoteDoesNotExist.code())
+        );
+    });
+}
+
+#[test]
+fn test_group_check_rejects_ciphers_outside_of_the_group() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let who = get_voting_authority();
+        let (vote_id, topic_id) =
+            setup_vote_in_key_generation(params.into(), TallyStrategy::Mixnet);
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        let (bob, bob_id, _) = get_sealer_bob();
+        let (charlie, _, _) = get_sealer_charlie();
+
+        // only a voting authority can enable the group check
+        assert_err!(
+            OffchainModule::set_group_check(bob.clone(), vote_id.clone(), true),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+        assert_ok!(OffchainModule::set_group_check(
+            who.clone(),
+            vote_id.clone(),
+            true
+        ));
+        assert!(OffchainModule::group_check(&vote_id));
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+
+        // p - 1 is not a quadratic residue modulo the safe prime p
+        let mut invalid = create_ballot(&topic_id, &pk, 7);
+        invalid.answers[0].1.b = (&pk.params.p - BigUint::one()).to_bytes_be();
+        assert_eq!(
+            OffchainModule::validate_ballot(&bob_id, &vote_id, &invalid),
+            Err(ErrorCode::CipherNotInGroup.code())
+        );
+        assert_err!(
+            OffchainModule::cast_ballot(bob.clone(), vote_id.clone(), invalid),
+            Error::<TestRuntime>::CipherNotInGroup
+        );
+        assert_ok!(OffchainModule::cast_ballot(
+            charlie,
+            vote_id.clone(),
+            create_ballot(&topic_id, &pk, 11)
+        ));
+
+        // the group check can't be disabled once the voting phase has started
+        assert_err!(
+            OffchainModule::set_group_check(who, vote_id.clone(), false),
+            Error::<TestRuntime>::VoteAlreadyStarted
+        );
+        assert_eq!(
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES).len(),
+            1
+        );
+    });
+}
+
+#[test]
+fn test_reveal_spoiled_ballot() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // Bob submits and spoils a ballot
+        let (bob, _, _) = get_sealer_bob();
+        let message = BigUint::from(1u32);
+        let r = BigUint::from(7u32);
+        let cipher: Cipher = ElGamal::encrypt_encode(&message, &r, &pk).into();
+        let ballot = Ballot {
+            answers: vec![(topic_id.clone(), cipher.clone())],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_ok!(OffchainModule::cast_ballot(
+            bob.clone(),
+            vote_id.clone(),
+            ballot
+        ));
+        assert_ok!(OffchainModule::spoil_ballot(
+            bob.clone(),
+            vote_id.clone(),
+            topic_id.clone()
+        ));
+        let hash = sp_io::hashing::blake2_256(&cipher.encode());
+        let reveal = SpoiledBallotReveal {
+            message: message.to_bytes_be(),
+            randomness: r.to_bytes_be(),
+            encoded: true,
+        };
+
+        // only Bob can reveal the randomness of his cipher
+        let (charlie, _, _) = get_sealer_charlie();
+        assert_err!(
+            OffchainModule::reveal_spoiled_ballot(
+                charlie,
+                vote_id.clone(),
+                topic_id.clone(),
+                hash,
+                reveal.clone()
+            ),
+            Error::<TestRuntime>::SpoiledBallotNotFound
+        );
+
+        // the revealed message must match the cipher
+        let wrong_reveal = SpoiledBallotReveal {
+            message: BigUint::from(2u32).to_bytes_be(),
+            ..reveal.clone()
+        };
+        assert_err!(
+            OffchainModule::reveal_spoiled_ballot(
+                bob.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                hash,
+                wrong_reveal
+            ),
+            Error::<TestRuntime>::InvalidSpoiledBallotReveal
+        );
+
+        assert_ok!(OffchainModule::reveal_spoiled_ballot(
+            bob.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            hash,
+            reveal.clone()
+        ));
+        let audit_ballot =
+            OffchainModule::spoiled_ballot((&vote_id, &topic_id), hash).unwrap();
+        assert_eq!(audit_ballot.reveal, Some(reveal.clone()));
+
+        // the randomness can only be revealed once
+        assert_err!(
+            OffchainModule::reveal_spoiled_ballot(bob, vote_id, topic_id, hash, reveal),
+            Error::<TestRuntime>::SpoiledBallotAlreadyRevealed
+        );
+    });
+}
+
+#[test]
+fn test_cast_ballot_verifies_validity_proofs() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let who = get_voting_authority();
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) =
+            setup_vote_in_key_generation(params.into(), TallyStrategy::Mixnet);
+        let topic =
+            setup_question("20201212-02", QuestionType::MultiSelect { max: 2 }, 3);
+        assert_ok!(OffchainModule::store_question(
+            who,
+            vote_id.clone(),
+            topic.clone(),
+            2
+        ));
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // Bob selects the first and the last candidate
+        let (bob, bob_id, _) = get_sealer_bob();
+        let selection = Topic::encode_selection(&[0, 2]);
+        let (cipher, proof) =
+            encrypt_valid_answer(&pk, &topic, selection, 7, &bob_id.encode());
+        let free_form: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(1u32), &BigUint::from(11u32), &pk)
+                .into();
+
+        // the answer to a question with candidates requires a validity proof
+        let ballot = Ballot {
+            answers: vec![
+                (topic_id.clone(), free_form.clone()),
+                (topic.id.clone(), cipher.clone()),
+            ],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_err!(
+            OffchainModule::cast_ballot(bob.clone(), vote_id.clone(), ballot.clone()),
+            Error::<TestRuntime>::BallotValidityProofError
+        );
+
+        // the proof is bound to Bob, it doesn't verify for Charlie
+        let ballot = Ballot {
+            validity_proofs: vec![None, proof],
+            ..ballot
+        };
+        let (charlie, charlie_id, _) = get_sealer_charlie();
+        assert_err!(
+            OffchainModule::cast_ballot(charlie.clone(), vote_id.clone(), ballot.clone()),
+            Error::<TestRuntime>::BallotValidityProofError
+        );
+
+        // Charlie selects too many candidates
+        let selection = Topic::encode_selection(&[0, 1, 2]);
+        let (invalid, invalid_proof) =
+            encrypt_valid_answer(&pk, &topic, selection, 13, &charlie_id.encode());
+        assert_err!(
+            OffchainModule::cast_ballot(
+                charlie,
+                vote_id.clone(),
+                Ballot {
+                    answers: vec![(topic.id.clone(), invalid)],
+                    proofs: Vec::new(),
+                    validity_proofs: vec![invalid_proof],
+                    credential: None,
+                }
+            ),
+            Error::<TestRuntime>::BallotValidityProofError
+        );
+
+        // the answer to the free-form question doesn't need a proof
+        assert_ok!(OffchainModule::cast_ballot(bob, vote_id.clone(), ballot));
+        assert_eq!(
+            OffchainModule::ciphers(&vote_id, &topic.id, NR_OF_SHUFFLES),
+            vec![cipher]
+        );
+    });
+}
+
+#[test]
+fn test_offchain_signed_tx_encoded() {
+    let (mut t, pool_state, _) = ExternalityBuilder::build();
+
+    t.execute_with(|| {
+        // Setup
+        let (params, _, pk) = Helper::setup_sm_system();
+        let q = &params.q();
+
+        // Setup Vote
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        let num: u64 = 32;
+        let big: BigUint = BigUint::from(num);
+        let r = OffchainModule::get_random_biguint_less_than(q).unwrap();
+
+        // use additive homomorphic encoding for message i.e. g^m
+        let cipher: Cipher = ElGamal::encrypt_encode(&big, &r, &pk).into();
+        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher)];
+        let ballot: Ballot = Ballot {
+            answers,
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+
+        // Test
+        OffchainModule::offchain_signed_tx(num, vote_id.clone(), topic_id).unwrap();
+
+        // Verify
+        let tx = pool_state.write().transactions.pop().unwrap();
+        assert!(pool_state.read().transactions.is_empty());
+        let tx = TestExtrinsic::decode(&mut &*tx).unwrap();
+        assert_eq!(tx.signature.unwrap().0, 0);
+        assert_eq!(tx.call, Call::cast_ballot(vote_id, ballot.clone()));
+    });
+}
+
+#[test]
+fn test_get_random_bytes() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let size: usize = 32;
+        let random = OffchainModule::get_random_bytes(size).unwrap();
+        assert_eq!(random.len(), size);
+    });
+}
+
+#[test]
+fn test_get_random_number_less_than() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let upper_bound: BigUint =
+            BigUint::parse_bytes(b"10981023801283012983912312", 10).unwrap();
+        let random = OffchainModule::get_random_biguint_less_than(&upper_bound).unwrap();
+        assert!(random < upper_bound);
+    });
+}
+
+#[test]
+fn test_get_random_number_less_than_should_panic_number_is_zero() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let upper_bound: BigUint = BigUint::parse_bytes(b"0", 10).unwrap();
+        OffchainModule::get_random_biguint_less_than(&upper_bound).expect_err(
+            "The returned value should be: '<Error<T>>::RandomnessUpperBoundZeroError'",
+        );
+    });
+}
+
+#[test]
+fn test_get_random_numbers_less_than() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let upper_bound: BigUint =
+            BigUint::parse_bytes(b"10981023801283012983912312", 10).unwrap();
+        let randoms: Vec<BigUint> =
+            OffchainModule::get_random_biguints_less_than(&upper_bound, 10).unwrap();
+        assert_eq!(randoms.len(), 10);
+        let zero = BigUint::zero();
+        for random in randoms.iter() {
+            assert!(random < &upper_bound);
+            assert!(random > &zero);
+        }
+    });
+}
+
+#[test]
+fn test_get_random_numbers_less_than_should_panic_number_is_zero() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let upper_bound: BigUint =
+            BigUint::parse_bytes(b"10981023801283012983912312", 10).unwrap();
+        OffchainModule::get_random_biguints_less_than(&upper_bound, 0).expect_err(
             "The returned value should be: '<Error<T>>::RandomnessUpperBoundZeroError'",
         );
     });
 }
 
 #[test]
-fn test_get_random_bigunint_range() {
+fn test_get_random_bigunint_range() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let lower: BigUint = BigUint::parse_bytes(b"0", 10).unwrap();
+        let upper: BigUint =
+            BigUint::parse_bytes(b"10981023801283012983912312", 10).unwrap();
+        let value = OffchainModule::get_random_bigunint_range(&lower, &upper).unwrap();
+
+        assert!(value < upper);
+        assert!(lower < value);
+    });
+}
+
+#[test]
+fn test_get_random_bigunint_range_upper_is_zero() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let lower: BigUint = BigUint::parse_bytes(b"0", 10).unwrap();
+        let upper: BigUint = BigUint::parse_bytes(b"0", 10).unwrap();
+        OffchainModule::get_random_bigunint_range(&lower, &upper)
+            .expect_err("The returned value should be: '<Error<T>>::RandomRangeError'");
+    });
+}
+
+#[test]
+fn test_get_random_bigunint_range_upper_is_not_larger_than_lower() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let lower: BigUint = BigUint::parse_bytes(b"5", 10).unwrap();
+        let upper: BigUint = BigUint::parse_bytes(b"5", 10).unwrap();
+        OffchainModule::get_random_bigunint_range(&lower, &upper)
+            .expect_err("The returned value should be: '<Error<T>>::RandomRangeError'");
+    });
+}
+
+#[test]
+fn test_get_random_range() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let lower: usize = 0;
+        let upper: usize = 100;
+        let value = OffchainModule::get_random_range(lower, upper).unwrap();
+
+        assert!(value < upper);
+        assert!(lower < value);
+    });
+}
+
+#[test]
+fn test_get_random_range_upper_is_zero_error() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let lower: usize = 0;
+        let upper: usize = 0;
+        OffchainModule::get_random_range(lower, upper)
+            .expect_err("The returned value should be: '<Error<T>>::RandomRangeError'");
+    });
+}
+
+#[test]
+fn test_get_random_range_upper_is_not_larger_than_lower_error() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let lower: usize = 5;
+        let upper: usize = 5;
+        OffchainModule::get_random_range(lower, upper)
+            .expect_err("The returned value should be: '<Error<T>>::RandomRangeError'");
+    });
+}
+
+#[test]
+fn test_generate_permutation_size_zero_error() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let size = 0;
+        OffchainModule::generate_permutation(size).expect_err(
+            "The returned value should be: '<Error<T>>::PermutationSizeZeroError'",
+        );
+    });
+}
+
+#[test]
+fn test_should_generate_a_permutation_size_three() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let size = 3;
+        let permutation = OffchainModule::generate_permutation(size).unwrap();
+
+        // check that the permutation has the expected size
+        assert!(permutation.len() == (size as usize));
+
+        // check that 0, 1, 2 occur at least once each
+        assert!(permutation.iter().any(|&value| value == 0));
+        assert!(permutation.iter().any(|&value| value == 1));
+        assert!(permutation.iter().any(|&value| value == 2));
+    });
+}
+
+#[test]
+fn test_fetch_ballots_size_zero() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let vote_id = "20201212".as_bytes().to_vec();
+        let topic_id = "Moritz for President?".as_bytes().to_vec();
+        // Read pallet storage (i.e. the submitted ballots)
+        // and assert an expected result.
+        let ciphers_from_chain: Vec<Cipher> =
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES);
+        assert!(ciphers_from_chain.len() == 0);
+    });
+}
+
+#[test]
+fn test_ciphers_are_stored_in_chunks() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let encoded: bool = false;
+
+        // store created public key and 6 ciphers (chunk size: 4)
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, encoded);
+
+        // the ciphers are split into two chunks
+        let ciphers: Vec<Cipher> =
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES);
+        assert_eq!(ciphers.len(), 6);
+        assert_eq!(
+            OffchainModule::cipher_chunk_count((&vote_id, &topic_id), NR_OF_SHUFFLES),
+            2
+        );
+        assert_eq!(
+            OffchainModule::cipher_chunk(&vote_id, &topic_id, NR_OF_SHUFFLES, 0).len(),
+            4
+        );
+        assert_eq!(
+            OffchainModule::cipher_chunk(&vote_id, &topic_id, NR_OF_SHUFFLES, 1).len(),
+            2
+        );
+        assert_eq!(
+            helpers::ciphers::get_cipher_count::<TestRuntime>(
+                &vote_id,
+                &topic_id,
+                NR_OF_SHUFFLES
+            ),
+            6
+        );
+
+        // a range spanning both chunks returns the correct ciphers
+        let range: Vec<Cipher> = helpers::ciphers::get_ciphers_in_range::<TestRuntime>(
+            &vote_id,
+            &topic_id,
+            NR_OF_SHUFFLES,
+            3,
+            2,
+        );
+        assert_eq!(range, ciphers[3..5].to_vec());
+
+        // a range exceeding the stored ciphers is cut off at the end
+        let range: Vec<Cipher> = helpers::ciphers::get_ciphers_in_range::<TestRuntime>(
+            &vote_id,
+            &topic_id,
+            NR_OF_SHUFFLES,
+            4,
+            10,
+        );
+        assert_eq!(range, ciphers[4..].to_vec());
+
+        // a page contains the range and the total number of ciphers
+        let page = OffchainModule::cipher_page(&vote_id, &topic_id, NR_OF_SHUFFLES, 3, 2);
+        assert_eq!(page.total, 6);
+        assert_eq!(page.ciphers, ciphers[3..5].to_vec());
+        let page = OffchainModule::cipher_page(&vote_id, &topic_id, NR_OF_SHUFFLES, 6, 2);
+        assert_eq!(page.total, 6);
+        assert!(page.ciphers.is_empty());
+    });
+}
+
+#[test]
+fn test_cipher_merkle_root_and_proofs() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+
+        // no ciphers -> no merkle root
+        assert_eq!(
+            OffchainModule::cipher_root((&vote_id, &topic_id), NR_OF_SHUFFLES),
+            None
+        );
+
+        // store created public key and 6 ciphers, each appended individually
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, false);
+
+        // the incrementally updated root equals the root over all ciphers
+        let ciphers: Vec<Cipher> =
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES);
+        let leaves: Vec<[u8; 32]> =
+            ciphers.iter().map(helpers::merkle::hash_leaf).collect();
+        let root =
+            OffchainModule::cipher_root((&vote_id, &topic_id), NR_OF_SHUFFLES).unwrap();
+        assert_eq!(root, helpers::merkle::merkle_root(leaves));
+
+        // every cipher has a valid inclusion proof
+        for index in 0..ciphers.len() as u64 {
+            let proof =
+                OffchainModule::cipher_proof(&vote_id, &topic_id, NR_OF_SHUFFLES, index)
+                    .unwrap();
+            assert_eq!(proof.nr_of_leaves, 6);
+            assert!(proof.verify(&root));
+        }
+
+        // a proof for a different cipher doesn't verify
+        let mut proof =
+            OffchainModule::cipher_proof(&vote_id, &topic_id, NR_OF_SHUFFLES, 2).unwrap();
+        proof.leaf = helpers::merkle::hash_leaf(&ciphers[3]);
+        assert!(!proof.verify(&root));
+
+        // there is no proof for a cipher which doesn't exist
+        assert_eq!(
+            OffchainModule::cipher_proof(&vote_id, &topic_id, NR_OF_SHUFFLES, 6),
+            None
+        );
+        assert_eq!(
+            OffchainModule::cipher_proof(&vote_id, &topic_id, NR_OF_SHUFFLES + 1, 0),
+            None
+        );
+    });
+}
+
+#[test]
+fn store_small_dummy_vote_works_encoded() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Setup Vote
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+
+        let message = BigUint::from(1u32);
+        let random = BigUint::from(7u32);
+
+        // encrypt the message -> encrypted message
+        // cipher = the crypto crate version of a ballot { a: BigUint, b: BigUint }
+        let big_cipher: BigCipher = ElGamal::encrypt_encode(&message, &random, &pk);
+
+        // transform the ballot into a from that the blockchain can handle
+        // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
+        let cipher: Cipher = big_cipher.clone().into();
+        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher.clone())];
+        let ballot: Ballot = Ballot {
+            answers,
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+
+        // create the voter (i.e. the transaction signer)
+        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        let voter = Origin::signed(account);
+
+        let vote_submission_result =
+            OffchainModule::cast_ballot(voter, vote_id.clone(), ballot);
+        assert_ok!(vote_submission_result);
+
+        // fetch the submitted ballot
+        let ciphers_from_chain: Vec<Cipher> =
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES);
+        assert!(ciphers_from_chain.len() > 0);
+
+        let cipher_from_chain: Cipher = ciphers_from_chain[0].clone();
+        assert_eq!(cipher, cipher_from_chain);
+
+        // transform the Ballot -> BigCipher
+        let big_cipher_from_chain: BigCipher = cipher_from_chain.into();
+        assert_eq!(big_cipher, big_cipher_from_chain);
+
+        let decrypted_vote = ElGamal::decrypt_decode(&big_cipher_from_chain, &sk);
+        assert_eq!(message, decrypted_vote);
+    });
+}
+
+#[test]
+fn store_small_dummy_vote_works() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Setup Vote
+        let (params, sk, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+
+        let message = BigUint::from(1u32);
+        let random = BigUint::from(7u32);
+
+        // encrypt the message -> encrypted message
+        // cipher = the crypto crate version of a ballot { a: BigUint, b: BigUint }
+        let big_cipher: BigCipher = ElGamal::encrypt(&message, &random, &pk);
+
+        // transform the ballot into a from that the blockchain can handle
+        // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
+        let cipher: Cipher = big_cipher.clone().into();
+        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher.clone())];
+        let ballot: Ballot = Ballot {
+            answers,
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+
+        // create the voter (i.e. the transaction signer)
+        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        let voter = Origin::signed(account);
+
+        let vote_submission_result =
+            OffchainModule::cast_ballot(voter, vote_id.clone(), ballot);
+        assert_ok!(vote_submission_result);
+
+        // fetch the submitted ballot
+        let ciphers_from_chain: Vec<Cipher> =
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES);
+        assert!(ciphers_from_chain.len() > 0);
+
+        let cipher_from_chain: Cipher = ciphers_from_chain[0].clone();
+        assert_eq!(cipher, cipher_from_chain);
+
+        // transform the Ballot -> BigCipher
+        let big_cipher_from_chain: BigCipher = cipher_from_chain.into();
+        assert_eq!(big_cipher, big_cipher_from_chain);
+
+        let decrypted_vote = ElGamal::decrypt(&big_cipher_from_chain, &sk);
+        assert_eq!(message, decrypted_vote);
+    });
+}
+
+#[test]
+fn store_real_size_vote_works_encoded() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Setup
+        let (params, sk, pk) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+
+        // encrypt the message -> encrypted message
+        // cipher = the crypto crate version of a ballot { a: BigUint, b: BigUint }
+        let message = BigUint::from(1u32);
+        let random =
+            BigUint::parse_bytes(b"170141183460469231731687303715884", 10).unwrap();
+        let big_cipher: BigCipher = ElGamal::encrypt_encode(&message, &random, &pk);
+
+        // transform the ballot into a from that the blockchain can handle
+        // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
+        let cipher: Cipher = big_cipher.clone().into();
+        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher.clone())];
+        let ballot: Ballot = Ballot {
+            answers,
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+
+        // create the voter (i.e. the transaction signer)
+        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        let voter = Origin::signed(account);
+
+        let vote_submission_result =
+            OffchainModule::cast_ballot(voter, vote_id.clone(), ballot);
+        assert_ok!(vote_submission_result);
+
+        // fetch the submitted ballot
+        let ciphers_from_chain: Vec<Cipher> =
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES);
+        assert!(ciphers_from_chain.len() > 0);
+
+        let cipher_from_chain: Cipher = ciphers_from_chain[0].clone();
+        assert_eq!(cipher, cipher_from_chain);
+
+        // transform the Ballot -> BigCipher
+        let big_cipher_from_chain: BigCipher = cipher_from_chain.into();
+        assert_eq!(big_cipher, big_cipher_from_chain);
+
+        let decrypted_vote = ElGamal::decrypt_decode(&big_cipher_from_chain, &sk);
+        assert_eq!(message, decrypted_vote);
+    });
+}
+
+#[test]
+fn store_real_size_vote_works() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Setup
+        let (params, sk, pk) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+
+        // encrypt the message -> encrypted message
+        // cipher = the crypto crate version of a ballot { a: BigUint, b: BigUint }
+        let message = BigUint::from(1u32);
+        let random =
+            BigUint::parse_bytes(b"170141183460469231731687303715884", 10).unwrap();
+        let big_cipher: BigCipher = ElGamal::encrypt(&message, &random, &pk);
+
+        // transform the ballot into a from that the blockchain can handle
+        // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
+        let cipher: Cipher = big_cipher.clone().into();
+        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher.clone())];
+        let ballot: Ballot = Ballot {
+            answers,
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+
+        // create the voter (i.e. the transaction signer)
+        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        let voter = Origin::signed(account);
+
+        let vote_submission_result =
+            OffchainModule::cast_ballot(voter, vote_id.clone(), ballot);
+        assert_ok!(vote_submission_result);
+
+        // fetch the submitted ballot
+        let ciphers_from_chain: Vec<Cipher> =
+            OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES);
+        assert!(ciphers_from_chain.len() > 0);
+
+        let cipher_from_chain: Cipher = ciphers_from_chain[0].clone();
+        assert_eq!(cipher, cipher_from_chain);
+
+        // transform the Ballot -> BigCipher
+        let big_cipher_from_chain: BigCipher = cipher_from_chain.into();
+        assert_eq!(big_cipher, big_cipher_from_chain);
+
+        let decrypted_vote = ElGamal::decrypt(&big_cipher_from_chain, &sk);
+        assert_eq!(message, decrypted_vote);
+    });
+}
+
+#[test]
+fn test_shuffle_ciphers_encoded() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Setup
+        let (params, sk, pk) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // create the public key
+        let messages = [
+            BigUint::from(5u32),
+            BigUint::from(10u32),
+            BigUint::from(15u32),
+        ];
+
+        // encrypt the message -> encrypted message
+        // cipher = the crypto crate version of a ballot { a: BigUint, b: BigUint }
+        let randoms = [
+            b"170141183460469231731687303715884",
+            b"170141183460469231731687303700084",
+            b"170141183400069231731687303700084",
+        ];
+
+        // create the voter (i.e. the transaction signer)
+        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        let mut ciphers: Vec<BigCipher> = Vec::new();
+        let voter = Origin::signed(account);
+
+        for index in 0..3 {
+            let random = BigUint::parse_bytes(randoms[index], 10).unwrap();
+
+            // transform the ballot into a from that the blockchain can handle
+            // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
+            let cipher: BigCipher =
+                ElGamal::encrypt_encode(&messages[index], &random, &pk);
+            ciphers.push(cipher.clone());
+            let cipher: Cipher = cipher.into();
+            let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher)];
+            let ballot: Ballot = Ballot {
+                answers,
+                proofs: Vec::new(),
+                validity_proofs: Vec::new(),
+                credential: None,
+            };
+
+            let vote_submission_result =
+                OffchainModule::cast_ballot(voter.clone(), vote_id.clone(), ballot);
+            assert_ok!(vote_submission_result);
+        }
+
+        // shuffle the votes
+        let shuffle_result = OffchainModule::shuffle_ciphers(&pk, ciphers);
+        let shuffled_big_ciphers: Vec<BigCipher> = shuffle_result.unwrap().0;
+        assert!(shuffled_big_ciphers.len() == 3);
+
+        // type conversion: BigCipher (BigUint) to Ballot (Vec<u8>)
+        let shuffled_ciphers: Vec<Cipher> = Wrapper(shuffled_big_ciphers).into();
+
+        // transform each ballot into a cipher, decrypt_decode it and finally collect the list of biguints
+        let decrypted_votes = shuffled_ciphers
+            .iter()
+            .map(|b| ElGamal::decrypt_decode(&(b.clone().into()), &sk))
+            .collect::<Vec<BigUint>>();
+
+        // check that at least one value is 5, 10, 15
+        assert!(decrypted_votes
+            .iter()
+            .any(|decrypted_vote| *decrypted_vote == messages[0]));
+        assert!(decrypted_votes
+            .iter()
+            .any(|decrypted_vote| *decrypted_vote == messages[1]));
+        assert!(decrypted_votes
+            .iter()
+            .any(|decrypted_vote| *decrypted_vote == messages[2]));
+    });
+}
+
+#[test]
+fn test_shuffle_ciphers() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Setup
+        let (params, sk, pk) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // create the public key
+        let messages = [
+            BigUint::from(1u32),
+            BigUint::from(3u32),
+            BigUint::from(5u32),
+        ];
+
+        // encrypt the message -> encrypted message
+        // cipher = the crypto crate version of a ballot { a: BigUint, b: BigUint }
+        let randoms = [
+            b"170141183460469231731687303715884",
+            b"170141183460469231731687303700084",
+            b"170141183400069231731687303700084",
+        ];
+
+        // create the voter (i.e. the transaction signer)
+        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        let mut ciphers: Vec<BigCipher> = Vec::new();
+        let voter = Origin::signed(account);
+
+        for index in 0..3 {
+            let random = BigUint::parse_bytes(randoms[index], 10).unwrap();
+
+            // transform the ballot into a from that the blockchain can handle
+            // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
+            let cipher: BigCipher = ElGamal::encrypt(&messages[index], &random, &pk);
+            ciphers.push(cipher.clone());
+            let cipher: Cipher = cipher.into();
+            let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher)];
+            let ballot: Ballot = Ballot {
+                answers,
+                proofs: Vec::new(),
+                validity_proofs: Vec::new(),
+                credential: None,
+            };
+
+            let vote_submission_result =
+                OffchainModule::cast_ballot(voter.clone(), vote_id.clone(), ballot);
+            assert_ok!(vote_submission_result);
+        }
+
+        // shuffle the votes
+        let shuffle_result = OffchainModule::shuffle_ciphers(&pk, ciphers);
+        let shuffled_big_ciphers: Vec<BigCipher> = shuffle_result.unwrap().0;
+        assert!(shuffled_big_ciphers.len() == 3);
+
+        // type conversion: BigCipher (BigUint) to Ballot (Vec<u8>)
+        let shuffled_ciphers: Vec<Cipher> = Wrapper(shuffled_big_ciphers).into();
+
+        // transform each ballot into a cipher, decrypt_decode it and finally collect the list of biguints
+        let decrypted_votes = shuffled_ciphers
+            .iter()
+            .map(|b| ElGamal::decrypt(&(b.clone().into()), &sk))
+            .collect::<Vec<BigUint>>();
+
+        // check that at least one value is 5, 10, 15
+        assert!(decrypted_votes
+            .iter()
+            .any(|decrypted_vote| *decrypted_vote == messages[0]));
+        assert!(decrypted_votes
+            .iter()
+            .any(|decrypted_vote| *decrypted_vote == messages[1]));
+        assert!(decrypted_votes
+            .iter()
+            .any(|decrypted_vote| *decrypted_vote == messages[2]));
+    });
+}
+
+#[test]
+fn test_shuffle_ciphers_no_ballots() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let vote_id = "20201212".as_bytes().to_vec();
+        let (_, _, pk) = Helper::setup_sm_system();
+        let ciphers: Vec<BigCipher> = Vec::new();
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // try -> to shuffle the ballots (which don't exist)
+        OffchainModule::shuffle_ciphers(&pk, ciphers).expect_err(
+            "The returned value should be: 'Error::<T>::ShuffleCiphersSizeZeroError'",
+        );
+    });
+}
+
+#[test]
+fn test_permute_vector() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let test_vec: Vec<BigUint> = vec![
+            BigUint::from(5u32),
+            BigUint::from(10u32),
+            BigUint::from(15u32),
+        ];
+        let permutation: Vec<usize> = vec![2, 0, 1];
+
+        let result = OffchainModule::permute_vector(test_vec.clone(), &permutation);
+        assert_eq!(result[0], test_vec[2]);
+        assert_eq!(result[1], test_vec[0]);
+        assert_eq!(result[2], test_vec[1]);
+    });
+}
+
+#[test]
+fn test_shuffle_proof_small_system_encoded() {
+    // good primes to use for testing
+    // p: 202178360940839 -> q: 101089180470419
+    // p: 4283 -> q: 2141
+    // p: 59 -> q: 29
+    // p: 47 -> q: 23
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
+        assert!(is_p_prime);
+        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
+        assert!(is_q_prime);
+
+        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, true);
+        assert!(is_proof_valid);
+    });
+}
+
+#[test]
+fn test_shuffle_proof_small_system() {
+    // good primes to use for testing
+    // p: 202178360940839 -> q: 101089180470419
+    // p: 4283 -> q: 2141
+    // p: 59 -> q: 29
+    // p: 47 -> q: 23
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
+        assert!(is_p_prime);
+        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
+        assert!(is_q_prime);
+
+        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, false);
+        assert!(is_proof_valid);
+    });
+}
+
+#[test]
+fn test_shuffle_proof_tiny_system_encoded() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_tiny_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
+        assert!(is_p_prime);
+        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
+        assert!(is_q_prime);
+
+        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, true);
+        assert!(is_proof_valid);
+    });
+}
+
+#[test]
+fn test_shuffle_proof_tiny_system() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_tiny_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
+        assert!(is_p_prime);
+        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
+        assert!(is_q_prime);
+
+        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, false);
+        assert!(is_proof_valid);
+    });
+}
+
+#[test]
+#[cfg(not(feature = "exact-shuffle-verification"))]
+fn test_shuffle_proof_batch_verification_rejects_invalid_commitments() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, false);
+
+        // shuffle the votes and generate the proof
+        let ciphers: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES)).into();
+        let (shuffled_ciphers, re_encryption_randoms, permutation) =
+            OffchainModule::shuffle_ciphers(&pk, ciphers.clone()).unwrap();
+        let proof: Proof = OffchainModule::generate_shuffle_proof(
+            &topic_id,
+            ciphers.clone(),
+            shuffled_ciphers.clone(),
+            re_encryption_randoms,
+            &permutation,
+            &pk,
+        )
+        .unwrap();
+
+        // the untouched proof is valid
+        let is_proof_valid = OffchainModule::verify_shuffle_proof(
+            &topic_id,
+            proof.clone(),
+            ciphers.clone(),
+            shuffled_ciphers.clone(),
+            &pk,
+            &ProtocolConfig::new(3),
+        )
+        .unwrap();
+        assert!(is_proof_valid);
+
+        // a single wrong commitment t_hat_i is detected
+        let mut invalid_t_hat = proof.clone();
+        invalid_t_hat.commitments.5[0] += 1u32;
+        let is_proof_valid = OffchainModule::verify_shuffle_proof(
+            &topic_id,
+            invalid_t_hat,
+            ciphers.clone(),
+            shuffled_ciphers.clone(),
+            &pk,
+            &ProtocolConfig::new(3),
+        )
+        .unwrap();
+        assert!(!is_proof_valid);
+
+        // a wrong commitment t4 is detected
+        let mut invalid_t4 = proof.clone();
+        invalid_t4.commitments.4 += 1u32;
+        let is_proof_valid = OffchainModule::verify_shuffle_proof(
+            &topic_id,
+            invalid_t4,
+            ciphers.clone(),
+            shuffled_ciphers.clone(),
+            &pk,
+            &ProtocolConfig::new(3),
+        )
+        .unwrap();
+        assert!(!is_proof_valid);
+
+        // missing commitments are rejected
+        let mut missing_t_hat = proof;
+        missing_t_hat.commitments.5.pop();
+        let is_proof_valid = OffchainModule::verify_shuffle_proof(
+            &topic_id,
+            missing_t_hat,
+            ciphers,
+            shuffled_ciphers,
+            &pk,
+            &ProtocolConfig::new(3),
+        )
+        .unwrap();
+        assert!(!is_proof_valid);
+    });
+}
+
+#[test]
+#[ignore = "will take over 30s to complete, run only when necessary"]
+fn test_shuffle_proof_medium_system() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
+        assert!(is_p_prime);
+        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
+        assert!(is_q_prime);
+
+        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, false);
+        assert!(is_proof_valid);
+    });
+}
+
+#[test]
+#[ignore = "will take over 30s to complete, run only when necessary"]
+fn test_shuffle_proof_large_system() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_lg_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
+        assert!(is_p_prime);
+        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
+        assert!(is_q_prime);
+
+        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, false);
+        assert!(is_proof_valid);
+    });
+}
+
+#[test]
+#[ignore = "will take over 60s to complete, run only when necessary"]
+fn test_shuffle_proof_xl_system() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_xl_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
+        assert!(is_p_prime);
+        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
+        assert!(is_q_prime);
+
+        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, false);
+        assert!(is_proof_valid);
+    });
+}
+
+#[test]
+fn test_set_vote_phase_not_a_voting_authority() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (_, _, pk) = Helper::setup_sm_system();
+
+        // create fake vote_id
+        let vote_id = "20201212".as_bytes().to_vec();
+
+        // Setup Public Key
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // use a normal user (i.e. the default voter)
+        // NOT a voting authority
+        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        let who = Origin::signed(account);
+
+        // try to change the vote phase
+        assert_err!(
+            OffchainModule::set_vote_phase(who, vote_id, VotePhase::Voting),
+            Error::<TestRuntime>::NotAVotingAuthority
+        )
+    });
+}
+
+#[test]
+fn test_set_vote_phase_vote_does_not_exist() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (_, _, pk) = Helper::setup_sm_system();
+
+        // create fake vote_id
+        let vote_id = "20201212".as_bytes().to_vec();
+
+        // Setup Public Key
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // create the submitter (i.e. the voting_authority)
+        // use Alice as VotingAuthority
+        let who = get_voting_authority();
+
+        // try to change the vote phase
+        assert_err!(
+            OffchainModule::set_vote_phase(who, vote_id, VotePhase::Voting),
+            Error::<TestRuntime>::VoteDoesNotExist
+        )
+    });
+}
+
+#[test]
+fn test_set_vote_phase() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+
+        // Setup Vote
+        let (vote_id, _) = setup_vote(params.into());
+
+        // Setup Public Key
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // create the submitter (i.e. the voting_authority)
+        // use Alice as VotingAuthority
+        let who = get_voting_authority();
+
+        // change the VotePhase to Voting
+        assert_ok!(OffchainModule::set_vote_phase(
+            who.clone(),
+            vote_id.clone(),
+            VotePhase::Voting
+        ));
+        assert_eq!(
+            OffchainModule::votes(vote_id.clone()).phase,
+            VotePhase::Voting
+        );
+
+        // change the VotePhase to Tallying
+        assert_ok!(OffchainModule::set_vote_phase(
+            who,
+            vote_id.clone(),
+            VotePhase::Tallying
+        ));
+        assert_eq!(OffchainModule::votes(vote_id).phase, VotePhase::Tallying);
+    });
+}
+
+#[test]
+fn test_set_vote_phase_cannot_return_to_key_generation() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, _) = Helper::setup_sm_system();
+        let who = get_voting_authority();
+
+        // a vote which hasn't started can stay in the key generation phase
+        let (vote_id, _) =
+            setup_vote_in_key_generation(params.into(), TallyStrategy::Mixnet);
+        assert_ok!(OffchainModule::set_vote_phase(
+            who.clone(),
+            vote_id.clone(),
+            VotePhase::KeyGeneration
+        ));
+        assert!(!OffchainModule::voting_started(&vote_id));
+
+        // once the voting phase has started, the vote can't return to the key generation
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+        assert!(OffchainModule::voting_started(&vote_id));
+        assert_err!(
+            OffchainModule::set_vote_phase(
+                who.clone(),
+                vote_id.clone(),
+                VotePhase::KeyGeneration
+            ),
+            Error::<TestRuntime>::VoteAlreadyStarted
+        );
+
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert_err!(
+            OffchainModule::set_vote_phase(
+                who,
+                vote_id.clone(),
+                VotePhase::KeyGeneration
+            ),
+            Error::<TestRuntime>::VoteAlreadyStarted
+        );
+        assert_eq!(OffchainModule::votes(vote_id).phase, VotePhase::Tallying);
+    });
+}
+
+#[test]
+fn test_vote_phase_transitions() {
+    use VotePhase::*;
+    let phases = [KeyGeneration, Voting, Tallying, Cancelled];
+    let valid = [
+        (KeyGeneration, KeyGeneration),
+        (KeyGeneration, Voting),
+        (KeyGeneration, Cancelled),
+        (Voting, KeyGeneration),
+        (Voting, Voting),
+        (Voting, Tallying),
+        (Voting, Cancelled),
+        (Tallying, Cancelled),
+    ];
+    for from in phases.iter() {
+        for to in phases.iter() {
+            let expected = valid.contains(&(from.clone(), to.clone()));
+            assert_eq!(
+                from.can_transition_to(to),
+                expected,
+                "{:?} -> {:?}",
+                from,
+                to
+            );
+        }
+    }
+}
+
+#[test]
+fn test_set_vote_phase_rejects_invalid_transitions() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, _) = Helper::setup_sm_system();
+        let who = get_voting_authority();
+
+        // the voting phase can't be skipped
+        let (vote_id, _) =
+            setup_vote_in_key_generation(params.into(), TallyStrategy::Mixnet);
+        assert_err!(
+            OffchainModule::set_vote_phase(
+                who.clone(),
+                vote_id.clone(),
+                VotePhase::Tallying
+            ),
+            Error::<TestRuntime>::InvalidPhaseTransition
+        );
+        assert!(OffchainModule::tallying_approvals(&vote_id).is_empty());
+
+        // a vote which is being tallied can't return to the voting phase
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert_err!(
+            OffchainModule::set_vote_phase(
+                who.clone(),
+                vote_id.clone(),
+                VotePhase::Voting
+            ),
+            Error::<TestRuntime>::InvalidPhaseTransition
+        );
+        assert_err!(
+            OffchainModule::set_vote_phase(who, vote_id.clone(), VotePhase::Tallying),
+            Error::<TestRuntime>::InvalidPhaseTransition
+        );
+        assert_eq!(OffchainModule::votes(&vote_id).phase, VotePhase::Tallying);
+
+        // the event carries the previous and the new phase
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::VotePhaseChanged(
+                vote_id.clone(),
+                VotePhase::Voting,
+                VotePhase::Tallying
+            ))));
+    });
+}
+
+#[test]
+fn test_force_vote_phase() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, _) = setup_vote(params.into());
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+
+        // only the root origin can force the phase
+        assert_err!(
+            OffchainModule::force_vote_phase(
+                get_voting_authority(),
+                vote_id.clone(),
+                VotePhase::Voting
+            ),
+            DispatchError::BadOrigin
+        );
+
+        // the transitions are not checked
+        assert_ok!(OffchainModule::force_vote_phase(
+            Origin::root(),
+            vote_id.clone(),
+            VotePhase::Voting
+        ));
+        assert_eq!(OffchainModule::votes(&vote_id).phase, VotePhase::Voting);
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::VotePhaseChanged(
+                vote_id.clone(),
+                VotePhase::Tallying,
+                VotePhase::Voting
+            ))));
+
+        // a vote is still only cancelled using cancel_vote
+        assert_err!(
+            OffchainModule::force_vote_phase(
+                Origin::root(),
+                vote_id.clone(),
+                VotePhase::Cancelled
+            ),
+            Error::<TestRuntime>::WrongVotePhase
+        );
+
+        // a started vote only returns to the key generation by rotating its key
+        assert_err!(
+            OffchainModule::force_vote_phase(
+                Origin::root(),
+                vote_id.clone(),
+                VotePhase::KeyGeneration
+            ),
+            Error::<TestRuntime>::VoteAlreadyStarted
+        );
+
+        // the ciphers can only be tallied once they are encrypted under the current key
+        let topic_id = OffchainModule::topics(&vote_id)[0].id.clone();
+        PendingKeySwitch::insert(&vote_id, vec![topic_id]);
+        assert_err!(
+            OffchainModule::force_vote_phase(
+                Origin::root(),
+                vote_id.clone(),
+                VotePhase::Tallying
+            ),
+            Error::<TestRuntime>::KeyRotationPending
+        );
+        assert_eq!(OffchainModule::votes(&vote_id).phase, VotePhase::Voting);
+        PendingKeySwitch::remove(&vote_id);
+
+        // re-entering the tallying phase keeps the first freeze of the ballot box
+        let freeze = OffchainModule::ballot_box_freeze(&vote_id).unwrap();
+        assert_ok!(OffchainModule::force_vote_phase(
+            Origin::root(),
+            vote_id.clone(),
+            VotePhase::Tallying
+        ));
+        assert_eq!(OffchainModule::ballot_box_freeze(&vote_id), Some(freeze));
+        let nr_of_freezes = System::events()
+            .iter()
+            .filter(|er| {
+                matches!(
+                    er.event,
+                    TestEvent::pallet_mixnet(RawEvent::BallotBoxFrozen(..))
+                )
+            })
+            .count();
+        assert_eq!(nr_of_freezes, 1);
+    });
+}
+
+#[test]
+fn test_vote_phase_query() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, _) = Helper::setup_sm_system();
+
+        // the vote doesn't exist yet
+        let vote_id = "20201212".as_bytes().to_vec();
+        assert_eq!(OffchainModule::vote_phase(&vote_id), None);
+
+        // Setup Vote
+        let (vote_id, _) = setup_vote(params.into());
+        assert_eq!(
+            OffchainModule::vote_phase(&vote_id),
+            Some(VotePhase::Voting)
+        );
+    });
+}
+
+#[test]
+fn test_vote_details_query() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let vote_id = "20201212".as_bytes().to_vec();
+        assert_eq!(OffchainModule::vote_details(&vote_id), None);
+
+        // the public key is only returned once it has been stored
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let details = OffchainModule::vote_details(&vote_id).unwrap();
+        assert_eq!(details.vote, OffchainModule::votes(&vote_id));
+        assert_eq!(details.topics.len(), 1);
+        assert_eq!(details.topics[0].id, topic_id);
+        assert_eq!(details.public_key, None);
+
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        let details = OffchainModule::vote_details(&vote_id).unwrap();
+        assert_eq!(details.public_key, Some(pk.into()));
+    });
+}
+
+#[test]
+fn test_store_public_key_share_fail_is_voting_authority() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Setup
+        let (params, sk, pk) = Helper::setup_md_system();
+        let (vote_id, _) = setup_vote(params.clone().into());
+
+        // create the submitter (i.e. the voting_authority)
+        // use Alice as VotingAuthority
+        let who = get_voting_authority();
+
+        // create public key share + proof
+        let sealer_id = "Bob".as_bytes();
+        let r = BigUint::parse_bytes(b"170141183460469231731687303715884", 10).unwrap();
+        let proof = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &r, sealer_id);
+        let pk_share = PublicKeyShare {
+            proof: proof.into(),
+            pk: pk.h.to_bytes_be(),
+        };
+
+        // submit the public key share
+        assert_err!(
+            OffchainModule::store_public_key_share(who, vote_id, pk_share.into()),
+            Error::<TestRuntime>::IsVotingAuthority
+        )
+    });
+}
+
+#[test]
+fn test_store_public_key_share_fail_no_sealers() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Setup
+        let (params, sk, pk) = Helper::setup_md_system();
+        let (vote_id, _) = setup_vote(params.clone().into());
+
+        // use a normal user (i.e. the default voter)
+        // NOT a voting authority
+        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        let who = Origin::signed(account);
+        let sealer_id = "Bob".as_bytes();
+
+        // create public key share + proof
+        let r = BigUint::parse_bytes(b"170141183460469231731687303715884", 10).unwrap();
+        let proof = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &r, sealer_id);
+        let pk_share = PublicKeyShare {
+            proof: proof.into(),
+            pk: pk.h.to_bytes_be(),
+        };
+
+        // submit the public key share
+        assert_err!(
+            OffchainModule::store_public_key_share(who, vote_id, pk_share.into()),
+            Error::<TestRuntime>::NotASealer
+        )
+    });
+}
+
+#[test]
+fn test_add_and_remove_sealer_between_votes() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (bob_origin, bob, _) = get_sealer_bob();
+        let (_, charlie, _) = get_sealer_charlie();
+        let dave: <TestRuntime as frame_system::Trait>::AccountId = account(&DAVE);
+
+        // only a voting authority can change the sealers
+        assert_err!(
+            OffchainModule::add_sealer(bob_origin, dave),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+        assert_eq!(OffchainModule::sealer_epoch(), 0);
+        assert_eq!(OffchainModule::sealer_set(0), vec![bob, charlie]);
+
+        // the vote is created with the sealers of the epoch 1
+        assert_ok!(OffchainModule::add_sealer(get_voting_authority(), dave));
+        assert!(System::events().iter().any(
+            |er| er.event == TestEvent::pallet_mixnet(RawEvent::SealerAdded(dave, 1))
+        ));
+        assert_err!(
+            OffchainModule::add_sealer(get_voting_authority(), dave),
+            Error::<TestRuntime>::SealerAlreadyAdded
+        );
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, _) = setup_vote(params.into());
+        assert_eq!(OffchainModule::vote_sealer_epoch(&vote_id), 1);
+
+        // the removal only applies to new votes, the vote keeps its sealers
+        assert_ok!(OffchainModule::remove_sealer(
+            get_voting_authority(),
+            charlie
+        ));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::SealerRemoved(charlie, 2))));
+        assert_eq!(OffchainModule::sealer_epoch(), 2);
+        assert_eq!(OffchainModule::sealers(), vec![bob, dave]);
+        assert_eq!(OffchainModule::sealer_set(2), vec![bob, dave]);
+        assert_eq!(
+            helpers::sealers::get_vote_sealers::<TestRuntime>(&vote_id),
+            vec![bob, charlie, dave]
+        );
+        assert!(!helpers::sealers::is_sealer::<TestRuntime>(&charlie));
+
+        // the sealers of a vote which is being tallied remain sealers
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert!(helpers::sealers::is_sealer::<TestRuntime>(&charlie));
+        assert_ok!(OffchainModule::heartbeat(Origin::signed(charlie)));
+
+        // the last sealer can't be removed
+        assert_err!(
+            OffchainModule::remove_sealer(get_voting_authority(), charlie),
+            Error::<TestRuntime>::SealerNotFound
+        );
+        assert_ok!(OffchainModule::remove_sealer(get_voting_authority(), bob));
+        assert_err!(
+            OffchainModule::remove_sealer(get_voting_authority(), dave),
+            Error::<TestRuntime>::TooFewSealers
+        );
+        assert_eq!(OffchainModule::sealers(), vec![dave]);
+    });
+}
+
+#[test]
+fn test_store_public_key_share() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Setup
+        let (params, sk, pk) = Helper::setup_md_system();
+        let (vote_id, _) = setup_vote(params.clone().into());
+
+        // use sealer bob
+        let (who, account_id, sealer_id) = get_sealer_bob();
+        let (_, proof) = setup_sealer(&params, &sk, &pk, who, &vote_id, &sealer_id);
+
+        // verify the public key share submission + proof verification
+        let shares: Vec<PublicKeyShare> = OffchainModule::key_shares(vote_id.clone());
+        assert_eq!(shares[0].pk, pk.h.to_bytes_be());
+        assert_eq!(shares[0].proof.challenge, proof.challenge.to_bytes_be());
+        assert_eq!(shares[0].proof.response, proof.response.to_bytes_be());
+
+        let share_by_sealer: PublicKeyShare =
+            OffchainModule::key_share_by_sealer((vote_id, account_id)).unwrap();
+        assert_eq!(share_by_sealer.pk, pk.h.to_bytes_be());
+        assert_eq!(
+            share_by_sealer.proof.challenge,
+            proof.challenge.to_bytes_be()
+        );
+        assert_eq!(share_by_sealer.proof.response, proof.response.to_bytes_be());
+    });
+}
+
+#[test]
+fn test_combine_public_key_shares_not_voting_authority() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // create fake vote_id
+        let vote_id = "20201212".as_bytes().to_vec();
+
+        // Use sealer instead of voting authority
+        let (bob, _, _) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::combine_public_key_shares(bob, vote_id),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+    });
+}
+
+#[test]
+fn test_combine_public_key_shares_vote_does_not_exist() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // create fake vote_id
+        let vote_id = "20201212".as_bytes().to_vec();
+
+        // use authority but vote doesn't exist
+        let who = get_voting_authority();
+        assert_err!(
+            OffchainModule::combine_public_key_shares(who, vote_id),
+            Error::<TestRuntime>::VoteDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn test_combine_public_key_shares() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Setup
+        let (params, sk, pk) = Helper::setup_md_system();
+        let (vote_id, _) = setup_vote(params.clone().into());
+
+        // Use 1. Sealer: Bob
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        let (bob_key, _) = setup_sealer(&params, &sk, &pk, bob, &vote_id, &bob_sealer_id);
+
+        // Use 2. Sealer: Charlie
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        let (charlie_key, _) =
+            setup_sealer(&params, &sk, &pk, charlie, &vote_id, &charlie_sealer_id);
+
+        // combine the public key shares
+        let voting_authority = get_voting_authority();
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority,
+            vote_id.clone()
+        ));
+
+        // VERIFY
+        // fetch the public key from the chain
+        let pk = ElGamalPK {
+            h: BigUint::from_bytes_be(&bob_key.pk)
+                .modmul(&BigUint::from_bytes_be(&charlie_key.pk), &params.p),
+            params: params.clone(),
+        };
+        let pk_from_chain: ElGamalPK =
+            OffchainModule::public_key(vote_id).unwrap().into();
+        assert_eq!(pk_from_chain, pk);
+    });
+}
+
+#[test]
+fn test_submit_decrypted_share_vote_does_not_exist() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Setup
+        let (_, _, pk) = Helper::setup_sm_system();
+
+        // create fake everything
+        let vote_id = "20201212".as_bytes().to_vec();
+        let topic_id = "vote1".as_bytes().to_vec();
+        let shares: Vec<Vec<u8>> = Vec::new();
+        let proof = DecryptedShareProof {
+            challenge: Vec::new(),
+            response: Vec::new(),
+        };
+
+        // Setup Public Key
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // create the submitter (i.e. the voting_authority)
+        // use Alice as VotingAuthority
+        let who = get_voting_authority();
+
+        assert_err!(
+            OffchainModule::submit_decrypted_shares(
+                who,
+                vote_id,
+                topic_id,
+                shares,
+                proof,
+                NR_OF_SHUFFLES
+            ),
+            Error::<TestRuntime>::VoteDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn test_submit_decrypted_share_wrong_vote_phase() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // setup public key
+        let (params, _, pk) = Helper::setup_sm_system();
+
+        // setup vote
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // fake proof + fake decrypted shares
+        let shares: Vec<Vec<u8>> = Vec::new();
+        let proof = DecryptedShareProof {
+            challenge: Vec::new(),
+            response: Vec::new(),
+        };
+
+        // create the submitter (i.e. the voting_authority)
+        // use Alice as VotingAuthority
+        let who = get_voting_authority();
+
+        assert_err!(
+            OffchainModule::submit_decrypted_shares(
+                who,
+                vote_id,
+                topic_id,
+                shares,
+                proof,
+                NR_OF_SHUFFLES
+            ),
+            Error::<TestRuntime>::WrongVotePhase
+        );
+    });
+}
+
+#[test]
+fn test_submit_decrypted_share_not_a_sealer() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // setup public key
+        let (params, _, pk) = Helper::setup_sm_system();
+
+        // setup vote
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // fake proof + fake decrypted shares
+        let shares: Vec<Vec<u8>> = Vec::new();
+        let proof = DecryptedShareProof {
+            challenge: Vec::new(),
+            response: Vec::new(),
+        };
+
+        // change the VotePhase to Tallying
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+
+        // check that the voting authority is not allowed
+        let voting_authority = get_voting_authority();
+        assert_err!(
+            OffchainModule::submit_decrypted_shares(
+                voting_authority,
+                vote_id,
+                topic_id,
+                shares,
+                proof,
+                NR_OF_SHUFFLES
+            ),
+            Error::<TestRuntime>::NotASealer
+        );
+    });
+}
+
+#[test]
+fn test_cast_ballots_of_election_fixture() {
+    let fixture = ElectionFixture::builder()
+        .key_size(KeySize::Medium)
+        .sealers(3)
+        .vote(b"20201212", &[b"20201212-01"])
+        .ballots(7)
+        .build();
+    let (mut t, _, _) = ExternalityBuilder::with_fixture(&fixture);
+    t.execute_with(|| {
+        // the fixture's vote, i.e., the one of setup_vote
+        let (vote_id, topic_id) = setup_vote_in_key_generation(
+            fixture.public_parameters(),
+            TallyStrategy::Mixnet,
+        );
+        let vote = fixture.vote(&vote_id).unwrap();
+        assert_eq!(vote.topic_ids, vec![topic_id.clone()]);
+
+        // the genesis sealers (Bob, Charlie and Dave) submit their key shares
+        for sealer in fixture.sealers.iter() {
+            let pk_share = PublicKeyShare {
+                proof: sealer.key_generation_proof().into(),
+                pk: sealer.pk.h.to_bytes_be(),
+            };
+            assert_ok!(OffchainModule::store_public_key_share(
+                Origin::signed(account(&sealer.account)),
+                vote_id.clone(),
+                pk_share
+            ));
+        }
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            get_voting_authority(),
+            vote_id.clone()
+        ));
+        assert_eq!(
+            OffchainModule::public_key(vote_id.clone()),
+            Some(fixture.system_public_key())
+        );
+
+        // every voter casts its pre-cast ballot
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+        for ballot in vote.ballots.iter() {
+            assert_ok!(OffchainModule::cast_ballot(
+                Origin::signed(account(&ballot.voter)),
+                vote_id.clone(),
+                Ballot {
+                    answers: ballot.answers.clone(),
+                    proofs: Vec::new(),
+                    validity_proofs: Vec::new(),
+                    credential: None,
+                }
+            ));
+        }
+
+        // the ciphers on chain decrypt to the expected tally
+        let sk = PrivateKey {
+            params: fixture.params.clone(),
+            x: fixture
+                .sealers
+                .iter()
+                .fold(BigUint::zero(), |x, sealer| x + &sealer.sk.x),
+        };
+        let ciphers: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES)).into();
+        let mut tally: BTreeMap<BigUint, u32> = BTreeMap::new();
+        for cipher in ciphers.iter() {
+            *tally
+                .entry(ElGamal::decrypt_decode(cipher, &sk))
+                .or_insert(0) += 1;
+        }
+        let expected: BTreeMap<BigUint, u32> = vote
+            .expected_tally(&topic_id)
+            .into_iter()
+            .map(|(message, count)| (BigUint::from(message), count))
+            .collect();
+        assert_eq!(tally, expected);
+    });
+}
+
+#[test]
+fn test_submit_decrypted_share() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Distributed Key Generation Setup
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.clone().into());
+
+        // Use 1. Sealer: Bob
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
+        let (_, _) = setup_sealer(
+            &params,
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
+
+        // Use 2. Sealer: Charlie
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
+        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
+        let (_, _) = setup_sealer(
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            charlie,
+            &vote_id,
+            &charlie_sealer_id,
+        );
+
+        // combine the public key shares
+        let voting_authority = get_voting_authority();
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority,
+            vote_id.clone()
+        ));
+
+        // get the public key from the chain
+        let system_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
+        let computed_system_pk: BigUint =
+            bob_pk.h.modmul(&charlie_pk.h, &bob_pk.params.p);
+        assert_eq!(system_pk.h, computed_system_pk);
+
+        // create encrypted votes - NOT ENCODED
+        setup_ciphers(&vote_id, &topic_id, &system_pk.clone().into(), false);
+
+        // change the VotePhase to Tallying
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+
+        // fetch the encrypted votes from chain
+        let encryptions: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES)).into();
+        assert!(encryptions.len() > 0);
+
+        // get bob's partial decryptions
+        let bob_partial_decrytpions = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &bob_sk))
+            .collect::<Vec<BigUint>>();
+
+        // convert the decrypted shares: Vec<BigUint> to Vec<Vec<u8>>
+        let bob_shares: Vec<Vec<u8>> = bob_partial_decrytpions
+            .iter()
+            .map(|c| c.to_bytes_be())
+            .collect::<Vec<Vec<u8>>>();
+
+        // create bob's proof using bob's public and private key share
+        let r = BigUint::parse_bytes(b"1234123123", 10).unwrap();
+        let bob_proof = DecryptionProof::generate_batched(
+            &params,
+            &bob_sk.x,
+            &bob_pk.h.into(),
+            &r,
+            &encryptions,
+            &bob_partial_decrytpions,
+            &bob_sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        );
+
+        // check that:
+        // 1. the decrypted share is submitted and
+        // 2. the proof is successfully verified
+        assert_ok!(OffchainModule::submit_decrypted_shares(
+            bob.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            bob_shares,
+            bob_proof.into(),
+            NR_OF_SHUFFLES
+        ));
+
+        // the submission is part of the audit trail
+        let audit =
+            OffchainModule::decryption_audit((&vote_id, &topic_id), NR_OF_SHUFFLES);
+        assert_eq!(audit.len(), 1);
+
+        // the decryption chain is incomplete, charlie hasn't submitted yet
+        assert!(!OffchainModule::verify_full_decryption_chain(
+            &vote_id,
+            &topic_id,
+            &NR_OF_SHUFFLES
+        ));
+
+        // one out of two sealers decrypted the ciphers
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::DecryptionProgress(
+                vote_id.clone(),
+                topic_id.clone(),
+                1,
+                2
+            ))));
+        let progress = OffchainModule::progress(&vote_id);
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].decrypted_shares, 1);
+        assert_eq!(progress[0].required_shares, 2);
+        assert!(!progress[0].tallied);
+    });
+}
+
+#[test]
+fn test_submit_negated_decrypted_shares() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Distributed Key Generation Setup
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.clone().into());
+
+        // Use 1. Sealer: Bob
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
+        let (_, _) = setup_sealer(
+            &params,
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
+
+        // Use 2. Sealer: Charlie
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
+        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
+        let (_, _) = setup_sealer(
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            charlie,
+            &vote_id,
+            &charlie_sealer_id,
+        );
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            get_voting_authority(),
+            vote_id.clone()
+        ));
+        let system_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
+        setup_ciphers(&vote_id, &topic_id, &system_pk.clone().into(), false);
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+
+        let encryptions: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES)).into();
+        assert!(encryptions.len() > 1);
+        let mut bob_partial_decrytpions = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &bob_sk))
+            .collect::<Vec<BigUint>>();
+
+        // bob multiplies two shares by the element of order 2 (p - 1),
+        // the batched proof over the negated shares is consistent
+        let p = &params.p;
+        let minus_one = p - 1u32;
+        for index in 0..2 {
+            bob_partial_decrytpions[index] =
+                bob_partial_decrytpions[index].modmul(&minus_one, p);
+        }
+        let bob_shares: Vec<Vec<u8>> = bob_partial_decrytpions
+            .iter()
+            .map(|c| c.to_bytes_be())
+            .collect::<Vec<Vec<u8>>>();
+        let r = BigUint::parse_bytes(b"1234123123", 10).unwrap();
+        let bob_proof = DecryptionProof::generate_batched(
+            &params,
+            &bob_sk.x,
+            &bob_pk.h.into(),
+            &r,
+            &encryptions,
+            &bob_partial_decrytpions,
+            &bob_sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        );
+
+        // the negated shares aren't group elements and are rejected
+        assert_err!(
+            OffchainModule::submit_decrypted_shares(
+                bob,
+                vote_id.clone(),
+                topic_id.clone(),
+                bob_shares,
+                bob_proof.into(),
+                NR_OF_SHUFFLES
+            ),
+            Error::<TestRuntime>::DecryptedShareProofError
+        );
+        assert!(
+            OffchainModule::decryption_audit((&vote_id, &topic_id), NR_OF_SHUFFLES)
+                .is_empty()
+        );
+    });
+}
+
+#[test]
+fn test_combine_decrypted_shares_vote_does_not_exist() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let voting_authority = get_voting_authority();
+        assert_err!(
+            OffchainModule::combine_decrypted_shares(
+                voting_authority,
+                "vote_id".as_bytes().to_vec(),
+                "topic_id".as_bytes().to_vec(),
+                false,
+                NR_OF_SHUFFLES
+            ),
+            Error::<TestRuntime>::VoteDoesNotExist
+        );
+    })
+}
+
+#[test]
+fn test_combine_decrypted_shares_wrong_vote_phase() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.clone().into());
+
+        // change the votephase to tallying
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+
+        // use bob as a submitter
+        let (bob, _, _) = get_sealer_bob();
+
+        // try to combine shares -> not a voting authority
+        assert_err!(
+            OffchainModule::combine_decrypted_shares(
+                bob,
+                vote_id,
+                topic_id,
+                false,
+                NR_OF_SHUFFLES
+            ),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+    })
+}
+
+#[test]
+fn test_combine_decrypted_shares_not_a_voting_authority() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.clone().into());
+
+        // try to combine shares -> voting phase not updated yet
+        let voting_authority = get_voting_authority();
+        assert_err!(
+            OffchainModule::combine_decrypted_shares(
+                voting_authority,
+                vote_id,
+                topic_id,
+                false,
+                NR_OF_SHUFFLES
+            ),
+            Error::<TestRuntime>::WrongVotePhase
+        );
+    })
+}
+
+#[test]
+fn test_combine_decrypted_shares() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Distributed Key Generation Setup
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.clone().into());
+
+        // Use 1. Sealer: Bob
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
+        let (_, _) = setup_sealer(
+            &params,
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
+
+        // Use 2. Sealer: Charlie
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
+        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
+        let (_, _) = setup_sealer(
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            charlie.clone(),
+            &vote_id,
+            &charlie_sealer_id,
+        );
+
+        // combine the public key shares
+        let voting_authority = get_voting_authority();
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
+
+        // get the public key from the chain
+        let system_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
+        let computed_system_pk: BigUint =
+            bob_pk.h.modmul(&charlie_pk.h, &bob_pk.params.p);
+        assert_eq!(system_pk.h, computed_system_pk);
+
+        // create encrypted votes - NOT ENCODED
+        setup_ciphers(&vote_id, &topic_id, &system_pk.clone().into(), false);
+
+        // change the VotePhase to Voting using the voting authority
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+
+        // fetch the encrypted votes from chain
+        let encryptions: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES)).into();
+        assert!(encryptions.len() > 0);
+
+        // get bob's partial decryptions
+        let bob_partial_decrytpions = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &bob_sk))
+            .collect::<Vec<BigUint>>();
+
+        // convert the decrypted shares: Vec<BigUint> to Vec<Vec<u8>>
+        let bob_shares: Vec<Vec<u8>> = bob_partial_decrytpions
+            .iter()
+            .map(|c| c.to_bytes_be())
+            .collect::<Vec<Vec<u8>>>();
+
+        // create bob's proof using bob's public and private key share
+        let r = BigUint::parse_bytes(b"1234123123", 10).unwrap();
+        let bob_proof = DecryptionProof::generate_batched(
+            &params,
+            &bob_sk.x,
+            &bob_pk.h.into(),
+            &r,
+            &encryptions,
+            &bob_partial_decrytpions,
+            &bob_sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        );
+
+        // check that:
+        // 1. the decrypted share is submitted and
+        // 2. the proof is successfully verified
+        assert_ok!(OffchainModule::submit_decrypted_shares(
+            bob.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            bob_shares,
+            bob_proof.into(),
+            NR_OF_SHUFFLES
+        ));
+
+        // get charlie's partial decryptions
+        let charlie_paritial_decryptions = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &charlie_sk))
+            .collect::<Vec<BigUint>>();
+
+        // convert the decrypted shares: Vec<BigUint> to Vec<Vec<u8>>
+        let charlie_shares: Vec<Vec<u8>> = charlie_paritial_decryptions
+            .iter()
+            .map(|c| c.to_bytes_be())
+            .collect::<Vec<Vec<u8>>>();
+
+        // create charlie's proof using charlie's public and private key share
+        let r = BigUint::parse_bytes(b"80981238129912392", 10).unwrap();
+        let charlie_proof = DecryptionProof::generate_batched(
+            &params,
+            &charlie_sk.x,
+            &charlie_pk.h.into(),
+            &r,
+            &encryptions,
+            &charlie_paritial_decryptions,
+            &charlie_sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        );
+
+        // check that:
+        // 1. the decrypted share is submitted and
+        // 2. the proof is successfully verified
+        assert_ok!(OffchainModule::submit_decrypted_shares(
+            charlie.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            charlie_shares,
+            charlie_proof.into(),
+            NR_OF_SHUFFLES
+        ));
+
+        // the full decryption chain can be verified
+        assert!(OffchainModule::verify_full_decryption_chain(
+            &vote_id,
+            &topic_id,
+            &NR_OF_SHUFFLES
+        ));
+
+        // combine the decrypted shares + tally topic
+        assert_ok!(OffchainModule::combine_decrypted_shares(
+            voting_authority,
+            vote_id.clone(),
+            topic_id.clone(),
+            false,
+            NR_OF_SHUFFLES
+        ));
+
+        // retrieve the tallied result from the storage on chain
+        let result: TopicResult = OffchainModule::tally((&vote_id, &topic_id)).unwrap();
+
+        // transform the result from Vec<u8> (bytes) back to Vec<BigUint>
+        let mut big_result: BTreeMap<BigUint, BigUint> = BTreeMap::new();
+        for (key, value) in result.iter() {
+            big_result.insert(BigUint::from_bytes_be(key), BigUint::from_bytes_be(value));
+        }
+
+        // check that there are 2 entries for each type of vote
+        assert_eq!(
+            big_result.get(&BigUint::from(4u32)).unwrap(),
+            &BigUint::from(2u32)
+        );
+        assert_eq!(
+            big_result.get(&BigUint::from(1u32)).unwrap(),
+            &BigUint::from(2u32)
+        );
+        assert_eq!(
+            big_result.get(&BigUint::from(3u32)).unwrap(),
+            &BigUint::from(2u32)
+        );
+    });
+}
+
+/// submits the partial decryptions of all sealers and combines them, returns the result
+fn decrypt_and_tally(
+    params: &ElGamalParams,
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    sealers: &[(Origin, PrivateKey, ElGamalPK, [u8; 32])],
+) -> TopicResult {
+    let encryptions: Vec<BigCipher> =
+        Wrapper(OffchainModule::ciphers(vote_id, topic_id, NR_OF_SHUFFLES)).into();
+    for (sealer, sk, pk, sealer_id) in sealers.iter() {
+        let partial_decryptions = encryptions
+            .iter()
+            .map(|cipher| ElGamal::partial_decrypt_a(cipher, sk))
+            .collect::<Vec<BigUint>>();
+        let shares: Vec<Vec<u8>> = partial_decryptions
+            .iter()
+            .map(|c| c.to_bytes_be())
+            .collect();
+        let r = BigUint::parse_bytes(b"1234123123", 10).unwrap();
+        let proof = DecryptionProof::generate_batched(
+            params,
+            &sk.x,
+            &pk.h,
+            &r,
+            &encryptions,
+            &partial_decryptions,
+            sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+        );
+        assert_ok!(OffchainModule::submit_decrypted_shares(
+            sealer.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            shares,
+            proof.into(),
+            NR_OF_SHUFFLES
+        ));
+    }
+    assert_ok!(OffchainModule::combine_decrypted_shares(
+        get_voting_authority(),
+        vote_id.clone(),
+        topic_id.clone(),
+        false,
+        NR_OF_SHUFFLES
+    ));
+    OffchainModule::tally((vote_id, topic_id)).unwrap()
+}
+
+#[test]
+fn test_combine_decrypted_shares_of_votes_sharing_a_topic_id() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // two votes whose topics have the same id
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.clone().into());
+        let other_vote_id = "20201213".as_bytes().to_vec();
+        let other_topic = Topic::new(
+            topic_id.clone(),
+            "Moritz for Vice President?".as_bytes().to_vec(),
+        );
+        assert_ok!(OffchainModule::propose_vote(
+            get_voting_authority(),
+            other_vote_id.clone(),
+            "Popular Vote of 13.12.2020".as_bytes().to_vec(),
+            params.clone().into(),
+            vec![other_topic],
+            2,
+            3,
+            TallyStrategy::Mixnet,
+        ));
+        set_vote_phase(other_vote_id.clone(), VotePhase::Voting);
+
+        // the sealers use the same keys for both votes
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
+        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
+        for id in [&vote_id, &other_vote_id].iter() {
+            setup_sealer(&params, &bob_sk, &bob_pk, bob.clone(), id, &bob_sealer_id);
+            setup_sealer(
+                &params,
+                &charlie_sk,
+                &charlie_pk,
+                charlie.clone(),
+                id,
+                &charlie_sealer_id,
+            );
+            assert_ok!(OffchainModule::combine_public_key_shares(
+                get_voting_authority(),
+                (*id).clone()
+            ));
+        }
+        let system_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
+
+        // the same ciphers are cast in both votes, the other vote gets an additional one
+        setup_ciphers(&vote_id, &topic_id, &system_pk, false);
+        setup_ciphers(&other_vote_id, &topic_id, &system_pk, false);
+        let r = BigUint::parse_bytes(b"161235", 10).unwrap();
+        let cipher: Cipher =
+            ElGamal::encrypt(&BigUint::from(4u32), &r, &system_pk).into();
+        let ballot = Ballot {
+            answers: vec![(topic_id.clone(), cipher)],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_ok!(OffchainModule::cast_ballot(
+            Origin::signed(Default::default()),
+            other_vote_id.clone(),
+            ballot
+        ));
+        assert_eq!(
+            OffchainModule::cipher_count((&vote_id, &topic_id), NR_OF_SHUFFLES),
+            6
+        );
+        assert_eq!(
+            OffchainModule::cipher_count((&other_vote_id, &topic_id), NR_OF_SHUFFLES),
+            7
+        );
+
+        // tally both votes
+        let sealers = vec![
+            (bob, bob_sk, bob_pk, bob_sealer_id),
+            (charlie, charlie_sk, charlie_pk, charlie_sealer_id),
+        ];
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        set_vote_phase(other_vote_id.clone(), VotePhase::Tallying);
+        let result = decrypt_and_tally(&params, &vote_id, &topic_id, &sealers);
+        assert!(OffchainModule::tally((&other_vote_id, &topic_id)).is_none());
+        let other_result =
+            decrypt_and_tally(&params, &other_vote_id, &topic_id, &sealers);
+
+        // the results only count the ciphers of their own vote
+        let count = |result: &TopicResult, vote: u32| {
+            result
+                .get(&BigUint::from(vote).to_bytes_be())
+                .map(|count| BigUint::from_bytes_be(count))
+        };
+        for vote in [1u32, 3].iter() {
+            assert_eq!(count(&result, *vote), Some(BigUint::from(2u32)));
+            assert_eq!(count(&other_result, *vote), Some(BigUint::from(2u32)));
+        }
+        assert_eq!(count(&result, 4), Some(BigUint::from(2u32)));
+        assert_eq!(count(&other_result, 4), Some(BigUint::from(3u32)));
+        assert_eq!(OffchainModule::tally((&vote_id, &topic_id)), Some(result));
+        assert_eq!(
+            OffchainModule::tally((&other_vote_id, &topic_id)),
+            Some(other_result)
+        );
+    });
+}
+
+#[test]
+fn test_combine_decrypted_shares_in_batches() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // Distributed Key Generation Setup
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.clone().into());
+
+        // setup the sealers bob and charlie
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
+        setup_sealer(
+            &params,
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
+        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
+        setup_sealer(
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            charlie.clone(),
+            &vote_id,
+            &charlie_sealer_id,
+        );
+
+        let voting_authority = get_voting_authority();
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
+        let system_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
+
+        // create encrypted votes - NOT ENCODED: 1, 3, 4, 1, 3, 4
+        setup_ciphers(&vote_id, &topic_id, &system_pk, false);
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+
+        // the ciphers are decrypted in batches of 4 ciphers
+        assert_ok!(OffchainModule::set_shuffle_batch_size(
+            voting_authority.clone(),
+            vote_id.clone(),
+            4
+        ));
+
+        let encryptions: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES)).into();
+        assert_eq!(encryptions.len(), 6);
+        submit_decrypted_shares(
+            &bob_sk,
+            &bob_pk,
+            bob,
+            &bob_sealer_id,
+            &vote_id,
+            &topic_id,
+            encryptions.clone(),
+        );
+        submit_decrypted_shares(
+            &charlie_sk,
+            &charlie_pk,
+            charlie,
+            &charlie_sealer_id,
+            &vote_id,
+            &topic_id,
+            encryptions,
+        );
+
+        // the tally can't be finalized before all batches have been combined
+        assert_err!(
+            OffchainModule::finalize_tally(
+                voting_authority.clone(),
+                vote_id.clone(),
+                topic_id.clone()
+            ),
+            Error::<TestRuntime>::TallyIncomplete
+        );
+
+        // the first batch: 1, 3, 4, 1
+        assert_ok!(OffchainModule::combine_decrypted_shares_batch(
+            voting_authority.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            false,
+            NR_OF_SHUFFLES
+        ));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::TallyBatchCombined(
+                vote_id.clone(),
+                topic_id.clone(),
+                4,
+                6
+            ))));
+        let partial: PartialTally = OffchainModule::partial_tally((&vote_id, &topic_id));
+        assert_eq!(partial.get(&vec![1u8]), Some(&2));
+        assert_eq!(partial.get(&vec![3u8]), Some(&1));
+        assert_eq!(partial.get(&vec![4u8]), Some(&1));
+
+        // all batches must be combined with the same parameters
+        assert_err!(
+            OffchainModule::combine_decrypted_shares_batch(
+                voting_authority.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                true,
+                NR_OF_SHUFFLES
+            ),
+            Error::<TestRuntime>::TallyParametersMismatch
+        );
+
+        // the second batch: 3, 4
+        assert_ok!(OffchainModule::combine_decrypted_shares_batch(
+            voting_authority.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            false,
+            NR_OF_SHUFFLES
+        ));
+        let progress = OffchainModule::tally_progress((&vote_id, &topic_id)).unwrap();
+        assert_eq!((progress.position, progress.total), (6, 6));
+        assert_err!(
+            OffchainModule::combine_decrypted_shares_batch(
+                voting_authority.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                false,
+                NR_OF_SHUFFLES
+            ),
+            Error::<TestRuntime>::NoCiphersLeftToTally
+        );
+
+        // the final tally only counts the partial tally
+        assert_ok!(OffchainModule::finalize_tally(
+            voting_authority.clone(),
+            vote_id.clone(),
+            topic_id.clone()
+        ));
+        let result: TopicResult = OffchainModule::tally((&vote_id, &topic_id)).unwrap();
+        let two = BigUint::from(2u32).to_bytes_be();
+        assert_eq!(result.len(), 3);
+        assert_eq!(result.get(&vec![1u8]), Some(&two));
+        assert_eq!(result.get(&vec![3u8]), Some(&two));
+        assert_eq!(result.get(&vec![4u8]), Some(&two));
+        assert!(OffchainModule::tally_progress((&vote_id, &topic_id)).is_none());
+        assert!(OffchainModule::partial_tally((&vote_id, &topic_id)).is_empty());
+
+        assert_err!(
+            OffchainModule::finalize_tally(voting_authority, vote_id, topic_id),
+            Error::<TestRuntime>::TopicHasAlreadyBeenTallied
+        );
+    });
+}
+
+#[test]
+fn test_set_decryption_threshold() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let lower: BigUint = BigUint::parse_bytes(b"0", 10).unwrap();
-        let upper: BigUint =
-            BigUint::parse_bytes(b"10981023801283012983912312", 10).unwrap();
-        let value = OffchainModule::get_random_bigunint_range(&lower, &upper).unwrap();
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, _) =
+            setup_vote_in_key_generation(params.into(), TallyStrategy::Mixnet);
 
-        assert!(value < upper);
-        assert!(lower < value);
+        // only a voting authority can set the threshold of an existing vote
+        let (bob, _, _) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::set_decryption_threshold(bob, vote_id.clone(), 1),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+        assert_err!(
+            OffchainModule::set_decryption_threshold(
+                get_voting_authority(),
+                b"20210101".to_vec(),
+                1
+            ),
+            Error::<TestRuntime>::VoteDoesNotExist
+        );
+
+        // the threshold is bounded by the number of sealers (2)
+        for threshold in [0, 3].iter() {
+            assert_err!(
+                OffchainModule::set_decryption_threshold(
+                    get_voting_authority(),
+                    vote_id.clone(),
+                    *threshold
+                ),
+                Error::<TestRuntime>::InvalidDecryptionThreshold
+            );
+        }
+        assert_ok!(OffchainModule::set_decryption_threshold(
+            get_voting_authority(),
+            vote_id.clone(),
+            1
+        ));
+        assert_eq!(OffchainModule::decryption_threshold(&vote_id), Some(1));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::DecryptionThresholdUpdated(
+                vote_id.clone(),
+                1
+            ))));
+
+        // the threshold can't be changed once the voting phase started
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+        assert_err!(
+            OffchainModule::set_decryption_threshold(
+                get_voting_authority(),
+                vote_id.clone(),
+                2
+            ),
+            Error::<TestRuntime>::WrongVotePhase
+        );
     });
 }
 
 #[test]
-fn test_get_random_bigunint_range_upper_is_zero() {
+fn test_combine_decrypted_shares_with_threshold() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let lower: BigUint = BigUint::parse_bytes(b"0", 10).unwrap();
-        let upper: BigUint = BigUint::parse_bytes(b"0", 10).unwrap();
-        OffchainModule::get_random_bigunint_range(&lower, &upper)
-            .expect_err("The returned value should be: '<Error<T>>::RandomRangeError'");
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) =
+            setup_vote_in_key_generation(params.clone().into(), TallyStrategy::Mixnet);
+        let voting_authority = get_voting_authority();
+        assert_ok!(OffchainModule::set_decryption_threshold(
+            voting_authority.clone(),
+            vote_id.clone(),
+            1
+        ));
+
+        // with a threshold of 1, the secret polynomial is constant,
+        // i.e., every sealer holds the secret key as its key share
+        let sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (pk, sk) = Helper::generate_key_pair(&params, &sk_x);
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        setup_sealer(&params, &sk, &pk, bob, &vote_id, &bob_sealer_id);
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        setup_sealer(
+            &params,
+            &sk,
+            &pk,
+            charlie.clone(),
+            &vote_id,
+            &charlie_sealer_id,
+        );
+
+        // the public key is interpolated from the public key shares
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
+        let system_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
+        assert_eq!(system_pk.h, pk.h);
+
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+        setup_ciphers(&vote_id, &topic_id, &system_pk, false);
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+
+        // only charlie decrypts the ciphers
+        let encryptions: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES)).into();
+        submit_decrypted_shares(
+            &sk,
+            &pk,
+            charlie,
+            &charlie_sealer_id,
+            &vote_id,
+            &topic_id,
+            encryptions,
+        );
+        assert!(OffchainModule::verify_full_decryption_chain(
+            &vote_id,
+            &topic_id,
+            &NR_OF_SHUFFLES
+        ));
+        assert_ok!(OffchainModule::combine_decrypted_shares(
+            voting_authority,
+            vote_id.clone(),
+            topic_id.clone(),
+            false,
+            NR_OF_SHUFFLES
+        ));
+
+        // check that there are 2 entries for each type of vote
+        let result: TopicResult = OffchainModule::tally((&vote_id, &topic_id)).unwrap();
+        let mut big_result: BTreeMap<BigUint, BigUint> = BTreeMap::new();
+        for (key, value) in result.iter() {
+            big_result.insert(BigUint::from_bytes_be(key), BigUint::from_bytes_be(value));
+        }
+        for vote in [1u32, 3, 4].iter() {
+            assert_eq!(
+                big_result.get(&BigUint::from(*vote)).unwrap(),
+                &BigUint::from(2u32)
+            );
+        }
+    });
+}
+
+#[test]
+fn test_decryption_stalled() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_md_system();
+        let (vote_id, topic_id) =
+            setup_vote_with_strategy(params.clone().into(), TallyStrategy::Homomorphic);
+        setup_public_key(vote_id.clone(), pk.clone().into());
+
+        // cast a yes vote - ENCODED
+        let cipher: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(1u32), &BigUint::from(7u32), &pk)
+                .into();
+        let ballot = Ballot {
+            answers: vec![(topic_id.clone(), cipher)],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_ok!(OffchainModule::cast_ballot(
+            Origin::signed(Default::default()),
+            vote_id.clone(),
+            ballot
+        ));
+
+        // the aggregate cipher is ready to be decrypted
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        System::set_block_number(1);
+        assert_ok!(OffchainModule::aggregate_ciphers(
+            get_voting_authority(),
+            vote_id.clone(),
+            topic_id.clone()
+        ));
+        let deadline = 1 + TestDecryptionTimeout::get();
+        let pending = OffchainModule::pending_decryption((&vote_id, &topic_id)).unwrap();
+        assert_eq!(pending.deadline, deadline);
+
+        // nothing is reported until the deadline passed
+        OffchainModule::on_initialize(deadline);
+        assert!(!System::events().iter().any(|er| matches!(
+            er.event,
+            TestEvent::pallet_mixnet(RawEvent::DecryptionStalled(..))
+        )));
+
+        // both sealers haven't submitted their decrypted shares
+        let (_, bob_id, _) = get_sealer_bob();
+        let (_, charlie_id, _) = get_sealer_charlie();
+        OffchainModule::on_initialize(deadline + 1);
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::DecryptionStalled(
+                vote_id.clone(),
+                topic_id.clone(),
+                vec![bob_id, charlie_id]
+            ))));
+
+        // the stalled decryption is reported again after another timeout
+        let pending = OffchainModule::pending_decryption((&vote_id, &topic_id)).unwrap();
+        assert_eq!(
+            pending.deadline,
+            deadline + 1 + TestDecryptionTimeout::get()
+        );
+    });
+}
+
+#[test]
+fn test_sealer_heartbeat() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let (bob, bob_id, _) = get_sealer_bob();
+        let (_, charlie_id, _) = get_sealer_charlie();
+
+        // only sealers can send heartbeats
+        assert_err!(
+            OffchainModule::heartbeat(Origin::signed(Default::default())),
+            Error::<TestRuntime>::NotASealer
+        );
+
+        // the sealers are monitored once the vote is being tallied
+        assert_eq!(OffchainModule::heartbeat_deadline(&bob_id), None);
+        System::set_block_number(1);
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert_eq!(OffchainModule::monitored_votes(), vec![vote_id.clone()]);
+        let deadline = 1 + 2 * TestHeartbeatInterval::get();
+        assert_eq!(OffchainModule::heartbeat_deadline(&bob_id), Some(deadline));
+        assert_eq!(
+            OffchainModule::heartbeat_deadline(&charlie_id),
+            Some(deadline)
+        );
+
+        // bob sends a heartbeat, charlie doesn't
+        System::set_block_number(3);
+        assert_ok!(OffchainModule::heartbeat(bob));
+        assert_eq!(OffchainModule::last_heartbeat(&bob_id), Some(3));
+        assert_eq!(
+            OffchainModule::heartbeat_deadline(&bob_id),
+            Some(3 + 2 * TestHeartbeatInterval::get())
+        );
+
+        // nothing is reported until the deadline passed
+        OffchainModule::on_initialize(deadline);
+        assert!(!System::events().iter().any(|er| matches!(
+            er.event,
+            TestEvent::pallet_mixnet(RawEvent::SealerUnresponsive(..))
+        )));
+
+        // only charlie missed its heartbeat
+        OffchainModule::on_initialize(deadline + 1);
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::SealerUnresponsive(
+                charlie_id.clone(),
+                None
+            ))));
+        assert!(!System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::SealerUnresponsive(
+                bob_id.clone(),
+                Some(3)
+            ))));
+
+        // the unresponsive sealer is reported again after another window
+        assert_eq!(
+            OffchainModule::heartbeat_deadline(&charlie_id),
+            Some(deadline + 1 + 2 * TestHeartbeatInterval::get())
+        );
+
+        // the sealers aren't monitored anymore once the vote has been tallied
+        Tally::insert((&vote_id, &topic_id), TopicResult::new());
+        assert!(helpers::liveness::is_vote_tallied::<TestRuntime>(&vote_id));
+        helpers::liveness::stop_liveness_monitoring::<TestRuntime>(&vote_id);
+        assert!(OffchainModule::monitored_votes().is_empty());
+        assert_eq!(OffchainModule::heartbeat_deadline(&bob_id), None);
+        assert_eq!(OffchainModule::heartbeat_deadline(&charlie_id), None);
+        assert_eq!(OffchainModule::last_heartbeat(&bob_id), Some(3));
+    });
+}
+
+#[test]
+fn test_certify_result() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        let (bob, bob_id, _) = get_sealer_bob();
+        let (charlie, _, _) = get_sealer_charlie();
+        let authority_id = Votes::<TestRuntime>::get(&vote_id).voting_authority;
+
+        // the result can only be certified once all topics have been tallied
+        assert_eq!(OffchainModule::vote_result(&vote_id), None);
+        assert_err!(
+            OffchainModule::certify_result(bob.clone(), vote_id.clone(), [0u8; 32]),
+            Error::<TestRuntime>::VoteNotTallied
+        );
+
+        let mut result: TopicResult = BTreeMap::new();
+        result.insert(vec![1u8], vec![2u8]);
+        Tally::insert((&vote_id, &topic_id), result.clone());
+        let vote_result = OffchainModule::vote_result(&vote_id).unwrap();
+        assert_eq!(vote_result.results, vec![(topic_id.clone(), result)]);
+        let result_hash = vote_result.hash();
+
+        // only voting authorities and sealers can certify the result
+        assert_err!(
+            OffchainModule::certify_result(
+                Origin::signed(Default::default()),
+                vote_id.clone(),
+                result_hash
+            ),
+            Error::<TestRuntime>::NotACertifier
+        );
+
+        // the result hash must match the results
+        assert_err!(
+            OffchainModule::certify_result(bob.clone(), vote_id.clone(), [0u8; 32]),
+            Error::<TestRuntime>::ResultHashMismatch
+        );
+
+        // a single sealer approval meets the quorum, but the voting authority is missing
+        assert_ok!(OffchainModule::certify_result(
+            bob.clone(),
+            vote_id.clone(),
+            result_hash
+        ));
+        assert_eq!(OffchainModule::certified_result(&vote_id), None);
+        let pending = OffchainModule::pending_result_approvals(&vote_id).unwrap();
+        assert_eq!(pending.approvals, vec![bob_id.clone()]);
+        assert_err!(
+            OffchainModule::certify_result(bob, vote_id.clone(), result_hash),
+            Error::<TestRuntime>::ResultAlreadyApproved
+        );
+
+        // the approval of the voting authority certifies the result
+        System::set_block_number(3);
+        assert_ok!(OffchainModule::certify_result(
+            get_voting_authority(),
+            vote_id.clone(),
+            result_hash
+        ));
+        let certified = OffchainModule::certified_result(&vote_id).unwrap();
+        assert_eq!(certified.result_hash, result_hash);
+        assert_eq!(certified.approvals, vec![bob_id, authority_id]);
+        assert_eq!(certified.block_number, 3);
+        assert_eq!(OffchainModule::pending_result_approvals(&vote_id), None);
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::ResultCertified(
+                vote_id.clone(),
+                result_hash
+            ))));
+
+        // the digest of the certified result is stored for other chains,
+        // the hash chain over the results is reproduced by the verifier (see: attestation)
+        let digest = OffchainModule::result_digest(&vote_id).unwrap();
+        assert_eq!(
+            digest.results_chain,
+            hex!("d9a818b62b36cae38683a8d8273138346791898eb6b58b6a260d7aeb04eab8af")
+        );
+        assert_eq!(
+            digest.proofs_root,
+            helpers::certification::get_proofs_root::<TestRuntime>(&vote_id)
+        );
+        assert_eq!(
+            digest,
+            ResultDigest::new(digest.results_chain, digest.proofs_root, 3)
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::ResultDigestCreated(
+                vote_id.clone(),
+                digest.digest
+            ))));
+
+        // a certified result is final
+        assert_err!(
+            OffchainModule::certify_result(charlie, vote_id, result_hash),
+            Error::<TestRuntime>::ResultAlreadyCertified
+        );
     });
 }
 
-#[test]
-fn test_get_random_bigunint_range_upper_is_not_larger_than_lower() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let lower: BigUint = BigUint::parse_bytes(b"5", 10).unwrap();
-        let upper: BigUint = BigUint::parse_bytes(b"5", 10).unwrap();
-        OffchainModule::get_random_bigunint_range(&lower, &upper)
-            .expect_err("The returned value should be: '<Error<T>>::RandomRangeError'");
-    });
+fn submit_decrypted_shares(
+    sk: &PrivateKey,
+    pk: &ElGamalPK,
+    who: Origin,
+    sealer_id: &[u8],
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+    encryptions: Vec<BigCipher>,
+) {
+    // compute the partial decryptions
+    let partial_decryptions = encryptions
+        .iter()
+        .map(|cipher| ElGamal::partial_decrypt_a(cipher, sk))
+        .collect::<Vec<BigUint>>();
+    let shares: Vec<Vec<u8>> = partial_decryptions
+        .iter()
+        .map(|c| c.to_bytes_be())
+        .collect::<Vec<Vec<u8>>>();
+
+    // create the proof using the sealer's public and private key share
+    let r = BigUint::parse_bytes(b"1234123123", 10).unwrap();
+    let proof = DecryptionProof::generate_batched(
+        &pk.params,
+        &sk.x,
+        &pk.h.clone().into(),
+        &r,
+        &encryptions,
+        &partial_decryptions,
+        sealer_id,
+        DEFAULT_BATCH_SOUNDNESS,
+    );
+    assert_ok!(OffchainModule::submit_decrypted_shares(
+        who,
+        vote_id.clone(),
+        topic_id.clone(),
+        shares,
+        proof.into(),
+        NR_OF_SHUFFLES
+    ));
 }
 
 #[test]
-fn test_get_random_range() {
+fn test_homomorphic_tally() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let lower: usize = 0;
-        let upper: usize = 100;
-        let value = OffchainModule::get_random_range(lower, upper).unwrap();
+        // Distributed Key Generation Setup
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) =
+            setup_vote_with_strategy(params.clone().into(), TallyStrategy::Homomorphic);
 
-        assert!(value < upper);
-        assert!(lower < value);
-    });
-}
+        // topics which are tallied homomorphically are never shuffled
+        let shuffle_state = OffchainModule::shuffle_state((&vote_id, &topic_id)).unwrap();
+        assert!(shuffle_state.done);
 
-#[test]
-fn test_get_random_range_upper_is_zero_error() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let lower: usize = 0;
-        let upper: usize = 0;
-        OffchainModule::get_random_range(lower, upper)
-            .expect_err("The returned value should be: '<Error<T>>::RandomRangeError'");
-    });
-}
+        // Use 1. Sealer: Bob
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
+        setup_sealer(
+            &params,
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
 
-#[test]
-fn test_get_random_range_upper_is_not_larger_than_lower_error() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let lower: usize = 5;
-        let upper: usize = 5;
-        OffchainModule::get_random_range(lower, upper)
-            .expect_err("The returned value should be: '<Error<T>>::RandomRangeError'");
-    });
-}
+        // Use 2. Sealer: Charlie
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
+        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
+        setup_sealer(
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            charlie.clone(),
+            &vote_id,
+            &charlie_sealer_id,
+        );
 
-#[test]
-fn test_generate_permutation_size_zero_error() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let size = 0;
-        OffchainModule::generate_permutation(size).expect_err(
-            "The returned value should be: '<Error<T>>::PermutationSizeZeroError'",
+        // combine the public key shares
+        let voting_authority = get_voting_authority();
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
+        let system_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
+
+        // cast 5 yes/no votes (3x yes, 2x no) - ENCODED
+        let voter = Origin::signed(Default::default());
+        let votes = vec![1u32, 0, 1, 1, 0];
+        for (index, vote) in votes.iter().enumerate() {
+            let random = BigUint::from(index as u32 + 7);
+            let cipher: Cipher =
+                ElGamal::encrypt_encode(&BigUint::from(*vote), &random, &system_pk)
+                    .into();
+            let ballot = Ballot {
+                answers: vec![(topic_id.clone(), cipher)],
+                proofs: Vec::new(),
+                validity_proofs: Vec::new(),
+                credential: None,
+            };
+            assert_ok!(OffchainModule::cast_ballot(
+                voter.clone(),
+                vote_id.clone(),
+                ballot
+            ));
+        }
+
+        // the ciphers can only be aggregated in the tallying phase
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert_ok!(OffchainModule::aggregate_ciphers(
+            voting_authority.clone(),
+            vote_id.clone(),
+            topic_id.clone()
+        ));
+        assert_err!(
+            OffchainModule::aggregate_ciphers(
+                voting_authority.clone(),
+                vote_id.clone(),
+                topic_id.clone()
+            ),
+            Error::<TestRuntime>::CiphersAlreadyAggregated
+        );
+
+        // the sealers only decrypt the aggregate cipher
+        let aggregate: BigCipher =
+            OffchainModule::aggregate_cipher((&vote_id, &topic_id))
+                .unwrap()
+                .into();
+        submit_decrypted_shares(
+            &bob_sk,
+            &bob_pk,
+            bob,
+            &bob_sealer_id,
+            &vote_id,
+            &topic_id,
+            vec![aggregate.clone()],
+        );
+        submit_decrypted_shares(
+            &charlie_sk,
+            &charlie_pk,
+            charlie,
+            &charlie_sealer_id,
+            &vote_id,
+            &topic_id,
+            vec![aggregate],
+        );
+
+        // combine the decrypted shares + tally topic
+        assert_ok!(OffchainModule::combine_decrypted_shares(
+            voting_authority,
+            vote_id.clone(),
+            topic_id.clone(),
+            true,
+            NR_OF_SHUFFLES
+        ));
+
+        // 2x no (0), 3x yes (1)
+        let result: TopicResult = OffchainModule::tally((&vote_id, &topic_id)).unwrap();
+        let mut expected: TopicResult = BTreeMap::new();
+        expected.insert(
+            BigUint::from(0u32).to_bytes_be(),
+            BigUint::from(2u32).to_bytes_be(),
+        );
+        expected.insert(
+            BigUint::from(1u32).to_bytes_be(),
+            BigUint::from(3u32).to_bytes_be(),
         );
+        assert_eq!(result, expected);
     });
 }
 
 #[test]
-fn test_should_generate_a_permutation_size_three() {
+fn test_set_voter_weight() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let size = 3;
-        let permutation = OffchainModule::generate_permutation(size).unwrap();
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, _) =
+            setup_vote_in_key_generation(params.into(), TallyStrategy::Homomorphic);
+        let (bob, bob_id, _) = get_sealer_bob();
 
-        // check that the permutation has the expected size
-        assert!(permutation.len() == (size as usize));
+        // only a voting authority can set the weight of a voter
+        assert_err!(
+            OffchainModule::set_voter_weight(bob, vote_id.clone(), bob_id, Some(3)),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
 
-        // check that 0, 1, 2 occur at least once each
-        assert!(permutation.iter().any(|&value| value == 0));
-        assert!(permutation.iter().any(|&value| value == 1));
-        assert!(permutation.iter().any(|&value| value == 2));
+        // the weight must be between 1 and MaxVoterWeight
+        let voting_authority = get_voting_authority();
+        for weight in [0, TestMaxVoterWeight::get() + 1].iter() {
+            assert_err!(
+                OffchainModule::set_voter_weight(
+                    voting_authority.clone(),
+                    vote_id.clone(),
+                    bob_id,
+                    Some(*weight)
+                ),
+                Error::<TestRuntime>::InvalidVoterWeight
+            );
+        }
+        assert_ok!(OffchainModule::set_voter_weight(
+            voting_authority.clone(),
+            vote_id.clone(),
+            bob_id,
+            Some(3)
+        ));
+        assert_eq!(OffchainModule::voter_weight(&vote_id, &bob_id), Some(3));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::VoterWeightUpdated(
+                vote_id.clone(),
+                bob_id,
+                Some(3)
+            ))));
+
+        // the weight can be removed
+        assert_ok!(OffchainModule::set_voter_weight(
+            voting_authority.clone(),
+            vote_id.clone(),
+            bob_id,
+            None
+        ));
+        assert_eq!(OffchainModule::voter_weight(&vote_id, &bob_id), None);
+
+        // the weights are frozen once the voting phase has started
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+        assert_err!(
+            OffchainModule::set_voter_weight(voting_authority, vote_id, bob_id, Some(3)),
+            Error::<TestRuntime>::VoteAlreadyStarted
+        );
     });
-}
 
-#[test]
-fn test_fetch_ballots_size_zero() {
+    // the mixnet tally doesn't support weights
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let topic_id = "Moritz for President?".as_bytes().to_vec();
-        // Read pallet storage (i.e. the submitted ballots)
-        // and assert an expected result.
-        let ciphers_from_chain: Vec<Cipher> =
-            OffchainModule::ciphers(topic_id, NR_OF_SHUFFLES);
-        assert!(ciphers_from_chain.len() == 0);
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, _) =
+            setup_vote_in_key_generation(params.into(), TallyStrategy::Mixnet);
+        let (_, bob_id, _) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::set_voter_weight(
+                get_voting_authority(),
+                vote_id,
+                bob_id,
+                Some(3)
+            ),
+            Error::<TestRuntime>::WrongTallyStrategy
+        );
     });
 }
 
 #[test]
-fn store_small_dummy_vote_works_encoded() {
+fn test_weighted_homomorphic_tally() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Setup Vote
-        let (params, sk, pk) = Helper::setup_sm_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
-
-        let message = BigUint::from(1u32);
-        let random = BigUint::from(7u32);
-
-        // encrypt the message -> encrypted message
-        // cipher = the crypto crate version of a ballot { a: BigUint, b: BigUint }
-        let big_cipher: BigCipher = ElGamal::encrypt_encode(&message, &random, &pk);
-
-        // transform the ballot into a from that the blockchain can handle
-        // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
-        let cipher: Cipher = big_cipher.clone().into();
-        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher.clone())];
-        let ballot: Ballot = Ballot { answers };
-
-        // create the voter (i.e. the transaction signer)
-        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
-        let voter = Origin::signed(account);
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote_in_key_generation(
+            params.clone().into(),
+            TallyStrategy::Homomorphic,
+        );
 
-        let vote_submission_result = OffchainModule::cast_ballot(voter, vote_id, ballot);
-        assert_ok!(vote_submission_result);
+        // the default voter has weight 3, Bob has weight 2 and Charlie has no weight (1)
+        let voting_authority = get_voting_authority();
+        let voter: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        let (bob, bob_id, bob_sealer_id) = get_sealer_bob();
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        for (account, weight) in [(voter, 3), (bob_id, 2)].iter() {
+            assert_ok!(OffchainModule::set_voter_weight(
+                voting_authority.clone(),
+                vote_id.clone(),
+                *account,
+                Some(*weight)
+            ));
+        }
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
 
-        // fetch the submitted ballot
-        let ciphers_from_chain: Vec<Cipher> =
-            OffchainModule::ciphers(topic_id, NR_OF_SHUFFLES);
-        assert!(ciphers_from_chain.len() > 0);
+        // setup the sealers and the public key
+        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
+        setup_sealer(
+            &params,
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
+        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
+        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
+        setup_sealer(
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            charlie.clone(),
+            &vote_id,
+            &charlie_sealer_id,
+        );
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
+        let system_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
 
-        let cipher_from_chain: Cipher = ciphers_from_chain[0].clone();
-        assert_eq!(cipher, cipher_from_chain);
+        // the answers of a weighted voter require a proof of its registered weight
+        let yes: Cipher =
+            ElGamal::encrypt_encode(&BigUint::one(), &BigUint::from(7u32), &system_pk)
+                .into();
+        let unproven = Ballot {
+            answers: vec![(topic_id.clone(), yes)],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_err!(
+            OffchainModule::cast_ballot(
+                Origin::signed(voter),
+                vote_id.clone(),
+                unproven.clone()
+            ),
+            Error::<TestRuntime>::BallotValidityProofError
+        );
+        let (cipher, proof) =
+            encrypt_weighted_answer(&system_pk, 1, 1, 9, &voter.encode());
+        let wrong_weight = Ballot {
+            answers: vec![(topic_id.clone(), cipher)],
+            proofs: Vec::new(),
+            validity_proofs: vec![proof],
+            credential: None,
+        };
+        assert_err!(
+            OffchainModule::cast_ballot(
+                Origin::signed(voter),
+                vote_id.clone(),
+                wrong_weight
+            ),
+            Error::<TestRuntime>::BallotValidityProofError
+        );
 
-        // transform the Ballot -> BigCipher
-        let big_cipher_from_chain: BigCipher = cipher_from_chain.into();
-        assert_eq!(big_cipher, big_cipher_from_chain);
+        // the default voter votes yes (3), Bob votes no (0) and Charlie votes yes (1)
+        let (cipher, proof) =
+            encrypt_weighted_answer(&system_pk, 3, 3, 11, &voter.encode());
+        let ballot = Ballot {
+            answers: vec![(topic_id.clone(), cipher)],
+            proofs: Vec::new(),
+            validity_proofs: vec![proof],
+            credential: None,
+        };
+        assert_ok!(OffchainModule::cast_ballot(
+            Origin::signed(voter),
+            vote_id.clone(),
+            ballot
+        ));
+        let (cipher, proof) =
+            encrypt_weighted_answer(&system_pk, 2, 0, 13, &bob_id.encode());
+        let ballot = Ballot {
+            answers: vec![(topic_id.clone(), cipher)],
+            proofs: Vec::new(),
+            validity_proofs: vec![proof],
+            credential: None,
+        };
+        assert_ok!(OffchainModule::cast_ballot(
+            bob.clone(),
+            vote_id.clone(),
+            ballot
+        ));
+        assert_ok!(OffchainModule::cast_ballot(
+            charlie.clone(),
+            vote_id.clone(),
+            unproven
+        ));
+        assert_eq!(OffchainModule::additional_weight((&vote_id, &topic_id)), 3);
 
-        let decrypted_vote = ElGamal::decrypt_decode(&big_cipher_from_chain, &sk);
-        assert_eq!(message, decrypted_vote);
+        // aggregate and decrypt the weighted answers
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert_ok!(OffchainModule::aggregate_ciphers(
+            voting_authority.clone(),
+            vote_id.clone(),
+            topic_id.clone()
+        ));
+        let aggregate: BigCipher =
+            OffchainModule::aggregate_cipher((&vote_id, &topic_id))
+                .unwrap()
+                .into();
+        submit_decrypted_shares(
+            &bob_sk,
+            &bob_pk,
+            bob,
+            &bob_sealer_id,
+            &vote_id,
+            &topic_id,
+            vec![aggregate.clone()],
+        );
+        submit_decrypted_shares(
+            &charlie_sk,
+            &charlie_pk,
+            charlie,
+            &charlie_sealer_id,
+            &vote_id,
+            &topic_id,
+            vec![aggregate],
+        );
+        assert_ok!(OffchainModule::combine_decrypted_shares(
+            voting_authority,
+            vote_id.clone(),
+            topic_id.clone(),
+            true,
+            NR_OF_SHUFFLES
+        ));
+
+        // the total weight is 6: 2x no (0), 4x yes (1)
+        let result: TopicResult = OffchainModule::tally((&vote_id, &topic_id)).unwrap();
+        let mut expected: TopicResult = BTreeMap::new();
+        expected.insert(
+            BigUint::from(0u32).to_bytes_be(),
+            BigUint::from(2u32).to_bytes_be(),
+        );
+        expected.insert(
+            BigUint::from(1u32).to_bytes_be(),
+            BigUint::from(4u32).to_bytes_be(),
+        );
+        assert_eq!(result, expected);
     });
 }
 
 #[test]
-fn store_small_dummy_vote_works() {
+fn test_tally_question_types() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Setup Vote
-        let (params, sk, pk) = Helper::setup_sm_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
-
-        let message = BigUint::from(1u32);
-        let random = BigUint::from(7u32);
-
-        // encrypt the message -> encrypted message
-        // cipher = the crypto crate version of a ballot { a: BigUint, b: BigUint }
-        let big_cipher: BigCipher = ElGamal::encrypt(&message, &random, &pk);
-
-        // transform the ballot into a from that the blockchain can handle
-        // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
-        let cipher: Cipher = big_cipher.clone().into();
-        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher.clone())];
-        let ballot: Ballot = Ballot { answers };
-
-        // create the voter (i.e. the transaction signer)
-        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
-        let voter = Origin::signed(account);
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, _) =
+            setup_vote_in_key_generation(params.clone().into(), TallyStrategy::Mixnet);
+        let voting_authority = get_voting_authority();
 
-        let vote_submission_result = OffchainModule::cast_ballot(voter, vote_id, ballot);
-        assert_ok!(vote_submission_result);
+        // add a multi-select and a ranked question
+        let multi_select =
+            setup_question("20201212-02", QuestionType::MultiSelect { max: 2 }, 3);
+        let ranked =
+            setup_question("20201212-03", QuestionType::Ranked { candidates: 2 }, 3);
+        for topic in [&multi_select, &ranked].iter() {
+            assert_ok!(OffchainModule::store_question(
+                voting_authority.clone(),
+                vote_id.clone(),
+                (*topic).clone(),
+                2
+            ));
+        }
 
-        // fetch the submitted ballot
-        let ciphers_from_chain: Vec<Cipher> =
-            OffchainModule::ciphers(topic_id, NR_OF_SHUFFLES);
-        assert!(ciphers_from_chain.len() > 0);
+        // Distributed Key Generation: Bob & Charlie
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
+        setup_sealer(
+            &params,
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
+        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
+        setup_sealer(
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            charlie.clone(),
+            &vote_id,
+            &charlie_sealer_id,
+        );
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
+        let system_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+
+        // cast 4 ballots, the last one doesn't answer any question
+        let voter = Origin::signed(Default::default());
+        let voter_id =
+            <TestRuntime as frame_system::Trait>::AccountId::default().encode();
+        let no_answer = NO_ANSWER as u64;
+        let answers = vec![
+            (
+                Topic::encode_selection(&[0, 1]),
+                ranked.encode_ranking(&[2, 0]),
+            ),
+            (
+                Topic::encode_selection(&[1]),
+                ranked.encode_ranking(&[0, 1]),
+            ),
+            (
+                Topic::encode_selection(&[1, 2]),
+                ranked.encode_ranking(&[2, 1]),
+            ),
+            (no_answer, no_answer),
+        ];
+        for (index, (selection, ranking)) in answers.into_iter().enumerate() {
+            let r = index as u32 * 2 + 7;
+            let (selection, selection_proof) =
+                encrypt_valid_answer(&system_pk, &multi_select, selection, r, &voter_id);
+            let (ranking, ranking_proof) =
+                encrypt_valid_answer(&system_pk, &ranked, ranking, r + 1, &voter_id);
+            let ballot = Ballot {
+                answers: vec![
+                    (multi_select.id.clone(), selection),
+                    (ranked.id.clone(), ranking),
+                ],
+                proofs: Vec::new(),
+                validity_proofs: vec![selection_proof, ranking_proof],
+                credential: None,
+            };
+            assert_ok!(OffchainModule::cast_ballot(
+                voter.clone(),
+                vote_id.clone(),
+                ballot
+            ));
+        }
 
-        let cipher_from_chain: Cipher = ciphers_from_chain[0].clone();
-        assert_eq!(cipher, cipher_from_chain);
+        // decrypt and tally both questions
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        for topic in [&multi_select, &ranked].iter() {
+            let ciphers: Vec<BigCipher> =
+                Wrapper(OffchainModule::ciphers(&vote_id, &topic.id, NR_OF_SHUFFLES))
+                    .into();
+            submit_decrypted_shares(
+                &bob_sk,
+                &bob_pk,
+                bob.clone(),
+                &bob_sealer_id,
+                &vote_id,
+                &topic.id,
+                ciphers.clone(),
+            );
+            submit_decrypted_shares(
+                &charlie_sk,
+                &charlie_pk,
+                charlie.clone(),
+                &charlie_sealer_id,
+                &vote_id,
+                &topic.id,
+                ciphers,
+            );
+
+            // the answers are always decoded, even if the tally isn't requested as encoded
+            assert_ok!(OffchainModule::combine_decrypted_shares(
+                voting_authority.clone(),
+                vote_id.clone(),
+                topic.id.clone(),
+                false,
+                NR_OF_SHUFFLES
+            ));
+        }
 
-        // transform the Ballot -> BigCipher
-        let big_cipher_from_chain: BigCipher = cipher_from_chain.into();
-        assert_eq!(big_cipher, big_cipher_from_chain);
+        let count = |option: u64, count: u32| {
+            (
+                BigUint::from(option).to_bytes_be(),
+                BigUint::from(count).to_bytes_be(),
+            )
+        };
 
-        let decrypted_vote = ElGamal::decrypt(&big_cipher_from_chain, &sk);
-        assert_eq!(message, decrypted_vote);
+        // every selected candidate is counted
+        let result: TopicResult =
+            OffchainModule::tally((&vote_id, &multi_select.id)).unwrap();
+        let expected: TopicResult =
+            vec![count(0, 1), count(1, 3), count(2, 1), count(no_answer, 1)]
+                .into_iter()
+                .collect();
+        assert_eq!(result, expected);
+
+        // the first preferences are counted
+        let result: TopicResult = OffchainModule::tally((&vote_id, &ranked.id)).unwrap();
+        let expected: TopicResult = vec![count(0, 1), count(2, 2), count(no_answer, 1)]
+            .into_iter()
+            .collect();
+        assert_eq!(result, expected);
     });
 }
 
 #[test]
-fn store_real_size_vote_works_encoded() {
+fn test_set_topic_packing() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Setup
-        let (params, sk, pk) = Helper::setup_md_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
-
-        // encrypt the message -> encrypted message
-        // cipher = the crypto crate version of a ballot { a: BigUint, b: BigUint }
-        let message = BigUint::from(1u32);
-        let random =
-            BigUint::parse_bytes(b"170141183460469231731687303715884", 10).unwrap();
-        let big_cipher: BigCipher = ElGamal::encrypt_encode(&message, &random, &pk);
-
-        // transform the ballot into a from that the blockchain can handle
-        // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
-        let cipher: Cipher = big_cipher.clone().into();
-        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher.clone())];
-        let ballot: Ballot = Ballot { answers };
-
-        // create the voter (i.e. the transaction signer)
-        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
-        let voter = Origin::signed(account);
-
-        let vote_submission_result = OffchainModule::cast_ballot(voter, vote_id, ballot);
-        assert_ok!(vote_submission_result);
+        // the 47bit group of the sm system fits 11 slots of 4bits
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, topic_id) =
+            setup_vote_in_key_generation(params.into(), TallyStrategy::Mixnet);
+        let voting_authority = get_voting_authority();
+        let packing = TopicPacking {
+            slots: 3,
+            slot_bits: 4,
+        };
+        assert_ok!(OffchainModule::set_topic_packing(
+            voting_authority.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            Some(packing.clone())
+        ));
+        assert_eq!(
+            OffchainModule::packed_topic((&vote_id, &topic_id)),
+            Some(packing.clone())
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::TopicPackingUpdated(
+                vote_id.clone(),
+                topic_id.clone(),
+                Some(packing.clone())
+            ))));
 
-        // fetch the submitted ballot
-        let ciphers_from_chain: Vec<Cipher> =
-            OffchainModule::ciphers(topic_id, NR_OF_SHUFFLES);
-        assert!(ciphers_from_chain.len() > 0);
+        // the slots must fit into a single message
+        assert_err!(
+            OffchainModule::set_topic_packing(
+                voting_authority.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                Some(TopicPacking {
+                    slots: 12,
+                    slot_bits: 4
+                })
+            ),
+            Error::<TestRuntime>::InvalidTopicPacking
+        );
 
-        let cipher_from_chain: Cipher = ciphers_from_chain[0].clone();
-        assert_eq!(cipher, cipher_from_chain);
+        // the topic must be a free-form question of the vote
+        assert_err!(
+            OffchainModule::set_topic_packing(
+                voting_authority.clone(),
+                vote_id.clone(),
+                "20201212-99".as_bytes().to_vec(),
+                Some(packing.clone())
+            ),
+            Error::<TestRuntime>::TopicNotInVote
+        );
+        let question = setup_question("20201212-02", QuestionType::SingleChoice, 3);
+        assert_ok!(OffchainModule::store_question(
+            voting_authority.clone(),
+            vote_id.clone(),
+            question.clone(),
+            2
+        ));
+        assert_err!(
+            OffchainModule::set_topic_packing(
+                voting_authority.clone(),
+                vote_id.clone(),
+                question.id,
+                Some(packing.clone())
+            ),
+            Error::<TestRuntime>::InvalidTopicPacking
+        );
 
-        // transform the Ballot -> BigCipher
-        let big_cipher_from_chain: BigCipher = cipher_from_chain.into();
-        assert_eq!(big_cipher, big_cipher_from_chain);
+        // the packing can be removed
+        assert_ok!(OffchainModule::set_topic_packing(
+            voting_authority.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            None
+        ));
+        assert_eq!(OffchainModule::packed_topic((&vote_id, &topic_id)), None);
 
-        let decrypted_vote = ElGamal::decrypt_decode(&big_cipher_from_chain, &sk);
-        assert_eq!(message, decrypted_vote);
+        // but not once the voting phase started
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+        assert_err!(
+            OffchainModule::set_topic_packing(
+                voting_authority,
+                vote_id,
+                topic_id,
+                Some(packing)
+            ),
+            Error::<TestRuntime>::VoteAlreadyStarted
+        );
     });
 }
 
 #[test]
-fn store_real_size_vote_works() {
+fn test_tally_packed_answers() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Setup
-        let (params, sk, pk) = Helper::setup_md_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
-
-        // encrypt the message -> encrypted message
-        // cipher = the crypto crate version of a ballot { a: BigUint, b: BigUint }
-        let message = BigUint::from(1u32);
-        let random =
-            BigUint::parse_bytes(b"170141183460469231731687303715884", 10).unwrap();
-        let big_cipher: BigCipher = ElGamal::encrypt(&message, &random, &pk);
-
-        // transform the ballot into a from that the blockchain can handle
-        // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
-        let cipher: Cipher = big_cipher.clone().into();
-        let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher.clone())];
-        let ballot: Ballot = Ballot { answers };
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) =
+            setup_vote_in_key_generation(params.clone().into(), TallyStrategy::Mixnet);
+        let voting_authority = get_voting_authority();
+        let packing = TopicPacking {
+            slots: 3,
+            slot_bits: 2,
+        };
+        assert_ok!(OffchainModule::set_topic_packing(
+            voting_authority.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            Some(packing.clone())
+        ));
 
-        // create the voter (i.e. the transaction signer)
-        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
-        let voter = Origin::signed(account);
+        // Distributed Key Generation: Bob & Charlie
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
+        setup_sealer(
+            &params,
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
+        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
+        setup_sealer(
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            charlie.clone(),
+            &vote_id,
+            &charlie_sealer_id,
+        );
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
+        let system_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+
+        // cast 3 ballots which answer 3 yes/no questions in a single cipher
+        let voter = Origin::signed(Default::default());
+        let packed_answers: Vec<Vec<u64>> =
+            vec![vec![1, 0, 1], vec![1, 1, 0], vec![1, 0, 0]];
+        for (index, answers) in packed_answers.into_iter().enumerate() {
+            let message = ElGamal::pack(&answers, packing.slot_bits, &params).unwrap();
+            let r = BigUint::from(index as u32 * 2 + 7);
+            let cipher: Cipher = ElGamal::encrypt(&message, &r, &system_pk).into();
+            let ballot = Ballot {
+                answers: vec![(topic_id.clone(), cipher)],
+                proofs: Vec::new(),
+                validity_proofs: Vec::new(),
+                credential: None,
+            };
+            assert_ok!(OffchainModule::cast_ballot(
+                voter.clone(),
+                vote_id.clone(),
+                ballot
+            ));
+        }
 
-        let vote_submission_result = OffchainModule::cast_ballot(voter, vote_id, ballot);
-        assert_ok!(vote_submission_result);
+        // decrypt and tally the packed question
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        let ciphers: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES)).into();
+        submit_decrypted_shares(
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &bob_sealer_id,
+            &vote_id,
+            &topic_id,
+            ciphers.clone(),
+        );
+        submit_decrypted_shares(
+            &charlie_sk,
+            &charlie_pk,
+            charlie.clone(),
+            &charlie_sealer_id,
+            &vote_id,
+            &topic_id,
+            ciphers,
+        );
 
-        // fetch the submitted ballot
-        let ciphers_from_chain: Vec<Cipher> =
-            OffchainModule::ciphers(topic_id, NR_OF_SHUFFLES);
-        assert!(ciphers_from_chain.len() > 0);
+        // packed answers are never decoded, even if the tally is requested as encoded
+        assert_ok!(OffchainModule::combine_decrypted_shares(
+            voting_authority.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            true,
+            NR_OF_SHUFFLES
+        ));
 
-        let cipher_from_chain: Cipher = ciphers_from_chain[0].clone();
-        assert_eq!(cipher, cipher_from_chain);
+        let count = |answer: u64, count: u32| {
+            (
+                BigUint::from(answer).to_bytes_be(),
+                BigUint::from(count).to_bytes_be(),
+            )
+        };
 
-        // transform the Ballot -> BigCipher
-        let big_cipher_from_chain: BigCipher = cipher_from_chain.into();
-        assert_eq!(big_cipher, big_cipher_from_chain);
+        // the answers are counted per slot
+        let expected: Vec<TopicResult> = vec![
+            vec![count(1, 3)].into_iter().collect(),
+            vec![count(0, 2), count(1, 1)].into_iter().collect(),
+            vec![count(0, 2), count(1, 1)].into_iter().collect(),
+        ];
+        for (slot, expected) in expected.into_iter().enumerate() {
+            assert_eq!(
+                OffchainModule::packed_tally((&vote_id, &topic_id), slot as u32),
+                expected
+            );
+        }
 
-        let decrypted_vote = ElGamal::decrypt(&big_cipher_from_chain, &sk);
-        assert_eq!(message, decrypted_vote);
+        // all messages have been unpacked
+        let result: TopicResult = OffchainModule::tally((&vote_id, &topic_id)).unwrap();
+        assert!(result.is_empty());
     });
 }
 
 #[test]
-fn test_shuffle_ciphers_encoded() {
+fn test_tally_ranked_questions_by_borda_and_instant_runoff() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Setup
-        let (params, sk, pk) = Helper::setup_md_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
-        setup_public_key(vote_id.clone(), pk.clone().into());
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, _) =
+            setup_vote_in_key_generation(params.clone().into(), TallyStrategy::Mixnet);
+        let voting_authority = get_voting_authority();
 
-        // create the public key
-        let messages = [
-            BigUint::from(5u32),
-            BigUint::from(10u32),
-            BigUint::from(15u32),
-        ];
+        // add two ranked questions, one tallied by Borda count and one by instant-runoff
+        let borda = Topic {
+            ranked_tally: RankedTallyMethod::Borda,
+            ..setup_question("20201212-02", QuestionType::Ranked { candidates: 3 }, 3)
+        };
+        let instant_runoff = Topic {
+            ranked_tally: RankedTallyMethod::InstantRunoff,
+            ..setup_question("20201212-03", QuestionType::Ranked { candidates: 3 }, 3)
+        };
+        for topic in [&borda, &instant_runoff].iter() {
+            assert_ok!(OffchainModule::store_question(
+                voting_authority.clone(),
+                vote_id.clone(),
+                (*topic).clone(),
+                2
+            ));
+        }
 
-        // encrypt the message -> encrypted message
-        // cipher = the crypto crate version of a ballot { a: BigUint, b: BigUint }
-        let randoms = [
-            b"170141183460469231731687303715884",
-            b"170141183460469231731687303700084",
-            b"170141183400069231731687303700084",
+        // Distributed Key Generation: Bob & Charlie
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
+        setup_sealer(
+            &params,
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
+        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
+        setup_sealer(
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            charlie.clone(),
+            &vote_id,
+            &charlie_sealer_id,
+        );
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
+        let system_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+
+        // cast 6 ballots with the same ranking for both questions, the last one doesn't answer
+        let voter = Origin::signed(Default::default());
+        let voter_id =
+            <TestRuntime as frame_system::Trait>::AccountId::default().encode();
+        let no_answer = NO_ANSWER as u64;
+        let rankings: Vec<Option<Vec<u32>>> = vec![
+            Some(vec![0, 1, 2]),
+            Some(vec![0, 2, 1]),
+            Some(vec![1, 0, 2]),
+            Some(vec![2, 1, 0]),
+            Some(vec![2, 0, 1]),
+            None,
         ];
+        for (index, ranking) in rankings.into_iter().enumerate() {
+            let answer = ranking.map_or(no_answer, |r| borda.encode_ranking(&r));
+            let r = index as u32 * 2 + 7;
+            let (borda_answer, borda_proof) =
+                encrypt_valid_answer(&system_pk, &borda, answer, r, &voter_id);
+            let (runoff_answer, runoff_proof) = encrypt_valid_answer(
+                &system_pk,
+                &instant_runoff,
+                answer,
+                r + 1,
+                &voter_id,
+            );
+            let ballot = Ballot {
+                answers: vec![
+                    (borda.id.clone(), borda_answer),
+                    (instant_runoff.id.clone(), runoff_answer),
+                ],
+                proofs: Vec::new(),
+                validity_proofs: vec![borda_proof, runoff_proof],
+                credential: None,
+            };
+            assert_ok!(OffchainModule::cast_ballot(
+                voter.clone(),
+                vote_id.clone(),
+                ballot
+            ));
+        }
 
-        // create the voter (i.e. the transaction signer)
-        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
-        let mut ciphers: Vec<BigCipher> = Vec::new();
-        let voter = Origin::signed(account);
+        // decrypt and tally both questions
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        for topic in [&borda, &instant_runoff].iter() {
+            let ciphers: Vec<BigCipher> =
+                Wrapper(OffchainModule::ciphers(&vote_id, &topic.id, NR_OF_SHUFFLES))
+                    .into();
+            submit_decrypted_shares(
+                &bob_sk,
+                &bob_pk,
+                bob.clone(),
+                &bob_sealer_id,
+                &vote_id,
+                &topic.id,
+                ciphers.clone(),
+            );
+            submit_decrypted_shares(
+                &charlie_sk,
+                &charlie_pk,
+                charlie.clone(),
+                &charlie_sealer_id,
+                &vote_id,
+                &topic.id,
+                ciphers,
+            );
+            assert_ok!(OffchainModule::combine_decrypted_shares(
+                voting_authority.clone(),
+                vote_id.clone(),
+                topic.id.clone(),
+                false,
+                NR_OF_SHUFFLES
+            ));
+        }
 
-        for index in 0..3 {
-            let random = BigUint::parse_bytes(randoms[index], 10).unwrap();
+        let count = |option: u64, count: u32| {
+            (
+                BigUint::from(option).to_bytes_be(),
+                BigUint::from(count).to_bytes_be(),
+            )
+        };
 
-            // transform the ballot into a from that the blockchain can handle
-            // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
-            let cipher: BigCipher =
-                ElGamal::encrypt_encode(&messages[index], &random, &pk);
-            ciphers.push(cipher.clone());
-            let cipher: Cipher = cipher.into();
-            let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher)];
-            let ballot: Ballot = Ballot { answers };
+        // the points of every candidate
+        let result: TopicResult = OffchainModule::tally((&vote_id, &borda.id)).unwrap();
+        let expected: TopicResult =
+            vec![count(0, 6), count(1, 4), count(2, 5), count(no_answer, 1)]
+                .into_iter()
+                .collect();
+        assert_eq!(result, expected);
+        assert!(OffchainModule::tally_rounds((&vote_id, &borda.id)).is_empty());
+
+        // the votes of the final round, candidate 1 is eliminated in the first round
+        let result: TopicResult =
+            OffchainModule::tally((&vote_id, &instant_runoff.id)).unwrap();
+        let expected: TopicResult = vec![count(0, 3), count(2, 2), count(no_answer, 1)]
+            .into_iter()
+            .collect();
+        assert_eq!(result, expected);
+        let rounds = OffchainModule::tally_rounds((&vote_id, &instant_runoff.id));
+        let eliminated: Vec<Option<u32>> =
+            rounds.iter().map(|round| round.eliminated).collect();
+        assert_eq!(eliminated, vec![Some(1), None]);
+    });
+}
 
-            let vote_submission_result =
-                OffchainModule::cast_ballot(voter.clone(), vote_id.clone(), ballot);
-            assert_ok!(vote_submission_result);
-        }
+#[test]
+fn test_set_metadata() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, _) = setup_vote(params.into());
+        let metadata =
+            VoteMetadata::Inline(br#"{"title":{"en":"Budget","de":"Budget"}}"#.to_vec());
 
-        // shuffle the votes
-        let shuffle_result = OffchainModule::shuffle_ciphers(&pk, ciphers);
-        let shuffled_big_ciphers: Vec<BigCipher> = shuffle_result.unwrap().0;
-        assert!(shuffled_big_ciphers.len() == 3);
+        // only a voting authority can set the metadata
+        let (bob, _, _) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::set_metadata(bob, vote_id.clone(), Some(metadata.clone())),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
 
-        // type conversion: BigCipher (BigUint) to Ballot (Vec<u8>)
-        let shuffled_ciphers: Vec<Cipher> = Wrapper(shuffled_big_ciphers).into();
+        // the vote must exist
+        assert_err!(
+            OffchainModule::set_metadata(
+                get_voting_authority(),
+                b"20210101".to_vec(),
+                Some(metadata.clone())
+            ),
+            Error::<TestRuntime>::VoteDoesNotExist
+        );
 
-        // transform each ballot into a cipher, decrypt_decode it and finally collect the list of biguints
-        let decrypted_votes = shuffled_ciphers
-            .iter()
-            .map(|b| ElGamal::decrypt_decode(&(b.clone().into()), &sk))
-            .collect::<Vec<BigUint>>();
+        // the metadata is bounded by MaxMetadataSize
+        let too_large =
+            VoteMetadata::Inline(vec![b'x'; TestMaxMetadataSize::get() as usize + 1]);
+        assert_err!(
+            OffchainModule::set_metadata(
+                get_voting_authority(),
+                vote_id.clone(),
+                Some(too_large)
+            ),
+            Error::<TestRuntime>::MetadataTooLarge
+        );
 
-        // check that at least one value is 5, 10, 15
-        assert!(decrypted_votes
-            .iter()
-            .any(|decrypted_vote| *decrypted_vote == messages[0]));
-        assert!(decrypted_votes
-            .iter()
-            .any(|decrypted_vote| *decrypted_vote == messages[1]));
-        assert!(decrypted_votes
-            .iter()
-            .any(|decrypted_vote| *decrypted_vote == messages[2]));
+        assert_ok!(OffchainModule::set_metadata(
+            get_voting_authority(),
+            vote_id.clone(),
+            Some(metadata.clone())
+        ));
+        assert_eq!(OffchainModule::metadata(&vote_id), Some(metadata));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::MetadataUpdated(
+                vote_id.clone(),
+                OffchainModule::voting_authorities()[0].clone()
+            ))));
+
+        // the metadata can be replaced by an IPFS hash and removed
+        let ipfs = VoteMetadata::Ipfs(
+            b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec(),
+        );
+        assert_ok!(OffchainModule::set_metadata(
+            get_voting_authority(),
+            vote_id.clone(),
+            Some(ipfs.clone())
+        ));
+        assert_eq!(OffchainModule::metadata(&vote_id), Some(ipfs));
+        assert_ok!(OffchainModule::set_metadata(
+            get_voting_authority(),
+            vote_id.clone(),
+            None
+        ));
+        assert_eq!(OffchainModule::metadata(&vote_id), None);
     });
 }
 
 #[test]
-fn test_shuffle_ciphers() {
+fn test_archive_vote() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Setup
-        let (params, sk, pk) = Helper::setup_md_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
-        setup_public_key(vote_id.clone(), pk.clone().into());
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) =
+            setup_vote_with_strategy(params.clone().into(), TallyStrategy::Homomorphic);
 
-        // create the public key
-        let messages = [
-            BigUint::from(1u32),
-            BigUint::from(3u32),
-            BigUint::from(5u32),
-        ];
+        // Use 1. Sealer: Bob, 2. Sealer: Charlie
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
+        setup_sealer(
+            &params,
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
+        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
+        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
+        setup_sealer(
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            charlie.clone(),
+            &vote_id,
+            &charlie_sealer_id,
+        );
+        let voting_authority = get_voting_authority();
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
+        let system_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
 
-        // encrypt the message -> encrypted message
-        // cipher = the crypto crate version of a ballot { a: BigUint, b: BigUint }
-        let randoms = [
-            b"170141183460469231731687303715884",
-            b"170141183460469231731687303700084",
-            b"170141183400069231731687303700084",
-        ];
+        // cast 3 yes/no votes
+        let voter = Origin::signed(Default::default());
+        for (index, vote) in [1u32, 0, 1].iter().enumerate() {
+            let random = BigUint::from(index as u32 + 7);
+            let cipher: Cipher =
+                ElGamal::encrypt_encode(&BigUint::from(*vote), &random, &system_pk)
+                    .into();
+            let ballot = Ballot {
+                answers: vec![(topic_id.clone(), cipher)],
+                proofs: Vec::new(),
+                validity_proofs: Vec::new(),
+                credential: None,
+            };
+            assert_ok!(OffchainModule::cast_ballot(
+                voter.clone(),
+                vote_id.clone(),
+                ballot
+            ));
+        }
 
-        // create the voter (i.e. the transaction signer)
-        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
-        let mut ciphers: Vec<BigCipher> = Vec::new();
-        let voter = Origin::signed(account);
+        // a vote can only be archived once its results are published
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert_err!(
+            OffchainModule::archive_vote(voting_authority.clone(), vote_id.clone()),
+            Error::<TestRuntime>::VoteNotTallied
+        );
 
-        for index in 0..3 {
-            let random = BigUint::parse_bytes(randoms[index], 10).unwrap();
+        assert_ok!(OffchainModule::aggregate_ciphers(
+            voting_authority.clone(),
+            vote_id.clone(),
+            topic_id.clone()
+        ));
+        let aggregate_cipher: Cipher =
+            OffchainModule::aggregate_cipher((&vote_id, &topic_id)).unwrap();
+        let aggregate: BigCipher = aggregate_cipher.clone().into();
+        submit_decrypted_shares(
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &bob_sealer_id,
+            &vote_id,
+            &topic_id,
+            vec![aggregate.clone()],
+        );
+        submit_decrypted_shares(
+            &charlie_sk,
+            &charlie_pk,
+            charlie,
+            &charlie_sealer_id,
+            &vote_id,
+            &topic_id,
+            vec![aggregate],
+        );
+        assert_ok!(OffchainModule::combine_decrypted_shares(
+            voting_authority.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            true,
+            NR_OF_SHUFFLES
+        ));
+        let result: TopicResult = OffchainModule::tally((&vote_id, &topic_id)).unwrap();
 
-            // transform the ballot into a from that the blockchain can handle
-            // i.e. a Substrate representation { a: Vec<u8>, b: Vec<u8> }
-            let cipher: BigCipher = ElGamal::encrypt(&messages[index], &random, &pk);
-            ciphers.push(cipher.clone());
-            let cipher: Cipher = cipher.into();
-            let answers: Vec<(TopicId, Cipher)> = vec![(topic_id.clone(), cipher)];
-            let ballot: Ballot = Ballot { answers };
+        // only the voting authority can archive the vote
+        assert_err!(
+            OffchainModule::archive_vote(bob, vote_id.clone()),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
 
-            let vote_submission_result =
-                OffchainModule::cast_ballot(voter.clone(), vote_id.clone(), ballot);
-            assert_ok!(vote_submission_result);
+        // the accepted decryption proofs are logged, their transcripts can be replayed
+        let log = OffchainModule::proof_log(&vote_id);
+        let audit =
+            OffchainModule::decryption_audit((&vote_id, &topic_id), NR_OF_SHUFFLES);
+        assert_eq!(log.len(), 2);
+        for (entry, audit_entry) in log.iter().zip(audit.iter()) {
+            let shares = OffchainModule::decrypted_shares(
+                (&vote_id, &topic_id),
+                &audit_entry.sealer,
+            );
+            let transcript_hash = helpers::transcripts::hash_transcript(
+                &vote_id,
+                &topic_id,
+                NR_OF_SHUFFLES,
+                &vec![aggregate_cipher.clone()],
+                &(&shares, &audit_entry.proof),
+            );
+            assert_eq!(entry.kind, ProofKind::Decryption);
+            assert_eq!(entry.submitter, audit_entry.sealer);
+            assert_eq!(entry.transcript_hash, transcript_hash);
         }
 
-        // shuffle the votes
-        let shuffle_result = OffchainModule::shuffle_ciphers(&pk, ciphers);
-        let shuffled_big_ciphers: Vec<BigCipher> = shuffle_result.unwrap().0;
-        assert!(shuffled_big_ciphers.len() == 3);
+        // 3 ciphers, the aggregate cipher and 2 decryption audit entries
+        let leaves = helpers::archive::get_archive_leaves::<TestRuntime>(&vote_id);
+        assert_eq!(leaves.len(), 6);
+        let merkle_root = helpers::merkle::merkle_root(leaves);
+        assert_ok!(OffchainModule::archive_vote(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
 
-        // type conversion: BigCipher (BigUint) to Ballot (Vec<u8>)
-        let shuffled_ciphers: Vec<Cipher> = Wrapper(shuffled_big_ciphers).into();
+        let archive = OffchainModule::archived_vote(&vote_id).unwrap();
+        assert_eq!(archive.merkle_root, merkle_root);
+        assert_eq!(archive.nr_of_leaves, 6);
 
-        // transform each ballot into a cipher, decrypt_decode it and finally collect the list of biguints
-        let decrypted_votes = shuffled_ciphers
-            .iter()
-            .map(|b| ElGamal::decrypt(&(b.clone().into()), &sk))
-            .collect::<Vec<BigUint>>();
+        // the ciphers and proofs are pruned, the results are kept
+        assert!(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES).is_empty());
+        assert_eq!(
+            OffchainModule::aggregate_cipher((&vote_id, &topic_id)),
+            None
+        );
+        assert!(
+            OffchainModule::decryption_audit((&vote_id, &topic_id), NR_OF_SHUFFLES)
+                .is_empty()
+        );
+        assert_eq!(OffchainModule::proof_log(&vote_id), log);
+        assert!(OffchainModule::key_shares(&vote_id).is_empty());
+        assert_eq!(OffchainModule::shuffle_state((&vote_id, &topic_id)), None);
+        assert_eq!(OffchainModule::tally((&vote_id, &topic_id)), Some(result));
+        assert_eq!(
+            OffchainModule::vote_phase(&vote_id),
+            Some(VotePhase::Tallying)
+        );
 
-        // check that at least one value is 5, 10, 15
-        assert!(decrypted_votes
-            .iter()
-            .any(|decrypted_vote| *decrypted_vote == messages[0]));
-        assert!(decrypted_votes
-            .iter()
-            .any(|decrypted_vote| *decrypted_vote == messages[1]));
-        assert!(decrypted_votes
-            .iter()
-            .any(|decrypted_vote| *decrypted_vote == messages[2]));
+        // an archived vote can neither be archived again nor reopened
+        assert_err!(
+            OffchainModule::archive_vote(voting_authority.clone(), vote_id.clone()),
+            Error::<TestRuntime>::VoteAlreadyArchived
+        );
+        assert_err!(
+            OffchainModule::set_vote_phase(voting_authority, vote_id, VotePhase::Voting),
+            Error::<TestRuntime>::VoteAlreadyArchived
+        );
     });
 }
 
 #[test]
-fn test_shuffle_ciphers_no_ballots() {
+fn test_cancel_vote() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let vote_id = "20201212".as_bytes().to_vec();
-        let (_, _, pk) = Helper::setup_sm_system();
-        let ciphers: Vec<BigCipher> = Vec::new();
+        set_ballot_deposit(10);
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
         setup_public_key(vote_id.clone(), pk.clone().into());
+        let acct: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
+        Balances::make_free_balance_be(&acct, 25);
+        assert_ok!(OffchainModule::cast_ballot(
+            Origin::signed(acct),
+            vote_id.clone(),
+            create_ballot(&topic_id, &pk, 1)
+        ));
+        let reason = "the ballot contained a wrong candidate".as_bytes().to_vec();
 
-        // try -> to shuffle the ballots (which don't exist)
-        OffchainModule::shuffle_ciphers(&pk, ciphers).expect_err(
-            "The returned value should be: 'Error::<T>::ShuffleCiphersSizeZeroError'",
+        // use Alice and Bob as VotingAuthorities, both need to approve
+        let alice = get_voting_authority();
+        let (bob, bob_id, _) = get_sealer_bob();
+        assert_err!(
+            OffchainModule::cancel_vote(bob.clone(), vote_id.clone(), reason.clone()),
+            Error::<TestRuntime>::NotAVotingAuthority
         );
-    });
-}
+        VotingAuthorities::<TestRuntime>::mutate(|authorities| authorities.push(bob_id));
+        ApprovalThreshold::put(2);
 
-#[test]
-fn test_permute_vector() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let test_vec: Vec<BigUint> = vec![
-            BigUint::from(5u32),
-            BigUint::from(10u32),
-            BigUint::from(15u32),
-        ];
-        let permutation: Vec<usize> = vec![2, 0, 1];
+        // a vote is only cancelled by cancel_vote, together with a short reason
+        assert_err!(
+            OffchainModule::set_vote_phase(
+                alice.clone(),
+                vote_id.clone(),
+                VotePhase::Cancelled
+            ),
+            Error::<TestRuntime>::WrongVotePhase
+        );
+        assert_err!(
+            OffchainModule::cancel_vote(
+                alice.clone(),
+                vote_id.clone(),
+                vec![0u8; MAX_CANCELLATION_REASON_LENGTH + 1]
+            ),
+            Error::<TestRuntime>::CancellationReasonTooLong
+        );
 
-        let result = OffchainModule::permute_vector(test_vec.clone(), &permutation);
-        assert_eq!(result[0], test_vec[2]);
-        assert_eq!(result[1], test_vec[0]);
-        assert_eq!(result[2], test_vec[1]);
-    });
-}
+        assert_ok!(OffchainModule::cancel_vote(
+            alice.clone(),
+            vote_id.clone(),
+            reason.clone()
+        ));
+        assert_eq!(OffchainModule::votes(&vote_id).phase, VotePhase::Voting);
+        assert_err!(
+            OffchainModule::cancel_vote(alice.clone(), vote_id.clone(), reason.clone()),
+            Error::<TestRuntime>::AlreadyApproved
+        );
+        assert_ok!(OffchainModule::cancel_vote(
+            bob,
+            vote_id.clone(),
+            reason.clone()
+        ));
+        assert_eq!(OffchainModule::votes(&vote_id).phase, VotePhase::Cancelled);
+        assert!(OffchainModule::cancellation_approvals(&vote_id).is_empty());
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::VoteCancelled(
+                vote_id.clone(),
+                reason.clone()
+            ))));
 
-#[test]
-fn test_shuffle_proof_small_system_encoded() {
-    // good primes to use for testing
-    // p: 202178360940839 -> q: 101089180470419
-    // p: 4283 -> q: 2141
-    // p: 59 -> q: 29
-    // p: 47 -> q: 23
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let (params, _, pk) = Helper::setup_sm_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
-        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
-        assert!(is_p_prime);
-        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
-        assert!(is_q_prime);
+        // neither ballots nor the tally are accepted anymore
+        assert_err!(
+            OffchainModule::cast_ballot(
+                Origin::signed(acct),
+                vote_id.clone(),
+                create_ballot(&topic_id, &pk, 2)
+            ),
+            Error::<TestRuntime>::WrongVotePhase
+        );
+        assert_err!(
+            OffchainModule::set_vote_phase(
+                alice.clone(),
+                vote_id.clone(),
+                VotePhase::Tallying
+            ),
+            Error::<TestRuntime>::VoteCancelled
+        );
+        assert_err!(
+            OffchainModule::cancel_vote(alice.clone(), vote_id.clone(), reason),
+            Error::<TestRuntime>::VoteCancelled
+        );
 
-        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, true);
-        assert!(is_proof_valid);
+        // the ballot deposits are refunded and the vote can be archived
+        assert_ok!(OffchainModule::claim_ballot_deposit(
+            Origin::signed(acct),
+            vote_id.clone()
+        ));
+        assert_eq!(Balances::free_balance(&acct), 25);
+        assert_ok!(OffchainModule::archive_vote(alice, vote_id.clone()));
+        assert!(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES).is_empty());
+        assert_eq!(
+            OffchainModule::archived_vote(&vote_id)
+                .unwrap()
+                .nr_of_leaves,
+            1
+        );
+        assert_eq!(
+            OffchainModule::vote_phase(&vote_id),
+            Some(VotePhase::Cancelled)
+        );
     });
 }
 
-#[test]
-fn test_shuffle_proof_small_system() {
-    // good primes to use for testing
-    // p: 202178360940839 -> q: 101089180470419
-    // p: 4283 -> q: 2141
-    // p: 59 -> q: 29
-    // p: 47 -> q: 23
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let (params, _, pk) = Helper::setup_sm_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
-        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
-        assert!(is_p_prime);
-        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
-        assert!(is_q_prime);
-
-        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, false);
-        assert!(is_proof_valid);
-    });
+fn create_key_switch_shares(
+    sk: &PrivateKey,
+    pk: &ElGamalPK,
+    new_pk: &ElGamalPK,
+    sealer_id: &[u8],
+    encryptions: Vec<BigCipher>,
+) -> (Vec<Cipher>, KeySwitchShareProof) {
+    let q = &pk.params.q();
+    let randoms: Vec<BigUint> = (0..encryptions.len())
+        .map(|i| BigUint::from(1234567u32 + i as u32) % q)
+        .collect();
+    let vec_r: Vec<BigUint> = (0..encryptions.len())
+        .map(|i| BigUint::from(7654321u32 + i as u32) % q)
+        .collect();
+    let shares: Vec<BigCipher> = encryptions
+        .iter()
+        .zip(randoms.iter())
+        .map(|(cipher, s)| ElGamal::key_switch_share(cipher, sk, s, new_pk))
+        .collect();
+
+    let r = BigUint::parse_bytes(b"1234123123", 10).unwrap();
+    let proof = KeySwitchProof::generate(
+        &pk.params,
+        &sk.x,
+        &pk.h,
+        &new_pk.h,
+        &randoms,
+        &r,
+        &vec_r,
+        encryptions,
+        shares.clone(),
+        sealer_id,
+    );
+    (Wrapper(shares).into(), proof.into())
 }
 
 #[test]
-fn test_shuffle_proof_tiny_system_encoded() {
+fn test_rotate_public_key_and_switch_ciphers() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let (params, _, pk) = Helper::setup_tiny_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
-        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
-        assert!(is_p_prime);
-        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
-        assert!(is_q_prime);
+        // Distributed Key Generation Setup
+        let (params, _, _) = Helper::setup_md_system();
+        let (vote_id, topic_id) = setup_vote(params.clone().into());
+        let voting_authority = get_voting_authority();
+        let (bob, _, bob_sealer_id) = get_sealer_bob();
+        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
 
-        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, true);
-        assert!(is_proof_valid);
-    });
-}
+        // the old key: Bob + Charlie
+        let (bob_pk, bob_sk) =
+            Helper::generate_key_pair(&params, &BigUint::from(12345678u32));
+        setup_sealer(
+            &params,
+            &bob_sk,
+            &bob_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
+        let (charlie_pk, charlie_sk) =
+            Helper::generate_key_pair(&params, &BigUint::from(87654321u32));
+        setup_sealer(
+            &params,
+            &charlie_sk,
+            &charlie_pk,
+            charlie.clone(),
+            &vote_id,
+            &charlie_sealer_id,
+        );
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
+        let old_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
+        setup_ciphers(&vote_id, &topic_id, &old_pk, true);
+        let encryptions: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES)).into();
 
-#[test]
-fn test_shuffle_proof_tiny_system() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let (params, _, pk) = Helper::setup_tiny_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
-        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
-        assert!(is_p_prime);
-        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
-        assert!(is_q_prime);
+        // rotate the public key -> a new key generation round starts
+        assert_ok!(OffchainModule::rotate_public_key(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
+        assert_eq!(
+            OffchainModule::vote_phase(&vote_id),
+            Some(VotePhase::KeyGeneration)
+        );
+        assert_eq!(OffchainModule::public_key(vote_id.clone()), None);
 
-        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, false);
-        assert!(is_proof_valid);
-    });
-}
+        // the vote has already started, its topics remain frozen
+        let topic: Topic = Topic::new(
+            "20201212-02".as_bytes().to_vec(),
+            "Moritz for King?".as_bytes().to_vec(),
+        );
+        assert_err!(
+            OffchainModule::store_question(
+                voting_authority.clone(),
+                vote_id.clone(),
+                topic,
+                2
+            ),
+            Error::<TestRuntime>::VoteAlreadyStarted
+        );
+        assert_eq!(
+            OffchainModule::pending_key_switch(&vote_id),
+            vec![topic_id.clone()]
+        );
 
-#[test]
-#[ignore = "will take over 30s to complete, run only when necessary"]
-fn test_shuffle_proof_medium_system() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let (params, _, pk) = Helper::setup_md_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
-        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
-        assert!(is_p_prime);
-        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
-        assert!(is_q_prime);
+        // the new key: Bob + Charlie with new key shares
+        let (bob_new_pk, bob_new_sk) =
+            Helper::generate_key_pair(&params, &BigUint::from(11223344u32));
+        setup_sealer(
+            &params,
+            &bob_new_sk,
+            &bob_new_pk,
+            bob.clone(),
+            &vote_id,
+            &bob_sealer_id,
+        );
+        let (charlie_new_pk, charlie_new_sk) =
+            Helper::generate_key_pair(&params, &BigUint::from(44332211u32));
+        setup_sealer(
+            &params,
+            &charlie_new_sk,
+            &charlie_new_pk,
+            charlie.clone(),
+            &vote_id,
+            &charlie_sealer_id,
+        );
+        assert_ok!(OffchainModule::combine_public_key_shares(
+            voting_authority.clone(),
+            vote_id.clone()
+        ));
+        let new_pk: ElGamalPK =
+            OffchainModule::public_key(vote_id.clone()).unwrap().into();
+        assert!(new_pk != old_pk);
+
+        // no ballots can be cast until the ciphers are switched to the new key
+        let voter = Origin::signed(Default::default());
+        let cipher: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(1u32), &BigUint::from(5u32), &new_pk)
+                .into();
+        let ballot = Ballot {
+            answers: vec![(topic_id.clone(), cipher)],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_err!(
+            OffchainModule::cast_ballot(voter, vote_id.clone(), ballot),
+            Error::<TestRuntime>::KeyRotationPending
+        );
 
-        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, false);
-        assert!(is_proof_valid);
-    });
-}
+        // a proof of another sealer is rejected
+        let (bob_shares, bob_proof) = create_key_switch_shares(
+            &bob_sk,
+            &bob_pk,
+            &new_pk,
+            &bob_sealer_id,
+            encryptions.clone(),
+        );
+        let (charlie_shares, charlie_proof) = create_key_switch_shares(
+            &charlie_sk,
+            &charlie_pk,
+            &new_pk,
+            &charlie_sealer_id,
+            encryptions.clone(),
+        );
+        assert_err!(
+            OffchainModule::submit_key_switch_shares(
+                bob.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                bob_shares.clone(),
+                charlie_proof.clone()
+            ),
+            Error::<TestRuntime>::KeySwitchShareProofError
+        );
 
-#[test]
-#[ignore = "will take over 30s to complete, run only when necessary"]
-fn test_shuffle_proof_large_system() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let (params, _, pk) = Helper::setup_lg_system();
-        let (vote_id, topic_id) = setup_vote(params.into());
-        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
-        assert!(is_p_prime);
-        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
-        assert!(is_q_prime);
+        // the ciphers are only switched once all sealers have submitted their shares
+        assert_ok!(OffchainModule::submit_key_switch_shares(
+            bob.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            bob_shares,
+            bob_proof
+        ));
+        let unchanged: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES)).into();
+        assert_eq!(unchanged, encryptions);
+        assert_ok!(OffchainModule::submit_key_switch_shares(
+            charlie,
+            vote_id.clone(),
+            topic_id.clone(),
+            charlie_shares,
+            charlie_proof
+        ));
+        assert!(OffchainModule::pending_key_switch(&vote_id).is_empty());
+        assert_eq!(
+            OffchainModule::key_switch_audit((&vote_id, &topic_id)).len(),
+            2
+        );
 
-        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, false);
-        assert!(is_proof_valid);
+        // the switched ciphers can be decrypted with the new key
+        let q = &params.q();
+        let new_sk_x = BigUint::from(11223344u32).modadd(&BigUint::from(44332211u32), q);
+        let (_, new_sk) = Helper::generate_key_pair(&params, &new_sk_x);
+        let switched: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, NR_OF_SHUFFLES)).into();
+        let messages: Vec<BigUint> = switched
+            .iter()
+            .map(|cipher| ElGamal::decrypt_decode(cipher, &new_sk))
+            .collect();
+        let expected: Vec<BigUint> = vec![1u32, 3, 4, 1, 3, 4]
+            .into_iter()
+            .map(BigUint::from)
+            .collect();
+        assert_eq!(messages, expected);
     });
 }
 
 #[test]
-#[ignore = "will take over 60s to complete, run only when necessary"]
-fn test_shuffle_proof_xl_system() {
-    let (mut t, _, _) = ExternalityBuilder::build();
+fn test_offchain_shuffling() {
+    let (mut t, pool_state, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let (params, _, pk) = Helper::setup_xl_system();
+        // Setup
+        let (params, _, pk) = Helper::setup_sm_system();
         let (vote_id, topic_id) = setup_vote(params.into());
-        let is_p_prime = OffchainModule::is_prime(&pk.params.p, 10).unwrap();
-        assert!(is_p_prime);
-        let is_q_prime = OffchainModule::is_prime(&pk.params.q(), 10).unwrap();
-        assert!(is_q_prime);
-
-        let is_proof_valid = shuffle_proof_test(vote_id, topic_id, pk, false);
-        assert!(is_proof_valid);
-    });
-}
+        let encoded: bool = false;
+        let block_number: <TestRuntime as frame_system::Trait>::BlockNumber =
+            (1u32).into();
 
-#[test]
-fn test_set_vote_phase_not_a_voting_authority() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let (_, _, pk) = Helper::setup_sm_system();
+        // store created public key and public parameters
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, encoded);
 
-        // create fake vote_id
-        let vote_id = "20201212".as_bytes().to_vec();
+        // change the VotePhase to Voting using the voting authority
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
 
-        // Setup Public Key
-        setup_public_key(vote_id.clone(), pk.clone().into());
+        // Test
+        let result = OffchainModule::offchain_shuffling(block_number);
+        assert_ok!(result);
 
-        // use a normal user (i.e. the default voter)
-        // NOT a voting authority
-        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
-        let who = Origin::signed(account);
+        // Verify
+        let tx = pool_state.write().transactions.pop().unwrap();
+        assert!(pool_state.read().transactions.is_empty());
+        let tx = TestExtrinsic::decode(&mut &*tx).unwrap();
+        assert_eq!(tx.signature.unwrap().0, 0);
 
-        // try to change the vote phase
-        assert_err!(
-            OffchainModule::set_vote_phase(who, vote_id, VotePhase::Voting),
-            Error::<TestRuntime>::NotAVotingAuthority
-        )
+        // TODO: find a way to compare Call signature without having to provide values
+        // assert_eq!(tx.call, Call::submit_shuffled_votes_and_proof);
     });
 }
 
 #[test]
-fn test_set_vote_phase_vote_does_not_exist() {
-    let (mut t, _, _) = ExternalityBuilder::build();
+fn test_offchain_decryption() {
+    let (mut t, pool_state, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let (_, _, pk) = Helper::setup_sm_system();
-
-        // create fake vote_id
-        let vote_id = "20201212".as_bytes().to_vec();
-
-        // Setup Public Key
+        // Setup: the key of the offchain worker is a sealer of the vote
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, topic_id) =
+            setup_vote_with_strategy(params.clone().into(), TallyStrategy::Homomorphic);
+        let sealer = sp_io::crypto::sr25519_public_keys(keys::KEY_TYPE)[0];
+        SealerSets::<TestRuntime>::mutate(0, |sealers| sealers.push(sealer));
+        let sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
+        let (pk, sk) = Helper::generate_key_pair(&params, &sk_x);
+        setup_sealer(
+            &params,
+            &sk,
+            &pk,
+            Origin::signed(sealer),
+            &vote_id,
+            &sealer.encode(),
+        );
         setup_public_key(vote_id.clone(), pk.clone().into());
 
-        // create the submitter (i.e. the voting_authority)
-        // use Alice as VotingAuthority
-        let who = get_voting_authority();
-
-        // try to change the vote phase
-        assert_err!(
-            OffchainModule::set_vote_phase(who, vote_id, VotePhase::Voting),
-            Error::<TestRuntime>::VoteDoesNotExist
-        )
-    });
-}
-
-#[test]
-fn test_set_vote_phase() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        let (params, _, pk) = Helper::setup_sm_system();
+        // cast a yes vote - ENCODED
+        let cipher: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(1u32), &BigUint::from(7u32), &pk)
+                .into();
+        let ballot = Ballot {
+            answers: vec![(topic_id.clone(), cipher)],
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: None,
+        };
+        assert_ok!(OffchainModule::cast_ballot(
+            Origin::signed(Default::default()),
+            vote_id.clone(),
+            ballot
+        ));
 
-        // Setup Vote
-        let (vote_id, _) = setup_vote(params.into());
+        // the aggregate cipher is ready to be decrypted
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert_ok!(OffchainModule::aggregate_ciphers(
+            get_voting_authority(),
+            vote_id.clone(),
+            topic_id.clone()
+        ));
+        let block_number: <TestRuntime as frame_system::Trait>::BlockNumber =
+            (1u32).into();
 
-        // Setup Public Key
-        setup_public_key(vote_id.clone(), pk.clone().into());
+        // the private key share is not configured -> nothing is decrypted
+        assert_ok!(OffchainModule::offchain_shuffling(block_number));
+        assert!(jobs::get_job_queue().is_empty());
+        assert!(pool_state.read().transactions.is_empty());
 
-        // create the submitter (i.e. the voting_authority)
-        // use Alice as VotingAuthority
-        let who = get_voting_authority();
+        // configure the private key share (hex encoded)
+        let key = [decrypt::SEALER_SK_PREFIX, &vote_id].concat();
+        sp_io::offchain::local_storage_set(
+            StorageKind::PERSISTENT,
+            &key,
+            sk_x.to_str_radix(16).as_bytes(),
+        );
 
-        // change the VotePhase to Voting
-        assert_ok!(OffchainModule::set_vote_phase(
-            who.clone(),
-            vote_id.clone(),
-            VotePhase::Voting
+        // the decrypted shares are submitted as an unsigned transaction
+        assert_ok!(OffchainModule::offchain_shuffling(block_number));
+        let tx = pool_state.write().transactions.pop().unwrap();
+        assert!(pool_state.read().transactions.is_empty());
+        let tx = TestExtrinsic::decode(&mut &*tx).unwrap();
+        assert_eq!(tx.signature, None);
+        let (submission, signature) = match tx.call {
+            Call::submit_decrypted_shares_unsigned(submission, signature) => {
+                (submission, signature)
+            }
+            _ => panic!("expected an unsigned submission of decrypted shares"),
+        };
+        assert_eq!(submission.public, sealer);
+        assert!(jobs::get_job_queue().is_empty());
+
+        // the proof of the decrypted shares is valid
+        assert_ok!(OffchainModule::submit_decrypted_shares_unsigned(
+            Origin::none(),
+            submission,
+            signature
         ));
         assert_eq!(
-            OffchainModule::votes(vote_id.clone()).phase,
-            VotePhase::Voting
+            OffchainModule::decrypted_shares((&vote_id, &topic_id), &sealer).len(),
+            1
         );
 
-        // change the VotePhase to Tallying
-        assert_ok!(OffchainModule::set_vote_phase(
-            who,
-            vote_id.clone(),
-            VotePhase::Tallying
-        ));
-        assert_eq!(OffchainModule::votes(vote_id).phase, VotePhase::Tallying);
+        // the topic is not decrypted again, the private key share has been removed
+        assert_ok!(OffchainModule::offchain_shuffling(block_number));
+        assert!(pool_state.read().transactions.is_empty());
+        assert_eq!(
+            sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &key),
+            Some(Vec::new())
+        );
     });
 }
 
 #[test]
-fn test_store_public_key_share_fail_is_voting_authority() {
+fn test_offchain_sealer_sk_removed_once_vote_cancelled() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Setup
-        let (params, sk, pk) = Helper::setup_md_system();
-        let (vote_id, _) = setup_vote(params.clone().into());
-
-        // create the submitter (i.e. the voting_authority)
-        // use Alice as VotingAuthority
-        let who = get_voting_authority();
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, _) = setup_vote(params.into());
+        let key = [decrypt::SEALER_SK_PREFIX, &vote_id].concat();
+        sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, &key, b"3039");
+        let block_number: <TestRuntime as frame_system::Trait>::BlockNumber =
+            (1u32).into();
 
-        // create public key share + proof
-        let sealer_id = "Bob".as_bytes();
-        let r = BigUint::parse_bytes(b"170141183460469231731687303715884", 10).unwrap();
-        let proof = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &r, sealer_id);
-        let pk_share = PublicKeyShare {
-            proof: proof.into(),
-            pk: pk.h.to_bytes_be(),
-        };
+        // the private key share is kept until the vote has been decrypted
+        assert_ok!(OffchainModule::offchain_shuffling(block_number));
+        assert_eq!(
+            sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &key),
+            Some(b"3039".to_vec())
+        );
 
-        // submit the public key share
-        assert_err!(
-            OffchainModule::store_public_key_share(who, vote_id, pk_share.into()),
-            Error::<TestRuntime>::IsVotingAuthority
-        )
+        // a cancelled vote is never decrypted
+        Votes::<TestRuntime>::mutate(&vote_id, |vote| vote.phase = VotePhase::Cancelled);
+        assert_ok!(OffchainModule::offchain_shuffling(block_number));
+        assert_eq!(
+            sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &key),
+            Some(Vec::new())
+        );
     });
 }
 
 #[test]
-fn test_store_public_key_share_fail_no_sealers() {
+fn test_offchain_histogram() {
+    let mut histogram = OffchainHistogram::default();
+    histogram.observe(0);
+    histogram.observe(10);
+    histogram.observe(11);
+    histogram.observe(120_000);
+    assert_eq!(histogram.buckets, vec![2, 1, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(histogram.sum, 120_021);
+    assert_eq!(histogram.count, 4);
+}
+
+#[test]
+fn test_offchain_metrics() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Setup
-        let (params, sk, pk) = Helper::setup_md_system();
-        let (vote_id, _) = setup_vote(params.clone().into());
-
-        // use a normal user (i.e. the default voter)
-        // NOT a voting authority
-        let account: <TestRuntime as frame_system::Trait>::AccountId = Default::default();
-        let who = Origin::signed(account);
-        let sealer_id = "Bob".as_bytes();
-
-        // create public key share + proof
-        let r = BigUint::parse_bytes(b"170141183460469231731687303715884", 10).unwrap();
-        let proof = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &r, sealer_id);
-        let pk_share = PublicKeyShare {
-            proof: proof.into(),
-            pk: pk.h.to_bytes_be(),
+        assert_eq!(metrics::get_metrics(), OffchainMetrics::default());
+
+        metrics::record_shuffle(4, 20, 600);
+        metrics::record_shuffle(2, 5, 700);
+        metrics::record_commitment();
+        metrics::record_reveal();
+        metrics::record_failed_submission();
+
+        let recorded = metrics::get_metrics();
+        assert_eq!(recorded.ciphers_shuffled, 6);
+        assert_eq!(recorded.shuffles_committed, 1);
+        assert_eq!(recorded.shuffles_revealed, 1);
+        assert_eq!(recorded.submissions_failed, 1);
+        assert_eq!(recorded.shuffle_ms.count, 2);
+        assert_eq!(recorded.shuffle_ms.sum, 25);
+        assert_eq!(recorded.proof_generation_ms.buckets[4], 2);
+
+        // a job which is given up on is counted
+        let job = OffchainJob::Shuffle {
+            vote_id: "20201212".as_bytes().to_vec(),
+            topic_id: "20201212-01".as_bytes().to_vec(),
+            iteration: 0,
+            start_position: 0,
         };
-
-        // submit the public key share
-        assert_err!(
-            OffchainModule::store_public_key_share(who, vote_id, pk_share.into()),
-            Error::<TestRuntime>::NotASealer
-        )
+        jobs::sync_jobs(vec![job.clone()]);
+        for block in 0..jobs::MAX_ATTEMPTS {
+            jobs::fail_job(&job, block as u64);
+        }
+        assert_eq!(metrics::get_metrics().jobs_dead_lettered, 1);
     });
 }
 
 #[test]
-fn test_store_public_key_share() {
+fn test_offchain_job_queue_retry_and_dead_letter() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Setup
-        let (params, sk, pk) = Helper::setup_md_system();
-        let (vote_id, _) = setup_vote(params.clone().into());
-
-        // use sealer bob
-        let (who, account_id, sealer_id) = get_sealer_bob();
-        let (_, proof) = setup_sealer(&params, &sk, &pk, who, &vote_id, &sealer_id);
+        let job = OffchainJob::Shuffle {
+            vote_id: "20201212".as_bytes().to_vec(),
+            topic_id: "20201212-01".as_bytes().to_vec(),
+            iteration: 0,
+            start_position: 0,
+        };
 
-        // verify the public key share submission + proof verification
-        let shares: Vec<PublicKeyShare> = OffchainModule::key_shares(vote_id.clone());
-        assert_eq!(shares[0].pk, pk.h.to_bytes_be());
-        assert_eq!(shares[0].proof.challenge, proof.challenge.to_bytes_be());
-        assert_eq!(shares[0].proof.response, proof.response.to_bytes_be());
+        // the job is queued and due immediately
+        jobs::sync_jobs(vec![job.clone(), job.clone()]);
+        assert_eq!(jobs::get_job_queue().len(), 1);
+        assert_eq!(jobs::get_due_jobs(1), vec![job.clone()]);
 
-        let share_by_sealer: PublicKeyShare =
-            OffchainModule::key_share_by_sealer((vote_id, account_id)).unwrap();
-        assert_eq!(share_by_sealer.pk, pk.h.to_bytes_be());
-        assert_eq!(
-            share_by_sealer.proof.challenge,
-            proof.challenge.to_bytes_be()
-        );
-        assert_eq!(share_by_sealer.proof.response, proof.response.to_bytes_be());
-    });
-}
+        // a failed attempt delays the job (backoff: 2 blocks)
+        jobs::fail_job(&job, 1);
+        assert!(jobs::get_due_jobs(2).is_empty());
+        assert_eq!(jobs::get_due_jobs(3), vec![job.clone()]);
 
-#[test]
-fn test_combine_public_key_shares_not_voting_authority() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        // create fake vote_id
-        let vote_id = "20201212".as_bytes().to_vec();
+        // the retry state is kept when syncing the same job again
+        jobs::sync_jobs(vec![job.clone()]);
+        assert_eq!(jobs::get_job_queue()[0].attempts, 1);
 
-        // Use sealer instead of voting authority
-        let (bob, _, _) = get_sealer_bob();
-        assert_err!(
-            OffchainModule::combine_public_key_shares(bob, vote_id),
-            Error::<TestRuntime>::NotAVotingAuthority
-        );
+        // after MAX_ATTEMPTS failed attempts the job is moved to the dead-letter log
+        for block in 0..(jobs::MAX_ATTEMPTS - 1) {
+            jobs::fail_job(&job, block as u64);
+        }
+        assert!(jobs::get_job_queue().is_empty());
+        let dead_letters = jobs::get_dead_letters();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].job, job);
+        assert_eq!(dead_letters[0].attempts, jobs::MAX_ATTEMPTS);
+
+        // dead jobs are not queued again
+        jobs::sync_jobs(vec![job.clone()]);
+        assert!(jobs::get_job_queue().is_empty());
+
+        // jobs which are no longer derived from the on-chain state are dropped
+        let next_job = OffchainJob::Shuffle {
+            vote_id: "20201212".as_bytes().to_vec(),
+            topic_id: "20201212-02".as_bytes().to_vec(),
+            iteration: 1,
+            start_position: 0,
+        };
+        jobs::sync_jobs(vec![next_job.clone()]);
+        jobs::sync_jobs(vec![]);
+        assert!(jobs::get_job_queue().is_empty());
     });
 }
 
 #[test]
-fn test_combine_public_key_shares_vote_does_not_exist() {
-    let (mut t, _, _) = ExternalityBuilder::build();
-    t.execute_with(|| {
-        // create fake vote_id
-        let vote_id = "20201212".as_bytes().to_vec();
+fn test_offchain_results_json() {
+    let vote_id = "20201212".as_bytes().to_vec();
+    let topic_id = "20201212-01".as_bytes().to_vec();
+    let mut result: TopicResult = BTreeMap::new();
+    result.insert(
+        BigUint::from(1u32).to_bytes_be(),
+        BigUint::from(3u32).to_bytes_be(),
+    );
+    result.insert(
+        BigUint::from(0u32).to_bytes_be(),
+        BigUint::from(300u32).to_bytes_be(),
+    );
 
-        // use authority but vote doesn't exist
-        let who = get_voting_authority();
-        assert_err!(
-            OffchainModule::combine_public_key_shares(who, vote_id),
-            Error::<TestRuntime>::VoteDoesNotExist
-        );
-    });
+    let json = results::results_json(&vote_id, &topic_id, &result);
+    assert_eq!(
+        std::str::from_utf8(&json).unwrap(),
+        "{\"vote_id\":\"20201212\",\"topic_id\":\"20201212-01\",\"result\":[{\"vote\":\"0\",\"count\":\"300\"},{\"vote\":\"1\",\"count\":\"3\"}]}"
+    );
+
+    // identifiers are escaped, identifiers which are not utf-8 are hex encoded
+    let json =
+        results::results_json(&"a\"b".as_bytes().to_vec(), &vec![0xff], &BTreeMap::new());
+    assert_eq!(
+        std::str::from_utf8(&json).unwrap(),
+        "{\"vote_id\":\"a\\\"b\",\"topic_id\":\"0xff\",\"result\":[]}"
+    );
 }
 
 #[test]
-fn test_combine_public_key_shares() {
+fn test_offchain_post_results_without_url() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
         // Setup
-        let (params, sk, pk) = Helper::setup_md_system();
-        let (vote_id, _) = setup_vote(params.clone().into());
-
-        // Use 1. Sealer: Bob
-        let (bob, _, bob_sealer_id) = get_sealer_bob();
-        let (bob_key, _) = setup_sealer(&params, &sk, &pk, bob, &vote_id, &bob_sealer_id);
-
-        // Use 2. Sealer: Charlie
-        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
-        let (charlie_key, _) =
-            setup_sealer(&params, &sk, &pk, charlie, &vote_id, &charlie_sealer_id);
-
-        // combine the public key shares
-        let voting_authority = get_voting_authority();
-        assert_ok!(OffchainModule::combine_public_key_shares(
-            voting_authority,
-            vote_id.clone()
-        ));
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let mut result: TopicResult = BTreeMap::new();
+        result.insert(
+            BigUint::from(1u32).to_bytes_be(),
+            BigUint::from(1u32).to_bytes_be(),
+        );
+        Tally::insert((&vote_id, &topic_id), result);
 
-        // VERIFY
-        // fetch the public key from the chain
-        let pk = ElGamalPK {
-            h: BigUint::from_bytes_be(&bob_key.pk)
-                .modmul(&BigUint::from_bytes_be(&charlie_key.pk), &params.p),
-            params: params.clone(),
-        };
-        let pk_from_chain: ElGamalPK =
-            OffchainModule::public_key(vote_id).unwrap().into();
-        assert_eq!(pk_from_chain, pk);
+        // no results endpoint is configured -> nothing is posted
+        assert_eq!(results::get_results_url(), None);
+        assert_ok!(OffchainModule::offchain_post_results());
+        assert!(results::get_posted_results().is_empty());
     });
 }
 
 #[test]
-fn test_submit_decrypted_share_vote_does_not_exist() {
+fn test_offchain_checkpoint_of_the_election_state() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
         // Setup
-        let (_, _, pk) = Helper::setup_sm_system();
-
-        // create fake everything
-        let vote_id = "20201212".as_bytes().to_vec();
-        let topic_id = "vote1".as_bytes().to_vec();
-        let shares: Vec<Vec<u8>> = Vec::new();
-        let proof = DecryptedShareProof {
-            challenge: Vec::new(),
-            response: Vec::new(),
-        };
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
 
-        // Setup Public Key
-        setup_public_key(vote_id.clone(), pk.clone().into());
+        let signed = OffchainModule::create_checkpoint(5).unwrap();
+        let decoded = SignedCheckpoint::from_cbor(&signed.to_cbor()).unwrap();
+        assert_eq!(decoded, signed);
 
-        // create the submitter (i.e. the voting_authority)
-        // use Alice as VotingAuthority
-        let who = get_voting_authority();
+        // the checkpoint is signed by the key of the offchain worker
+        let public = sr25519::Public::decode(&mut &signed.signer[..]).unwrap();
+        let signature = sr25519::Signature::decode(&mut &signed.signature[..]).unwrap();
+        assert_eq!(
+            public,
+            sp_io::crypto::sr25519_public_keys(keys::KEY_TYPE)[0]
+        );
+        assert!(sp_io::crypto::sr25519_verify(
+            &signature,
+            &signed.message(),
+            &public
+        ));
 
-        assert_err!(
-            OffchainModule::submit_decrypted_shares(
-                who,
-                vote_id,
-                topic_id,
-                shares,
-                proof,
-                NR_OF_SHUFFLES
-            ),
-            Error::<TestRuntime>::VoteDoesNotExist
+        // the checkpoint contains the raw storage of the pallet, ordered by key
+        let checkpoint = decoded.decode_checkpoint().unwrap();
+        assert_eq!(checkpoint.block_number, 5);
+        assert_eq!(checkpoint.parent_hash, System::parent_hash().encode());
+        assert!(checkpoint
+            .entries
+            .windows(2)
+            .all(|pair| pair[0].0 < pair[1].0));
+        let get_entry = |key: Vec<u8>| {
+            checkpoint
+                .entries
+                .iter()
+                .find(|(entry_key, _)| entry_key == &key)
+                .map(|(_, value)| value.clone())
+        };
+        assert_eq!(
+            get_entry(Votes::<TestRuntime>::hashed_key_for(&vote_id)),
+            Some(Votes::<TestRuntime>::get(&vote_id).encode())
+        );
+        assert_eq!(
+            get_entry(Topics::hashed_key_for(&vote_id)),
+            Some(Topics::get(&vote_id).encode())
         );
+        assert!(get_entry(Tally::hashed_key_for((&vote_id, &topic_id))).is_none());
+
+        // the storage of the other pallets isn't part of the checkpoint
+        let prefix = sp_io::hashing::twox_128(STORAGE_MODULE_PREFIX);
+        assert!(checkpoint
+            .entries
+            .iter()
+            .all(|(key, _)| key.starts_with(&prefix)));
     });
 }
 
 #[test]
-fn test_submit_decrypted_share_wrong_vote_phase() {
+fn test_offchain_job_selection_interleaves_votes() {
+    let job = |vote: &str| OffchainJob::Shuffle {
+        vote_id: vote.as_bytes().to_vec(),
+        topic_id: "20201212-01".as_bytes().to_vec(),
+        iteration: 0,
+        start_position: 0,
+    };
+    let due_jobs = vec![job("vote-1"), job("vote-2")];
+    let nr_of_sealers = 2;
+
+    // both sealers work on the same job, before the next job is selected
+    let selected = (0..8u64)
+        .map(|block| jobs::select_job(due_jobs.clone(), block, nr_of_sealers).unwrap())
+        .collect::<Vec<OffchainJob>>();
+    assert_eq!(selected[0], job("vote-1"));
+    assert_eq!(selected[1], job("vote-1"));
+    assert_eq!(selected[2], job("vote-2"));
+    assert_eq!(selected[3], job("vote-2"));
+    assert_eq!(selected[4], job("vote-1"));
+    assert_eq!(selected[7], job("vote-2"));
+
+    // no job is selected if there is nothing to do
+    assert_eq!(jobs::select_job(vec![], 1, nr_of_sealers), None);
+}
+
+#[test]
+fn test_offchain_adaptive_batch_size() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // setup public key
-        let (params, _, pk) = Helper::setup_sm_system();
+        // the configured batch size is used until a batch has been measured
+        assert_eq!(sizing::get_batch_size(2048, 1, 2000, 1, 100), 100);
+
+        // 10 ciphers in 500ms -> 50ms per cipher -> 40 ciphers within the budget
+        sizing::record_batch(2048, 10, 500);
+        assert_eq!(sizing::get_cipher_costs()[0].micros_per_cipher, 50_000);
+        assert_eq!(sizing::get_batch_size(2048, 1, 2000, 1, 100), 40);
+
+        // a linked shuffle shuffles the batch of every topic
+        assert_eq!(sizing::get_batch_size(2048, 4, 2000, 1, 100), 10);
+
+        // the batch size is clamped to the configured range
+        assert_eq!(sizing::get_batch_size(2048, 1, 2000, 1, 20), 20);
+        assert_eq!(sizing::get_batch_size(2048, 1, 10, 2, 100), 2);
+
+        // the cost per cipher is smoothed: 0.75 * 50ms + 0.25 * 10ms = 40ms
+        sizing::record_batch(2048, 10, 100);
+        assert_eq!(sizing::get_batch_size(2048, 1, 2000, 1, 100), 50);
+
+        // the costs are measured per key size
+        sizing::record_batch(512, 100, 100);
+        assert_eq!(sizing::get_cipher_costs().len(), 2);
+        assert_eq!(sizing::get_batch_size(512, 1, 2000, 1, 1000), 1000);
+        assert_eq!(sizing::get_batch_size(1024, 1, 2000, 1, 100), 100);
+    });
+}
 
-        // setup vote
-        let (vote_id, topic_id) = setup_vote(params.into());
-        setup_public_key(vote_id.clone(), pk.clone().into());
+#[test]
+fn test_offchain_generators_cache() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, _) = Helper::setup_sm_system();
+        let p = &params.p;
+        let q = &params.q();
+        let topic_id = "20201212-01".as_bytes();
 
-        // fake proof + fake decrypted shares
-        let shares: Vec<Vec<u8>> = Vec::new();
-        let proof = DecryptedShareProof {
-            challenge: Vec::new(),
-            response: Vec::new(),
-        };
+        // the generators are computed and cached on the first batch
+        let vec_h = generators::get_generators(topic_id, p, q, 3);
+        assert_eq!(vec_h, Helper::get_generators(topic_id, p, 3));
 
-        // create the submitter (i.e. the voting_authority)
-        // use Alice as VotingAuthority
-        let who = get_voting_authority();
+        // a larger batch extends the cache, a smaller batch uses its first generators
+        let vec_h = generators::get_generators(topic_id, p, q, 5);
+        assert_eq!(vec_h, Helper::get_generators(topic_id, p, 5));
+        assert_eq!(
+            generators::get_generators(topic_id, p, q, 2),
+            vec_h[..2].to_vec()
+        );
 
-        assert_err!(
-            OffchainModule::submit_decrypted_shares(
-                who,
-                vote_id,
-                topic_id,
-                shares,
-                proof,
-                NR_OF_SHUFFLES
-            ),
-            Error::<TestRuntime>::WrongVotePhase
+        // the generators depend on the id
+        let other_topic_id = "20201212-02".as_bytes();
+        assert_eq!(
+            generators::get_generators(other_topic_id, p, q, 2),
+            Helper::get_generators(other_topic_id, p, 2)
+        );
+        assert_ne!(
+            generators::get_generators(other_topic_id, p, q, 2),
+            vec_h[..2].to_vec()
         );
     });
 }
 
 #[test]
-fn test_submit_decrypted_share_not_a_sealer() {
+fn test_validate_unsigned_decrypted_shares() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // setup public key
-        let (params, _, pk) = Helper::setup_sm_system();
-
-        // setup vote
+        let (params, _, _) = Helper::setup_sm_system();
         let (vote_id, topic_id) = setup_vote(params.into());
-        setup_public_key(vote_id.clone(), pk.clone().into());
 
-        // fake proof + fake decrypted shares
-        let shares: Vec<Vec<u8>> = Vec::new();
-        let proof = DecryptedShareProof {
-            challenge: Vec::new(),
-            response: Vec::new(),
+        // the payload is signed with the key of the offchain worker
+        let public = sp_io::crypto::sr25519_public_keys(keys::KEY_TYPE)[0];
+        let submission = DecryptedSharesSubmission {
+            vote_id: vote_id.clone(),
+            topic_id,
+            shares: vec![vec![1, 2, 3]],
+            proof: DecryptedShareProof::default(),
+            nr_of_shuffles: NR_OF_SHUFFLES,
+            public,
+        };
+        let signature =
+            SignedPayload::<TestRuntime>::sign::<keys::TestAuthId>(&submission).unwrap();
+        let call =
+            Call::submit_decrypted_shares_unsigned(submission.clone(), signature.clone());
+        let validate = |call: &Call<TestRuntime>| {
+            OffchainModule::validate_unsigned(TransactionSource::External, call)
         };
 
-        // change the VotePhase to Tallying
+        // the signer is not a sealer
+        assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+
+        // the vote is not being tallied
+        SealerSets::<TestRuntime>::mutate(0, |sealers| sealers.push(public));
+        assert_eq!(validate(&call), InvalidTransaction::Stale.into());
+
         set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        let valid = validate(&call).unwrap();
+        assert_eq!(valid.priority, TestUnsignedPriority::get());
+        assert_eq!(valid.longevity, TestDecryptionTimeout::get());
 
-        // check that the voting authority is not allowed
-        let voting_authority = get_voting_authority();
+        // the signature doesn't match the payload
+        let mut tampered = submission.clone();
+        tampered.shares = vec![vec![4, 5, 6]];
+        let call = Call::submit_decrypted_shares_unsigned(tampered, signature.clone());
+        assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+
+        // only the unsigned submissions of the sealers are valid
+        assert_eq!(
+            validate(&Call::do_nothing_when_its_not_your_turn()),
+            InvalidTransaction::Call.into()
+        );
+
+        // unsigned transactions must be submitted without origin
         assert_err!(
-            OffchainModule::submit_decrypted_shares(
-                voting_authority,
-                vote_id,
-                topic_id,
-                shares,
-                proof,
-                NR_OF_SHUFFLES
+            OffchainModule::submit_decrypted_shares_unsigned(
+                Origin::signed(public),
+                submission,
+                signature
             ),
-            Error::<TestRuntime>::NotASealer
+            DispatchError::BadOrigin
         );
     });
 }
 
 #[test]
-fn test_submit_decrypted_share() {
+fn test_validate_unsigned_heartbeat() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Distributed Key Generation Setup
-        let (params, _, _) = Helper::setup_md_system();
-        let (vote_id, topic_id) = setup_vote(params.clone().into());
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, _) = setup_vote(params.into());
+        System::set_block_number(1);
 
-        // Use 1. Sealer: Bob
-        let (bob, _, bob_sealer_id) = get_sealer_bob();
-        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
-        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
-        let (_, _) = setup_sealer(
-            &params,
-            &bob_sk,
-            &bob_pk,
-            bob.clone(),
-            &vote_id,
-            &bob_sealer_id,
-        );
+        // the heartbeat is signed with the key of the offchain worker
+        let public = sp_io::crypto::sr25519_public_keys(keys::KEY_TYPE)[0];
+        let heartbeat = HeartbeatPayload {
+            block_number: 1,
+            public,
+        };
+        let signature =
+            SignedPayload::<TestRuntime>::sign::<keys::TestAuthId>(&heartbeat).unwrap();
+        let call = Call::heartbeat_unsigned(heartbeat.clone(), signature.clone());
+        let validate = |call: &Call<TestRuntime>| {
+            OffchainModule::validate_unsigned(TransactionSource::External, call)
+        };
 
-        // Use 2. Sealer: Charlie
-        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
-        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
-        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
-        let (_, _) = setup_sealer(
-            &params,
-            &charlie_sk,
-            &charlie_pk,
-            charlie,
-            &vote_id,
-            &charlie_sealer_id,
+        // the signer is not a sealer
+        assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+
+        // no vote is being tallied
+        SealerSets::<TestRuntime>::mutate(0, |sealers| sealers.push(public));
+        Sealers::<TestRuntime>::mutate(|sealers| sealers.push(public));
+        assert_eq!(validate(&call), InvalidTransaction::Stale.into());
+
+        set_vote_phase(vote_id, VotePhase::Tallying);
+        let valid = validate(&call).unwrap();
+        assert_eq!(valid.priority, TestUnsignedPriority::get());
+        assert_eq!(valid.longevity, TestHeartbeatInterval::get());
+
+        // the heartbeat can't be sent ahead of time
+        let mut future = heartbeat.clone();
+        future.block_number = 2;
+        let future_signature =
+            SignedPayload::<TestRuntime>::sign::<keys::TestAuthId>(&future).unwrap();
+        assert_eq!(
+            validate(&Call::heartbeat_unsigned(future, future_signature)),
+            InvalidTransaction::Future.into()
         );
 
-        // combine the public key shares
-        let voting_authority = get_voting_authority();
-        assert_ok!(OffchainModule::combine_public_key_shares(
-            voting_authority,
-            vote_id.clone()
+        // a single heartbeat per interval
+        assert_ok!(OffchainModule::heartbeat_unsigned(
+            Origin::none(),
+            heartbeat.clone(),
+            signature.clone()
         ));
+        assert_eq!(OffchainModule::last_heartbeat(&public), Some(1));
+        assert_eq!(validate(&call), InvalidTransaction::Stale.into());
+
+        let next_block = 1 + TestHeartbeatInterval::get();
+        System::set_block_number(next_block);
+        let next = HeartbeatPayload {
+            block_number: next_block,
+            public,
+        };
+        let next_signature =
+            SignedPayload::<TestRuntime>::sign::<keys::TestAuthId>(&next).unwrap();
+        assert!(validate(&Call::heartbeat_unsigned(next, next_signature)).is_ok());
 
-        // get the public key from the chain
-        let system_pk: ElGamalPK =
-            OffchainModule::public_key(vote_id.clone()).unwrap().into();
-        let computed_system_pk: BigUint =
-            bob_pk.h.modmul(&charlie_pk.h, &bob_pk.params.p);
-        assert_eq!(system_pk.h, computed_system_pk);
+        // the signature doesn't match the payload
+        let mut tampered = heartbeat.clone();
+        tampered.block_number = next_block;
+        assert_eq!(
+            validate(&Call::heartbeat_unsigned(tampered, signature.clone())),
+            InvalidTransaction::BadProof.into()
+        );
 
-        // create encrypted votes - NOT ENCODED
-        setup_ciphers(&vote_id, &topic_id, &system_pk.clone().into(), false);
+        // unsigned transactions must be submitted without origin
+        assert_err!(
+            OffchainModule::heartbeat_unsigned(
+                Origin::signed(public),
+                heartbeat,
+                signature
+            ),
+            DispatchError::BadOrigin
+        );
+    });
+}
 
-        // change the VotePhase to Tallying
-        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+#[test]
+fn test_shuffle_status_index_key() {
+    let vote_id = "20201212".as_bytes().to_vec();
+    let topic_id = "20201212-01".as_bytes().to_vec();
+    let key = helpers::indexing::get_shuffle_status_key(&vote_id, &topic_id);
+    assert!(key.starts_with(SHUFFLE_STATUS_PREFIX));
+    assert_eq!(
+        &key[SHUFFLE_STATUS_PREFIX.len()..],
+        &(vote_id.clone(), topic_id).encode()[..]
+    );
 
-        // fetch the encrypted votes from chain
-        let encryptions: Vec<BigCipher> =
-            Wrapper(OffchainModule::ciphers(&topic_id, NR_OF_SHUFFLES)).into();
-        assert!(encryptions.len() > 0);
+    // the keys of the topics differ
+    let other_topic_id = "20201212-02".as_bytes().to_vec();
+    assert_ne!(
+        key,
+        helpers::indexing::get_shuffle_status_key(&vote_id, &other_topic_id)
+    );
+}
 
-        // get bob's partial decryptions
-        let bob_partial_decrytpions = encryptions
-            .iter()
-            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &bob_sk))
-            .collect::<Vec<BigUint>>();
+#[test]
+fn test_validate_unsigned_shuffle_requires_commitment() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, false);
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
 
-        // convert the decrypted shares: Vec<BigUint> to Vec<Vec<u8>>
-        let bob_shares: Vec<Vec<u8>> = bob_partial_decrytpions
-            .iter()
-            .map(|c| c.to_bytes_be())
-            .collect::<Vec<Vec<u8>>>();
+        let public = sp_io::crypto::sr25519_public_keys(keys::KEY_TYPE)[0];
+        SealerSets::<TestRuntime>::mutate(0, |sealers| sealers.push(public));
+        let submission = ShuffleSubmission {
+            vote_id: vote_id.clone(),
+            topic_id: topic_id.clone(),
+            payload: ShufflePayload::default(),
+            public,
+        };
+        let signature =
+            SignedPayload::<TestRuntime>::sign::<keys::TestAuthId>(&submission).unwrap();
+        let call = Call::submit_shuffled_votes_and_proof_unsigned(submission, signature);
 
-        // create bob's proof using bob's public and private key share
-        let r = BigUint::parse_bytes(b"1234123123", 10).unwrap();
-        let bob_proof = DecryptionProof::generate(
-            &params,
-            &bob_sk.x,
-            &bob_pk.h.into(),
-            &r,
-            encryptions,
-            bob_partial_decrytpions,
-            &bob_sealer_id,
+        // the sealer hasn't committed to the shuffle
+        assert_eq!(
+            OffchainModule::validate_unsigned(TransactionSource::External, &call),
+            InvalidTransaction::Stale.into()
         );
-
-        // check that:
-        // 1. the decrypted share is submitted and
-        // 2. the proof is successfully verified
-        assert_ok!(OffchainModule::submit_decrypted_shares(
-            bob.clone(),
-            vote_id,
-            topic_id,
-            bob_shares,
-            bob_proof.into(),
-            NR_OF_SHUFFLES
-        ));
     });
 }
 
 #[test]
-fn test_combine_decrypted_shares_vote_does_not_exist() {
+fn test_submit_smaller_shuffle_batch() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let voting_authority = get_voting_authority();
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, false);
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        let (bob, _, _) = get_sealer_bob();
+        let (charlie, _, _) = get_sealer_charlie();
+
+        let shuffle_state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
+            .expect("shuffle state should exist for all existing votes & topics!");
+        assert_eq!(shuffle_state.batch_size, 2);
+
+        // the offchain worker may shuffle fewer ciphers than configured
+        let payload: ShufflePayload =
+            OffchainModule::offchain_shuffle_and_proof(&vote_id, &topic_id, 0, &pk, 0, 1)
+                .unwrap();
+        assert_eq!(payload.ciphers.len(), 1);
+        assert_ok!(commit_and_reveal_shuffle(
+            bob,
+            vote_id.clone(),
+            topic_id.clone(),
+            payload
+        ));
+
+        // the next batch starts after the shuffled ciphers, the configured batch size is kept
+        let shuffle_state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
+            .expect("shuffle state should exist for all existing votes & topics!");
+        assert_eq!(shuffle_state.start_position, 1);
+        assert_eq!(shuffle_state.batch_size, 2);
+
+        // but never more ciphers than configured
+        let payload: ShufflePayload =
+            OffchainModule::offchain_shuffle_and_proof(&vote_id, &topic_id, 0, &pk, 1, 3)
+                .unwrap();
         assert_err!(
-            OffchainModule::combine_decrypted_shares(
-                voting_authority,
-                "vote_id".as_bytes().to_vec(),
-                "topic_id".as_bytes().to_vec(),
-                false,
-                NR_OF_SHUFFLES
-            ),
-            Error::<TestRuntime>::VoteDoesNotExist
+            commit_and_reveal_shuffle(charlie, vote_id, topic_id, payload),
+            Error::<TestRuntime>::ShuffleStateIncorrect
         );
-    })
+    });
 }
 
 #[test]
-fn test_combine_decrypted_shares_wrong_vote_phase() {
+fn test_shuffle_cipher_count_mismatch() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let (params, _, _) = Helper::setup_md_system();
-        let (vote_id, topic_id) = setup_vote(params.clone().into());
-
-        // change the votephase to tallying
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, false);
         set_vote_phase(vote_id.clone(), VotePhase::Tallying);
-
-        // use bob as a submitter
         let (bob, _, _) = get_sealer_bob();
+        let (charlie, _, _) = get_sealer_charlie();
 
-        // try to combine shares -> not a voting authority
+        // the number of ciphers is maintained on every insert
+        assert_eq!(OffchainModule::cipher_count((&vote_id, &topic_id), 0), 6);
+        assert_eq!(OffchainModule::cipher_count((&vote_id, &topic_id), 1), 0);
+
+        // a shuffle which drops a cipher of the batch
+        let mut payload: ShufflePayload =
+            OffchainModule::offchain_shuffle_and_proof(&vote_id, &topic_id, 0, &pk, 0, 2)
+                .unwrap();
+        let dropped = payload.ciphers.pop().unwrap();
         assert_err!(
-            OffchainModule::combine_decrypted_shares(
-                bob,
-                vote_id,
-                topic_id,
-                false,
-                NR_OF_SHUFFLES
+            commit_and_reveal_shuffle(
+                bob.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                payload.clone()
             ),
-            Error::<TestRuntime>::NotAVotingAuthority
+            Error::<TestRuntime>::CipherCountMismatch
         );
-    })
+
+        // the valid shuffle of the batch is accepted
+        payload.ciphers.push(dropped);
+        assert_ok!(commit_and_reveal_shuffle(
+            bob,
+            vote_id.clone(),
+            topic_id.clone(),
+            payload.clone()
+        ));
+        assert_eq!(OffchainModule::cipher_count((&vote_id, &topic_id), 1), 2);
+
+        // a cipher injected into the next iteration is detected by the next shuffle
+        helpers::ciphers::append_ciphers::<TestRuntime>(
+            &vote_id,
+            &topic_id,
+            1,
+            vec![payload.ciphers[0].clone()],
+        );
+        let payload: ShufflePayload =
+            OffchainModule::offchain_shuffle_and_proof(&vote_id, &topic_id, 0, &pk, 2, 2)
+                .unwrap();
+        assert_err!(
+            commit_and_reveal_shuffle(charlie, vote_id, topic_id, payload),
+            Error::<TestRuntime>::CipherCountMismatch
+        );
+    });
 }
 
 #[test]
-fn test_combine_decrypted_shares_not_a_voting_authority() {
+fn test_shuffle_with_protocol_config() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        let (params, _, _) = Helper::setup_md_system();
-        let (vote_id, topic_id) = setup_vote(params.clone().into());
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, false);
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        let (bob, _, _) = get_sealer_bob();
 
-        // try to combine shares -> voting phase not updated yet
-        let voting_authority = get_voting_authority();
+        // the protocol parameters are stored with the vote
+        let config: ProtocolConfig = OffchainModule::protocol_config(&vote_id).unwrap();
+        assert_eq!(config.version, PROTOCOL_VERSION);
+        assert_eq!(
+            config.nr_of_shuffles,
+            OffchainModule::votes(&vote_id).nr_of_shuffles
+        );
+        assert!(config.is_supported());
+
+        // the proofs of a vote with an unknown protocol version are rejected
+        let payload: ShufflePayload =
+            OffchainModule::offchain_shuffle_and_proof(&vote_id, &topic_id, 0, &pk, 0, 2)
+                .unwrap();
+        let mut unsupported = config.clone();
+        unsupported.version = PROTOCOL_VERSION + 1;
+        ProtocolConfigs::insert(&vote_id, unsupported);
         assert_err!(
-            OffchainModule::combine_decrypted_shares(
-                voting_authority,
-                vote_id,
-                topic_id,
-                false,
-                NR_OF_SHUFFLES
+            commit_and_reveal_shuffle(
+                bob.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                payload.clone()
             ),
-            Error::<TestRuntime>::WrongVotePhase
+            Error::<TestRuntime>::UnsupportedProtocol
         );
-    })
+
+        // the proof is verified with the batch exponents of the vote's protocol
+        let mut config = config;
+        config.batch_exponent_bits = 128;
+        ProtocolConfigs::insert(&vote_id, config);
+        assert_ok!(commit_and_reveal_shuffle(bob, vote_id, topic_id, payload));
+    });
 }
 
 #[test]
-fn test_combine_decrypted_shares() {
+fn test_set_hash_function() {
     let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Distributed Key Generation Setup
-        let (params, _, _) = Helper::setup_md_system();
-        let (vote_id, topic_id) = setup_vote(params.clone().into());
-
-        // Use 1. Sealer: Bob
-        let (bob, _, bob_sealer_id) = get_sealer_bob();
-        let bob_sk_x = BigUint::parse_bytes(b"12345678", 10).unwrap();
-        let (bob_pk, bob_sk) = Helper::generate_key_pair(&params, &bob_sk_x);
-        let (_, _) = setup_sealer(
-            &params,
-            &bob_sk,
-            &bob_pk,
-            bob.clone(),
-            &vote_id,
-            &bob_sealer_id,
+        let (params, sk, pk) = Helper::setup_md_system();
+        let (vote_id, _) =
+            setup_vote_in_key_generation(params.clone().into(), TallyStrategy::Mixnet);
+        let (bob, _, sealer_id) = get_sealer_bob();
+        assert_eq!(
+            OffchainModule::protocol_config(&vote_id)
+                .unwrap()
+                .hash_function,
+            HashFunction::Blake2b
         );
 
-        // Use 2. Sealer: Charlie
-        let (charlie, _, charlie_sealer_id) = get_sealer_charlie();
-        let charlie_sk_x = BigUint::parse_bytes(b"87654321", 10).unwrap();
-        let (charlie_pk, charlie_sk) = Helper::generate_key_pair(&params, &charlie_sk_x);
-        let (_, _) = setup_sealer(
-            &params,
-            &charlie_sk,
-            &charlie_pk,
-            charlie.clone(),
-            &vote_id,
-            &charlie_sealer_id,
+        // only a voting authority can set the hash function
+        assert_err!(
+            OffchainModule::set_hash_function(
+                bob.clone(),
+                vote_id.clone(),
+                HashFunction::Sha256
+            ),
+            Error::<TestRuntime>::NotAVotingAuthority
         );
-
-        // combine the public key shares
-        let voting_authority = get_voting_authority();
-        assert_ok!(OffchainModule::combine_public_key_shares(
-            voting_authority.clone(),
-            vote_id.clone()
+        assert_ok!(OffchainModule::set_hash_function(
+            get_voting_authority(),
+            vote_id.clone(),
+            HashFunction::Sha256
         ));
+        assert_eq!(
+            OffchainModule::protocol_config(&vote_id)
+                .unwrap()
+                .hash_function,
+            HashFunction::Sha256
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::HashFunctionUpdated(
+                vote_id.clone(),
+                HashFunction::Sha256
+            ))));
 
-        // get the public key from the chain
-        let system_pk: ElGamalPK =
-            OffchainModule::public_key(vote_id.clone()).unwrap().into();
-        let computed_system_pk: BigUint =
-            bob_pk.h.modmul(&charlie_pk.h, &bob_pk.params.p);
-        assert_eq!(system_pk.h, computed_system_pk);
-
-        // create encrypted votes - NOT ENCODED
-        setup_ciphers(&vote_id, &topic_id, &system_pk.clone().into(), false);
-
-        // change the VotePhase to Voting using the voting authority
-        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
-
-        // fetch the encrypted votes from chain
-        let encryptions: Vec<BigCipher> =
-            Wrapper(OffchainModule::ciphers(&topic_id, NR_OF_SHUFFLES)).into();
-        assert!(encryptions.len() > 0);
-
-        // get bob's partial decryptions
-        let bob_partial_decrytpions = encryptions
-            .iter()
-            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &bob_sk))
-            .collect::<Vec<BigUint>>();
-
-        // convert the decrypted shares: Vec<BigUint> to Vec<Vec<u8>>
-        let bob_shares: Vec<Vec<u8>> = bob_partial_decrytpions
-            .iter()
-            .map(|c| c.to_bytes_be())
-            .collect::<Vec<Vec<u8>>>();
-
-        // create bob's proof using bob's public and private key share
-        let r = BigUint::parse_bytes(b"1234123123", 10).unwrap();
-        let bob_proof = DecryptionProof::generate(
+        // the proof of the public key share must use the hash function of the vote
+        let r = BigUint::parse_bytes(b"1701411834604692317316873", 10).unwrap();
+        let proof = KeyGenerationProof::generate(&params, &sk.x, &pk.h, &r, &sealer_id);
+        let pk_share = PublicKeyShare {
+            proof: proof.into(),
+            pk: pk.h.to_bytes_be(),
+        };
+        assert_err!(
+            OffchainModule::store_public_key_share(
+                bob.clone(),
+                vote_id.clone(),
+                pk_share
+            ),
+            Error::<TestRuntime>::PublicKeyShareProofError
+        );
+        let proof = KeyGenerationProof::generate_with_hash(
             &params,
-            &bob_sk.x,
-            &bob_pk.h.into(),
+            &sk.x,
+            &pk.h,
             &r,
-            encryptions.clone(),
-            bob_partial_decrytpions,
-            &bob_sealer_id,
+            &sealer_id,
+            crypto::hash::HashFunction::Sha256,
         );
-
-        // check that:
-        // 1. the decrypted share is submitted and
-        // 2. the proof is successfully verified
-        assert_ok!(OffchainModule::submit_decrypted_shares(
-            bob.clone(),
+        let pk_share = PublicKeyShare {
+            proof: proof.into(),
+            pk: pk.h.to_bytes_be(),
+        };
+        assert_ok!(OffchainModule::store_public_key_share(
+            bob,
             vote_id.clone(),
-            topic_id.clone(),
-            bob_shares,
-            bob_proof.into(),
-            NR_OF_SHUFFLES
+            pk_share
         ));
 
-        // get charlie's partial decryptions
-        let charlie_paritial_decryptions = encryptions
-            .iter()
-            .map(|cipher| ElGamal::partial_decrypt_a(cipher, &charlie_sk))
-            .collect::<Vec<BigUint>>();
-
-        // convert the decrypted shares: Vec<BigUint> to Vec<Vec<u8>>
-        let charlie_shares: Vec<Vec<u8>> = charlie_paritial_decryptions
-            .iter()
-            .map(|c| c.to_bytes_be())
-            .collect::<Vec<Vec<u8>>>();
-
-        // create charlie's proof using charlie's public and private key share
-        let r = BigUint::parse_bytes(b"80981238129912392", 10).unwrap();
-        let charlie_proof = DecryptionProof::generate(
-            &params,
-            &charlie_sk.x,
-            &charlie_pk.h.into(),
-            &r,
-            encryptions,
-            charlie_paritial_decryptions,
-            &charlie_sealer_id,
+        // the hash function can't be changed once a public key share has been submitted
+        assert_err!(
+            OffchainModule::set_hash_function(
+                get_voting_authority(),
+                vote_id,
+                HashFunction::Blake2b
+            ),
+            Error::<TestRuntime>::KeyGenerationStarted
         );
+    });
+}
 
-        // check that:
-        // 1. the decrypted share is submitted and
-        // 2. the proof is successfully verified
-        assert_ok!(OffchainModule::submit_decrypted_shares(
-            charlie.clone(),
+#[test]
+fn test_shuffle_proof_with_the_hash_function_of_the_vote() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) =
+            setup_vote_in_key_generation(params.into(), TallyStrategy::Mixnet);
+        assert_ok!(OffchainModule::set_hash_function(
+            get_voting_authority(),
             vote_id.clone(),
-            topic_id.clone(),
-            charlie_shares,
-            charlie_proof.into(),
-            NR_OF_SHUFFLES
-        ));
-
-        // combine the decrypted shares + tally topic
-        assert_ok!(OffchainModule::combine_decrypted_shares(
-            voting_authority,
-            vote_id,
-            topic_id.clone(),
-            false,
-            NR_OF_SHUFFLES
+            HashFunction::Sha3_256
         ));
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, false);
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
 
-        // retrieve the tallied result from the storage on chain
-        let result: TopicResult = OffchainModule::tally(topic_id).unwrap();
-
-        // transform the result from Vec<u8> (bytes) back to Vec<BigUint>
-        let mut big_result: BTreeMap<BigUint, BigUint> = BTreeMap::new();
-        for (key, value) in result.iter() {
-            big_result.insert(BigUint::from_bytes_be(key), BigUint::from_bytes_be(value));
-        }
+        // the offchain worker proves the shuffle with the hash function of the vote
+        let payload: ShufflePayload =
+            OffchainModule::offchain_shuffle_and_proof(&vote_id, &topic_id, 0, &pk, 0, 3)
+                .unwrap();
+        let ciphers: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, 0)[..3].to_vec()).into();
+        let shuffled: Vec<BigCipher> = Wrapper(payload.ciphers.clone()).into();
+        let context = OffchainModule::ballot_box_freeze(&vote_id).unwrap().digest;
+        let config = OffchainModule::protocol_config(&vote_id).unwrap();
+        assert!(OffchainModule::verify_shuffle_proof_in_context(
+            &topic_id,
+            &context,
+            payload.proof.clone().into(),
+            ciphers.clone(),
+            shuffled.clone(),
+            &pk,
+            &config,
+        )
+        .unwrap());
 
-        // check that there are 2 entries for each type of vote
-        assert_eq!(
-            big_result.get(&BigUint::from(4u32)).unwrap(),
-            &BigUint::from(2u32)
-        );
-        assert_eq!(
-            big_result.get(&BigUint::from(1u32)).unwrap(),
-            &BigUint::from(2u32)
-        );
-        assert_eq!(
-            big_result.get(&BigUint::from(3u32)).unwrap(),
-            &BigUint::from(2u32)
-        );
+        // the challenges of another hash function don't match
+        assert!(!OffchainModule::verify_shuffle_proof_in_context(
+            &topic_id,
+            &context,
+            payload.proof.clone().into(),
+            ciphers,
+            shuffled,
+            &pk,
+            &ProtocolConfig::new(3),
+        )
+        .unwrap());
+        let (bob, _, _) = get_sealer_bob();
+        assert_ok!(commit_and_reveal_shuffle(bob, vote_id, topic_id, payload));
     });
 }
 
 #[test]
-fn test_offchain_shuffling() {
-    let (mut t, pool_state, _) = ExternalityBuilder::build();
+fn test_ballot_box_freeze_binds_the_shuffle_proofs() {
+    let (mut t, _, _) = ExternalityBuilder::build();
     t.execute_with(|| {
-        // Setup
         let (params, _, pk) = Helper::setup_sm_system();
         let (vote_id, topic_id) = setup_vote(params.into());
-        let encoded: bool = false;
-        let block_number: <TestRuntime as frame_system::Trait>::BlockNumber =
-            (1u32).into();
-
-        // store created public key and public parameters
         setup_public_key(vote_id.clone(), pk.clone().into());
-        setup_ciphers(&vote_id, &topic_id, &pk, encoded);
-
-        // change the VotePhase to Voting using the voting authority
+        setup_ciphers(&vote_id, &topic_id, &pk, false);
+        assert!(OffchainModule::ballot_box_freeze(&vote_id).is_none());
         set_vote_phase(vote_id.clone(), VotePhase::Tallying);
 
-        // Test
-        let result = OffchainModule::offchain_shuffling(block_number);
-        assert_ok!(result);
+        // the ciphers are frozen once the tallying phase starts
+        let freeze = OffchainModule::ballot_box_freeze(&vote_id).unwrap();
+        let topics = vec![(
+            topic_id.clone(),
+            OffchainModule::cipher_count((&vote_id, &topic_id), 0),
+            OffchainModule::cipher_root((&vote_id, &topic_id), 0).unwrap(),
+        )];
+        let expected = BallotBoxFreeze::new(
+            System::parent_hash(),
+            System::block_number(),
+            BallotBoxFreeze::<sp_core::H256, u64>::ciphers_chain(&vote_id, &topics),
+        );
+        assert_eq!(freeze, expected);
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::BallotBoxFrozen(
+                vote_id.clone(),
+                freeze.block_hash,
+                freeze.digest
+            ))));
 
-        // Verify
-        let tx = pool_state.write().transactions.pop().unwrap();
-        assert!(pool_state.read().transactions.is_empty());
-        let tx = TestExtrinsic::decode(&mut &*tx).unwrap();
-        assert_eq!(tx.signature.unwrap().0, 0);
+        // the proof is only valid in the context of the freeze
+        let payload: ShufflePayload =
+            OffchainModule::offchain_shuffle_and_proof(&vote_id, &topic_id, 0, &pk, 0, 3)
+                .unwrap();
+        let ciphers: Vec<BigCipher> =
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, 0)[..3].to_vec()).into();
+        let shuffled: Vec<BigCipher> = Wrapper(payload.ciphers.clone()).into();
+        let config = ProtocolConfig::new(3);
+        assert!(OffchainModule::verify_shuffle_proof_in_context(
+            &topic_id,
+            &freeze.digest,
+            payload.proof.clone().into(),
+            ciphers.clone(),
+            shuffled.clone(),
+            &pk,
+            &config,
+        )
+        .unwrap());
+        assert!(!OffchainModule::verify_shuffle_proof(
+            &topic_id,
+            payload.proof.clone().into(),
+            ciphers,
+            shuffled,
+            &pk,
+            &config,
+        )
+        .unwrap());
 
-        // TODO: find a way to compare Call signature without having to provide values
-        // assert_eq!(tx.call, Call::submit_shuffled_votes_and_proof);
+        // a shuffle proven without the context (e.g. of another ballot box) is rejected
+        let (bob, _, _) = get_sealer_bob();
+        BallotBoxFreezes::<TestRuntime>::remove(&vote_id);
+        let unbound: ShufflePayload =
+            OffchainModule::offchain_shuffle_and_proof(&vote_id, &topic_id, 0, &pk, 0, 3)
+                .unwrap();
+        BallotBoxFreezes::<TestRuntime>::insert(&vote_id, freeze);
+        assert_err!(
+            commit_and_reveal_shuffle(
+                bob.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                unbound
+            ),
+            Error::<TestRuntime>::ShuffleProofVerifcationFailed
+        );
+        assert_ok!(commit_and_reveal_shuffle(bob, vote_id, topic_id, payload));
     });
 }
 
@@ -2027,14 +8589,15 @@ fn test_submit_shuffled_votes_and_proof() {
 
         // get the encrypted votes
         let big_ciphers_from_chain: Vec<BigCipher> =
-            Wrapper(OffchainModule::ciphers(&topic_id, nr_of_shuffles)).into();
+            Wrapper(OffchainModule::ciphers(&vote_id, &topic_id, nr_of_shuffles)).into();
         assert!(big_ciphers_from_chain.len() > 0);
 
         // change the VotePhase to Voting using the voting authority
         set_vote_phase(vote_id.clone(), VotePhase::Tallying);
 
-        // get any sealer that is allowed to submit the votes
-        let (bob, _, _) = get_sealer_bob();
+        // the sealers take turns, starting with the first sealer
+        let (bob, bob_account, _) = get_sealer_bob();
+        let (charlie, charlie_account, _) = get_sealer_charlie();
 
         //
         // State: No Ciphers Shuffled Yet
@@ -2049,6 +8612,7 @@ fn test_submit_shuffled_votes_and_proof() {
 
         // shuffle the votes + create proof
         let payload: ShufflePayload = OffchainModule::offchain_shuffle_and_proof(
+            &vote_id,
             &topic_id,
             shuffle_state.iteration,
             &pk,
@@ -2056,8 +8620,14 @@ fn test_submit_shuffled_votes_and_proof() {
             shuffle_state.batch_size,
         )
         .unwrap();
-        // submit the proof and the shuffled votes
-        let response = OffchainModule::submit_shuffled_votes_and_proof(
+        // the shuffle of the batch is recorded in the metrics of the offchain worker
+        let recorded = metrics::get_metrics();
+        assert_eq!(recorded.ciphers_shuffled, payload.ciphers.len() as u64);
+        assert_eq!(recorded.shuffle_ms.count, 1);
+        assert_eq!(recorded.proof_generation_ms.count, 1);
+
+        // commit to and submit the proof and the shuffled votes
+        let response = commit_and_reveal_shuffle(
             bob.clone(),
             vote_id.clone(),
             topic_id.clone(),
@@ -2068,7 +8638,7 @@ fn test_submit_shuffled_votes_and_proof() {
         // verify that the shuffled votes have been stored
         // at the new index: shuffle_state.iteration + 1
         let shuffled_from_chain: Vec<Cipher> =
-            Ciphers::get(&topic_id, shuffle_state.iteration + 1);
+            OffchainModule::ciphers(&vote_id, &topic_id, shuffle_state.iteration + 1);
         assert!(!shuffled_from_chain.is_empty());
         assert_eq!(shuffled_from_chain.len(), payload.ciphers.len());
 
@@ -2076,29 +8646,423 @@ fn test_submit_shuffled_votes_and_proof() {
         // State: 2/6 Ciphers Shuffled
         //
 
+        // the commitment is removed once the shuffle has been revealed
+        assert_eq!(
+            OffchainModule::shuffle_commitment((&vote_id, &topic_id)),
+            None
+        );
+
         // re-submit the proof and the shuffled votes
         // make sure that the 2nd time the request fails
+        assert_err!(
+            commit_and_reveal_shuffle(
+                charlie.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                payload
+            ),
+            Error::<TestRuntime>::ShuffleStateIncorrect
+        );
+
+        // perform the next shuffle
+        // get the new shuffle state
+        let shuffle_state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
+            .expect("shuffle state should exist for all existing votes & topics!");
+        // check that the shuffle state computation is correct
+        assert_eq!(shuffle_state.start_position, 2);
+        assert_eq!(shuffle_state.batch_size, 2);
+        assert_eq!(shuffle_state.iteration, 0);
+
+        // shuffle the votes + create proof
+        let payload: ShufflePayload = OffchainModule::offchain_shuffle_and_proof(
+            &vote_id,
+            &topic_id,
+            shuffle_state.iteration,
+            &pk,
+            shuffle_state.start_position,
+            shuffle_state.batch_size,
+        )
+        .unwrap();
+        // commit to and submit the proof and the shuffled votes
+        let response = commit_and_reveal_shuffle(
+            charlie.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            payload.clone(),
+        );
+        assert_ok!(response);
+
+        // verify that the shuffled votes have been stored
+        // at the new index: shuffle_state.iteration + 1
+        let shuffled_from_chain: Vec<Cipher> =
+            OffchainModule::ciphers(&vote_id, &topic_id, shuffle_state.iteration + 1);
+        assert!(!shuffled_from_chain.is_empty());
+        assert_eq!(shuffled_from_chain.len(), 2 * payload.ciphers.len());
+
+        //
+        // State: 4/6 Ciphers Shuffled
+        //
+
+        // perform the next shuffle
+        // get the new shuffle state
+        let shuffle_state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
+            .expect("shuffle state should exist for all existing votes & topics!");
+        // check that the shuffle state computation is correct
+        assert_eq!(shuffle_state.start_position, 4);
+        assert_eq!(shuffle_state.batch_size, 2);
+        assert_eq!(shuffle_state.iteration, 0);
+
+        // shuffle the votes + create proof
+        let payload: ShufflePayload = OffchainModule::offchain_shuffle_and_proof(
+            &vote_id,
+            &topic_id,
+            shuffle_state.iteration,
+            &pk,
+            shuffle_state.start_position,
+            shuffle_state.batch_size,
+        )
+        .unwrap();
+        // commit to and submit the proof and the shuffled votes
+        let response = commit_and_reveal_shuffle(
+            bob.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            payload.clone(),
+        );
+        assert_ok!(response);
+
+        // verify that the shuffled votes have been stored
+        // at the new index: shuffle_state.iteration + 1
+        let shuffled_from_chain: Vec<Cipher> =
+            OffchainModule::ciphers(&vote_id, &topic_id, shuffle_state.iteration + 1);
+        assert!(!shuffled_from_chain.is_empty());
+        assert_eq!(shuffled_from_chain.len(), big_ciphers_from_chain.len());
+
+        //
+        // State: 6/6 Ciphers Shuffled, 2nd Shuffle Iteration Starts
+        //
+
+        // get the new shuffle state
+        let shuffle_state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
+            .expect("shuffle state should exist for all existing votes & topics!");
+        // check that the shuffle state computation is correct
+        assert_eq!(shuffle_state.start_position, 0);
+        assert_eq!(shuffle_state.batch_size, 2);
+        assert_eq!(shuffle_state.iteration, 1);
+
+        // the shuffling is not completed yet
+        assert!(!System::events().iter().any(|er| matches!(
+            er.event,
+            TestEvent::pallet_mixnet(RawEvent::ShuffleCompleted(..))
+        )));
+
+        // every accepted batch and the completed iteration are announced
+        let accepted_batches: Vec<(u8, u64, u64)> = System::events()
+            .into_iter()
+            .filter_map(|er| match er.event {
+                TestEvent::pallet_mixnet(RawEvent::ShuffleBatchAccepted(
+                    _,
+                    _,
+                    iteration,
+                    start_position,
+                    nr_of_ciphers,
+                )) => Some((iteration, start_position, nr_of_ciphers)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(accepted_batches, vec![(0, 0, 2), (0, 2, 2), (0, 4, 2)]);
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::ShuffleIterationCompleted(
+                vote_id.clone(),
+                topic_id.clone(),
+                1
+            ))));
+
+        // the progress reflects the completed iteration
+        let progress = OffchainModule::progress(&vote_id);
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].topic_id, topic_id);
+        assert_eq!(progress[0].nr_of_ciphers, 6);
+        assert_eq!(progress[0].shuffle_iteration, 1);
+        assert_eq!(progress[0].shuffle_start_position, 0);
+        assert!(!progress[0].shuffle_done);
+
+        // every accepted shuffle proof is logged with its sealer, the rejected one is not
+        let log = OffchainModule::proof_log(&vote_id);
+        let submitters: Vec<<TestRuntime as frame_system::Trait>::AccountId> =
+            log.iter().map(|entry| entry.submitter).collect();
+        assert_eq!(submitters, vec![bob_account, charlie_account, bob_account]);
+        assert!(log.iter().all(|entry| entry.kind == ProofKind::Shuffle
+            && entry.topic_id == topic_id
+            && entry.iteration == 0));
+        assert!(log[0].block_number < log[1].block_number);
+    });
+}
+
+#[test]
+fn test_shuffle_commit_reveal() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let encoded: bool = false;
+
+        // store created public key and public parameters
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, encoded);
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+
+        let (bob, bob_id, _) = get_sealer_bob();
+        let (charlie, charlie_id, _) = get_sealer_charlie();
+
+        // shuffle the votes + create proof
+        let shuffle_state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
+            .expect("shuffle state should exist for all existing votes & topics!");
+        let payload: ShufflePayload = OffchainModule::offchain_shuffle_and_proof(
+            &vote_id,
+            &topic_id,
+            shuffle_state.iteration,
+            &pk,
+            shuffle_state.start_position,
+            shuffle_state.batch_size,
+        )
+        .unwrap();
+        let commitment = shuffle::commitment::hash_shuffle_payload(&payload);
+
+        // a shuffle cannot be revealed without a commitment
         assert_err!(
             OffchainModule::submit_shuffled_votes_and_proof(
                 bob.clone(),
                 vote_id.clone(),
                 topic_id.clone(),
-                payload
+                payload.clone()
+            ),
+            Error::<TestRuntime>::ShuffleNotCommitted
+        );
+
+        // bob commits to the shuffle
+        let commit_block = System::block_number();
+        assert_ok!(OffchainModule::commit_shuffle(
+            bob.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            commitment
+        ));
+        let stored = OffchainModule::shuffle_commitment((&vote_id, &topic_id)).unwrap();
+        assert_eq!(stored.sealer, bob_id);
+        assert_eq!(stored.commitment, commitment);
+        assert_eq!(stored.block_number, commit_block);
+
+        // the shuffle cannot be revealed in the same block
+        assert_err!(
+            OffchainModule::submit_shuffled_votes_and_proof(
+                bob.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                payload.clone()
+            ),
+            Error::<TestRuntime>::ShuffleRevealTooEarly
+        );
+
+        // charlie can neither replace nor reveal bob's pending commitment during bob's turn
+        System::set_block_number(commit_block + 1);
+        assert_err!(
+            OffchainModule::commit_shuffle(
+                charlie.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                commitment
+            ),
+            Error::<TestRuntime>::NotYourShuffleTurn
+        );
+        assert_err!(
+            OffchainModule::submit_shuffled_votes_and_proof(
+                charlie.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                payload.clone()
+            ),
+            Error::<TestRuntime>::NotYourShuffleTurn
+        );
+
+        // the revealed payload has to match the commitment
+        let mut tampered = payload.clone();
+        tampered.ciphers.reverse();
+        assert_err!(
+            OffchainModule::submit_shuffled_votes_and_proof(
+                bob.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                tampered
+            ),
+            Error::<TestRuntime>::ShuffleCommitmentMismatch
+        );
+
+        // after the timeout, it's charlie's turn and charlie takes over bob's commitment
+        System::set_block_number(commit_block + TestShuffleTurnTimeout::get() + 1);
+        assert_ok!(OffchainModule::commit_shuffle(
+            charlie.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            commitment
+        ));
+        assert_err!(
+            OffchainModule::submit_shuffled_votes_and_proof(
+                bob.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                payload.clone()
+            ),
+            Error::<TestRuntime>::NotYourShuffleTurn
+        );
+
+        // charlie reveals the shuffle in a later block
+        System::set_block_number(System::block_number() + 1);
+        assert_ok!(OffchainModule::submit_shuffled_votes_and_proof(
+            charlie.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            payload
+        ));
+        assert_eq!(
+            OffchainModule::shuffle_commitment((&vote_id, &topic_id)),
+            None
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::ShuffleCommitted(
+                topic_id.clone(),
+                charlie_id.clone()
+            ))));
+    });
+}
+
+#[test]
+fn test_shuffle_turns() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, false);
+
+        // the turn of the first sealer starts with the tallying phase
+        let start = 5;
+        System::set_block_number(start);
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        let (_, bob_id, _) = get_sealer_bob();
+        let (charlie, charlie_id, _) = get_sealer_charlie();
+        let turn = OffchainModule::shuffle_turn((&vote_id, &topic_id));
+        assert_eq!(turn.sealer_index, 0);
+        assert_eq!(turn.since, start);
+
+        // the turn only passes to the next sealer once it timed out, not block by block
+        let sealer_at = |block: u64| {
+            shuffle::turn::get_shuffle_sealer::<TestRuntime>(&vote_id, &topic_id, block)
+        };
+        let timeout = TestShuffleTurnTimeout::get();
+        assert_eq!(sealer_at(start + 1), Some(bob_id));
+        assert_eq!(sealer_at(start + timeout), Some(bob_id));
+        assert_eq!(sealer_at(start + timeout + 1), Some(charlie_id));
+        assert_eq!(sealer_at(start + 2 * timeout + 1), Some(charlie_id));
+        assert_eq!(sealer_at(start + 2 * timeout + 2), Some(bob_id));
+
+        // charlie shuffles once bob's turn timed out
+        System::set_block_number(start + timeout + 1);
+        let shuffle_state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
+            .expect("shuffle state should exist for all existing votes & topics!");
+        let payload: ShufflePayload = OffchainModule::offchain_shuffle_and_proof(
+            &vote_id,
+            &topic_id,
+            shuffle_state.iteration,
+            &pk,
+            shuffle_state.start_position,
+            shuffle_state.batch_size,
+        )
+        .unwrap();
+        assert_ok!(commit_and_reveal_shuffle(
+            charlie.clone(),
+            vote_id.clone(),
+            topic_id.clone(),
+            payload
+        ));
+
+        // the turn passes to bob as soon as charlie revealed its shuffle
+        let turn = OffchainModule::shuffle_turn((&vote_id, &topic_id));
+        assert_eq!(turn.sealer_index, 0);
+        assert_eq!(turn.since, System::block_number());
+        assert_eq!(sealer_at(System::block_number()), Some(bob_id));
+    });
+}
+
+#[test]
+fn test_set_shuffle_batch_size() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+
+        // the batch size of a question is bounded as well
+        let topic = Topic::new(b"20201212-02".to_vec(), b"Budget 2021?".to_vec());
+        assert_err!(
+            OffchainModule::store_question(
+                get_voting_authority(),
+                vote_id.clone(),
+                topic,
+                0
+            ),
+            Error::<TestRuntime>::InvalidShuffleBatchSize
+        );
+
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, false);
+
+        // only a voting authority can set the batch size of an existing vote
+        let (bob, _, _) = get_sealer_bob();
+        let (charlie, _, _) = get_sealer_charlie();
+        assert_err!(
+            OffchainModule::set_shuffle_batch_size(bob.clone(), vote_id.clone(), 4),
+            Error::<TestRuntime>::NotAVotingAuthority
+        );
+        assert_err!(
+            OffchainModule::set_shuffle_batch_size(
+                get_voting_authority(),
+                b"20210101".to_vec(),
+                4
             ),
-            Error::<TestRuntime>::ShuffleStateIncorrect
+            Error::<TestRuntime>::VoteDoesNotExist
         );
 
-        // perform the next shuffle
-        // get the new shuffle state
+        // the batch size is bounded by MinShuffleBatchSize and MaxShuffleBatchSize
+        for batch_size in [0, TestMaxShuffleBatchSize::get() + 1].iter() {
+            assert_err!(
+                OffchainModule::set_shuffle_batch_size(
+                    get_voting_authority(),
+                    vote_id.clone(),
+                    *batch_size
+                ),
+                Error::<TestRuntime>::InvalidShuffleBatchSize
+            );
+        }
+        assert_ok!(OffchainModule::set_shuffle_batch_size(
+            get_voting_authority(),
+            vote_id.clone(),
+            4
+        ));
+        assert_eq!(OffchainModule::shuffle_batch_size(&vote_id), Some(4));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::ShuffleBatchSizeUpdated(
+                vote_id.clone(),
+                4
+            ))));
+
+        // the first batch is shuffled with the batch size once the tallying phase starts
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
         let shuffle_state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
             .expect("shuffle state should exist for all existing votes & topics!");
-        // check that the shuffle state computation is correct
-        assert_eq!(shuffle_state.start_position, 2);
-        assert_eq!(shuffle_state.batch_size, 2);
-        assert_eq!(shuffle_state.iteration, 0);
+        assert_eq!(shuffle_state.batch_size, 4);
 
-        // shuffle the votes + create proof
         let payload: ShufflePayload = OffchainModule::offchain_shuffle_and_proof(
+            &vote_id,
             &topic_id,
             shuffle_state.iteration,
             &pk,
@@ -2106,37 +9070,27 @@ fn test_submit_shuffled_votes_and_proof() {
             shuffle_state.batch_size,
         )
         .unwrap();
-        // submit the proof and the shuffled votes
-        let response = OffchainModule::submit_shuffled_votes_and_proof(
-            bob.clone(),
+        assert_eq!(payload.ciphers.len(), 4);
+        assert_ok!(commit_and_reveal_shuffle(
+            bob,
             vote_id.clone(),
             topic_id.clone(),
-            payload.clone(),
-        );
-        assert_ok!(response);
-
-        // verify that the shuffled votes have been stored
-        // at the new index: shuffle_state.iteration + 1
-        let shuffled_from_chain: Vec<Cipher> =
-            Ciphers::get(&topic_id, shuffle_state.iteration + 1);
-        assert!(!shuffled_from_chain.is_empty());
-        assert_eq!(shuffled_from_chain.len(), 2 * payload.ciphers.len());
-
-        //
-        // State: 4/6 Ciphers Shuffled
-        //
+            payload
+        ));
 
-        // perform the next shuffle
-        // get the new shuffle state
+        // a batch size set while shuffling applies from the next batch on
+        assert_ok!(OffchainModule::set_shuffle_batch_size(
+            get_voting_authority(),
+            vote_id.clone(),
+            3
+        ));
         let shuffle_state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
             .expect("shuffle state should exist for all existing votes & topics!");
-        // check that the shuffle state computation is correct
         assert_eq!(shuffle_state.start_position, 4);
-        assert_eq!(shuffle_state.batch_size, 2);
-        assert_eq!(shuffle_state.iteration, 0);
+        assert_eq!(shuffle_state.batch_size, 4);
 
-        // shuffle the votes + create proof
         let payload: ShufflePayload = OffchainModule::offchain_shuffle_and_proof(
+            &vote_id,
             &topic_id,
             shuffle_state.iteration,
             &pk,
@@ -2144,33 +9098,210 @@ fn test_submit_shuffled_votes_and_proof() {
             shuffle_state.batch_size,
         )
         .unwrap();
-        // submit the proof and the shuffled votes
-        let response = OffchainModule::submit_shuffled_votes_and_proof(
-            bob.clone(),
+        assert_eq!(payload.ciphers.len(), 2);
+        assert_ok!(commit_and_reveal_shuffle(
+            charlie,
             vote_id.clone(),
             topic_id.clone(),
-            payload.clone(),
+            payload
+        ));
+
+        let shuffle_state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
+            .expect("shuffle state should exist for all existing votes & topics!");
+        assert_eq!(shuffle_state.iteration, 1);
+        assert_eq!(shuffle_state.start_position, 0);
+        assert_eq!(shuffle_state.batch_size, 3);
+    });
+}
+
+/// commits to the linked shuffle payload (lead topic) and reveals it in the next block
+fn commit_and_reveal_linked_shuffle(
+    sealer: Origin,
+    vote_id: VoteId,
+    lead_topic_id: TopicId,
+    payload: LinkedShufflePayload,
+) -> DispatchResult {
+    let commitment = shuffle::commitment::hash_shuffle_payload(&payload);
+    OffchainModule::commit_shuffle(
+        sealer.clone(),
+        vote_id.clone(),
+        lead_topic_id,
+        commitment,
+    )?;
+    System::set_block_number(System::block_number() + 1);
+    OffchainModule::submit_linked_shuffle(sealer, vote_id, payload)
+}
+
+#[test]
+fn test_linked_shuffle() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) =
+            setup_vote_in_key_generation(params.into(), TallyStrategy::Mixnet);
+        let second_topic = Topic::new(b"20201212-02".to_vec(), b"Budget 2021?".to_vec());
+        let second_topic_id = second_topic.id.clone();
+        assert_ok!(OffchainModule::store_question(
+            get_voting_authority(),
+            vote_id.clone(),
+            second_topic,
+            2
+        ));
+
+        // only a voting authority can link the shuffles of a vote
+        let (bob, _, _) = get_sealer_bob();
+        let (charlie, _, _) = get_sealer_charlie();
+        assert_err!(
+            OffchainModule::set_linked_shuffle(bob.clone(), vote_id.clone(), true),
+            Error::<TestRuntime>::NotAVotingAuthority
         );
-        assert_ok!(response);
+        assert_ok!(OffchainModule::set_linked_shuffle(
+            get_voting_authority(),
+            vote_id.clone(),
+            true
+        ));
+        assert!(OffchainModule::linked_shuffle(&vote_id));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::pallet_mixnet(RawEvent::LinkedShuffleUpdated(
+                vote_id.clone(),
+                true
+            ))));
 
-        // verify that the shuffled votes have been stored
-        // at the new index: shuffle_state.iteration + 1
-        let shuffled_from_chain: Vec<Cipher> =
-            Ciphers::get(&topic_id, shuffle_state.iteration + 1);
-        assert!(!shuffled_from_chain.is_empty());
-        assert_eq!(shuffled_from_chain.len(), big_ciphers_from_chain.len());
+        // all topics of a vote with linked shuffles use the same batch size
+        let topic = Topic::new(b"20201212-03".to_vec(), b"Tax 2021?".to_vec());
+        assert_err!(
+            OffchainModule::store_question(
+                get_voting_authority(),
+                vote_id.clone(),
+                topic,
+                3
+            ),
+            Error::<TestRuntime>::LinkedShuffleMismatch
+        );
 
-        //
-        // State: 6/6 Ciphers Shuffled, 2nd Shuffle Iteration Starts
-        //
+        // every ballot answers all topics
+        set_vote_phase(vote_id.clone(), VotePhase::Voting);
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        assert_err!(
+            OffchainModule::set_linked_shuffle(
+                get_voting_authority(),
+                vote_id.clone(),
+                false
+            ),
+            Error::<TestRuntime>::VoteAlreadyStarted
+        );
+        let voter = Origin::signed(Default::default());
+        let incomplete = create_ballot(&topic_id, &pk, 21);
+        assert_err!(
+            OffchainModule::cast_ballot(voter.clone(), vote_id.clone(), incomplete),
+            Error::<TestRuntime>::IncompleteLinkedBallot
+        );
+        for random in 1..5u32 {
+            let mut ballot = create_ballot(&topic_id, &pk, random);
+            let second = create_ballot(&second_topic_id, &pk, random + 10);
+            ballot.answers.extend(second.answers);
+            assert_ok!(OffchainModule::cast_ballot(
+                voter.clone(),
+                vote_id.clone(),
+                ballot
+            ));
+        }
+        assert_err!(
+            OffchainModule::spoil_ballot(voter, vote_id.clone(), topic_id.clone()),
+            Error::<TestRuntime>::IncompleteLinkedBallot
+        );
 
-        // get the new shuffle state
-        let shuffle_state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
+        // the topics can't be shuffled on their own
+        set_vote_phase(vote_id.clone(), VotePhase::Tallying);
+        assert_err!(
+            OffchainModule::commit_shuffle(
+                bob.clone(),
+                vote_id.clone(),
+                second_topic_id.clone(),
+                [0u8; 32]
+            ),
+            Error::<TestRuntime>::WrongShuffleMode
+        );
+        let state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
             .expect("shuffle state should exist for all existing votes & topics!");
-        // check that the shuffle state computation is correct
-        assert_eq!(shuffle_state.start_position, 0);
-        assert_eq!(shuffle_state.batch_size, 2);
-        assert_eq!(shuffle_state.iteration, 1);
+        let payload: ShufflePayload = OffchainModule::offchain_shuffle_and_proof(
+            &vote_id,
+            &topic_id,
+            state.iteration,
+            &pk,
+            state.start_position,
+            state.batch_size,
+        )
+        .unwrap();
+        assert_err!(
+            OffchainModule::submit_shuffled_votes_and_proof(
+                bob.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                payload
+            ),
+            Error::<TestRuntime>::WrongShuffleMode
+        );
+
+        // the same batch of both topics is shuffled with the same permutation
+        let topic_ids = vec![topic_id.clone(), second_topic_id.clone()];
+        let payload: LinkedShufflePayload =
+            OffchainModule::offchain_linked_shuffle_and_proof(
+                &vote_id,
+                &topic_ids,
+                state.iteration,
+                &pk,
+                state.start_position,
+                state.batch_size,
+            )
+            .unwrap();
+        assert_eq!(
+            payload[0].1.proof.permutation_commitments,
+            payload[1].1.proof.permutation_commitments
+        );
+        assert_ok!(commit_and_reveal_linked_shuffle(
+            bob,
+            vote_id.clone(),
+            topic_id.clone(),
+            payload
+        ));
+        for id in topic_ids.iter() {
+            let state: ShuffleState = ShuffleStateStore::get((&vote_id, id))
+                .expect("shuffle state should exist for all existing votes & topics!");
+            assert_eq!(state.start_position, 2);
+            assert_eq!(ShuffleProofs::get((&vote_id, id)).len(), 1);
+        }
+
+        // separately shuffled topics use different permutations
+        let state: ShuffleState = ShuffleStateStore::get((&vote_id, &topic_id))
+            .expect("shuffle state should exist for all existing votes & topics!");
+        let payload: LinkedShufflePayload = topic_ids
+            .iter()
+            .map(|id| {
+                let mut linked = OffchainModule::offchain_linked_shuffle_and_proof(
+                    &vote_id,
+                    &[id.clone()],
+                    state.iteration,
+                    &pk,
+                    state.start_position,
+                    state.batch_size,
+                )
+                .unwrap();
+                linked.remove(0)
+            })
+            .collect();
+        assert_err!(
+            commit_and_reveal_linked_shuffle(
+                charlie.clone(),
+                vote_id.clone(),
+                topic_id.clone(),
+                payload
+            ),
+            Error::<TestRuntime>::LinkedShuffleMismatch
+        );
+        let state: ShuffleState = ShuffleStateStore::get((&vote_id, &second_topic_id))
+            .expect("shuffle state should exist for all existing votes & topics!");
+        assert_eq!(state.start_position, 2);
     });
 }
 
@@ -2189,7 +9320,445 @@ fn test_setup_ciphers_nr_of_shuffles_not_correct() {
 
         // get the encrypted votes from chain @ nr_of_shuffles + 1
         let new_nr_of_shuffles = nr_of_shuffles + 1;
-        let from_chain: Vec<Cipher> = Ciphers::get(&topic_id, new_nr_of_shuffles);
+        let from_chain: Vec<Cipher> =
+            OffchainModule::ciphers(&vote_id, &topic_id, new_nr_of_shuffles);
         assert!(from_chain.is_empty());
     });
 }
+
+#[test]
+fn test_migrate_storage_of_initial_release() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // new chains start at the current version
+        assert_eq!(OffchainModule::storage_version(), STORAGE_VERSION);
+
+        // a vote, ballot and ciphers stored by the initial release
+        StorageVersion::put(0);
+        let (params, _, pk) = Helper::setup_sm_system();
+        let vote_id = "20201212".as_bytes().to_vec();
+        let topic_id = "20201212-01".as_bytes().to_vec();
+        let (_, voter, _) = get_sealer_bob();
+        let ciphers: Vec<Cipher> = (1..4u32)
+            .map(|r| {
+                ElGamal::encrypt_encode(&BigUint::from(1u32), &BigUint::from(r), &pk)
+                    .into()
+            })
+            .collect();
+
+        let old_vote = VoteV0 {
+            voting_authority: voter,
+            title: vote_id.clone(),
+            phase: VotePhase::Voting,
+            params: params.into(),
+        };
+        let vote_key = Blake2_128Concat::hash(&vote_id.encode());
+        put_storage_value(b"OffchainModule", b"Votes", &vote_key, old_vote);
+
+        let question = "Moritz for President?".as_bytes().to_vec();
+        let old_topics: Vec<TopicV1> = vec![(topic_id.clone(), question)];
+        put_storage_value(b"OffchainModule", b"Topics", &vote_key, old_topics);
+
+        let old_ballot = BallotV0 {
+            answers: vec![(topic_id.clone(), ciphers[0].clone())],
+        };
+        let ballot_key = [vote_key, Blake2_128Concat::hash(&voter.encode())].concat();
+        put_storage_value(b"OffchainModule", b"Ballots", &ballot_key, old_ballot);
+
+        let cipher_key = [
+            Blake2_128Concat::hash(&topic_id.encode()),
+            Blake2_128Concat::hash(&0u8.encode()),
+        ]
+        .concat();
+        put_storage_value(b"OffchainModule", b"Ciphers", &cipher_key, ciphers.clone());
+
+        // migrate
+        OffchainModule::on_runtime_upgrade();
+        assert_eq!(OffchainModule::storage_version(), STORAGE_VERSION);
+
+        let vote = OffchainModule::votes(&vote_id);
+        assert_eq!(vote.phase, VotePhase::Voting);
+        assert_eq!(vote.nr_of_shuffles, 3);
+        assert_eq!(vote.tally_strategy, TallyStrategy::Mixnet);
+        assert!(OffchainModule::voting_started(&vote_id));
+
+        let ballot = OffchainModule::ballots(&vote_id, voter);
+        assert_eq!(ballot.answers, vec![(topic_id.clone(), ciphers[0].clone())]);
+        assert!(ballot.proofs.is_empty());
+
+        assert_eq!(OffchainModule::ciphers(&vote_id, &topic_id, 0), ciphers);
+        assert!(OffchainModule::cipher_root((&vote_id, &topic_id), 0).is_some());
+
+        // the migration only runs once
+        OffchainModule::on_runtime_upgrade();
+        assert_eq!(OffchainModule::ciphers(&vote_id, &topic_id, 0), ciphers);
+    });
+}
+
+#[test]
+fn test_migrate_storage_to_question_types() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // a vote proposal, topics and a ballot stored by version 1
+        StorageVersion::put(1);
+        let (params, _, pk) = Helper::setup_sm_system();
+        let vote_id = "20201212".as_bytes().to_vec();
+        let topic_id = "20201212-01".as_bytes().to_vec();
+        let question = "Moritz for President?".as_bytes().to_vec();
+        let (_, voter, _) = get_sealer_bob();
+
+        let old_topics: Vec<TopicV1> = vec![(topic_id.clone(), question.clone())];
+        let vote_key = Blake2_128Concat::hash(&vote_id.encode());
+        put_storage_value(b"OffchainModule", b"Topics", &vote_key, old_topics.clone());
+
+        let old_proposal = VoteProposalV1 {
+            vote: Vote {
+                voting_authority: voter,
+                title: vote_id.clone(),
+                phase: VotePhase::KeyGeneration,
+                params: params.into(),
+                nr_of_shuffles: 3,
+                tally_strategy: TallyStrategy::Mixnet,
+            },
+            topics: old_topics,
+            batch_size: 2,
+            approvals: vec![voter],
+        };
+        put_storage_value(b"OffchainModule", b"VoteProposals", &vote_key, old_proposal);
+
+        let cipher: Cipher =
+            ElGamal::encrypt_encode(&BigUint::from(1u32), &BigUint::from(7u32), &pk)
+                .into();
+        let old_ballot = BallotV1 {
+            answers: vec![(topic_id.clone(), cipher.clone())],
+            proofs: Vec::new(),
+        };
+        let ballot_key = [vote_key, Blake2_128Concat::hash(&voter.encode())].concat();
+        put_storage_value(b"OffchainModule", b"Ballots", &ballot_key, old_ballot);
+
+        // migrate
+        OffchainModule::on_runtime_upgrade();
+        assert_eq!(OffchainModule::storage_version(), STORAGE_VERSION);
+
+        // the existing topics become free-form questions
+        let topics = vec![Topic::new(topic_id.clone(), question)];
+        assert_eq!(OffchainModule::topics(&vote_id), topics);
+        let proposal = OffchainModule::vote_proposal(&vote_id).unwrap();
+        assert_eq!(proposal.topics, topics);
+        assert_eq!(proposal.approvals, vec![voter]);
+
+        let ballot = OffchainModule::ballots(&vote_id, voter);
+        assert_eq!(ballot.answers, vec![(topic_id, cipher)]);
+        assert!(ballot.validity_proofs.is_empty());
+    });
+}
+
+#[test]
+fn test_migrate_storage_to_ranked_tally_methods() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // a vote proposal and topics stored by version 2
+        StorageVersion::put(2);
+        let (params, _, _) = Helper::setup_sm_system();
+        let vote_id = "20201212".as_bytes().to_vec();
+        let (_, voter, _) = get_sealer_bob();
+
+        let old_topics = vec![TopicV2 {
+            id: "20201212-01".as_bytes().to_vec(),
+            question: "Who for President?".as_bytes().to_vec(),
+            question_type: QuestionType::Ranked { candidates: 2 },
+            candidates: vec!["Alice".as_bytes().to_vec(), "Bob".as_bytes().to_vec()],
+        }];
+        let vote_key = Blake2_128Concat::hash(&vote_id.encode());
+        put_storage_value(b"OffchainModule", b"Topics", &vote_key, old_topics.clone());
+
+        let old_proposal = VoteProposalV2 {
+            vote: Vote {
+                voting_authority: voter,
+                title: vote_id.clone(),
+                phase: VotePhase::KeyGeneration,
+                params: params.into(),
+                nr_of_shuffles: 3,
+                tally_strategy: TallyStrategy::Mixnet,
+            },
+            topics: old_topics.clone(),
+            batch_size: 2,
+            approvals: vec![voter],
+        };
+        put_storage_value(b"OffchainModule", b"VoteProposals", &vote_key, old_proposal);
+
+        // migrate
+        OffchainModule::on_runtime_upgrade();
+        assert_eq!(OffchainModule::storage_version(), STORAGE_VERSION);
+
+        // the existing ranked questions are tallied by first preference
+        let topics = vec![Topic {
+            id: old_topics[0].id.clone(),
+            question: old_topics[0].question.clone(),
+            question_type: QuestionType::Ranked { candidates: 2 },
+            candidates: old_topics[0].candidates.clone(),
+            ranked_tally: RankedTallyMethod::FirstPreference,
+        }];
+        assert_eq!(OffchainModule::topics(&vote_id), topics);
+        let proposal = OffchainModule::vote_proposal(&vote_id).unwrap();
+        assert_eq!(proposal.topics, topics);
+    });
+}
+
+#[test]
+fn test_migrate_storage_to_cipher_counts() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // ciphers stored by version 3, i.e. without their number
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, false);
+        let ciphers = OffchainModule::ciphers(&vote_id, &topic_id, 0);
+        let shuffled = ciphers[..3].to_vec();
+        helpers::ciphers::append_ciphers::<TestRuntime>(&vote_id, &topic_id, 1, shuffled);
+        CipherCounts::remove_prefix((&vote_id, &topic_id));
+        move_to_legacy_cipher_keys(&vote_id, &topic_id);
+        StorageVersion::put(3);
+
+        // migrate
+        OffchainModule::on_runtime_upgrade();
+        assert_eq!(OffchainModule::storage_version(), STORAGE_VERSION);
+
+        // the ciphers of all shuffle iterations are counted
+        assert_eq!(OffchainModule::cipher_count((&vote_id, &topic_id), 0), 6);
+        assert_eq!(OffchainModule::cipher_count((&vote_id, &topic_id), 1), 3);
+        assert_eq!(OffchainModule::cipher_count((&vote_id, &topic_id), 2), 0);
+        assert_eq!(OffchainModule::ciphers(&vote_id, &topic_id, 0), ciphers);
+    });
+}
+
+#[test]
+fn test_migrate_storage_to_protocol_configs() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // a vote stored by version 4, i.e. without its protocol parameters
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, _) = setup_vote(params.into());
+        ProtocolConfigs::remove(&vote_id);
+        StorageVersion::put(4);
+
+        // migrate
+        OffchainModule::on_runtime_upgrade();
+        assert_eq!(OffchainModule::storage_version(), STORAGE_VERSION);
+
+        // the vote has been created with the first version of the protocol
+        let nr_of_shuffles = OffchainModule::votes(&vote_id).nr_of_shuffles;
+        assert_eq!(
+            OffchainModule::protocol_config(&vote_id),
+            Some(ProtocolConfig::new(nr_of_shuffles))
+        );
+    });
+}
+
+#[test]
+fn test_migrate_storage_to_sealer_epochs() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // a vote stored by version 5, i.e. without the sealer epochs
+        let (params, _, _) = Helper::setup_sm_system();
+        let (vote_id, _) = setup_vote(params.into());
+        SealerSets::<TestRuntime>::remove(0);
+        VoteSealerEpochs::remove(&vote_id);
+        StorageVersion::put(5);
+
+        // migrate
+        OffchainModule::on_runtime_upgrade();
+        assert_eq!(OffchainModule::storage_version(), STORAGE_VERSION);
+
+        // the vote belongs to the first epoch, i.e., to the current sealers
+        assert_eq!(OffchainModule::vote_sealer_epoch(&vote_id), 0);
+        assert_eq!(OffchainModule::sealer_set(0), OffchainModule::sealers());
+        assert_eq!(
+            helpers::sealers::get_vote_sealers::<TestRuntime>(&vote_id),
+            OffchainModule::sealers()
+        );
+    });
+}
+
+#[test]
+fn test_every_error_has_an_error_code() {
+    use frame_support::{error::ModuleErrorMetadata, metadata::DecodeDifferent};
+
+    let errors = Error::<TestRuntime>::metadata();
+    for error in errors.iter() {
+        let name = match error.name {
+            DecodeDifferent::Encode(name) => name,
+            DecodeDifferent::Decoded(_) => unreachable!("the metadata is static"),
+        };
+        assert!(
+            ErrorCode::from_name(name).is_some(),
+            "{} has no error code",
+            name
+        );
+    }
+    assert_eq!(errors.len(), ErrorCode::ALL.len());
+
+    assert_eq!(
+        ErrorCode::from(Error::<TestRuntime>::WrongVotePhase),
+        ErrorCode::WrongVotePhase
+    );
+    assert_eq!(
+        ErrorCode::from(Error::<TestRuntime>::NotYourShuffleTurn).code(),
+        61
+    );
+}
+
+/// moves all values of the storage item from the key (vote_id, topic_id)
+/// to the key topic_id, i.e., to their keys before they were keyed by the vote
+fn move_to_legacy_keys<V: Decode + Encode>(
+    item: &[u8],
+    vote_id: &VoteId,
+    topic_id: &TopicId,
+) {
+    let prefix = Blake2_128Concat::hash(&(vote_id, topic_id).encode());
+    let values: Vec<(Vec<u8>, V)> = StorageIterator::<V>::new(b"OffchainModule", item)
+        .drain()
+        .collect();
+    for (key, value) in values.into_iter() {
+        let legacy_key = if key.starts_with(&prefix) {
+            [
+                Blake2_128Concat::hash(&topic_id.encode()),
+                key[prefix.len()..].to_vec(),
+            ]
+            .concat()
+        } else {
+            key
+        };
+        put_storage_value(b"OffchainModule", item, &legacy_key, value);
+    }
+}
+
+/// moves the ciphers of the topic, their counts, merkle trees and hashes
+/// to their keys before they were keyed by the vote
+fn move_to_legacy_cipher_keys(vote_id: &VoteId, topic_id: &TopicId) {
+    move_to_legacy_keys::<Vec<Cipher>>(b"Ciphers", vote_id, topic_id);
+    move_to_legacy_keys::<ChunkIndex>(b"CipherChunkCount", vote_id, topic_id);
+    move_to_legacy_keys::<u64>(b"CipherCounts", vote_id, topic_id);
+    move_to_legacy_keys::<[u8; 32]>(b"CipherRoots", vote_id, topic_id);
+    move_to_legacy_keys::<Vec<Option<[u8; 32]>>>(b"CipherPeaks", vote_id, topic_id);
+    move_to_legacy_keys::<bool>(b"CipherHashes", vote_id, topic_id);
+}
+
+#[test]
+fn test_migrate_storage_to_ciphers_keyed_by_vote() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // the storage of a topic stored by version 6, i.e. keyed by the topic only
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        setup_public_key(vote_id.clone(), pk.clone().into());
+        setup_ciphers(&vote_id, &topic_id, &pk, false);
+        let ciphers = OffchainModule::ciphers(&vote_id, &topic_id, 0);
+        let root = OffchainModule::cipher_root((&vote_id, &topic_id), 0);
+        let (_, bob, _) = get_sealer_bob();
+        let key = (&vote_id, &topic_id);
+        let result: TopicResult = vec![(vec![1u8], vec![2u8])].into_iter().collect();
+        let partial_tally: PartialTally = vec![(vec![1u8], 2u64)].into_iter().collect();
+        AdditionalWeights::insert(key, 3);
+        AggregateCiphers::insert(key, ciphers[0].clone());
+        Tally::insert(key, result.clone());
+        PackedTally::insert(key, 0, result.clone());
+        PartialTallies::insert(key, partial_tally.clone());
+        DecryptedShares::<TestRuntime>::insert(key, bob, vec![vec![1u8]]);
+        KeySwitchShares::<TestRuntime>::insert(key, bob, vec![ciphers[1].clone()]);
+
+        move_to_legacy_cipher_keys(&vote_id, &topic_id);
+        move_to_legacy_keys::<u64>(b"AdditionalWeights", &vote_id, &topic_id);
+        move_to_legacy_keys::<Cipher>(b"AggregateCiphers", &vote_id, &topic_id);
+        move_to_legacy_keys::<TopicResult>(b"Tally", &vote_id, &topic_id);
+        move_to_legacy_keys::<TopicResult>(b"PackedTally", &vote_id, &topic_id);
+        move_to_legacy_keys::<PartialTally>(b"PartialTallies", &vote_id, &topic_id);
+        move_to_legacy_keys::<Vec<Vec<u8>>>(b"DecryptedShares", &vote_id, &topic_id);
+        move_to_legacy_keys::<Vec<Cipher>>(b"KeySwitchShares", &vote_id, &topic_id);
+        assert!(OffchainModule::ciphers(&vote_id, &topic_id, 0).is_empty());
+        assert_eq!(OffchainModule::tally(key), None);
+
+        // the same topic id of another vote which is already keyed by the vote
+        let other_vote_id = "20201213".as_bytes().to_vec();
+        let other_result: TopicResult =
+            vec![(vec![4u8], vec![1u8])].into_iter().collect();
+        Tally::insert((&other_vote_id, &topic_id), other_result.clone());
+        StorageVersion::put(6);
+
+        // migrate
+        OffchainModule::on_runtime_upgrade();
+        assert_eq!(OffchainModule::storage_version(), STORAGE_VERSION);
+
+        // the ciphers are keyed by the vote, the counts and merkle roots are kept
+        assert_eq!(OffchainModule::ciphers(&vote_id, &topic_id, 0), ciphers);
+        assert_eq!(OffchainModule::cipher_count(key, 0), ciphers.len() as u64);
+        assert_eq!(OffchainModule::cipher_root(key, 0), root);
+        assert_eq!(CipherHashes::iter_prefix_values(key).count(), ciphers.len());
+
+        // the remaining storage of the topic is keyed by the vote
+        assert_eq!(OffchainModule::additional_weight(key), 3);
+        assert_eq!(
+            OffchainModule::aggregate_cipher(key),
+            Some(ciphers[0].clone())
+        );
+        assert_eq!(OffchainModule::tally(key), Some(result.clone()));
+        assert_eq!(OffchainModule::packed_tally(key, 0), result);
+        assert_eq!(OffchainModule::partial_tally(key), partial_tally);
+        assert_eq!(OffchainModule::decrypted_shares(key, bob), vec![vec![1u8]]);
+        assert_eq!(
+            KeySwitchShares::<TestRuntime>::get(key, bob),
+            vec![ciphers[1].clone()]
+        );
+
+        // the other vote keeps its own storage and doesn't see the ciphers
+        assert_eq!(
+            OffchainModule::tally((&other_vote_id, &topic_id)),
+            Some(other_result)
+        );
+        assert!(OffchainModule::ciphers(&other_vote_id, &topic_id, 0).is_empty());
+        assert_eq!(
+            OffchainModule::cipher_count((&other_vote_id, &topic_id), 0),
+            0
+        );
+    });
+}
+
+#[test]
+fn test_migrate_storage_to_ballots_with_credentials() {
+    let (mut t, _, _) = ExternalityBuilder::build();
+    t.execute_with(|| {
+        // a ballot stored by version 7, i.e. without a credential
+        let (params, _, pk) = Helper::setup_sm_system();
+        let (vote_id, topic_id) = setup_vote(params.into());
+        let (_, voter, _) = get_sealer_bob();
+        let old_ballot = BallotV2 {
+            answers: create_ballot(&topic_id, &pk, 7).answers,
+            proofs: Vec::new(),
+            validity_proofs: vec![None],
+        };
+        let ballot_key = [
+            Blake2_128Concat::hash(&vote_id.encode()),
+            Blake2_128Concat::hash(&voter.encode()),
+        ]
+        .concat();
+        put_storage_value(
+            b"OffchainModule",
+            b"Ballots",
+            &ballot_key,
+            old_ballot.clone(),
+        );
+        StorageVersion::put(7);
+
+        // migrate
+        OffchainModule::on_runtime_upgrade();
+        assert_eq!(OffchainModule::storage_version(), STORAGE_VERSION);
+
+        // the ballot carries no credential, i.e., it is counted without a credential check
+        let ballot = OffchainModule::ballots(&vote_id, voter);
+        assert_eq!(ballot.answers, old_ballot.answers);
+        assert_eq!(ballot.validity_proofs, old_ballot.validity_proofs);
+        assert_eq!(ballot.credential, None);
+        assert!(OffchainModule::credential_ballots(&vote_id).is_empty());
+    });
+}
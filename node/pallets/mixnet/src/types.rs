@@ -1,304 +1,380 @@
 use alloc::str::FromStr;
 use codec::{Decode, Encode};
-use crypto::proofs::{decryption::DecryptionProof, keygen::KeyGenerationProof};
-use crypto::types::{Cipher as BigCipher, ElGamalParams, PublicKey as ElGamalPK};
+use crypto::encryption::DEFAULT_DECODE_BOUND;
+use crypto::helper::{BATCH_EXPONENT_BITS, GENERATOR_DOMAIN, MAX_BATCH_EXPONENT_BITS};
 use frame_system::offchain::{SignedPayload, SigningTypes};
-use num_bigint::BigUint;
-use num_traits::One;
+use sp_core::{ed25519, sr25519};
 use sp_runtime::RuntimeDebug;
 use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
-/// the BigCipher from the crypto crate.
-/// different types which the blockchain can handle.
+/// the wire types (and their conversions from and to the types of the crypto crate)
+/// are shared with the clients, see: `mixnet_primitives::wire`
+pub use mixnet_primitives::wire::{
+    BallotProof, BigS, BigSAsBytes, BigTAsBytes, Cipher, CredentialBlindingProof,
+    DecryptedShareProof, EligibilityKey, KeySwitchShareProof, PublicKey,
+    PublicKeyShareProof, PublicParameters, QAsBigUint, QAsVecU8, ShuffleProof,
+    ShuffleProofAsBytes, ValidityProof, Wrapper,
+};
+
+/// the nullifier of a blind-signed eligibility token (anonymous ballots), see: `mixnet_primitives::tokens`
+pub use mixnet_primitives::tokens::Nullifier;
+
+// the payload submitted after performing a shuffle proof in an offchain worker
+// contains the shuffle proof and the shuffle_votes
 #[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
-pub struct Cipher {
-    pub a: Vec<u8>,
-    pub b: Vec<u8>,
+pub struct ShufflePayload {
+    pub iteration: u8,
+    pub ciphers: Vec<Cipher>,
+    pub proof: ShuffleProofAsBytes,
+    pub start_position: u64,
+    pub batch_size: u64,
 }
 
-impl Into<Cipher> for BigCipher {
-    fn into(self) -> Cipher {
-        Cipher {
-            a: self.a.to_bytes_be(),
-            b: self.b.to_bytes_be(),
-        }
-    }
-}
+// the payload of a linked shuffle, i.e., the shuffles of the same batch of all topics
+// in the order of the topics. all topics are shuffled with the same permutation.
+pub type LinkedShufflePayload = Vec<(TopicId, ShufflePayload)>;
 
-impl Into<BigCipher> for Cipher {
-    fn into(self) -> BigCipher {
-        BigCipher {
-            a: BigUint::from_bytes_be(&self.a),
-            b: BigUint::from_bytes_be(&self.b),
-        }
-    }
+// a data type to store information about the current status of the shuffle operations
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct ShuffleState {
+    pub iteration: u8,
+    pub start_position: u64,
+    pub batch_size: u64,
+    pub done: bool,
 }
 
-/// required to perform into() conversion for trait Vec
-/// for Vec<Cipher> is not allowed, since trait Vec is not defined here
-pub struct Wrapper<T>(pub Vec<T>);
+/// the key prefix of the shuffle status of a topic in the offchain index,
+/// followed by the SCALE encoded (vote_id, topic_id)
+pub const SHUFFLE_STATUS_PREFIX: &[u8] = b"provotum::mixnet::index::shuffle_status::";
 
-impl Into<Vec<BigCipher>> for Wrapper<Cipher> {
-    fn into(self) -> Vec<BigCipher> {
-        self.0
-            .into_iter()
-            .map(|v| v.into())
-            .collect::<Vec<BigCipher>>()
-    }
+/// the shuffle state of a topic as written to the offchain index, i.e., the mixing progress
+/// served by the nodes with offchain indexing enabled (see: `helpers::indexing`)
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct ShuffleStatus<BlockNumber> {
+    pub state: ShuffleState,
+    /// the block in which the shuffle state changed
+    pub updated_at: BlockNumber,
 }
 
-impl Into<Vec<Cipher>> for Wrapper<BigCipher> {
-    fn into(self) -> Vec<Cipher> {
-        self.0
-            .into_iter()
-            .map(|v| v.into())
-            .collect::<Vec<Cipher>>()
-    }
-}
+pub type VoteId = Vec<u8>;
+pub type Title = Vec<u8>;
+pub type VoteTemplateId = Vec<u8>;
 
-/// the PublicKey from the crypto crate.
-/// different types which the blockchain can handle.
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
-pub struct PublicKey {
-    pub params: PublicParameters,
-    pub h: Vec<u8>,
+// both types are strings encoded as bytes
+pub type NrOfShuffles = u8;
+pub type TopicId = Vec<u8>;
+pub type TopicQuestion = Vec<u8>;
+
+// index of a chunk of ciphers in the cipher storage
+pub type ChunkIndex = u32;
+
+// result types
+pub type Plaintext = Vec<u8>;
+pub type Count = Vec<u8>;
+pub type TopicResult = BTreeMap<Plaintext, Count>;
+// the number of ciphers per decoded plaintext of the batches tallied so far
+pub type PartialTally = BTreeMap<Plaintext, u64>;
+
+// the name of a candidate (string as Vec<u8>)
+pub type Candidate = Vec<u8>;
+
+/// the maximal number of candidates of a question.
+pub const MAX_CANDIDATES: u32 = 8;
+
+/// the maximal number of valid answers of a question with candidates.
+/// the validity proof of an answer contains one challenge and response per valid answer.
+pub const MAX_VALID_ANSWERS: u64 = 256;
+
+/// the maximal length of the reason of a cancelled vote (string as Vec<u8>).
+pub const MAX_CANCELLATION_REASON_LENGTH: usize = 256;
+
+/// the maximal number of ciphers of a batch imported by the voting authority, see: helpers::imports.
+pub const MAX_CIPHERS_PER_IMPORT: usize = 1000;
+
+/// the maximal length of the source tag of an imported batch of ciphers (string as Vec<u8>).
+pub const MAX_SOURCE_TAG_LENGTH: usize = 64;
+
+/// the maximal number of topics of a vote template, see: helpers::templates.
+pub const MAX_TOPICS_PER_TEMPLATE: usize = 32;
+
+/// the maximal number of answers packed into a single cipher, see: TopicPacking.
+pub const MAX_PACKED_SLOTS: u32 = 64;
+
+/// the type of a question decides which answers are valid and how they are tallied.
+/// every answer is a single encoded message (g^m), the message m is:
+/// - SingleChoice: the index of the selected candidate
+/// - MultiSelect: the bitmask of the selected candidates (bit i -> candidate i)
+/// - Ranked: the ranking, see: `Topic::encode_ranking`
+/// questions without candidates are free-form, i.e., any message is accepted (yes/no questions).
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub enum QuestionType {
+    /// exactly one candidate is selected
+    SingleChoice,
+    /// at most max candidates are selected
+    MultiSelect { max: u32 },
+    /// the given number of candidates are ranked (in order of preference)
+    Ranked { candidates: u32 },
 }
 
-impl Into<PublicKey> for ElGamalPK {
-    fn into(self) -> PublicKey {
-        PublicKey {
-            params: self.params.into(),
-            h: self.h.to_bytes_be(),
-        }
+impl Default for QuestionType {
+    fn default() -> Self {
+        QuestionType::SingleChoice
     }
 }
 
-impl Into<ElGamalPK> for PublicKey {
-    fn into(self) -> ElGamalPK {
-        ElGamalPK {
-            params: self.params.into(),
-            h: BigUint::from_bytes_be(&self.h),
-        }
+/// the tally method of a ranked question, see: `tally`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub enum RankedTallyMethod {
+    /// counts the first preference of every ranking
+    FirstPreference,
+    /// every ranked candidate gets points by its rank
+    Borda,
+    /// eliminates the candidate with the fewest votes until a candidate has a majority
+    InstantRunoff,
+}
+
+impl Default for RankedTallyMethod {
+    fn default() -> Self {
+        RankedTallyMethod::FirstPreference
     }
 }
 
-/// the ElGamalParams from the crypto crate.
-/// different types which the blockchain can handle.
+/// a round of an instant-runoff tally
 #[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
-pub struct PublicParameters {
-    pub p: Vec<u8>,
-    // 1. public generator g
-    pub g: Vec<u8>,
-    // 2. public generator h
-    pub h: Vec<u8>,
+pub struct TallyRound {
+    /// the number of votes per remaining candidate (index)
+    pub counts: BTreeMap<u32, u64>,
+    /// the candidate which is eliminated after the round, None in the final round
+    pub eliminated: Option<u32>,
 }
 
-/// defines the function q = (p - 1) / 2 with return type BigUint.
-/// implemented for PublicParameters (i.e. ElGamalParams from the crypto crate)
-pub trait QAsBigUint {
-    fn q(&self) -> BigUint;
+/// the progress of a tally which combines the decrypted shares batch by batch
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct TallyProgress {
+    /// all batches of the topic must be combined with the same parameters
+    pub encoded: bool,
+    pub nr_of_shuffles: NrOfShuffles,
+    /// the number of ciphers which have been combined and decoded
+    pub position: u64,
+    /// the number of ciphers to decrypt
+    pub total: u64,
 }
 
-impl QAsBigUint for PublicParameters {
-    fn q(&self) -> BigUint {
-        let p: BigUint = BigUint::from_bytes_be(&self.p);
-        let q: BigUint = (p - BigUint::one()) / BigUint::from(2u32);
-        q
+impl TallyProgress {
+    pub fn is_complete(&self) -> bool {
+        self.position >= self.total
     }
 }
 
-/// defines the function q = (p - 1) / 2 with return type Vec<u8>
-/// implemented for PublicParameters (i.e. ElGamalParams from the crypto crate)
-pub trait QAsVecU8 {
-    fn q(&self) -> Vec<u8>;
+/// a question of a vote
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct Topic {
+    pub id: TopicId,
+    pub question: TopicQuestion,
+    pub question_type: QuestionType,
+    pub candidates: Vec<Candidate>,
+    /// only ranked questions can be tallied by another method than FirstPreference
+    pub ranked_tally: RankedTallyMethod,
 }
 
-impl QAsVecU8 for PublicParameters {
-    fn q(&self) -> Vec<u8> {
-        let p: BigUint = BigUint::from_bytes_be(&self.p);
-        let q: BigUint = (p - BigUint::one()) / BigUint::from(2u32);
-        q.to_bytes_be()
+impl Topic {
+    /// creates a free-form single choice question, i.e., without candidates
+    pub fn new(id: TopicId, question: TopicQuestion) -> Self {
+        Topic {
+            id,
+            question,
+            question_type: QuestionType::SingleChoice,
+            candidates: Vec::new(),
+            ranked_tally: RankedTallyMethod::FirstPreference,
+        }
     }
-}
 
-impl Into<PublicParameters> for ElGamalParams {
-    fn into(self) -> PublicParameters {
-        PublicParameters {
-            p: self.p.to_bytes_be(),
-            g: self.g.to_bytes_be(),
-            h: self.h.to_bytes_be(),
-        }
+    /// true if the answers need to prove that they are valid, i.e., the question has candidates
+    pub fn requires_validity_proof(&self) -> bool {
+        !self.candidates.is_empty()
     }
-}
 
-impl Into<ElGamalParams> for PublicParameters {
-    fn into(self) -> ElGamalParams {
-        ElGamalParams {
-            p: BigUint::from_bytes_be(&self.p),
-            g: BigUint::from_bytes_be(&self.g),
-            h: BigUint::from_bytes_be(&self.h),
+    /// returns the number of valid answers (excluding NO_ANSWER)
+    /// without creating them, None if the number overflows.
+    pub fn nr_of_valid_answers(&self) -> Option<u64> {
+        let n = self.candidates.len() as u64;
+        match self.question_type {
+            QuestionType::SingleChoice => Some(n),
+            QuestionType::MultiSelect { max } => {
+                // the number of subsets with at most max candidates: sum(n choose j)
+                let mut binomial: u64 = 1;
+                let mut sum: u64 = 1;
+                for j in 1..=(max as u64).min(n) {
+                    binomial = binomial.checked_mul(n - j + 1)? / j;
+                    sum = sum.checked_add(binomial)?;
+                }
+                Some(sum)
+            }
+            QuestionType::Ranked { candidates } => {
+                // the number of k-permutations of n: n! / (n - k)!
+                let k = candidates as u64;
+                if k > n {
+                    return Some(0);
+                }
+                ((n - k + 1)..=n).try_fold(1u64, |product, i| product.checked_mul(i))
+            }
         }
     }
-}
 
-/// Algorithm 8.47: The s value of the ShuffleProof
-#[derive(Default, Clone, PartialEq, Eq, Debug)]
-pub struct BigS {
-    pub s1: BigUint,               // s1
-    pub s2: BigUint,               // s2
-    pub s3: BigUint,               // s3
-    pub s4: BigUint,               // s4
-    pub vec_s_hat: Vec<BigUint>,   // vec_s_hat
-    pub vec_s_tilde: Vec<BigUint>, // vec_s_tilde
-}
+    /// true if the question can be answered and its answers can be proven to be valid.
+    /// free-form questions are only valid as single choice questions.
+    pub fn is_valid(&self) -> bool {
+        let n = self.candidates.len() as u32;
+        let valid_type = match self.question_type {
+            QuestionType::SingleChoice => true,
+            QuestionType::MultiSelect { max } => n > 0 && max > 0 && max <= n,
+            QuestionType::Ranked { candidates } => {
+                n > 0 && candidates > 0 && candidates <= n
+            }
+        };
+        let valid_size = !self.requires_validity_proof()
+            || (n <= MAX_CANDIDATES
+                && self
+                    .nr_of_valid_answers()
+                    .map_or(false, |count| count <= MAX_VALID_ANSWERS));
+        let valid_tally = match self.question_type {
+            QuestionType::Ranked { .. } => true,
+            _ => self.ranked_tally == RankedTallyMethod::FirstPreference,
+        };
+        valid_type && valid_size && valid_tally
+    }
 
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
-pub struct BigSAsBytes {
-    pub s1: Vec<u8>,               // s1
-    pub s2: Vec<u8>,               // s2
-    pub s3: Vec<u8>,               // s3
-    pub s4: Vec<u8>,               // s4
-    pub vec_s_hat: Vec<Vec<u8>>,   // vec_s_hat
-    pub vec_s_tilde: Vec<Vec<u8>>, // vec_s_tilde
-}
-
-impl Into<BigS> for BigSAsBytes {
-    fn into(self) -> BigS {
-        BigS {
-            s1: BigUint::from_bytes_be(&self.s1),
-            s2: BigUint::from_bytes_be(&self.s2),
-            s3: BigUint::from_bytes_be(&self.s3),
-            s4: BigUint::from_bytes_be(&self.s4),
-            vec_s_hat: self
-                .vec_s_hat
-                .iter()
-                .map(|v| BigUint::from_bytes_be(v))
-                .collect::<Vec<BigUint>>(),
-            vec_s_tilde: self
-                .vec_s_tilde
-                .iter()
-                .map(|v| BigUint::from_bytes_be(v))
-                .collect::<Vec<BigUint>>(),
-        }
+    /// encodes the ranking: m = sum((c_i + 1) * (n + 1)^i) with c_i the candidate at rank i.
+    pub fn encode_ranking(&self, ranking: &[u32]) -> u64 {
+        let base = self.candidates.len() as u64 + 1;
+        ranking.iter().rev().fold(0u64, |message, candidate| {
+            message * base + *candidate as u64 + 1
+        })
     }
-}
 
-impl Into<BigSAsBytes> for BigS {
-    fn into(self) -> BigSAsBytes {
-        BigSAsBytes {
-            s1: self.s1.to_bytes_be(),
-            s2: self.s2.to_bytes_be(),
-            s3: self.s3.to_bytes_be(),
-            s4: self.s4.to_bytes_be(),
-            vec_s_hat: self
-                .vec_s_hat
-                .into_iter()
-                .map(|v| v.to_bytes_be())
-                .collect::<Vec<Vec<u8>>>(),
-            vec_s_tilde: self
-                .vec_s_tilde
-                .into_iter()
-                .map(|v| v.to_bytes_be())
-                .collect::<Vec<Vec<u8>>>(),
+    /// decodes a ranked answer, None if the message is not a valid ranking of the question.
+    pub fn decode_ranking(&self, message: u64) -> Option<Vec<u32>> {
+        let k = match self.question_type {
+            QuestionType::Ranked { candidates } => candidates,
+            _ => return None,
+        };
+        let base = self.candidates.len() as u64 + 1;
+        let mut ranking: Vec<u32> = Vec::with_capacity(k as usize);
+        let mut rest = message;
+        for _ in 0..k {
+            let digit = rest % base;
+            rest /= base;
+            // every rank must be filled with a different candidate
+            if digit == 0 || ranking.contains(&(digit as u32 - 1)) {
+                return None;
+            }
+            ranking.push(digit as u32 - 1);
+        }
+        if rest != 0 {
+            return None;
         }
+        Some(ranking)
     }
-}
-
-/// Algorithm 8.47: The ShuffleProof
-#[derive(Default, Clone, PartialEq, Eq, Debug)]
-pub struct ShuffleProof {
-    pub challenge: BigUint,                          // challenge
-    pub S: BigS,                                     // S
-    pub permutation_commitments: Vec<BigUint>,       // permutation_commitments
-    pub permutation_chain_commitments: Vec<BigUint>, // permutation_chain_commitments
-}
 
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
-pub struct ShuffleProofAsBytes {
-    pub challenge: Vec<u8>,                          // challenge
-    pub S: BigSAsBytes,                              // S
-    pub permutation_commitments: Vec<Vec<u8>>,       // permutation_commitments
-    pub permutation_chain_commitments: Vec<Vec<u8>>, // permutation_chain_commitments
-}
-
-impl Into<ShuffleProof> for ShuffleProofAsBytes {
-    fn into(self) -> ShuffleProof {
-        ShuffleProof {
-            challenge: BigUint::from_bytes_be(&self.challenge),
-            S: self.S.into(),
-            permutation_commitments: self
-                .permutation_commitments
-                .iter()
-                .map(|v| BigUint::from_bytes_be(v))
-                .collect::<Vec<BigUint>>(),
-            permutation_chain_commitments: self
-                .permutation_chain_commitments
-                .iter()
-                .map(|v| BigUint::from_bytes_be(v))
-                .collect::<Vec<BigUint>>(),
-        }
+    /// encodes the selected candidates of a multi-select question as bitmask
+    pub fn encode_selection(selection: &[u32]) -> u64 {
+        selection
+            .iter()
+            .fold(0u64, |mask, candidate| mask | (1u64 << candidate))
     }
-}
 
-impl Into<ShuffleProofAsBytes> for ShuffleProof {
-    fn into(self) -> ShuffleProofAsBytes {
-        ShuffleProofAsBytes {
-            challenge: self.challenge.to_bytes_be(),
-            S: self.S.into(),
-            permutation_commitments: self
-                .permutation_commitments
-                .into_iter()
-                .map(|v| v.to_bytes_be())
-                .collect::<Vec<Vec<u8>>>(),
-            permutation_chain_commitments: self
-                .permutation_chain_commitments
-                .into_iter()
-                .map(|v| v.to_bytes_be())
-                .collect::<Vec<Vec<u8>>>(),
+    /// returns all valid answers of a question with candidates (incl. NO_ANSWER), empty for free-form questions.
+    /// the list is the list of valid messages of the validity proof of an answer.
+    pub fn valid_answers(&self) -> Vec<u64> {
+        if !self.requires_validity_proof() {
+            return Vec::new();
         }
+        let n = self.candidates.len() as u32;
+        let mut answers: Vec<u64> = match self.question_type {
+            QuestionType::SingleChoice => (0..n as u64).collect(),
+            QuestionType::MultiSelect { max } => (0..(1u64 << n))
+                .filter(|mask| mask.count_ones() <= max)
+                .collect(),
+            QuestionType::Ranked { candidates } => {
+                let upper = (n as u64 + 1).pow(candidates);
+                (0..upper)
+                    .filter(|message| self.decode_ranking(*message).is_some())
+                    .collect()
+            }
+        };
+        answers.push(NO_ANSWER as u64);
+        answers
     }
 }
 
-// the payload submitted after performing a shuffle proof in an offchain worker
-// contains the shuffle proof and the shuffle_votes
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
-pub struct ShufflePayload {
-    pub iteration: u8,
-    pub ciphers: Vec<Cipher>,
-    pub proof: ShuffleProofAsBytes,
-    pub start_position: u64,
-    pub batch_size: u64,
-}
+/// the message encrypted for the topics a voter didn't answer.
+/// the largest message which can be decoded is reserved for it, therefore,
+/// the mixnet tally reports the unanswered topics as a separate option.
+/// votes using the homomorphic tally are padded with an encryption of 0 instead.
+pub const NO_ANSWER: u32 = DEFAULT_DECODE_BOUND as u32;
 
-// a data type to store information about the current status of the shuffle operations
+/// A ballot is composed of all answers of a voter
 #[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
-pub struct ShuffleState {
-    pub iteration: u8,
-    pub start_position: u64,
-    pub batch_size: u64,
-    pub done: bool,
+pub struct Ballot {
+    pub answers: Vec<(TopicId, Cipher)>,
+    /// either empty or one proof per answer (in the order of the answers)
+    pub proofs: Vec<BallotProof>,
+    /// either empty or one entry per answer (in the order of the answers).
+    /// required for the answers of questions with candidates, None for free-form questions.
+    pub validity_proofs: Vec<Option<ValidityProof>>,
+    /// the encrypted credential of the voter (coercion resistance), required if a credential
+    /// has been registered for the voter. the ballot is only counted if the credential is
+    /// plaintext equivalent to the registered one, see: CredentialBallots.
+    pub credential: Option<Cipher>,
 }
 
-pub type VoteId = Vec<u8>;
-pub type Title = Vec<u8>;
-
-// both types are strings encoded as bytes
-pub type NrOfShuffles = u8;
-pub type TopicId = Vec<u8>;
-pub type TopicQuestion = Vec<u8>;
-
-// result types
-pub type Plaintext = Vec<u8>;
-pub type Count = Vec<u8>;
-pub type TopicResult = BTreeMap<Plaintext, Count>;
+impl Ballot {
+    /// the number of ciphers of the ballot, i.e., its answers and its encrypted credential
+    pub fn nr_of_ciphers(&self) -> usize {
+        self.answers.len() + self.credential.iter().count()
+    }
 
-// topicId and question (string as Vec<u8>)
-pub type Topic = (TopicId, TopicQuestion);
+    /// true if the ballot contains exactly one answer for each topic, in the order of the topics.
+    /// all ballots of this shape are indistinguishable, regardless of the answered topics.
+    pub fn has_uniform_shape(&self, topics: &[Topic]) -> bool {
+        self.answers.len() == topics.len()
+            && self
+                .answers
+                .iter()
+                .zip(topics.iter())
+                .all(|((answered, _), topic)| *answered == topic.id)
+    }
 
-/// A ballot is composed of all answers of a voter
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
-pub struct Ballot {
-    pub answers: Vec<(TopicId, Cipher)>,
+    /// orders the answers like the topics and inserts a cipher created by no_answer
+    /// for every topic which hasn't been answered, i.e., an encryption of NO_ANSWER.
+    /// answers for topics which aren't part of the vote are dropped.
+    /// the proofs are dropped as well, they need to be created for the padded answers.
+    /// the encrypted credential is kept.
+    pub fn pad<F>(self, topics: &[Topic], mut no_answer: F) -> Ballot
+    where
+        F: FnMut(&TopicId) -> Cipher,
+    {
+        let mut answers = self.answers;
+        let padded = topics
+            .iter()
+            .map(|Topic { id: topic_id, .. }| {
+                let cipher = match answers
+                    .iter()
+                    .position(|(answered, _)| answered == topic_id)
+                {
+                    Some(index) => answers.swap_remove(index).1,
+                    None => no_answer(topic_id),
+                };
+                (topic_id.clone(), cipher)
+            })
+            .collect();
+        Ballot {
+            answers: padded,
+            proofs: Vec::new(),
+            validity_proofs: Vec::new(),
+            credential: self.credential,
+        }
+    }
 }
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
@@ -306,6 +382,8 @@ pub enum VotePhase {
     KeyGeneration,
     Voting,
     Tallying,
+    /// the vote has been aborted, neither ballots nor the tally are accepted anymore
+    Cancelled,
 }
 
 // Default defines the starting value when VotePhase is created
@@ -315,6 +393,35 @@ impl Default for VotePhase {
     }
 }
 
+impl VotePhase {
+    /// the transitions between the phases of a vote: the key generation is followed by the voting
+    /// and the tallying phase, a key rotation returns to the key generation. a vote can be cancelled
+    /// in every phase, a cancelled vote never leaves its phase. staying in the key generation or
+    /// voting phase is valid (e.g. storing the public key of a vote in the voting phase).
+    /// the match is exhaustive on purpose, i.e., a new phase doesn't compile without its transitions.
+    pub fn can_transition_to(&self, next: &VotePhase) -> bool {
+        use VotePhase::*;
+        match (self, next) {
+            (KeyGeneration, KeyGeneration)
+            | (KeyGeneration, Voting)
+            | (Voting, Voting)
+            | (Voting, KeyGeneration)
+            | (Voting, Tallying)
+            | (KeyGeneration, Cancelled)
+            | (Voting, Cancelled)
+            | (Tallying, Cancelled) => true,
+            (KeyGeneration, Tallying)
+            | (Tallying, KeyGeneration)
+            | (Tallying, Voting)
+            | (Tallying, Tallying) => false,
+            (Cancelled, KeyGeneration)
+            | (Cancelled, Voting)
+            | (Cancelled, Tallying)
+            | (Cancelled, Cancelled) => false,
+        }
+    }
+}
+
 impl FromStr for VotePhase {
     type Err = ();
     fn from_str(input: &str) -> Result<VotePhase, Self::Err> {
@@ -322,6 +429,34 @@ impl FromStr for VotePhase {
             "KeyGeneration" => Ok(VotePhase::KeyGeneration),
             "Voting" => Ok(VotePhase::Voting),
             "Tallying" => Ok(VotePhase::Tallying),
+            "Cancelled" => Ok(VotePhase::Cancelled),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The strategy used to tally the topics of a vote.
+/// Mixnet: the ciphers are shuffled and each cipher is decrypted individually.
+/// Homomorphic: the ciphers are added homomorphically and only the sum is decrypted.
+/// Only suited for yes/no questions (0 or 1) using encoded messages.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub enum TallyStrategy {
+    Mixnet,
+    Homomorphic,
+}
+
+impl Default for TallyStrategy {
+    fn default() -> Self {
+        Self::Mixnet
+    }
+}
+
+impl FromStr for TallyStrategy {
+    type Err = ();
+    fn from_str(input: &str) -> Result<TallyStrategy, Self::Err> {
+        match input {
+            "Mixnet" => Ok(TallyStrategy::Mixnet),
+            "Homomorphic" => Ok(TallyStrategy::Homomorphic),
             _ => Err(()),
         }
     }
@@ -335,31 +470,141 @@ pub struct Vote<AccountId> {
     pub title: Title,
     pub phase: VotePhase,
     pub params: PublicParameters,
+    // the number of required shuffle iterations (mixing rounds)
+    pub nr_of_shuffles: NrOfShuffles,
+    pub tally_strategy: TallyStrategy,
 }
 
-// the public key generation proof submitted by the sealer -> this prooves knowledge of a secret key that belongs to the submitted public key
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
-pub struct PublicKeyShareProof {
-    pub challenge: Vec<u8>,
-    pub response: Vec<u8>,
+/// The version of the protocol new votes are created with.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The hash function of the Fiat-Shamir challenges of the key generation, decryption,
+/// key switch and shuffle proofs. The generators and the batch exponents always use Blake2b.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HashFunction {
+    Blake2b,
+    Sha256,
+    Sha3_256,
+}
+
+impl Default for HashFunction {
+    fn default() -> Self {
+        HashFunction::Blake2b
+    }
+}
+
+impl FromStr for HashFunction {
+    type Err = ();
+    fn from_str(input: &str) -> Result<HashFunction, Self::Err> {
+        match input {
+            "Blake2b" => Ok(HashFunction::Blake2b),
+            "Sha256" => Ok(HashFunction::Sha256),
+            "Sha3_256" => Ok(HashFunction::Sha3_256),
+            _ => Err(()),
+        }
+    }
 }
 
-impl Into<PublicKeyShareProof> for KeyGenerationProof {
-    fn into(self) -> PublicKeyShareProof {
-        PublicKeyShareProof {
-            challenge: self.challenge.to_bytes_be(),
-            response: self.response.to_bytes_be(),
+impl From<HashFunction> for crypto::hash::HashFunction {
+    fn from(hash_function: HashFunction) -> Self {
+        match hash_function {
+            HashFunction::Blake2b => crypto::hash::HashFunction::Blake2b,
+            HashFunction::Sha256 => crypto::hash::HashFunction::Sha256,
+            HashFunction::Sha3_256 => crypto::hash::HashFunction::Sha3_256,
         }
     }
 }
 
-impl Into<KeyGenerationProof> for PublicKeyShareProof {
-    fn into(self) -> KeyGenerationProof {
-        KeyGenerationProof {
-            challenge: BigUint::from_bytes_be(&self.challenge),
-            response: BigUint::from_bytes_be(&self.response),
+/// The parameters of the protocol a vote has been created with, stored with the vote.
+/// The proofs of the vote are generated and verified using these parameters (instead of the
+/// constants of the current release), i.e., a verifier knows exactly what to check.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct ProtocolConfig {
+    pub version: u32,
+    pub hash_function: HashFunction,
+    /// the number of bits of the random exponents of the batched proof verification
+    pub batch_exponent_bits: u32,
+    /// the domain of the seeds from which the generators of the shuffle proof are derived
+    pub generator_domain: Vec<u8>,
+    /// the number of required shuffle iterations (mixing rounds)
+    pub nr_of_shuffles: NrOfShuffles,
+}
+
+impl ProtocolConfig {
+    /// the parameters of the current protocol version
+    pub fn new(nr_of_shuffles: NrOfShuffles) -> Self {
+        ProtocolConfig {
+            version: PROTOCOL_VERSION,
+            hash_function: HashFunction::default(),
+            batch_exponent_bits: BATCH_EXPONENT_BITS as u32,
+            generator_domain: GENERATOR_DOMAIN.as_bytes().to_vec(),
+            nr_of_shuffles,
         }
     }
+
+    /// true if the proofs of the vote can be generated and verified by this release,
+    /// all hash functions are supported
+    pub fn is_supported(&self) -> bool {
+        self.version >= 1
+            && self.version <= PROTOCOL_VERSION
+            && self.batch_exponent_bits >= 1
+            && self.batch_exponent_bits as usize <= MAX_BATCH_EXPONENT_BITS
+            && self.generator_domain == GENERATOR_DOMAIN.as_bytes()
+    }
+}
+
+/// The metadata of a vote which front-ends use to render the ballot, e.g., descriptions
+/// of the vote and its questions in multiple languages and the biographies of the candidates.
+/// The metadata is informational only, it is neither parsed nor validated on-chain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub enum VoteMetadata {
+    /// the IPFS hash (CID) of the metadata document
+    Ipfs(Vec<u8>),
+    /// the metadata document itself (JSON)
+    Inline(Vec<u8>),
+}
+
+impl VoteMetadata {
+    /// the number of bytes of the hash or the document
+    pub fn size(&self) -> usize {
+        match self {
+            VoteMetadata::Ipfs(hash) => hash.len(),
+            VoteMetadata::Inline(document) => document.len(),
+        }
+    }
+}
+
+/// A vote proposed by a voting authority together with its topics.
+/// The vote is created once sufficient voting authorities approved it (the proposer included).
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct VoteProposal<AccountId> {
+    pub vote: Vote<AccountId>,
+    pub topics: Vec<Topic>,
+    pub batch_size: u64,
+    pub approvals: Vec<AccountId>,
+}
+
+/// the answers of several independent free-form questions packed into the cipher of a single topic
+/// (ballot packing), i.e., they are shuffled and decrypted once. the answer of question i occupies
+/// the i-th slot of `slot_bits` bits, see: `crypto::encryption::ElGamal::pack`.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct TopicPacking {
+    /// the number of packed answers, at most MAX_PACKED_SLOTS
+    pub slots: u32,
+    /// the width of a slot in bits, every answer must be less than 2^slot_bits
+    pub slot_bits: u32,
+}
+
+/// the reusable configuration of recurring votes, e.g. monthly board votes.
+/// a vote created from the template gets its own vote_id, title and phase, the ids of its
+/// topics are prefixed with the vote_id (see: helpers::templates).
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct VoteTemplate {
+    pub params: PublicParameters,
+    pub topics: Vec<Topic>,
+    pub batch_size: u64,
+    pub nr_of_shuffles: NrOfShuffles,
+    pub tally_strategy: TallyStrategy,
 }
 
 // the public key share submitted by each sealer to generated the system's public key
@@ -371,26 +616,405 @@ pub struct PublicKeyShare {
 
 pub type DecryptedShare = Vec<u8>;
 
+/// an entry of the decryption audit trail.
+/// ties the decrypted shares of a sealer (via their hash) to the submitted proof.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct DecryptionAuditEntry<AccountId> {
+    pub sealer: AccountId,
+    pub shares_hash: [u8; 32],
+    pub proof: DecryptedShareProof,
+}
+
+/// an entry of the key switch audit trail.
+/// ties the key switch shares of a sealer (via their hash) to the submitted proof.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct KeySwitchAuditEntry<AccountId> {
+    pub sealer: AccountId,
+    pub shares_hash: [u8; 32],
+    pub proof: KeySwitchShareProof,
+}
+
+/// the kind of an accepted proof in the proof log.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProofKind {
+    Shuffle,
+    Decryption,
+}
+
+/// an entry of the proof log of a vote, i.e., the transcript hash of an accepted proof.
+/// the hash commits to the inputs and the proof (see: helpers::transcripts),
+/// i.e., a proof can be replayed and matched against its entry even once its payload has been pruned.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct ProofLogEntry<AccountId, BlockNumber> {
+    pub kind: ProofKind,
+    pub topic_id: TopicId,
+    /// the shuffle iteration of the proven ciphers, i.e., the # of shuffles of the decrypted ciphers
+    pub iteration: NrOfShuffles,
+    pub submitter: AccountId,
+    pub block_number: BlockNumber,
+    pub transcript_hash: [u8; 32],
+}
+
+/// the evidence that a ballot has been recorded on chain.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct BallotReceipt<BlockNumber, Hash> {
+    /// the block in which the ballot was included
+    pub block_number: BlockNumber,
+    /// the hash of the block, resolved when the receipt is queried.
+    /// only the hashes of recent blocks are known on chain, default otherwise.
+    pub block_hash: Hash,
+    /// the blake2 hash of all ciphers of the ballot
+    pub cipher_hash: [u8; 32],
+    /// the index of the BallotSubmitted event in the block
+    pub event_index: u32,
+}
+
+/// a batch of ciphers collected off-chain (e.g. postal ballots) and imported by the voting authority.
+/// the imported ciphers are the positions [start, start + nr_of_ciphers) of the submitted ciphers of the topic.
 #[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
-pub struct DecryptedShareProof {
-    pub challenge: Vec<u8>,
-    pub response: Vec<u8>,
+pub struct CipherImport<AccountId, BlockNumber> {
+    pub topic_id: TopicId,
+    /// the collection system the ciphers stem from (string as Vec<u8>), e.g. the postal channel
+    pub source_tag: Vec<u8>,
+    /// the voting authority which imported the ciphers
+    pub importer: AccountId,
+    /// the block in which the ciphers have been imported
+    pub block_number: BlockNumber,
+    /// the position of the first imported cipher in the submitted ciphers of the topic
+    pub start: u64,
+    pub nr_of_ciphers: u64,
+    /// the merkle root over the blake2 hashes of the imported ciphers
+    pub merkle_root: [u8; 32],
 }
 
-impl From<DecryptionProof> for DecryptedShareProof {
-    fn from(source: DecryptionProof) -> Self {
-        DecryptedShareProof {
-            challenge: source.challenge.to_bytes_be(),
-            response: source.response.to_bytes_be(),
+/// a cipher which has been spoiled by its voter (audit ballot), it is excluded from the tally.
+/// the voter reveals the randomness later on such that anyone can check the encryption.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct SpoiledBallot<AccountId> {
+    pub voter: AccountId,
+    pub cipher: Cipher,
+    /// the public key the cipher is encrypted with
+    pub public_key: PublicKey,
+    pub reveal: Option<SpoiledBallotReveal>,
+}
+
+/// the plaintext and the randomness of a spoiled cipher.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct SpoiledBallotReveal {
+    pub message: Vec<u8>,
+    pub randomness: Vec<u8>,
+    /// whether the message was encoded (g^m) before encrypting it
+    pub encoded: bool,
+}
+
+/// the turn of a sealer to shuffle the next batch of a topic, see: shuffle::turn
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct ShuffleTurn<BlockNumber> {
+    /// the index of the sealer (in Sealers) whose turn it is
+    pub sealer_index: u32,
+    /// the block in which the turn started or the sealer committed to its shuffle
+    pub since: BlockNumber,
+}
+
+/// the commitment of a sealer to a shuffle, i.e., the hash of its ShufflePayload.
+/// the shuffle can only be revealed in a later block than the one of the commitment.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct ShuffleCommitment<AccountId, BlockNumber> {
+    pub sealer: AccountId,
+    pub commitment: [u8; 32],
+    pub block_number: BlockNumber,
+}
+
+/// a topic whose ciphers are ready to be decrypted by the sealers, see: helpers::progress.
+/// removed once sufficient sealers have submitted their decrypted shares.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct PendingDecryption<BlockNumber> {
+    pub vote_id: VoteId,
+    /// the block after which the decryption of the topic is reported as stalled
+    pub deadline: BlockNumber,
+}
+
+/// the number of ballots an account submitted in the current rate limit period, see: helpers::limits.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct BallotRate<BlockNumber> {
+    /// the block in which the current period started
+    pub period_start: BlockNumber,
+    pub count: u32,
+}
+
+/// the merkle inclusion proof of a cipher in the cipher list of a topic and shuffle iteration.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct CipherMerkleProof {
+    /// the blake2 hash of the cipher
+    pub leaf: [u8; 32],
+    /// the position of the cipher in the cipher list
+    pub index: u64,
+    /// the number of ciphers in the cipher list
+    pub nr_of_leaves: u64,
+    /// the sibling hashes from the leaf up to the root
+    pub path: Vec<[u8; 32]>,
+}
+
+/// the commitment which replaces the ciphers and proofs of an archived vote.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct VoteArchive {
+    /// the merkle root over the hashes of all ciphers and proofs of the vote
+    pub merkle_root: [u8; 32],
+    /// the number of leaves of the merkle tree
+    pub nr_of_leaves: u64,
+}
+
+/// the results of all topics of a tallied vote, see: helpers::certification.
+/// the voting authority and the sealers certify the hash of the results.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct VoteResult {
+    pub vote_id: VoteId,
+    /// the result of each topic in the order of the topics of the vote
+    pub results: Vec<(TopicId, TopicResult)>,
+}
+
+impl VoteResult {
+    /// the blake2 hash of the SCALE encoded results
+    pub fn hash(&self) -> [u8; 32] {
+        sp_io::hashing::blake2_256(&self.encode())
+    }
+
+    /// the head of the blake2 hash chain over the results of the topics, see: ResultDigest.
+    /// d_0 = H(context | vote_id), d_i = H(d_i-1 | H(topic_id | n | plaintext_1 | count_1 | ..))
+    /// where n is the number of plaintexts of the topic (u32, big-endian), the plaintexts are
+    /// in ascending order and all byte strings are prefixed by their length (u32, big-endian).
+    pub fn results_chain(&self) -> [u8; 32] {
+        let mut input: Vec<u8> = RESULT_DIGEST_CONTEXT.to_vec();
+        extend_length_prefixed(&mut input, &self.vote_id);
+        let mut chain = sp_io::hashing::blake2_256(&input);
+
+        for (topic_id, result) in self.results.iter() {
+            let mut input: Vec<u8> = Vec::new();
+            extend_length_prefixed(&mut input, topic_id);
+            input.extend_from_slice(&(result.len() as u32).to_be_bytes());
+            for (plaintext, count) in result.iter() {
+                extend_length_prefixed(&mut input, plaintext);
+                extend_length_prefixed(&mut input, count);
+            }
+            let topic_hash = sp_io::hashing::blake2_256(&input);
+
+            let mut link: Vec<u8> = chain.to_vec();
+            link.extend_from_slice(&topic_hash);
+            chain = sp_io::hashing::blake2_256(&link);
         }
+        chain
     }
 }
 
-impl From<DecryptedShareProof> for DecryptionProof {
-    fn from(source: DecryptedShareProof) -> Self {
-        DecryptionProof {
-            challenge: BigUint::from_bytes_be(&source.challenge),
-            response: BigUint::from_bytes_be(&source.response),
+/// the context of the result digest, separates it from other hashes of the chain
+pub const RESULT_DIGEST_CONTEXT: &[u8] = b"provotum-mixnet/result-digest/v1";
+
+/// appends the bytes prefixed by their length (u32, big-endian)
+fn extend_length_prefixed(input: &mut Vec<u8>, bytes: &[u8]) {
+    input.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    input.extend_from_slice(bytes);
+}
+
+/// the digest of a certified result which other chains (bridges, light clients) can consume.
+/// the digest is a plain blake2 hash chain, it can be reproduced without SCALE
+/// (see: VoteResult::results_chain and the `attestation` of the verifier).
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct ResultDigest<BlockNumber> {
+    /// the head of the hash chain over the results of the topics
+    pub results_chain: [u8; 32],
+    /// the merkle root of the commitments to the ciphers and proofs, see: helpers::certification
+    pub proofs_root: [u8; 32],
+    /// H(results_chain | proofs_root)
+    pub digest: [u8; 32],
+    /// the block in which the result has been certified
+    pub block_number: BlockNumber,
+}
+
+impl<BlockNumber> ResultDigest<BlockNumber> {
+    pub fn new(
+        results_chain: [u8; 32],
+        proofs_root: [u8; 32],
+        block_number: BlockNumber,
+    ) -> Self {
+        let mut input: Vec<u8> = results_chain.to_vec();
+        input.extend_from_slice(&proofs_root);
+        ResultDigest {
+            results_chain,
+            proofs_root,
+            digest: sp_io::hashing::blake2_256(&input),
+            block_number,
+        }
+    }
+}
+
+/// the context of the ballot box freeze, separates it from other hashes of the chain
+pub const BALLOT_BOX_FREEZE_CONTEXT: &[u8] = b"provotum-mixnet/ballot-box-freeze/v1";
+
+/// the ciphers of a vote at the end of the voting phase, recorded once the tallying phase starts
+/// (see: helpers::freeze). the digest is the context of the challenges of all shuffle proofs of
+/// the vote, i.e. the mixing is bound to exactly the ciphers present in the given block.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct BallotBoxFreeze<Hash, BlockNumber> {
+    /// the hash of the last block of the voting phase
+    pub block_hash: Hash,
+    /// the block in which the tallying phase started
+    pub block_number: BlockNumber,
+    /// the head of the hash chain over the cipher lists of the topics
+    pub ciphers_chain: [u8; 32],
+    /// H(block_hash | ciphers_chain)
+    pub digest: [u8; 32],
+}
+
+impl<Hash: AsRef<[u8]>, BlockNumber> BallotBoxFreeze<Hash, BlockNumber> {
+    /// the head of the blake2 hash chain over the cipher lists of the topics:
+    /// d_0 = H(context | vote_id), d_i = H(d_i-1 | H(topic_id | n | root))
+    /// where n is the number of ciphers of the topic (u64, big-endian) and root the merkle root
+    /// of the ciphers (see: CipherRoots), the ids are prefixed by their length (u32, big-endian).
+    pub fn ciphers_chain(
+        vote_id: &[u8],
+        topics: &[(TopicId, u64, [u8; 32])],
+    ) -> [u8; 32] {
+        let mut input: Vec<u8> = BALLOT_BOX_FREEZE_CONTEXT.to_vec();
+        extend_length_prefixed(&mut input, vote_id);
+        let mut chain = sp_io::hashing::blake2_256(&input);
+
+        for (topic_id, nr_of_ciphers, root) in topics.iter() {
+            let mut input: Vec<u8> = Vec::new();
+            extend_length_prefixed(&mut input, topic_id);
+            input.extend_from_slice(&nr_of_ciphers.to_be_bytes());
+            input.extend_from_slice(root);
+            let topic_hash = sp_io::hashing::blake2_256(&input);
+
+            let mut link: Vec<u8> = chain.to_vec();
+            link.extend_from_slice(&topic_hash);
+            chain = sp_io::hashing::blake2_256(&link);
+        }
+        chain
+    }
+
+    pub fn new(
+        block_hash: Hash,
+        block_number: BlockNumber,
+        ciphers_chain: [u8; 32],
+    ) -> Self {
+        let mut input: Vec<u8> = block_hash.as_ref().to_vec();
+        input.extend_from_slice(&ciphers_chain);
+        BallotBoxFreeze {
+            digest: sp_io::hashing::blake2_256(&input),
+            block_hash,
+            block_number,
+            ciphers_chain,
+        }
+    }
+}
+
+/// the approvals of the result of a vote which hasn't been certified yet.
+/// the approvals are reset if the result hash changes.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct ResultApprovals<AccountId> {
+    pub result_hash: [u8; 32],
+    /// the voting authorities and sealers which approved the result hash
+    pub approvals: Vec<AccountId>,
+}
+
+/// the certified result of a vote, approved by a voting authority and `CertificationQuorum` sealers.
+/// downstream systems can treat the result as final.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct CertifiedResult<AccountId, BlockNumber> {
+    pub result_hash: [u8; 32],
+    /// the voting authorities and sealers which approved the result hash
+    pub approvals: Vec<AccountId>,
+    /// the block in which the quorum has been met
+    pub block_number: BlockNumber,
+}
+
+/// offchain local storage key of the metrics of the offchain worker, read by the node (see: `OffchainMetrics`)
+pub const OFFCHAIN_METRICS_KEY: &[u8] = b"provotum::mixnet::ocw::metrics";
+
+/// the upper bounds (in milliseconds) of the buckets of the duration histograms of the offchain worker
+pub const OFFCHAIN_METRICS_BUCKETS_MS: [u64; 8] =
+    [10, 50, 100, 500, 1_000, 5_000, 10_000, 60_000];
+
+/// a histogram of durations (in milliseconds) recorded by the offchain worker.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct OffchainHistogram {
+    /// the number of observations per bucket of `OFFCHAIN_METRICS_BUCKETS_MS` (not cumulative),
+    /// the last bucket counts the observations above the largest bound
+    pub buckets: Vec<u64>,
+    /// the sum of all observations
+    pub sum: u64,
+    /// the number of observations
+    pub count: u64,
+}
+
+impl OffchainHistogram {
+    pub fn observe(&mut self, value: u64) {
+        self.buckets
+            .resize(OFFCHAIN_METRICS_BUCKETS_MS.len() + 1, 0);
+        let bucket = OFFCHAIN_METRICS_BUCKETS_MS
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(OFFCHAIN_METRICS_BUCKETS_MS.len());
+        self.buckets[bucket] = self.buckets[bucket].saturating_add(1);
+        self.sum = self.sum.saturating_add(value);
+        self.count = self.count.saturating_add(1);
+    }
+}
+
+/// the metrics of the offchain worker of a node, all counters are cumulative since the node's
+/// offchain storage has been created. see: `offchain::metrics`
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct OffchainMetrics {
+    /// the number of ciphers shuffled
+    pub ciphers_shuffled: u64,
+    /// the number of submitted shuffle commitments
+    pub shuffles_committed: u64,
+    /// the number of revealed shuffles
+    pub shuffles_revealed: u64,
+    /// the number of signed transactions which couldn't be submitted
+    pub submissions_failed: u64,
+    /// the number of jobs moved to the dead-letter log
+    pub jobs_dead_lettered: u64,
+    /// the time to shuffle a batch of ciphers
+    pub shuffle_ms: OffchainHistogram,
+    /// the time to generate the shuffle proof of a batch
+    pub proof_generation_ms: OffchainHistogram,
+}
+
+/// offchain local storage key of the latest signed checkpoint of the election state taken by
+/// the offchain worker, the raw CBOR encoded `mixnet_primitives::checkpoint::SignedCheckpoint`.
+/// the node writes every new checkpoint to the directory configured by `--checkpoint-path`.
+pub const CHECKPOINT_KEY: &[u8] = b"provotum::mixnet::ocw::checkpoint";
+
+/// the prefix of the storage of the pallet (see: decl_storage), a checkpoint contains all entries below twox_128(prefix)
+pub const STORAGE_MODULE_PREFIX: &[u8] = b"OffchainModule";
+
+/// the public key of the identity provider of a vote which signs the eligibility claims of the voters,
+/// see: mixnet_primitives::claims
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum IdentityProvider {
+    Sr25519(sr25519::Public),
+    Ed25519(ed25519::Public),
+}
+
+/// the signature of an eligibility claim, must match the key type of the identity provider
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ClaimSignature {
+    Sr25519(sr25519::Signature),
+    Ed25519(ed25519::Signature),
+}
+
+impl IdentityProvider {
+    /// true if the identity provider signed the message
+    pub fn verify(&self, message: &[u8], signature: &ClaimSignature) -> bool {
+        match (self, signature) {
+            (IdentityProvider::Sr25519(public), ClaimSignature::Sr25519(signature)) => {
+                sp_io::crypto::sr25519_verify(signature, message, public)
+            }
+            (IdentityProvider::Ed25519(public), ClaimSignature::Ed25519(signature)) => {
+                sp_io::crypto::ed25519_verify(signature, message, public)
+            }
+            _ => false,
         }
     }
 }
@@ -407,3 +1031,93 @@ impl<T: SigningTypes> SignedPayload<T> for Payload<T::Public> {
         self.public.clone()
     }
 }
+
+/// the revealed shuffle of an unsigned transaction, signed by the sealer which committed to it.
+/// see: `submit_shuffled_votes_and_proof_unsigned`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ShuffleSubmission<Public> {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+    pub payload: ShufflePayload,
+    /// the public key of the sealer, identifies its account
+    pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for ShuffleSubmission<T::Public> {
+    fn public(&self) -> T::Public {
+        self.public.clone()
+    }
+}
+
+/// the decrypted shares of an unsigned transaction, signed by the sealer.
+/// see: `submit_decrypted_shares_unsigned`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DecryptedSharesSubmission<Public> {
+    pub vote_id: VoteId,
+    pub topic_id: TopicId,
+    pub shares: Vec<DecryptedShare>,
+    pub proof: DecryptedShareProof,
+    pub nr_of_shuffles: NrOfShuffles,
+    /// the public key of the sealer, identifies its account
+    pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for DecryptedSharesSubmission<T::Public> {
+    fn public(&self) -> T::Public {
+        self.public.clone()
+    }
+}
+
+/// the heartbeat of a sealer sent as an unsigned transaction, signed by the sealer.
+/// see: `heartbeat_unsigned`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct HeartbeatPayload<Public, BlockNumber> {
+    /// the block in which the offchain worker sent the heartbeat
+    pub block_number: BlockNumber,
+    /// the public key of the sealer, identifies its account
+    pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for HeartbeatPayload<T::Public, T::BlockNumber> {
+    fn public(&self) -> T::Public {
+        self.public.clone()
+    }
+}
+
+/// a summary of the mixing and decryption progress of a topic.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct TopicProgress {
+    pub topic_id: TopicId,
+    /// the number of ciphers cast for the topic, i.e., the size of the anonymity set
+    pub nr_of_ciphers: u64,
+    /// the number of shuffle iterations required by the vote
+    pub nr_of_shuffles: NrOfShuffles,
+    /// the shuffle iteration currently in progress
+    pub shuffle_iteration: NrOfShuffles,
+    /// the position of the next shuffle batch in the current iteration
+    pub shuffle_start_position: u64,
+    pub shuffle_done: bool,
+    /// the number of sealers which submitted their decrypted shares
+    pub decrypted_shares: u32,
+    /// the number of sealers which must submit their decrypted shares
+    pub required_shares: u32,
+    pub tallied: bool,
+}
+
+/// a page of the ciphers of a topic and shuffle iteration, see: `Module::cipher_page`
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, Debug)]
+pub struct CipherPage {
+    /// the number of ciphers of the topic and shuffle iteration
+    pub total: u64,
+    /// the ciphers in the range [offset, offset + limit)
+    pub ciphers: Vec<Cipher>,
+}
+
+/// a vote together with its questions and its public key, see: `Module::vote_details`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct VoteDetails<AccountId> {
+    pub vote: Vote<AccountId>,
+    pub topics: Vec<Topic>,
+    /// None until the public key shares of the sealers have been combined
+    pub public_key: Option<PublicKey>,
+}
@@ -0,0 +1,851 @@
+//! Weights and fee policy of the dispatchables of pallet_mixnet.
+//!
+//! The weights are derived from the benchmark results in `node/results/2021-03-16-2.txt`
+//! (median slopes, `--repeat 10`). The per cipher slopes of the cipher dependent calls
+//! are fitted over the `_100`, `_1000` (and `_10000`) variants of the benchmarks.
+//! `set_vote_phase`, `cancel_vote`, `commit_shuffle`, `aggregate_ciphers`, `rotate_public_key`,
+//! `submit_key_switch_shares`, `archive_vote`, `register_credential`, `approve_vote`,
+//! `spoil_ballot`, `reveal_spoiled_ballot`, `set_metadata`, `set_shuffle_batch_size`,
+//! `set_decryption_threshold`, `set_hash_function`, `whitelist_vote`, `claim_ballot_deposit`, `set_voter_weight`,
+//! `set_linked_shuffle`, `set_topic_packing`, `heartbeat`, `certify_result`, `set_identity_provider`, `set_eligibility_key`,
+//! `register_voter_with_claim`, `add_sealer`, `remove_sealer`, `set_group_check` and `finalize_tally` are not
+//! benchmarked yet, their weights are estimated from the closest benchmarked call. The weight of `submit_linked_shuffle` is the weight of
+//! `submit_shuffled_votes_and_proof` per topic. The weight of `cast_relayed_ballot` is the weight
+//! of `cast_ballot` and the verification of the voter's signature (estimated). The weight of
+//! `cast_anonymous_ballot` is the weight of `cast_ballot` and the verification of the blind
+//! signature of the eligibility token, i.e., an RSA verification with a 3072 bit modulus, and of the
+//! signature of the one-time key (estimated).
+//! The weight of `import_ciphers` is estimated from the storage accesses of `cast_ballot` without
+//! the ballot proofs, the rate limit and the deposit, and includes the merkle root of the batch.
+//! The weight of `register_voter_with_claim` is the weight of `register_credential` and the
+//! verification of the signature of the identity provider (estimated).
+//! The weight of `check_ciphers_in_group` is added to the cast ballot calls, i.e., two modular exponentiations
+//! per cipher. It is charged whether the vote checks the group membership of the ciphers (see: `set_group_check`) or not.
+//! It is estimated from `verify_public_key_share_proof`, the `check_ciphers_in_group_10` and `_100` and the
+//! `cast_ballot_with_group_check` benchmarks measure the cost per cipher and per ballot.
+//! The weight of `create_vote_from_template` is the weight of `propose_vote` with the
+//! maximal number of topics of a template and the read of the template, the weight of
+//! `store_vote_template` is estimated from the storage accesses of `propose_vote`.
+//! `force_vote_phase` uses the weight of `set_vote_phase`.
+//! The benchmarked weight of `cast_ballot` includes the storage accesses of the ballot rate limit
+//! and the ballot deposit (4 reads, 3 writes) and of the voter weight (1 read, 1 read and write
+//! per answer), which have been added after the benchmark. The same applies to the voter weight
+//! of `spoil_ballot` (2 reads, 1 write).
+//! The weight of `certify_result` includes the result digest of the final approval,
+//! estimated for a vote with a single topic and at most three shuffles.
+//! The weight of `combine_decrypted_shares_batch` is fitted over the variants of
+//! `combine_decrypted_shares` and includes the partial tally (2 reads, 2 writes).
+//! The weights of `submit_shuffled_votes_and_proof` and `submit_decrypted_shares` include the
+//! entry of the proof log (1 write), which has been added after the benchmark.
+//! The weight of `revoke_voter` is the weight of `spoil_ballot` and the revocation list
+//! (2 reads, 2 writes), estimated for a ballot with a single answer.
+//! The weight of `submit_credential_blindings` is estimated per ballot from `submit_key_switch_shares`
+//! (a proof of correct blinding per credential quotient) and includes the reads of the ballot and the
+//! registered credential of its voter. The weight of `submit_credential_decryptions` is the weight of
+//! `submit_decrypted_shares` per ballot and includes the exclusion of a discarded ballot (estimated for
+//! a ballot with a single answer, 4 reads and 4 writes) and the freeze of the ballot box by the last sealer.
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Pays, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_mixnet.
+pub trait WeightInfo {
+    fn set_vote_phase() -> Weight;
+    fn cancel_vote() -> Weight;
+    fn store_public_key() -> Weight;
+    fn store_public_key_share() -> Weight;
+    fn combine_public_key_shares() -> Weight;
+    fn propose_vote(t: u32) -> Weight;
+    fn store_vote_template(t: u32) -> Weight;
+    fn create_vote_from_template(t: u32) -> Weight;
+    fn approve_vote() -> Weight;
+    fn store_question() -> Weight;
+    fn cast_ballot(c: u32) -> Weight;
+    fn cast_relayed_ballot(c: u32) -> Weight;
+    fn cast_anonymous_ballot(c: u32) -> Weight;
+    fn import_ciphers(c: u32) -> Weight;
+    fn commit_shuffle() -> Weight;
+    fn submit_shuffled_votes_and_proof(c: u32) -> Weight;
+    fn submit_decrypted_shares(c: u32) -> Weight;
+    fn combine_decrypted_shares() -> Weight;
+    fn combine_decrypted_shares_batch(c: u32) -> Weight;
+    fn finalize_tally() -> Weight;
+    fn aggregate_ciphers() -> Weight;
+    fn rotate_public_key() -> Weight;
+    fn submit_key_switch_shares(c: u32) -> Weight;
+    fn archive_vote() -> Weight;
+    fn register_credential() -> Weight;
+    fn set_identity_provider() -> Weight;
+    fn set_eligibility_key() -> Weight;
+    fn register_voter_with_claim() -> Weight;
+    fn spoil_ballot() -> Weight;
+    fn reveal_spoiled_ballot() -> Weight;
+    fn set_metadata() -> Weight;
+    fn set_shuffle_batch_size() -> Weight;
+    fn set_decryption_threshold() -> Weight;
+    fn set_hash_function() -> Weight;
+    fn whitelist_vote() -> Weight;
+    fn claim_ballot_deposit() -> Weight;
+    fn set_voter_weight() -> Weight;
+    fn set_linked_shuffle() -> Weight;
+    fn set_topic_packing() -> Weight;
+    fn submit_linked_shuffle(c: u32, t: u32) -> Weight;
+    fn heartbeat() -> Weight;
+    fn certify_result() -> Weight;
+    fn add_sealer() -> Weight;
+    fn remove_sealer() -> Weight;
+    fn revoke_voter() -> Weight;
+    fn set_group_check() -> Weight;
+    fn check_ciphers_in_group(c: u32) -> Weight;
+    fn submit_credential_blindings(c: u32) -> Weight;
+    fn submit_credential_decryptions(c: u32) -> Weight;
+    fn do_nothing_when_its_not_your_turn() -> Weight;
+}
+
+/// Weights for pallet_mixnet using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn set_vote_phase() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_vote() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn store_public_key() -> Weight {
+        (13_390_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn store_public_key_share() -> Weight {
+        (5_595_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn combine_public_key_shares() -> Weight {
+        (32_130_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn propose_vote(t: u32) -> Weight {
+        (18_520_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(
+                T::DbWeight::get().writes((1 as Weight).saturating_mul(t as Weight)),
+            )
+    }
+    fn store_vote_template(t: u32) -> Weight {
+        (18_520_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add((100_000 as Weight).saturating_mul(t as Weight))
+    }
+    fn create_vote_from_template(t: u32) -> Weight {
+        Self::propose_vote(t).saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn approve_vote() -> Weight {
+        (18_520_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn store_question() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn cast_ballot(c: u32) -> Weight {
+        (18_510_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(
+                T::DbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)),
+            )
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+            .saturating_add(
+                T::DbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)),
+            )
+    }
+    fn cast_relayed_ballot(c: u32) -> Weight {
+        Self::cast_ballot(c).saturating_add(50_000_000 as Weight)
+    }
+    fn cast_anonymous_ballot(c: u32) -> Weight {
+        Self::cast_ballot(c).saturating_add(550_000_000 as Weight)
+    }
+    fn import_ciphers(c: u32) -> Weight {
+        (18_510_000 as Weight)
+            .saturating_add((1_250_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().reads(c as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+            .saturating_add(
+                T::DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)),
+            )
+    }
+    fn commit_shuffle() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn submit_shuffled_votes_and_proof(c: u32) -> Weight {
+        (39_744_000_000 as Weight)
+            .saturating_add((23_346_637_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
+    fn submit_decrypted_shares(c: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((4_966_438_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn combine_decrypted_shares() -> Weight {
+        (6_686_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn combine_decrypted_shares_batch(c: u32) -> Weight {
+        (753_000_000 as Weight)
+            .saturating_add((593_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn finalize_tally() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn aggregate_ciphers() -> Weight {
+        (6_686_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn rotate_public_key() -> Weight {
+        (32_130_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn submit_key_switch_shares(c: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((9_932_876_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn archive_vote() -> Weight {
+        (6_686_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(20 as Weight))
+            .saturating_add(T::DbWeight::get().writes(20 as Weight))
+    }
+    fn register_credential() -> Weight {
+        (13_390_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_identity_provider() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_eligibility_key() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn register_voter_with_claim() -> Weight {
+        (63_390_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn spoil_ballot() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+    fn reveal_spoiled_ballot() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_metadata() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_shuffle_batch_size() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_decryption_threshold() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_hash_function() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn whitelist_vote() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn claim_ballot_deposit() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn set_voter_weight() -> Weight {
+        (13_390_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_linked_shuffle() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_topic_packing() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn submit_linked_shuffle(c: u32, t: u32) -> Weight {
+        Self::submit_shuffled_votes_and_proof(c).saturating_mul(t as Weight)
+    }
+    fn heartbeat() -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn certify_result() -> Weight {
+        (35_320_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(16 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn add_sealer() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn remove_sealer() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn revoke_voter() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+    fn set_group_check() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn submit_credential_blindings(c: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((9_932_876_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(
+                T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)),
+            )
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn submit_credential_decryptions(c: u32) -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add((4_966_438_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(
+                T::DbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)),
+            )
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(
+                T::DbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)),
+            )
+    }
+    fn check_ciphers_in_group(c: u32) -> Weight {
+        (3_720_000_000 as Weight).saturating_mul(c as Weight)
+    }
+    fn do_nothing_when_its_not_your_turn() -> Weight {
+        10_000_000 as Weight
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn set_vote_phase() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_vote() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn store_public_key() -> Weight {
+        (13_390_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn store_public_key_share() -> Weight {
+        (5_595_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn combine_public_key_shares() -> Weight {
+        (32_130_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn propose_vote(t: u32) -> Weight {
+        (18_520_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(
+                RocksDbWeight::get().writes((1 as Weight).saturating_mul(t as Weight)),
+            )
+    }
+    fn store_vote_template(t: u32) -> Weight {
+        (18_520_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add((100_000 as Weight).saturating_mul(t as Weight))
+    }
+    fn create_vote_from_template(t: u32) -> Weight {
+        Self::propose_vote(t).saturating_add(RocksDbWeight::get().reads(1 as Weight))
+    }
+    fn approve_vote() -> Weight {
+        (18_520_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn store_question() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn cast_ballot(c: u32) -> Weight {
+        (18_510_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(
+                RocksDbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)),
+            )
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+            .saturating_add(
+                RocksDbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)),
+            )
+    }
+    fn cast_relayed_ballot(c: u32) -> Weight {
+        Self::cast_ballot(c).saturating_add(50_000_000 as Weight)
+    }
+    fn cast_anonymous_ballot(c: u32) -> Weight {
+        Self::cast_ballot(c).saturating_add(550_000_000 as Weight)
+    }
+    fn import_ciphers(c: u32) -> Weight {
+        (18_510_000 as Weight)
+            .saturating_add((1_250_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(c as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+            .saturating_add(
+                RocksDbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)),
+            )
+    }
+    fn commit_shuffle() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn submit_shuffled_votes_and_proof(c: u32) -> Weight {
+        (39_744_000_000 as Weight)
+            .saturating_add((23_346_637_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+    }
+    fn submit_decrypted_shares(c: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((4_966_438_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn combine_decrypted_shares() -> Weight {
+        (6_686_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn combine_decrypted_shares_batch(c: u32) -> Weight {
+        (753_000_000 as Weight)
+            .saturating_add((593_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn finalize_tally() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn aggregate_ciphers() -> Weight {
+        (6_686_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn rotate_public_key() -> Weight {
+        (32_130_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn submit_key_switch_shares(c: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((9_932_876_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn archive_vote() -> Weight {
+        (6_686_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(20 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(20 as Weight))
+    }
+    fn register_credential() -> Weight {
+        (13_390_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_identity_provider() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_eligibility_key() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn register_voter_with_claim() -> Weight {
+        (63_390_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn spoil_ballot() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
+    }
+    fn reveal_spoiled_ballot() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_metadata() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_shuffle_batch_size() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_decryption_threshold() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_hash_function() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn whitelist_vote() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn claim_ballot_deposit() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn set_voter_weight() -> Weight {
+        (13_390_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_linked_shuffle() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_topic_packing() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn submit_linked_shuffle(c: u32, t: u32) -> Weight {
+        Self::submit_shuffled_votes_and_proof(c).saturating_mul(t as Weight)
+    }
+    fn heartbeat() -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn certify_result() -> Weight {
+        (35_320_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(16 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn add_sealer() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn remove_sealer() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn revoke_voter() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(11 as Weight))
+    }
+    fn set_group_check() -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn submit_credential_blindings(c: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((9_932_876_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(
+                RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)),
+            )
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn submit_credential_decryptions(c: u32) -> Weight {
+        (17_660_000 as Weight)
+            .saturating_add((4_966_438_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(
+                RocksDbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)),
+            )
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(
+                RocksDbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)),
+            )
+    }
+    fn check_ciphers_in_group(c: u32) -> Weight {
+        (3_720_000_000 as Weight).saturating_mul(c as Weight)
+    }
+    fn do_nothing_when_its_not_your_turn() -> Weight {
+        10_000_000 as Weight
+    }
+}
+
+/// Decides per dispatchable whether the caller pays a transaction fee.
+pub trait FeePolicy {
+    fn set_vote_phase() -> Pays;
+    fn cancel_vote() -> Pays;
+    fn store_public_key() -> Pays;
+    fn store_public_key_share() -> Pays;
+    fn combine_public_key_shares() -> Pays;
+    fn propose_vote() -> Pays;
+    fn store_vote_template() -> Pays;
+    fn create_vote_from_template() -> Pays;
+    fn approve_vote() -> Pays;
+    fn store_question() -> Pays;
+    fn cast_ballot() -> Pays;
+    fn cast_relayed_ballot() -> Pays;
+    fn import_ciphers() -> Pays;
+    fn commit_shuffle() -> Pays;
+    fn submit_shuffled_votes_and_proof() -> Pays;
+    fn submit_decrypted_shares() -> Pays;
+    fn combine_decrypted_shares() -> Pays;
+    fn combine_decrypted_shares_batch() -> Pays;
+    fn finalize_tally() -> Pays;
+    fn aggregate_ciphers() -> Pays;
+    fn rotate_public_key() -> Pays;
+    fn submit_key_switch_shares() -> Pays;
+    fn archive_vote() -> Pays;
+    fn register_credential() -> Pays;
+    fn set_identity_provider() -> Pays;
+    fn set_eligibility_key() -> Pays;
+    fn register_voter_with_claim() -> Pays;
+    fn spoil_ballot() -> Pays;
+    fn reveal_spoiled_ballot() -> Pays;
+    fn set_metadata() -> Pays;
+    fn set_shuffle_batch_size() -> Pays;
+    fn set_decryption_threshold() -> Pays;
+    fn set_hash_function() -> Pays;
+    fn whitelist_vote() -> Pays;
+    fn claim_ballot_deposit() -> Pays;
+    fn set_voter_weight() -> Pays;
+    fn set_linked_shuffle() -> Pays;
+    fn set_topic_packing() -> Pays;
+    fn submit_linked_shuffle() -> Pays;
+    fn heartbeat() -> Pays;
+    fn certify_result() -> Pays;
+    fn add_sealer() -> Pays;
+    fn remove_sealer() -> Pays;
+    fn revoke_voter() -> Pays;
+    fn set_group_check() -> Pays;
+    fn submit_credential_blindings() -> Pays;
+    fn submit_credential_decryptions() -> Pays;
+    fn do_nothing_when_its_not_your_turn() -> Pays;
+}
+
+/// All dispatchables are free of charge.
+impl FeePolicy for () {
+    fn set_vote_phase() -> Pays {
+        Pays::No
+    }
+    fn cancel_vote() -> Pays {
+        Pays::No
+    }
+    fn store_public_key() -> Pays {
+        Pays::No
+    }
+    fn store_public_key_share() -> Pays {
+        Pays::No
+    }
+    fn combine_public_key_shares() -> Pays {
+        Pays::No
+    }
+    fn propose_vote() -> Pays {
+        Pays::No
+    }
+    fn store_vote_template() -> Pays {
+        Pays::No
+    }
+    fn create_vote_from_template() -> Pays {
+        Pays::No
+    }
+    fn approve_vote() -> Pays {
+        Pays::No
+    }
+    fn store_question() -> Pays {
+        Pays::No
+    }
+    fn cast_ballot() -> Pays {
+        Pays::No
+    }
+    fn cast_relayed_ballot() -> Pays {
+        Pays::No
+    }
+    fn import_ciphers() -> Pays {
+        Pays::No
+    }
+    fn commit_shuffle() -> Pays {
+        Pays::No
+    }
+    fn submit_shuffled_votes_and_proof() -> Pays {
+        Pays::No
+    }
+    fn submit_decrypted_shares() -> Pays {
+        Pays::No
+    }
+    fn combine_decrypted_shares() -> Pays {
+        Pays::No
+    }
+    fn combine_decrypted_shares_batch() -> Pays {
+        Pays::No
+    }
+    fn finalize_tally() -> Pays {
+        Pays::No
+    }
+    fn aggregate_ciphers() -> Pays {
+        Pays::No
+    }
+    fn rotate_public_key() -> Pays {
+        Pays::No
+    }
+    fn submit_key_switch_shares() -> Pays {
+        Pays::No
+    }
+    fn archive_vote() -> Pays {
+        Pays::No
+    }
+    fn register_credential() -> Pays {
+        Pays::No
+    }
+    fn set_identity_provider() -> Pays {
+        Pays::No
+    }
+    fn set_eligibility_key() -> Pays {
+        Pays::No
+    }
+    fn register_voter_with_claim() -> Pays {
+        Pays::No
+    }
+    fn spoil_ballot() -> Pays {
+        Pays::No
+    }
+    fn reveal_spoiled_ballot() -> Pays {
+        Pays::No
+    }
+    fn set_metadata() -> Pays {
+        Pays::No
+    }
+    fn set_shuffle_batch_size() -> Pays {
+        Pays::No
+    }
+    fn set_decryption_threshold() -> Pays {
+        Pays::No
+    }
+    fn set_hash_function() -> Pays {
+        Pays::No
+    }
+    fn whitelist_vote() -> Pays {
+        Pays::No
+    }
+    fn claim_ballot_deposit() -> Pays {
+        Pays::No
+    }
+    fn set_voter_weight() -> Pays {
+        Pays::No
+    }
+    fn set_linked_shuffle() -> Pays {
+        Pays::No
+    }
+    fn set_topic_packing() -> Pays {
+        Pays::No
+    }
+    fn submit_linked_shuffle() -> Pays {
+        Pays::No
+    }
+    fn heartbeat() -> Pays {
+        Pays::No
+    }
+    fn certify_result() -> Pays {
+        Pays::No
+    }
+    fn add_sealer() -> Pays {
+        Pays::No
+    }
+    fn remove_sealer() -> Pays {
+        Pays::No
+    }
+    fn revoke_voter() -> Pays {
+        Pays::No
+    }
+    fn set_group_check() -> Pays {
+        Pays::No
+    }
+    fn submit_credential_blindings() -> Pays {
+        Pays::No
+    }
+    fn submit_credential_decryptions() -> Pays {
+        Pays::No
+    }
+    fn do_nothing_when_its_not_your_turn() -> Pays {
+        Pays::No
+    }
+}
@@ -0,0 +1,80 @@
+use codec::Decode;
+use hex_literal::hex;
+
+/// the public keys (sr25519) of the dev accounts, see: `sp_keyring::AccountKeyring`
+pub const ALICE: [u8; 32] =
+    hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+pub const BOB: [u8; 32] =
+    hex!("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48");
+pub const CHARLIE: [u8; 32] =
+    hex!("90b5ab205c6974c9ea841be688864633dc9ca8a357843eeacf2314649965fe22");
+pub const DAVE: [u8; 32] =
+    hex!("306721211d5404bd9da88e0204360a1a9ab8b87c66c1bc2fcdd37f3c2222cc20");
+pub const EVE: [u8; 32] =
+    hex!("e659a7a1628cdd93febc04a4e0646ea20e9f5f0ce097d9a05290d4a9e054df4e");
+pub const FERDIE: [u8; 32] =
+    hex!("1cbd2d43530a44705ad088af313e18f80b53ef16b36177cd4b77b846f2a5f07c");
+
+/// the sealers of the dev chain in the order of the fixtures, Bob and Charlie are the genesis sealers
+pub const DEV_SEALERS: [[u8; 32]; 5] = [BOB, CHARLIE, DAVE, EVE, FERDIE];
+
+/// the prefixes of the derived accounts
+pub const AUTHORITY_PREFIX: &[u8] = b"authority";
+pub const SEALER_PREFIX: &[u8] = b"sealer";
+pub const VOTER_PREFIX: &[u8] = b"voter";
+
+/// returns the account derived from the prefix and the index: the prefix (at most 28 bytes)
+/// followed by zeros and the index (big-endian) in the last 4 bytes.
+/// the derived accounts aren't public keys, i.e., they can only sign in a mock runtime.
+pub fn derived(prefix: &[u8], index: u32) -> [u8; 32] {
+    assert!(
+        prefix.len() <= 28,
+        "the prefix must be at most 28 bytes long!"
+    );
+    let mut id = [0u8; 32];
+    id[..prefix.len()].copy_from_slice(prefix);
+    id[28..].copy_from_slice(&index.to_be_bytes());
+    id
+}
+
+/// the i-th voter (starting at 0)
+pub fn voter(index: u32) -> [u8; 32] {
+    derived(VOTER_PREFIX, index)
+}
+
+/// the i-th voting authority (starting at 0): Alice, followed by the derived authorities
+pub fn voting_authority(index: u32) -> [u8; 32] {
+    match index {
+        0 => ALICE,
+        _ => derived(AUTHORITY_PREFIX, index),
+    }
+}
+
+/// the i-th sealer (starting at 0): the dev sealers, followed by the derived sealers
+pub fn sealer(index: u32) -> [u8; 32] {
+    match DEV_SEALERS.get(index as usize) {
+        Some(id) => *id,
+        None => derived(SEALER_PREFIX, index),
+    }
+}
+
+/// decodes the account into the `AccountId` of the runtime (e.g. `sr25519::Public`)
+pub fn account<AccountId: Decode>(id: &[u8; 32]) -> AccountId {
+    AccountId::decode(&mut &id[..])
+        .expect("the AccountId must be decodable from 32 bytes!")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derived_accounts_are_distinct() {
+        assert_eq!(voting_authority(0), ALICE);
+        assert_eq!(sealer(1), CHARLIE);
+        assert_ne!(sealer(5), sealer(6));
+        assert_ne!(voter(0), voter(1));
+        assert_ne!(voter(1), derived(SEALER_PREFIX, 1));
+        assert_eq!(&voter(258)[28..], &[0, 0, 1, 2]);
+    }
+}
@@ -0,0 +1,428 @@
+use crate::accounts;
+use alloc::{collections::btree_map::BTreeMap, format, string::String, vec, vec::Vec};
+use crypto::{
+    encryption::ElGamal,
+    helper::Helper,
+    proofs::keygen::KeyGenerationProof,
+    types::{ElGamalParams, ModuloOperations, PrivateKey, PublicKey as ElGamalPK},
+};
+use mixnet_primitives::wire::{Cipher, PublicKey, PublicParameters};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+/// the private key shares of the dev sealers (Bob, Charlie, ...), the ones of the unit tests
+const DEV_SEALER_KEYS: [&[u8]; 5] = [
+    b"12345678",
+    b"87654321",
+    b"13572468",
+    b"24681357",
+    b"11223344",
+];
+
+/// the random of the proofs of the public key shares, the one of the unit tests
+const KEY_GENERATION_RANDOM: &[u8] = b"1701411834604692317316873";
+
+/// the length of the key of the ElGamal system, see: `crypto::helper::Helper`
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum KeySize {
+    /// 6bit (p = 47), only a few distinct messages can be encrypted
+    Tiny,
+    /// 48bit
+    Small,
+    /// 256bit
+    Bits256,
+    /// 512bit
+    Bits512,
+    /// 1024bit
+    Medium,
+    /// 2048bit, the key length of the dev chain
+    Large,
+    /// 3072bit
+    ExtraLarge,
+}
+
+impl Default for KeySize {
+    fn default() -> Self {
+        KeySize::Small
+    }
+}
+
+impl KeySize {
+    pub fn params(&self) -> ElGamalParams {
+        let (params, _, _) = match self {
+            KeySize::Tiny => Helper::setup_tiny_system(),
+            KeySize::Small => Helper::setup_sm_system(),
+            KeySize::Bits256 => Helper::setup_256bit_system(),
+            KeySize::Bits512 => Helper::setup_512bit_system(),
+            KeySize::Medium => Helper::setup_md_system(),
+            KeySize::Large => Helper::setup_lg_system(),
+            KeySize::ExtraLarge => Helper::setup_xl_system(),
+        };
+        params
+    }
+}
+
+/// a sealer with its key share
+#[derive(Clone, Debug)]
+pub struct SealerFixture {
+    /// the account of the sealer, see: `accounts::sealer`
+    pub account: [u8; 32],
+    pub sk: PrivateKey,
+    pub pk: ElGamalPK,
+}
+
+impl SealerFixture {
+    /// the proof of knowledge of the private key share, bound to the account of the sealer
+    pub fn key_generation_proof(&self) -> KeyGenerationProof {
+        let q = self.pk.params.q();
+        let r = BigUint::parse_bytes(KEY_GENERATION_RANDOM, 10).unwrap() % q;
+        KeyGenerationProof::generate(
+            &self.pk.params,
+            &self.sk.x,
+            &self.pk.h,
+            &r,
+            &self.account,
+        )
+    }
+}
+
+/// a ballot with one answer per topic of the vote
+#[derive(Clone, Debug)]
+pub struct BallotFixture {
+    /// the voter, see: `accounts::voter`
+    pub voter: [u8; 32],
+    /// the encrypted answers (topic_id, cipher) in the order of the topics of the vote
+    pub answers: Vec<(Vec<u8>, Cipher)>,
+    /// the plaintext of every answer
+    pub messages: Vec<u64>,
+    /// the random every answer has been encrypted with, e.g. to prove the knowledge of the plaintext
+    pub randoms: Vec<BigUint>,
+}
+
+/// a vote with its topics and pre-cast ballots
+#[derive(Clone, Debug)]
+pub struct VoteFixture {
+    pub vote_id: Vec<u8>,
+    pub title: Vec<u8>,
+    pub topic_ids: Vec<Vec<u8>>,
+    pub ballots: Vec<BallotFixture>,
+}
+
+impl VoteFixture {
+    /// the expected result of the topic: the number of ballots per message
+    pub fn expected_tally(&self, topic_id: &[u8]) -> BTreeMap<u64, u32> {
+        let index = self
+            .topic_ids
+            .iter()
+            .position(|id| id.as_slice() == topic_id)
+            .expect("the topic must be part of the vote!");
+        let mut tally: BTreeMap<u64, u32> = BTreeMap::new();
+        for ballot in self.ballots.iter() {
+            *tally.entry(ballot.messages[index]).or_insert(0) += 1;
+        }
+        tally
+    }
+}
+
+/// the accounts, keys, votes and ballots of an election
+#[derive(Clone, Debug)]
+pub struct ElectionFixture {
+    pub key_size: KeySize,
+    pub params: ElGamalParams,
+    pub voting_authorities: Vec<[u8; 32]>,
+    pub sealers: Vec<SealerFixture>,
+    /// the public key of the votes, i.e., the combination of the public key shares of all sealers
+    pub public_key: ElGamalPK,
+    pub votes: Vec<VoteFixture>,
+    /// whether the messages are encoded (g^m) before the encryption
+    pub encoded: bool,
+}
+
+impl ElectionFixture {
+    pub fn builder() -> ElectionFixtureBuilder {
+        ElectionFixtureBuilder::default()
+    }
+
+    /// the public parameters as stored by the pallet
+    pub fn public_parameters(&self) -> PublicParameters {
+        self.params.clone().into()
+    }
+
+    /// the public key as stored by the pallet once the key shares are combined
+    pub fn system_public_key(&self) -> PublicKey {
+        self.public_key.clone().into()
+    }
+
+    pub fn vote(&self, vote_id: &[u8]) -> Option<&VoteFixture> {
+        self.votes
+            .iter()
+            .find(|vote| vote.vote_id.as_slice() == vote_id)
+    }
+}
+
+/// builds an `ElectionFixture`, by default: a 48bit key, Alice as voting authority,
+/// Bob and Charlie as sealers and no votes.
+#[derive(Clone, Debug)]
+pub struct ElectionFixtureBuilder {
+    key_size: KeySize,
+    nr_of_voting_authorities: u32,
+    nr_of_sealers: u32,
+    votes: Vec<(Vec<u8>, Vec<Vec<u8>>)>,
+    nr_of_ballots: u32,
+    messages: Vec<u64>,
+    encoded: bool,
+}
+
+impl Default for ElectionFixtureBuilder {
+    fn default() -> Self {
+        ElectionFixtureBuilder {
+            key_size: KeySize::default(),
+            nr_of_voting_authorities: 1,
+            nr_of_sealers: 2,
+            votes: Vec::new(),
+            nr_of_ballots: 0,
+            messages: vec![1, 3, 4],
+            encoded: true,
+        }
+    }
+}
+
+impl ElectionFixtureBuilder {
+    pub fn key_size(mut self, key_size: KeySize) -> Self {
+        self.key_size = key_size;
+        self
+    }
+
+    pub fn voting_authorities(mut self, nr_of_voting_authorities: u32) -> Self {
+        self.nr_of_voting_authorities = nr_of_voting_authorities;
+        self
+    }
+
+    pub fn sealers(mut self, nr_of_sealers: u32) -> Self {
+        self.nr_of_sealers = nr_of_sealers;
+        self
+    }
+
+    /// adds a vote with the topics, the title is derived from the vote_id
+    pub fn vote(mut self, vote_id: &[u8], topic_ids: &[&[u8]]) -> Self {
+        let topic_ids = topic_ids.iter().map(|id| id.to_vec()).collect();
+        self.votes.push((vote_id.to_vec(), topic_ids));
+        self
+    }
+
+    /// the number of ballots cast per vote
+    pub fn ballots(mut self, nr_of_ballots: u32) -> Self {
+        self.nr_of_ballots = nr_of_ballots;
+        self
+    }
+
+    /// the messages the voters choose from: the i-th voter answers every topic with
+    /// the (i + topic)-th message (modulo the number of messages)
+    pub fn messages(mut self, messages: &[u64]) -> Self {
+        self.messages = messages.to_vec();
+        self
+    }
+
+    /// whether the messages are encoded (g^m) before the encryption.
+    /// messages which aren't encoded must be quadratic residues (see: `ElGamal::encrypt`).
+    pub fn encoded(mut self, encoded: bool) -> Self {
+        self.encoded = encoded;
+        self
+    }
+
+    pub fn build(self) -> ElectionFixture {
+        assert!(
+            self.nr_of_voting_authorities > 0,
+            "there must be a voting authority!"
+        );
+        assert!(self.nr_of_sealers > 0, "there must be a sealer!");
+        assert!(!self.messages.is_empty(), "there must be a message!");
+
+        let params = self.key_size.params();
+        let q = params.q();
+        let voting_authorities = (0..self.nr_of_voting_authorities)
+            .map(accounts::voting_authority)
+            .collect();
+        let sealers: Vec<SealerFixture> = (0..self.nr_of_sealers)
+            .map(|index| setup_sealer(&params, index))
+            .collect();
+        let public_key = ElGamalPK {
+            h: sealers.iter().fold(BigUint::one(), |h, sealer| {
+                h.modmul(&sealer.pk.h, &params.p)
+            }),
+            params: params.clone(),
+        };
+
+        if !self.encoded {
+            for message in self.messages.iter() {
+                assert!(
+                    BigUint::from(*message).modpow(&q, &params.p) == BigUint::one(),
+                    "the message {} must be a quadratic residue to be encrypted without encoding!",
+                    message
+                );
+            }
+        }
+
+        let votes = self
+            .votes
+            .iter()
+            .map(|(vote_id, topic_ids)| VoteFixture {
+                vote_id: vote_id.clone(),
+                title: format!("Vote {}", String::from_utf8_lossy(vote_id)).into_bytes(),
+                topic_ids: topic_ids.clone(),
+                ballots: (0..self.nr_of_ballots)
+                    .map(|index| {
+                        setup_ballot(
+                            &public_key,
+                            topic_ids,
+                            &self.messages,
+                            index,
+                            self.encoded,
+                        )
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        ElectionFixture {
+            key_size: self.key_size,
+            params,
+            voting_authorities,
+            sealers,
+            public_key,
+            votes,
+            encoded: self.encoded,
+        }
+    }
+}
+
+/// the key share of the i-th sealer, the dev sealers use the keys of the unit tests
+fn setup_sealer(params: &ElGamalParams, index: u32) -> SealerFixture {
+    let q = params.q();
+    let x = match DEV_SEALER_KEYS.get(index as usize) {
+        Some(key) => BigUint::parse_bytes(key, 10).unwrap(),
+        None => {
+            BigUint::from(index) * BigUint::from(7_919u32) + BigUint::from(104_729u32)
+        }
+    } % &q;
+    // the private key share must not be zero, i.e., in the small systems
+    let x = if x.is_zero() { BigUint::one() } else { x };
+    let (pk, sk) = Helper::generate_key_pair(params, &x);
+    SealerFixture {
+        account: accounts::sealer(index),
+        sk,
+        pk,
+    }
+}
+
+/// the ballot of the i-th voter, the randoms are derived from the index of the voter and the topic
+fn setup_ballot(
+    pk: &ElGamalPK,
+    topic_ids: &[Vec<u8>],
+    messages: &[u64],
+    index: u32,
+    encoded: bool,
+) -> BallotFixture {
+    let q = pk.params.q();
+    let mut answers = Vec::with_capacity(topic_ids.len());
+    let mut plaintexts = Vec::with_capacity(topic_ids.len());
+    let mut randoms = Vec::with_capacity(topic_ids.len());
+
+    for (position, topic_id) in topic_ids.iter().enumerate() {
+        let message = messages[(index as usize + position) % messages.len()];
+        let seed = index as u64 * topic_ids.len() as u64 + position as u64;
+        let r = BigUint::from(seed) % (&q - BigUint::one()) + BigUint::one();
+        let m = BigUint::from(message);
+        let cipher = match encoded {
+            true => ElGamal::encrypt_encode(&m, &r, pk),
+            false => ElGamal::encrypt(&m, &r, pk),
+        };
+        answers.push((topic_id.clone(), cipher.into()));
+        plaintexts.push(message);
+        randoms.push(r);
+    }
+
+    BallotFixture {
+        voter: accounts::voter(index),
+        answers,
+        messages: plaintexts,
+        randoms,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto::types::Cipher as BigCipher;
+
+    #[test]
+    fn test_default_fixture_has_the_dev_authorities() {
+        let fixture = ElectionFixture::builder().build();
+        assert_eq!(fixture.voting_authorities, vec![accounts::ALICE]);
+        let sealers: Vec<[u8; 32]> = fixture
+            .sealers
+            .iter()
+            .map(|sealer| sealer.account)
+            .collect();
+        assert_eq!(sealers, vec![accounts::BOB, accounts::CHARLIE]);
+        assert!(fixture.votes.is_empty());
+    }
+
+    #[test]
+    fn test_public_key_combines_the_key_shares() {
+        let fixture = ElectionFixture::builder().sealers(7).build();
+        let sk_x = fixture.sealers.iter().fold(BigUint::zero(), |x, sealer| {
+            (x + &sealer.sk.x) % fixture.params.q()
+        });
+        let (pk, _) = Helper::generate_key_pair(&fixture.params, &sk_x);
+        assert_eq!(fixture.public_key.h, pk.h);
+        for sealer in fixture.sealers.iter() {
+            let proof = sealer.key_generation_proof();
+            assert!(KeyGenerationProof::verify(
+                &fixture.params,
+                &sealer.pk.h,
+                &proof,
+                &sealer.account
+            ));
+        }
+    }
+
+    #[test]
+    fn test_ballots_decrypt_to_their_messages() {
+        let fixture = ElectionFixture::builder()
+            .key_size(KeySize::Tiny)
+            .vote(b"20201212", &[b"20201212-01", b"20201212-02"])
+            .ballots(5)
+            .build();
+        let vote = fixture.vote(b"20201212").unwrap();
+        assert_eq!(vote.ballots.len(), 5);
+
+        let sk = PrivateKey {
+            params: fixture.params.clone(),
+            x: fixture
+                .sealers
+                .iter()
+                .fold(BigUint::zero(), |x, sealer| x + &sealer.sk.x),
+        };
+        for ballot in vote.ballots.iter() {
+            for (((_, cipher), message), r) in ballot
+                .answers
+                .iter()
+                .zip(ballot.messages.iter())
+                .zip(ballot.randoms.iter())
+            {
+                let cipher: BigCipher = cipher.clone().into();
+                assert_eq!(
+                    ElGamal::decrypt_decode(&cipher, &sk),
+                    BigUint::from(*message)
+                );
+                let m = BigUint::from(*message);
+                assert_eq!(ElGamal::encrypt_encode(&m, r, &fixture.public_key), cipher);
+            }
+        }
+
+        let tally = vote.expected_tally(b"20201212-01");
+        assert_eq!(tally.values().sum::<u32>(), 5);
+        assert_eq!(tally.get(&1), Some(&2));
+    }
+}
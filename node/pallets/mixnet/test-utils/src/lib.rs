@@ -0,0 +1,20 @@
+//! Test fixtures of the mixnet pallet, shared by the unit tests and benchmarks of the
+//! pallet and the simulation harness of the clients.
+//!
+//! The accounts of the dev chain (Alice as voting authority, Bob and Charlie as sealers)
+//! and the accounts derived from an index (e.g. voters) are in `accounts`.
+//! An `ElectionFixture` (see: `fixture`) bundles the voting authorities, the sealers with
+//! their key shares, the votes and the pre-cast ballots of an election of configurable
+//! size and key length. The fixtures don't depend on a runtime: the accounts are decoded
+//! into the `AccountId` of the runtime and the ciphers are the wire types of the pallet.
+//! All keys and randoms are derived from an index, i.e., the fixtures are deterministic.
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+pub mod accounts;
+pub mod fixture;
+
+pub use fixture::{
+    BallotFixture, ElectionFixture, ElectionFixtureBuilder, KeySize, SealerFixture,
+    VoteFixture,
+};
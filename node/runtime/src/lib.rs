@@ -18,7 +18,7 @@ use sp_runtime::traits::{
 };
 use sp_runtime::{
     create_runtime_str, generic, impl_opaque_keys,
-    transaction_validity::{TransactionSource, TransactionValidity},
+    transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
     ApplyExtrinsicResult, MultiSignature, SaturatedConversion,
 };
 use sp_std::prelude::*;
@@ -34,7 +34,7 @@ pub use frame_support::{
         constants::{
             BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND,
         },
-        IdentityFee, Weight,
+        IdentityFee, Pays, Weight,
     },
     StorageValue,
 };
@@ -101,7 +101,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("provotum"),
     impl_name: create_runtime_str!("provotum"),
     authoring_version: 1,
-    spec_version: 2,
+    spec_version: 3,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -272,6 +272,25 @@ impl pallet_sudo::Trait for Runtime {
 
 parameter_types! {
     pub const BlockDuration: BlockNumber = 1u64;
+    pub const CipherChunkSize: u32 = 256;
+    pub const ShuffleTurnTimeout: BlockNumber = 10u64;
+    pub const DecryptionTimeout: BlockNumber = 100u64;
+    pub const HeartbeatInterval: BlockNumber = 20u64;
+    pub const CheckpointInterval: BlockNumber = 100u64;
+    pub const CertificationQuorum: u32 = 2;
+    pub const MaxMetadataSize: u32 = 4096;
+    pub const MaxVoteIdLength: u32 = 64;
+    pub const MaxTitleLength: u32 = 256;
+    pub const MaxTopicIdLength: u32 = 64;
+    pub const MaxQuestionLength: u32 = 1024;
+    pub const MinShuffleBatchSize: u64 = 1;
+    pub const MaxShuffleBatchSize: u64 = 1000;
+    pub const ShuffleTimeBudget: u64 = 2000;
+    pub const MixnetUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+    pub const BallotDeposit: Balance = 1_000;
+    pub const MaxBallotsPerPeriod: u32 = 10;
+    pub const BallotRatePeriod: BlockNumber = 10u64;
+    pub const MaxVoterWeight: u32 = 1_000_000;
 }
 
 impl pallet_mixnet::Trait for Runtime {
@@ -279,6 +298,184 @@ impl pallet_mixnet::Trait for Runtime {
     type Call = Call;
     type AuthorityId = pallet_mixnet::keys::TestAuthId;
     type BlockDuration = BlockDuration;
+    type CipherChunkSize = CipherChunkSize;
+    type ShuffleTurnTimeout = ShuffleTurnTimeout;
+    type DecryptionTimeout = DecryptionTimeout;
+    type HeartbeatInterval = HeartbeatInterval;
+    type CheckpointInterval = CheckpointInterval;
+    type CertificationQuorum = CertificationQuorum;
+    type MaxMetadataSize = MaxMetadataSize;
+    type MaxVoteIdLength = MaxVoteIdLength;
+    type MaxTitleLength = MaxTitleLength;
+    type MaxTopicIdLength = MaxTopicIdLength;
+    type MaxQuestionLength = MaxQuestionLength;
+    type MinShuffleBatchSize = MinShuffleBatchSize;
+    type MaxShuffleBatchSize = MaxShuffleBatchSize;
+    type ShuffleTimeBudget = ShuffleTimeBudget;
+    type UnsignedPriority = MixnetUnsignedPriority;
+    type Currency = Balances;
+    type BallotDeposit = BallotDeposit;
+    type MaxBallotsPerPeriod = MaxBallotsPerPeriod;
+    type BallotRatePeriod = BallotRatePeriod;
+    type MaxVoterWeight = MaxVoterWeight;
+    type WeightInfo = pallet_mixnet::weights::SubstrateWeight<Runtime>;
+    type FeePolicy = MixnetFeePolicy;
+}
+
+/// Casting and spoiling a ballot and registering a voter by an eligibility claim is free of charge,
+/// voters don't need to hold any funds.
+/// All other extrinsics are restricted to the voting authority or the sealers
+/// and are charged so that other accounts cannot flood the chain with them.
+/// The fee of `archive_vote` is refunded if the vote is archived successfully.
+/// The sealers reveal their shuffles and submit their decrypted shares as unsigned transactions
+/// (signed payloads), which are never charged.
+pub struct MixnetFeePolicy;
+
+impl pallet_mixnet::weights::FeePolicy for MixnetFeePolicy {
+    fn set_vote_phase() -> Pays {
+        Pays::Yes
+    }
+    fn cancel_vote() -> Pays {
+        Pays::Yes
+    }
+    fn store_public_key() -> Pays {
+        Pays::Yes
+    }
+    fn store_public_key_share() -> Pays {
+        Pays::Yes
+    }
+    fn combine_public_key_shares() -> Pays {
+        Pays::Yes
+    }
+    fn propose_vote() -> Pays {
+        Pays::Yes
+    }
+    fn store_vote_template() -> Pays {
+        Pays::Yes
+    }
+    fn create_vote_from_template() -> Pays {
+        Pays::Yes
+    }
+    fn approve_vote() -> Pays {
+        Pays::Yes
+    }
+    fn store_question() -> Pays {
+        Pays::Yes
+    }
+    fn cast_ballot() -> Pays {
+        Pays::No
+    }
+    fn cast_relayed_ballot() -> Pays {
+        Pays::No
+    }
+    fn import_ciphers() -> Pays {
+        Pays::Yes
+    }
+    fn commit_shuffle() -> Pays {
+        Pays::Yes
+    }
+    fn submit_shuffled_votes_and_proof() -> Pays {
+        Pays::Yes
+    }
+    fn submit_decrypted_shares() -> Pays {
+        Pays::Yes
+    }
+    fn combine_decrypted_shares() -> Pays {
+        Pays::Yes
+    }
+    fn combine_decrypted_shares_batch() -> Pays {
+        Pays::Yes
+    }
+    fn finalize_tally() -> Pays {
+        Pays::Yes
+    }
+    fn aggregate_ciphers() -> Pays {
+        Pays::Yes
+    }
+    fn rotate_public_key() -> Pays {
+        Pays::Yes
+    }
+    fn submit_key_switch_shares() -> Pays {
+        Pays::Yes
+    }
+    fn archive_vote() -> Pays {
+        Pays::Yes
+    }
+    fn register_credential() -> Pays {
+        Pays::Yes
+    }
+    fn set_identity_provider() -> Pays {
+        Pays::Yes
+    }
+    fn set_eligibility_key() -> Pays {
+        Pays::Yes
+    }
+    fn register_voter_with_claim() -> Pays {
+        Pays::No
+    }
+    fn spoil_ballot() -> Pays {
+        Pays::No
+    }
+    fn reveal_spoiled_ballot() -> Pays {
+        Pays::No
+    }
+    fn set_metadata() -> Pays {
+        Pays::Yes
+    }
+    fn set_shuffle_batch_size() -> Pays {
+        Pays::Yes
+    }
+    fn set_decryption_threshold() -> Pays {
+        Pays::Yes
+    }
+    fn set_hash_function() -> Pays {
+        Pays::Yes
+    }
+    fn whitelist_vote() -> Pays {
+        Pays::Yes
+    }
+    fn claim_ballot_deposit() -> Pays {
+        Pays::Yes
+    }
+    fn set_voter_weight() -> Pays {
+        Pays::Yes
+    }
+    fn set_linked_shuffle() -> Pays {
+        Pays::Yes
+    }
+    fn set_topic_packing() -> Pays {
+        Pays::Yes
+    }
+    fn submit_linked_shuffle() -> Pays {
+        Pays::Yes
+    }
+    fn heartbeat() -> Pays {
+        Pays::Yes
+    }
+    fn certify_result() -> Pays {
+        Pays::Yes
+    }
+    fn add_sealer() -> Pays {
+        Pays::Yes
+    }
+    fn remove_sealer() -> Pays {
+        Pays::Yes
+    }
+    fn revoke_voter() -> Pays {
+        Pays::Yes
+    }
+    fn set_group_check() -> Pays {
+        Pays::Yes
+    }
+    fn submit_credential_blindings() -> Pays {
+        Pays::Yes
+    }
+    fn submit_credential_decryptions() -> Pays {
+        Pays::Yes
+    }
+    fn do_nothing_when_its_not_your_turn() -> Pays {
+        Pays::Yes
+    }
 }
 
 // Payload data to be signed when making signed transaction from off-chain workers
@@ -367,7 +564,7 @@ construct_runtime!(
         Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 
         // include the custom logic from the pallet-mixnet.
-        PalletMixnet: pallet_mixnet::{Module, Call, Storage, Event<T>, Config<T>},
+        PalletMixnet: pallet_mixnet::{Module, Call, Storage, Event<T>, Config<T>, ValidateUnsigned},
     }
 );
 
@@ -530,6 +727,99 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_mixnet_runtime_api::MixnetApi<Block, AccountId> for Runtime {
+        fn get_cipher_chunk_count(
+            vote_id: pallet_mixnet::types::VoteId,
+            topic_id: pallet_mixnet::types::TopicId,
+            nr_of_shuffles: pallet_mixnet::types::NrOfShuffles,
+        ) -> pallet_mixnet::types::ChunkIndex {
+            PalletMixnet::cipher_chunk_count((vote_id, topic_id), nr_of_shuffles)
+        }
+
+        fn get_cipher_chunk(
+            vote_id: pallet_mixnet::types::VoteId,
+            topic_id: pallet_mixnet::types::TopicId,
+            nr_of_shuffles: pallet_mixnet::types::NrOfShuffles,
+            chunk_index: pallet_mixnet::types::ChunkIndex,
+        ) -> Vec<pallet_mixnet::types::Cipher> {
+            PalletMixnet::cipher_chunk(&vote_id, &topic_id, nr_of_shuffles, chunk_index)
+        }
+
+        fn get_ciphers(
+            vote_id: pallet_mixnet::types::VoteId,
+            topic_id: pallet_mixnet::types::TopicId,
+            nr_of_shuffles: pallet_mixnet::types::NrOfShuffles,
+            offset: u64,
+            limit: u64,
+        ) -> pallet_mixnet::types::CipherPage {
+            PalletMixnet::cipher_page(&vote_id, &topic_id, nr_of_shuffles, offset, limit)
+        }
+
+        fn get_cipher_proof(
+            vote_id: pallet_mixnet::types::VoteId,
+            topic_id: pallet_mixnet::types::TopicId,
+            nr_of_shuffles: pallet_mixnet::types::NrOfShuffles,
+            index: u64,
+        ) -> Option<pallet_mixnet::types::CipherMerkleProof> {
+            PalletMixnet::cipher_proof(&vote_id, &topic_id, nr_of_shuffles, index)
+        }
+
+        fn get_vote_phase(
+            vote_id: pallet_mixnet::types::VoteId,
+        ) -> Option<pallet_mixnet::types::VotePhase> {
+            PalletMixnet::vote_phase(&vote_id)
+        }
+
+        fn get_vote(
+            vote_id: pallet_mixnet::types::VoteId,
+        ) -> Option<pallet_mixnet::types::VoteDetails<AccountId>> {
+            PalletMixnet::vote_details(&vote_id)
+        }
+
+        fn get_result(
+            vote_id: pallet_mixnet::types::VoteId,
+            topic_id: pallet_mixnet::types::TopicId,
+        ) -> Option<pallet_mixnet::types::TopicResult> {
+            PalletMixnet::tally((vote_id, topic_id))
+        }
+
+        fn get_shuffle_progress(
+            vote_id: pallet_mixnet::types::VoteId,
+            topic_id: pallet_mixnet::types::TopicId,
+        ) -> Option<pallet_mixnet::types::ShuffleState> {
+            PalletMixnet::shuffle_state((vote_id, topic_id))
+        }
+
+        fn get_progress(
+            vote_id: pallet_mixnet::types::VoteId,
+        ) -> Vec<pallet_mixnet::types::TopicProgress> {
+            PalletMixnet::progress(&vote_id)
+        }
+
+        fn verify_full_decryption_chain(
+            vote_id: pallet_mixnet::types::VoteId,
+            topic_id: pallet_mixnet::types::TopicId,
+            nr_of_shuffles: pallet_mixnet::types::NrOfShuffles,
+        ) -> bool {
+            PalletMixnet::verify_full_decryption_chain(&vote_id, &topic_id, &nr_of_shuffles)
+        }
+
+        fn get_ballot_receipt(
+            vote_id: pallet_mixnet::types::VoteId,
+            account: AccountId,
+        ) -> Option<pallet_mixnet::types::BallotReceipt<BlockNumber, Hash>> {
+            PalletMixnet::ballot_receipt(&vote_id, &account)
+        }
+
+        fn validate_ballot(
+            vote_id: pallet_mixnet::types::VoteId,
+            account: AccountId,
+            ballot: pallet_mixnet::types::Ballot,
+        ) -> Result<(), u16> {
+            PalletMixnet::validate_ballot(&account, &vote_id, &ballot)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn dispatch_benchmark(
@@ -1,11 +1,13 @@
 mod health;
 mod index;
 mod randomizer;
+mod randomness;
 
 use actix_web::{App, HttpServer};
 use health::get_health;
 use index::get_index;
-use randomizer::randomize_ballot;
+use randomizer::{randomize_ballot, re_encrypt_ballot};
+use randomness::{get_permutation, get_randomness};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -14,6 +16,9 @@ async fn main() -> std::io::Result<()> {
             .service(get_index)
             .service(get_health)
             .service(randomize_ballot)
+            .service(re_encrypt_ballot)
+            .service(get_randomness)
+            .service(get_permutation)
     })
     .bind(("0.0.0.0", 8080))?
     .run()
@@ -21,6 +21,21 @@ pub struct ResponseBody {
     pub cipher: Cipher,
 }
 
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct ReEncryptionResponseBody {
+    pub cipher: Cipher,
+}
+
+/// re-encrypts the cipher with fresh randomness, without a proof of the re-encryption
+#[post("/reencrypt")]
+pub async fn re_encrypt_ballot(body: web::Json<RequestBody>) -> impl Responder {
+    let pk = &body.pk;
+    let r = Random::get_random_less_than(&pk.params.q());
+    web::Json(ReEncryptionResponseBody {
+        cipher: ElGamal::re_encrypt(&body.cipher, &r, pk),
+    })
+}
+
 #[post("/randomize")]
 pub async fn randomize_ballot(body: web::Json<RequestBody>) -> impl Responder {
     // common values
@@ -52,7 +67,9 @@ pub async fn randomize_ballot(body: web::Json<RequestBody>) -> impl Responder {
 
 #[cfg(test)]
 mod tests {
-    use super::{randomize_ballot, RequestBody, ResponseBody};
+    use super::{
+        randomize_ballot, re_encrypt_ballot, ReEncryptionResponseBody, RequestBody, ResponseBody,
+    };
     use actix_web::{test, App};
     use crypto::{
         encryption::ElGamal, helper::Helper, proofs::re_encryption::ReEncryptionProof,
@@ -106,4 +123,32 @@ mod tests {
         let decrypted = ElGamal::decrypt(&re_encrypted_cipher, &sk);
         assert_eq!(&decrypted, vote);
     }
+
+    #[actix_rt::test]
+    async fn test_post_re_encrypt_ballot() {
+        let app = App::new().service(re_encrypt_ballot);
+        let mut test_app = test::init_service(app).await;
+
+        let (_, sk, pk) = Helper::setup_sm_system();
+        let q = &pk.params.q();
+        let vote = &BigUint::from(13u32);
+        let r = Random::get_random_less_than(q);
+        let cipher = ElGamal::encrypt(vote, &r, &pk);
+        let request_body = RequestBody {
+            pk: pk.clone(),
+            cipher: cipher.clone(),
+        };
+
+        // send post request to re-encrypt ballot
+        let req = test::TestRequest::post()
+            .uri("/reencrypt")
+            .set_json(&request_body)
+            .to_request();
+        let resp: ReEncryptionResponseBody = test::read_response_json(&mut test_app, req).await;
+
+        // the cipher changes but still contains the vote
+        assert_ne!(&resp.cipher, &cipher);
+        let decrypted = ElGamal::decrypt(&resp.cipher, &sk);
+        assert_eq!(&decrypted, vote);
+    }
 }
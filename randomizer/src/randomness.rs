@@ -0,0 +1,106 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use crypto::random::Random;
+use num_bigint::{BigUint, RandBigInt};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+
+/// the largest random number which can be requested (in bits)
+pub const MAX_BITS: u64 = 8192;
+
+/// the largest permutation which can be requested
+pub const MAX_PERMUTATION_SIZE: usize = 100_000;
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct RandomnessResponse {
+    pub bits: u64,
+    #[serde(with = "crypto::proofs::wire::serde_hex")]
+    pub value: BigUint,
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct PermutationResponse {
+    pub permutation: Vec<usize>,
+}
+
+/// returns a random number of at most `bits` bits drawn from the OS random number generator
+#[get("/randomness/{bits}")]
+pub async fn get_randomness(bits: web::Path<u64>) -> impl Responder {
+    let bits = bits.into_inner();
+    if bits == 0 || bits > MAX_BITS {
+        return HttpResponse::BadRequest()
+            .body(format!("bits must be in the range [1, {}]", MAX_BITS));
+    }
+    let value = OsRng.gen_biguint(bits);
+    HttpResponse::Ok().json(RandomnessResponse { bits, value })
+}
+
+/// returns a random permutation of the numbers 0..n
+#[get("/permutation/{n}")]
+pub async fn get_permutation(n: web::Path<usize>) -> impl Responder {
+    let n = n.into_inner();
+    if n == 0 || n > MAX_PERMUTATION_SIZE {
+        return HttpResponse::BadRequest().body(format!(
+            "n must be in the range [1, {}]",
+            MAX_PERMUTATION_SIZE
+        ));
+    }
+    let permutation = Random::generate_permutation(&n);
+    HttpResponse::Ok().json(PermutationResponse { permutation })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        get_permutation, get_randomness, PermutationResponse, RandomnessResponse, MAX_BITS,
+    };
+    use actix_web::{http::StatusCode, test, App};
+
+    #[actix_rt::test]
+    async fn test_get_randomness() {
+        let app = App::new().service(get_randomness);
+        let mut test_app = test::init_service(app).await;
+
+        let req = test::TestRequest::get().uri("/randomness/256").to_request();
+        let resp: RandomnessResponse = test::read_response_json(&mut test_app, req).await;
+        assert_eq!(resp.bits, 256);
+        assert!(resp.value.bits() <= 256);
+
+        // two random numbers are not the same
+        let req = test::TestRequest::get().uri("/randomness/256").to_request();
+        let other: RandomnessResponse = test::read_response_json(&mut test_app, req).await;
+        assert_ne!(resp.value, other.value);
+    }
+
+    #[actix_rt::test]
+    async fn test_get_randomness_out_of_range() {
+        let app = App::new().service(get_randomness);
+        let mut test_app = test::init_service(app).await;
+
+        let req = test::TestRequest::get().uri("/randomness/0").to_request();
+        let resp = test::call_service(&mut test_app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let uri = format!("/randomness/{}", MAX_BITS + 1);
+        let req = test::TestRequest::get().uri(&uri).to_request();
+        let resp = test::call_service(&mut test_app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_rt::test]
+    async fn test_get_permutation() {
+        let app = App::new().service(get_permutation);
+        let mut test_app = test::init_service(app).await;
+
+        let req = test::TestRequest::get().uri("/permutation/10").to_request();
+        let resp: PermutationResponse = test::read_response_json(&mut test_app, req).await;
+
+        // the permutation contains every number of 0..10 exactly once
+        let mut sorted = resp.permutation.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..10).collect::<Vec<usize>>());
+
+        let req = test::TestRequest::get().uri("/permutation/0").to_request();
+        let resp = test::call_service(&mut test_app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
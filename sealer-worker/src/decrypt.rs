@@ -0,0 +1,49 @@
+//! Decrypts the shuffled ciphers of a topic with the private key share of the sealer,
+//! the same computation as `provotum-cli sealer decrypt`.
+use crypto::{
+    encryption::ElGamal,
+    hash::HashFunction,
+    helper::Helper,
+    proofs::decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
+    random::Random,
+    secret,
+    types::Cipher as BigCipher,
+};
+use num_bigint::BigUint;
+use pallet_mixnet::types::{Cipher, DecryptedShare, DecryptedShareProof, Wrapper};
+
+/// the partial decryptions of the ciphers and the proof of their correctness,
+/// the challenge of the proof is computed with the hash function of the vote
+pub fn partial_decrypt(
+    ciphers: Vec<Cipher>,
+    sk_as_string: &str,
+    sealer_id: &[u8; 32],
+    hash_function: HashFunction,
+) -> (Vec<DecryptedShare>, DecryptedShareProof) {
+    let (params, sk, pk) = Helper::setup_lg_system_with_sk(sk_as_string.as_bytes());
+    let encryptions: Vec<BigCipher> = Wrapper(ciphers).into();
+
+    // the ciphers are decrypted on all cores
+    let partial_decryptions: Vec<BigUint> = ElGamal::partial_decrypt_many(&encryptions, &sk);
+    let shares: Vec<DecryptedShare> = partial_decryptions
+        .iter()
+        .map(|share| share.to_bytes_be())
+        .collect();
+
+    let mut r = Random::get_random_less_than(&params.q());
+    let proof = DecryptionProof::generate_batched_with_hash(
+        &params,
+        &sk.x,
+        &pk.h.into(),
+        &r,
+        &encryptions,
+        &partial_decryptions,
+        sealer_id,
+        DEFAULT_BATCH_SOUNDNESS,
+        hash_function,
+    );
+
+    // the private key is wiped when it is dropped, the random of the proof reveals it
+    secret::wipe(&mut r);
+    (shares, proof.into())
+}
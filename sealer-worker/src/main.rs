@@ -0,0 +1,94 @@
+mod decrypt;
+mod shuffle;
+mod worker;
+
+use async_std::task;
+use clap::Clap;
+use mixnet_primitives::ErrorCode;
+use sp_keyring::sr25519::sr25519::Pair;
+use std::time::Duration;
+use substrate_subxt::{
+    sp_core::Pair as _, ClientBuilder, Error, NodeTemplateRuntime, RuntimeError,
+};
+use worker::{Worker, WorkerConfig};
+
+/// The name of the mixnet pallet in the runtime
+const MODULE: &str = "PalletMixnet";
+
+/// A sealer which shuffles, proves and decrypts the ciphers of the votes outside the validator node
+#[derive(Clap, Debug)]
+#[clap(
+    name = "sealer-worker",
+    version = "1.0",
+    author = "Moritz Eck <moritz.eck@gmail.com>"
+)]
+pub struct Opts {
+    /// The websocket url of the node
+    #[clap(long, default_value = "ws://127.0.0.1:9944")]
+    pub url: String,
+    /// The secret uri of the sealer's account, e.g. //Bob
+    #[clap(long)]
+    pub suri: String,
+    /// The private key share of the sealer, the partial decryptions are only submitted if set
+    #[clap(long)]
+    pub sk: Option<String>,
+    /// The number of threads to shuffle and prove the batches with
+    #[clap(long, default_value = "4")]
+    pub threads: usize,
+    /// The maximum number of ciphers per batch (at most the batch size configured on chain)
+    #[clap(long)]
+    pub batch_size: Option<u64>,
+    /// The number of blocks after which the turn of a sealer times out (ShuffleTurnTimeout of the runtime)
+    #[clap(long, default_value = "10")]
+    pub turn_timeout: u64,
+    /// The interval between two polls in milliseconds
+    #[clap(long, default_value = "3000")]
+    pub interval: u64,
+}
+
+/// describes the error, errors of the mixnet pallet are described by their message and error code
+fn describe(error: &Error) -> String {
+    match error {
+        Error::Runtime(RuntimeError::Module(module_error)) if module_error.module == MODULE => {
+            match ErrorCode::from_name(&module_error.error) {
+                Some(code) => code.to_string(),
+                None => format!("{:?}", error),
+            }
+        }
+        _ => format!("{:?}", error),
+    }
+}
+
+async fn run(opts: Opts) -> Result<(), Error> {
+    let pair = Pair::from_string(&opts.suri, None).map_err(|_| "invalid secret uri!")?;
+    let client = ClientBuilder::<NodeTemplateRuntime>::new()
+        .set_url(opts.url.clone())
+        .build()
+        .await?;
+    let config = WorkerConfig {
+        sk: opts.sk,
+        threads: opts.threads,
+        batch_size: opts.batch_size,
+        turn_timeout: opts.turn_timeout,
+    };
+    let mut worker = Worker::new(client, pair, config);
+
+    // a failed poll (e.g. a rejected extrinsic) is retried with the next poll
+    loop {
+        if let Err(err) = worker.poll().await {
+            println!(
+                "failed to perform the work of the sealer: {}",
+                describe(&err)
+            );
+        }
+        task::sleep(Duration::from_millis(opts.interval)).await;
+    }
+}
+
+fn main() {
+    let opts: Opts = Opts::parse();
+    println!("Sealer Worker. Connecting to {}...", opts.url);
+    if let Err(err) = task::block_on(run(opts)) {
+        println!("failed to start the sealer worker: {}", describe(&err));
+    }
+}
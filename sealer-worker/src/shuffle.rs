@@ -0,0 +1,162 @@
+//! Shuffles and proves the batches of ciphers, the same computation as the offchain worker
+//! (see: `offchain_shuffle_and_proof` of the pallet) with the randomness of the operating system.
+//! The batches are distributed over several threads.
+use crypto::{
+    encryption::ElGamal,
+    hash::HashFunction,
+    helper::Helper,
+    proofs::shuffle::{ShuffleProof, ShuffleProofRandoms, ShuffleProverTables},
+    random::Random,
+    secret,
+    types::{Cipher as BigCipher, PublicKey as ElGamalPK},
+};
+use num_bigint::BigUint;
+use pallet_mixnet::types::{
+    LinkedShufflePayload, ShufflePayload, ShuffleProof as Proof, TopicId, VoteId, Wrapper,
+};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+/// A batch of ciphers to shuffle. A linked shuffle contains the same batch of all topics
+/// of the vote, otherwise the batch of a single topic.
+#[derive(Clone, Debug)]
+pub struct ShuffleTask {
+    pub vote_id: VoteId,
+    /// the topic whose shuffle state the task belongs to (the first topic of a linked shuffle)
+    pub topic_id: TopicId,
+    pub linked: bool,
+    pub pk: ElGamalPK,
+    pub iteration: u8,
+    pub start_position: u64,
+    pub batch_size: u64,
+    /// the ciphers of the batch of every topic
+    pub ciphers: Vec<(TopicId, Vec<BigCipher>)>,
+    /// the context of the proofs: the digest of the ballot box freeze of the vote
+    pub context: Vec<u8>,
+    /// the hash function of the challenges of the proofs (see: `ProtocolConfig` of the vote)
+    pub hash_function: HashFunction,
+}
+
+fn get_randoms(q: &BigUint, size: usize) -> Vec<BigUint> {
+    (0..size).map(|_| Random::get_random_less_than(q)).collect()
+}
+
+/// shuffles the batch and generates the proofs, all topics of the batch are shuffled with
+/// the same permutation and their proofs share the commitment to the permutation.
+/// the generators are derived from the topic id, or the vote id for linked shuffles.
+pub fn shuffle_and_prove(task: &ShuffleTask) -> Result<LinkedShufflePayload, String> {
+    let pk = &task.pk;
+    let q = &pk.params.q();
+    let size = task
+        .ciphers
+        .first()
+        .map(|(_, ciphers)| ciphers.len())
+        .unwrap_or(0);
+    if size == 0
+        || task
+            .ciphers
+            .iter()
+            .any(|(_, ciphers)| ciphers.len() != size)
+    {
+        return Err("the batch is empty or the topics differ in size".to_string());
+    }
+
+    let id = if task.linked {
+        &task.vote_id
+    } else {
+        &task.topic_id
+    };
+    let vec_h = Helper::get_generators(id, &pk.params.p, size);
+    let tables = ShuffleProverTables::new(&pk.params);
+    let mut permutation = Random::generate_permutation(&size);
+    let mut permutation_commitment = ShuffleProof::generate_permutation_commitment_with_table(
+        &pk.params,
+        &permutation,
+        get_randoms(q, size),
+        vec_h.clone(),
+        &tables.g,
+    );
+
+    // the proofs wipe the re-encryption randoms and their copies of the commitment randoms
+    let payload: Result<LinkedShufflePayload, String> = task
+        .ciphers
+        .iter()
+        .map(|(topic_id, ciphers)| {
+            let randoms = get_randoms(q, size);
+            let shuffled: Vec<BigCipher> = ElGamal::shuffle(ciphers, &permutation, &randoms, pk)
+                .into_iter()
+                .map(|item| item.0)
+                .collect();
+            let proof: Proof = ShuffleProof::prove_with_hash(
+                ciphers.clone(),
+                shuffled.clone(),
+                randoms,
+                &permutation,
+                &vec_h,
+                permutation_commitment.clone(),
+                pk,
+                &tables,
+                ShuffleProofRandoms::random(q, size),
+                &task.context,
+                task.hash_function,
+            )
+            .ok_or("the public key has no inverse")?
+            .into();
+            let shuffle = ShufflePayload {
+                ciphers: Wrapper(shuffled).into(),
+                proof: proof.into(),
+                iteration: task.iteration,
+                start_position: task.start_position,
+                batch_size: task.batch_size,
+            };
+            Ok((topic_id.clone(), shuffle))
+        })
+        .collect();
+
+    // the permutation and the commitment randoms are wiped, also if a proof failed
+    secret::wipe_permutation(&mut permutation);
+    secret::wipe_all(&mut permutation_commitment.randoms);
+    payload
+}
+
+/// shuffles the batches on {threads} threads, returns the results in the order of the tasks
+pub fn shuffle_batches(
+    tasks: Vec<ShuffleTask>,
+    threads: usize,
+) -> Vec<(ShuffleTask, Result<LinkedShufflePayload, String>)> {
+    let nr_of_tasks = tasks.len();
+    let queue = Arc::new(Mutex::new(tasks.into_iter().enumerate()));
+    let (sender, receiver) = mpsc::channel();
+
+    let workers: Vec<thread::JoinHandle<()>> = (0..threads.max(1).min(nr_of_tasks))
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            let sender = sender.clone();
+            thread::spawn(move || loop {
+                // the lock is released before the batch is shuffled
+                let next = queue.lock().expect("the queue is never poisoned").next();
+                match next {
+                    Some((index, task)) => {
+                        let result = shuffle_and_prove(&task);
+                        if sender.send((index, task, result)).is_err() {
+                            return;
+                        }
+                    }
+                    None => return,
+                }
+            })
+        })
+        .collect();
+    drop(sender);
+
+    let mut results: Vec<(usize, ShuffleTask, Result<LinkedShufflePayload, String>)> =
+        receiver.iter().collect();
+    for worker in workers {
+        let _ = worker.join();
+    }
+    results.sort_by_key(|(index, _, _)| *index);
+    results
+        .into_iter()
+        .map(|(_, task, result)| (task, result))
+        .collect()
+}
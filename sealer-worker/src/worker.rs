@@ -0,0 +1,404 @@
+//! Polls the node for pending work of the sealer and submits the results as extrinsics.
+//!
+//! The worker follows the commit-reveal protocol of the offchain worker: if it is the sealer's
+//! turn, it shuffles the next batch of a topic, commits to the hash of the payload and reveals
+//! the payload in a later block. Once the ciphers of a topic are ready to be decrypted, it
+//! submits the partial decryptions of the sealer.
+use crate::decrypt::partial_decrypt;
+use crate::shuffle::{shuffle_batches, ShuffleTask};
+use async_std::task;
+use codec::Encode;
+use crypto::{
+    hash::HashFunction,
+    types::{Cipher as BigCipher, PublicKey as ElGamalPK},
+};
+use pallet_mixnet::types::{
+    Cipher, LinkedShufflePayload, NrOfShuffles, ShuffleCommitment, ShuffleTurn, TopicId, VoteId,
+    VotePhase, Wrapper,
+};
+use provotum_client_sdk::rpc::{
+    commit_shuffle, get_aggregate_cipher, get_ballot_box_freeze, get_block_number, get_ciphers,
+    get_decrypted_shares, get_linked_shuffle, get_pending_decryption, get_protocol_config,
+    get_shuffle_commitment, get_shuffle_state, get_shuffle_turn, get_topics, get_vote,
+    get_vote_ids, get_vote_public_key, get_vote_sealers, submit_linked_shuffle,
+    submit_partial_decryptions, submit_shuffled_votes_and_proof,
+};
+use provotum_client_sdk::{AccountId, BlockNumber, Signer};
+use sp_keyring::sr25519::sr25519::Pair;
+use std::collections::HashMap;
+use substrate_subxt::{
+    sp_core::{hashing::blake2_256, Pair as _},
+    Client, Error, NodeTemplateRuntime, PairSigner, Signer as _,
+};
+use zeroize::Zeroize;
+
+/// The configuration of the worker.
+pub struct WorkerConfig {
+    /// the private key share of the sealer, no partial decryptions are submitted if not set
+    pub sk: Option<String>,
+    /// the number of threads to shuffle and prove the batches with
+    pub threads: usize,
+    /// the maximum number of ciphers per batch, at most the batch size configured on chain
+    pub batch_size: Option<u64>,
+    /// the `ShuffleTurnTimeout` of the runtime
+    pub turn_timeout: BlockNumber,
+}
+
+/// a payload the worker committed to, it is revealed in a later block
+struct PendingReveal {
+    commitment: [u8; 32],
+    linked: bool,
+    payload: LinkedShufflePayload,
+}
+
+pub struct Worker {
+    client: Client<NodeTemplateRuntime>,
+    signer: Signer,
+    sealer: AccountId,
+    sealer_id: [u8; 32],
+    config: WorkerConfig,
+    pending: HashMap<(VoteId, TopicId), PendingReveal>,
+}
+
+/// the index of the sealer whose turn it is at the block, the turn passes to the next sealer
+/// every `turn_timeout + 1` blocks (see: `get_shuffle_turn` of the pallet)
+pub fn get_turn_index(
+    turn: &ShuffleTurn<BlockNumber>,
+    block_number: BlockNumber,
+    nr_of_sealers: u64,
+    turn_timeout: BlockNumber,
+) -> u32 {
+    let period = turn_timeout.saturating_add(1);
+    let timeouts = block_number.saturating_sub(turn.since) / period;
+    if timeouts == 0 || nr_of_sealers == 0 {
+        return turn.sealer_index;
+    }
+    ((turn.sealer_index as u64 + timeouts % nr_of_sealers) % nr_of_sealers) as u32
+}
+
+impl Worker {
+    pub fn new(client: Client<NodeTemplateRuntime>, pair: Pair, config: WorkerConfig) -> Self {
+        let sealer_id: [u8; 32] = pair.public().0;
+        let signer = PairSigner::<NodeTemplateRuntime, Pair>::new(pair);
+        let sealer = signer.account_id().clone();
+        Worker {
+            client,
+            signer,
+            sealer,
+            sealer_id,
+            config,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// performs all pending work of the sealer in the current block
+    pub async fn poll(&mut self) -> Result<(), Error> {
+        let block_number = get_block_number(&self.client).await?;
+
+        let mut tasks: Vec<ShuffleTask> = Vec::new();
+        for vote_id in get_vote_ids(&self.client).await? {
+            let vote = get_vote(&self.client, vote_id.clone()).await?;
+            if vote.phase != VotePhase::Tallying {
+                continue;
+            }
+
+            // the proofs would be rejected if the protocol of the vote is not supported
+            let hash_function: HashFunction =
+                match get_protocol_config(&self.client, vote_id.clone()).await? {
+                    Some(config) if config.is_supported() => config.hash_function.into(),
+                    _ => {
+                        println!("unsupported protocol, skipping vote: {:?}", vote_id);
+                        continue;
+                    }
+                };
+
+            // the vote is sealed by the sealers of the epoch it has been created in
+            let sealers = get_vote_sealers(&self.client, vote_id.clone()).await?;
+            let sealer_index = match sealers.iter().position(|sealer| *sealer == self.sealer) {
+                Some(index) => index as u32,
+                None => continue,
+            };
+
+            let topic_ids: Vec<TopicId> = get_topics(&self.client, vote_id.clone())
+                .await?
+                .into_iter()
+                .map(|topic| topic.id)
+                .collect();
+            if self.config.sk.is_some() {
+                for topic_id in topic_ids.iter() {
+                    self.decrypt(&vote_id, topic_id, vote.nr_of_shuffles, hash_function)
+                        .await?;
+                }
+            }
+
+            // the topics of a vote with linked shuffles are shuffled by the job of the first topic
+            let linked = get_linked_shuffle(&self.client, vote_id.clone()).await?;
+            let nr_of_jobs = if linked { 1 } else { topic_ids.len() };
+            for topic_id in topic_ids.iter().take(nr_of_jobs) {
+                let state = match get_shuffle_state(&self.client, vote_id.clone(), topic_id.clone())
+                    .await?
+                {
+                    Some(state) if !state.done && state.iteration < vote.nr_of_shuffles => state,
+                    _ => continue,
+                };
+
+                let turn =
+                    get_shuffle_turn(&self.client, vote_id.clone(), topic_id.clone()).await?;
+                let is_my_turn = get_turn_index(
+                    &turn,
+                    block_number,
+                    sealers.len() as u64,
+                    self.config.turn_timeout,
+                ) == sealer_index;
+                let commitment =
+                    get_shuffle_commitment(&self.client, vote_id.clone(), topic_id.clone()).await?;
+                if self
+                    .reveal_if_due(&vote_id, topic_id, &commitment, block_number)
+                    .await?
+                    || !self.can_commit(is_my_turn, &commitment, block_number)
+                {
+                    continue;
+                }
+
+                // the ciphers of the batch of all topics of the job
+                let batch_size = self
+                    .config
+                    .batch_size
+                    .map(|size| size.min(state.batch_size))
+                    .unwrap_or(state.batch_size);
+                let job_topics: Vec<TopicId> = if linked {
+                    topic_ids.clone()
+                } else {
+                    vec![topic_id.clone()]
+                };
+                let mut ciphers: Vec<(TopicId, Vec<BigCipher>)> = Vec::new();
+                for job_topic in job_topics {
+                    let all: Vec<Cipher> = get_ciphers(
+                        &self.client,
+                        vote_id.clone(),
+                        job_topic.clone(),
+                        state.iteration,
+                    )
+                    .await?;
+                    let batch: Vec<Cipher> = all
+                        .into_iter()
+                        .skip(state.start_position as usize)
+                        .take(batch_size as usize)
+                        .collect();
+                    ciphers.push((job_topic, Wrapper(batch).into()));
+                }
+                let pk: ElGamalPK = get_vote_public_key(&self.client, vote_id.clone())
+                    .await?
+                    .into();
+                // the proofs are bound to the ballot box freeze of the vote (if recorded)
+                let context: Vec<u8> = get_ballot_box_freeze(&self.client, vote_id.clone())
+                    .await?
+                    .map(|freeze| freeze.digest.to_vec())
+                    .unwrap_or_default();
+                tasks.push(ShuffleTask {
+                    vote_id: vote_id.clone(),
+                    topic_id: topic_id.clone(),
+                    linked,
+                    pk,
+                    iteration: state.iteration,
+                    start_position: state.start_position,
+                    batch_size,
+                    ciphers,
+                    context,
+                    hash_function,
+                });
+            }
+        }
+
+        if tasks.is_empty() {
+            return Ok(());
+        }
+
+        // the batches are shuffled in parallel, the commitments are submitted one by one
+        println!("shuffling {} batches...", tasks.len());
+        let threads = self.config.threads;
+        let results = task::spawn_blocking(move || shuffle_batches(tasks, threads)).await;
+        for (task, result) in results {
+            let payload = match result {
+                Ok(payload) => payload,
+                Err(err) => {
+                    println!("failed to shuffle topic {:?}: {}", task.topic_id, err);
+                    continue;
+                }
+            };
+            self.commit(task, payload).await?;
+        }
+        Ok(())
+    }
+
+    /// true if the sealer commits to a new shuffle (see: `get_shuffle_action` of the offchain worker)
+    fn can_commit(
+        &self,
+        is_my_turn: bool,
+        commitment: &Option<ShuffleCommitment<AccountId, BlockNumber>>,
+        block_number: BlockNumber,
+    ) -> bool {
+        match commitment {
+            // another sealer didn't reveal in time -> take over
+            Some(pending) if pending.sealer != self.sealer => {
+                is_my_turn
+                    && block_number
+                        > pending
+                            .block_number
+                            .saturating_add(self.config.turn_timeout)
+            }
+            // no pending commitment or the committed payload has been lost
+            _ => is_my_turn,
+        }
+    }
+
+    async fn commit(
+        &mut self,
+        task: ShuffleTask,
+        payload: LinkedShufflePayload,
+    ) -> Result<(), Error> {
+        // the commitment is the hash of the payload which is revealed
+        let commitment = if task.linked {
+            blake2_256(&payload.encode())
+        } else {
+            let (_, shuffle) = payload.first().ok_or("the payload is empty!")?;
+            blake2_256(&shuffle.encode())
+        };
+        commit_shuffle(
+            &self.client,
+            &self.signer,
+            task.vote_id.clone(),
+            task.topic_id.clone(),
+            commitment,
+        )
+        .await?;
+        println!(
+            "committed to shuffle of topic {:?}, iteration: {}, start: {}",
+            task.topic_id, task.iteration, task.start_position
+        );
+        self.pending.insert(
+            (task.vote_id, task.topic_id),
+            PendingReveal {
+                commitment,
+                linked: task.linked,
+                payload,
+            },
+        );
+        Ok(())
+    }
+
+    /// reveals the payload the sealer committed to in an earlier block, returns true if the
+    /// sealer has a pending reveal for the topic (even if it can't be revealed yet)
+    async fn reveal_if_due(
+        &mut self,
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+        commitment: &Option<ShuffleCommitment<AccountId, BlockNumber>>,
+        block_number: BlockNumber,
+    ) -> Result<bool, Error> {
+        let key = (vote_id.clone(), topic_id.clone());
+        let on_chain = match (commitment, self.pending.get(&key)) {
+            (Some(on_chain), Some(pending))
+                if on_chain.sealer == self.sealer && on_chain.commitment == pending.commitment =>
+            {
+                on_chain
+            }
+            // the commitment has been taken over or the shuffle has been revealed
+            _ => {
+                self.pending.remove(&key);
+                return Ok(false);
+            }
+        };
+        if block_number <= on_chain.block_number {
+            return Ok(true);
+        }
+
+        let pending = self
+            .pending
+            .remove(&key)
+            .expect("pending reveal has been checked");
+        if pending.linked {
+            submit_linked_shuffle(&self.client, &self.signer, vote_id.clone(), pending.payload)
+                .await?;
+        } else {
+            let (_, shuffle) = pending
+                .payload
+                .into_iter()
+                .next()
+                .ok_or("the payload is empty!")?;
+            submit_shuffled_votes_and_proof(
+                &self.client,
+                &self.signer,
+                vote_id.clone(),
+                topic_id.clone(),
+                shuffle,
+            )
+            .await?;
+        }
+        println!("revealed shuffle of topic {:?}", topic_id);
+        Ok(true)
+    }
+
+    /// submits the partial decryptions of the topic once it's ready to be decrypted
+    async fn decrypt(
+        &self,
+        vote_id: &VoteId,
+        topic_id: &TopicId,
+        nr_of_shuffles: NrOfShuffles,
+        hash_function: HashFunction,
+    ) -> Result<(), Error> {
+        let sk = match &self.config.sk {
+            Some(sk) => sk,
+            None => return Ok(()),
+        };
+        if get_pending_decryption(&self.client, vote_id.clone(), topic_id.clone())
+            .await?
+            .is_none()
+            || !get_decrypted_shares(
+                &self.client,
+                vote_id.clone(),
+                topic_id.clone(),
+                self.sealer.clone(),
+            )
+            .await?
+            .is_empty()
+        {
+            return Ok(());
+        }
+
+        // if the topic is tallied homomorphically, only the aggregate cipher is decrypted
+        let ciphers: Vec<Cipher> =
+            match get_aggregate_cipher(&self.client, vote_id.clone(), topic_id.clone()).await? {
+                Some(aggregate) => vec![aggregate],
+                None => {
+                    get_ciphers(
+                        &self.client,
+                        vote_id.clone(),
+                        topic_id.clone(),
+                        nr_of_shuffles,
+                    )
+                    .await?
+                }
+            };
+        let mut sk = sk.clone();
+        let sealer_id = self.sealer_id;
+        let (shares, proof) = task::spawn_blocking(move || {
+            let decryption = partial_decrypt(ciphers, &sk, &sealer_id, hash_function);
+            sk.zeroize();
+            decryption
+        })
+        .await;
+        submit_partial_decryptions(
+            &self.client,
+            &self.signer,
+            vote_id.clone(),
+            topic_id.clone(),
+            shares,
+            proof,
+            nr_of_shuffles,
+        )
+        .await?;
+        println!("submitted partial decryptions of topic {:?}", topic_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+//! The attestation of a certified result for other chains, e.g. Substrate chains or Ethereum light clients.
+//!
+//! Once the result of a vote is certified, the pallet stores a `ResultDigest`: a blake2 hash chain
+//! over the results of all topics which is bound to the merkle root of the ciphers and proofs.
+//! The digest is a plain byte layout, i.e. it can be reproduced without SCALE. This module
+//! recomputes the hash chain from the results and creates a compact attestation of the digest.
+use blake2::{
+    digest::{Update, VariableOutput},
+    VarBlake2b,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The current version of the attestation format.
+pub const ATTESTATION_VERSION: u8 = 1;
+
+/// The context of the result digest, see: `RESULT_DIGEST_CONTEXT` of the pallet.
+pub const RESULT_DIGEST_CONTEXT: &[u8] = b"provotum-mixnet/result-digest/v1";
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum AttestationError {
+    /// a value is not a hex string (or a hash doesn't have 32 bytes)
+    InvalidHex(String),
+}
+
+/// The result of a topic as stored on chain (see: `TopicResult` of the pallet).
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct TopicOutcome {
+    pub topic_id: String,
+    /// the count of every plaintext, both are hex strings of their bytes
+    pub results: BTreeMap<String, String>,
+}
+
+/// The compact attestation of a certified result. All hashes are hex strings.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct ResultAttestation {
+    pub version: u8,
+    pub vote_id: String,
+    /// the block in which the result has been certified
+    pub block_number: u64,
+    /// the head of the hash chain over the results of the topics
+    pub results_chain: String,
+    /// the merkle root of the commitments to the ciphers and proofs (as stored on chain)
+    pub proofs_root: String,
+    /// H(results_chain | proofs_root), must match the `ResultDigest` stored on chain
+    pub digest: String,
+}
+
+/// the blake2b hash with 32 bytes output (`blake2_256` of substrate)
+pub(crate) fn blake2_256(input: &[u8]) -> [u8; 32] {
+    let mut hasher = VarBlake2b::new(32).expect("32 is a valid output size");
+    hasher.update(input);
+    let mut hash = [0u8; 32];
+    hasher.finalize_variable(|output| hash.copy_from_slice(output));
+    hash
+}
+
+/// appends the bytes prefixed by their length (u32, big-endian)
+pub(crate) fn extend_length_prefixed(input: &mut Vec<u8>, bytes: &[u8]) {
+    input.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    input.extend_from_slice(bytes);
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, AttestationError> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|_| AttestationError::InvalidHex(value.to_string()))
+}
+
+fn decode_hash(value: &str) -> Result<[u8; 32], AttestationError> {
+    let bytes = decode_hex(value)?;
+    let mut hash = [0u8; 32];
+    if bytes.len() != hash.len() {
+        return Err(AttestationError::InvalidHex(value.to_string()));
+    }
+    hash.copy_from_slice(&bytes);
+    Ok(hash)
+}
+
+/// computes the head of the hash chain over the results of the topics (in the order of the vote):
+/// d_0 = H(context | vote_id), d_i = H(d_i-1 | H(topic_id | n | plaintext_1 | count_1 | ..))
+/// where n is the number of plaintexts of the topic (u32, big-endian), the plaintexts are
+/// in ascending order and all byte strings are prefixed by their length (u32, big-endian).
+pub fn results_chain(vote_id: &str, topics: &[TopicOutcome]) -> Result<[u8; 32], AttestationError> {
+    let mut input: Vec<u8> = RESULT_DIGEST_CONTEXT.to_vec();
+    extend_length_prefixed(&mut input, vote_id.as_bytes());
+    let mut chain = blake2_256(&input);
+
+    for topic in topics.iter() {
+        // the plaintexts are ordered by their bytes, as in the BTreeMap of the pallet
+        let mut results: BTreeMap<Vec<u8>, Vec<u8>> = BTreeMap::new();
+        for (plaintext, count) in topic.results.iter() {
+            results.insert(decode_hex(plaintext)?, decode_hex(count)?);
+        }
+
+        let mut input: Vec<u8> = Vec::new();
+        extend_length_prefixed(&mut input, topic.topic_id.as_bytes());
+        input.extend_from_slice(&(results.len() as u32).to_be_bytes());
+        for (plaintext, count) in results.iter() {
+            extend_length_prefixed(&mut input, plaintext);
+            extend_length_prefixed(&mut input, count);
+        }
+        let topic_hash = blake2_256(&input);
+
+        let mut link: Vec<u8> = chain.to_vec();
+        link.extend_from_slice(&topic_hash);
+        chain = blake2_256(&link);
+    }
+    Ok(chain)
+}
+
+/// computes the digest of the result: H(results_chain | proofs_root)
+pub fn result_digest(results_chain: &[u8; 32], proofs_root: &[u8; 32]) -> [u8; 32] {
+    let mut input: Vec<u8> = results_chain.to_vec();
+    input.extend_from_slice(proofs_root);
+    blake2_256(&input)
+}
+
+/// creates the attestation of the results of a vote, the proofs root is read from the chain
+/// (see: `ResultDigest` of the pallet). the digest of the attestation must match the digest on chain.
+pub fn attest(
+    vote_id: &str,
+    topics: &[TopicOutcome],
+    proofs_root: &str,
+    block_number: u64,
+) -> Result<ResultAttestation, AttestationError> {
+    let chain = results_chain(vote_id, topics)?;
+    let root = decode_hash(proofs_root)?;
+    Ok(ResultAttestation {
+        version: ATTESTATION_VERSION,
+        vote_id: vote_id.to_string(),
+        block_number,
+        results_chain: hex::encode(chain),
+        proofs_root: hex::encode(root),
+        digest: hex::encode(result_digest(&chain, &root)),
+    })
+}
+
+impl ResultAttestation {
+    /// true if the attestation belongs to the results, i.e. the hash chain and the digest are valid
+    pub fn verify(&self, topics: &[TopicOutcome]) -> bool {
+        match attest(&self.vote_id, topics, &self.proofs_root, self.block_number) {
+            Ok(expected) => expected == *self,
+            Err(_) => false,
+        }
+    }
+
+    /// the binary encoding for the submission to other chains (all integers big-endian):
+    /// version (1 byte) | block number (8 bytes) | results chain | proofs root | digest (32 bytes each)
+    /// | vote id (prefixed by its length, 4 bytes)
+    pub fn to_bytes(&self) -> Result<Vec<u8>, AttestationError> {
+        let mut bytes: Vec<u8> = vec![self.version];
+        bytes.extend_from_slice(&self.block_number.to_be_bytes());
+        bytes.extend_from_slice(&decode_hash(&self.results_chain)?);
+        bytes.extend_from_slice(&decode_hash(&self.proofs_root)?);
+        bytes.extend_from_slice(&decode_hash(&self.digest)?);
+        extend_length_prefixed(&mut bytes, self.vote_id.as_bytes());
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{attest, results_chain, AttestationError, TopicOutcome};
+    use std::collections::BTreeMap;
+
+    fn topics() -> Vec<TopicOutcome> {
+        let mut results = BTreeMap::new();
+        results.insert("01".to_string(), "02".to_string());
+        vec![TopicOutcome {
+            topic_id: "20201212-01".to_string(),
+            results,
+        }]
+    }
+
+    #[test]
+    fn it_should_reproduce_the_results_chain_of_the_pallet() {
+        // the same results as in test_certify_result of the pallet
+        let chain = results_chain("20201212", &topics()).unwrap();
+        assert_eq!(
+            hex::encode(chain),
+            "d9a818b62b36cae38683a8d8273138346791898eb6b58b6a260d7aeb04eab8af"
+        );
+
+        let attestation = attest("20201212", &topics(), &"00".repeat(32), 3).unwrap();
+        assert_eq!(
+            attestation.digest,
+            "22bba3b278cab1887aa8ec825955c31cdd73e262d9e50a061ff8b5b944512899"
+        );
+    }
+
+    #[test]
+    fn it_should_verify_attestation() {
+        let attestation = attest("20201212", &topics(), &"ab".repeat(32), 3).unwrap();
+        assert!(attestation.verify(&topics()));
+
+        // the attestation is bound to the results
+        let mut modified = topics();
+        modified[0]
+            .results
+            .insert("01".to_string(), "03".to_string());
+        assert!(!attestation.verify(&modified));
+
+        let bytes = attestation.to_bytes().unwrap();
+        assert_eq!(bytes.len(), 1 + 8 + 3 * 32 + 4 + 8);
+        assert_eq!(bytes[0], attestation.version);
+    }
+
+    #[test]
+    fn it_should_not_attest_invalid_proofs_root() {
+        assert_eq!(
+            attest("20201212", &topics(), "ab", 3),
+            Err(AttestationError::InvalidHex("ab".to_string()))
+        );
+    }
+}
@@ -0,0 +1,827 @@
+use crate::freeze::{ciphers_chain, freeze_digest};
+use crate::shuffle::verify_shuffle_proof;
+use crate::transcript::{
+    into_ciphers, DecryptionRecord, SealerKeyShare, ShuffleRecord, TopicTranscript, Transcript,
+    TranscriptFreeze, TRANSCRIPT_VERSION,
+};
+use blake2::{Blake2b, Digest};
+use crypto::{
+    hash::HashFunction,
+    proofs::{
+        decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
+        keygen::KeyGenerationProof,
+    },
+    types::{Cipher, ElGamalParams, ModuloOperations, PublicKey},
+};
+use num_bigint::BigUint;
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum AuditError {
+    UnsupportedVersion(u8),
+    /// the vote has been created with a protocol the verifier doesn't implement
+    UnsupportedProtocol(u32),
+    Serialization(String),
+}
+
+/// The kind of a verified proof (or consistency check).
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum CheckKind {
+    /// the proof of knowledge of the private key of a sealer's public key share
+    KeyGeneration,
+    /// the public key of the vote is the product of all public key shares
+    PublicKey,
+    /// the shuffle proof of a batch of ciphers
+    Shuffle,
+    /// the shuffled batches of an iteration add up to the ciphers of the next iteration
+    ShuffleChain,
+    /// the shuffles of the same batch of all topics share the permutation commitments (linked shuffles)
+    LinkedShuffle,
+    /// the proof of the partial decryptions of a sealer
+    Decryption,
+    /// the ballot box freeze matches the submitted ciphers, the shuffle proofs are bound to its digest
+    BallotBoxFreeze,
+}
+
+/// The result of a single check.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct CheckResult {
+    pub kind: CheckKind,
+    /// a description of the verified values, e.g. the sealer or the topic and the batch
+    pub subject: String,
+    pub valid: bool,
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.valid { "PASS" } else { "FAIL" };
+        write!(f, "[{}] {:?}: {}", status, self.kind, self.subject)
+    }
+}
+
+/// The audit report of a vote.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct AuditReport {
+    pub vote_id: String,
+    /// the blake2b hash (hex) of the audited transcript (JSON)
+    pub transcript_hash: String,
+    pub results: Vec<CheckResult>,
+    /// true if all checks passed
+    pub valid: bool,
+}
+
+/// computes the blake2b hash (hex) of the transcript (JSON)
+pub fn hash_transcript(transcript: &Transcript) -> Result<String, AuditError> {
+    let json = serde_json::to_vec(transcript)
+        .map_err(|error| AuditError::Serialization(error.to_string()))?;
+    Ok(hex::encode(Blake2b::digest(&json)))
+}
+
+fn check(kind: CheckKind, subject: String, valid: bool) -> CheckResult {
+    CheckResult {
+        kind,
+        subject,
+        valid,
+    }
+}
+
+fn verify_key_share(
+    params: &ElGamalParams,
+    share: &SealerKeyShare,
+    hash_function: HashFunction,
+) -> CheckResult {
+    let valid = match hex::decode(&share.sealer) {
+        Ok(sealer_id) => KeyGenerationProof::verify_with_hash(
+            params,
+            &share.pk,
+            &share.proof,
+            &sealer_id,
+            hash_function,
+        ),
+        Err(_) => false,
+    };
+    check(
+        CheckKind::KeyGeneration,
+        format!("sealer {}", share.sealer),
+        valid,
+    )
+}
+
+fn verify_public_key(transcript: &Transcript) -> CheckResult {
+    let p = &transcript.params.p;
+    let combined = transcript
+        .key_shares
+        .iter()
+        .fold(BigUint::one(), |product, share| {
+            product.modmul(&share.pk, p)
+        });
+    let valid = !transcript.key_shares.is_empty() && combined == transcript.public_key;
+    check(
+        CheckKind::PublicKey,
+        format!("vote {}", transcript.vote_id),
+        valid,
+    )
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x")).ok()
+}
+
+/// checks that the hash chain of the freeze matches the submitted ciphers of the topics
+/// and that its digest is bound to the hash of the last block of the voting phase
+fn verify_ballot_box_freeze(transcript: &Transcript, freeze: &TranscriptFreeze) -> CheckResult {
+    let topics: Vec<(&str, Vec<Cipher>)> = transcript
+        .topics
+        .iter()
+        .map(|topic| {
+            let submitted = topic.ciphers.first().map(|ciphers| into_ciphers(ciphers));
+            (topic.topic_id.as_str(), submitted.unwrap_or_default())
+        })
+        .collect();
+    let chain = ciphers_chain(&transcript.vote_id, &topics);
+    let valid = match (
+        decode_hex(&freeze.block_hash),
+        decode_hex(&freeze.ciphers_chain),
+        decode_hex(&freeze.digest),
+    ) {
+        (Some(block_hash), Some(ciphers_chain), Some(digest)) => {
+            ciphers_chain == chain && digest == freeze_digest(&block_hash, &chain)
+        }
+        _ => false,
+    };
+    check(
+        CheckKind::BallotBoxFreeze,
+        format!("vote {}, block {}", transcript.vote_id, freeze.block_number),
+        valid,
+    )
+}
+
+/// the generators of the permutation commitments are derived from the id,
+/// i.e. the topic id or the vote id (linked shuffles).
+/// the challenge is bound to the context, i.e. the digest of the ballot box freeze (if any).
+fn verify_shuffle(
+    pk: &PublicKey,
+    id: &[u8],
+    context: &[u8],
+    topic: &TopicTranscript,
+    record: &ShuffleRecord,
+    hash_function: HashFunction,
+) -> CheckResult {
+    let start = record.start_position as usize;
+    let end = start + record.shuffled_ciphers.len();
+    let encryptions: Option<Vec<Cipher>> = topic
+        .ciphers
+        .get(record.iteration as usize)
+        .and_then(|ciphers| ciphers.get(start..end))
+        .map(into_ciphers);
+
+    // the batch contains at most batch_size ciphers (the last batch may be smaller)
+    let valid = match encryptions {
+        Some(encryptions) if record.shuffled_ciphers.len() as u64 <= record.batch_size => {
+            verify_shuffle_proof(
+                id,
+                context,
+                &record.proof,
+                encryptions,
+                into_ciphers(&record.shuffled_ciphers),
+                pk,
+                hash_function,
+            )
+        }
+        _ => false,
+    };
+    check(
+        CheckKind::Shuffle,
+        format!(
+            "topic {}, iteration {}, ciphers {}..{}",
+            topic.topic_id, record.iteration, start, end
+        ),
+        valid,
+    )
+}
+
+/// checks that the shuffled batches of each iteration add up to the ciphers of the next iteration
+/// and that the ciphers have been shuffled the required number of times.
+fn verify_shuffle_chain(topic: &TopicTranscript, nr_of_shuffles: u8) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+    results.push(check(
+        CheckKind::ShuffleChain,
+        format!("topic {}, {} iterations", topic.topic_id, nr_of_shuffles),
+        topic.ciphers.len() == nr_of_shuffles as usize + 1,
+    ));
+    for (iteration, ciphers) in topic.ciphers.iter().enumerate().skip(1) {
+        let mut batches: Vec<&ShuffleRecord> = topic
+            .shuffles
+            .iter()
+            .filter(|record| record.iteration as usize + 1 == iteration)
+            .collect();
+        batches.sort_by_key(|record| record.start_position);
+
+        // the batches must be consecutive
+        let mut position: u64 = 0;
+        let mut shuffled: Vec<Cipher> = Vec::new();
+        let mut is_consecutive = true;
+        for record in batches.into_iter() {
+            is_consecutive &= record.start_position == position;
+            position += record.shuffled_ciphers.len() as u64;
+            shuffled.extend(into_ciphers(&record.shuffled_ciphers));
+        }
+        results.push(check(
+            CheckKind::ShuffleChain,
+            format!("topic {}, iteration {}", topic.topic_id, iteration),
+            is_consecutive && shuffled == into_ciphers(ciphers),
+        ));
+    }
+    results
+}
+
+/// checks that the same batch of all topics has been shuffled with the same permutation,
+/// i.e. the shuffle proofs of the batch share the permutation commitments (linked shuffles).
+fn verify_linked_shuffles(transcript: &Transcript) -> Vec<CheckResult> {
+    let lead = match transcript.topics.first() {
+        Some(lead) => lead,
+        None => return Vec::new(),
+    };
+    transcript
+        .topics
+        .iter()
+        .skip(1)
+        .map(|topic| {
+            let is_linked = |record: &ShuffleRecord| {
+                topic.shuffles.iter().any(|other| {
+                    other.iteration == record.iteration
+                        && other.start_position == record.start_position
+                        && other.proof.permutation_commitments
+                            == record.proof.permutation_commitments
+                })
+            };
+            let valid =
+                topic.shuffles.len() == lead.shuffles.len() && lead.shuffles.iter().all(is_linked);
+            check(
+                CheckKind::LinkedShuffle,
+                format!(
+                    "topic {}, linked to topic {}",
+                    topic.topic_id, lead.topic_id
+                ),
+                valid,
+            )
+        })
+        .collect()
+}
+
+fn verify_decryption(
+    transcript: &Transcript,
+    topic: &TopicTranscript,
+    record: &DecryptionRecord,
+    hash_function: HashFunction,
+) -> CheckResult {
+    let params: ElGamalParams = (&transcript.params).into();
+    let ciphers = topic.ciphers_to_decrypt();
+    let sealer_pk = transcript
+        .key_shares
+        .iter()
+        .find(|share| share.sealer == record.sealer)
+        .map(|share| &share.pk);
+
+    let valid = match (sealer_pk, hex::decode(&record.sealer)) {
+        (Some(sealer_pk), Ok(sealer_id)) => DecryptionProof::verify_batched_with_hash(
+            &params,
+            sealer_pk,
+            &record.proof,
+            &ciphers,
+            &record.shares,
+            &sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+            hash_function,
+        ),
+        _ => false,
+    };
+    check(
+        CheckKind::Decryption,
+        format!("topic {}, sealer {}", topic.topic_id, record.sealer),
+        valid,
+    )
+}
+
+/// returns a failed check for each sealer which hasn't submitted its partial decryptions
+fn verify_all_sealers_decrypted(
+    transcript: &Transcript,
+    topic: &TopicTranscript,
+) -> Vec<CheckResult> {
+    transcript
+        .key_shares
+        .iter()
+        .filter(|share| {
+            !topic
+                .decryptions
+                .iter()
+                .any(|record| record.sealer == share.sealer)
+        })
+        .map(|share| {
+            check(
+                CheckKind::Decryption,
+                format!(
+                    "topic {}, sealer {} (no partial decryptions)",
+                    topic.topic_id, share.sealer
+                ),
+                false,
+            )
+        })
+        .collect()
+}
+
+/// re-verifies all proofs of the transcript and checks the consistency of the shuffled ciphers.
+pub fn audit(transcript: &Transcript) -> Result<AuditReport, AuditError> {
+    if transcript.version != TRANSCRIPT_VERSION {
+        return Err(AuditError::UnsupportedVersion(transcript.version));
+    }
+    if let Some(protocol) = transcript.protocol.as_ref() {
+        if !protocol.is_supported() {
+            return Err(AuditError::UnsupportedProtocol(protocol.version));
+        }
+    }
+    // the challenges are computed with the hash function of the vote, transcripts without a protocol use Blake2b
+    let hash_function = transcript
+        .protocol
+        .as_ref()
+        .and_then(|protocol| protocol.hash_function())
+        .unwrap_or_default();
+    let params: ElGamalParams = (&transcript.params).into();
+    let pk = transcript.public_key();
+    let mut results: Vec<CheckResult> = Vec::new();
+
+    // key generation
+    for share in transcript.key_shares.iter() {
+        results.push(verify_key_share(&params, share, hash_function));
+    }
+    results.push(verify_public_key(transcript));
+
+    // the ballot box freeze, the shuffle proofs of older votes have no context
+    let context: Vec<u8> = match transcript.freeze.as_ref() {
+        Some(freeze) => {
+            results.push(verify_ballot_box_freeze(transcript, freeze));
+            decode_hex(&freeze.digest).unwrap_or_default()
+        }
+        None => Vec::new(),
+    };
+
+    for topic in transcript.topics.iter() {
+        // mixing, only votes using the mixnet tally are shuffled
+        if topic.aggregate.is_none() {
+            let id = if transcript.linked_shuffles {
+                transcript.vote_id.as_bytes()
+            } else {
+                topic.topic_id.as_bytes()
+            };
+            for record in topic.shuffles.iter() {
+                results.push(verify_shuffle(
+                    &pk,
+                    id,
+                    &context,
+                    topic,
+                    record,
+                    hash_function,
+                ));
+            }
+            results.extend(verify_shuffle_chain(topic, transcript.nr_of_shuffles));
+        }
+
+        // decryption, once the topic has been decrypted all sealers must have submitted their shares
+        for record in topic.decryptions.iter() {
+            results.push(verify_decryption(transcript, topic, record, hash_function));
+        }
+        if !topic.decryptions.is_empty() {
+            results.extend(verify_all_sealers_decrypted(transcript, topic));
+        }
+    }
+
+    if transcript.linked_shuffles {
+        results.extend(verify_linked_shuffles(transcript));
+    }
+
+    let valid = results.iter().all(|result| result.valid);
+    Ok(AuditReport {
+        vote_id: transcript.vote_id.clone(),
+        transcript_hash: hash_transcript(transcript)?,
+        results,
+        valid,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{audit, AuditError, CheckKind};
+    use crate::freeze::{ciphers_chain, freeze_digest};
+    use crate::shuffle::tests::{shuffle_and_prove, shuffle_and_prove_in_context};
+    use crate::transcript::{
+        from_ciphers, DecryptionRecord, SealerKeyShare, ShuffleRecord, TopicTranscript, Transcript,
+        TranscriptFreeze, TranscriptProtocol, PROTOCOL_VERSION, TRANSCRIPT_VERSION,
+    };
+    use crypto::{
+        encryption::ElGamal,
+        hash::HashFunction,
+        helper::{Helper, GENERATOR_DOMAIN},
+        proofs::{
+            decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
+            keygen::KeyGenerationProof,
+        },
+        random::Random,
+        types::{Cipher, PrivateKey},
+    };
+    use num_bigint::BigUint;
+
+    const TOPIC_ID: &str = "Topic";
+
+    /// creates the transcript of a vote with two sealers, one topic, two shuffles and all partial decryptions.
+    /// the second shuffle is performed in two batches.
+    fn setup_transcript() -> Transcript {
+        setup_transcript_with_generators(TOPIC_ID)
+    }
+
+    /// creates the transcript, the generators of the permutation commitments are derived from the id
+    fn setup_transcript_with_generators(id: &str) -> Transcript {
+        build_transcript(id, false, HashFunction::Blake2b)
+    }
+
+    /// creates the transcript of a vote whose ballot box has been frozen at the end of the voting phase,
+    /// the shuffle proofs are bound to the digest of the freeze
+    fn setup_frozen_transcript() -> Transcript {
+        build_transcript(TOPIC_ID, true, HashFunction::Blake2b)
+    }
+
+    /// creates the transcript of a vote whose proofs are computed with the hash function
+    fn setup_transcript_with_hash(hash_function: HashFunction) -> Transcript {
+        let mut transcript = build_transcript(TOPIC_ID, false, hash_function);
+        transcript.protocol = Some(TranscriptProtocol {
+            version: PROTOCOL_VERSION,
+            hash_function: format!("{:?}", hash_function),
+            batch_exponent_bits: 64,
+            generator_domain: GENERATOR_DOMAIN.into(),
+            nr_of_shuffles: 2,
+        });
+        transcript
+    }
+
+    fn build_transcript(id: &str, frozen: bool, hash_function: HashFunction) -> Transcript {
+        let (params, _, _) = Helper::setup_sm_system();
+        let (p, q) = (&params.p, &params.q());
+        let sealers: Vec<(String, PrivateKey)> = ["Bob", "Charlie"]
+            .iter()
+            .map(|name| {
+                let sk = PrivateKey {
+                    params: params.clone(),
+                    x: Random::get_random_less_than(q),
+                };
+                (hex::encode(name.as_bytes()), sk)
+            })
+            .collect();
+
+        // key generation
+        let key_shares: Vec<SealerKeyShare> = sealers
+            .iter()
+            .map(|(sealer, sk)| {
+                let pk = params.g.modpow(&sk.x, p);
+                let r = Random::get_random_less_than(q);
+                let id = hex::decode(sealer).unwrap();
+                let proof = KeyGenerationProof::generate_with_hash(
+                    &params,
+                    &sk.x,
+                    &pk,
+                    &r,
+                    &id,
+                    hash_function,
+                );
+                SealerKeyShare {
+                    sealer: sealer.clone(),
+                    pk,
+                    proof,
+                }
+            })
+            .collect();
+        let pk_shares: Vec<BigUint> = key_shares.iter().map(|share| share.pk.clone()).collect();
+        let pk = crypto::types::PublicKey {
+            params: params.clone(),
+            h: pk_shares[0].clone(),
+        }
+        .combine_public_keys_bigunits(&pk_shares[1..]);
+
+        // voting & mixing
+        let submitted: Vec<Cipher> = (0..4u32)
+            .map(|m| {
+                ElGamal::encrypt_encode(&BigUint::from(m), &Random::get_random_less_than(q), &pk)
+            })
+            .collect();
+        let freeze = if frozen {
+            let block_hash = [7u8; 32];
+            let chain = ciphers_chain("Vote", &[(TOPIC_ID, submitted.clone())]);
+            Some(TranscriptFreeze {
+                block_number: 42,
+                block_hash: hex::encode(block_hash),
+                ciphers_chain: hex::encode(chain),
+                digest: hex::encode(freeze_digest(&block_hash, &chain)),
+            })
+        } else {
+            None
+        };
+        let context: Vec<u8> = freeze
+            .as_ref()
+            .map(|freeze| hex::decode(&freeze.digest).unwrap())
+            .unwrap_or_default();
+        let id = id.as_bytes();
+        let (first, proof) =
+            shuffle_and_prove_in_context(id, &context, &submitted, &pk, hash_function);
+        let mut shuffles = vec![ShuffleRecord {
+            iteration: 0,
+            start_position: 0,
+            batch_size: 4,
+            shuffled_ciphers: from_ciphers(&first),
+            proof,
+        }];
+        let mut second: Vec<Cipher> = Vec::new();
+        for start_position in [0usize, 2].iter() {
+            let batch = &first[*start_position..*start_position + 2];
+            let (shuffled, proof) =
+                shuffle_and_prove_in_context(id, &context, batch, &pk, hash_function);
+            shuffles.push(ShuffleRecord {
+                iteration: 1,
+                start_position: *start_position as u64,
+                batch_size: 2,
+                shuffled_ciphers: from_ciphers(&shuffled),
+                proof,
+            });
+            second.extend(shuffled);
+        }
+
+        // decryption
+        let decryptions: Vec<DecryptionRecord> = sealers
+            .iter()
+            .zip(pk_shares.iter())
+            .map(|((sealer, sk), pk_share)| {
+                let shares: Vec<BigUint> = second
+                    .iter()
+                    .map(|cipher| ElGamal::partial_decrypt_a(cipher, sk))
+                    .collect();
+                let r = Random::get_random_less_than(q);
+                let id = hex::decode(sealer).unwrap();
+                let proof = DecryptionProof::generate_batched_with_hash(
+                    &params,
+                    &sk.x,
+                    pk_share,
+                    &r,
+                    &second,
+                    &shares,
+                    &id,
+                    DEFAULT_BATCH_SOUNDNESS,
+                    hash_function,
+                );
+                DecryptionRecord {
+                    sealer: sealer.clone(),
+                    shares,
+                    proof,
+                }
+            })
+            .collect();
+
+        Transcript {
+            version: TRANSCRIPT_VERSION,
+            vote_id: "Vote".into(),
+            params: (&params).into(),
+            public_key: pk.h,
+            nr_of_shuffles: 2,
+            protocol: None,
+            linked_shuffles: false,
+            freeze,
+            block: None,
+            key_shares,
+            topics: vec![TopicTranscript {
+                topic_id: TOPIC_ID.into(),
+                ciphers: vec![
+                    from_ciphers(&submitted),
+                    from_ciphers(&first),
+                    from_ciphers(&second),
+                ],
+                shuffles,
+                aggregate: None,
+                decryptions,
+            }],
+        }
+    }
+
+    fn failed_checks(transcript: &Transcript) -> Vec<CheckKind> {
+        let report = audit(transcript).unwrap();
+        assert_eq!(
+            report.valid,
+            report.results.iter().all(|result| result.valid)
+        );
+        report
+            .results
+            .into_iter()
+            .filter(|result| !result.valid)
+            .map(|result| result.kind)
+            .collect()
+    }
+
+    #[test]
+    fn it_should_audit_valid_transcript() {
+        let transcript = setup_transcript();
+        let report = audit(&transcript).unwrap();
+
+        // 2 key shares, the public key, 3 shuffles, 3 shuffle chain checks and 2 decryptions
+        assert_eq!(report.results.len(), 11);
+        assert!(report.valid);
+    }
+
+    #[test]
+    fn it_should_detect_replaced_shuffled_cipher() {
+        let mut transcript = setup_transcript();
+        let topic = &mut transcript.topics[0];
+
+        // replace a cipher of the first shuffle, also in the stored ciphers of the iteration
+        let cipher = topic.ciphers[0][0].clone();
+        topic.shuffles[0].shuffled_ciphers[0] = cipher.clone();
+        topic.ciphers[1][0] = cipher;
+
+        // the shuffle proof fails and, since the next shuffle used the original cipher, the chain breaks
+        let failed = failed_checks(&transcript);
+        assert!(failed.contains(&CheckKind::Shuffle));
+    }
+
+    #[test]
+    fn it_should_detect_unshuffled_iteration() {
+        let mut transcript = setup_transcript();
+        let topic = &mut transcript.topics[0];
+        topic.shuffles.retain(|record| record.iteration == 0);
+
+        let failed = failed_checks(&transcript);
+        assert_eq!(failed, vec![CheckKind::ShuffleChain]);
+    }
+
+    #[test]
+    fn it_should_detect_invalid_key_share() {
+        let mut transcript = setup_transcript();
+        transcript.key_shares.swap(0, 1);
+        let sealer = transcript.key_shares[0].sealer.clone();
+        transcript.key_shares[0].sealer = transcript.key_shares[1].sealer.clone();
+        transcript.key_shares[1].sealer = sealer;
+
+        // the proofs are bound to the sealers, the decryptions use the wrong public key shares
+        let failed = failed_checks(&transcript);
+        assert!(failed.contains(&CheckKind::KeyGeneration));
+        assert!(failed.contains(&CheckKind::Decryption));
+    }
+
+    #[test]
+    fn it_should_detect_missing_partial_decryptions() {
+        let mut transcript = setup_transcript();
+        transcript.topics[0].decryptions.pop();
+
+        let failed = failed_checks(&transcript);
+        assert_eq!(failed, vec![CheckKind::Decryption]);
+    }
+
+    /// creates the transcript of a vote with linked shuffles and two topics,
+    /// the second topic is a copy of the first one, i.e. it's shuffled with the same permutations.
+    fn setup_linked_transcript() -> Transcript {
+        let mut transcript = setup_transcript_with_generators("Vote");
+        transcript.linked_shuffles = true;
+        let mut topic = transcript.topics[0].clone();
+        topic.topic_id = "Other".into();
+        transcript.topics.push(topic);
+        transcript
+    }
+
+    #[test]
+    fn it_should_audit_linked_shuffles() {
+        let transcript = setup_linked_transcript();
+        let report = audit(&transcript).unwrap();
+        assert!(report.valid);
+        assert!(report
+            .results
+            .iter()
+            .any(|result| result.kind == CheckKind::LinkedShuffle));
+
+        // the generators of the proofs of linked shuffles are derived from the vote id
+        let mut transcript = setup_transcript();
+        transcript.linked_shuffles = true;
+        assert_eq!(failed_checks(&transcript), vec![CheckKind::Shuffle; 3]);
+    }
+
+    #[test]
+    fn it_should_detect_unlinked_shuffle() {
+        let mut transcript = setup_linked_transcript();
+
+        // the second topic is shuffled with another permutation
+        let batch: Vec<Cipher> = transcript.topics[1].ciphers[0]
+            .iter()
+            .map(|cipher| cipher.0.clone())
+            .collect();
+        let pk = transcript.public_key();
+        let (shuffled, proof) = shuffle_and_prove("Vote".as_bytes(), &batch, &pk);
+        let topic = &mut transcript.topics[1];
+        topic.shuffles[0].shuffled_ciphers = from_ciphers(&shuffled);
+        topic.shuffles[0].proof = proof;
+        topic.ciphers[1] = from_ciphers(&shuffled);
+
+        // the proof of the replaced shuffle is valid, but it doesn't share the permutation commitments
+        let report = audit(&transcript).unwrap();
+        let is_valid = |kind: CheckKind, subject: &str| {
+            report.results.iter().any(|result| {
+                result.kind == kind && result.subject.starts_with(subject) && result.valid
+            })
+        };
+        assert!(is_valid(CheckKind::Shuffle, "topic Other, iteration 0"));
+        assert!(!is_valid(CheckKind::LinkedShuffle, "topic Other"));
+    }
+
+    #[test]
+    fn it_should_audit_frozen_ballot_box() {
+        let transcript = setup_frozen_transcript();
+        let report = audit(&transcript).unwrap();
+        assert!(report.valid);
+        assert!(report
+            .results
+            .iter()
+            .any(|result| result.kind == CheckKind::BallotBoxFreeze));
+
+        // the shuffle proofs are only valid in the context of the freeze
+        let mut unfrozen = transcript.clone();
+        unfrozen.freeze = None;
+        assert_eq!(failed_checks(&unfrozen), vec![CheckKind::Shuffle; 3]);
+    }
+
+    #[test]
+    fn it_should_detect_cipher_added_after_the_freeze() {
+        let mut transcript = setup_frozen_transcript();
+        let cipher = transcript.topics[0].ciphers[0][0].clone();
+        transcript.topics[0].ciphers[0].push(cipher);
+
+        // the freeze doesn't match the submitted ciphers (and the ciphers aren't shuffled)
+        let failed = failed_checks(&transcript);
+        assert!(failed.contains(&CheckKind::BallotBoxFreeze));
+
+        // the digest must be bound to the block hash
+        let mut transcript = setup_frozen_transcript();
+        if let Some(freeze) = transcript.freeze.as_mut() {
+            freeze.block_hash = hex::encode([8u8; 32]);
+        }
+        assert_eq!(failed_checks(&transcript), vec![CheckKind::BallotBoxFreeze]);
+    }
+
+    #[test]
+    fn it_should_reject_unsupported_transcript_version() {
+        let mut transcript = setup_transcript();
+        transcript.version = TRANSCRIPT_VERSION + 1;
+        assert_eq!(
+            audit(&transcript),
+            Err(AuditError::UnsupportedVersion(TRANSCRIPT_VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn it_should_reject_unsupported_protocol() {
+        let mut transcript = setup_transcript();
+        let mut protocol = TranscriptProtocol {
+            version: PROTOCOL_VERSION,
+            hash_function: "Blake2b".into(),
+            batch_exponent_bits: 64,
+            generator_domain: GENERATOR_DOMAIN.into(),
+            nr_of_shuffles: 2,
+        };
+        transcript.protocol = Some(protocol.clone());
+        assert!(audit(&transcript).unwrap().valid);
+
+        // the generators of another domain cannot be derived
+        protocol.generator_domain = "provotum/v2/generator/".into();
+        transcript.protocol = Some(protocol);
+        assert_eq!(
+            audit(&transcript),
+            Err(AuditError::UnsupportedProtocol(PROTOCOL_VERSION))
+        );
+    }
+
+    #[test]
+    fn it_should_audit_with_the_hash_function_of_the_protocol() {
+        let mut transcript = setup_transcript_with_hash(HashFunction::Sha256);
+        assert!(audit(&transcript).unwrap().valid);
+
+        // the challenges of all proofs depend on the hash function
+        transcript.protocol.as_mut().unwrap().hash_function = "Blake2b".into();
+        let failed = failed_checks(&transcript);
+        assert!(failed.contains(&CheckKind::KeyGeneration));
+        assert!(failed.contains(&CheckKind::Shuffle));
+        assert!(failed.contains(&CheckKind::Decryption));
+
+        // the names of the crypto crate are accepted as well
+        let mut transcript = setup_transcript_with_hash(HashFunction::Sha3_256);
+        transcript.protocol.as_mut().unwrap().hash_function = "sha3-256".into();
+        assert!(audit(&transcript).unwrap().valid);
+
+        transcript.protocol.as_mut().unwrap().hash_function = "Md5".into();
+        assert_eq!(
+            audit(&transcript),
+            Err(AuditError::UnsupportedProtocol(PROTOCOL_VERSION))
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use clap::Clap;
+
+/// The Provotum Verifier to audit the proofs of a vote
+#[derive(Clap, Debug)]
+#[clap(
+    name = "provotum-verifier",
+    version = "1.0",
+    author = "Moritz Eck <moritz.eck@gmail.com>"
+)]
+pub struct Opts {
+    #[clap(subcommand)]
+    pub subcmd: SubCommand,
+}
+
+#[derive(Clap, Debug)]
+pub enum SubCommand {
+    #[clap(name = "audit")]
+    Audit(Audit),
+    #[clap(name = "verify_report")]
+    VerifyReport(VerifyReport),
+}
+
+/// A subcommand to verify all proofs of an exported transcript and sign the audit report
+#[derive(Clap, Debug)]
+pub struct Audit {
+    /// The path of the transcript (JSON), see: provotum-cli export_transcript
+    #[clap(short, long)]
+    pub transcript: String,
+    /// The private key of the auditor as string
+    #[clap(short, long)]
+    pub sk: String,
+    /// The path to write the signed report to, printed if not set
+    #[clap(short, long)]
+    pub output: Option<String>,
+}
+
+/// A subcommand to verify the signature of an audit report
+#[derive(Clap, Debug)]
+pub struct VerifyReport {
+    /// The path of the signed report (JSON)
+    #[clap(short, long)]
+    pub report: String,
+    /// The path of the audited transcript, if set the report must belong to it
+    #[clap(short, long)]
+    pub transcript: Option<String>,
+}
@@ -0,0 +1,124 @@
+//! The ballot box freeze of a vote, i.e. the ciphers present at the end of the voting phase.
+//!
+//! Once the tallying phase starts, the pallet records a `BallotBoxFreeze`: the hash of the last block
+//! of the voting phase and a blake2 hash chain over the number and the merkle root of the submitted
+//! ciphers of every topic. Its digest is the context of the challenges of all shuffle proofs of the vote.
+//! This module recomputes the hash chain and the digest from the ciphers of the transcript.
+use crate::attestation::{blake2_256, extend_length_prefixed};
+use crypto::types::Cipher;
+
+/// The context of the ballot box freeze, see: `BALLOT_BOX_FREEZE_CONTEXT` of the pallet.
+pub const BALLOT_BOX_FREEZE_CONTEXT: &[u8] = b"provotum-mixnet/ballot-box-freeze/v1";
+
+/// appends the length in the SCALE compact encoding (the length prefix of a Vec<u8>)
+fn extend_compact_length(input: &mut Vec<u8>, length: usize) {
+    let length = length as u64;
+    if length < 1 << 6 {
+        input.push((length as u8) << 2);
+    } else if length < 1 << 14 {
+        input.extend_from_slice(&(((length as u16) << 2) | 0b01).to_le_bytes());
+    } else if length < 1 << 30 {
+        input.extend_from_slice(&(((length as u32) << 2) | 0b10).to_le_bytes());
+    } else {
+        let nr_of_bytes = 8 - length.leading_zeros() as usize / 8;
+        input.push((((nr_of_bytes - 4) as u8) << 2) | 0b11);
+        input.extend_from_slice(&length.to_le_bytes()[..nr_of_bytes]);
+    }
+}
+
+/// the hash of the SCALE encoded cipher (a and b as big-endian bytes), the leaf of the merkle tree
+fn hash_cipher(cipher: &Cipher) -> [u8; 32] {
+    let mut input: Vec<u8> = Vec::new();
+    for value in [&cipher.a, &cipher.b].iter() {
+        let bytes = value.to_bytes_be();
+        extend_compact_length(&mut input, bytes.len());
+        input.extend_from_slice(&bytes);
+    }
+    blake2_256(&input)
+}
+
+/// computes the merkle root of the ciphers, see: `CipherRoots` of the pallet.
+/// the tree is a binary blake2_256 merkle tree, a node without a sibling is promoted
+/// to the next level unchanged. the root of an empty tree is zero.
+pub fn cipher_root(ciphers: &[Cipher]) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = ciphers.iter().map(hash_cipher).collect();
+    if level.is_empty() {
+        return [0u8; 32];
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => blake2_256(&[&left[..], &right[..]].concat()),
+                _ => pair[0],
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// computes the head of the hash chain over the submitted ciphers of the topics (in the order of the vote):
+/// d_0 = H(context | vote_id), d_i = H(d_i-1 | H(topic_id | n | root))
+/// where n is the number of ciphers of the topic (u64, big-endian) and root their merkle root,
+/// the ids are prefixed by their length (u32, big-endian).
+pub fn ciphers_chain(vote_id: &str, topics: &[(&str, Vec<Cipher>)]) -> [u8; 32] {
+    let mut input: Vec<u8> = BALLOT_BOX_FREEZE_CONTEXT.to_vec();
+    extend_length_prefixed(&mut input, vote_id.as_bytes());
+    let mut chain = blake2_256(&input);
+
+    for (topic_id, ciphers) in topics.iter() {
+        let mut input: Vec<u8> = Vec::new();
+        extend_length_prefixed(&mut input, topic_id.as_bytes());
+        input.extend_from_slice(&(ciphers.len() as u64).to_be_bytes());
+        input.extend_from_slice(&cipher_root(ciphers));
+        let topic_hash = blake2_256(&input);
+
+        let mut link: Vec<u8> = chain.to_vec();
+        link.extend_from_slice(&topic_hash);
+        chain = blake2_256(&link);
+    }
+    chain
+}
+
+/// computes the digest of the freeze: H(block_hash | ciphers_chain)
+pub fn freeze_digest(block_hash: &[u8], ciphers_chain: &[u8; 32]) -> [u8; 32] {
+    let mut input: Vec<u8> = block_hash.to_vec();
+    input.extend_from_slice(ciphers_chain);
+    blake2_256(&input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cipher_root, extend_compact_length};
+    use crypto::types::Cipher;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn it_should_encode_compact_lengths() {
+        let encode = |length: usize| {
+            let mut input = Vec::new();
+            extend_compact_length(&mut input, length);
+            input
+        };
+        assert_eq!(encode(0), vec![0x00]);
+        assert_eq!(encode(63), vec![0xfc]);
+        assert_eq!(encode(64), vec![0x01, 0x01]);
+        assert_eq!(encode(256), vec![0x01, 0x04]);
+        assert_eq!(encode(1 << 14), vec![0x02, 0x00, 0x01, 0x00]);
+        assert_eq!(encode(1 << 30), vec![0x03, 0x00, 0x00, 0x00, 0x40]);
+    }
+
+    #[test]
+    fn it_should_promote_the_node_without_sibling() {
+        let ciphers: Vec<Cipher> = (1..=3u32)
+            .map(|m| Cipher {
+                a: BigUint::from(m),
+                b: BigUint::from(m + 10),
+            })
+            .collect();
+        let root = cipher_root(&ciphers);
+        assert_ne!(root, cipher_root(&ciphers[..2]));
+        assert_eq!(cipher_root(&[]), [0u8; 32]);
+        assert_ne!(cipher_root(&ciphers[..1]), [0u8; 32]);
+    }
+}
@@ -0,0 +1,12 @@
+//! Re-verifies all proofs of a vote using only the crypto crate, e.g. to audit an election as a third party.
+//!
+//! The public values of a vote are exported from the chain as a transcript (see: `transcript`).
+//! The verifier checks the key generation, shuffle and decryption proofs of the transcript
+//! and creates an audit report which is signed by the auditor (see: `report`).
+//! The certified result of a vote can be attested to other chains (see: `attestation`).
+pub mod attestation;
+pub mod audit;
+pub mod freeze;
+pub mod report;
+pub mod shuffle;
+pub mod transcript;
@@ -0,0 +1,84 @@
+mod cli;
+
+use clap::Clap;
+use cli::{Opts, SubCommand};
+use crypto::helper::Helper;
+use serde::de::DeserializeOwned;
+use std::fs;
+use verifier::{
+    audit::{audit, hash_transcript},
+    report::{auditor_params, SignedReport},
+    transcript::Transcript,
+};
+
+fn read_json<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let json = fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+    serde_json::from_str(&json).map_err(|error| format!("{}: {}", path, error))
+}
+
+fn run_audit(transcript: String, sk: String, output: Option<String>) -> Result<bool, String> {
+    let transcript: Transcript = read_json(&transcript)?;
+    if let Some(block) = transcript.block.as_ref() {
+        println!("transcript of block #{} (0x{})", block.number, block.hash);
+    }
+    let report = audit(&transcript).map_err(|error| format!("{:?}", error))?;
+    for result in report.results.iter() {
+        println!("{}", result);
+    }
+
+    // sign the report with the auditor's private key
+    let (_, sk, _) = Helper::setup_lg_system_with_sk(sk.as_bytes());
+    let signed = SignedReport::sign(report, &sk).map_err(|error| format!("{:?}", error))?;
+    let json = serde_json::to_string_pretty(&signed).map_err(|error| error.to_string())?;
+    match output {
+        Some(path) => fs::write(&path, json).map_err(|error| format!("{}: {}", path, error))?,
+        None => println!("{}", json),
+    }
+    Ok(signed.report.valid)
+}
+
+fn run_verify_report(report: String, transcript: Option<String>) -> Result<bool, String> {
+    let signed: SignedReport = read_json(&report)?;
+    let mut valid = signed.verify(&auditor_params());
+    if let Some(transcript) = transcript {
+        let transcript: Transcript = read_json(&transcript)?;
+        let hash = hash_transcript(&transcript).map_err(|error| format!("{:?}", error))?;
+        valid &= hash == signed.report.transcript_hash;
+    }
+    Ok(valid)
+}
+
+fn main() {
+    let opts: Opts = Opts::parse();
+
+    match opts.subcmd {
+        SubCommand::Audit(t) => {
+            println!("Auditing transcript... {:?}", t.transcript);
+            match run_audit(t.transcript, t.sk, t.output) {
+                Ok(true) => println!("all proofs are valid!"),
+                Ok(false) => {
+                    println!("audit failed: at least one proof is invalid!");
+                    std::process::exit(1);
+                }
+                Err(err) => {
+                    println!("failed to audit transcript: {}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        SubCommand::VerifyReport(t) => {
+            println!("Verifying report... {:?}", t);
+            match run_verify_report(t.report, t.transcript) {
+                Ok(true) => println!("the report is valid!"),
+                Ok(false) => {
+                    println!("the report is invalid!");
+                    std::process::exit(1);
+                }
+                Err(err) => {
+                    println!("failed to verify report: {}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
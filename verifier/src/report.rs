@@ -0,0 +1,100 @@
+use crate::audit::{AuditError, AuditReport};
+use crypto::{
+    helper::Helper,
+    proofs::keygen::KeyGenerationProof,
+    random::Random,
+    types::{ElGamalParams, PrivateKey},
+};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+/// The audit report signed by the auditor.
+///
+/// The signature is a Schnorr signature: a proof of knowledge of the auditor's private key
+/// whose challenge includes the report (JSON), i.e. the report is the id of the proof.
+/// The auditor's keys are elements of the 2048bit group also used by the sealers.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct SignedReport {
+    pub report: AuditReport,
+    /// the public key of the auditor
+    #[serde(with = "crypto::proofs::wire::serde_hex")]
+    pub auditor: BigUint,
+    pub signature: KeyGenerationProof,
+}
+
+/// the group parameters of the auditor's keys
+pub fn auditor_params() -> ElGamalParams {
+    let (params, _, _) = Helper::setup_lg_system();
+    params
+}
+
+fn report_as_bytes(report: &AuditReport) -> Result<Vec<u8>, AuditError> {
+    serde_json::to_vec(report).map_err(|error| AuditError::Serialization(error.to_string()))
+}
+
+impl SignedReport {
+    /// signs the report with the private key of the auditor
+    pub fn sign(report: AuditReport, sk: &PrivateKey) -> Result<SignedReport, AuditError> {
+        let params = &sk.params;
+        let auditor = params.g.modpow(&sk.x, &params.p);
+        let r = Random::get_random_less_than(&params.q());
+        let message = report_as_bytes(&report)?;
+        let signature = KeyGenerationProof::generate(params, &sk.x, &auditor, &r, &message);
+        Ok(SignedReport {
+            report,
+            auditor,
+            signature,
+        })
+    }
+
+    /// verifies the signature of the report
+    pub fn verify(&self, params: &ElGamalParams) -> bool {
+        match report_as_bytes(&self.report) {
+            Ok(message) => {
+                KeyGenerationProof::verify(params, &self.auditor, &self.signature, &message)
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SignedReport;
+    use crate::audit::{AuditReport, CheckKind, CheckResult};
+    use crypto::helper::Helper;
+
+    fn report() -> AuditReport {
+        AuditReport {
+            vote_id: "Vote".into(),
+            transcript_hash: "00".into(),
+            results: vec![CheckResult {
+                kind: CheckKind::Shuffle,
+                subject: "topic Topic, iteration 0, ciphers 0..4".into(),
+                valid: true,
+            }],
+            valid: true,
+        }
+    }
+
+    #[test]
+    fn it_should_verify_signed_report() {
+        let (params, sk, _) = Helper::setup_sm_system();
+        let signed = SignedReport::sign(report(), &sk).unwrap();
+        assert!(signed.verify(&params));
+
+        // the signature is preserved by (de)serializing the report
+        let json = serde_json::to_string(&signed).unwrap();
+        let decoded: SignedReport = serde_json::from_str(&json).unwrap();
+        assert!(decoded.verify(&params));
+    }
+
+    #[test]
+    fn it_should_not_verify_modified_report() {
+        let (params, sk, _) = Helper::setup_sm_system();
+        let mut signed = SignedReport::sign(report(), &sk).unwrap();
+        signed.report.results[0].valid = false;
+        signed.report.valid = false;
+        assert!(!signed.verify(&params));
+    }
+}
@@ -0,0 +1,342 @@
+use crate::transcript::ShuffleProof as Proof;
+use crypto::{
+    hash::HashFunction,
+    proofs::shuffle::ShuffleProof,
+    types::{Cipher, PublicKey, ShuffleProofValues},
+};
+
+impl From<&Proof> for ShuffleProofValues {
+    fn from(proof: &Proof) -> Self {
+        ShuffleProofValues {
+            challenge: proof.challenge.clone(),
+            s1: proof.s1.clone(),
+            s2: proof.s2.clone(),
+            s3: proof.s3.clone(),
+            s4: proof.s4.clone(),
+            vec_s_hat: proof.vec_s_hat.clone(),
+            vec_s_tilde: proof.vec_s_tilde.clone(),
+            permutation_commitments: proof.permutation_commitments.clone(),
+            permutation_chain_commitments: proof.permutation_chain_commitments.clone(),
+        }
+    }
+}
+
+/// CheckShuffleProof Algorithm 8.51 (CHVoteSpec 3.1), see: `ShuffleProof::verify` of the crypto crate.
+///
+/// In contrast to the pallet, all commitments t are recomputed from the proof
+/// (the commitments submitted by the prover are not required).
+/// Returns false instead of panicking if the proof doesn't match the size of the shuffle.
+/// The challenge is bound to the context, i.e. the digest of the ballot box freeze (if any),
+/// and computed with the hash function of the vote.
+pub fn verify_shuffle_proof(
+    id: &[u8], // topicId (vote question)
+    context: &[u8],
+    proof: &Proof,
+    encryptions: Vec<Cipher>,
+    shuffled_encryptions: Vec<Cipher>,
+    pk: &PublicKey,
+    hash_function: HashFunction,
+) -> bool {
+    ShuffleProof::verify_in_context_with_hash(
+        id,
+        context,
+        &proof.into(),
+        encryptions,
+        shuffled_encryptions,
+        pk,
+        hash_function,
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::verify_shuffle_proof;
+    use crate::transcript::ShuffleProof as Proof;
+    use crypto::{
+        encryption::ElGamal,
+        hash::HashFunction,
+        helper::Helper,
+        proofs::shuffle::ShuffleProof,
+        random::Random,
+        types::{BigT, BigY, Cipher, ModuloOperations, PublicKey},
+    };
+    use num_bigint::BigUint;
+    use num_traits::{One, Zero};
+
+    fn randoms(q: &BigUint, size: usize) -> Vec<BigUint> {
+        (0..size).map(|_| Random::get_random_less_than(q)).collect()
+    }
+
+    fn sum_of_products(a: &[BigUint], b: &[BigUint], q: &BigUint) -> BigUint {
+        a.iter()
+            .zip(b.iter())
+            .fold(BigUint::zero(), |sum, (a_i, b_i)| {
+                sum.modadd(&a_i.modmul(b_i, q), q)
+            })
+    }
+
+    /// shuffles the encryptions and generates the shuffle proof (Algorithm 8.47, as in the pallet)
+    pub fn shuffle_and_prove(
+        id: &[u8],
+        encryptions: &[Cipher],
+        pk: &PublicKey,
+    ) -> (Vec<Cipher>, Proof) {
+        shuffle_and_prove_in_context(id, &[], encryptions, pk, HashFunction::Blake2b)
+    }
+
+    /// shuffles the encryptions and generates the shuffle proof whose challenge is bound to the context,
+    /// the challenges are computed with the hash function
+    pub fn shuffle_and_prove_in_context(
+        id: &[u8],
+        context: &[u8],
+        encryptions: &[Cipher],
+        pk: &PublicKey,
+        hash_function: HashFunction,
+    ) -> (Vec<Cipher>, Proof) {
+        let params = &pk.params;
+        let (g, h, p, q) = (&params.g, &params.h, &params.p, &params.q());
+        let size = encryptions.len();
+
+        // shuffle: permutation (reversed order) and re-encryption
+        let permutation: Vec<usize> = (0..size).rev().collect();
+        let vec_r_tilde = randoms(q, size);
+        let e_tilde: Vec<Cipher> = ElGamal::shuffle(encryptions, &permutation, &vec_r_tilde, pk)
+            .into_iter()
+            .map(|(cipher, _, _)| cipher)
+            .collect();
+
+        // commitments: permutation and chain
+        let vec_h = Helper::get_generators(id, p, size);
+        let commitment = ShuffleProof::generate_permutation_commitment(
+            params,
+            &permutation,
+            randoms(q, size),
+            vec_h.clone(),
+        );
+        let (vec_c, vec_r) = (commitment.commitments, commitment.randoms);
+        let vec_u = ShuffleProof::get_challenges_with_hash(
+            size,
+            encryptions.to_vec(),
+            e_tilde.clone(),
+            vec_c.clone(),
+            pk,
+            hash_function,
+        );
+        let u_tilde: Vec<BigUint> = permutation.iter().map(|j| vec_u[*j].clone()).collect();
+        let chain =
+            ShuffleProof::generate_commitment_chain(u_tilde.clone(), randoms(q, size), params);
+        let (vec_c_hat, vec_r_hat) = (chain.commitments, chain.randoms);
+
+        // commitments t and their random values w
+        let vec_w_hat = randoms(q, size);
+        let vec_w_tilde = randoms(q, size);
+        let (w1, w2, w3, w4) = (
+            Random::get_random_less_than(q),
+            Random::get_random_less_than(q),
+            Random::get_random_less_than(q),
+            Random::get_random_less_than(q),
+        );
+        let mut vec_t_hat = Vec::new();
+        let (mut r_i, mut u_i) = (BigUint::zero(), BigUint::one());
+        for i in 0..size {
+            let r_i_dash = vec_w_hat[i].modadd(&vec_w_tilde[i].modmul(&r_i, q), q);
+            r_i = vec_r_hat[i].modadd(&u_tilde[i].modmul(&r_i, q), q);
+            let u_i_dash = vec_w_tilde[i].modmul(&u_i, q);
+            u_i = u_tilde[i].modmul(&u_i, q);
+            vec_t_hat.push(g.modpow(&r_i_dash, p).modmul(&h.modpow(&u_i_dash, p), p));
+        }
+        let t1 = g.modpow(&w1, p);
+        let t2 = g.modpow(&w2, p);
+        let t3 = vec_h
+            .iter()
+            .zip(vec_w_tilde.iter())
+            .fold(g.modpow(&w3, p), |t, (h_i, w)| {
+                t.modmul(&h_i.modpow(w, p), p)
+            });
+        let t4_1 = e_tilde
+            .iter()
+            .zip(vec_w_tilde.iter())
+            .fold(g.modpow(&w4, p).invmod(p).unwrap(), |t, (c, w)| {
+                t.modmul(&c.a.modpow(w, p), p)
+            });
+        let t4_2 = e_tilde
+            .iter()
+            .zip(vec_w_tilde.iter())
+            .fold(pk.h.modpow(&w4, p).invmod(p).unwrap(), |t, (c, w)| {
+                t.modmul(&c.b.modpow(w, p), p)
+            });
+
+        // the challenge
+        let public_value: BigY = (
+            encryptions.to_vec(),
+            e_tilde.clone(),
+            vec_c.clone(),
+            vec_c_hat.clone(),
+            &pk.h,
+        );
+        let public_commitment: BigT = (t1, t2, t3, t4_1, t4_2, vec_t_hat);
+        let challenge = ShuffleProof::get_challenge_in_context_with_hash(
+            public_value,
+            public_commitment,
+            context,
+            q,
+            hash_function,
+        );
+
+        // the responses s
+        let mut v = vec![BigUint::one()];
+        for i in (0..(size - 1)).rev() {
+            let v_i = u_tilde[i + 1].modmul(v.last().unwrap(), q);
+            v.push(v_i);
+        }
+        v.reverse();
+        let r_flat = vec_r
+            .iter()
+            .fold(BigUint::zero(), |sum, r| sum.modadd(r, q));
+        let s1 = w1.modsub(&challenge.modmul(&r_flat, q), q);
+        let s2 = w2.modsub(&challenge.modmul(&sum_of_products(&vec_r_hat, &v, q), q), q);
+        let s3 = w3.modsub(&challenge.modmul(&sum_of_products(&vec_r, &vec_u, q), q), q);
+        let s4 = w4.modsub(
+            &challenge.modmul(&sum_of_products(&vec_r_tilde, &vec_u, q), q),
+            q,
+        );
+        let vec_s_hat = (0..size)
+            .map(|i| vec_w_hat[i].modsub(&challenge.modmul(&vec_r_hat[i], q), q))
+            .collect();
+        let vec_s_tilde = (0..size)
+            .map(|i| vec_w_tilde[i].modsub(&challenge.modmul(&u_tilde[i], q), q))
+            .collect();
+
+        let proof = Proof {
+            challenge,
+            s1,
+            s2,
+            s3,
+            s4,
+            vec_s_hat,
+            vec_s_tilde,
+            permutation_commitments: vec_c,
+            permutation_chain_commitments: vec_c_hat,
+        };
+        (e_tilde, proof)
+    }
+
+    fn setup_shuffle() -> (PublicKey, Vec<Cipher>) {
+        let (_, _, pk) = Helper::setup_sm_system();
+        let q = pk.params.q();
+        let encryptions = (0..4u32)
+            .map(|m| {
+                ElGamal::encrypt_encode(&BigUint::from(m), &Random::get_random_less_than(&q), &pk)
+            })
+            .collect();
+        (pk, encryptions)
+    }
+
+    #[test]
+    fn it_should_verify_shuffle_proof() {
+        let id = "Topic".as_bytes();
+        let (pk, encryptions) = setup_shuffle();
+        let (shuffled, proof) = shuffle_and_prove(id, &encryptions, &pk);
+
+        let is_valid = verify_shuffle_proof(
+            id,
+            &[],
+            &proof,
+            encryptions,
+            shuffled,
+            &pk,
+            HashFunction::Blake2b,
+        );
+        assert!(is_valid);
+    }
+
+    #[test]
+    fn it_should_verify_shuffle_proof_with_the_hash_function_of_the_vote() {
+        let id = "Topic".as_bytes();
+        let (pk, encryptions) = setup_shuffle();
+        let (shuffled, proof) =
+            shuffle_and_prove_in_context(id, &[], &encryptions, &pk, HashFunction::Sha3_256);
+
+        let is_valid = verify_shuffle_proof(
+            id,
+            &[],
+            &proof,
+            encryptions.clone(),
+            shuffled.clone(),
+            &pk,
+            HashFunction::Sha3_256,
+        );
+        assert!(is_valid);
+
+        // the challenges of another hash function don't match
+        let is_valid = verify_shuffle_proof(
+            id,
+            &[],
+            &proof,
+            encryptions,
+            shuffled,
+            &pk,
+            HashFunction::Blake2b,
+        );
+        assert!(!is_valid);
+    }
+
+    #[test]
+    fn it_should_not_verify_shuffle_proof_of_another_topic() {
+        let (pk, encryptions) = setup_shuffle();
+        let (shuffled, proof) = shuffle_and_prove("Topic".as_bytes(), &encryptions, &pk);
+
+        let is_valid = verify_shuffle_proof(
+            "Other".as_bytes(),
+            &[],
+            &proof,
+            encryptions,
+            shuffled,
+            &pk,
+            HashFunction::Blake2b,
+        );
+        assert!(!is_valid);
+    }
+
+    #[test]
+    fn it_should_not_verify_shuffle_proof_of_modified_ciphers() {
+        let id = "Topic".as_bytes();
+        let (pk, encryptions) = setup_shuffle();
+        let (mut shuffled, proof) = shuffle_and_prove(id, &encryptions, &pk);
+
+        // replace a shuffled cipher by an encryption of another vote
+        let q = pk.params.q();
+        shuffled[0] =
+            ElGamal::encrypt_encode(&BigUint::from(7u32), &Random::get_random_less_than(&q), &pk);
+
+        let is_valid = verify_shuffle_proof(
+            id,
+            &[],
+            &proof,
+            encryptions,
+            shuffled,
+            &pk,
+            HashFunction::Blake2b,
+        );
+        assert!(!is_valid);
+    }
+
+    #[test]
+    fn it_should_not_verify_shuffle_proof_of_wrong_size() {
+        let id = "Topic".as_bytes();
+        let (pk, encryptions) = setup_shuffle();
+        let (mut shuffled, proof) = shuffle_and_prove(id, &encryptions, &pk);
+        shuffled.pop();
+
+        let is_valid = verify_shuffle_proof(
+            id,
+            &[],
+            &proof,
+            encryptions,
+            shuffled,
+            &pk,
+            HashFunction::Blake2b,
+        );
+        assert!(!is_valid);
+    }
+}
@@ -0,0 +1,242 @@
+//! The transcript of a vote: all public values required to re-verify its proofs.
+//!
+//! A transcript is exported from the chain by the client (`provotum-cli export_transcript`)
+//! and serialized as JSON. Big integers are serialized as lower-case hex strings of their bytes
+//! (see: `crypto::proofs::wire::serde_hex`), account ids as hex strings of their SCALE encoding.
+use crypto::{
+    hash::HashFunction,
+    helper::{GENERATOR_DOMAIN, MAX_BATCH_EXPONENT_BITS},
+    proofs::{decryption::DecryptionProof, keygen::KeyGenerationProof},
+    types::{Cipher, ElGamalParams, PublicKey},
+};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+/// The current version of the transcript format.
+pub const TRANSCRIPT_VERSION: u8 = 1;
+
+/// The latest protocol version the verifier supports, see: `PROTOCOL_VERSION` of the pallet.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The parameters of the protocol the vote has been created with (see: `ProtocolConfig` of the pallet).
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct TranscriptProtocol {
+    pub version: u32,
+    /// the hash function of the challenges of the proofs, e.g. "Blake2b" or "Sha256"
+    pub hash_function: String,
+    /// the number of bits of the random exponents of the batched proof verification
+    pub batch_exponent_bits: u32,
+    /// the domain of the seeds from which the generators of the shuffle proof are derived
+    pub generator_domain: String,
+    pub nr_of_shuffles: u8,
+}
+
+impl TranscriptProtocol {
+    /// returns the hash function of the challenges, None if the verifier doesn't implement it.
+    /// the name is either the name of the variant of the pallet (e.g. "Sha3_256") or of the crypto crate (e.g. "sha3-256").
+    pub fn hash_function(&self) -> Option<HashFunction> {
+        HashFunction::ALL
+            .iter()
+            .find(|hash| format!("{:?}", hash) == self.hash_function)
+            .copied()
+            .or_else(|| HashFunction::from_name(&self.hash_function))
+    }
+
+    /// true if the proofs of the vote can be verified by this release
+    pub fn is_supported(&self) -> bool {
+        self.version >= 1
+            && self.version <= PROTOCOL_VERSION
+            && self.hash_function().is_some()
+            && self.batch_exponent_bits >= 1
+            && self.batch_exponent_bits as usize <= MAX_BATCH_EXPONENT_BITS
+            && self.generator_domain == GENERATOR_DOMAIN
+    }
+}
+
+/// The public parameters of a vote.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct TranscriptParams {
+    #[serde(with = "crypto::proofs::wire::serde_hex")]
+    pub p: BigUint,
+    #[serde(with = "crypto::proofs::wire::serde_hex")]
+    pub g: BigUint,
+    #[serde(with = "crypto::proofs::wire::serde_hex")]
+    pub h: BigUint,
+}
+
+impl From<&ElGamalParams> for TranscriptParams {
+    fn from(params: &ElGamalParams) -> Self {
+        TranscriptParams {
+            p: params.p.clone(),
+            g: params.g.clone(),
+            h: params.h.clone(),
+        }
+    }
+}
+
+impl From<&TranscriptParams> for ElGamalParams {
+    fn from(params: &TranscriptParams) -> Self {
+        ElGamalParams {
+            p: params.p.clone(),
+            g: params.g.clone(),
+            h: params.h.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct TranscriptCipher(#[serde(with = "crypto::proofs::wire::serde_hex::cipher")] pub Cipher);
+
+/// converts a list of transcript ciphers into ciphers
+pub fn into_ciphers(ciphers: &[TranscriptCipher]) -> Vec<Cipher> {
+    ciphers.iter().map(|cipher| cipher.0.clone()).collect()
+}
+
+/// converts a list of ciphers into transcript ciphers
+pub fn from_ciphers(ciphers: &[Cipher]) -> Vec<TranscriptCipher> {
+    ciphers
+        .iter()
+        .map(|cipher| TranscriptCipher(cipher.clone()))
+        .collect()
+}
+
+/// The public key share of a sealer together with its proof of knowledge of the private key.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct SealerKeyShare {
+    /// the SCALE encoded account id of the sealer (hex), the id of all its proofs
+    pub sealer: String,
+    #[serde(with = "crypto::proofs::wire::serde_hex")]
+    pub pk: BigUint,
+    pub proof: KeyGenerationProof,
+}
+
+/// The shuffle proof (Algorithm 8.47) of a single shuffled batch of ciphers.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct ShuffleProof {
+    #[serde(with = "crypto::proofs::wire::serde_hex")]
+    pub challenge: BigUint,
+    #[serde(with = "crypto::proofs::wire::serde_hex")]
+    pub s1: BigUint,
+    #[serde(with = "crypto::proofs::wire::serde_hex")]
+    pub s2: BigUint,
+    #[serde(with = "crypto::proofs::wire::serde_hex")]
+    pub s3: BigUint,
+    #[serde(with = "crypto::proofs::wire::serde_hex")]
+    pub s4: BigUint,
+    #[serde(with = "crypto::proofs::wire::serde_hex::vec")]
+    pub vec_s_hat: Vec<BigUint>,
+    #[serde(with = "crypto::proofs::wire::serde_hex::vec")]
+    pub vec_s_tilde: Vec<BigUint>,
+    #[serde(with = "crypto::proofs::wire::serde_hex::vec")]
+    pub permutation_commitments: Vec<BigUint>,
+    #[serde(with = "crypto::proofs::wire::serde_hex::vec")]
+    pub permutation_chain_commitments: Vec<BigUint>,
+}
+
+/// A shuffled batch of ciphers: the ciphers [start_position, start_position + batch_size)
+/// of the given iteration are shuffled into the ciphers of the next iteration.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct ShuffleRecord {
+    pub iteration: u8,
+    pub start_position: u64,
+    pub batch_size: u64,
+    pub shuffled_ciphers: Vec<TranscriptCipher>,
+    pub proof: ShuffleProof,
+}
+
+/// The partial decryptions of a sealer together with its proof.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct DecryptionRecord {
+    /// the SCALE encoded account id of the sealer (hex)
+    pub sealer: String,
+    #[serde(with = "crypto::proofs::wire::serde_hex::vec")]
+    pub shares: Vec<BigUint>,
+    pub proof: DecryptionProof,
+}
+
+/// All public values of a topic (question).
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct TopicTranscript {
+    pub topic_id: String,
+    /// the ciphers by the number of shuffles, i.e. the first entry contains the submitted ciphers
+    pub ciphers: Vec<Vec<TranscriptCipher>>,
+    pub shuffles: Vec<ShuffleRecord>,
+    /// the homomorphic sum of all ciphers, only set for votes using the homomorphic tally
+    pub aggregate: Option<TranscriptCipher>,
+    pub decryptions: Vec<DecryptionRecord>,
+}
+
+impl TopicTranscript {
+    /// the ciphers which are decrypted by the sealers:
+    /// the aggregate (homomorphic tally) or the ciphers of the last shuffle iteration (mixnet tally)
+    pub fn ciphers_to_decrypt(&self) -> Vec<Cipher> {
+        match &self.aggregate {
+            Some(aggregate) => vec![aggregate.0.clone()],
+            None => self
+                .ciphers
+                .last()
+                .map(|ciphers| into_ciphers(ciphers))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The ciphers of the vote at the end of the voting phase (see: `BallotBoxFreeze` of the pallet).
+/// The digest is the context of the challenges of all shuffle proofs. All hashes are hex strings.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct TranscriptFreeze {
+    /// the block in which the tallying phase started
+    pub block_number: u64,
+    /// the hash of the last block of the voting phase
+    pub block_hash: String,
+    /// the head of the hash chain over the submitted ciphers of the topics
+    pub ciphers_chain: String,
+    /// H(block_hash | ciphers_chain)
+    pub digest: String,
+}
+
+/// The block whose state the transcript has been exported from. The hash is a hex string.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct TranscriptBlock {
+    pub number: u64,
+    pub hash: String,
+}
+
+/// The transcript of a vote.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+pub struct Transcript {
+    pub version: u8,
+    pub vote_id: String,
+    pub params: TranscriptParams,
+    /// the public key of the vote (the value h)
+    #[serde(with = "crypto::proofs::wire::serde_hex")]
+    pub public_key: BigUint,
+    pub nr_of_shuffles: u8,
+    /// the protocol the vote has been created with, transcripts of votes created before the protocol
+    /// has been recorded on chain don't contain it (they are audited using the first protocol version)
+    #[serde(default)]
+    pub protocol: Option<TranscriptProtocol>,
+    /// true if the same batch of all topics is shuffled with the same permutation (linked shuffles),
+    /// the generators of the permutation commitments are then derived from the vote id
+    #[serde(default)]
+    pub linked_shuffles: bool,
+    /// the ciphers at the end of the voting phase, transcripts of votes tallied before
+    /// the ballot box freeze has been recorded on chain don't contain it (their proofs have no context)
+    #[serde(default)]
+    pub freeze: Option<TranscriptFreeze>,
+    /// the block all values have been read at, i.e. the storage view the proofs are audited against,
+    /// transcripts exported before the export has been pinned to a block don't contain it
+    #[serde(default)]
+    pub block: Option<TranscriptBlock>,
+    pub key_shares: Vec<SealerKeyShare>,
+    pub topics: Vec<TopicTranscript>,
+}
+
+impl Transcript {
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            params: (&self.params).into(),
+            h: self.public_key.clone(),
+        }
+    }
+}
@@ -0,0 +1,361 @@
+//! Verifies the key generation proofs of the public key shares and the decryption proofs of the
+//! partial decryptions of the sealers (CHVoteSpec 3.2, Algorithms 8.8 and 8.50) using the verifiers
+//! of the crypto crate, e.g. for the election monitor of public observers.
+//!
+//! The inputs use the format of the transcript (see: `provotum-cli export_transcript`):
+//! the key share (`key_shares`) and the decryption record (`decryptions` of a topic) of the sealer,
+//! the parameters of the vote and its hash function. Big integers are hex strings of their
+//! big-endian bytes (optionally 0x prefixed), the id of the proofs is the SCALE encoded account
+//! id of the sealer (hex). The result tells which check failed (see: `Verification`).
+use crate::shuffle::{decode_biguint, decode_ciphers, Params, ShuffleError};
+use crypto::{
+    hash::HashFunction,
+    proofs::{
+        decryption::{DecryptionProof, DEFAULT_BATCH_SOUNDNESS},
+        keygen::KeyGenerationProof,
+        ProofFailure,
+    },
+    types::{Cipher as BigCipher, ElGamalParams},
+};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DkgError {
+    /// an input isn't valid JSON
+    Serialization(String),
+    /// a big integer or the id of the sealer isn't a hex string
+    InvalidHex(String),
+    /// the hash function isn't supported
+    UnknownHashFunction(String),
+}
+
+impl fmt::Display for DkgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DkgError::Serialization(error) => write!(f, "invalid JSON: {}", error),
+            DkgError::InvalidHex(value) => write!(f, "invalid hex value: {}", value),
+            DkgError::UnknownHashFunction(name) => write!(f, "unknown hash function: {}", name),
+        }
+    }
+}
+
+impl From<ShuffleError> for DkgError {
+    fn from(error: ShuffleError) -> Self {
+        match error {
+            ShuffleError::Serialization(error) => DkgError::Serialization(error),
+            ShuffleError::InvalidHex(value) => DkgError::InvalidHex(value),
+        }
+    }
+}
+
+/// The proof of a key share or of a decryption, both consist of a challenge and a response.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Proof {
+    pub challenge: String,
+    pub response: String,
+}
+
+/// The public key share of a sealer and its key generation proof (see: `key_shares` of the transcript).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct KeyShare {
+    /// the SCALE encoded account id of the sealer (hex)
+    pub sealer: String,
+    pub pk: String,
+    pub proof: Proof,
+}
+
+/// The partial decryptions of a sealer and their batched decryption proof
+/// (see: `decryptions` of a topic of the transcript).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct DecryptionRecord {
+    /// the SCALE encoded account id of the sealer (hex)
+    pub sealer: String,
+    pub shares: Vec<String>,
+    pub proof: Proof,
+}
+
+/// The check of a proof which failed.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Check {
+    /// a public value (the modulus, the public key share, a cipher or a partial decryption) isn't
+    /// an element of the group, i.e. p <= 3 or the value isn't in [1, p)
+    Group,
+    /// the number of ciphers and partial decryptions differ, or there are none
+    InputLength,
+    /// the public key share has no inverse mod p
+    PublicKey,
+    /// the recomputed challenge differs from the challenge of the proof
+    Challenge,
+    /// g^d != b * h^c mod p (key generation proof)
+    Response,
+}
+
+impl From<ProofFailure> for Check {
+    fn from(failure: ProofFailure) -> Self {
+        match failure {
+            ProofFailure::InputLength => Check::InputLength,
+            ProofFailure::PublicKey => Check::PublicKey,
+            ProofFailure::Group => Check::Group,
+            ProofFailure::Challenge => Check::Challenge,
+            ProofFailure::Response => Check::Response,
+        }
+    }
+}
+
+/// The result of the verification of a proof, `failed` is the check which failed if it isn't valid.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Verification {
+    pub valid: bool,
+    pub failed: Option<Check>,
+}
+
+impl From<Result<(), Check>> for Verification {
+    fn from(result: Result<(), Check>) -> Self {
+        Verification {
+            valid: result.is_ok(),
+            failed: result.err(),
+        }
+    }
+}
+
+impl Proof {
+    fn decode_keygen(&self) -> Result<KeyGenerationProof, DkgError> {
+        Ok(KeyGenerationProof {
+            challenge: decode_biguint(&self.challenge)?,
+            response: decode_biguint(&self.response)?,
+        })
+    }
+
+    fn decode_decryption(&self) -> Result<DecryptionProof, DkgError> {
+        Ok(DecryptionProof {
+            challenge: decode_biguint(&self.challenge)?,
+            response: decode_biguint(&self.response)?,
+        })
+    }
+}
+
+/// the hash function of the vote as in the transcript, e.g. `Blake2b` or `blake2b`
+fn decode_hash_function(name: &str) -> Result<HashFunction, DkgError> {
+    HashFunction::ALL
+        .iter()
+        .copied()
+        .find(|hash| format!("{:?}", hash) == name)
+        .or_else(|| HashFunction::from_name(name))
+        .ok_or_else(|| DkgError::UnknownHashFunction(name.to_string()))
+}
+
+fn decode_params(params_json: &str) -> Result<ElGamalParams, DkgError> {
+    let params: Params = serde_json::from_str(params_json)
+        .map_err(|error| DkgError::Serialization(error.to_string()))?;
+    Ok(ElGamalParams {
+        p: decode_biguint(&params.p)?,
+        g: decode_biguint(&params.g)?,
+        h: decode_biguint(&params.h)?,
+    })
+}
+
+fn decode_sealer_id(sealer: &str) -> Result<Vec<u8>, DkgError> {
+    hex::decode(sealer.strip_prefix("0x").unwrap_or(sealer))
+        .map_err(|_| DkgError::InvalidHex(sealer.to_string()))
+}
+
+/// the verifiers of the crypto crate don't check the group, a modulus p <= 3 has no valid q
+fn ensure_group_elements<'a>(
+    params: &ElGamalParams,
+    values: impl IntoIterator<Item = &'a BigUint>,
+) -> Result<(), Check> {
+    let one = BigUint::from(1u32);
+    let p = &params.p;
+    if *p <= BigUint::from(3u32) || params.g <= one || params.g >= *p {
+        return Err(Check::Group);
+    }
+    if values.into_iter().all(|value| value >= &one && value < p) {
+        Ok(())
+    } else {
+        Err(Check::Group)
+    }
+}
+
+/// verifies that the sealer knows the private key of its public key share
+pub fn verify_keygen_proof(
+    share_json: &str,
+    params_json: &str,
+    hash_function: &str,
+) -> Result<Verification, DkgError> {
+    let share: KeyShare = serde_json::from_str(share_json)
+        .map_err(|error| DkgError::Serialization(error.to_string()))?;
+    let params = decode_params(params_json)?;
+    let hash = decode_hash_function(hash_function)?;
+    let sealer_id = decode_sealer_id(&share.sealer)?;
+    let pk = decode_biguint(&share.pk)?;
+    let proof = share.proof.decode_keygen()?;
+
+    let result = ensure_group_elements(&params, vec![&pk]).and_then(|_| {
+        KeyGenerationProof::check_with_hash(&params, &pk, &proof, &sealer_id, hash)
+            .map_err(Check::from)
+    });
+    Ok(result.into())
+}
+
+/// verifies that the partial decryptions of the sealer are the decryptions of the ciphers
+/// with the private key of its public key share (batched decryption proof)
+pub fn verify_decryption_proof(
+    record_json: &str,
+    ciphers_json: &str,
+    pk_share: &str,
+    params_json: &str,
+    hash_function: &str,
+) -> Result<Verification, DkgError> {
+    let record: DecryptionRecord = serde_json::from_str(record_json)
+        .map_err(|error| DkgError::Serialization(error.to_string()))?;
+    let params = decode_params(params_json)?;
+    let hash = decode_hash_function(hash_function)?;
+    let sealer_id = decode_sealer_id(&record.sealer)?;
+    let pk = decode_biguint(pk_share)?;
+    let ciphers: Vec<BigCipher> = decode_ciphers(ciphers_json)?;
+    let shares: Vec<BigUint> = record
+        .shares
+        .iter()
+        .map(|share| decode_biguint(share))
+        .collect::<Result<_, _>>()?;
+    let proof = record.proof.decode_decryption()?;
+
+    let values = ciphers
+        .iter()
+        .flat_map(|cipher| vec![&cipher.a, &cipher.b])
+        .chain(shares.iter())
+        .chain(vec![&pk]);
+    let result = ensure_group_elements(&params, values).and_then(|_| {
+        DecryptionProof::check_batched_with_hash(
+            &params,
+            &pk,
+            &proof,
+            &ciphers,
+            &shares,
+            &sealer_id,
+            DEFAULT_BATCH_SOUNDNESS,
+            hash,
+        )
+        .map_err(Check::from)
+    });
+    Ok(result.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the 48 bit test system (see: `Helper::setup_sm_system`), the sealer is Alice
+    const PARAMS: &str = r#"{"p":"b7e151629927","g":"04","h":"09"}"#;
+    const SEALER: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+    const PK_SHARE: &str = "b66d69792651";
+    const KEY_PROOF: &str = r#"{"challenge":"2fd1413c2422","response":"5a9d22fd9808"}"#;
+    const CIPHERS: &str =
+        r#"[{"a":"8c040acd3f83","b":"4445af4dea56"},{"a":"086c370d3297","b":"3c90231b100a"}]"#;
+    const SHARES: &str = r#"["4445af4dea56","1e48118d8805"]"#;
+    const DECRYPTION_PROOF: &str = r#"{"challenge":"04f5d4d6b704","response":"1bb205d42718"}"#;
+
+    fn key_share(sealer: &str, pk: &str) -> String {
+        format!(
+            r#"{{"sealer":"{}","pk":"{}","proof":{}}}"#,
+            sealer, pk, KEY_PROOF
+        )
+    }
+
+    fn decryption_record(shares: &str) -> String {
+        format!(
+            r#"{{"sealer":"{}","shares":{},"proof":{}}}"#,
+            SEALER, shares, DECRYPTION_PROOF
+        )
+    }
+
+    fn failed(check: Check) -> Verification {
+        Verification {
+            valid: false,
+            failed: Some(check),
+        }
+    }
+
+    #[test]
+    fn it_should_verify_a_keygen_proof() {
+        let share = key_share(SEALER, PK_SHARE);
+        assert_eq!(
+            verify_keygen_proof(&share, PARAMS, "Blake2b"),
+            Ok(Verification {
+                valid: true,
+                failed: None
+            })
+        );
+        assert_eq!(
+            verify_keygen_proof(&share, PARAMS, "blake2b").map(|result| result.valid),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn it_should_return_the_failed_check_of_a_keygen_proof() {
+        // the proof of another sealer, or computed with another hash function
+        let share = key_share(&SEALER.replace("d4", "d5"), PK_SHARE);
+        assert_eq!(
+            verify_keygen_proof(&share, PARAMS, "Blake2b"),
+            Ok(failed(Check::Challenge))
+        );
+        let share = key_share(SEALER, PK_SHARE);
+        assert_eq!(
+            verify_keygen_proof(&share, PARAMS, "Sha256"),
+            Ok(failed(Check::Challenge))
+        );
+
+        // the public key share isn't an element of the group
+        let share = key_share(SEALER, "b7e151629927");
+        assert_eq!(
+            verify_keygen_proof(&share, PARAMS, "Blake2b"),
+            Ok(failed(Check::Group))
+        );
+    }
+
+    #[test]
+    fn it_should_verify_a_decryption_proof() {
+        let record = decryption_record(SHARES);
+        assert_eq!(
+            verify_decryption_proof(&record, CIPHERS, PK_SHARE, PARAMS, "Blake2b"),
+            Ok(Verification {
+                valid: true,
+                failed: None
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_return_the_failed_check_of_a_decryption_proof() {
+        // a modified partial decryption
+        let record = decryption_record(r#"["4445af4dea56","1e48118d8806"]"#);
+        assert_eq!(
+            verify_decryption_proof(&record, CIPHERS, PK_SHARE, PARAMS, "Blake2b"),
+            Ok(failed(Check::Challenge))
+        );
+
+        // a missing partial decryption
+        let record = decryption_record(r#"["4445af4dea56"]"#);
+        assert_eq!(
+            verify_decryption_proof(&record, CIPHERS, PK_SHARE, PARAMS, "Blake2b"),
+            Ok(failed(Check::InputLength))
+        );
+    }
+
+    #[test]
+    fn it_should_reject_malformed_inputs() {
+        let record = decryption_record(SHARES);
+        assert_eq!(
+            verify_decryption_proof(&record, CIPHERS, PK_SHARE, PARAMS, "md5"),
+            Err(DkgError::UnknownHashFunction("md5".to_string()))
+        );
+        assert_eq!(
+            verify_decryption_proof(&record, CIPHERS, "xy", PARAMS, "Blake2b"),
+            Err(DkgError::InvalidHex("xy".to_string()))
+        );
+    }
+}
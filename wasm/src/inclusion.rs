@@ -0,0 +1,302 @@
+//! Verifies the inclusion of a cipher in two steps:
+//!
+//! 1. the storage proof (`state_getReadProof`) proves the merkle root of the topic's ciphers
+//!    in the `CipherRoots` storage against the state root of the block header
+//! 2. the merkle path (`mixnet_getCipherProof`) proves the cipher against the merkle root
+use codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use sp_core::{
+    hashing::{blake2_128, blake2_256, twox_128},
+    Blake2Hasher, H256,
+};
+use sp_trie::{read_trie_value, Layout, StorageProof};
+use std::fmt;
+
+/// the storage prefix of the mixnet pallet, see: `decl_storage` of pallet-mixnet
+const STORAGE_PREFIX: &[u8] = b"OffchainModule";
+
+/// the storage item which maps the topic of a vote and a shuffle iteration to its merkle root
+const CIPHER_ROOTS: &[u8] = b"CipherRoots";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InclusionError {
+    /// the header or the proof isn't valid JSON
+    Serialization(String),
+    /// a value isn't 0x prefixed hex or a hash isn't 32 bytes long
+    InvalidHex(String),
+    /// the storage proof doesn't contain all trie nodes to read the merkle root
+    IncompleteStorageProof,
+}
+
+impl fmt::Display for InclusionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InclusionError::Serialization(error) => write!(f, "invalid JSON: {}", error),
+            InclusionError::InvalidHex(value) => write!(f, "invalid hex value: {}", value),
+            InclusionError::IncompleteStorageProof => {
+                write!(f, "the storage proof doesn't match the state root")
+            }
+        }
+    }
+}
+
+/// The block header, only the state root is required.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Header {
+    pub state_root: String,
+}
+
+/// The merkle inclusion proof of a cipher, as returned by `mixnet_getCipherProof`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CipherProof {
+    /// hex encoded blake2 hash of the SCALE encoded cipher
+    pub leaf: String,
+    pub index: u64,
+    pub nr_of_leaves: u64,
+    /// hex encoded sibling hashes from the leaf up to the root
+    pub path: Vec<String>,
+}
+
+/// The proof that a cipher is included in the ciphers of a topic at a block.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionProof {
+    pub vote_id: String,
+    pub topic_id: String,
+    /// the shuffle iteration of the ciphers, 0 for the ciphers cast by the voters
+    pub nr_of_shuffles: u8,
+    /// hex encoded trie nodes returned by `state_getReadProof` for the cipher root storage key
+    pub storage_proof: Vec<String>,
+    pub cipher_proof: CipherProof,
+}
+
+/// returns the storage key of CipherRoots((vote_id, topic_id), nr_of_shuffles),
+/// both keys are hashed with blake2_128_concat
+pub fn cipher_root_storage_key(vote_id: &str, topic_id: &str, nr_of_shuffles: u8) -> Vec<u8> {
+    let topic: Vec<u8> = (vote_id.as_bytes().to_vec(), topic_id.as_bytes().to_vec()).encode();
+    let nr_of_shuffles: Vec<u8> = nr_of_shuffles.encode();
+    let mut key: Vec<u8> = Vec::new();
+    key.extend_from_slice(&twox_128(STORAGE_PREFIX));
+    key.extend_from_slice(&twox_128(CIPHER_ROOTS));
+    key.extend_from_slice(&blake2_128(&topic));
+    key.extend_from_slice(&topic);
+    key.extend_from_slice(&blake2_128(&nr_of_shuffles));
+    key.extend_from_slice(&nr_of_shuffles);
+    key
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, InclusionError> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    hex::decode(digits).map_err(|_| InclusionError::InvalidHex(value.to_string()))
+}
+
+fn decode_hash(value: &str) -> Result<[u8; 32], InclusionError> {
+    let bytes = decode_hex(value)?;
+    if bytes.len() != 32 {
+        return Err(InclusionError::InvalidHex(value.to_string()));
+    }
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&bytes);
+    Ok(hash)
+}
+
+/// reads the merkle root of the topic's ciphers from the storage proof,
+/// None if no ciphers are stored for the topic and shuffle iteration
+fn read_cipher_root(
+    state_root: &H256,
+    proof: &InclusionProof,
+) -> Result<Option<[u8; 32]>, InclusionError> {
+    let nodes: Vec<Vec<u8>> = proof
+        .storage_proof
+        .iter()
+        .map(|node| decode_hex(node))
+        .collect::<Result<_, _>>()?;
+    let db = StorageProof::new(nodes).into_memory_db::<Blake2Hasher>();
+    let key = cipher_root_storage_key(&proof.vote_id, &proof.topic_id, proof.nr_of_shuffles);
+    let value = read_trie_value::<Layout<Blake2Hasher>, _>(&db, state_root, &key)
+        .map_err(|_| InclusionError::IncompleteStorageProof)?;
+    Ok(value.and_then(|value| <[u8; 32]>::decode(&mut &value[..]).ok()))
+}
+
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut input: Vec<u8> = Vec::with_capacity(64);
+    input.extend_from_slice(left);
+    input.extend_from_slice(right);
+    blake2_256(&input)
+}
+
+/// recomputes the root from the leaf and its path and compares it to the expected root.
+/// the tree is the binary blake2_256 merkle tree of the pallet (see: `helpers::merkle`),
+/// a node without a sibling is promoted to the next level unchanged.
+fn verify_merkle_path(
+    root: &[u8; 32],
+    leaf: &[u8; 32],
+    index: u64,
+    nr_of_leaves: u64,
+    path: &[[u8; 32]],
+) -> bool {
+    if index >= nr_of_leaves {
+        return false;
+    }
+    let mut siblings = path.iter();
+    let mut hash = *leaf;
+    let mut index = index;
+    let mut width = nr_of_leaves;
+    while width > 1 {
+        let is_right_node = index % 2 == 1;
+        if is_right_node || index + 1 < width {
+            let sibling = match siblings.next() {
+                Some(sibling) => sibling,
+                None => return false,
+            };
+            hash = if is_right_node {
+                hash_node(sibling, &hash)
+            } else {
+                hash_node(&hash, sibling)
+            };
+        }
+        index /= 2;
+        width = (width + 1) / 2;
+    }
+    siblings.next().is_none() && &hash == root
+}
+
+/// verifies that the SCALE encoded cipher is included in the ciphers of the topic
+/// committed to by the state root of the header
+pub fn verify_ballot_inclusion(
+    header_json: &str,
+    merkle_proof: &str,
+    cipher_bytes: &[u8],
+) -> Result<bool, InclusionError> {
+    let header: Header = serde_json::from_str(header_json)
+        .map_err(|error| InclusionError::Serialization(error.to_string()))?;
+    let proof: InclusionProof = serde_json::from_str(merkle_proof)
+        .map_err(|error| InclusionError::Serialization(error.to_string()))?;
+
+    // the merkle path must start at the hash of the cipher
+    let leaf = decode_hash(&proof.cipher_proof.leaf)?;
+    if leaf != blake2_256(cipher_bytes) {
+        return Ok(false);
+    }
+    let path: Vec<[u8; 32]> = proof
+        .cipher_proof
+        .path
+        .iter()
+        .map(|node| decode_hash(node))
+        .collect::<Result<_, _>>()?;
+
+    let state_root = H256::from(decode_hash(&header.state_root)?);
+    let cipher_root = match read_cipher_root(&state_root, &proof)? {
+        Some(cipher_root) => cipher_root,
+        None => return Ok(false),
+    };
+    Ok(verify_merkle_path(
+        &cipher_root,
+        &leaf,
+        proof.cipher_proof.index,
+        proof.cipher_proof.nr_of_leaves,
+        &path,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_trie::{MemoryDB, TrieDBMut, TrieMut};
+
+    const VOTE_ID: &str = "20201212";
+    const TOPIC_ID: &str = "20201212-01";
+
+    fn to_hex(bytes: &[u8]) -> String {
+        format!("0x{}", hex::encode(bytes))
+    }
+
+    /// creates the ciphers of a topic, the state with their merkle root
+    /// and the inclusion proof of the last cipher
+    fn setup_inclusion_proof() -> (String, InclusionProof, Vec<Vec<u8>>) {
+        let ciphers: Vec<Vec<u8>> = (1u8..=3)
+            .map(|i| (vec![i; 4], vec![i + 10; 4]).encode())
+            .collect();
+        let leaves: Vec<[u8; 32]> = ciphers.iter().map(|cipher| blake2_256(cipher)).collect();
+        let left = hash_node(&leaves[0], &leaves[1]);
+        let cipher_root = hash_node(&left, &leaves[2]);
+
+        // store the merkle root in the state trie, the proof contains all trie nodes
+        let mut db = MemoryDB::<Blake2Hasher>::default();
+        let mut state_root = H256::default();
+        {
+            let mut trie = TrieDBMut::<Layout<Blake2Hasher>>::new(&mut db, &mut state_root);
+            let key = cipher_root_storage_key(VOTE_ID, TOPIC_ID, 0);
+            trie.insert(&key, &cipher_root.encode()).unwrap();
+        }
+        let storage_proof: Vec<String> = db
+            .drain()
+            .into_iter()
+            .map(|(_, (node, _))| to_hex(&node))
+            .collect();
+
+        let header = format!(
+            r#"{{"parentHash":"0x{}","number":"0x2a","stateRoot":"{}","extrinsicsRoot":"0x{}","digest":{{"logs":[]}}}}"#,
+            "00".repeat(32),
+            to_hex(state_root.as_bytes()),
+            "00".repeat(32),
+        );
+        let proof = InclusionProof {
+            vote_id: VOTE_ID.to_string(),
+            topic_id: TOPIC_ID.to_string(),
+            nr_of_shuffles: 0,
+            storage_proof,
+            cipher_proof: CipherProof {
+                leaf: to_hex(&leaves[2]),
+                index: 2,
+                nr_of_leaves: 3,
+                path: vec![to_hex(&left)],
+            },
+        };
+        (header, proof, ciphers)
+    }
+
+    #[test]
+    fn it_should_verify_the_inclusion_of_a_cipher() {
+        let (header, proof, ciphers) = setup_inclusion_proof();
+        let proof = serde_json::to_string(&proof).unwrap();
+        assert_eq!(
+            verify_ballot_inclusion(&header, &proof, &ciphers[2]),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn it_should_not_verify_another_cipher() {
+        let (header, proof, ciphers) = setup_inclusion_proof();
+        let proof = serde_json::to_string(&proof).unwrap();
+        assert_eq!(
+            verify_ballot_inclusion(&header, &proof, &ciphers[0]),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn it_should_not_verify_a_cipher_of_another_shuffle_iteration() {
+        let (header, mut proof, ciphers) = setup_inclusion_proof();
+        proof.nr_of_shuffles = 1;
+        let proof = serde_json::to_string(&proof).unwrap();
+        assert_eq!(
+            verify_ballot_inclusion(&header, &proof, &ciphers[2]),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn it_should_reject_a_storage_proof_of_another_state() {
+        let (_, proof, ciphers) = setup_inclusion_proof();
+        let header = format!(r#"{{"stateRoot":"0x{}"}}"#, "11".repeat(32));
+        let proof = serde_json::to_string(&proof).unwrap();
+        assert_eq!(
+            verify_ballot_inclusion(&header, &proof, &ciphers[2]),
+            Err(InclusionError::IncompleteStorageProof)
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! WebAssembly bindings for voter-facing web apps.
+//!
+//! A voter can verify that the cipher of its ballot is included in the ciphers of a topic
+//! using only a block header (e.g. from a light client), without trusting the RPC node
+//! which served the proofs (see: `inclusion`).
+//! The voter can also re-derive the randomness of its encryptions from its seed,
+//! and sign its ballot for a relayer (paper channel, see: `relay`).
+//! Public observers can re-check the shuffle proofs of the mixes (see: `shuffle`),
+//! the key generation proofs and the decryption proofs of the sealers (see: `dkg`).
+pub mod dkg;
+pub mod inclusion;
+pub mod relay;
+pub mod shuffle;
+
+use crypto::derivation::Derivation;
+use mixnet_primitives::relay::relayed_ballot_message as ballot_message;
+use num_bigint::BigUint;
+use wasm_bindgen::prelude::*;
+
+/// Verifies that the SCALE encoded cipher is included in the ciphers committed to by the
+/// `CipherRoots` storage at the block with the given header (JSON, see: `chain_getHeader`).
+/// The merkle proof is a JSON encoded `InclusionProof`.
+/// Returns false if one of the proofs doesn't verify, throws if an input is malformed.
+#[wasm_bindgen]
+pub fn verify_ballot_inclusion(
+    header_json: &str,
+    merkle_proof: &str,
+    cipher_bytes: &[u8],
+) -> Result<bool, JsValue> {
+    inclusion::verify_ballot_inclusion(header_json, merkle_proof, cipher_bytes)
+        .map_err(|error| JsValue::from_str(&error.to_string()))
+}
+
+/// Returns the hex encoded storage key of the merkle root of the ciphers of the vote's topic
+/// after the given number of shuffles, i.e. the key to request with `state_getReadProof`.
+#[wasm_bindgen]
+pub fn cipher_root_storage_key(vote_id: &str, topic_id: &str, nr_of_shuffles: u8) -> String {
+    format!(
+        "0x{}",
+        hex::encode(inclusion::cipher_root_storage_key(
+            vote_id,
+            topic_id,
+            nr_of_shuffles
+        ))
+    )
+}
+
+/// Derives the randomness of the answer to the topic from the voter's seed (see: `crypto::derivation`).
+/// The modulus p of the vote is hex encoded (see: `provotum_getVote`), the randomness is in Z_q
+/// with q = (p - 1) / 2. Returns the 0x prefixed hex encoded randomness, throws if p is malformed.
+#[wasm_bindgen]
+pub fn derive_randomness(
+    seed: &[u8],
+    vote_id: &str,
+    topic_id: &str,
+    attempt: u32,
+    p: &str,
+) -> Result<String, JsValue> {
+    let p = BigUint::parse_bytes(p.trim_start_matches("0x").as_bytes(), 16)
+        .filter(|p| p > &BigUint::from(3u32))
+        .ok_or_else(|| JsValue::from_str("p is not a valid hex encoded modulus"))?;
+    let q = (p - 1u32) / 2u32;
+    let r =
+        Derivation::derive_randomness(seed, vote_id.as_bytes(), topic_id.as_bytes(), attempt, &q)
+            .ok_or_else(|| JsValue::from_str("the modulus is too large to derive the randomness"))?;
+    Ok(format!("0x{:x}", r))
+}
+
+/// Returns the message the voter signs (sr25519) to relay the SCALE encoded ballot.
+#[wasm_bindgen]
+pub fn relayed_ballot_message(vote_id: &str, ballot: &[u8]) -> Vec<u8> {
+    ballot_message(vote_id.as_bytes(), ballot)
+}
+
+/// Encodes the SCALE encoded ballot, the voter's public key and its signature of the
+/// relayed ballot message as compact text for a QR code (CBOR, base45).
+/// Throws if the public key or the signature are malformed.
+#[wasm_bindgen]
+pub fn encode_compact_ballot(
+    vote_id: &str,
+    ballot: &[u8],
+    voter: &[u8],
+    signature: &[u8],
+) -> Result<String, JsValue> {
+    relay::encode_compact_ballot(vote_id, ballot, voter, signature)
+        .map_err(|error| JsValue::from_str(&error.to_string()))
+}
+
+/// Decodes a scanned compact ballot, returns the JSON encoded `ScannedBallot`
+/// (the arguments of `cast_relayed_ballot`). Throws if the text isn't a compact ballot.
+#[wasm_bindgen]
+pub fn decode_compact_ballot(text: &str) -> Result<String, JsValue> {
+    let scanned = relay::decode_compact_ballot(text)
+        .map_err(|error| JsValue::from_str(&error.to_string()))?;
+    serde_json::to_string(&scanned).map_err(|error| JsValue::from_str(&error.to_string()))
+}
+
+/// Verifies the shuffle proof of a batch of ciphers using the verifier of the crypto crate.
+/// The proof (including the topic id), the ciphers, the shuffled ciphers and the public key
+/// of the vote are JSON encoded, see: `shuffle`.
+/// Returns false if the proof doesn't verify, throws if an input is malformed.
+#[wasm_bindgen]
+pub fn verify_shuffle_proof(
+    proof_json: &str,
+    ciphers_json: &str,
+    shuffled_json: &str,
+    pk_json: &str,
+) -> Result<bool, JsValue> {
+    shuffle::verify_shuffle_proof(proof_json, ciphers_json, shuffled_json, pk_json)
+        .map_err(|error| JsValue::from_str(&error.to_string()))
+}
+
+/// Verifies the key generation proof of the public key share of a sealer.
+/// The key share (sealer, pk and proof) and the parameters of the vote are JSON encoded, the hash
+/// function is the one of the vote, see: `dkg`. Returns the JSON encoded `Verification`
+/// (`valid` and the check which `failed`), throws if an input is malformed.
+#[wasm_bindgen]
+pub fn verify_keygen_proof(
+    share_json: &str,
+    params_json: &str,
+    hash_function: &str,
+) -> Result<String, JsValue> {
+    let verification = dkg::verify_keygen_proof(share_json, params_json, hash_function)
+        .map_err(|error| JsValue::from_str(&error.to_string()))?;
+    serde_json::to_string(&verification).map_err(|error| JsValue::from_str(&error.to_string()))
+}
+
+/// Verifies the batched decryption proof of the partial decryptions of a sealer.
+/// The decryption record (sealer, shares and proof), the decrypted ciphers and the parameters of
+/// the vote are JSON encoded, the public key share of the sealer is hex encoded, see: `dkg`.
+/// Returns the JSON encoded `Verification`, throws if an input is malformed.
+#[wasm_bindgen]
+pub fn verify_decryption_proof(
+    record_json: &str,
+    ciphers_json: &str,
+    pk_share: &str,
+    params_json: &str,
+    hash_function: &str,
+) -> Result<String, JsValue> {
+    let verification = dkg::verify_decryption_proof(
+        record_json,
+        ciphers_json,
+        pk_share,
+        params_json,
+        hash_function,
+    )
+    .map_err(|error| JsValue::from_str(&error.to_string()))?;
+    serde_json::to_string(&verification).map_err(|error| JsValue::from_str(&error.to_string()))
+}
@@ -0,0 +1,75 @@
+//! Encodes and scans the compact ballots of the paper channel (see: `mixnet_primitives::relay`).
+//!
+//! The web app of the voter signs the message of the ballot (e.g. using a browser extension)
+//! and encodes the signed ballot for a QR code. The web app of a relayer scans the QR code
+//! and submits the ballot using `cast_relayed_ballot`.
+use mixnet_primitives::relay::{CompactBallot, CompactBallotError};
+use serde::{Deserialize, Serialize};
+
+/// A scanned compact ballot, all bytes are 0x prefixed hex.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ScannedBallot {
+    pub vote_id: String,
+    /// the SCALE encoded ballot, the `ballot` argument of `cast_relayed_ballot`
+    pub ballot: String,
+    /// the sr25519 public key of the voter
+    pub voter: String,
+    /// the sr25519 signature of the voter
+    pub signature: String,
+}
+
+/// Encodes the signed ballot for a QR code.
+pub fn encode_compact_ballot(
+    vote_id: &str,
+    ballot: &[u8],
+    voter: &[u8],
+    signature: &[u8],
+) -> Result<String, CompactBallotError> {
+    let compact = CompactBallot {
+        vote_id: vote_id.as_bytes().to_vec(),
+        ballot: ballot.to_vec(),
+        voter: voter.to_vec(),
+        signature: signature.to_vec(),
+    };
+
+    // the voter and the signature are checked when the ballot is scanned
+    let text = compact.to_base45();
+    CompactBallot::from_base45(&text)?;
+    Ok(text)
+}
+
+/// Decodes a scanned compact ballot.
+pub fn decode_compact_ballot(text: &str) -> Result<ScannedBallot, CompactBallotError> {
+    let compact = CompactBallot::from_base45(text)?;
+    Ok(ScannedBallot {
+        vote_id: String::from_utf8_lossy(&compact.vote_id).into_owned(),
+        ballot: format!("0x{}", hex::encode(compact.ballot)),
+        voter: format!("0x{}", hex::encode(compact.voter)),
+        signature: format!("0x{}", hex::encode(compact.signature)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_scan_an_encoded_ballot() {
+        let text =
+            encode_compact_ballot("20201212", &[4, 1, 2, 3], &[7u8; 32], &[9u8; 64]).unwrap();
+        let scanned = decode_compact_ballot(&text).unwrap();
+        assert_eq!(scanned.vote_id, "20201212");
+        assert_eq!(scanned.ballot, "0x04010203");
+        assert_eq!(scanned.voter, format!("0x{}", "07".repeat(32)));
+        assert_eq!(scanned.signature, format!("0x{}", "09".repeat(64)));
+    }
+
+    #[test]
+    fn it_should_not_encode_a_ballot_without_signature() {
+        assert_eq!(
+            encode_compact_ballot("20201212", &[4, 1, 2, 3], &[7u8; 32], &[]),
+            Err(CompactBallotError::InvalidSignature)
+        );
+    }
+}
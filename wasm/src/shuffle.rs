@@ -0,0 +1,233 @@
+//! Verifies the shuffle proof of a mix (CHVoteSpec 3.1, Algorithm 8.51) using the verifier of
+//! the crypto crate, e.g. for the election monitor of public observers.
+//!
+//! All inputs are JSON, big integers are hex strings of their big-endian bytes (optionally 0x prefixed).
+//! The proof and the ciphers use the format of the transcript (see: `provotum-cli export_transcript`),
+//! the proof additionally contains the id from which the generators of the permutation commitments
+//! are derived, i.e. the topic id (the vote id for linked shuffles), and the context of its challenge,
+//! i.e. the digest of the ballot box freeze of the vote (if recorded).
+use crypto::{
+    proofs::shuffle::ShuffleProof as Verifier,
+    types::{Cipher as BigCipher, ElGamalParams, PublicKey as ElGamalPK, ShuffleProofValues},
+};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShuffleError {
+    /// an input isn't valid JSON
+    Serialization(String),
+    /// a big integer isn't a hex string
+    InvalidHex(String),
+}
+
+impl fmt::Display for ShuffleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShuffleError::Serialization(error) => write!(f, "invalid JSON: {}", error),
+            ShuffleError::InvalidHex(value) => write!(f, "invalid hex value: {}", value),
+        }
+    }
+}
+
+/// The shuffle proof of a batch of ciphers (see: `ShuffleProof` of the transcript).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ShuffleProof {
+    /// the topic id, or the vote id of linked shuffles
+    pub id: String,
+    pub challenge: String,
+    pub s1: String,
+    pub s2: String,
+    pub s3: String,
+    pub s4: String,
+    #[serde(alias = "vec_s_hat")]
+    pub vec_s_hat: Vec<String>,
+    #[serde(alias = "vec_s_tilde")]
+    pub vec_s_tilde: Vec<String>,
+    #[serde(alias = "permutation_commitments")]
+    pub permutation_commitments: Vec<String>,
+    #[serde(alias = "permutation_chain_commitments")]
+    pub permutation_chain_commitments: Vec<String>,
+    /// the digest of the ballot box freeze (hex), the proofs of older votes have no context
+    #[serde(default)]
+    pub context: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Cipher {
+    pub a: String,
+    pub b: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Params {
+    pub p: String,
+    pub g: String,
+    pub h: String,
+}
+
+/// The public key of the vote, as in the transcript: its parameters and the value h.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicKey {
+    pub params: Params,
+    #[serde(alias = "public_key")]
+    pub public_key: String,
+}
+
+pub(crate) fn decode_biguint(value: &str) -> Result<BigUint, ShuffleError> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    let bytes = hex::decode(digits).map_err(|_| ShuffleError::InvalidHex(value.to_string()))?;
+    if bytes.is_empty() {
+        return Err(ShuffleError::InvalidHex(value.to_string()));
+    }
+    Ok(BigUint::from_bytes_be(&bytes))
+}
+
+fn decode_biguints(values: &[String]) -> Result<Vec<BigUint>, ShuffleError> {
+    values.iter().map(|value| decode_biguint(value)).collect()
+}
+
+pub(crate) fn decode_ciphers(ciphers_json: &str) -> Result<Vec<BigCipher>, ShuffleError> {
+    let ciphers: Vec<Cipher> = serde_json::from_str(ciphers_json)
+        .map_err(|error| ShuffleError::Serialization(error.to_string()))?;
+    ciphers
+        .iter()
+        .map(|cipher| {
+            Ok(BigCipher {
+                a: decode_biguint(&cipher.a)?,
+                b: decode_biguint(&cipher.b)?,
+            })
+        })
+        .collect()
+}
+
+impl ShuffleProof {
+    fn values(&self) -> Result<ShuffleProofValues, ShuffleError> {
+        Ok(ShuffleProofValues {
+            challenge: decode_biguint(&self.challenge)?,
+            s1: decode_biguint(&self.s1)?,
+            s2: decode_biguint(&self.s2)?,
+            s3: decode_biguint(&self.s3)?,
+            s4: decode_biguint(&self.s4)?,
+            vec_s_hat: decode_biguints(&self.vec_s_hat)?,
+            vec_s_tilde: decode_biguints(&self.vec_s_tilde)?,
+            permutation_commitments: decode_biguints(&self.permutation_commitments)?,
+            permutation_chain_commitments: decode_biguints(&self.permutation_chain_commitments)?,
+        })
+    }
+}
+
+impl PublicKey {
+    fn decode(&self) -> Result<ElGamalPK, ShuffleError> {
+        let params = ElGamalParams {
+            p: decode_biguint(&self.params.p)?,
+            g: decode_biguint(&self.params.g)?,
+            h: decode_biguint(&self.params.h)?,
+        };
+        Ok(ElGamalPK {
+            params,
+            h: decode_biguint(&self.public_key)?,
+        })
+    }
+}
+
+/// verifies that the shuffled ciphers are a re-encryption and permutation of the ciphers
+pub fn verify_shuffle_proof(
+    proof_json: &str,
+    ciphers_json: &str,
+    shuffled_json: &str,
+    pk_json: &str,
+) -> Result<bool, ShuffleError> {
+    let proof: ShuffleProof = serde_json::from_str(proof_json)
+        .map_err(|error| ShuffleError::Serialization(error.to_string()))?;
+    let pk: PublicKey = serde_json::from_str(pk_json)
+        .map_err(|error| ShuffleError::Serialization(error.to_string()))?;
+    let ciphers = decode_ciphers(ciphers_json)?;
+    let shuffled = decode_ciphers(shuffled_json)?;
+
+    // the verifier of the crypto crate doesn't check the group, a modulus p <= 3 has no valid q
+    let pk = pk.decode()?;
+    if pk.params.p <= BigUint::from(3u32) {
+        return Ok(false);
+    }
+    let context: Vec<u8> = match proof.context.as_ref() {
+        Some(context) => hex::decode(context.strip_prefix("0x").unwrap_or(context))
+            .map_err(|_| ShuffleError::InvalidHex(context.to_string()))?,
+        None => Vec::new(),
+    };
+    Ok(Verifier::verify_in_context(
+        proof.id.as_bytes(),
+        &context,
+        &proof.values()?,
+        ciphers,
+        shuffled,
+        &pk,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a shuffle of two ciphers of the 48 bit test system (see: `Helper::setup_sm_system`)
+    const PROOF: &str = r#"{"id":"20201212-01","challenge":"4eb69f49ad5f","s1":"32ebb2b68204","s2":"02669c380917","s3":"16c4e655f839","s4":"35a6adef507e","vec_s_hat":["0c83252e2ef1","2f22c94a3f07"],"vec_s_tilde":["511c0a4cc77e","1828dc8ffe7d"],"permutation_commitments":["177d0e444598","703dfd07bd77"],"permutation_chain_commitments":["780bb3f02890","4dffd61724d9"]}"#;
+    const CIPHERS: &str =
+        r#"[{"a":"0ac91319dd51","b":"3dcd7df48785"},{"a":"b56597a6d145","b":"0a3d62f51abe"}]"#;
+    const SHUFFLED: &str =
+        r#"[{"a":"6caf36bdfd80","b":"33fddaad00ea"},{"a":"1cdeb00f7ec5","b":"8995f27d7902"}]"#;
+    const PUBLIC_KEY: &str =
+        r#"{"params":{"p":"b7e151629927","g":"04","h":"09"},"publicKey":"b66d69792651"}"#;
+
+    #[test]
+    fn it_should_verify_a_shuffle_proof() {
+        assert_eq!(
+            verify_shuffle_proof(PROOF, CIPHERS, SHUFFLED, PUBLIC_KEY),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn it_should_not_verify_a_shuffle_proof_of_another_topic() {
+        let proof = PROOF.replace("20201212-01", "20201212-02");
+        assert_eq!(
+            verify_shuffle_proof(&proof, CIPHERS, SHUFFLED, PUBLIC_KEY),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn it_should_not_verify_a_shuffle_proof_in_another_context() {
+        let proof = PROOF.replace(r#""id""#, r#""context":"0x00ff","id""#);
+        assert_eq!(
+            verify_shuffle_proof(&proof, CIPHERS, SHUFFLED, PUBLIC_KEY),
+            Ok(false)
+        );
+        let proof = PROOF.replace(r#""id""#, r#""context":"0xzz","id""#);
+        assert_eq!(
+            verify_shuffle_proof(&proof, CIPHERS, SHUFFLED, PUBLIC_KEY),
+            Err(ShuffleError::InvalidHex("0xzz".into()))
+        );
+    }
+
+    #[test]
+    fn it_should_not_verify_modified_ciphers() {
+        // the shuffled ciphers in another order
+        let shuffled =
+            r#"[{"a":"1cdeb00f7ec5","b":"8995f27d7902"},{"a":"6caf36bdfd80","b":"33fddaad00ea"}]"#;
+        assert_eq!(
+            verify_shuffle_proof(PROOF, CIPHERS, shuffled, PUBLIC_KEY),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn it_should_reject_malformed_ciphers() {
+        assert_eq!(
+            verify_shuffle_proof(PROOF, r#"[{"a":"xy","b":"01"}]"#, SHUFFLED, PUBLIC_KEY),
+            Err(ShuffleError::InvalidHex("xy".to_string()))
+        );
+    }
+}